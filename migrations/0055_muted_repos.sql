@@ -0,0 +1,7 @@
+CREATE TABLE IF NOT EXISTS muted_repos (
+  user_id TEXT NOT NULL,
+  repo_id INTEGER NOT NULL,
+  created_at TEXT NOT NULL,
+  PRIMARY KEY (user_id, repo_id),
+  FOREIGN KEY(user_id) REFERENCES users(id) ON DELETE CASCADE
+);
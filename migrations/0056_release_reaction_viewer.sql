@@ -0,0 +1,13 @@
+CREATE TABLE IF NOT EXISTS release_reaction_viewer (
+  user_id TEXT NOT NULL,
+  release_id INTEGER NOT NULL,
+  viewer_plus1 INTEGER NOT NULL DEFAULT 0,
+  viewer_laugh INTEGER NOT NULL DEFAULT 0,
+  viewer_heart INTEGER NOT NULL DEFAULT 0,
+  viewer_hooray INTEGER NOT NULL DEFAULT 0,
+  viewer_rocket INTEGER NOT NULL DEFAULT 0,
+  viewer_eyes INTEGER NOT NULL DEFAULT 0,
+  fetched_at TEXT NOT NULL,
+  PRIMARY KEY (user_id, release_id),
+  FOREIGN KEY(user_id) REFERENCES users(id) ON DELETE CASCADE
+);
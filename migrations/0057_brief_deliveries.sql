@@ -0,0 +1,21 @@
+ALTER TABLE users ADD COLUMN brief_email_enabled INTEGER NOT NULL DEFAULT 0;
+ALTER TABLE users ADD COLUMN brief_email TEXT;
+
+CREATE TABLE IF NOT EXISTS brief_deliveries (
+  id TEXT PRIMARY KEY,
+  user_id TEXT NOT NULL,
+  brief_id TEXT NOT NULL,
+  channel TEXT NOT NULL,
+  recipient TEXT,
+  status TEXT NOT NULL,
+  error_text TEXT,
+  html_body TEXT,
+  sent_at TEXT,
+  created_at TEXT NOT NULL,
+  updated_at TEXT NOT NULL,
+  UNIQUE (brief_id, channel),
+  FOREIGN KEY(user_id) REFERENCES users(id) ON DELETE CASCADE
+);
+
+CREATE INDEX IF NOT EXISTS idx_brief_deliveries_user_created_at
+  ON brief_deliveries(user_id, created_at DESC);
@@ -0,0 +1,19 @@
+CREATE TABLE IF NOT EXISTS notification_channels (
+  id TEXT PRIMARY KEY,
+  user_id TEXT NOT NULL,
+  kind TEXT NOT NULL,
+  url_ciphertext BLOB NOT NULL,
+  url_nonce BLOB NOT NULL,
+  url_host TEXT NOT NULL,
+  enabled INTEGER NOT NULL DEFAULT 1,
+  events_json TEXT NOT NULL DEFAULT '["brief.ready","releases.new"]',
+  last_delivery_status TEXT,
+  last_delivery_error TEXT,
+  last_delivery_at TEXT,
+  created_at TEXT NOT NULL,
+  updated_at TEXT NOT NULL,
+  FOREIGN KEY(user_id) REFERENCES users(id) ON DELETE CASCADE
+);
+
+CREATE INDEX IF NOT EXISTS idx_notification_channels_user_created_at
+  ON notification_channels(user_id, created_at DESC);
@@ -0,0 +1 @@
+ALTER TABLE briefs ADD COLUMN coverage_json TEXT;
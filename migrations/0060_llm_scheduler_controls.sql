@@ -0,0 +1,6 @@
+ALTER TABLE admin_runtime_settings
+  ADD COLUMN llm_scheduler_paused INTEGER NOT NULL DEFAULT 0;
+
+ALTER TABLE admin_runtime_settings
+  ADD COLUMN llm_request_interval_ms INTEGER
+  CHECK (llm_request_interval_ms IS NULL OR (llm_request_interval_ms >= 100 AND llm_request_interval_ms <= 600000));
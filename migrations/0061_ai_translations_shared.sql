@@ -0,0 +1,13 @@
+-- Cross-user translation cache for releases of public repos. Rows are keyed
+-- by entity only and must never be written for private repositories.
+CREATE TABLE IF NOT EXISTS ai_translations_shared (
+  entity_type TEXT NOT NULL,
+  entity_id TEXT NOT NULL,
+  lang TEXT NOT NULL,
+  source_hash TEXT NOT NULL,
+  title TEXT,
+  summary TEXT,
+  created_at TEXT NOT NULL,
+  updated_at TEXT NOT NULL,
+  PRIMARY KEY(entity_type, entity_id, lang)
+);
@@ -0,0 +1,14 @@
+-- Per-chunk results of long release detail translations, so a retry only
+-- re-translates the chunks that did not finish.
+CREATE TABLE IF NOT EXISTS ai_translation_chunks (
+  user_id TEXT NOT NULL,
+  entity_id TEXT NOT NULL,
+  source_hash TEXT NOT NULL,
+  chunk_index INTEGER NOT NULL,
+  chunk_hash TEXT NOT NULL,
+  translated TEXT NOT NULL,
+  created_at TEXT NOT NULL,
+  updated_at TEXT NOT NULL,
+  PRIMARY KEY(user_id, entity_id, source_hash, chunk_index),
+  FOREIGN KEY(user_id) REFERENCES users(id) ON DELETE CASCADE
+);
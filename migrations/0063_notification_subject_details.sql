@@ -0,0 +1,6 @@
+-- Subject (issue/PR/...) details for notifications. `subject_url` is the raw
+-- GitHub API URL of the subject; the other columns cache the last lookup.
+ALTER TABLE notifications ADD COLUMN subject_url TEXT;
+ALTER TABLE notifications ADD COLUMN subject_state TEXT;
+ALTER TABLE notifications ADD COLUMN subject_body TEXT;
+ALTER TABLE notifications ADD COLUMN subject_fetched_at TEXT;
@@ -0,0 +1,12 @@
+-- Last GitHub rate-limit budget seen per user and token kind, so sync can
+-- back off before it runs out and the UI can explain throttled syncs.
+CREATE TABLE IF NOT EXISTS github_rate_limits (
+  user_id TEXT NOT NULL,
+  token_kind TEXT NOT NULL,
+  limit_total INTEGER NOT NULL,
+  remaining INTEGER NOT NULL,
+  reset_at TEXT NOT NULL,
+  updated_at TEXT NOT NULL,
+  PRIMARY KEY(user_id, token_kind),
+  FOREIGN KEY(user_id) REFERENCES users(id) ON DELETE CASCADE
+);
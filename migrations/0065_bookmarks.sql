@@ -0,0 +1,19 @@
+-- Read-later list. The title/repo/url snapshot keeps a bookmark listable after
+-- the release itself is purged (for example once the repo is unstarred).
+CREATE TABLE IF NOT EXISTS bookmarks (
+  id TEXT PRIMARY KEY,
+  user_id TEXT NOT NULL,
+  kind TEXT NOT NULL,
+  entity_id TEXT NOT NULL,
+  note TEXT,
+  snapshot_title TEXT,
+  snapshot_repo_full_name TEXT,
+  snapshot_html_url TEXT,
+  created_at TEXT NOT NULL,
+  updated_at TEXT NOT NULL,
+  UNIQUE(user_id, kind, entity_id),
+  FOREIGN KEY(user_id) REFERENCES users(id) ON DELETE CASCADE
+);
+
+CREATE INDEX IF NOT EXISTS idx_bookmarks_user_created_at
+  ON bookmarks(user_id, created_at DESC, id DESC);
@@ -0,0 +1,9 @@
+-- Releases each user has marked as seen, for "unseen only" feed filtering and
+-- per-repo unseen badges.
+CREATE TABLE IF NOT EXISTS release_seen (
+  user_id TEXT NOT NULL,
+  release_id INTEGER NOT NULL,
+  seen_at TEXT NOT NULL,
+  PRIMARY KEY(user_id, release_id),
+  FOREIGN KEY(user_id) REFERENCES users(id) ON DELETE CASCADE
+);
@@ -0,0 +1,25 @@
+-- Multi-account sync: one connection per user is the primary identity, and
+-- starred repos / notifications remember which connection synced them so
+-- unlinking an account can purge exactly its rows.
+ALTER TABLE github_connections ADD COLUMN is_primary INTEGER NOT NULL DEFAULT 0;
+
+UPDATE github_connections
+SET is_primary = 1
+WHERE id IN (
+  SELECT id
+  FROM (
+    SELECT
+      id,
+      ROW_NUMBER() OVER (PARTITION BY user_id ORDER BY linked_at ASC, id ASC) AS rn
+    FROM github_connections
+  )
+  WHERE rn = 1
+);
+
+ALTER TABLE starred_repos ADD COLUMN source_github_connection_id TEXT;
+ALTER TABLE notifications ADD COLUMN source_github_connection_id TEXT;
+
+CREATE INDEX IF NOT EXISTS idx_starred_repos_source_connection
+  ON starred_repos(user_id, source_github_connection_id);
+CREATE INDEX IF NOT EXISTS idx_notifications_source_connection
+  ON notifications(user_id, source_github_connection_id);
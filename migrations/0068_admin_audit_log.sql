@@ -0,0 +1,16 @@
+-- Who changed what through admin endpoints. Rows are written in the same
+-- transaction as the mutation, so a rolled-back change leaves no entry.
+CREATE TABLE IF NOT EXISTS admin_audit_log (
+  id TEXT PRIMARY KEY,
+  acting_user_id TEXT NOT NULL,
+  target_user_id TEXT,
+  action TEXT NOT NULL,
+  before_json TEXT NOT NULL,
+  after_json TEXT NOT NULL,
+  created_at TEXT NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_admin_audit_log_created_at
+  ON admin_audit_log(created_at DESC, id DESC);
+CREATE INDEX IF NOT EXISTS idx_admin_audit_log_target_created_at
+  ON admin_audit_log(target_user_id, created_at DESC, id DESC);
@@ -0,0 +1,3 @@
+-- Minutes between scheduled release/notification syncs for a user; 0 keeps
+-- syncing manual.
+ALTER TABLE users ADD COLUMN sync_frequency_minutes INTEGER NOT NULL DEFAULT 0;
@@ -0,0 +1,4 @@
+-- Filled by the next starred sync; existing rows stay NULL until then.
+ALTER TABLE starred_repos ADD COLUMN language TEXT;
+ALTER TABLE starred_repos ADD COLUMN topics_json TEXT;
+ALTER TABLE starred_repos ADD COLUMN is_archived INTEGER;
@@ -0,0 +1,8 @@
+-- Sanitized HTML for release notes and their translations, keyed by a hash of
+-- every renderer input. Rows never change once written.
+CREATE TABLE IF NOT EXISTS release_render_cache (
+  source_hash TEXT PRIMARY KEY,
+  html TEXT NOT NULL,
+  stripped_elements INTEGER NOT NULL,
+  created_at TEXT NOT NULL
+);
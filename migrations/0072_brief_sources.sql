@@ -0,0 +1,3 @@
+-- Releases behind each brief section, in render order, so the UI can link
+-- back to them. NULL for briefs generated before sources were tracked.
+ALTER TABLE briefs ADD COLUMN sources_json TEXT;
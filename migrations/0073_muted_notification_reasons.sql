@@ -0,0 +1,5 @@
+-- GitHub notification reasons the user muted, as a JSON array. Muted
+-- notifications arrive already read, or are not stored at all when
+-- skip_muted_notifications is set.
+ALTER TABLE users ADD COLUMN muted_notification_reasons TEXT NOT NULL DEFAULT '[]';
+ALTER TABLE users ADD COLUMN skip_muted_notifications INTEGER NOT NULL DEFAULT 0;
@@ -0,0 +1,8 @@
+-- Per-user secret for the Atom release feed. Only the SHA-256 of the token is
+-- stored; rotating replaces the row, so the previous feed URL stops working.
+CREATE TABLE IF NOT EXISTS feed_tokens (
+  user_id TEXT PRIMARY KEY NOT NULL,
+  token_hash TEXT NOT NULL UNIQUE,
+  created_at TEXT NOT NULL,
+  FOREIGN KEY(user_id) REFERENCES users(id) ON DELETE CASCADE
+);
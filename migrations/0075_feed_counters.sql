@@ -0,0 +1,9 @@
+-- Baseline for the "new releases since you last looked" badge, plus indexes
+-- that keep /api/counters cheap enough to poll every minute.
+ALTER TABLE users ADD COLUMN feed_last_seen_at TEXT;
+
+CREATE INDEX IF NOT EXISTS idx_repo_releases_repo_published_or_created
+  ON repo_releases(repo_id, COALESCE(published_at, created_at));
+
+CREATE INDEX IF NOT EXISTS idx_notifications_user_unread
+  ON notifications(user_id, unread);
@@ -0,0 +1,114 @@
+-- Repos followed through an imported GitHub star list or another user's
+-- public stars. Kept apart from starred_repos so the star sync, which
+-- replaces that table wholesale, never drops them.
+CREATE TABLE IF NOT EXISTS followed_repos (
+  user_id TEXT NOT NULL,
+  repo_id INTEGER NOT NULL,
+  full_name TEXT NOT NULL,
+  owner_login TEXT NOT NULL,
+  name TEXT NOT NULL,
+  description TEXT,
+  html_url TEXT NOT NULL,
+  owner_avatar_url TEXT,
+  import_source TEXT NOT NULL CHECK (import_source IN ('user', 'list')),
+  import_ref TEXT NOT NULL,
+  created_at TEXT NOT NULL,
+  updated_at TEXT NOT NULL,
+  PRIMARY KEY (user_id, repo_id),
+  FOREIGN KEY(user_id) REFERENCES users(id) ON DELETE CASCADE
+);
+
+CREATE INDEX IF NOT EXISTS idx_followed_repos_repo_id
+  ON followed_repos(repo_id);
+
+-- `source` tells which relation made the repo visible: a star wins over an
+-- owned repo, which wins over a follow.
+DROP VIEW IF EXISTS user_release_visible_repos;
+
+CREATE VIEW user_release_visible_repos AS
+SELECT
+  sr.user_id AS user_id,
+  sr.repo_id AS repo_id,
+  sr.full_name AS full_name,
+  sr.owner_login AS owner_login,
+  sr.name AS name,
+  sr.description AS description,
+  sr.html_url AS html_url,
+  sr.stargazed_at AS stargazed_at,
+  sr.is_private AS is_private,
+  sr.updated_at AS updated_at,
+  sr.owner_avatar_url AS owner_avatar_url,
+  sr.open_graph_image_url AS open_graph_image_url,
+  sr.uses_custom_open_graph_image AS uses_custom_open_graph_image,
+  'starred' AS source
+FROM starred_repos sr
+
+UNION ALL
+
+SELECT
+  ob.user_id AS user_id,
+  ob.repo_id AS repo_id,
+  ob.repo_full_name AS full_name,
+  CASE
+    WHEN instr(ob.repo_full_name, '/') > 0
+      THEN substr(ob.repo_full_name, 1, instr(ob.repo_full_name, '/') - 1)
+    ELSE ob.repo_full_name
+  END AS owner_login,
+  CASE
+    WHEN instr(ob.repo_full_name, '/') > 0
+      THEN substr(ob.repo_full_name, instr(ob.repo_full_name, '/') + 1)
+    ELSE ob.repo_full_name
+  END AS name,
+  NULL AS description,
+  'https://github.com/' || ob.repo_full_name AS html_url,
+  NULL AS stargazed_at,
+  0 AS is_private,
+  ob.updated_at AS updated_at,
+  ob.owner_avatar_url AS owner_avatar_url,
+  ob.open_graph_image_url AS open_graph_image_url,
+  ob.uses_custom_open_graph_image AS uses_custom_open_graph_image,
+  'owned' AS source
+FROM owned_repo_star_baselines ob
+JOIN users u
+  ON u.id = ob.user_id
+WHERE u.include_own_releases != 0
+  AND NOT EXISTS (
+    SELECT 1
+    FROM starred_repos sr
+    WHERE sr.user_id = ob.user_id
+      AND sr.repo_id = ob.repo_id
+  )
+
+UNION ALL
+
+SELECT
+  fr.user_id AS user_id,
+  fr.repo_id AS repo_id,
+  fr.full_name AS full_name,
+  fr.owner_login AS owner_login,
+  fr.name AS name,
+  fr.description AS description,
+  fr.html_url AS html_url,
+  NULL AS stargazed_at,
+  0 AS is_private,
+  fr.updated_at AS updated_at,
+  fr.owner_avatar_url AS owner_avatar_url,
+  NULL AS open_graph_image_url,
+  0 AS uses_custom_open_graph_image,
+  'followed' AS source
+FROM followed_repos fr
+WHERE NOT EXISTS (
+    SELECT 1
+    FROM starred_repos sr
+    WHERE sr.user_id = fr.user_id
+      AND sr.repo_id = fr.repo_id
+  )
+  AND NOT EXISTS (
+    SELECT 1
+    FROM owned_repo_star_baselines ob
+    JOIN users u
+      ON u.id = ob.user_id
+    WHERE ob.user_id = fr.user_id
+      AND ob.repo_id = fr.repo_id
+      AND u.include_own_releases != 0
+  );
@@ -0,0 +1,11 @@
+-- Last run of each database maintenance action, manual or scheduled, so the
+-- admin UI can show when the WAL was last truncated or the file vacuumed.
+CREATE TABLE IF NOT EXISTS db_maintenance_runs (
+  action TEXT PRIMARY KEY NOT NULL
+    CHECK (action IN ('checkpoint', 'vacuum', 'integrity_check', 'analyze')),
+  source TEXT NOT NULL CHECK (source IN ('manual', 'schedule')),
+  ok INTEGER NOT NULL,
+  duration_ms INTEGER NOT NULL,
+  detail_json TEXT NOT NULL,
+  finished_at TEXT NOT NULL
+);
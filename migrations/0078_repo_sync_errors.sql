@@ -0,0 +1,23 @@
+-- Per-user record of repos whose individual sync failed (deleted, DMCA'd,
+-- access revoked), so the gap shows up as an issue instead of silence.
+-- A later successful sync of the repo sets resolved_at; dismissing does too.
+CREATE TABLE IF NOT EXISTS repo_sync_errors (
+  id TEXT PRIMARY KEY NOT NULL,
+  user_id TEXT NOT NULL,
+  repo_id INTEGER NOT NULL,
+  repo_full_name TEXT NOT NULL,
+  sync_type TEXT NOT NULL CHECK (sync_type IN ('releases')),
+  error_code TEXT NOT NULL,
+  message TEXT NOT NULL,
+  occurred_at TEXT NOT NULL,
+  resolved_at TEXT,
+  FOREIGN KEY(user_id) REFERENCES users(id) ON DELETE CASCADE
+);
+
+CREATE UNIQUE INDEX IF NOT EXISTS idx_repo_sync_errors_open
+  ON repo_sync_errors(user_id, repo_id, sync_type)
+  WHERE resolved_at IS NULL;
+
+CREATE INDEX IF NOT EXISTS idx_repo_sync_errors_repo_open
+  ON repo_sync_errors(repo_id, sync_type)
+  WHERE resolved_at IS NULL;
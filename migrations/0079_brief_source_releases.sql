@@ -0,0 +1,21 @@
+-- One row per release a brief was built from, in render order. `included` is
+-- 0 when the summary batches never returned the release and its bullets fell
+-- back to a release-notes excerpt.
+CREATE TABLE brief_sources (
+  brief_id TEXT NOT NULL,
+  release_id INTEGER NOT NULL,
+  section TEXT NOT NULL,
+  ordinal INTEGER NOT NULL,
+  included INTEGER NOT NULL DEFAULT 1,
+  created_at TEXT NOT NULL,
+  PRIMARY KEY (brief_id, release_id),
+  FOREIGN KEY(brief_id) REFERENCES briefs(id) ON DELETE CASCADE,
+  FOREIGN KEY(release_id) REFERENCES repo_releases(release_id) ON DELETE CASCADE
+);
+
+CREATE INDEX idx_brief_sources_brief_ordinal
+  ON brief_sources(brief_id, ordinal ASC);
+
+-- Briefs written before this table existed cannot be backfilled; this flag
+-- tells an empty source list apart from one that was never recorded.
+ALTER TABLE briefs ADD COLUMN sources_tracked INTEGER NOT NULL DEFAULT 0;
@@ -0,0 +1,12 @@
+-- Claim lane for queued tasks: `high` for user-initiated API work, `normal`
+-- for scheduled fan-out, `low` for maintenance and backfills. Existing rows
+-- keep the old FIFO behaviour as `normal`.
+ALTER TABLE job_tasks ADD COLUMN priority TEXT NOT NULL DEFAULT 'normal'
+  CHECK (priority IN ('high', 'normal', 'low'));
+
+CREATE INDEX idx_job_tasks_status_priority_created
+  ON job_tasks(status, priority, created_at);
+
+CREATE INDEX idx_job_tasks_started_at
+  ON job_tasks(started_at DESC)
+  WHERE started_at IS NOT NULL;
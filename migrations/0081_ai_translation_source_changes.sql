@@ -0,0 +1,7 @@
+-- Set by release sync when a release's title or body changes after its
+-- translations were cached. A row counts as stale while this is newer than
+-- its updated_at, so writing a fresh translation clears it on its own.
+ALTER TABLE ai_translations ADD COLUMN source_changed_at TEXT;
+
+CREATE INDEX IF NOT EXISTS idx_ai_translations_entity
+  ON ai_translations(entity_type, entity_id);
@@ -0,0 +1,23 @@
+-- Translations that came back as an error from the batch pipeline, waiting
+-- for the background retry worker. `attempts` counts failures so far and
+-- `next_attempt_at` follows a per-item backoff; the row is removed once the
+-- item translates or runs out of attempts.
+CREATE TABLE IF NOT EXISTS translation_retry_queue (
+  id TEXT PRIMARY KEY NOT NULL,
+  user_id TEXT NOT NULL,
+  entity_type TEXT NOT NULL CHECK (entity_type IN ('release')),
+  entity_id TEXT NOT NULL,
+  lang TEXT NOT NULL,
+  attempts INTEGER NOT NULL DEFAULT 1,
+  next_attempt_at TEXT NOT NULL,
+  last_error TEXT,
+  created_at TEXT NOT NULL,
+  updated_at TEXT NOT NULL,
+  FOREIGN KEY(user_id) REFERENCES users(id) ON DELETE CASCADE
+);
+
+CREATE UNIQUE INDEX IF NOT EXISTS idx_translation_retry_queue_item
+  ON translation_retry_queue(user_id, entity_type, entity_id, lang);
+
+CREATE INDEX IF NOT EXISTS idx_translation_retry_queue_due
+  ON translation_retry_queue(next_attempt_at);
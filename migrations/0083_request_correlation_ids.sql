@@ -0,0 +1,13 @@
+-- x-request-id of the HTTP request that created the row, so a request can be
+-- followed to the tasks and LLM calls it caused. Tasks pass theirs on to the
+-- child tasks and LLM calls they create.
+ALTER TABLE job_tasks ADD COLUMN request_id TEXT;
+ALTER TABLE llm_calls ADD COLUMN request_id TEXT;
+
+CREATE INDEX IF NOT EXISTS idx_job_tasks_request_id
+  ON job_tasks(request_id)
+  WHERE request_id IS NOT NULL;
+
+CREATE INDEX IF NOT EXISTS idx_llm_calls_request_id
+  ON llm_calls(request_id)
+  WHERE request_id IS NOT NULL;
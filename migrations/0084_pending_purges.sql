@@ -0,0 +1,18 @@
+-- Repos that dropped out of a user's stars, waiting out a grace period before
+-- their releases and translations are deleted. Starring the repo again before
+-- `purge_after` removes the row and cancels the purge.
+CREATE TABLE IF NOT EXISTS pending_purges (
+  user_id TEXT NOT NULL,
+  repo_id INTEGER NOT NULL,
+  repo_full_name TEXT NOT NULL,
+  unstarred_at TEXT NOT NULL,
+  purge_after TEXT NOT NULL,
+  PRIMARY KEY (user_id, repo_id),
+  FOREIGN KEY(user_id) REFERENCES users(id) ON DELETE CASCADE
+);
+
+CREATE INDEX IF NOT EXISTS idx_pending_purges_purge_after
+  ON pending_purges(purge_after);
+
+CREATE INDEX IF NOT EXISTS idx_pending_purges_repo_id
+  ON pending_purges(repo_id);
@@ -0,0 +1,19 @@
+-- Downloadable files attached to a release, shared across users like
+-- repo_releases itself; access goes through the release's repo visibility.
+CREATE TABLE IF NOT EXISTS release_assets (
+  asset_id INTEGER PRIMARY KEY,
+  release_id INTEGER NOT NULL,
+  name TEXT NOT NULL,
+  size INTEGER NOT NULL DEFAULT 0,
+  download_count INTEGER NOT NULL DEFAULT 0,
+  browser_download_url TEXT NOT NULL,
+  content_type TEXT,
+  updated_at TEXT NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_release_assets_release_id
+  ON release_assets(release_id, name);
+
+-- Set whenever a release's asset list was stored, so releases synced before
+-- assets existed can be told apart from releases that have none.
+ALTER TABLE repo_releases ADD COLUMN assets_synced_at TEXT;
@@ -0,0 +1,13 @@
+-- Set when a thread is archived, either by the user or by the maintenance
+-- prune once it has gone without updates for the archive window. A newer
+-- update from GitHub clears it again.
+ALTER TABLE notifications ADD COLUMN archived_at TEXT;
+
+-- Default inbox listing: newest first among the user's unarchived threads.
+CREATE INDEX IF NOT EXISTS idx_notifications_user_inbox
+  ON notifications(user_id, updated_at DESC)
+  WHERE archived_at IS NULL;
+
+-- Lets the maintenance prune find stale and expired threads without a scan.
+CREATE INDEX IF NOT EXISTS idx_notifications_updated_at
+  ON notifications(updated_at);
@@ -0,0 +1,17 @@
+-- A user's thumbs up/down on one of their briefs, with an optional note.
+-- Rating again replaces the previous answer; regenerated briefs start fresh
+-- because feedback is keyed by brief id, not date.
+CREATE TABLE IF NOT EXISTS brief_feedback (
+  user_id TEXT NOT NULL,
+  brief_id TEXT NOT NULL,
+  rating TEXT NOT NULL CHECK (rating IN ('up', 'down')),
+  note TEXT,
+  created_at TEXT NOT NULL,
+  updated_at TEXT NOT NULL,
+  PRIMARY KEY (user_id, brief_id),
+  FOREIGN KEY(user_id) REFERENCES users(id) ON DELETE CASCADE,
+  FOREIGN KEY(brief_id) REFERENCES briefs(id) ON DELETE CASCADE
+);
+
+CREATE INDEX IF NOT EXISTS idx_brief_feedback_updated_at
+  ON brief_feedback(updated_at);
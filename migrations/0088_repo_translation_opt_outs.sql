@@ -0,0 +1,9 @@
+-- Repos whose releases a user does not want translated. A row means
+-- `translation_enabled = false`; re-enabling deletes the row.
+CREATE TABLE IF NOT EXISTS repo_translation_opt_outs (
+  user_id TEXT NOT NULL,
+  repo_id INTEGER NOT NULL,
+  created_at TEXT NOT NULL,
+  PRIMARY KEY (user_id, repo_id),
+  FOREIGN KEY(user_id) REFERENCES users(id) ON DELETE CASCADE
+);
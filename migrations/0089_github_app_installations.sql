@@ -0,0 +1,27 @@
+-- GitHub App installations reported by the `installation` webhooks, with the
+-- outcome of the last installation token mint for the admin view.
+CREATE TABLE IF NOT EXISTS github_app_installations (
+  installation_id INTEGER PRIMARY KEY,
+  account_login TEXT NOT NULL,
+  account_type TEXT,
+  -- `all` covers every repo of the account, `selected` only the mapped rows.
+  repository_selection TEXT NOT NULL DEFAULT 'selected',
+  suspended_at TEXT,
+  last_token_minted_at TEXT,
+  last_token_error TEXT,
+  last_token_error_at TEXT,
+  created_at TEXT NOT NULL,
+  updated_at TEXT NOT NULL
+);
+
+CREATE TABLE IF NOT EXISTS github_app_installation_repos (
+  installation_id INTEGER NOT NULL,
+  repo_id INTEGER NOT NULL,
+  full_name TEXT NOT NULL,
+  created_at TEXT NOT NULL,
+  PRIMARY KEY (installation_id, repo_id),
+  FOREIGN KEY(installation_id) REFERENCES github_app_installations(installation_id) ON DELETE CASCADE
+);
+
+CREATE INDEX IF NOT EXISTS idx_github_app_installation_repos_repo_id
+  ON github_app_installation_repos(repo_id);
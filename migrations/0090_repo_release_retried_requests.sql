@@ -0,0 +1,3 @@
+-- GitHub REST requests the last run of a work item retried, so the user sync
+-- that waited on it can report them like notification and social sync do.
+ALTER TABLE repo_release_work_items ADD COLUMN last_retried_requests INTEGER NOT NULL DEFAULT 0;
@@ -0,0 +1,20 @@
+-- Which linked GitHub accounts star each repo. A repo starred by several
+-- accounts keeps one starred_repos row plus one source row per account, so
+-- unlinking an account only drops the repos no remaining account stars.
+CREATE TABLE IF NOT EXISTS starred_repo_sources (
+  user_id TEXT NOT NULL,
+  repo_id INTEGER NOT NULL,
+  github_connection_id TEXT NOT NULL,
+  PRIMARY KEY (user_id, repo_id, github_connection_id)
+);
+
+CREATE INDEX IF NOT EXISTS idx_starred_repo_sources_connection
+  ON starred_repo_sources(user_id, github_connection_id);
+
+INSERT OR IGNORE INTO starred_repo_sources (user_id, repo_id, github_connection_id)
+SELECT user_id, repo_id, source_github_connection_id
+FROM starred_repos
+WHERE source_github_connection_id IS NOT NULL;
+
+DROP INDEX IF EXISTS idx_starred_repos_source_connection;
+ALTER TABLE starred_repos DROP COLUMN source_github_connection_id;
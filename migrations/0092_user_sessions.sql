@@ -0,0 +1,10 @@
+-- Sessions a user signed in with, recorded at login so account deletion can
+-- drop exactly those tower_sessions rows instead of matching session data.
+CREATE TABLE IF NOT EXISTS user_sessions (
+  session_id TEXT PRIMARY KEY,
+  user_id TEXT NOT NULL,
+  created_at TEXT NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_user_sessions_user_id
+  ON user_sessions(user_id);
@@ -0,0 +1,697 @@
+use std::{collections::BTreeMap, sync::Arc};
+
+use axum::{
+    Json,
+    extract::{Path, State},
+    http::StatusCode,
+};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use sqlx::SqliteConnection;
+use tower_sessions::Session;
+
+use crate::{
+    admin_audit, api,
+    error::{ApiError, ApiJson, codes},
+    state::AppState,
+};
+
+/// Phrase `DELETE /api/me` must echo back before anything is removed.
+pub const ACCOUNT_DELETE_CONFIRMATION: &str = "delete my account";
+
+/// Tower-sessions keeps its rows in the app database under this name.
+const SESSION_TABLE: &str = "tower_sessions";
+
+/// Rows owned by the user, removed children-first so the purge doesn't lean
+/// on `ON DELETE CASCADE` being declared (or enforced) for every table.
+const ACCOUNT_DELETE_STEPS: &[(&str, &str)] = &[
+    (
+        "translation_requests",
+        "DELETE FROM translation_requests WHERE scope_user_id = ?",
+    ),
+    (
+        "translation_batch_items",
+        "DELETE FROM translation_batch_items WHERE work_item_id IN (SELECT id FROM translation_work_items WHERE scope_user_id = ?)",
+    ),
+    (
+        "translation_work_items",
+        "DELETE FROM translation_work_items WHERE scope_user_id = ?",
+    ),
+    (
+        "ai_translation_chunks",
+        "DELETE FROM ai_translation_chunks WHERE user_id = ?",
+    ),
+    (
+        "ai_translations",
+        "DELETE FROM ai_translations WHERE user_id = ?",
+    ),
+    (
+        "translation_retry_queue",
+        "DELETE FROM translation_retry_queue WHERE user_id = ?",
+    ),
+    (
+        "brief_release_memberships",
+        "DELETE FROM brief_release_memberships WHERE brief_id IN (SELECT id FROM briefs WHERE user_id = ?)",
+    ),
+    (
+        "brief_sources",
+        "DELETE FROM brief_sources WHERE brief_id IN (SELECT id FROM briefs WHERE user_id = ?)",
+    ),
+    (
+        "brief_deliveries",
+        "DELETE FROM brief_deliveries WHERE user_id = ?",
+    ),
+    (
+        "brief_feedback",
+        "DELETE FROM brief_feedback WHERE user_id = ?",
+    ),
+    ("briefs", "DELETE FROM briefs WHERE user_id = ?"),
+    ("bookmarks", "DELETE FROM bookmarks WHERE user_id = ?"),
+    ("release_seen", "DELETE FROM release_seen WHERE user_id = ?"),
+    ("muted_repos", "DELETE FROM muted_repos WHERE user_id = ?"),
+    (
+        "repo_translation_opt_outs",
+        "DELETE FROM repo_translation_opt_outs WHERE user_id = ?",
+    ),
+    ("feed_tokens", "DELETE FROM feed_tokens WHERE user_id = ?"),
+    (
+        "notification_channels",
+        "DELETE FROM notification_channels WHERE user_id = ?",
+    ),
+    (
+        "notifications",
+        "DELETE FROM notifications WHERE user_id = ?",
+    ),
+    ("releases", "DELETE FROM releases WHERE user_id = ?"),
+    (
+        "starred_repos",
+        "DELETE FROM starred_repos WHERE user_id = ?",
+    ),
+    (
+        "starred_repo_sources",
+        "DELETE FROM starred_repo_sources WHERE user_id = ?",
+    ),
+    (
+        "pending_purges",
+        "DELETE FROM pending_purges WHERE user_id = ?",
+    ),
+    (
+        "owned_repo_star_baselines",
+        "DELETE FROM owned_repo_star_baselines WHERE user_id = ?",
+    ),
+    (
+        "followed_repos",
+        "DELETE FROM followed_repos WHERE user_id = ?",
+    ),
+    (
+        "repo_sync_errors",
+        "DELETE FROM repo_sync_errors WHERE user_id = ?",
+    ),
+    (
+        "repo_star_current_members",
+        "DELETE FROM repo_star_current_members WHERE user_id = ?",
+    ),
+    (
+        "repo_star_sync_baselines",
+        "DELETE FROM repo_star_sync_baselines WHERE user_id = ?",
+    ),
+    (
+        "follower_current_members",
+        "DELETE FROM follower_current_members WHERE user_id = ?",
+    ),
+    (
+        "follower_sync_baselines",
+        "DELETE FROM follower_sync_baselines WHERE user_id = ?",
+    ),
+    (
+        "social_activity_events",
+        "DELETE FROM social_activity_events WHERE user_id = ?",
+    ),
+    ("sync_state", "DELETE FROM sync_state WHERE user_id = ?"),
+    (
+        "github_rate_limits",
+        "DELETE FROM github_rate_limits WHERE user_id = ?",
+    ),
+    (
+        "release_reaction_viewer",
+        "DELETE FROM release_reaction_viewer WHERE user_id = ?",
+    ),
+    (
+        "reaction_pat_tokens",
+        "DELETE FROM reaction_pat_tokens WHERE user_id = ?",
+    ),
+    ("user_tokens", "DELETE FROM user_tokens WHERE user_id = ?"),
+    (
+        "user_passkeys",
+        "DELETE FROM user_passkeys WHERE user_id = ?",
+    ),
+    (
+        "github_connections",
+        "DELETE FROM github_connections WHERE user_id = ?",
+    ),
+    (
+        "linuxdo_connections",
+        "DELETE FROM linuxdo_connections WHERE user_id = ?",
+    ),
+];
+
+/// Shared operational history keeps its rows but forgets who asked; LLM
+/// calls stay around for usage and billing totals.
+const ACCOUNT_ANONYMIZE_STEPS: &[(&str, &str)] = &[
+    (
+        "translation_requests",
+        "UPDATE translation_requests SET requested_by = NULL WHERE requested_by = ?",
+    ),
+    (
+        "repo_release_watchers",
+        "UPDATE repo_release_watchers SET user_id = NULL WHERE user_id = ?",
+    ),
+    (
+        "sync_subscription_events",
+        "UPDATE sync_subscription_events SET user_id = NULL WHERE user_id = ?",
+    ),
+    (
+        "job_tasks",
+        "UPDATE job_tasks SET requested_by = NULL WHERE requested_by = ?",
+    ),
+    (
+        "llm_calls",
+        "UPDATE llm_calls SET requested_by = NULL WHERE requested_by = ?",
+    ),
+    (
+        "llm_call_events",
+        "UPDATE llm_call_events SET requested_by = NULL WHERE requested_by = ?",
+    ),
+];
+
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct DeleteAccountRequest {
+    /// Must equal `delete my account`.
+    confirm: String,
+}
+
+/// Rows touched per table by an account purge.
+#[derive(Debug, Default, Serialize, JsonSchema)]
+pub struct AccountDeletionSummary {
+    deleted: BTreeMap<String, u64>,
+    anonymized: BTreeMap<String, u64>,
+}
+
+/// Permanently deletes the caller's account and everything it owns, then
+/// ends the session.
+pub async fn delete_me(
+    State(state): State<Arc<AppState>>,
+    api::AuthedUser(user_id): api::AuthedUser,
+    // Flushed once the account is gone.
+    session: Session,
+    ApiJson(req): ApiJson<DeleteAccountRequest>,
+) -> Result<Json<AccountDeletionSummary>, ApiError> {
+    if req.confirm.trim() != ACCOUNT_DELETE_CONFIRMATION {
+        return Err(ApiError::bad_request(format!(
+            "confirm must be \"{ACCOUNT_DELETE_CONFIRMATION}\""
+        )));
+    }
+
+    let mut tx = state.pool.begin().await.map_err(ApiError::internal)?;
+    api::guard_user_deletion(&mut tx, &user_id).await?;
+    let summary = purge_user(&mut tx, &user_id).await?;
+    tx.commit().await.map_err(ApiError::internal)?;
+
+    state.feed_events.close_user(&user_id);
+    session.flush().await.map_err(ApiError::internal)?;
+    Ok(Json(summary))
+}
+
+pub async fn admin_delete_user(
+    State(state): State<Arc<AppState>>,
+    api::AdminUser(acting_user_id): api::AdminUser,
+    Path(target_user_id): Path<String>,
+) -> Result<Json<AccountDeletionSummary>, ApiError> {
+    let target_user_id = api::parse_local_id_param(target_user_id, "user_id")?;
+    state
+        .admin_mutation_limiter
+        .check(acting_user_id.as_str())?;
+    if target_user_id == acting_user_id {
+        return Err(ApiError::new(
+            StatusCode::CONFLICT,
+            codes::CANNOT_DELETE_SELF,
+            "admin cannot delete self here; use DELETE /api/me",
+        ));
+    }
+
+    let mut tx = state.pool.begin().await.map_err(ApiError::internal)?;
+    api::guard_user_deletion(&mut tx, &target_user_id).await?;
+    let login = sqlx::query_scalar::<_, String>(r#"SELECT login FROM users WHERE id = ?"#)
+        .bind(&target_user_id)
+        .fetch_one(&mut *tx)
+        .await
+        .map_err(ApiError::internal)?;
+    let summary = purge_user(&mut tx, &target_user_id).await?;
+    admin_audit::record(
+        &mut tx,
+        acting_user_id.as_str(),
+        Some(target_user_id.as_str()),
+        admin_audit::ADMIN_AUDIT_USER_DELETE,
+        &json!({ "login": login }),
+        &json!({ "deleted": &summary.deleted, "anonymized": &summary.anonymized }),
+    )
+    .await?;
+    tx.commit().await.map_err(ApiError::internal)?;
+
+    state.feed_events.close_user(&target_user_id);
+    Ok(Json(summary))
+}
+
+async fn purge_user(
+    conn: &mut SqliteConnection,
+    user_id: &str,
+) -> Result<AccountDeletionSummary, ApiError> {
+    let mut summary = AccountDeletionSummary::default();
+    for (table, sql) in ACCOUNT_ANONYMIZE_STEPS {
+        let affected = execute_for_user(conn, sql, user_id).await?;
+        summary.anonymized.insert((*table).to_owned(), affected);
+    }
+    for (table, sql) in ACCOUNT_DELETE_STEPS {
+        let affected = execute_for_user(conn, sql, user_id).await?;
+        summary.deleted.insert((*table).to_owned(), affected);
+    }
+
+    // Other devices' sessions would be rejected anyway once the user row is
+    // gone, but dropping them now keeps no trace of the account behind.
+    let has_session_table = sqlx::query_scalar::<_, i64>(
+        r#"SELECT COUNT(*) FROM sqlite_master WHERE type = 'table' AND name = ?"#,
+    )
+    .bind(SESSION_TABLE)
+    .fetch_one(&mut *conn)
+    .await
+    .map_err(ApiError::internal)?
+        > 0;
+    if has_session_table {
+        let sql = format!(
+            "DELETE FROM {SESSION_TABLE} WHERE id IN (SELECT session_id FROM user_sessions WHERE user_id = ?)"
+        );
+        let affected = execute_for_user(conn, &sql, user_id).await?;
+        summary.deleted.insert("sessions".to_owned(), affected);
+    }
+    let affected =
+        execute_for_user(conn, "DELETE FROM user_sessions WHERE user_id = ?", user_id).await?;
+    summary.deleted.insert("user_sessions".to_owned(), affected);
+
+    let affected = execute_for_user(conn, "DELETE FROM users WHERE id = ?", user_id).await?;
+    summary.deleted.insert("users".to_owned(), affected);
+    Ok(summary)
+}
+
+async fn execute_for_user(
+    conn: &mut SqliteConnection,
+    sql: &str,
+    user_id: &str,
+) -> Result<u64, ApiError> {
+    sqlx::query(sql)
+        .bind(user_id)
+        .execute(&mut *conn)
+        .await
+        .map(|result| result.rows_affected())
+        .map_err(ApiError::internal)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use sqlx::{
+        SqlitePool,
+        sqlite::{SqliteConnectOptions, SqlitePoolOptions},
+    };
+    use tower_sessions::MemoryStore;
+
+    use crate::config::AppConfig;
+
+    /// Columns that tie a row to a user; every table carrying one is checked.
+    const USER_REFERENCE_COLUMNS: &[&str] = &["user_id", "scope_user_id", "requested_by"];
+
+    fn member_id() -> String {
+        crate::local_id::test_local_id("account-member")
+    }
+
+    fn admin_id() -> String {
+        crate::local_id::test_local_id("account-admin")
+    }
+
+    async fn setup_pool() -> SqlitePool {
+        let database_path = std::env::temp_dir().join(format!(
+            "octo-rill-account-test-{}.db",
+            crate::local_id::generate_local_id(),
+        ));
+        let options = SqliteConnectOptions::new()
+            .filename(&database_path)
+            .create_if_missing(true);
+        let pool = SqlitePoolOptions::new()
+            .max_connections(1)
+            .connect_with(options)
+            .await
+            .expect("create sqlite db");
+        sqlx::migrate!("./migrations")
+            .run(&pool)
+            .await
+            .expect("run migrations");
+        tower_sessions_sqlx_store::SqliteStore::new(pool.clone())
+            .migrate()
+            .await
+            .expect("create session table");
+        pool
+    }
+
+    async fn seed_user(pool: &SqlitePool, user_id: &str, github_user_id: i64, is_admin: bool) {
+        sqlx::query(
+            r#"
+            INSERT INTO users (id, github_user_id, login, is_admin, created_at, updated_at)
+            VALUES (?, ?, ?, ?, '2026-02-01T00:00:00Z', '2026-02-01T00:00:00Z')
+            "#,
+        )
+        .bind(user_id)
+        .bind(github_user_id)
+        .bind(format!("user-{github_user_id}"))
+        .bind(i64::from(is_admin))
+        .execute(pool)
+        .await
+        .expect("seed user");
+    }
+
+    async fn seed_member_data(pool: &SqlitePool, user_id: &str) {
+        for statement in [
+            r#"
+            INSERT INTO starred_repos (
+              id, user_id, repo_id, full_name, owner_login, name, html_url, is_private, updated_at
+            )
+            VALUES (
+              'star-account-1', 'USER', 42, 'octo/rill', 'octo', 'rill',
+              'https://github.com/octo/rill', 0, '2026-02-01T00:00:00Z'
+            )
+            "#,
+            r#"
+            INSERT INTO notifications (id, user_id, thread_id, reason, updated_at, unread)
+            VALUES ('notif-account-1', 'USER', 'thread-1', 'mention', '2026-03-11T00:00:00Z', 1)
+            "#,
+            r#"
+            INSERT INTO ai_translations (
+              id, user_id, entity_type, entity_id, lang, source_hash, title, status,
+              created_at, updated_at
+            )
+            VALUES (
+              'tr-account-1', 'USER', 'release', '1001', 'zh-CN', 'hash', '版本', 'ready',
+              '2026-02-10T00:00:00Z', '2026-02-10T00:00:00Z'
+            )
+            "#,
+            r#"
+            INSERT INTO briefs (id, user_id, date, content_markdown, created_at, updated_at)
+            VALUES ('brief-account-1', 'USER', '2026-03-10', '## brief', '2026-03-10T01:00:00Z', '2026-03-10T01:00:00Z')
+            "#,
+            r#"
+            INSERT INTO reaction_pat_tokens (
+              user_id, token_ciphertext, token_nonce, masked_token, updated_at
+            )
+            VALUES ('USER', X'DEADBEEF', X'00', 'ghp_****', '2026-02-01T00:00:00Z')
+            "#,
+            r#"
+            INSERT INTO muted_repos (user_id, repo_id, created_at)
+            VALUES ('USER', 42, '2026-02-01T00:00:00Z')
+            "#,
+            r#"
+            INSERT INTO followed_repos (
+              user_id, repo_id, full_name, owner_login, name, html_url,
+              import_source, import_ref, created_at, updated_at
+            )
+            VALUES (
+              'USER', 43, 'octo/flow', 'octo', 'flow', 'https://github.com/octo/flow',
+              'user', 'someone', '2026-02-01T00:00:00Z', '2026-02-01T00:00:00Z'
+            )
+            "#,
+            r#"
+            INSERT INTO repo_sync_errors (
+              id, user_id, repo_id, repo_full_name, sync_type, error_code, message, occurred_at
+            )
+            VALUES (
+              'sync-error-account-1', 'USER', 43, 'octo/flow', 'releases', 'repo_inaccessible',
+              'github returned 404 Not Found', '2026-02-01T00:00:00Z'
+            )
+            "#,
+            r#"
+            INSERT INTO feed_tokens (user_id, token_hash, created_at)
+            VALUES ('USER', 'hash-account-1', '2026-02-01T00:00:00Z')
+            "#,
+            r#"
+            INSERT INTO job_tasks (
+              id, task_type, status, source, requested_by, payload_json, created_at, updated_at
+            )
+            VALUES (
+              'task-account-1', 'sync.releases', 'succeeded', 'api', 'USER', '{}',
+              '2026-02-01T00:00:00Z', '2026-02-01T00:00:00Z'
+            )
+            "#,
+            r#"
+            INSERT INTO llm_calls (
+              id, status, source, model, requested_by, max_tokens, prompt_text,
+              created_at, updated_at
+            )
+            VALUES (
+              'llm-account-1', 'succeeded', 'api.translate', 'gpt-test', 'USER', 100, 'prompt',
+              '2026-02-01T00:00:00Z', '2026-02-01T00:00:00Z'
+            )
+            "#,
+        ] {
+            sqlx::query(&statement.replace("'USER'", &format!("'{user_id}'")))
+                .execute(pool)
+                .await
+                .expect("seed member data");
+        }
+        seed_session(pool, "session-account-1", user_id, user_id).await;
+    }
+
+    /// Seeds a stored session signed in as `user_id` whose data mentions
+    /// `data_user_id`, e.g. the target of an impersonation.
+    async fn seed_session(pool: &SqlitePool, session_id: &str, user_id: &str, data_user_id: &str) {
+        sqlx::query(
+            r#"
+            INSERT INTO tower_sessions (id, data, expiry_date)
+            VALUES (?, ?, 4102444800)
+            "#,
+        )
+        .bind(session_id)
+        .bind(format!("user_id:{data_user_id}").into_bytes())
+        .execute(pool)
+        .await
+        .expect("seed session row");
+        sqlx::query(
+            r#"
+            INSERT INTO user_sessions (session_id, user_id, created_at)
+            VALUES (?, ?, '2026-02-01T00:00:00Z')
+            "#,
+        )
+        .bind(session_id)
+        .bind(user_id)
+        .execute(pool)
+        .await
+        .expect("seed user session");
+    }
+
+    async fn remaining_user_rows(pool: &SqlitePool, user_id: &str) -> Vec<(String, i64)> {
+        let tables = sqlx::query_scalar::<_, String>(
+            r#"SELECT name FROM sqlite_master WHERE type = 'table' AND name NOT LIKE 'sqlite_%'"#,
+        )
+        .fetch_all(pool)
+        .await
+        .expect("list tables");
+        let mut remaining = Vec::new();
+        for table in tables {
+            let columns = sqlx::query_scalar::<_, String>(&format!(
+                "SELECT name FROM pragma_table_info('{table}')"
+            ))
+            .fetch_all(pool)
+            .await
+            .expect("list columns");
+            for column in columns
+                .iter()
+                .filter(|column| USER_REFERENCE_COLUMNS.contains(&column.as_str()))
+            {
+                let count = sqlx::query_scalar::<_, i64>(&format!(
+                    "SELECT COUNT(*) FROM {table} WHERE {column} = ?"
+                ))
+                .bind(user_id)
+                .fetch_one(pool)
+                .await
+                .expect("count user rows");
+                if count > 0 {
+                    remaining.push((format!("{table}.{column}"), count));
+                }
+            }
+        }
+        let users = sqlx::query_scalar::<_, i64>("SELECT COUNT(*) FROM users WHERE id = ?")
+            .bind(user_id)
+            .fetch_one(pool)
+            .await
+            .expect("count users");
+        if users > 0 {
+            remaining.push(("users.id".to_owned(), users));
+        }
+        let sessions = sqlx::query_scalar::<_, i64>(
+            "SELECT COUNT(*) FROM tower_sessions WHERE id IN (SELECT session_id FROM user_sessions WHERE user_id = ?)",
+        )
+        .bind(user_id)
+        .fetch_one(pool)
+        .await
+        .expect("count sessions");
+        if sessions > 0 {
+            remaining.push(("tower_sessions.id".to_owned(), sessions));
+        }
+        remaining
+    }
+
+    fn setup_state(pool: SqlitePool) -> Arc<AppState> {
+        Arc::new(AppState::for_tests(pool, AppConfig::for_tests()))
+    }
+
+    async fn session_for(user_id: &str) -> Session {
+        let session = Session::new(None, Arc::new(MemoryStore::default()), None);
+        session
+            .insert("user_id", user_id)
+            .await
+            .expect("insert session user id");
+        session
+    }
+
+    fn confirmation(text: &str) -> ApiJson<DeleteAccountRequest> {
+        ApiJson(DeleteAccountRequest {
+            confirm: text.to_owned(),
+        })
+    }
+
+    #[tokio::test]
+    async fn delete_me_purges_every_user_row_and_keeps_other_accounts() {
+        let pool = setup_pool().await;
+        seed_user(&pool, &admin_id(), 1, true).await;
+        seed_user(&pool, &member_id(), 2, false).await;
+        seed_member_data(&pool, &member_id()).await;
+        // An admin session whose data mentions the member must survive.
+        seed_session(&pool, "session-account-admin", &admin_id(), &member_id()).await;
+        let state = setup_state(pool.clone());
+
+        let err = delete_me(
+            State(state.clone()),
+            api::AuthedUser(member_id()),
+            session_for(&member_id()).await,
+            confirmation("yes"),
+        )
+        .await
+        .expect_err("wrong confirmation is rejected");
+        assert_eq!(err.code(), "bad_request");
+        assert!(!remaining_user_rows(&pool, &member_id()).await.is_empty());
+
+        let session = session_for(&member_id()).await;
+        let Json(summary) = delete_me(
+            State(state.clone()),
+            api::AuthedUser(member_id()),
+            session.clone(),
+            confirmation(ACCOUNT_DELETE_CONFIRMATION),
+        )
+        .await
+        .expect("delete own account");
+
+        assert_eq!(summary.deleted.get("users"), Some(&1));
+        assert_eq!(summary.deleted.get("starred_repos"), Some(&1));
+        assert_eq!(summary.deleted.get("sessions"), Some(&1));
+        assert_eq!(summary.deleted.get("user_sessions"), Some(&1));
+        assert_eq!(summary.anonymized.get("job_tasks"), Some(&1));
+        assert_eq!(summary.anonymized.get("llm_calls"), Some(&1));
+        assert_eq!(
+            remaining_user_rows(&pool, &member_id()).await,
+            Vec::<(String, i64)>::new()
+        );
+        assert_eq!(
+            session
+                .get::<String>("user_id")
+                .await
+                .expect("read session"),
+            None
+        );
+
+        let kept_calls = sqlx::query_scalar::<_, i64>("SELECT COUNT(*) FROM llm_calls")
+            .fetch_one(&pool)
+            .await
+            .expect("count llm calls");
+        assert_eq!(kept_calls, 1, "llm calls are kept for usage totals");
+        let admins = sqlx::query_scalar::<_, i64>("SELECT COUNT(*) FROM users WHERE id = ?")
+            .bind(admin_id())
+            .fetch_one(&pool)
+            .await
+            .expect("count admin");
+        assert_eq!(admins, 1);
+        let admin_sessions = sqlx::query_scalar::<_, i64>(
+            "SELECT COUNT(*) FROM tower_sessions WHERE id = 'session-account-admin'",
+        )
+        .fetch_one(&pool)
+        .await
+        .expect("count admin sessions");
+        assert_eq!(admin_sessions, 1, "sessions are matched by id, not by data");
+    }
+
+    #[tokio::test]
+    async fn account_deletion_keeps_the_last_admin() {
+        let pool = setup_pool().await;
+        seed_user(&pool, &admin_id(), 1, true).await;
+        seed_user(&pool, &member_id(), 2, false).await;
+        let state = setup_state(pool.clone());
+
+        let err = delete_me(
+            State(state.clone()),
+            api::AuthedUser(admin_id()),
+            session_for(&admin_id()).await,
+            confirmation(ACCOUNT_DELETE_CONFIRMATION),
+        )
+        .await
+        .expect_err("last admin cannot delete self");
+        assert_eq!(err.code(), "last_admin_guard");
+
+        let err = admin_delete_user(
+            State(state.clone()),
+            api::AdminUser(admin_id()),
+            Path(admin_id()),
+        )
+        .await
+        .expect_err("admin endpoint refuses self deletion");
+        assert_eq!(err.code(), "cannot_delete_self");
+
+        let Json(summary) = admin_delete_user(
+            State(state.clone()),
+            api::AdminUser(admin_id()),
+            Path(member_id()),
+        )
+        .await
+        .expect("admin deletes member");
+        assert_eq!(summary.deleted.get("users"), Some(&1));
+        let audited = sqlx::query_scalar::<_, i64>(
+            "SELECT COUNT(*) FROM admin_audit_log WHERE action = ? AND target_user_id = ?",
+        )
+        .bind(admin_audit::ADMIN_AUDIT_USER_DELETE)
+        .bind(member_id())
+        .fetch_one(&pool)
+        .await
+        .expect("count audit rows");
+        assert_eq!(audited, 1);
+
+        let err = admin_delete_user(
+            State(state.clone()),
+            api::AdminUser(admin_id()),
+            Path(member_id()),
+        )
+        .await
+        .expect_err("deleted user is gone");
+        assert_eq!(err.code(), "not_found");
+        let remaining_admins = sqlx::query_scalar::<_, i64>("SELECT COUNT(*) FROM users")
+            .fetch_one(&pool)
+            .await
+            .expect("count users");
+        assert_eq!(remaining_admins, 1);
+    }
+}
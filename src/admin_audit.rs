@@ -0,0 +1,224 @@
+use std::{
+    collections::{HashMap, VecDeque},
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+use axum::{
+    Json,
+    extract::{Query, State},
+    http::StatusCode,
+};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use sqlx::SqliteConnection;
+
+use crate::{
+    api,
+    error::{ApiError, codes},
+    local_id,
+    state::AppState,
+};
+
+/// Mutations one admin may issue per [`ADMIN_MUTATION_WINDOW`] before getting
+/// 429s. Meant to stop runaway scripts, not to meter normal clicking.
+pub const ADMIN_MUTATIONS_PER_WINDOW: usize = 30;
+pub const ADMIN_MUTATION_WINDOW: Duration = Duration::from_secs(60);
+
+pub const ADMIN_AUDIT_USER_UPDATE: &str = "user.update";
+pub const ADMIN_AUDIT_USER_DELETE: &str = "user.delete";
+pub const ADMIN_AUDIT_SCHEDULED_SLOT_UPDATE: &str = "scheduled_slot.update";
+pub const ADMIN_AUDIT_TRANSLATION_CACHE_PURGE: &str = "translation_cache.purge";
+pub const ADMIN_AUDIT_IMPERSONATION_START: &str = "impersonation.start";
+pub const ADMIN_AUDIT_IMPERSONATION_STOP: &str = "impersonation.stop";
+
+/// Sliding-window counter of recent admin mutations, keyed by acting user.
+/// Process-local on purpose: a restart resetting the window is harmless.
+#[derive(Clone, Debug, Default)]
+pub struct AdminMutationLimiter {
+    hits: Arc<Mutex<HashMap<String, VecDeque<Instant>>>>,
+}
+
+impl AdminMutationLimiter {
+    /// Counts one mutation for `acting_user_id`, rejecting it once the
+    /// window is full.
+    pub fn check(&self, acting_user_id: &str) -> Result<(), ApiError> {
+        self.check_at(acting_user_id, Instant::now())
+    }
+
+    fn check_at(&self, acting_user_id: &str, now: Instant) -> Result<(), ApiError> {
+        let mut hits = self.hits.lock().expect("admin mutation limiter poisoned");
+        hits.retain(|_, window| {
+            while window
+                .front()
+                .is_some_and(|hit| now.duration_since(*hit) >= ADMIN_MUTATION_WINDOW)
+            {
+                window.pop_front();
+            }
+            !window.is_empty()
+        });
+        let window = hits.entry(acting_user_id.to_owned()).or_default();
+        if window.len() >= ADMIN_MUTATIONS_PER_WINDOW {
+            return Err(ApiError::new(
+                StatusCode::TOO_MANY_REQUESTS,
+                codes::ADMIN_RATE_LIMITED,
+                "too many admin changes; retry in a minute",
+            ));
+        }
+        window.push_back(now);
+        Ok(())
+    }
+}
+
+/// Writes one audit row on the caller's connection, so it commits or rolls
+/// back together with the mutation it describes.
+pub async fn record(
+    conn: &mut SqliteConnection,
+    acting_user_id: &str,
+    target_user_id: Option<&str>,
+    action: &str,
+    before: &Value,
+    after: &Value,
+) -> Result<(), ApiError> {
+    sqlx::query(
+        r#"
+        INSERT INTO admin_audit_log (
+          id, acting_user_id, target_user_id, action, before_json, after_json, created_at
+        )
+        VALUES (?, ?, ?, ?, ?, ?, ?)
+        "#,
+    )
+    .bind(local_id::generate_local_id())
+    .bind(acting_user_id)
+    .bind(target_user_id)
+    .bind(action)
+    .bind(before.to_string())
+    .bind(after.to_string())
+    .bind(chrono::Utc::now().to_rfc3339())
+    .execute(conn)
+    .await
+    .map_err(ApiError::internal)?;
+    Ok(())
+}
+
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct AdminAuditQuery {
+    target_user_id: Option<String>,
+    page: Option<i64>,
+    page_size: Option<i64>,
+}
+
+#[derive(Debug, Serialize, JsonSchema, sqlx::FromRow)]
+pub struct AdminAuditItem {
+    pub id: String,
+    pub acting_user_id: String,
+    pub acting_login: Option<String>,
+    pub target_user_id: Option<String>,
+    pub target_login: Option<String>,
+    pub action: String,
+    pub before_json: String,
+    pub after_json: String,
+    pub created_at: String,
+}
+
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct AdminAuditListResponse {
+    pub items: Vec<AdminAuditItem>,
+    pub page: i64,
+    pub page_size: i64,
+    pub total: i64,
+}
+
+pub async fn admin_list_audit(
+    State(state): State<Arc<AppState>>,
+    _: api::AdminUser,
+    Query(query): Query<AdminAuditQuery>,
+) -> Result<Json<AdminAuditListResponse>, ApiError> {
+    let page = query.page.unwrap_or(1);
+    if page < 1 {
+        return Err(ApiError::bad_request("page must be >= 1"));
+    }
+    let page_size = query.page_size.unwrap_or(20).clamp(1, 100);
+    let offset = page
+        .checked_sub(1)
+        .and_then(|value| value.checked_mul(page_size))
+        .ok_or_else(|| ApiError::bad_request("page is too large"))?;
+    let target_user_id = query
+        .target_user_id
+        .map(|raw| api::parse_local_id_param(raw, "target_user_id"))
+        .transpose()?;
+
+    let total = sqlx::query_scalar::<_, i64>(
+        r#"
+        SELECT COUNT(*)
+        FROM admin_audit_log
+        WHERE ? IS NULL OR target_user_id = ?
+        "#,
+    )
+    .bind(target_user_id.as_deref())
+    .bind(target_user_id.as_deref())
+    .fetch_one(&state.pool)
+    .await
+    .map_err(ApiError::internal)?;
+
+    let items = sqlx::query_as::<_, AdminAuditItem>(
+        r#"
+        SELECT
+          log.id,
+          log.acting_user_id,
+          acting.login AS acting_login,
+          log.target_user_id,
+          target.login AS target_login,
+          log.action,
+          log.before_json,
+          log.after_json,
+          log.created_at
+        FROM admin_audit_log log
+        LEFT JOIN users acting ON acting.id = log.acting_user_id
+        LEFT JOIN users target ON target.id = log.target_user_id
+        WHERE ? IS NULL OR log.target_user_id = ?
+        ORDER BY log.created_at DESC, log.id DESC
+        LIMIT ? OFFSET ?
+        "#,
+    )
+    .bind(target_user_id.as_deref())
+    .bind(target_user_id.as_deref())
+    .bind(page_size)
+    .bind(offset)
+    .fetch_all(&state.pool)
+    .await
+    .map_err(ApiError::internal)?;
+
+    Ok(Json(AdminAuditListResponse {
+        items,
+        page,
+        page_size,
+        total,
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn limiter_rejects_past_window_budget_and_recovers_after_window() {
+        let limiter = AdminMutationLimiter::default();
+        let start = Instant::now();
+        for _ in 0..ADMIN_MUTATIONS_PER_WINDOW {
+            limiter.check_at("admin-a", start).expect("within budget");
+        }
+
+        let err = limiter
+            .check_at("admin-a", start)
+            .expect_err("budget exhausted");
+        assert_eq!(err.code(), "admin_rate_limited");
+        limiter
+            .check_at("admin-b", start)
+            .expect("other admins keep their own budget");
+        limiter
+            .check_at("admin-a", start + ADMIN_MUTATION_WINDOW)
+            .expect("window slid past earlier mutations");
+    }
+}
@@ -22,6 +22,8 @@ pub struct AdminRuntimeSettingsSnapshot {
     pub translation_general_worker_concurrency: usize,
     pub translation_dedicated_worker_concurrency: usize,
     pub repo_release_worker_concurrency: usize,
+    pub llm_scheduler_paused: bool,
+    pub llm_request_interval_ms: Option<u64>,
 }
 
 pub const DEFAULT_SYNC_AUTO_FETCH_INTERVAL_MINUTES: i64 = 60;
@@ -30,6 +32,8 @@ pub const DEFAULT_REPO_RELEASE_WORKER_CONCURRENCY: usize = 8;
 pub const MAX_REPO_RELEASE_WORKER_CONCURRENCY: usize = 32;
 pub const DEFAULT_REPO_REFRESH_SYSTEM_BUDGET_PER_WINDOW: i64 = 1000;
 pub const MAX_REPO_REFRESH_SYSTEM_BUDGET_PER_WINDOW: i64 = 20_000;
+pub const MIN_LLM_REQUEST_INTERVAL_MS: u64 = 100;
+pub const MAX_LLM_REQUEST_INTERVAL_MS: u64 = 600_000;
 
 pub fn normalize_sync_auto_fetch_interval_minutes(value: i64) -> i64 {
     value.clamp(1, 120)
@@ -39,6 +43,10 @@ pub fn normalize_retry_recent_failures_interval_minutes(value: i64) -> i64 {
     value.clamp(1, 120)
 }
 
+pub fn normalize_llm_request_interval_ms(value: u64) -> u64 {
+    value.clamp(MIN_LLM_REQUEST_INTERVAL_MS, MAX_LLM_REQUEST_INTERVAL_MS)
+}
+
 pub fn normalize_repo_release_worker_concurrency(value: i64) -> usize {
     usize::try_from(value.clamp(
         1,
@@ -429,6 +437,8 @@ pub async fn load_or_seed_runtime_settings(
         translation_general_worker_concurrency: DEFAULT_TRANSLATION_GENERAL_WORKER_CONCURRENCY,
         translation_dedicated_worker_concurrency: DEFAULT_TRANSLATION_DEDICATED_WORKER_CONCURRENCY,
         repo_release_worker_concurrency: DEFAULT_REPO_RELEASE_WORKER_CONCURRENCY,
+        llm_scheduler_paused: false,
+        llm_request_interval_ms: None,
     };
     let now = Utc::now().to_rfc3339();
     sqlx::query(
@@ -497,6 +507,42 @@ pub async fn update_llm_runtime_settings(
         .ok_or_else(|| anyhow::anyhow!("admin runtime settings row missing after llm update"))
 }
 
+/// Persists the scheduler pause flag and request interval override. `None`
+/// leaves a field untouched; `Some(None)` for the interval drops the override
+/// so the env default applies again.
+pub async fn update_llm_scheduler_controls(
+    pool: &SqlitePool,
+    paused: Option<bool>,
+    request_interval_ms: Option<Option<u64>>,
+) -> Result<AdminRuntimeSettingsSnapshot> {
+    let current = fetch_runtime_settings(pool)
+        .await?
+        .ok_or_else(|| anyhow::anyhow!("admin runtime settings row missing"))?;
+    let paused = paused.unwrap_or(current.llm_scheduler_paused);
+    let request_interval_ms = request_interval_ms
+        .unwrap_or(current.llm_request_interval_ms)
+        .map(normalize_llm_request_interval_ms);
+    let now = Utc::now().to_rfc3339();
+    sqlx::query(
+        r#"
+        UPDATE admin_runtime_settings
+        SET
+          llm_scheduler_paused = ?,
+          llm_request_interval_ms = ?,
+          updated_at = ?
+        WHERE id = 1
+        "#,
+    )
+    .bind(i64::from(paused))
+    .bind(request_interval_ms.map(|value| i64::try_from(value).unwrap_or(i64::MAX)))
+    .bind(now.as_str())
+    .execute(pool)
+    .await?;
+    fetch_runtime_settings(pool).await?.ok_or_else(|| {
+        anyhow::anyhow!("admin runtime settings row missing after llm scheduler update")
+    })
+}
+
 pub async fn update_translation_runtime_settings(
     pool: &SqlitePool,
     general_worker_concurrency: usize,
@@ -529,6 +575,12 @@ pub async fn load_ai_model_context_limit(pool: &SqlitePool) -> Result<Option<u32
         .and_then(|snapshot| snapshot.ai_model_context_limit))
 }
 
+pub async fn load_llm_request_interval_override(pool: &SqlitePool) -> Result<Option<u64>> {
+    Ok(fetch_runtime_settings(pool)
+        .await?
+        .and_then(|snapshot| snapshot.llm_request_interval_ms))
+}
+
 pub async fn load_llm_models(pool: &SqlitePool) -> Result<Vec<String>> {
     Ok(fetch_runtime_settings(pool)
         .await?
@@ -549,6 +601,14 @@ pub async fn sync_persisted_runtime_settings(
         .llm_scheduler
         .set_model_routing(snapshot.llm_models.clone())
         .await;
+    state
+        .llm_scheduler
+        .set_paused(snapshot.llm_scheduler_paused);
+    state.llm_scheduler.set_request_interval_ms(
+        snapshot
+            .llm_request_interval_ms
+            .unwrap_or(state.config.ai_request_interval_ms),
+    );
     state
         .translation_scheduler
         .apply_runtime_config(
@@ -572,7 +632,9 @@ async fn fetch_runtime_settings(pool: &SqlitePool) -> Result<Option<AdminRuntime
           llm_models_json,
           translation_general_worker_concurrency,
           translation_dedicated_worker_concurrency,
-          repo_release_worker_concurrency
+          repo_release_worker_concurrency,
+          llm_scheduler_paused,
+          llm_request_interval_ms
         FROM admin_runtime_settings
         WHERE id = 1
         LIMIT 1
@@ -598,6 +660,10 @@ async fn fetch_runtime_settings(pool: &SqlitePool) -> Result<Option<AdminRuntime
         repo_release_worker_concurrency: normalize_repo_release_worker_concurrency(
             row.get::<i64, _>("repo_release_worker_concurrency"),
         ),
+        llm_scheduler_paused: row.get::<i64, _>("llm_scheduler_paused") != 0,
+        llm_request_interval_ms: row
+            .get::<Option<i64>, _>("llm_request_interval_ms")
+            .and_then(|value| u64::try_from(value).ok()),
     }))
 }
 
@@ -726,6 +792,52 @@ mod tests {
         state.translation_scheduler.abort_all().await;
     }
 
+    #[allow(clippy::await_holding_lock)]
+    #[tokio::test]
+    async fn llm_scheduler_controls_survive_restart_and_fall_back_to_env_interval() {
+        let _env_guard = env_lock().lock().expect("lock env");
+        clear_legacy_context_limit_env();
+        let pool = setup_pool().await;
+        let mut config = test_config(1);
+        config.ai_request_interval_ms = 250;
+        load_or_seed_runtime_settings(&pool, &config)
+            .await
+            .expect("seed runtime settings");
+
+        let stored = update_llm_scheduler_controls(&pool, Some(true), Some(Some(5)))
+            .await
+            .expect("update scheduler controls");
+        assert!(stored.llm_scheduler_paused);
+        assert_eq!(
+            stored.llm_request_interval_ms,
+            Some(MIN_LLM_REQUEST_INTERVAL_MS)
+        );
+
+        // A fresh state stands in for a restarted process.
+        let state = setup_state(pool.clone(), config.clone());
+        assert!(!state.llm_scheduler.is_paused());
+        sync_persisted_runtime_settings(state.clone())
+            .await
+            .expect("sync persisted runtime settings");
+        assert!(state.llm_scheduler.is_paused());
+        assert_eq!(
+            state.llm_scheduler.request_interval_ms(),
+            MIN_LLM_REQUEST_INTERVAL_MS
+        );
+
+        let stored = update_llm_scheduler_controls(&pool, None, Some(None))
+            .await
+            .expect("clear interval override");
+        assert!(stored.llm_scheduler_paused);
+        assert_eq!(stored.llm_request_interval_ms, None);
+        sync_persisted_runtime_settings(state.clone())
+            .await
+            .expect("sync cleared override");
+        assert_eq!(state.llm_scheduler.request_interval_ms(), 250);
+
+        state.translation_scheduler.abort_all().await;
+    }
+
     #[allow(clippy::await_holding_lock)]
     #[tokio::test]
     async fn load_or_seed_runtime_settings_backfills_legacy_context_limit_from_env() {
@@ -837,6 +949,12 @@ mod tests {
             webauthn,
             encryption_key: config.encryption_key.clone(),
             runtime_owner_id: generate_local_id(),
+            feed_events: crate::feed_events::FeedEventHub::default(),
+            admin_mutation_limiter: crate::admin_audit::AdminMutationLimiter::default(),
+            translation_flights: crate::ai::TranslationSingleflight::default(),
+            request_limiter: crate::rate_limit::RequestRateLimiter::default(),
+            github_probe: crate::health::GitHubProbe::default(),
+            github_app_tokens: None,
             pool,
             sqlite_writer: crate::sqlite_write::SqliteWriteCoordinator::new(),
             config,
@@ -854,6 +972,16 @@ mod tests {
             static_dir: None,
             task_log_dir: std::env::temp_dir().join("octo-rill-admin-runtime-tests"),
             job_worker_concurrency: 2,
+            reaction_viewer_ttl_secs: 600,
+            release_backfill_limit: 10,
+            star_purge_grace_days: 14,
+            notification_archive_days: 90,
+            notification_retention_days: 365,
+            task_retention_days: 30,
+            backup_dir: std::env::temp_dir().join("octo-rill-backups-tests"),
+            backup_retention_count: 7,
+            shutdown_grace_secs: 30,
+            task_timeouts: crate::jobs::TaskTimeouts::default(),
             encryption_key: EncryptionKey::from_base64(
                 "AAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAA=",
             )
@@ -864,12 +992,23 @@ mod tests {
                 redirect_url: Url::parse("http://127.0.0.1:58090/auth/callback")
                     .expect("parse github redirect"),
             },
+            github_app: None,
             linuxdo: None,
             ai: None,
             ai_max_concurrency,
+            ai_translate_concurrency: 2,
+            ai_skip_translation_cjk_percent: 30,
+            ai_daily_token_budget_per_user: None,
+            ai_request_interval_ms: 0,
             ai_daily_at_local: None,
             app_default_time_zone: crate::briefs::DEFAULT_DAILY_BRIEF_TIME_ZONE.to_owned(),
+            smtp: None,
             logging: crate::observability::LoggingThresholds::default(),
+            metrics_token: None,
+            trust_request_id_header: false,
+            trust_forwarded_headers: false,
+            readyz_check_github: false,
+            rate_limits: crate::rate_limit::RateLimits::default(),
         }
     }
 }
@@ -1,24 +1,25 @@
 use std::collections::{BTreeMap, HashMap, HashSet, VecDeque};
 use std::future::Future;
-use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering};
 use std::sync::{Arc, Mutex, OnceLock};
 use std::time::{Duration, Instant};
 
 use anyhow::{Context, Result, anyhow};
 use chrono::{
-    DateTime, FixedOffset, Local, NaiveDate, NaiveDateTime, NaiveTime, TimeZone, Timelike, Utc,
+    DateTime, FixedOffset, NaiveDate, NaiveDateTime, NaiveTime, TimeZone, Timelike, Utc,
     offset::LocalResult,
 };
 use rand::RngExt;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 use sha2::{Digest, Sha256};
+use tokio::sync::mpsc;
 use url::Url;
 
 use crate::{
     admin_runtime,
     briefs::{self, DailyWindow as UserDailyWindow},
-    config::AiConfig,
+    config::{AiConfig, AiProvider, AiStartupProbe},
     jobs, local_id, observability,
     release_links::{
         InternalReleaseRef, build_internal_brief_release_href_from_html_url,
@@ -29,12 +30,18 @@ use crate::{
     state::AppState,
 };
 
+mod provider;
+
+use provider::{ChatRequest, chat_provider, merge_usage, with_derived_total};
+
 const MODEL_LIMIT_UNKNOWN_FALLBACK: u32 = 32_768;
 const MODEL_LIMIT_SYNC_INTERVAL: Duration = Duration::from_secs(24 * 60 * 60);
 const MODEL_LIMIT_REFRESH_RETRY_INTERVAL: Duration = Duration::from_secs(5 * 60);
 const MODEL_LIMIT_SOURCE_REQUEST_TIMEOUT: Duration = Duration::from_secs(3);
 const MODEL_LIMIT_SAFETY_MIN_TOKENS: u32 = 512;
 const MODEL_LIMIT_SAFETY_RATIO: f64 = 0.05;
+const AI_HEALTH_CACHE_TTL: Duration = Duration::from_secs(5 * 60);
+const AI_HEALTH_PROBE_TIMEOUT: Duration = Duration::from_secs(10);
 const MODEL_LIMIT_SOURCE_OPENROUTER: &str = "https://openrouter.ai/api/v1/models";
 const MODEL_LIMIT_SOURCE_LITELLM: &str =
     "https://raw.githubusercontent.com/BerriAI/litellm/main/model_prices_and_context_window.json";
@@ -48,11 +55,18 @@ const LLM_TRANSLATION_EMPTY_CONTENT_MAX_ATTEMPTS: usize = 8;
 const LLM_RETRY_BACKOFF_BASE: Duration = Duration::from_millis(500);
 const LLM_RETRY_BACKOFF_CAP: Duration = Duration::from_secs(5);
 const LLM_RETRY_BACKOFF_JITTER_MAX_MS: u64 = 250;
-const LLM_CALL_LOG_RETENTION: Duration = Duration::from_secs(7 * 24 * 60 * 60);
 const LLM_CALL_LOG_CLEANUP_INTERVAL: Duration = Duration::from_secs(60 * 60);
 const LLM_MODEL_FINAL_FAILURE_THRESHOLD: u32 = 3;
 const LLM_MODEL_FAILURE_COOLDOWN: Duration = Duration::from_secs(10 * 60);
 const AI_RESPONSE_MISSING_CONTENT_ERROR: &str = "AI response missing content";
+const BRIEF_RELEASE_LIMIT: i64 = 300;
+const BRIEF_PROJECT_SUMMARY_MAX_TOKENS: u32 = 1_400;
+const BRIEF_PROJECT_BATCH_OVERHEAD_TOKENS: u32 = 320;
+const BRIEF_POLISH_MAX_TOKENS: u32 = 1_600;
+const BRIEF_PROJECT_SUMMARY_SYSTEM_PROMPT: &str =
+    "你是一个严谨的发布说明整理助手，擅长在不遗漏关键信息的前提下提炼 GitHub Release 变更。";
+const BRIEF_POLISH_SYSTEM_PROMPT: &str =
+    "你是一个发布日报编辑器，负责在不丢失信息的前提下用简体中文优化可读性。";
 
 #[derive(Debug, Default)]
 struct ModelLimitCatalog {
@@ -74,6 +88,9 @@ struct OpenRouterModelItem {
 
 static MODEL_LIMIT_CATALOG: OnceLock<tokio::sync::RwLock<ModelLimitCatalog>> = OnceLock::new();
 static BUILTIN_MODEL_LIMITS: OnceLock<HashMap<String, u32>> = OnceLock::new();
+/// Last probe per `base_url|model`, so repeated health checks don't hit upstream.
+static AI_HEALTH_CACHE: OnceLock<tokio::sync::Mutex<HashMap<String, (Instant, AiHealthReport)>>> =
+    OnceLock::new();
 
 tokio::task_local! {
     static LLM_CALL_CONTEXT: LlmCallContext;
@@ -94,6 +111,8 @@ pub struct LlmSchedulerRuntimeStatus {
     pub available_slots: i64,
     pub waiting_calls: i64,
     pub in_flight_calls: i64,
+    pub paused: bool,
+    pub request_interval_ms: i64,
 }
 
 #[derive(Debug, Clone)]
@@ -120,17 +139,85 @@ pub struct LlmScheduler {
     status_overrides: tokio::sync::RwLock<HashMap<String, LlmCallAdminOverride>>,
     waiting_calls: AtomicUsize,
     in_flight_calls: AtomicUsize,
+    paused: AtomicBool,
+    request_interval_ms: AtomicU64,
 }
 
 #[derive(Debug, Default)]
 struct SchedulerGateState {
+    interactive_waiters: VecDeque<Arc<SchedulerQueuedWaiterState>>,
     waiters: VecDeque<Arc<SchedulerQueuedWaiterState>>,
+    /// Interactive grants in a row while background calls were waiting.
+    interactive_streak: usize,
+    next_dispatch_at: Option<Instant>,
+}
+
+/// Dispatch queue of an LLM call. Calls made on behalf of an API request
+/// (`source` starting with `api.`) jump ahead of scheduled/background work,
+/// but background still gets one of every [`LLM_LANE_FAIRNESS_WINDOW`] slots.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum LlmCallLane {
+    Interactive,
+    Background,
+}
+
+impl LlmCallLane {
+    pub(crate) fn for_source(source: &str) -> Self {
+        if source.starts_with("api.") {
+            Self::Interactive
+        } else {
+            Self::Background
+        }
+    }
+}
+
+const LLM_LANE_FAIRNESS_WINDOW: usize = 5;
+
+/// Picks the queue the next free slot goes to: interactive first, unless it
+/// already took the last `LLM_LANE_FAIRNESS_WINDOW - 1` slots while
+/// background calls were waiting.
+fn next_waiter_lane(
+    interactive_waiting: bool,
+    background_waiting: bool,
+    interactive_streak: usize,
+) -> Option<LlmCallLane> {
+    match (interactive_waiting, background_waiting) {
+        (true, true) if interactive_streak + 1 >= LLM_LANE_FAIRNESS_WINDOW => {
+            Some(LlmCallLane::Background)
+        }
+        (true, _) => Some(LlmCallLane::Interactive),
+        (false, true) => Some(LlmCallLane::Background),
+        (false, false) => None,
+    }
 }
 
 #[derive(Debug)]
 struct SchedulerQueuedWaiterState {
     notify: tokio::sync::Notify,
     state: AtomicUsize,
+    call: Option<LlmQueuedCall>,
+}
+
+/// The `llm_calls` row a queued waiter belongs to, kept so admins can see
+/// and act on what is stuck in the scheduler queue.
+#[derive(Debug, Clone)]
+pub(crate) struct LlmQueuedCall {
+    pub call_id: String,
+    pub source: String,
+    pub requested_by: Option<String>,
+    pub parent_task_id: Option<String>,
+    pub parent_task_type: Option<String>,
+    pub enqueued_at: DateTime<Utc>,
+    pub estimated_tokens: u32,
+}
+
+#[derive(Debug, Clone)]
+pub(crate) struct LlmQueueEntrySnapshot {
+    pub call: LlmQueuedCall,
+    pub lane: &'static str,
+    /// 1-based position within the entry's lane.
+    pub position: usize,
+    pub wait_reason: &'static str,
 }
 
 #[derive(Debug, Default)]
@@ -153,17 +240,28 @@ pub struct SelectedLlmModel {
 }
 
 impl SchedulerQueuedWaiterState {
-    fn new() -> Self {
+    fn new(call: Option<LlmQueuedCall>) -> Self {
         Self {
             notify: tokio::sync::Notify::new(),
             state: AtomicUsize::new(SCHEDULER_WAITER_WAITING),
+            call,
         }
     }
+
+    fn is_call(&self, call_id: &str) -> bool {
+        self.call
+            .as_ref()
+            .is_some_and(|call| call.call_id == call_id)
+    }
 }
 
 const SCHEDULER_WAITER_WAITING: usize = 0;
 const SCHEDULER_WAITER_GRANTED: usize = 1;
 const SCHEDULER_WAITER_CANCELED: usize = 2;
+/// Removed from the queue by an admin; the waiting call gives up.
+const SCHEDULER_WAITER_DROPPED: usize = 3;
+
+pub(crate) const LLM_CALL_CANCELLED_BY_ADMIN: &str = "cancelled by admin";
 
 #[derive(Debug, Clone, Copy)]
 pub struct InputBudget {
@@ -185,6 +283,8 @@ impl LlmScheduler {
             status_overrides: tokio::sync::RwLock::new(HashMap::new()),
             waiting_calls: AtomicUsize::new(0),
             in_flight_calls: AtomicUsize::new(0),
+            paused: AtomicBool::new(false),
+            request_interval_ms: AtomicU64::new(0),
         }
     }
 
@@ -199,6 +299,29 @@ impl LlmScheduler {
         self.schedule_waiters_locked(&mut gate);
     }
 
+    pub fn is_paused(&self) -> bool {
+        self.paused.load(Ordering::Acquire)
+    }
+
+    /// Paused schedulers keep accepting calls but hold them in the queue
+    /// until resumed; calls already in flight are not interrupted.
+    pub fn set_paused(&self, paused: bool) {
+        self.paused.store(paused, Ordering::Release);
+        if !paused {
+            let mut gate = self.gate.lock().expect("llm scheduler gate lock poisoned");
+            self.schedule_waiters_locked(&mut gate);
+        }
+    }
+
+    pub fn request_interval_ms(&self) -> u64 {
+        self.request_interval_ms.load(Ordering::Relaxed)
+    }
+
+    pub fn set_request_interval_ms(&self, request_interval_ms: u64) {
+        self.request_interval_ms
+            .store(request_interval_ms, Ordering::Relaxed);
+    }
+
     pub(crate) async fn admin_overrides(&self) -> HashMap<String, LlmCallAdminOverride> {
         self.status_overrides.read().await.clone()
     }
@@ -314,24 +437,46 @@ impl LlmScheduler {
             waiting_calls: i64::try_from(self.waiting_calls.load(Ordering::Relaxed))
                 .unwrap_or(i64::MAX),
             in_flight_calls: i64::try_from(in_flight_calls).unwrap_or(i64::MAX),
+            paused: self.is_paused(),
+            request_interval_ms: i64::try_from(self.request_interval_ms()).unwrap_or(i64::MAX),
         }
     }
 
-    async fn acquire_slot(self: &Arc<Self>) -> (i64, SchedulerInFlightGuard) {
+    #[cfg(test)]
+    async fn acquire_slot(self: &Arc<Self>, lane: LlmCallLane) -> (i64, SchedulerInFlightGuard) {
+        self.acquire_call_slot(lane, None)
+            .await
+            .expect("anonymous waiters cannot be dropped")
+    }
+
+    /// Waits for a free slot. Returns `None` when an admin dropped the call
+    /// from the queue while it was waiting.
+    async fn acquire_call_slot(
+        self: &Arc<Self>,
+        lane: LlmCallLane,
+        call: Option<LlmQueuedCall>,
+    ) -> Option<(i64, SchedulerInFlightGuard)> {
         let queue_started_at = Instant::now();
         let mut waiting_guard = None;
         let queued_waiter = {
             let mut gate = self.gate.lock().expect("llm scheduler gate lock poisoned");
             self.prune_canceled_waiters_locked(&mut gate);
             if gate.waiters.is_empty()
+                && gate.interactive_waiters.is_empty()
+                && !self.is_paused()
                 && self.in_flight_calls.load(Ordering::Relaxed) < self.max_concurrency()
             {
                 self.in_flight_calls.fetch_add(1, Ordering::Relaxed);
                 None
             } else {
                 waiting_guard = Some(SchedulerWaitingGuard::new(&self.waiting_calls));
-                let state = Arc::new(SchedulerQueuedWaiterState::new());
-                gate.waiters.push_back(Arc::clone(&state));
+                let state = Arc::new(SchedulerQueuedWaiterState::new(call));
+                match lane {
+                    LlmCallLane::Interactive => {
+                        gate.interactive_waiters.push_back(Arc::clone(&state))
+                    }
+                    LlmCallLane::Background => gate.waiters.push_back(Arc::clone(&state)),
+                }
                 Some(SchedulerQueuedWaiter::new(self, state))
             }
         };
@@ -339,28 +484,148 @@ impl LlmScheduler {
         if let Some(mut queued_waiter) = queued_waiter {
             queued_waiter.state.notify.notified().await;
             queued_waiter.complete();
+            if queued_waiter.state.state.load(Ordering::Acquire) == SCHEDULER_WAITER_DROPPED {
+                return None;
+            }
         }
 
         drop(waiting_guard);
-        let wait_ms = i64::try_from(queue_started_at.elapsed().as_millis()).unwrap_or(i64::MAX);
-        (
-            wait_ms,
-            SchedulerInFlightGuard {
-                scheduler: Arc::clone(self),
-                slot_released: false,
-            },
-        )
+        let in_flight_guard = SchedulerInFlightGuard {
+            scheduler: Arc::clone(self),
+            slot_released: false,
+        };
+        if let Some(dispatch_at) = self.reserve_dispatch_at() {
+            tokio::time::sleep_until(dispatch_at.into()).await;
+        }
+        let waited = queue_started_at.elapsed();
+        observability::record_llm_scheduler_wait(waited);
+        let wait_ms = i64::try_from(waited.as_millis()).unwrap_or(i64::MAX);
+        Some((wait_ms, in_flight_guard))
+    }
+
+    /// Calls waiting for a slot, interactive lane first, each lane in the
+    /// order it will be dispatched.
+    pub(crate) fn queue_snapshot(&self) -> Vec<LlmQueueEntrySnapshot> {
+        let mut gate = self.gate.lock().expect("llm scheduler gate lock poisoned");
+        self.prune_canceled_waiters_locked(&mut gate);
+        let paused = self.is_paused();
+        let interactive_waiting = !gate.interactive_waiters.is_empty();
+        let lanes = [
+            ("interactive", &gate.interactive_waiters),
+            ("background", &gate.waiters),
+        ];
+        let mut entries = Vec::new();
+        for (lane, waiters) in lanes {
+            for (idx, waiter) in waiters.iter().enumerate() {
+                let Some(call) = waiter.call.as_ref() else {
+                    continue;
+                };
+                let wait_reason = if paused {
+                    "scheduler_paused"
+                } else if idx > 0 {
+                    "queued_behind_earlier_calls"
+                } else if lane == "background" && interactive_waiting {
+                    "interactive_calls_first"
+                } else {
+                    "no_free_slot"
+                };
+                entries.push(LlmQueueEntrySnapshot {
+                    call: call.clone(),
+                    lane,
+                    position: idx + 1,
+                    wait_reason,
+                });
+            }
+        }
+        entries
+    }
+
+    /// Moves a waiting call to the front of its lane.
+    pub(crate) fn promote_queued_call(&self, call_id: &str) -> bool {
+        let mut gate = self.gate.lock().expect("llm scheduler gate lock poisoned");
+        self.prune_canceled_waiters_locked(&mut gate);
+        let gate = &mut *gate;
+        for waiters in [&mut gate.interactive_waiters, &mut gate.waiters] {
+            if let Some(idx) = waiters.iter().position(|waiter| waiter.is_call(call_id)) {
+                if let Some(waiter) = waiters.remove(idx) {
+                    waiters.push_front(waiter);
+                }
+                return true;
+            }
+        }
+        false
+    }
+
+    /// Removes a waiting call from the queue and wakes it so it gives up;
+    /// calls already granted a slot are left alone.
+    fn drop_queued_call(&self, call_id: &str) -> bool {
+        let mut gate = self.gate.lock().expect("llm scheduler gate lock poisoned");
+        self.prune_canceled_waiters_locked(&mut gate);
+        let gate = &mut *gate;
+        for waiters in [&mut gate.interactive_waiters, &mut gate.waiters] {
+            let Some(idx) = waiters.iter().position(|waiter| waiter.is_call(call_id)) else {
+                continue;
+            };
+            let Some(waiter) = waiters.remove(idx) else {
+                return false;
+            };
+            let dropped = waiter
+                .state
+                .compare_exchange(
+                    SCHEDULER_WAITER_WAITING,
+                    SCHEDULER_WAITER_DROPPED,
+                    Ordering::AcqRel,
+                    Ordering::Acquire,
+                )
+                .is_ok();
+            if dropped {
+                waiter.notify.notify_one();
+            }
+            return dropped;
+        }
+        false
+    }
+
+    /// Books the next dispatch time so consecutive upstream requests are at
+    /// least `request_interval_ms` apart; returns when this call may start.
+    fn reserve_dispatch_at(&self) -> Option<Instant> {
+        let interval = Duration::from_millis(self.request_interval_ms());
+        let mut gate = self.gate.lock().expect("llm scheduler gate lock poisoned");
+        if interval.is_zero() {
+            gate.next_dispatch_at = None;
+            return None;
+        }
+        let now = Instant::now();
+        let dispatch_at = gate.next_dispatch_at.filter(|at| *at > now).unwrap_or(now);
+        gate.next_dispatch_at = Some(dispatch_at + interval);
+        (dispatch_at > now).then_some(dispatch_at)
     }
 
     fn prune_canceled_waiters_locked(&self, gate: &mut SchedulerGateState) {
+        gate.interactive_waiters
+            .retain(|waiter| waiter.state.load(Ordering::Acquire) != SCHEDULER_WAITER_CANCELED);
         gate.waiters
             .retain(|waiter| waiter.state.load(Ordering::Acquire) != SCHEDULER_WAITER_CANCELED);
     }
 
     fn schedule_waiters_locked(&self, gate: &mut SchedulerGateState) {
         self.prune_canceled_waiters_locked(gate);
+        if self.is_paused() {
+            return;
+        }
         while self.in_flight_calls.load(Ordering::Relaxed) < self.max_concurrency() {
-            let Some(waiter) = gate.waiters.pop_front() else {
+            let Some(lane) = next_waiter_lane(
+                !gate.interactive_waiters.is_empty(),
+                !gate.waiters.is_empty(),
+                gate.interactive_streak,
+            ) else {
+                break;
+            };
+            let waiter = match lane {
+                LlmCallLane::Interactive => gate.interactive_waiters.pop_front(),
+                LlmCallLane::Background => gate.waiters.pop_front(),
+            };
+            let Some(waiter) = waiter else {
                 break;
             };
             if waiter
@@ -375,6 +640,12 @@ impl LlmScheduler {
             {
                 continue;
             }
+            gate.interactive_streak =
+                if lane == LlmCallLane::Interactive && !gate.waiters.is_empty() {
+                    gate.interactive_streak + 1
+                } else {
+                    0
+                };
             self.in_flight_calls.fetch_add(1, Ordering::Relaxed);
             waiter.notify.notify_one();
         }
@@ -436,6 +707,20 @@ fn current_llm_call_context() -> Option<LlmCallContext> {
     LLM_CALL_CONTEXT.try_with(Clone::clone).ok()
 }
 
+pub(crate) fn current_parent_task_id() -> Option<String> {
+    LLM_CALL_CONTEXT
+        .try_with(|ctx| ctx.parent_task_id.clone())
+        .ok()
+        .flatten()
+}
+
+pub(crate) fn current_parent_task_type() -> Option<String> {
+    LLM_CALL_CONTEXT
+        .try_with(|ctx| ctx.parent_task_type.clone())
+        .ok()
+        .flatten()
+}
+
 fn llm_parent_task_type_uses_translation_empty_content_retry_budget(
     parent_task_type: Option<&str>,
 ) -> bool {
@@ -702,16 +987,188 @@ async fn refresh_model_limits(state: &AppState, force: bool) -> Result<()> {
     Ok(())
 }
 
-fn lookup_model_limit_in_map(map: &HashMap<String, u32>, model: &str) -> Option<u32> {
-    for alias in model_aliases(model) {
-        if let Some(limit) = map.get(&alias) {
-            return Some(*limit);
+fn lookup_model_limit_in_map(
+    map: &HashMap<String, u32>,
+    provider: AiProvider,
+    model: &str,
+) -> Option<u32> {
+    for name in chat_provider(provider).catalog_model_names(model) {
+        for alias in model_aliases(&name) {
+            if let Some(limit) = map.get(&alias) {
+                return Some(*limit);
+            }
         }
     }
     None
 }
 
-pub(crate) fn resolve_model_input_limit_for_model(model: &str) -> (u32, &'static str) {
+fn configured_ai_provider(state: &AppState) -> AiProvider {
+    state
+        .config
+        .ai
+        .as_ref()
+        .map(|ai| ai.provider)
+        .unwrap_or_default()
+}
+
+#[derive(Debug, Clone, Serialize, schemars::JsonSchema)]
+pub struct AiHealthReport {
+    pub configured: bool,
+    pub ok: bool,
+    pub base_url: Option<String>,
+    pub model: Option<String>,
+    pub checked_at: String,
+    /// Served from the probe cache rather than a fresh upstream request.
+    pub cached: bool,
+    pub latency_ms: Option<i64>,
+    /// `None` when the upstream model list could not be read.
+    pub model_available: Option<bool>,
+    pub model_input_limit: Option<u32>,
+    pub model_input_limit_source: Option<String>,
+    pub warnings: Vec<String>,
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct AiModelListResponse {
+    data: Vec<AiModelListItem>,
+}
+
+#[derive(Debug, Deserialize)]
+struct AiModelListItem {
+    id: String,
+}
+
+async fn fetch_ai_model_ids(state: &AppState, ai: &AiConfig) -> Result<Vec<String>> {
+    let url = ai.base_url.join("models").context("invalid AI_BASE_URL")?;
+    let resp = chat_provider(ai.provider)
+        .authorize(state.http.get(url), &ai.api_key)
+        .timeout(AI_HEALTH_PROBE_TIMEOUT)
+        .send()
+        .await
+        .context("AI models request failed")?;
+    let status = resp.status();
+    if !status.is_success() {
+        return Err(anyhow!("AI models request returned {status}"));
+    }
+    let body = resp
+        .json::<AiModelListResponse>()
+        .await
+        .context("AI models response is not a `data` model list")?;
+    Ok(body.data.into_iter().map(|item| item.id).collect())
+}
+
+async fn run_ai_health_probe(state: &AppState, ai: &AiConfig) -> AiHealthReport {
+    let started_at = Instant::now();
+    let listed = fetch_ai_model_ids(state, ai).await;
+    let latency_ms = i64::try_from(started_at.elapsed().as_millis()).unwrap_or(i64::MAX);
+    let (model_available, error) = match listed {
+        Ok(ids) => {
+            let available = ids.iter().any(|id| id.trim() == ai.model);
+            let error = (!available)
+                .then(|| format!("model {} is not listed by {}models", ai.model, ai.base_url));
+            (Some(available), error)
+        }
+        Err(err) => (None, Some(format!("{err:#}"))),
+    };
+    let (limit, limit_source) = resolve_model_input_limit_for_status(state, &ai.model).await;
+
+    AiHealthReport {
+        configured: true,
+        ok: error.is_none(),
+        base_url: Some(ai.base_url.to_string()),
+        model: Some(ai.model.clone()),
+        checked_at: Utc::now().to_rfc3339(),
+        cached: false,
+        latency_ms: Some(latency_ms),
+        model_available,
+        model_input_limit: Some(limit),
+        model_input_limit_source: Some(limit_source.to_owned()),
+        warnings: ai.base_url_warning().into_iter().collect(),
+        error,
+    }
+}
+
+/// Checks the configured upstream by listing `{base}/models`, which catches a
+/// wrong base URL, key, or model name without spending tokens.
+pub async fn probe_ai_health(state: &AppState) -> AiHealthReport {
+    let Some(ai) = state.config.ai.as_ref() else {
+        return AiHealthReport {
+            configured: false,
+            ok: false,
+            base_url: None,
+            model: None,
+            checked_at: Utc::now().to_rfc3339(),
+            cached: false,
+            latency_ms: None,
+            model_available: None,
+            model_input_limit: None,
+            model_input_limit_source: None,
+            warnings: Vec::new(),
+            error: Some("AI is not configured (AI_API_KEY is missing)".to_owned()),
+        };
+    };
+
+    let key = format!("{}|{}", ai.base_url, ai.model);
+    // Held across the probe so concurrent callers share one upstream request.
+    let mut cache = AI_HEALTH_CACHE.get_or_init(Default::default).lock().await;
+    if let Some((probed_at, report)) = cache.get(&key)
+        && probed_at.elapsed() < AI_HEALTH_CACHE_TTL
+    {
+        return AiHealthReport {
+            cached: true,
+            ..report.clone()
+        };
+    }
+    let report = run_ai_health_probe(state, ai).await;
+    cache.insert(key, (Instant::now(), report.clone()));
+    report
+}
+
+/// Logs AI configuration problems before the server starts serving; with
+/// `AI_STARTUP_PROBE_STRICT` a failed probe aborts startup instead.
+pub async fn check_ai_config_on_startup(state: &AppState) -> Result<()> {
+    let Some(ai) = state.config.ai.as_ref() else {
+        return Ok(());
+    };
+    if let Some(warning) = ai.base_url_warning() {
+        tracing::warn!(event = "config.ai", base_url = %ai.base_url, "{warning}");
+    }
+    if ai.startup_probe == AiStartupProbe::Off {
+        return Ok(());
+    }
+
+    let report = probe_ai_health(state).await;
+    let Some(error) = report.error else {
+        tracing::info!(
+            event = "upstream.call",
+            operation = "ai.startup_probe",
+            model = %ai.model,
+            latency_ms = report.latency_ms.unwrap_or_default(),
+            model_input_limit = report.model_input_limit.unwrap_or_default(),
+            "AI startup probe succeeded"
+        );
+        return Ok(());
+    };
+    if ai.startup_probe == AiStartupProbe::Strict {
+        return Err(anyhow!("AI startup probe failed: {error}"));
+    }
+    tracing::warn!(
+        event = "upstream.call",
+        operation = "ai.startup_probe",
+        error_kind = "probe_failed",
+        model = %ai.model,
+        base_url = %ai.base_url,
+        error = %error,
+        "AI startup probe failed; AI features will likely error until AI_BASE_URL/AI_MODEL are fixed"
+    );
+    Ok(())
+}
+
+pub(crate) fn resolve_model_input_limit_for_model(
+    provider: AiProvider,
+    model: &str,
+) -> (u32, &'static str) {
     if model.is_empty() {
         return (
             MODEL_LIMIT_UNKNOWN_FALLBACK,
@@ -719,7 +1176,7 @@ pub(crate) fn resolve_model_input_limit_for_model(model: &str) -> (u32, &'static
         );
     }
 
-    if let Some(limit) = lookup_model_limit_in_map(builtin_model_limits(), model) {
+    if let Some(limit) = lookup_model_limit_in_map(builtin_model_limits(), provider, model) {
         return (limit.max(1), MODEL_LIMIT_RESOLUTION_BUILTIN_CATALOG);
     }
 
@@ -747,12 +1204,13 @@ pub(crate) async fn resolve_model_input_limit_for_status(
         );
     }
 
+    let provider = configured_ai_provider(state);
     let (limit, source) = {
         let guard = model_limit_catalog().read().await;
-        if let Some(limit) = lookup_model_limit_in_map(&guard.synced_limits, model) {
+        if let Some(limit) = lookup_model_limit_in_map(&guard.synced_limits, provider, model) {
             (limit.max(1), MODEL_LIMIT_RESOLUTION_SYNCED_CATALOG)
         } else {
-            resolve_model_input_limit_for_model(model)
+            resolve_model_input_limit_for_model(provider, model)
         }
     };
     (limit, source)
@@ -804,12 +1262,15 @@ pub async fn select_model_for_new_calls(state: &AppState) -> SelectedLlmModel {
         );
     }
 
+    let provider = configured_ai_provider(state);
     let (model_input_limit, fallback_source) = {
         let guard = model_limit_catalog().read().await;
-        if let Some(limit) = lookup_model_limit_in_map(&guard.synced_limits, model.as_str()) {
+        if let Some(limit) =
+            lookup_model_limit_in_map(&guard.synced_limits, provider, model.as_str())
+        {
             (limit.max(1), MODEL_LIMIT_RESOLUTION_SYNCED_CATALOG)
         } else if let Some(limit) =
-            lookup_model_limit_in_map(builtin_model_limits(), model.as_str())
+            lookup_model_limit_in_map(builtin_model_limits(), provider, model.as_str())
         {
             (limit.max(1), MODEL_LIMIT_RESOLUTION_BUILTIN_CATALOG)
         } else {
@@ -833,8 +1294,29 @@ pub async fn resolve_model_input_limit_with_source(state: &AppState) -> (u32, &'
 
 pub async fn compute_input_budget_with_source(state: &AppState, max_tokens: u32) -> InputBudget {
     let selected = select_model_for_new_calls(state).await;
-    let model_limit = selected.model_input_limit;
-    let fallback_source = selected.fallback_source;
+    input_budget_for_model_limit(
+        selected.model_input_limit,
+        selected.fallback_source,
+        max_tokens,
+    )
+}
+
+/// Input budget for a specific model, e.g. a fallback about to be attempted
+/// in place of the routed one.
+pub(crate) async fn compute_input_budget_for_model(
+    state: &AppState,
+    model: &str,
+    max_tokens: u32,
+) -> InputBudget {
+    let (model_limit, fallback_source) = resolve_model_input_limit_for_status(state, model).await;
+    input_budget_for_model_limit(model_limit, fallback_source, max_tokens)
+}
+
+fn input_budget_for_model_limit(
+    model_limit: u32,
+    fallback_source: &'static str,
+    max_tokens: u32,
+) -> InputBudget {
     let output_reserve = max_tokens;
     let ratio_margin = (f64::from(model_limit) * MODEL_LIMIT_SAFETY_RATIO).ceil() as u32;
     let margin = ratio_margin.max(MODEL_LIMIT_SAFETY_MIN_TOKENS);
@@ -930,9 +1412,12 @@ pub fn spawn_model_catalog_sync_task(state: Arc<AppState>) -> tokio::task::Abort
     handle.abort_handle()
 }
 
+// Call rows (and their metrics) live as long as task history; the prompt and
+// response bodies are truncated much earlier by the maintenance prune job.
 async fn cleanup_expired_llm_calls(state: &AppState) -> Result<u64> {
-    let retention_seconds = i64::try_from(LLM_CALL_LOG_RETENTION.as_secs()).unwrap_or(i64::MAX);
-    let cutoff = (chrono::Utc::now() - chrono::Duration::seconds(retention_seconds)).to_rfc3339();
+    let retention_days = i64::try_from(state.config.task_retention_days)
+        .context("task retention days out of range")?;
+    let cutoff = (chrono::Utc::now() - chrono::Duration::days(retention_days)).to_rfc3339();
     let started_at = Instant::now();
     match state
         .sqlite_writer
@@ -1117,6 +1602,86 @@ struct SocialSummaryRendered {
 struct BuiltBriefContent {
     content_markdown: String,
     releases: Vec<ReleaseDigest>,
+    coverage: BriefCoverage,
+    sources: Vec<BriefSource>,
+    /// Releases the model returned bullets for; the rest use a release-notes
+    /// excerpt.
+    summarized_release_ids: HashSet<i64>,
+}
+
+/// What a brief snapshot actually looked at, so an empty-looking brief can be
+/// told apart from a window that really had no releases.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct BriefCoverage {
+    /// Unmuted, non-draft releases published inside the window.
+    pub releases_total: usize,
+    pub repos_covered: usize,
+    /// Window releases cut by the per-brief release limit.
+    #[serde(alias = "releases_skipped_budget")]
+    pub releases_skipped_limit: usize,
+    /// Releases the model returned bullets for, in brief order.
+    pub summarized_release_ids: Vec<String>,
+}
+
+impl BriefCoverage {
+    fn for_releases(
+        releases_total: usize,
+        releases: &[ReleaseDigest],
+        summarized_release_ids: &HashSet<i64>,
+    ) -> Self {
+        let repos_covered = releases
+            .iter()
+            .map(|release| release.full_name.as_str())
+            .collect::<HashSet<_>>()
+            .len();
+        Self {
+            releases_total: releases_total.max(releases.len()),
+            repos_covered,
+            releases_skipped_limit: releases_total.saturating_sub(releases.len()),
+            summarized_release_ids: releases
+                .iter()
+                .filter(|release| summarized_release_ids.contains(&release.release_id))
+                .map(|release| release.release_id.to_string())
+                .collect(),
+        }
+    }
+
+    pub fn from_json(raw: Option<&str>) -> Option<Self> {
+        raw.and_then(|value| serde_json::from_str(value).ok())
+    }
+}
+
+/// One release a brief section was built from, in render order. `section` is
+/// the repo heading the release sits under.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BriefSource {
+    pub section: String,
+    pub release_id: String,
+    pub title: String,
+    pub html_url: String,
+}
+
+impl BriefSource {
+    fn for_repos(repos: &[RepoRendered]) -> Vec<Self> {
+        repos
+            .iter()
+            .flat_map(|repo| {
+                repo.releases.iter().map(|release| Self {
+                    section: repo.full_name.clone(),
+                    release_id: release.release_id.to_string(),
+                    title: release.title.clone(),
+                    html_url: release.html_url.clone(),
+                })
+            })
+            .collect()
+    }
+
+    /// Snapshots written before sources were tracked read back as empty.
+    pub fn list_from_json(raw: Option<&str>) -> Vec<Self> {
+        raw.and_then(|value| serde_json::from_str(value).ok())
+            .unwrap_or_default()
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -1129,15 +1694,8 @@ pub struct StoredBrief {
     pub effective_local_boundary: String,
     pub content_markdown: String,
     pub release_ids: Vec<i64>,
-}
-
-#[derive(Debug, Serialize)]
-struct ChatCompletionsRequest<'a> {
-    model: &'a str,
-    messages: Vec<ChatMessage<'a>>,
-    temperature: f32,
-    max_tokens: u32,
-    stream: bool,
+    pub coverage: Option<BriefCoverage>,
+    pub sources: Vec<BriefSource>,
 }
 
 #[derive(Debug, Serialize)]
@@ -1146,57 +1704,12 @@ struct ChatMessage<'a> {
     content: &'a str,
 }
 
-#[derive(Debug, Deserialize)]
-struct ChatCompletionsResponse {
-    choices: Vec<Choice>,
-    usage: Option<ChatCompletionsUsage>,
-}
-
-#[derive(Debug, Deserialize)]
-struct Choice {
-    message: ChoiceMessage,
-}
-
-#[derive(Debug, Deserialize)]
-struct ChoiceMessage {
-    content: Option<String>,
-}
-
-#[derive(Debug, Deserialize)]
-struct ChatCompletionsStreamChunk {
-    choices: Vec<ChatCompletionsStreamChoice>,
-    usage: Option<ChatCompletionsUsage>,
-}
-
-#[derive(Debug, Deserialize)]
-struct ChatCompletionsStreamChoice {
-    delta: ChatCompletionsStreamDelta,
-}
-
-#[derive(Debug, Deserialize)]
-struct ChatCompletionsStreamDelta {
-    content: Option<String>,
-}
-
-#[derive(Debug, Deserialize)]
-struct ChatCompletionsUsage {
-    prompt_tokens: Option<u64>,
-    completion_tokens: Option<u64>,
-    total_tokens: Option<u64>,
-    prompt_tokens_details: Option<ChatPromptTokensDetails>,
-}
-
-#[derive(Debug, Deserialize)]
-struct ChatPromptTokensDetails {
-    cached_tokens: Option<u64>,
-}
-
-#[derive(Debug)]
-struct LlmTokenUsage {
-    input_tokens: Option<i64>,
-    output_tokens: Option<i64>,
-    cached_input_tokens: Option<i64>,
-    total_tokens: Option<i64>,
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct LlmTokenUsage {
+    pub input_tokens: Option<i64>,
+    pub output_tokens: Option<i64>,
+    pub cached_input_tokens: Option<i64>,
+    pub total_tokens: Option<i64>,
 }
 
 #[derive(Debug)]
@@ -1207,6 +1720,24 @@ struct ChatCompletionOutput {
     usage: LlmTokenUsage,
 }
 
+/// Incremental output of [`chat_completion_stream`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ChatCompletionDelta {
+    /// A content fragment appended to the reply of the current attempt.
+    Content(String),
+    /// The previous attempt failed and is being retried; fragments received
+    /// so far should be discarded.
+    Restart,
+}
+
+/// Final record of a streamed chat completion, as written to `llm_calls`.
+#[derive(Debug)]
+pub struct ChatCompletionStreamOutput {
+    pub content: String,
+    pub first_token_wait_ms: Option<i64>,
+    pub usage: LlmTokenUsage,
+}
+
 #[derive(Debug, Deserialize)]
 struct ProjectSummaryPayload {
     items: Vec<ProjectSummaryItem>,
@@ -1230,24 +1761,6 @@ fn safe_i64_from_u64(value: Option<u64>) -> Option<i64> {
     value.and_then(|raw| i64::try_from(raw).ok())
 }
 
-fn llm_token_usage_from_chat_usage(usage: Option<ChatCompletionsUsage>) -> LlmTokenUsage {
-    usage
-        .map(|raw| LlmTokenUsage {
-            input_tokens: safe_i64_from_u64(raw.prompt_tokens),
-            output_tokens: safe_i64_from_u64(raw.completion_tokens),
-            cached_input_tokens: raw
-                .prompt_tokens_details
-                .and_then(|details| safe_i64_from_u64(details.cached_tokens)),
-            total_tokens: safe_i64_from_u64(raw.total_tokens),
-        })
-        .unwrap_or(LlmTokenUsage {
-            input_tokens: None,
-            output_tokens: None,
-            cached_input_tokens: None,
-            total_tokens: None,
-        })
-}
-
 fn build_llm_messages_json(messages: &[ChatMessage<'_>]) -> Option<String> {
     serde_json::to_string(messages).ok()
 }
@@ -1315,6 +1828,36 @@ fn extract_plain_text_error_message(body: &[u8]) -> Option<String> {
     (!trimmed.is_empty()).then_some("upstream_plain_text_error".to_owned())
 }
 
+/// Non-success HTTP answer from the chat endpoint. `code` is the provider's
+/// error identifier (`error.code`, or `error.type` for Anthropic) when the
+/// body carries one.
+#[derive(Debug, thiserror::Error)]
+#[error("AI returned {status}: {message}")]
+pub struct AiError {
+    pub provider: AiProvider,
+    pub status: reqwest::StatusCode,
+    pub code: Option<String>,
+    pub message: String,
+}
+
+impl AiError {
+    pub fn find(err: &anyhow::Error) -> Option<&Self> {
+        err.chain().find_map(|cause| cause.downcast_ref::<Self>())
+    }
+
+    pub fn is_non_retryable(&self) -> bool {
+        chat_provider(self.provider).rejection_is_final(
+            self.status,
+            self.code.as_deref(),
+            &self.message,
+        ) || ai_response_message_is_non_retryable(&self.message)
+            || self
+                .code
+                .as_deref()
+                .is_some_and(ai_response_message_is_non_retryable)
+    }
+}
+
 fn ai_response_message_is_non_retryable(message: &str) -> bool {
     let msg = message.to_ascii_lowercase();
     msg.contains("invalid_model_error")
@@ -1328,269 +1871,295 @@ fn ai_response_message_is_non_retryable(message: &str) -> bool {
         || (msg.contains("forbidden") && msg.contains("upstream"))
 }
 
-fn parse_chat_completion_sse_output(
-    body: &[u8],
-    content_type: &str,
-    first_token_wait_ms: Option<i64>,
-) -> std::result::Result<ChatCompletionOutput, ChatCompletionAttemptError> {
-    fn build_assistant_output(
-        content: String,
-        usage: Option<ChatCompletionsUsage>,
-        first_token_wait_ms: Option<i64>,
-    ) -> ChatCompletionOutput {
-        let output_messages_json = serde_json::to_string(&[ChatMessage {
-            role: "assistant",
-            content: content.as_str(),
-        }])
-        .unwrap_or_else(|_| "[]".to_owned());
-
-        ChatCompletionOutput {
-            content,
-            output_messages_json,
-            first_token_wait_ms,
-            usage: llm_token_usage_from_chat_usage(usage),
+/// Incremental decoder for `text/event-stream` chat completion bodies in
+/// the wire format of `provider`. Bytes can be fed in arbitrary chunks; only complete
+/// lines are interpreted, so multi-byte characters split across network
+/// reads are handled.
+#[derive(Debug, Default)]
+struct ChatCompletionSseParser {
+    provider: AiProvider,
+    pending: Vec<u8>,
+    event_name: Option<String>,
+    event_data: Vec<String>,
+    content: String,
+    usage: Option<LlmTokenUsage>,
+    saw_done: bool,
+}
+
+impl ChatCompletionSseParser {
+    fn new(provider: AiProvider) -> Self {
+        Self {
+            provider,
+            ..Self::default()
         }
     }
 
-    fn parse_event_data(
-        data: &str,
-        content: &mut String,
-        usage: &mut Option<ChatCompletionsUsage>,
-        saw_done: &mut bool,
-    ) -> Result<()> {
-        let trimmed = data.trim();
-        if trimmed.is_empty() {
-            return Ok(());
+    /// Feeds a chunk of the response body and returns the content fragments
+    /// completed by it, in arrival order.
+    fn push(&mut self, bytes: &[u8]) -> Result<Vec<String>> {
+        self.pending.extend_from_slice(bytes);
+        let mut fragments = Vec::new();
+        while let Some(newline) = self.pending.iter().position(|byte| *byte == b'\n') {
+            let line = self.pending.drain(..=newline).collect::<Vec<_>>();
+            let line =
+                std::str::from_utf8(&line).context("chat completion SSE line is not UTF-8")?;
+            self.process_line(line, &mut fragments)?;
         }
-        if trimmed == "[DONE]" {
-            *saw_done = true;
-            return Ok(());
+        Ok(fragments)
+    }
+
+    /// Flushes a trailing unterminated line and the last pending event.
+    fn finish(&mut self) -> Result<Vec<String>> {
+        let mut fragments = Vec::new();
+        if !self.pending.is_empty() {
+            let line = std::mem::take(&mut self.pending);
+            let line =
+                std::str::from_utf8(&line).context("chat completion SSE line is not UTF-8")?;
+            self.process_line(line, &mut fragments)?;
         }
+        self.flush_event(&mut fragments)?;
+        Ok(fragments)
+    }
 
-        let chunk: ChatCompletionsStreamChunk =
-            serde_json::from_str(trimmed).context("decode chat completion SSE chunk failed")?;
-        for choice in chunk.choices {
-            if let Some(fragment) = choice.delta.content {
-                content.push_str(&fragment);
-            }
+    fn process_line(&mut self, line: &str, fragments: &mut Vec<String>) -> Result<()> {
+        let trimmed = line.trim_end();
+        if trimmed.is_empty() {
+            return self.flush_event(fragments);
+        }
+        if let Some(name) = trimmed.strip_prefix("event:") {
+            self.event_name = Some(name.trim().to_owned());
+            return Ok(());
         }
-        if chunk.usage.is_some() {
-            *usage = chunk.usage;
+        if let Some(data) = trimmed.strip_prefix("data:") {
+            self.event_data.push(data.trim().to_owned());
         }
         Ok(())
     }
 
-    let raw = std::str::from_utf8(body).map_err(|err| ChatCompletionAttemptError {
-        err: anyhow!(
-            "AI returned invalid SSE success response (content-type {content_type}): {err}"
-        ),
-        retryable: false,
-        retry_after: None,
-        first_token_wait_ms,
-    })?;
-
-    let mut event_name: Option<String> = None;
-    let mut event_data = Vec::new();
-    let mut content = String::new();
-    let mut usage = None;
-    let mut saw_done = false;
-
-    let mut flush_event =
-        |event_name: &mut Option<String>, event_data: &mut Vec<String>| -> Result<()> {
-            if event_data.is_empty() {
-                event_name.take();
-                return Ok(());
-            }
-
-            let payload = event_data.join("\n");
-            let is_error_event = event_name
-                .as_deref()
-                .map(|value| value.eq_ignore_ascii_case("error"))
-                .unwrap_or(false);
-            event_name.take();
-            event_data.clear();
-
-            if is_error_event {
-                anyhow::bail!(payload.trim().to_owned());
-            }
+    fn flush_event(&mut self, fragments: &mut Vec<String>) -> Result<()> {
+        let is_error_event = self
+            .event_name
+            .take()
+            .map(|value| value.eq_ignore_ascii_case("error"))
+            .unwrap_or(false);
+        if self.event_data.is_empty() {
+            return Ok(());
+        }
 
-            parse_event_data(payload.as_str(), &mut content, &mut usage, &mut saw_done)
-        };
+        let payload = self.event_data.join("\n");
+        self.event_data.clear();
+        if is_error_event {
+            anyhow::bail!(payload.trim().to_owned());
+        }
 
-    for line in raw.lines() {
-        let trimmed = line.trim_end();
+        let trimmed = payload.trim();
         if trimmed.is_empty() {
-            flush_event(&mut event_name, &mut event_data).map_err(|err| {
-                let message = err.to_string();
-                ChatCompletionAttemptError {
-                    err: anyhow!(
-                        "AI returned event-stream success response error (content-type {content_type}): {message}"
-                    ),
-                    retryable: !ai_response_message_is_non_retryable(&message),
-                    retry_after: None,
-                    first_token_wait_ms,
-                }
-            })?;
-            continue;
+            return Ok(());
         }
-
-        if let Some(name) = trimmed.strip_prefix("event:") {
-            event_name = Some(name.trim().to_owned());
-            continue;
+        if trimmed == "[DONE]" {
+            self.saw_done = true;
+            return Ok(());
         }
 
-        if let Some(data) = trimmed.strip_prefix("data:") {
-            event_data.push(data.trim().to_owned());
+        let chunk = chat_provider(self.provider).parse_stream_chunk(trimmed)?;
+        for fragment in chunk.fragments {
+            if !fragment.is_empty() {
+                self.content.push_str(&fragment);
+                fragments.push(fragment);
+            }
         }
+        if let Some(usage) = chunk.usage {
+            self.usage = Some(merge_usage(self.usage.take(), usage));
+        }
+        self.saw_done |= chunk.done;
+        Ok(())
     }
 
-    flush_event(&mut event_name, &mut event_data).map_err(|err| {
-        let message = err.to_string();
-        ChatCompletionAttemptError {
+    fn into_output(
+        self,
+        body: &[u8],
+        content_type: &str,
+        first_token_wait_ms: Option<i64>,
+    ) -> std::result::Result<ChatCompletionOutput, ChatCompletionAttemptError> {
+        let content = self.content.trim().to_owned();
+        if !content.is_empty() {
+            let output_messages_json = serde_json::to_string(&[ChatMessage {
+                role: "assistant",
+                content: content.as_str(),
+            }])
+            .unwrap_or_else(|_| "[]".to_owned());
+            return Ok(ChatCompletionOutput {
+                content,
+                output_messages_json,
+                first_token_wait_ms,
+                usage: with_derived_total(self.usage.unwrap_or_default()),
+            });
+        }
+
+        let message = if self.saw_done {
+            "AI event-stream response completed without assistant content".to_owned()
+        } else {
+            extract_error_message(body)
+                .unwrap_or_else(|| "AI event-stream response ended before [DONE]".to_owned())
+        };
+        Err(ChatCompletionAttemptError {
             err: anyhow!(
-                "AI returned event-stream success response error (content-type {content_type}): {message}"
+                "AI returned invalid event-stream success response (content-type {content_type}): {message}"
             ),
             retryable: !ai_response_message_is_non_retryable(&message),
             retry_after: None,
             first_token_wait_ms,
-        }
-    })?;
-
-    let content = content.trim().to_owned();
-    if !content.is_empty() {
-        return Ok(build_assistant_output(content, usage, first_token_wait_ms));
+        })
     }
+}
 
-    let message = if saw_done {
-        "AI event-stream response completed without assistant content".to_owned()
-    } else {
-        extract_error_message(body)
-            .unwrap_or_else(|| "AI event-stream response ended before [DONE]".to_owned())
-    };
-    Err(ChatCompletionAttemptError {
+fn sse_event_attempt_error(
+    err: anyhow::Error,
+    content_type: &str,
+    first_token_wait_ms: Option<i64>,
+) -> ChatCompletionAttemptError {
+    let message = err.to_string();
+    ChatCompletionAttemptError {
         err: anyhow!(
-            "AI returned invalid event-stream success response (content-type {content_type}): {message}"
+            "AI returned event-stream success response error (content-type {content_type}): {message}"
         ),
         retryable: !ai_response_message_is_non_retryable(&message),
         retry_after: None,
         first_token_wait_ms,
-    })
+    }
 }
 
-pub fn sha256_hex(input: &str) -> String {
-    let mut hasher = Sha256::new();
-    hasher.update(input.as_bytes());
-    let digest = hasher.finalize();
-    let mut out = String::with_capacity(digest.len() * 2);
-    for b in digest {
-        use std::fmt::Write as _;
-        write!(&mut out, "{:02x}", b).expect("sha256 hex");
-    }
-    out
+fn parse_chat_completion_sse_output(
+    provider: AiProvider,
+    body: &[u8],
+    content_type: &str,
+    first_token_wait_ms: Option<i64>,
+) -> std::result::Result<ChatCompletionOutput, ChatCompletionAttemptError> {
+    std::str::from_utf8(body).map_err(|err| ChatCompletionAttemptError {
+        err: anyhow!(
+            "AI returned invalid SSE success response (content-type {content_type}): {err}"
+        ),
+        retryable: false,
+        retry_after: None,
+        first_token_wait_ms,
+    })?;
+
+    let mut parser = ChatCompletionSseParser::new(provider);
+    parser
+        .push(body)
+        .and_then(|_| parser.finish())
+        .map_err(|err| sse_event_attempt_error(err, content_type, first_token_wait_ms))?;
+    parser.into_output(body, content_type, first_token_wait_ms)
 }
 
-#[allow(dead_code)]
-fn compute_daily_window_naive(
-    now_local: NaiveDateTime,
-    at: NaiveTime,
-) -> (NaiveDateTime, NaiveDateTime, NaiveDate) {
-    let today = now_local.date();
-    let mut end = NaiveDateTime::new(today, at);
-    // Use the latest boundary <= now.
-    if now_local < end {
-        end -= chrono::Duration::days(1);
-    }
-    let start = end - chrono::Duration::hours(24);
-    (start, end, end.date())
+/// Identifies one translation of one piece of content. Two requests with the
+/// same key would produce the same cache row, so only one of them needs to
+/// reach the model.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct TranslationFlightKey {
+    pub user_id: String,
+    pub entity_type: &'static str,
+    pub entity_id: String,
+    pub lang: String,
+    pub source_hash: String,
 }
 
-#[allow(dead_code)]
-fn local_from_naive(naive: NaiveDateTime) -> chrono::DateTime<Local> {
-    match Local.from_local_datetime(&naive) {
-        LocalResult::Single(dt) => dt,
-        // If DST makes the time ambiguous, prefer the earliest.
-        LocalResult::Ambiguous(dt, _) => dt,
-        // This shouldn't happen for typical daily times like 08:00. Fallback to a
-        // non-panicking conversion for robustness.
-        LocalResult::None => {
-            chrono::DateTime::<chrono::Utc>::from_naive_utc_and_offset(naive, chrono::Utc)
-                .with_timezone(&Local)
-        }
-    }
+type TranslationFlightResult = Option<Arc<dyn std::any::Any + Send + Sync>>;
+
+/// In-process singleflight for translations: while one caller translates a
+/// key, concurrent callers with the same key wait for its result instead of
+/// issuing their own model calls.
+#[derive(Clone, Default)]
+pub struct TranslationSingleflight {
+    inflight: Arc<
+        Mutex<HashMap<TranslationFlightKey, tokio::sync::watch::Receiver<TranslationFlightResult>>>,
+    >,
 }
 
-#[allow(dead_code)]
-pub struct DailyWindow {
-    pub key_date: NaiveDate,
-    pub start_local: chrono::DateTime<Local>,
-    pub end_local: chrono::DateTime<Local>,
-    pub start_utc: chrono::DateTime<chrono::Utc>,
-    pub end_utc: chrono::DateTime<chrono::Utc>,
+enum TranslationFlightRole {
+    Lead(tokio::sync::watch::Sender<TranslationFlightResult>),
+    Wait(tokio::sync::watch::Receiver<TranslationFlightResult>),
 }
 
-#[allow(dead_code)]
-pub fn compute_window_for_key_date(
-    key_date: NaiveDate,
-    at: NaiveTime,
-) -> (chrono::DateTime<Local>, chrono::DateTime<Local>) {
-    let end_naive = NaiveDateTime::new(key_date, at);
-    let start_naive = end_naive - chrono::Duration::hours(24);
-    (local_from_naive(start_naive), local_from_naive(end_naive))
+/// Clears the key when the leading caller finishes, fails or is dropped.
+struct TranslationFlightGuard<'a> {
+    flights: &'a TranslationSingleflight,
+    key: &'a TranslationFlightKey,
 }
 
-#[allow(dead_code)]
-fn daily_window_for_key_date(key_date: NaiveDate, at: NaiveTime) -> DailyWindow {
-    let (start_local, end_local) = compute_window_for_key_date(key_date, at);
-    DailyWindow {
-        key_date,
-        start_utc: start_local.with_timezone(&chrono::Utc),
-        end_utc: end_local.with_timezone(&chrono::Utc),
-        start_local,
-        end_local,
+impl Drop for TranslationFlightGuard<'_> {
+    fn drop(&mut self) {
+        self.flights
+            .inflight
+            .lock()
+            .expect("translation singleflight poisoned")
+            .remove(self.key);
     }
 }
 
-#[allow(dead_code)]
-pub fn compute_daily_window(at: Option<NaiveTime>, now: chrono::DateTime<Local>) -> DailyWindow {
-    let now_naive = now.naive_local();
-    let at = at.or_else(|| NaiveTime::from_hms_opt(8, 0, 0));
-    if let Some(at) = at {
-        let (start_naive, end_naive, key_date) = compute_daily_window_naive(now_naive, at);
-        let start_local = local_from_naive(start_naive);
-        let end_local = local_from_naive(end_naive);
-        return DailyWindow {
-            key_date,
-            start_utc: start_local.with_timezone(&chrono::Utc),
-            end_utc: end_local.with_timezone(&chrono::Utc),
-            start_local,
-            end_local,
-        };
-    }
+impl TranslationSingleflight {
+    /// Runs `translate` unless the same key is already in flight, in which
+    /// case the leader's result (including an error) is shared. A leader that
+    /// is dropped before finishing hands the key to the next waiter.
+    pub async fn run<T, F, Fut>(&self, key: TranslationFlightKey, translate: F) -> T
+    where
+        T: Clone + Send + Sync + 'static,
+        F: FnOnce() -> Fut,
+        Fut: Future<Output = T>,
+    {
+        let mut translate = Some(translate);
+        loop {
+            let role = {
+                let mut inflight = self
+                    .inflight
+                    .lock()
+                    .expect("translation singleflight poisoned");
+                match inflight.get(&key) {
+                    Some(receiver) => TranslationFlightRole::Wait(receiver.clone()),
+                    None => {
+                        let (sender, receiver) = tokio::sync::watch::channel(None);
+                        inflight.insert(key.clone(), receiver);
+                        TranslationFlightRole::Lead(sender)
+                    }
+                }
+            };
 
-    let end_local = now;
-    let start_local = end_local - chrono::Duration::hours(24);
-    DailyWindow {
-        key_date: end_local.date_naive(),
-        start_utc: start_local.with_timezone(&chrono::Utc),
-        end_utc: end_local.with_timezone(&chrono::Utc),
-        start_local,
-        end_local,
+            match role {
+                TranslationFlightRole::Lead(sender) => {
+                    let _guard = TranslationFlightGuard {
+                        flights: self,
+                        key: &key,
+                    };
+                    let translate = translate.take().expect("singleflight leads once");
+                    let out = translate().await;
+                    sender.send_replace(Some(Arc::new(out.clone())));
+                    return out;
+                }
+                TranslationFlightRole::Wait(mut receiver) => {
+                    let shared = match receiver.wait_for(Option::is_some).await {
+                        Ok(value) => value.clone(),
+                        // The leader went away without a result; try again.
+                        Err(_) => continue,
+                    };
+                    if let Some(out) = shared.and_then(|value| value.downcast_ref::<T>().cloned()) {
+                        return out;
+                    }
+                }
+            }
+        }
     }
 }
 
-#[cfg(test)]
-pub fn recent_key_dates(
-    at: NaiveTime,
-    now: chrono::DateTime<Local>,
-    days: usize,
-) -> Vec<NaiveDate> {
-    if days == 0 {
-        return Vec::new();
+pub fn sha256_hex(input: &str) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(input.as_bytes());
+    let digest = hasher.finalize();
+    let mut out = String::with_capacity(digest.len() * 2);
+    for b in digest {
+        use std::fmt::Write as _;
+        write!(&mut out, "{:02x}", b).expect("sha256 hex");
     }
-    let current = compute_daily_window(Some(at), now).key_date;
-    (0..days)
-        .map(|offset| current - chrono::Duration::days(offset as i64))
-        .collect()
+    out
 }
 
 #[derive(Debug)]
@@ -1763,6 +2332,7 @@ struct LlmCallLogRecord {
     parent_task_id: Option<String>,
     parent_task_type: Option<String>,
     parent_translation_batch_id: Option<String>,
+    request_id: Option<String>,
 }
 
 fn build_llm_call_log_record() -> LlmCallLogRecord {
@@ -1781,7 +2351,69 @@ fn build_llm_call_log_record() -> LlmCallLogRecord {
         parent_translation_batch_id: context
             .as_ref()
             .and_then(|ctx| ctx.parent_translation_batch_id.clone()),
+        request_id: crate::observability::current_request_id(),
+    }
+}
+
+pub const AI_BUDGET_EXCEEDED_ERROR: &str = "ai_budget_exceeded";
+
+/// A call refused because its requesting user already spent today's
+/// `AI_DAILY_TOKEN_BUDGET_PER_USER`.
+#[derive(Debug, thiserror::Error)]
+#[error("{AI_BUDGET_EXCEEDED_ERROR}: daily token budget exhausted ({used}/{budget})")]
+pub struct AiBudgetExceeded {
+    pub used: i64,
+    pub budget: i64,
+}
+
+impl AiBudgetExceeded {
+    pub fn find(err: &anyhow::Error) -> Option<&Self> {
+        err.chain().find_map(|cause| cause.downcast_ref::<Self>())
+    }
+}
+
+pub fn is_ai_budget_exceeded_error(err: &anyhow::Error) -> bool {
+    AiBudgetExceeded::find(err).is_some()
+}
+
+/// Sums `total_tokens` recorded for `user_id` on the given UTC day.
+pub(crate) async fn load_user_daily_total_tokens(
+    pool: &sqlx::SqlitePool,
+    user_id: &str,
+    day: NaiveDate,
+) -> Result<i64> {
+    let next_day = day.succ_opt().unwrap_or(day);
+    sqlx::query_scalar::<_, i64>(
+        r#"
+        SELECT COALESCE(SUM(total_tokens), 0)
+        FROM llm_calls
+        WHERE requested_by = ?
+          AND created_at >= ?
+          AND created_at < ?
+        "#,
+    )
+    .bind(user_id)
+    .bind(day.format("%Y-%m-%d").to_string())
+    .bind(next_day.format("%Y-%m-%d").to_string())
+    .fetch_one(pool)
+    .await
+    .context("failed to load user daily llm token usage")
+}
+
+/// Rejects new calls attributed to a user once today's recorded usage has
+/// reached `AI_DAILY_TOKEN_BUDGET_PER_USER`. Calls without a requesting user
+/// (system schedulers, shared batches) are never limited.
+async fn ensure_ai_budget_available(state: &AppState, requested_by: Option<&str>) -> Result<()> {
+    let (Some(budget), Some(user_id)) = (state.config.ai_daily_token_budget_per_user, requested_by)
+    else {
+        return Ok(());
+    };
+    let budget = i64::try_from(budget).unwrap_or(i64::MAX);
+    let used = load_user_daily_total_tokens(&state.pool, user_id, Utc::now().date_naive()).await?;
+    if used >= budget {
+        return Err(AiBudgetExceeded { used, budget }.into());
     }
+    Ok(())
 }
 
 async fn insert_llm_call(
@@ -1807,12 +2439,13 @@ async fn insert_llm_call(
                   parent_task_id,
                   parent_task_type,
                   parent_translation_batch_id,
+                  request_id,
                   max_tokens,
                   prompt_text,
                   input_messages_json,
                   created_at,
                   updated_at
-                ) VALUES (?, 'queued', ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+                ) VALUES (?, 'queued', ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
                 "#,
             )
             .bind(log.id.as_str())
@@ -1822,6 +2455,7 @@ async fn insert_llm_call(
             .bind(log.parent_task_id.as_deref())
             .bind(log.parent_task_type.as_deref())
             .bind(log.parent_translation_batch_id.as_deref())
+            .bind(log.request_id.as_deref())
             .bind(i64::from(max_tokens))
             .bind(prompt_text)
             .bind(input_messages_json)
@@ -2057,6 +2691,7 @@ async fn finalize_llm_call(
             Ok::<_, anyhow::Error>(())
         })
         .await?;
+    observability::record_llm_call(update.status, update.input_tokens, update.output_tokens);
     append_llm_call_event(
         state,
         call_id,
@@ -2084,6 +2719,52 @@ async fn finalize_llm_call(
     Ok(())
 }
 
+/// Drops a waiting call from the scheduler queue and fails its `llm_calls`
+/// row. Returns `false` when the call is not (or no longer) queued.
+pub(crate) async fn cancel_queued_llm_call(state: &AppState, call_id: &str) -> Result<bool> {
+    if !state.llm_scheduler.drop_queued_call(call_id) {
+        return Ok(false);
+    }
+    let now = chrono::Utc::now().to_rfc3339();
+    state
+        .sqlite_writer
+        .write("llm_call_cancel", |_| async {
+            sqlx::query(
+                r#"
+                UPDATE llm_calls
+                SET status = 'failed',
+                    error_text = ?,
+                    finished_at = ?,
+                    runtime_owner_id = NULL,
+                    lease_heartbeat_at = NULL,
+                    updated_at = ?
+                WHERE id = ?
+                "#,
+            )
+            .bind(LLM_CALL_CANCELLED_BY_ADMIN)
+            .bind(now.as_str())
+            .bind(now.as_str())
+            .bind(call_id)
+            .execute(&state.pool)
+            .await
+            .context("cancel llm_call failed")?;
+            Ok::<_, anyhow::Error>(())
+        })
+        .await?;
+    state.llm_scheduler.clear_admin_override(call_id).await;
+    observability::record_llm_call("failed", None, None);
+    append_llm_call_event(
+        state,
+        call_id,
+        "llm.failed",
+        "failed",
+        serde_json::json!({ "error_text_preview": LLM_CALL_CANCELLED_BY_ADMIN }),
+    )
+    .await
+    .context("append llm_call cancelled event failed")?;
+    Ok(true)
+}
+
 async fn reconcile_admin_override_after_persist(
     state: &AppState,
     call_id: &str,
@@ -2128,10 +2809,12 @@ async fn chat_completion_once(
     system: &str,
     user: &str,
     max_tokens: u32,
+    deltas: Option<&mpsc::UnboundedSender<ChatCompletionDelta>>,
 ) -> std::result::Result<ChatCompletionOutput, ChatCompletionAttemptError> {
+    let provider = chat_provider(ai.provider);
     let url = ai
         .base_url
-        .join("chat/completions")
+        .join(provider.endpoint())
         .context("invalid AI_BASE_URL")
         .map_err(|err| ChatCompletionAttemptError {
             err,
@@ -2140,28 +2823,17 @@ async fn chat_completion_once(
             first_token_wait_ms: None,
         })?;
 
-    let req = ChatCompletionsRequest {
+    let req = provider.build_request(&ChatRequest {
         model: &ai.model,
-        messages: vec![
-            ChatMessage {
-                role: "system",
-                content: system,
-            },
-            ChatMessage {
-                role: "user",
-                content: user,
-            },
-        ],
-        temperature: 0.2,
+        system,
+        user,
         max_tokens,
-        stream: false,
-    };
+        stream: deltas.is_some(),
+    });
 
     let response_wait_started_at = Instant::now();
-    let resp = state
-        .http
-        .post(url)
-        .bearer_auth(&ai.api_key)
+    let resp = provider
+        .authorize(state.http.post(url), &ai.api_key)
         .json(&req)
         .send()
         .await
@@ -2195,6 +2867,21 @@ async fn chat_completion_once(
         .and_then(|value| value.to_str().ok())
         .map(str::to_owned)
         .unwrap_or_default();
+    if let Some(deltas) = deltas
+        && status.is_success()
+        && content_type
+            .to_ascii_lowercase()
+            .contains("text/event-stream")
+    {
+        return read_chat_completion_event_stream(
+            ai.provider,
+            resp,
+            content_type.as_str(),
+            response_wait_started_at,
+            deltas,
+        )
+        .await;
+    }
     let body = resp
         .bytes()
         .await
@@ -2208,8 +2895,14 @@ async fn chat_completion_once(
     if !status.is_success() {
         let msg = extract_error_message(&body).unwrap_or_else(|| "upstream_error".to_owned());
         return Err(ChatCompletionAttemptError {
-            err: anyhow!("AI returned {status}: {msg}"),
             retryable: is_retryable_status(status) && !ai_response_message_is_non_retryable(&msg),
+            err: AiError {
+                provider: ai.provider,
+                status,
+                code: provider.error_code(&body),
+                message: msg,
+            }
+            .into(),
             retry_after,
             first_token_wait_ms,
         });
@@ -2217,7 +2910,12 @@ async fn chat_completion_once(
 
     let content_type_lower = content_type.to_ascii_lowercase();
     if content_type_lower.contains("text/event-stream") {
-        return parse_chat_completion_sse_output(&body, content_type.as_str(), first_token_wait_ms);
+        return parse_chat_completion_sse_output(
+            ai.provider,
+            &body,
+            content_type.as_str(),
+            first_token_wait_ms,
+        );
     }
     if !content_type_lower.is_empty()
         && !content_type_lower.contains("application/json")
@@ -2234,8 +2932,9 @@ async fn chat_completion_once(
         });
     }
 
-    let resp: ChatCompletionsResponse =
-        serde_json::from_slice(&body).map_err(|err| ChatCompletionAttemptError {
+    let resp = provider
+        .parse_response(&body)
+        .map_err(|err| ChatCompletionAttemptError {
             err: anyhow!(
                 "AI response json decode failed: {}",
                 extract_error_message(&body)
@@ -2248,12 +2947,8 @@ async fn chat_completion_once(
             first_token_wait_ms,
         })?;
 
-    let ChatCompletionsResponse { choices, usage } = resp;
-
-    let content = choices
-        .into_iter()
-        .next()
-        .and_then(|c| c.message.content)
+    let content = resp
+        .content
         .map(|s| s.trim().to_owned())
         .filter(|s| !s.is_empty())
         .ok_or_else(|| ChatCompletionAttemptError {
@@ -2268,32 +2963,116 @@ async fn chat_completion_once(
         content: content.as_str(),
     }])
     .unwrap_or_else(|_| "[]".to_owned());
-    let usage = llm_token_usage_from_chat_usage(usage);
 
     Ok(ChatCompletionOutput {
         content,
         output_messages_json,
         first_token_wait_ms,
-        usage,
+        usage: resp.usage,
     })
 }
 
+/// Reads a streamed chat completion chunk by chunk, forwarding content
+/// fragments as they arrive. `first_token_wait_ms` is measured up to the first
+/// non-empty fragment rather than the response headers.
+async fn read_chat_completion_event_stream(
+    provider: AiProvider,
+    mut resp: reqwest::Response,
+    content_type: &str,
+    response_wait_started_at: Instant,
+    deltas: &mpsc::UnboundedSender<ChatCompletionDelta>,
+) -> std::result::Result<ChatCompletionOutput, ChatCompletionAttemptError> {
+    let mut parser = ChatCompletionSseParser::new(provider);
+    let mut first_token_wait_ms = None;
+    let forward = |fragments: Vec<String>, first_token_wait_ms: &mut Option<i64>| {
+        for fragment in fragments {
+            first_token_wait_ms.get_or_insert_with(|| {
+                i64::try_from(response_wait_started_at.elapsed().as_millis()).unwrap_or(i64::MAX)
+            });
+            // The consumer may stop listening; the call still runs to completion.
+            let _ = deltas.send(ChatCompletionDelta::Content(fragment));
+        }
+    };
+
+    loop {
+        let chunk = resp
+            .chunk()
+            .await
+            .map_err(|err| ChatCompletionAttemptError {
+                err: anyhow!("AI read response failed: {err}"),
+                retryable: true,
+                retry_after: None,
+                first_token_wait_ms,
+            })?;
+        let Some(chunk) = chunk else {
+            break;
+        };
+        let fragments = parser
+            .push(&chunk)
+            .map_err(|err| sse_event_attempt_error(err, content_type, first_token_wait_ms))?;
+        forward(fragments, &mut first_token_wait_ms);
+    }
+    let fragments = parser
+        .finish()
+        .map_err(|err| sse_event_attempt_error(err, content_type, first_token_wait_ms))?;
+    forward(fragments, &mut first_token_wait_ms);
+
+    parser.into_output(&[], content_type, first_token_wait_ms)
+}
+
 pub async fn chat_completion(
     state: &AppState,
     system: &str,
     user: &str,
     max_tokens: u32,
 ) -> Result<String> {
+    run_chat_completion(state, system, user, max_tokens, None)
+        .await
+        .map(|output| output.content)
+}
+
+/// Streaming variant of [`chat_completion`]: content fragments are sent to
+/// `deltas` as the provider produces them, and the returned record carries
+/// the full reply together with token usage. Retries, scheduling and
+/// `llm_calls` accounting are shared with the non-streaming path.
+pub async fn chat_completion_stream(
+    state: &AppState,
+    system: &str,
+    user: &str,
+    max_tokens: u32,
+    deltas: mpsc::UnboundedSender<ChatCompletionDelta>,
+) -> Result<ChatCompletionStreamOutput> {
+    run_chat_completion(state, system, user, max_tokens, Some(&deltas))
+        .await
+        .map(|output| ChatCompletionStreamOutput {
+            content: output.content,
+            first_token_wait_ms: output.first_token_wait_ms,
+            usage: output.usage,
+        })
+}
+
+async fn run_chat_completion(
+    state: &AppState,
+    system: &str,
+    user: &str,
+    max_tokens: u32,
+    deltas: Option<&mpsc::UnboundedSender<ChatCompletionDelta>>,
+) -> Result<ChatCompletionOutput> {
     let Some(base_ai) = state.config.ai.clone() else {
         return Err(anyhow!("AI is not configured (AI_API_KEY is missing)"));
     };
+    if jobs::current_task_cancel_requested(state).await {
+        return Err(jobs::TaskCanceled.into());
+    }
+    let log_record = build_llm_call_log_record();
+    ensure_ai_budget_available(state, log_record.requested_by.as_deref()).await?;
+    let mut log_record = Some(log_record);
     let selected_model = select_model_for_new_calls(state).await;
     let mut ai = base_ai;
     if !selected_model.model.trim().is_empty() {
         ai.model = selected_model.model.clone();
     }
 
-    let log_record = build_llm_call_log_record();
     let prompt_text = format!("system:\n{system}\n\nuser:\n{user}");
     let input_messages = vec![
         ChatMessage {
@@ -2306,49 +3085,165 @@ pub async fn chat_completion(
         },
     ];
     let input_messages_json = build_llm_messages_json(&input_messages);
-    let llm_call_persisted = match insert_llm_call(
-        state,
-        &log_record,
-        ai.model.as_str(),
+    let prompt = LlmCallPrompt {
+        system,
+        user,
         max_tokens,
-        &prompt_text,
-        input_messages_json.as_deref(),
-    )
-    .await
-    {
-        Ok(()) => true,
-        Err(err) => {
-            tracing::warn!(
-                event = "sqlite.write",
-                operation = "ai.llm_call_log_insert",
-                error_kind = "insert_failed",
-                error_chain = %observability::error_chain_summary(err.as_ref()),
-                "llm call log insert failed"
-            );
-            false
-        }
+        prompt_text: prompt_text.as_str(),
+        input_messages_json: input_messages_json.as_deref(),
     };
 
-    let model_for_call = ai.model.clone();
-    let mut total_wait_ms = 0_i64;
-    let mut started_at: Option<Instant> = None;
-    let mut started_at_timestamp: Option<String> = None;
-    let translation_source_uses_empty_content_budget =
-        llm_call_uses_translation_empty_content_retry_budget(
-            log_record.source.as_str(),
-            log_record.parent_task_type.as_deref(),
-        );
-    let mut translation_empty_content_budget_active = false;
-    let mut attempt = 0_usize;
-    loop {
-        attempt = attempt.saturating_add(1);
-        let (wait_ms, mut in_flight_guard) = state.llm_scheduler.acquire_slot().await;
-        total_wait_ms = total_wait_ms.saturating_add(wait_ms.max(0));
-        let attempt_count = i64::try_from(attempt).unwrap_or(i64::MAX);
-        let mut heartbeat = runtime::LeaseHeartbeat::disabled();
-
-        if started_at.is_none() {
-            started_at = Some(Instant::now());
+    let models = llm_model_fallback_chain(&ai);
+    let prompt_tokens = estimate_text_tokens(system).saturating_add(estimate_text_tokens(user));
+    let mut attempts_used = 0_usize;
+    let mut last_err = None;
+    for (idx, model) in models.iter().enumerate() {
+        if idx > 0 {
+            let budget = compute_input_budget_for_model(state, model, max_tokens).await;
+            if prompt_tokens > budget.input_budget {
+                tracing::warn!(
+                    event = "upstream.call",
+                    operation = "ai.chat_completions",
+                    model = model.as_str(),
+                    prompt_tokens,
+                    input_budget = budget.input_budget,
+                    "skipping fallback model whose context limit cannot fit the prompt"
+                );
+                continue;
+            }
+        }
+        ai.model = model.clone();
+        let record = log_record.take().unwrap_or_else(build_llm_call_log_record);
+        match run_chat_completion_with_model(state, &ai, &record, &prompt, deltas, attempts_used)
+            .await
+        {
+            Ok(output) => return Ok(output),
+            Err(failure) => {
+                attempts_used = failure.attempt_count;
+                if !ai_error_is_non_retryable(&failure.err) {
+                    return Err(failure.err);
+                }
+                tracing::warn!(
+                    event = "upstream.call",
+                    operation = "ai.chat_completions",
+                    model = model.as_str(),
+                    remaining_models = models.len().saturating_sub(idx + 1),
+                    error_chain = %observability::error_chain_summary(failure.err.as_ref()),
+                    "ai model rejected the request; trying next fallback model"
+                );
+                last_err = Some(failure.err);
+            }
+        }
+    }
+    Err(last_err.unwrap_or_else(|| anyhow!("no llm model available for the request")))
+}
+
+/// Primary model first, then `AI_FALLBACK_MODELS` in configured order.
+fn llm_model_fallback_chain(ai: &AiConfig) -> Vec<String> {
+    let mut models = vec![ai.model.clone()];
+    for model in &ai.fallback_models {
+        if !models.iter().any(|existing| existing == model) {
+            models.push(model.clone());
+        }
+    }
+    models
+}
+
+struct LlmCallPrompt<'a> {
+    system: &'a str,
+    user: &'a str,
+    max_tokens: u32,
+    prompt_text: &'a str,
+    input_messages_json: Option<&'a str>,
+}
+
+struct LlmModelCallFailure {
+    err: anyhow::Error,
+    attempt_count: usize,
+}
+
+/// Runs one model's retry loop under its own `llm_calls` row. Attempt
+/// numbering continues from `prior_attempts` so a fallback chain keeps a
+/// single increasing `attempt_count` across models.
+async fn run_chat_completion_with_model(
+    state: &AppState,
+    ai: &AiConfig,
+    log_record: &LlmCallLogRecord,
+    prompt: &LlmCallPrompt<'_>,
+    deltas: Option<&mpsc::UnboundedSender<ChatCompletionDelta>>,
+    prior_attempts: usize,
+) -> Result<ChatCompletionOutput, LlmModelCallFailure> {
+    let LlmCallPrompt {
+        system,
+        user,
+        max_tokens,
+        prompt_text,
+        input_messages_json,
+    } = *prompt;
+    let llm_call_persisted = match insert_llm_call(
+        state,
+        log_record,
+        ai.model.as_str(),
+        max_tokens,
+        prompt_text,
+        input_messages_json,
+    )
+    .await
+    {
+        Ok(()) => true,
+        Err(err) => {
+            tracing::warn!(
+                event = "sqlite.write",
+                operation = "ai.llm_call_log_insert",
+                error_kind = "insert_failed",
+                error_chain = %observability::error_chain_summary(err.as_ref()),
+                "llm call log insert failed"
+            );
+            false
+        }
+    };
+
+    let model_for_call = ai.model.clone();
+    let lane = LlmCallLane::for_source(log_record.source.as_str());
+    let estimated_tokens = estimate_text_tokens(system).saturating_add(estimate_text_tokens(user));
+    let mut total_wait_ms = 0_i64;
+    let mut started_at: Option<Instant> = None;
+    let mut started_at_timestamp: Option<String> = None;
+    let translation_source_uses_empty_content_budget =
+        llm_call_uses_translation_empty_content_retry_budget(
+            log_record.source.as_str(),
+            log_record.parent_task_type.as_deref(),
+        );
+    let mut translation_empty_content_budget_active = false;
+    let mut attempt = prior_attempts;
+    loop {
+        attempt = attempt.saturating_add(1);
+        let queued_call = LlmQueuedCall {
+            call_id: log_record.id.clone(),
+            source: log_record.source.clone(),
+            requested_by: log_record.requested_by.clone(),
+            parent_task_id: log_record.parent_task_id.clone(),
+            parent_task_type: log_record.parent_task_type.clone(),
+            enqueued_at: Utc::now(),
+            estimated_tokens,
+        };
+        // A dropped call's row was already failed by `cancel_queued_llm_call`.
+        let Some((wait_ms, mut in_flight_guard)) = state
+            .llm_scheduler
+            .acquire_call_slot(lane, Some(queued_call))
+            .await
+        else {
+            return Err(LlmModelCallFailure {
+                err: anyhow!(LLM_CALL_CANCELLED_BY_ADMIN),
+                attempt_count: attempt,
+            });
+        };
+        total_wait_ms = total_wait_ms.saturating_add(wait_ms.max(0));
+        let attempt_count = i64::try_from(attempt).unwrap_or(i64::MAX);
+        let mut heartbeat = runtime::LeaseHeartbeat::disabled();
+
+        if started_at.is_none() {
+            started_at = Some(Instant::now());
             started_at_timestamp = Some(chrono::Utc::now().to_rfc3339());
         }
 
@@ -2396,7 +3291,13 @@ pub async fn chat_completion(
             }
         }
 
-        let attempt_result = chat_completion_once(state, &ai, system, user, max_tokens).await;
+        if attempt > 1
+            && let Some(deltas) = deltas
+        {
+            let _ = deltas.send(ChatCompletionDelta::Restart);
+        }
+        let attempt_result =
+            chat_completion_once(state, ai, system, user, max_tokens, deltas).await;
         match attempt_result {
             Ok(output) => {
                 state
@@ -2460,7 +3361,7 @@ pub async fn chat_completion(
                     .await;
                 }
                 heartbeat.stop().await;
-                return Ok(output.content);
+                return Ok(output);
             }
             Err(attempt_err) => {
                 let ChatCompletionAttemptError {
@@ -2475,7 +3376,8 @@ pub async fn chat_completion(
                 }
                 let max_attempts =
                     max_llm_attempts_for_call(translation_empty_content_budget_active);
-                if !retryable || attempt >= max_attempts {
+                let model_attempt = attempt.saturating_sub(prior_attempts);
+                if !retryable || model_attempt >= max_attempts {
                     state
                         .llm_scheduler
                         .record_model_final_failure(model_for_call.as_str())
@@ -2538,9 +3440,12 @@ pub async fn chat_completion(
                         .await;
                     }
                     heartbeat.stop().await;
-                    return Err(err);
+                    return Err(LlmModelCallFailure {
+                        err,
+                        attempt_count: attempt,
+                    });
                 }
-                let retry_delay = next_retry_delay(attempt, retry_after);
+                let retry_delay = next_retry_delay(model_attempt, retry_after);
                 let requeued_at = chrono::Utc::now().to_rfc3339();
                 if llm_call_persisted {
                     state
@@ -2589,7 +3494,7 @@ pub async fn chat_completion(
                     operation = "ai.chat_completions",
                     attempt,
                     max_attempts,
-                    retries_left = max_attempts.saturating_sub(attempt),
+                    retries_left = max_attempts.saturating_sub(model_attempt),
                     elapsed_ms = first_token_wait_ms.unwrap_or_default(),
                     retry_after_ms = retry_after
                         .map(|delay| i64::try_from(delay.as_millis()).unwrap_or(i64::MAX)),
@@ -2604,16 +3509,13 @@ pub async fn chat_completion(
     }
 }
 
-fn ai_error_is_non_retryable(err: &anyhow::Error) -> bool {
-    let msg = err.to_string();
-    let msg_lower = msg.to_ascii_lowercase();
-    let status_422_non_context = msg_lower.contains("ai returned 422")
-        && !msg_lower.contains("context")
-        && !msg_lower.contains("length");
-    ai_response_message_is_non_retryable(&msg)
-        || msg_lower.contains("ai returned 401")
-        || msg_lower.contains("ai returned 403")
-        || status_422_non_context
+/// Whether a failed call should skip straight to the next fallback model
+/// instead of being retried.
+pub(crate) fn ai_error_is_non_retryable(err: &anyhow::Error) -> bool {
+    match AiError::find(err) {
+        Some(err) => err.is_non_retryable(),
+        None => ai_response_message_is_non_retryable(&err.to_string()),
+    }
 }
 
 fn spawn_llm_call_lease_heartbeat(
@@ -2744,6 +3646,41 @@ pub async fn recover_linked_llm_calls_for_batch(
     Ok(())
 }
 
+/// Fails the calls a job task left queued or running, e.g. after the worker
+/// abandoned the task on timeout.
+pub async fn fail_linked_llm_calls_for_task(
+    state: &AppState,
+    task_id: &str,
+    message: &str,
+) -> Result<()> {
+    let call_ids = sqlx::query_scalar::<_, String>(
+        r#"
+        SELECT id
+        FROM llm_calls
+        WHERE parent_task_id = ?
+          AND status IN ('queued', 'running')
+        ORDER BY created_at ASC, id ASC
+        "#,
+    )
+    .bind(task_id)
+    .fetch_all(&state.pool)
+    .await
+    .context("load linked llm calls for task failed")?;
+
+    for call_id in call_ids {
+        recover_llm_call_with_message(
+            state,
+            call_id.as_str(),
+            message,
+            None,
+            None,
+            "llm.parent_task_failed",
+        )
+        .await?;
+    }
+    Ok(())
+}
+
 pub async fn recover_runtime_state(state: &AppState) -> Result<()> {
     recover_runtime_state_with_mode(state, runtime::RuntimeRecoveryMode::Sweep).await
 }
@@ -3685,13 +4622,7 @@ async fn summarize_project_with_ai(
     }
 
     let prompt = build_project_prompt(full_name, releases);
-    let raw = chat_completion(
-        state,
-        "你是一个严谨的发布说明整理助手，擅长在不遗漏关键信息的前提下提炼 GitHub Release 变更。",
-        &prompt,
-        1200,
-    )
-    .await?;
+    let raw = chat_completion(state, BRIEF_PROJECT_SUMMARY_SYSTEM_PROMPT, &prompt, 1200).await?;
 
     let Some(payload) = parse_project_summary_payload(&raw) else {
         return Err(anyhow!("project summary json decode failed"));
@@ -3716,43 +4647,31 @@ async fn summarize_project_with_ai(
 
 async fn summarize_projects_with_ai(
     state: &AppState,
-    projects: &[(String, Vec<ReleaseDigest>)],
+    plan: &BriefPlan,
 ) -> HashMap<i64, Vec<String>> {
+    let projects = &plan.projects;
     if projects.is_empty() {
         return HashMap::new();
     }
 
-    let estimated = projects
-        .iter()
-        .map(|(_, releases)| {
-            releases
-                .iter()
-                .map(|rel| estimate_text_tokens(&rel.title) + estimate_text_tokens(&rel.body) + 48)
-                .sum::<u32>()
-                .max(1)
-        })
-        .collect::<Vec<_>>();
-    let budget_info = compute_input_budget_with_source(state, 1_400).await;
-    let budget = budget_info.input_budget;
-    let groups = pack_batch_indices(&estimated, budget, 320);
-    let split_count = groups.len().saturating_sub(1);
-    let saved_calls = projects.len().saturating_sub(groups.len());
-    let estimated_tokens = estimated.iter().copied().sum::<u32>();
+    let split_count = plan.batches.len().saturating_sub(1);
+    let saved_calls = projects.len().saturating_sub(plan.batches.len());
+    let estimated_tokens = plan.project_tokens.iter().copied().sum::<u32>();
     tracing::info!(
         batch_size = projects.len(),
         estimated_tokens,
         split_count,
         saved_calls,
-        fallback_source = budget_info.fallback_source,
-        input_budget = budget_info.input_budget,
-        model_input_limit = budget_info.model_input_limit,
+        fallback_source = plan.budget.fallback_source,
+        input_budget = plan.budget.input_budget,
+        model_input_limit = plan.budget.model_input_limit,
         "daily brief batch plan"
     );
 
     let mut merged = HashMap::<i64, Vec<String>>::new();
     let mut abort_remaining_batches = false;
-    for group in groups {
-        if abort_remaining_batches {
+    for group in &plan.batches {
+        if abort_remaining_batches || jobs::current_task_cancel_requested(state).await {
             break;
         }
 
@@ -3764,9 +4683,9 @@ async fn summarize_projects_with_ai(
 
         let raw = chat_completion(
             state,
-            "你是一个严谨的发布说明整理助手，擅长在不遗漏关键信息的前提下提炼 GitHub Release 变更。",
+            BRIEF_PROJECT_SUMMARY_SYSTEM_PROMPT,
             &prompt,
-            1_400,
+            BRIEF_PROJECT_SUMMARY_MAX_TOKENS,
         )
         .await;
 
@@ -3826,9 +4745,40 @@ async fn summarize_projects_with_ai(
             if !need_repo_fallback {
                 continue;
             }
-            if let Ok(map) = summarize_project_with_ai(state, full_name, releases).await {
-                for (release_id, bullets) in map {
-                    merged.entry(release_id).or_insert(bullets);
+            let Ok(map) = summarize_project_with_ai(state, full_name, releases).await else {
+                continue;
+            };
+            for (release_id, bullets) in map {
+                merged.entry(release_id).or_insert(bullets);
+            }
+
+            // The per-repo prompt can still drop items; ask for each leftover
+            // release on its own before falling back to body excerpts.
+            if releases.len() < 2 {
+                continue;
+            }
+            let missing = releases
+                .iter()
+                .filter(|release| !merged.contains_key(&release.release_id))
+                .collect::<Vec<_>>();
+            for release in missing {
+                match summarize_project_with_ai(state, full_name, std::slice::from_ref(release))
+                    .await
+                {
+                    Ok(mut map) => {
+                        if let Some(bullets) = map.remove(&release.release_id) {
+                            merged.insert(release.release_id, bullets);
+                        }
+                    }
+                    Err(err) => {
+                        tracing::warn!(
+                            event = "upstream.call",
+                            operation = "ai.release_summary_retry",
+                            release_id = release.release_id,
+                            error_chain = %observability::error_chain_summary(err.as_ref()),
+                            "single release summary retry failed; using release notes excerpt"
+                        );
+                    }
                 }
             }
         }
@@ -4126,7 +5076,7 @@ where
         ORDER BY
           COALESCE(published_at, created_at, updated_at) DESC,
           release_id DESC
-        LIMIT 300
+        LIMIT {BRIEF_RELEASE_LIMIT}
         "#
     );
 
@@ -4250,11 +5200,39 @@ fn reconcile_brief_release_links(markdown: &str, releases: &[ReleaseDigest]) ->
     out
 }
 
-async fn polish_brief_markdown(
-    state: &AppState,
-    markdown: &str,
-    releases: &[ReleaseDigest],
-) -> Option<String> {
+/// Appends the canonical `[GitHub Release](html_url)` link to top-level
+/// release bullets that lost it during polishing. URLs always come from the
+/// stored release rows, never from model output.
+fn inject_release_source_links(markdown: &str, releases: &[ReleaseDigest]) -> String {
+    let mut out = String::with_capacity(markdown.len());
+    for line in markdown.split_inclusive('\n') {
+        let (body, newline) = match line.strip_suffix('\n') {
+            Some(body) => (body, "\n"),
+            None => (line, ""),
+        };
+        let release = body
+            .starts_with("- [")
+            .then(|| extract_markdown_link_targets(body).into_iter().next())
+            .flatten()
+            .and_then(|target| parse_internal_release_ref(&target))
+            .and_then(|reference| {
+                releases
+                    .iter()
+                    .find(|release| internal_release_ref_matches_digest(&reference, release))
+            });
+        out.push_str(body);
+        if let Some(release) = release
+            && release.html_url.starts_with("https://github.com/")
+            && !body.contains(release.html_url.as_str())
+        {
+            out.push_str(&format!(" · [GitHub Release]({})", release.html_url));
+        }
+        out.push_str(newline);
+    }
+    out
+}
+
+fn build_polish_brief_prompt(markdown: &str) -> String {
     let social_structure_rule = match extract_social_summary_items(markdown) {
         SocialSummaryItems {
             has_section: true,
@@ -4284,21 +5262,28 @@ async fn polish_brief_markdown(
             "2) 只保留原文已有章节；若原文没有“## 获星与关注”章节，禁止新增任何社交摘要章节或小节；"
         }
     };
-    let prompt = format!(
+    format!(
         "请在不删减任何 release 条目与社交摘要的前提下，对下面日报做一次统一润色。\n\n硬性要求：\n1) 保留所有链接原样（尤其 /owner/repo/releases/tag/<tag>?from=briefs）；\n{social_structure_rule}\n3) 默认使用简体中文优化可读性；技术术语、代码标识符、commit type、包名、API 名、项目名、版本号和原始标题可以保留英文；\n4) 不要输出 markdown code block，也不要把整篇内容包进 ```markdown ```；\n5) 不新增编造事实；\n6) release 与 repo 的顺序必须保持不变；\n7) 必须严格保持 Markdown 层级：仓库标题保持 `### [repo](...)`；每条 release 保持顶层 `- [title](/owner/repo/releases/tag/<tag>?from=briefs)`；release 下的摘要与“相关链接”必须保持缩进两个空格的子 bullet `  - ...`；\n8) 不得把 release 下的子 bullet 改写成普通段落、硬换行文本或空行分隔；\n9) 除章节、仓库标题、release block 之间已有的单个空行外，不得新增额外空行；\n10) 你只能改写既有 bullet 的措辞、去重或压缩重复表达，不能改结构。\n\n日报原文：\n{markdown}",
-    );
+    )
+}
 
+async fn polish_brief_markdown(
+    state: &AppState,
+    markdown: &str,
+    releases: &[ReleaseDigest],
+) -> Option<String> {
+    let prompt = build_polish_brief_prompt(markdown);
     let polished = chat_completion(
         state,
-        "你是一个发布日报编辑器，负责在不丢失信息的前提下用简体中文优化可读性。",
+        BRIEF_POLISH_SYSTEM_PROMPT,
         &prompt,
-        1600,
+        BRIEF_POLISH_MAX_TOKENS,
     )
     .await
     .ok()?;
 
     let stripped = strip_outer_markdown_fence(&polished);
-    let sanitized = sanitize_markdown_links(stripped);
+    let sanitized = inject_release_source_links(&sanitize_markdown_links(stripped), releases);
 
     if !brief_content_is_canonical(&sanitized) {
         return None;
@@ -4316,25 +5301,69 @@ async fn polish_brief_markdown(
     Some(sanitized)
 }
 
-async fn build_brief_content_from_digests(
+/// Everything a brief run settles before its first LLM call: the releases it
+/// covers, how the per-project summaries are batched against the input budget,
+/// and the social digest. Executing a plan is the only step that talks to the
+/// model.
+#[derive(Debug)]
+struct BriefPlan {
+    releases: Vec<ReleaseDigest>,
+    releases_total: usize,
+    social: Vec<SocialActivityDigest>,
+    projects: Vec<(String, Vec<ReleaseDigest>)>,
+    project_tokens: Vec<u32>,
+    batches: Vec<Vec<usize>>,
+    budget: InputBudget,
+}
+
+fn estimate_project_summary_tokens(releases: &[ReleaseDigest]) -> u32 {
+    releases
+        .iter()
+        .map(|rel| estimate_text_tokens(&rel.title) + estimate_text_tokens(&rel.body) + 48)
+        .sum::<u32>()
+        .max(1)
+}
+
+async fn plan_brief(
     state: &AppState,
     releases: Vec<ReleaseDigest>,
+    releases_total: usize,
     social: Vec<SocialActivityDigest>,
-) -> Result<BuiltBriefContent> {
-    let grouped = group_by_repo(&releases)
+) -> BriefPlan {
+    let projects = group_by_repo(&releases)
         .into_iter()
         .collect::<Vec<(String, Vec<ReleaseDigest>)>>();
-
-    let ai_bullets = summarize_projects_with_ai(state, &grouped).await;
-
-    let mut repos = Vec::with_capacity(grouped.len());
-    for (full_name, project_releases) in grouped {
-        repos.push(build_repo_rendered(
-            &full_name,
-            &project_releases,
-            Some(&ai_bullets),
-        ));
+    let project_tokens = projects
+        .iter()
+        .map(|(_, project_releases)| estimate_project_summary_tokens(project_releases))
+        .collect::<Vec<_>>();
+    let budget = compute_input_budget_with_source(state, BRIEF_PROJECT_SUMMARY_MAX_TOKENS).await;
+    let batches = pack_batch_indices(
+        &project_tokens,
+        budget.input_budget,
+        BRIEF_PROJECT_BATCH_OVERHEAD_TOKENS,
+    );
+    BriefPlan {
+        releases,
+        releases_total,
+        social,
+        projects,
+        project_tokens,
+        batches,
+        budget,
     }
+}
+
+fn render_brief_repos(
+    projects: &[(String, Vec<ReleaseDigest>)],
+    ai_bullets: Option<&HashMap<i64, Vec<String>>>,
+) -> Vec<RepoRendered> {
+    let mut repos = projects
+        .iter()
+        .map(|(full_name, project_releases)| {
+            build_repo_rendered(full_name, project_releases, ai_bullets)
+        })
+        .collect::<Vec<_>>();
     repos.sort_by(|left, right| {
         let left_latest = left
             .releases
@@ -4348,30 +5377,51 @@ async fn build_brief_content_from_digests(
             .unwrap_or("");
         compare_desc_with_tiebreak(left_latest, right_latest, &left.full_name, &right.full_name)
     });
-    let social_summary = build_social_summary(&social);
+    repos
+}
 
-    let deterministic = sanitize_markdown_links(&build_brief_markdown(&repos, &social_summary));
+async fn execute_brief_plan(state: &AppState, plan: BriefPlan) -> Result<BuiltBriefContent> {
+    let ai_bullets = summarize_projects_with_ai(state, &plan).await;
+    let repos = render_brief_repos(&plan.projects, Some(&ai_bullets));
+    let social_summary = build_social_summary(&plan.social);
+    let releases = plan.releases;
 
-    if state.config.ai.is_none() || releases.is_empty() {
-        return Ok(BuiltBriefContent {
-            content_markdown: reconcile_brief_release_links(&deterministic, &releases),
-            releases,
-        });
-    }
+    let summarized_release_ids = ai_bullets.into_keys().collect::<HashSet<_>>();
 
-    if let Some(polished) = polish_brief_markdown(state, &deterministic, &releases).await {
-        return Ok(BuiltBriefContent {
-            content_markdown: reconcile_brief_release_links(&polished, &releases),
-            releases,
-        });
-    }
+    let deterministic = sanitize_markdown_links(&build_brief_markdown(&repos, &social_summary));
+    let coverage =
+        BriefCoverage::for_releases(plan.releases_total, &releases, &summarized_release_ids);
+    let sources = BriefSource::for_repos(&repos);
+
+    let polished = if state.config.ai.is_none()
+        || releases.is_empty()
+        || jobs::current_task_cancel_requested(state).await
+    {
+        None
+    } else {
+        polish_brief_markdown(state, &deterministic, &releases).await
+    };
+    let markdown = polished.as_deref().unwrap_or(&deterministic);
 
     Ok(BuiltBriefContent {
-        content_markdown: reconcile_brief_release_links(&deterministic, &releases),
+        content_markdown: reconcile_brief_release_links(markdown, &releases),
         releases,
+        coverage,
+        sources,
+        summarized_release_ids,
     })
 }
 
+async fn build_brief_content_from_digests(
+    state: &AppState,
+    releases: Vec<ReleaseDigest>,
+    social: Vec<SocialActivityDigest>,
+) -> Result<BuiltBriefContent> {
+    let releases_total = releases.len();
+    let plan = plan_brief(state, releases, releases_total, social).await;
+    execute_brief_plan(state, plan).await
+}
+
 async fn build_brief_content(
     state: &AppState,
     window: &UserDailyWindow,
@@ -4380,7 +5430,7 @@ async fn build_brief_content(
     let start_utc = window.start_utc.to_rfc3339();
     let end_utc = window.end_utc.to_rfc3339();
 
-    let rows = sqlx::query_as::<_, ReleaseRow>(
+    let rows = sqlx::query_as::<_, BriefReleaseRow>(
         r#"
         SELECT
           r.release_id,
@@ -4390,74 +5440,442 @@ async fn build_brief_content(
           r.body,
           r.html_url,
           COALESCE(r.published_at, r.created_at, r.updated_at) AS published_at,
-          r.is_prerelease
+          r.is_prerelease,
+          COUNT(*) OVER () AS window_total
         FROM repo_releases r
-        JOIN user_release_visible_repos sr
-          ON sr.user_id = ? AND sr.repo_id = r.repo_id
+        JOIN user_release_visible_repos sr ON sr.repo_id = r.repo_id
         WHERE sr.user_id = ?
           AND r.is_draft = 0
           AND COALESCE(r.published_at, r.created_at, r.updated_at) >= ?
           AND COALESCE(r.published_at, r.created_at, r.updated_at) < ?
+          AND NOT EXISTS (
+            SELECT 1
+            FROM muted_repos m
+            WHERE m.user_id = sr.user_id
+              AND m.repo_id = r.repo_id
+          )
         ORDER BY
           COALESCE(r.published_at, r.created_at, r.updated_at) DESC,
           r.release_id DESC
-        LIMIT 300
+        LIMIT ?
         "#,
     )
     .bind(user_id)
-    .bind(user_id)
     .bind(&start_utc)
     .bind(&end_utc)
+    .bind(BRIEF_RELEASE_LIMIT)
     .fetch_all(&state.pool)
     .await
     .context("failed to query releases for brief")?;
+    let releases_total = rows
+        .first()
+        .map(|row| usize::try_from(row.window_total).unwrap_or_default())
+        .unwrap_or_default();
+    let rows = rows.into_iter().map(|row| row.release).collect();
 
     let social =
         load_social_activity_digests_for_window(state, user_id, &start_utc, &end_utc).await?;
-    build_brief_content_from_digests(state, to_release_digest(rows), social).await
+    let plan = plan_brief(state, to_release_digest(rows), releases_total, social).await;
+    execute_brief_plan(state, plan).await
+}
+
+/// Releases a preview looks at on either side of the window, capped at a day,
+/// so near misses show up as `out_of_window`.
+const BRIEF_PREVIEW_WINDOW_MARGIN_HOURS: i64 = 24;
+const BRIEF_PREVIEW_EXTRA_CANDIDATES: i64 = 100;
+
+const BRIEF_PREVIEW_EXCLUDED_OUT_OF_WINDOW: &str = "out_of_window";
+const BRIEF_PREVIEW_EXCLUDED_OVER_BUDGET: &str = "over_budget";
+const BRIEF_PREVIEW_EXCLUDED_MUTED: &str = "muted";
+
+/// A brief release row with the number of window releases the query
+/// matched before its `LIMIT`.
+#[derive(Debug, sqlx::FromRow)]
+struct BriefReleaseRow {
+    #[sqlx(flatten)]
+    release: ReleaseRow,
+    window_total: i64,
+}
+
+#[derive(Debug, sqlx::FromRow)]
+struct BriefReleaseCandidateRow {
+    #[sqlx(flatten)]
+    release: ReleaseRow,
+    muted: i64,
+    window_total: i64,
+}
+
+#[derive(Debug, Clone, Serialize, schemars::JsonSchema)]
+pub struct BriefPreviewRelease {
+    pub release_id: String,
+    pub repo_full_name: String,
+    pub title: String,
+    pub published_at: String,
+    /// Estimate fed into batch packing: title, notes and a fixed per-release
+    /// overhead.
+    pub estimated_tokens: u32,
+    /// Set on excluded releases: `out_of_window`, `over_budget` or `muted`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reason: Option<&'static str>,
+}
+
+impl BriefPreviewRelease {
+    fn from_digest(release: &ReleaseDigest, reason: Option<&'static str>) -> Self {
+        Self {
+            release_id: release.release_id.to_string(),
+            repo_full_name: release.full_name.clone(),
+            title: release.title.clone(),
+            published_at: release.published_at.clone(),
+            estimated_tokens: estimate_project_summary_tokens(std::slice::from_ref(release)),
+            reason,
+        }
+    }
 }
 
-#[allow(dead_code)]
-fn resolve_daily_boundary(at: Option<NaiveTime>) -> NaiveTime {
-    at.or_else(|| NaiveTime::from_hms_opt(8, 0, 0))
-        .expect("08:00 is valid")
+#[derive(Debug, Clone, Serialize, schemars::JsonSchema)]
+pub struct BriefPreviewPrompt {
+    /// `project_summary` for each packed batch, `polish` for the final pass.
+    pub kind: &'static str,
+    pub repos: Vec<String>,
+    pub system: String,
+    pub user: String,
+    pub max_tokens: u32,
+    pub estimated_input_tokens: u32,
 }
 
-async fn replace_brief_memberships(
-    tx: &mut sqlx::Transaction<'_, sqlx::Sqlite>,
-    brief_id: &str,
-    releases: &[ReleaseDigest],
-    created_at: &str,
-) -> Result<()> {
-    sqlx::query(
+/// A brief run stopped right before the model: nothing is written and no
+/// `llm_calls` rows are created.
+#[derive(Debug, Clone, Serialize, schemars::JsonSchema)]
+pub struct BriefPreview {
+    pub date: String,
+    pub window_start: String,
+    pub window_end: String,
+    pub effective_time_zone: String,
+    pub ai_enabled: bool,
+    pub model_input_limit: u32,
+    pub input_budget: u32,
+    pub budget_source: &'static str,
+    pub releases_total: usize,
+    pub included: Vec<BriefPreviewRelease>,
+    pub excluded: Vec<BriefPreviewRelease>,
+    /// Prompts in the order they would be sent. Per-repo retries that only
+    /// run after a failed batch are not listed.
+    pub prompts: Vec<BriefPreviewPrompt>,
+    /// The digest the polish prompt is built from, rendered with release-note
+    /// excerpts where the model would have written the bullets.
+    pub draft_markdown: String,
+}
+
+/// Splits release candidates the way the brief query does: in-window,
+/// unmuted releases newest first up to `release_limit`, everything else
+/// excluded with a reason. Candidates must already be ordered newest first.
+fn select_brief_releases(
+    candidates: Vec<(ReleaseDigest, bool)>,
+    start_utc: &str,
+    end_utc: &str,
+    release_limit: usize,
+) -> (Vec<ReleaseDigest>, Vec<(ReleaseDigest, &'static str)>) {
+    let mut included = Vec::new();
+    let mut excluded = Vec::new();
+    for (release, muted) in candidates {
+        let published_at = release.published_at.as_str();
+        let in_window = published_at >= start_utc && published_at < end_utc;
+        if !in_window {
+            excluded.push((release, BRIEF_PREVIEW_EXCLUDED_OUT_OF_WINDOW));
+        } else if muted {
+            excluded.push((release, BRIEF_PREVIEW_EXCLUDED_MUTED));
+        } else if included.len() >= release_limit {
+            excluded.push((release, BRIEF_PREVIEW_EXCLUDED_OVER_BUDGET));
+        } else {
+            included.push(release);
+        }
+    }
+    (included, excluded)
+}
+
+/// Loads preview candidates newest first, along with how many unmuted
+/// releases fall inside the window before `limit` applies.
+async fn load_brief_release_candidates(
+    state: &AppState,
+    user_id: &str,
+    window: &UserDailyWindow,
+    limit: i64,
+) -> Result<(Vec<(ReleaseDigest, bool)>, usize)> {
+    let margin = (window.end_utc - window.start_utc)
+        .min(chrono::Duration::hours(BRIEF_PREVIEW_WINDOW_MARGIN_HOURS));
+    let start_utc = window.start_utc.to_rfc3339();
+    let end_utc = window.end_utc.to_rfc3339();
+    let rows = sqlx::query_as::<_, BriefReleaseCandidateRow>(
         r#"
-        DELETE FROM brief_release_memberships
-        WHERE brief_id = ?
+        SELECT
+          c.release_id,
+          c.repo_id,
+          c.tag_name,
+          c.name,
+          c.body,
+          c.html_url,
+          COALESCE(c.published_at, c.created_at, c.updated_at) AS published_at,
+          c.is_prerelease,
+          c.muted,
+          SUM(CASE WHEN c.in_window = 1 AND c.muted = 0 THEN 1 ELSE 0 END) OVER ()
+            AS window_total
+        FROM (
+          SELECT
+            r.*,
+            (COALESCE(r.published_at, r.created_at, r.updated_at) >= ?
+              AND COALESCE(r.published_at, r.created_at, r.updated_at) < ?) AS in_window,
+            EXISTS (
+              SELECT 1
+              FROM muted_repos m
+              WHERE m.user_id = sr.user_id
+                AND m.repo_id = r.repo_id
+            ) AS muted
+          FROM repo_releases r
+          JOIN user_release_visible_repos sr ON sr.repo_id = r.repo_id
+          WHERE sr.user_id = ?
+            AND r.is_draft = 0
+            AND COALESCE(r.published_at, r.created_at, r.updated_at) >= ?
+            AND COALESCE(r.published_at, r.created_at, r.updated_at) < ?
+        ) c
+        ORDER BY
+          c.in_window DESC,
+          COALESCE(c.published_at, c.created_at, c.updated_at) DESC,
+          c.release_id DESC
+        LIMIT ?
         "#,
     )
-    .bind(brief_id)
-    .execute(&mut **tx)
+    .bind(&start_utc)
+    .bind(&end_utc)
+    .bind(user_id)
+    .bind((window.start_utc - margin).to_rfc3339())
+    .bind((window.end_utc + margin).to_rfc3339())
+    .bind(limit)
+    .fetch_all(&state.pool)
     .await
-    .context("failed to clear brief memberships")?;
+    .context("failed to query release candidates for brief preview")?;
 
-    for (ordinal, release) in releases.iter().enumerate() {
-        sqlx::query(
-            r#"
-            INSERT INTO brief_release_memberships (
-              brief_id, release_id, release_ts_utc, ordinal, created_at
-            )
-            VALUES (?, ?, ?, ?, ?)
-            "#,
-        )
-        .bind(brief_id)
-        .bind(release.release_id)
-        .bind(&release.published_at)
-        .bind(i64::try_from(ordinal).unwrap_or(i64::MAX))
-        .bind(created_at)
-        .execute(&mut **tx)
-        .await
-        .context("failed to insert brief membership")?;
-    }
+    let window_total = rows
+        .first()
+        .map(|row| usize::try_from(row.window_total).unwrap_or_default())
+        .unwrap_or_default();
+    let (releases, muted): (Vec<_>, Vec<_>) = rows
+        .into_iter()
+        .map(|row| (row.release, row.muted != 0))
+        .unzip();
+    Ok((
+        to_release_digest(releases).into_iter().zip(muted).collect(),
+        window_total,
+    ))
+}
+
+fn preview_prompts(plan: &BriefPlan, draft_markdown: &str) -> Vec<BriefPreviewPrompt> {
+    let mut prompts = plan
+        .batches
+        .iter()
+        .map(|group| {
+            let batch_projects = group
+                .iter()
+                .map(|idx| plan.projects[*idx].clone())
+                .collect::<Vec<_>>();
+            BriefPreviewPrompt {
+                kind: "project_summary",
+                repos: batch_projects
+                    .iter()
+                    .map(|(full_name, _)| full_name.clone())
+                    .collect(),
+                system: BRIEF_PROJECT_SUMMARY_SYSTEM_PROMPT.to_owned(),
+                user: build_projects_batch_prompt(&batch_projects),
+                max_tokens: BRIEF_PROJECT_SUMMARY_MAX_TOKENS,
+                estimated_input_tokens: BRIEF_PROJECT_BATCH_OVERHEAD_TOKENS
+                    + group
+                        .iter()
+                        .map(|idx| plan.project_tokens[*idx])
+                        .sum::<u32>(),
+            }
+        })
+        .collect::<Vec<_>>();
+    if !plan.releases.is_empty() {
+        let user = build_polish_brief_prompt(draft_markdown);
+        prompts.push(BriefPreviewPrompt {
+            kind: "polish",
+            repos: plan
+                .projects
+                .iter()
+                .map(|(full_name, _)| full_name.clone())
+                .collect(),
+            system: BRIEF_POLISH_SYSTEM_PROMPT.to_owned(),
+            estimated_input_tokens: estimate_text_tokens(BRIEF_POLISH_SYSTEM_PROMPT)
+                + estimate_text_tokens(&user),
+            user,
+            max_tokens: BRIEF_POLISH_MAX_TOKENS,
+        });
+    }
+    prompts
+}
+
+async fn preview_brief_with_limit(
+    state: &AppState,
+    user_id: &str,
+    window: &UserDailyWindow,
+    release_limit: usize,
+) -> Result<BriefPreview> {
+    let start_utc = window.start_utc.to_rfc3339();
+    let end_utc = window.end_utc.to_rfc3339();
+    let candidate_limit = i64::try_from(release_limit).unwrap_or(BRIEF_RELEASE_LIMIT)
+        + BRIEF_PREVIEW_EXTRA_CANDIDATES;
+    let (candidates, releases_total) =
+        load_brief_release_candidates(state, user_id, window, candidate_limit).await?;
+    let (included, excluded) =
+        select_brief_releases(candidates, &start_utc, &end_utc, release_limit);
+    let social =
+        load_social_activity_digests_for_window(state, user_id, &start_utc, &end_utc).await?;
+    let plan = plan_brief(state, included, releases_total, social).await;
+
+    let repos = render_brief_repos(&plan.projects, None);
+    let draft_markdown = sanitize_markdown_links(&build_brief_markdown(
+        &repos,
+        &build_social_summary(&plan.social),
+    ));
+    let prompts = preview_prompts(&plan, &draft_markdown);
+
+    Ok(BriefPreview {
+        date: window.display_date.clone(),
+        window_start: start_utc,
+        window_end: end_utc,
+        effective_time_zone: window.effective_time_zone.clone(),
+        ai_enabled: state.config.ai.is_some(),
+        model_input_limit: plan.budget.model_input_limit,
+        input_budget: plan.budget.input_budget,
+        budget_source: plan.budget.fallback_source,
+        releases_total: plan.releases_total,
+        included: plan
+            .releases
+            .iter()
+            .map(|release| BriefPreviewRelease::from_digest(release, None))
+            .collect(),
+        excluded: excluded
+            .iter()
+            .map(|(release, reason)| BriefPreviewRelease::from_digest(release, Some(reason)))
+            .collect(),
+        prompts,
+        draft_markdown,
+    })
+}
+
+/// Runs the brief pipeline for `window` up to, but not including, the model
+/// calls.
+pub async fn preview_brief_for_window(
+    state: &AppState,
+    user_id: &str,
+    window: &UserDailyWindow,
+) -> Result<BriefPreview> {
+    preview_brief_with_limit(
+        state,
+        user_id,
+        window,
+        usize::try_from(BRIEF_RELEASE_LIMIT).unwrap_or_default(),
+    )
+    .await
+}
+
+#[allow(dead_code)]
+fn resolve_daily_boundary(at: Option<NaiveTime>) -> NaiveTime {
+    at.or_else(|| NaiveTime::from_hms_opt(8, 0, 0))
+        .expect("08:00 is valid")
+}
+
+async fn replace_brief_memberships(
+    tx: &mut sqlx::Transaction<'_, sqlx::Sqlite>,
+    brief_id: &str,
+    releases: &[ReleaseDigest],
+    created_at: &str,
+) -> Result<()> {
+    sqlx::query(
+        r#"
+        DELETE FROM brief_release_memberships
+        WHERE brief_id = ?
+        "#,
+    )
+    .bind(brief_id)
+    .execute(&mut **tx)
+    .await
+    .context("failed to clear brief memberships")?;
+
+    for (ordinal, release) in releases.iter().enumerate() {
+        sqlx::query(
+            r#"
+            INSERT INTO brief_release_memberships (
+              brief_id, release_id, release_ts_utc, ordinal, created_at
+            )
+            VALUES (?, ?, ?, ?, ?)
+            "#,
+        )
+        .bind(brief_id)
+        .bind(release.release_id)
+        .bind(&release.published_at)
+        .bind(i64::try_from(ordinal).unwrap_or(i64::MAX))
+        .bind(created_at)
+        .execute(&mut **tx)
+        .await
+        .context("failed to insert brief membership")?;
+    }
+
+    Ok(())
+}
+
+async fn replace_brief_sources(
+    tx: &mut sqlx::Transaction<'_, sqlx::Sqlite>,
+    brief_id: &str,
+    built: &BuiltBriefContent,
+    created_at: &str,
+) -> Result<()> {
+    sqlx::query(
+        r#"
+        DELETE FROM brief_sources
+        WHERE brief_id = ?
+        "#,
+    )
+    .bind(brief_id)
+    .execute(&mut **tx)
+    .await
+    .context("failed to clear brief sources")?;
+
+    for (ordinal, source) in built.sources.iter().enumerate() {
+        let Ok(release_id) = source.release_id.parse::<i64>() else {
+            continue;
+        };
+        sqlx::query(
+            r#"
+            INSERT INTO brief_sources (
+              brief_id, release_id, section, ordinal, included, created_at
+            )
+            VALUES (?, ?, ?, ?, ?, ?)
+            "#,
+        )
+        .bind(brief_id)
+        .bind(release_id)
+        .bind(&source.section)
+        .bind(i64::try_from(ordinal).unwrap_or(i64::MAX))
+        .bind(i64::from(
+            built.summarized_release_ids.contains(&release_id),
+        ))
+        .bind(created_at)
+        .execute(&mut **tx)
+        .await
+        .context("failed to insert brief source")?;
+    }
+
+    sqlx::query(
+        r#"
+        UPDATE briefs
+        SET sources_tracked = 1
+        WHERE id = ?
+        "#,
+    )
+    .bind(brief_id)
+    .execute(&mut **tx)
+    .await
+    .context("failed to mark brief sources tracked")?;
 
     Ok(())
 }
@@ -4472,6 +5890,8 @@ async fn load_stored_brief_snapshot(state: &AppState, brief_id: &str) -> Result<
         effective_time_zone: Option<String>,
         effective_local_boundary: Option<String>,
         content_markdown: String,
+        coverage_json: Option<String>,
+        sources_json: Option<String>,
     }
 
     #[derive(Debug, sqlx::FromRow)]
@@ -4488,7 +5908,9 @@ async fn load_stored_brief_snapshot(state: &AppState, brief_id: &str) -> Result<
           window_end_utc,
           effective_time_zone,
           effective_local_boundary,
-          content_markdown
+          content_markdown,
+          coverage_json,
+          sources_json
         FROM briefs
         WHERE id = ?
         LIMIT 1
@@ -4532,6 +5954,8 @@ async fn load_stored_brief_snapshot(state: &AppState, brief_id: &str) -> Result<
             .context("stored brief snapshot missing effective_local_boundary")?,
         content_markdown: row.content_markdown,
         release_ids,
+        coverage: BriefCoverage::from_json(row.coverage_json.as_deref()),
+        sources: BriefSource::list_from_json(row.sources_json.as_deref()),
     })
 }
 
@@ -4625,6 +6049,8 @@ async fn upsert_daily_brief_snapshot(
                 .iter()
                 .map(|release| release.release_id)
                 .collect(),
+            coverage: Some(built.coverage.clone()),
+            sources: built.sources.clone(),
         });
     }
 
@@ -4645,10 +6071,12 @@ async fn upsert_daily_brief_snapshot(
           effective_local_boundary,
           generation_source,
           content_markdown,
+          coverage_json,
+          sources_json,
           created_at,
           updated_at
         )
-        VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+        VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
         ON CONFLICT(user_id, window_start_utc, window_end_utc)
         WHERE window_start_utc IS NOT NULL AND window_end_utc IS NOT NULL
         DO NOTHING
@@ -4664,6 +6092,8 @@ async fn upsert_daily_brief_snapshot(
     .bind(&window.effective_local_boundary)
     .bind(generation_source)
     .bind(&built.content_markdown)
+    .bind(serde_json::to_string(&built.coverage).context("serialize brief coverage")?)
+    .bind(serde_json::to_string(&built.sources).context("serialize brief sources")?)
     .bind(&now)
     .bind(&now)
     .fetch_optional(&mut *tx)
@@ -4730,10 +6160,13 @@ async fn upsert_daily_brief_snapshot(
                 .iter()
                 .map(|release| release.release_id)
                 .collect(),
+            coverage: Some(built.coverage.clone()),
+            sources: built.sources.clone(),
         });
     };
 
     replace_brief_memberships(&mut tx, &brief_id, &built.releases, &now).await?;
+    replace_brief_sources(&mut tx, &brief_id, built, &now).await?;
     tx.commit()
         .await
         .context("failed to commit brief snapshot")?;
@@ -4751,6 +6184,8 @@ async fn upsert_daily_brief_snapshot(
             .iter()
             .map(|release| release.release_id)
             .collect(),
+        coverage: Some(built.coverage.clone()),
+        sources: built.sources.clone(),
     })
 }
 
@@ -4833,6 +6268,8 @@ async fn overwrite_brief_snapshot(
             effective_local_boundary = ?,
             generation_source = ?,
             content_markdown = ?,
+            coverage_json = ?,
+            sources_json = ?,
             updated_at = ?
         WHERE id = ?
         "#,
@@ -4844,13 +6281,16 @@ async fn overwrite_brief_snapshot(
     .bind(&window.effective_local_boundary)
     .bind(generation_source)
     .bind(&built.content_markdown)
+    .bind(serde_json::to_string(&built.coverage).context("serialize brief coverage")?)
+    .bind(serde_json::to_string(&built.sources).context("serialize brief sources")?)
     .bind(now)
     .bind(brief_id)
     .execute(&mut **tx)
     .await
     .context("failed to overwrite brief snapshot")?;
 
-    replace_brief_memberships(tx, brief_id, &built.releases, now).await
+    replace_brief_memberships(tx, brief_id, &built.releases, now).await?;
+    replace_brief_sources(tx, brief_id, built, now).await
 }
 
 #[derive(Debug, Clone, sqlx::FromRow)]
@@ -5127,6 +6567,8 @@ async fn refresh_existing_brief_snapshot(
             .into_iter()
             .map(|release| release.release_id)
             .collect(),
+        coverage: Some(built.coverage),
+        sources: built.sources,
     })
 }
 
@@ -5174,6 +6616,17 @@ pub async fn generate_daily_brief_snapshot_for_key_date(
     generate_daily_brief_snapshot_for_window(state, user_id, &window, "manual").await
 }
 
+pub async fn generate_brief_snapshot_for_range(
+    state: &AppState,
+    user_id: &str,
+    start_utc: chrono::DateTime<chrono::Utc>,
+    end_utc: chrono::DateTime<chrono::Utc>,
+) -> Result<StoredBrief> {
+    let preferences = briefs::load_daily_brief_preferences(state, user_id).await?;
+    let window = briefs::compute_daily_window_for_range(&preferences, start_utc, end_utc)?;
+    generate_daily_brief_snapshot_for_window(state, user_id, &window, "manual").await
+}
+
 #[allow(dead_code)]
 pub async fn generate_daily_brief_for_key_date(
     state: &AppState,
@@ -5742,6 +7195,8 @@ pub async fn recompute_legacy_brief_snapshot(
             .iter()
             .map(|release| release.release_id)
             .collect(),
+        coverage: None,
+        sources: Vec::new(),
     })
 }
 
@@ -5781,9 +7236,150 @@ mod tests {
         setup_llm_state_with_ai(None).await
     }
 
+    fn translation_flight_key(source_hash: &str) -> TranslationFlightKey {
+        TranslationFlightKey {
+            user_id: "user-1".to_owned(),
+            entity_type: "release_detail",
+            entity_id: "42".to_owned(),
+            lang: "zh-CN".to_owned(),
+            source_hash: source_hash.to_owned(),
+        }
+    }
+
+    #[tokio::test]
+    async fn translation_singleflight_shares_one_run_between_concurrent_callers() {
+        let flights = TranslationSingleflight::default();
+        let runs = Arc::new(AtomicUsize::new(0));
+        let (release_tx, release_rx) = tokio::sync::oneshot::channel::<()>();
+
+        let leader = {
+            let flights = flights.clone();
+            let runs = runs.clone();
+            tokio::spawn(async move {
+                flights
+                    .run(translation_flight_key("hash-a"), || async move {
+                        runs.fetch_add(1, Ordering::SeqCst);
+                        release_rx.await.expect("release leader");
+                        Ok::<_, String>("译文".to_owned())
+                    })
+                    .await
+            })
+        };
+        while runs.load(Ordering::SeqCst) == 0 {
+            tokio::task::yield_now().await;
+        }
+        let waiter = {
+            let flights = flights.clone();
+            let runs = runs.clone();
+            tokio::spawn(async move {
+                flights
+                    .run(translation_flight_key("hash-a"), || async move {
+                        runs.fetch_add(1, Ordering::SeqCst);
+                        Ok::<_, String>("duplicate".to_owned())
+                    })
+                    .await
+            })
+        };
+        let other_hash = flights
+            .run(translation_flight_key("hash-b"), || async {
+                Ok::<_, String>("other".to_owned())
+            })
+            .await;
+        tokio::task::yield_now().await;
+        release_tx.send(()).expect("release leader");
+
+        assert_eq!(leader.await.expect("leader"), Ok("译文".to_owned()));
+        assert_eq!(waiter.await.expect("waiter"), Ok("译文".to_owned()));
+        assert_eq!(other_hash, Ok("other".to_owned()));
+        assert_eq!(runs.load(Ordering::SeqCst), 1);
+        assert!(flights.inflight.lock().expect("inflight").is_empty());
+    }
+
+    #[tokio::test]
+    async fn translation_singleflight_shares_errors_and_allows_retry() {
+        let flights = TranslationSingleflight::default();
+        let (release_tx, release_rx) = tokio::sync::oneshot::channel::<()>();
+
+        let leader = {
+            let flights = flights.clone();
+            tokio::spawn(async move {
+                flights
+                    .run(translation_flight_key("hash-a"), || async move {
+                        release_rx.await.expect("release leader");
+                        Err::<String, _>("upstream failed".to_owned())
+                    })
+                    .await
+            })
+        };
+        while flights.inflight.lock().expect("inflight").is_empty() {
+            tokio::task::yield_now().await;
+        }
+        let waiter = {
+            let flights = flights.clone();
+            tokio::spawn(async move {
+                flights
+                    .run(translation_flight_key("hash-a"), || async {
+                        Ok::<_, String>("duplicate".to_owned())
+                    })
+                    .await
+            })
+        };
+        tokio::task::yield_now().await;
+        release_tx.send(()).expect("release leader");
+
+        assert_eq!(
+            leader.await.expect("leader"),
+            Err("upstream failed".to_owned())
+        );
+        assert_eq!(
+            waiter.await.expect("waiter"),
+            Err("upstream failed".to_owned())
+        );
+
+        let retried = flights
+            .run(translation_flight_key("hash-a"), || async {
+                Ok::<_, String>("retried".to_owned())
+            })
+            .await;
+        assert_eq!(retried, Ok("retried".to_owned()));
+    }
+
+    #[tokio::test]
+    async fn translation_singleflight_hands_over_when_leader_is_dropped() {
+        let flights = TranslationSingleflight::default();
+        let leader = {
+            let flights = flights.clone();
+            tokio::spawn(async move {
+                flights
+                    .run(translation_flight_key("hash-a"), || {
+                        std::future::pending::<Result<String, String>>()
+                    })
+                    .await
+            })
+        };
+        while flights.inflight.lock().expect("inflight").is_empty() {
+            tokio::task::yield_now().await;
+        }
+        let waiter = {
+            let flights = flights.clone();
+            tokio::spawn(async move {
+                flights
+                    .run(translation_flight_key("hash-a"), || async {
+                        Ok::<_, String>("taken over".to_owned())
+                    })
+                    .await
+            })
+        };
+        tokio::task::yield_now().await;
+        leader.abort();
+
+        assert_eq!(waiter.await.expect("waiter"), Ok("taken over".to_owned()));
+    }
+
     async fn setup_llm_state_with_pool(
         pool: sqlx::SqlitePool,
         base_url: Option<Url>,
+        provider: AiProvider,
     ) -> Arc<AppState> {
         let encryption_key =
             EncryptionKey::from_base64("AAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAA=")
@@ -5798,6 +7394,16 @@ mod tests {
             static_dir: None,
             task_log_dir: std::env::temp_dir().join("octo-rill-ai-tests"),
             job_worker_concurrency: 1,
+            reaction_viewer_ttl_secs: 600,
+            release_backfill_limit: 10,
+            star_purge_grace_days: 14,
+            notification_archive_days: 90,
+            notification_retention_days: 365,
+            task_retention_days: 30,
+            backup_dir: std::env::temp_dir().join("octo-rill-backups-tests"),
+            backup_retention_count: 7,
+            shutdown_grace_secs: 30,
+            task_timeouts: crate::jobs::TaskTimeouts::default(),
             encryption_key: encryption_key.clone(),
             github: GitHubOAuthConfig {
                 client_id: "test-client-id".to_owned(),
@@ -5805,16 +7411,30 @@ mod tests {
                 redirect_url: Url::parse("http://127.0.0.1:58090/auth/callback")
                     .expect("parse github redirect"),
             },
+            github_app: None,
             linuxdo: None,
             ai: base_url.map(|base_url| AiConfig {
+                provider,
                 base_url,
                 model: "gpt-test".to_owned(),
                 api_key: "test-api-key".to_owned(),
+                fallback_models: Vec::new(),
+                startup_probe: crate::config::AiStartupProbe::Off,
             }),
             ai_max_concurrency: 1,
+            ai_translate_concurrency: 2,
+            ai_skip_translation_cjk_percent: 30,
+            ai_daily_token_budget_per_user: None,
+            ai_request_interval_ms: 0,
             ai_daily_at_local: None,
             app_default_time_zone: crate::briefs::DEFAULT_DAILY_BRIEF_TIME_ZONE.to_owned(),
+            smtp: None,
             logging: crate::observability::LoggingThresholds::default(),
+            metrics_token: None,
+            trust_request_id_header: false,
+            trust_forwarded_headers: false,
+            readyz_check_github: false,
+            rate_limits: crate::rate_limit::RateLimits::default(),
         };
         let github_oauth = build_oauth_client(&config).expect("build oauth client");
         let webauthn = crate::state::build_webauthn(&config).expect("build webauthn");
@@ -5839,10 +7459,23 @@ mod tests {
             webauthn,
             encryption_key,
             runtime_owner_id: "ai-test-runtime-owner".to_owned(),
+            feed_events: crate::feed_events::FeedEventHub::default(),
+            admin_mutation_limiter: crate::admin_audit::AdminMutationLimiter::default(),
+            translation_flights: crate::ai::TranslationSingleflight::default(),
+            request_limiter: crate::rate_limit::RequestRateLimiter::default(),
+            github_probe: crate::health::GitHubProbe::default(),
+            github_app_tokens: None,
         })
     }
 
     async fn setup_llm_state_with_ai(base_url: Option<Url>) -> Arc<AppState> {
+        setup_llm_state_with_provider(base_url, AiProvider::OpenAi).await
+    }
+
+    async fn setup_llm_state_with_provider(
+        base_url: Option<Url>,
+        provider: AiProvider,
+    ) -> Arc<AppState> {
         let pool = SqlitePoolOptions::new()
             .max_connections(1)
             .connect("sqlite::memory:")
@@ -5852,7 +7485,7 @@ mod tests {
             .run(&pool)
             .await
             .expect("run migrations");
-        setup_llm_state_with_pool(pool, base_url).await
+        setup_llm_state_with_pool(pool, base_url, provider).await
     }
 
     async fn setup_llm_state_with_busy_timeout(
@@ -5877,7 +7510,7 @@ mod tests {
             .run(&pool)
             .await
             .expect("run migrations");
-        setup_llm_state_with_pool(pool, None).await
+        setup_llm_state_with_pool(pool, None, AiProvider::OpenAi).await
     }
 
     fn test_release_digest(
@@ -5919,9 +7552,120 @@ mod tests {
 
     #[test]
     fn ai_non_retryable_error_keeps_rate_limit_retryable_for_fallback() {
-        assert!(!ai_error_is_non_retryable(&anyhow::anyhow!(
-            "ai returned 429: upstream rate limit"
-        )));
+        let err = anyhow::Error::from(AiError {
+            provider: AiProvider::OpenAi,
+            status: reqwest::StatusCode::TOO_MANY_REQUESTS,
+            code: None,
+            message: "upstream rate limit".to_owned(),
+        });
+        assert_eq!(
+            err.to_string(),
+            "AI returned 429 Too Many Requests: upstream rate limit"
+        );
+        assert!(!ai_error_is_non_retryable(&err));
+    }
+
+    #[test]
+    fn ai_error_classifies_statuses_and_context_length_422() {
+        let upstream = |status: u16, code: Option<&str>, message: &str| AiError {
+            provider: AiProvider::OpenAi,
+            status: reqwest::StatusCode::from_u16(status).expect("status"),
+            code: code.map(str::to_owned),
+            message: message.to_owned(),
+        };
+
+        assert!(upstream(401, None, "invalid api key").is_non_retryable());
+        assert!(upstream(403, None, "forbidden").is_non_retryable());
+        assert!(upstream(422, None, "unsupported parameter").is_non_retryable());
+        assert!(!upstream(422, None, "maximum context length exceeded").is_non_retryable());
+        assert!(
+            !upstream(422, Some("context_length_exceeded"), "prompt too big").is_non_retryable()
+        );
+        assert!(upstream(400, Some("insufficient_quota"), "quota").is_non_retryable());
+        assert!(upstream(404, None, "model not found").is_non_retryable());
+        assert!(!upstream(500, None, "upstream_error").is_non_retryable());
+
+        let wrapped = anyhow::Error::from(upstream(401, None, "invalid api key"))
+            .context("release translation failed");
+        assert!(ai_error_is_non_retryable(&wrapped));
+        assert_eq!(
+            AiError::find(&wrapped).map(|err| err.status),
+            Some(reqwest::StatusCode::UNAUTHORIZED)
+        );
+    }
+
+    #[test]
+    fn extract_error_code_reads_provider_code() {
+        let extract_error_code = |body: &[u8]| chat_provider(AiProvider::OpenAi).error_code(body);
+        assert_eq!(
+            extract_error_code(
+                br#"{"error":{"message":"too long","code":"context_length_exceeded"}}"#
+            )
+            .as_deref(),
+            Some("context_length_exceeded")
+        );
+        assert_eq!(
+            extract_error_code(br#"{"error":{"code":429}}"#).as_deref(),
+            Some("429")
+        );
+        assert_eq!(extract_error_code(br#"{"error":{"message":"x"}}"#), None);
+        assert_eq!(extract_error_code(b"plain text"), None);
+        assert_eq!(
+            chat_provider(AiProvider::Anthropic)
+                .error_code(
+                    br#"{"type":"error","error":{"type":"not_found_error","message":"model: x"}}"#
+                )
+                .as_deref(),
+            Some("not_found_error")
+        );
+    }
+
+    #[test]
+    fn anthropic_errors_classify_by_status_and_type() {
+        let upstream = |status: u16, code: &str, message: &str| AiError {
+            provider: AiProvider::Anthropic,
+            status: reqwest::StatusCode::from_u16(status).expect("status"),
+            code: Some(code.to_owned()),
+            message: message.to_owned(),
+        };
+
+        assert!(upstream(401, "authentication_error", "invalid x-api-key").is_non_retryable());
+        assert!(upstream(404, "not_found_error", "model: claude-x").is_non_retryable());
+        assert!(
+            !upstream(
+                400,
+                "invalid_request_error",
+                "prompt is too long: 210000 tokens > 200000 maximum"
+            )
+            .is_non_retryable()
+        );
+        assert!(!upstream(529, "overloaded_error", "Overloaded").is_non_retryable());
+        assert!(is_retryable_status(
+            reqwest::StatusCode::from_u16(529).expect("status")
+        ));
+    }
+
+    #[test]
+    fn model_limit_lookup_maps_native_anthropic_model_names() {
+        assert_eq!(
+            resolve_model_input_limit_for_model(AiProvider::Anthropic, "claude-3-5-haiku-20241022"),
+            (200_000, MODEL_LIMIT_RESOLUTION_BUILTIN_CATALOG)
+        );
+        assert_eq!(
+            resolve_model_input_limit_for_model(AiProvider::Anthropic, "claude-sonnet-4-20250514"),
+            (200_000, MODEL_LIMIT_RESOLUTION_BUILTIN_CATALOG)
+        );
+        assert_eq!(
+            resolve_model_input_limit_for_model(AiProvider::OpenAi, "claude-3-5-haiku-20241022"),
+            (
+                MODEL_LIMIT_UNKNOWN_FALLBACK,
+                MODEL_LIMIT_RESOLUTION_UNKNOWN_FALLBACK
+            )
+        );
+        assert_eq!(
+            resolve_model_input_limit_for_model(AiProvider::OpenAi, "gpt-4o-mini"),
+            (128_000, MODEL_LIMIT_RESOLUTION_BUILTIN_CATALOG)
+        );
     }
 
     #[test]
@@ -5937,9 +7681,12 @@ mod tests {
     #[tokio::test]
     async fn llm_scheduler_limits_parallelism_and_reports_waiters() {
         let scheduler = Arc::new(LlmScheduler::new(1));
-        let (_wait_ms, first_guard) = scheduler.acquire_slot().await;
+        let (_wait_ms, first_guard) = scheduler.acquire_slot(LlmCallLane::Background).await;
         let queued_scheduler = Arc::clone(&scheduler);
-        let queued = tokio::spawn(async move { queued_scheduler.acquire_slot().await });
+        let queued =
+            tokio::spawn(
+                async move { queued_scheduler.acquire_slot(LlmCallLane::Background).await },
+            );
 
         tokio::time::sleep(Duration::from_millis(25)).await;
 
@@ -5967,40 +7714,230 @@ mod tests {
     }
 
     #[tokio::test]
-    async fn llm_scheduler_resize_wakes_queued_call_without_releasing_active_slot() {
-        let scheduler = Arc::new(LlmScheduler::new(1));
-        let (_wait_ms, first_guard) = scheduler.acquire_slot().await;
+    async fn llm_scheduler_pause_queues_calls_until_resumed() {
+        let scheduler = Arc::new(LlmScheduler::new(2));
+        scheduler.set_paused(true);
         let queued_scheduler = Arc::clone(&scheduler);
-        let queued = tokio::spawn(async move { queued_scheduler.acquire_slot().await });
+        let queued =
+            tokio::spawn(
+                async move { queued_scheduler.acquire_slot(LlmCallLane::Background).await },
+            );
 
         tokio::time::sleep(Duration::from_millis(25)).await;
-        scheduler.set_max_concurrency(2).await;
 
-        let (queued_wait_ms, second_guard) = tokio::time::timeout(Duration::from_secs(1), queued)
+        let status = scheduler.runtime_status();
+        assert!(status.paused);
+        assert_eq!(status.in_flight_calls, 0);
+        assert_eq!(status.available_slots, 2);
+        assert_eq!(status.waiting_calls, 1);
+        assert!(!queued.is_finished());
+
+        scheduler.set_paused(false);
+        let (_wait_ms, guard) = tokio::time::timeout(Duration::from_secs(1), queued)
             .await
-            .expect("queued acquire should be notified after resize")
+            .expect("queued acquire should resume")
             .expect("queued acquire should finish");
-
-        assert!(queued_wait_ms >= 0);
         let status = scheduler.runtime_status();
-        assert_eq!(status.max_concurrency, 2);
-        assert_eq!(status.available_slots, 0);
-        assert_eq!(status.in_flight_calls, 2);
+        assert!(!status.paused);
+        assert_eq!(status.in_flight_calls, 1);
         assert_eq!(status.waiting_calls, 0);
+        drop(guard);
+    }
 
-        drop(second_guard);
+    fn queued_test_call(call_id: &str) -> LlmQueuedCall {
+        LlmQueuedCall {
+            call_id: call_id.to_owned(),
+            source: "tests.llm.queue".to_owned(),
+            requested_by: None,
+            parent_task_id: None,
+            parent_task_type: None,
+            enqueued_at: Utc::now(),
+            estimated_tokens: 42,
+        }
+    }
+
+    #[tokio::test]
+    async fn llm_scheduler_queue_snapshot_reflects_promoted_call() {
+        let scheduler = Arc::new(LlmScheduler::new(1));
+        let (_wait_ms, first_guard) = scheduler.acquire_slot(LlmCallLane::Background).await;
+        let mut queued = Vec::new();
+        for call_id in ["call-a", "call-b", "call-c"] {
+            let queued_scheduler = Arc::clone(&scheduler);
+            let call = queued_test_call(call_id);
+            queued.push(tokio::spawn(async move {
+                queued_scheduler
+                    .acquire_call_slot(LlmCallLane::Background, Some(call))
+                    .await
+            }));
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+
+        let order = |scheduler: &LlmScheduler| {
+            scheduler
+                .queue_snapshot()
+                .into_iter()
+                .map(|entry| (entry.call.call_id, entry.position))
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(
+            order(&scheduler),
+            vec![
+                ("call-a".to_owned(), 1),
+                ("call-b".to_owned(), 2),
+                ("call-c".to_owned(), 3),
+            ]
+        );
+
+        assert!(scheduler.promote_queued_call("call-c"));
+        assert!(!scheduler.promote_queued_call("call-missing"));
+        let snapshot = scheduler.queue_snapshot();
+        assert_eq!(snapshot[0].wait_reason, "no_free_slot");
+        assert_eq!(snapshot[1].wait_reason, "queued_behind_earlier_calls");
+        assert_eq!(snapshot[0].lane, "background");
+        assert_eq!(snapshot[0].call.estimated_tokens, 42);
+        assert_eq!(
+            order(&scheduler),
+            vec![
+                ("call-c".to_owned(), 1),
+                ("call-a".to_owned(), 2),
+                ("call-b".to_owned(), 3),
+            ]
+        );
+
+        drop(first_guard);
+        let (_wait_ms, promoted_guard) =
+            tokio::time::timeout(Duration::from_secs(1), queued.pop().expect("third waiter"))
+                .await
+                .expect("promoted call should be dispatched first")
+                .expect("promoted acquire should finish")
+                .expect("promoted call should get a slot");
+        assert!(queued.iter().all(|handle| !handle.is_finished()));
+        drop(promoted_guard);
+        for handle in queued {
+            let (_wait_ms, guard) = handle
+                .await
+                .expect("queued acquire should finish")
+                .expect("queued call should get a slot");
+            drop(guard);
+        }
+        assert!(scheduler.queue_snapshot().is_empty());
+    }
+
+    #[tokio::test]
+    async fn cancel_queued_llm_call_drops_waiter_and_fails_row() {
+        let state = setup_llm_state().await;
+        let log = LlmCallLogRecord {
+            id: "call-queued-cancel".to_owned(),
+            source: "tests.llm.queue".to_owned(),
+            requested_by: None,
+            parent_task_id: None,
+            parent_task_type: None,
+            parent_translation_batch_id: None,
+            request_id: None,
+        };
+        insert_llm_call(state.as_ref(), &log, "gpt-test", 512, "prompt", Some("[]"))
+            .await
+            .expect("seed llm call");
+
+        let scheduler = Arc::clone(&state.llm_scheduler);
+        scheduler.set_paused(true);
+        let queued_scheduler = Arc::clone(&scheduler);
+        let call = queued_test_call(log.id.as_str());
+        let queued = tokio::spawn(async move {
+            queued_scheduler
+                .acquire_call_slot(LlmCallLane::Background, Some(call))
+                .await
+        });
+        tokio::time::sleep(Duration::from_millis(25)).await;
+        assert_eq!(
+            scheduler.queue_snapshot()[0].wait_reason,
+            "scheduler_paused"
+        );
+
+        assert!(
+            cancel_queued_llm_call(state.as_ref(), log.id.as_str())
+                .await
+                .expect("cancel queued call")
+        );
+        let acquired = tokio::time::timeout(Duration::from_secs(1), queued)
+            .await
+            .expect("dropped waiter should wake")
+            .expect("dropped acquire should finish");
+        assert!(acquired.is_none());
+        assert!(scheduler.queue_snapshot().is_empty());
+        assert_eq!(scheduler.runtime_status().waiting_calls, 0);
+        assert!(
+            !cancel_queued_llm_call(state.as_ref(), log.id.as_str())
+                .await
+                .expect("cancel is idempotent")
+        );
+
+        let row = sqlx::query("SELECT status, error_text, finished_at FROM llm_calls WHERE id = ?")
+            .bind(log.id.as_str())
+            .fetch_one(&state.pool)
+            .await
+            .expect("load cancelled llm call");
+        assert_eq!(row.get::<String, _>("status"), "failed");
+        assert_eq!(
+            row.get::<Option<String>, _>("error_text").as_deref(),
+            Some(LLM_CALL_CANCELLED_BY_ADMIN)
+        );
+        assert!(row.get::<Option<String>, _>("finished_at").is_some());
+    }
+
+    #[tokio::test]
+    async fn llm_scheduler_spaces_dispatches_by_request_interval() {
+        let scheduler = Arc::new(LlmScheduler::new(2));
+        scheduler.set_request_interval_ms(120);
+
+        let (first_wait_ms, first_guard) = scheduler.acquire_slot(LlmCallLane::Background).await;
+        let (second_wait_ms, second_guard) = scheduler.acquire_slot(LlmCallLane::Background).await;
+
+        assert!(first_wait_ms < 100);
+        assert!(second_wait_ms >= 100);
+        drop(second_guard);
+        drop(first_guard);
+    }
+
+    #[tokio::test]
+    async fn llm_scheduler_resize_wakes_queued_call_without_releasing_active_slot() {
+        let scheduler = Arc::new(LlmScheduler::new(1));
+        let (_wait_ms, first_guard) = scheduler.acquire_slot(LlmCallLane::Background).await;
+        let queued_scheduler = Arc::clone(&scheduler);
+        let queued =
+            tokio::spawn(
+                async move { queued_scheduler.acquire_slot(LlmCallLane::Background).await },
+            );
+
+        tokio::time::sleep(Duration::from_millis(25)).await;
+        scheduler.set_max_concurrency(2).await;
+
+        let (queued_wait_ms, second_guard) = tokio::time::timeout(Duration::from_secs(1), queued)
+            .await
+            .expect("queued acquire should be notified after resize")
+            .expect("queued acquire should finish");
+
+        assert!(queued_wait_ms >= 0);
+        let status = scheduler.runtime_status();
+        assert_eq!(status.max_concurrency, 2);
+        assert_eq!(status.available_slots, 0);
+        assert_eq!(status.in_flight_calls, 2);
+        assert_eq!(status.waiting_calls, 0);
+
+        drop(second_guard);
         drop(first_guard);
     }
 
     #[tokio::test]
     async fn llm_scheduler_release_keeps_older_waiter_ahead_of_new_arrival() {
         let scheduler = Arc::new(LlmScheduler::new(1));
-        let (_wait_ms, first_guard) = scheduler.acquire_slot().await;
+        let (_wait_ms, first_guard) = scheduler.acquire_slot(LlmCallLane::Background).await;
         let queued_scheduler = Arc::clone(&scheduler);
         let (queued_acquired_tx, queued_acquired_rx) = tokio::sync::oneshot::channel();
         let (release_queued_tx, release_queued_rx) = tokio::sync::oneshot::channel();
         let queued = tokio::spawn(async move {
-            let (_queued_wait_ms, queued_guard) = queued_scheduler.acquire_slot().await;
+            let (_queued_wait_ms, queued_guard) =
+                queued_scheduler.acquire_slot(LlmCallLane::Background).await;
             let _ = queued_acquired_tx.send(());
             let _ = release_queued_rx.await;
             drop(queued_guard);
@@ -6018,7 +7955,8 @@ mod tests {
         });
 
         let newcomer_started_at = Instant::now();
-        let (_newcomer_wait_ms, newcomer_guard) = scheduler.acquire_slot().await;
+        let (_newcomer_wait_ms, newcomer_guard) =
+            scheduler.acquire_slot(LlmCallLane::Background).await;
         assert!(
             newcomer_started_at.elapsed() >= Duration::from_millis(20),
             "older queued waiter should receive the freed slot first"
@@ -6028,6 +7966,88 @@ mod tests {
         queued.await.expect("queued waiter should finish");
     }
 
+    #[tokio::test]
+    async fn llm_scheduler_grants_interactive_waiter_before_older_background_waiter() {
+        let scheduler = Arc::new(LlmScheduler::new(1));
+        let (_wait_ms, first_guard) = scheduler.acquire_slot(LlmCallLane::Background).await;
+        let (order_tx, mut order_rx) = tokio::sync::mpsc::unbounded_channel();
+
+        let background_scheduler = Arc::clone(&scheduler);
+        let background_tx = order_tx.clone();
+        let background = tokio::spawn(async move {
+            let (_wait_ms, guard) = background_scheduler
+                .acquire_slot(LlmCallLane::Background)
+                .await;
+            let _ = background_tx.send("background");
+            drop(guard);
+        });
+        tokio::time::sleep(Duration::from_millis(25)).await;
+
+        let interactive_scheduler = Arc::clone(&scheduler);
+        let interactive = tokio::spawn(async move {
+            let (_wait_ms, guard) = interactive_scheduler
+                .acquire_slot(LlmCallLane::for_source("api.translate_release"))
+                .await;
+            let _ = order_tx.send("interactive");
+            tokio::time::sleep(Duration::from_millis(10)).await;
+            drop(guard);
+        });
+        tokio::time::sleep(Duration::from_millis(25)).await;
+        assert_eq!(scheduler.runtime_status().waiting_calls, 2);
+
+        drop(first_guard);
+        interactive.await.expect("interactive waiter should finish");
+        background.await.expect("background waiter should finish");
+
+        assert_eq!(order_rx.recv().await, Some("interactive"));
+        assert_eq!(order_rx.recv().await, Some("background"));
+    }
+
+    #[test]
+    fn llm_lane_selection_prefers_interactive_but_never_starves_background() {
+        assert_eq!(
+            LlmCallLane::for_source("api.generate_brief"),
+            LlmCallLane::Interactive
+        );
+        assert_eq!(
+            LlmCallLane::for_source("job.brief.daily_slot"),
+            LlmCallLane::Background
+        );
+
+        assert_eq!(next_waiter_lane(false, false, 0), None);
+        assert_eq!(
+            next_waiter_lane(false, true, 0),
+            Some(LlmCallLane::Background)
+        );
+        assert_eq!(
+            next_waiter_lane(true, false, 9),
+            Some(LlmCallLane::Interactive)
+        );
+
+        let mut streak = 0;
+        let mut grants = Vec::new();
+        for _ in 0..10 {
+            let lane = next_waiter_lane(true, true, streak).expect("both lanes waiting");
+            streak = if lane == LlmCallLane::Interactive {
+                streak + 1
+            } else {
+                0
+            };
+            grants.push(lane);
+        }
+        for window in grants.chunks(LLM_LANE_FAIRNESS_WINDOW) {
+            assert_eq!(
+                window
+                    .iter()
+                    .filter(|lane| **lane == LlmCallLane::Background)
+                    .count(),
+                1,
+                "{grants:?}"
+            );
+        }
+        assert_eq!(grants[0], LlmCallLane::Interactive);
+    }
+
     #[tokio::test]
     async fn llm_scheduler_status_overrides_round_trip() {
         let scheduler = Arc::new(LlmScheduler::new(1));
@@ -6128,6 +8148,7 @@ mod tests {
             parent_task_id: None,
             parent_task_type: None,
             parent_translation_batch_id: None,
+            request_id: None,
         };
 
         insert_llm_call(
@@ -6192,6 +8213,64 @@ mod tests {
         assert_eq!(payload["retry_delay_ms"], serde_json::json!(3000));
     }
 
+    #[tokio::test]
+    async fn probe_ai_health_reports_model_availability_and_caches_result() {
+        let hits = Arc::new(AtomicUsize::new(0));
+        let server_hits = Arc::clone(&hits);
+        let base_url = spawn_test_ai_server(Router::new().route(
+            "/models",
+            axum::routing::get(move || {
+                let hits = Arc::clone(&server_hits);
+                async move {
+                    hits.fetch_add(1, Ordering::SeqCst);
+                    axum::Json(serde_json::json!({
+                        "data": [{ "id": "gpt-other" }, { "id": "gpt-test" }]
+                    }))
+                }
+            }),
+        ))
+        .await;
+        let state = setup_llm_state_with_ai(Some(base_url)).await;
+
+        let first = probe_ai_health(state.as_ref()).await;
+        assert!(first.configured);
+        assert!(first.ok, "{first:?}");
+        assert!(!first.cached);
+        assert_eq!(first.model_available, Some(true));
+        assert!(first.latency_ms.is_some());
+        assert!(first.model_input_limit.is_some_and(|limit| limit > 0));
+
+        let second = probe_ai_health(state.as_ref()).await;
+        assert!(second.cached);
+        assert_eq!(second.checked_at, first.checked_at);
+        assert_eq!(hits.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn probe_ai_health_flags_unlisted_model_and_upstream_errors() {
+        let listed = spawn_test_ai_server(Router::new().route(
+            "/models",
+            axum::routing::get(|| async {
+                axum::Json(serde_json::json!({ "data": [{ "id": "gpt-other" }] }))
+            }),
+        ))
+        .await;
+        let report = probe_ai_health(setup_llm_state_with_ai(Some(listed)).await.as_ref()).await;
+        assert!(!report.ok);
+        assert_eq!(report.model_available, Some(false));
+        assert!(report.error.is_some_and(|error| error.contains("gpt-test")));
+
+        let missing = spawn_test_ai_server(Router::new()).await;
+        let report = probe_ai_health(setup_llm_state_with_ai(Some(missing)).await.as_ref()).await;
+        assert!(!report.ok);
+        assert_eq!(report.model_available, None);
+        assert!(report.error.is_some_and(|error| error.contains("404")));
+
+        let report = probe_ai_health(setup_llm_state_with_ai(None).await.as_ref()).await;
+        assert!(!report.configured);
+        assert!(!report.ok);
+    }
+
     #[tokio::test]
     async fn chat_completion_once_rejects_non_json_success_response_without_retry() {
         let base_url = spawn_test_ai_server(Router::new().route(
@@ -6211,7 +8290,7 @@ mod tests {
         let state = setup_llm_state_with_ai(Some(base_url)).await;
         let ai = state.config.ai.clone().expect("test ai config");
 
-        let err = chat_completion_once(state.as_ref(), &ai, "system", "user", 128)
+        let err = chat_completion_once(state.as_ref(), &ai, "system", "user", 128, None)
             .await
             .expect_err("non-json success response should fail");
 
@@ -6242,7 +8321,7 @@ mod tests {
         let state = setup_llm_state_with_ai(Some(base_url)).await;
         let ai = state.config.ai.clone().expect("test ai config");
 
-        let err = chat_completion_once(state.as_ref(), &ai, "system", "user", 128)
+        let err = chat_completion_once(state.as_ref(), &ai, "system", "user", 128, None)
             .await
             .expect_err("plain text upstream error should fail");
 
@@ -6289,7 +8368,7 @@ mod tests {
         let state = setup_llm_state_with_ai(Some(base_url)).await;
         let ai = state.config.ai.clone().expect("test ai config");
 
-        let output = chat_completion_once(state.as_ref(), &ai, "system", "user", 128)
+        let output = chat_completion_once(state.as_ref(), &ai, "system", "user", 128, None)
             .await
             .expect("successful SSE response should be parsed");
 
@@ -6298,45 +8377,526 @@ mod tests {
         assert_eq!(output.usage.output_tokens, Some(1));
         assert_eq!(output.usage.total_tokens, Some(15));
 
-        let payload = seen_payload
-            .lock()
+        let payload = seen_payload
+            .lock()
+            .await
+            .clone()
+            .expect("request payload should be captured");
+        assert_eq!(payload["stream"], serde_json::json!(false));
+        assert_eq!(payload["model"], serde_json::json!("gpt-test"));
+    }
+
+    #[test]
+    fn chat_completion_sse_parser_reassembles_content_across_split_chunks() {
+        let body = concat!(
+            "data: {\"choices\":[{\"delta\":{\"role\":\"assistant\",\"content\":\"\"}}]}\r\n\r\n",
+            "data: {\"choices\":[{\"delta\":{\"content\":\"发布\"}}]}\n\n",
+            "data: {\"choices\":[{\"delta\":{\"content\":\" notes\"}}]}\n\n",
+            "data: {\"choices\":[],\"usage\":{\"prompt_tokens\":21,\"completion_tokens\":3,",
+            "\"total_tokens\":24,\"prompt_tokens_details\":{\"cached_tokens\":8}}}\n\n",
+            "data: [DONE]\n\n"
+        )
+        .as_bytes();
+
+        let mut parser = ChatCompletionSseParser::default();
+        let mut fragments = Vec::new();
+        // Seven-byte reads split both lines and the multi-byte characters.
+        for chunk in body.chunks(7) {
+            fragments.extend(parser.push(chunk).expect("push chunk"));
+        }
+        fragments.extend(parser.finish().expect("finish stream"));
+
+        assert_eq!(fragments, vec!["发布".to_owned(), " notes".to_owned()]);
+        let output = parser
+            .into_output(&[], "text/event-stream", Some(5))
+            .expect("stream output");
+        assert_eq!(output.content, "发布 notes");
+        assert_eq!(output.first_token_wait_ms, Some(5));
+        assert_eq!(
+            output.usage,
+            LlmTokenUsage {
+                input_tokens: Some(21),
+                output_tokens: Some(3),
+                cached_input_tokens: Some(8),
+                total_tokens: Some(24),
+            }
+        );
+    }
+
+    #[test]
+    fn chat_completion_sse_parser_surfaces_error_events() {
+        let mut parser = ChatCompletionSseParser::default();
+        let err = parser
+            .push(b"event: error\ndata: {\"error\":{\"message\":\"insufficient_quota\"}}\n\n")
+            .expect_err("error event should fail");
+
+        assert!(err.to_string().contains("insufficient_quota"));
+    }
+
+    #[tokio::test]
+    async fn chat_completion_stream_forwards_deltas_and_records_first_token_wait() {
+        let seen_payload = Arc::new(tokio::sync::Mutex::new(None::<Value>));
+        let route_payload = Arc::clone(&seen_payload);
+        let base_url = spawn_test_ai_server(Router::new().route(
+            "/chat/completions",
+            post(move |Json(payload): Json<Value>| {
+                let route_payload = Arc::clone(&route_payload);
+                async move {
+                    *route_payload.lock().await = Some(payload);
+                    (
+                        StatusCode::OK,
+                        [(axum::http::header::CONTENT_TYPE, "text/event-stream")],
+                        concat!(
+                            "data: {\"choices\":[{\"delta\":{\"content\":\"hello\"}}]}\n\n",
+                            "data: {\"choices\":[{\"delta\":{\"content\":\" world\"}}]}\n\n",
+                            "data: {\"choices\":[],\"usage\":{\"prompt_tokens\":9,",
+                            "\"completion_tokens\":2,\"total_tokens\":11}}\n\n",
+                            "data: [DONE]\n\n"
+                        ),
+                    )
+                }
+            }),
+        ))
+        .await;
+        let state = setup_llm_state_with_ai(Some(base_url)).await;
+        let (delta_tx, mut delta_rx) = tokio::sync::mpsc::unbounded_channel();
+
+        let output = chat_completion_stream(state.as_ref(), "system", "user", 128, delta_tx)
+            .await
+            .expect("streamed completion");
+
+        let mut deltas = Vec::new();
+        while let Some(delta) = delta_rx.recv().await {
+            deltas.push(delta);
+        }
+        assert_eq!(
+            deltas,
+            vec![
+                ChatCompletionDelta::Content("hello".to_owned()),
+                ChatCompletionDelta::Content(" world".to_owned()),
+            ]
+        );
+        assert_eq!(output.content, "hello world");
+        assert_eq!(output.usage.input_tokens, Some(9));
+        assert_eq!(output.usage.output_tokens, Some(2));
+        assert_eq!(output.usage.total_tokens, Some(11));
+        assert!(output.first_token_wait_ms.is_some());
+
+        let payload = seen_payload
+            .lock()
+            .await
+            .clone()
+            .expect("request payload should be captured");
+        assert_eq!(payload["stream"], serde_json::json!(true));
+        assert_eq!(
+            payload["stream_options"],
+            serde_json::json!({ "include_usage": true })
+        );
+
+        let (status, first_token_wait_ms, output_tokens) =
+            sqlx::query_as::<_, (String, Option<i64>, Option<i64>)>(
+                r#"
+                SELECT status, first_token_wait_ms, output_tokens
+                FROM llm_calls
+                ORDER BY created_at DESC
+                LIMIT 1
+                "#,
+            )
+            .fetch_one(&state.pool)
+            .await
+            .expect("load llm call");
+        assert_eq!(status, "succeeded");
+        assert!(first_token_wait_ms.is_some());
+        assert_eq!(output_tokens, Some(2));
+    }
+
+    /// Golden request and response bodies per provider, sent through the
+    /// same `chat_completion` path every caller uses.
+    #[tokio::test]
+    async fn chat_completion_speaks_each_provider_wire_format() {
+        let cases = [
+            (
+                AiProvider::OpenAi,
+                "/chat/completions",
+                serde_json::json!({
+                    "model": "gpt-test",
+                    "messages": [
+                        { "role": "system", "content": "system" },
+                        { "role": "user", "content": "user" }
+                    ],
+                    "temperature": 0.2,
+                    "max_tokens": 128,
+                    "stream": false
+                }),
+                serde_json::json!({
+                    "id": "chatcmpl-1",
+                    "choices": [
+                        { "index": 0, "message": { "role": "assistant", "content": " hello " } }
+                    ],
+                    "usage": {
+                        "prompt_tokens": 30,
+                        "completion_tokens": 5,
+                        "total_tokens": 35,
+                        "prompt_tokens_details": { "cached_tokens": 12 }
+                    }
+                }),
+            ),
+            (
+                AiProvider::Anthropic,
+                "/messages",
+                serde_json::json!({
+                    "model": "gpt-test",
+                    "system": "system",
+                    "messages": [{ "role": "user", "content": "user" }],
+                    "temperature": 0.2,
+                    "max_tokens": 128,
+                    "stream": false
+                }),
+                serde_json::json!({
+                    "id": "msg_1",
+                    "type": "message",
+                    "role": "assistant",
+                    "content": [{ "type": "text", "text": " hello " }],
+                    "stop_reason": "end_turn",
+                    "usage": {
+                        "input_tokens": 18,
+                        "cache_read_input_tokens": 12,
+                        "cache_creation_input_tokens": 0,
+                        "output_tokens": 5
+                    }
+                }),
+            ),
+        ];
+
+        for (provider, path, expected_request, response) in cases {
+            let seen = Arc::new(tokio::sync::Mutex::new(None::<(Value, Option<String>)>));
+            let route_seen = Arc::clone(&seen);
+            let base_url = spawn_test_ai_server(Router::new().route(
+                path,
+                post(
+                    move |headers: axum::http::HeaderMap, Json(payload): Json<Value>| {
+                        let route_seen = Arc::clone(&route_seen);
+                        let response = response.clone();
+                        async move {
+                            let auth = headers
+                                .get("x-api-key")
+                                .or_else(|| headers.get(axum::http::header::AUTHORIZATION))
+                                .and_then(|value| value.to_str().ok())
+                                .map(str::to_owned);
+                            *route_seen.lock().await = Some((payload, auth));
+                            Json(response)
+                        }
+                    },
+                ),
+            ))
+            .await;
+            let state = setup_llm_state_with_provider(Some(base_url), provider).await;
+
+            let content = chat_completion(state.as_ref(), "system", "user", 128)
+                .await
+                .expect("chat completion");
+            assert_eq!(content, "hello", "{provider:?}");
+
+            let (payload, auth) = seen.lock().await.clone().expect("captured request");
+            assert_eq!(payload, expected_request, "{provider:?}");
+            let expected_auth = match provider {
+                AiProvider::OpenAi => "Bearer test-api-key",
+                AiProvider::Anthropic => "test-api-key",
+            };
+            assert_eq!(auth.as_deref(), Some(expected_auth), "{provider:?}");
+
+            let tokens = sqlx::query_as::<_, (Option<i64>, Option<i64>, Option<i64>, Option<i64>)>(
+                r#"
+                SELECT input_tokens, output_tokens, cached_input_tokens, total_tokens
+                FROM llm_calls
+                "#,
+            )
+            .fetch_one(&state.pool)
+            .await
+            .expect("load llm call tokens");
+            assert_eq!(
+                tokens,
+                (Some(30), Some(5), Some(12), Some(35)),
+                "{provider:?}"
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn chat_completion_stream_decodes_anthropic_events() {
+        let base_url = spawn_test_ai_server(Router::new().route(
+            "/messages",
+            post(|Json(payload): Json<Value>| async move {
+                assert_eq!(payload["stream"], serde_json::json!(true));
+                assert!(payload.get("stream_options").is_none());
+                (
+                    StatusCode::OK,
+                    [(axum::http::header::CONTENT_TYPE, "text/event-stream")],
+                    concat!(
+                        "event: message_start\n",
+                        "data: {\"type\":\"message_start\",\"message\":{\"id\":\"msg_1\",",
+                        "\"content\":[],\"usage\":{\"input_tokens\":9,",
+                        "\"cache_read_input_tokens\":4,\"output_tokens\":1}}}\n\n",
+                        "event: content_block_start\n",
+                        "data: {\"type\":\"content_block_start\",\"index\":0,",
+                        "\"content_block\":{\"type\":\"text\",\"text\":\"\"}}\n\n",
+                        "event: ping\n",
+                        "data: {\"type\":\"ping\"}\n\n",
+                        "event: content_block_delta\n",
+                        "data: {\"type\":\"content_block_delta\",\"index\":0,",
+                        "\"delta\":{\"type\":\"text_delta\",\"text\":\"hello\"}}\n\n",
+                        "event: content_block_delta\n",
+                        "data: {\"type\":\"content_block_delta\",\"index\":0,",
+                        "\"delta\":{\"type\":\"text_delta\",\"text\":\" world\"}}\n\n",
+                        "event: content_block_stop\n",
+                        "data: {\"type\":\"content_block_stop\",\"index\":0}\n\n",
+                        "event: message_delta\n",
+                        "data: {\"type\":\"message_delta\",\"delta\":{\"stop_reason\":\"end_turn\"},",
+                        "\"usage\":{\"output_tokens\":2}}\n\n",
+                        "event: message_stop\n",
+                        "data: {\"type\":\"message_stop\"}\n\n"
+                    ),
+                )
+            }),
+        ))
+        .await;
+        let state = setup_llm_state_with_provider(Some(base_url), AiProvider::Anthropic).await;
+        let (delta_tx, mut delta_rx) = tokio::sync::mpsc::unbounded_channel();
+
+        let output = chat_completion_stream(state.as_ref(), "system", "user", 128, delta_tx)
+            .await
+            .expect("streamed completion");
+
+        let mut deltas = Vec::new();
+        while let Some(delta) = delta_rx.recv().await {
+            deltas.push(delta);
+        }
+        assert_eq!(
+            deltas,
+            vec![
+                ChatCompletionDelta::Content("hello".to_owned()),
+                ChatCompletionDelta::Content(" world".to_owned()),
+            ]
+        );
+        assert_eq!(output.content, "hello world");
+        assert_eq!(
+            output.usage,
+            LlmTokenUsage {
+                input_tokens: Some(13),
+                output_tokens: Some(2),
+                cached_input_tokens: Some(4),
+                total_tokens: Some(15),
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn chat_completion_once_marks_upstream_401_inside_502_as_non_retryable() {
+        let base_url = spawn_test_ai_server(Router::new().route(
+            "/chat/completions",
+            post(|| async {
+                (
+                    StatusCode::BAD_GATEWAY,
+                    Json(serde_json::json!({
+                        "error": {
+                            "message": "AppChatReverse: Chat failed, 401",
+                            "code": "upstream_error"
+                        }
+                    })),
+                )
+            }),
+        ))
+        .await;
+        let state = setup_llm_state_with_ai(Some(base_url)).await;
+        let ai = state.config.ai.clone().expect("test ai config");
+
+        let err = chat_completion_once(state.as_ref(), &ai, "system", "user", 128, None)
+            .await
+            .expect_err("upstream 401 should fail");
+
+        assert!(!err.retryable);
+        assert!(
+            err.err
+                .to_string()
+                .contains("AppChatReverse: Chat failed, 401")
+        );
+    }
+
+    #[tokio::test]
+    async fn chat_completion_falls_back_to_next_model_after_forbidden_response() {
+        let seen_models = Arc::new(tokio::sync::Mutex::new(Vec::<String>::new()));
+        let route_models = Arc::clone(&seen_models);
+        let base_url = spawn_test_ai_server(Router::new().route(
+            "/chat/completions",
+            post(move |Json(payload): Json<Value>| {
+                let route_models = Arc::clone(&route_models);
+                async move {
+                    let model = payload["model"].as_str().unwrap_or_default().to_owned();
+                    route_models.lock().await.push(model.clone());
+                    if model == "model-a" {
+                        return (
+                            StatusCode::FORBIDDEN,
+                            Json(serde_json::json!({
+                                "error": { "message": "model access denied" }
+                            })),
+                        );
+                    }
+                    (
+                        StatusCode::OK,
+                        Json(serde_json::json!({
+                            "choices": [{ "message": { "content": "from b" } }]
+                        })),
+                    )
+                }
+            }),
+        ))
+        .await;
+        let mut state = setup_llm_state_with_ai(Some(base_url)).await;
+        let ai = Arc::get_mut(&mut state)
+            .expect("exclusive test state")
+            .config
+            .ai
+            .as_mut()
+            .expect("ai config");
+        ai.model = "model-a".to_owned();
+        ai.fallback_models = vec!["model-a".to_owned(), "model-b".to_owned()];
+
+        let output = chat_completion(state.as_ref(), "system", "user", 128)
+            .await
+            .expect("fallback model should answer");
+
+        assert_eq!(output, "from b");
+        assert_eq!(
+            seen_models.lock().await.clone(),
+            vec!["model-a".to_owned(), "model-b".to_owned()]
+        );
+        let rows = sqlx::query_as::<_, (String, String, i64, Option<String>)>(
+            r#"
+            SELECT model, status, attempt_count, error_text
+            FROM llm_calls
+            ORDER BY attempt_count ASC
+            "#,
+        )
+        .fetch_all(&state.pool)
+        .await
+        .expect("load llm calls");
+        assert_eq!(rows.len(), 2);
+        assert_eq!(rows[0].0, "model-a");
+        assert_eq!(rows[0].1, "failed");
+        assert_eq!(rows[0].2, 1);
+        assert!(rows[0].3.as_deref().is_some_and(|err| err.contains("403")));
+        assert_eq!(rows[1].0, "model-b");
+        assert_eq!(rows[1].1, "succeeded");
+        assert_eq!(rows[1].2, 2);
+        assert_eq!(rows[1].3, None);
+    }
+
+    #[tokio::test]
+    async fn chat_completion_enforces_daily_token_budget_per_user() {
+        let seen_attempts = Arc::new(AtomicUsize::new(0));
+        let route_attempts = Arc::clone(&seen_attempts);
+        let base_url = spawn_test_ai_server(Router::new().route(
+            "/chat/completions",
+            post(move || {
+                let route_attempts = Arc::clone(&route_attempts);
+                async move {
+                    route_attempts.fetch_add(1, Ordering::SeqCst);
+                    (
+                        StatusCode::OK,
+                        Json(serde_json::json!({
+                            "choices": [{ "message": { "content": "ok" } }],
+                            "usage": { "prompt_tokens": 1, "completion_tokens": 1, "total_tokens": 2 }
+                        })),
+                    )
+                }
+            }),
+        ))
+        .await;
+        let mut state = setup_llm_state_with_ai(Some(base_url)).await;
+        Arc::get_mut(&mut state)
+            .expect("exclusive test state")
+            .config
+            .ai_daily_token_budget_per_user = Some(10_000);
+
+        let now = Utc::now().to_rfc3339();
+        sqlx::query(
+            r#"
+            INSERT INTO users (id, github_user_id, login, created_at, updated_at)
+            VALUES (?, ?, ?, ?, ?)
+            "#,
+        )
+        .bind("user-budget")
+        .bind(3001_i64)
+        .bind("budget-user")
+        .bind(now.as_str())
+        .bind(now.as_str())
+        .execute(&state.pool)
+        .await
+        .expect("insert budget user");
+        let yesterday = (Utc::now() - chrono::Duration::days(1)).to_rfc3339();
+        for (id, total_tokens, created_at) in [
+            ("llm-budget-today", 9_900_i64, now.as_str()),
+            ("llm-budget-yesterday", 50_000_i64, yesterday.as_str()),
+        ] {
+            sqlx::query(
+                r#"
+                INSERT INTO llm_calls (
+                  id, status, source, model, requested_by, max_tokens, prompt_text,
+                  total_tokens, created_at, updated_at
+                ) VALUES (?, 'succeeded', 'tests.llm.budget', 'gpt-test', ?, 128, 'prompt', ?, ?, ?)
+                "#,
+            )
+            .bind(id)
+            .bind("user-budget")
+            .bind(total_tokens)
+            .bind(created_at)
+            .bind(created_at)
+            .execute(&state.pool)
+            .await
+            .expect("seed llm usage");
+        }
+        let context = || LlmCallContext {
+            source: "api.translate_release".to_owned(),
+            requested_by: Some("user-budget".to_owned()),
+            parent_task_id: None,
+            parent_task_type: None,
+            parent_translation_batch_id: None,
+        };
+
+        let allowed = with_llm_call_context(context(), async {
+            chat_completion(state.as_ref(), "system", "user", 128).await
+        })
+        .await
+        .expect("99% of budget should still dispatch");
+        assert_eq!(allowed, "ok");
+        assert_eq!(seen_attempts.load(Ordering::SeqCst), 1);
+
+        sqlx::query("UPDATE llm_calls SET total_tokens = 10100 WHERE id = 'llm-budget-today'")
+            .execute(&state.pool)
             .await
-            .clone()
-            .expect("request payload should be captured");
-        assert_eq!(payload["stream"], serde_json::json!(false));
-        assert_eq!(payload["model"], serde_json::json!("gpt-test"));
-    }
-
-    #[tokio::test]
-    async fn chat_completion_once_marks_upstream_401_inside_502_as_non_retryable() {
-        let base_url = spawn_test_ai_server(Router::new().route(
-            "/chat/completions",
-            post(|| async {
-                (
-                    StatusCode::BAD_GATEWAY,
-                    Json(serde_json::json!({
-                        "error": {
-                            "message": "AppChatReverse: Chat failed, 401",
-                            "code": "upstream_error"
-                        }
-                    })),
-                )
-            }),
-        ))
-        .await;
-        let state = setup_llm_state_with_ai(Some(base_url)).await;
-        let ai = state.config.ai.clone().expect("test ai config");
+            .expect("raise usage over budget");
+        let calls_before = sqlx::query_scalar::<_, i64>("SELECT COUNT(*) FROM llm_calls")
+            .fetch_one(&state.pool)
+            .await
+            .expect("count llm calls");
 
-        let err = chat_completion_once(state.as_ref(), &ai, "system", "user", 128)
+        let err = with_llm_call_context(context(), async {
+            chat_completion(state.as_ref(), "system", "user", 128).await
+        })
+        .await
+        .expect_err("101% of budget should be rejected");
+        assert!(is_ai_budget_exceeded_error(&err));
+        assert_eq!(seen_attempts.load(Ordering::SeqCst), 1);
+        let calls_after = sqlx::query_scalar::<_, i64>("SELECT COUNT(*) FROM llm_calls")
+            .fetch_one(&state.pool)
             .await
-            .expect_err("upstream 401 should fail");
+            .expect("count llm calls");
+        assert_eq!(calls_after, calls_before);
 
-        assert!(!err.retryable);
-        assert!(
-            err.err
-                .to_string()
-                .contains("AppChatReverse: Chat failed, 401")
-        );
+        let unattributed = chat_completion(state.as_ref(), "system", "user", 128)
+            .await
+            .expect("calls without a requesting user are not limited");
+        assert_eq!(unattributed, "ok");
     }
 
     #[tokio::test]
@@ -6844,6 +9404,7 @@ mod tests {
             parent_task_id: None,
             parent_task_type: None,
             parent_translation_batch_id: None,
+            request_id: None,
         };
 
         insert_llm_call(state.as_ref(), &log, "gpt-test", 512, "prompt", Some("[]"))
@@ -6902,6 +9463,7 @@ mod tests {
             parent_task_id: None,
             parent_task_type: None,
             parent_translation_batch_id: None,
+            request_id: None,
         };
 
         insert_llm_call(state.as_ref(), &log, "gpt-test", 512, "prompt", Some("[]"))
@@ -6965,6 +9527,7 @@ mod tests {
             parent_task_id: None,
             parent_task_type: None,
             parent_translation_batch_id: None,
+            request_id: None,
         };
 
         insert_llm_call(state.as_ref(), &log, "gpt-test", 512, "prompt", Some("[]"))
@@ -7024,6 +9587,7 @@ mod tests {
             parent_task_id: None,
             parent_task_type: None,
             parent_translation_batch_id: None,
+            request_id: None,
         };
 
         insert_llm_call(state.as_ref(), &log, "gpt-test", 512, "prompt", Some("[]"))
@@ -7117,56 +9681,6 @@ mod tests {
         );
     }
 
-    #[test]
-    fn daily_window_after_boundary() {
-        let at = NaiveTime::from_hms_opt(8, 0, 0).unwrap();
-        let now = NaiveDate::from_ymd_opt(2026, 2, 21)
-            .unwrap()
-            .and_hms_opt(9, 0, 0)
-            .unwrap();
-        let (start, end, key_date) = compute_daily_window_naive(now, at);
-        assert_eq!(
-            start,
-            NaiveDate::from_ymd_opt(2026, 2, 20)
-                .unwrap()
-                .and_hms_opt(8, 0, 0)
-                .unwrap()
-        );
-        assert_eq!(
-            end,
-            NaiveDate::from_ymd_opt(2026, 2, 21)
-                .unwrap()
-                .and_hms_opt(8, 0, 0)
-                .unwrap()
-        );
-        assert_eq!(key_date, NaiveDate::from_ymd_opt(2026, 2, 21).unwrap());
-    }
-
-    #[test]
-    fn daily_window_before_boundary() {
-        let at = NaiveTime::from_hms_opt(8, 0, 0).unwrap();
-        let now = NaiveDate::from_ymd_opt(2026, 2, 21)
-            .unwrap()
-            .and_hms_opt(7, 0, 0)
-            .unwrap();
-        let (start, end, key_date) = compute_daily_window_naive(now, at);
-        assert_eq!(
-            start,
-            NaiveDate::from_ymd_opt(2026, 2, 19)
-                .unwrap()
-                .and_hms_opt(8, 0, 0)
-                .unwrap()
-        );
-        assert_eq!(
-            end,
-            NaiveDate::from_ymd_opt(2026, 2, 20)
-                .unwrap()
-                .and_hms_opt(8, 0, 0)
-                .unwrap()
-        );
-        assert_eq!(key_date, NaiveDate::from_ymd_opt(2026, 2, 20).unwrap());
-    }
-
     #[test]
     fn parse_legacy_brief_window_from_markdown_resolves_naive_local_timestamps() {
         let markdown = concat!(
@@ -7205,22 +9719,6 @@ mod tests {
         assert_eq!(window.end_utc.to_rfc3339(), "2026-03-07T00:00:00+00:00");
     }
 
-    #[test]
-    fn recent_key_dates_returns_descending_days() {
-        let at = NaiveTime::from_hms_opt(8, 0, 0).unwrap();
-        let now = local_from_naive(
-            NaiveDate::from_ymd_opt(2026, 2, 21)
-                .unwrap()
-                .and_hms_opt(10, 0, 0)
-                .unwrap(),
-        );
-        let dates = recent_key_dates(at, now, 3);
-        assert_eq!(dates.len(), 3);
-        assert_eq!(dates[0], NaiveDate::from_ymd_opt(2026, 2, 21).unwrap());
-        assert_eq!(dates[1], NaiveDate::from_ymd_opt(2026, 2, 20).unwrap());
-        assert_eq!(dates[2], NaiveDate::from_ymd_opt(2026, 2, 19).unwrap());
-    }
-
     #[test]
     fn extract_github_links_filters_non_github() {
         let links = extract_github_links(
@@ -7744,50 +10242,333 @@ mod tests {
         .await;
         let state = setup_llm_state_with_ai(Some(base_url)).await;
 
-        let built = build_brief_content_from_digests(
-            state.as_ref(),
-            vec![ReleaseDigest {
-                release_id: 42,
-                full_name: "acme/rocket".to_owned(),
-                title: "v1.0.0".to_owned(),
-                body: "fix: keep formatting stable".to_owned(),
-                html_url: "https://github.com/acme/rocket/releases/tag/v1.0.0".to_owned(),
-                published_at: "2026-03-06T12:00:00Z".to_owned(),
-                is_prerelease: false,
-            }],
-            vec![SocialActivityDigest {
-                kind: "follower_received".to_owned(),
-                repo_full_name: None,
-                actor_github_user_id: 1,
-                actor_login: "alice".to_owned(),
-                actor_html_url: Some("https://github.com/alice".to_owned()),
-                occurred_at: "2026-03-06T18:00:00Z".to_owned(),
-            }],
+        let built = build_brief_content_from_digests(
+            state.as_ref(),
+            vec![ReleaseDigest {
+                release_id: 42,
+                full_name: "acme/rocket".to_owned(),
+                title: "v1.0.0".to_owned(),
+                body: "fix: keep formatting stable".to_owned(),
+                html_url: "https://github.com/acme/rocket/releases/tag/v1.0.0".to_owned(),
+                published_at: "2026-03-06T12:00:00Z".to_owned(),
+                is_prerelease: false,
+            }],
+            vec![SocialActivityDigest {
+                kind: "follower_received".to_owned(),
+                repo_full_name: None,
+                actor_github_user_id: 1,
+                actor_login: "alice".to_owned(),
+                actor_html_url: Some("https://github.com/alice".to_owned()),
+                occurred_at: "2026-03-06T18:00:00Z".to_owned(),
+            }],
+        )
+        .await
+        .expect("build brief content");
+
+        assert!(brief_content_is_canonical(&built.content_markdown));
+        assert!(
+            built
+                .content_markdown
+                .contains("  - fix: keep formatting stable")
+        );
+        assert!(
+            !built
+                .content_markdown
+                .contains("fix: keep formatting stable  \n")
+        );
+        assert!(built.content_markdown.contains("## 获星与关注"));
+        assert!(built.content_markdown.contains("### 关注"));
+        assert!(!built.content_markdown.contains("### 获星"));
+        assert_eq!(
+            extract_internal_release_ref_sequence(&built.content_markdown),
+            vec![
+                parse_internal_release_ref("/acme/rocket/releases/tag/v1.0.0?from=briefs")
+                    .expect("canonical internal release ref")
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn build_brief_content_injects_release_links_and_round_trips_sources() {
+        let base_url = spawn_test_ai_server(Router::new().route(
+            "/chat/completions",
+            post(|Json(payload): Json<Value>| async move {
+                let prompt = payload["messages"][1]["content"]
+                    .as_str()
+                    .expect("user prompt should be present");
+
+                let content = if let Some((_, original)) = prompt.split_once("日报原文：\n") {
+                    // The model rewords a bullet and drops one canonical link.
+                    original
+                        .replace(
+                            " · [GitHub Release](https://github.com/acme/rocket/releases/tag/v1.0.0)",
+                            "",
+                        )
+                        .replace("ship the rocket", "发射火箭")
+                } else {
+                    serde_json::json!({
+                        "items": [
+                            { "release_id": 42, "summary_bullets": ["ship the rocket"] },
+                            { "release_id": 43, "summary_bullets": ["polish the comet"] }
+                        ]
+                    })
+                    .to_string()
+                };
+
+                (
+                    StatusCode::OK,
+                    Json(serde_json::json!({
+                        "choices": [{
+                            "message": { "content": content }
+                        }]
+                    })),
+                )
+            }),
+        ))
+        .await;
+        let state = setup_llm_state_with_ai(Some(base_url)).await;
+        let now = "2026-03-07T09:00:00Z";
+
+        sqlx::query(
+            r#"
+            INSERT INTO users (id, github_user_id, login, created_at, updated_at)
+            VALUES (?, ?, ?, ?, ?)
+            "#,
+        )
+        .bind("user-brief-sources")
+        .bind(2007_i64)
+        .bind("brief-sources")
+        .bind(now)
+        .bind(now)
+        .execute(&state.pool)
+        .await
+        .expect("insert user");
+
+        let digests = [
+            (42_i64, "acme/rocket", "2026-03-06T12:00:00Z"),
+            (43, "acme/comet", "2026-03-06T18:00:00Z"),
+        ]
+        .into_iter()
+        .map(|(release_id, full_name, published_at)| ReleaseDigest {
+            release_id,
+            full_name: full_name.to_owned(),
+            title: "v1.0.0".to_owned(),
+            body: "- release notes".to_owned(),
+            html_url: format!("https://github.com/{full_name}/releases/tag/v1.0.0"),
+            published_at: published_at.to_owned(),
+            is_prerelease: false,
+        })
+        .collect::<Vec<_>>();
+        for digest in &digests {
+            sqlx::query(
+                r#"
+                INSERT INTO repo_releases (
+                  id, repo_id, release_id, node_id, tag_name, name, body, html_url,
+                  published_at, created_at, is_prerelease, is_draft, updated_at
+                )
+                VALUES (?, ?, ?, ?, 'v1.0.0', 'v1.0.0', '', ?, ?, ?, 0, 0, ?)
+                "#,
+            )
+            .bind(format!("repo-release-sources-{}", digest.release_id))
+            .bind(digest.release_id)
+            .bind(digest.release_id)
+            .bind(format!("node-{}", digest.release_id))
+            .bind(&digest.html_url)
+            .bind(&digest.published_at)
+            .bind(&digest.published_at)
+            .bind(now)
+            .execute(&state.pool)
+            .await
+            .expect("insert repo release");
+        }
+
+        let built = build_brief_content_from_digests(state.as_ref(), digests, Vec::new())
+            .await
+            .expect("build brief content");
+
+        assert!(built.content_markdown.contains("  - 发射火箭"));
+        for full_name in ["acme/rocket", "acme/comet"] {
+            let link =
+                format!("· [GitHub Release](https://github.com/{full_name}/releases/tag/v1.0.0)");
+            assert_eq!(
+                built.content_markdown.matches(&link).count(),
+                1,
+                "{full_name} keeps exactly one canonical link"
+            );
+        }
+        let expected_sources = vec![
+            BriefSource {
+                section: "acme/comet".to_owned(),
+                release_id: "43".to_owned(),
+                title: "v1.0.0".to_owned(),
+                html_url: "https://github.com/acme/comet/releases/tag/v1.0.0".to_owned(),
+            },
+            BriefSource {
+                section: "acme/rocket".to_owned(),
+                release_id: "42".to_owned(),
+                title: "v1.0.0".to_owned(),
+                html_url: "https://github.com/acme/rocket/releases/tag/v1.0.0".to_owned(),
+            },
+        ];
+        assert_eq!(built.sources, expected_sources);
+
+        let preferences = briefs::DailyBriefPreferences {
+            local_time: NaiveTime::from_hms_opt(8, 0, 0).expect("08:00"),
+            time_zone: "Asia/Shanghai".to_owned(),
+        };
+        let window = briefs::compute_daily_window_for_key_date(
+            &preferences,
+            NaiveDate::from_ymd_opt(2026, 3, 7).expect("date"),
+        )
+        .expect("window");
+        let stored = upsert_daily_brief_snapshot(
+            state.as_ref(),
+            "user-brief-sources",
+            &window,
+            &built,
+            "manual",
+        )
+        .await
+        .expect("store brief snapshot");
+
+        let reloaded = load_stored_brief_snapshot(state.as_ref(), &stored.id)
+            .await
+            .expect("reload brief snapshot");
+        assert_eq!(reloaded.sources, expected_sources);
+    }
+
+    #[tokio::test]
+    async fn brief_snapshot_records_sources_and_flags_releases_the_summary_dropped() {
+        let base_url = spawn_test_ai_server(Router::new().route(
+            "/chat/completions",
+            post(|Json(payload): Json<Value>| async move {
+                let prompt = payload["messages"][1]["content"]
+                    .as_str()
+                    .expect("user prompt should be present");
+                // Every summary call only ever covers release 42.
+                let content = if let Some((_, original)) = prompt.split_once("日报原文：\n") {
+                    original.to_owned()
+                } else {
+                    serde_json::json!({
+                        "items": [{ "release_id": 42, "summary_bullets": ["ship the rocket"] }]
+                    })
+                    .to_string()
+                };
+                (
+                    StatusCode::OK,
+                    Json(serde_json::json!({
+                        "choices": [{
+                            "message": { "content": content }
+                        }]
+                    })),
+                )
+            }),
+        ))
+        .await;
+        let state = setup_llm_state_with_ai(Some(base_url)).await;
+        let now = "2026-03-07T09:00:00Z";
+        let user_id = "user-brief-source-rows";
+
+        sqlx::query(
+            r#"
+            INSERT INTO users (id, github_user_id, login, created_at, updated_at)
+            VALUES (?, ?, ?, ?, ?)
+            "#,
+        )
+        .bind(user_id)
+        .bind(2008_i64)
+        .bind("brief-source-rows")
+        .bind(now)
+        .bind(now)
+        .execute(&state.pool)
+        .await
+        .expect("insert user");
+
+        let digests = [
+            (42_i64, "acme/rocket", "2026-03-06T12:00:00Z"),
+            (43, "acme/comet", "2026-03-06T18:00:00Z"),
+        ]
+        .into_iter()
+        .map(|(release_id, full_name, published_at)| ReleaseDigest {
+            release_id,
+            full_name: full_name.to_owned(),
+            title: "v1.0.0".to_owned(),
+            body: "- release notes".to_owned(),
+            html_url: format!("https://github.com/{full_name}/releases/tag/v1.0.0"),
+            published_at: published_at.to_owned(),
+            is_prerelease: false,
+        })
+        .collect::<Vec<_>>();
+        for digest in &digests {
+            sqlx::query(
+                r#"
+                INSERT INTO repo_releases (
+                  id, repo_id, release_id, node_id, tag_name, name, body, html_url,
+                  published_at, created_at, is_prerelease, is_draft, updated_at
+                )
+                VALUES (?, ?, ?, ?, 'v1.0.0', 'v1.0.0', '', ?, ?, ?, 0, 0, ?)
+                "#,
+            )
+            .bind(format!("repo-release-source-rows-{}", digest.release_id))
+            .bind(digest.release_id)
+            .bind(digest.release_id)
+            .bind(format!("node-{}", digest.release_id))
+            .bind(&digest.html_url)
+            .bind(&digest.published_at)
+            .bind(&digest.published_at)
+            .bind(now)
+            .execute(&state.pool)
+            .await
+            .expect("insert repo release");
+        }
+
+        let built = build_brief_content_from_digests(state.as_ref(), digests, Vec::new())
+            .await
+            .expect("build brief content");
+        assert_eq!(built.summarized_release_ids, HashSet::from([42_i64]));
+        assert_eq!(built.coverage.releases_total, 2);
+        assert_eq!(built.coverage.summarized_release_ids, vec!["42".to_owned()]);
+
+        let preferences = briefs::DailyBriefPreferences {
+            local_time: NaiveTime::from_hms_opt(8, 0, 0).expect("08:00"),
+            time_zone: "Asia/Shanghai".to_owned(),
+        };
+        let window = briefs::compute_daily_window_for_key_date(
+            &preferences,
+            NaiveDate::from_ymd_opt(2026, 3, 7).expect("date"),
+        )
+        .expect("window");
+        let stored =
+            upsert_daily_brief_snapshot(state.as_ref(), user_id, &window, &built, "manual")
+                .await
+                .expect("store brief snapshot");
+
+        let rows = sqlx::query_as::<_, (i64, String, i64, i64)>(
+            r#"
+            SELECT release_id, section, ordinal, included
+            FROM brief_sources
+            WHERE brief_id = ?
+            ORDER BY ordinal ASC
+            "#,
         )
+        .bind(&stored.id)
+        .fetch_all(&state.pool)
         .await
-        .expect("build brief content");
-
-        assert!(brief_content_is_canonical(&built.content_markdown));
-        assert!(
-            built
-                .content_markdown
-                .contains("  - fix: keep formatting stable")
-        );
-        assert!(
-            !built
-                .content_markdown
-                .contains("fix: keep formatting stable  \n")
-        );
-        assert!(built.content_markdown.contains("## 获星与关注"));
-        assert!(built.content_markdown.contains("### 关注"));
-        assert!(!built.content_markdown.contains("### 获星"));
+        .expect("load brief sources");
         assert_eq!(
-            extract_internal_release_ref_sequence(&built.content_markdown),
+            rows,
             vec![
-                parse_internal_release_ref("/acme/rocket/releases/tag/v1.0.0?from=briefs")
-                    .expect("canonical internal release ref")
+                (43, "acme/comet".to_owned(), 0, 0),
+                (42, "acme/rocket".to_owned(), 1, 1),
             ]
         );
+        let sources_tracked =
+            sqlx::query_scalar::<_, i64>(r#"SELECT sources_tracked FROM briefs WHERE id = ?"#)
+                .bind(&stored.id)
+                .fetch_one(&state.pool)
+                .await
+                .expect("load sources flag");
+        assert_eq!(sources_tracked, 1);
     }
 
     #[tokio::test]
@@ -8409,6 +11190,366 @@ mod tests {
         assert_eq!(stored.release_ids, vec![407]);
     }
 
+    #[tokio::test]
+    async fn build_brief_content_reports_coverage_for_window_releases() {
+        let state = setup_llm_state().await;
+        let now = "2026-03-07T09:00:00Z";
+
+        sqlx::query(
+            r#"
+            INSERT INTO users (id, github_user_id, login, created_at, updated_at)
+            VALUES (?, ?, ?, ?, ?)
+            "#,
+        )
+        .bind("user-brief-coverage")
+        .bind(2006_i64)
+        .bind("brief-coverage")
+        .bind(now)
+        .bind(now)
+        .execute(&state.pool)
+        .await
+        .expect("insert user");
+
+        for (repo_id, full_name) in [(1_i64, "acme/rocket"), (2_i64, "acme/comet")] {
+            let (owner, name) = full_name.split_once('/').expect("full name");
+            sqlx::query(
+                r#"
+                INSERT INTO starred_repos (
+                  id, user_id, repo_id, full_name, owner_login, name,
+                  description, html_url, stargazed_at, is_private, updated_at
+                )
+                VALUES (?, ?, ?, ?, ?, ?, NULL, ?, ?, 0, ?)
+                "#,
+            )
+            .bind(format!("star-coverage-{repo_id}"))
+            .bind("user-brief-coverage")
+            .bind(repo_id)
+            .bind(full_name)
+            .bind(owner)
+            .bind(name)
+            .bind(format!("https://github.com/{full_name}"))
+            .bind(now)
+            .bind(now)
+            .execute(&state.pool)
+            .await
+            .expect("insert starred repo");
+        }
+
+        // Three releases inside the window across two repos, one draft inside
+        // the window and one published before it.
+        for (release_id, repo_id, published_at, is_draft) in [
+            (501_i64, 1_i64, "2026-03-06T12:00:00Z", 0_i64),
+            (502, 1, "2026-03-06T18:00:00Z", 0),
+            (503, 2, "2026-03-06T20:00:00Z", 0),
+            (504, 2, "2026-03-06T21:00:00Z", 1),
+            (505, 2, "2026-03-05T12:00:00Z", 0),
+        ] {
+            sqlx::query(
+                r#"
+                INSERT INTO repo_releases (
+                  id, repo_id, release_id, node_id, tag_name, name, body, html_url,
+                  published_at, created_at, is_prerelease, is_draft, updated_at
+                )
+                VALUES (?, ?, ?, ?, ?, ?, '', ?, ?, ?, 0, ?, ?)
+                "#,
+            )
+            .bind(format!("repo-release-coverage-{release_id}"))
+            .bind(repo_id)
+            .bind(release_id)
+            .bind(format!("node-{release_id}"))
+            .bind(format!("v{release_id}"))
+            .bind(format!("v{release_id}"))
+            .bind(format!("https://example.invalid/releases/{release_id}"))
+            .bind(published_at)
+            .bind(published_at)
+            .bind(is_draft)
+            .bind(now)
+            .execute(&state.pool)
+            .await
+            .expect("insert repo release");
+        }
+
+        let preferences = briefs::DailyBriefPreferences {
+            local_time: NaiveTime::from_hms_opt(8, 0, 0).expect("08:00"),
+            time_zone: "Asia/Shanghai".to_owned(),
+        };
+        let window = briefs::compute_daily_window_for_key_date(
+            &preferences,
+            NaiveDate::from_ymd_opt(2026, 3, 7).expect("date"),
+        )
+        .expect("window");
+
+        let built = build_brief_content(state.as_ref(), &window, "user-brief-coverage")
+            .await
+            .expect("build brief content");
+
+        assert_eq!(built.coverage.releases_total, 3);
+        assert_eq!(built.coverage.repos_covered, 2);
+        assert_eq!(built.coverage.releases_skipped_limit, 0);
+        assert!(built.coverage.summarized_release_ids.is_empty());
+    }
+
+    #[tokio::test]
+    async fn build_brief_content_skips_releases_from_muted_repos() {
+        let state = setup_llm_state().await;
+        let now = "2026-03-07T09:00:00Z";
+
+        sqlx::query(
+            r#"
+            INSERT INTO users (id, github_user_id, login, created_at, updated_at)
+            VALUES (?, ?, ?, ?, ?)
+            "#,
+        )
+        .bind("user-brief-muted")
+        .bind(2007_i64)
+        .bind("brief-muted")
+        .bind(now)
+        .bind(now)
+        .execute(&state.pool)
+        .await
+        .expect("insert user");
+
+        for (repo_id, full_name) in [(1_i64, "acme/rocket"), (2_i64, "acme/comet")] {
+            let (owner, name) = full_name.split_once('/').expect("full name");
+            sqlx::query(
+                r#"
+                INSERT INTO starred_repos (
+                  id, user_id, repo_id, full_name, owner_login, name,
+                  description, html_url, stargazed_at, is_private, updated_at
+                )
+                VALUES (?, ?, ?, ?, ?, ?, NULL, ?, ?, 0, ?)
+                "#,
+            )
+            .bind(format!("star-muted-{repo_id}"))
+            .bind("user-brief-muted")
+            .bind(repo_id)
+            .bind(full_name)
+            .bind(owner)
+            .bind(name)
+            .bind(format!("https://github.com/{full_name}"))
+            .bind(now)
+            .bind(now)
+            .execute(&state.pool)
+            .await
+            .expect("insert starred repo");
+        }
+
+        for (release_id, repo_id) in [(601_i64, 1_i64), (602, 2)] {
+            sqlx::query(
+                r#"
+                INSERT INTO repo_releases (
+                  id, repo_id, release_id, node_id, tag_name, name, body, html_url,
+                  published_at, created_at, is_prerelease, is_draft, updated_at
+                )
+                VALUES (?, ?, ?, ?, ?, ?, '', ?, ?, ?, 0, 0, ?)
+                "#,
+            )
+            .bind(format!("repo-release-muted-{release_id}"))
+            .bind(repo_id)
+            .bind(release_id)
+            .bind(format!("node-{release_id}"))
+            .bind(format!("v{release_id}"))
+            .bind(format!("v{release_id}"))
+            .bind(format!("https://example.invalid/releases/{release_id}"))
+            .bind("2026-03-06T12:00:00Z")
+            .bind("2026-03-06T12:00:00Z")
+            .bind(now)
+            .execute(&state.pool)
+            .await
+            .expect("insert repo release");
+        }
+
+        sqlx::query(r#"INSERT INTO muted_repos (user_id, repo_id, created_at) VALUES (?, 2, ?)"#)
+            .bind("user-brief-muted")
+            .bind(now)
+            .execute(&state.pool)
+            .await
+            .expect("mute repo");
+
+        let preferences = briefs::DailyBriefPreferences {
+            local_time: NaiveTime::from_hms_opt(8, 0, 0).expect("08:00"),
+            time_zone: "Asia/Shanghai".to_owned(),
+        };
+        let window = briefs::compute_daily_window_for_key_date(
+            &preferences,
+            NaiveDate::from_ymd_opt(2026, 3, 7).expect("date"),
+        )
+        .expect("window");
+
+        let built = build_brief_content(state.as_ref(), &window, "user-brief-muted")
+            .await
+            .expect("build brief content");
+
+        let release_ids = built
+            .releases
+            .iter()
+            .map(|release| release.release_id)
+            .collect::<Vec<_>>();
+        assert_eq!(release_ids, vec![601]);
+        assert_eq!(built.coverage.releases_total, 1);
+        assert!(!built.content_markdown.contains("acme/comet"));
+    }
+
+    #[tokio::test]
+    async fn preview_brief_plan_excludes_out_of_window_muted_and_over_budget_releases() {
+        let state = setup_llm_state().await;
+        let now = "2026-03-07T09:00:00Z";
+        let user_id = "user-brief-preview";
+
+        sqlx::query(
+            r#"
+            INSERT INTO users (id, github_user_id, login, created_at, updated_at)
+            VALUES (?, ?, ?, ?, ?)
+            "#,
+        )
+        .bind(user_id)
+        .bind(2007_i64)
+        .bind("brief-preview")
+        .bind(now)
+        .bind(now)
+        .execute(&state.pool)
+        .await
+        .expect("insert user");
+
+        for (repo_id, full_name) in [
+            (1_i64, "acme/rocket"),
+            (2_i64, "acme/comet"),
+            (3_i64, "acme/noisy"),
+        ] {
+            let (owner, name) = full_name.split_once('/').expect("full name");
+            sqlx::query(
+                r#"
+                INSERT INTO starred_repos (
+                  id, user_id, repo_id, full_name, owner_login, name,
+                  description, html_url, stargazed_at, is_private, updated_at
+                )
+                VALUES (?, ?, ?, ?, ?, ?, NULL, ?, ?, 0, ?)
+                "#,
+            )
+            .bind(format!("star-preview-{repo_id}"))
+            .bind(user_id)
+            .bind(repo_id)
+            .bind(full_name)
+            .bind(owner)
+            .bind(name)
+            .bind(format!("https://github.com/{full_name}"))
+            .bind(now)
+            .bind(now)
+            .execute(&state.pool)
+            .await
+            .expect("insert starred repo");
+        }
+        sqlx::query(r#"INSERT INTO muted_repos (user_id, repo_id, created_at) VALUES (?, 3, ?)"#)
+            .bind(user_id)
+            .bind(now)
+            .execute(&state.pool)
+            .await
+            .expect("mute repo");
+
+        for (release_id, repo_id, published_at, is_draft) in [
+            (601_i64, 1_i64, "2026-03-06T12:00:00Z", 0_i64),
+            (602, 1, "2026-03-06T18:00:00Z", 0),
+            (603, 2, "2026-03-06T20:00:00Z", 0),
+            (604, 2, "2026-03-06T21:00:00Z", 1),
+            (605, 2, "2026-03-05T12:00:00Z", 0),
+            (606, 3, "2026-03-06T22:00:00Z", 0),
+        ] {
+            let full_name = match repo_id {
+                1 => "acme/rocket",
+                2 => "acme/comet",
+                _ => "acme/noisy",
+            };
+            sqlx::query(
+                r#"
+                INSERT INTO repo_releases (
+                  id, repo_id, release_id, node_id, tag_name, name, body, html_url,
+                  published_at, created_at, is_prerelease, is_draft, updated_at
+                )
+                VALUES (?, ?, ?, ?, ?, ?, '- fixed things', ?, ?, ?, 0, ?, ?)
+                "#,
+            )
+            .bind(format!("repo-release-preview-{release_id}"))
+            .bind(repo_id)
+            .bind(release_id)
+            .bind(format!("node-{release_id}"))
+            .bind(format!("v{release_id}"))
+            .bind(format!("v{release_id}"))
+            .bind(format!(
+                "https://github.com/{full_name}/releases/tag/v{release_id}"
+            ))
+            .bind(published_at)
+            .bind(published_at)
+            .bind(is_draft)
+            .bind(now)
+            .execute(&state.pool)
+            .await
+            .expect("insert repo release");
+        }
+
+        let preferences = briefs::DailyBriefPreferences {
+            local_time: NaiveTime::from_hms_opt(8, 0, 0).expect("08:00"),
+            time_zone: "Asia/Shanghai".to_owned(),
+        };
+        let window = briefs::compute_daily_window_for_key_date(
+            &preferences,
+            NaiveDate::from_ymd_opt(2026, 3, 7).expect("date"),
+        )
+        .expect("window");
+
+        let preview = preview_brief_with_limit(state.as_ref(), user_id, &window, 2)
+            .await
+            .expect("preview brief");
+
+        let included = preview
+            .included
+            .iter()
+            .map(|release| release.release_id.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(included, vec!["603", "602"]);
+        let excluded = preview
+            .excluded
+            .iter()
+            .map(|release| (release.release_id.as_str(), release.reason.unwrap_or("")))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            excluded,
+            vec![
+                ("606", "muted"),
+                ("601", "over_budget"),
+                ("605", "out_of_window"),
+            ]
+        );
+        assert_eq!(preview.releases_total, 3);
+        assert!(
+            preview
+                .included
+                .iter()
+                .all(|release| release.estimated_tokens > 48)
+        );
+
+        let kinds = preview
+            .prompts
+            .iter()
+            .map(|prompt| prompt.kind)
+            .collect::<Vec<_>>();
+        assert_eq!(kinds, vec!["project_summary", "polish"]);
+        let batch = &preview.prompts[0];
+        assert_eq!(batch.repos, vec!["acme/comet", "acme/rocket"]);
+        assert!(batch.user.contains("release_id: 603"));
+        assert!(batch.user.contains("release_id: 602"));
+        assert!(!batch.user.contains("release_id: 601"));
+        assert!(!batch.user.contains("release_id: 606"));
+        assert!(batch.estimated_input_tokens > BRIEF_PROJECT_BATCH_OVERHEAD_TOKENS);
+        assert!(preview.draft_markdown.contains("v603"));
+        assert!(!preview.draft_markdown.contains("v601"));
+
+        let llm_calls = sqlx::query_scalar::<_, i64>(r#"SELECT COUNT(*) FROM llm_calls"#)
+            .fetch_one(&state.pool)
+            .await
+            .expect("count llm calls");
+        assert_eq!(llm_calls, 0);
+    }
+
     #[tokio::test]
     async fn upsert_daily_brief_snapshot_refreshes_existing_snapshot_in_place() {
         let state = setup_llm_state().await;
@@ -8578,6 +11719,9 @@ mod tests {
                 published_at: "2026-03-06T18:00:00Z".to_owned(),
                 is_prerelease: false,
             }],
+            coverage: BriefCoverage::default(),
+            sources: Vec::new(),
+            summarized_release_ids: HashSet::new(),
         };
 
         let stored = upsert_daily_brief_snapshot(
@@ -8623,6 +11767,71 @@ mod tests {
         assert_eq!(memberships, vec![402]);
     }
 
+    #[tokio::test]
+    async fn listed_brief_window_survives_daily_boundary_config_change() {
+        let state = setup_llm_state().await;
+        let user_id = crate::local_id::test_local_id("brief-window-config-change");
+        let now = "2026-03-07T09:00:00Z";
+        sqlx::query(
+            r#"
+            INSERT INTO users (id, github_user_id, login, created_at, updated_at)
+            VALUES (?, ?, ?, ?, ?)
+            "#,
+        )
+        .bind(user_id.as_str())
+        .bind(2006_i64)
+        .bind("brief-window-config-change")
+        .bind(now)
+        .bind(now)
+        .execute(&state.pool)
+        .await
+        .expect("insert user");
+
+        let preferences = briefs::load_daily_brief_preferences(state.as_ref(), &user_id)
+            .await
+            .expect("load preferences");
+        let window = briefs::compute_daily_window_for_key_date(
+            &preferences,
+            NaiveDate::from_ymd_opt(2026, 3, 7).expect("date"),
+        )
+        .expect("window");
+        let built = BuiltBriefContent {
+            content_markdown: "snapshot".to_owned(),
+            releases: Vec::new(),
+            coverage: BriefCoverage::default(),
+            sources: Vec::new(),
+            summarized_release_ids: HashSet::new(),
+        };
+        upsert_daily_brief_snapshot(state.as_ref(), &user_id, &window, &built, "scheduled")
+            .await
+            .expect("store snapshot");
+
+        // The user never picked a time zone, so the new default moves their
+        // next window; the stored one must stay where it was generated.
+        let mut inner = (*state).clone();
+        inner.config.ai_daily_at_local = Some(NaiveTime::from_hms_opt(20, 0, 0).expect("20:00"));
+        inner.config.app_default_time_zone = "America/New_York".to_owned();
+        let state = Arc::new(inner);
+        let moved = briefs::load_daily_brief_preferences(state.as_ref(), &user_id)
+            .await
+            .expect("reload preferences");
+        assert_ne!(moved, preferences);
+
+        let Json(items) = crate::api::list_briefs(
+            axum::extract::State(state),
+            crate::api::AuthedUser(user_id.clone()),
+        )
+        .await
+        .expect("list briefs");
+        let listed = serde_json::to_value(&items).expect("serialize briefs");
+        assert_eq!(listed[0]["window_start"], window.start_utc.to_rfc3339());
+        assert_eq!(listed[0]["window_end"], window.end_utc.to_rfc3339());
+        assert_eq!(
+            listed[0]["effective_time_zone"],
+            window.effective_time_zone.as_str()
+        );
+    }
+
     #[tokio::test]
     async fn upsert_daily_brief_snapshot_normalizes_legacy_placeholder_with_same_window() {
         let state = setup_llm_state().await;
@@ -8722,6 +11931,9 @@ mod tests {
                 published_at: "2026-03-06T12:00:00Z".to_owned(),
                 is_prerelease: false,
             }],
+            coverage: BriefCoverage::default(),
+            sources: Vec::new(),
+            summarized_release_ids: HashSet::new(),
         };
 
         let stored = upsert_daily_brief_snapshot(
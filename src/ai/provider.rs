@@ -0,0 +1,458 @@
+//! Wire formats of the chat APIs `AI_PROVIDER` can select. Everything
+//! around a request — scheduling, retries, fallback models and `llm_calls`
+//! accounting — lives in the parent module and is shared by all of them.
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use super::{ChatMessage, LlmTokenUsage, safe_i64_from_u64, strip_model_suffix};
+use crate::config::AiProvider;
+
+const ANTHROPIC_API_VERSION: &str = "2023-06-01";
+
+/// One chat call as the rest of the module sees it.
+pub(super) struct ChatRequest<'a> {
+    pub(super) model: &'a str,
+    pub(super) system: &'a str,
+    pub(super) user: &'a str,
+    pub(super) max_tokens: u32,
+    pub(super) stream: bool,
+}
+
+/// Decoded non-streaming success body.
+pub(super) struct ChatResponse {
+    pub(super) content: Option<String>,
+    pub(super) usage: LlmTokenUsage,
+}
+
+/// Decoded payload of one SSE event.
+#[derive(Debug, Default)]
+pub(super) struct ChatStreamChunk {
+    pub(super) fragments: Vec<String>,
+    /// Usage reported by this event; merged into what earlier events reported.
+    pub(super) usage: Option<LlmTokenUsage>,
+    /// The provider signalled the end of the reply.
+    pub(super) done: bool,
+}
+
+pub(super) trait ChatProvider: Send + Sync {
+    /// Endpoint path joined onto `AI_BASE_URL`.
+    fn endpoint(&self) -> &'static str;
+
+    /// Adds credentials and any headers the API requires on every request,
+    /// including the model list used by the health probe.
+    fn authorize(&self, request: reqwest::RequestBuilder, api_key: &str)
+    -> reqwest::RequestBuilder;
+
+    fn build_request(&self, request: &ChatRequest<'_>) -> Value;
+
+    fn parse_response(&self, body: &[u8]) -> serde_json::Result<ChatResponse>;
+
+    /// Decodes the data of one SSE event. `[DONE]` sentinels and error events
+    /// are handled by the caller.
+    fn parse_stream_chunk(&self, data: &str) -> Result<ChatStreamChunk>;
+
+    /// Maps the provider's usage object onto the `llm_calls` token columns.
+    /// `input_tokens` always includes cached input.
+    fn extract_usage(&self, usage: &Value) -> LlmTokenUsage;
+
+    /// Machine-readable error identifier of an error body.
+    fn error_code(&self, body: &[u8]) -> Option<String>;
+
+    /// Whether an error status or code rules out every retry of this model,
+    /// so the call should move on to the next fallback model.
+    fn rejection_is_final(
+        &self,
+        status: reqwest::StatusCode,
+        code: Option<&str>,
+        message: &str,
+    ) -> bool;
+
+    /// Names the model limit catalogs may list `model` under, most specific
+    /// first.
+    fn catalog_model_names(&self, model: &str) -> Vec<String>;
+}
+
+pub(super) fn chat_provider(provider: AiProvider) -> &'static dyn ChatProvider {
+    match provider {
+        AiProvider::OpenAi => &OpenAiChatProvider,
+        AiProvider::Anthropic => &AnthropicChatProvider,
+    }
+}
+
+/// Fills `total_tokens` for providers that only report its parts.
+pub(super) fn with_derived_total(usage: LlmTokenUsage) -> LlmTokenUsage {
+    let total_tokens = usage.total_tokens.or_else(|| {
+        usage
+            .input_tokens
+            .zip(usage.output_tokens)
+            .map(|(input, output)| input.saturating_add(output))
+    });
+    LlmTokenUsage {
+        total_tokens,
+        ..usage
+    }
+}
+
+/// Later events win field by field; fields they leave out keep earlier values.
+pub(super) fn merge_usage(earlier: Option<LlmTokenUsage>, later: LlmTokenUsage) -> LlmTokenUsage {
+    let Some(earlier) = earlier else {
+        return later;
+    };
+    LlmTokenUsage {
+        input_tokens: later.input_tokens.or(earlier.input_tokens),
+        output_tokens: later.output_tokens.or(earlier.output_tokens),
+        cached_input_tokens: later.cached_input_tokens.or(earlier.cached_input_tokens),
+        total_tokens: later.total_tokens.or(earlier.total_tokens),
+    }
+}
+
+fn json_u64(value: &Value, key: &str) -> Option<u64> {
+    value.get(key).and_then(Value::as_u64)
+}
+
+pub(super) struct OpenAiChatProvider;
+
+#[derive(Debug, Serialize)]
+struct ChatCompletionsRequest<'a> {
+    model: &'a str,
+    messages: Vec<ChatMessage<'a>>,
+    temperature: f64,
+    max_tokens: u32,
+    stream: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    stream_options: Option<ChatStreamOptions>,
+}
+
+#[derive(Debug, Serialize)]
+struct ChatStreamOptions {
+    include_usage: bool,
+}
+
+#[derive(Debug, Deserialize)]
+struct ChatCompletionsResponse {
+    choices: Vec<Choice>,
+    usage: Option<Value>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Choice {
+    message: ChoiceMessage,
+}
+
+#[derive(Debug, Deserialize)]
+struct ChoiceMessage {
+    content: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ChatCompletionsStreamChunk {
+    choices: Vec<ChatCompletionsStreamChoice>,
+    usage: Option<Value>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ChatCompletionsStreamChoice {
+    delta: ChatCompletionsStreamDelta,
+}
+
+#[derive(Debug, Deserialize)]
+struct ChatCompletionsStreamDelta {
+    content: Option<String>,
+}
+
+impl ChatProvider for OpenAiChatProvider {
+    fn endpoint(&self) -> &'static str {
+        "chat/completions"
+    }
+
+    fn authorize(
+        &self,
+        request: reqwest::RequestBuilder,
+        api_key: &str,
+    ) -> reqwest::RequestBuilder {
+        request.bearer_auth(api_key)
+    }
+
+    fn build_request(&self, request: &ChatRequest<'_>) -> Value {
+        serde_json::to_value(ChatCompletionsRequest {
+            model: request.model,
+            messages: vec![
+                ChatMessage {
+                    role: "system",
+                    content: request.system,
+                },
+                ChatMessage {
+                    role: "user",
+                    content: request.user,
+                },
+            ],
+            temperature: 0.2,
+            max_tokens: request.max_tokens,
+            stream: request.stream,
+            stream_options: request.stream.then_some(ChatStreamOptions {
+                include_usage: true,
+            }),
+        })
+        .unwrap_or(Value::Null)
+    }
+
+    fn parse_response(&self, body: &[u8]) -> serde_json::Result<ChatResponse> {
+        let ChatCompletionsResponse { choices, usage } = serde_json::from_slice(body)?;
+        Ok(ChatResponse {
+            content: choices.into_iter().next().and_then(|c| c.message.content),
+            usage: self.extract_usage(usage.as_ref().unwrap_or(&Value::Null)),
+        })
+    }
+
+    fn parse_stream_chunk(&self, data: &str) -> Result<ChatStreamChunk> {
+        let chunk: ChatCompletionsStreamChunk =
+            serde_json::from_str(data).context("decode chat completion SSE chunk failed")?;
+        Ok(ChatStreamChunk {
+            fragments: chunk
+                .choices
+                .into_iter()
+                .filter_map(|choice| choice.delta.content)
+                .collect(),
+            usage: chunk.usage.map(|usage| self.extract_usage(&usage)),
+            done: false,
+        })
+    }
+
+    fn extract_usage(&self, usage: &Value) -> LlmTokenUsage {
+        LlmTokenUsage {
+            input_tokens: safe_i64_from_u64(json_u64(usage, "prompt_tokens")),
+            output_tokens: safe_i64_from_u64(json_u64(usage, "completion_tokens")),
+            cached_input_tokens: safe_i64_from_u64(
+                usage
+                    .get("prompt_tokens_details")
+                    .and_then(|details| json_u64(details, "cached_tokens")),
+            ),
+            total_tokens: safe_i64_from_u64(json_u64(usage, "total_tokens")),
+        }
+    }
+
+    fn error_code(&self, body: &[u8]) -> Option<String> {
+        let value: Value = serde_json::from_slice(body).ok()?;
+        match value.get("error")?.get("code")? {
+            Value::String(code) => Some(code.trim().to_owned()).filter(|code| !code.is_empty()),
+            Value::Number(code) => Some(code.to_string()),
+            _ => None,
+        }
+    }
+
+    /// Some providers answer an oversized prompt with 422 rather than 400.
+    /// That rejection is model specific, so a fallback model with a larger
+    /// window is still worth trying.
+    fn rejection_is_final(
+        &self,
+        status: reqwest::StatusCode,
+        code: Option<&str>,
+        message: &str,
+    ) -> bool {
+        match status {
+            reqwest::StatusCode::UNAUTHORIZED | reqwest::StatusCode::FORBIDDEN => true,
+            reqwest::StatusCode::UNPROCESSABLE_ENTITY => {
+                let message = message.to_ascii_lowercase();
+                let context_length = code == Some("context_length_exceeded")
+                    || message.contains("context")
+                    || message.contains("length");
+                !context_length
+            }
+            _ => false,
+        }
+    }
+
+    fn catalog_model_names(&self, model: &str) -> Vec<String> {
+        vec![model.to_owned()]
+    }
+}
+
+pub(super) struct AnthropicChatProvider;
+
+#[derive(Debug, Serialize)]
+struct AnthropicMessagesRequest<'a> {
+    model: &'a str,
+    system: &'a str,
+    messages: Vec<ChatMessage<'a>>,
+    temperature: f64,
+    max_tokens: u32,
+    stream: bool,
+}
+
+#[derive(Debug, Deserialize)]
+struct AnthropicMessagesResponse {
+    #[serde(default)]
+    content: Vec<AnthropicContentBlock>,
+    usage: Option<Value>,
+}
+
+#[derive(Debug, Deserialize)]
+struct AnthropicContentBlock {
+    #[serde(rename = "type")]
+    kind: String,
+    text: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct AnthropicStreamEvent {
+    #[serde(rename = "type")]
+    kind: String,
+    delta: Option<AnthropicStreamDelta>,
+    message: Option<AnthropicMessagesResponse>,
+    usage: Option<Value>,
+}
+
+#[derive(Debug, Deserialize)]
+struct AnthropicStreamDelta {
+    #[serde(rename = "type")]
+    kind: Option<String>,
+    text: Option<String>,
+}
+
+impl ChatProvider for AnthropicChatProvider {
+    fn endpoint(&self) -> &'static str {
+        "messages"
+    }
+
+    fn authorize(
+        &self,
+        request: reqwest::RequestBuilder,
+        api_key: &str,
+    ) -> reqwest::RequestBuilder {
+        request
+            .header("x-api-key", api_key)
+            .header("anthropic-version", ANTHROPIC_API_VERSION)
+    }
+
+    fn build_request(&self, request: &ChatRequest<'_>) -> Value {
+        serde_json::to_value(AnthropicMessagesRequest {
+            model: request.model,
+            system: request.system,
+            messages: vec![ChatMessage {
+                role: "user",
+                content: request.user,
+            }],
+            temperature: 0.2,
+            max_tokens: request.max_tokens,
+            stream: request.stream,
+        })
+        .unwrap_or(Value::Null)
+    }
+
+    fn parse_response(&self, body: &[u8]) -> serde_json::Result<ChatResponse> {
+        let AnthropicMessagesResponse { content, usage } = serde_json::from_slice(body)?;
+        let text = content
+            .into_iter()
+            .filter(|block| block.kind == "text")
+            .filter_map(|block| block.text)
+            .collect::<String>();
+        Ok(ChatResponse {
+            content: Some(text),
+            usage: with_derived_total(self.extract_usage(usage.as_ref().unwrap_or(&Value::Null))),
+        })
+    }
+
+    fn parse_stream_chunk(&self, data: &str) -> Result<ChatStreamChunk> {
+        let event: AnthropicStreamEvent =
+            serde_json::from_str(data).context("decode messages SSE event failed")?;
+        let mut chunk = ChatStreamChunk::default();
+        match event.kind.as_str() {
+            "message_start" => {
+                chunk.usage = event
+                    .message
+                    .and_then(|message| message.usage)
+                    .map(|usage| self.extract_usage(&usage));
+            }
+            "content_block_delta" => {
+                if let Some(delta) = event.delta
+                    && delta.kind.as_deref() == Some("text_delta")
+                    && let Some(text) = delta.text
+                {
+                    chunk.fragments.push(text);
+                }
+            }
+            "message_delta" => {
+                chunk.usage = event.usage.map(|usage| self.extract_usage(&usage));
+            }
+            "message_stop" => chunk.done = true,
+            _ => {}
+        }
+        Ok(chunk)
+    }
+
+    /// `input_tokens` excludes cache reads and writes here, so they are added
+    /// back to match OpenAI's `prompt_tokens`.
+    fn extract_usage(&self, usage: &Value) -> LlmTokenUsage {
+        let uncached = json_u64(usage, "input_tokens");
+        let cache_read = json_u64(usage, "cache_read_input_tokens");
+        let cache_write = json_u64(usage, "cache_creation_input_tokens");
+        let input = uncached.map(|uncached| {
+            uncached
+                .saturating_add(cache_read.unwrap_or(0))
+                .saturating_add(cache_write.unwrap_or(0))
+        });
+        LlmTokenUsage {
+            input_tokens: safe_i64_from_u64(input),
+            output_tokens: safe_i64_from_u64(json_u64(usage, "output_tokens")),
+            cached_input_tokens: safe_i64_from_u64(cache_read),
+            total_tokens: None,
+        }
+    }
+
+    fn error_code(&self, body: &[u8]) -> Option<String> {
+        let value: Value = serde_json::from_slice(body).ok()?;
+        value
+            .get("error")?
+            .get("type")?
+            .as_str()
+            .map(str::trim)
+            .filter(|code| !code.is_empty())
+            .map(str::to_owned)
+    }
+
+    /// An unknown model answers 404 `not_found_error`; like a bad key, no
+    /// retry of the same model can succeed. Oversized prompts come back as a
+    /// 400 `invalid_request_error` and are left to the caller.
+    fn rejection_is_final(
+        &self,
+        status: reqwest::StatusCode,
+        code: Option<&str>,
+        _message: &str,
+    ) -> bool {
+        matches!(
+            status,
+            reqwest::StatusCode::UNAUTHORIZED
+                | reqwest::StatusCode::FORBIDDEN
+                | reqwest::StatusCode::NOT_FOUND
+        ) || matches!(
+            code,
+            Some("authentication_error" | "permission_error" | "not_found_error")
+        )
+    }
+
+    /// Catalogs list Claude models as e.g. `anthropic/claude-3.5-haiku`, while
+    /// the native API calls the same model `claude-3-5-haiku-20241022`.
+    fn catalog_model_names(&self, model: &str) -> Vec<String> {
+        let mut base = strip_model_suffix(&model.trim().to_ascii_lowercase());
+        if let Some((head, date)) = base.rsplit_once('-')
+            && date.len() == 8
+            && date.bytes().all(|byte| byte.is_ascii_digit())
+        {
+            base = head.to_owned();
+        }
+        let bytes = base.as_bytes();
+        let dotted = base
+            .char_indices()
+            .map(|(idx, ch)| {
+                let between_digits = ch == '-'
+                    && idx > 0
+                    && bytes[idx - 1].is_ascii_digit()
+                    && bytes.get(idx + 1).is_some_and(u8::is_ascii_digit);
+                if between_digits { '.' } else { ch }
+            })
+            .collect::<String>();
+        vec![model.to_owned(), format!("anthropic/{dotted}")]
+    }
+}
@@ -0,0 +1,6777 @@
+use super::*;
+
+#[derive(Debug, Serialize, JsonSchema, sqlx::FromRow)]
+pub struct AdminUserItem {
+    pub(super) id: String,
+    pub(super) github_user_id: i64,
+    pub(super) login: String,
+    pub(super) name: Option<String>,
+    pub(super) avatar_url: Option<String>,
+    pub(super) email: Option<String>,
+    pub(super) is_admin: bool,
+    pub(super) is_disabled: bool,
+    pub(super) repo_total: i64,
+    pub(super) include_own_releases: bool,
+    pub(super) last_active_at: Option<String>,
+    pub(super) created_at: String,
+    pub(super) updated_at: String,
+    #[sqlx(skip)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(super) stats: Option<AdminUserStats>,
+}
+
+/// Storage and sync footprint of one user.
+#[derive(Debug, Clone, Default, Serialize, JsonSchema)]
+pub struct AdminUserStats {
+    pub(super) starred_repos: i64,
+    /// Shared release rows reachable through the user's stars.
+    pub(super) releases: i64,
+    pub(super) release_body_bytes: i64,
+    pub(super) notifications: i64,
+    pub(super) ai_translations: i64,
+    pub(super) briefs: i64,
+    pub(super) llm_calls: i64,
+    pub(super) llm_total_tokens: i64,
+    /// Latest successful finish per sync task type.
+    pub(super) last_synced_at: BTreeMap<String, String>,
+    pub(super) reaction_pat_configured: bool,
+}
+
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct AdminUsersListResponse {
+    pub(super) items: Vec<AdminUserItem>,
+    pub(super) page: i64,
+    pub(super) page_size: i64,
+    pub(super) total: i64,
+    pub(super) guard: AdminUsersGuardSummary,
+}
+
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct AdminUsersGuardSummary {
+    pub(super) admin_total: i64,
+    pub(super) active_admin_total: i64,
+}
+
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct AdminUsersQuery {
+    pub(super) query: Option<String>,
+    pub(super) role: Option<String>,
+    pub(super) status: Option<String>,
+    pub(super) page: Option<i64>,
+    pub(super) page_size: Option<i64>,
+    /// Attach per-user stats to the current page.
+    pub(super) include_stats: Option<bool>,
+}
+
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct AdminUserPatchRequest {
+    pub(super) is_admin: Option<bool>,
+    pub(super) is_disabled: Option<bool>,
+}
+
+impl AdminUserPatchRequest {
+    pub(super) fn validate(&self) -> Result<(), ApiError> {
+        if self.is_admin.is_none() && self.is_disabled.is_none() {
+            return Err(ApiError::bad_request(
+                "at least one field (is_admin/is_disabled) is required",
+            ));
+        }
+        Ok(())
+    }
+}
+
+pub(super) const ADMIN_USER_BULK_LIMIT: usize = 100;
+
+/// Applies the same flag change to every listed user in one transaction.
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct AdminUserBulkRequest {
+    pub(super) user_ids: Vec<String>,
+    pub(super) is_admin: Option<bool>,
+    pub(super) is_disabled: Option<bool>,
+}
+
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct AdminUserBulkResponse {
+    pub(super) items: Vec<AdminUserItem>,
+}
+
+#[derive(Debug, Clone)]
+pub(super) struct AdminUserUpdateGuard {
+    pub(super) acting_user_id: String,
+    pub(super) target_user_id: String,
+    pub(super) target_is_admin: bool,
+    pub(super) target_is_disabled: bool,
+    pub(super) next_is_admin: bool,
+    pub(super) next_is_disabled: bool,
+    pub(super) admin_count: i64,
+    pub(super) active_admin_count: i64,
+}
+
+pub(super) fn guard_admin_user_update(guard: AdminUserUpdateGuard) -> Result<(), ApiError> {
+    if guard.target_user_id == guard.acting_user_id && guard.next_is_disabled {
+        return Err(ApiError::new(
+            StatusCode::CONFLICT,
+            codes::CANNOT_DISABLE_SELF,
+            "admin cannot disable self",
+        ));
+    }
+
+    if guard.target_is_admin && !guard.next_is_admin && guard.admin_count <= 1 {
+        return Err(ApiError::new(
+            StatusCode::CONFLICT,
+            codes::LAST_ADMIN_GUARD,
+            "at least one admin is required",
+        ));
+    }
+
+    let target_is_active_admin = guard.target_is_admin && !guard.target_is_disabled;
+    let next_is_active_admin = guard.next_is_admin && !guard.next_is_disabled;
+    if target_is_active_admin && !next_is_active_admin && guard.active_admin_count <= 1 {
+        return Err(ApiError::new(
+            StatusCode::CONFLICT,
+            codes::LAST_ADMIN_GUARD,
+            "at least one active admin is required",
+        ));
+    }
+
+    Ok(())
+}
+
+pub(super) fn admin_users_offset(page: i64, page_size: i64) -> Result<i64, ApiError> {
+    page.checked_sub(1)
+        .and_then(|value| value.checked_mul(page_size))
+        .ok_or_else(|| ApiError::bad_request("page is too large"))
+}
+
+pub async fn admin_list_users(
+    State(state): State<Arc<AppState>>,
+    _: AdminUser,
+    Query(query): Query<AdminUsersQuery>,
+) -> Result<Json<AdminUsersListResponse>, ApiError> {
+    let role = query.role.unwrap_or_else(|| "all".to_owned());
+    if role != "all" && role != "admin" && role != "user" {
+        return Err(ApiError::bad_request("invalid role filter"));
+    }
+    let status = query.status.unwrap_or_else(|| "all".to_owned());
+    if status != "all" && status != "enabled" && status != "disabled" {
+        return Err(ApiError::bad_request("invalid status filter"));
+    }
+
+    let page = query.page.unwrap_or(1);
+    if page < 1 {
+        return Err(ApiError::bad_request("page must be >= 1"));
+    }
+    let page_size = query.page_size.unwrap_or(20).clamp(1, 100);
+    let offset = admin_users_offset(page, page_size)?;
+
+    let query_text = query.query.unwrap_or_default().trim().to_lowercase();
+    let query_like = format!("%{query_text}%");
+
+    let total = sqlx::query_scalar::<_, i64>(
+        r#"
+        SELECT COUNT(*)
+        FROM users
+        WHERE
+          (? = '' OR lower(login) LIKE ? OR lower(COALESCE(name, '')) LIKE ? OR lower(COALESCE(email, '')) LIKE ?)
+          AND (? = 'all' OR (? = 'admin' AND is_admin = 1) OR (? = 'user' AND is_admin = 0))
+          AND (? = 'all' OR (? = 'enabled' AND is_disabled = 0) OR (? = 'disabled' AND is_disabled = 1))
+        "#,
+    )
+    .bind(query_text.as_str())
+    .bind(query_like.as_str())
+    .bind(query_like.as_str())
+    .bind(query_like.as_str())
+    .bind(role.as_str())
+    .bind(role.as_str())
+    .bind(role.as_str())
+    .bind(status.as_str())
+    .bind(status.as_str())
+    .bind(status.as_str())
+    .fetch_one(&state.pool)
+    .await
+    .map_err(ApiError::internal)?;
+
+    let items = sqlx::query_as::<_, AdminUserItem>(
+        r#"
+        WITH repo_totals AS (
+          SELECT
+            repo_sources.user_id,
+            COUNT(DISTINCT repo_sources.repo_id) AS repo_total
+          FROM (
+            SELECT sr.user_id, sr.repo_id
+            FROM starred_repos sr
+            JOIN users starred_users ON starred_users.id = sr.user_id
+            WHERE starred_users.is_disabled = 0
+            UNION ALL
+            SELECT ob.user_id, ob.repo_id
+            FROM owned_repo_star_baselines ob
+            JOIN users owned_users ON owned_users.id = ob.user_id
+            WHERE owned_users.is_disabled = 0
+              AND owned_users.include_own_releases != 0
+            UNION ALL
+            SELECT fr.user_id, fr.repo_id
+            FROM followed_repos fr
+            JOIN users followed_users ON followed_users.id = fr.user_id
+            WHERE followed_users.is_disabled = 0
+          ) repo_sources
+          GROUP BY repo_sources.user_id
+        )
+        SELECT
+          users.id,
+          users.github_user_id,
+          users.login,
+          users.name,
+          users.avatar_url,
+          users.email,
+          users.is_admin,
+          users.is_disabled,
+          COALESCE(repo_totals.repo_total, 0) AS repo_total,
+          users.include_own_releases,
+          users.last_active_at,
+          users.created_at,
+          users.updated_at
+        FROM users
+        LEFT JOIN repo_totals ON repo_totals.user_id = users.id
+        WHERE
+          (? = '' OR lower(users.login) LIKE ? OR lower(COALESCE(users.name, '')) LIKE ? OR lower(COALESCE(users.email, '')) LIKE ?)
+          AND (? = 'all' OR (? = 'admin' AND users.is_admin = 1) OR (? = 'user' AND users.is_admin = 0))
+          AND (? = 'all' OR (? = 'enabled' AND users.is_disabled = 0) OR (? = 'disabled' AND users.is_disabled = 1))
+        ORDER BY users.created_at ASC, users.id ASC
+        LIMIT ? OFFSET ?
+        "#,
+    )
+    .bind(query_text.as_str())
+    .bind(query_like.as_str())
+    .bind(query_like.as_str())
+    .bind(query_like.as_str())
+    .bind(role.as_str())
+    .bind(role.as_str())
+    .bind(role.as_str())
+    .bind(status.as_str())
+    .bind(status.as_str())
+    .bind(status.as_str())
+    .bind(page_size)
+    .bind(offset)
+    .fetch_all(&state.pool)
+    .await
+    .map_err(ApiError::internal)?;
+    let mut items = items;
+    if query.include_stats.unwrap_or(false) {
+        let user_ids = items.iter().map(|item| item.id.clone()).collect::<Vec<_>>();
+        let mut stats = load_admin_user_stats(state.as_ref(), &user_ids).await?;
+        for item in &mut items {
+            item.stats = Some(stats.remove(&item.id).unwrap_or_default());
+        }
+    }
+
+    let admin_total =
+        sqlx::query_scalar::<_, i64>(r#"SELECT COUNT(*) FROM users WHERE is_admin = 1"#)
+            .fetch_one(&state.pool)
+            .await
+            .map_err(ApiError::internal)?;
+
+    let active_admin_total = sqlx::query_scalar::<_, i64>(
+        r#"SELECT COUNT(*) FROM users WHERE is_admin = 1 AND is_disabled = 0"#,
+    )
+    .fetch_one(&state.pool)
+    .await
+    .map_err(ApiError::internal)?;
+
+    Ok(Json(AdminUsersListResponse {
+        items,
+        page,
+        page_size,
+        total,
+        guard: AdminUsersGuardSummary {
+            admin_total,
+            active_admin_total,
+        },
+    }))
+}
+
+pub async fn admin_get_user_stats(
+    State(state): State<Arc<AppState>>,
+    _: AdminUser,
+    Path(user_id): Path<String>,
+) -> Result<Json<AdminUserStats>, ApiError> {
+    let user_id = parse_local_id_param(user_id, "user_id")?;
+    sqlx::query_scalar::<_, String>(r#"SELECT id FROM users WHERE id = ? LIMIT 1"#)
+        .bind(user_id.as_str())
+        .fetch_optional(&state.pool)
+        .await
+        .map_err(ApiError::internal)?
+        .ok_or_else(|| ApiError::new(StatusCode::NOT_FOUND, codes::NOT_FOUND, "user not found"))?;
+
+    let mut stats = load_admin_user_stats(state.as_ref(), std::slice::from_ref(&user_id)).await?;
+    Ok(Json(stats.remove(&user_id).unwrap_or_default()))
+}
+
+pub(super) const ADMIN_USER_STATS_SYNC_TASK_TYPES: [&str; 4] = [
+    jobs::TASK_SYNC_STARRED,
+    jobs::TASK_SYNC_RELEASES,
+    jobs::TASK_SYNC_NOTIFICATIONS,
+    jobs::TASK_SYNC_ALL,
+];
+
+/// Runs `select` grouped by `user_column` for the given users. Each row yields
+/// the user id followed by two integer aggregates.
+pub(super) async fn load_admin_user_stat_totals(
+    state: &AppState,
+    select: &str,
+    user_column: &str,
+    user_ids: &[String],
+) -> Result<HashMap<String, (i64, i64)>, ApiError> {
+    let mut query = QueryBuilder::<sqlx::Sqlite>::new(select);
+    query.push(" WHERE ").push(user_column).push(" IN (");
+    let mut separated = query.separated(", ");
+    for user_id in user_ids {
+        separated.push_bind(user_id.as_str());
+    }
+    query.push(") GROUP BY ").push(user_column);
+
+    let rows = query
+        .build()
+        .fetch_all(&state.pool)
+        .await
+        .map_err(ApiError::internal)?;
+    rows.iter()
+        .map(|row| {
+            Ok((
+                row.try_get::<String, _>(0)?,
+                (row.try_get::<i64, _>(1)?, row.try_get::<i64, _>(2)?),
+            ))
+        })
+        .collect::<Result<_, sqlx::Error>>()
+        .map_err(ApiError::internal)
+}
+
+pub(super) async fn load_admin_user_stats(
+    state: &AppState,
+    user_ids: &[String],
+) -> Result<HashMap<String, AdminUserStats>, ApiError> {
+    let mut stats = user_ids
+        .iter()
+        .map(|user_id| (user_id.clone(), AdminUserStats::default()))
+        .collect::<HashMap<_, _>>();
+    if user_ids.is_empty() {
+        return Ok(stats);
+    }
+
+    type ApplyTotals = fn(&mut AdminUserStats, i64, i64);
+    let totals: [(&str, &str, ApplyTotals); 7] = [
+        (
+            "SELECT user_id, COUNT(*), 0 FROM starred_repos",
+            "user_id",
+            |entry, count, _| entry.starred_repos = count,
+        ),
+        (
+            r#"
+            SELECT sr.user_id, COUNT(rr.id), COALESCE(SUM(LENGTH(CAST(rr.body AS BLOB))), 0)
+            FROM starred_repos sr
+            JOIN repo_releases rr ON rr.repo_id = sr.repo_id
+            "#,
+            "sr.user_id",
+            |entry, count, bytes| {
+                entry.releases = count;
+                entry.release_body_bytes = bytes;
+            },
+        ),
+        (
+            "SELECT user_id, COUNT(*), 0 FROM notifications",
+            "user_id",
+            |entry, count, _| entry.notifications = count,
+        ),
+        (
+            "SELECT user_id, COUNT(*), 0 FROM ai_translations",
+            "user_id",
+            |entry, count, _| entry.ai_translations = count,
+        ),
+        (
+            "SELECT user_id, COUNT(*), 0 FROM briefs",
+            "user_id",
+            |entry, count, _| entry.briefs = count,
+        ),
+        (
+            r#"
+            SELECT
+              requested_by,
+              COUNT(*),
+              COALESCE(SUM(COALESCE(total_tokens, COALESCE(input_tokens, 0) + COALESCE(output_tokens, 0))), 0)
+            FROM llm_calls
+            "#,
+            "requested_by",
+            |entry, count, tokens| {
+                entry.llm_calls = count;
+                entry.llm_total_tokens = tokens;
+            },
+        ),
+        (
+            "SELECT user_id, COUNT(*), 0 FROM reaction_pat_tokens",
+            "user_id",
+            |entry, count, _| entry.reaction_pat_configured = count > 0,
+        ),
+    ];
+    for (select, user_column, apply) in totals {
+        let rows = load_admin_user_stat_totals(state, select, user_column, user_ids).await?;
+        for (user_id, (count, extra)) in rows {
+            if let Some(entry) = stats.get_mut(&user_id) {
+                apply(entry, count, extra);
+            }
+        }
+    }
+
+    let mut query = QueryBuilder::<sqlx::Sqlite>::new(
+        r#"
+        SELECT json_extract(payload_json, '$.user_id') AS user_id, task_type, MAX(finished_at)
+        FROM job_tasks
+        WHERE status = 'succeeded'
+          AND finished_at IS NOT NULL
+          AND task_type IN (
+        "#,
+    );
+    let mut separated = query.separated(", ");
+    for task_type in ADMIN_USER_STATS_SYNC_TASK_TYPES {
+        separated.push_bind(task_type);
+    }
+    query.push(") AND json_extract(payload_json, '$.user_id') IN (");
+    let mut separated = query.separated(", ");
+    for user_id in user_ids {
+        separated.push_bind(user_id.as_str());
+    }
+    query.push(") GROUP BY 1, 2");
+    let rows = query
+        .build()
+        .fetch_all(&state.pool)
+        .await
+        .map_err(ApiError::internal)?;
+    for row in rows {
+        let user_id = row.try_get::<String, _>(0).map_err(ApiError::internal)?;
+        let task_type = row.try_get::<String, _>(1).map_err(ApiError::internal)?;
+        let finished_at = row.try_get::<String, _>(2).map_err(ApiError::internal)?;
+        if let Some(entry) = stats.get_mut(&user_id) {
+            entry.last_synced_at.insert(task_type, finished_at);
+        }
+    }
+
+    Ok(stats)
+}
+
+pub async fn admin_patch_user(
+    State(state): State<Arc<AppState>>,
+    AdminUser(acting_user_id): AdminUser,
+    Path(target_user_id): Path<String>,
+    ApiJson(req): ApiJson<AdminUserPatchRequest>,
+) -> Result<Json<AdminUserItem>, ApiError> {
+    let target_user_id = parse_local_id_param(target_user_id, "user_id")?;
+    state
+        .admin_mutation_limiter
+        .check(acting_user_id.as_str())?;
+    req.validate()?;
+
+    let mut tx = state.pool.begin().await.map_err(ApiError::internal)?;
+    let mut counts = AdminRoleCounts::load(&mut tx).await?;
+    let updated = apply_admin_user_update(
+        &mut tx,
+        acting_user_id.as_str(),
+        target_user_id.as_str(),
+        &req,
+        &mut counts,
+    )
+    .await?;
+    tx.commit().await.map_err(ApiError::internal)?;
+    if updated.is_disabled {
+        state.feed_events.close_user(&target_user_id);
+    }
+    Ok(Json(updated))
+}
+
+pub async fn admin_bulk_update_users(
+    State(state): State<Arc<AppState>>,
+    AdminUser(acting_user_id): AdminUser,
+    ApiJson(req): ApiJson<AdminUserBulkRequest>,
+) -> Result<Json<AdminUserBulkResponse>, ApiError> {
+    state
+        .admin_mutation_limiter
+        .check(acting_user_id.as_str())?;
+    let patch = AdminUserPatchRequest {
+        is_admin: req.is_admin,
+        is_disabled: req.is_disabled,
+    };
+    patch.validate()?;
+    if req.user_ids.is_empty() {
+        return Err(ApiError::bad_request("user_ids must not be empty"));
+    }
+    if req.user_ids.len() > ADMIN_USER_BULK_LIMIT {
+        return Err(ApiError::bad_request(format!(
+            "at most {ADMIN_USER_BULK_LIMIT} users can be updated at once"
+        )));
+    }
+    let mut target_user_ids = Vec::with_capacity(req.user_ids.len());
+    for raw in req.user_ids {
+        let user_id = parse_local_id_param(raw, "user_id")?;
+        if !target_user_ids.contains(&user_id) {
+            target_user_ids.push(user_id);
+        }
+    }
+
+    // Any failure drops the transaction, so a rejected batch changes nothing.
+    let mut tx = state.pool.begin().await.map_err(ApiError::internal)?;
+    let mut counts = AdminRoleCounts::load(&mut tx).await?;
+    let mut items = Vec::with_capacity(target_user_ids.len());
+    for target_user_id in &target_user_ids {
+        let updated = apply_admin_user_update(
+            &mut tx,
+            acting_user_id.as_str(),
+            target_user_id,
+            &patch,
+            &mut counts,
+        )
+        .await
+        .map_err(|err| err.with_message_prefix(format!("user {target_user_id}")))?;
+        items.push(updated);
+    }
+    tx.commit().await.map_err(ApiError::internal)?;
+
+    for item in items.iter().filter(|item| item.is_disabled) {
+        state.feed_events.close_user(&item.id);
+    }
+    Ok(Json(AdminUserBulkResponse { items }))
+}
+
+/// Admin headcounts inside an update transaction. Each applied change is
+/// folded back in, so later targets in a batch are guarded against the
+/// in-flight state rather than the counts the batch started from.
+#[derive(Debug, Clone, Copy)]
+pub(super) struct AdminRoleCounts {
+    pub(super) admins: i64,
+    pub(super) active_admins: i64,
+}
+
+impl AdminRoleCounts {
+    pub(super) async fn load(conn: &mut sqlx::SqliteConnection) -> Result<Self, ApiError> {
+        let (admins, active_admins) = sqlx::query_as::<_, (i64, i64)>(
+            r#"
+            SELECT
+              COALESCE(SUM(CASE WHEN is_admin = 1 THEN 1 ELSE 0 END), 0),
+              COALESCE(SUM(CASE WHEN is_admin = 1 AND is_disabled = 0 THEN 1 ELSE 0 END), 0)
+            FROM users
+            "#,
+        )
+        .fetch_one(conn)
+        .await
+        .map_err(ApiError::internal)?;
+        Ok(Self {
+            admins,
+            active_admins,
+        })
+    }
+
+    pub(super) fn apply(&mut self, guard: &AdminUserUpdateGuard) {
+        self.admins += i64::from(guard.next_is_admin) - i64::from(guard.target_is_admin);
+        let was_active = guard.target_is_admin && !guard.target_is_disabled;
+        let is_active = guard.next_is_admin && !guard.next_is_disabled;
+        self.active_admins += i64::from(is_active) - i64::from(was_active);
+    }
+}
+
+/// Deleting an account is checked like demoting it, so neither the last admin
+/// nor the last active admin can be removed.
+pub(crate) async fn guard_user_deletion(
+    conn: &mut sqlx::SqliteConnection,
+    target_user_id: &str,
+) -> Result<(), ApiError> {
+    let target = sqlx::query_as::<_, (i64, i64)>(
+        r#"
+        SELECT is_admin, is_disabled
+        FROM users
+        WHERE id = ?
+        "#,
+    )
+    .bind(target_user_id)
+    .fetch_optional(&mut *conn)
+    .await
+    .map_err(ApiError::internal)?;
+    let Some((is_admin, is_disabled)) = target else {
+        return Err(ApiError::new(
+            StatusCode::NOT_FOUND,
+            codes::NOT_FOUND,
+            "user not found",
+        ));
+    };
+
+    let counts = AdminRoleCounts::load(conn).await?;
+    guard_admin_user_update(AdminUserUpdateGuard {
+        acting_user_id: target_user_id.to_owned(),
+        target_user_id: target_user_id.to_owned(),
+        target_is_admin: is_admin != 0,
+        target_is_disabled: is_disabled != 0,
+        next_is_admin: false,
+        next_is_disabled: false,
+        admin_count: counts.admins,
+        active_admin_count: counts.active_admins,
+    })
+}
+
+pub(super) async fn apply_admin_user_update(
+    conn: &mut sqlx::SqliteConnection,
+    acting_user_id: &str,
+    target_user_id: &str,
+    req: &AdminUserPatchRequest,
+    counts: &mut AdminRoleCounts,
+) -> Result<AdminUserItem, ApiError> {
+    #[derive(Debug, sqlx::FromRow)]
+    struct AdminPatchTargetRow {
+        id: String,
+        is_admin: i64,
+        is_disabled: i64,
+    }
+
+    let target = sqlx::query_as::<_, AdminPatchTargetRow>(
+        r#"
+        SELECT id, is_admin, is_disabled
+        FROM users
+        WHERE id = ?
+        "#,
+    )
+    .bind(target_user_id)
+    .fetch_optional(&mut *conn)
+    .await
+    .map_err(ApiError::internal)?;
+
+    let Some(target) = target else {
+        return Err(ApiError::new(
+            StatusCode::NOT_FOUND,
+            codes::NOT_FOUND,
+            "user not found",
+        ));
+    };
+
+    let target_is_admin = target.is_admin != 0;
+    let target_is_disabled = target.is_disabled != 0;
+    let next_is_admin = req.is_admin.unwrap_or(target_is_admin);
+    let next_is_disabled = req.is_disabled.unwrap_or(target_is_disabled);
+
+    let guard = AdminUserUpdateGuard {
+        acting_user_id: acting_user_id.to_owned(),
+        target_user_id: target.id,
+        target_is_admin,
+        target_is_disabled,
+        next_is_admin,
+        next_is_disabled,
+        admin_count: counts.admins,
+        active_admin_count: counts.active_admins,
+    };
+    guard_admin_user_update(guard.clone())?;
+    counts.apply(&guard);
+
+    let now = chrono::Utc::now().to_rfc3339();
+    sqlx::query(
+        r#"
+        UPDATE users
+        SET is_admin = ?, is_disabled = ?, updated_at = ?
+        WHERE id = ?
+        "#,
+    )
+    .bind(if next_is_admin { 1_i64 } else { 0_i64 })
+    .bind(if next_is_disabled { 1_i64 } else { 0_i64 })
+    .bind(now.as_str())
+    .bind(target_user_id)
+    .execute(&mut *conn)
+    .await
+    .map_err(ApiError::internal)?;
+
+    admin_audit::record(
+        conn,
+        acting_user_id,
+        Some(target_user_id),
+        admin_audit::ADMIN_AUDIT_USER_UPDATE,
+        &json!({ "is_admin": target_is_admin, "is_disabled": target_is_disabled }),
+        &json!({ "is_admin": next_is_admin, "is_disabled": next_is_disabled }),
+    )
+    .await?;
+
+    sqlx::query_as::<_, AdminUserItem>(
+        r#"
+        WITH repo_totals AS (
+          SELECT
+            repo_sources.user_id,
+            COUNT(DISTINCT repo_sources.repo_id) AS repo_total
+          FROM (
+            SELECT sr.user_id, sr.repo_id
+            FROM starred_repos sr
+            JOIN users starred_users ON starred_users.id = sr.user_id
+            WHERE starred_users.is_disabled = 0
+            UNION ALL
+            SELECT ob.user_id, ob.repo_id
+            FROM owned_repo_star_baselines ob
+            JOIN users owned_users ON owned_users.id = ob.user_id
+            WHERE owned_users.is_disabled = 0
+              AND owned_users.include_own_releases != 0
+            UNION ALL
+            SELECT fr.user_id, fr.repo_id
+            FROM followed_repos fr
+            JOIN users followed_users ON followed_users.id = fr.user_id
+            WHERE followed_users.is_disabled = 0
+          ) repo_sources
+          GROUP BY repo_sources.user_id
+        )
+        SELECT
+          users.id,
+          users.github_user_id,
+          users.login,
+          users.name,
+          users.avatar_url,
+          users.email,
+          users.is_admin,
+          users.is_disabled,
+          COALESCE(repo_totals.repo_total, 0) AS repo_total,
+          users.include_own_releases,
+          users.last_active_at,
+          users.created_at,
+          users.updated_at
+        FROM users
+        LEFT JOIN repo_totals ON repo_totals.user_id = users.id
+        WHERE users.id = ?
+        "#,
+    )
+    .bind(target_user_id)
+    .fetch_one(&mut *conn)
+    .await
+    .map_err(ApiError::internal)
+}
+
+pub(super) const ADMIN_USER_TASK_SOURCE: &str = "admin.manual";
+pub(super) const ADMIN_USER_TASK_TYPES: [&str; 4] = [
+    jobs::TASK_SYNC_STARRED,
+    jobs::TASK_SYNC_RELEASES,
+    jobs::TASK_SYNC_NOTIFICATIONS,
+    jobs::TASK_BRIEF_GENERATE,
+];
+
+#[derive(Debug, Deserialize)]
+pub struct AdminUserTaskRequest {
+    pub(super) task_type: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct AdminUserTaskResponse {
+    pub(super) task_id: String,
+    pub(super) task_type: String,
+    pub(super) status: String,
+    pub(super) user_id: String,
+}
+
+pub async fn admin_enqueue_user_task(
+    State(state): State<Arc<AppState>>,
+    AdminUser(acting_user_id): AdminUser,
+    Path(target_user_id): Path<String>,
+    ApiJson(req): ApiJson<AdminUserTaskRequest>,
+) -> Result<Json<AdminUserTaskResponse>, ApiError> {
+    let target_user_id = parse_local_id_param(target_user_id, "user_id")?;
+
+    let task_type = req.task_type.trim();
+    let Some(task_type) = ADMIN_USER_TASK_TYPES
+        .into_iter()
+        .find(|candidate| *candidate == task_type)
+    else {
+        return Err(ApiError::bad_request(format!(
+            "invalid task_type, expected one of {}",
+            ADMIN_USER_TASK_TYPES.join("|")
+        )));
+    };
+
+    let target_is_disabled =
+        sqlx::query_scalar::<_, i64>(r#"SELECT is_disabled FROM users WHERE id = ? LIMIT 1"#)
+            .bind(target_user_id.as_str())
+            .fetch_optional(&state.pool)
+            .await
+            .map_err(ApiError::internal)?
+            .ok_or_else(|| {
+                ApiError::new(StatusCode::NOT_FOUND, codes::NOT_FOUND, "user not found")
+            })?;
+    if target_is_disabled != 0 {
+        return Err(ApiError::new(
+            StatusCode::CONFLICT,
+            codes::TARGET_USER_DISABLED,
+            "target user is disabled",
+        ));
+    }
+
+    let payload = if task_type == jobs::TASK_BRIEF_GENERATE {
+        json!({
+            "user_id": target_user_id.as_str(),
+            "key_date": Value::Null,
+        })
+    } else {
+        json!({ "user_id": target_user_id.as_str() })
+    };
+    let task = jobs::enqueue_task(
+        state.as_ref(),
+        jobs::NewTask {
+            task_type: task_type.to_owned(),
+            payload,
+            source: ADMIN_USER_TASK_SOURCE.to_owned(),
+            requested_by: Some(acting_user_id),
+            parent_task_id: None,
+            priority: jobs::TaskPriority::High,
+        },
+    )
+    .await?;
+
+    Ok(Json(AdminUserTaskResponse {
+        task_id: task.task_id,
+        task_type: task.task_type,
+        status: task.status,
+        user_id: target_user_id,
+    }))
+}
+
+#[derive(Debug, Serialize)]
+pub struct SyncRuntimeConfigResponse {
+    pub(super) sync_auto_fetch_interval_minutes: i64,
+    pub(super) retry_recent_failures_interval_minutes: i64,
+    pub(super) repo_release_worker_concurrency: usize,
+    pub(super) repo_refresh_system_budget_per_window: i64,
+    pub(super) recent_sync_tasks: Vec<SyncAutoFetchTaskItem>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct SyncRuntimeConfigPatchRequest {
+    pub(super) sync_auto_fetch_interval_minutes: i64,
+    pub(super) retry_recent_failures_interval_minutes: Option<i64>,
+    pub(super) repo_release_worker_concurrency: Option<i64>,
+    pub(super) repo_refresh_system_budget_per_window: Option<i64>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct AdminRepoGovernanceSummary {
+    pub(super) dedup_repo_count: i64,
+    pub(super) pressure_windows: f64,
+    pub(super) last_full_cycle_completed_at: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct AdminRepoGovernanceCycleSummary {
+    pub(super) active_cycle_id: Option<String>,
+    pub(super) active_cycle_started_at: Option<String>,
+    pub(super) active_cycle_repo_count: i64,
+    pub(super) active_cycle_completed_count: i64,
+}
+
+#[derive(Debug, Serialize)]
+pub struct AdminRepoGovernanceGridCell {
+    pub(super) repo_id: i64,
+    pub(super) age_bucket: String,
+    pub(super) band_label: String,
+    pub(super) urgency_score: f64,
+    pub(super) system_attempt_status: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct AdminRepoGovernanceOverviewResponse {
+    pub(super) summary: AdminRepoGovernanceSummary,
+    pub(super) cycle: AdminRepoGovernanceCycleSummary,
+    pub(super) settings: SyncRuntimeConfigResponse,
+    pub(super) grid_cells: Vec<AdminRepoGovernanceGridCell>,
+}
+
+#[derive(Debug, Serialize, sqlx::FromRow)]
+pub struct AdminRepoGovernanceListItem {
+    pub(super) repo_id: i64,
+    pub(super) repo_full_name: String,
+    pub(super) watcher_user_count: i64,
+    pub(super) watcher_repo_total_sum: i64,
+    pub(super) cached_stargazer_count: Option<i64>,
+    pub(super) priority_rank: i64,
+    pub(super) target_window: i64,
+    pub(super) target_interval_minutes: i64,
+    pub(super) urgency_score: f64,
+    pub(super) urgency_bucket: String,
+    pub(super) system_last_selected_at: Option<String>,
+    pub(super) system_last_success_at: Option<String>,
+    pub(super) system_last_attempt_at: Option<String>,
+    pub(super) system_last_attempt_status: Option<String>,
+    pub(super) system_last_attempt_error: Option<String>,
+    pub(super) actual_last_success_at: Option<String>,
+    pub(super) actual_last_success_source: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct AdminRepoGovernanceListResponse {
+    pub(super) items: Vec<AdminRepoGovernanceListItem>,
+    pub(super) page: i64,
+    pub(super) page_size: i64,
+    pub(super) total: i64,
+    pub(super) target_window_options: Vec<AdminRepoGovernanceTargetWindowOption>,
+}
+
+#[derive(Debug, Serialize, sqlx::FromRow)]
+pub struct AdminRepoGovernanceTargetWindowOption {
+    pub(super) target_window: i64,
+    pub(super) repo_count: i64,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct AdminRepoGovernanceListQuery {
+    pub(super) page: Option<i64>,
+    pub(super) page_size: Option<i64>,
+    pub(super) query: Option<String>,
+    pub(super) aging: Option<String>,
+    pub(super) target_windows: Option<String>,
+    pub(super) urgency_min: Option<f64>,
+    pub(super) urgency_max: Option<f64>,
+}
+
+pub(super) fn parse_admin_repo_target_windows(raw: Option<&str>) -> Result<Vec<i64>, ApiError> {
+    let Some(raw) = raw else {
+        return Ok(Vec::new());
+    };
+    let trimmed = raw.trim();
+    if trimmed.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let mut windows = Vec::new();
+    let mut seen = HashSet::new();
+    for part in trimmed.split(',') {
+        let window = part.trim().parse::<i64>().map_err(|_| {
+            ApiError::bad_request("target_windows must be comma-separated positive integers")
+        })?;
+        if window <= 0 {
+            return Err(ApiError::bad_request(
+                "target_windows must be comma-separated positive integers",
+            ));
+        }
+        if seen.insert(window) {
+            windows.push(window);
+        }
+    }
+    Ok(windows)
+}
+
+pub(super) fn validate_admin_repo_urgency_range(
+    urgency_min: Option<f64>,
+    urgency_max: Option<f64>,
+) -> Result<(), ApiError> {
+    if urgency_min.is_some_and(|value| !value.is_finite() || value < 0.0) {
+        return Err(ApiError::bad_request(
+            "urgency_min must be a finite number >= 0",
+        ));
+    }
+    if urgency_max.is_some_and(|value| !value.is_finite() || value < 0.0) {
+        return Err(ApiError::bad_request(
+            "urgency_max must be a finite number >= 0",
+        ));
+    }
+    if let (Some(min), Some(max)) = (urgency_min, urgency_max)
+        && min > max
+    {
+        return Err(ApiError::bad_request(
+            "urgency_min must be less than or equal to urgency_max",
+        ));
+    }
+    Ok(())
+}
+
+pub(super) async fn load_sync_runtime_config(
+    state: &AppState,
+) -> Result<SyncRuntimeConfigResponse, ApiError> {
+    let interval = admin_runtime::load_sync_auto_fetch_interval_minutes(&state.pool)
+        .await
+        .map_err(ApiError::internal)?;
+    let repo_release_worker_concurrency =
+        admin_runtime::load_repo_release_worker_concurrency(&state.pool)
+            .await
+            .map_err(ApiError::internal)?;
+    let retry_recent_failures_interval_minutes =
+        admin_runtime::load_retry_recent_failures_interval_minutes(&state.pool)
+            .await
+            .map_err(ApiError::internal)?;
+    let repo_refresh_system_budget_per_window =
+        admin_runtime::load_repo_refresh_system_budget_per_window(&state.pool)
+            .await
+            .map_err(ApiError::internal)?;
+
+    Ok(SyncRuntimeConfigResponse {
+        sync_auto_fetch_interval_minutes: interval,
+        retry_recent_failures_interval_minutes,
+        repo_release_worker_concurrency,
+        repo_refresh_system_budget_per_window,
+        recent_sync_tasks: load_recent_sync_auto_fetch_tasks(state).await?,
+    })
+}
+
+pub(super) async fn persist_sync_runtime_config(
+    state: &AppState,
+    req: SyncRuntimeConfigPatchRequest,
+) -> Result<SyncRuntimeConfigResponse, ApiError> {
+    if !(1..=120).contains(&req.sync_auto_fetch_interval_minutes) {
+        return Err(ApiError::bad_request(
+            "sync_auto_fetch_interval_minutes must be between 1 and 120",
+        ));
+    }
+    if let Some(interval) = req.retry_recent_failures_interval_minutes
+        && !(1..=120).contains(&interval)
+    {
+        return Err(ApiError::bad_request(
+            "retry_recent_failures_interval_minutes must be between 1 and 120",
+        ));
+    }
+    if let Some(concurrency) = req.repo_release_worker_concurrency
+        && !(1..=32).contains(&concurrency)
+    {
+        return Err(ApiError::bad_request(
+            "repo_release_worker_concurrency must be between 1 and 32",
+        ));
+    }
+    if let Some(budget) = req.repo_refresh_system_budget_per_window
+        && !(1..=20_000).contains(&budget)
+    {
+        return Err(ApiError::bad_request(
+            "repo_refresh_system_budget_per_window must be between 1 and 20000",
+        ));
+    }
+
+    admin_runtime::update_sync_auto_fetch_interval_minutes(
+        &state.pool,
+        req.sync_auto_fetch_interval_minutes,
+    )
+    .await
+    .map_err(ApiError::internal)?;
+    if let Some(interval) = req.retry_recent_failures_interval_minutes {
+        admin_runtime::update_retry_recent_failures_interval_minutes(&state.pool, interval)
+            .await
+            .map_err(ApiError::internal)?;
+    }
+    if let Some(concurrency) = req.repo_release_worker_concurrency {
+        admin_runtime::update_repo_release_worker_concurrency(&state.pool, concurrency)
+            .await
+            .map_err(ApiError::internal)?;
+    }
+    if let Some(budget) = req.repo_refresh_system_budget_per_window {
+        admin_runtime::update_repo_refresh_system_budget_per_window(&state.pool, budget)
+            .await
+            .map_err(ApiError::internal)?;
+    }
+
+    load_sync_runtime_config(state).await
+}
+
+pub async fn admin_get_sync_runtime_config(
+    State(state): State<Arc<AppState>>,
+    _: AdminUser,
+) -> Result<Json<SyncRuntimeConfigResponse>, ApiError> {
+    Ok(Json(load_sync_runtime_config(state.as_ref()).await?))
+}
+
+pub async fn admin_patch_sync_runtime_config(
+    State(state): State<Arc<AppState>>,
+    _: AdminUser,
+    ApiJson(req): ApiJson<SyncRuntimeConfigPatchRequest>,
+) -> Result<Json<SyncRuntimeConfigResponse>, ApiError> {
+    Ok(Json(
+        persist_sync_runtime_config(state.as_ref(), req).await?,
+    ))
+}
+
+pub async fn admin_get_repo_governance_overview(
+    State(state): State<Arc<AppState>>,
+    _: AdminUser,
+) -> Result<Json<AdminRepoGovernanceOverviewResponse>, ApiError> {
+    let settings = load_sync_runtime_config(state.as_ref()).await?;
+
+    let dedup_repo_count =
+        sqlx::query_scalar::<_, i64>(r#"SELECT COUNT(*) FROM repo_refresh_governance_snapshots"#)
+            .fetch_one(&state.pool)
+            .await
+            .map_err(ApiError::internal)?;
+
+    let pressure_windows = sqlx::query_scalar::<_, f64>(
+        r#"
+        SELECT COALESCE(
+          SUM(
+            MAX(
+              0,
+              MIN(COALESCE(urgency_score, 1), 4.0) - 1.0
+            )
+          ) / CAST(? AS REAL),
+          0
+        )
+        FROM repo_refresh_governance_snapshots
+        "#,
+    )
+    .bind(settings.repo_refresh_system_budget_per_window)
+    .fetch_one(&state.pool)
+    .await
+    .map_err(ApiError::internal)?;
+
+    let last_full_cycle_completed_at = sqlx::query_scalar::<_, Option<String>>(
+        r#"
+        SELECT completed_at
+        FROM repo_refresh_governance_cycles
+        WHERE status = 'completed'
+        ORDER BY completed_at DESC, updated_at DESC
+        LIMIT 1
+        "#,
+    )
+    .fetch_optional(&state.pool)
+    .await
+    .map_err(ApiError::internal)?
+    .flatten();
+
+    let active_cycle_row = sqlx::query(
+        r#"
+        SELECT id, started_at, frozen_repo_count, completed_repo_count
+        FROM repo_refresh_governance_cycles
+        WHERE status = 'active'
+        ORDER BY started_at DESC
+        LIMIT 1
+        "#,
+    )
+    .fetch_optional(&state.pool)
+    .await
+    .map_err(ApiError::internal)?;
+
+    let cycle = if let Some(row) = active_cycle_row {
+        AdminRepoGovernanceCycleSummary {
+            active_cycle_id: row.get("id"),
+            active_cycle_started_at: row.get("started_at"),
+            active_cycle_repo_count: row.get("frozen_repo_count"),
+            active_cycle_completed_count: row.get("completed_repo_count"),
+        }
+    } else {
+        AdminRepoGovernanceCycleSummary {
+            active_cycle_id: None,
+            active_cycle_started_at: None,
+            active_cycle_repo_count: 0,
+            active_cycle_completed_count: 0,
+        }
+    };
+
+    let grid_rows = sqlx::query(
+        r#"
+        SELECT
+          repo_id,
+          actual_last_success_at,
+          target_interval_minutes,
+          urgency_score,
+          system_last_attempt_status
+        FROM repo_refresh_governance_snapshots
+        ORDER BY priority_rank ASC, repo_id ASC
+        "#,
+    )
+    .fetch_all(&state.pool)
+    .await
+    .map_err(ApiError::internal)?;
+    let now = chrono::Utc::now();
+    let grid_cells = grid_rows
+        .into_iter()
+        .map(|row| {
+            let actual_last_success_at: Option<String> = row.get("actual_last_success_at");
+            let target_interval_minutes: i64 = row.get("target_interval_minutes");
+            let urgency_score: f64 = row.get("urgency_score");
+            let age_bucket = actual_last_success_at
+                .as_deref()
+                .and_then(|value| chrono::DateTime::parse_from_rfc3339(value).ok())
+                .map(|value| {
+                    now.signed_duration_since(value.with_timezone(&chrono::Utc))
+                        .num_minutes()
+                })
+                .map(|age_minutes| {
+                    if age_minutes <= 30 {
+                        "fresh"
+                    } else if age_minutes <= 6 * 60 {
+                        "warm"
+                    } else if age_minutes <= 24 * 60 {
+                        "aging"
+                    } else {
+                        "stale"
+                    }
+                })
+                .unwrap_or("unknown")
+                .to_owned();
+            let band_label = if target_interval_minutes <= 10 {
+                "10m"
+            } else if target_interval_minutes <= 60 {
+                "1h"
+            } else if target_interval_minutes <= 6 * 60 {
+                "6h"
+            } else {
+                "long"
+            }
+            .to_owned();
+            AdminRepoGovernanceGridCell {
+                repo_id: row.get("repo_id"),
+                age_bucket,
+                band_label,
+                urgency_score,
+                system_attempt_status: row.get("system_last_attempt_status"),
+            }
+        })
+        .collect();
+
+    Ok(Json(AdminRepoGovernanceOverviewResponse {
+        summary: AdminRepoGovernanceSummary {
+            dedup_repo_count,
+            pressure_windows,
+            last_full_cycle_completed_at,
+        },
+        cycle,
+        settings,
+        grid_cells,
+    }))
+}
+
+pub async fn admin_list_repo_governance(
+    State(state): State<Arc<AppState>>,
+    _: AdminUser,
+    Query(query): Query<AdminRepoGovernanceListQuery>,
+) -> Result<Json<AdminRepoGovernanceListResponse>, ApiError> {
+    let page = query.page.unwrap_or(1).max(1);
+    let page_size = query.page_size.unwrap_or(100).clamp(1, 500);
+    let offset = (page - 1) * page_size;
+    let query_text = query.query.unwrap_or_default().trim().to_lowercase();
+    let query_like = format!("%{query_text}%");
+    let aging = query.aging.unwrap_or_else(|| "all".to_owned());
+    if !matches!(aging.as_str(), "all" | "stale" | "missing") {
+        return Err(ApiError::bad_request("invalid aging filter"));
+    }
+    let target_windows = parse_admin_repo_target_windows(query.target_windows.as_deref())?;
+    validate_admin_repo_urgency_range(query.urgency_min, query.urgency_max)?;
+
+    let target_window_options = sqlx::query_as::<_, AdminRepoGovernanceTargetWindowOption>(
+        r#"
+        SELECT target_window, COUNT(*) AS repo_count
+        FROM repo_refresh_governance_snapshots
+        GROUP BY target_window
+        ORDER BY target_window ASC
+        "#,
+    )
+    .fetch_all(&state.pool)
+    .await
+    .map_err(ApiError::internal)?;
+
+    let append_repo_governance_filters =
+        |builder: &mut QueryBuilder<'_, sqlx::Sqlite>, include_limit: bool| {
+            builder.push(" WHERE (");
+            builder.push_bind(query_text.clone());
+            builder.push(" = '' OR lower(repo_full_name) LIKE ");
+            builder.push_bind(query_like.clone());
+            builder.push(")");
+            builder.push(" AND (");
+            builder.push_bind(aging.clone());
+            builder.push(" = 'all' OR (");
+            builder.push_bind(aging.clone());
+            builder.push(" = 'stale' AND actual_last_success_at IS NOT NULL AND julianday(actual_last_success_at) <= julianday('now', '-1 day')) OR (");
+            builder.push_bind(aging.clone());
+            builder.push(" = 'missing' AND actual_last_success_at IS NULL))");
+            if !target_windows.is_empty() {
+                builder.push(" AND target_window IN (");
+                let mut separated = builder.separated(", ");
+                for window in &target_windows {
+                    separated.push_bind(*window);
+                }
+                separated.push_unseparated(")");
+            }
+            if let Some(urgency_min) = query.urgency_min {
+                builder.push(" AND urgency_score >= ");
+                builder.push_bind(urgency_min);
+            }
+            if let Some(urgency_max) = query.urgency_max {
+                builder.push(" AND urgency_score <= ");
+                builder.push_bind(urgency_max);
+            }
+            if include_limit {
+                builder.push(
+                    r#"
+        ORDER BY
+          urgency_score DESC,
+          priority_rank ASC,
+          repo_id ASC
+        LIMIT "#,
+                );
+                builder.push_bind(page_size);
+                builder.push(" OFFSET ");
+                builder.push_bind(offset);
+            }
+        };
+
+    let mut total_query = QueryBuilder::new(
+        r#"
+        SELECT COUNT(*)
+        FROM repo_refresh_governance_snapshots
+        "#,
+    );
+    append_repo_governance_filters(&mut total_query, false);
+    let total = total_query
+        .build_query_scalar::<i64>()
+        .fetch_one(&state.pool)
+        .await
+        .map_err(ApiError::internal)?;
+
+    let mut items_query = QueryBuilder::new(
+        r#"
+        SELECT
+          repo_id,
+          repo_full_name,
+          watcher_user_count,
+          watcher_repo_total_sum,
+          cached_stargazer_count,
+          priority_rank,
+          target_window,
+          target_interval_minutes,
+          urgency_score,
+          urgency_bucket,
+          system_last_selected_at,
+          system_last_success_at,
+          system_last_attempt_at,
+          system_last_attempt_status,
+          system_last_attempt_error,
+          actual_last_success_at,
+          actual_last_success_source
+        FROM repo_refresh_governance_snapshots
+        "#,
+    );
+    append_repo_governance_filters(&mut items_query, true);
+    let items = items_query
+        .build_query_as::<AdminRepoGovernanceListItem>()
+        .fetch_all(&state.pool)
+        .await
+        .map_err(ApiError::internal)?;
+
+    Ok(Json(AdminRepoGovernanceListResponse {
+        items,
+        page,
+        page_size,
+        total,
+        target_window_options,
+    }))
+}
+
+#[derive(Debug, Serialize)]
+pub struct AdminJobsOverviewResponse {
+    pub(super) queued: i64,
+    pub(super) running: i64,
+    pub(super) failed_24h: i64,
+    /// Subset of `failed_24h` the worker abandoned on timeout.
+    pub(super) timed_out_24h: i64,
+    pub(super) succeeded_24h: i64,
+    pub(super) enabled_scheduled_slots: i64,
+    pub(super) total_scheduled_slots: i64,
+    pub(super) users_due_for_sync_next_hour: i64,
+    pub(super) last_prune_at: Option<String>,
+}
+
+pub async fn admin_jobs_overview(
+    State(state): State<Arc<AppState>>,
+    _: AdminUser,
+) -> Result<Json<AdminJobsOverviewResponse>, ApiError> {
+    let queued =
+        sqlx::query_scalar::<_, i64>(r#"SELECT COUNT(*) FROM job_tasks WHERE status = 'queued'"#)
+            .fetch_one(&state.pool)
+            .await
+            .map_err(ApiError::internal)?;
+    let running =
+        sqlx::query_scalar::<_, i64>(r#"SELECT COUNT(*) FROM job_tasks WHERE status = 'running'"#)
+            .fetch_one(&state.pool)
+            .await
+            .map_err(ApiError::internal)?;
+    let failed_24h = sqlx::query_scalar::<_, i64>(
+        r#"
+        SELECT COUNT(*)
+        FROM job_tasks
+        WHERE status = 'failed'
+          AND finished_at IS NOT NULL
+          AND finished_at >= ?
+        "#,
+    )
+    .bind((chrono::Utc::now() - chrono::Duration::hours(24)).to_rfc3339())
+    .fetch_one(&state.pool)
+    .await
+    .map_err(ApiError::internal)?;
+    let timed_out_24h = sqlx::query_scalar::<_, i64>(
+        r#"
+        SELECT COUNT(*)
+        FROM job_tasks
+        WHERE status = 'failed'
+          AND finished_at IS NOT NULL
+          AND finished_at >= ?
+          AND error_message LIKE ? || '%'
+        "#,
+    )
+    .bind((chrono::Utc::now() - chrono::Duration::hours(24)).to_rfc3339())
+    .bind(jobs::TASK_TIMEOUT_ERROR_PREFIX)
+    .fetch_one(&state.pool)
+    .await
+    .map_err(ApiError::internal)?;
+    let succeeded_24h = sqlx::query_scalar::<_, i64>(
+        r#"
+        SELECT COUNT(*)
+        FROM job_tasks
+        WHERE status = 'succeeded'
+          AND finished_at IS NOT NULL
+          AND finished_at >= ?
+        "#,
+    )
+    .bind((chrono::Utc::now() - chrono::Duration::hours(24)).to_rfc3339())
+    .fetch_one(&state.pool)
+    .await
+    .map_err(ApiError::internal)?;
+
+    let enabled_scheduled_slots = sqlx::query_scalar::<_, i64>(
+        r#"SELECT COUNT(*) FROM daily_brief_hour_slots WHERE enabled = 1"#,
+    )
+    .fetch_one(&state.pool)
+    .await
+    .map_err(ApiError::internal)?;
+    let total_scheduled_slots =
+        sqlx::query_scalar::<_, i64>(r#"SELECT COUNT(*) FROM daily_brief_hour_slots"#)
+            .fetch_one(&state.pool)
+            .await
+            .map_err(ApiError::internal)?;
+    let users_due_for_sync_next_hour = jobs::count_users_due_for_sync(
+        state.as_ref(),
+        chrono::Utc::now() + chrono::Duration::hours(1),
+    )
+    .await
+    .map_err(ApiError::internal)?;
+    let last_prune_at = jobs::load_last_maintenance_prune_at(state.as_ref())
+        .await
+        .map_err(ApiError::internal)?;
+
+    Ok(Json(AdminJobsOverviewResponse {
+        queued,
+        running,
+        failed_24h,
+        timed_out_24h,
+        succeeded_24h,
+        enabled_scheduled_slots,
+        total_scheduled_slots,
+        users_due_for_sync_next_hour,
+        last_prune_at,
+    }))
+}
+
+#[derive(Debug, Deserialize)]
+pub struct AdminDashboardQuery {
+    pub(super) window: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct AdminDashboardResponse {
+    pub(super) generated_at: String,
+    pub(super) time_zone: String,
+    pub(super) summary: AdminDashboardSummary,
+    pub(super) today_live: AdminDashboardTodayLive,
+    pub(super) status_breakdown: AdminDashboardStatusBreakdown,
+    pub(super) task_share: Vec<AdminDashboardTaskShareItem>,
+    pub(super) trend_points: Vec<AdminDashboardTrendPoint>,
+    pub(super) llm_health: AdminDashboardLlmHealth,
+    pub(super) window_meta: AdminDashboardWindowMeta,
+}
+
+#[derive(Debug, Serialize)]
+pub struct AdminDashboardSummary {
+    pub(super) total_users: i64,
+    pub(super) active_users_today: i64,
+    pub(super) ongoing_tasks_total: i64,
+    pub(super) queued_tasks: i64,
+    pub(super) running_tasks: i64,
+    pub(super) ongoing_by_task: AdminDashboardOngoingTaskBreakdown,
+}
+
+#[derive(Debug, Serialize)]
+pub struct AdminDashboardOngoingTaskBreakdown {
+    pub(super) translations: i64,
+    pub(super) summaries: i64,
+    pub(super) briefs: i64,
+}
+
+#[derive(Debug, Serialize)]
+pub struct AdminDashboardTodayLive {
+    pub(super) date: String,
+    pub(super) total_users: i64,
+    pub(super) active_users: i64,
+    pub(super) ongoing_tasks_total: i64,
+    pub(super) queued_tasks: i64,
+    pub(super) running_tasks: i64,
+}
+
+#[derive(Debug, Serialize)]
+pub struct AdminDashboardStatusBreakdown {
+    pub(super) queued_total: i64,
+    pub(super) running_total: i64,
+    pub(super) succeeded_total: i64,
+    pub(super) failed_total: i64,
+    pub(super) canceled_total: i64,
+    pub(super) business_counts: AdminDashboardBusinessCounts,
+    pub(super) total: i64,
+    pub(super) items: Vec<AdminDashboardTaskStatusItem>,
+}
+
+#[derive(Debug, Serialize, Clone, Default)]
+pub struct AdminDashboardBusinessCounts {
+    pub(super) ok: i64,
+    pub(super) partial: i64,
+    pub(super) failed: i64,
+    pub(super) disabled: i64,
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct AdminDashboardTaskStatusItem {
+    pub(super) task_type: String,
+    pub(super) label: String,
+    pub(super) queued: i64,
+    pub(super) running: i64,
+    pub(super) succeeded: i64,
+    pub(super) failed: i64,
+    pub(super) canceled: i64,
+    pub(super) business_counts: AdminDashboardBusinessCounts,
+    pub(super) total: i64,
+    pub(super) success_rate: f64,
+    pub(super) business_success_rate: f64,
+}
+
+#[derive(Debug, Serialize)]
+pub struct AdminDashboardTaskShareItem {
+    pub(super) task_type: String,
+    pub(super) label: String,
+    pub(super) total: i64,
+    pub(super) share_ratio: f64,
+    pub(super) success_rate: f64,
+    pub(super) business_counts: AdminDashboardBusinessCounts,
+    pub(super) business_success_rate: f64,
+}
+
+#[derive(Debug, Serialize)]
+pub struct AdminDashboardTrendPoint {
+    pub(super) date: String,
+    pub(super) label: String,
+    pub(super) total_users: i64,
+    pub(super) active_users: i64,
+    pub(super) translations_total: i64,
+    pub(super) translations_failed: i64,
+    pub(super) translations_partial: i64,
+    pub(super) translations_business_failed: i64,
+    pub(super) summaries_total: i64,
+    pub(super) summaries_failed: i64,
+    pub(super) summaries_partial: i64,
+    pub(super) summaries_business_failed: i64,
+    pub(super) briefs_total: i64,
+    pub(super) briefs_failed: i64,
+    pub(super) briefs_partial: i64,
+    pub(super) briefs_business_failed: i64,
+}
+
+#[derive(Debug, Serialize)]
+pub struct AdminDashboardWindowMeta {
+    pub(super) selected_window: String,
+    pub(super) available_windows: Vec<String>,
+    pub(super) window_start: String,
+    pub(super) window_end: String,
+    pub(super) point_count: i64,
+}
+
+#[derive(Debug, Serialize)]
+pub struct AdminDashboardLlmHealthBucket {
+    pub(super) label: String,
+    pub(super) count: i64,
+}
+
+#[derive(Debug, Serialize)]
+pub struct AdminDashboardLlmHealth {
+    pub(super) calls_24h: i64,
+    pub(super) failed_24h: i64,
+    pub(super) last_failure_at: Option<String>,
+    pub(super) top_failure_reasons: Vec<AdminDashboardLlmHealthBucket>,
+    pub(super) top_failure_sources: Vec<AdminDashboardLlmHealthBucket>,
+}
+
+#[allow(dead_code)]
+#[derive(Debug, sqlx::FromRow)]
+pub(super) struct AdminDashboardRollupRow {
+    pub(super) rollup_date: String,
+    pub(super) task_type: String,
+    pub(super) total_users: i64,
+    pub(super) active_users: i64,
+    pub(super) queued_count: i64,
+    pub(super) running_count: i64,
+    pub(super) succeeded_count: i64,
+    pub(super) failed_count: i64,
+    pub(super) canceled_count: i64,
+    pub(super) business_ok_count: i64,
+    pub(super) business_partial_count: i64,
+    pub(super) business_failed_count: i64,
+    pub(super) business_disabled_count: i64,
+}
+
+#[derive(Debug, Clone, Default, sqlx::FromRow)]
+pub(super) struct AdminDashboardStatusCountRow {
+    pub(super) queued_count: i64,
+    pub(super) running_count: i64,
+    pub(super) succeeded_count: i64,
+    pub(super) failed_count: i64,
+    pub(super) canceled_count: i64,
+    pub(super) business_ok_count: i64,
+    pub(super) business_partial_count: i64,
+    pub(super) business_failed_count: i64,
+    pub(super) business_disabled_count: i64,
+}
+
+#[derive(Debug, Clone, sqlx::FromRow)]
+pub(super) struct AdminDashboardReleaseTaskRow {
+    pub(super) status: String,
+    pub(super) payload_json: String,
+    pub(super) result_json: Option<String>,
+}
+
+pub(super) const ADMIN_DASHBOARD_DEFAULT_WINDOW_DAYS: i64 = 7;
+pub(crate) const ADMIN_DASHBOARD_PREAGGREGATE_DAYS: i64 = 30;
+pub(super) const ADMIN_DASHBOARD_WINDOW_OPTIONS: [(&str, i64); 2] = [("7d", 7), ("30d", 30)];
+
+pub(super) fn admin_dashboard_system_time_zone(state: &AppState) -> Tz {
+    briefs::default_daily_brief_time_zone(&state.config)
+        .parse::<Tz>()
+        .unwrap_or(chrono_tz::UTC)
+}
+
+pub(super) fn resolve_admin_dashboard_window_days(
+    raw: Option<&str>,
+) -> Result<(String, i64), ApiError> {
+    let trimmed = raw.unwrap_or_default().trim();
+    if trimmed.is_empty() {
+        return Ok(("7d".to_owned(), ADMIN_DASHBOARD_DEFAULT_WINDOW_DAYS));
+    }
+    ADMIN_DASHBOARD_WINDOW_OPTIONS
+        .iter()
+        .find_map(|(label, days)| (*label == trimmed).then_some((label.to_string(), *days)))
+        .ok_or_else(|| ApiError::bad_request("invalid window"))
+}
+
+pub(super) fn local_day_bounds_utc(
+    time_zone: Tz,
+    day: chrono::NaiveDate,
+) -> Result<(chrono::DateTime<chrono::Utc>, chrono::DateTime<chrono::Utc>), ApiError> {
+    let start_local = time_zone
+        .with_ymd_and_hms(day.year(), day.month(), day.day(), 0, 0, 0)
+        .single()
+        .or_else(|| {
+            time_zone
+                .with_ymd_and_hms(day.year(), day.month(), day.day(), 0, 0, 0)
+                .earliest()
+        })
+        .ok_or_else(|| ApiError::internal(anyhow::anyhow!("invalid local day start")))?;
+    let next_day = day
+        .succ_opt()
+        .ok_or_else(|| ApiError::internal(anyhow::anyhow!("invalid next local day")))?;
+    let end_local = time_zone
+        .with_ymd_and_hms(next_day.year(), next_day.month(), next_day.day(), 0, 0, 0)
+        .single()
+        .or_else(|| {
+            time_zone
+                .with_ymd_and_hms(next_day.year(), next_day.month(), next_day.day(), 0, 0, 0)
+                .earliest()
+        })
+        .ok_or_else(|| ApiError::internal(anyhow::anyhow!("invalid local day end")))?;
+    Ok((
+        start_local.with_timezone(&chrono::Utc),
+        end_local.with_timezone(&chrono::Utc),
+    ))
+}
+
+pub(super) async fn count_admin_dashboard_total_users_now(
+    pool: &sqlx::SqlitePool,
+) -> Result<i64, ApiError> {
+    sqlx::query_scalar::<_, i64>(
+        r#"
+        SELECT COUNT(*)
+        FROM users
+        "#,
+    )
+    .fetch_one(pool)
+    .await
+    .map_err(ApiError::internal)
+}
+
+pub(super) async fn count_admin_dashboard_total_users_at(
+    pool: &sqlx::SqlitePool,
+    end_at: &str,
+) -> Result<i64, ApiError> {
+    sqlx::query_scalar::<_, i64>(
+        r#"
+        SELECT COUNT(*)
+        FROM users
+        WHERE julianday(created_at) < julianday(?)
+        "#,
+    )
+    .bind(end_at)
+    .fetch_one(pool)
+    .await
+    .map_err(ApiError::internal)
+}
+
+pub(super) async fn count_admin_dashboard_active_users_between(
+    pool: &sqlx::SqlitePool,
+    start_at: &str,
+    end_at: &str,
+) -> Result<i64, ApiError> {
+    sqlx::query_scalar::<_, i64>(
+        r#"
+        SELECT COUNT(*)
+        FROM users
+        WHERE last_active_at IS NOT NULL
+          AND julianday(last_active_at) >= julianday(?)
+          AND julianday(last_active_at) <= julianday(?)
+        "#,
+    )
+    .bind(start_at)
+    .bind(end_at)
+    .fetch_one(pool)
+    .await
+    .map_err(ApiError::internal)
+}
+
+pub(super) fn admin_dashboard_status_count_business_counts(
+    counts: &AdminDashboardStatusCountRow,
+) -> AdminDashboardBusinessCounts {
+    AdminDashboardBusinessCounts {
+        ok: counts.business_ok_count,
+        partial: counts.business_partial_count,
+        failed: counts.business_failed_count,
+        disabled: counts.business_disabled_count,
+    }
+}
+
+pub(super) fn admin_dashboard_business_terminal_total(
+    counts: &AdminDashboardBusinessCounts,
+) -> i64 {
+    counts.ok + counts.partial + counts.failed + counts.disabled
+}
+
+pub(super) fn admin_dashboard_business_success_rate(counts: &AdminDashboardBusinessCounts) -> f64 {
+    let terminal_total = admin_dashboard_business_terminal_total(counts);
+    if terminal_total <= 0 {
+        0.0
+    } else {
+        counts.ok as f64 / terminal_total as f64
+    }
+}
+
+pub(super) async fn load_admin_dashboard_release_task_status_counts(
+    pool: &sqlx::SqlitePool,
+    task_type: &str,
+    start_at: &str,
+    end_at: &str,
+) -> Result<AdminDashboardStatusCountRow, ApiError> {
+    let rows = sqlx::query_as::<_, AdminDashboardReleaseTaskRow>(
+        r#"
+        SELECT
+          status,
+          payload_json,
+          result_json
+        FROM job_tasks
+        WHERE task_type = ?
+          AND julianday(created_at) >= julianday(?)
+          AND julianday(created_at) <= julianday(?)
+        "#,
+    )
+    .bind(task_type)
+    .bind(start_at)
+    .bind(end_at)
+    .fetch_all(pool)
+    .await
+    .map_err(ApiError::internal)?;
+
+    let mut counts = AdminDashboardStatusCountRow::default();
+    for row in rows {
+        match row.status.as_str() {
+            jobs::STATUS_QUEUED => counts.queued_count += 1,
+            jobs::STATUS_RUNNING => counts.running_count += 1,
+            jobs::STATUS_SUCCEEDED => counts.succeeded_count += 1,
+            jobs::STATUS_FAILED => counts.failed_count += 1,
+            jobs::STATUS_CANCELED => counts.canceled_count += 1,
+            _ => {}
+        }
+
+        let payload_value = parse_json_value(Some(row.payload_json.as_str()));
+        let payload_object = payload_value
+            .as_ref()
+            .and_then(serde_json::Value::as_object);
+        let result_value = parse_json_value(row.result_json.as_deref());
+        let result_object = result_value.as_ref().and_then(serde_json::Value::as_object);
+        let summary = derive_release_batch_summary(payload_object, result_object);
+        match release_batch_business_code(row.status.as_str(), &summary) {
+            "ok" => counts.business_ok_count += 1,
+            "partial" => counts.business_partial_count += 1,
+            "failed" => counts.business_failed_count += 1,
+            "disabled" => counts.business_disabled_count += 1,
+            _ => {}
+        }
+    }
+
+    Ok(counts)
+}
+
+pub(super) async fn load_admin_dashboard_brief_task_status_counts(
+    pool: &sqlx::SqlitePool,
+    task_type: &str,
+    start_at: &str,
+    end_at: &str,
+) -> Result<AdminDashboardStatusCountRow, ApiError> {
+    sqlx::query_as::<_, AdminDashboardStatusCountRow>(
+        r#"
+        SELECT
+          COALESCE(SUM(CASE WHEN status = 'queued' THEN 1 ELSE 0 END), 0) AS queued_count,
+          COALESCE(SUM(CASE WHEN status = 'running' THEN 1 ELSE 0 END), 0) AS running_count,
+          COALESCE(SUM(CASE WHEN status = 'succeeded' THEN 1 ELSE 0 END), 0) AS succeeded_count,
+          COALESCE(SUM(CASE WHEN status = 'failed' THEN 1 ELSE 0 END), 0) AS failed_count,
+          COALESCE(SUM(CASE WHEN status = 'canceled' THEN 1 ELSE 0 END), 0) AS canceled_count,
+          COALESCE(SUM(CASE WHEN business_code = 'ok' THEN 1 ELSE 0 END), 0) AS business_ok_count,
+          COALESCE(SUM(CASE WHEN business_code = 'partial' THEN 1 ELSE 0 END), 0) AS business_partial_count,
+          COALESCE(SUM(CASE WHEN business_code = 'failed' THEN 1 ELSE 0 END), 0) AS business_failed_count,
+          COALESCE(SUM(CASE WHEN business_code = 'disabled' THEN 1 ELSE 0 END), 0) AS business_disabled_count
+        FROM (
+          SELECT
+            status,
+            CASE
+              WHEN status = 'canceled' THEN 'unknown'
+              WHEN status = 'queued' OR status = 'running' THEN 'unknown'
+              WHEN status = 'failed' THEN 'failed'
+              WHEN canceled_flag != 0 THEN 'partial'
+              WHEN total_users = 0 THEN 'ok'
+              WHEN failed_users > 0 AND succeeded_users = 0 THEN 'failed'
+              WHEN failed_users > 0 THEN 'partial'
+              WHEN succeeded_users > 0 THEN 'ok'
+              ELSE 'unknown'
+            END AS business_code
+          FROM (
+            SELECT
+              status,
+              COALESCE(CAST(json_extract(result_json, '$.total') AS INTEGER), 0) AS total_users,
+              COALESCE(CAST(json_extract(result_json, '$.succeeded') AS INTEGER), 0) AS succeeded_users,
+              COALESCE(CAST(json_extract(result_json, '$.failed') AS INTEGER), 0) AS failed_users,
+              COALESCE(CAST(json_extract(result_json, '$.canceled') AS INTEGER), 0) AS canceled_flag
+            FROM job_tasks
+            WHERE task_type = ?
+              AND julianday(created_at) >= julianday(?)
+              AND julianday(created_at) <= julianday(?)
+          ) task_rows
+        ) business_rows
+        "#,
+    )
+    .bind(task_type)
+    .bind(start_at)
+    .bind(end_at)
+    .fetch_one(pool)
+    .await
+    .map_err(ApiError::internal)
+}
+
+pub(super) async fn load_admin_dashboard_task_status_counts(
+    pool: &sqlx::SqlitePool,
+    task_type: &str,
+    start_at: &str,
+    end_at: &str,
+) -> Result<AdminDashboardStatusCountRow, ApiError> {
+    match task_type {
+        jobs::TASK_TRANSLATE_RELEASE_BATCH | jobs::TASK_SUMMARIZE_RELEASE_SMART_BATCH => {
+            load_admin_dashboard_release_task_status_counts(pool, task_type, start_at, end_at).await
+        }
+        jobs::TASK_BRIEF_DAILY_SLOT => {
+            load_admin_dashboard_brief_task_status_counts(pool, task_type, start_at, end_at).await
+        }
+        _ => Ok(AdminDashboardStatusCountRow::default()),
+    }
+}
+
+pub(super) async fn upsert_admin_dashboard_rollup_for_day(
+    state: &AppState,
+    time_zone: Tz,
+    day: chrono::NaiveDate,
+) -> Result<(), ApiError> {
+    let (start_utc, end_utc) = local_day_bounds_utc(time_zone, day)?;
+    let start_at = start_utc.to_rfc3339();
+    let end_at = end_utc.to_rfc3339();
+    let total_users = count_admin_dashboard_total_users_at(&state.pool, end_at.as_str()).await?;
+    let active_users =
+        count_admin_dashboard_active_users_between(&state.pool, start_at.as_str(), end_at.as_str())
+            .await?;
+    let updated_at = chrono::Utc::now().to_rfc3339();
+    let day_value = day.format("%Y-%m-%d").to_string();
+    let time_zone_value = time_zone.name().to_owned();
+
+    for (task_type, _) in ADMIN_DASHBOARD_TASK_TYPES {
+        let counts = load_admin_dashboard_task_status_counts(
+            &state.pool,
+            task_type,
+            start_at.as_str(),
+            end_at.as_str(),
+        )
+        .await?;
+        sqlx::query(
+            r#"
+            INSERT INTO admin_dashboard_daily_rollups (
+              rollup_date,
+              time_zone,
+              task_type,
+              total_users,
+              active_users,
+              queued_count,
+              running_count,
+              succeeded_count,
+              failed_count,
+              canceled_count,
+              business_ok_count,
+              business_partial_count,
+              business_failed_count,
+              business_disabled_count,
+              updated_at
+            )
+            VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+            ON CONFLICT(rollup_date, time_zone, task_type)
+            DO UPDATE SET
+              total_users = excluded.total_users,
+              active_users = excluded.active_users,
+              queued_count = excluded.queued_count,
+              running_count = excluded.running_count,
+              succeeded_count = excluded.succeeded_count,
+              failed_count = excluded.failed_count,
+              canceled_count = excluded.canceled_count,
+              business_ok_count = excluded.business_ok_count,
+              business_partial_count = excluded.business_partial_count,
+              business_failed_count = excluded.business_failed_count,
+              business_disabled_count = excluded.business_disabled_count,
+              updated_at = excluded.updated_at
+            "#,
+        )
+        .bind(day_value.as_str())
+        .bind(time_zone_value.as_str())
+        .bind(task_type)
+        .bind(total_users)
+        .bind(active_users)
+        .bind(counts.queued_count)
+        .bind(counts.running_count)
+        .bind(counts.succeeded_count)
+        .bind(counts.failed_count)
+        .bind(counts.canceled_count)
+        .bind(counts.business_ok_count)
+        .bind(counts.business_partial_count)
+        .bind(counts.business_failed_count)
+        .bind(counts.business_disabled_count)
+        .bind(updated_at.as_str())
+        .execute(&state.pool)
+        .await
+        .map_err(ApiError::internal)?;
+    }
+
+    Ok(())
+}
+
+pub(crate) async fn refresh_admin_dashboard_rollups(
+    state: &AppState,
+    days: i64,
+) -> Result<(), ApiError> {
+    let time_zone = admin_dashboard_system_time_zone(state);
+    let now_local = chrono::Utc::now().with_timezone(&time_zone);
+    let end_day = now_local.date_naive();
+    let start_day = end_day
+        .checked_sub_signed(chrono::Duration::days(days.saturating_sub(1)))
+        .ok_or_else(|| {
+            ApiError::internal(anyhow::anyhow!("invalid admin dashboard refresh range"))
+        })?;
+
+    let mut day = start_day;
+    loop {
+        upsert_admin_dashboard_rollup_for_day(state, time_zone, day).await?;
+        if day >= end_day {
+            break;
+        }
+        day = day.succ_opt().ok_or_else(|| {
+            ApiError::internal(anyhow::anyhow!(
+                "invalid admin dashboard refresh day iteration"
+            ))
+        })?;
+    }
+
+    Ok(())
+}
+
+pub(super) fn build_admin_dashboard_task_status_item(
+    task_type: &str,
+    label: &str,
+    counts: &AdminDashboardStatusCountRow,
+) -> AdminDashboardTaskStatusItem {
+    let total = counts.queued_count
+        + counts.running_count
+        + counts.succeeded_count
+        + counts.failed_count
+        + counts.canceled_count;
+    let finished_total = counts.succeeded_count + counts.failed_count + counts.canceled_count;
+    let success_rate = if finished_total <= 0 {
+        0.0
+    } else {
+        counts.succeeded_count as f64 / finished_total as f64
+    };
+    let business_counts = admin_dashboard_status_count_business_counts(counts);
+
+    AdminDashboardTaskStatusItem {
+        task_type: task_type.to_owned(),
+        label: label.to_owned(),
+        queued: counts.queued_count,
+        running: counts.running_count,
+        succeeded: counts.succeeded_count,
+        failed: counts.failed_count,
+        canceled: counts.canceled_count,
+        business_success_rate: admin_dashboard_business_success_rate(&business_counts),
+        business_counts,
+        total,
+        success_rate,
+    }
+}
+
+pub(super) fn build_admin_dashboard_task_share_item(
+    total_tasks: i64,
+    item: &AdminDashboardTaskStatusItem,
+) -> AdminDashboardTaskShareItem {
+    let share_ratio = if total_tasks <= 0 {
+        0.0
+    } else {
+        item.total as f64 / total_tasks as f64
+    };
+    AdminDashboardTaskShareItem {
+        task_type: item.task_type.clone(),
+        label: item.label.clone(),
+        total: item.total,
+        share_ratio,
+        success_rate: item.success_rate,
+        business_counts: item.business_counts.clone(),
+        business_success_rate: item.business_success_rate,
+    }
+}
+
+pub(super) async fn load_admin_dashboard_rollups(
+    pool: &sqlx::SqlitePool,
+    time_zone: Tz,
+    start_date: &str,
+    end_date: &str,
+) -> Result<Vec<AdminDashboardRollupRow>, ApiError> {
+    sqlx::query_as::<_, AdminDashboardRollupRow>(
+        r#"
+        SELECT
+          rollup_date,
+          task_type,
+          total_users,
+          active_users,
+          queued_count,
+          running_count,
+          succeeded_count,
+          failed_count,
+          canceled_count,
+          business_ok_count,
+          business_partial_count,
+          business_failed_count,
+          business_disabled_count
+        FROM admin_dashboard_daily_rollups
+        WHERE time_zone = ?
+          AND rollup_date >= ?
+          AND rollup_date <= ?
+        ORDER BY rollup_date ASC, task_type ASC
+        "#,
+    )
+    .bind(time_zone.name())
+    .bind(start_date)
+    .bind(end_date)
+    .fetch_all(pool)
+    .await
+    .map_err(ApiError::internal)
+}
+
+pub(super) async fn load_admin_dashboard_ongoing_counts(
+    pool: &sqlx::SqlitePool,
+) -> Result<AdminDashboardOngoingTaskBreakdown, ApiError> {
+    let rows = sqlx::query_as::<_, (String, i64)>(
+        r#"
+        SELECT task_type, COUNT(*) AS total
+        FROM job_tasks
+        WHERE status IN ('queued', 'running')
+          AND task_type IN (?, ?, ?)
+        GROUP BY task_type
+        "#,
+    )
+    .bind(jobs::TASK_TRANSLATE_RELEASE_BATCH)
+    .bind(jobs::TASK_SUMMARIZE_RELEASE_SMART_BATCH)
+    .bind(jobs::TASK_BRIEF_DAILY_SLOT)
+    .fetch_all(pool)
+    .await
+    .map_err(ApiError::internal)?;
+
+    let mut translations = 0_i64;
+    let mut summaries = 0_i64;
+    let mut briefs = 0_i64;
+    for (task_type, total) in rows {
+        match task_type.as_str() {
+            jobs::TASK_TRANSLATE_RELEASE_BATCH => translations = total,
+            jobs::TASK_SUMMARIZE_RELEASE_SMART_BATCH => summaries = total,
+            jobs::TASK_BRIEF_DAILY_SLOT => briefs = total,
+            _ => {}
+        }
+    }
+    Ok(AdminDashboardOngoingTaskBreakdown {
+        translations,
+        summaries,
+        briefs,
+    })
+}
+
+pub(super) async fn count_admin_dashboard_live_tasks_by_status(
+    pool: &sqlx::SqlitePool,
+    status: &str,
+) -> Result<i64, ApiError> {
+    sqlx::query_scalar::<_, i64>(
+        r#"
+        SELECT COUNT(*)
+        FROM job_tasks
+        WHERE status = ?
+          AND task_type IN (?, ?, ?)
+        "#,
+    )
+    .bind(status)
+    .bind(jobs::TASK_TRANSLATE_RELEASE_BATCH)
+    .bind(jobs::TASK_SUMMARIZE_RELEASE_SMART_BATCH)
+    .bind(jobs::TASK_BRIEF_DAILY_SLOT)
+    .fetch_one(pool)
+    .await
+    .map_err(ApiError::internal)
+}
+
+pub(super) async fn load_admin_dashboard_llm_health(
+    pool: &sqlx::SqlitePool,
+    now_utc: chrono::DateTime<chrono::Utc>,
+) -> Result<AdminDashboardLlmHealth, ApiError> {
+    let since = (now_utc - chrono::Duration::hours(24)).to_rfc3339();
+    let until = now_utc.to_rfc3339();
+    let health_window_timestamp_expr = "COALESCE(finished_at, updated_at, created_at)";
+    let calls_24h = sqlx::query_scalar::<_, i64>(&format!(
+        r#"
+        SELECT COUNT(*)
+        FROM llm_calls
+        WHERE {health_window_timestamp_expr} >= ?
+          AND {health_window_timestamp_expr} <= ?
+        "#,
+    ))
+    .bind(since.as_str())
+    .bind(until.as_str())
+    .fetch_one(pool)
+    .await
+    .map_err(ApiError::internal)?;
+    let failed_24h = sqlx::query_scalar::<_, i64>(&format!(
+        r#"
+        SELECT COUNT(*)
+        FROM llm_calls
+        WHERE {health_window_timestamp_expr} >= ?
+          AND {health_window_timestamp_expr} <= ?
+          AND status = 'failed'
+        "#,
+    ))
+    .bind(since.as_str())
+    .bind(until.as_str())
+    .fetch_one(pool)
+    .await
+    .map_err(ApiError::internal)?;
+    let last_failure_at = sqlx::query_scalar::<_, Option<String>>(&format!(
+        r#"
+        SELECT MAX({health_window_timestamp_expr})
+        FROM llm_calls
+        WHERE {health_window_timestamp_expr} >= ?
+          AND {health_window_timestamp_expr} <= ?
+          AND status = 'failed'
+        "#,
+    ))
+    .bind(since.as_str())
+    .bind(until.as_str())
+    .fetch_one(pool)
+    .await
+    .map_err(ApiError::internal)?;
+    let top_failure_reasons = sqlx::query_as::<_, (String, i64)>(&format!(
+        r#"
+        SELECT
+          COALESCE(NULLIF(TRIM(error_text), ''), '未记录错误文本') AS label,
+          COUNT(*) AS count
+        FROM llm_calls
+        WHERE {health_window_timestamp_expr} >= ?
+          AND {health_window_timestamp_expr} <= ?
+          AND status = 'failed'
+        GROUP BY label
+        ORDER BY count DESC, label ASC
+        LIMIT 5
+        "#,
+    ))
+    .bind(since.as_str())
+    .bind(until.as_str())
+    .fetch_all(pool)
+    .await
+    .map_err(ApiError::internal)?
+    .into_iter()
+    .map(|(label, count)| AdminDashboardLlmHealthBucket { label, count })
+    .collect::<Vec<_>>();
+    let top_failure_sources = sqlx::query_as::<_, (String, i64)>(&format!(
+        r#"
+        SELECT source AS label, COUNT(*) AS count
+        FROM llm_calls
+        WHERE {health_window_timestamp_expr} >= ?
+          AND {health_window_timestamp_expr} <= ?
+          AND status = 'failed'
+        GROUP BY source
+        ORDER BY count DESC, label ASC
+        LIMIT 5
+        "#,
+    ))
+    .bind(since.as_str())
+    .bind(until.as_str())
+    .fetch_all(pool)
+    .await
+    .map_err(ApiError::internal)?
+    .into_iter()
+    .map(|(label, count)| AdminDashboardLlmHealthBucket { label, count })
+    .collect::<Vec<_>>();
+
+    Ok(AdminDashboardLlmHealth {
+        calls_24h,
+        failed_24h,
+        last_failure_at,
+        top_failure_reasons,
+        top_failure_sources,
+    })
+}
+
+pub(super) async fn load_admin_dashboard_today_live_snapshot(
+    state: &AppState,
+    time_zone: Tz,
+    now_utc: chrono::DateTime<chrono::Utc>,
+) -> Result<(AdminDashboardTodayLive, AdminDashboardStatusBreakdown), ApiError> {
+    let now_local = now_utc.with_timezone(&time_zone);
+    let today = now_local.date_naive();
+    let (start_utc, _) = local_day_bounds_utc(time_zone, today)?;
+    let start_at = start_utc.to_rfc3339();
+    let end_at = now_utc.to_rfc3339();
+
+    let total_users = count_admin_dashboard_total_users_now(&state.pool).await?;
+    let active_users =
+        count_admin_dashboard_active_users_between(&state.pool, start_at.as_str(), end_at.as_str())
+            .await?;
+    let ongoing_by_task = load_admin_dashboard_ongoing_counts(&state.pool).await?;
+    let queued_tasks =
+        count_admin_dashboard_live_tasks_by_status(&state.pool, jobs::STATUS_QUEUED).await?;
+    let running_tasks =
+        count_admin_dashboard_live_tasks_by_status(&state.pool, jobs::STATUS_RUNNING).await?;
+    let ongoing_tasks_total =
+        ongoing_by_task.translations + ongoing_by_task.summaries + ongoing_by_task.briefs;
+
+    let mut items = Vec::with_capacity(ADMIN_DASHBOARD_TASK_TYPES.len());
+    for (task_type, label) in ADMIN_DASHBOARD_TASK_TYPES {
+        let counts = load_admin_dashboard_task_status_counts(
+            &state.pool,
+            task_type,
+            start_at.as_str(),
+            end_at.as_str(),
+        )
+        .await?;
+        items.push(build_admin_dashboard_task_status_item(
+            task_type, label, &counts,
+        ));
+    }
+
+    let queued_total = items.iter().map(|item| item.queued).sum::<i64>();
+    let running_total = items.iter().map(|item| item.running).sum::<i64>();
+    let succeeded_total = items.iter().map(|item| item.succeeded).sum::<i64>();
+    let failed_total = items.iter().map(|item| item.failed).sum::<i64>();
+    let canceled_total = items.iter().map(|item| item.canceled).sum::<i64>();
+    let business_counts =
+        items
+            .iter()
+            .fold(AdminDashboardBusinessCounts::default(), |mut acc, item| {
+                acc.ok += item.business_counts.ok;
+                acc.partial += item.business_counts.partial;
+                acc.failed += item.business_counts.failed;
+                acc.disabled += item.business_counts.disabled;
+                acc
+            });
+    let total = items.iter().map(|item| item.total).sum::<i64>();
+
+    Ok((
+        AdminDashboardTodayLive {
+            date: today.format("%Y-%m-%d").to_string(),
+            total_users,
+            active_users,
+            ongoing_tasks_total,
+            queued_tasks,
+            running_tasks,
+        },
+        AdminDashboardStatusBreakdown {
+            queued_total,
+            running_total,
+            succeeded_total,
+            failed_total,
+            canceled_total,
+            business_counts,
+            total,
+            items,
+        },
+    ))
+}
+
+pub async fn admin_dashboard(
+    State(state): State<Arc<AppState>>,
+    _: AdminUser,
+    Query(query): Query<AdminDashboardQuery>,
+) -> Result<Json<AdminDashboardResponse>, ApiError> {
+    let now_utc = chrono::Utc::now();
+    let (selected_window, window_days) =
+        resolve_admin_dashboard_window_days(query.window.as_deref())?;
+    let time_zone = admin_dashboard_system_time_zone(state.as_ref());
+    let now_local = now_utc.with_timezone(&time_zone);
+    let end_day = now_local.date_naive();
+    let start_day = end_day
+        .checked_sub_signed(chrono::Duration::days(window_days - 1))
+        .ok_or_else(|| {
+            ApiError::internal(anyhow::anyhow!("invalid admin dashboard rollup range"))
+        })?;
+
+    let rollups = load_admin_dashboard_rollups(
+        &state.pool,
+        time_zone,
+        start_day.format("%Y-%m-%d").to_string().as_str(),
+        end_day.format("%Y-%m-%d").to_string().as_str(),
+    )
+    .await?;
+    let (today_live, status_breakdown) =
+        load_admin_dashboard_today_live_snapshot(state.as_ref(), time_zone, now_utc).await?;
+    let ongoing_by_task = load_admin_dashboard_ongoing_counts(&state.pool).await?;
+    let llm_health = load_admin_dashboard_llm_health(&state.pool, now_utc).await?;
+
+    let today_value = today_live.date.clone();
+    let mut trend_points_by_date = HashMap::<String, AdminDashboardTrendPoint>::new();
+    let mut day = start_day;
+    loop {
+        let day_value = day.format("%Y-%m-%d").to_string();
+        trend_points_by_date.insert(
+            day_value.clone(),
+            AdminDashboardTrendPoint {
+                label: day_value.get(5..).unwrap_or(day_value.as_str()).to_owned(),
+                date: day_value,
+                total_users: 0,
+                active_users: 0,
+                translations_total: 0,
+                translations_failed: 0,
+                translations_partial: 0,
+                translations_business_failed: 0,
+                summaries_total: 0,
+                summaries_failed: 0,
+                summaries_partial: 0,
+                summaries_business_failed: 0,
+                briefs_total: 0,
+                briefs_failed: 0,
+                briefs_partial: 0,
+                briefs_business_failed: 0,
+            },
+        );
+        if day >= end_day {
+            break;
+        }
+        day = day.succ_opt().ok_or_else(|| {
+            ApiError::internal(anyhow::anyhow!(
+                "invalid admin dashboard trend day iteration"
+            ))
+        })?;
+    }
+
+    for row in rollups {
+        let point = trend_points_by_date
+            .entry(row.rollup_date.clone())
+            .or_insert_with(|| AdminDashboardTrendPoint {
+                label: row
+                    .rollup_date
+                    .get(5..)
+                    .unwrap_or(row.rollup_date.as_str())
+                    .to_owned(),
+                date: row.rollup_date.clone(),
+                total_users: row.total_users,
+                active_users: row.active_users,
+                translations_total: 0,
+                translations_failed: 0,
+                translations_partial: 0,
+                translations_business_failed: 0,
+                summaries_total: 0,
+                summaries_failed: 0,
+                summaries_partial: 0,
+                summaries_business_failed: 0,
+                briefs_total: 0,
+                briefs_failed: 0,
+                briefs_partial: 0,
+                briefs_business_failed: 0,
+            });
+        point.total_users = row.total_users;
+        point.active_users = row.active_users;
+        match row.task_type.as_str() {
+            jobs::TASK_TRANSLATE_RELEASE_BATCH => {
+                point.translations_total = row.queued_count
+                    + row.running_count
+                    + row.succeeded_count
+                    + row.failed_count
+                    + row.canceled_count;
+                point.translations_failed = row.failed_count;
+                point.translations_partial = row.business_partial_count;
+                point.translations_business_failed = row.business_failed_count;
+            }
+            jobs::TASK_SUMMARIZE_RELEASE_SMART_BATCH => {
+                point.summaries_total = row.queued_count
+                    + row.running_count
+                    + row.succeeded_count
+                    + row.failed_count
+                    + row.canceled_count;
+                point.summaries_failed = row.failed_count;
+                point.summaries_partial = row.business_partial_count;
+                point.summaries_business_failed = row.business_failed_count;
+            }
+            jobs::TASK_BRIEF_DAILY_SLOT => {
+                point.briefs_total = row.queued_count
+                    + row.running_count
+                    + row.succeeded_count
+                    + row.failed_count
+                    + row.canceled_count;
+                point.briefs_failed = row.failed_count;
+                point.briefs_partial = row.business_partial_count;
+                point.briefs_business_failed = row.business_failed_count;
+            }
+            _ => {}
+        }
+    }
+
+    let today_point = trend_points_by_date
+        .entry(today_value.clone())
+        .or_insert_with(|| AdminDashboardTrendPoint {
+            label: today_value
+                .get(5..)
+                .unwrap_or(today_value.as_str())
+                .to_owned(),
+            date: today_value.clone(),
+            total_users: 0,
+            active_users: 0,
+            translations_total: 0,
+            translations_failed: 0,
+            translations_partial: 0,
+            translations_business_failed: 0,
+            summaries_total: 0,
+            summaries_failed: 0,
+            summaries_partial: 0,
+            summaries_business_failed: 0,
+            briefs_total: 0,
+            briefs_failed: 0,
+            briefs_partial: 0,
+            briefs_business_failed: 0,
+        });
+    today_point.total_users = today_live.total_users;
+    today_point.active_users = today_live.active_users;
+    for item in &status_breakdown.items {
+        match item.task_type.as_str() {
+            jobs::TASK_TRANSLATE_RELEASE_BATCH => {
+                today_point.translations_total = item.total;
+                today_point.translations_failed = item.failed;
+                today_point.translations_partial = item.business_counts.partial;
+                today_point.translations_business_failed = item.business_counts.failed;
+            }
+            jobs::TASK_SUMMARIZE_RELEASE_SMART_BATCH => {
+                today_point.summaries_total = item.total;
+                today_point.summaries_failed = item.failed;
+                today_point.summaries_partial = item.business_counts.partial;
+                today_point.summaries_business_failed = item.business_counts.failed;
+            }
+            jobs::TASK_BRIEF_DAILY_SLOT => {
+                today_point.briefs_total = item.total;
+                today_point.briefs_failed = item.failed;
+                today_point.briefs_partial = item.business_counts.partial;
+                today_point.briefs_business_failed = item.business_counts.failed;
+            }
+            _ => {}
+        }
+    }
+
+    let mut trend_points = trend_points_by_date.into_values().collect::<Vec<_>>();
+    trend_points.sort_by(|left, right| left.date.cmp(&right.date));
+
+    let task_share = status_breakdown
+        .items
+        .iter()
+        .map(|item| build_admin_dashboard_task_share_item(status_breakdown.total, item))
+        .collect::<Vec<_>>();
+
+    Ok(Json(AdminDashboardResponse {
+        generated_at: now_utc.to_rfc3339(),
+        time_zone: time_zone.name().to_owned(),
+        summary: AdminDashboardSummary {
+            total_users: today_live.total_users,
+            active_users_today: today_live.active_users,
+            ongoing_tasks_total: today_live.ongoing_tasks_total,
+            queued_tasks: today_live.queued_tasks,
+            running_tasks: today_live.running_tasks,
+            ongoing_by_task,
+        },
+        today_live,
+        status_breakdown,
+        task_share,
+        trend_points,
+        llm_health,
+        window_meta: AdminDashboardWindowMeta {
+            selected_window,
+            available_windows: ADMIN_DASHBOARD_WINDOW_OPTIONS
+                .iter()
+                .map(|(label, _)| (*label).to_owned())
+                .collect(),
+            window_start: start_day.format("%Y-%m-%d").to_string(),
+            window_end: today_value,
+            point_count: window_days,
+        },
+    }))
+}
+
+pub async fn feed_events_sse(
+    State(state): State<Arc<AppState>>,
+    AuthedUser(user_id): AuthedUser,
+) -> Result<Response, ApiError> {
+    Ok(feed_events::feed_sse_response(state, user_id))
+}
+
+pub async fn admin_jobs_events_sse(
+    State(state): State<Arc<AppState>>,
+    _: AdminUser,
+    headers: HeaderMap,
+) -> Result<Response, ApiError> {
+    Ok(jobs::admin_jobs_sse_response(
+        state,
+        jobs::last_event_id(&headers),
+    ))
+}
+
+#[derive(Debug, Deserialize)]
+pub struct AdminRealtimeTasksQuery {
+    pub(super) status: Option<String>,
+    pub(super) task_type: Option<String>,
+    pub(super) exclude_task_type: Option<String>,
+    pub(super) task_group: Option<String>,
+    pub(super) priority: Option<String>,
+    pub(super) request_id: Option<String>,
+    pub(super) page: Option<i64>,
+    pub(super) page_size: Option<i64>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct AdminRealtimeTaskItem {
+    pub(super) id: String,
+    pub(super) task_type: String,
+    pub(super) status: String,
+    pub(super) source: String,
+    pub(super) priority: String,
+    pub(super) skipped: bool,
+    pub(super) requested_by: Option<String>,
+    pub(super) parent_task_id: Option<String>,
+    pub(super) request_id: Option<String>,
+    pub(super) cancel_requested: bool,
+    pub(super) error_message: Option<String>,
+    pub(super) created_at: String,
+    pub(super) started_at: Option<String>,
+    pub(super) finished_at: Option<String>,
+    pub(super) updated_at: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(super) diagnostics: Option<AdminTaskDiagnostics>,
+}
+
+#[derive(Debug, sqlx::FromRow)]
+pub(super) struct AdminRealtimeTaskRow {
+    pub(super) id: String,
+    pub(super) task_type: String,
+    pub(super) status: String,
+    pub(super) source: String,
+    pub(super) priority: String,
+    pub(super) skipped: bool,
+    pub(super) requested_by: Option<String>,
+    pub(super) parent_task_id: Option<String>,
+    pub(super) request_id: Option<String>,
+    pub(super) cancel_requested: bool,
+    pub(super) error_message: Option<String>,
+    pub(super) created_at: String,
+    pub(super) started_at: Option<String>,
+    pub(super) finished_at: Option<String>,
+    pub(super) updated_at: String,
+}
+
+impl From<AdminRealtimeTaskRow> for AdminRealtimeTaskItem {
+    fn from(row: AdminRealtimeTaskRow) -> Self {
+        Self {
+            id: row.id,
+            task_type: row.task_type,
+            status: row.status,
+            source: row.source,
+            priority: row.priority,
+            skipped: row.skipped,
+            requested_by: row.requested_by,
+            parent_task_id: row.parent_task_id,
+            request_id: row.request_id,
+            cancel_requested: row.cancel_requested,
+            error_message: row.error_message,
+            created_at: row.created_at,
+            started_at: row.started_at,
+            finished_at: row.finished_at,
+            updated_at: row.updated_at,
+            diagnostics: None,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, sqlx::FromRow)]
+pub struct AdminRealtimeTaskDetailItem {
+    pub(super) id: String,
+    pub(super) task_type: String,
+    pub(super) status: String,
+    pub(super) source: String,
+    pub(super) priority: String,
+    pub(super) requested_by: Option<String>,
+    pub(super) parent_task_id: Option<String>,
+    pub(super) request_id: Option<String>,
+    pub(super) cancel_requested: bool,
+    pub(super) error_message: Option<String>,
+    pub(super) payload_json: String,
+    pub(super) result_json: Option<String>,
+    #[serde(skip_serializing)]
+    pub(super) log_file_path: Option<String>,
+    pub(super) created_at: String,
+    pub(super) started_at: Option<String>,
+    pub(super) finished_at: Option<String>,
+    pub(super) updated_at: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct AdminRealtimeTasksResponse {
+    pub(super) items: Vec<AdminRealtimeTaskItem>,
+    pub(super) page: i64,
+    pub(super) page_size: i64,
+    pub(super) total: i64,
+}
+
+pub async fn admin_list_realtime_tasks(
+    State(state): State<Arc<AppState>>,
+    _: AdminUser,
+    Query(query): Query<AdminRealtimeTasksQuery>,
+) -> Result<Json<AdminRealtimeTasksResponse>, ApiError> {
+    let page = query.page.unwrap_or(1).max(1);
+    let page_size = query.page_size.unwrap_or(20).clamp(1, 100);
+    let offset = admin_users_offset(page, page_size)?;
+    let status = query.status.unwrap_or_else(|| "all".to_owned());
+    let task_type = query.task_type.unwrap_or_default();
+    let exclude_task_type = query.exclude_task_type.unwrap_or_default();
+    let task_group = query.task_group.unwrap_or_else(|| "all".to_owned());
+    let priority = query.priority.unwrap_or_else(|| "all".to_owned());
+    if priority != "all" && jobs::TaskPriority::parse(&priority).is_none() {
+        return Err(ApiError::bad_request("invalid priority filter"));
+    }
+    let request_id = query.request_id.unwrap_or_default().trim().to_owned();
+    let mut total_query =
+        QueryBuilder::<sqlx::Sqlite>::new("SELECT COUNT(*) FROM job_tasks WHERE 1 = 1");
+    append_admin_realtime_task_filters(
+        &mut total_query,
+        status.clone(),
+        task_type.clone(),
+        exclude_task_type.clone(),
+        task_group.clone(),
+        priority.clone(),
+        request_id.clone(),
+    );
+    let total = total_query
+        .build_query_scalar::<i64>()
+        .fetch_one(&state.pool)
+        .await
+        .map_err(ApiError::internal)?;
+
+    let mut items_query = QueryBuilder::<sqlx::Sqlite>::new(
+        r#"
+        SELECT
+          id,
+          task_type,
+          status,
+          source,
+          priority,
+          COALESCE(json_extract(result_json, '$.skipped'), 0) = 1 AS skipped,
+          requested_by,
+          parent_task_id,
+          request_id,
+          cancel_requested,
+          error_message,
+          created_at,
+          started_at,
+          finished_at,
+          updated_at
+        FROM job_tasks
+        WHERE 1 = 1
+        "#,
+    );
+    append_admin_realtime_task_filters(
+        &mut items_query,
+        status,
+        task_type,
+        exclude_task_type,
+        task_group.clone(),
+        priority,
+        request_id,
+    );
+    items_query.push(" ORDER BY created_at DESC, id DESC LIMIT ");
+    items_query.push_bind(page_size);
+    items_query.push(" OFFSET ");
+    items_query.push_bind(offset);
+    let items = items_query
+        .build_query_as::<AdminRealtimeTaskRow>()
+        .fetch_all(&state.pool)
+        .await
+        .map_err(ApiError::internal)?
+        .into_iter()
+        .map(AdminRealtimeTaskItem::from)
+        .collect::<Vec<_>>();
+
+    let mut items = items
+        .into_iter()
+        .filter(|item| match task_group.as_str() {
+            "scheduled" => jobs::is_scheduled_task_type(&item.task_type),
+            "realtime" => !jobs::is_scheduled_task_type(&item.task_type),
+            _ => true,
+        })
+        .collect::<Vec<_>>();
+    attach_subscription_list_diagnostics(state.as_ref(), &mut items).await?;
+
+    Ok(Json(AdminRealtimeTasksResponse {
+        items,
+        page,
+        page_size,
+        total,
+    }))
+}
+
+pub(super) fn append_admin_realtime_task_filters(
+    query: &mut QueryBuilder<'_, sqlx::Sqlite>,
+    status: String,
+    task_type: String,
+    exclude_task_type: String,
+    task_group: String,
+    priority: String,
+    request_id: String,
+) {
+    if status != "all" {
+        query.push(" AND status = ");
+        // Accept the British spelling used by some clients for the same status.
+        if status == "cancelled" {
+            query.push_bind(jobs::STATUS_CANCELED.to_owned());
+        } else {
+            query.push_bind(status);
+        }
+    }
+    if !task_type.is_empty() {
+        query.push(" AND task_type = ");
+        query.push_bind(task_type);
+    }
+    if !exclude_task_type.is_empty() {
+        query.push(" AND task_type != ");
+        query.push_bind(exclude_task_type);
+    }
+    if priority != "all" {
+        query.push(" AND priority = ");
+        query.push_bind(priority);
+    }
+    if !request_id.is_empty() {
+        query.push(" AND request_id = ");
+        query.push_bind(request_id);
+    }
+    match task_group.as_str() {
+        "scheduled" => {
+            query.push(" AND task_type IN (");
+            let mut separated = query.separated(", ");
+            for task in jobs::SCHEDULED_TASK_TYPES {
+                separated.push_bind(*task);
+            }
+            separated.push_unseparated(")");
+        }
+        "realtime" => {
+            query.push(" AND task_type NOT IN (");
+            let mut separated = query.separated(", ");
+            for task in jobs::SCHEDULED_TASK_TYPES {
+                separated.push_bind(*task);
+            }
+            separated.push_unseparated(")");
+        }
+        _ => {}
+    }
+}
+
+#[derive(Clone, Debug, Serialize, sqlx::FromRow)]
+pub struct AdminTaskEventItem {
+    pub(super) id: String,
+    pub(super) event_type: String,
+    pub(super) payload_json: String,
+    pub(super) created_at: String,
+}
+
+#[derive(Clone, Debug, sqlx::FromRow)]
+pub(super) struct AdminTaskDiagnosticEventRow {
+    pub(super) task_id: String,
+    pub(super) id: String,
+    pub(super) event_type: String,
+    pub(super) payload_json: String,
+    pub(super) created_at: String,
+}
+
+impl From<AdminTaskDiagnosticEventRow> for AdminTaskEventItem {
+    fn from(row: AdminTaskDiagnosticEventRow) -> Self {
+        Self {
+            id: row.id,
+            event_type: row.event_type,
+            payload_json: row.payload_json,
+            created_at: row.created_at,
+        }
+    }
+}
+
+pub(super) const ADMIN_TASK_DETAIL_EVENT_LIMIT: i64 = 200;
+pub(super) const ADMIN_SYNC_SUBSCRIPTION_EVENT_LIMIT: i64 = 20;
+
+#[derive(Debug, Serialize)]
+pub struct AdminTaskEventMeta {
+    pub(super) returned: i64,
+    pub(super) total: i64,
+    pub(super) limit: i64,
+    pub(super) truncated: bool,
+}
+
+pub(super) async fn load_subscription_diagnostic_progress_events(
+    state: &AppState,
+    task_id: &str,
+) -> Result<Vec<AdminTaskEventItem>, ApiError> {
+    sqlx::query_as::<_, AdminTaskEventItem>(
+        r#"
+        SELECT id, event_type, payload_json, created_at
+        FROM job_task_events
+        WHERE task_id = ?
+          AND event_type = 'task.progress'
+          AND json_valid(payload_json)
+          AND json_extract(payload_json, '$.stage') IN (
+            'collect',
+            'star_progress',
+            'star_summary',
+            'repo_collect',
+            'release_attached',
+            'release_progress',
+            'release_summary',
+            'social_progress',
+            'social_summary',
+            'notifications_progress',
+            'notifications_summary'
+          )
+        ORDER BY rowid ASC
+        "#,
+    )
+    .bind(task_id)
+    .fetch_all(&state.pool)
+    .await
+    .map_err(ApiError::internal)
+}
+
+pub(super) async fn load_subscription_diagnostic_progress_events_for_tasks(
+    state: &AppState,
+    task_ids: &[String],
+) -> Result<HashMap<String, Vec<AdminTaskEventItem>>, ApiError> {
+    if task_ids.is_empty() {
+        return Ok(HashMap::new());
+    }
+
+    let mut query = QueryBuilder::<sqlx::Sqlite>::new(
+        r#"
+        SELECT task_id, id, event_type, payload_json, created_at
+        FROM job_task_events
+        WHERE task_id IN (
+        "#,
+    );
+    {
+        let mut separated = query.separated(", ");
+        for task_id in task_ids {
+            separated.push_bind(task_id);
+        }
+    }
+    query.push(
+        r#"
+        )
+          AND event_type = 'task.progress'
+          AND json_valid(payload_json)
+          AND json_extract(payload_json, '$.stage') IN (
+            'collect',
+            'star_progress',
+            'star_summary',
+            'repo_collect',
+            'release_attached',
+            'release_progress',
+            'release_summary',
+            'social_progress',
+            'social_summary',
+            'notifications_progress',
+            'notifications_summary'
+          )
+        ORDER BY task_id ASC, rowid ASC
+        "#,
+    );
+
+    let rows = query
+        .build_query_as::<AdminTaskDiagnosticEventRow>()
+        .fetch_all(&state.pool)
+        .await
+        .map_err(ApiError::internal)?;
+    let mut grouped = HashMap::<String, Vec<AdminTaskEventItem>>::new();
+    for row in rows {
+        grouped
+            .entry(row.task_id.clone())
+            .or_default()
+            .push(AdminTaskEventItem::from(row));
+    }
+    Ok(grouped)
+}
+
+pub(super) async fn load_recent_sync_subscription_events(
+    state: &AppState,
+    task_id: &str,
+) -> Result<Vec<AdminSyncSubscriptionEventItem>, ApiError> {
+    let rows = sqlx::query_as::<_, AdminSyncSubscriptionEventRow>(
+        r#"
+        SELECT
+          id,
+          task_id,
+          stage,
+          event_type,
+          severity,
+          recoverable,
+          attempt,
+          user_id,
+          repo_id,
+          repo_full_name,
+          payload_json,
+          created_at
+        FROM sync_subscription_events
+        WHERE task_id = ?
+        ORDER BY rowid DESC
+        LIMIT ?
+        "#,
+    )
+    .bind(task_id)
+    .bind(ADMIN_SYNC_SUBSCRIPTION_EVENT_LIMIT)
+    .fetch_all(&state.pool)
+    .await
+    .map_err(ApiError::internal)?;
+    Ok(rows.into_iter().map(map_sync_subscription_event).collect())
+}
+
+pub(super) async fn load_recent_sync_subscription_events_for_tasks(
+    state: &AppState,
+    task_ids: &[String],
+) -> Result<HashMap<String, Vec<AdminSyncSubscriptionEventItem>>, ApiError> {
+    if task_ids.is_empty() {
+        return Ok(HashMap::new());
+    }
+
+    let mut query = QueryBuilder::<sqlx::Sqlite>::new(
+        r#"
+        SELECT
+          id,
+          task_id,
+          stage,
+          event_type,
+          severity,
+          recoverable,
+          attempt,
+          user_id,
+          repo_id,
+          repo_full_name,
+          payload_json,
+          created_at
+        FROM (
+          SELECT
+            id,
+            task_id,
+            stage,
+            event_type,
+            severity,
+            recoverable,
+            attempt,
+            user_id,
+            repo_id,
+            repo_full_name,
+            payload_json,
+            created_at,
+            ROW_NUMBER() OVER (PARTITION BY task_id ORDER BY rowid DESC) AS task_event_rank
+          FROM sync_subscription_events
+          WHERE task_id IN (
+        "#,
+    );
+    {
+        let mut separated = query.separated(", ");
+        for task_id in task_ids {
+            separated.push_bind(task_id);
+        }
+    }
+    query.push(
+        r#"
+          )
+        )
+        WHERE task_event_rank <=
+        "#,
+    );
+    query.push_bind(ADMIN_SYNC_SUBSCRIPTION_EVENT_LIMIT);
+    query.push(" ORDER BY task_id ASC, created_at DESC, id DESC");
+
+    let rows = query
+        .build_query_as::<AdminSyncSubscriptionEventRow>()
+        .fetch_all(&state.pool)
+        .await
+        .map_err(ApiError::internal)?;
+    let mut grouped = HashMap::<String, Vec<AdminSyncSubscriptionEventItem>>::new();
+    for row in rows {
+        grouped
+            .entry(row.task_id.clone())
+            .or_default()
+            .push(map_sync_subscription_event(row));
+    }
+    Ok(grouped)
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct AdminBusinessOutcome {
+    pub code: String, // ok | partial | failed | disabled | unknown
+    pub label: String,
+    pub message: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct AdminTaskDiagnostics {
+    pub(super) business_outcome: AdminBusinessOutcome,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(super) translate_release_batch: Option<AdminTranslateReleaseBatchDiagnostics>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(super) brief_daily_slot: Option<AdminBriefDailySlotDiagnostics>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(super) brief_generate: Option<AdminBriefGenerateDiagnostics>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(super) brief_history_recompute: Option<AdminBriefHistoryRecomputeDiagnostics>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(super) brief_refresh_content: Option<AdminBriefRefreshContentDiagnostics>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(super) retry_recent_failures: Option<AdminRetryRecentFailuresDiagnostics>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(super) sync_access_refresh: Option<AdminSyncAccessRefreshDiagnostics>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(super) sync_subscriptions: Option<AdminSyncSubscriptionsDiagnostics>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct AdminTranslateReleaseBatchDiagnostics {
+    pub(super) target_user_id: Option<String>,
+    pub(super) release_total: i64,
+    pub(super) summary: AdminTranslateReleaseBatchSummary,
+    pub(super) progress: AdminTranslateReleaseBatchProgress,
+    pub(super) items: Vec<AdminTranslateReleaseBatchItemDiagnostic>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, PartialEq, Eq)]
+pub struct AdminTranslateReleaseBatchSummary {
+    pub(super) total: i64,
+    pub(super) ready: i64,
+    pub(super) missing: i64,
+    pub(super) disabled: i64,
+    pub(super) error: i64,
+}
+
+#[derive(Debug, Serialize)]
+pub struct AdminTranslateReleaseBatchProgress {
+    pub(super) processed: i64,
+    pub(super) last_stage: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct AdminTranslateReleaseBatchItemDiagnostic {
+    pub(super) release_id: String,
+    pub(super) item_status: String,
+    pub(super) item_error: Option<String>,
+    pub(super) last_event_at: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct AdminBriefDailySlotDiagnostics {
+    pub(super) hour_utc: Option<i64>,
+    pub(super) summary: AdminBriefDailySlotSummary,
+    pub(super) users: Vec<AdminBriefDailySlotUserDiagnostic>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct AdminBriefDailySlotSummary {
+    pub(super) total_users: i64,
+    pub(super) progressed_users: i64,
+    pub(super) succeeded_users: i64,
+    pub(super) failed_users: i64,
+    pub(super) canceled: bool,
+}
+
+#[derive(Debug, Serialize)]
+pub struct AdminBriefDailySlotUserDiagnostic {
+    pub(super) user_id: String,
+    pub(super) key_date: Option<String>,
+    pub(super) local_boundary: Option<String>,
+    pub(super) time_zone: Option<String>,
+    pub(super) window_start_utc: Option<String>,
+    pub(super) window_end_utc: Option<String>,
+    pub(super) state: String, // succeeded | failed | running
+    pub(super) error: Option<String>,
+    pub(super) last_event_at: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct AdminBriefGenerateDiagnostics {
+    pub(super) target_user_id: Option<String>,
+    pub(super) brief_id: Option<String>,
+    pub(super) content_length: Option<i64>,
+    pub(super) key_date: Option<String>,
+    pub(super) date: Option<String>,
+    pub(super) window_start_utc: Option<String>,
+    pub(super) window_end_utc: Option<String>,
+    pub(super) effective_time_zone: Option<String>,
+    pub(super) effective_local_boundary: Option<String>,
+    pub(super) release_count: Option<i64>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct AdminBriefHistoryRecomputeDiagnostics {
+    pub(super) total: i64,
+    pub(super) processed: i64,
+    pub(super) succeeded: i64,
+    pub(super) failed: i64,
+    pub(super) current_brief_id: Option<String>,
+    pub(super) last_error: Option<String>,
+    pub(super) canceled: bool,
+}
+
+#[derive(Debug, Serialize)]
+pub struct AdminBriefRefreshContentDiagnostics {
+    pub(super) total: i64,
+    pub(super) processed: i64,
+    pub(super) succeeded: i64,
+    pub(super) failed: i64,
+    pub(super) current_brief_id: Option<String>,
+    pub(super) last_error: Option<String>,
+    pub(super) canceled: bool,
+}
+
+#[derive(Debug, Serialize)]
+pub struct AdminRetryRecentFailuresKindDiagnostics {
+    pub(super) total: i64,
+    pub(super) processed: i64,
+    pub(super) succeeded: i64,
+    pub(super) failed: i64,
+    pub(super) skipped: i64,
+    pub(super) timed_out: bool,
+    pub(super) duration_ms: i64,
+    pub(super) current_id: Option<String>,
+    pub(super) last_error: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct AdminRetryRecentFailuresDiagnostics {
+    pub(super) skipped: bool,
+    pub(super) skip_reason: Option<String>,
+    pub(super) canceled: bool,
+    pub(super) schedule_key: Option<String>,
+    pub(super) interval_minutes: Option<i64>,
+    pub(super) daily_brief: AdminRetryRecentFailuresKindDiagnostics,
+    pub(super) polish: AdminRetryRecentFailuresKindDiagnostics,
+    pub(super) translation: AdminRetryRecentFailuresKindDiagnostics,
+}
+
+#[derive(Debug, Serialize)]
+pub struct AdminSyncSubscriptionStarDiagnostics {
+    pub(super) total_users: i64,
+    pub(super) succeeded_users: i64,
+    pub(super) failed_users: i64,
+    pub(super) total_repos: i64,
+}
+
+#[derive(Debug, Serialize)]
+pub struct AdminSyncSubscriptionReleaseDiagnostics {
+    pub(super) total_repos: i64,
+    pub(super) succeeded_repos: i64,
+    pub(super) failed_repos: i64,
+    pub(super) candidate_failures: i64,
+    pub(super) fetched_count: i64,
+    pub(super) inserted_count: i64,
+    pub(super) updated_count: i64,
+    pub(super) unchanged_count: i64,
+    pub(super) pages_fetched: i64,
+}
+
+#[derive(Debug, Serialize)]
+pub struct AdminSyncSubscriptionSocialDiagnostics {
+    pub(super) total_users: i64,
+    pub(super) succeeded_users: i64,
+    pub(super) failed_users: i64,
+    pub(super) repo_stars: i64,
+    pub(super) followers: i64,
+    pub(super) events: i64,
+}
+
+#[derive(Debug, Serialize)]
+pub struct AdminSyncSubscriptionNotificationsDiagnostics {
+    pub(super) total_users: i64,
+    pub(super) succeeded_users: i64,
+    pub(super) failed_users: i64,
+    pub(super) notifications: i64,
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct AdminSyncSubscriptionEventItem {
+    pub(super) id: String,
+    pub(super) stage: String,
+    pub(super) event_type: String,
+    pub(super) severity: String,
+    pub(super) recoverable: bool,
+    pub(super) attempt: i64,
+    pub(super) user_id: Option<String>,
+    pub(super) repo_id: Option<i64>,
+    pub(super) repo_full_name: Option<String>,
+    pub(super) message: Option<String>,
+    pub(super) created_at: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct AdminSyncAccessRefreshFailureDiagnostic {
+    pub(super) operation: Option<String>,
+    pub(super) error_kind: Option<String>,
+    pub(super) error_stage: Option<String>,
+    pub(super) retryable: bool,
+    pub(super) http_status: Option<i64>,
+    pub(super) timeout_ms: Option<i64>,
+    pub(super) elapsed_ms: Option<i64>,
+    pub(super) attempts: Option<i64>,
+    pub(super) retry_limit: Option<i64>,
+    pub(super) error_chain: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct AdminSyncAccessRefreshDiagnostics {
+    pub(super) log_available: bool,
+    pub(super) log_download_path: Option<String>,
+    pub(super) star_repos: i64,
+    pub(super) release_repos: i64,
+    pub(super) releases: i64,
+    pub(super) social_repo_stars: i64,
+    pub(super) social_followers: i64,
+    pub(super) social_events: i64,
+    pub(super) notifications: i64,
+    pub(super) social_error: Option<String>,
+    pub(super) notifications_error: Option<String>,
+    pub(super) failure: Option<AdminSyncAccessRefreshFailureDiagnostic>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct AdminSyncSubscriptionsDiagnostics {
+    pub(super) trigger: Option<String>,
+    pub(super) schedule_key: Option<String>,
+    pub(super) skipped: bool,
+    pub(super) skip_reason: Option<String>,
+    pub(super) log_available: bool,
+    pub(super) log_download_path: Option<String>,
+    pub(super) star: AdminSyncSubscriptionStarDiagnostics,
+    pub(super) release: AdminSyncSubscriptionReleaseDiagnostics,
+    pub(super) social: AdminSyncSubscriptionSocialDiagnostics,
+    pub(super) notifications: AdminSyncSubscriptionNotificationsDiagnostics,
+    pub(super) releases_written: i64,
+    pub(super) critical_events: i64,
+    pub(super) recent_events: Vec<AdminSyncSubscriptionEventItem>,
+}
+
+#[derive(Debug, sqlx::FromRow)]
+pub(super) struct AdminSyncSubscriptionEventRow {
+    pub(super) id: String,
+    pub(super) task_id: String,
+    pub(super) stage: String,
+    pub(super) event_type: String,
+    pub(super) severity: String,
+    pub(super) recoverable: bool,
+    pub(super) attempt: i64,
+    pub(super) user_id: Option<String>,
+    pub(super) repo_id: Option<i64>,
+    pub(super) repo_full_name: Option<String>,
+    pub(super) payload_json: Option<String>,
+    pub(super) created_at: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct AdminRealtimeTaskDetailResponse {
+    pub(super) task: AdminRealtimeTaskDetailItem,
+    pub(super) events: Vec<AdminTaskEventItem>,
+    pub(super) event_meta: AdminTaskEventMeta,
+    pub(super) diagnostics: Option<AdminTaskDiagnostics>,
+}
+
+pub(super) fn parse_json_value(raw: Option<&str>) -> Option<serde_json::Value> {
+    let raw = raw?.trim();
+    if raw.is_empty() {
+        return None;
+    }
+    serde_json::from_str(raw).ok()
+}
+
+pub(super) fn json_value_to_string(value: &serde_json::Value) -> Option<String> {
+    if let Some(raw) = value.as_str() {
+        let trimmed = raw.trim();
+        if trimmed.is_empty() {
+            None
+        } else {
+            Some(trimmed.to_owned())
+        }
+    } else if value.is_number() || value.is_boolean() {
+        Some(value.to_string())
+    } else {
+        None
+    }
+}
+
+pub(super) fn json_value_to_i64(value: &serde_json::Value) -> Option<i64> {
+    if let Some(number) = value.as_i64() {
+        return Some(number);
+    }
+    if let Some(number) = value.as_u64() {
+        return i64::try_from(number).ok();
+    }
+    value
+        .as_str()
+        .map(str::trim)
+        .filter(|raw| !raw.is_empty())
+        .and_then(|raw| raw.parse::<i64>().ok())
+}
+
+pub(super) fn json_value_to_bool(value: &serde_json::Value) -> Option<bool> {
+    if let Some(boolean) = value.as_bool() {
+        return Some(boolean);
+    }
+    if let Some(number) = value.as_i64() {
+        return Some(number != 0);
+    }
+    value.as_str().map(str::trim).and_then(|raw| match raw {
+        "1" | "true" | "TRUE" => Some(true),
+        "0" | "false" | "FALSE" => Some(false),
+        _ => None,
+    })
+}
+
+pub(super) fn json_object_get_i64(
+    object: Option<&serde_json::Map<String, serde_json::Value>>,
+    key: &str,
+) -> Option<i64> {
+    object
+        .and_then(|obj| obj.get(key))
+        .and_then(json_value_to_i64)
+}
+
+pub(super) fn json_object_get_string(
+    object: Option<&serde_json::Map<String, serde_json::Value>>,
+    key: &str,
+) -> Option<String> {
+    object
+        .and_then(|obj| obj.get(key))
+        .and_then(json_value_to_string)
+}
+
+pub(super) fn json_object_get_local_id(
+    object: Option<&serde_json::Map<String, serde_json::Value>>,
+    key: &str,
+) -> Option<String> {
+    object.and_then(|obj| obj.get(key)).and_then(|value| {
+        value
+            .as_str()
+            .map(ToOwned::to_owned)
+            .or_else(|| value.as_i64().map(|id| id.to_string()))
+    })
+}
+
+pub(super) fn json_object_get_array_len(
+    object: Option<&serde_json::Map<String, serde_json::Value>>,
+    key: &str,
+) -> Option<i64> {
+    object
+        .and_then(|obj| obj.get(key))
+        .and_then(serde_json::Value::as_array)
+        .and_then(|items| i64::try_from(items.len()).ok())
+}
+
+pub(super) fn json_object_get_bool(
+    object: Option<&serde_json::Map<String, serde_json::Value>>,
+    key: &str,
+) -> Option<bool> {
+    object
+        .and_then(|obj| obj.get(key))
+        .and_then(json_value_to_bool)
+}
+
+pub(super) fn json_object_get_object<'a>(
+    object: Option<&'a serde_json::Map<String, serde_json::Value>>,
+    key: &str,
+) -> Option<&'a serde_json::Map<String, serde_json::Value>> {
+    object
+        .and_then(|obj| obj.get(key))
+        .and_then(serde_json::Value::as_object)
+}
+
+pub(super) fn realtime_task_log_available(task: &AdminRealtimeTaskDetailItem) -> bool {
+    task.log_file_path
+        .as_deref()
+        .filter(|value| !value.trim().is_empty())
+        .map(std::path::Path::new)
+        .is_some_and(std::path::Path::is_file)
+}
+
+pub(super) fn realtime_task_log_download_path(task_id: &str) -> String {
+    format!(
+        "/api/admin/jobs/realtime/{}/log",
+        urlencoding::encode(task_id)
+    )
+}
+
+pub(super) fn business_outcome(
+    code: &str,
+    label: &str,
+    message: impl Into<String>,
+) -> AdminBusinessOutcome {
+    AdminBusinessOutcome {
+        code: code.to_owned(),
+        label: label.to_owned(),
+        message: message.into(),
+    }
+}
+
+pub(super) fn task_events_for_diagnostics(
+    events: &[AdminTaskEventItem],
+) -> Vec<&AdminTaskEventItem> {
+    let mut ordered_events = events.iter().collect::<Vec<_>>();
+    ordered_events.sort_by(|left, right| {
+        left.created_at
+            .cmp(&right.created_at)
+            .then_with(|| left.id.cmp(&right.id))
+    });
+    ordered_events
+}
+
+pub(super) fn derive_release_batch_summary(
+    payload_object: Option<&serde_json::Map<String, serde_json::Value>>,
+    result_object: Option<&serde_json::Map<String, serde_json::Value>>,
+) -> AdminTranslateReleaseBatchSummary {
+    let payload_total = json_object_get_array_len(payload_object, "release_ids").unwrap_or(0);
+    let explicit_total = json_object_get_i64(result_object, "total").unwrap_or(0);
+    let mut summary = AdminTranslateReleaseBatchSummary {
+        total: explicit_total,
+        ready: json_object_get_i64(result_object, "ready").unwrap_or(0),
+        missing: json_object_get_i64(result_object, "missing").unwrap_or(0),
+        disabled: json_object_get_i64(result_object, "disabled").unwrap_or(0),
+        error: json_object_get_i64(result_object, "error").unwrap_or(0),
+    };
+    let mut result_items_total = 0_i64;
+
+    if let Some(items) = result_object
+        .and_then(|obj| obj.get("items"))
+        .and_then(serde_json::Value::as_array)
+    {
+        summary.ready = 0;
+        summary.missing = 0;
+        summary.disabled = 0;
+        summary.error = 0;
+        result_items_total = i64::try_from(items.len()).unwrap_or(i64::MAX);
+        for item in items {
+            let item_status = item
+                .as_object()
+                .and_then(|obj| obj.get("status"))
+                .and_then(json_value_to_string)
+                .unwrap_or_default();
+            match item_status.as_str() {
+                "ready" => summary.ready += 1,
+                "missing" => summary.missing += 1,
+                "disabled" => summary.disabled += 1,
+                "error" => summary.error += 1,
+                _ => {}
+            }
+        }
+    }
+
+    let counted_total = summary.ready + summary.missing + summary.disabled + summary.error;
+    summary.total = summary
+        .total
+        .max(payload_total)
+        .max(result_items_total)
+        .max(counted_total);
+    summary
+}
+
+pub(super) fn release_batch_business_code(
+    status: &str,
+    summary: &AdminTranslateReleaseBatchSummary,
+) -> &'static str {
+    let release_total = summary.total.max(0);
+    if matches!(
+        status,
+        jobs::STATUS_CANCELED | jobs::STATUS_QUEUED | jobs::STATUS_RUNNING
+    ) {
+        "unknown"
+    } else if status == jobs::STATUS_FAILED {
+        "failed"
+    } else if release_total == 0 {
+        "ok"
+    } else if summary.disabled == release_total {
+        "disabled"
+    } else if summary.error > 0 && summary.ready == 0 {
+        "failed"
+    } else if summary.error > 0 || summary.missing > 0 {
+        "partial"
+    } else if summary.ready > 0 && summary.ready >= release_total {
+        "ok"
+    } else if summary.ready > 0 {
+        "partial"
+    } else {
+        "unknown"
+    }
+}
+
+pub(super) fn release_batch_business_outcome(
+    status: &str,
+    summary: &AdminTranslateReleaseBatchSummary,
+    error_message: Option<&str>,
+) -> AdminBusinessOutcome {
+    if status == jobs::STATUS_CANCELED {
+        return business_outcome("partial", "已取消", "任务已取消，结果可能不完整。");
+    }
+    if matches!(status, jobs::STATUS_QUEUED | jobs::STATUS_RUNNING) {
+        return business_outcome("unknown", "处理中", "任务正在执行中，结果尚未稳定。");
+    }
+    match release_batch_business_code(status, summary) {
+        "failed" if status == jobs::STATUS_FAILED => business_outcome(
+            "failed",
+            "业务失败",
+            error_message
+                .map(str::to_owned)
+                .unwrap_or_else(|| "任务执行失败，未产出可用翻译。".to_owned()),
+        ),
+        "ok" if summary.total == 0 => {
+            business_outcome("ok", "无需翻译", "任务未命中可翻译的 Release。")
+        }
+        "disabled" => business_outcome("disabled", "AI 已禁用", "翻译能力未启用，任务未进行翻译。"),
+        "failed" => business_outcome("failed", "业务失败", "任务已运行完成，但全部翻译项失败。"),
+        "partial" if summary.error > 0 || summary.missing > 0 => business_outcome(
+            "partial",
+            "部分成功",
+            "部分 Release 翻译成功，部分失败或缺失。",
+        ),
+        "ok" => business_outcome("ok", "业务成功", "所有目标 Release 已完成翻译。"),
+        "partial" => business_outcome("partial", "部分成功", "已有翻译结果，但统计未完全收敛。"),
+        _ => business_outcome(
+            "unknown",
+            "结果未知",
+            "任务状态显示成功，但缺少可判定的翻译结果统计。",
+        ),
+    }
+}
+
+pub(super) fn build_translate_release_batch_diagnostics(
+    task: &AdminRealtimeTaskDetailItem,
+    events: &[AdminTaskEventItem],
+) -> (AdminBusinessOutcome, AdminTranslateReleaseBatchDiagnostics) {
+    let payload_value = parse_json_value(Some(task.payload_json.as_str()));
+    let payload_object = payload_value
+        .as_ref()
+        .and_then(serde_json::Value::as_object);
+    let result_value = parse_json_value(task.result_json.as_deref());
+    let result_object = result_value.as_ref().and_then(serde_json::Value::as_object);
+
+    let target_user_id = json_object_get_local_id(payload_object, "user_id");
+
+    let mut summary = derive_release_batch_summary(payload_object, result_object);
+
+    let ordered_events = task_events_for_diagnostics(events);
+
+    let mut processed = 0_i64;
+    let mut last_stage: Option<String> = None;
+    let mut item_pos_by_release_id = HashMap::<String, usize>::new();
+    let mut items: Vec<AdminTranslateReleaseBatchItemDiagnostic> = Vec::new();
+    for event in ordered_events {
+        if event.event_type != "task.progress" {
+            continue;
+        }
+        let payload_value = parse_json_value(Some(event.payload_json.as_str()));
+        let payload_object = payload_value
+            .as_ref()
+            .and_then(serde_json::Value::as_object);
+        let Some(stage) = json_object_get_string(payload_object, "stage") else {
+            continue;
+        };
+        last_stage = Some(stage.clone());
+        if stage != "release" {
+            continue;
+        }
+
+        processed += 1;
+        let release_id = json_object_get_string(payload_object, "release_id")
+            .unwrap_or_else(|| "unknown".to_owned());
+        let item_status = json_object_get_string(payload_object, "item_status")
+            .unwrap_or_else(|| "unknown".to_owned());
+        let item_error = json_object_get_string(payload_object, "item_error");
+        if let Some(pos) = item_pos_by_release_id.get(&release_id).copied() {
+            items[pos].item_status = item_status;
+            items[pos].item_error = item_error;
+            items[pos].last_event_at = event.created_at.clone();
+            continue;
+        }
+        let pos = items.len();
+        item_pos_by_release_id.insert(release_id.clone(), pos);
+        items.push(AdminTranslateReleaseBatchItemDiagnostic {
+            release_id,
+            item_status,
+            item_error,
+            last_event_at: event.created_at.clone(),
+        });
+    }
+
+    if items.is_empty() {
+        let fallback_item_time = task
+            .finished_at
+            .clone()
+            .unwrap_or_else(|| task.updated_at.clone());
+        if let Some(result_items) = result_object
+            .and_then(|obj| obj.get("items"))
+            .and_then(serde_json::Value::as_array)
+        {
+            for item in result_items {
+                let item_object = item.as_object();
+                items.push(AdminTranslateReleaseBatchItemDiagnostic {
+                    release_id: json_object_get_string(item_object, "id")
+                        .unwrap_or_else(|| "unknown".to_owned()),
+                    item_status: json_object_get_string(item_object, "status")
+                        .unwrap_or_else(|| "unknown".to_owned()),
+                    item_error: json_object_get_string(item_object, "error"),
+                    last_event_at: fallback_item_time.clone(),
+                });
+            }
+        }
+        if processed == 0 {
+            processed = i64::try_from(items.len()).unwrap_or(i64::MAX);
+        }
+        if last_stage.is_none() && !items.is_empty() {
+            last_stage = Some("release".to_owned());
+        }
+    }
+
+    if summary.total == 0 {
+        summary.total = processed;
+    }
+
+    let release_total = summary.total.max(processed);
+    let progress = AdminTranslateReleaseBatchProgress {
+        processed,
+        last_stage,
+    };
+
+    let outcome = release_batch_business_outcome(
+        task.status.as_str(),
+        &summary,
+        task.error_message.as_deref(),
+    );
+
+    (
+        outcome,
+        AdminTranslateReleaseBatchDiagnostics {
+            target_user_id,
+            release_total,
+            summary,
+            progress,
+            items,
+        },
+    )
+}
+
+pub(super) fn upsert_daily_slot_user_diag(
+    users: &mut Vec<AdminBriefDailySlotUserDiagnostic>,
+    index: &mut HashMap<String, usize>,
+    user_id: String,
+    event_created_at: &str,
+) -> usize {
+    if let Some(pos) = index.get(&user_id).copied() {
+        users[pos].last_event_at = event_created_at.to_owned();
+        return pos;
+    }
+    let pos = users.len();
+    users.push(AdminBriefDailySlotUserDiagnostic {
+        user_id: user_id.clone(),
+        key_date: None,
+        local_boundary: None,
+        time_zone: None,
+        window_start_utc: None,
+        window_end_utc: None,
+        state: "running".to_owned(),
+        error: None,
+        last_event_at: event_created_at.to_owned(),
+    });
+    index.insert(user_id, pos);
+    pos
+}
+
+pub(super) fn build_brief_daily_slot_diagnostics(
+    task: &AdminRealtimeTaskDetailItem,
+    events: &[AdminTaskEventItem],
+) -> (AdminBusinessOutcome, AdminBriefDailySlotDiagnostics) {
+    let payload_value = parse_json_value(Some(task.payload_json.as_str()));
+    let payload_object = payload_value
+        .as_ref()
+        .and_then(serde_json::Value::as_object);
+    let result_value = parse_json_value(task.result_json.as_deref());
+    let result_object = result_value.as_ref().and_then(serde_json::Value::as_object);
+
+    let hour_utc = json_object_get_i64(payload_object, "hour_utc");
+    let mut collected_total_users = 0_i64;
+    let mut progressed_users = 0_i64;
+    let mut summary_total_from_event: Option<i64> = None;
+    let mut summary_succeeded_from_event: Option<i64> = None;
+    let mut summary_failed_from_event: Option<i64> = None;
+    let mut summary_canceled_from_event: Option<bool> = None;
+    let mut succeeded_from_events = 0_i64;
+    let mut failed_from_events = 0_i64;
+
+    let mut users: Vec<AdminBriefDailySlotUserDiagnostic> = Vec::new();
+    let mut user_index = HashMap::<String, usize>::new();
+    let ordered_events = task_events_for_diagnostics(events);
+    for event in ordered_events {
+        if event.event_type != "task.progress" {
+            continue;
+        }
+        let payload_value = parse_json_value(Some(event.payload_json.as_str()));
+        let payload_object = payload_value
+            .as_ref()
+            .and_then(serde_json::Value::as_object);
+        let Some(stage) = json_object_get_string(payload_object, "stage") else {
+            continue;
+        };
+        match stage.as_str() {
+            "collect" => {
+                if let Some(total_users) = json_object_get_i64(payload_object, "total_users") {
+                    collected_total_users = total_users;
+                }
+            }
+            "generate" => {
+                if let Some(index_value) = json_object_get_i64(payload_object, "index") {
+                    progressed_users = progressed_users.max(index_value);
+                }
+                if let Some(user_id) = json_object_get_local_id(payload_object, "user_id") {
+                    let pos = upsert_daily_slot_user_diag(
+                        &mut users,
+                        &mut user_index,
+                        user_id,
+                        &event.created_at,
+                    );
+                    if let Some(key_date) = json_object_get_string(payload_object, "key_date") {
+                        users[pos].key_date = Some(key_date);
+                    }
+                    users[pos].local_boundary =
+                        json_object_get_string(payload_object, "local_boundary");
+                    users[pos].time_zone = json_object_get_string(payload_object, "time_zone");
+                    users[pos].window_start_utc =
+                        json_object_get_string(payload_object, "window_start_utc");
+                    users[pos].window_end_utc =
+                        json_object_get_string(payload_object, "window_end_utc");
+                }
+            }
+            "user_failed" => {
+                failed_from_events += 1;
+                if let Some(user_id) = json_object_get_local_id(payload_object, "user_id") {
+                    let pos = upsert_daily_slot_user_diag(
+                        &mut users,
+                        &mut user_index,
+                        user_id,
+                        &event.created_at,
+                    );
+                    users[pos].state = "failed".to_owned();
+                    users[pos].error = json_object_get_string(payload_object, "error");
+                    if let Some(key_date) = json_object_get_string(payload_object, "key_date") {
+                        users[pos].key_date = Some(key_date);
+                    }
+                    users[pos].local_boundary =
+                        json_object_get_string(payload_object, "local_boundary");
+                    users[pos].time_zone = json_object_get_string(payload_object, "time_zone");
+                }
+            }
+            "user_succeeded" => {
+                succeeded_from_events += 1;
+                if let Some(user_id) = json_object_get_local_id(payload_object, "user_id") {
+                    let pos = upsert_daily_slot_user_diag(
+                        &mut users,
+                        &mut user_index,
+                        user_id,
+                        &event.created_at,
+                    );
+                    users[pos].state = "succeeded".to_owned();
+                    users[pos].error = None;
+                    if let Some(key_date) = json_object_get_string(payload_object, "key_date") {
+                        users[pos].key_date = Some(key_date);
+                    }
+                    users[pos].local_boundary =
+                        json_object_get_string(payload_object, "local_boundary");
+                    users[pos].time_zone = json_object_get_string(payload_object, "time_zone");
+                    users[pos].window_start_utc =
+                        json_object_get_string(payload_object, "window_start_utc");
+                    users[pos].window_end_utc =
+                        json_object_get_string(payload_object, "window_end_utc");
+                }
+            }
+            "summary" => {
+                summary_total_from_event = json_object_get_i64(payload_object, "total");
+                summary_succeeded_from_event = json_object_get_i64(payload_object, "succeeded");
+                summary_failed_from_event = json_object_get_i64(payload_object, "failed");
+                summary_canceled_from_event = json_object_get_bool(payload_object, "canceled");
+            }
+            _ => {}
+        }
+    }
+
+    let total_users = summary_total_from_event
+        .or_else(|| json_object_get_i64(result_object, "total"))
+        .unwrap_or(collected_total_users.max(i64::try_from(users.len()).unwrap_or(0)));
+    let succeeded_users = summary_succeeded_from_event
+        .or_else(|| json_object_get_i64(result_object, "succeeded"))
+        .unwrap_or(succeeded_from_events);
+    let failed_users = summary_failed_from_event
+        .or_else(|| json_object_get_i64(result_object, "failed"))
+        .unwrap_or(failed_from_events);
+    let canceled = summary_canceled_from_event
+        .or_else(|| json_object_get_bool(result_object, "canceled"))
+        .unwrap_or(false);
+
+    let summary = AdminBriefDailySlotSummary {
+        total_users,
+        progressed_users,
+        succeeded_users,
+        failed_users,
+        canceled,
+    };
+
+    users.sort_by_key(|item| item.user_id.clone());
+
+    let outcome = if task.status == jobs::STATUS_FAILED {
+        business_outcome(
+            "failed",
+            "业务失败",
+            task.error_message
+                .clone()
+                .unwrap_or_else(|| "日报任务执行失败。".to_owned()),
+        )
+    } else if task.status == jobs::STATUS_CANCELED {
+        business_outcome(
+            "partial",
+            "已取消",
+            "任务在执行中被取消，部分用户可能未处理。",
+        )
+    } else if task.status == jobs::STATUS_QUEUED || task.status == jobs::STATUS_RUNNING {
+        business_outcome("unknown", "处理中", "任务正在执行中，结果尚未稳定。")
+    } else if canceled {
+        business_outcome(
+            "partial",
+            "已取消",
+            "任务在执行中被取消，部分用户可能未处理。",
+        )
+    } else if total_users == 0 {
+        business_outcome("ok", "无需执行", "当前小时槽没有可执行用户。")
+    } else if failed_users > 0 && succeeded_users == 0 {
+        business_outcome(
+            "failed",
+            "业务失败",
+            "日报任务执行完成，但全部用户处理失败。",
+        )
+    } else if failed_users > 0 {
+        business_outcome("partial", "部分成功", "部分用户日报生成成功，部分失败。")
+    } else if succeeded_users > 0 {
+        business_outcome("ok", "业务成功", "日报任务已完成，目标用户处理成功。")
+    } else {
+        business_outcome(
+            "unknown",
+            "结果未知",
+            "任务已完成，但缺少可判定的用户执行结果。",
+        )
+    };
+
+    (
+        outcome,
+        AdminBriefDailySlotDiagnostics {
+            hour_utc,
+            summary,
+            users,
+        },
+    )
+}
+
+pub(super) fn build_brief_generate_diagnostics(
+    task: &AdminRealtimeTaskDetailItem,
+) -> (AdminBusinessOutcome, AdminBriefGenerateDiagnostics) {
+    let payload_value = parse_json_value(Some(task.payload_json.as_str()));
+    let payload_object = payload_value
+        .as_ref()
+        .and_then(serde_json::Value::as_object);
+    let result_value = parse_json_value(task.result_json.as_deref());
+    let result_object = result_value.as_ref().and_then(serde_json::Value::as_object);
+
+    let content_length = json_object_get_i64(result_object, "content_length");
+    let diagnostics = AdminBriefGenerateDiagnostics {
+        target_user_id: json_object_get_local_id(payload_object, "user_id"),
+        brief_id: json_object_get_local_id(result_object, "brief_id"),
+        content_length,
+        key_date: json_object_get_string(payload_object, "key_date"),
+        date: json_object_get_string(result_object, "date"),
+        window_start_utc: json_object_get_string(result_object, "window_start_utc"),
+        window_end_utc: json_object_get_string(result_object, "window_end_utc"),
+        effective_time_zone: json_object_get_string(result_object, "effective_time_zone"),
+        effective_local_boundary: json_object_get_string(result_object, "effective_local_boundary"),
+        release_count: json_object_get_i64(result_object, "release_count"),
+    };
+
+    let outcome = if task.status == jobs::STATUS_FAILED {
+        business_outcome(
+            "failed",
+            "业务失败",
+            task.error_message
+                .clone()
+                .unwrap_or_else(|| "日报生成失败。".to_owned()),
+        )
+    } else if task.status == jobs::STATUS_CANCELED {
+        business_outcome("partial", "已取消", "任务在执行中被取消，结果可能不完整。")
+    } else if task.status == jobs::STATUS_SUCCEEDED {
+        if content_length.unwrap_or(0) > 0 {
+            business_outcome("ok", "业务成功", "日报内容已生成并写入。")
+        } else {
+            business_outcome(
+                "unknown",
+                "结果未知",
+                "任务已完成，但缺少可见的日报内容长度。",
+            )
+        }
+    } else {
+        business_outcome("unknown", "处理中", "任务尚未完成。")
+    };
+
+    (outcome, diagnostics)
+}
+
+pub(super) fn build_brief_history_recompute_diagnostics(
+    task: &AdminRealtimeTaskDetailItem,
+    events: &[AdminTaskEventItem],
+) -> (AdminBusinessOutcome, AdminBriefHistoryRecomputeDiagnostics) {
+    let result_value = parse_json_value(task.result_json.as_deref());
+    let result_object = result_value.as_ref().and_then(serde_json::Value::as_object);
+
+    let mut total_from_event: Option<i64> = None;
+    let mut processed_from_event: Option<i64> = None;
+    let mut succeeded_from_event: Option<i64> = None;
+    let mut failed_from_event: Option<i64> = None;
+    let mut canceled_from_event: Option<bool> = None;
+    let ordered_events = task_events_for_diagnostics(events);
+    let mut current_brief_id: Option<String> = None;
+    let mut last_error: Option<String> = None;
+
+    for event in ordered_events {
+        if event.event_type != "task.progress" {
+            continue;
+        }
+        let payload_value = parse_json_value(Some(event.payload_json.as_str()));
+        let payload_object = payload_value
+            .as_ref()
+            .and_then(serde_json::Value::as_object);
+        let Some(stage) = json_object_get_string(payload_object, "stage") else {
+            continue;
+        };
+        match stage.as_str() {
+            "recompute" | "brief_succeeded" => {
+                current_brief_id = json_object_get_string(payload_object, "brief_id");
+            }
+            "brief_failed" => {
+                current_brief_id = json_object_get_string(payload_object, "brief_id");
+                last_error = json_object_get_string(payload_object, "error");
+            }
+            "summary" => {
+                total_from_event = json_object_get_i64(payload_object, "total");
+                processed_from_event = json_object_get_i64(payload_object, "processed");
+                succeeded_from_event = json_object_get_i64(payload_object, "succeeded");
+                failed_from_event = json_object_get_i64(payload_object, "failed");
+                canceled_from_event = json_object_get_bool(payload_object, "canceled");
+                current_brief_id = None;
+            }
+            _ => {}
+        }
+    }
+
+    let total = total_from_event
+        .or_else(|| json_object_get_i64(result_object, "total"))
+        .unwrap_or(0);
+    let processed = processed_from_event
+        .or_else(|| json_object_get_i64(result_object, "processed"))
+        .unwrap_or(0);
+    let succeeded = succeeded_from_event
+        .or_else(|| json_object_get_i64(result_object, "succeeded"))
+        .unwrap_or(0);
+    let failed = failed_from_event
+        .or_else(|| json_object_get_i64(result_object, "failed"))
+        .unwrap_or(0);
+    let canceled = canceled_from_event
+        .or_else(|| json_object_get_bool(result_object, "canceled"))
+        .unwrap_or(false);
+
+    let diagnostics = AdminBriefHistoryRecomputeDiagnostics {
+        total,
+        processed,
+        succeeded,
+        failed,
+        current_brief_id,
+        last_error,
+        canceled,
+    };
+
+    let outcome = if task.status == jobs::STATUS_FAILED {
+        business_outcome(
+            "failed",
+            "业务失败",
+            task.error_message
+                .clone()
+                .unwrap_or_else(|| "历史日报重算失败。".to_owned()),
+        )
+    } else if task.status == jobs::STATUS_CANCELED || canceled {
+        business_outcome("partial", "已取消", "历史日报重算在执行中被取消。")
+    } else if total == 0 {
+        business_outcome("ok", "无需执行", "没有遗留旧日报需要重算。")
+    } else if failed > 0 && succeeded == 0 {
+        business_outcome("failed", "业务失败", "历史日报重算未成功处理任何遗留日报。")
+    } else if failed > 0 {
+        business_outcome("partial", "部分成功", "历史日报已部分重算，仍有失败项。")
+    } else if succeeded > 0 {
+        business_outcome("ok", "业务成功", "历史日报重算已完成。")
+    } else {
+        business_outcome("unknown", "处理中", "历史日报重算尚未完成。")
+    };
+
+    (outcome, diagnostics)
+}
+
+pub(super) fn build_brief_refresh_content_diagnostics(
+    task: &AdminRealtimeTaskDetailItem,
+    events: &[AdminTaskEventItem],
+) -> (AdminBusinessOutcome, AdminBriefRefreshContentDiagnostics) {
+    let result_value = parse_json_value(task.result_json.as_deref());
+    let result_object = result_value.as_ref().and_then(serde_json::Value::as_object);
+
+    let mut total_from_event: Option<i64> = None;
+    let mut processed_from_event: Option<i64> = None;
+    let mut succeeded_from_event: Option<i64> = None;
+    let mut failed_from_event: Option<i64> = None;
+    let mut canceled_from_event: Option<bool> = None;
+    let ordered_events = task_events_for_diagnostics(events);
+    let mut current_brief_id: Option<String> = None;
+    let mut last_error: Option<String> = None;
+
+    for event in ordered_events {
+        if event.event_type != "task.progress" {
+            continue;
+        }
+        let payload_value = parse_json_value(Some(event.payload_json.as_str()));
+        let payload_object = payload_value
+            .as_ref()
+            .and_then(serde_json::Value::as_object);
+        let Some(stage) = json_object_get_string(payload_object, "stage") else {
+            continue;
+        };
+        match stage.as_str() {
+            "collect" => {
+                total_from_event = json_object_get_i64(payload_object, "total_briefs");
+            }
+            "refresh" => {
+                if let Some(index) = json_object_get_i64(payload_object, "index") {
+                    processed_from_event = Some(processed_from_event.unwrap_or(0).max(index));
+                }
+                if let Some(total) = json_object_get_i64(payload_object, "total") {
+                    total_from_event = Some(total_from_event.unwrap_or(0).max(total));
+                }
+                current_brief_id = json_object_get_string(payload_object, "brief_id");
+            }
+            "brief_succeeded" => {
+                if let Some(index) = json_object_get_i64(payload_object, "index") {
+                    processed_from_event = Some(processed_from_event.unwrap_or(0).max(index));
+                }
+                if let Some(total) = json_object_get_i64(payload_object, "total") {
+                    total_from_event = Some(total_from_event.unwrap_or(0).max(total));
+                }
+                succeeded_from_event = Some(succeeded_from_event.unwrap_or(0) + 1);
+                current_brief_id = json_object_get_string(payload_object, "brief_id");
+            }
+            "brief_failed" => {
+                if let Some(index) = json_object_get_i64(payload_object, "index") {
+                    processed_from_event = Some(processed_from_event.unwrap_or(0).max(index));
+                }
+                if let Some(total) = json_object_get_i64(payload_object, "total") {
+                    total_from_event = Some(total_from_event.unwrap_or(0).max(total));
+                }
+                failed_from_event = Some(failed_from_event.unwrap_or(0) + 1);
+                current_brief_id = json_object_get_string(payload_object, "brief_id");
+                last_error = json_object_get_string(payload_object, "error");
+            }
+            "summary" => {
+                total_from_event = json_object_get_i64(payload_object, "total");
+                processed_from_event = json_object_get_i64(payload_object, "processed");
+                succeeded_from_event = json_object_get_i64(payload_object, "succeeded");
+                failed_from_event = json_object_get_i64(payload_object, "failed");
+                canceled_from_event = json_object_get_bool(payload_object, "canceled");
+                current_brief_id = None;
+            }
+            _ => {}
+        }
+    }
+
+    let total = total_from_event
+        .or_else(|| json_object_get_i64(result_object, "total"))
+        .unwrap_or(0);
+    let processed = processed_from_event
+        .or_else(|| json_object_get_i64(result_object, "processed"))
+        .unwrap_or(0);
+    let succeeded = succeeded_from_event
+        .or_else(|| json_object_get_i64(result_object, "succeeded"))
+        .unwrap_or(0);
+    let failed = failed_from_event
+        .or_else(|| json_object_get_i64(result_object, "failed"))
+        .unwrap_or(0);
+    let canceled = canceled_from_event
+        .or_else(|| json_object_get_bool(result_object, "canceled"))
+        .unwrap_or(false);
+
+    let diagnostics = AdminBriefRefreshContentDiagnostics {
+        total,
+        processed,
+        succeeded,
+        failed,
+        current_brief_id,
+        last_error,
+        canceled,
+    };
+
+    let outcome = if task.status == jobs::STATUS_FAILED {
+        business_outcome(
+            "failed",
+            "业务失败",
+            task.error_message
+                .clone()
+                .unwrap_or_else(|| "日报内容修复失败。".to_owned()),
+        )
+    } else if task.status == jobs::STATUS_CANCELED || canceled {
+        business_outcome("partial", "已取消", "日报内容修复在执行中被取消。")
+    } else if total == 0 {
+        business_outcome("ok", "无需执行", "没有命中过时格式的日报需要修复。")
+    } else if failed > 0 && succeeded == 0 {
+        business_outcome("failed", "业务失败", "日报内容修复未成功处理任何候选日报。")
+    } else if failed > 0 {
+        business_outcome("partial", "部分成功", "日报内容已部分修复，仍有失败项。")
+    } else if succeeded > 0 {
+        business_outcome("ok", "业务成功", "日报内容修复已完成。")
+    } else {
+        business_outcome("unknown", "处理中", "日报内容修复尚未完成。")
+    };
+
+    (outcome, diagnostics)
+}
+
+pub(super) fn retry_kind_diagnostics_from_value(
+    value: Option<&serde_json::Value>,
+) -> AdminRetryRecentFailuresKindDiagnostics {
+    let object = value.and_then(serde_json::Value::as_object);
+    AdminRetryRecentFailuresKindDiagnostics {
+        total: json_object_get_i64(object, "total").unwrap_or(0),
+        processed: json_object_get_i64(object, "processed").unwrap_or(0),
+        succeeded: json_object_get_i64(object, "succeeded").unwrap_or(0),
+        failed: json_object_get_i64(object, "failed").unwrap_or(0),
+        skipped: json_object_get_i64(object, "skipped").unwrap_or(0),
+        timed_out: json_object_get_bool(object, "timed_out").unwrap_or(false),
+        duration_ms: json_object_get_i64(object, "duration_ms").unwrap_or(0),
+        current_id: json_object_get_string(object, "current_id"),
+        last_error: json_object_get_string(object, "last_error"),
+    }
+}
+
+pub(super) fn build_retry_recent_failures_diagnostics(
+    task: &AdminRealtimeTaskDetailItem,
+) -> (AdminBusinessOutcome, AdminRetryRecentFailuresDiagnostics) {
+    let result_value = parse_json_value(task.result_json.as_deref());
+    let result_object = result_value.as_ref().and_then(serde_json::Value::as_object);
+    let daily_brief = retry_kind_diagnostics_from_value(
+        result_object.and_then(|object| object.get("daily_brief")),
+    );
+    let polish =
+        retry_kind_diagnostics_from_value(result_object.and_then(|object| object.get("polish")));
+    let translation = retry_kind_diagnostics_from_value(
+        result_object.and_then(|object| object.get("translation")),
+    );
+    let skipped = json_object_get_bool(result_object, "skipped").unwrap_or(false);
+    let skip_reason = json_object_get_string(result_object, "skip_reason");
+    let canceled = task.status == jobs::STATUS_CANCELED
+        || json_object_get_bool(result_object, "canceled").unwrap_or(false);
+    let diagnostics = AdminRetryRecentFailuresDiagnostics {
+        skipped,
+        skip_reason: skip_reason.clone(),
+        canceled,
+        schedule_key: json_object_get_string(result_object, "schedule_key"),
+        interval_minutes: json_object_get_i64(result_object, "interval_minutes"),
+        daily_brief,
+        polish,
+        translation,
+    };
+
+    let total_processed = diagnostics.daily_brief.processed
+        + diagnostics.polish.processed
+        + diagnostics.translation.processed;
+    let total_succeeded = diagnostics.daily_brief.succeeded
+        + diagnostics.polish.succeeded
+        + diagnostics.translation.succeeded;
+    let total_failed =
+        diagnostics.daily_brief.failed + diagnostics.polish.failed + diagnostics.translation.failed;
+    let total_timed_out = diagnostics.daily_brief.timed_out
+        || diagnostics.polish.timed_out
+        || diagnostics.translation.timed_out;
+
+    let outcome = if skipped {
+        business_outcome(
+            "ok",
+            "已跳过",
+            skip_reason.unwrap_or_else(|| "上一轮失败数据重试仍在运行，本轮已跳过。".to_owned()),
+        )
+    } else if canceled {
+        business_outcome("unknown", "已取消", "失败数据重试任务已取消。")
+    } else if task.status == jobs::STATUS_FAILED {
+        business_outcome(
+            "failed",
+            "业务失败",
+            task.error_message
+                .clone()
+                .unwrap_or_else(|| "失败数据重试任务失败。".to_owned()),
+        )
+    } else if task.status == jobs::STATUS_QUEUED || task.status == jobs::STATUS_RUNNING {
+        business_outcome("unknown", "处理中", "失败数据重试任务正在执行。")
+    } else if total_processed == 0 {
+        business_outcome("ok", "无需执行", "最近 24 小时没有可重试失败数据。")
+    } else if total_failed > 0 && total_succeeded == 0 {
+        business_outcome("failed", "业务失败", "失败数据重试未成功处理任何对象。")
+    } else if total_failed > 0 || total_timed_out {
+        business_outcome(
+            "partial",
+            "部分成功",
+            "失败数据重试已部分推进，仍有失败或超时。",
+        )
+    } else {
+        business_outcome("ok", "业务成功", "失败数据重试已完成。")
+    };
+
+    (outcome, diagnostics)
+}
+
+pub(super) fn map_sync_subscription_event(
+    row: AdminSyncSubscriptionEventRow,
+) -> AdminSyncSubscriptionEventItem {
+    let payload_value = parse_json_value(row.payload_json.as_deref());
+    let payload_object = payload_value
+        .as_ref()
+        .and_then(serde_json::Value::as_object);
+
+    AdminSyncSubscriptionEventItem {
+        id: row.id,
+        stage: row.stage,
+        event_type: row.event_type,
+        severity: row.severity,
+        recoverable: row.recoverable,
+        attempt: row.attempt,
+        user_id: row.user_id,
+        repo_id: row.repo_id,
+        repo_full_name: row.repo_full_name,
+        message: json_object_get_string(payload_object, "message"),
+        created_at: row.created_at,
+    }
+}
+
+pub(super) fn latest_access_refresh_failure(
+    events: &[AdminTaskEventItem],
+) -> Option<AdminSyncAccessRefreshFailureDiagnostic> {
+    let mut latest = None;
+    for event in task_events_for_diagnostics(events) {
+        if event.event_type != "task.progress" {
+            continue;
+        }
+        let payload_value = parse_json_value(Some(event.payload_json.as_str()));
+        let payload_object = payload_value
+            .as_ref()
+            .and_then(serde_json::Value::as_object);
+        if json_object_get_string(payload_object, "stage").as_deref() != Some("star_failed") {
+            continue;
+        }
+        latest = Some(AdminSyncAccessRefreshFailureDiagnostic {
+            operation: json_object_get_string(payload_object, "operation"),
+            error_kind: json_object_get_string(payload_object, "error_kind"),
+            error_stage: json_object_get_string(payload_object, "error_stage"),
+            retryable: json_object_get_bool(payload_object, "retryable").unwrap_or(false),
+            http_status: json_object_get_i64(payload_object, "http_status"),
+            timeout_ms: json_object_get_i64(payload_object, "timeout_ms"),
+            elapsed_ms: json_object_get_i64(payload_object, "elapsed_ms"),
+            attempts: json_object_get_i64(payload_object, "attempts"),
+            retry_limit: json_object_get_i64(payload_object, "retry_limit"),
+            error_chain: json_object_get_string(payload_object, "error_chain"),
+        });
+    }
+    latest
+}
+
+pub(super) fn build_sync_access_refresh_diagnostics(
+    task: &AdminRealtimeTaskDetailItem,
+    events: &[AdminTaskEventItem],
+) -> (AdminBusinessOutcome, AdminSyncAccessRefreshDiagnostics) {
+    let result_value = parse_json_value(task.result_json.as_deref());
+    let result_object = result_value.as_ref().and_then(serde_json::Value::as_object);
+    let starred_object = json_object_get_object(result_object, "starred");
+    let release_object = json_object_get_object(result_object, "release");
+    let social_object = json_object_get_object(result_object, "social");
+    let notifications_object = json_object_get_object(result_object, "notifications");
+    let failure = latest_access_refresh_failure(events);
+    let social_error = json_object_get_string(result_object, "social_error");
+    let notifications_error = json_object_get_string(result_object, "notifications_error");
+    let log_available = realtime_task_log_available(task);
+    let log_download_path = log_available.then(|| realtime_task_log_download_path(&task.id));
+    let diagnostics = AdminSyncAccessRefreshDiagnostics {
+        log_available,
+        log_download_path,
+        star_repos: json_object_get_i64(starred_object, "repos").unwrap_or(0),
+        release_repos: json_object_get_i64(release_object, "repos").unwrap_or(0),
+        releases: json_object_get_i64(release_object, "releases").unwrap_or(0),
+        social_repo_stars: json_object_get_i64(social_object, "repo_stars").unwrap_or(0),
+        social_followers: json_object_get_i64(social_object, "followers").unwrap_or(0),
+        social_events: json_object_get_i64(social_object, "events").unwrap_or(0),
+        notifications: json_object_get_i64(notifications_object, "notifications").unwrap_or(0),
+        social_error: social_error.clone(),
+        notifications_error: notifications_error.clone(),
+        failure,
+    };
+
+    let outcome = if task.status == jobs::STATUS_FAILED {
+        if let Some(failure) = diagnostics.failure.as_ref() {
+            let mut parts = Vec::new();
+            if let Some(operation) = failure.operation.as_deref() {
+                parts.push(format!("operation={operation}"));
+            }
+            if let Some(error_stage) = failure.error_stage.as_deref() {
+                parts.push(format!("stage={error_stage}"));
+            }
+            if let Some(error_kind) = failure.error_kind.as_deref() {
+                parts.push(format!("error_kind={error_kind}"));
+            }
+            if let Some(elapsed_ms) = failure.elapsed_ms {
+                parts.push(format!("elapsed={}ms", elapsed_ms));
+            }
+            if let Some(timeout_ms) = failure.timeout_ms {
+                parts.push(format!("timeout={}ms", timeout_ms));
+            }
+            business_outcome(
+                "failed",
+                "Star 阶段失败",
+                if parts.is_empty() {
+                    task.error_message
+                        .clone()
+                        .unwrap_or_else(|| "访问增量同步在 Star 阶段失败。".to_owned())
+                } else {
+                    format!("访问增量同步在 Star 阶段失败：{}。", parts.join(" · "))
+                },
+            )
+        } else {
+            business_outcome(
+                "failed",
+                "访问同步失败",
+                task.error_message
+                    .clone()
+                    .unwrap_or_else(|| "访问增量同步执行失败。".to_owned()),
+            )
+        }
+    } else if task.status == jobs::STATUS_CANCELED {
+        business_outcome("partial", "已取消", "访问增量同步已取消，结果可能不完整。")
+    } else if matches!(
+        task.status.as_str(),
+        jobs::STATUS_QUEUED | jobs::STATUS_RUNNING
+    ) {
+        business_outcome(
+            "unknown",
+            "处理中",
+            "访问增量同步正在执行中，结果尚未稳定。",
+        )
+    } else if social_error.is_some() || notifications_error.is_some() {
+        business_outcome(
+            "partial",
+            "部分成功",
+            "Star / Release 已完成，但 social 或 Inbox 出现 best-effort 异常。",
+        )
+    } else {
+        business_outcome(
+            "ok",
+            "业务成功",
+            "访问增量同步已完成 Star / Release / social / Inbox。",
+        )
+    };
+
+    (outcome, diagnostics)
+}
+
+pub(super) fn build_sync_subscriptions_diagnostics(
+    task: &AdminRealtimeTaskDetailItem,
+    events: &[AdminTaskEventItem],
+    recent_events: &[AdminSyncSubscriptionEventItem],
+) -> (AdminBusinessOutcome, AdminSyncSubscriptionsDiagnostics) {
+    let payload_value = parse_json_value(Some(task.payload_json.as_str()));
+    let payload_object = payload_value
+        .as_ref()
+        .and_then(serde_json::Value::as_object);
+    let result_value = parse_json_value(task.result_json.as_deref());
+    let result_object = result_value.as_ref().and_then(serde_json::Value::as_object);
+    let star_object = json_object_get_object(result_object, "star");
+    let release_object = json_object_get_object(result_object, "release");
+    let social_object = json_object_get_object(result_object, "social");
+    let notifications_object = json_object_get_object(result_object, "notifications");
+
+    let skipped = json_object_get_bool(result_object, "skipped").unwrap_or(false);
+    let skip_reason = json_object_get_string(result_object, "skip_reason");
+    let trigger = json_object_get_string(payload_object, "trigger");
+    let schedule_key = json_object_get_string(payload_object, "schedule_key");
+    let mut star = AdminSyncSubscriptionStarDiagnostics {
+        total_users: json_object_get_i64(star_object, "total_users").unwrap_or(0),
+        succeeded_users: json_object_get_i64(star_object, "succeeded_users").unwrap_or(0),
+        failed_users: json_object_get_i64(star_object, "failed_users").unwrap_or(0),
+        total_repos: json_object_get_i64(star_object, "total_repos").unwrap_or(0),
+    };
+    let mut release = AdminSyncSubscriptionReleaseDiagnostics {
+        total_repos: json_object_get_i64(release_object, "total_repos").unwrap_or(0),
+        succeeded_repos: json_object_get_i64(release_object, "succeeded_repos").unwrap_or(0),
+        failed_repos: json_object_get_i64(release_object, "failed_repos").unwrap_or(0),
+        candidate_failures: json_object_get_i64(release_object, "candidate_failures").unwrap_or(0),
+        fetched_count: json_object_get_i64(release_object, "fetched_count").unwrap_or(0),
+        inserted_count: json_object_get_i64(release_object, "inserted_count").unwrap_or(0),
+        updated_count: json_object_get_i64(release_object, "updated_count").unwrap_or(0),
+        unchanged_count: json_object_get_i64(release_object, "unchanged_count").unwrap_or(0),
+        pages_fetched: json_object_get_i64(release_object, "pages_fetched").unwrap_or(0),
+    };
+    let mut social = AdminSyncSubscriptionSocialDiagnostics {
+        total_users: json_object_get_i64(social_object, "total_users").unwrap_or(0),
+        succeeded_users: json_object_get_i64(social_object, "succeeded_users").unwrap_or(0),
+        failed_users: json_object_get_i64(social_object, "failed_users").unwrap_or(0),
+        repo_stars: json_object_get_i64(social_object, "repo_stars").unwrap_or(0),
+        followers: json_object_get_i64(social_object, "followers").unwrap_or(0),
+        events: json_object_get_i64(social_object, "events").unwrap_or(0),
+    };
+    let mut notifications = AdminSyncSubscriptionNotificationsDiagnostics {
+        total_users: json_object_get_i64(notifications_object, "total_users").unwrap_or(0),
+        succeeded_users: json_object_get_i64(notifications_object, "succeeded_users").unwrap_or(0),
+        failed_users: json_object_get_i64(notifications_object, "failed_users").unwrap_or(0),
+        notifications: json_object_get_i64(notifications_object, "notifications").unwrap_or(0),
+    };
+    let mut releases_written = json_object_get_i64(result_object, "releases_written").unwrap_or(0);
+    let critical_events = json_object_get_i64(result_object, "critical_events").unwrap_or(0);
+    apply_sync_subscription_progress_events(
+        events,
+        result_object.is_none(),
+        star_object.is_none(),
+        release_object.is_none(),
+        social_object.is_none(),
+        notifications_object.is_none(),
+        json_object_get_i64(result_object, "releases_written").is_none(),
+        &mut star,
+        &mut release,
+        &mut social,
+        &mut notifications,
+        &mut releases_written,
+    );
+    let log_available = realtime_task_log_available(task);
+    let log_download_path = log_available.then(|| realtime_task_log_download_path(&task.id));
+    let star_pending = (star.total_users - star.succeeded_users - star.failed_users).max(0);
+    let release_pending =
+        (release.total_repos - release.succeeded_repos - release.failed_repos).max(0);
+    let social_pending = (social.total_users - social.succeeded_users - social.failed_users).max(0);
+    let notifications_pending =
+        (notifications.total_users - notifications.succeeded_users - notifications.failed_users)
+            .max(0);
+
+    let outcome = if task.status == jobs::STATUS_FAILED {
+        business_outcome(
+            "failed",
+            "业务失败",
+            task.error_message
+                .clone()
+                .unwrap_or_else(|| "订阅同步任务执行失败。".to_owned()),
+        )
+    } else if skipped {
+        business_outcome(
+            "disabled",
+            "已跳过",
+            match skip_reason.as_deref() {
+                Some("previous_run_active") => {
+                    "上一轮订阅同步仍在执行，本轮仅记录跳过结果。".to_owned()
+                }
+                Some(other) => format!("本轮任务已跳过：{other}"),
+                None => "本轮任务已跳过。".to_owned(),
+            },
+        )
+    } else if task.status == jobs::STATUS_CANCELED {
+        business_outcome("partial", "已取消", "任务在执行中被取消，结果可能不完整。")
+    } else if task.status == jobs::STATUS_QUEUED || task.status == jobs::STATUS_RUNNING {
+        business_outcome("unknown", "处理中", "任务正在执行中，结果尚未稳定。")
+    } else if star.total_users == 0 {
+        business_outcome("ok", "无需执行", "当前没有可同步的启用用户。")
+    } else if star.succeeded_users == 0 && star.failed_users > 0 {
+        business_outcome(
+            "failed",
+            "业务失败",
+            "Star 阶段全部失败，未进入仓库抓取阶段。",
+        )
+    } else if release.total_repos > 0 && release.succeeded_repos == 0 && release.failed_repos > 0 {
+        business_outcome(
+            "failed",
+            "业务失败",
+            "Release 阶段全部失败，未能写入任何仓库结果。",
+        )
+    } else if star.failed_users > 0
+        || release.failed_repos > 0
+        || social.failed_users > 0
+        || notifications.failed_users > 0
+        || star_pending > 0
+        || release_pending > 0
+        || social_pending > 0
+        || notifications_pending > 0
+        || critical_events > 0
+    {
+        business_outcome(
+            "partial",
+            "部分完成",
+            "任务已结束，但仍存在失败、剩余工作项或关键告警，请查看阶段摘要与最近关键事件。",
+        )
+    } else if release.total_repos == 0 {
+        business_outcome(
+            "ok",
+            "业务成功",
+            "Star 阶段已完成，本轮没有需要抓取 Release 的仓库。",
+        )
+    } else {
+        business_outcome("ok", "业务成功", "订阅同步任务已完成。")
+    };
+
+    (
+        outcome,
+        AdminSyncSubscriptionsDiagnostics {
+            trigger,
+            schedule_key,
+            skipped,
+            skip_reason,
+            log_available,
+            log_download_path,
+            star,
+            release,
+            social,
+            notifications,
+            releases_written,
+            critical_events,
+            recent_events: recent_events.to_vec(),
+        },
+    )
+}
+
+#[allow(clippy::too_many_arguments)]
+pub(super) fn apply_sync_subscription_progress_events(
+    events: &[AdminTaskEventItem],
+    fill_all: bool,
+    fill_star: bool,
+    fill_release: bool,
+    fill_social: bool,
+    fill_notifications: bool,
+    fill_releases_written: bool,
+    star: &mut AdminSyncSubscriptionStarDiagnostics,
+    release: &mut AdminSyncSubscriptionReleaseDiagnostics,
+    social: &mut AdminSyncSubscriptionSocialDiagnostics,
+    notifications: &mut AdminSyncSubscriptionNotificationsDiagnostics,
+    releases_written: &mut i64,
+) {
+    for event in task_events_for_diagnostics(events) {
+        if event.event_type != "task.progress" {
+            continue;
+        }
+        let payload_value = parse_json_value(Some(event.payload_json.as_str()));
+        let payload_object = payload_value
+            .as_ref()
+            .and_then(serde_json::Value::as_object);
+        let Some(stage) = json_object_get_string(payload_object, "stage") else {
+            continue;
+        };
+
+        match stage.as_str() {
+            "collect" => {
+                if (fill_all || fill_star)
+                    && let Some(total_users) = json_object_get_i64(payload_object, "total_users")
+                {
+                    star.total_users = total_users;
+                }
+            }
+            "star_progress" => {
+                if fill_all || fill_star {
+                    star.total_users = json_object_get_i64(payload_object, "total_users")
+                        .unwrap_or(star.total_users);
+                    star.succeeded_users = json_object_get_i64(payload_object, "succeeded_users")
+                        .unwrap_or(star.succeeded_users);
+                    star.failed_users = json_object_get_i64(payload_object, "failed_users")
+                        .unwrap_or(star.failed_users);
+                    star.total_repos = json_object_get_i64(payload_object, "total_repos")
+                        .unwrap_or(star.total_repos);
+                }
+            }
+            "star_summary" => {
+                if fill_all || fill_star {
+                    star.total_users = json_object_get_i64(payload_object, "total_users")
+                        .unwrap_or(star.total_users);
+                    star.succeeded_users = json_object_get_i64(payload_object, "succeeded_users")
+                        .unwrap_or(star.succeeded_users);
+                    star.failed_users = json_object_get_i64(payload_object, "failed_users")
+                        .unwrap_or(star.failed_users);
+                    star.total_repos = json_object_get_i64(payload_object, "total_repos")
+                        .unwrap_or(star.total_repos);
+                }
+            }
+            "repo_collect" => {
+                if (fill_all || fill_release)
+                    && let Some(total_repos) = json_object_get_i64(payload_object, "total_repos")
+                {
+                    release.total_repos = total_repos;
+                }
+            }
+            "release_attached" => {
+                if fill_all || fill_release {
+                    release.total_repos =
+                        json_object_get_i64(payload_object, "repos").unwrap_or(release.total_repos);
+                }
+            }
+            "release_progress" => {
+                if fill_all || fill_release {
+                    release.total_repos = json_object_get_i64(payload_object, "total_repos")
+                        .unwrap_or(release.total_repos);
+                    release.succeeded_repos =
+                        json_object_get_i64(payload_object, "succeeded_repos")
+                            .unwrap_or(release.succeeded_repos);
+                    release.failed_repos = json_object_get_i64(payload_object, "failed_repos")
+                        .unwrap_or(release.failed_repos);
+                    release.candidate_failures =
+                        json_object_get_i64(payload_object, "candidate_failures")
+                            .unwrap_or(release.candidate_failures);
+                    release.fetched_count = json_object_get_i64(payload_object, "fetched_count")
+                        .unwrap_or(release.fetched_count);
+                    release.inserted_count = json_object_get_i64(payload_object, "inserted_count")
+                        .unwrap_or(release.inserted_count);
+                    release.updated_count = json_object_get_i64(payload_object, "updated_count")
+                        .unwrap_or(release.updated_count);
+                    release.unchanged_count =
+                        json_object_get_i64(payload_object, "unchanged_count")
+                            .unwrap_or(release.unchanged_count);
+                    release.pages_fetched = json_object_get_i64(payload_object, "pages_fetched")
+                        .unwrap_or(release.pages_fetched);
+                }
+                if fill_all || fill_releases_written {
+                    *releases_written = json_object_get_i64(payload_object, "releases_written")
+                        .unwrap_or(*releases_written);
+                }
+            }
+            "release_summary" => {
+                if fill_all || fill_release {
+                    release.total_repos = json_object_get_i64(payload_object, "total_repos")
+                        .unwrap_or(release.total_repos);
+                    release.succeeded_repos =
+                        json_object_get_i64(payload_object, "succeeded_repos")
+                            .unwrap_or(release.succeeded_repos);
+                    release.failed_repos = json_object_get_i64(payload_object, "failed_repos")
+                        .unwrap_or(release.failed_repos);
+                    release.candidate_failures =
+                        json_object_get_i64(payload_object, "candidate_failures")
+                            .unwrap_or(release.candidate_failures);
+                    release.fetched_count = json_object_get_i64(payload_object, "fetched_count")
+                        .unwrap_or(release.fetched_count);
+                    release.inserted_count = json_object_get_i64(payload_object, "inserted_count")
+                        .unwrap_or(release.inserted_count);
+                    release.updated_count = json_object_get_i64(payload_object, "updated_count")
+                        .unwrap_or(release.updated_count);
+                    release.unchanged_count =
+                        json_object_get_i64(payload_object, "unchanged_count")
+                            .unwrap_or(release.unchanged_count);
+                    release.pages_fetched = json_object_get_i64(payload_object, "pages_fetched")
+                        .unwrap_or(release.pages_fetched);
+                }
+                if fill_all || fill_releases_written {
+                    *releases_written = json_object_get_i64(payload_object, "releases_written")
+                        .unwrap_or(*releases_written);
+                }
+            }
+            "social_progress" => {
+                if fill_all || fill_social {
+                    social.total_users = json_object_get_i64(payload_object, "total_users")
+                        .unwrap_or(social.total_users);
+                    social.succeeded_users = json_object_get_i64(payload_object, "succeeded_users")
+                        .unwrap_or(social.succeeded_users);
+                    social.failed_users = json_object_get_i64(payload_object, "failed_users")
+                        .unwrap_or(social.failed_users);
+                    social.repo_stars = json_object_get_i64(payload_object, "repo_stars")
+                        .unwrap_or(social.repo_stars);
+                    social.followers = json_object_get_i64(payload_object, "followers")
+                        .unwrap_or(social.followers);
+                    social.events =
+                        json_object_get_i64(payload_object, "events").unwrap_or(social.events);
+                }
+            }
+            "social_summary" => {
+                if fill_all || fill_social {
+                    social.total_users = json_object_get_i64(payload_object, "total_users")
+                        .unwrap_or(social.total_users);
+                    social.succeeded_users = json_object_get_i64(payload_object, "succeeded_users")
+                        .unwrap_or(social.succeeded_users);
+                    social.failed_users = json_object_get_i64(payload_object, "failed_users")
+                        .unwrap_or(social.failed_users);
+                    social.repo_stars = json_object_get_i64(payload_object, "repo_stars")
+                        .unwrap_or(social.repo_stars);
+                    social.followers = json_object_get_i64(payload_object, "followers")
+                        .unwrap_or(social.followers);
+                    social.events =
+                        json_object_get_i64(payload_object, "events").unwrap_or(social.events);
+                }
+            }
+            "notifications_progress" => {
+                if fill_all || fill_notifications {
+                    notifications.total_users = json_object_get_i64(payload_object, "total_users")
+                        .unwrap_or(notifications.total_users);
+                    notifications.succeeded_users =
+                        json_object_get_i64(payload_object, "succeeded_users")
+                            .unwrap_or(notifications.succeeded_users);
+                    notifications.failed_users =
+                        json_object_get_i64(payload_object, "failed_users")
+                            .unwrap_or(notifications.failed_users);
+                    notifications.notifications =
+                        json_object_get_i64(payload_object, "notifications")
+                            .unwrap_or(notifications.notifications);
+                }
+            }
+            "notifications_summary" => {
+                if fill_all || fill_notifications {
+                    notifications.total_users = json_object_get_i64(payload_object, "total_users")
+                        .unwrap_or(notifications.total_users);
+                    notifications.succeeded_users =
+                        json_object_get_i64(payload_object, "succeeded_users")
+                            .unwrap_or(notifications.succeeded_users);
+                    notifications.failed_users =
+                        json_object_get_i64(payload_object, "failed_users")
+                            .unwrap_or(notifications.failed_users);
+                    notifications.notifications =
+                        json_object_get_i64(payload_object, "notifications")
+                            .unwrap_or(notifications.notifications);
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+pub(super) fn build_task_diagnostics(
+    task: &AdminRealtimeTaskDetailItem,
+    events: &[AdminTaskEventItem],
+    subscription_events: &[AdminSyncSubscriptionEventItem],
+) -> Option<AdminTaskDiagnostics> {
+    match task.task_type.as_str() {
+        jobs::TASK_TRANSLATE_RELEASE_BATCH | jobs::TASK_SUMMARIZE_RELEASE_SMART_BATCH => {
+            let (business_outcome, diagnostics) =
+                build_translate_release_batch_diagnostics(task, events);
+            Some(AdminTaskDiagnostics {
+                business_outcome,
+                translate_release_batch: Some(diagnostics),
+                brief_daily_slot: None,
+                brief_generate: None,
+                brief_history_recompute: None,
+                brief_refresh_content: None,
+                retry_recent_failures: None,
+                sync_access_refresh: None,
+                sync_subscriptions: None,
+            })
+        }
+        jobs::TASK_BRIEF_DAILY_SLOT => {
+            let (business_outcome, diagnostics) = build_brief_daily_slot_diagnostics(task, events);
+            Some(AdminTaskDiagnostics {
+                business_outcome,
+                translate_release_batch: None,
+                brief_daily_slot: Some(diagnostics),
+                brief_generate: None,
+                brief_history_recompute: None,
+                brief_refresh_content: None,
+                retry_recent_failures: None,
+                sync_access_refresh: None,
+                sync_subscriptions: None,
+            })
+        }
+        jobs::TASK_BRIEF_GENERATE => {
+            let (business_outcome, diagnostics) = build_brief_generate_diagnostics(task);
+            Some(AdminTaskDiagnostics {
+                business_outcome,
+                translate_release_batch: None,
+                brief_daily_slot: None,
+                brief_generate: Some(diagnostics),
+                brief_history_recompute: None,
+                brief_refresh_content: None,
+                retry_recent_failures: None,
+                sync_access_refresh: None,
+                sync_subscriptions: None,
+            })
+        }
+        jobs::TASK_BRIEF_HISTORY_RECOMPUTE => {
+            let (business_outcome, diagnostics) =
+                build_brief_history_recompute_diagnostics(task, events);
+            Some(AdminTaskDiagnostics {
+                business_outcome,
+                translate_release_batch: None,
+                brief_daily_slot: None,
+                brief_generate: None,
+                brief_history_recompute: Some(diagnostics),
+                brief_refresh_content: None,
+                retry_recent_failures: None,
+                sync_access_refresh: None,
+                sync_subscriptions: None,
+            })
+        }
+        jobs::TASK_BRIEF_REFRESH_CONTENT => {
+            let (business_outcome, diagnostics) =
+                build_brief_refresh_content_diagnostics(task, events);
+            Some(AdminTaskDiagnostics {
+                business_outcome,
+                translate_release_batch: None,
+                brief_daily_slot: None,
+                brief_generate: None,
+                brief_history_recompute: None,
+                brief_refresh_content: Some(diagnostics),
+                retry_recent_failures: None,
+                sync_access_refresh: None,
+                sync_subscriptions: None,
+            })
+        }
+        jobs::TASK_RETRY_RECENT_FAILURES => {
+            let (business_outcome, diagnostics) = build_retry_recent_failures_diagnostics(task);
+            Some(AdminTaskDiagnostics {
+                business_outcome,
+                translate_release_batch: None,
+                brief_daily_slot: None,
+                brief_generate: None,
+                brief_history_recompute: None,
+                brief_refresh_content: None,
+                retry_recent_failures: Some(diagnostics),
+                sync_access_refresh: None,
+                sync_subscriptions: None,
+            })
+        }
+        jobs::TASK_SYNC_ACCESS_REFRESH => {
+            let (business_outcome, diagnostics) =
+                build_sync_access_refresh_diagnostics(task, events);
+            Some(AdminTaskDiagnostics {
+                business_outcome,
+                translate_release_batch: None,
+                brief_daily_slot: None,
+                brief_generate: None,
+                brief_history_recompute: None,
+                brief_refresh_content: None,
+                retry_recent_failures: None,
+                sync_access_refresh: Some(diagnostics),
+                sync_subscriptions: None,
+            })
+        }
+        jobs::TASK_SYNC_SUBSCRIPTIONS => {
+            let (business_outcome, diagnostics) =
+                build_sync_subscriptions_diagnostics(task, events, subscription_events);
+            Some(AdminTaskDiagnostics {
+                business_outcome,
+                translate_release_batch: None,
+                brief_daily_slot: None,
+                brief_generate: None,
+                brief_history_recompute: None,
+                brief_refresh_content: None,
+                retry_recent_failures: None,
+                sync_access_refresh: None,
+                sync_subscriptions: Some(diagnostics),
+            })
+        }
+        _ => None,
+    }
+}
+
+pub(super) async fn attach_subscription_list_diagnostics(
+    state: &AppState,
+    items: &mut [AdminRealtimeTaskItem],
+) -> Result<(), ApiError> {
+    let task_ids = items
+        .iter()
+        .filter(|item| item.task_type == jobs::TASK_SYNC_SUBSCRIPTIONS)
+        .map(|item| item.id.clone())
+        .collect::<Vec<_>>();
+    if task_ids.is_empty() {
+        return Ok(());
+    }
+
+    let mut tasks_query = QueryBuilder::<sqlx::Sqlite>::new(
+        r#"
+        SELECT
+          id,
+          task_type,
+          status,
+          source,
+          priority,
+          requested_by,
+          parent_task_id,
+          request_id,
+          cancel_requested,
+          error_message,
+          payload_json,
+          result_json,
+          log_file_path,
+          created_at,
+          started_at,
+          finished_at,
+          updated_at
+        FROM job_tasks
+        WHERE id IN (
+        "#,
+    );
+    {
+        let mut separated = tasks_query.separated(", ");
+        for task_id in &task_ids {
+            separated.push_bind(task_id);
+        }
+    }
+    tasks_query.push(")");
+    let tasks = tasks_query
+        .build_query_as::<AdminRealtimeTaskDetailItem>()
+        .fetch_all(&state.pool)
+        .await
+        .map_err(ApiError::internal)?
+        .into_iter()
+        .map(|task| (task.id.clone(), task))
+        .collect::<HashMap<_, _>>();
+    let mut diagnostic_events =
+        load_subscription_diagnostic_progress_events_for_tasks(state, &task_ids).await?;
+    let mut subscription_events =
+        load_recent_sync_subscription_events_for_tasks(state, &task_ids).await?;
+
+    for item in items
+        .iter_mut()
+        .filter(|item| item.task_type == jobs::TASK_SYNC_SUBSCRIPTIONS)
+    {
+        let Some(task) = tasks.get(item.id.as_str()) else {
+            continue;
+        };
+        let diagnostic_events = diagnostic_events
+            .remove(item.id.as_str())
+            .unwrap_or_default();
+        let subscription_events = subscription_events
+            .remove(item.id.as_str())
+            .unwrap_or_default();
+        item.diagnostics = build_task_diagnostics(task, &diagnostic_events, &subscription_events);
+    }
+
+    Ok(())
+}
+
+pub async fn admin_get_realtime_task_detail(
+    State(state): State<Arc<AppState>>,
+    _: AdminUser,
+    Path(task_id): Path<String>,
+) -> Result<Json<AdminRealtimeTaskDetailResponse>, ApiError> {
+    let task_id = parse_local_id_param(task_id, "task_id")?;
+    Ok(Json(
+        load_realtime_task_detail_response(state.as_ref(), task_id.as_str()).await?,
+    ))
+}
+
+pub(super) async fn load_realtime_task_detail_response(
+    state: &AppState,
+    task_id: &str,
+) -> Result<AdminRealtimeTaskDetailResponse, ApiError> {
+    let task = sqlx::query_as::<_, AdminRealtimeTaskDetailItem>(
+        r#"
+        SELECT
+          id,
+          task_type,
+          status,
+          source,
+          priority,
+          requested_by,
+          parent_task_id,
+          request_id,
+          cancel_requested,
+          error_message,
+          payload_json,
+          result_json,
+          log_file_path,
+          created_at,
+          started_at,
+          finished_at,
+          updated_at
+        FROM job_tasks
+        WHERE id = ?
+        LIMIT 1
+        "#,
+    )
+    .bind(task_id)
+    .fetch_optional(&state.pool)
+    .await
+    .map_err(ApiError::internal)?
+    .ok_or_else(|| ApiError::new(StatusCode::NOT_FOUND, codes::NOT_FOUND, "task not found"))?;
+
+    let event_total = sqlx::query_scalar::<_, i64>(
+        r#"
+        SELECT COUNT(*)
+        FROM job_task_events
+        WHERE task_id = ?
+        "#,
+    )
+    .bind(task_id)
+    .fetch_one(&state.pool)
+    .await
+    .map_err(ApiError::internal)?;
+
+    let events = sqlx::query_as::<_, AdminTaskEventItem>(
+        r#"
+        SELECT id, event_type, payload_json, created_at
+        FROM job_task_events
+        WHERE task_id = ?
+        ORDER BY rowid DESC
+        LIMIT ?
+        "#,
+    )
+    .bind(task_id)
+    .bind(ADMIN_TASK_DETAIL_EVENT_LIMIT)
+    .fetch_all(&state.pool)
+    .await
+    .map_err(ApiError::internal)?;
+
+    let returned = i64::try_from(events.len()).unwrap_or(ADMIN_TASK_DETAIL_EVENT_LIMIT);
+    let event_total = event_total.max(returned);
+    let event_meta = AdminTaskEventMeta {
+        returned,
+        total: event_total,
+        limit: ADMIN_TASK_DETAIL_EVENT_LIMIT,
+        truncated: event_total > returned,
+    };
+
+    let subscription_events = if task.task_type == jobs::TASK_SYNC_SUBSCRIPTIONS {
+        load_recent_sync_subscription_events(state, task_id).await?
+    } else {
+        Vec::new()
+    };
+
+    let diagnostic_events = if task.task_type == jobs::TASK_SYNC_SUBSCRIPTIONS {
+        load_subscription_diagnostic_progress_events(state, task_id).await?
+    } else {
+        events.clone()
+    };
+
+    let diagnostics = build_task_diagnostics(&task, &diagnostic_events, &subscription_events);
+
+    Ok(AdminRealtimeTaskDetailResponse {
+        task,
+        events,
+        event_meta,
+        diagnostics,
+    })
+}
+
+pub async fn admin_download_realtime_task_log(
+    State(state): State<Arc<AppState>>,
+    _: AdminUser,
+    Path(task_id): Path<String>,
+) -> Result<Response, ApiError> {
+    let task_id = parse_local_id_param(task_id, "task_id")?;
+
+    let log_file_path = jobs::load_task_log_path(state.as_ref(), task_id.as_str())
+        .await
+        .map_err(ApiError::internal)?
+        .ok_or_else(|| {
+            ApiError::new(
+                StatusCode::NOT_FOUND,
+                codes::NOT_FOUND,
+                "task log not found",
+            )
+        })?;
+
+    let file = tokio::fs::File::open(&log_file_path).await.map_err(|err| {
+        if err.kind() == std::io::ErrorKind::NotFound {
+            ApiError::new(
+                StatusCode::NOT_FOUND,
+                codes::NOT_FOUND,
+                "task log not found",
+            )
+        } else {
+            ApiError::internal(err)
+        }
+    })?;
+
+    let stream = async_stream::stream! {
+        let mut file = file;
+        let mut buffer = [0_u8; 16 * 1024];
+        loop {
+            match file.read(&mut buffer).await {
+                Ok(0) => break,
+                Ok(read) => yield Ok::<Bytes, std::io::Error>(Bytes::copy_from_slice(&buffer[..read])),
+                Err(err) => {
+                    yield Err::<Bytes, std::io::Error>(err);
+                    break;
+                }
+            }
+        }
+    };
+
+    let filename = format!("{}.ndjson", task_id);
+    let mut response = Response::new(Body::from_stream(stream));
+    *response.status_mut() = StatusCode::OK;
+    response.headers_mut().insert(
+        header::CONTENT_TYPE,
+        HeaderValue::from_static("application/x-ndjson"),
+    );
+    response.headers_mut().insert(
+        header::CONTENT_DISPOSITION,
+        HeaderValue::from_str(&format!(r#"attachment; filename="{}""#, filename))
+            .map_err(ApiError::internal)?,
+    );
+
+    Ok(response)
+}
+
+#[derive(Debug, Serialize)]
+pub struct AdminTaskActionResponse {
+    pub(super) task_id: String,
+    pub(super) status: String,
+}
+
+pub async fn admin_retry_realtime_task(
+    State(state): State<Arc<AppState>>,
+    AdminUser(acting_user_id): AdminUser,
+    Path(task_id): Path<String>,
+) -> Result<Json<AdminTaskActionResponse>, ApiError> {
+    let task_id = parse_local_id_param(task_id, "task_id")?;
+    let task = jobs::retry_task(state.as_ref(), task_id.as_str(), acting_user_id).await?;
+
+    Ok(Json(AdminTaskActionResponse {
+        task_id: task.task_id,
+        status: task.status,
+    }))
+}
+
+pub async fn admin_cancel_realtime_task(
+    State(state): State<Arc<AppState>>,
+    Path(task_id): Path<String>,
+    _: AdminUser,
+) -> Result<Json<AdminTaskActionResponse>, ApiError> {
+    let task_id = parse_local_id_param(task_id, "task_id")?;
+    let status = jobs::cancel_task(state.as_ref(), task_id.as_str()).await?;
+
+    Ok(Json(AdminTaskActionResponse { task_id, status }))
+}
+
+#[derive(Debug, Serialize)]
+pub struct AdminMaintenancePruneResponse {
+    pub(super) task_id: String,
+    #[serde(flatten)]
+    pub(super) stats: jobs::PruneStats,
+}
+
+pub async fn admin_run_maintenance_prune(
+    State(state): State<Arc<AppState>>,
+    AdminUser(acting_user_id): AdminUser,
+) -> Result<Json<AdminMaintenancePruneResponse>, ApiError> {
+    let (task_id, stats) = jobs::run_maintenance_prune_now(state.as_ref(), Some(acting_user_id))
+        .await
+        .map_err(ApiError::internal)?;
+
+    Ok(Json(AdminMaintenancePruneResponse { task_id, stats }))
+}
+
+#[derive(Debug, Serialize, sqlx::FromRow)]
+pub struct AdminScheduledSlotItem {
+    pub(super) hour_utc: i64,
+    pub(super) enabled: bool,
+    pub(super) last_dispatch_at: Option<String>,
+    pub(super) updated_at: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct AdminScheduledSlotsResponse {
+    pub(super) items: Vec<AdminScheduledSlotItem>,
+}
+
+pub async fn admin_list_scheduled_slots(
+    State(state): State<Arc<AppState>>,
+    _: AdminUser,
+) -> Result<Json<AdminScheduledSlotsResponse>, ApiError> {
+    let items = sqlx::query_as::<_, AdminScheduledSlotItem>(
+        r#"
+        SELECT hour_utc, enabled, last_dispatch_at, updated_at
+        FROM daily_brief_hour_slots
+        ORDER BY hour_utc ASC
+        "#,
+    )
+    .fetch_all(&state.pool)
+    .await
+    .map_err(ApiError::internal)?;
+    Ok(Json(AdminScheduledSlotsResponse { items }))
+}
+
+#[derive(Debug, Deserialize)]
+pub struct AdminPatchScheduledSlotRequest {
+    pub(super) enabled: bool,
+}
+
+pub async fn admin_patch_scheduled_slot(
+    State(state): State<Arc<AppState>>,
+    AdminUser(acting_user_id): AdminUser,
+    Path(hour_utc): Path<i64>,
+    ApiJson(req): ApiJson<AdminPatchScheduledSlotRequest>,
+) -> Result<Json<AdminScheduledSlotItem>, ApiError> {
+    if !(0..=23).contains(&hour_utc) {
+        return Err(ApiError::bad_request("hour_utc must be 0..23"));
+    }
+    state
+        .admin_mutation_limiter
+        .check(acting_user_id.as_str())?;
+
+    let mut tx = state.pool.begin().await.map_err(ApiError::internal)?;
+    let before_enabled = sqlx::query_scalar::<_, bool>(
+        r#"
+        SELECT enabled
+        FROM daily_brief_hour_slots
+        WHERE hour_utc = ?
+        "#,
+    )
+    .bind(hour_utc)
+    .fetch_optional(&mut *tx)
+    .await
+    .map_err(ApiError::internal)?;
+
+    let now = chrono::Utc::now().to_rfc3339();
+    sqlx::query(
+        r#"
+        UPDATE daily_brief_hour_slots
+        SET enabled = ?, updated_at = ?
+        WHERE hour_utc = ?
+        "#,
+    )
+    .bind(if req.enabled { 1_i64 } else { 0_i64 })
+    .bind(now.as_str())
+    .bind(hour_utc)
+    .execute(&mut *tx)
+    .await
+    .map_err(ApiError::internal)?;
+
+    if let Some(before_enabled) = before_enabled {
+        admin_audit::record(
+            &mut tx,
+            acting_user_id.as_str(),
+            None,
+            admin_audit::ADMIN_AUDIT_SCHEDULED_SLOT_UPDATE,
+            &json!({ "hour_utc": hour_utc, "enabled": before_enabled }),
+            &json!({ "hour_utc": hour_utc, "enabled": req.enabled }),
+        )
+        .await?;
+    }
+    tx.commit().await.map_err(ApiError::internal)?;
+
+    let item = sqlx::query_as::<_, AdminScheduledSlotItem>(
+        r#"
+        SELECT hour_utc, enabled, last_dispatch_at, updated_at
+        FROM daily_brief_hour_slots
+        WHERE hour_utc = ?
+        LIMIT 1
+        "#,
+    )
+    .bind(hour_utc)
+    .fetch_optional(&state.pool)
+    .await
+    .map_err(ApiError::internal)?
+    .ok_or_else(|| ApiError::new(StatusCode::NOT_FOUND, codes::NOT_FOUND, "slot not found"))?;
+
+    Ok(Json(item))
+}
+
+#[derive(Debug, Serialize, sqlx::FromRow)]
+pub struct AdminLlmCallItem {
+    pub(super) id: String,
+    pub(super) status: String,
+    pub(super) source: String,
+    pub(super) model: String,
+    pub(super) requested_by: Option<String>,
+    pub(super) parent_task_id: Option<String>,
+    pub(super) parent_task_type: Option<String>,
+    pub(super) request_id: Option<String>,
+    pub(super) max_tokens: i64,
+    pub(super) attempt_count: i64,
+    pub(super) scheduler_wait_ms: i64,
+    pub(super) first_token_wait_ms: Option<i64>,
+    pub(super) duration_ms: Option<i64>,
+    pub(super) input_tokens: Option<i64>,
+    pub(super) output_tokens: Option<i64>,
+    pub(super) cached_input_tokens: Option<i64>,
+    pub(super) total_tokens: Option<i64>,
+    pub(super) created_at: String,
+    pub(super) started_at: Option<String>,
+    pub(super) finished_at: Option<String>,
+    pub(super) updated_at: String,
+}
+
+#[derive(Debug, Serialize, sqlx::FromRow)]
+pub struct AdminLlmCallDetailItem {
+    pub(super) id: String,
+    pub(super) status: String,
+    pub(super) source: String,
+    pub(super) model: String,
+    pub(super) requested_by: Option<String>,
+    pub(super) parent_task_id: Option<String>,
+    pub(super) parent_task_type: Option<String>,
+    pub(super) request_id: Option<String>,
+    pub(super) max_tokens: i64,
+    pub(super) attempt_count: i64,
+    pub(super) scheduler_wait_ms: i64,
+    pub(super) first_token_wait_ms: Option<i64>,
+    pub(super) duration_ms: Option<i64>,
+    pub(super) input_tokens: Option<i64>,
+    pub(super) output_tokens: Option<i64>,
+    pub(super) cached_input_tokens: Option<i64>,
+    pub(super) total_tokens: Option<i64>,
+    pub(super) input_messages_json: Option<String>,
+    pub(super) output_messages_json: Option<String>,
+    pub(super) prompt_text: String,
+    pub(super) response_text: Option<String>,
+    pub(super) error_text: Option<String>,
+    pub(super) created_at: String,
+    pub(super) started_at: Option<String>,
+    pub(super) finished_at: Option<String>,
+    pub(super) updated_at: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct AdminLlmCallsResponse {
+    pub(super) items: Vec<AdminLlmCallItem>,
+    pub(super) page: i64,
+    pub(super) page_size: i64,
+    pub(super) total: i64,
+}
+
+#[derive(Debug, Serialize)]
+pub struct AdminLlmModelStatusItem {
+    pub(super) model: String,
+    pub(super) priority: i64,
+    pub(super) status: String,
+    pub(super) consecutive_final_failures: i64,
+    pub(super) cooldown_until: Option<String>,
+    pub(super) effective_input_limit: i64,
+    pub(super) effective_input_limit_source: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct AdminLlmSchedulerStatusResponse {
+    pub(super) scheduler_enabled: bool,
+    pub(super) llm_models: Vec<String>,
+    pub(super) selected_model_for_new_calls: Option<String>,
+    pub(super) max_concurrency: i64,
+    pub(super) ai_model_context_limit: Option<i64>,
+    pub(super) effective_model_input_limit: i64,
+    pub(super) effective_model_input_limit_source: String,
+    pub(super) model_statuses: Vec<AdminLlmModelStatusItem>,
+    pub(super) available_slots: i64,
+    pub(super) waiting_calls: i64,
+    pub(super) in_flight_calls: i64,
+    pub(super) paused: bool,
+    pub(super) request_interval_ms: i64,
+    pub(super) request_interval_source: &'static str,
+    pub(super) calls_24h: i64,
+    pub(super) failed_24h: i64,
+    pub(super) avg_wait_ms_24h: Option<i64>,
+    pub(super) avg_duration_ms_24h: Option<i64>,
+    pub(super) last_success_at: Option<String>,
+    pub(super) last_failure_at: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct AdminLlmRuntimeConfigUpdateRequest {
+    pub(super) max_concurrency: i64,
+    #[serde(default, deserialize_with = "deserialize_optional_nullable_i64")]
+    pub(super) ai_model_context_limit: Option<Option<i64>>,
+    #[serde(default)]
+    pub(super) llm_models: Option<Vec<String>>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct AdminLlmSchedulerControlRequest {
+    #[serde(default)]
+    pub(super) paused: Option<bool>,
+    #[serde(default, deserialize_with = "deserialize_optional_nullable_i64")]
+    pub(super) request_interval_ms: Option<Option<i64>>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct AdminLlmCallsQuery {
+    pub(super) status: Option<String>,
+    pub(super) source: Option<String>,
+    pub(super) requested_by: Option<String>,
+    pub(super) parent_task_id: Option<String>,
+    pub(super) request_id: Option<String>,
+    pub(super) started_from: Option<String>,
+    pub(super) started_to: Option<String>,
+    pub(super) sort: Option<String>,
+    pub(super) page: Option<i64>,
+    pub(super) page_size: Option<i64>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct AdminLlmUsageQuery {
+    pub(super) date: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct AdminLlmCallsExportQuery {
+    pub(super) format: Option<String>, // csv (default)
+    pub(super) status: Option<String>,
+    pub(super) source: Option<String>,
+    pub(super) requested_by: Option<String>,
+    pub(super) parent_task_id: Option<String>,
+    pub(super) request_id: Option<String>,
+    pub(super) started_from: Option<String>,
+    pub(super) started_to: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct AdminLlmUsageSummaryQuery {
+    pub(super) bucket: Option<String>, // day (default) | hour
+    pub(super) started_from: Option<String>,
+    pub(super) started_to: Option<String>,
+}
+
+#[derive(Debug, sqlx::FromRow)]
+pub(super) struct AdminLlmUsageSummaryRow {
+    pub(super) bucket_start: String,
+    pub(super) source: String,
+    pub(super) call_count: i64,
+    pub(super) succeeded_count: i64,
+    pub(super) failed_count: i64,
+    pub(super) input_tokens: i64,
+    pub(super) output_tokens: i64,
+    pub(super) total_tokens: i64,
+    pub(super) duration_ms_sum: i64,
+    pub(super) duration_count: i64,
+}
+
+#[derive(Debug, Default, Serialize)]
+pub struct AdminLlmUsageStats {
+    pub(super) call_count: i64,
+    pub(super) failed_count: i64,
+    /// Failed share of finished calls; queued and running calls are left out.
+    pub(super) failure_rate: f64,
+    pub(super) input_tokens: i64,
+    pub(super) output_tokens: i64,
+    pub(super) total_tokens: i64,
+    pub(super) avg_duration_ms: Option<f64>,
+    #[serde(skip)]
+    pub(super) succeeded_count: i64,
+    #[serde(skip)]
+    pub(super) duration_ms_sum: i64,
+    #[serde(skip)]
+    pub(super) duration_count: i64,
+}
+
+impl AdminLlmUsageStats {
+    pub(super) fn add(&mut self, row: &AdminLlmUsageSummaryRow) {
+        self.call_count += row.call_count;
+        self.succeeded_count += row.succeeded_count;
+        self.failed_count += row.failed_count;
+        self.input_tokens += row.input_tokens;
+        self.output_tokens += row.output_tokens;
+        self.total_tokens += row.total_tokens;
+        self.duration_ms_sum += row.duration_ms_sum;
+        self.duration_count += row.duration_count;
+        let finished = self.succeeded_count + self.failed_count;
+        self.failure_rate = if finished > 0 {
+            self.failed_count as f64 / finished as f64
+        } else {
+            0.0
+        };
+        self.avg_duration_ms = (self.duration_count > 0)
+            .then(|| self.duration_ms_sum as f64 / self.duration_count as f64);
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct AdminLlmUsageSourceStats {
+    pub(super) source: String,
+    #[serde(flatten)]
+    pub(super) stats: AdminLlmUsageStats,
+}
+
+#[derive(Debug, Serialize)]
+pub struct AdminLlmUsageSummaryBucket {
+    /// UTC start of the bucket, RFC3339.
+    pub(super) bucket_start: String,
+    #[serde(flatten)]
+    pub(super) totals: AdminLlmUsageStats,
+    pub(super) sources: Vec<AdminLlmUsageSourceStats>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct AdminLlmUsageSummaryResponse {
+    pub(super) bucket: &'static str,
+    pub(super) started_from: String,
+    pub(super) started_to: Option<String>,
+    pub(super) buckets: Vec<AdminLlmUsageSummaryBucket>,
+}
+
+#[derive(Debug, Serialize, sqlx::FromRow)]
+pub struct AdminLlmUsageItem {
+    pub(super) user_id: String,
+    pub(super) login: Option<String>,
+    pub(super) call_count: i64,
+    pub(super) input_tokens: i64,
+    pub(super) output_tokens: i64,
+    pub(super) total_tokens: i64,
+}
+
+#[derive(Debug, Serialize)]
+pub struct AdminLlmUsageResponse {
+    pub(super) date: String,
+    pub(super) daily_token_budget_per_user: Option<usize>,
+    pub(super) items: Vec<AdminLlmUsageItem>,
+}
+
+pub(super) fn llm_calls_status_rank(status: &str) -> i32 {
+    match status {
+        "running" => 0,
+        "queued" => 1,
+        _ => 2,
+    }
+}
+
+pub(super) fn apply_llm_call_admin_override(
+    item: &mut AdminLlmCallItem,
+    snapshot: &ai::LlmCallAdminOverride,
+) {
+    item.status = snapshot.status.clone();
+    item.attempt_count = snapshot.attempt_count;
+    item.scheduler_wait_ms = snapshot.scheduler_wait_ms;
+    item.first_token_wait_ms = snapshot.first_token_wait_ms;
+    item.duration_ms = snapshot.duration_ms;
+    item.input_tokens = snapshot.input_tokens;
+    item.output_tokens = snapshot.output_tokens;
+    item.cached_input_tokens = snapshot.cached_input_tokens;
+    item.total_tokens = snapshot.total_tokens;
+    if let Some(started_at) = snapshot.started_at.clone() {
+        item.started_at = Some(started_at);
+    }
+    if let Some(finished_at) = snapshot.finished_at.clone() {
+        item.finished_at = Some(finished_at);
+    }
+    item.updated_at = snapshot.updated_at.clone();
+}
+
+pub(super) fn apply_llm_call_detail_admin_override(
+    item: &mut AdminLlmCallDetailItem,
+    snapshot: &ai::LlmCallAdminOverride,
+) {
+    item.status = snapshot.status.clone();
+    item.attempt_count = snapshot.attempt_count;
+    item.scheduler_wait_ms = snapshot.scheduler_wait_ms;
+    item.first_token_wait_ms = snapshot.first_token_wait_ms;
+    item.duration_ms = snapshot.duration_ms;
+    item.input_tokens = snapshot.input_tokens;
+    item.output_tokens = snapshot.output_tokens;
+    item.cached_input_tokens = snapshot.cached_input_tokens;
+    item.total_tokens = snapshot.total_tokens;
+    if let Some(output_messages_json) = snapshot.output_messages_json.clone() {
+        item.output_messages_json = Some(output_messages_json);
+    }
+    if let Some(response_text) = snapshot.response_text.clone() {
+        item.response_text = Some(response_text);
+    }
+    if let Some(error_text) = snapshot.error_text.clone() {
+        item.error_text = Some(error_text);
+    }
+    if let Some(started_at) = snapshot.started_at.clone() {
+        item.started_at = Some(started_at);
+    }
+    if let Some(finished_at) = snapshot.finished_at.clone() {
+        item.finished_at = Some(finished_at);
+    }
+    item.updated_at = snapshot.updated_at.clone();
+}
+
+pub(super) fn apply_llm_call_admin_overrides(
+    items: &mut [AdminLlmCallItem],
+    overrides: &HashMap<String, ai::LlmCallAdminOverride>,
+) {
+    for item in items {
+        if let Some(snapshot) = overrides.get(&item.id) {
+            apply_llm_call_admin_override(item, snapshot);
+        }
+    }
+}
+
+pub(super) fn llm_call_matches_status_filter(item: &AdminLlmCallItem, status: &str) -> bool {
+    status == "all" || item.status == status
+}
+
+pub(super) fn llm_call_started_at_sort_key(item: &AdminLlmCallItem) -> i64 {
+    item.started_at
+        .as_deref()
+        .map(llm_call_created_at_sort_key)
+        .unwrap_or_else(|| llm_call_created_at_sort_key(&item.created_at))
+}
+
+pub(super) fn llm_call_matches_started_filter(
+    item: &AdminLlmCallItem,
+    started_from: Option<&str>,
+    started_to: Option<&str>,
+) -> bool {
+    let started_at = llm_call_started_at_sort_key(item);
+    if let Some(lower_bound) = started_from.map(llm_call_created_at_sort_key)
+        && started_at < lower_bound
+    {
+        return false;
+    }
+    if let Some(upper_bound) = started_to.map(llm_call_created_at_sort_key)
+        && started_at > upper_bound
+    {
+        return false;
+    }
+    true
+}
+
+pub(super) fn llm_call_created_at_sort_key(created_at: &str) -> i64 {
+    chrono::DateTime::parse_from_rfc3339(created_at)
+        .map(|value| value.timestamp_millis())
+        .unwrap_or(i64::MIN)
+}
+
+pub(super) fn sort_admin_llm_calls(items: &mut [AdminLlmCallItem], sort: &str) {
+    items.sort_by(|left, right| match sort {
+        "status_grouped" => llm_calls_status_rank(&left.status)
+            .cmp(&llm_calls_status_rank(&right.status))
+            .then_with(|| {
+                llm_call_created_at_sort_key(&right.created_at)
+                    .cmp(&llm_call_created_at_sort_key(&left.created_at))
+            })
+            .then_with(|| right.created_at.cmp(&left.created_at))
+            .then_with(|| right.id.cmp(&left.id)),
+        _ => llm_call_created_at_sort_key(&right.created_at)
+            .cmp(&llm_call_created_at_sort_key(&left.created_at))
+            .then_with(|| right.created_at.cmp(&left.created_at))
+            .then_with(|| right.id.cmp(&left.id)),
+    });
+}
+
+pub(super) fn parse_llm_calls_status_filter(value: Option<String>) -> Result<String, ApiError> {
+    let status = value.unwrap_or_else(|| "all".to_owned());
+    if !matches!(
+        status.as_str(),
+        "all" | "queued" | "running" | "succeeded" | "failed"
+    ) {
+        return Err(ApiError::bad_request("invalid status filter"));
+    }
+    Ok(status)
+}
+
+pub(super) fn parse_llm_calls_filter_timestamp(
+    value: Option<String>,
+    field: &str,
+) -> Result<Option<String>, ApiError> {
+    let Some(raw) = value else {
+        return Ok(None);
+    };
+    let trimmed = raw.trim();
+    if trimmed.is_empty() {
+        return Ok(None);
+    }
+    let parsed = chrono::DateTime::parse_from_rfc3339(trimmed)
+        .map_err(|_| ApiError::bad_request(format!("{field} must be RFC3339")))?;
+    Ok(Some(parsed.to_rfc3339()))
+}
+
+pub async fn admin_get_llm_scheduler_status(
+    State(state): State<Arc<AppState>>,
+    _: AdminUser,
+) -> Result<Json<AdminLlmSchedulerStatusResponse>, ApiError> {
+    admin_runtime::sync_persisted_runtime_settings(state.clone())
+        .await
+        .map_err(ApiError::internal)?;
+    Ok(Json(
+        load_admin_llm_scheduler_status_response(state.as_ref()).await?,
+    ))
+}
+
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct AdminLlmQueueItem {
+    pub(super) call_id: String,
+    pub(super) source: String,
+    pub(super) requested_by: Option<String>,
+    pub(super) parent_task_id: Option<String>,
+    pub(super) parent_task_type: Option<String>,
+    pub(super) enqueued_at: String,
+    pub(super) estimated_tokens: i64,
+    /// `interactive` calls are dispatched ahead of `background` ones.
+    pub(super) lane: &'static str,
+    /// 1-based position within the lane.
+    pub(super) position: i64,
+    /// `scheduler_paused`, `queued_behind_earlier_calls`,
+    /// `interactive_calls_first` or `no_free_slot`.
+    pub(super) wait_reason: &'static str,
+}
+
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct AdminLlmQueueResponse {
+    pub(super) items: Vec<AdminLlmQueueItem>,
+}
+
+fn admin_llm_queue_response(state: &AppState) -> AdminLlmQueueResponse {
+    let items = state
+        .llm_scheduler
+        .queue_snapshot()
+        .into_iter()
+        .map(|entry| AdminLlmQueueItem {
+            call_id: entry.call.call_id,
+            source: entry.call.source,
+            requested_by: entry.call.requested_by,
+            parent_task_id: entry.call.parent_task_id,
+            parent_task_type: entry.call.parent_task_type,
+            enqueued_at: entry.call.enqueued_at.to_rfc3339(),
+            estimated_tokens: i64::from(entry.call.estimated_tokens),
+            lane: entry.lane,
+            position: i64::try_from(entry.position).unwrap_or(i64::MAX),
+            wait_reason: entry.wait_reason,
+        })
+        .collect();
+    AdminLlmQueueResponse { items }
+}
+
+fn queued_llm_call_not_found() -> ApiError {
+    ApiError::new(
+        StatusCode::NOT_FOUND,
+        codes::NOT_FOUND,
+        "llm call is not queued",
+    )
+}
+
+/// Calls currently waiting in the in-memory LLM scheduler queue.
+pub async fn admin_get_llm_queue(
+    State(state): State<Arc<AppState>>,
+    _: AdminUser,
+) -> Json<AdminLlmQueueResponse> {
+    Json(admin_llm_queue_response(state.as_ref()))
+}
+
+pub async fn admin_promote_llm_queue_call(
+    State(state): State<Arc<AppState>>,
+    Path(call_id): Path<String>,
+    _: AdminUser,
+) -> Result<Json<AdminLlmQueueResponse>, ApiError> {
+    if !state.llm_scheduler.promote_queued_call(call_id.as_str()) {
+        return Err(queued_llm_call_not_found());
+    }
+    Ok(Json(admin_llm_queue_response(state.as_ref())))
+}
+
+pub async fn admin_drop_llm_queue_call(
+    State(state): State<Arc<AppState>>,
+    Path(call_id): Path<String>,
+    _: AdminUser,
+) -> Result<Json<AdminLlmQueueResponse>, ApiError> {
+    let dropped = ai::cancel_queued_llm_call(state.as_ref(), call_id.as_str())
+        .await
+        .map_err(ApiError::internal)?;
+    if !dropped {
+        return Err(queued_llm_call_not_found());
+    }
+    Ok(Json(admin_llm_queue_response(state.as_ref())))
+}
+
+/// Probes the AI upstream on demand; results are cached for a few minutes.
+pub async fn admin_get_ai_health(
+    State(state): State<Arc<AppState>>,
+    _: AdminUser,
+) -> Result<Json<ai::AiHealthReport>, ApiError> {
+    Ok(Json(ai::probe_ai_health(state.as_ref()).await))
+}
+
+pub async fn admin_patch_llm_runtime_config(
+    State(state): State<Arc<AppState>>,
+    _: AdminUser,
+    ApiJson(req): ApiJson<AdminLlmRuntimeConfigUpdateRequest>,
+) -> Result<Json<AdminLlmSchedulerStatusResponse>, ApiError> {
+    let max_concurrency = parse_positive_admin_concurrency(req.max_concurrency, "max_concurrency")?;
+    let ai_model_context_limit = match req.ai_model_context_limit {
+        Some(Some(value)) => Some(parse_positive_runtime_limit(
+            value,
+            "ai_model_context_limit",
+        )?),
+        Some(None) => None,
+        None => admin_runtime::load_ai_model_context_limit(&state.pool)
+            .await
+            .map_err(ApiError::internal)?,
+    };
+    let llm_models = match req.llm_models {
+        Some(models) => parse_llm_models(models)?,
+        None => admin_runtime::load_llm_models(&state.pool)
+            .await
+            .map_err(ApiError::internal)?,
+    };
+    admin_runtime::update_llm_runtime_settings(
+        &state.pool,
+        max_concurrency,
+        ai_model_context_limit,
+        &llm_models,
+    )
+    .await
+    .map_err(ApiError::internal)?;
+    admin_runtime::sync_persisted_runtime_settings(state.clone())
+        .await
+        .map_err(ApiError::internal)?;
+
+    Ok(Json(
+        load_admin_llm_scheduler_status_response(state.as_ref()).await?,
+    ))
+}
+
+pub async fn admin_patch_llm_scheduler(
+    State(state): State<Arc<AppState>>,
+    _: AdminUser,
+    ApiJson(req): ApiJson<AdminLlmSchedulerControlRequest>,
+) -> Result<Json<AdminLlmSchedulerStatusResponse>, ApiError> {
+    let request_interval_ms = req
+        .request_interval_ms
+        .map(|value| value.map(|ms| u64::try_from(ms.max(0)).unwrap_or(0)));
+    admin_runtime::load_or_seed_runtime_settings(&state.pool, &state.config)
+        .await
+        .map_err(ApiError::internal)?;
+    admin_runtime::update_llm_scheduler_controls(&state.pool, req.paused, request_interval_ms)
+        .await
+        .map_err(ApiError::internal)?;
+    admin_runtime::sync_persisted_runtime_settings(state.clone())
+        .await
+        .map_err(ApiError::internal)?;
+
+    Ok(Json(
+        load_admin_llm_scheduler_status_response(state.as_ref()).await?,
+    ))
+}
+
+pub(super) async fn load_admin_llm_scheduler_status_response(
+    state: &AppState,
+) -> Result<AdminLlmSchedulerStatusResponse, ApiError> {
+    let runtime = state.llm_scheduler.runtime_status();
+    let request_interval_source =
+        match admin_runtime::load_llm_request_interval_override(&state.pool)
+            .await
+            .map_err(ApiError::internal)?
+        {
+            Some(_) => "override",
+            None => "env",
+        };
+    let routing = state
+        .llm_scheduler
+        .routing_status(state.config.ai.as_ref().map(|cfg| cfg.model.as_str()))
+        .await;
+    let cutoff = (chrono::Utc::now() - chrono::Duration::hours(24)).to_rfc3339();
+    let (calls_24h, failed_24h, avg_wait_raw, avg_duration_raw) =
+        sqlx::query_as::<_, (i64, i64, Option<f64>, Option<f64>)>(
+            r#"
+            SELECT
+              COUNT(*) AS calls_24h,
+              COALESCE(SUM(CASE WHEN status = 'failed' THEN 1 ELSE 0 END), 0) AS failed_24h,
+              AVG(CAST(scheduler_wait_ms AS REAL)) AS avg_wait_ms_24h,
+              AVG(CAST(duration_ms AS REAL)) AS avg_duration_ms_24h
+            FROM llm_calls
+            WHERE created_at >= ?
+            "#,
+        )
+        .bind(cutoff.as_str())
+        .fetch_one(&state.pool)
+        .await
+        .map_err(ApiError::internal)?;
+
+    let last_success_at = sqlx::query_scalar::<_, Option<String>>(
+        r#"
+        SELECT MAX(finished_at)
+        FROM llm_calls
+        WHERE status = 'succeeded'
+        "#,
+    )
+    .fetch_one(&state.pool)
+    .await
+    .map_err(ApiError::internal)?;
+
+    let last_failure_at = sqlx::query_scalar::<_, Option<String>>(
+        r#"
+        SELECT MAX(finished_at)
+        FROM llm_calls
+        WHERE status = 'failed'
+        "#,
+    )
+    .fetch_one(&state.pool)
+    .await
+    .map_err(ApiError::internal)?;
+    let selected_model = ai::select_model_for_new_calls(state).await;
+    let ai_model_context_limit = admin_runtime::load_ai_model_context_limit(&state.pool)
+        .await
+        .map_err(ApiError::internal)?
+        .map(i64::from);
+    let mut model_statuses = Vec::with_capacity(routing.model_statuses.len());
+    for status in routing.model_statuses {
+        let (effective_input_limit, effective_input_limit_source) =
+            ai::resolve_model_input_limit_for_status(state, &status.model).await;
+        model_statuses.push(AdminLlmModelStatusItem {
+            model: status.model,
+            priority: status.priority,
+            status: status.status.to_owned(),
+            consecutive_final_failures: status.consecutive_final_failures,
+            cooldown_until: status.cooldown_until,
+            effective_input_limit: i64::from(effective_input_limit),
+            effective_input_limit_source: effective_input_limit_source.to_owned(),
+        });
+    }
+
+    Ok(AdminLlmSchedulerStatusResponse {
+        scheduler_enabled: state.config.ai.is_some(),
+        llm_models: routing.llm_models,
+        selected_model_for_new_calls: routing.selected_model_for_new_calls,
+        max_concurrency: runtime.max_concurrency,
+        ai_model_context_limit,
+        effective_model_input_limit: i64::from(selected_model.model_input_limit),
+        effective_model_input_limit_source: selected_model.fallback_source.to_owned(),
+        model_statuses,
+        available_slots: runtime.available_slots,
+        waiting_calls: runtime.waiting_calls,
+        in_flight_calls: runtime.in_flight_calls,
+        paused: runtime.paused,
+        request_interval_ms: runtime.request_interval_ms,
+        request_interval_source,
+        calls_24h,
+        failed_24h,
+        avg_wait_ms_24h: avg_wait_raw.map(|value| value.round() as i64),
+        avg_duration_ms_24h: avg_duration_raw.map(|value| value.round() as i64),
+        last_success_at,
+        last_failure_at,
+    })
+}
+
+pub(super) fn parse_positive_admin_concurrency(value: i64, field: &str) -> Result<usize, ApiError> {
+    let parsed = usize::try_from(value)
+        .map_err(|_| ApiError::bad_request(format!("{field} must be a positive integer")))?;
+    if parsed == 0 {
+        return Err(ApiError::bad_request(format!(
+            "{field} must be a positive integer"
+        )));
+    }
+    if parsed > tokio::sync::Semaphore::MAX_PERMITS {
+        return Err(ApiError::bad_request(format!(
+            "{field} must be a positive integer <= {}",
+            tokio::sync::Semaphore::MAX_PERMITS
+        )));
+    }
+    Ok(parsed)
+}
+
+pub(super) fn parse_positive_runtime_limit(value: i64, field: &str) -> Result<u32, ApiError> {
+    let parsed = u32::try_from(value)
+        .map_err(|_| ApiError::bad_request(format!("{field} must be a positive integer")))?;
+    if parsed == 0 {
+        return Err(ApiError::bad_request(format!(
+            "{field} must be a positive integer"
+        )));
+    }
+    Ok(parsed)
+}
+
+pub(super) fn parse_llm_models(models: Vec<String>) -> Result<Vec<String>, ApiError> {
+    let nonblank_count = models
+        .iter()
+        .filter(|model| !model.trim().is_empty())
+        .count();
+    let normalized = admin_runtime::normalize_llm_models(models);
+    if normalized.is_empty() {
+        return Err(ApiError::bad_request(
+            "llm_models must contain at least one non-empty model".to_owned(),
+        ));
+    }
+    if normalized.len() != nonblank_count {
+        return Err(ApiError::bad_request(
+            "llm_models must not contain blank or duplicate models".to_owned(),
+        ));
+    }
+    Ok(normalized)
+}
+
+pub(super) fn deserialize_optional_nullable_i64<'de, D>(
+    deserializer: D,
+) -> Result<Option<Option<i64>>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    Ok(Some(Option::<i64>::deserialize(deserializer)?))
+}
+
+pub(super) struct AdminLlmCallListScope<'a> {
+    pub(super) status: Option<&'a str>,
+    pub(super) source: &'a str,
+    pub(super) requested_by: Option<&'a str>,
+    pub(super) parent_task_id: &'a str,
+    pub(super) request_id: &'a str,
+    pub(super) started_from: Option<&'a str>,
+    pub(super) started_to: Option<&'a str>,
+}
+
+pub(super) struct AdminLlmCallIdScope<'a> {
+    pub(super) include_ids: Option<&'a [String]>,
+    pub(super) exclude_ids: Option<&'a [String]>,
+}
+
+pub(super) struct AdminLlmCallPage {
+    pub(super) limit: Option<i64>,
+    pub(super) offset: Option<i64>,
+}
+
+pub(super) fn push_llm_call_filters(
+    query: &mut sqlx::QueryBuilder<sqlx::Sqlite>,
+    scope: &AdminLlmCallListScope<'_>,
+    ids: &AdminLlmCallIdScope<'_>,
+) {
+    query.push(" WHERE 1 = 1");
+
+    if let Some(status) = scope.status.filter(|value| *value != "all") {
+        query.push(" AND status = ");
+        query.push_bind(status.to_owned());
+    }
+    if !scope.source.is_empty() {
+        query.push(" AND source = ");
+        query.push_bind(scope.source.to_owned());
+    }
+    if let Some(requested_by) = scope.requested_by {
+        query.push(" AND requested_by = ");
+        query.push_bind(requested_by.to_owned());
+    }
+    if !scope.parent_task_id.is_empty() {
+        query.push(" AND parent_task_id = ");
+        query.push_bind(scope.parent_task_id.to_owned());
+    }
+    if !scope.request_id.is_empty() {
+        query.push(" AND request_id = ");
+        query.push_bind(scope.request_id.to_owned());
+    }
+    if let Some(started_from) = scope.started_from {
+        query.push(" AND unixepoch(COALESCE(started_at, created_at)) >= unixepoch(");
+        query.push_bind(started_from.to_owned());
+        query.push(")");
+    }
+    if let Some(started_to) = scope.started_to {
+        query.push(" AND unixepoch(COALESCE(started_at, created_at)) <= unixepoch(");
+        query.push_bind(started_to.to_owned());
+        query.push(")");
+    }
+    if let Some(include_ids) = ids.include_ids {
+        if include_ids.is_empty() {
+            query.push(" AND 1 = 0");
+        } else {
+            query.push(" AND id IN (");
+            {
+                let mut separated = query.separated(", ");
+                for id in include_ids {
+                    separated.push_bind(id.clone());
+                }
+            }
+            query.push(")");
+        }
+    }
+    if let Some(exclude_ids) = ids.exclude_ids.filter(|ids| !ids.is_empty()) {
+        query.push(" AND id NOT IN (");
+        {
+            let mut separated = query.separated(", ");
+            for id in exclude_ids {
+                separated.push_bind(id.clone());
+            }
+        }
+        query.push(")");
+    }
+}
+
+pub(super) const LLM_CALL_ORDER_BY_STATUS_GROUPED: &str = r#"
+                ORDER BY
+                  CASE
+                    WHEN status = 'running' THEN 0
+                    WHEN status = 'queued' THEN 1
+                    ELSE 2
+                  END,
+                  julianday(created_at) DESC,
+                  created_at DESC,
+                  id DESC
+                "#;
+pub(super) const LLM_CALL_ORDER_BY_CREATED_DESC: &str =
+    " ORDER BY julianday(created_at) DESC, created_at DESC, id DESC";
+
+pub(super) fn llm_call_order_by_clause(
+    scope: &AdminLlmCallListScope<'_>,
+    sort: &str,
+) -> &'static str {
+    match sort {
+        "status_grouped" if scope.status.unwrap_or("all") == "all" => {
+            LLM_CALL_ORDER_BY_STATUS_GROUPED
+        }
+        _ => LLM_CALL_ORDER_BY_CREATED_DESC,
+    }
+}
+
+pub(super) fn push_llm_call_order_by(
+    query: &mut sqlx::QueryBuilder<sqlx::Sqlite>,
+    scope: &AdminLlmCallListScope<'_>,
+    sort: &str,
+) {
+    query.push(llm_call_order_by_clause(scope, sort));
+}
+
+pub(super) async fn count_admin_llm_calls(
+    pool: &sqlx::SqlitePool,
+    scope: &AdminLlmCallListScope<'_>,
+    ids: &AdminLlmCallIdScope<'_>,
+) -> Result<i64, ApiError> {
+    let mut query = sqlx::QueryBuilder::<sqlx::Sqlite>::new(
+        r#"
+        SELECT COUNT(*)
+        FROM llm_calls
+        "#,
+    );
+    push_llm_call_filters(&mut query, scope, ids);
+    query
+        .build_query_scalar::<i64>()
+        .fetch_one(pool)
+        .await
+        .map_err(ApiError::internal)
+}
+
+pub(super) const ADMIN_LLM_CALL_ITEM_SELECT: &str = r#"
+        SELECT
+          id,
+          status,
+          source,
+          model,
+          requested_by,
+          parent_task_id,
+          parent_task_type,
+          request_id,
+          max_tokens,
+          attempt_count,
+          scheduler_wait_ms,
+          first_token_wait_ms,
+          duration_ms,
+          input_tokens,
+          output_tokens,
+          cached_input_tokens,
+          total_tokens,
+          created_at,
+          started_at,
+          finished_at,
+          updated_at
+        FROM llm_calls
+        "#;
+
+pub(super) async fn load_admin_llm_call_items(
+    pool: &sqlx::SqlitePool,
+    scope: &AdminLlmCallListScope<'_>,
+    sort: &str,
+    ids: &AdminLlmCallIdScope<'_>,
+    page: AdminLlmCallPage,
+) -> Result<Vec<AdminLlmCallItem>, ApiError> {
+    let mut query = sqlx::QueryBuilder::<sqlx::Sqlite>::new(ADMIN_LLM_CALL_ITEM_SELECT);
+    push_llm_call_filters(&mut query, scope, ids);
+    push_llm_call_order_by(&mut query, scope, sort);
+    if let Some(limit) = page.limit {
+        query.push(" LIMIT ");
+        query.push_bind(limit);
+    }
+    if let Some(offset) = page.offset {
+        query.push(" OFFSET ");
+        query.push_bind(offset);
+    }
+    query
+        .build_query_as::<AdminLlmCallItem>()
+        .fetch_all(pool)
+        .await
+        .map_err(ApiError::internal)
+}
+
+pub async fn admin_list_llm_calls(
+    State(state): State<Arc<AppState>>,
+    _: AdminUser,
+    Query(query): Query<AdminLlmCallsQuery>,
+) -> Result<Json<AdminLlmCallsResponse>, ApiError> {
+    let page = query.page.unwrap_or(1).max(1);
+    let page_size = query.page_size.unwrap_or(20).clamp(1, 100);
+    let offset = admin_users_offset(page, page_size)?;
+    let status = parse_llm_calls_status_filter(query.status)?;
+
+    let source = query.source.unwrap_or_default().trim().to_owned();
+    let requested_by = query.requested_by.clone();
+    let parent_task_id = query.parent_task_id.unwrap_or_default().trim().to_owned();
+    let request_id = query.request_id.unwrap_or_default().trim().to_owned();
+    let started_from = parse_llm_calls_filter_timestamp(query.started_from, "started_from")?;
+    let started_to = parse_llm_calls_filter_timestamp(query.started_to, "started_to")?;
+    let sort = query.sort.unwrap_or_else(|| "created_desc".to_owned());
+    if !matches!(sort.as_str(), "created_desc" | "status_grouped") {
+        return Err(ApiError::bad_request("invalid sort filter"));
+    }
+
+    let base_scope = AdminLlmCallListScope {
+        status: Some(status.as_str()),
+        source: source.as_str(),
+        requested_by: requested_by.as_deref(),
+        parent_task_id: parent_task_id.as_str(),
+        request_id: request_id.as_str(),
+        started_from: started_from.as_deref(),
+        started_to: started_to.as_deref(),
+    };
+    let override_scope = AdminLlmCallListScope {
+        status: None,
+        source: source.as_str(),
+        requested_by: requested_by.as_deref(),
+        parent_task_id: parent_task_id.as_str(),
+        request_id: request_id.as_str(),
+        started_from: None,
+        started_to: None,
+    };
+    let no_ids = AdminLlmCallIdScope {
+        include_ids: None,
+        exclude_ids: None,
+    };
+
+    let overrides = state.llm_scheduler.admin_overrides().await;
+    if overrides.is_empty() {
+        let total = count_admin_llm_calls(&state.pool, &base_scope, &no_ids).await?;
+        let items = load_admin_llm_call_items(
+            &state.pool,
+            &base_scope,
+            sort.as_str(),
+            &no_ids,
+            AdminLlmCallPage {
+                limit: Some(page_size),
+                offset: Some(offset),
+            },
+        )
+        .await?;
+
+        return Ok(Json(AdminLlmCallsResponse {
+            items,
+            page,
+            page_size,
+            total,
+        }));
+    }
+
+    let override_ids = overrides.keys().cloned().collect::<Vec<_>>();
+    let include_override_ids = AdminLlmCallIdScope {
+        include_ids: Some(&override_ids),
+        exclude_ids: None,
+    };
+    let exclude_override_ids = AdminLlmCallIdScope {
+        include_ids: None,
+        exclude_ids: Some(&override_ids),
+    };
+    let mut override_items = load_admin_llm_call_items(
+        &state.pool,
+        &override_scope,
+        sort.as_str(),
+        &include_override_ids,
+        AdminLlmCallPage {
+            limit: None,
+            offset: None,
+        },
+    )
+    .await?;
+    apply_llm_call_admin_overrides(&mut override_items, &overrides);
+    override_items.retain(|item| {
+        llm_call_matches_status_filter(item, status.as_str())
+            && llm_call_matches_started_filter(item, started_from.as_deref(), started_to.as_deref())
+    });
+    sort_admin_llm_calls(&mut override_items, sort.as_str());
+
+    let base_total = count_admin_llm_calls(&state.pool, &base_scope, &exclude_override_ids).await?;
+    let total = base_total.saturating_add(i64::try_from(override_items.len()).unwrap_or(i64::MAX));
+
+    let fetch_limit = offset.saturating_add(page_size);
+    let mut items = load_admin_llm_call_items(
+        &state.pool,
+        &base_scope,
+        sort.as_str(),
+        &exclude_override_ids,
+        AdminLlmCallPage {
+            limit: Some(fetch_limit),
+            offset: Some(0),
+        },
+    )
+    .await?;
+    items.extend(override_items);
+    sort_admin_llm_calls(&mut items, sort.as_str());
+
+    let start = usize::try_from(offset).unwrap_or(usize::MAX);
+    let size = usize::try_from(page_size).unwrap_or(100);
+    let items = items.into_iter().skip(start).take(size).collect::<Vec<_>>();
+
+    Ok(Json(AdminLlmCallsResponse {
+        items,
+        page,
+        page_size,
+        total,
+    }))
+}
+
+pub async fn admin_get_llm_call_detail(
+    State(state): State<Arc<AppState>>,
+    _: AdminUser,
+    Path(call_id): Path<String>,
+) -> Result<Json<AdminLlmCallDetailItem>, ApiError> {
+    let call_id = parse_local_id_param(call_id, "call_id")?;
+
+    let mut item = sqlx::query_as::<_, AdminLlmCallDetailItem>(
+        r#"
+        SELECT
+          id,
+          status,
+          source,
+          model,
+          requested_by,
+          parent_task_id,
+          parent_task_type,
+          request_id,
+          max_tokens,
+          attempt_count,
+          scheduler_wait_ms,
+          first_token_wait_ms,
+          duration_ms,
+          input_tokens,
+          output_tokens,
+          cached_input_tokens,
+          total_tokens,
+          input_messages_json,
+          output_messages_json,
+          prompt_text,
+          response_text,
+          error_text,
+          created_at,
+          started_at,
+          finished_at,
+          updated_at
+        FROM llm_calls
+        WHERE id = ?
+        LIMIT 1
+        "#,
+    )
+    .bind(call_id.as_str())
+    .fetch_optional(&state.pool)
+    .await
+    .map_err(ApiError::internal)?
+    .ok_or_else(|| {
+        ApiError::new(
+            StatusCode::NOT_FOUND,
+            codes::NOT_FOUND,
+            "llm call not found",
+        )
+    })?;
+
+    if let Some(snapshot) = state.llm_scheduler.admin_overrides().await.get(&item.id) {
+        apply_llm_call_detail_admin_override(&mut item, snapshot);
+    }
+
+    Ok(Json(item))
+}
+
+pub async fn admin_get_llm_usage(
+    State(state): State<Arc<AppState>>,
+    Query(query): Query<AdminLlmUsageQuery>,
+    _: AdminUser,
+) -> Result<Json<AdminLlmUsageResponse>, ApiError> {
+    let date = match query
+        .date
+        .as_deref()
+        .map(str::trim)
+        .filter(|v| !v.is_empty())
+    {
+        Some(raw) => chrono::NaiveDate::parse_from_str(raw, "%Y-%m-%d")
+            .map_err(|_| ApiError::bad_request("invalid date, expected YYYY-MM-DD"))?,
+        None => chrono::Utc::now().date_naive(),
+    };
+    let next_date = date.succ_opt().unwrap_or(date);
+
+    let items = sqlx::query_as::<_, AdminLlmUsageItem>(
+        r#"
+        SELECT
+          CAST(c.requested_by AS TEXT) AS user_id,
+          u.login AS login,
+          COUNT(*) AS call_count,
+          COALESCE(SUM(c.input_tokens), 0) AS input_tokens,
+          COALESCE(SUM(c.output_tokens), 0) AS output_tokens,
+          COALESCE(SUM(c.total_tokens), 0) AS total_tokens
+        FROM llm_calls c
+        LEFT JOIN users u ON u.id = c.requested_by
+        WHERE c.requested_by IS NOT NULL
+          AND c.created_at >= ?
+          AND c.created_at < ?
+        GROUP BY c.requested_by
+        ORDER BY total_tokens DESC, user_id ASC
+        "#,
+    )
+    .bind(date.format("%Y-%m-%d").to_string())
+    .bind(next_date.format("%Y-%m-%d").to_string())
+    .fetch_all(&state.pool)
+    .await
+    .map_err(ApiError::internal)?;
+
+    Ok(Json(AdminLlmUsageResponse {
+        date: date.format("%Y-%m-%d").to_string(),
+        daily_token_budget_per_user: state.config.ai_daily_token_budget_per_user,
+        items,
+    }))
+}
+
+pub(super) const ADMIN_LLM_CALLS_CSV_HEADER: &str = "id,status,source,model,requested_by,parent_task_id,parent_task_type,max_tokens,attempt_count,scheduler_wait_ms,first_token_wait_ms,duration_ms,input_tokens,output_tokens,cached_input_tokens,total_tokens,created_at,started_at,finished_at\n";
+
+pub(super) fn csv_field(value: &str) -> std::borrow::Cow<'_, str> {
+    if value.contains([',', '"', '\n', '\r']) {
+        std::borrow::Cow::Owned(format!("\"{}\"", value.replace('"', "\"\"")))
+    } else {
+        std::borrow::Cow::Borrowed(value)
+    }
+}
+
+pub(super) fn admin_llm_call_csv_row(item: &AdminLlmCallItem) -> String {
+    fn opt<T: ToString>(value: Option<T>) -> String {
+        value.map(|value| value.to_string()).unwrap_or_default()
+    }
+    let fields = [
+        item.id.clone(),
+        item.status.clone(),
+        item.source.clone(),
+        item.model.clone(),
+        opt(item.requested_by.as_deref()),
+        opt(item.parent_task_id.as_deref()),
+        opt(item.parent_task_type.as_deref()),
+        item.max_tokens.to_string(),
+        item.attempt_count.to_string(),
+        item.scheduler_wait_ms.to_string(),
+        opt(item.first_token_wait_ms),
+        opt(item.duration_ms),
+        opt(item.input_tokens),
+        opt(item.output_tokens),
+        opt(item.cached_input_tokens),
+        opt(item.total_tokens),
+        item.created_at.clone(),
+        opt(item.started_at.as_deref()),
+        opt(item.finished_at.as_deref()),
+    ];
+    let mut row = fields
+        .iter()
+        .map(|field| csv_field(field))
+        .collect::<Vec<_>>()
+        .join(",");
+    row.push('\n');
+    row
+}
+
+/// Streams every llm_calls row matching the list filters as CSV, one row at
+/// a time off the query cursor. Rows carry persisted state only; the live
+/// scheduler overrides the list applies to running calls are not merged in.
+pub async fn admin_export_llm_calls(
+    State(state): State<Arc<AppState>>,
+    _: AdminUser,
+    Query(query): Query<AdminLlmCallsExportQuery>,
+) -> Result<Response, ApiError> {
+    match query.format.as_deref().map(str::trim) {
+        None | Some("") | Some("csv") => {}
+        Some(_) => return Err(ApiError::bad_request("format must be csv")),
+    }
+    let status = parse_llm_calls_status_filter(query.status)?;
+    let source = query.source.unwrap_or_default().trim().to_owned();
+    let requested_by = query.requested_by;
+    let parent_task_id = query.parent_task_id.unwrap_or_default().trim().to_owned();
+    let request_id = query.request_id.unwrap_or_default().trim().to_owned();
+    let started_from = parse_llm_calls_filter_timestamp(query.started_from, "started_from")?;
+    let started_to = parse_llm_calls_filter_timestamp(query.started_to, "started_to")?;
+
+    let pool = state.pool.clone();
+    let stream = async_stream::stream! {
+        yield Ok::<Bytes, sqlx::Error>(Bytes::from_static(ADMIN_LLM_CALLS_CSV_HEADER.as_bytes()));
+        let scope = AdminLlmCallListScope {
+            status: Some(status.as_str()),
+            source: source.as_str(),
+            requested_by: requested_by.as_deref(),
+            parent_task_id: parent_task_id.as_str(),
+            request_id: request_id.as_str(),
+            started_from: started_from.as_deref(),
+            started_to: started_to.as_deref(),
+        };
+        let no_ids = AdminLlmCallIdScope {
+            include_ids: None,
+            exclude_ids: None,
+        };
+        let mut query = sqlx::QueryBuilder::<sqlx::Sqlite>::new(ADMIN_LLM_CALL_ITEM_SELECT);
+        push_llm_call_filters(&mut query, &scope, &no_ids);
+        push_llm_call_order_by(&mut query, &scope, "created_desc");
+        let mut rows = query.build_query_as::<AdminLlmCallItem>().fetch(&pool);
+        while let Some(row) = rows.next().await {
+            match row {
+                Ok(item) => yield Ok(Bytes::from(admin_llm_call_csv_row(&item))),
+                Err(err) => {
+                    yield Err(err);
+                    return;
+                }
+            }
+        }
+    };
+
+    let filename = format!(
+        "llm-calls-{}.csv",
+        chrono::Utc::now().format("%Y%m%dT%H%M%SZ")
+    );
+    let mut response = Response::new(Body::from_stream(stream));
+    response.headers_mut().insert(
+        header::CONTENT_TYPE,
+        HeaderValue::from_static("text/csv; charset=utf-8"),
+    );
+    response.headers_mut().insert(
+        header::CONTENT_DISPOSITION,
+        HeaderValue::from_str(&format!(r#"attachment; filename="{filename}""#))
+            .map_err(ApiError::internal)?,
+    );
+    Ok(response)
+}
+
+pub(super) fn admin_llm_usage_summary_buckets(
+    rows: Vec<AdminLlmUsageSummaryRow>,
+) -> Vec<AdminLlmUsageSummaryBucket> {
+    let mut buckets: Vec<AdminLlmUsageSummaryBucket> = Vec::new();
+    for row in rows {
+        if buckets
+            .last()
+            .is_none_or(|bucket| bucket.bucket_start != row.bucket_start)
+        {
+            buckets.push(AdminLlmUsageSummaryBucket {
+                bucket_start: row.bucket_start.clone(),
+                totals: AdminLlmUsageStats::default(),
+                sources: Vec::new(),
+            });
+        }
+        let bucket = buckets.last_mut().expect("bucket just pushed");
+        bucket.totals.add(&row);
+        let mut stats = AdminLlmUsageStats::default();
+        stats.add(&row);
+        bucket.sources.push(AdminLlmUsageSourceStats {
+            source: row.source,
+            stats,
+        });
+    }
+    buckets
+}
+
+/// Token, call, failure and latency totals per UTC day or hour, split by
+/// source. Calls are placed by `COALESCE(started_at, created_at)`, the same
+/// timestamp the call list filters on. Defaults to the last 30 days (day) or
+/// 48 hours (hour).
+pub async fn admin_get_llm_usage_summary(
+    State(state): State<Arc<AppState>>,
+    _: AdminUser,
+    Query(query): Query<AdminLlmUsageSummaryQuery>,
+) -> Result<Json<AdminLlmUsageSummaryResponse>, ApiError> {
+    let (bucket, bucket_format, default_span) = match query.bucket.as_deref().map(str::trim) {
+        None | Some("") | Some("day") => ("day", "%Y-%m-%dT00:00:00Z", chrono::Duration::days(30)),
+        Some("hour") => ("hour", "%Y-%m-%dT%H:00:00Z", chrono::Duration::hours(48)),
+        Some(_) => return Err(ApiError::bad_request("bucket must be day or hour")),
+    };
+    let started_to = parse_llm_calls_filter_timestamp(query.started_to, "started_to")?;
+    let started_from = match parse_llm_calls_filter_timestamp(query.started_from, "started_from")? {
+        Some(value) => value,
+        None => {
+            let end = started_to
+                .as_deref()
+                .and_then(|value| chrono::DateTime::parse_from_rfc3339(value).ok())
+                .map(|value| value.with_timezone(&chrono::Utc))
+                .unwrap_or_else(chrono::Utc::now);
+            (end - default_span).to_rfc3339()
+        }
+    };
+
+    let rows = sqlx::query_as::<_, AdminLlmUsageSummaryRow>(
+        r#"
+        SELECT
+          strftime(?, COALESCE(started_at, created_at)) AS bucket_start,
+          source,
+          COUNT(*) AS call_count,
+          COALESCE(SUM(CASE WHEN status = 'succeeded' THEN 1 ELSE 0 END), 0) AS succeeded_count,
+          COALESCE(SUM(CASE WHEN status = 'failed' THEN 1 ELSE 0 END), 0) AS failed_count,
+          COALESCE(SUM(input_tokens), 0) AS input_tokens,
+          COALESCE(SUM(output_tokens), 0) AS output_tokens,
+          COALESCE(SUM(total_tokens), 0) AS total_tokens,
+          COALESCE(SUM(duration_ms), 0) AS duration_ms_sum,
+          COUNT(duration_ms) AS duration_count
+        FROM llm_calls
+        WHERE unixepoch(COALESCE(started_at, created_at)) >= unixepoch(?)
+          AND (? IS NULL OR unixepoch(COALESCE(started_at, created_at)) <= unixepoch(?))
+        GROUP BY bucket_start, source
+        ORDER BY bucket_start ASC, source ASC
+        "#,
+    )
+    .bind(bucket_format)
+    .bind(started_from.as_str())
+    .bind(started_to.as_deref())
+    .bind(started_to.as_deref())
+    .fetch_all(&state.pool)
+    .await
+    .map_err(ApiError::internal)?;
+
+    Ok(Json(AdminLlmUsageSummaryResponse {
+        bucket,
+        started_from,
+        started_to,
+        buckets: admin_llm_usage_summary_buckets(rows),
+    }))
+}
+
+const ADMIN_BRIEF_FEEDBACK_RECENT_NOTES: i64 = 50;
+
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct AdminBriefFeedbackQuery {
+    /// RFC3339; defaults to 30 days ago. Feedback is placed by its last
+    /// change.
+    pub(super) since: Option<String>,
+}
+
+#[derive(Debug, Default, PartialEq, Serialize, JsonSchema)]
+pub struct AdminBriefFeedbackDistribution {
+    pub(super) total: i64,
+    pub(super) up: i64,
+    pub(super) down: i64,
+    /// Share of `up` among all ratings; null when there are none.
+    pub(super) up_ratio: Option<f64>,
+    pub(super) with_note: i64,
+}
+
+impl AdminBriefFeedbackDistribution {
+    pub(super) fn from_counts(up: i64, down: i64, with_note: i64) -> Self {
+        let total = up + down;
+        Self {
+            total,
+            up,
+            down,
+            up_ratio: (total > 0).then(|| up as f64 / total as f64),
+            with_note,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, JsonSchema, sqlx::FromRow)]
+pub struct AdminBriefFeedbackNote {
+    /// Stable per user but not reversible to the user id.
+    pub(super) user_ref: String,
+    pub(super) brief_date: String,
+    pub(super) rating: String,
+    pub(super) note: String,
+    pub(super) updated_at: String,
+}
+
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct AdminBriefFeedbackResponse {
+    pub(super) since: String,
+    pub(super) distribution: AdminBriefFeedbackDistribution,
+    /// Newest first, at most 50.
+    pub(super) recent_notes: Vec<AdminBriefFeedbackNote>,
+}
+
+pub(super) fn brief_feedback_user_ref(user_id: &str) -> String {
+    ai::sha256_hex(&format!("brief-feedback-user:{user_id}"))[..12].to_owned()
+}
+
+/// Thumbs up/down counts across all users' briefs since `since`, plus the
+/// latest notes with user ids replaced by an opaque reference.
+pub async fn admin_get_brief_feedback(
+    State(state): State<Arc<AppState>>,
+    Query(query): Query<AdminBriefFeedbackQuery>,
+    AdminUser(_acting_user_id): AdminUser,
+) -> Result<Json<AdminBriefFeedbackResponse>, ApiError> {
+    let since = match parse_llm_calls_filter_timestamp(query.since, "since")? {
+        Some(value) => value,
+        None => (chrono::Utc::now() - chrono::Duration::days(30)).to_rfc3339(),
+    };
+
+    let (up, down, with_note) = sqlx::query_as::<_, (i64, i64, i64)>(
+        r#"
+        SELECT
+          COALESCE(SUM(CASE WHEN rating = 'up' THEN 1 ELSE 0 END), 0),
+          COALESCE(SUM(CASE WHEN rating = 'down' THEN 1 ELSE 0 END), 0),
+          COUNT(note)
+        FROM brief_feedback
+        WHERE unixepoch(updated_at) >= unixepoch(?)
+        "#,
+    )
+    .bind(since.as_str())
+    .fetch_one(&state.pool)
+    .await
+    .map_err(ApiError::internal)?;
+
+    #[derive(sqlx::FromRow)]
+    struct NoteRow {
+        user_id: String,
+        brief_date: String,
+        rating: String,
+        note: String,
+        updated_at: String,
+    }
+    let recent_notes = sqlx::query_as::<_, NoteRow>(
+        r#"
+        SELECT f.user_id, b.date AS brief_date, f.rating, f.note, f.updated_at
+        FROM brief_feedback f
+        JOIN briefs b ON b.id = f.brief_id
+        WHERE f.note IS NOT NULL
+          AND unixepoch(f.updated_at) >= unixepoch(?)
+        ORDER BY unixepoch(f.updated_at) DESC, f.brief_id DESC
+        LIMIT ?
+        "#,
+    )
+    .bind(since.as_str())
+    .bind(ADMIN_BRIEF_FEEDBACK_RECENT_NOTES)
+    .fetch_all(&state.pool)
+    .await
+    .map_err(ApiError::internal)?
+    .into_iter()
+    .map(|row| AdminBriefFeedbackNote {
+        user_ref: brief_feedback_user_ref(&row.user_id),
+        brief_date: row.brief_date,
+        rating: row.rating,
+        note: row.note,
+        updated_at: row.updated_at,
+    })
+    .collect();
+
+    Ok(Json(AdminBriefFeedbackResponse {
+        since,
+        distribution: AdminBriefFeedbackDistribution::from_counts(up, down, with_note),
+        recent_notes,
+    }))
+}
@@ -0,0 +1,3945 @@
+use super::*;
+
+#[derive(Debug, Serialize)]
+pub struct StarredRepoItem {
+    pub(super) repo_id: i64,
+    pub(super) full_name: String,
+    pub(super) description: Option<String>,
+    pub(super) html_url: String,
+    pub(super) stargazed_at: Option<String>,
+    pub(super) is_private: i64,
+    pub(super) is_muted: bool,
+    /// `false` once the viewer opted this repo out of release translation.
+    pub(super) translation_enabled: bool,
+    /// Null until the next starred sync after the column was added.
+    pub(super) language: Option<String>,
+    pub(super) topics: Vec<String>,
+    pub(super) is_archived: Option<bool>,
+    /// The repo's last release sync failed for this user and is listed in
+    /// `/api/sync/issues`.
+    pub(super) has_sync_error: bool,
+}
+
+#[derive(Debug, sqlx::FromRow)]
+pub(super) struct StarredRepoRow {
+    pub(super) repo_id: i64,
+    pub(super) full_name: String,
+    pub(super) description: Option<String>,
+    pub(super) html_url: String,
+    pub(super) stargazed_at: Option<String>,
+    pub(super) is_private: i64,
+    pub(super) is_muted: bool,
+    pub(super) translation_enabled: bool,
+    pub(super) language: Option<String>,
+    pub(super) topics_json: Option<String>,
+    pub(super) is_archived: Option<i64>,
+    pub(super) has_sync_error: bool,
+}
+
+impl From<StarredRepoRow> for StarredRepoItem {
+    fn from(row: StarredRepoRow) -> Self {
+        Self {
+            repo_id: row.repo_id,
+            full_name: row.full_name,
+            description: row.description,
+            html_url: row.html_url,
+            stargazed_at: row.stargazed_at,
+            is_private: row.is_private,
+            is_muted: row.is_muted,
+            translation_enabled: row.translation_enabled,
+            language: row.language,
+            topics: row
+                .topics_json
+                .as_deref()
+                .and_then(|raw| serde_json::from_str(raw).ok())
+                .unwrap_or_default(),
+            is_archived: row.is_archived.map(|value| value != 0),
+            has_sync_error: row.has_sync_error,
+        }
+    }
+}
+
+pub(super) const STARRED_LEGACY_LIMIT: i64 = 2000;
+pub(super) const STARRED_PAGE_SIZE_DEFAULT: i64 = 50;
+pub(super) const STARRED_PAGE_SIZE_MAX: i64 = 200;
+
+#[derive(Debug, Default, Deserialize, JsonSchema)]
+pub struct StarredQuery {
+    pub(super) group_by: Option<String>,
+    pub(super) page: Option<i64>,
+    pub(super) page_size: Option<i64>,
+    /// Case-insensitive substring match on full_name/description.
+    pub(super) query: Option<String>,
+    /// `public`, `private` or `all` (default).
+    pub(super) visibility: Option<String>,
+}
+
+/// Flat list by default; `group_by=language` wraps the same items in groups,
+/// and `page`/`page_size` switch to the paginated shape.
+#[derive(Debug, Serialize)]
+#[serde(untagged)]
+pub enum StarredListResponse {
+    Flat(Vec<StarredRepoItem>),
+    Grouped {
+        groups: Vec<StarredRepoGroup>,
+    },
+    Paged {
+        items: Vec<StarredRepoItem>,
+        page: i64,
+        page_size: i64,
+        total: i64,
+    },
+}
+
+#[derive(Debug, Serialize)]
+pub struct StarredRepoGroup {
+    pub(super) language: Option<String>,
+    pub(super) count: usize,
+    pub(super) repos: Vec<StarredRepoItem>,
+}
+
+/// Largest language first; repos without a known language go last.
+pub(super) fn group_starred_repos_by_language(
+    repos: Vec<StarredRepoItem>,
+) -> Vec<StarredRepoGroup> {
+    let mut groups = BTreeMap::<Option<String>, Vec<StarredRepoItem>>::new();
+    for repo in repos {
+        groups.entry(repo.language.clone()).or_default().push(repo);
+    }
+    let mut groups = groups
+        .into_iter()
+        .map(|(language, repos)| StarredRepoGroup {
+            language,
+            count: repos.len(),
+            repos,
+        })
+        .collect::<Vec<_>>();
+    groups.sort_by(|left, right| {
+        left.language
+            .is_none()
+            .cmp(&right.language.is_none())
+            .then_with(|| right.count.cmp(&left.count))
+            .then_with(|| left.language.cmp(&right.language))
+    });
+    groups
+}
+
+/// Lists the viewer's starred repos, newest star first.
+///
+/// Without `page`/`page_size` this keeps the legacy response: a flat array
+/// (or language groups) capped at 2000 rows. Passing either switches to
+/// `{ items, page, page_size, total }` with `page_size` clamped to 1..=200.
+/// `query` and `visibility` filter both shapes.
+pub async fn list_starred(
+    State(state): State<Arc<AppState>>,
+    AuthedUser(user_id): AuthedUser,
+    Query(q): Query<StarredQuery>,
+) -> Result<Json<StarredListResponse>, ApiError> {
+    let group_by_language = match q.group_by.as_deref().map(str::trim) {
+        None | Some("") => false,
+        Some("language") => true,
+        Some(_) => return Err(ApiError::bad_request("group_by must be language")),
+    };
+    let visibility = match q.visibility.as_deref().map(str::trim) {
+        None | Some("") | Some("all") => "all",
+        Some("public") => "public",
+        Some("private") => "private",
+        Some(_) => {
+            return Err(ApiError::bad_request(
+                "visibility must be public, private or all",
+            ));
+        }
+    };
+    let query_text = q.query.unwrap_or_default().trim().to_lowercase();
+    let query_like = format!("%{query_text}%");
+
+    let paged = q.page.is_some() || q.page_size.is_some();
+    if paged && group_by_language {
+        return Err(ApiError::bad_request(
+            "group_by cannot be combined with page/page_size",
+        ));
+    }
+    let page = q.page.unwrap_or(1);
+    if page < 1 {
+        return Err(ApiError::bad_request("page must be >= 1"));
+    }
+    let (page_size, offset) = if paged {
+        let page_size = q
+            .page_size
+            .unwrap_or(STARRED_PAGE_SIZE_DEFAULT)
+            .clamp(1, STARRED_PAGE_SIZE_MAX);
+        (page_size, admin_users_offset(page, page_size)?)
+    } else {
+        (STARRED_LEGACY_LIMIT, 0)
+    };
+
+    let rows = sqlx::query_as::<_, StarredRepoRow>(
+        r#"
+        SELECT
+          sr.repo_id,
+          sr.full_name,
+          sr.description,
+          sr.html_url,
+          sr.stargazed_at,
+          sr.is_private,
+          sr.language,
+          sr.topics_json,
+          sr.is_archived,
+          EXISTS (
+            SELECT 1
+            FROM muted_repos m
+            WHERE m.user_id = sr.user_id
+              AND m.repo_id = sr.repo_id
+          ) AS is_muted,
+          NOT EXISTS (
+            SELECT 1
+            FROM repo_translation_opt_outs t
+            WHERE t.user_id = sr.user_id
+              AND t.repo_id = sr.repo_id
+          ) AS translation_enabled,
+          EXISTS (
+            SELECT 1
+            FROM repo_sync_errors se
+            WHERE se.user_id = sr.user_id
+              AND se.repo_id = sr.repo_id
+              AND se.resolved_at IS NULL
+          ) AS has_sync_error
+        FROM starred_repos sr
+        WHERE sr.user_id = ?
+          AND (? = '' OR lower(sr.full_name) LIKE ? OR lower(COALESCE(sr.description, '')) LIKE ?)
+          AND (? = 'all' OR (? = 'public' AND sr.is_private = 0) OR (? = 'private' AND sr.is_private != 0))
+        ORDER BY sr.stargazed_at DESC, sr.repo_id DESC
+        LIMIT ? OFFSET ?
+        "#,
+    )
+    .bind(&user_id)
+    .bind(query_text.as_str())
+    .bind(query_like.as_str())
+    .bind(query_like.as_str())
+    .bind(visibility)
+    .bind(visibility)
+    .bind(visibility)
+    .bind(page_size)
+    .bind(offset)
+    .fetch_all(&state.pool)
+    .await
+    .map_err(ApiError::internal)?;
+    let repos = rows.into_iter().map(StarredRepoItem::from).collect();
+
+    if !paged {
+        return Ok(Json(if group_by_language {
+            StarredListResponse::Grouped {
+                groups: group_starred_repos_by_language(repos),
+            }
+        } else {
+            StarredListResponse::Flat(repos)
+        }));
+    }
+
+    let total = sqlx::query_scalar::<_, i64>(
+        r#"
+        SELECT COUNT(*)
+        FROM starred_repos sr
+        WHERE sr.user_id = ?
+          AND (? = '' OR lower(sr.full_name) LIKE ? OR lower(COALESCE(sr.description, '')) LIKE ?)
+          AND (? = 'all' OR (? = 'public' AND sr.is_private = 0) OR (? = 'private' AND sr.is_private != 0))
+        "#,
+    )
+    .bind(&user_id)
+    .bind(query_text.as_str())
+    .bind(query_like.as_str())
+    .bind(query_like.as_str())
+    .bind(visibility)
+    .bind(visibility)
+    .bind(visibility)
+    .fetch_one(&state.pool)
+    .await
+    .map_err(ApiError::internal)?;
+
+    Ok(Json(StarredListResponse::Paged {
+        items: repos,
+        page,
+        page_size,
+        total,
+    }))
+}
+
+#[derive(Debug, Serialize)]
+pub struct RepoMuteResponse {
+    pub(super) repo_id: i64,
+    pub(super) is_muted: bool,
+}
+
+pub(super) fn parse_repo_id_param(raw: &str) -> Result<i64, ApiError> {
+    let repo_id_raw = raw.trim();
+    if repo_id_raw.is_empty() {
+        return Err(ApiError::bad_request("repo_id is required"));
+    }
+    repo_id_raw
+        .parse::<i64>()
+        .map_err(|_| ApiError::bad_request("repo_id must be an integer string"))
+}
+
+pub async fn mute_repo(
+    State(state): State<Arc<AppState>>,
+    AuthedUser(user_id): AuthedUser,
+    Path(repo_id): Path<String>,
+) -> Result<Json<RepoMuteResponse>, ApiError> {
+    let repo_id = parse_repo_id_param(&repo_id)?;
+
+    let visible = sqlx::query_scalar::<_, i64>(
+        r#"
+        SELECT COUNT(*)
+        FROM user_release_visible_repos
+        WHERE user_id = ?
+          AND repo_id = ?
+        "#,
+    )
+    .bind(user_id.as_str())
+    .bind(repo_id)
+    .fetch_one(&state.pool)
+    .await
+    .map_err(ApiError::internal)?;
+    if visible == 0 {
+        return Err(ApiError::new(
+            StatusCode::NOT_FOUND,
+            codes::REPO_NOT_FOUND,
+            "repository is not in your watched repositories",
+        ));
+    }
+
+    sqlx::query(
+        r#"
+        INSERT INTO muted_repos (user_id, repo_id, created_at)
+        VALUES (?, ?, ?)
+        ON CONFLICT(user_id, repo_id) DO NOTHING
+        "#,
+    )
+    .bind(user_id.as_str())
+    .bind(repo_id)
+    .bind(chrono::Utc::now().to_rfc3339())
+    .execute(&state.pool)
+    .await
+    .map_err(ApiError::internal)?;
+
+    Ok(Json(RepoMuteResponse {
+        repo_id,
+        is_muted: true,
+    }))
+}
+
+pub async fn unmute_repo(
+    State(state): State<Arc<AppState>>,
+    AuthedUser(user_id): AuthedUser,
+    Path(repo_id): Path<String>,
+) -> Result<Json<RepoMuteResponse>, ApiError> {
+    let repo_id = parse_repo_id_param(&repo_id)?;
+
+    sqlx::query(
+        r#"
+        DELETE FROM muted_repos
+        WHERE user_id = ?
+          AND repo_id = ?
+        "#,
+    )
+    .bind(user_id.as_str())
+    .bind(repo_id)
+    .execute(&state.pool)
+    .await
+    .map_err(ApiError::internal)?;
+
+    Ok(Json(RepoMuteResponse {
+        repo_id,
+        is_muted: false,
+    }))
+}
+
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct RepoPreferencesRequest {
+    /// `false` stops release translations for this repo; omitted leaves the
+    /// current value unchanged.
+    pub(super) translation_enabled: Option<bool>,
+}
+
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct RepoPreferencesResponse {
+    pub(super) repo_id: i64,
+    pub(super) translation_enabled: bool,
+}
+
+/// Updates the viewer's per-repo preferences.
+///
+/// Opting a repo out of translation makes its releases report `opted_out`
+/// from the feed and the batch endpoints instead of reaching the LLM.
+pub async fn patch_repo_preferences(
+    State(state): State<Arc<AppState>>,
+    Path(repo_id): Path<String>,
+    AuthedUser(user_id): AuthedUser,
+    ApiJson(req): ApiJson<RepoPreferencesRequest>,
+) -> Result<Json<RepoPreferencesResponse>, ApiError> {
+    let repo_id = parse_repo_id_param(&repo_id)?;
+
+    let visible = sqlx::query_scalar::<_, i64>(
+        r#"
+        SELECT COUNT(*)
+        FROM user_release_visible_repos
+        WHERE user_id = ?
+          AND repo_id = ?
+        "#,
+    )
+    .bind(user_id.as_str())
+    .bind(repo_id)
+    .fetch_one(&state.pool)
+    .await
+    .map_err(ApiError::internal)?;
+    if visible == 0 {
+        return Err(ApiError::new(
+            StatusCode::NOT_FOUND,
+            codes::REPO_NOT_FOUND,
+            "repository is not in your watched repositories",
+        ));
+    }
+
+    match req.translation_enabled {
+        Some(false) => {
+            sqlx::query(
+                r#"
+                INSERT INTO repo_translation_opt_outs (user_id, repo_id, created_at)
+                VALUES (?, ?, ?)
+                ON CONFLICT(user_id, repo_id) DO NOTHING
+                "#,
+            )
+            .bind(user_id.as_str())
+            .bind(repo_id)
+            .bind(chrono::Utc::now().to_rfc3339())
+            .execute(&state.pool)
+            .await
+            .map_err(ApiError::internal)?;
+        }
+        Some(true) => {
+            sqlx::query(
+                r#"
+                DELETE FROM repo_translation_opt_outs
+                WHERE user_id = ?
+                  AND repo_id = ?
+                "#,
+            )
+            .bind(user_id.as_str())
+            .bind(repo_id)
+            .execute(&state.pool)
+            .await
+            .map_err(ApiError::internal)?;
+        }
+        None => {}
+    }
+
+    let opted_out = repo_translation_opted_out(&state.pool, user_id.as_str(), repo_id)
+        .await
+        .map_err(ApiError::internal)?;
+    Ok(Json(RepoPreferencesResponse {
+        repo_id,
+        translation_enabled: !opted_out,
+    }))
+}
+
+pub(super) async fn repo_translation_opted_out(
+    pool: &sqlx::SqlitePool,
+    user_id: &str,
+    repo_id: i64,
+) -> Result<bool, sqlx::Error> {
+    sqlx::query_scalar::<_, bool>(
+        r#"
+        SELECT EXISTS (
+          SELECT 1
+          FROM repo_translation_opt_outs
+          WHERE user_id = ?
+            AND repo_id = ?
+        )
+        "#,
+    )
+    .bind(user_id)
+    .bind(repo_id)
+    .fetch_one(pool)
+    .await
+}
+
+#[derive(Debug, Deserialize)]
+pub struct DashboardUpdatesQuery {
+    pub(super) token: Option<String>,
+    pub(super) feed_type: Option<String>,
+    pub(super) include: Option<String>,
+    pub(super) scope: Option<String>,
+    pub(super) items: Option<String>,
+    pub(super) org: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct DashboardUpdatesResponse {
+    pub(super) token: String,
+    pub(super) generated_at: String,
+    pub(super) lists: DashboardUpdatesLists,
+}
+
+#[derive(Debug, Serialize)]
+pub struct DashboardUpdatesLists {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(super) feed: Option<DashboardListUpdate>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(super) briefs: Option<DashboardListUpdate>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(super) notifications: Option<DashboardListUpdate>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct DashboardListUpdate {
+    pub(super) changed: bool,
+    pub(super) new_count: usize,
+    pub(super) latest_keys: Vec<String>,
+}
+
+#[derive(Debug, Deserialize, Serialize, Default)]
+pub(super) struct DashboardUpdatesToken {
+    pub(super) feed: Vec<String>,
+    pub(super) briefs: Vec<String>,
+    pub(super) notifications: Vec<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(super) enum FeedScope {
+    Repo { owner: String, repo: String },
+    Repos { items: Vec<String> },
+    Org { org: String },
+    Mine,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub(super) struct DashboardUpdateInclude {
+    pub(super) feed: bool,
+    pub(super) briefs: bool,
+    pub(super) notifications: bool,
+}
+
+impl DashboardUpdateInclude {
+    pub(super) fn all() -> Self {
+        Self {
+            feed: true,
+            briefs: true,
+            notifications: true,
+        }
+    }
+}
+
+pub(super) fn parse_dashboard_update_include(
+    raw: Option<&str>,
+) -> Result<DashboardUpdateInclude, ApiError> {
+    let Some(raw) = raw else {
+        return Ok(DashboardUpdateInclude::all());
+    };
+    let mut include = DashboardUpdateInclude {
+        feed: false,
+        briefs: false,
+        notifications: false,
+    };
+    let mut saw_any = false;
+    for part in raw
+        .split(',')
+        .map(str::trim)
+        .filter(|part| !part.is_empty())
+    {
+        saw_any = true;
+        match part {
+            "feed" => include.feed = true,
+            "briefs" => include.briefs = true,
+            "notifications" | "inbox" => include.notifications = true,
+            _ => return Err(ApiError::bad_request(format!("invalid include: {part}"))),
+        }
+    }
+    if saw_any {
+        Ok(include)
+    } else {
+        Ok(DashboardUpdateInclude::all())
+    }
+}
+
+pub(super) fn parse_dashboard_feed_type(raw: Option<&str>) -> Result<FeedTypeSelection, ApiError> {
+    match raw.map(str::trim).filter(|value| !value.is_empty()) {
+        None | Some("all") => Ok(FeedTypeSelection::all()),
+        Some("releases") => parse_feed_types(Some("releases")),
+        Some("stars") => parse_feed_types(Some("stars")),
+        Some("followers") => parse_feed_types(Some("followers")),
+        Some(value) => Err(ApiError::bad_request(format!("invalid feed_type: {value}"))),
+    }
+}
+
+pub(super) fn normalize_repo_scope_item(raw: &str) -> Option<String> {
+    let trimmed = raw.trim().trim_matches('/');
+    let (owner, repo) = trimmed.split_once('/')?;
+    let owner = owner.trim();
+    let repo = repo.trim();
+    if owner.is_empty() || repo.is_empty() {
+        return None;
+    }
+    Some(format!("{owner}/{repo}"))
+}
+
+pub(super) fn parse_feed_scope(
+    scope: Option<&str>,
+    items: Option<&str>,
+    org: Option<&str>,
+) -> Result<Option<FeedScope>, ApiError> {
+    let Some(scope) = scope.map(str::trim).filter(|value| !value.is_empty()) else {
+        return Ok(None);
+    };
+    match scope {
+        "repo" => {
+            let repo_item = items
+                .and_then(normalize_repo_scope_item)
+                .ok_or_else(|| ApiError::bad_request("scope=repo requires items=owner/repo"))?;
+            let (owner, repo) = repo_item
+                .split_once('/')
+                .ok_or_else(|| ApiError::bad_request("invalid repo scope item"))?;
+            Ok(Some(FeedScope::Repo {
+                owner: owner.to_owned(),
+                repo: repo.to_owned(),
+            }))
+        }
+        "repos" => {
+            let mut deduped = Vec::new();
+            let mut seen = HashSet::new();
+            for raw in items
+                .unwrap_or("")
+                .split(',')
+                .map(str::trim)
+                .filter(|value| !value.is_empty())
+            {
+                let normalized = normalize_repo_scope_item(raw)
+                    .ok_or_else(|| ApiError::bad_request("invalid repo scope item"))?;
+                let key = normalized.to_ascii_lowercase();
+                if seen.insert(key) {
+                    deduped.push(normalized);
+                }
+            }
+            if deduped.is_empty() {
+                return Ok(Some(FeedScope::Repos { items: Vec::new() }));
+            }
+            if deduped.len() > 12 {
+                return Err(ApiError::bad_request("scope repos items exceeds limit"));
+            }
+            Ok(Some(FeedScope::Repos { items: deduped }))
+        }
+        "org" => {
+            let org = org
+                .map(str::trim)
+                .filter(|value| !value.is_empty())
+                .ok_or_else(|| ApiError::bad_request("scope=org requires org"))?;
+            Ok(Some(FeedScope::Org {
+                org: org.to_owned(),
+            }))
+        }
+        "mine" => Ok(Some(FeedScope::Mine)),
+        value => Err(ApiError::bad_request(format!("invalid scope: {value}"))),
+    }
+}
+
+pub(super) fn decode_dashboard_updates_token(
+    raw: Option<&str>,
+) -> Result<Option<DashboardUpdatesToken>, ApiError> {
+    let Some(raw) = raw.map(str::trim).filter(|value| !value.is_empty()) else {
+        return Ok(None);
+    };
+    let bytes = URL_SAFE_NO_PAD
+        .decode(raw)
+        .map_err(|_| ApiError::bad_request("invalid dashboard updates token"))?;
+    serde_json::from_slice::<DashboardUpdatesToken>(&bytes)
+        .map(Some)
+        .map_err(|_| ApiError::bad_request("invalid dashboard updates token"))
+}
+
+pub(super) fn encode_dashboard_updates_token(
+    token: &DashboardUpdatesToken,
+) -> Result<String, ApiError> {
+    let bytes = serde_json::to_vec(token).map_err(ApiError::internal)?;
+    Ok(URL_SAFE_NO_PAD.encode(bytes))
+}
+
+pub(super) fn build_dashboard_list_update(
+    previous_signature_digests: Option<&[String]>,
+    latest_signatures: &[String],
+) -> DashboardListUpdate {
+    let latest_keys = latest_signatures
+        .iter()
+        .map(|signature| dashboard_update_signature_key(signature).to_owned())
+        .collect::<Vec<_>>();
+    let latest_digests = compact_dashboard_signatures(latest_signatures);
+    let Some(previous_signature_digests) = previous_signature_digests else {
+        return DashboardListUpdate {
+            changed: false,
+            new_count: 0,
+            latest_keys,
+        };
+    };
+    if previous_signature_digests == latest_digests {
+        return DashboardListUpdate {
+            changed: false,
+            new_count: 0,
+            latest_keys,
+        };
+    }
+    let previous = previous_signature_digests
+        .iter()
+        .map(|digest| digest.as_str())
+        .collect::<HashSet<_>>();
+    let mut changed_keys = Vec::new();
+    let mut seen_changed_keys = HashSet::new();
+    for (signature, digest) in latest_signatures.iter().zip(latest_digests.iter()) {
+        if previous.contains(digest.as_str()) {
+            continue;
+        }
+        let key = dashboard_update_signature_key(signature);
+        if seen_changed_keys.insert(key) {
+            changed_keys.push(key.to_owned());
+        }
+    }
+    DashboardListUpdate {
+        changed: !changed_keys.is_empty(),
+        new_count: changed_keys.len(),
+        latest_keys: changed_keys,
+    }
+}
+
+pub(super) fn dashboard_update_signature_digest(signature: &str) -> String {
+    ai::sha256_hex(signature).chars().take(16).collect()
+}
+
+pub(super) fn compact_dashboard_signatures(signatures: &[String]) -> Vec<String> {
+    signatures
+        .iter()
+        .map(|signature| dashboard_update_signature_digest(signature))
+        .collect()
+}
+
+pub(super) fn dashboard_update_signature_key(signature: &str) -> &str {
+    signature
+        .split_once('|')
+        .map(|(key, _)| key)
+        .unwrap_or(signature)
+}
+
+pub(super) fn option_signature_value(value: Option<&str>) -> &str {
+    value.unwrap_or("")
+}
+
+pub(super) fn dashboard_feed_signature(row: &FeedRow) -> String {
+    let key = format!("{}:{}", row.kind, row.entity_id);
+    let state_hash = ai::sha256_hex(&format!(
+        "sort={}\ntrans_hash={}\ntrans_status={}\ntrans_work={}\ntrans_title={}\ntrans_summary={}\ndetail_hash={}\ndetail_status={}\ndetail_work={}\ndetail_title={}\ndetail_summary={}\nsmart_hash={}\nsmart_status={}\nsmart_work={}\nsmart_title={}\nsmart_summary={}\n",
+        row.sort_ts,
+        option_signature_value(row.trans_source_hash.as_deref()),
+        option_signature_value(row.trans_status.as_deref()),
+        option_signature_value(row.trans_work_status.as_deref()),
+        option_signature_value(row.trans_title.as_deref()),
+        option_signature_value(row.trans_summary.as_deref()),
+        option_signature_value(row.detail_trans_source_hash.as_deref()),
+        option_signature_value(row.detail_trans_status.as_deref()),
+        option_signature_value(row.detail_trans_work_status.as_deref()),
+        option_signature_value(row.detail_trans_title.as_deref()),
+        option_signature_value(row.detail_trans_summary.as_deref()),
+        option_signature_value(row.smart_source_hash.as_deref()),
+        option_signature_value(row.smart_status.as_deref()),
+        option_signature_value(row.smart_work_status.as_deref()),
+        option_signature_value(row.smart_title.as_deref()),
+        option_signature_value(row.smart_summary.as_deref()),
+    ));
+    format!("{key}|{state_hash}")
+}
+
+pub(super) async fn load_dashboard_feed_signatures(
+    state: &AppState,
+    user_id: &str,
+    types: FeedTypeSelection,
+    scope: Option<&FeedScope>,
+    viewer_login: Option<&str>,
+) -> Result<Vec<String>, ApiError> {
+    fetch_feed_items(
+        state,
+        user_id,
+        None,
+        types,
+        scope,
+        &FeedRepoFilter::default(),
+        viewer_login,
+        30,
+    )
+    .await
+    .map(|rows| {
+        rows.into_iter()
+            .map(|row| dashboard_feed_signature(&row))
+            .collect()
+    })
+}
+
+pub(super) async fn load_dashboard_brief_signatures(
+    state: &AppState,
+    user_id: &str,
+) -> Result<Vec<String>, ApiError> {
+    let signatures = sqlx::query_scalar::<_, String>(
+        r#"
+        SELECT 'brief:' || id || '|' || updated_at
+        FROM briefs
+        WHERE user_id = ?
+        ORDER BY COALESCE(window_end_utc, created_at) DESC, created_at DESC, id DESC
+        LIMIT 50
+        "#,
+    )
+    .bind(user_id)
+    .fetch_all(&state.pool)
+    .await
+    .map_err(ApiError::internal)?;
+    Ok(signatures)
+}
+
+pub(super) async fn load_dashboard_notification_signatures(
+    state: &AppState,
+    user_id: &str,
+) -> Result<Vec<String>, ApiError> {
+    let signatures = sqlx::query_scalar::<_, String>(
+        r#"
+        SELECT 'notification:' || thread_id || '|' || COALESCE(updated_at, '') || '|' || unread
+        FROM notifications
+        WHERE user_id = ?
+          AND archived_at IS NULL
+        ORDER BY updated_at DESC, thread_id DESC
+        LIMIT 50
+        "#,
+    )
+    .bind(user_id)
+    .fetch_all(&state.pool)
+    .await
+    .map_err(ApiError::internal)?;
+    Ok(signatures)
+}
+
+pub async fn dashboard_updates(
+    State(state): State<Arc<AppState>>,
+    AuthedUser(user_id): AuthedUser,
+    Query(q): Query<DashboardUpdatesQuery>,
+) -> Result<Json<DashboardUpdatesResponse>, ApiError> {
+    let viewer = load_viewer_user(state.as_ref(), &user_id).await?;
+    let previous = decode_dashboard_updates_token(q.token.as_deref())?;
+    let include = parse_dashboard_update_include(q.include.as_deref())?;
+    let feed_types = parse_dashboard_feed_type(q.feed_type.as_deref())?;
+    let scope = parse_feed_scope(q.scope.as_deref(), q.items.as_deref(), q.org.as_deref())?;
+
+    let mut next_token = DashboardUpdatesToken::default();
+    let mut lists = DashboardUpdatesLists {
+        feed: None,
+        briefs: None,
+        notifications: None,
+    };
+
+    if include.feed {
+        let signatures = load_dashboard_feed_signatures(
+            state.as_ref(),
+            &user_id,
+            feed_types,
+            scope.as_ref(),
+            Some(viewer.login.as_str()),
+        )
+        .await?;
+        lists.feed = Some(build_dashboard_list_update(
+            previous.as_ref().map(|token| token.feed.as_slice()),
+            &signatures,
+        ));
+        next_token.feed = compact_dashboard_signatures(&signatures);
+    } else if let Some(previous) = previous.as_ref() {
+        next_token.feed = previous.feed.clone();
+    }
+
+    if include.briefs {
+        let signatures = load_dashboard_brief_signatures(state.as_ref(), &user_id).await?;
+        lists.briefs = Some(build_dashboard_list_update(
+            previous.as_ref().map(|token| token.briefs.as_slice()),
+            &signatures,
+        ));
+        next_token.briefs = compact_dashboard_signatures(&signatures);
+    } else if let Some(previous) = previous.as_ref() {
+        next_token.briefs = previous.briefs.clone();
+    }
+
+    if include.notifications {
+        let signatures = load_dashboard_notification_signatures(state.as_ref(), &user_id).await?;
+        lists.notifications = Some(build_dashboard_list_update(
+            previous
+                .as_ref()
+                .map(|token| token.notifications.as_slice()),
+            &signatures,
+        ));
+        next_token.notifications = compact_dashboard_signatures(&signatures);
+    } else if let Some(previous) = previous.as_ref() {
+        next_token.notifications = previous.notifications.clone();
+    }
+
+    Ok(Json(DashboardUpdatesResponse {
+        token: encode_dashboard_updates_token(&next_token)?,
+        generated_at: chrono::Utc::now().to_rfc3339(),
+        lists,
+    }))
+}
+
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct ReturnModeQuery {
+    #[schemars(with = "Option<ReturnMode>")]
+    pub(super) return_mode: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ReturnMode {
+    Sync,
+    TaskId,
+    Sse,
+}
+
+impl ReturnMode {
+    pub(super) fn from_query(query: &ReturnModeQuery) -> Result<Self, ApiError> {
+        let raw = query
+            .return_mode
+            .as_deref()
+            .map(str::trim)
+            .filter(|value| !value.is_empty())
+            .unwrap_or("sync");
+        match raw {
+            "sync" => Ok(Self::Sync),
+            "task_id" => Ok(Self::TaskId),
+            "sse" => Ok(Self::Sse),
+            _ => Err(ApiError::bad_request(
+                "invalid return_mode, expected sync|task_id|sse",
+            )),
+        }
+    }
+}
+
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct TaskAcceptedResponse {
+    pub(super) mode: ReturnMode,
+    pub(super) task_id: String,
+    pub(super) task_type: String,
+    #[schemars(extend("enum" = ["queued", "running", "succeeded", "failed", "canceled"]))]
+    pub(super) status: String,
+}
+
+pub(super) async fn enqueue_or_stream_task(
+    state: Arc<AppState>,
+    mode: ReturnMode,
+    new_task: jobs::NewTask,
+) -> Result<Response, ApiError> {
+    let task = jobs::enqueue_task(state.as_ref(), new_task).await?;
+
+    match mode {
+        ReturnMode::TaskId => Ok(Json(TaskAcceptedResponse {
+            mode: ReturnMode::TaskId,
+            task_id: task.task_id,
+            task_type: task.task_type,
+            status: task.status,
+        })
+        .into_response()),
+        ReturnMode::Sse => Ok(jobs::task_sse_response(state, task.task_id, None)),
+        ReturnMode::Sync => Err(ApiError::internal("unexpected sync return mode")),
+    }
+}
+
+pub(super) async fn enqueue_singleton_or_stream_task(
+    state: Arc<AppState>,
+    mode: ReturnMode,
+    new_task: jobs::NewTask,
+) -> Result<Response, ApiError> {
+    let task = jobs::enqueue_singleton_task_for_requester(state.as_ref(), new_task)
+        .await
+        .map_err(ApiError::internal)?;
+
+    match mode {
+        ReturnMode::TaskId => Ok(Json(TaskAcceptedResponse {
+            mode: ReturnMode::TaskId,
+            task_id: task.task_id,
+            task_type: task.task_type,
+            status: task.status,
+        })
+        .into_response()),
+        ReturnMode::Sse => Ok(jobs::task_sse_response(state, task.task_id, None)),
+        ReturnMode::Sync => Err(ApiError::internal("unexpected sync return mode")),
+    }
+}
+
+pub async fn task_events_sse(
+    State(state): State<Arc<AppState>>,
+    AuthedUser(user_id): AuthedUser,
+    Path(task_id): Path<String>,
+    headers: HeaderMap,
+) -> Result<Response, ApiError> {
+    let task_exists = sqlx::query_scalar::<_, i64>(
+        r#"
+        SELECT COUNT(*)
+        FROM job_tasks
+        WHERE id = ? AND requested_by = ?
+        "#,
+    )
+    .bind(task_id.as_str())
+    .bind(user_id.as_str())
+    .fetch_one(&state.pool)
+    .await
+    .map_err(ApiError::internal)?;
+    if task_exists == 0 {
+        return Err(ApiError::new(
+            StatusCode::NOT_FOUND,
+            codes::NOT_FOUND,
+            "task not found",
+        ));
+    }
+    Ok(jobs::task_sse_response(
+        state,
+        task_id,
+        jobs::last_event_id(&headers),
+    ))
+}
+
+pub(super) async fn run_with_api_llm_context<F, T>(
+    source: &str,
+    requested_by: Option<String>,
+    fut: F,
+) -> T
+where
+    F: Future<Output = T>,
+{
+    ai::with_llm_call_context(
+        ai::LlmCallContext {
+            source: source.to_owned(),
+            requested_by,
+            parent_task_id: None,
+            parent_task_type: None,
+            parent_translation_batch_id: None,
+        },
+        fut,
+    )
+    .await
+}
+
+pub async fn sync_starred(
+    State(state): State<Arc<AppState>>,
+    AuthedUser(user_id): AuthedUser,
+    Query(mode_query): Query<ReturnModeQuery>,
+) -> Result<Response, ApiError> {
+    let mode = ReturnMode::from_query(&mode_query)?;
+
+    if matches!(mode, ReturnMode::Sync) {
+        let res = sync::sync_starred(state.as_ref(), user_id.as_str())
+            .await
+            .map_err(ApiError::internal)?;
+        return Ok(Json(res).into_response());
+    }
+
+    enqueue_singleton_or_stream_task(
+        state,
+        mode,
+        jobs::NewTask {
+            task_type: jobs::TASK_SYNC_STARRED.to_owned(),
+            payload: json!({ "user_id": user_id.clone() }),
+            source: "api.sync_starred".to_owned(),
+            requested_by: Some(user_id.clone()),
+            parent_task_id: None,
+            priority: jobs::TaskPriority::High,
+        },
+    )
+    .await
+}
+
+pub async fn sync_all(
+    State(state): State<Arc<AppState>>,
+    AuthedUser(user_id): AuthedUser,
+    Query(mode_query): Query<ReturnModeQuery>,
+) -> Result<Response, ApiError> {
+    let mode = ReturnMode::from_query(&mode_query)?;
+
+    if matches!(mode, ReturnMode::Sync) {
+        let body = execute_sync_all_sync_with(
+            state.as_ref(),
+            user_id.as_str(),
+            |state, user_id| Box::pin(sync::sync_starred(state, user_id)),
+            |state, user_id| Box::pin(sync::sync_releases(state, user_id)),
+            |state, user_id| {
+                Box::pin(async move {
+                    sync::sync_social_activity_best_effort(state, user_id, "api.sync_all.sync")
+                        .await
+                })
+            },
+            |state, user_id| Box::pin(sync::sync_notifications(state, user_id)),
+        )
+        .await;
+        return Ok(Json(body?).into_response());
+    }
+
+    enqueue_singleton_or_stream_task(
+        state,
+        mode,
+        jobs::NewTask {
+            task_type: jobs::TASK_SYNC_ACCESS_REFRESH.to_owned(),
+            payload: json!({ "user_id": user_id.clone() }),
+            source: "api.sync_all".to_owned(),
+            requested_by: Some(user_id.clone()),
+            parent_task_id: None,
+            priority: jobs::TaskPriority::High,
+        },
+    )
+    .await
+}
+
+pub(super) type SyncAllApiFuture<'a, T> =
+    Pin<Box<dyn Future<Output = anyhow::Result<T>> + Send + 'a>>;
+pub(super) type SyncAllSocialFuture<'a> =
+    Pin<Box<dyn Future<Output = (sync::SyncSocialActivityResult, Option<String>)> + Send + 'a>>;
+
+pub(super) async fn execute_sync_all_sync_with<
+    SyncStarred,
+    SyncReleases,
+    SyncSocial,
+    SyncNotifications,
+>(
+    state: &AppState,
+    user_id: &str,
+    sync_starred: SyncStarred,
+    sync_releases: SyncReleases,
+    sync_social: SyncSocial,
+    sync_notifications: SyncNotifications,
+) -> Result<Value, ApiError>
+where
+    SyncStarred: for<'a> Fn(&'a AppState, &'a str) -> SyncAllApiFuture<'a, sync::SyncStarredResult>,
+    SyncReleases:
+        for<'a> Fn(&'a AppState, &'a str) -> SyncAllApiFuture<'a, sync::SyncReleasesResult>,
+    SyncSocial: for<'a> Fn(&'a AppState, &'a str) -> SyncAllSocialFuture<'a>,
+    SyncNotifications:
+        for<'a> Fn(&'a AppState, &'a str) -> SyncAllApiFuture<'a, sync::SyncNotificationsResult>,
+{
+    let starred = sync_starred(state, user_id)
+        .await
+        .map_err(ApiError::internal)?;
+    let releases = sync_releases(state, user_id)
+        .await
+        .map_err(ApiError::internal)?;
+    let (social, social_error) = sync_social(state, user_id).await;
+    let notifications = sync_notifications(state, user_id)
+        .await
+        .map_err(ApiError::internal)?;
+
+    let mut body = json!({
+        "starred": starred,
+        "releases": releases,
+        "social": social,
+        "notifications": notifications,
+    });
+    if let Some(error) = social_error {
+        body["social_error"] = Value::String(error);
+    }
+    Ok(body)
+}
+
+#[derive(Debug, Default, Deserialize)]
+pub struct SyncReleasesQuery {
+    pub(super) force: Option<bool>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct GitHubRateLimitResponse {
+    pub(super) items: Vec<github_rate_limit::GitHubRateLimitRow>,
+    pub(super) low_remaining_threshold: i64,
+}
+
+pub async fn get_github_rate_limit(
+    State(state): State<Arc<AppState>>,
+    AuthedUser(user_id): AuthedUser,
+) -> Result<Json<GitHubRateLimitResponse>, ApiError> {
+    let items = github_rate_limit::load_rate_limits(state.as_ref(), user_id.as_str())
+        .await
+        .map_err(ApiError::internal)?;
+    Ok(Json(GitHubRateLimitResponse {
+        items,
+        low_remaining_threshold: github_rate_limit::GITHUB_RATE_LIMIT_LOW_REMAINING,
+    }))
+}
+
+pub async fn sync_releases(
+    State(state): State<Arc<AppState>>,
+    AuthedUser(user_id): AuthedUser,
+    Query(mode_query): Query<ReturnModeQuery>,
+    Query(sync_query): Query<SyncReleasesQuery>,
+) -> Result<Response, ApiError> {
+    let mode = ReturnMode::from_query(&mode_query)?;
+    let force = sync_query.force.unwrap_or(false);
+
+    if matches!(mode, ReturnMode::Sync) {
+        let res = sync::sync_releases_with_force(state.as_ref(), user_id.as_str(), force)
+            .await
+            .map_err(ApiError::internal)?;
+        return Ok(Json(res).into_response());
+    }
+
+    let payload = if force {
+        json!({ "user_id": user_id.clone(), "force": true })
+    } else {
+        json!({ "user_id": user_id.clone() })
+    };
+    enqueue_singleton_or_stream_task(
+        state,
+        mode,
+        jobs::NewTask {
+            task_type: jobs::TASK_SYNC_RELEASES.to_owned(),
+            payload,
+            source: "api.sync_releases".to_owned(),
+            requested_by: Some(user_id.clone()),
+            parent_task_id: None,
+            priority: jobs::TaskPriority::High,
+        },
+    )
+    .await
+}
+
+pub async fn sync_notifications(
+    State(state): State<Arc<AppState>>,
+    AuthedUser(user_id): AuthedUser,
+    Query(mode_query): Query<ReturnModeQuery>,
+) -> Result<Response, ApiError> {
+    let mode = ReturnMode::from_query(&mode_query)?;
+
+    if matches!(mode, ReturnMode::Sync) {
+        let res = sync::sync_notifications(state.as_ref(), user_id.as_str())
+            .await
+            .map_err(ApiError::internal)?;
+        return Ok(Json(res).into_response());
+    }
+
+    enqueue_singleton_or_stream_task(
+        state,
+        mode,
+        jobs::NewTask {
+            task_type: jobs::TASK_SYNC_NOTIFICATIONS.to_owned(),
+            payload: json!({ "user_id": user_id.clone() }),
+            source: "api.sync_notifications".to_owned(),
+            requested_by: Some(user_id.clone()),
+            parent_task_id: None,
+            priority: jobs::TaskPriority::High,
+        },
+    )
+    .await
+}
+
+pub async fn backfill_release_node_ids(
+    State(state): State<Arc<AppState>>,
+    Query(mode_query): Query<ReturnModeQuery>,
+    AuthedUser(user_id): AuthedUser,
+) -> Result<Response, ApiError> {
+    let mode = ReturnMode::from_query(&mode_query)?;
+
+    if matches!(mode, ReturnMode::Sync) {
+        let res = sync::backfill_release_node_ids(state.as_ref(), user_id.as_str())
+            .await
+            .map_err(ApiError::internal)?;
+        return Ok(Json(res).into_response());
+    }
+
+    enqueue_singleton_or_stream_task(
+        state,
+        mode,
+        jobs::NewTask {
+            task_type: jobs::TASK_SYNC_RELEASE_NODE_IDS.to_owned(),
+            payload: json!({ "user_id": user_id.clone() }),
+            source: "api.backfill_release_node_ids".to_owned(),
+            requested_by: Some(user_id.clone()),
+            parent_task_id: None,
+            priority: jobs::TaskPriority::Low,
+        },
+    )
+    .await
+}
+
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct FeedQuery {
+    pub(super) cursor: Option<String>,
+    pub(super) limit: Option<i64>,
+    pub(super) types: Option<String>,
+    pub(super) scope: Option<String>,
+    pub(super) items: Option<String>,
+    pub(super) org: Option<String>,
+    pub(super) unseen_only: Option<bool>,
+    pub(super) language: Option<String>,
+    pub(super) topic: Option<String>,
+    pub(super) exclude_archived: Option<bool>,
+    pub(super) excerpt: Option<String>,
+    pub(super) excerpt_lines: Option<i64>,
+    pub(super) excerpt_chars: Option<i64>,
+    /// On the first page, also advance the `/api/counters` baseline to now.
+    pub(super) mark_seen: Option<bool>,
+    /// `full` (default) or `basic`; `basic` returns headline fields only and
+    /// leaves out the excerpt and translated/smart summaries.
+    #[schemars(extend("enum" = ["full", "basic"]))]
+    pub(super) fields: Option<String>,
+}
+
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct FeedResponse {
+    pub(super) items: Vec<FeedItem>,
+    pub(super) next_cursor: Option<String>,
+}
+
+/// `fields=basic` page: the same items projected onto [`FeedItemBasic`].
+#[derive(Debug, Serialize)]
+pub struct FeedBasicResponse {
+    pub(super) items: Vec<FeedItemBasic>,
+    pub(super) next_cursor: Option<String>,
+}
+
+/// Headline projection of a [`FeedItem`] for clients that render titles
+/// only; the heavy text fields are never serialized.
+#[derive(Debug, Serialize)]
+pub struct FeedItemBasic {
+    pub(super) kind: String,
+    pub(super) ts: String,
+    pub(super) id: String,
+    pub(super) repo_full_name: Option<String>,
+    pub(super) repo_owner_login: Option<String>,
+    pub(super) repo_owner_avatar_url: Option<String>,
+    pub(super) repo_html_url: Option<String>,
+    pub(super) repo_visual: Option<RepoVisual>,
+    pub(super) title: Option<String>,
+    pub(super) subtitle: Option<String>,
+    pub(super) reason: Option<String>,
+    pub(super) subject_type: Option<String>,
+    pub(super) html_url: Option<String>,
+    pub(super) unread: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(super) actor: Option<FeedActor>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(super) source: Option<String>,
+    pub(super) translated: Option<FeedTitleTranslation>,
+    pub(super) smart: Option<FeedTitleTranslation>,
+    pub(super) reactions: Option<ReleaseReactions>,
+    pub(super) bookmarked: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(super) bookmark_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(super) assets_count: Option<i64>,
+}
+
+/// Title-level part of [`TranslatedItem`] / [`SmartItem`].
+#[derive(Debug, Serialize)]
+pub struct FeedTitleTranslation {
+    pub(super) lang: String,
+    pub(super) status: String,
+    pub(super) title: Option<String>,
+    pub(super) error_code: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(super) auto_translate: Option<bool>,
+}
+
+impl From<TranslatedItem> for FeedTitleTranslation {
+    fn from(item: TranslatedItem) -> Self {
+        Self {
+            lang: item.lang,
+            status: item.status,
+            title: item.title,
+            error_code: item.error_code,
+            auto_translate: item.auto_translate,
+        }
+    }
+}
+
+impl From<SmartItem> for FeedTitleTranslation {
+    fn from(item: SmartItem) -> Self {
+        Self {
+            lang: item.lang,
+            status: item.status,
+            title: item.title,
+            error_code: item.error_code,
+            auto_translate: item.auto_translate,
+        }
+    }
+}
+
+impl From<FeedItem> for FeedItemBasic {
+    fn from(item: FeedItem) -> Self {
+        Self {
+            kind: item.kind,
+            ts: item.ts,
+            id: item.id,
+            repo_full_name: item.repo_full_name,
+            repo_owner_login: item.repo_owner_login,
+            repo_owner_avatar_url: item.repo_owner_avatar_url,
+            repo_html_url: item.repo_html_url,
+            repo_visual: item.repo_visual,
+            title: item.title,
+            subtitle: item.subtitle,
+            reason: item.reason,
+            subject_type: item.subject_type,
+            html_url: item.html_url,
+            unread: item.unread,
+            actor: item.actor,
+            source: item.source,
+            translated: item.translated.map(Into::into),
+            smart: item.smart.map(Into::into),
+            reactions: item.reactions,
+            bookmarked: item.bookmarked,
+            bookmark_id: item.bookmark_id,
+            assets_count: item.assets_count,
+        }
+    }
+}
+
+impl From<FeedResponse> for FeedBasicResponse {
+    fn from(page: FeedResponse) -> Self {
+        Self {
+            items: page.items.into_iter().map(Into::into).collect(),
+            next_cursor: page.next_cursor,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) enum FeedFields {
+    Full,
+    Basic,
+}
+
+impl FeedFields {
+    pub(super) fn from_query(raw: Option<&str>) -> Result<Self, ApiError> {
+        match raw.map(str::trim).filter(|value| !value.is_empty()) {
+            None | Some("full") => Ok(Self::Full),
+            Some("basic") => Ok(Self::Basic),
+            Some(_) => Err(ApiError::bad_request("fields must be full or basic")),
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub struct FeedReactionRefreshRequest {
+    pub(super) release_ids: Vec<String>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+pub struct FeedReactionRefreshQuery {
+    pub(super) refresh_reactions: Option<bool>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct FeedReactionRefreshResponse {
+    pub(super) items: Vec<FeedReactionRefreshItem>,
+    /// Set when GitHub could not be reached; affected items fall back to
+    /// locally stored counts with status `stale`.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub(super) warnings: Vec<FeedWarning>,
+}
+
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+pub struct FeedWarning {
+    pub(super) code: String,
+    pub(super) message: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct FeedReactionRefreshItem {
+    pub(super) release_id: String,
+    pub(super) reactions: ReleaseReactions,
+}
+
+#[derive(Debug, Serialize, Clone, JsonSchema)]
+pub struct RepoVisual {
+    pub(super) owner_avatar_url: Option<String>,
+    pub(super) open_graph_image_url: Option<String>,
+    pub(super) uses_custom_open_graph_image: bool,
+}
+
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct FeedActor {
+    pub(super) login: String,
+    pub(super) avatar_url: Option<String>,
+    pub(super) html_url: Option<String>,
+}
+
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct FeedItem {
+    pub(super) kind: String,
+    pub(super) ts: String,
+    pub(super) id: String,
+    pub(super) repo_full_name: Option<String>,
+    pub(super) repo_owner_login: Option<String>,
+    pub(super) repo_owner_avatar_url: Option<String>,
+    pub(super) repo_description: Option<String>,
+    pub(super) repo_html_url: Option<String>,
+    pub(super) repo_visual: Option<RepoVisual>,
+    pub(super) title: Option<String>,
+    pub(super) body: Option<String>,
+    pub(super) body_truncated: bool,
+    pub(super) subtitle: Option<String>,
+    pub(super) reason: Option<String>,
+    pub(super) subject_type: Option<String>,
+    pub(super) html_url: Option<String>,
+    pub(super) unread: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(super) actor: Option<FeedActor>,
+    /// Why a release's repo is in the feed; owned repos count as `starred`.
+    /// Omitted for non-release items.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[schemars(extend("enum" = ["starred", "followed"]))]
+    pub(super) source: Option<String>,
+    pub(super) translated: Option<TranslatedItem>,
+    pub(super) smart: Option<SmartItem>,
+    pub(super) reactions: Option<ReleaseReactions>,
+    pub(super) bookmarked: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(super) bookmark_id: Option<String>,
+    /// Stored downloadable assets of a release; omitted for non-release items.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(super) assets_count: Option<i64>,
+}
+
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct TranslatedItem {
+    pub(super) lang: String,
+    #[schemars(extend("enum" = ["ready", "missing", "disabled", "opted_out", "error"]))]
+    pub(super) status: String,
+    pub(super) title: Option<String>,
+    pub(super) summary: Option<String>,
+    pub(super) error_code: Option<String>,
+    pub(super) error_summary: Option<String>,
+    pub(super) error_detail: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(super) auto_translate: Option<bool>,
+    /// Release detail only: which translation mode produced the content.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[schemars(extend("enum" = ["full", "summary"]))]
+    pub(super) mode: Option<String>,
+    /// Language guessed from the source text; `zh` means the original was
+    /// already Chinese and is served as-is instead of being translated.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[schemars(extend("enum" = ["zh", "ja", "ko", "en"]))]
+    pub(super) detected_lang: Option<String>,
+}
+
+#[derive(Debug, Serialize, Clone, JsonSchema)]
+pub struct SmartItem {
+    pub(super) lang: String,
+    #[schemars(extend("enum" = ["ready", "missing", "disabled", "opted_out", "error", "insufficient"]))]
+    pub(super) status: String,
+    pub(super) title: Option<String>,
+    pub(super) summary: Option<String>,
+    pub(super) error_code: Option<String>,
+    pub(super) error_summary: Option<String>,
+    pub(super) error_detail: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(super) auto_translate: Option<bool>,
+}
+
+#[derive(Debug, Serialize, Clone, JsonSchema)]
+pub struct ReleaseReactions {
+    pub(super) counts: ReleaseReactionCounts,
+    pub(super) viewer: ReleaseReactionViewer,
+    #[schemars(extend("enum" = ["ready", "stale", "sync_required"]))]
+    pub(super) status: String,
+}
+
+#[derive(Debug, Serialize, Clone, Default, JsonSchema)]
+pub struct ReleaseReactionCounts {
+    pub(super) plus1: i64,
+    pub(super) laugh: i64,
+    pub(super) heart: i64,
+    pub(super) hooray: i64,
+    pub(super) rocket: i64,
+    pub(super) eyes: i64,
+}
+
+#[derive(Debug, Serialize, Clone, Default, JsonSchema)]
+pub struct ReleaseReactionViewer {
+    pub(super) plus1: bool,
+    pub(super) laugh: bool,
+    pub(super) heart: bool,
+    pub(super) hooray: bool,
+    pub(super) rocket: bool,
+    pub(super) eyes: bool,
+}
+
+#[derive(Debug, sqlx::FromRow)]
+pub(super) struct FeedRow {
+    pub(super) kind: String,
+    pub(super) sort_ts: String,
+    pub(super) ts: String,
+    pub(super) id_key: String,
+    pub(super) entity_id: String,
+    pub(super) release_id: Option<i64>,
+    pub(super) release_node_id: Option<String>,
+    pub(super) repo_full_name: Option<String>,
+    pub(super) repo_owner_login: Option<String>,
+    pub(super) owner_avatar_url: Option<String>,
+    pub(super) repo_description: Option<String>,
+    pub(super) repo_html_url: Option<String>,
+    pub(super) open_graph_image_url: Option<String>,
+    pub(super) uses_custom_open_graph_image: Option<i64>,
+    pub(super) release_tag_name: Option<String>,
+    pub(super) release_previous_tag_name: Option<String>,
+    pub(super) title: Option<String>,
+    pub(super) subtitle: Option<String>,
+    pub(super) reason: Option<String>,
+    pub(super) subject_type: Option<String>,
+    pub(super) html_url: Option<String>,
+    pub(super) unread: Option<i64>,
+    pub(super) actor_login: Option<String>,
+    pub(super) actor_avatar_url: Option<String>,
+    pub(super) actor_html_url: Option<String>,
+    pub(super) release_body: Option<String>,
+    pub(super) react_plus1: Option<i64>,
+    pub(super) react_laugh: Option<i64>,
+    pub(super) react_heart: Option<i64>,
+    pub(super) react_hooray: Option<i64>,
+    pub(super) react_rocket: Option<i64>,
+    pub(super) react_eyes: Option<i64>,
+    /// `source` of the visibility view row; `None` for non-release items.
+    pub(super) repo_source: Option<String>,
+    pub(super) trans_source_hash: Option<String>,
+    pub(super) trans_status: Option<String>,
+    pub(super) trans_title: Option<String>,
+    pub(super) trans_summary: Option<String>,
+    pub(super) trans_error_text: Option<String>,
+    pub(super) trans_work_status: Option<String>,
+    pub(super) detail_trans_source_hash: Option<String>,
+    pub(super) detail_trans_status: Option<String>,
+    pub(super) detail_trans_title: Option<String>,
+    pub(super) detail_trans_summary: Option<String>,
+    pub(super) detail_trans_error_text: Option<String>,
+    pub(super) detail_trans_work_status: Option<String>,
+    pub(super) smart_source_hash: Option<String>,
+    pub(super) smart_status: Option<String>,
+    pub(super) smart_title: Option<String>,
+    pub(super) smart_summary: Option<String>,
+    pub(super) smart_error_text: Option<String>,
+    pub(super) smart_work_status: Option<String>,
+    pub(super) bookmark_id: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ReleaseReactionContent {
+    Plus1,
+    Laugh,
+    Heart,
+    Hooray,
+    Rocket,
+    Eyes,
+}
+
+impl ReleaseReactionContent {
+    pub(super) fn from_client_str(raw: &str) -> Option<Self> {
+        match raw {
+            "plus1" => Some(Self::Plus1),
+            "laugh" => Some(Self::Laugh),
+            "heart" => Some(Self::Heart),
+            "hooray" => Some(Self::Hooray),
+            "rocket" => Some(Self::Rocket),
+            "eyes" => Some(Self::Eyes),
+            _ => None,
+        }
+    }
+
+    pub(super) fn as_graphql_enum(self) -> &'static str {
+        match self {
+            Self::Plus1 => "THUMBS_UP",
+            Self::Laugh => "LAUGH",
+            Self::Heart => "HEART",
+            Self::Hooray => "HOORAY",
+            Self::Rocket => "ROCKET",
+            Self::Eyes => "EYES",
+        }
+    }
+}
+
+pub(super) fn feed_kind_rank(kind: &str) -> Option<i64> {
+    match kind {
+        "release" => Some(5),
+        "release_update" => Some(4),
+        "announcement" => Some(4),
+        "repo_forked" => Some(3),
+        "repo_star_received" => Some(2),
+        "follower_received" => Some(1),
+        "notification" => Some(0),
+        _ => None,
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub(super) struct FeedTypeSelection {
+    pub(super) releases: bool,
+    pub(super) stars: bool,
+    pub(super) followers: bool,
+    pub(super) ambient: bool,
+    /// Only releases the user has not marked seen; other kinds are dropped.
+    pub(super) unseen_only: bool,
+}
+
+/// Narrows the feed by starred repo metadata. Repos only visible through
+/// `include_own_releases` have no such metadata and drop out once a language
+/// or topic is set; archived repos stay in unless excluded explicitly.
+#[derive(Debug, Clone, Default)]
+pub(super) struct FeedRepoFilter {
+    pub(super) language: Option<String>,
+    pub(super) topic: Option<String>,
+    pub(super) exclude_archived: bool,
+}
+
+impl FeedRepoFilter {
+    pub(super) fn from_query(q: &FeedQuery) -> Self {
+        let clean = |value: Option<&str>| {
+            value
+                .map(str::trim)
+                .filter(|value| !value.is_empty())
+                .map(ToOwned::to_owned)
+        };
+        Self {
+            language: clean(q.language.as_deref()),
+            topic: clean(q.topic.as_deref()),
+            exclude_archived: q.exclude_archived.unwrap_or(false),
+        }
+    }
+
+    pub(super) fn is_active(&self) -> bool {
+        self.language.is_some() || self.topic.is_some() || self.exclude_archived
+    }
+}
+
+impl FeedTypeSelection {
+    pub(super) fn all() -> Self {
+        Self {
+            releases: true,
+            stars: true,
+            followers: true,
+            ambient: true,
+            unseen_only: false,
+        }
+    }
+}
+
+pub(super) fn parse_feed_types(types: Option<&str>) -> Result<FeedTypeSelection, ApiError> {
+    let Some(types) = types else {
+        return Ok(FeedTypeSelection::all());
+    };
+
+    let mut selection = FeedTypeSelection {
+        releases: false,
+        stars: false,
+        followers: false,
+        ambient: false,
+        unseen_only: false,
+    };
+    let mut saw_any = false;
+    for part in types.split(',').map(|s| s.trim()).filter(|s| !s.is_empty()) {
+        saw_any = true;
+        match part {
+            "releases" | "release" => selection.releases = true,
+            "stars" | "star" => selection.stars = true,
+            "followers" | "follower" => selection.followers = true,
+            "notifications" | "notification" | "inbox" => {
+                return Err(ApiError::bad_request(
+                    "feed does not include inbox items; use /api/notifications for inbox",
+                ));
+            }
+            _ => return Err(ApiError::bad_request(format!("invalid types: {part}"))),
+        }
+    }
+
+    if !saw_any {
+        return Ok(FeedTypeSelection::all());
+    }
+
+    Ok(selection)
+}
+
+pub(super) fn parse_release_id_param(raw: &str) -> Result<i64, ApiError> {
+    let release_id_raw = raw.trim();
+    if release_id_raw.is_empty() {
+        return Err(ApiError::bad_request("release_id is required"));
+    }
+    ensure_id_param_len(release_id_raw, "release_id")?;
+    release_id_raw
+        .parse::<i64>()
+        .map_err(|_| ApiError::bad_request("release_id must be an integer string"))
+}
+
+/// Longest client-supplied release/thread id accepted; real ids are far
+/// shorter, and anything bigger would only end up in logs and queries.
+pub(super) const MAX_ID_PARAM_CHARS: usize = 64;
+
+pub(super) fn ensure_id_param_len(value: &str, field: &str) -> Result<(), ApiError> {
+    if value.chars().count() > MAX_ID_PARAM_CHARS {
+        return Err(ApiError::bad_request(format!(
+            "{field} must be at most {MAX_ID_PARAM_CHARS} characters"
+        )));
+    }
+    Ok(())
+}
+
+pub(super) fn normalize_visual_url(raw: Option<String>) -> Option<String> {
+    raw.and_then(|value| {
+        let trimmed = value.trim();
+        if trimmed.is_empty() {
+            None
+        } else {
+            Some(trimmed.to_owned())
+        }
+    })
+}
+
+pub(super) fn repo_visual_from_parts(
+    owner_avatar_url: Option<String>,
+    open_graph_image_url: Option<String>,
+    uses_custom_open_graph_image: bool,
+) -> Option<RepoVisual> {
+    let owner_avatar_url = normalize_visual_url(owner_avatar_url);
+    let open_graph_image_url = normalize_visual_url(open_graph_image_url);
+    if owner_avatar_url.is_none() && open_graph_image_url.is_none() && !uses_custom_open_graph_image
+    {
+        return None;
+    }
+
+    Some(RepoVisual {
+        owner_avatar_url,
+        open_graph_image_url,
+        uses_custom_open_graph_image,
+    })
+}
+
+pub(crate) fn release_detail_source_hash(
+    repo_full_name: &str,
+    original_title: &str,
+    original_body: &str,
+) -> String {
+    let normalized_body = original_body.replace("\r\n", "\n");
+    let source = format!(
+        "v=1\nkind=release_detail\nrepo={}\ntitle={}\nbody={}\n",
+        repo_full_name.trim(),
+        original_title.trim(),
+        normalized_body.trim(),
+    );
+    ai::sha256_hex(&source)
+}
+
+pub(crate) const RELEASE_FEED_BODY_MAX_CHARS: usize = 3_000;
+
+pub(crate) fn release_feed_body(body: Option<&str>) -> Option<String> {
+    FeedExcerpt::default().apply(body).0
+}
+
+pub(super) const FEED_EXCERPT_MIN_CHARS: usize = 200;
+pub(super) const FEED_EXCERPT_MAX_CHARS: usize = 20_000;
+pub(super) const FEED_EXCERPT_MAX_LINES: usize = 500;
+
+/// How much of a release body the feed returns. This only shapes the
+/// displayed `body`; translation and smart source hashes are always computed
+/// from the default [`release_feed_body`] excerpt so different display limits
+/// share one cache entry instead of re-translating the same release.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) enum FeedExcerpt {
+    Limited {
+        max_chars: usize,
+        max_lines: Option<usize>,
+    },
+    Full,
+}
+
+impl Default for FeedExcerpt {
+    fn default() -> Self {
+        Self::Limited {
+            max_chars: RELEASE_FEED_BODY_MAX_CHARS,
+            max_lines: None,
+        }
+    }
+}
+
+impl FeedExcerpt {
+    pub(super) fn from_query(
+        mode: Option<&str>,
+        lines: Option<i64>,
+        chars: Option<i64>,
+    ) -> Result<Self, ApiError> {
+        match mode.map(str::trim).filter(|value| !value.is_empty()) {
+            None | Some("preview") => {}
+            Some("full") => return Ok(Self::Full),
+            Some(_) => return Err(ApiError::bad_request("excerpt must be preview or full")),
+        }
+        let max_chars = chars.map_or(RELEASE_FEED_BODY_MAX_CHARS, |chars| {
+            usize::try_from(chars)
+                .unwrap_or(0)
+                .clamp(FEED_EXCERPT_MIN_CHARS, FEED_EXCERPT_MAX_CHARS)
+        });
+        let max_lines = lines.map(|lines| {
+            usize::try_from(lines)
+                .unwrap_or(0)
+                .clamp(1, FEED_EXCERPT_MAX_LINES)
+        });
+        Ok(Self::Limited {
+            max_chars,
+            max_lines,
+        })
+    }
+
+    /// Returns the displayed body and whether it was cut short.
+    pub(super) fn apply(self, body: Option<&str>) -> (Option<String>, bool) {
+        let Some(body) = body else {
+            return (None, false);
+        };
+        let normalized = body.replace("\r\n", "\n");
+        let trimmed = normalized.trim();
+        if trimmed.is_empty() {
+            return (None, false);
+        }
+        let Self::Limited {
+            max_chars,
+            max_lines,
+        } = self
+        else {
+            return (Some(trimmed.to_owned()), false);
+        };
+        let (mut lines, mut truncated) = release_body_excerpt_lines(trimmed);
+        if let Some(max_lines) = max_lines
+            && lines.len() > max_lines
+        {
+            lines.truncate(max_lines);
+            truncated = true;
+        }
+        while lines.last().is_some_and(|line| line.trim().is_empty()) {
+            lines.pop();
+        }
+        if lines.is_empty() {
+            return (None, false);
+        }
+        let (excerpt, cut) = truncate_excerpt_lines(&lines, max_chars);
+        (Some(excerpt.trim().to_owned()), truncated || cut)
+    }
+}
+
+/// Lines of a release body as the feed shows them. HTML comments that release
+/// bots emit are dropped outside code fences, and tables keep their header and
+/// first rows. The flag reports table rows that were left out; dropped
+/// comments are not content and do not count.
+fn release_body_excerpt_lines(body: &str) -> (Vec<String>, bool) {
+    let mut lines: Vec<String> = Vec::new();
+    let mut dropped_rows = false;
+    let mut in_code = false;
+    let mut in_comment = false;
+    let mut after_comment = false;
+    let mut table_rows = 0usize;
+    for raw in body.lines() {
+        let mut line = raw;
+        if in_comment {
+            let Some(end) = line.find("-->") else {
+                continue;
+            };
+            in_comment = false;
+            line = &line[end + 3..];
+            if line.trim().is_empty() {
+                after_comment = true;
+                continue;
+            }
+        }
+        let fence = line.trim_start().starts_with("```");
+        if fence || in_code {
+            in_code ^= fence;
+            table_rows = 0;
+            after_comment = false;
+            lines.push(line.to_owned());
+            continue;
+        }
+
+        let (stripped, opens_comment) = strip_html_comments(line);
+        in_comment = opens_comment;
+        let stripped = stripped.trim_end();
+        if stripped.trim().is_empty() {
+            table_rows = 0;
+            if !line.trim().is_empty() {
+                // The line only held a comment.
+                after_comment = true;
+                continue;
+            }
+            // Do not leave a double gap where a comment line was removed.
+            if after_comment && lines.last().is_none_or(|line| line.trim().is_empty()) {
+                continue;
+            }
+            lines.push(String::new());
+            continue;
+        }
+        after_comment = false;
+
+        if stripped.trim_start().starts_with('|') {
+            table_rows += 1;
+            // Header + separator + the first few rows stay a real table.
+            if table_rows > RELEASE_EXCERPT_TABLE_MAX_ROWS + 2 {
+                dropped_rows = true;
+                continue;
+            }
+        } else {
+            table_rows = 0;
+        }
+        lines.push(stripped.to_owned());
+    }
+    (lines, dropped_rows)
+}
+
+/// Bump whenever the release feed translation source format changes. v6 drops
+/// HTML comments and long table tails from the body; rows whose body reads the
+/// same either way are upgraded from the v5 hash.
+pub(super) const RELEASE_FEED_TRANSLATION_SOURCE_VERSION: u32 = 6;
+
+pub(crate) fn release_feed_translation_source_hash(
+    repo_full_name: &str,
+    title: &str,
+    body: Option<&str>,
+) -> String {
+    release_feed_translation_source_hash_at(
+        RELEASE_FEED_TRANSLATION_SOURCE_VERSION,
+        repo_full_name,
+        title,
+        body,
+    )
+}
+
+/// Hash the same content had under the previous source version. Cache rows
+/// still carrying it translate identical content, so they are upgraded in
+/// place instead of being paid for again.
+pub(super) fn previous_release_feed_translation_source_hash(
+    repo_full_name: &str,
+    title: &str,
+    body: Option<&str>,
+) -> String {
+    release_feed_translation_source_hash_at(
+        RELEASE_FEED_TRANSLATION_SOURCE_VERSION - 1,
+        repo_full_name,
+        title,
+        body,
+    )
+}
+
+pub(super) fn release_feed_translation_source_hash_at(
+    version: u32,
+    repo_full_name: &str,
+    title: &str,
+    body: Option<&str>,
+) -> String {
+    ai::sha256_hex(&format!(
+        "v={version}\nkind=release\nrepo={}\ntitle={}\nbody={}\n",
+        repo_full_name.trim(),
+        title.trim(),
+        body.unwrap_or("").trim(),
+    ))
+}
+
+pub(crate) fn release_feed_body_is_over_limit(body: Option<&str>) -> bool {
+    FeedExcerpt::default().apply(body).1
+}
+
+pub(super) fn release_detail_translation_ready(body: Option<&str>, summary: Option<&str>) -> bool {
+    let body_has_content = body.map(str::trim).is_some_and(|s| !s.is_empty());
+    if !body_has_content {
+        return true;
+    }
+    summary.map(str::trim).is_some_and(|s| !s.is_empty())
+}
+
+#[allow(clippy::too_many_arguments)]
+pub(super) async fn fetch_feed_items(
+    state: &AppState,
+    user_id: &str,
+    cursor: Option<&FeedCursor>,
+    types: FeedTypeSelection,
+    scope: Option<&FeedScope>,
+    repo_filter: &FeedRepoFilter,
+    viewer_login: Option<&str>,
+    limit: i64,
+) -> Result<Vec<FeedRow>, ApiError> {
+    let sql = r#"
+        WITH scoped_visible_repos AS (
+          SELECT *
+          FROM user_release_visible_repos vr
+          WHERE vr.user_id = ?
+            AND (
+              ? = ''
+              OR (? = 'repo' AND lower(vr.full_name) = lower(?))
+              OR (
+                ? = 'repos'
+                AND EXISTS (
+                  SELECT 1
+                  FROM json_each(?)
+                  WHERE lower(json_each.value) = lower(vr.full_name)
+                )
+              )
+              OR (? = 'org' AND lower(vr.owner_login) = lower(?))
+              OR (? = 'mine' AND lower(vr.owner_login) = lower(?))
+            )
+            AND (
+              ? IS NULL
+              OR EXISTS (
+                SELECT 1
+                FROM starred_repos srf
+                WHERE srf.user_id = vr.user_id
+                  AND srf.repo_id = vr.repo_id
+                  AND lower(srf.language) = lower(?)
+              )
+            )
+            AND (
+              ? IS NULL
+              OR EXISTS (
+                SELECT 1
+                FROM starred_repos srf, json_each(COALESCE(srf.topics_json, '[]')) topic
+                WHERE srf.user_id = vr.user_id
+                  AND srf.repo_id = vr.repo_id
+                  AND lower(topic.value) = lower(?)
+              )
+            )
+            AND (
+              ? = 0
+              OR NOT EXISTS (
+                SELECT 1
+                FROM starred_repos srf
+                WHERE srf.user_id = vr.user_id
+                  AND srf.repo_id = vr.repo_id
+                  AND srf.is_archived = 1
+              )
+            )
+        ),
+        items AS (
+          SELECT
+            'release' AS kind,
+            5 AS kind_rank,
+            sort_ts,
+            ts,
+            id_key,
+            entity_id,
+            release_id,
+            release_node_id,
+            repo_full_name,
+            repo_owner_login,
+            owner_avatar_url,
+            repo_description,
+            repo_html_url,
+            open_graph_image_url,
+            uses_custom_open_graph_image,
+            release_tag_name,
+            release_previous_tag_name,
+            title,
+            subtitle,
+            reason,
+            subject_type,
+            html_url,
+            unread,
+            actor_login,
+            actor_avatar_url,
+            actor_html_url,
+            release_body,
+            react_plus1,
+            react_laugh,
+            react_heart,
+            react_hooray,
+            react_rocket,
+            react_eyes,
+            repo_source
+          FROM (
+            SELECT
+              COALESCE(r.published_at, r.created_at, r.updated_at) AS sort_ts,
+              COALESCE(r.published_at, r.created_at, r.updated_at) AS ts,
+              printf('%020d', r.release_id) AS id_key,
+              CAST(r.release_id AS TEXT) AS entity_id,
+              r.release_id AS release_id,
+              r.node_id AS release_node_id,
+              sr.full_name AS repo_full_name,
+              sr.owner_login AS repo_owner_login,
+              sr.owner_avatar_url AS owner_avatar_url,
+              sr.description AS repo_description,
+              sr.html_url AS repo_html_url,
+              sr.open_graph_image_url AS open_graph_image_url,
+              sr.uses_custom_open_graph_image AS uses_custom_open_graph_image,
+              r.tag_name AS release_tag_name,
+              LAG(r.tag_name) OVER (
+                PARTITION BY r.repo_id
+                ORDER BY COALESCE(r.published_at, r.created_at, r.updated_at) ASC, r.release_id ASC
+              ) AS release_previous_tag_name,
+              COALESCE(NULLIF(TRIM(r.name), ''), r.tag_name) AS title,
+              NULL AS subtitle,
+              NULL AS reason,
+              NULL AS subject_type,
+              r.html_url AS html_url,
+              NULL AS unread,
+              NULL AS actor_login,
+              NULL AS actor_avatar_url,
+              NULL AS actor_html_url,
+              r.body AS release_body,
+              r.react_plus1 AS react_plus1,
+              r.react_laugh AS react_laugh,
+              r.react_heart AS react_heart,
+              r.react_hooray AS react_hooray,
+              r.react_rocket AS react_rocket,
+              r.react_eyes AS react_eyes,
+              sr.source AS repo_source
+            FROM repo_releases r
+            JOIN scoped_visible_repos sr
+              ON sr.repo_id = r.repo_id
+            WHERE NOT EXISTS (
+              SELECT 1
+              FROM muted_repos m
+              WHERE m.user_id = sr.user_id
+                AND m.repo_id = r.repo_id
+            )
+          ) rel
+          -- Filter after LAG so previous tags stay those of the full history.
+          WHERE ? = 0
+            OR NOT EXISTS (
+              SELECT 1
+              FROM release_seen rs
+              WHERE rs.user_id = ?
+                AND rs.release_id = rel.release_id
+            )
+          UNION ALL
+          SELECT
+            e.kind AS kind,
+            CASE e.kind
+              WHEN 'announcement' THEN 4
+              WHEN 'release_update' THEN 4
+              WHEN 'repo_forked' THEN 3
+              WHEN 'repo_star_received' THEN 2
+              WHEN 'follower_received' THEN 1
+              ELSE 0
+            END AS kind_rank,
+            e.occurred_at AS sort_ts,
+            e.occurred_at AS ts,
+            e.id AS id_key,
+            e.id AS entity_id,
+            NULL AS release_id,
+            NULL AS release_node_id,
+            e.repo_full_name AS repo_full_name,
+            vr.owner_login AS repo_owner_login,
+            COALESCE(e.repo_owner_avatar_url, ob.owner_avatar_url) AS owner_avatar_url,
+            vr.description AS repo_description,
+            CASE
+              WHEN e.repo_full_name IS NOT NULL
+                THEN COALESCE(vr.html_url, 'https://github.com/' || e.repo_full_name)
+              ELSE NULL
+            END AS repo_html_url,
+            COALESCE(e.repo_open_graph_image_url, ob.open_graph_image_url) AS open_graph_image_url,
+            COALESCE(
+              e.repo_uses_custom_open_graph_image,
+              ob.uses_custom_open_graph_image
+            ) AS uses_custom_open_graph_image,
+            NULL AS release_tag_name,
+            NULL AS release_previous_tag_name,
+            e.title AS title,
+            CASE e.kind
+              WHEN 'announcement' THEN '仓库公告'
+              WHEN 'release_update' THEN 'Release'
+              WHEN 'repo_forked' THEN 'Fork'
+              ELSE NULL
+            END AS subtitle,
+            NULL AS reason,
+            CASE e.kind
+              WHEN 'announcement' THEN 'discussion'
+              WHEN 'release_update' THEN 'release'
+              WHEN 'repo_forked' THEN 'repository'
+              ELSE NULL
+            END AS subject_type,
+            COALESCE(e.html_url, e.actor_html_url, 'https://github.com/' || e.actor_login) AS html_url,
+            NULL AS unread,
+            e.actor_login AS actor_login,
+            e.actor_avatar_url AS actor_avatar_url,
+            e.actor_html_url AS actor_html_url,
+            e.body AS release_body,
+            NULL AS react_plus1,
+            NULL AS react_laugh,
+            NULL AS react_heart,
+            NULL AS react_hooray,
+            NULL AS react_rocket,
+            NULL AS react_eyes,
+            NULL AS repo_source
+          FROM social_activity_events e
+          LEFT JOIN owned_repo_star_baselines ob
+            ON ob.user_id = e.user_id AND ob.repo_id = e.repo_id
+          LEFT JOIN scoped_visible_repos vr
+            ON vr.repo_id = e.repo_id
+          WHERE e.user_id = ?
+            AND (? = 0 OR (e.repo_full_name IS NOT NULL AND vr.repo_id IS NOT NULL))
+        )
+        SELECT
+          i.kind, i.sort_ts, i.ts, i.id_key, i.entity_id, i.release_id, i.release_node_id,
+          i.repo_full_name, i.repo_owner_login, i.owner_avatar_url, i.repo_description, i.repo_html_url,
+          i.open_graph_image_url, i.uses_custom_open_graph_image,
+          i.release_tag_name, i.release_previous_tag_name,
+          i.title, i.subtitle, i.reason, i.subject_type, i.html_url, i.unread,
+          i.actor_login, i.actor_avatar_url, i.actor_html_url,
+          i.release_body, i.react_plus1, i.react_laugh, i.react_heart, i.react_hooray, i.react_rocket, i.react_eyes,
+          i.repo_source,
+          t.source_hash AS trans_source_hash,
+          t.status AS trans_status,
+          t.title AS trans_title,
+          t.summary AS trans_summary,
+          t.error_text AS trans_error_text,
+          tw.status AS trans_work_status,
+          dt.source_hash AS detail_trans_source_hash,
+          dt.status AS detail_trans_status,
+          dt.title AS detail_trans_title,
+          dt.summary AS detail_trans_summary,
+          dt.error_text AS detail_trans_error_text,
+          dtw.status AS detail_trans_work_status,
+          s.source_hash AS smart_source_hash,
+          s.status AS smart_status,
+          s.title AS smart_title,
+          s.summary AS smart_summary,
+          s.error_text AS smart_error_text,
+          sw.status AS smart_work_status,
+          bm.id AS bookmark_id
+        FROM items i
+        LEFT JOIN ai_translations t
+          ON t.user_id = ? AND t.entity_type = 'release' AND t.entity_id = i.entity_id AND t.lang = 'zh-CN' AND t.status IN ('ready', 'disabled', 'missing', 'error')
+        LEFT JOIN translation_work_items tw
+          ON tw.id = t.active_work_item_id
+        LEFT JOIN ai_translations dt
+          ON dt.user_id = ? AND dt.entity_type = 'release_detail' AND dt.entity_id = i.entity_id AND dt.lang = 'zh-CN' AND dt.status IN ('ready', 'disabled', 'missing', 'error')
+        LEFT JOIN translation_work_items dtw
+          ON dtw.id = dt.active_work_item_id
+        LEFT JOIN ai_translations s
+          ON s.user_id = ? AND s.entity_type = 'release_smart' AND s.entity_id = i.entity_id AND s.lang = 'zh-CN' AND s.status IN ('ready', 'disabled', 'missing', 'error')
+        LEFT JOIN translation_work_items sw
+          ON sw.id = s.active_work_item_id
+        LEFT JOIN bookmarks bm
+          ON bm.user_id = ? AND bm.kind = i.kind AND bm.entity_id = i.entity_id
+        WHERE (
+          (? = 1 AND i.kind = 'release')
+          OR (? = 1 AND i.kind = 'repo_star_received')
+          OR (? = 1 AND i.kind = 'follower_received')
+          OR (? = 1 AND i.kind IN ('announcement', 'release_update', 'repo_forked'))
+        )
+          AND (
+            ? = 0
+            OR i.sort_ts < ?
+            OR (i.sort_ts = ? AND i.kind_rank < ?)
+            OR (i.sort_ts = ? AND i.kind_rank = ? AND i.id_key < ?)
+          )
+        ORDER BY i.sort_ts DESC, i.kind_rank DESC, i.id_key DESC
+        LIMIT ?
+    "#;
+
+    let has_cursor = cursor.is_some();
+    let cursor = cursor.cloned();
+    let scope_kind = match scope {
+        Some(FeedScope::Repo { .. }) => "repo",
+        Some(FeedScope::Repos { .. }) => "repos",
+        Some(FeedScope::Org { .. }) => "org",
+        Some(FeedScope::Mine) => "mine",
+        None => "",
+    };
+    let scope_repo_item = match scope {
+        Some(FeedScope::Repo { owner, repo }) => Some(format!("{owner}/{repo}")),
+        _ => None,
+    };
+    let scope_repo_items_json = match scope {
+        Some(FeedScope::Repos { items }) => {
+            Some(serde_json::to_string(items).map_err(ApiError::internal)?)
+        }
+        _ => None,
+    };
+    let scope_org = match scope {
+        Some(FeedScope::Org { org }) => Some(org.clone()),
+        _ => None,
+    };
+    let scope_mine_owner = if matches!(scope, Some(FeedScope::Mine)) {
+        Some(viewer_login.unwrap_or("").to_owned())
+    } else {
+        None
+    };
+    let scoped_all = scope.is_some();
+    // Social events and followers carry no starred repo metadata, so a repo
+    // filter limits them like a scope does.
+    let repo_filtered = repo_filter.is_active();
+
+    let qy = sqlx::query_as::<_, FeedRow>(sql)
+        .bind(user_id)
+        .bind(scope_kind)
+        .bind(scope_kind)
+        .bind(scope_repo_item.as_deref())
+        .bind(scope_kind)
+        .bind(scope_repo_items_json.as_deref())
+        .bind(scope_kind)
+        .bind(scope_org.as_deref())
+        .bind(scope_kind)
+        .bind(scope_mine_owner.as_deref())
+        .bind(repo_filter.language.as_deref())
+        .bind(repo_filter.language.as_deref())
+        .bind(repo_filter.topic.as_deref())
+        .bind(repo_filter.topic.as_deref())
+        .bind(if repo_filter.exclude_archived {
+            1_i64
+        } else {
+            0_i64
+        })
+        .bind(if types.unseen_only { 1_i64 } else { 0_i64 })
+        .bind(user_id)
+        .bind(user_id)
+        .bind(if scoped_all || repo_filtered {
+            1_i64
+        } else {
+            0_i64
+        })
+        .bind(user_id)
+        .bind(user_id)
+        .bind(user_id)
+        .bind(user_id);
+    qy.bind(if scoped_all || types.releases || types.unseen_only {
+        1_i64
+    } else {
+        0_i64
+    })
+    .bind(if types.stars && !types.unseen_only {
+        1_i64
+    } else {
+        0_i64
+    })
+    .bind(if scoped_all || repo_filtered || types.unseen_only {
+        0_i64
+    } else if types.followers {
+        1_i64
+    } else {
+        0_i64
+    })
+    .bind(if (scoped_all || types.ambient) && !types.unseen_only {
+        1_i64
+    } else {
+        0_i64
+    })
+    .bind(if has_cursor { 1_i64 } else { 0_i64 })
+    .bind(cursor.as_ref().map(|c| c.sort_ts.as_str()))
+    .bind(cursor.as_ref().map(|c| c.sort_ts.as_str()))
+    .bind(cursor.as_ref().map(|c| c.kind_rank))
+    .bind(cursor.as_ref().map(|c| c.sort_ts.as_str()))
+    .bind(cursor.as_ref().map(|c| c.kind_rank))
+    .bind(cursor.as_ref().map(|c| c.id_key.as_str()))
+    .bind(limit)
+    .fetch_all(&state.pool)
+    .await
+    .map_err(ApiError::internal)
+}
+
+pub(super) async fn fetch_visible_release_reaction_rows(
+    state: &AppState,
+    user_id: &str,
+    release_ids: &[i64],
+) -> Result<Vec<ReleaseReactionRow>, ApiError> {
+    if release_ids.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let placeholders = (0..release_ids.len())
+        .map(|_| "?")
+        .collect::<Vec<_>>()
+        .join(", ");
+    let sql = format!(
+        r#"
+        SELECT rr.release_id, rr.node_id
+        FROM repo_releases rr
+        JOIN user_release_visible_repos sr
+          ON sr.user_id = ? AND sr.repo_id = rr.repo_id
+        WHERE rr.release_id IN ({placeholders})
+        "#
+    );
+
+    let mut query = sqlx::query_as::<_, ReleaseReactionRow>(&sql).bind(user_id);
+    for release_id in release_ids {
+        query = query.bind(release_id);
+    }
+    query
+        .fetch_all(&state.pool)
+        .await
+        .map_err(ApiError::internal)
+}
+
+pub(super) fn release_counts_from_row(r: &FeedRow) -> ReleaseReactionCounts {
+    ReleaseReactionCounts {
+        plus1: r.react_plus1.unwrap_or(0),
+        laugh: r.react_laugh.unwrap_or(0),
+        heart: r.react_heart.unwrap_or(0),
+        hooray: r.react_hooray.unwrap_or(0),
+        rocket: r.react_rocket.unwrap_or(0),
+        eyes: r.react_eyes.unwrap_or(0),
+    }
+}
+
+pub(super) fn release_reactions_status(r: &FeedRow) -> &'static str {
+    if r.release_node_id
+        .as_deref()
+        .map(|s| s.trim())
+        .filter(|s| !s.is_empty())
+        .is_none()
+    {
+        "sync_required"
+    } else {
+        "ready"
+    }
+}
+
+pub(super) const SMART_NO_VALUABLE_VERSION_INFO: &str = "no_valuable_version_info";
+pub(super) const RELEASE_FEED_MARKDOWN_MISMATCH_ERROR: &str =
+    "release translation failed to preserve markdown structure";
+pub(super) const RELEASE_DETAIL_MARKDOWN_MISMATCH_ERROR: &str =
+    "release detail translation failed to preserve markdown structure";
+
+pub(super) fn translation_error_metadata(
+    error_text: Option<&str>,
+) -> (Option<String>, Option<String>, Option<String>) {
+    let classified = crate::translations::classify_translation_error(error_text);
+    (
+        classified.as_ref().map(|value| value.code.to_owned()),
+        classified.as_ref().map(|value| value.summary.to_owned()),
+        classified.map(|value| value.detail),
+    )
+}
+
+pub(super) fn translated_item(
+    status: &str,
+    title: Option<String>,
+    summary: Option<String>,
+    auto_translate: Option<bool>,
+    error_text: Option<&str>,
+) -> TranslatedItem {
+    let (error_code, error_summary, error_detail) = translation_error_metadata(error_text);
+    TranslatedItem {
+        lang: "zh-CN".to_owned(),
+        status: status.to_owned(),
+        title,
+        summary,
+        error_code,
+        error_summary,
+        error_detail,
+        auto_translate,
+        mode: None,
+        detected_lang: None,
+    }
+}
+
+/// Source-language label for a release, judged on the full title and body
+/// with the same threshold that decides whether translation is skipped.
+pub(super) fn release_detected_lang(state: &AppState, title: &str, body: &str) -> Option<String> {
+    crate::lang_detect::detect_source_lang(
+        format!("{title}\n{body}").as_str(),
+        state.config.ai_skip_translation_cjk_percent,
+    )
+    .map(str::to_owned)
+}
+
+pub(super) fn smart_item(
+    status: &str,
+    title: Option<String>,
+    summary: Option<String>,
+    auto_translate: Option<bool>,
+    error_text: Option<&str>,
+) -> SmartItem {
+    let (error_code, error_summary, error_detail) = translation_error_metadata(error_text);
+    SmartItem {
+        lang: "zh-CN".to_owned(),
+        status: status.to_owned(),
+        title,
+        summary,
+        error_code,
+        error_summary,
+        error_detail,
+        auto_translate,
+    }
+}
+
+pub(super) fn translated_terminal_item(
+    status: &str,
+    error_text: Option<&str>,
+) -> Option<TranslatedItem> {
+    match status {
+        "disabled" => Some(translated_item("disabled", None, None, None, None)),
+        "error" if crate::translations::release_translation_error_is_retryable(error_text) => {
+            Some(translated_missing_item(true))
+        }
+        "missing" | "error" => Some(translated_item(
+            status,
+            None,
+            None,
+            Some(false),
+            if status == "error" { error_text } else { None },
+        )),
+        _ => None,
+    }
+}
+
+pub(super) fn translated_ready_item(
+    raw_title: Option<String>,
+    raw_summary: Option<String>,
+    auto_translate: Option<bool>,
+) -> Option<TranslatedItem> {
+    let (title, summary) = normalize_translation_fields(raw_title, raw_summary);
+    if title.is_none() && summary.is_none() {
+        return None;
+    }
+    Some(translated_item(
+        "ready",
+        title,
+        summary,
+        auto_translate,
+        None,
+    ))
+}
+
+pub(super) fn translated_release_feed_ready_item(
+    raw_title: Option<String>,
+    raw_summary: Option<String>,
+    body: Option<&str>,
+    auto_translate: Option<bool>,
+) -> Option<TranslatedItem> {
+    let (mut title, mut summary) = normalize_translation_fields(raw_title, raw_summary);
+    let mut status = "ready";
+    let mut error_text = None;
+    if title.is_none() && summary.is_none() {
+        status = "missing";
+    }
+    if status == "ready"
+        && let (Some(src), Some(s)) = (body, summary.as_deref())
+        && !markdown_structure_preserved(src, s, MarkdownStructureCheck::Relaxed)
+    {
+        status = "error";
+        title = None;
+        summary = None;
+        error_text = Some(RELEASE_FEED_MARKDOWN_MISMATCH_ERROR);
+    }
+
+    Some(translated_item(
+        status,
+        title,
+        summary,
+        if status == "error" {
+            Some(false)
+        } else {
+            auto_translate
+        },
+        error_text,
+    ))
+}
+
+pub(super) fn translated_missing_item(auto_translate: bool) -> TranslatedItem {
+    translated_item(
+        "missing",
+        None,
+        None,
+        if auto_translate { None } else { Some(false) },
+        None,
+    )
+}
+
+pub(super) fn smart_terminal_item(status: &str, error_text: Option<&str>) -> Option<SmartItem> {
+    match status {
+        "disabled" => Some(smart_item("disabled", None, None, None, None)),
+        "missing" if error_text == Some(SMART_NO_VALUABLE_VERSION_INFO) => {
+            Some(smart_item("insufficient", None, None, Some(false), None))
+        }
+        "error" if smart_error_is_retryable(error_text) => Some(smart_missing_item(Some(true))),
+        "missing" | "error" => Some(smart_item(status, None, None, Some(false), error_text)),
+        _ => None,
+    }
+}
+
+pub(super) fn smart_ready_item(
+    raw_title: Option<String>,
+    raw_summary: Option<String>,
+    auto_translate: Option<bool>,
+) -> Option<SmartItem> {
+    let (title, summary) = normalize_translation_fields(raw_title, raw_summary);
+    summary.as_ref()?;
+    Some(smart_item("ready", title, summary, auto_translate, None))
+}
+
+pub(super) fn smart_missing_item(auto_translate: Option<bool>) -> SmartItem {
+    smart_item("missing", None, None, auto_translate, None)
+}
+
+pub(super) fn smart_error_is_retryable(error_text: Option<&str>) -> bool {
+    crate::translations::release_translation_error_is_retryable(error_text)
+}
+
+pub(super) fn feed_item_source(repo_source: Option<&str>) -> Option<String> {
+    repo_source.map(|source| {
+        if source == "followed" {
+            "followed"
+        } else {
+            "starred"
+        }
+        .to_owned()
+    })
+}
+
+pub(super) fn feed_item_from_row(
+    r: FeedRow,
+    ai_enabled: bool,
+    cached_reactions: Option<&CachedReleaseReactions>,
+    excerpt: FeedExcerpt,
+) -> FeedItem {
+    let actor = r.actor_login.as_ref().map(|login| FeedActor {
+        login: login.clone(),
+        avatar_url: r.actor_avatar_url.clone(),
+        html_url: r.actor_html_url.clone(),
+    });
+    let repo_owner_avatar_url = normalize_visual_url(r.owner_avatar_url.clone());
+    let repo_visual = repo_visual_from_parts(
+        r.owner_avatar_url.clone(),
+        r.open_graph_image_url.clone(),
+        r.uses_custom_open_graph_image.unwrap_or(0) != 0,
+    );
+
+    if r.kind != "release" {
+        let (body, body_truncated) = match r.kind.as_str() {
+            "announcement" => excerpt.apply(r.release_body.as_deref()),
+            _ => (None, false),
+        };
+        return FeedItem {
+            kind: r.kind,
+            ts: r.ts,
+            id: r.entity_id,
+            repo_full_name: r.repo_full_name,
+            repo_owner_login: r.repo_owner_login,
+            repo_owner_avatar_url,
+            repo_description: r.repo_description,
+            repo_html_url: r.repo_html_url,
+            repo_visual,
+            title: r.title,
+            body,
+            body_truncated,
+            subtitle: r.subtitle,
+            reason: r.reason,
+            subject_type: r.subject_type,
+            html_url: r.html_url,
+            unread: r.unread,
+            actor,
+            source: None,
+            translated: None,
+            smart: None,
+            reactions: None,
+            bookmarked: r.bookmark_id.is_some(),
+            bookmark_id: r.bookmark_id,
+            assets_count: None,
+        };
+    }
+
+    // Translation state is always judged against the default excerpt; the
+    // requested excerpt only decides what `body` the client gets back.
+    let (body, body_truncated) = match r.kind.as_str() {
+        "release" => FeedExcerpt::default().apply(r.release_body.as_deref()),
+        _ => (None, false),
+    };
+    let (display_body, display_body_truncated) = if excerpt == FeedExcerpt::default() {
+        (body.clone(), body_truncated)
+    } else {
+        excerpt.apply(r.release_body.as_deref())
+    };
+
+    let smart_current_hash = match r.kind.as_str() {
+        "release" => crate::translations::release_smart_feed_source_hash(
+            r.entity_id.as_str(),
+            r.repo_full_name.as_deref().unwrap_or(""),
+            r.title.as_deref().unwrap_or(""),
+            body.as_deref(),
+            r.release_tag_name.as_deref().unwrap_or(""),
+            r.release_previous_tag_name.as_deref(),
+        ),
+        _ => String::new(),
+    };
+
+    let translated = if !ai_enabled {
+        Some(translated_item("disabled", None, None, None, None))
+    } else {
+        let current_hash = release_feed_translation_source_hash(
+            r.repo_full_name.as_deref().unwrap_or(""),
+            r.title.as_deref().unwrap_or(""),
+            body.as_deref(),
+        );
+        let detail_current_hash = release_detail_source_hash(
+            r.repo_full_name.as_deref().unwrap_or(""),
+            r.title.as_deref().unwrap_or(""),
+            r.release_body.as_deref().unwrap_or(""),
+        );
+        let refresh_in_flight = r.trans_source_hash.as_deref() != Some(current_hash.as_str())
+            && r.trans_status.as_deref() == Some("ready")
+            && matches!(
+                r.trans_work_status.as_deref(),
+                Some("queued" | "batched" | "running")
+            );
+        let detail_refresh_in_flight = r.detail_trans_source_hash.as_deref()
+            != Some(detail_current_hash.as_str())
+            && r.detail_trans_status.as_deref() == Some("ready")
+            && matches!(
+                r.detail_trans_work_status.as_deref(),
+                Some("queued" | "batched" | "running")
+            );
+        if r.detail_trans_source_hash.as_deref() == Some(detail_current_hash.as_str()) {
+            if let Some(status) = r.detail_trans_status.as_deref()
+                && status != "ready"
+            {
+                if !body_truncated
+                    && matches!(status, "missing" | "error")
+                    && r.trans_source_hash.as_deref() == Some(current_hash.as_str())
+                    && r.trans_status.as_deref() == Some("ready")
+                {
+                    translated_release_feed_ready_item(
+                        r.trans_title.clone(),
+                        r.trans_summary.clone(),
+                        body.as_deref(),
+                        None,
+                    )
+                } else {
+                    translated_terminal_item(status, r.detail_trans_error_text.as_deref())
+                }
+            } else {
+                let (title, summary) = normalize_translation_fields(
+                    r.detail_trans_title.clone(),
+                    r.detail_trans_summary.clone(),
+                );
+                if !release_detail_translation_ready(r.release_body.as_deref(), summary.as_deref())
+                {
+                    if !body_truncated
+                        && r.trans_source_hash.as_deref() == Some(current_hash.as_str())
+                        && r.trans_status.as_deref() == Some("ready")
+                    {
+                        translated_release_feed_ready_item(
+                            r.trans_title.clone(),
+                            r.trans_summary.clone(),
+                            body.as_deref(),
+                            None,
+                        )
+                    } else {
+                        Some(translated_item(
+                            "error",
+                            None,
+                            None,
+                            Some(false),
+                            Some(RELEASE_DETAIL_MARKDOWN_MISMATCH_ERROR),
+                        ))
+                    }
+                } else {
+                    Some(translated_item("ready", title, summary, None, None))
+                }
+            }
+        } else if detail_refresh_in_flight {
+            translated_ready_item(
+                r.detail_trans_title.clone(),
+                r.detail_trans_summary.clone(),
+                Some(true),
+            )
+            .or_else(|| Some(translated_missing_item(false)))
+        } else if r.trans_source_hash.as_deref() == Some(current_hash.as_str()) {
+            if let Some(status) = r.trans_status.as_deref()
+                && status != "ready"
+            {
+                if matches!(status, "disabled" | "error") {
+                    translated_terminal_item(status, r.trans_error_text.as_deref())
+                } else {
+                    Some(translated_missing_item(true))
+                }
+            } else {
+                translated_release_feed_ready_item(
+                    r.trans_title.clone(),
+                    r.trans_summary.clone(),
+                    body.as_deref(),
+                    None,
+                )
+            }
+        } else if refresh_in_flight {
+            translated_ready_item(r.trans_title.clone(), r.trans_summary.clone(), Some(true))
+                .or_else(|| Some(translated_missing_item(false)))
+        } else {
+            Some(translated_missing_item(true))
+        }
+    };
+
+    let smart = if !ai_enabled {
+        Some(smart_item("disabled", None, None, None, None))
+    } else {
+        let refresh_in_flight = r.smart_source_hash.as_deref() != Some(smart_current_hash.as_str())
+            && r.smart_status.as_deref() == Some("ready")
+            && matches!(
+                r.smart_work_status.as_deref(),
+                Some("queued" | "batched" | "running")
+            );
+        if r.smart_source_hash.as_deref() == Some(smart_current_hash.as_str()) {
+            if let Some(status) = r.smart_status.as_deref()
+                && status != "ready"
+            {
+                smart_terminal_item(status, r.smart_error_text.as_deref())
+            } else {
+                let ready = smart_ready_item(r.smart_title.clone(), r.smart_summary.clone(), None);
+                if ready.is_none() {
+                    Some(smart_missing_item(None))
+                } else {
+                    ready
+                }
+            }
+        } else if refresh_in_flight {
+            smart_ready_item(r.smart_title.clone(), r.smart_summary.clone(), Some(true))
+                .or_else(|| Some(smart_missing_item(Some(false))))
+        } else {
+            Some(smart_missing_item(None))
+        }
+    };
+
+    let mut status = release_reactions_status(&r);
+    let counts = release_counts_from_row(&r);
+    let mut viewer = ReleaseReactionViewer::default();
+    if let Some(cached) = cached_reactions
+        && status == "ready"
+    {
+        viewer = cached.viewer.clone();
+        if !cached.is_fresh {
+            status = "stale";
+        }
+    }
+
+    FeedItem {
+        kind: r.kind,
+        ts: r.ts,
+        id: r.entity_id,
+        repo_full_name: r.repo_full_name,
+        repo_owner_login: r.repo_owner_login,
+        repo_owner_avatar_url,
+        repo_description: r.repo_description,
+        repo_html_url: r.repo_html_url,
+        repo_visual,
+        title: r.title,
+        body: display_body,
+        body_truncated: display_body_truncated,
+        subtitle: r.subtitle,
+        reason: r.reason,
+        subject_type: r.subject_type,
+        html_url: r.html_url,
+        unread: r.unread,
+        actor: None,
+        source: feed_item_source(r.repo_source.as_deref()),
+        translated,
+        smart,
+        reactions: Some(ReleaseReactions {
+            counts,
+            viewer,
+            status: status.to_owned(),
+        }),
+        bookmarked: r.bookmark_id.is_some(),
+        bookmark_id: r.bookmark_id,
+        assets_count: None,
+    }
+}
+
+pub async fn list_feed(
+    state: State<Arc<AppState>>,
+    user: AuthedUser,
+    Query(q): Query<FeedQuery>,
+) -> Result<Response, ApiError> {
+    let fields = FeedFields::from_query(q.fields.as_deref())?;
+    let Json(page) = list_feed_page(state, user, Query(q)).await?;
+    Ok(match fields {
+        FeedFields::Full => Json(page).into_response(),
+        FeedFields::Basic => Json(FeedBasicResponse::from(page)).into_response(),
+    })
+}
+
+/// The full-field feed page behind [`list_feed`].
+pub(super) async fn list_feed_page(
+    State(state): State<Arc<AppState>>,
+    AuthedUser(user_id): AuthedUser,
+    Query(q): Query<FeedQuery>,
+) -> Result<Json<FeedResponse>, ApiError> {
+    let started_at = Instant::now();
+    let viewer = load_viewer_user(state.as_ref(), &user_id).await?;
+    let mut types = parse_feed_types(q.types.as_deref())?;
+    types.unseen_only = q.unseen_only.unwrap_or(false);
+    let scope = parse_feed_scope(q.scope.as_deref(), q.items.as_deref(), q.org.as_deref())?;
+
+    let excerpt = FeedExcerpt::from_query(q.excerpt.as_deref(), q.excerpt_lines, q.excerpt_chars)?;
+    let limit = q.limit.unwrap_or(30).clamp(1, 100);
+    let cursor = q.cursor.as_deref().map(str::trim).filter(|s| !s.is_empty());
+    let feed_cursor = match cursor {
+        Some(c) => Some(FeedCursor::decode(c)?),
+        None => None,
+    };
+
+    let db_started_at = Instant::now();
+    let repo_filter = FeedRepoFilter::from_query(&q);
+    let rows = fetch_feed_items(
+        state.as_ref(),
+        &user_id,
+        feed_cursor.as_ref(),
+        types,
+        scope.as_ref(),
+        &repo_filter,
+        Some(viewer.login.as_str()),
+        limit,
+    )
+    .await?;
+    let page = feed_page_from_rows(state.as_ref(), &user_id, rows, limit, excerpt).await?;
+    if q.mark_seen.unwrap_or(false) && feed_cursor.is_none() {
+        touch_feed_last_seen_at(state.as_ref(), &user_id).await?;
+    }
+    let db_elapsed = db_started_at.elapsed();
+    let sync_required = page
+        .items
+        .iter()
+        .filter(|item| {
+            item.reactions
+                .as_ref()
+                .is_some_and(|reactions| reactions.status == "sync_required")
+        })
+        .count();
+    if sync_required > FEED_NODE_ID_BACKFILL_THRESHOLD {
+        enqueue_release_node_id_backfill_best_effort(state.as_ref(), &user_id).await;
+    }
+    let (translation_hits, translation_misses) = page
+        .items
+        .iter()
+        .filter_map(|item| item.translated.as_ref())
+        .fold((0, 0), |(hits, misses), translated| {
+            match translated.status.as_str() {
+                "ready" => (hits + 1, misses),
+                "missing" => (hits, misses + 1),
+                _ => (hits, misses),
+            }
+        });
+    observability::record_cache_lookups("feed_translation", translation_hits, translation_misses);
+
+    tracing::info!(
+        db_ms = db_elapsed.as_millis() as u64,
+        total_ms = started_at.elapsed().as_millis() as u64,
+        item_count = page.items.len(),
+        "feed hot path served from local cache"
+    );
+
+    Ok(Json(page))
+}
+
+/// A feed page with more `sync_required` releases than this queues a node id
+/// backfill for the viewer.
+pub(super) const FEED_NODE_ID_BACKFILL_THRESHOLD: usize = 5;
+/// Releases a backfill could not resolve stay `sync_required`; without a
+/// cooldown every feed load would queue another run for them.
+pub(super) const FEED_NODE_ID_BACKFILL_COOLDOWN: chrono::Duration = chrono::Duration::hours(6);
+
+pub(super) async fn enqueue_release_node_id_backfill_best_effort(state: &AppState, user_id: &str) {
+    let since = (chrono::Utc::now() - FEED_NODE_ID_BACKFILL_COOLDOWN).to_rfc3339();
+    let recent = sqlx::query_scalar::<_, i64>(
+        r#"
+        SELECT COUNT(*)
+        FROM job_tasks
+        WHERE task_type = ?
+          AND requested_by = ?
+          AND created_at >= ?
+        "#,
+    )
+    .bind(jobs::TASK_SYNC_RELEASE_NODE_IDS)
+    .bind(user_id)
+    .bind(since.as_str())
+    .fetch_one(&state.pool)
+    .await;
+    match recent {
+        Ok(0) => {}
+        Ok(_) => return,
+        Err(err) => {
+            tracing::warn!(?err, user_id, "feed: check recent node id backfill failed");
+            return;
+        }
+    }
+
+    if let Err(err) = jobs::enqueue_singleton_task_for_requester(
+        state,
+        jobs::NewTask {
+            task_type: jobs::TASK_SYNC_RELEASE_NODE_IDS.to_owned(),
+            payload: json!({ "user_id": user_id }),
+            source: "api.feed.node_id_backfill".to_owned(),
+            requested_by: Some(user_id.to_owned()),
+            parent_task_id: None,
+            priority: jobs::TaskPriority::Low,
+        },
+    )
+    .await
+    {
+        tracing::warn!(?err, user_id, "feed: enqueue node id backfill failed");
+    }
+}
+
+/// Turns feed-shaped rows into response items with cached reactions and
+/// translation state, plus the cursor for the next page.
+pub(super) async fn feed_page_from_rows(
+    state: &AppState,
+    user_id: &str,
+    rows: Vec<FeedRow>,
+    limit: i64,
+    excerpt: FeedExcerpt,
+) -> Result<FeedResponse, ApiError> {
+    let release_ids = rows
+        .iter()
+        .filter(|r| r.kind == "release")
+        .filter_map(|r| r.release_id)
+        .collect::<Vec<_>>();
+    let cached_reactions = load_cached_release_reactions(state, user_id, &release_ids).await?;
+    let asset_counts = load_release_asset_counts(state, &release_ids).await?;
+    let ai_enabled = state.config.ai.is_some();
+    let opted_out = if ai_enabled {
+        load_translation_opted_out_release_ids(state, user_id, &release_ids).await?
+    } else {
+        HashSet::new()
+    };
+
+    let mut items = Vec::with_capacity(rows.len());
+    let mut next_cursor: Option<String> = None;
+    for (idx, r) in rows.into_iter().enumerate() {
+        if idx == limit.saturating_sub(1) as usize {
+            next_cursor = Some(FeedCursor::from_row(&r).encode());
+        }
+        let cached = r.release_id.and_then(|id| cached_reactions.get(&id));
+        let detected_lang = (ai_enabled && r.kind == "release")
+            .then(|| {
+                release_detected_lang(
+                    state,
+                    r.title.as_deref().unwrap_or(""),
+                    r.release_body.as_deref().unwrap_or(""),
+                )
+            })
+            .flatten();
+        let release_id = r.release_id.filter(|_| r.kind == "release");
+        let mut item = feed_item_from_row(r, ai_enabled, cached, excerpt);
+        if release_id.is_some_and(|id| opted_out.contains(&id)) {
+            if item.translated.is_some() {
+                item.translated = Some(translated_item("opted_out", None, None, Some(false), None));
+            }
+            if item.smart.is_some() {
+                item.smart = Some(smart_item("opted_out", None, None, Some(false), None));
+            }
+        }
+        if let Some(translated) = item.translated.as_mut() {
+            translated.detected_lang = detected_lang;
+        }
+        item.assets_count = release_id.map(|id| asset_counts.get(&id).copied().unwrap_or_default());
+        items.push(item);
+    }
+
+    // If we returned fewer than limit, there's no next page.
+    if items.len() < limit as usize {
+        next_cursor = None;
+    }
+
+    Ok(FeedResponse { items, next_cursor })
+}
+
+/// Releases on the page whose repo the viewer opted out of translation.
+pub(super) async fn load_translation_opted_out_release_ids(
+    state: &AppState,
+    user_id: &str,
+    release_ids: &[i64],
+) -> Result<HashSet<i64>, ApiError> {
+    if release_ids.is_empty() {
+        return Ok(HashSet::new());
+    }
+
+    let placeholders = (0..release_ids.len())
+        .map(|_| "?")
+        .collect::<Vec<_>>()
+        .join(", ");
+    let sql = format!(
+        r#"
+        SELECT r.release_id
+        FROM repo_releases r
+        JOIN repo_translation_opt_outs o
+          ON o.repo_id = r.repo_id
+         AND o.user_id = ?
+        WHERE r.release_id IN ({placeholders})
+        "#
+    );
+    let mut query = sqlx::query_scalar::<_, i64>(&sql).bind(user_id);
+    for release_id in release_ids {
+        query = query.bind(release_id);
+    }
+    let rows = query
+        .fetch_all(&state.pool)
+        .await
+        .map_err(ApiError::internal)?;
+    Ok(rows.into_iter().collect())
+}
+
+#[derive(Debug, Deserialize)]
+pub struct RepoReleasesQuery {
+    pub(super) cursor: Option<String>,
+    pub(super) limit: Option<i64>,
+}
+
+#[derive(Debug, Serialize, sqlx::FromRow)]
+pub struct RepoReleasesHeader {
+    pub(super) repo_id: i64,
+    pub(super) full_name: String,
+    pub(super) description: Option<String>,
+    pub(super) html_url: String,
+    pub(super) stargazed_at: Option<String>,
+    pub(super) latest_tag: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct RepoReleasesResponse {
+    pub(super) repo: RepoReleasesHeader,
+    pub(super) items: Vec<FeedItem>,
+    pub(super) next_cursor: Option<String>,
+}
+
+pub async fn list_repo_releases(
+    State(state): State<Arc<AppState>>,
+    AuthedUser(user_id): AuthedUser,
+    Path(repo_id): Path<String>,
+    Query(q): Query<RepoReleasesQuery>,
+) -> Result<Json<RepoReleasesResponse>, ApiError> {
+    let repo_id = parse_repo_id_param(&repo_id)?;
+    let limit = q.limit.unwrap_or(30).clamp(1, 100);
+    let cursor = match q.cursor.as_deref().map(str::trim).filter(|s| !s.is_empty()) {
+        Some(c) => Some(FeedCursor::decode(c)?),
+        None => None,
+    };
+
+    let repo = sqlx::query_as::<_, RepoReleasesHeader>(
+        r#"
+        SELECT
+          sr.repo_id,
+          sr.full_name,
+          sr.description,
+          sr.html_url,
+          sr.stargazed_at,
+          (
+            SELECT r.tag_name
+            FROM repo_releases r
+            WHERE r.repo_id = sr.repo_id
+            ORDER BY COALESCE(r.published_at, r.created_at, r.updated_at) DESC, r.release_id DESC
+            LIMIT 1
+          ) AS latest_tag
+        FROM starred_repos sr
+        WHERE sr.user_id = ? AND sr.repo_id = ?
+        LIMIT 1
+        "#,
+    )
+    .bind(user_id.as_str())
+    .bind(repo_id)
+    .fetch_optional(&state.pool)
+    .await
+    .map_err(ApiError::internal)?
+    .ok_or_else(|| {
+        ApiError::new(
+            StatusCode::NOT_FOUND,
+            codes::REPO_NOT_FOUND,
+            "repository is not in your starred repositories",
+        )
+    })?;
+
+    let rows = fetch_repo_release_rows(
+        state.as_ref(),
+        user_id.as_str(),
+        repo_id,
+        cursor.as_ref(),
+        limit,
+    )
+    .await?;
+    let page = feed_page_from_rows(
+        state.as_ref(),
+        &user_id,
+        rows,
+        limit,
+        FeedExcerpt::default(),
+    )
+    .await?;
+
+    Ok(Json(RepoReleasesResponse {
+        repo,
+        items: page.items,
+        next_cursor: page.next_cursor,
+    }))
+}
+
+/// Release history of one starred repo in feed row shape, newest first.
+pub(super) async fn fetch_repo_release_rows(
+    state: &AppState,
+    user_id: &str,
+    repo_id: i64,
+    cursor: Option<&FeedCursor>,
+    limit: i64,
+) -> Result<Vec<FeedRow>, ApiError> {
+    sqlx::query_as::<_, FeedRow>(
+        r#"
+        WITH items AS (
+          SELECT
+            COALESCE(r.published_at, r.created_at, r.updated_at) AS sort_ts,
+            COALESCE(r.published_at, r.created_at, r.updated_at) AS ts,
+            printf('%020d', r.release_id) AS id_key,
+            CAST(r.release_id AS TEXT) AS entity_id,
+            r.release_id AS release_id,
+            r.node_id AS release_node_id,
+            sr.full_name AS repo_full_name,
+            sr.owner_login AS repo_owner_login,
+            sr.owner_avatar_url AS owner_avatar_url,
+            sr.description AS repo_description,
+            sr.html_url AS repo_html_url,
+            sr.open_graph_image_url AS open_graph_image_url,
+            sr.uses_custom_open_graph_image AS uses_custom_open_graph_image,
+            r.tag_name AS release_tag_name,
+            LAG(r.tag_name) OVER (
+              ORDER BY COALESCE(r.published_at, r.created_at, r.updated_at) ASC, r.release_id ASC
+            ) AS release_previous_tag_name,
+            COALESCE(NULLIF(TRIM(r.name), ''), r.tag_name) AS title,
+            r.html_url AS html_url,
+            r.body AS release_body,
+            r.react_plus1 AS react_plus1,
+            r.react_laugh AS react_laugh,
+            r.react_heart AS react_heart,
+            r.react_hooray AS react_hooray,
+            r.react_rocket AS react_rocket,
+            r.react_eyes AS react_eyes
+          FROM repo_releases r
+          JOIN starred_repos sr
+            ON sr.user_id = ? AND sr.repo_id = r.repo_id
+          WHERE r.repo_id = ?
+        )
+        SELECT
+          'release' AS kind, i.sort_ts, i.ts, i.id_key, i.entity_id, i.release_id, i.release_node_id,
+          i.repo_full_name, i.repo_owner_login, i.owner_avatar_url, i.repo_description, i.repo_html_url,
+          i.open_graph_image_url, i.uses_custom_open_graph_image,
+          i.release_tag_name, i.release_previous_tag_name,
+          i.title, NULL AS subtitle, NULL AS reason, NULL AS subject_type, i.html_url, NULL AS unread,
+          NULL AS actor_login, NULL AS actor_avatar_url, NULL AS actor_html_url,
+          i.release_body, i.react_plus1, i.react_laugh, i.react_heart, i.react_hooray, i.react_rocket, i.react_eyes,
+          'starred' AS repo_source,
+          t.source_hash AS trans_source_hash,
+          t.status AS trans_status,
+          t.title AS trans_title,
+          t.summary AS trans_summary,
+          t.error_text AS trans_error_text,
+          tw.status AS trans_work_status,
+          dt.source_hash AS detail_trans_source_hash,
+          dt.status AS detail_trans_status,
+          dt.title AS detail_trans_title,
+          dt.summary AS detail_trans_summary,
+          dt.error_text AS detail_trans_error_text,
+          dtw.status AS detail_trans_work_status,
+          s.source_hash AS smart_source_hash,
+          s.status AS smart_status,
+          s.title AS smart_title,
+          s.summary AS smart_summary,
+          s.error_text AS smart_error_text,
+          sw.status AS smart_work_status,
+          bm.id AS bookmark_id
+        FROM items i
+        LEFT JOIN ai_translations t
+          ON t.user_id = ? AND t.entity_type = 'release' AND t.entity_id = i.entity_id AND t.lang = 'zh-CN' AND t.status IN ('ready', 'disabled', 'missing', 'error')
+        LEFT JOIN translation_work_items tw
+          ON tw.id = t.active_work_item_id
+        LEFT JOIN ai_translations dt
+          ON dt.user_id = ? AND dt.entity_type = 'release_detail' AND dt.entity_id = i.entity_id AND dt.lang = 'zh-CN' AND dt.status IN ('ready', 'disabled', 'missing', 'error')
+        LEFT JOIN translation_work_items dtw
+          ON dtw.id = dt.active_work_item_id
+        LEFT JOIN ai_translations s
+          ON s.user_id = ? AND s.entity_type = 'release_smart' AND s.entity_id = i.entity_id AND s.lang = 'zh-CN' AND s.status IN ('ready', 'disabled', 'missing', 'error')
+        LEFT JOIN translation_work_items sw
+          ON sw.id = s.active_work_item_id
+        LEFT JOIN bookmarks bm
+          ON bm.user_id = ? AND bm.kind = 'release' AND bm.entity_id = i.entity_id
+        WHERE (
+          ? = 0
+          OR i.sort_ts < ?
+          OR (i.sort_ts = ? AND i.id_key < ?)
+        )
+        ORDER BY i.sort_ts DESC, i.id_key DESC
+        LIMIT ?
+        "#,
+    )
+    .bind(user_id)
+    .bind(repo_id)
+    .bind(user_id)
+    .bind(user_id)
+    .bind(user_id)
+    .bind(user_id)
+    .bind(if cursor.is_some() { 1_i64 } else { 0_i64 })
+    .bind(cursor.map(|c| c.sort_ts.as_str()))
+    .bind(cursor.map(|c| c.sort_ts.as_str()))
+    .bind(cursor.map(|c| c.id_key.as_str()))
+    .bind(limit)
+    .fetch_all(&state.pool)
+    .await
+    .map_err(ApiError::internal)
+}
+
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct MarkFeedSeenRequest {
+    pub(super) release_ids: Vec<String>,
+}
+
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct MarkFeedSeenResponse {
+    pub(super) marked: usize,
+}
+
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct RepoUnseenCountResponse {
+    pub(super) repo_id: i64,
+    pub(super) unseen_count: i64,
+}
+
+/// Marks releases as seen in one statement so a whole feed page can be
+/// acknowledged at once; marking again just refreshes `seen_at`.
+pub async fn mark_feed_seen(
+    State(state): State<Arc<AppState>>,
+    AuthedUser(user_id): AuthedUser,
+    ApiJson(req): ApiJson<MarkFeedSeenRequest>,
+) -> Result<Json<MarkFeedSeenResponse>, ApiError> {
+    let release_ids = parse_unique_release_ids(&req.release_ids, 200)?;
+    let release_ids_json = serde_json::to_string(&release_ids).map_err(ApiError::internal)?;
+    let now = chrono::Utc::now().to_rfc3339();
+
+    sqlx::query(
+        r#"
+        INSERT INTO release_seen (user_id, release_id, seen_at)
+        SELECT ?, CAST(json_each.value AS INTEGER), ?
+        FROM json_each(?)
+        WHERE true
+        ON CONFLICT(user_id, release_id) DO UPDATE SET
+          seen_at = excluded.seen_at
+        "#,
+    )
+    .bind(user_id.as_str())
+    .bind(now.as_str())
+    .bind(release_ids_json.as_str())
+    .execute(&state.pool)
+    .await
+    .map_err(ApiError::internal)?;
+
+    Ok(Json(MarkFeedSeenResponse {
+        marked: release_ids.len(),
+    }))
+}
+
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct CountersResponse {
+    pub(super) unread_notifications: i64,
+    /// Visible releases published after `last_feed_seen_at`; 0 until the
+    /// feed has been marked seen once.
+    pub(super) new_releases_since: i64,
+    pub(super) last_feed_seen_at: Option<String>,
+}
+
+/// Badge counters for polling clients; two COUNT queries instead of a feed
+/// page.
+pub async fn get_counters(
+    State(state): State<Arc<AppState>>,
+    AuthedUser(user_id): AuthedUser,
+) -> Result<Json<CountersResponse>, ApiError> {
+    Ok(Json(load_counters(state.as_ref(), &user_id).await?))
+}
+
+/// Moves the new-release baseline to now and returns the reset counters.
+pub async fn mark_feed_seen_at(
+    State(state): State<Arc<AppState>>,
+    AuthedUser(user_id): AuthedUser,
+) -> Result<Json<CountersResponse>, ApiError> {
+    touch_feed_last_seen_at(state.as_ref(), &user_id).await?;
+    Ok(Json(load_counters(state.as_ref(), &user_id).await?))
+}
+
+pub(super) async fn touch_feed_last_seen_at(
+    state: &AppState,
+    user_id: &str,
+) -> Result<(), ApiError> {
+    // Second precision with a `Z` suffix, so it compares as text against
+    // GitHub's release timestamps.
+    let now = chrono::Utc::now().to_rfc3339_opts(chrono::SecondsFormat::Secs, true);
+    sqlx::query(r#"UPDATE users SET feed_last_seen_at = ? WHERE id = ?"#)
+        .bind(now.as_str())
+        .bind(user_id)
+        .execute(&state.pool)
+        .await
+        .map_err(ApiError::internal)?;
+    Ok(())
+}
+
+pub(super) async fn load_counters(
+    state: &AppState,
+    user_id: &str,
+) -> Result<CountersResponse, ApiError> {
+    let unread_notifications = sqlx::query_scalar::<_, i64>(
+        r#"
+        SELECT COUNT(*)
+        FROM notifications n
+        JOIN users u ON u.id = n.user_id
+        WHERE n.user_id = ?
+          AND n.unread != 0
+          AND n.archived_at IS NULL
+          AND COALESCE(n.reason, '') NOT IN (
+            SELECT value FROM json_each(u.muted_notification_reasons)
+          )
+        "#,
+    )
+    .bind(user_id)
+    .fetch_one(&state.pool)
+    .await
+    .map_err(ApiError::internal)?;
+
+    let last_feed_seen_at = sqlx::query_scalar::<_, Option<String>>(
+        r#"SELECT feed_last_seen_at FROM users WHERE id = ?"#,
+    )
+    .bind(user_id)
+    .fetch_one(&state.pool)
+    .await
+    .map_err(ApiError::internal)?;
+
+    let new_releases_since = match last_feed_seen_at.as_deref() {
+        Some(seen_at) => sqlx::query_scalar::<_, i64>(
+            r#"
+            SELECT COUNT(*)
+            FROM repo_releases r
+            JOIN user_release_visible_repos sr
+              ON sr.user_id = ? AND sr.repo_id = r.repo_id
+            WHERE COALESCE(r.published_at, r.created_at) > ?
+              AND NOT EXISTS (
+                SELECT 1
+                FROM muted_repos m
+                WHERE m.user_id = sr.user_id
+                  AND m.repo_id = r.repo_id
+              )
+            "#,
+        )
+        .bind(user_id)
+        .bind(seen_at)
+        .fetch_one(&state.pool)
+        .await
+        .map_err(ApiError::internal)?,
+        None => 0,
+    };
+
+    Ok(CountersResponse {
+        unread_notifications,
+        new_releases_since,
+        last_feed_seen_at,
+    })
+}
+
+pub async fn get_repo_unseen_release_count(
+    State(state): State<Arc<AppState>>,
+    AuthedUser(user_id): AuthedUser,
+    Path(repo_id): Path<String>,
+) -> Result<Json<RepoUnseenCountResponse>, ApiError> {
+    let repo_id = parse_repo_id_param(&repo_id)?;
+
+    let unseen_count = sqlx::query_scalar::<_, i64>(
+        r#"
+        SELECT COUNT(r.release_id)
+        FROM starred_repos sr
+        LEFT JOIN repo_releases r
+          ON r.repo_id = sr.repo_id
+          AND NOT EXISTS (
+            SELECT 1
+            FROM release_seen rs
+            WHERE rs.user_id = sr.user_id
+              AND rs.release_id = r.release_id
+          )
+        WHERE sr.user_id = ? AND sr.repo_id = ?
+        GROUP BY sr.repo_id
+        "#,
+    )
+    .bind(user_id.as_str())
+    .bind(repo_id)
+    .fetch_optional(&state.pool)
+    .await
+    .map_err(ApiError::internal)?
+    .ok_or_else(|| {
+        ApiError::new(
+            StatusCode::NOT_FOUND,
+            codes::REPO_NOT_FOUND,
+            "repository is not in your starred repositories",
+        )
+    })?;
+
+    Ok(Json(RepoUnseenCountResponse {
+        repo_id,
+        unseen_count,
+    }))
+}
+
+pub(super) const BOOKMARK_NOTE_MAX_CHARS: usize = 2000;
+
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct BookmarksQuery {
+    pub(super) cursor: Option<String>,
+    pub(super) limit: Option<i64>,
+}
+
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct CreateBookmarkRequest {
+    pub(super) kind: String,
+    pub(super) entity_id: String,
+    pub(super) note: Option<String>,
+}
+
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct BookmarkItem {
+    pub(super) id: String,
+    pub(super) note: Option<String>,
+    pub(super) created_at: String,
+    /// False once the release is gone from the local cache; `item` then only
+    /// carries the title/repo/url snapshotted when the bookmark was made.
+    pub(super) release_available: bool,
+    pub(super) item: FeedItem,
+}
+
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct BookmarksResponse {
+    pub(super) items: Vec<BookmarkItem>,
+    pub(super) next_cursor: Option<String>,
+}
+
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct BookmarkDeleteResponse {
+    pub(super) id: String,
+    pub(super) kind: String,
+    pub(super) entity_id: String,
+    pub(super) bookmarked: bool,
+}
+
+#[derive(Debug, sqlx::FromRow)]
+pub(super) struct BookmarkRow {
+    pub(super) note: Option<String>,
+    #[sqlx(flatten)]
+    pub(super) feed: FeedRow,
+}
+
+#[derive(Debug, sqlx::FromRow)]
+pub(super) struct BookmarkSnapshotRow {
+    pub(super) title: Option<String>,
+    pub(super) repo_full_name: Option<String>,
+    pub(super) html_url: Option<String>,
+}
+
+pub(super) fn bookmark_not_found() -> ApiError {
+    ApiError::new(
+        StatusCode::NOT_FOUND,
+        codes::BOOKMARK_NOT_FOUND,
+        "bookmark not found",
+    )
+}
+
+pub(super) fn normalize_bookmark_note(note: Option<&str>) -> Result<Option<String>, ApiError> {
+    let Some(note) = note.map(str::trim).filter(|note| !note.is_empty()) else {
+        return Ok(None);
+    };
+    if note.chars().count() > BOOKMARK_NOTE_MAX_CHARS {
+        return Err(ApiError::bad_request(format!(
+            "note must be at most {BOOKMARK_NOTE_MAX_CHARS} characters"
+        )));
+    }
+    Ok(Some(note.to_owned()))
+}
+
+pub async fn list_bookmarks(
+    State(state): State<Arc<AppState>>,
+    AuthedUser(user_id): AuthedUser,
+    Query(q): Query<BookmarksQuery>,
+) -> Result<Json<BookmarksResponse>, ApiError> {
+    let limit = q.limit.unwrap_or(30).clamp(1, 100);
+    let cursor = match q.cursor.as_deref().map(str::trim).filter(|s| !s.is_empty()) {
+        Some(c) => Some(FeedCursor::decode_bookmark(c)?),
+        None => None,
+    };
+
+    let rows = fetch_bookmark_rows(
+        state.as_ref(),
+        user_id.as_str(),
+        None,
+        cursor.as_ref(),
+        limit,
+    )
+    .await?;
+    let (items, next_cursor) =
+        bookmark_items_from_rows(state.as_ref(), &user_id, rows, limit).await?;
+    Ok(Json(BookmarksResponse { items, next_cursor }))
+}
+
+/// Bookmarks a release the user can see. Bookmarking it again keeps the
+/// original bookmark and only replaces the note and snapshot.
+pub async fn create_bookmark(
+    State(state): State<Arc<AppState>>,
+    AuthedUser(user_id): AuthedUser,
+    ApiJson(req): ApiJson<CreateBookmarkRequest>,
+) -> Result<Json<BookmarkItem>, ApiError> {
+    if req.kind.trim() != "release" {
+        return Err(ApiError::bad_request("kind must be release"));
+    }
+    let release_id = parse_release_id_param(&req.entity_id)?;
+    let note = normalize_bookmark_note(req.note.as_deref())?;
+
+    let snapshot = sqlx::query_as::<_, BookmarkSnapshotRow>(
+        r#"
+        SELECT
+          COALESCE(NULLIF(TRIM(r.name), ''), r.tag_name) AS title,
+          vr.full_name AS repo_full_name,
+          r.html_url AS html_url
+        FROM repo_releases r
+        JOIN user_release_visible_repos vr
+          ON vr.user_id = ? AND vr.repo_id = r.repo_id
+        WHERE r.release_id = ?
+        LIMIT 1
+        "#,
+    )
+    .bind(user_id.as_str())
+    .bind(release_id)
+    .fetch_optional(&state.pool)
+    .await
+    .map_err(ApiError::internal)?
+    .ok_or_else(|| ApiError::new(StatusCode::NOT_FOUND, codes::NOT_FOUND, "release not found"))?;
+
+    let now = chrono::Utc::now().to_rfc3339();
+    let bookmark_id = sqlx::query_scalar::<_, String>(
+        r#"
+        INSERT INTO bookmarks (
+          id, user_id, kind, entity_id, note,
+          snapshot_title, snapshot_repo_full_name, snapshot_html_url,
+          created_at, updated_at
+        )
+        VALUES (?, ?, 'release', ?, ?, ?, ?, ?, ?, ?)
+        ON CONFLICT(user_id, kind, entity_id) DO UPDATE SET
+          note = excluded.note,
+          snapshot_title = excluded.snapshot_title,
+          snapshot_repo_full_name = excluded.snapshot_repo_full_name,
+          snapshot_html_url = excluded.snapshot_html_url,
+          updated_at = excluded.updated_at
+        RETURNING id
+        "#,
+    )
+    .bind(local_id::generate_local_id())
+    .bind(user_id.as_str())
+    .bind(release_id.to_string())
+    .bind(note.as_deref())
+    .bind(snapshot.title.as_deref())
+    .bind(snapshot.repo_full_name.as_deref())
+    .bind(snapshot.html_url.as_deref())
+    .bind(now.as_str())
+    .bind(now.as_str())
+    .fetch_one(&state.pool)
+    .await
+    .map_err(ApiError::internal)?;
+
+    let rows = fetch_bookmark_rows(
+        state.as_ref(),
+        user_id.as_str(),
+        Some(bookmark_id.as_str()),
+        None,
+        1,
+    )
+    .await?;
+    let (items, _) = bookmark_items_from_rows(state.as_ref(), &user_id, rows, 1).await?;
+    items
+        .into_iter()
+        .next()
+        .map(Json)
+        .ok_or_else(bookmark_not_found)
+}
+
+pub async fn delete_bookmark(
+    State(state): State<Arc<AppState>>,
+    AuthedUser(user_id): AuthedUser,
+    Path(bookmark_id): Path<String>,
+) -> Result<Json<BookmarkDeleteResponse>, ApiError> {
+    let bookmark_id = parse_local_id_param(bookmark_id, "bookmark_id")?;
+
+    let (kind, entity_id) = sqlx::query_as::<_, (String, String)>(
+        r#"
+        DELETE FROM bookmarks
+        WHERE id = ?
+          AND user_id = ?
+        RETURNING kind, entity_id
+        "#,
+    )
+    .bind(bookmark_id.as_str())
+    .bind(user_id.as_str())
+    .fetch_optional(&state.pool)
+    .await
+    .map_err(ApiError::internal)?
+    .ok_or_else(bookmark_not_found)?;
+
+    Ok(Json(BookmarkDeleteResponse {
+        id: bookmark_id,
+        kind,
+        entity_id,
+        bookmarked: false,
+    }))
+}
+
+pub(super) async fn bookmark_items_from_rows(
+    state: &AppState,
+    user_id: &str,
+    rows: Vec<BookmarkRow>,
+    limit: i64,
+) -> Result<(Vec<BookmarkItem>, Option<String>), ApiError> {
+    let details = rows
+        .iter()
+        .map(|row| {
+            (
+                row.feed.id_key.clone(),
+                row.note.clone(),
+                row.feed.sort_ts.clone(),
+                row.feed.release_id.is_some(),
+            )
+        })
+        .collect::<Vec<_>>();
+    let page = feed_page_from_rows(
+        state,
+        user_id,
+        rows.into_iter().map(|row| row.feed).collect(),
+        limit,
+        FeedExcerpt::default(),
+    )
+    .await?;
+    let items = details
+        .into_iter()
+        .zip(page.items)
+        .map(
+            |((id, note, created_at, release_available), item)| BookmarkItem {
+                id,
+                note,
+                created_at,
+                release_available,
+                item,
+            },
+        )
+        .collect();
+    Ok((items, page.next_cursor))
+}
+
+/// Bookmarks in feed row shape, most recently bookmarked first. Releases that
+/// are no longer cached fall back to the snapshot taken at bookmark time.
+pub(super) async fn fetch_bookmark_rows(
+    state: &AppState,
+    user_id: &str,
+    bookmark_id: Option<&str>,
+    cursor: Option<&FeedCursor>,
+    limit: i64,
+) -> Result<Vec<BookmarkRow>, ApiError> {
+    sqlx::query_as::<_, BookmarkRow>(
+        r#"
+        WITH items AS (
+          SELECT
+            b.id AS bookmark_id,
+            b.kind AS kind,
+            b.note AS note,
+            b.created_at AS sort_ts,
+            COALESCE(r.published_at, r.created_at, r.updated_at, b.created_at) AS ts,
+            b.id AS id_key,
+            b.entity_id AS entity_id,
+            r.release_id AS release_id,
+            r.node_id AS release_node_id,
+            COALESCE(vr.full_name, b.snapshot_repo_full_name) AS repo_full_name,
+            vr.owner_login AS repo_owner_login,
+            vr.owner_avatar_url AS owner_avatar_url,
+            vr.description AS repo_description,
+            vr.html_url AS repo_html_url,
+            vr.open_graph_image_url AS open_graph_image_url,
+            vr.uses_custom_open_graph_image AS uses_custom_open_graph_image,
+            r.tag_name AS release_tag_name,
+            (
+              SELECT p.tag_name
+              FROM repo_releases p
+              WHERE p.repo_id = r.repo_id
+                AND (
+                  COALESCE(p.published_at, p.created_at, p.updated_at)
+                    < COALESCE(r.published_at, r.created_at, r.updated_at)
+                  OR (
+                    COALESCE(p.published_at, p.created_at, p.updated_at)
+                      = COALESCE(r.published_at, r.created_at, r.updated_at)
+                    AND p.release_id < r.release_id
+                  )
+                )
+              ORDER BY COALESCE(p.published_at, p.created_at, p.updated_at) DESC, p.release_id DESC
+              LIMIT 1
+            ) AS release_previous_tag_name,
+            COALESCE(NULLIF(TRIM(r.name), ''), r.tag_name, b.snapshot_title) AS title,
+            COALESCE(r.html_url, b.snapshot_html_url) AS html_url,
+            r.body AS release_body,
+            r.react_plus1 AS react_plus1,
+            r.react_laugh AS react_laugh,
+            r.react_heart AS react_heart,
+            r.react_hooray AS react_hooray,
+            r.react_rocket AS react_rocket,
+            r.react_eyes AS react_eyes,
+            vr.source AS repo_source
+          FROM bookmarks b
+          LEFT JOIN repo_releases r
+            ON b.kind = 'release' AND r.release_id = CAST(b.entity_id AS INTEGER)
+          LEFT JOIN user_release_visible_repos vr
+            ON vr.user_id = b.user_id AND vr.repo_id = r.repo_id
+          WHERE b.user_id = ?
+            AND (? IS NULL OR b.id = ?)
+        )
+        SELECT
+          i.kind, i.note, i.sort_ts, i.ts, i.id_key, i.entity_id, i.release_id, i.release_node_id,
+          i.repo_full_name, i.repo_owner_login, i.owner_avatar_url, i.repo_description, i.repo_html_url,
+          i.open_graph_image_url, i.uses_custom_open_graph_image,
+          i.release_tag_name, i.release_previous_tag_name,
+          i.title, NULL AS subtitle, NULL AS reason, NULL AS subject_type, i.html_url, NULL AS unread,
+          NULL AS actor_login, NULL AS actor_avatar_url, NULL AS actor_html_url,
+          i.release_body, i.react_plus1, i.react_laugh, i.react_heart, i.react_hooray, i.react_rocket, i.react_eyes,
+          i.repo_source,
+          t.source_hash AS trans_source_hash,
+          t.status AS trans_status,
+          t.title AS trans_title,
+          t.summary AS trans_summary,
+          t.error_text AS trans_error_text,
+          tw.status AS trans_work_status,
+          dt.source_hash AS detail_trans_source_hash,
+          dt.status AS detail_trans_status,
+          dt.title AS detail_trans_title,
+          dt.summary AS detail_trans_summary,
+          dt.error_text AS detail_trans_error_text,
+          dtw.status AS detail_trans_work_status,
+          s.source_hash AS smart_source_hash,
+          s.status AS smart_status,
+          s.title AS smart_title,
+          s.summary AS smart_summary,
+          s.error_text AS smart_error_text,
+          sw.status AS smart_work_status,
+          i.bookmark_id
+        FROM items i
+        LEFT JOIN ai_translations t
+          ON t.user_id = ? AND t.entity_type = 'release' AND t.entity_id = i.entity_id AND t.lang = 'zh-CN' AND t.status IN ('ready', 'disabled', 'missing', 'error')
+        LEFT JOIN translation_work_items tw
+          ON tw.id = t.active_work_item_id
+        LEFT JOIN ai_translations dt
+          ON dt.user_id = ? AND dt.entity_type = 'release_detail' AND dt.entity_id = i.entity_id AND dt.lang = 'zh-CN' AND dt.status IN ('ready', 'disabled', 'missing', 'error')
+        LEFT JOIN translation_work_items dtw
+          ON dtw.id = dt.active_work_item_id
+        LEFT JOIN ai_translations s
+          ON s.user_id = ? AND s.entity_type = 'release_smart' AND s.entity_id = i.entity_id AND s.lang = 'zh-CN' AND s.status IN ('ready', 'disabled', 'missing', 'error')
+        LEFT JOIN translation_work_items sw
+          ON sw.id = s.active_work_item_id
+        WHERE (
+          ? = 0
+          OR i.sort_ts < ?
+          OR (i.sort_ts = ? AND i.id_key < ?)
+        )
+        ORDER BY i.sort_ts DESC, i.id_key DESC
+        LIMIT ?
+        "#,
+    )
+    .bind(user_id)
+    .bind(bookmark_id)
+    .bind(bookmark_id)
+    .bind(user_id)
+    .bind(user_id)
+    .bind(user_id)
+    .bind(if cursor.is_some() { 1_i64 } else { 0_i64 })
+    .bind(cursor.map(|c| c.sort_ts.as_str()))
+    .bind(cursor.map(|c| c.sort_ts.as_str()))
+    .bind(cursor.map(|c| c.id_key.as_str()))
+    .bind(limit)
+    .fetch_all(&state.pool)
+    .await
+    .map_err(ApiError::internal)
+}
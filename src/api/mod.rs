@@ -0,0 +1,493 @@
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+use std::convert::Infallible;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Instant;
+
+use anyhow::Context;
+use axum::body::{Body, Bytes};
+use axum::extract::{FromRequestParts, Path, Query};
+use axum::http::{HeaderMap, HeaderValue, StatusCode, header};
+use axum::response::{IntoResponse, Response};
+use axum::{Json, extract::State};
+use base64::{Engine as _, engine::general_purpose::URL_SAFE_NO_PAD};
+use chrono::{Datelike, TimeZone};
+use chrono_tz::Tz;
+use futures::{StreamExt, TryStreamExt};
+use schemars::JsonSchema;
+use serde::{Deserialize, Deserializer, Serialize};
+use serde_json::{Value, json};
+use sqlx::{QueryBuilder, Row};
+use tokio::{io::AsyncReadExt, sync::mpsc};
+use tower_sessions::Session;
+
+use crate::release_links::{
+    InternalReleaseRef, ReleaseLocator, build_github_release_url_prefixes,
+    parse_internal_release_ref, parse_release_locator_from_github_release_url,
+    parse_repo_full_name_from_release_url, resolve_release_refs,
+};
+use crate::{
+    admin_audit, admin_runtime, ai, briefs, feed_events, github_rate_limit, impersonation, jobs,
+    local_id, notify, observability, release_render, runtime, sync, translation_retry,
+};
+use crate::{
+    error::{ApiError, ApiJson, codes},
+    passkeys::{
+        PasskeySummary, PendingPasskeyCredentialSession, load_passkey_summaries,
+        pending_passkey_bind_is_expired,
+    },
+    state::AppState,
+};
+
+mod admin;
+mod feed;
+mod notifications;
+mod profile;
+mod reactions;
+mod release_diff;
+mod releases;
+mod translations;
+mod util;
+
+pub use admin::*;
+pub use feed::*;
+pub use notifications::*;
+pub use profile::*;
+pub use reactions::*;
+pub use release_diff::*;
+pub use releases::*;
+pub use translations::*;
+use util::*;
+
+/// Routes served by the handlers in this module; server.rs nests them under
+/// `/api` next to the other modules' routes and applies the shared layers.
+pub(crate) fn router(state: Arc<AppState>) -> axum::Router<Arc<AppState>> {
+    use axum::middleware;
+    use axum::routing::{delete, get, patch, post, put};
+
+    axum::Router::new()
+        .route("/me", get(me).delete(crate::account::delete_me))
+        .route("/me/profile", get(me_get_profile).patch(me_patch_profile))
+        .route("/tasks/{task_id}/events", get(task_events_sse))
+        .route(
+            "/starred",
+            get(list_starred).layer(middleware::from_fn_with_state(
+                state.clone(),
+                crate::conditional::starred_etag,
+            )),
+        )
+        .route("/repos/{repo_id}/mute", post(mute_repo).delete(unmute_repo))
+        .route(
+            "/repos/{repo_id}/preferences",
+            patch(patch_repo_preferences),
+        )
+        .route("/repos/{repo_id}/releases", get(list_repo_releases))
+        .route(
+            "/repos/{repo_id}/releases/unseen_count",
+            get(get_repo_unseen_release_count),
+        )
+        .route("/releases", get(list_releases))
+        .route(
+            "/releases/backfill-node-ids",
+            post(backfill_release_node_ids),
+        )
+        .route("/releases/{release_id}/detail", get(get_release_detail))
+        .route("/releases/{release_id}/rendered", get(get_rendered_release))
+        .route(
+            "/releases/{release_id}/diff-prev",
+            get(get_release_diff_prev),
+        )
+        .route(
+            "/repos/{owner}/{repo}/releases/tag/{tag}/detail",
+            get(get_release_detail_by_repo_tag),
+        )
+        .route(
+            "/public/repos/{owner}/{repo}/releases",
+            get(public_list_repo_releases),
+        )
+        .route(
+            "/public/repos/{owner}/{repo}/releases/tag/{tag}",
+            get(public_get_repo_release_detail),
+        )
+        .route("/notifications", get(list_notifications))
+        .route("/notifications/{thread_id}", get(get_notification_detail))
+        .route(
+            "/notifications/{thread_id}/archive",
+            post(archive_notification),
+        )
+        .route("/dashboard/updates", get(dashboard_updates))
+        .route("/bookmarks", get(list_bookmarks).post(create_bookmark))
+        .route("/bookmarks/{bookmark_id}", delete(delete_bookmark))
+        .route(
+            "/feed",
+            get(list_feed).layer(middleware::from_fn_with_state(
+                state.clone(),
+                crate::conditional::feed_etag,
+            )),
+        )
+        .route("/feed/events", get(feed_events_sse))
+        .route("/feed/seen", post(mark_feed_seen))
+        .route("/feed/seen-at", post(mark_feed_seen_at))
+        .route("/counters", get(get_counters))
+        .route("/feed/reactions/refresh", post(refresh_feed_reactions))
+        .route("/admin/users", get(admin_list_users))
+        .route("/admin/users/bulk", post(admin_bulk_update_users))
+        .route(
+            "/admin/users/{user_id}",
+            patch(admin_patch_user).delete(crate::account::admin_delete_user),
+        )
+        .route("/admin/users/{user_id}/stats", get(admin_get_user_stats))
+        .route("/admin/translations", delete(admin_delete_translations))
+        .route(
+            "/admin/translations/repair",
+            post(admin_repair_translations),
+        )
+        .route(
+            "/admin/users/{user_id}/tasks",
+            post(admin_enqueue_user_task),
+        )
+        .route(
+            "/admin/users/{user_id}/briefs/preview",
+            post(admin_preview_user_brief),
+        )
+        .route(
+            "/admin/users/{user_id}/profile",
+            get(admin_get_user_profile).patch(admin_patch_user_profile),
+        )
+        .route("/admin/dashboard", get(admin_dashboard))
+        .route(
+            "/admin/repos/overview",
+            get(admin_get_repo_governance_overview),
+        )
+        .route("/admin/repos", get(admin_list_repo_governance))
+        .route(
+            "/admin/public-release-repos",
+            get(admin_list_public_release_repos),
+        )
+        .route(
+            "/admin/public-release-repos/{usage_id}",
+            delete(admin_delete_public_release_repo),
+        )
+        .route("/admin/jobs/overview", get(admin_jobs_overview))
+        .route("/admin/jobs/events", get(admin_jobs_events_sse))
+        .route("/admin/jobs/realtime", get(admin_list_realtime_tasks))
+        .route(
+            "/admin/jobs/realtime/{task_id}",
+            get(admin_get_realtime_task_detail),
+        )
+        .route(
+            "/admin/jobs/realtime/{task_id}/log",
+            get(admin_download_realtime_task_log),
+        )
+        .route(
+            "/admin/jobs/realtime/{task_id}/retry",
+            post(admin_retry_realtime_task),
+        )
+        .route(
+            "/admin/jobs/realtime/{task_id}/cancel",
+            post(admin_cancel_realtime_task),
+        )
+        .route(
+            "/admin/maintenance/prune",
+            post(admin_run_maintenance_prune),
+        )
+        .route("/admin/jobs/scheduled", get(admin_list_scheduled_slots))
+        .route(
+            "/admin/jobs/scheduled/{hour_utc}",
+            patch(admin_patch_scheduled_slot),
+        )
+        .route(
+            "/admin/jobs/sync/runtime-config",
+            get(admin_get_sync_runtime_config).patch(admin_patch_sync_runtime_config),
+        )
+        .route(
+            "/admin/jobs/llm/status",
+            get(admin_get_llm_scheduler_status),
+        )
+        .route(
+            "/admin/jobs/llm/runtime-config",
+            patch(admin_patch_llm_runtime_config),
+        )
+        .route("/admin/jobs/llm/calls", get(admin_list_llm_calls))
+        .route("/admin/ai/health", get(admin_get_ai_health))
+        .route("/admin/llm/usage", get(admin_get_llm_usage))
+        .route("/admin/llm/usage/summary", get(admin_get_llm_usage_summary))
+        .route("/admin/llm/calls/export", get(admin_export_llm_calls))
+        .route("/admin/llm/scheduler", patch(admin_patch_llm_scheduler))
+        .route("/admin/llm/queue", get(admin_get_llm_queue))
+        .route(
+            "/admin/llm/queue/{call_id}",
+            delete(admin_drop_llm_queue_call),
+        )
+        .route(
+            "/admin/llm/queue/{call_id}/promote",
+            post(admin_promote_llm_queue_call),
+        )
+        .route("/admin/briefs/feedback", get(admin_get_brief_feedback))
+        .route(
+            "/admin/jobs/llm/calls/{call_id}",
+            get(admin_get_llm_call_detail),
+        )
+        .route("/reaction-token/status", get(reaction_token_status))
+        .route("/me/linuxdo", get(me_get_linuxdo).delete(me_delete_linuxdo))
+        .route("/me/github-connections", get(me_get_github_connections))
+        .route(
+            "/me/github-connections/{connection_id}",
+            delete(me_delete_github_connection),
+        )
+        .route("/accounts", get(me_get_github_connections))
+        .route(
+            "/accounts/{connection_id}",
+            delete(me_delete_github_connection),
+        )
+        .route("/me/passkeys", get(me_get_passkeys))
+        .route("/me/passkeys/{passkey_id}", delete(me_delete_passkey))
+        .route("/auth/bind-context", get(auth_get_bind_context))
+        .route("/reaction-token/check", post(check_reaction_token))
+        .route("/reaction-token/diagnose", post(diagnose_reaction_token))
+        .route("/reaction-token", put(upsert_reaction_token))
+        .route("/release/reactions/toggle", post(toggle_release_reaction))
+        .route(
+            "/briefs",
+            get(list_briefs).layer(middleware::from_fn_with_state(
+                state.clone(),
+                crate::conditional::briefs_etag,
+            )),
+        )
+        .route("/briefs/deliveries", get(list_brief_deliveries))
+        .route("/briefs/generate", post(generate_brief))
+        .route("/briefs/preview", post(preview_brief))
+        .route("/briefs/{date}/sources", get(get_brief_sources))
+        .route("/briefs/{date}/translate", post(translate_brief))
+        .route("/briefs/{date}/feedback", post(submit_brief_feedback))
+        .route(
+            "/channels",
+            get(list_notification_channels).post(create_notification_channel),
+        )
+        .route(
+            "/channels/{channel_id}",
+            patch(patch_notification_channel).delete(delete_notification_channel),
+        )
+        .route("/translate/releases/batch", post(translate_releases_batch))
+        .route(
+            "/translate/releases/batch/stream",
+            post(translate_releases_batch_stream),
+        )
+        .route(
+            "/translations/batch/{task_id}/stream",
+            get(attach_translate_releases_batch_stream),
+        )
+        .route("/translations", delete(delete_translations))
+        .route("/translations/repair", post(repair_translations))
+        .route("/translate/release", post(translate_release))
+        .route(
+            "/translate/release/detail/batch",
+            post(translate_release_detail_batch),
+        )
+        .route("/translate/release/detail", post(translate_release_detail))
+        .route(
+            "/translate/notifications/batch",
+            post(translate_notifications_batch),
+        )
+        .route("/translate/notification", post(translate_notification))
+        .route("/sync/starred", post(sync_starred))
+        .route("/sync/all", post(sync_all))
+        .route("/github/rate-limit", get(get_github_rate_limit))
+        .route("/sync/releases", post(sync_releases))
+        .route("/sync/notifications", post(sync_notifications))
+}
+
+pub(crate) async fn require_user_id(session: &Session) -> Result<String, ApiError> {
+    let Some(user_id) = session
+        .get::<String>("user_id")
+        .await
+        .map_err(ApiError::internal)?
+    else {
+        return Err(ApiError::new(
+            StatusCode::UNAUTHORIZED,
+            codes::UNAUTHORIZED,
+            "not logged in",
+        ));
+    };
+    touch_authenticated_session(session).await?;
+    parse_local_id_param(user_id, "user_id")
+}
+
+pub(super) async fn touch_authenticated_session(session: &Session) -> Result<(), ApiError> {
+    let now = chrono::Utc::now().timestamp();
+    let last_touched_at = session
+        .get::<i64>(SESSION_ACTIVITY_TOUCHED_AT)
+        .await
+        .map_err(ApiError::internal)?;
+
+    if last_touched_at
+        .is_some_and(|value| now.saturating_sub(value) < SESSION_ACTIVITY_TOUCH_INTERVAL_SECS)
+    {
+        return Ok(());
+    }
+
+    session
+        .insert(SESSION_ACTIVITY_TOUCHED_AT, now)
+        .await
+        .map_err(ApiError::internal)
+}
+
+pub(super) fn ensure_account_enabled(is_disabled: bool) -> Result<(), ApiError> {
+    if is_disabled {
+        return Err(ApiError::new(
+            StatusCode::FORBIDDEN,
+            codes::ACCOUNT_DISABLED,
+            "account is disabled",
+        ));
+    }
+    Ok(())
+}
+
+#[derive(Debug, sqlx::FromRow)]
+pub(super) struct SessionAccessRow {
+    pub(super) is_disabled: i64,
+    pub(super) last_active_at: Option<String>,
+}
+
+pub(crate) async fn require_active_user_id(
+    state: &AppState,
+    session: &Session,
+) -> Result<String, ApiError> {
+    let user_id = require_user_id(session).await?;
+    // Impersonated users may be disabled, and looking at their data must not
+    // count as their activity.
+    if let Some(target_user_id) =
+        impersonation::resolve_impersonation(state, session, &user_id).await?
+    {
+        return Ok(target_user_id);
+    }
+    let row = sqlx::query_as::<_, SessionAccessRow>(
+        r#"
+        SELECT is_disabled, last_active_at
+        FROM users
+        WHERE id = ?
+        "#,
+    )
+    .bind(&user_id)
+    .fetch_optional(&state.pool)
+    .await
+    .map_err(ApiError::internal)?;
+
+    let Some(row) = row else {
+        session.clear().await;
+        return Err(ApiError::new(
+            StatusCode::UNAUTHORIZED,
+            codes::UNAUTHORIZED,
+            "session user not found",
+        ));
+    };
+
+    if let Err(err) = ensure_account_enabled(row.is_disabled != 0) {
+        session.clear().await;
+        return Err(err);
+    }
+
+    if load_pending_access_sync_reason(session).await?.is_none()
+        && last_active_is_stale(row.last_active_at.as_deref())
+    {
+        let reason = if row.last_active_at.is_some() {
+            ACCESS_SYNC_REASON_INACTIVE_OVER_1H
+        } else {
+            "first_visit"
+        };
+        mark_pending_access_sync_reason(session, reason).await?;
+    }
+
+    touch_user_last_active_at(state, &user_id).await?;
+
+    Ok(user_id)
+}
+
+pub(crate) async fn require_admin_user_id(
+    state: &AppState,
+    session: &Session,
+) -> Result<String, ApiError> {
+    let user_id = require_active_user_id(state, session).await?;
+    let is_admin =
+        sqlx::query_scalar::<_, i64>(r#"SELECT is_admin FROM users WHERE id = ? LIMIT 1"#)
+            .bind(&user_id)
+            .fetch_one(&state.pool)
+            .await
+            .map_err(ApiError::internal)?;
+    if is_admin == 0 {
+        return Err(ApiError::new(
+            StatusCode::FORBIDDEN,
+            codes::FORBIDDEN_ADMIN_ONLY,
+            "admin permission required",
+        ));
+    }
+    Ok(user_id)
+}
+
+/// Signed-in user with an enabled account, resolved through
+/// [`require_active_user_id`]; while an admin impersonates someone this is the
+/// impersonated user.
+pub(crate) struct AuthedUser(pub(crate) String);
+
+/// Signed-in user holding the admin role, resolved through
+/// [`require_admin_user_id`].
+pub(crate) struct AdminUser(pub(crate) String);
+
+async fn request_session(
+    parts: &mut axum::http::request::Parts,
+    state: &Arc<AppState>,
+) -> Result<Session, ApiError> {
+    Session::from_request_parts(parts, state)
+        .await
+        .map_err(|(_, message)| ApiError::internal(message))
+}
+
+impl FromRequestParts<Arc<AppState>> for AuthedUser {
+    type Rejection = ApiError;
+
+    async fn from_request_parts(
+        parts: &mut axum::http::request::Parts,
+        state: &Arc<AppState>,
+    ) -> Result<Self, Self::Rejection> {
+        let session = request_session(parts, state).await?;
+        require_active_user_id(state, &session).await.map(Self)
+    }
+}
+
+impl FromRequestParts<Arc<AppState>> for AdminUser {
+    type Rejection = ApiError;
+
+    async fn from_request_parts(
+        parts: &mut axum::http::request::Parts,
+        state: &Arc<AppState>,
+    ) -> Result<Self, Self::Rejection> {
+        let session = request_session(parts, state).await?;
+        require_admin_user_id(state, &session).await.map(Self)
+    }
+}
+
+#[cfg(test)]
+pub(crate) async fn ensure_owned_repo_visual_columns(
+    pool: &sqlx::SqlitePool,
+) -> Result<(), sqlx::Error> {
+    for statement in [
+        r#"ALTER TABLE owned_repo_star_baselines ADD COLUMN owner_avatar_url TEXT"#,
+        r#"ALTER TABLE owned_repo_star_baselines ADD COLUMN open_graph_image_url TEXT"#,
+        r#"ALTER TABLE owned_repo_star_baselines ADD COLUMN uses_custom_open_graph_image INTEGER NOT NULL DEFAULT 0"#,
+    ] {
+        match sqlx::query(statement).execute(pool).await {
+            Ok(_) => {}
+            Err(sqlx::Error::Database(err))
+                if err.message().contains("duplicate column name")
+                    || err.message().contains("no such table") => {}
+            Err(err) => return Err(err),
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests;
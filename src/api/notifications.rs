@@ -0,0 +1,1925 @@
+use super::*;
+
+#[derive(Debug, Serialize, sqlx::FromRow)]
+pub struct NotificationItem {
+    pub(super) thread_id: String,
+    pub(super) repo_full_name: Option<String>,
+    pub(super) subject_title: Option<String>,
+    pub(super) subject_type: Option<String>,
+    pub(super) reason: Option<String>,
+    pub(super) updated_at: Option<String>,
+    pub(super) unread: i64,
+    pub(super) html_url: Option<String>,
+    /// Set once the thread was archived by the user or by age.
+    pub(super) archived_at: Option<String>,
+}
+
+#[derive(Debug, Default, Deserialize, JsonSchema)]
+pub struct NotificationListQuery {
+    /// `subject` collapses notifications about the same subject into one
+    /// item; omitted returns one item per thread.
+    pub(super) group: Option<String>,
+    /// Comma-separated notification reasons to keep, e.g.
+    /// `mention,review_requested`; omitted keeps every reason.
+    pub(super) reasons: Option<String>,
+    /// `true` also returns archived notifications.
+    pub(super) include_archived: Option<bool>,
+}
+
+/// One subject's notifications collapsed together. Thread fields come from
+/// the most recently updated notification.
+#[derive(Debug, Serialize, PartialEq, Eq)]
+pub struct NotificationSubjectGroup {
+    pub(super) thread_id: String,
+    pub(super) repo_full_name: Option<String>,
+    pub(super) subject_title: Option<String>,
+    pub(super) subject_type: Option<String>,
+    pub(super) updated_at: Option<String>,
+    /// 1 when any notification of the subject is unread.
+    pub(super) unread: i64,
+    pub(super) html_url: Option<String>,
+    pub(super) events_count: i64,
+    pub(super) reasons: Vec<String>,
+}
+
+#[derive(Debug, sqlx::FromRow)]
+pub(super) struct NotificationSubjectGroupRow {
+    pub(super) thread_id: String,
+    pub(super) repo_full_name: Option<String>,
+    pub(super) subject_title: Option<String>,
+    pub(super) subject_type: Option<String>,
+    pub(super) updated_at: Option<String>,
+    pub(super) unread: i64,
+    pub(super) html_url: Option<String>,
+    pub(super) events_count: i64,
+    pub(super) reasons: Option<String>,
+}
+
+impl From<NotificationSubjectGroupRow> for NotificationSubjectGroup {
+    fn from(row: NotificationSubjectGroupRow) -> Self {
+        let mut reasons = row
+            .reasons
+            .as_deref()
+            .unwrap_or("")
+            .split(',')
+            .map(str::trim)
+            .filter(|reason| !reason.is_empty())
+            .map(str::to_owned)
+            .collect::<Vec<_>>();
+        reasons.sort();
+        reasons.dedup();
+        Self {
+            thread_id: row.thread_id,
+            repo_full_name: row.repo_full_name,
+            subject_title: row.subject_title,
+            subject_type: row.subject_type,
+            updated_at: row.updated_at,
+            unread: row.unread,
+            html_url: row.html_url,
+            events_count: row.events_count,
+            reasons,
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+#[serde(untagged)]
+pub enum NotificationListItems {
+    Flat(Vec<NotificationItem>),
+    Subject(Vec<NotificationSubjectGroup>),
+}
+
+/// Counts over every cached notification, not just the returned page.
+#[derive(Debug, Default, Serialize, PartialEq, Eq)]
+pub struct NotificationCounts {
+    pub(super) total: i64,
+    pub(super) unread: i64,
+    /// Keyed by GitHub subject type; notifications without one count as
+    /// `unknown`.
+    pub(super) by_subject_type: BTreeMap<String, i64>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct NotificationListResponse {
+    /// `none` or `subject`, echoing the grouping that was applied.
+    pub(super) group: &'static str,
+    pub(super) items: NotificationListItems,
+    pub(super) counts: NotificationCounts,
+}
+
+pub(super) const NOTIFICATION_LIST_LIMIT: i64 = 200;
+
+/// Validates against the GitHub reason vocabulary, dropping blanks and
+/// duplicates.
+pub(super) fn normalize_notification_reasons<'a>(
+    reasons: impl IntoIterator<Item = &'a str>,
+) -> Result<Vec<String>, ApiError> {
+    let mut normalized = Vec::<String>::new();
+    for reason in reasons.into_iter().map(str::trim) {
+        if reason.is_empty() || normalized.iter().any(|existing| existing == reason) {
+            continue;
+        }
+        if !sync::NOTIFICATION_REASONS.contains(&reason) {
+            return Err(ApiError::bad_request(format!(
+                "unknown notification reason: {reason}"
+            )));
+        }
+        normalized.push(reason.to_owned());
+    }
+    Ok(normalized)
+}
+
+pub async fn list_notifications(
+    State(state): State<Arc<AppState>>,
+    AuthedUser(user_id): AuthedUser,
+    Query(query): Query<NotificationListQuery>,
+) -> Result<Json<NotificationListResponse>, ApiError> {
+    let grouped = match query.group.as_deref().map(str::trim) {
+        None | Some("" | "none") => false,
+        Some("subject") => true,
+        Some(_) => return Err(ApiError::bad_request("group must be none or subject")),
+    };
+    let reasons_json = match query.reasons.as_deref() {
+        Some(raw) => {
+            let reasons = normalize_notification_reasons(raw.split(','))?;
+            (!reasons.is_empty())
+                .then(|| serde_json::to_string(&reasons))
+                .transpose()
+                .map_err(ApiError::internal)?
+        }
+        None => None,
+    };
+    let include_archived = query.include_archived.unwrap_or(false);
+
+    let items = if grouped {
+        NotificationListItems::Subject(
+            load_notification_subject_groups(
+                state.as_ref(),
+                user_id.as_str(),
+                reasons_json.as_deref(),
+                include_archived,
+            )
+            .await?,
+        )
+    } else {
+        let sql = format!(
+            r#"
+            SELECT
+              thread_id, repo_full_name, subject_title, subject_type, reason, updated_at, unread,
+              html_url, archived_at
+            FROM notifications
+            WHERE user_id = ?
+              {archived_filter}
+              AND (? IS NULL OR reason IN (SELECT value FROM json_each(?)))
+            ORDER BY updated_at DESC
+            LIMIT ?
+            "#,
+            archived_filter = notification_archived_filter(include_archived),
+        );
+        let items = sqlx::query_as::<_, NotificationItem>(&sql)
+            .bind(user_id.as_str())
+            .bind(reasons_json.as_deref())
+            .bind(reasons_json.as_deref())
+            .bind(NOTIFICATION_LIST_LIMIT)
+            .fetch_all(&state.pool)
+            .await
+            .map_err(ApiError::internal)?;
+        NotificationListItems::Flat(items)
+    };
+    let counts = load_notification_counts(state.as_ref(), user_id.as_str()).await?;
+
+    Ok(Json(NotificationListResponse {
+        group: if grouped { "subject" } else { "none" },
+        items,
+        counts,
+    }))
+}
+
+/// Spelled out in the SQL text rather than bound so the default listing can
+/// use the partial inbox index.
+pub(super) fn notification_archived_filter(include_archived: bool) -> &'static str {
+    if include_archived {
+        ""
+    } else {
+        "AND archived_at IS NULL"
+    }
+}
+
+/// Groups by (repo, subject title, subject type). Notifications without a
+/// repo never merge: unrelated threads would otherwise collapse whenever
+/// their titles matched, so each one keys on its own thread id instead.
+pub(super) async fn load_notification_subject_groups(
+    state: &AppState,
+    user_id: &str,
+    reasons_json: Option<&str>,
+    include_archived: bool,
+) -> Result<Vec<NotificationSubjectGroup>, ApiError> {
+    let sql = format!(
+        r#"
+        WITH keyed AS (
+          SELECT
+            thread_id,
+            repo_full_name,
+            subject_title,
+            subject_type,
+            reason,
+            updated_at,
+            unread,
+            html_url,
+            CASE
+              WHEN repo_full_name IS NULL THEN 'thread:' || thread_id
+              ELSE 'repo:' || repo_full_name
+            END AS repo_key
+          FROM notifications
+          WHERE user_id = ?
+            {archived_filter}
+            AND (? IS NULL OR reason IN (SELECT value FROM json_each(?)))
+        ),
+        ranked AS (
+          SELECT
+            keyed.*,
+            ROW_NUMBER() OVER (
+              PARTITION BY repo_key, subject_title, subject_type
+              ORDER BY updated_at DESC, thread_id DESC
+            ) AS rn
+          FROM keyed
+        ),
+        grouped AS (
+          SELECT
+            repo_key,
+            subject_title,
+            subject_type,
+            COUNT(*) AS events_count,
+            MAX(CASE WHEN unread != 0 THEN 1 ELSE 0 END) AS unread,
+            GROUP_CONCAT(DISTINCT reason) AS reasons
+          FROM keyed
+          GROUP BY repo_key, subject_title, subject_type
+        )
+        SELECT
+          r.thread_id,
+          r.repo_full_name,
+          r.subject_title,
+          r.subject_type,
+          r.updated_at,
+          g.unread,
+          r.html_url,
+          g.events_count,
+          g.reasons
+        FROM ranked r
+        JOIN grouped g
+          ON g.repo_key = r.repo_key
+         AND g.subject_title IS r.subject_title
+         AND g.subject_type IS r.subject_type
+        WHERE r.rn = 1
+        ORDER BY r.updated_at DESC, r.thread_id DESC
+        LIMIT ?
+        "#,
+        archived_filter = notification_archived_filter(include_archived),
+    );
+    let rows = sqlx::query_as::<_, NotificationSubjectGroupRow>(&sql)
+        .bind(user_id)
+        .bind(reasons_json)
+        .bind(reasons_json)
+        .bind(NOTIFICATION_LIST_LIMIT)
+        .fetch_all(&state.pool)
+        .await
+        .map_err(ApiError::internal)?;
+    Ok(rows
+        .into_iter()
+        .map(NotificationSubjectGroup::from)
+        .collect())
+}
+
+/// Notifications with a muted reason never count as unread, even when they
+/// were synced before the reason was muted. Archived notifications are not
+/// counted at all.
+pub(super) async fn load_notification_counts(
+    state: &AppState,
+    user_id: &str,
+) -> Result<NotificationCounts, ApiError> {
+    let rows = sqlx::query_as::<_, (String, i64, i64)>(
+        r#"
+        SELECT
+          COALESCE(n.subject_type, 'unknown') AS subject_type,
+          COUNT(*) AS total,
+          COALESCE(SUM(CASE
+            WHEN n.unread != 0
+              AND COALESCE(n.reason, '') NOT IN (
+                SELECT value FROM json_each(u.muted_notification_reasons)
+              )
+            THEN 1
+            ELSE 0
+          END), 0) AS unread
+        FROM notifications n
+        JOIN users u ON u.id = n.user_id
+        WHERE n.user_id = ?
+          AND n.archived_at IS NULL
+        GROUP BY COALESCE(n.subject_type, 'unknown')
+        "#,
+    )
+    .bind(user_id)
+    .fetch_all(&state.pool)
+    .await
+    .map_err(ApiError::internal)?;
+
+    let mut counts = NotificationCounts::default();
+    for (subject_type, total, unread) in rows {
+        counts.total += total;
+        counts.unread += unread;
+        *counts.by_subject_type.entry(subject_type).or_default() += total;
+    }
+    Ok(counts)
+}
+
+#[derive(Debug, sqlx::FromRow)]
+pub(super) struct NotificationDetailRow {
+    pub(super) thread_id: String,
+    pub(super) repo_full_name: Option<String>,
+    pub(super) subject_title: Option<String>,
+    pub(super) subject_type: Option<String>,
+    pub(super) reason: Option<String>,
+    pub(super) updated_at: Option<String>,
+    pub(super) unread: i64,
+    pub(super) html_url: Option<String>,
+    pub(super) subject_url: Option<String>,
+    pub(super) subject_state: Option<String>,
+    pub(super) subject_body: Option<String>,
+    pub(super) subject_fetched_at: Option<String>,
+    pub(super) archived_at: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct NotificationDetailTranslation {
+    pub(super) lang: String,
+    pub(super) title: Option<String>,
+    pub(super) summary: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct NotificationDetailResponse {
+    #[serde(flatten)]
+    pub(super) item: NotificationItem,
+    /// `open`, `closed`, `merged`, or `gone` once GitHub reports the subject
+    /// deleted; `None` until the subject has been looked up.
+    pub(super) subject_state: Option<String>,
+    pub(super) subject_body: Option<String>,
+    pub(super) translation: Option<NotificationDetailTranslation>,
+}
+
+pub async fn get_notification_detail(
+    State(state): State<Arc<AppState>>,
+    Path(thread_id_raw): Path<String>,
+    AuthedUser(user_id): AuthedUser,
+) -> Result<Json<NotificationDetailResponse>, ApiError> {
+    let thread_id = thread_id_raw.trim();
+    if thread_id.is_empty() {
+        return Err(ApiError::bad_request("thread_id is required"));
+    }
+    ensure_id_param_len(thread_id, "thread_id")?;
+    load_notification_detail(state.as_ref(), user_id.as_str(), thread_id)
+        .await
+        .map(Json)
+}
+
+/// Hides the thread from the default listing until GitHub reports a newer
+/// update for it.
+pub async fn archive_notification(
+    State(state): State<Arc<AppState>>,
+    Path(thread_id_raw): Path<String>,
+    AuthedUser(user_id): AuthedUser,
+) -> Result<Json<NotificationDetailResponse>, ApiError> {
+    let thread_id = thread_id_raw.trim();
+    if thread_id.is_empty() {
+        return Err(ApiError::bad_request("thread_id is required"));
+    }
+    ensure_id_param_len(thread_id, "thread_id")?;
+    let archived_at = chrono::Utc::now().to_rfc3339();
+    let updated = state
+        .sqlite_writer
+        .write_foreground("notifications_archive", |_| async {
+            sqlx::query(
+                r#"
+                UPDATE notifications
+                SET archived_at = COALESCE(archived_at, ?)
+                WHERE user_id = ? AND thread_id = ?
+                "#,
+            )
+            .bind(archived_at.as_str())
+            .bind(user_id.as_str())
+            .bind(thread_id)
+            .execute(&state.pool)
+            .await
+            .map(|result| result.rows_affected())
+            .context("failed to archive notification")
+        })
+        .await
+        .map_err(ApiError::internal)?;
+    if updated == 0 {
+        return Err(ApiError::new(
+            StatusCode::NOT_FOUND,
+            codes::NOT_FOUND,
+            "notification not found",
+        ));
+    }
+    load_notification_detail(state.as_ref(), user_id.as_str(), thread_id)
+        .await
+        .map(Json)
+}
+
+/// The subject is looked up again whenever the thread moved since the last
+/// lookup, so state changes (closed, merged, deleted) show up with it.
+pub(super) fn notification_subject_needs_refresh(row: &NotificationDetailRow) -> bool {
+    if row.subject_url.is_none() {
+        return false;
+    }
+    let Some(fetched_at) = row.subject_fetched_at.as_deref() else {
+        return true;
+    };
+    let parse = |value: &str| chrono::DateTime::parse_from_rfc3339(value).ok();
+    match (parse(fetched_at), row.updated_at.as_deref().and_then(parse)) {
+        (Some(fetched_at), Some(updated_at)) => fetched_at < updated_at,
+        (None, _) => true,
+        (Some(_), None) => false,
+    }
+}
+
+pub(super) async fn refresh_notification_subject(
+    state: &AppState,
+    user_id: &str,
+    row: &mut NotificationDetailRow,
+) -> Result<(), ApiError> {
+    let Some(subject_url) = row.subject_url.clone() else {
+        return Ok(());
+    };
+    match sync::fetch_notification_subject(state, user_id, subject_url.as_str()).await {
+        Ok(sync::NotificationSubjectLookup::Found(detail)) => {
+            row.subject_state = detail.subject_state();
+            row.subject_body = detail.body;
+            if detail.html_url.is_some() {
+                row.html_url = detail.html_url;
+            }
+        }
+        Ok(sync::NotificationSubjectLookup::Gone) => {
+            row.subject_state = Some("gone".to_owned());
+        }
+        Err(err) => {
+            tracing::warn!(
+                user_id,
+                thread_id = row.thread_id.as_str(),
+                ?err,
+                "notification subject lookup failed; serving cached detail"
+            );
+            return Ok(());
+        }
+    }
+
+    let fetched_at = chrono::Utc::now().to_rfc3339();
+    sqlx::query(
+        r#"
+        UPDATE notifications
+        SET subject_state = ?, subject_body = ?, html_url = ?, subject_fetched_at = ?
+        WHERE user_id = ? AND thread_id = ?
+        "#,
+    )
+    .bind(row.subject_state.as_deref())
+    .bind(row.subject_body.as_deref())
+    .bind(row.html_url.as_deref())
+    .bind(fetched_at.as_str())
+    .bind(user_id)
+    .bind(row.thread_id.as_str())
+    .execute(&state.pool)
+    .await
+    .map_err(ApiError::internal)?;
+    row.subject_fetched_at = Some(fetched_at);
+    Ok(())
+}
+
+pub(super) async fn load_notification_detail(
+    state: &AppState,
+    user_id: &str,
+    thread_id: &str,
+) -> Result<NotificationDetailResponse, ApiError> {
+    let row = sqlx::query_as::<_, NotificationDetailRow>(
+        r#"
+        SELECT
+          thread_id, repo_full_name, subject_title, subject_type, reason, updated_at, unread,
+          html_url, subject_url, subject_state, subject_body, subject_fetched_at, archived_at
+        FROM notifications
+        WHERE user_id = ? AND thread_id = ?
+        LIMIT 1
+        "#,
+    )
+    .bind(user_id)
+    .bind(thread_id)
+    .fetch_optional(&state.pool)
+    .await
+    .map_err(ApiError::internal)?;
+    let Some(mut row) = row else {
+        return Err(ApiError::new(
+            StatusCode::NOT_FOUND,
+            codes::NOT_FOUND,
+            "notification not found",
+        ));
+    };
+
+    if notification_subject_needs_refresh(&row) {
+        refresh_notification_subject(state, user_id, &mut row).await?;
+    }
+
+    let source_hash = notification_translation_source_hash(
+        row.repo_full_name.as_deref(),
+        row.subject_title.as_deref(),
+        row.reason.as_deref(),
+        row.subject_type.as_deref(),
+    );
+    let translation = sqlx::query_as::<_, (Option<String>, Option<String>)>(
+        r#"
+        SELECT title, summary
+        FROM ai_translations
+        WHERE user_id = ?
+          AND entity_type = 'notification'
+          AND entity_id = ?
+          AND lang = 'zh-CN'
+          AND source_hash = ?
+          AND status = 'ready'
+        LIMIT 1
+        "#,
+    )
+    .bind(user_id)
+    .bind(row.thread_id.as_str())
+    .bind(source_hash.as_str())
+    .fetch_optional(&state.pool)
+    .await
+    .map_err(ApiError::internal)?
+    .map(|(title, summary)| NotificationDetailTranslation {
+        lang: "zh-CN".to_owned(),
+        title,
+        summary,
+    });
+
+    Ok(NotificationDetailResponse {
+        item: NotificationItem {
+            thread_id: row.thread_id,
+            repo_full_name: row.repo_full_name,
+            subject_title: row.subject_title,
+            subject_type: row.subject_type,
+            reason: row.reason,
+            updated_at: row.updated_at,
+            unread: row.unread,
+            html_url: row.html_url,
+            archived_at: row.archived_at,
+        },
+        subject_state: row.subject_state,
+        subject_body: row.subject_body,
+        translation,
+    })
+}
+
+#[derive(Debug, Serialize, sqlx::FromRow)]
+pub struct BriefDeliveryItem {
+    pub(super) id: String,
+    pub(super) brief_id: String,
+    pub(super) brief_date: Option<String>,
+    pub(super) channel: String,
+    pub(super) recipient: Option<String>,
+    pub(super) status: String, // sent | failed | skipped | dry_run
+    pub(super) error_text: Option<String>,
+    pub(super) html_body: Option<String>,
+    pub(super) sent_at: Option<String>,
+    pub(super) created_at: String,
+    pub(super) updated_at: String,
+}
+
+pub async fn list_brief_deliveries(
+    State(state): State<Arc<AppState>>,
+    AuthedUser(user_id): AuthedUser,
+) -> Result<Json<Vec<BriefDeliveryItem>>, ApiError> {
+    let items = sqlx::query_as::<_, BriefDeliveryItem>(
+        r#"
+        SELECT
+          d.id,
+          d.brief_id,
+          b.date AS brief_date,
+          d.channel,
+          d.recipient,
+          d.status,
+          d.error_text,
+          d.html_body,
+          d.sent_at,
+          d.created_at,
+          d.updated_at
+        FROM brief_deliveries d
+        LEFT JOIN briefs b ON b.id = d.brief_id
+        WHERE d.user_id = ?
+        ORDER BY d.updated_at DESC, d.id DESC
+        LIMIT 30
+        "#,
+    )
+    .bind(&user_id)
+    .fetch_all(&state.pool)
+    .await
+    .map_err(ApiError::internal)?;
+
+    Ok(Json(items))
+}
+
+#[derive(Debug, Serialize)]
+pub struct NotificationChannelItem {
+    pub(super) id: String,
+    pub(super) kind: String, // generic | slack | discord
+    /// Only the host is echoed back; the full URL usually carries a secret.
+    pub(super) url_host: String,
+    pub(super) enabled: bool,
+    pub(super) events: Vec<String>,
+    pub(super) last_delivery_status: Option<String>,
+    pub(super) last_delivery_error: Option<String>,
+    pub(super) last_delivery_at: Option<String>,
+    pub(super) created_at: String,
+    pub(super) updated_at: String,
+}
+
+#[derive(Debug, sqlx::FromRow)]
+pub(super) struct NotificationChannelRow {
+    pub(super) id: String,
+    pub(super) kind: String,
+    pub(super) url_host: String,
+    pub(super) enabled: i64,
+    pub(super) events_json: String,
+    pub(super) last_delivery_status: Option<String>,
+    pub(super) last_delivery_error: Option<String>,
+    pub(super) last_delivery_at: Option<String>,
+    pub(super) created_at: String,
+    pub(super) updated_at: String,
+}
+
+impl From<NotificationChannelRow> for NotificationChannelItem {
+    fn from(row: NotificationChannelRow) -> Self {
+        Self {
+            id: row.id,
+            kind: row.kind,
+            url_host: row.url_host,
+            enabled: row.enabled != 0,
+            events: serde_json::from_str(&row.events_json).unwrap_or_default(),
+            last_delivery_status: row.last_delivery_status,
+            last_delivery_error: row.last_delivery_error,
+            last_delivery_at: row.last_delivery_at,
+            created_at: row.created_at,
+            updated_at: row.updated_at,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub struct NotificationChannelCreateRequest {
+    pub(super) kind: String,
+    pub(super) url: String,
+    #[serde(default)]
+    pub(super) enabled: Option<bool>,
+    /// Defaults to every supported event.
+    #[serde(default)]
+    pub(super) events: Option<Vec<String>>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct NotificationChannelPatchRequest {
+    #[serde(default)]
+    pub(super) kind: Option<String>,
+    #[serde(default)]
+    pub(super) url: Option<String>,
+    #[serde(default)]
+    pub(super) enabled: Option<bool>,
+    #[serde(default)]
+    pub(super) events: Option<Vec<String>>,
+}
+
+pub(super) fn notification_channel_events_json(raw: Option<&[String]>) -> Result<String, ApiError> {
+    let events = match raw {
+        Some(raw) => notify::normalize_channel_events(raw)
+            .map_err(|err| ApiError::bad_request(err.to_string()))?,
+        None => notify::NOTIFY_EVENTS.to_vec(),
+    };
+    serde_json::to_string(&events).map_err(ApiError::internal)
+}
+
+pub(super) fn encrypt_notification_channel_url(
+    state: &AppState,
+    raw: &str,
+) -> Result<(crate::crypto::EncryptedSecret, String), ApiError> {
+    let url =
+        notify::validate_channel_url(raw).map_err(|err| ApiError::bad_request(err.to_string()))?;
+    notify::encrypt_channel_url(state, &url).map_err(ApiError::internal)
+}
+
+pub(super) async fn load_notification_channel_items(
+    state: &AppState,
+    user_id: &str,
+) -> Result<Vec<NotificationChannelItem>, ApiError> {
+    let rows = sqlx::query_as::<_, NotificationChannelRow>(
+        r#"
+        SELECT
+          id, kind, url_host, enabled, events_json, last_delivery_status, last_delivery_error,
+          last_delivery_at, created_at, updated_at
+        FROM notification_channels
+        WHERE user_id = ?
+        ORDER BY created_at ASC, id ASC
+        "#,
+    )
+    .bind(user_id)
+    .fetch_all(&state.pool)
+    .await
+    .map_err(ApiError::internal)?;
+    Ok(rows
+        .into_iter()
+        .map(NotificationChannelItem::from)
+        .collect())
+}
+
+pub(super) async fn load_notification_channel_item(
+    state: &AppState,
+    user_id: &str,
+    channel_id: &str,
+) -> Result<NotificationChannelItem, ApiError> {
+    sqlx::query_as::<_, NotificationChannelRow>(
+        r#"
+        SELECT
+          id, kind, url_host, enabled, events_json, last_delivery_status, last_delivery_error,
+          last_delivery_at, created_at, updated_at
+        FROM notification_channels
+        WHERE id = ?
+          AND user_id = ?
+        LIMIT 1
+        "#,
+    )
+    .bind(channel_id)
+    .bind(user_id)
+    .fetch_optional(&state.pool)
+    .await
+    .map_err(ApiError::internal)?
+    .map(NotificationChannelItem::from)
+    .ok_or_else(notification_channel_not_found)
+}
+
+pub(super) fn notification_channel_not_found() -> ApiError {
+    ApiError::new(
+        StatusCode::NOT_FOUND,
+        codes::NOTIFICATION_CHANNEL_NOT_FOUND,
+        "notification channel not found",
+    )
+}
+
+pub async fn list_notification_channels(
+    State(state): State<Arc<AppState>>,
+    AuthedUser(user_id): AuthedUser,
+) -> Result<Json<Vec<NotificationChannelItem>>, ApiError> {
+    let items = load_notification_channel_items(state.as_ref(), user_id.as_str()).await?;
+    Ok(Json(items))
+}
+
+pub async fn create_notification_channel(
+    State(state): State<Arc<AppState>>,
+    AuthedUser(user_id): AuthedUser,
+    ApiJson(req): ApiJson<NotificationChannelCreateRequest>,
+) -> Result<Json<NotificationChannelItem>, ApiError> {
+    let kind = notify::normalize_channel_kind(&req.kind)
+        .map_err(|err| ApiError::bad_request(err.to_string()))?;
+    let (encrypted, url_host) = encrypt_notification_channel_url(state.as_ref(), &req.url)?;
+    let events_json = notification_channel_events_json(req.events.as_deref())?;
+
+    let channel_id = local_id::generate_local_id();
+    let now = chrono::Utc::now().to_rfc3339();
+    sqlx::query(
+        r#"
+        INSERT INTO notification_channels (
+          id, user_id, kind, url_ciphertext, url_nonce, url_host, enabled, events_json,
+          created_at, updated_at
+        )
+        VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+        "#,
+    )
+    .bind(channel_id.as_str())
+    .bind(user_id.as_str())
+    .bind(kind)
+    .bind(encrypted.ciphertext)
+    .bind(encrypted.nonce)
+    .bind(url_host.as_str())
+    .bind(i64::from(req.enabled.unwrap_or(true)))
+    .bind(events_json.as_str())
+    .bind(now.as_str())
+    .bind(now.as_str())
+    .execute(&state.pool)
+    .await
+    .map_err(ApiError::internal)?;
+
+    let item =
+        load_notification_channel_item(state.as_ref(), user_id.as_str(), channel_id.as_str())
+            .await?;
+    Ok(Json(item))
+}
+
+pub async fn patch_notification_channel(
+    State(state): State<Arc<AppState>>,
+    AuthedUser(user_id): AuthedUser,
+    Path(channel_id): Path<String>,
+    ApiJson(req): ApiJson<NotificationChannelPatchRequest>,
+) -> Result<Json<NotificationChannelItem>, ApiError> {
+    let channel_id = parse_local_id_param(channel_id, "channel_id")?;
+    // Ensure the channel belongs to the caller before touching anything.
+    load_notification_channel_item(state.as_ref(), user_id.as_str(), channel_id.as_str()).await?;
+
+    let kind = req
+        .kind
+        .as_deref()
+        .map(notify::normalize_channel_kind)
+        .transpose()
+        .map_err(|err| ApiError::bad_request(err.to_string()))?;
+    let url = req
+        .url
+        .as_deref()
+        .map(|raw| encrypt_notification_channel_url(state.as_ref(), raw))
+        .transpose()?;
+    let events_json = req
+        .events
+        .as_deref()
+        .map(|events| notification_channel_events_json(Some(events)))
+        .transpose()?;
+    let (url_ciphertext, url_nonce, url_host) = match url {
+        Some((encrypted, host)) => (
+            Some(encrypted.ciphertext),
+            Some(encrypted.nonce),
+            Some(host),
+        ),
+        None => (None, None, None),
+    };
+
+    let now = chrono::Utc::now().to_rfc3339();
+    sqlx::query(
+        r#"
+        UPDATE notification_channels
+        SET kind = COALESCE(?, kind),
+            url_ciphertext = COALESCE(?, url_ciphertext),
+            url_nonce = COALESCE(?, url_nonce),
+            url_host = COALESCE(?, url_host),
+            enabled = COALESCE(?, enabled),
+            events_json = COALESCE(?, events_json),
+            updated_at = ?
+        WHERE id = ?
+          AND user_id = ?
+        "#,
+    )
+    .bind(kind)
+    .bind(url_ciphertext)
+    .bind(url_nonce)
+    .bind(url_host)
+    .bind(req.enabled.map(i64::from))
+    .bind(events_json)
+    .bind(now.as_str())
+    .bind(channel_id.as_str())
+    .bind(user_id.as_str())
+    .execute(&state.pool)
+    .await
+    .map_err(ApiError::internal)?;
+
+    let item =
+        load_notification_channel_item(state.as_ref(), user_id.as_str(), channel_id.as_str())
+            .await?;
+    Ok(Json(item))
+}
+
+pub async fn delete_notification_channel(
+    State(state): State<Arc<AppState>>,
+    AuthedUser(user_id): AuthedUser,
+    Path(channel_id): Path<String>,
+) -> Result<Json<Vec<NotificationChannelItem>>, ApiError> {
+    let channel_id = parse_local_id_param(channel_id, "channel_id")?;
+
+    let deleted = sqlx::query(
+        r#"
+        DELETE FROM notification_channels
+        WHERE id = ?
+          AND user_id = ?
+        "#,
+    )
+    .bind(channel_id.as_str())
+    .bind(user_id.as_str())
+    .execute(&state.pool)
+    .await
+    .map_err(ApiError::internal)?
+    .rows_affected();
+    if deleted == 0 {
+        return Err(notification_channel_not_found());
+    }
+
+    let items = load_notification_channel_items(state.as_ref(), user_id.as_str()).await?;
+    Ok(Json(items))
+}
+
+#[derive(Debug, Serialize, sqlx::FromRow)]
+pub struct BriefItem {
+    pub(super) id: String,
+    pub(super) date: String,
+    pub(super) window_start: Option<String>,
+    pub(super) window_end: Option<String>,
+    pub(super) effective_time_zone: Option<String>,
+    pub(super) effective_local_boundary: Option<String>,
+    pub(super) release_count: usize,
+    pub(super) release_ids: Vec<String>,
+    pub(super) content_markdown: String,
+    pub(super) coverage: Option<ai::BriefCoverage>,
+    /// Releases behind each repo section, for "jump to release" links.
+    pub(super) sources: Vec<ai::BriefSource>,
+    pub(super) created_at: String,
+    /// Cached translations of this brief's date; `stale` once the brief was
+    /// regenerated after translating.
+    pub(super) translations: Vec<BriefTranslationSummary>,
+    /// The caller's own rating of this brief, if any.
+    pub(super) feedback: Option<BriefFeedback>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct BriefTranslationSummary {
+    pub(super) lang: String,
+    pub(super) status: String,
+}
+
+pub async fn list_briefs(
+    State(state): State<Arc<AppState>>,
+    AuthedUser(user_id): AuthedUser,
+) -> Result<Json<Vec<BriefItem>>, ApiError> {
+    #[derive(Debug, sqlx::FromRow)]
+    struct BriefRow {
+        id: String,
+        date: String,
+        window_start_utc: Option<String>,
+        window_end_utc: Option<String>,
+        effective_time_zone: Option<String>,
+        effective_local_boundary: Option<String>,
+        generation_source: String,
+        content_markdown: String,
+        coverage_json: Option<String>,
+        sources_json: Option<String>,
+        created_at: String,
+    }
+
+    #[derive(Debug, sqlx::FromRow)]
+    struct BriefMembershipRow {
+        brief_id: String,
+        release_id: i64,
+    }
+
+    let rows = sqlx::query_as::<_, BriefRow>(
+        r#"
+        SELECT
+          id,
+          date,
+          window_start_utc,
+          window_end_utc,
+          effective_time_zone,
+          effective_local_boundary,
+          generation_source,
+          content_markdown,
+          coverage_json,
+          sources_json,
+          created_at
+        FROM briefs
+        WHERE user_id = ?
+        ORDER BY COALESCE(window_end_utc, created_at) DESC, created_at DESC, id DESC
+        "#,
+    )
+    .bind(&user_id)
+    .fetch_all(&state.pool)
+    .await
+    .map_err(ApiError::internal)?;
+
+    let mut release_ids_by_brief = HashMap::<String, Vec<String>>::new();
+    if !rows.is_empty() {
+        let membership_rows = sqlx::query_as::<_, BriefMembershipRow>(
+            r#"
+            SELECT m.brief_id, m.release_id
+            FROM brief_release_memberships m
+            JOIN briefs b ON b.id = m.brief_id
+            WHERE b.user_id = ?
+            ORDER BY m.brief_id ASC, m.ordinal ASC
+            "#,
+        )
+        .bind(&user_id)
+        .fetch_all(&state.pool)
+        .await
+        .map_err(ApiError::internal)?;
+        for row in membership_rows {
+            release_ids_by_brief
+                .entry(row.brief_id)
+                .or_default()
+                .push(row.release_id.to_string());
+        }
+    }
+
+    let mut markdown_release_ids_by_brief = HashMap::<String, Vec<String>>::new();
+    let mut markdown_release_refs_by_brief = HashMap::<String, Vec<InternalReleaseRef>>::new();
+    let mut all_markdown_release_refs = Vec::<InternalReleaseRef>::new();
+    for row in &rows {
+        if !brief_uses_markdown_release_fallback(&row.generation_source)
+            || release_ids_by_brief.contains_key(&row.id)
+        {
+            continue;
+        }
+        let refs = extract_brief_release_refs(&row.content_markdown);
+        if refs.is_empty() {
+            continue;
+        }
+        all_markdown_release_refs.extend(refs.iter().cloned());
+        markdown_release_refs_by_brief.insert(row.id.clone(), refs);
+    }
+
+    if !all_markdown_release_refs.is_empty() {
+        let resolved_ids_by_ref =
+            resolve_brief_release_refs_batched(&state.pool, &all_markdown_release_refs)
+                .await
+                .map_err(ApiError::internal)?;
+
+        for (brief_id, refs) in markdown_release_refs_by_brief {
+            let mut seen_release_ids = HashSet::<i64>::new();
+            let release_ids = refs
+                .into_iter()
+                .filter_map(|reference| resolved_ids_by_ref.get(&reference).copied())
+                .filter(|release_id| seen_release_ids.insert(*release_id))
+                .map(|value| value.to_string())
+                .collect::<Vec<_>>();
+            if !release_ids.is_empty() {
+                markdown_release_ids_by_brief.insert(brief_id, release_ids);
+            }
+        }
+    }
+
+    let mut translations_by_date = HashMap::<String, Vec<(String, String, String)>>::new();
+    if !rows.is_empty() {
+        let translation_rows = sqlx::query_as::<_, (String, String, String, String)>(
+            r#"
+            SELECT entity_id, lang, source_hash, status
+            FROM ai_translations
+            WHERE user_id = ? AND entity_type = 'brief'
+            ORDER BY entity_id ASC, lang ASC
+            "#,
+        )
+        .bind(&user_id)
+        .fetch_all(&state.pool)
+        .await
+        .map_err(ApiError::internal)?;
+        for (date, lang, source_hash, status) in translation_rows {
+            translations_by_date
+                .entry(date)
+                .or_default()
+                .push((lang, source_hash, status));
+        }
+    }
+
+    let mut feedback_by_brief = HashMap::<String, BriefFeedback>::new();
+    if !rows.is_empty() {
+        let feedback_rows = sqlx::query_as::<_, BriefFeedbackRow>(
+            r#"
+            SELECT brief_id, rating, note, updated_at
+            FROM brief_feedback
+            WHERE user_id = ?
+            "#,
+        )
+        .bind(&user_id)
+        .fetch_all(&state.pool)
+        .await
+        .map_err(ApiError::internal)?;
+        for row in feedback_rows {
+            feedback_by_brief.insert(row.brief_id, row.feedback);
+        }
+    }
+
+    let items = rows
+        .into_iter()
+        .map(|r| {
+            let feedback = feedback_by_brief.remove(&r.id);
+            let release_ids = release_ids_by_brief
+                .remove(&r.id)
+                .or_else(|| markdown_release_ids_by_brief.remove(&r.id))
+                .unwrap_or_default();
+            let source_hash = brief_translation_source_hash(&r.content_markdown);
+            let translations = translations_by_date
+                .get(&r.date)
+                .into_iter()
+                .flatten()
+                .map(|(lang, hash, status)| BriefTranslationSummary {
+                    lang: lang.clone(),
+                    status: if *hash == source_hash {
+                        status.clone()
+                    } else {
+                        "stale".to_owned()
+                    },
+                })
+                .collect();
+            BriefItem {
+                id: r.id,
+                date: r.date,
+                window_start: r.window_start_utc,
+                window_end: r.window_end_utc,
+                effective_time_zone: r.effective_time_zone,
+                effective_local_boundary: r.effective_local_boundary,
+                release_count: release_ids.len(),
+                release_ids,
+                content_markdown: r.content_markdown,
+                coverage: ai::BriefCoverage::from_json(r.coverage_json.as_deref()),
+                sources: ai::BriefSource::list_from_json(r.sources_json.as_deref()),
+                created_at: r.created_at,
+                translations,
+                feedback,
+            }
+        })
+        .collect::<Vec<_>>();
+
+    Ok(Json(items))
+}
+
+/// Target languages a brief can be translated into, with the name used in
+/// the prompt.
+pub(super) const BRIEF_TRANSLATION_LANGS: [(&str, &str); 3] =
+    [("zh-CN", "简体中文"), ("en", "English"), ("ja", "日本語")];
+
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct TranslateBriefRequest {
+    #[schemars(extend("enum" = ["zh-CN", "en", "ja"]))]
+    pub(super) lang: String,
+}
+
+pub(super) fn brief_translation_source_hash(content_markdown: &str) -> String {
+    let normalized = content_markdown.replace("\r\n", "\n");
+    ai::sha256_hex(&format!("v=1\nkind=brief\nbody={}\n", normalized.trim()))
+}
+
+/// Translates the latest brief of `date` into another language. The result
+/// is cached per date and language and recomputed once the brief content
+/// changes.
+pub async fn translate_brief(
+    State(state): State<Arc<AppState>>,
+    AuthedUser(user_id): AuthedUser,
+    Path(date): Path<String>,
+    ApiJson(req): ApiJson<TranslateBriefRequest>,
+) -> Result<Json<TranslateResponse>, ApiError> {
+    let translated = run_with_api_llm_context(
+        "api.translate_brief",
+        Some(user_id.clone()),
+        translate_brief_for_user(state.as_ref(), user_id.as_str(), &date, &req.lang),
+    )
+    .await?;
+    Ok(Json(translated))
+}
+
+pub(super) async fn translate_brief_for_user(
+    state: &AppState,
+    user_id: &str,
+    date: &str,
+    lang: &str,
+) -> Result<TranslateResponse, ApiError> {
+    let date = chrono::NaiveDate::parse_from_str(date.trim(), "%Y-%m-%d")
+        .map_err(|_| ApiError::bad_request("date must be YYYY-MM-DD"))?
+        .to_string();
+    let (lang, language) = BRIEF_TRANSLATION_LANGS
+        .into_iter()
+        .find(|(known, _)| *known == lang.trim())
+        .ok_or_else(|| ApiError::bad_request("unsupported lang"))?;
+
+    let content_markdown = sqlx::query_scalar::<_, String>(
+        r#"
+        SELECT content_markdown
+        FROM briefs
+        WHERE user_id = ? AND date = ?
+        ORDER BY created_at DESC, id DESC
+        LIMIT 1
+        "#,
+    )
+    .bind(user_id)
+    .bind(date.as_str())
+    .fetch_optional(&state.pool)
+    .await
+    .map_err(ApiError::internal)?
+    .ok_or_else(|| ApiError::new(StatusCode::NOT_FOUND, codes::NOT_FOUND, "brief not found"))?;
+
+    if state.config.ai.is_none() {
+        return Ok(TranslateResponse {
+            lang: lang.to_owned(),
+            status: "disabled".to_owned(),
+            title: None,
+            summary: None,
+        });
+    }
+
+    let source_hash = brief_translation_source_hash(&content_markdown);
+    let cached = sqlx::query_as::<_, (String, String, Option<String>)>(
+        r#"
+        SELECT source_hash, status, summary
+        FROM ai_translations
+        WHERE user_id = ? AND entity_type = 'brief' AND entity_id = ? AND lang = ?
+        LIMIT 1
+        "#,
+    )
+    .bind(user_id)
+    .bind(date.as_str())
+    .bind(lang)
+    .fetch_optional(&state.pool)
+    .await
+    .map_err(ApiError::internal)?;
+    if let Some((cached_hash, status, summary)) = cached
+        && cached_hash == source_hash
+        && status == "ready"
+    {
+        return Ok(TranslateResponse {
+            lang: lang.to_owned(),
+            status,
+            title: None,
+            summary,
+        });
+    }
+
+    let requested_at = chrono::Utc::now().to_rfc3339();
+    let upsert = TranslationUpsert {
+        entity_type: "brief",
+        entity_id: &date,
+        lang,
+        source_hash: &source_hash,
+        title: None,
+        summary: None,
+    };
+    mark_translation_requested(state, user_id, requested_at.as_str(), upsert).await?;
+
+    let budget = release_detail_chunk_budget(state).await;
+    let chunks = split_markdown_chunks(&content_markdown, budget.max_chars);
+    let mut translated = String::new();
+    for (idx, chunk) in chunks.iter().enumerate() {
+        if chunk.trim().is_empty() {
+            translated.push_str(chunk);
+            continue;
+        }
+        match translate_brief_chunk(state, budget, language, chunk, idx + 1, chunks.len()).await {
+            Ok(out) => translated.push_str(&out),
+            Err(err) => {
+                let error_text = err.to_string();
+                upsert_translation_terminal_status(
+                    state,
+                    user_id,
+                    requested_at.as_str(),
+                    upsert,
+                    "error",
+                    Some(error_text.as_str()),
+                )
+                .await?;
+                return Err(err);
+            }
+        }
+    }
+
+    let summary = (!translated.trim().is_empty()).then_some(translated);
+    upsert_translation(
+        state,
+        user_id,
+        requested_at.as_str(),
+        TranslationUpsert {
+            summary: summary.as_deref(),
+            ..upsert
+        },
+    )
+    .await?;
+
+    Ok(TranslateResponse {
+        lang: lang.to_owned(),
+        status: "ready".to_owned(),
+        title: None,
+        summary,
+    })
+}
+
+#[derive(Debug, sqlx::FromRow)]
+pub(super) struct BriefSourceRow {
+    pub(super) section: String,
+    pub(super) included: i64,
+    #[sqlx(flatten)]
+    pub(super) feed: FeedRow,
+}
+
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct BriefSourceItem {
+    /// Repo heading of the brief section the release sits under.
+    pub(super) section: String,
+    /// False when the summary never came back for this release and the brief
+    /// fell back to a release-notes excerpt.
+    pub(super) included: bool,
+    pub(super) item: FeedItem,
+}
+
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct BriefSourcesResponse {
+    pub(super) brief_id: String,
+    pub(super) date: String,
+    /// False for briefs generated before sources were recorded; `items` is
+    /// then always empty.
+    pub(super) sources_available: bool,
+    pub(super) items: Vec<BriefSourceItem>,
+}
+
+/// Releases the latest brief of `date` was built from, in render order.
+pub async fn get_brief_sources(
+    State(state): State<Arc<AppState>>,
+    AuthedUser(user_id): AuthedUser,
+    Path(date): Path<String>,
+) -> Result<Json<BriefSourcesResponse>, ApiError> {
+    let date = chrono::NaiveDate::parse_from_str(date.trim(), "%Y-%m-%d")
+        .map_err(|_| ApiError::bad_request("date must be YYYY-MM-DD"))?
+        .to_string();
+
+    let (brief_id, sources_tracked) = sqlx::query_as::<_, (String, i64)>(
+        r#"
+        SELECT id, sources_tracked
+        FROM briefs
+        WHERE user_id = ? AND date = ?
+        ORDER BY created_at DESC, id DESC
+        LIMIT 1
+        "#,
+    )
+    .bind(user_id.as_str())
+    .bind(date.as_str())
+    .fetch_optional(&state.pool)
+    .await
+    .map_err(ApiError::internal)?
+    .ok_or_else(|| ApiError::new(StatusCode::NOT_FOUND, codes::NOT_FOUND, "brief not found"))?;
+
+    let rows = sqlx::query_as::<_, BriefSourceRow>(
+        r#"
+        WITH items AS (
+          SELECT
+            bs.section AS section,
+            bs.included AS included,
+            bs.ordinal AS ordinal,
+            'release' AS kind,
+            COALESCE(r.published_at, r.created_at, r.updated_at) AS sort_ts,
+            COALESCE(r.published_at, r.created_at, r.updated_at) AS ts,
+            printf('%020d', r.release_id) AS id_key,
+            CAST(r.release_id AS TEXT) AS entity_id,
+            r.release_id AS release_id,
+            r.node_id AS release_node_id,
+            COALESCE(vr.full_name, bs.section) AS repo_full_name,
+            vr.owner_login AS repo_owner_login,
+            vr.owner_avatar_url AS owner_avatar_url,
+            vr.description AS repo_description,
+            vr.html_url AS repo_html_url,
+            vr.open_graph_image_url AS open_graph_image_url,
+            vr.uses_custom_open_graph_image AS uses_custom_open_graph_image,
+            r.tag_name AS release_tag_name,
+            (
+              SELECT p.tag_name
+              FROM repo_releases p
+              WHERE p.repo_id = r.repo_id
+                AND (
+                  COALESCE(p.published_at, p.created_at, p.updated_at)
+                    < COALESCE(r.published_at, r.created_at, r.updated_at)
+                  OR (
+                    COALESCE(p.published_at, p.created_at, p.updated_at)
+                      = COALESCE(r.published_at, r.created_at, r.updated_at)
+                    AND p.release_id < r.release_id
+                  )
+                )
+              ORDER BY COALESCE(p.published_at, p.created_at, p.updated_at) DESC, p.release_id DESC
+              LIMIT 1
+            ) AS release_previous_tag_name,
+            COALESCE(NULLIF(TRIM(r.name), ''), r.tag_name) AS title,
+            r.html_url AS html_url,
+            r.body AS release_body,
+            r.react_plus1 AS react_plus1,
+            r.react_laugh AS react_laugh,
+            r.react_heart AS react_heart,
+            r.react_hooray AS react_hooray,
+            r.react_rocket AS react_rocket,
+            r.react_eyes AS react_eyes,
+            vr.source AS repo_source
+          FROM brief_sources bs
+          JOIN repo_releases r
+            ON r.release_id = bs.release_id
+          LEFT JOIN user_release_visible_repos vr
+            ON vr.user_id = ? AND vr.repo_id = r.repo_id
+          WHERE bs.brief_id = ?
+        )
+        SELECT
+          i.section, i.included,
+          i.kind, i.sort_ts, i.ts, i.id_key, i.entity_id, i.release_id, i.release_node_id,
+          i.repo_full_name, i.repo_owner_login, i.owner_avatar_url, i.repo_description, i.repo_html_url,
+          i.open_graph_image_url, i.uses_custom_open_graph_image,
+          i.release_tag_name, i.release_previous_tag_name,
+          i.title, NULL AS subtitle, NULL AS reason, NULL AS subject_type, i.html_url, NULL AS unread,
+          NULL AS actor_login, NULL AS actor_avatar_url, NULL AS actor_html_url,
+          i.release_body, i.react_plus1, i.react_laugh, i.react_heart, i.react_hooray, i.react_rocket, i.react_eyes,
+          i.repo_source,
+          t.source_hash AS trans_source_hash,
+          t.status AS trans_status,
+          t.title AS trans_title,
+          t.summary AS trans_summary,
+          t.error_text AS trans_error_text,
+          tw.status AS trans_work_status,
+          dt.source_hash AS detail_trans_source_hash,
+          dt.status AS detail_trans_status,
+          dt.title AS detail_trans_title,
+          dt.summary AS detail_trans_summary,
+          dt.error_text AS detail_trans_error_text,
+          dtw.status AS detail_trans_work_status,
+          s.source_hash AS smart_source_hash,
+          s.status AS smart_status,
+          s.title AS smart_title,
+          s.summary AS smart_summary,
+          s.error_text AS smart_error_text,
+          sw.status AS smart_work_status,
+          bm.id AS bookmark_id
+        FROM items i
+        LEFT JOIN ai_translations t
+          ON t.user_id = ? AND t.entity_type = 'release' AND t.entity_id = i.entity_id AND t.lang = 'zh-CN' AND t.status IN ('ready', 'disabled', 'missing', 'error')
+        LEFT JOIN translation_work_items tw
+          ON tw.id = t.active_work_item_id
+        LEFT JOIN ai_translations dt
+          ON dt.user_id = ? AND dt.entity_type = 'release_detail' AND dt.entity_id = i.entity_id AND dt.lang = 'zh-CN' AND dt.status IN ('ready', 'disabled', 'missing', 'error')
+        LEFT JOIN translation_work_items dtw
+          ON dtw.id = dt.active_work_item_id
+        LEFT JOIN ai_translations s
+          ON s.user_id = ? AND s.entity_type = 'release_smart' AND s.entity_id = i.entity_id AND s.lang = 'zh-CN' AND s.status IN ('ready', 'disabled', 'missing', 'error')
+        LEFT JOIN translation_work_items sw
+          ON sw.id = s.active_work_item_id
+        LEFT JOIN bookmarks bm
+          ON bm.user_id = ? AND bm.kind = i.kind AND bm.entity_id = i.entity_id
+        ORDER BY i.ordinal ASC
+        "#,
+    )
+    .bind(user_id.as_str())
+    .bind(brief_id.as_str())
+    .bind(user_id.as_str())
+    .bind(user_id.as_str())
+    .bind(user_id.as_str())
+    .bind(user_id.as_str())
+    .fetch_all(&state.pool)
+    .await
+    .map_err(ApiError::internal)?;
+
+    let details = rows
+        .iter()
+        .map(|row| (row.section.clone(), row.included != 0))
+        .collect::<Vec<_>>();
+    let page = feed_page_from_rows(
+        state.as_ref(),
+        user_id.as_str(),
+        rows.into_iter().map(|row| row.feed).collect(),
+        i64::MAX,
+        FeedExcerpt::default(),
+    )
+    .await?;
+    let items = details
+        .into_iter()
+        .zip(page.items)
+        .map(|((section, included), item)| BriefSourceItem {
+            section,
+            included,
+            item,
+        })
+        .collect();
+
+    Ok(Json(BriefSourcesResponse {
+        brief_id,
+        date,
+        sources_available: sources_tracked != 0,
+        items,
+    }))
+}
+
+pub(super) const BRIEF_FEEDBACK_NOTE_MAX_CHARS: usize = 2000;
+
+#[derive(Debug, Clone, Serialize, JsonSchema, sqlx::FromRow)]
+pub struct BriefFeedback {
+    /// `up` or `down`.
+    pub(super) rating: String,
+    pub(super) note: Option<String>,
+    pub(super) updated_at: String,
+}
+
+#[derive(Debug, sqlx::FromRow)]
+pub(super) struct BriefFeedbackRow {
+    pub(super) brief_id: String,
+    #[sqlx(flatten)]
+    pub(super) feedback: BriefFeedback,
+}
+
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct BriefFeedbackRequest {
+    #[schemars(extend("enum" = ["up", "down"]))]
+    pub(super) rating: String,
+    pub(super) note: Option<String>,
+}
+
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct BriefFeedbackResponse {
+    pub(super) brief_id: String,
+    pub(super) date: String,
+    pub(super) feedback: BriefFeedback,
+}
+
+/// Rates the latest brief of `date`. Rating it again replaces the earlier
+/// rating and note. A thumbs-down with a note is logged as a warning so it
+/// surfaces to admins before they open the feedback summary.
+pub async fn submit_brief_feedback(
+    State(state): State<Arc<AppState>>,
+    Path(date): Path<String>,
+    AuthedUser(user_id): AuthedUser,
+    ApiJson(req): ApiJson<BriefFeedbackRequest>,
+) -> Result<Json<BriefFeedbackResponse>, ApiError> {
+    let date = chrono::NaiveDate::parse_from_str(date.trim(), "%Y-%m-%d")
+        .map_err(|_| ApiError::bad_request("date must be YYYY-MM-DD"))?
+        .to_string();
+    let rating = match req.rating.trim() {
+        "up" => "up",
+        "down" => "down",
+        _ => return Err(ApiError::bad_request("rating must be up or down")),
+    };
+    let note = match req.note.as_deref().map(str::trim) {
+        None | Some("") => None,
+        Some(note) if note.chars().count() > BRIEF_FEEDBACK_NOTE_MAX_CHARS => {
+            return Err(ApiError::bad_request(format!(
+                "note must be at most {BRIEF_FEEDBACK_NOTE_MAX_CHARS} characters"
+            )));
+        }
+        Some(note) => Some(note.to_owned()),
+    };
+
+    let brief_id = sqlx::query_scalar::<_, String>(
+        r#"
+        SELECT id
+        FROM briefs
+        WHERE user_id = ? AND date = ?
+        ORDER BY created_at DESC, id DESC
+        LIMIT 1
+        "#,
+    )
+    .bind(user_id.as_str())
+    .bind(date.as_str())
+    .fetch_optional(&state.pool)
+    .await
+    .map_err(ApiError::internal)?
+    .ok_or_else(|| ApiError::new(StatusCode::NOT_FOUND, codes::NOT_FOUND, "brief not found"))?;
+
+    let now = chrono::Utc::now().to_rfc3339();
+    let feedback = state
+        .sqlite_writer
+        .write_foreground("brief_feedback_upsert", |_| async {
+            sqlx::query_as::<_, BriefFeedback>(
+                r#"
+                INSERT INTO brief_feedback (user_id, brief_id, rating, note, created_at, updated_at)
+                VALUES (?, ?, ?, ?, ?, ?)
+                ON CONFLICT(user_id, brief_id) DO UPDATE SET
+                  rating = excluded.rating,
+                  note = excluded.note,
+                  updated_at = excluded.updated_at
+                RETURNING rating, note, updated_at
+                "#,
+            )
+            .bind(user_id.as_str())
+            .bind(brief_id.as_str())
+            .bind(rating)
+            .bind(note.as_deref())
+            .bind(now.as_str())
+            .bind(now.as_str())
+            .fetch_one(&state.pool)
+            .await
+            .context("failed to store brief feedback")
+        })
+        .await
+        .map_err(ApiError::internal)?;
+
+    if rating == "down" && note.is_some() {
+        tracing::warn!(
+            user_id = %user_id,
+            brief_id = %brief_id,
+            brief_date = %date,
+            note = note.as_deref().unwrap_or_default(),
+            "brief rated down with a note"
+        );
+    }
+
+    Ok(Json(BriefFeedbackResponse {
+        brief_id,
+        date,
+        feedback,
+    }))
+}
+
+pub(super) async fn translate_brief_chunk(
+    state: &AppState,
+    budget: ReleaseDetailChunkBudget,
+    language: &str,
+    chunk: &str,
+    current: usize,
+    total: usize,
+) -> Result<String, ApiError> {
+    let system = format!(
+        "你是一个严谨的技术日报翻译助手，负责把 Markdown 日报翻译成{language}并保持 Markdown 结构。"
+    );
+    let prompt = format!(
+        "Chunk: {current}/{total}\n\nDaily brief chunk (Markdown):\n{chunk}\n\n请把这段日报翻译成{language} Markdown，要求：\n1) 保留原有 Markdown 结构（标题/列表/表格/引用/代码块）；\n2) 保留链接 URL、仓库名与代码；\n3) 不新增、不删减信息；\n4) 只输出翻译后的 Markdown，不要解释。"
+    );
+    let translated = ai::chat_completion(state, &system, &prompt, budget.max_output_tokens)
+        .await
+        .map_err(ai_call_api_error)?;
+    let translated = normalize_markdown_translation_output(chunk, translated);
+    if markdown_structure_preserved(chunk, &translated, RELEASE_DETAIL_CHUNK_STRUCTURE_CHECK) {
+        return Ok(translated);
+    }
+
+    let retry_prompt = format!(
+        "{prompt}\n\n上一次译文（结构不一致，需重译）：\n{translated}\n\n请重新翻译，并严格满足：译文非空行数必须与原文完全一致，每行保留相同 Markdown 前缀（#, -, 1., >）。"
+    );
+    let retry = ai::chat_completion(state, &system, &retry_prompt, budget.max_output_tokens)
+        .await
+        .map_err(ai_call_api_error)?;
+    let retry = normalize_markdown_translation_output(chunk, retry);
+    if !markdown_structure_preserved(chunk, &retry, RELEASE_DETAIL_CHUNK_STRUCTURE_CHECK) {
+        return Err(ApiError::internal(
+            "brief translation failed to preserve markdown structure",
+        ));
+    }
+    Ok(retry)
+}
+
+#[derive(Debug, Serialize)]
+pub struct BriefGenerateResponse {
+    pub(super) id: String,
+    pub(super) date: String,
+    pub(super) window_start: String,
+    pub(super) window_end: String,
+    pub(super) effective_time_zone: String,
+    pub(super) effective_local_boundary: String,
+    pub(super) release_count: usize,
+    pub(super) release_ids: Vec<String>,
+    pub(super) content_markdown: String,
+    pub(super) coverage: Option<ai::BriefCoverage>,
+    pub(super) sources: Vec<ai::BriefSource>,
+}
+
+#[derive(Debug, Deserialize, Default)]
+pub struct BriefGenerateRequest {
+    pub(super) date: Option<String>,
+    pub(super) window_hours: Option<i64>,
+    pub(super) window_start: Option<String>,
+    pub(super) window_end: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(super) enum BriefGenerateTarget {
+    Current,
+    KeyDate(chrono::NaiveDate),
+    Range {
+        start_utc: chrono::DateTime<chrono::Utc>,
+        end_utc: chrono::DateTime<chrono::Utc>,
+    },
+}
+
+pub(super) fn parse_brief_window_timestamp(
+    raw: &str,
+    field: &str,
+) -> Result<chrono::DateTime<chrono::Utc>, ApiError> {
+    chrono::DateTime::parse_from_rfc3339(raw.trim())
+        .map(|value| value.with_timezone(&chrono::Utc))
+        .map_err(|_| ApiError::bad_request(format!("invalid {field}, expected RFC3339")))
+}
+
+pub(super) fn resolve_brief_generate_target(
+    req: BriefGenerateRequest,
+    now: chrono::DateTime<chrono::Utc>,
+) -> Result<BriefGenerateTarget, ApiError> {
+    let has_explicit_window = req.window_start.is_some() || req.window_end.is_some();
+    let selectors = [
+        req.date.is_some(),
+        req.window_hours.is_some(),
+        has_explicit_window,
+    ];
+    if selectors.into_iter().filter(|selected| *selected).count() > 1 {
+        return Err(ApiError::bad_request(
+            "date, window_hours and window_start/window_end are mutually exclusive",
+        ));
+    }
+
+    if let Some(date) = req.date.as_deref() {
+        let key_date = chrono::NaiveDate::parse_from_str(date, "%Y-%m-%d")
+            .map_err(|_| ApiError::bad_request("invalid date, expected YYYY-MM-DD"))?;
+        return Ok(BriefGenerateTarget::KeyDate(key_date));
+    }
+
+    if let Some(window_hours) = req.window_hours {
+        let window_hours = window_hours.clamp(
+            briefs::ON_DEMAND_BRIEF_MIN_WINDOW_HOURS,
+            briefs::ON_DEMAND_BRIEF_MAX_WINDOW_HOURS,
+        );
+        return Ok(BriefGenerateTarget::Range {
+            start_utc: now - chrono::Duration::hours(window_hours),
+            end_utc: now,
+        });
+    }
+
+    if has_explicit_window {
+        let (Some(window_start), Some(window_end)) =
+            (req.window_start.as_deref(), req.window_end.as_deref())
+        else {
+            return Err(ApiError::bad_request(
+                "window_start and window_end must be provided together",
+            ));
+        };
+        let start_utc = parse_brief_window_timestamp(window_start, "window_start")?;
+        let end_utc = parse_brief_window_timestamp(window_end, "window_end")?;
+        if end_utc <= start_utc {
+            return Err(ApiError::bad_request(
+                "window_end must be after window_start",
+            ));
+        }
+        if end_utc - start_utc > chrono::Duration::days(briefs::ON_DEMAND_BRIEF_MAX_WINDOW_DAYS) {
+            return Err(ApiError::bad_request(format!(
+                "brief window must not exceed {} days",
+                briefs::ON_DEMAND_BRIEF_MAX_WINDOW_DAYS
+            )));
+        }
+        return Ok(BriefGenerateTarget::Range { start_utc, end_utc });
+    }
+
+    Ok(BriefGenerateTarget::Current)
+}
+
+pub async fn generate_brief(
+    State(state): State<Arc<AppState>>,
+    AuthedUser(user_id): AuthedUser,
+    Query(mode_query): Query<ReturnModeQuery>,
+    payload: Option<Json<BriefGenerateRequest>>,
+) -> Result<Response, ApiError> {
+    let mode = ReturnMode::from_query(&mode_query)?;
+    let target = resolve_brief_generate_target(
+        payload.map(|Json(body)| body).unwrap_or_default(),
+        chrono::Utc::now(),
+    )?;
+
+    if !matches!(mode, ReturnMode::Sync) {
+        let mut task_payload = json!({ "user_id": user_id.clone() });
+        match &target {
+            BriefGenerateTarget::Current => {
+                task_payload["key_date"] = Value::Null;
+            }
+            BriefGenerateTarget::KeyDate(key_date) => {
+                task_payload["key_date"] = json!(key_date.to_string());
+            }
+            BriefGenerateTarget::Range { start_utc, end_utc } => {
+                task_payload["window_start"] = json!(start_utc.to_rfc3339());
+                task_payload["window_end"] = json!(end_utc.to_rfc3339());
+            }
+        }
+        return enqueue_or_stream_task(
+            state,
+            mode,
+            jobs::NewTask {
+                task_type: jobs::TASK_BRIEF_GENERATE.to_owned(),
+                payload: task_payload,
+                source: "api.generate_brief".to_owned(),
+                requested_by: Some(user_id.clone()),
+                parent_task_id: None,
+                priority: jobs::TaskPriority::High,
+            },
+        )
+        .await;
+    }
+
+    let snapshot = match target {
+        BriefGenerateTarget::KeyDate(key_date) => {
+            run_with_api_llm_context(
+                "api.generate_brief.sync",
+                Some(user_id.clone()),
+                ai::generate_daily_brief_snapshot_for_key_date(
+                    state.as_ref(),
+                    user_id.as_str(),
+                    key_date,
+                ),
+            )
+            .await
+        }
+        BriefGenerateTarget::Range { start_utc, end_utc } => {
+            run_with_api_llm_context(
+                "api.generate_brief.sync",
+                Some(user_id.clone()),
+                ai::generate_brief_snapshot_for_range(
+                    state.as_ref(),
+                    user_id.as_str(),
+                    start_utc,
+                    end_utc,
+                ),
+            )
+            .await
+        }
+        BriefGenerateTarget::Current => {
+            run_with_api_llm_context(
+                "api.generate_brief.sync",
+                Some(user_id.clone()),
+                ai::generate_daily_brief_snapshot_for_current(state.as_ref(), user_id.as_str()),
+            )
+            .await
+        }
+    }
+    .map_err(ai_call_api_error)?;
+    briefs::deliver_brief_email_best_effort(state.as_ref(), user_id.as_str(), &snapshot).await;
+    notify::notify_brief_ready_best_effort(
+        state.as_ref(),
+        user_id.as_str(),
+        &snapshot,
+        "api.generate_brief.notify",
+        None,
+    )
+    .await;
+
+    Ok(Json(BriefGenerateResponse {
+        id: snapshot.id,
+        date: snapshot.date,
+        window_start: snapshot.window_start,
+        window_end: snapshot.window_end,
+        effective_time_zone: snapshot.effective_time_zone,
+        effective_local_boundary: snapshot.effective_local_boundary,
+        release_count: snapshot.release_ids.len(),
+        release_ids: snapshot
+            .release_ids
+            .into_iter()
+            .map(|value| value.to_string())
+            .collect(),
+        content_markdown: snapshot.content_markdown,
+        coverage: snapshot.coverage,
+        sources: snapshot.sources,
+    })
+    .into_response())
+}
+
+pub(super) async fn resolve_brief_preview_window(
+    state: &AppState,
+    user_id: &str,
+    target: BriefGenerateTarget,
+) -> Result<briefs::DailyWindow, ApiError> {
+    let preferences = briefs::load_daily_brief_preferences(state, user_id)
+        .await
+        .map_err(ApiError::internal)?;
+    match target {
+        BriefGenerateTarget::Current => {
+            briefs::compute_current_daily_window(&preferences, chrono::Utc::now())
+        }
+        BriefGenerateTarget::KeyDate(key_date) => {
+            briefs::compute_daily_window_for_key_date(&preferences, key_date)
+        }
+        BriefGenerateTarget::Range { start_utc, end_utc } => {
+            briefs::compute_daily_window_for_range(&preferences, start_utc, end_utc)
+        }
+    }
+    .map_err(ApiError::internal)
+}
+
+pub(super) async fn preview_brief_for_user(
+    state: &AppState,
+    user_id: &str,
+    req: BriefGenerateRequest,
+) -> Result<Json<ai::BriefPreview>, ApiError> {
+    let target = resolve_brief_generate_target(req, chrono::Utc::now())?;
+    let window = resolve_brief_preview_window(state, user_id, target).await?;
+    let preview = ai::preview_brief_for_window(state, user_id, &window)
+        .await
+        .map_err(ApiError::internal)?;
+    Ok(Json(preview))
+}
+
+/// Dry run of `generate_brief`: same window selectors, but it returns the
+/// assembled prompts instead of calling the model or storing a snapshot.
+pub async fn preview_brief(
+    State(state): State<Arc<AppState>>,
+    AuthedUser(user_id): AuthedUser,
+    payload: Option<Json<BriefGenerateRequest>>,
+) -> Result<Json<ai::BriefPreview>, ApiError> {
+    preview_brief_for_user(
+        state.as_ref(),
+        user_id.as_str(),
+        payload.map(|Json(body)| body).unwrap_or_default(),
+    )
+    .await
+}
+
+pub async fn admin_preview_user_brief(
+    State(state): State<Arc<AppState>>,
+    _: AdminUser,
+    Path(user_id): Path<String>,
+    payload: Option<Json<BriefGenerateRequest>>,
+) -> Result<Json<ai::BriefPreview>, ApiError> {
+    let user_id = parse_local_id_param(user_id, "user_id")?;
+    sqlx::query_scalar::<_, String>(r#"SELECT id FROM users WHERE id = ? LIMIT 1"#)
+        .bind(user_id.as_str())
+        .fetch_optional(&state.pool)
+        .await
+        .map_err(ApiError::internal)?
+        .ok_or_else(|| ApiError::new(StatusCode::NOT_FOUND, codes::NOT_FOUND, "user not found"))?;
+
+    preview_brief_for_user(
+        state.as_ref(),
+        user_id.as_str(),
+        payload.map(|Json(body)| body).unwrap_or_default(),
+    )
+    .await
+}
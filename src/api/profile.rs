@@ -0,0 +1,1299 @@
+use super::*;
+
+pub(super) const SESSION_PENDING_ACCESS_SYNC_REASON: &str = "pending_access_sync_reason";
+pub(super) const SESSION_ACTIVITY_TOUCHED_AT: &str = "activity_touched_at";
+pub(super) const SESSION_ACTIVITY_TOUCH_INTERVAL_SECS: i64 = 15 * 60;
+pub(super) const ACCESS_SYNC_REASON_INACTIVE_OVER_1H: &str = "inactive_over_1h";
+pub(super) const ADMIN_DASHBOARD_TASK_TYPES: [(&str, &str); 3] = [
+    (jobs::TASK_TRANSLATE_RELEASE_BATCH, "翻译"),
+    (jobs::TASK_SUMMARIZE_RELEASE_SMART_BATCH, "润色"),
+    (jobs::TASK_BRIEF_DAILY_SLOT, "日报"),
+];
+
+pub(super) fn resolve_release_full_name(html_url: &str, repo_id: i64) -> String {
+    parse_repo_full_name_from_release_url(html_url).unwrap_or_else(|| format!("unknown/{repo_id}"))
+}
+
+pub(super) fn extract_brief_release_refs(markdown: &str) -> Vec<InternalReleaseRef> {
+    let mut refs = Vec::new();
+    let mut seen = HashSet::new();
+    let mut i = 0usize;
+
+    while i < markdown.len() {
+        let rest = &markdown[i..];
+
+        if rest.starts_with('[')
+            && let Some(text_end_rel) = rest.find("](")
+            && let Some(url_end_rel) = rest[text_end_rel + 2..].find(')')
+        {
+            let url_start = i + text_end_rel + 2;
+            let url_end = url_start + url_end_rel;
+            let target = &markdown[url_start..url_end];
+            if let Some(reference) = parse_internal_release_ref(target)
+                && seen.insert(reference.clone())
+            {
+                refs.push(reference);
+            }
+            i = url_end + 1;
+            continue;
+        }
+
+        let mut chars = rest.chars();
+        let ch = chars.next().expect("rest is non-empty");
+        i += ch.len_utf8();
+    }
+
+    refs
+}
+
+#[cfg(test)]
+pub(super) fn extract_brief_release_ids(markdown: &str) -> Vec<i64> {
+    extract_brief_release_refs(markdown)
+        .into_iter()
+        .filter_map(|reference| match reference {
+            InternalReleaseRef::ReleaseId(release_id) => Some(release_id),
+            InternalReleaseRef::Locator(_) => None,
+        })
+        .collect()
+}
+
+pub(super) fn brief_contains_release_link(
+    markdown: &str,
+    release_id: i64,
+    locator: Option<&ReleaseLocator>,
+) -> bool {
+    extract_brief_release_refs(markdown)
+        .into_iter()
+        .any(|candidate| match candidate {
+            InternalReleaseRef::ReleaseId(candidate_id) => candidate_id == release_id,
+            InternalReleaseRef::Locator(candidate_locator) => {
+                locator.is_some_and(|expected_locator| candidate_locator.matches(expected_locator))
+            }
+        })
+}
+
+pub(super) fn brief_uses_markdown_release_fallback(generation_source: &str) -> bool {
+    matches!(
+        generation_source,
+        "legacy" | "history_recompute_failed" | "content_refresh_failed"
+    )
+}
+
+pub(super) const BRIEF_RELEASE_REF_LOCATOR_BATCH_LIMIT: usize = 300;
+
+pub(super) async fn resolve_brief_release_refs_batched(
+    pool: &sqlx::SqlitePool,
+    refs: &[InternalReleaseRef],
+) -> Result<HashMap<InternalReleaseRef, i64>, sqlx::Error> {
+    let mut unique_refs = Vec::<InternalReleaseRef>::new();
+    let mut seen_refs = HashSet::<InternalReleaseRef>::new();
+    for reference in refs {
+        if seen_refs.insert(reference.clone()) {
+            unique_refs.push(reference.clone());
+        }
+    }
+
+    let mut resolved_ids_by_ref = HashMap::<InternalReleaseRef, i64>::new();
+    let mut batch = Vec::<InternalReleaseRef>::new();
+    let mut batch_locator_count = 0usize;
+
+    for reference in unique_refs {
+        let locator_count = usize::from(matches!(reference, InternalReleaseRef::Locator(_)));
+        if !batch.is_empty()
+            && batch_locator_count + locator_count > BRIEF_RELEASE_REF_LOCATOR_BATCH_LIMIT
+        {
+            let resolved = resolve_release_refs(pool, &batch).await?;
+            resolved_ids_by_ref.extend(resolved.resolved);
+            batch.clear();
+            batch_locator_count = 0;
+        }
+
+        batch.push(reference);
+        batch_locator_count += locator_count;
+    }
+
+    if !batch.is_empty() {
+        let resolved = resolve_release_refs(pool, &batch).await?;
+        resolved_ids_by_ref.extend(resolved.resolved);
+    }
+
+    Ok(resolved_ids_by_ref)
+}
+
+pub(super) async fn user_has_brief_access_to_release(
+    state: &AppState,
+    user_id: &str,
+    release_id: i64,
+    locator: Option<&ReleaseLocator>,
+) -> Result<bool, ApiError> {
+    let membership_exists = sqlx::query_scalar::<_, i64>(
+        r#"
+        SELECT COUNT(*)
+        FROM brief_release_memberships m
+        JOIN briefs b ON b.id = m.brief_id
+        WHERE b.user_id = ?
+          AND m.release_id = ?
+        "#,
+    )
+    .bind(user_id)
+    .bind(release_id)
+    .fetch_one(&state.pool)
+    .await
+    .map_err(ApiError::internal)?;
+
+    if membership_exists > 0 {
+        return Ok(true);
+    }
+
+    let briefs = sqlx::query_scalar::<_, String>(
+        r#"
+        SELECT content_markdown
+        FROM briefs
+        WHERE user_id = ?
+          AND generation_source IN ('legacy', 'history_recompute_failed', 'content_refresh_failed')
+        "#,
+    )
+    .bind(user_id)
+    .fetch_all(&state.pool)
+    .await
+    .map_err(ApiError::internal)?;
+
+    Ok(briefs
+        .iter()
+        .any(|markdown| brief_contains_release_link(markdown, release_id, locator)))
+}
+
+pub(crate) fn parse_local_id_param(raw: String, field: &str) -> Result<String, ApiError> {
+    local_id::normalize_local_id(&raw)
+        .ok_or_else(|| ApiError::bad_request(format!("invalid {field}")))
+}
+
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct MeResponse {
+    pub(super) user: UserSummary,
+    pub(super) access_sync: AccessSyncBootstrap,
+    pub(super) dashboard: DashboardBootstrap,
+    /// Set while an admin views the app as `user`; every write is rejected.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(super) impersonating: Option<MeImpersonation>,
+}
+
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct MeImpersonation {
+    pub(super) admin_id: String,
+    pub(super) user: UserSummary,
+}
+
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct UserSummary {
+    pub(super) id: String,
+    pub(super) github_user_id: i64,
+    pub(super) login: String,
+    pub(super) name: Option<String>,
+    pub(super) avatar_url: Option<String>,
+    pub(super) email: Option<String>,
+    pub(super) is_admin: bool,
+}
+
+#[derive(Debug, sqlx::FromRow)]
+pub(super) struct MeUserRow {
+    pub(super) id: String,
+    pub(super) is_admin: i64,
+    pub(super) is_disabled: i64,
+    pub(super) last_active_at: Option<String>,
+    pub(super) include_own_releases: i64,
+}
+
+#[derive(Debug, sqlx::FromRow)]
+pub(super) struct MeFirstGitHubRow {
+    pub(super) github_user_id: i64,
+    pub(super) login: String,
+    pub(super) name: Option<String>,
+    pub(super) avatar_url: Option<String>,
+    pub(super) email: Option<String>,
+}
+
+pub(super) async fn load_viewer_user(
+    state: &AppState,
+    user_id: &str,
+) -> Result<MeFirstGitHubRow, ApiError> {
+    sqlx::query_as::<_, MeFirstGitHubRow>(
+        r#"
+        SELECT github_user_id, login, name, avatar_url, email
+        FROM github_connections
+        WHERE user_id = ?
+        ORDER BY linked_at ASC, id ASC
+        LIMIT 1
+        "#,
+    )
+    .bind(user_id)
+    .fetch_optional(&state.pool)
+    .await
+    .map_err(ApiError::internal)?
+    .ok_or_else(|| {
+        ApiError::new(
+            StatusCode::UNAUTHORIZED,
+            codes::UNAUTHORIZED,
+            "session user has no github connection",
+        )
+    })
+}
+
+#[derive(Debug, sqlx::FromRow)]
+pub(super) struct MeLinuxDoAvatarRow {
+    pub(super) avatar_url: Option<String>,
+}
+
+#[derive(Debug, Serialize, JsonSchema)]
+pub(super) struct AccessSyncBootstrap {
+    pub(super) task_id: Option<String>,
+    pub(super) task_type: Option<String>,
+    pub(super) event_path: Option<String>,
+    pub(super) reason: String,
+}
+
+#[derive(Debug, Serialize, JsonSchema)]
+pub(super) struct DashboardBootstrap {
+    pub(super) daily_boundary_local: String,
+    pub(super) daily_boundary_time_zone: Option<String>,
+    pub(super) daily_boundary_utc_offset_minutes: i32,
+    pub(super) include_own_releases: bool,
+}
+
+impl AccessSyncBootstrap {
+    pub(super) fn none() -> Self {
+        Self {
+            task_id: None,
+            task_type: None,
+            event_path: None,
+            reason: "none".to_owned(),
+        }
+    }
+
+    pub(super) fn from_task(task: jobs::EnqueuedTask, reason: impl Into<String>) -> Self {
+        let task_id = task.task_id;
+        Self {
+            event_path: Some(format!("/api/tasks/{task_id}/events")),
+            task_id: Some(task_id),
+            task_type: Some(task.task_type),
+            reason: reason.into(),
+        }
+    }
+}
+
+pub async fn me(
+    State(state): State<Arc<AppState>>,
+    // Reads the raw session: `me` reports the impersonation and disabled
+    // state that the AuthedUser extractor resolves away or rejects.
+    session: Session,
+) -> Result<Json<MeResponse>, ApiError> {
+    let session_user_id = require_user_id(&session).await?;
+    let impersonated_user_id =
+        impersonation::resolve_impersonation(state.as_ref(), &session, &session_user_id).await?;
+    let user_id = impersonated_user_id
+        .clone()
+        .unwrap_or_else(|| session_user_id.clone());
+    let row = sqlx::query_as::<_, MeUserRow>(
+        r#"
+        SELECT id, is_admin, is_disabled, last_active_at, include_own_releases
+        FROM users
+        WHERE id = ?
+        "#,
+    )
+    .bind(&user_id)
+    .fetch_optional(&state.pool)
+    .await
+    .map_err(ApiError::internal)?;
+
+    let Some(row) = row else {
+        session.clear().await;
+        return Err(ApiError::new(
+            StatusCode::UNAUTHORIZED,
+            codes::UNAUTHORIZED,
+            "session user not found",
+        ));
+    };
+
+    if impersonated_user_id.is_none()
+        && let Err(err) = ensure_account_enabled(row.is_disabled != 0)
+    {
+        session.clear().await;
+        return Err(err);
+    }
+
+    let first_github = sqlx::query_as::<_, MeFirstGitHubRow>(
+        r#"
+        SELECT github_user_id, login, name, avatar_url, email
+        FROM github_connections
+        WHERE user_id = ?
+        ORDER BY linked_at ASC, id ASC
+        LIMIT 1
+        "#,
+    )
+    .bind(&row.id)
+    .fetch_optional(&state.pool)
+    .await
+    .map_err(ApiError::internal)?;
+    let Some(first_github) = first_github else {
+        session.clear().await;
+        return Err(ApiError::new(
+            StatusCode::UNAUTHORIZED,
+            codes::UNAUTHORIZED,
+            "session user has no github connection",
+        ));
+    };
+
+    let linuxdo_avatar = sqlx::query_as::<_, MeLinuxDoAvatarRow>(
+        r#"
+        SELECT avatar_url
+        FROM linuxdo_connections
+        WHERE user_id = ?
+        LIMIT 1
+        "#,
+    )
+    .bind(&row.id)
+    .fetch_optional(&state.pool)
+    .await
+    .map_err(ApiError::internal)?
+    .and_then(|record| record.avatar_url);
+
+    // Impersonation is read-only: no sync task and no activity for the target.
+    let access_sync = if impersonated_user_id.is_some() {
+        AccessSyncBootstrap::none()
+    } else {
+        let access_sync = maybe_bootstrap_access_sync(state.as_ref(), &session, &row).await?;
+        touch_user_last_active_at(state.as_ref(), &row.id).await?;
+        access_sync
+    };
+    let preferences = briefs::load_daily_brief_preferences(state.as_ref(), &row.id)
+        .await
+        .map_err(ApiError::internal)?;
+    let daily_boundary_local = briefs::format_daily_brief_local_time(preferences.local_time);
+    let daily_boundary_time_zone = Some(preferences.time_zone.clone());
+    let daily_boundary_utc_offset_minutes =
+        briefs::current_utc_offset_minutes(&preferences, chrono::Utc::now())
+            .map_err(ApiError::internal)?;
+
+    let user = UserSummary {
+        id: row.id,
+        github_user_id: first_github.github_user_id,
+        login: first_github.login,
+        name: first_github.name,
+        avatar_url: linuxdo_avatar.or(first_github.avatar_url),
+        email: first_github.email,
+        is_admin: row.is_admin != 0,
+    };
+    let impersonating = impersonated_user_id.map(|_| MeImpersonation {
+        admin_id: session_user_id,
+        user: user.clone(),
+    });
+    Ok(Json(MeResponse {
+        user,
+        access_sync,
+        dashboard: DashboardBootstrap {
+            daily_boundary_local,
+            daily_boundary_time_zone,
+            daily_boundary_utc_offset_minutes,
+            include_own_releases: row.include_own_releases != 0,
+        },
+        impersonating,
+    }))
+}
+
+pub(super) fn last_active_is_stale(last_active_at: Option<&str>) -> bool {
+    let Some(last_active_at) = last_active_at else {
+        return true;
+    };
+    let Ok(last_active_at) = chrono::DateTime::parse_from_rfc3339(last_active_at) else {
+        return true;
+    };
+    chrono::Utc::now().signed_duration_since(last_active_at.with_timezone(&chrono::Utc))
+        >= chrono::Duration::hours(1)
+}
+
+pub(super) async fn maybe_bootstrap_access_sync(
+    state: &AppState,
+    session: &Session,
+    row: &MeUserRow,
+) -> Result<AccessSyncBootstrap, ApiError> {
+    if let Some(task) = jobs::find_inflight_task_for_requester(
+        state,
+        jobs::TASK_SYNC_ACCESS_REFRESH,
+        row.id.as_str(),
+    )
+    .await
+    .map_err(ApiError::internal)?
+    {
+        clear_pending_access_sync_reason(session).await?;
+        return Ok(AccessSyncBootstrap::from_task(task, "reused_inflight"));
+    }
+
+    let pending_reason = load_pending_access_sync_reason(session).await?;
+    let reason = if let Some(reason) = pending_reason.as_deref() {
+        reason
+    } else if row.last_active_at.is_some() {
+        ACCESS_SYNC_REASON_INACTIVE_OVER_1H
+    } else {
+        "first_visit"
+    };
+
+    if pending_reason.is_none() && !last_active_is_stale(row.last_active_at.as_deref()) {
+        return Ok(AccessSyncBootstrap::none());
+    }
+
+    let task = jobs::enqueue_singleton_task_for_requester(
+        state,
+        jobs::NewTask {
+            task_type: jobs::TASK_SYNC_ACCESS_REFRESH.to_owned(),
+            payload: json!({ "user_id": row.id.clone() }),
+            source: "api.me".to_owned(),
+            requested_by: Some(row.id.clone()),
+            parent_task_id: None,
+            priority: jobs::TaskPriority::High,
+        },
+    )
+    .await
+    .map_err(ApiError::internal)?;
+    clear_pending_access_sync_reason(session).await?;
+    Ok(AccessSyncBootstrap::from_task(task, reason))
+}
+
+pub(super) async fn touch_user_last_active_at(
+    state: &AppState,
+    user_id: &str,
+) -> Result<(), ApiError> {
+    let now = chrono::Utc::now().to_rfc3339();
+    let result = state
+        .sqlite_writer
+        .try_write("user_activity", || async {
+            sqlx::query(
+                r#"
+                UPDATE users
+                SET last_active_at = ?
+                WHERE id = ?
+                "#,
+            )
+            .bind(now.as_str())
+            .bind(user_id)
+            .execute(&state.pool)
+            .await
+            .context("failed to touch user last_active_at")?;
+            Ok(())
+        })
+        .await;
+    match result {
+        Ok(Some(())) => {}
+        Ok(None) => {
+            tracing::debug!(
+                user_id,
+                "skip best-effort last_active_at touch because sqlite writer is busy"
+            );
+        }
+        Err(err) => {
+            if crate::sqlite_write::is_sqlite_busy_error(err.as_ref()) {
+                tracing::warn!(
+                    user_id,
+                    err = %err,
+                    "skip best-effort last_active_at touch after sqlite busy retries"
+                );
+                return Ok(());
+            }
+            return Err(ApiError::internal(err));
+        }
+    }
+    Ok(())
+}
+
+pub(super) async fn load_pending_access_sync_reason(
+    session: &Session,
+) -> Result<Option<String>, ApiError> {
+    session
+        .get::<String>(SESSION_PENDING_ACCESS_SYNC_REASON)
+        .await
+        .map_err(ApiError::internal)
+}
+
+pub(super) async fn mark_pending_access_sync_reason(
+    session: &Session,
+    reason: &str,
+) -> Result<(), ApiError> {
+    session
+        .insert(SESSION_PENDING_ACCESS_SYNC_REASON, reason)
+        .await
+        .map_err(ApiError::internal)
+}
+
+pub(super) async fn clear_pending_access_sync_reason(session: &Session) -> Result<(), ApiError> {
+    session
+        .remove::<String>(SESSION_PENDING_ACCESS_SYNC_REASON)
+        .await
+        .map(|_| ())
+        .map_err(ApiError::internal)
+}
+
+#[derive(Debug, Serialize)]
+pub struct DailyBriefProfileResponse {
+    pub(super) user_id: String,
+    pub(super) daily_brief_local_time: String,
+    pub(super) daily_brief_time_zone: String,
+    pub(super) include_own_releases: bool,
+    pub(super) brief_email_enabled: bool,
+    pub(super) brief_email: Option<String>,
+    pub(super) sync_frequency_minutes: i64,
+    pub(super) muted_notification_reasons: Vec<String>,
+    pub(super) skip_muted_notifications: bool,
+    pub(super) last_active_at: Option<String>,
+}
+
+pub type AdminUserProfileResponse = DailyBriefProfileResponse;
+pub type MeProfileResponse = DailyBriefProfileResponse;
+
+#[derive(Debug, Deserialize)]
+pub struct DailyBriefProfilePatchRequest {
+    pub(super) daily_brief_local_time: String,
+    pub(super) daily_brief_time_zone: String,
+    #[serde(default)]
+    pub(super) include_own_releases: Option<bool>,
+    #[serde(default)]
+    pub(super) brief_email_enabled: Option<bool>,
+    /// Overrides the GitHub account email for brief delivery; blank clears it.
+    #[serde(default)]
+    pub(super) brief_email: Option<String>,
+    /// One of [`jobs::USER_SYNC_FREQUENCY_CHOICES`]; 0 turns scheduled sync off.
+    #[serde(default)]
+    pub(super) sync_frequency_minutes: Option<i64>,
+    /// Values from [`sync::NOTIFICATION_REASONS`]; replaces the stored list.
+    #[serde(default)]
+    pub(super) muted_notification_reasons: Option<Vec<String>>,
+    /// Drop muted notifications during sync instead of storing them as read.
+    #[serde(default)]
+    pub(super) skip_muted_notifications: Option<bool>,
+}
+
+#[derive(Debug, Serialize, sqlx::FromRow)]
+pub struct SyncAutoFetchTaskItem {
+    pub(super) id: String,
+    pub(super) status: String,
+    pub(super) source: String,
+    pub(super) skipped: bool,
+    pub(super) duration_ms: Option<i64>,
+    pub(super) created_at: String,
+    pub(super) started_at: Option<String>,
+    pub(super) finished_at: Option<String>,
+}
+
+#[derive(Debug, sqlx::FromRow)]
+pub(super) struct DailyBriefProfileRow {
+    pub(super) daily_brief_local_time: Option<String>,
+    pub(super) daily_brief_time_zone: Option<String>,
+    pub(super) include_own_releases: i64,
+    pub(super) brief_email_enabled: i64,
+    pub(super) brief_email: Option<String>,
+    pub(super) sync_frequency_minutes: i64,
+    pub(super) muted_notification_reasons: String,
+    pub(super) skip_muted_notifications: i64,
+    pub(super) daily_brief_utc_time: String,
+    pub(super) last_active_at: Option<String>,
+}
+
+pub(super) async fn load_recent_sync_auto_fetch_tasks(
+    state: &AppState,
+) -> Result<Vec<SyncAutoFetchTaskItem>, ApiError> {
+    sqlx::query_as::<_, SyncAutoFetchTaskItem>(
+        r#"
+        WITH recent_roots AS (
+          SELECT
+            id,
+            status,
+            source,
+            result_json,
+            created_at,
+            started_at,
+            finished_at
+          FROM job_tasks
+          WHERE task_type = ?
+            AND COALESCE(json_extract(result_json, '$.skipped'), 0) != 1
+          ORDER BY created_at DESC, id DESC
+          LIMIT 3
+        ),
+        chain_tasks AS (
+          SELECT
+            id AS root_id,
+            finished_at
+          FROM recent_roots
+
+          UNION ALL
+
+          SELECT
+            child.parent_task_id AS root_id,
+            child.finished_at
+          FROM job_tasks child
+          JOIN recent_roots root ON root.id = child.parent_task_id
+          WHERE child.task_type IN (?, ?)
+            AND child.parent_task_id IS NOT NULL
+        ),
+        chain_rollup AS (
+          SELECT
+            root_id,
+            MAX(finished_at) AS chain_finished_at,
+            SUM(CASE WHEN finished_at IS NULL THEN 1 ELSE 0 END) AS unfinished_count
+          FROM chain_tasks
+          GROUP BY root_id
+        )
+        SELECT
+            root.id,
+            root.status,
+            root.source,
+            COALESCE(json_extract(root.result_json, '$.skipped'), 0) = 1 AS skipped,
+            CASE
+            WHEN rollup.unfinished_count = 0 AND rollup.chain_finished_at IS NOT NULL
+              THEN CAST((julianday(rollup.chain_finished_at) - julianday(root.created_at)) * 86400000 AS INTEGER)
+            ELSE NULL
+          END AS duration_ms,
+          root.created_at,
+          root.started_at,
+          CASE
+            WHEN rollup.unfinished_count = 0 THEN rollup.chain_finished_at
+            ELSE NULL
+          END AS finished_at
+        FROM recent_roots root
+        JOIN chain_rollup rollup ON rollup.root_id = root.id
+        ORDER BY root.created_at DESC, root.id DESC
+        "#,
+    )
+    .bind(jobs::TASK_SYNC_SUBSCRIPTIONS)
+    .bind(jobs::TASK_TRANSLATE_RELEASE_BATCH)
+    .bind(jobs::TASK_SUMMARIZE_RELEASE_SMART_BATCH)
+    .fetch_all(&state.pool)
+    .await
+    .map_err(ApiError::internal)
+}
+
+pub(super) async fn load_daily_brief_profile(
+    state: &AppState,
+    user_id: &str,
+) -> Result<DailyBriefProfileResponse, ApiError> {
+    let row = sqlx::query_as::<_, DailyBriefProfileRow>(
+        r#"
+        SELECT
+          daily_brief_local_time,
+          daily_brief_time_zone,
+          include_own_releases,
+          brief_email_enabled,
+          brief_email,
+          sync_frequency_minutes,
+          muted_notification_reasons,
+          skip_muted_notifications,
+          daily_brief_utc_time,
+          last_active_at
+        FROM users
+        WHERE id = ?
+        LIMIT 1
+        "#,
+    )
+    .bind(user_id)
+    .fetch_optional(&state.pool)
+    .await
+    .map_err(ApiError::internal)?;
+
+    let Some(row) = row else {
+        return Err(ApiError::new(
+            StatusCode::NOT_FOUND,
+            codes::NOT_FOUND,
+            "user not found",
+        ));
+    };
+
+    let preferences = briefs::derive_daily_brief_preferences(
+        &state.config,
+        row.daily_brief_local_time.as_deref(),
+        row.daily_brief_time_zone.as_deref(),
+        Some(row.daily_brief_utc_time.as_str()),
+        chrono::Utc::now(),
+    );
+
+    Ok(DailyBriefProfileResponse {
+        user_id: user_id.to_owned(),
+        daily_brief_local_time: briefs::format_daily_brief_local_time(preferences.local_time),
+        daily_brief_time_zone: preferences.time_zone,
+        include_own_releases: row.include_own_releases != 0,
+        brief_email_enabled: row.brief_email_enabled != 0,
+        brief_email: row.brief_email,
+        sync_frequency_minutes: row.sync_frequency_minutes,
+        muted_notification_reasons: serde_json::from_str(&row.muted_notification_reasons)
+            .unwrap_or_default(),
+        skip_muted_notifications: row.skip_muted_notifications != 0,
+        last_active_at: row.last_active_at,
+    })
+}
+
+pub(super) async fn persist_daily_brief_profile(
+    state: &AppState,
+    user_id: &str,
+    req: DailyBriefProfilePatchRequest,
+) -> Result<DailyBriefProfileResponse, ApiError> {
+    let local_time = briefs::parse_daily_brief_local_time(&req.daily_brief_local_time)
+        .map_err(|err| ApiError::bad_request(err.to_string()))?;
+    let time_zone = briefs::parse_daily_brief_time_zone(&req.daily_brief_time_zone)
+        .map_err(|err| ApiError::bad_request(err.to_string()))?;
+    briefs::validate_hour_aligned_time_zone(&time_zone, chrono::Utc::now())
+        .map_err(|err| ApiError::bad_request(err.to_string()))?;
+    let enabled_hours = briefs::load_enabled_daily_brief_scheduler_hours(&state.pool)
+        .await
+        .map_err(ApiError::internal)?;
+    let missing_hours =
+        briefs::missing_daily_brief_scheduler_hours(local_time, &time_zone, &enabled_hours)
+            .map_err(|err| ApiError::bad_request(err.to_string()))?;
+    if !missing_hours.is_empty() {
+        let missing_hours = missing_hours
+            .into_iter()
+            .map(|hour| format!("{hour:02}:00Z"))
+            .collect::<Vec<_>>()
+            .join(", ");
+        return Err(ApiError::bad_request(format!(
+            "invalid daily brief schedule for current scheduler configuration (missing enabled UTC slots: {missing_hours})"
+        )));
+    }
+
+    let brief_email = req
+        .brief_email
+        .as_deref()
+        .map(str::trim)
+        .map(|value| {
+            if value.is_empty() {
+                return Ok(None);
+            }
+            value
+                .parse::<lettre::Address>()
+                .map(|_| Some(value.to_owned()))
+                .map_err(|_| ApiError::bad_request("invalid brief email address"))
+        })
+        .transpose()?;
+    if let Some(minutes) = req.sync_frequency_minutes
+        && !jobs::USER_SYNC_FREQUENCY_CHOICES.contains(&minutes)
+    {
+        return Err(ApiError::bad_request(format!(
+            "sync_frequency_minutes must be one of {:?}",
+            jobs::USER_SYNC_FREQUENCY_CHOICES
+        )));
+    }
+    let muted_notification_reasons = req
+        .muted_notification_reasons
+        .map(|reasons| {
+            let reasons = normalize_notification_reasons(reasons.iter().map(String::as_str))?;
+            serde_json::to_string(&reasons).map_err(ApiError::internal)
+        })
+        .transpose()?;
+
+    let now = chrono::Utc::now().to_rfc3339();
+    sqlx::query(
+        r#"
+        UPDATE users
+        SET daily_brief_local_time = ?,
+            daily_brief_time_zone = ?,
+            include_own_releases = COALESCE(?, include_own_releases),
+            brief_email_enabled = COALESCE(?, brief_email_enabled),
+            brief_email = CASE WHEN ? THEN ? ELSE brief_email END,
+            sync_frequency_minutes = COALESCE(?, sync_frequency_minutes),
+            muted_notification_reasons = COALESCE(?, muted_notification_reasons),
+            skip_muted_notifications = COALESCE(?, skip_muted_notifications),
+            updated_at = ?
+        WHERE id = ?
+        "#,
+    )
+    .bind(briefs::format_daily_brief_local_time(local_time))
+    .bind(time_zone)
+    .bind(
+        req.include_own_releases
+            .map(|value| if value { 1_i64 } else { 0_i64 }),
+    )
+    .bind(
+        req.brief_email_enabled
+            .map(|value| if value { 1_i64 } else { 0_i64 }),
+    )
+    .bind(brief_email.is_some())
+    .bind(brief_email.flatten())
+    .bind(req.sync_frequency_minutes)
+    .bind(muted_notification_reasons)
+    .bind(
+        req.skip_muted_notifications
+            .map(|value| if value { 1_i64 } else { 0_i64 }),
+    )
+    .bind(now.as_str())
+    .bind(user_id)
+    .execute(&state.pool)
+    .await
+    .map_err(ApiError::internal)?;
+
+    load_daily_brief_profile(state, user_id).await
+}
+
+pub async fn me_get_profile(
+    State(state): State<Arc<AppState>>,
+    AuthedUser(user_id): AuthedUser,
+) -> Result<Json<MeProfileResponse>, ApiError> {
+    Ok(Json(
+        load_daily_brief_profile(state.as_ref(), &user_id).await?,
+    ))
+}
+
+pub async fn me_patch_profile(
+    State(state): State<Arc<AppState>>,
+    AuthedUser(user_id): AuthedUser,
+    ApiJson(req): ApiJson<DailyBriefProfilePatchRequest>,
+) -> Result<Json<MeProfileResponse>, ApiError> {
+    Ok(Json(
+        persist_daily_brief_profile(state.as_ref(), &user_id, req).await?,
+    ))
+}
+
+#[derive(Debug, Serialize, sqlx::FromRow)]
+pub struct LinuxDoConnectionResponse {
+    pub(super) linuxdo_user_id: i64,
+    pub(super) username: String,
+    pub(super) name: Option<String>,
+    pub(super) avatar_url: Option<String>,
+    pub(super) trust_level: i64,
+    pub(super) active: bool,
+    pub(super) silenced: bool,
+    pub(super) linked_at: String,
+    pub(super) updated_at: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct MeLinuxDoResponse {
+    pub(super) available: bool,
+    pub(super) connection: Option<LinuxDoConnectionResponse>,
+}
+
+#[derive(Debug, Serialize, JsonSchema, sqlx::FromRow)]
+pub struct GitHubConnectionResponse {
+    pub(super) id: String,
+    pub(super) github_user_id: i64,
+    pub(super) login: String,
+    pub(super) name: Option<String>,
+    pub(super) avatar_url: Option<String>,
+    pub(super) email: Option<String>,
+    pub(super) scopes: String,
+    pub(super) is_primary: bool,
+    pub(super) linked_at: String,
+    pub(super) updated_at: String,
+}
+
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct MeGitHubConnectionsResponse {
+    pub(super) items: Vec<GitHubConnectionResponse>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct AuthBindContextLinuxDoResponse {
+    pub(super) linuxdo_user_id: i64,
+    pub(super) username: String,
+    pub(super) name: Option<String>,
+    pub(super) avatar_url: String,
+    pub(super) trust_level: i64,
+    pub(super) active: bool,
+    pub(super) silenced: bool,
+}
+
+#[derive(Debug, Serialize)]
+pub struct AuthBindContextResponse {
+    pub(super) linuxdo_available: bool,
+    pub(super) pending_linuxdo: Option<AuthBindContextLinuxDoResponse>,
+    pub(super) pending_passkey: Option<AuthBindContextPasskeyResponse>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct AuthBindContextPasskeyResponse {
+    pub(super) label: String,
+    pub(super) created_at: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct MePasskeysResponse {
+    pub(super) items: Vec<PasskeySummary>,
+}
+
+pub async fn me_get_linuxdo(
+    State(state): State<Arc<AppState>>,
+    AuthedUser(user_id): AuthedUser,
+) -> Result<Json<MeLinuxDoResponse>, ApiError> {
+    let connection = sqlx::query_as::<_, LinuxDoConnectionResponse>(
+        r#"
+        SELECT
+          linuxdo_user_id,
+          username,
+          name,
+          avatar_url,
+          trust_level,
+          active,
+          silenced,
+          linked_at,
+          updated_at
+        FROM linuxdo_connections
+        WHERE user_id = ?
+        LIMIT 1
+        "#,
+    )
+    .bind(user_id)
+    .fetch_optional(&state.pool)
+    .await
+    .map_err(ApiError::internal)?;
+
+    Ok(Json(MeLinuxDoResponse {
+        available: state.config.linuxdo.is_some(),
+        connection,
+    }))
+}
+
+pub async fn me_delete_linuxdo(
+    State(state): State<Arc<AppState>>,
+    AuthedUser(user_id): AuthedUser,
+) -> Result<Json<MeLinuxDoResponse>, ApiError> {
+    sqlx::query(
+        r#"
+        DELETE FROM linuxdo_connections
+        WHERE user_id = ?
+        "#,
+    )
+    .bind(user_id)
+    .execute(&state.pool)
+    .await
+    .map_err(ApiError::internal)?;
+
+    Ok(Json(MeLinuxDoResponse {
+        available: state.config.linuxdo.is_some(),
+        connection: None,
+    }))
+}
+
+pub async fn auth_get_bind_context(
+    State(state): State<Arc<AppState>>,
+    session: Session,
+) -> Result<Json<AuthBindContextResponse>, ApiError> {
+    #[derive(Debug, Deserialize)]
+    struct PendingLinuxDoSession {
+        linuxdo_user_id: i64,
+        username: String,
+        name: Option<String>,
+        avatar_url: String,
+        trust_level: i64,
+        active: bool,
+        silenced: bool,
+    }
+
+    let pending_linuxdo = session
+        .get::<PendingLinuxDoSession>("pending_linuxdo")
+        .await
+        .map_err(ApiError::internal)?
+        .map(|pending| AuthBindContextLinuxDoResponse {
+            linuxdo_user_id: pending.linuxdo_user_id,
+            username: pending.username,
+            name: pending.name,
+            avatar_url: pending.avatar_url,
+            trust_level: pending.trust_level,
+            active: pending.active,
+            silenced: pending.silenced,
+        });
+    let pending_passkey = session
+        .get::<PendingPasskeyCredentialSession>("pending_passkey_credential")
+        .await
+        .map_err(ApiError::internal)?;
+    let pending_passkey = if let Some(pending_passkey) = pending_passkey {
+        if pending_passkey_bind_is_expired(&pending_passkey) {
+            let _ = session
+                .remove::<PendingPasskeyCredentialSession>("pending_passkey_credential")
+                .await;
+            None
+        } else {
+            Some(AuthBindContextPasskeyResponse {
+                label: pending_passkey.label,
+                created_at: pending_passkey.created_at,
+            })
+        }
+    } else {
+        None
+    };
+
+    Ok(Json(AuthBindContextResponse {
+        linuxdo_available: state.config.linuxdo.is_some(),
+        pending_linuxdo,
+        pending_passkey,
+    }))
+}
+
+pub async fn me_get_github_connections(
+    State(state): State<Arc<AppState>>,
+    AuthedUser(user_id): AuthedUser,
+) -> Result<Json<MeGitHubConnectionsResponse>, ApiError> {
+    let items = sqlx::query_as::<_, GitHubConnectionResponse>(
+        r#"
+        SELECT
+          id,
+          github_user_id,
+          login,
+          name,
+          avatar_url,
+          email,
+          scopes,
+          is_primary,
+          linked_at,
+          updated_at
+        FROM github_connections
+        WHERE user_id = ?
+        ORDER BY linked_at ASC, id ASC
+        "#,
+    )
+    .bind(user_id)
+    .fetch_all(&state.pool)
+    .await
+    .map_err(ApiError::internal)?;
+
+    Ok(Json(MeGitHubConnectionsResponse { items }))
+}
+
+pub async fn me_get_passkeys(
+    State(state): State<Arc<AppState>>,
+    AuthedUser(user_id): AuthedUser,
+) -> Result<Json<MePasskeysResponse>, ApiError> {
+    let items = load_passkey_summaries(state.as_ref(), user_id.as_str()).await?;
+    Ok(Json(MePasskeysResponse { items }))
+}
+
+pub async fn me_delete_github_connection(
+    State(state): State<Arc<AppState>>,
+    AuthedUser(user_id): AuthedUser,
+    Path(connection_id): Path<String>,
+) -> Result<Json<MeGitHubConnectionsResponse>, ApiError> {
+    let connection_id = parse_local_id_param(connection_id, "connection_id")?;
+
+    let Some(was_primary) = sqlx::query_scalar::<_, bool>(
+        r#"
+        SELECT is_primary
+        FROM github_connections
+        WHERE id = ?
+          AND user_id = ?
+        "#,
+    )
+    .bind(connection_id.as_str())
+    .bind(user_id.as_str())
+    .fetch_optional(&state.pool)
+    .await
+    .map_err(ApiError::internal)?
+    else {
+        return Err(ApiError::new(
+            StatusCode::NOT_FOUND,
+            codes::GITHUB_CONNECTION_NOT_FOUND,
+            "github connection not found",
+        ));
+    };
+
+    let total = sqlx::query_scalar::<_, i64>(
+        r#"
+        SELECT COUNT(*)
+        FROM github_connections
+        WHERE user_id = ?
+        "#,
+    )
+    .bind(user_id.as_str())
+    .fetch_one(&state.pool)
+    .await
+    .map_err(ApiError::internal)?;
+    if total <= 1 {
+        return Err(ApiError::new(
+            StatusCode::CONFLICT,
+            codes::LAST_GITHUB_CONNECTION_GUARD,
+            "at least one github connection is required",
+        ));
+    }
+
+    let mut tx = state.pool.begin().await.map_err(ApiError::internal)?;
+    sqlx::query(
+        r#"
+        DELETE FROM github_connections
+        WHERE id = ?
+          AND user_id = ?
+        "#,
+    )
+    .bind(connection_id.as_str())
+    .bind(user_id.as_str())
+    .execute(&mut *tx)
+    .await
+    .map_err(ApiError::internal)?;
+
+    // Stars go only when no remaining account stars the same repo.
+    sqlx::query(
+        r#"
+        DELETE FROM starred_repos
+        WHERE user_id = ?
+          AND repo_id IN (
+            SELECT repo_id
+            FROM starred_repo_sources
+            WHERE user_id = ?
+              AND github_connection_id = ?
+          )
+          AND NOT EXISTS (
+            SELECT 1
+            FROM starred_repo_sources s
+            WHERE s.user_id = starred_repos.user_id
+              AND s.repo_id = starred_repos.repo_id
+              AND s.github_connection_id <> ?
+          )
+        "#,
+    )
+    .bind(user_id.as_str())
+    .bind(user_id.as_str())
+    .bind(connection_id.as_str())
+    .bind(connection_id.as_str())
+    .execute(&mut *tx)
+    .await
+    .map_err(ApiError::internal)?;
+
+    for sql in [
+        r#"
+        DELETE FROM reaction_pat_tokens
+        WHERE user_id = ?
+          AND owner_github_connection_id = ?
+        "#,
+        r#"
+        DELETE FROM starred_repo_sources
+        WHERE user_id = ?
+          AND github_connection_id = ?
+        "#,
+        // A notification thread belongs to the one account it was synced
+        // through, so those rows go with it.
+        r#"
+        DELETE FROM notifications
+        WHERE user_id = ?
+          AND source_github_connection_id = ?
+        "#,
+    ] {
+        sqlx::query(sql)
+            .bind(user_id.as_str())
+            .bind(connection_id.as_str())
+            .execute(&mut *tx)
+            .await
+            .map_err(ApiError::internal)?;
+    }
+
+    if was_primary {
+        sqlx::query(
+            r#"
+            UPDATE github_connections
+            SET is_primary = 1
+            WHERE id = (
+              SELECT id
+              FROM github_connections
+              WHERE user_id = ?
+              ORDER BY linked_at ASC, id ASC
+              LIMIT 1
+            )
+            "#,
+        )
+        .bind(user_id.as_str())
+        .execute(&mut *tx)
+        .await
+        .map_err(ApiError::internal)?;
+    }
+
+    tx.commit().await.map_err(ApiError::internal)?;
+
+    me_get_github_connections(State(state), AuthedUser(user_id)).await
+}
+
+pub async fn me_delete_passkey(
+    State(state): State<Arc<AppState>>,
+    AuthedUser(user_id): AuthedUser,
+    Path(passkey_id): Path<String>,
+) -> Result<Json<MePasskeysResponse>, ApiError> {
+    let passkey_id = parse_local_id_param(passkey_id, "passkey_id")?;
+
+    let exists = sqlx::query_scalar::<_, i64>(
+        r#"
+        SELECT COUNT(*)
+        FROM user_passkeys
+        WHERE id = ?
+          AND user_id = ?
+        "#,
+    )
+    .bind(passkey_id.as_str())
+    .bind(user_id.as_str())
+    .fetch_one(&state.pool)
+    .await
+    .map_err(ApiError::internal)?;
+    if exists == 0 {
+        return Err(ApiError::new(
+            StatusCode::NOT_FOUND,
+            codes::PASSKEY_NOT_FOUND,
+            "passkey not found",
+        ));
+    }
+
+    let mut tx = state.pool.begin().await.map_err(ApiError::internal)?;
+    sqlx::query(
+        r#"
+        DELETE FROM user_passkeys
+        WHERE id = ?
+          AND user_id = ?
+        "#,
+    )
+    .bind(passkey_id.as_str())
+    .bind(user_id.as_str())
+    .execute(&mut *tx)
+    .await
+    .map_err(ApiError::internal)?;
+
+    let remaining = sqlx::query_scalar::<_, i64>(
+        r#"
+        SELECT COUNT(*)
+        FROM user_passkeys
+        WHERE user_id = ?
+        "#,
+    )
+    .bind(user_id.as_str())
+    .fetch_one(&mut *tx)
+    .await
+    .map_err(ApiError::internal)?;
+
+    if remaining == 0 {
+        sqlx::query(
+            r#"
+            UPDATE users
+            SET passkey_user_handle_uuid = NULL, updated_at = ?
+            WHERE id = ?
+            "#,
+        )
+        .bind(chrono::Utc::now().to_rfc3339())
+        .bind(user_id.as_str())
+        .execute(&mut *tx)
+        .await
+        .map_err(ApiError::internal)?;
+    }
+
+    tx.commit().await.map_err(ApiError::internal)?;
+
+    me_get_passkeys(State(state), AuthedUser(user_id)).await
+}
+
+pub async fn admin_get_user_profile(
+    State(state): State<Arc<AppState>>,
+    Path(user_id): Path<String>,
+    _: AdminUser,
+) -> Result<Json<AdminUserProfileResponse>, ApiError> {
+    let user_id = parse_local_id_param(user_id, "user_id")?;
+    Ok(Json(
+        load_daily_brief_profile(state.as_ref(), &user_id).await?,
+    ))
+}
+
+pub async fn admin_patch_user_profile(
+    State(state): State<Arc<AppState>>,
+    _: AdminUser,
+    Path(user_id): Path<String>,
+    ApiJson(req): ApiJson<DailyBriefProfilePatchRequest>,
+) -> Result<Json<AdminUserProfileResponse>, ApiError> {
+    let user_id = parse_local_id_param(user_id, "user_id")?;
+    Ok(Json(
+        persist_daily_brief_profile(state.as_ref(), &user_id, req).await?,
+    ))
+}
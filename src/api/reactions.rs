@@ -0,0 +1,2181 @@
+use super::*;
+
+#[derive(Debug, Serialize)]
+pub struct ReactionTokenCheckSummary {
+    pub(super) state: String, // idle | valid | invalid | error
+    pub(super) message: Option<String>,
+    pub(super) checked_at: Option<String>,
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct ReactionTokenOwnerSummary {
+    pub(super) github_connection_id: String,
+    pub(super) github_user_id: i64,
+    pub(super) login: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ReactionTokenStatusResponse {
+    pub(super) configured: bool,
+    /// The GitHub login token already has `public_repo`/`repo`, so reactions
+    /// work without a PAT.
+    pub(super) oauth_ready: bool,
+    pub(super) masked_token: Option<String>,
+    pub(super) check: ReactionTokenCheckSummary,
+    pub(super) owner: Option<ReactionTokenOwnerSummary>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ReactionTokenRequest {
+    pub(super) token: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ReactionTokenCheckResponse {
+    pub(super) state: String, // valid | invalid
+    pub(super) message: String,
+    pub(super) owner: Option<ReactionTokenOwnerSummary>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+pub struct ReactionTokenDiagnoseRequest {
+    /// `owner/name`; when absent a sample of the user's starred repos is
+    /// checked, private ones first.
+    pub(super) repo_full_name: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ReactionTokenDiagnoseResponse {
+    /// False once GitHub rejected the PAT itself; the stored check state is
+    /// flipped to `invalid` at the same time.
+    pub(super) token_valid: bool,
+    pub(super) items: Vec<ReactionTokenRepoDiagnosis>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ReactionTokenRepoDiagnosis {
+    pub(super) repo_full_name: String,
+    pub(super) repo_visible: bool,
+    pub(super) can_react: bool,
+    /// GitHub's `viewerPermission` for the PAT owner, e.g. `READ` or `ADMIN`.
+    pub(super) viewer_permission: Option<String>,
+    pub(super) suggested_fix: Option<String>,
+}
+
+#[derive(Debug, sqlx::FromRow)]
+pub(super) struct ReactionTokenStatusRow {
+    pub(super) masked_token: String,
+    pub(super) last_check_state: String,
+    pub(super) last_check_message: Option<String>,
+    pub(super) last_checked_at: Option<String>,
+    pub(super) owner_github_connection_id: Option<String>,
+    pub(super) owner_github_user_id: Option<i64>,
+    pub(super) owner_login: Option<String>,
+}
+
+#[derive(Debug, sqlx::FromRow)]
+pub(super) struct ReactionTokenSecretRow {
+    pub(super) token_ciphertext: Vec<u8>,
+    pub(super) token_nonce: Vec<u8>,
+}
+
+pub(super) fn mask_pat_token(token: &str) -> String {
+    let trimmed = token.trim();
+    let chars = trimmed.chars().collect::<Vec<_>>();
+    if chars.len() <= 8 {
+        return "********".to_owned();
+    }
+    let head = chars[..4].iter().collect::<String>();
+    let tail = chars[chars.len().saturating_sub(4)..]
+        .iter()
+        .collect::<String>();
+    format!("{head}...{tail}")
+}
+
+pub(super) fn has_public_repo_scope(scopes: &str) -> bool {
+    scopes
+        .split(|c: char| c == ',' || c.is_whitespace())
+        .map(str::trim)
+        .any(|scope| scope == "public_repo")
+}
+
+/// GitHub PATs (classic and fine-grained) are short runs of `[A-Za-z0-9_]`;
+/// anything else is rejected before it reaches GitHub or the database.
+pub(super) const MAX_PAT_TOKEN_CHARS: usize = 255;
+
+pub(super) fn validate_pat_token_shape(token: &str) -> Result<(), ApiError> {
+    if token.len() > MAX_PAT_TOKEN_CHARS {
+        return Err(ApiError::bad_request(format!(
+            "token must be at most {MAX_PAT_TOKEN_CHARS} characters"
+        )));
+    }
+    if !token
+        .bytes()
+        .all(|byte| byte.is_ascii_alphanumeric() || byte == b'_')
+    {
+        return Err(ApiError::bad_request(
+            "token may only contain letters, digits and underscores",
+        ));
+    }
+    Ok(())
+}
+
+pub(super) async fn check_reaction_pat_with_github(
+    state: &AppState,
+    token: &str,
+    user_id: Option<&str>,
+) -> Result<ReactionTokenCheckResponse, ApiError> {
+    let token = token.trim();
+    if token.is_empty() {
+        return Err(ApiError::bad_request("token is required"));
+    }
+    validate_pat_token_shape(token)?;
+
+    let resp = state
+        .http
+        .get("https://api.github.com/user")
+        .bearer_auth(token)
+        .header(reqwest::header::USER_AGENT, "OctoRill")
+        .header(reqwest::header::ACCEPT, "application/vnd.github+json")
+        .header("X-GitHub-Api-Version", "2022-11-28")
+        .send()
+        .await
+        .map_err(ApiError::internal)?;
+
+    let status = resp.status();
+    let headers = resp.headers().clone();
+    let body = resp.text().await.map_err(ApiError::internal)?;
+    if let Some(user_id) = user_id {
+        github_rate_limit::record(
+            state,
+            user_id,
+            github_rate_limit::GitHubTokenKind::Pat,
+            &headers,
+        )
+        .await;
+    }
+
+    if status == reqwest::StatusCode::OK {
+        let github_user =
+            serde_json::from_str::<serde_json::Value>(&body).map_err(ApiError::internal)?;
+        let github_user_id = github_user
+            .get("id")
+            .and_then(|value| value.as_i64())
+            .ok_or_else(|| ApiError::internal("github user id missing from PAT check"))?;
+        let github_login = github_user
+            .get("login")
+            .and_then(|value| value.as_str())
+            .ok_or_else(|| ApiError::internal("github login missing from PAT check"))?
+            .to_owned();
+        let scopes = headers
+            .get("x-oauth-scopes")
+            .and_then(|v| v.to_str().ok())
+            .unwrap_or("");
+        if !scopes.is_empty() && !has_repo_scope(scopes) && !has_public_repo_scope(scopes) {
+            return Ok(ReactionTokenCheckResponse {
+                state: "invalid".to_owned(),
+                message: "classic PAT needs public_repo (public) or repo (private)".to_owned(),
+                owner: None,
+            });
+        }
+
+        let owner = if let Some(user_id) = user_id {
+            let owner = sqlx::query_as::<_, (String, i64, String)>(
+                r#"
+                SELECT id, github_user_id, login
+                FROM github_connections
+                WHERE user_id = ?
+                  AND github_user_id = ?
+                LIMIT 1
+                "#,
+            )
+            .bind(user_id)
+            .bind(github_user_id)
+            .fetch_optional(&state.pool)
+            .await
+            .map_err(ApiError::internal)?;
+
+            let Some((github_connection_id, github_user_id, login)) = owner else {
+                return Ok(ReactionTokenCheckResponse {
+                    state: "invalid".to_owned(),
+                    message:
+                        "PAT owner is not bound to the current OctoRill account; bind that GitHub account first"
+                            .to_owned(),
+                    owner: None,
+                });
+            };
+            Some(ReactionTokenOwnerSummary {
+                github_connection_id,
+                github_user_id,
+                login,
+            })
+        } else {
+            Some(ReactionTokenOwnerSummary {
+                github_connection_id: String::new(),
+                github_user_id,
+                login: github_login,
+            })
+        };
+
+        return Ok(ReactionTokenCheckResponse {
+            state: "valid".to_owned(),
+            message: "token is valid".to_owned(),
+            owner,
+        });
+    }
+
+    if status == reqwest::StatusCode::UNAUTHORIZED {
+        return Ok(ReactionTokenCheckResponse {
+            state: "invalid".to_owned(),
+            message: "token is invalid or expired".to_owned(),
+            owner: None,
+        });
+    }
+
+    if status == reqwest::StatusCode::FORBIDDEN {
+        let remaining = headers
+            .get("x-ratelimit-remaining")
+            .and_then(|v| v.to_str().ok())
+            .map(str::trim);
+        if remaining == Some("0") || is_rate_limit_message(&body) {
+            return Err(github_rate_limited_error());
+        }
+        return Ok(ReactionTokenCheckResponse {
+            state: "invalid".to_owned(),
+            message: "token cannot access GitHub user API; check PAT permissions".to_owned(),
+            owner: None,
+        });
+    }
+
+    Err(ApiError::new(
+        StatusCode::BAD_GATEWAY,
+        codes::GITHUB_UNAVAILABLE,
+        format!("github check failed with status {status}"),
+    ))
+}
+
+pub(super) async fn load_reaction_pat_status_row(
+    state: &AppState,
+    user_id: &str,
+) -> Result<Option<ReactionTokenStatusRow>, ApiError> {
+    sqlx::query_as::<_, ReactionTokenStatusRow>(
+        r#"
+        SELECT
+          masked_token,
+          last_check_state,
+          last_check_message,
+          last_checked_at,
+          owner_github_connection_id,
+          owner_github_user_id,
+          owner_login
+        FROM reaction_pat_tokens
+        WHERE user_id = ?
+        "#,
+    )
+    .bind(user_id)
+    .fetch_optional(&state.pool)
+    .await
+    .map_err(ApiError::internal)
+}
+
+pub(super) async fn load_reaction_pat_token(
+    state: &AppState,
+    user_id: &str,
+) -> Result<Option<String>, ApiError> {
+    let row = sqlx::query_as::<_, ReactionTokenSecretRow>(
+        r#"
+        SELECT token_ciphertext, token_nonce
+        FROM reaction_pat_tokens
+        WHERE user_id = ?
+        "#,
+    )
+    .bind(user_id)
+    .fetch_optional(&state.pool)
+    .await
+    .map_err(ApiError::internal)?;
+
+    row.map(|r| {
+        state
+            .encryption_key
+            .decrypt_str(&r.token_ciphertext, &r.token_nonce)
+            .map_err(|_| {
+                ApiError::new(
+                    StatusCode::UNPROCESSABLE_ENTITY,
+                    codes::PAT_INVALID,
+                    "PAT is invalid or expired",
+                )
+            })
+    })
+    .transpose()
+}
+
+pub(super) async fn persist_reaction_pat_check_result(
+    state: &AppState,
+    user_id: &str,
+    check_state: &str,
+    check_message: Option<&str>,
+) -> Result<(), ApiError> {
+    sqlx::query(
+        r#"
+        UPDATE reaction_pat_tokens
+        SET last_check_state = ?,
+            last_check_message = ?,
+            last_checked_at = ?,
+            updated_at = ?
+        WHERE user_id = ?
+        "#,
+    )
+    .bind(check_state)
+    .bind(check_message)
+    .bind(chrono::Utc::now().to_rfc3339())
+    .bind(chrono::Utc::now().to_rfc3339())
+    .bind(user_id)
+    .execute(&state.pool)
+    .await
+    .map_err(ApiError::internal)?;
+    Ok(())
+}
+
+pub(super) const SESSION_KEY_REACTION_OAUTH_SCOPES: &str = "reaction_oauth_scopes";
+
+/// Credential used for release reaction reads and writes. The OAuth login
+/// token wins whenever it carries `public_repo`/`repo`; the reaction PAT is
+/// only the fallback.
+pub(super) enum ReactionToken {
+    OAuth(String),
+    Pat(String),
+}
+
+impl ReactionToken {
+    pub(super) fn secret(&self) -> &str {
+        match self {
+            Self::OAuth(token) | Self::Pat(token) => token,
+        }
+    }
+}
+
+/// Result of walking the OAuth → PAT ladder. `oauth_lacks_scope` tells the
+/// caller whether a linked GitHub login was found but could not be used, so
+/// errors can point at re-login instead of the PAT setup flow.
+pub(super) enum ReactionTokenSelection {
+    Ready(ReactionToken),
+    Missing { oauth_lacks_scope: bool },
+}
+
+/// Session cache of OAuth scope checks keyed by a token fingerprint, so a
+/// re-login with broader scopes is checked again instead of reusing a stale
+/// negative answer.
+pub(super) type ReactionOAuthScopeCache = HashMap<String, bool>;
+
+pub(super) fn reaction_oauth_scope_cache_key(connection_id: &str, token: &str) -> String {
+    use sha2::{Digest, Sha256};
+
+    let digest = Sha256::digest(token.as_bytes());
+    let fingerprint = digest[..8]
+        .iter()
+        .map(|byte| format!("{byte:02x}"))
+        .collect::<String>();
+    format!("{connection_id}:{fingerprint}")
+}
+
+/// Asks GitHub which scopes the OAuth token carries. Returns `None` when the
+/// answer is inconclusive (network error, rate limit, 5xx) so the caller does
+/// not cache it.
+pub(super) async fn check_reaction_oauth_scope(
+    state: &AppState,
+    user_id: &str,
+    token: &str,
+) -> Option<bool> {
+    let url = match state.github_rest_api_base.join("user") {
+        Ok(url) => url,
+        Err(err) => {
+            tracing::warn!(?err, "invalid github rest api base for oauth scope check");
+            return None;
+        }
+    };
+    let resp = match state
+        .http
+        .get(url)
+        .bearer_auth(token)
+        .header(reqwest::header::USER_AGENT, "OctoRill")
+        .header(reqwest::header::ACCEPT, "application/vnd.github+json")
+        .header("X-GitHub-Api-Version", "2022-11-28")
+        .send()
+        .await
+    {
+        Ok(resp) => resp,
+        Err(err) => {
+            tracing::warn!(?err, "github oauth scope check failed");
+            return None;
+        }
+    };
+    github_rate_limit::record(
+        state,
+        user_id,
+        github_rate_limit::GitHubTokenKind::OAuth,
+        resp.headers(),
+    )
+    .await;
+
+    let status = resp.status();
+    if status == reqwest::StatusCode::UNAUTHORIZED {
+        return Some(false);
+    }
+    if !status.is_success() {
+        tracing::warn!(%status, "github oauth scope check returned non-success status");
+        return None;
+    }
+    let scopes = resp
+        .headers()
+        .get("x-oauth-scopes")
+        .and_then(|v| v.to_str().ok())
+        .unwrap_or("");
+    Some(has_repo_scope(scopes) || has_public_repo_scope(scopes))
+}
+
+pub(super) async fn forget_reaction_oauth_scope(session: &Session) {
+    if let Err(err) = session
+        .remove::<ReactionOAuthScopeCache>(SESSION_KEY_REACTION_OAUTH_SCOPES)
+        .await
+    {
+        tracing::warn!(?err, "failed to clear cached reaction oauth scopes");
+    }
+}
+
+/// Returns the first linked GitHub login token that carries
+/// `public_repo`/`repo`, plus whether any login was rejected for scope.
+pub(super) async fn select_reaction_oauth_token(
+    state: &AppState,
+    session: &Session,
+    user_id: &str,
+) -> (Option<String>, bool) {
+    let connections = match state.load_github_connections(user_id).await {
+        Ok(connections) => connections,
+        Err(err) => {
+            tracing::warn!(?err, "failed to load github connections for reactions");
+            Vec::new()
+        }
+    };
+
+    let mut cache = session
+        .get::<ReactionOAuthScopeCache>(SESSION_KEY_REACTION_OAUTH_SCOPES)
+        .await
+        .ok()
+        .flatten()
+        .unwrap_or_default();
+    let mut cache_dirty = false;
+    let mut oauth_lacks_scope = false;
+    let mut selected = None;
+    for connection in connections {
+        let token = connection.access_token.trim();
+        if token.is_empty() {
+            continue;
+        }
+        let key = reaction_oauth_scope_cache_key(&connection.id, token);
+        let sufficient = match cache.get(&key) {
+            Some(sufficient) => Some(*sufficient),
+            None => {
+                let checked = check_reaction_oauth_scope(state, user_id, token).await;
+                if let Some(sufficient) = checked {
+                    cache.insert(key, sufficient);
+                    cache_dirty = true;
+                }
+                checked
+            }
+        };
+        match sufficient {
+            Some(true) => {
+                selected = Some(token.to_owned());
+                break;
+            }
+            Some(false) => oauth_lacks_scope = true,
+            None => {}
+        }
+    }
+    if cache_dirty
+        && let Err(err) = session
+            .insert(SESSION_KEY_REACTION_OAUTH_SCOPES, &cache)
+            .await
+    {
+        tracing::warn!(?err, "failed to cache reaction oauth scopes");
+    }
+    (selected, oauth_lacks_scope)
+}
+
+/// Picks the credential for reaction calls: a linked GitHub login with
+/// enough scope first, then the reaction PAT.
+pub(super) async fn select_reaction_token(
+    state: &AppState,
+    session: &Session,
+    user_id: &str,
+) -> Result<ReactionTokenSelection, ApiError> {
+    let (oauth_token, oauth_lacks_scope) =
+        select_reaction_oauth_token(state, session, user_id).await;
+    if let Some(token) = oauth_token {
+        return Ok(ReactionTokenSelection::Ready(ReactionToken::OAuth(token)));
+    }
+
+    match load_reaction_pat_token(state, user_id).await {
+        Ok(Some(token)) => Ok(ReactionTokenSelection::Ready(ReactionToken::Pat(token))),
+        Ok(None) => Ok(ReactionTokenSelection::Missing { oauth_lacks_scope }),
+        Err(err) if err.code() == codes::PAT_INVALID => {
+            let _ = persist_reaction_pat_check_result(
+                state,
+                user_id,
+                "invalid",
+                Some("PAT is invalid or expired"),
+            )
+            .await;
+            Err(err)
+        }
+        Err(err) => Err(err),
+    }
+}
+
+pub(super) fn reaction_oauth_scope_error() -> ApiError {
+    ApiError::new(
+        StatusCode::FORBIDDEN,
+        codes::REAUTH_REQUIRED,
+        "GitHub login lacks public_repo or repo scope; re-login via GitHub OAuth to grant it",
+    )
+}
+
+/// Maps a GitHub auth failure to the credential that caused it: OAuth tokens
+/// need a re-login with broader scope, PATs need to be replaced.
+pub(super) async fn reaction_token_auth_error(
+    state: &AppState,
+    session: &Session,
+    user_id: &str,
+    token: &ReactionToken,
+) -> ApiError {
+    match token {
+        ReactionToken::OAuth(_) => {
+            forget_reaction_oauth_scope(session).await;
+            reaction_oauth_scope_error()
+        }
+        ReactionToken::Pat(_) => {
+            let _ = persist_reaction_pat_check_result(
+                state,
+                user_id,
+                "invalid",
+                Some("PAT is invalid or expired"),
+            )
+            .await;
+            ApiError::new(
+                StatusCode::UNPROCESSABLE_ENTITY,
+                codes::PAT_INVALID,
+                "PAT is invalid or expired",
+            )
+        }
+    }
+}
+
+pub async fn reaction_token_status(
+    State(state): State<Arc<AppState>>,
+    AuthedUser(user_id): AuthedUser,
+    // Holds the cached OAuth scope check for the reaction token.
+    session: Session,
+) -> Result<Json<ReactionTokenStatusResponse>, ApiError> {
+    let (oauth_token, _) = select_reaction_oauth_token(state.as_ref(), &session, &user_id).await;
+    let oauth_ready = oauth_token.is_some();
+    let row = load_reaction_pat_status_row(state.as_ref(), &user_id).await?;
+    let Some(row) = row else {
+        return Ok(Json(ReactionTokenStatusResponse {
+            configured: false,
+            oauth_ready,
+            masked_token: None,
+            check: ReactionTokenCheckSummary {
+                state: "idle".to_owned(),
+                message: None,
+                checked_at: None,
+            },
+            owner: None,
+        }));
+    };
+
+    Ok(Json(ReactionTokenStatusResponse {
+        configured: true,
+        oauth_ready,
+        masked_token: Some(row.masked_token),
+        check: ReactionTokenCheckSummary {
+            state: match row.last_check_state.as_str() {
+                "valid" => "valid".to_owned(),
+                "invalid" => "invalid".to_owned(),
+                "error" => "error".to_owned(),
+                _ => "idle".to_owned(),
+            },
+            message: row.last_check_message,
+            checked_at: row.last_checked_at,
+        },
+        owner: match (
+            row.owner_github_connection_id,
+            row.owner_github_user_id,
+            row.owner_login,
+        ) {
+            (Some(github_connection_id), Some(github_user_id), Some(login)) => {
+                Some(ReactionTokenOwnerSummary {
+                    github_connection_id,
+                    github_user_id,
+                    login,
+                })
+            }
+            _ => None,
+        },
+    }))
+}
+
+pub async fn check_reaction_token(
+    State(state): State<Arc<AppState>>,
+    AuthedUser(user_id): AuthedUser,
+    ApiJson(req): ApiJson<ReactionTokenRequest>,
+) -> Result<Json<ReactionTokenCheckResponse>, ApiError> {
+    let checked =
+        check_reaction_pat_with_github(state.as_ref(), req.token.as_str(), Some(user_id.as_str()))
+            .await?;
+    Ok(Json(checked))
+}
+
+/// Starred repos probed when a diagnosis names no repository.
+pub(super) const REACTION_TOKEN_DIAGNOSE_SAMPLE_SIZE: i64 = 5;
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(super) struct GraphQlRepoAccessData {
+    pub(super) repository: Option<GraphQlRepoAccess>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(super) struct GraphQlRepoAccess {
+    pub(super) viewer_permission: Option<String>,
+    pub(super) releases: GraphQlRepoAccessReleases,
+}
+
+#[derive(Debug, Deserialize)]
+pub(super) struct GraphQlRepoAccessReleases {
+    pub(super) nodes: Vec<Option<GraphQlRepoAccessRelease>>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(super) struct GraphQlRepoAccessRelease {
+    pub(super) viewer_can_react: bool,
+}
+
+/// Looks up what the token may do in one repository. A repository GitHub
+/// hides from the token comes back as `Ok(None)`; auth, rate-limit and
+/// access-restriction failures keep their usual `ApiError` codes.
+pub(super) async fn fetch_reaction_repo_access(
+    state: &AppState,
+    access_token: &str,
+    owner: &str,
+    name: &str,
+) -> Result<Option<GraphQlRepoAccess>, ApiError> {
+    let query = r#"
+      query($owner: String!, $name: String!) {
+        repository(owner: $owner, name: $name) {
+          viewerPermission
+          releases(first: 1, orderBy: { field: CREATED_AT, direction: DESC }) {
+            nodes {
+              viewerCanReact
+              reactionGroups {
+                content
+              }
+            }
+          }
+        }
+      }
+    "#;
+
+    let payload = serde_json::json!({
+        "query": query,
+        "variables": { "owner": owner, "name": name },
+    });
+
+    let resp = state
+        .http
+        .post(state.github_graphql_url.clone())
+        .bearer_auth(access_token)
+        .header(reqwest::header::USER_AGENT, "OctoRill")
+        .header(reqwest::header::ACCEPT, "application/vnd.github+json")
+        .header("X-GitHub-Api-Version", "2022-11-28")
+        .json(&payload)
+        .send()
+        .await
+        .map_err(ApiError::internal)?;
+    github_rate_limit::observe(github_rate_limit::GitHubTokenKind::GraphQl, resp.headers());
+
+    let status = resp.status();
+    if status == reqwest::StatusCode::UNAUTHORIZED || status == reqwest::StatusCode::FORBIDDEN {
+        let headers = resp.headers().clone();
+        let body = resp.text().await.map_err(ApiError::internal)?;
+        if let Some(err) = github_graphql_http_error(status, &headers, &body) {
+            return Err(err);
+        }
+        return Err(ApiError::internal(format!(
+            "github graphql returned {status}: {body}"
+        )));
+    }
+
+    let GraphQlResponse { data, errors } = resp
+        .error_for_status()
+        .map_err(ApiError::internal)?
+        .json::<GraphQlResponse<GraphQlRepoAccessData>>()
+        .await
+        .map_err(ApiError::internal)?;
+    if let Some(errors) = errors
+        && !errors.is_empty()
+    {
+        if let Some(err) = github_graphql_errors_to_api_error(&errors) {
+            return Err(err);
+        }
+        // The remaining errors are GitHub's NOT_FOUND for repositories the
+        // token cannot see; those surface as a null `repository`.
+        if data.is_none() {
+            let msg = errors
+                .into_iter()
+                .map(|e| e.message)
+                .collect::<Vec<_>>()
+                .join("; ");
+            return Err(ApiError::internal(format!("github graphql error: {msg}")));
+        }
+    }
+    Ok(data.and_then(|d| d.repository))
+}
+
+pub(super) fn diagnose_reaction_repo_access(
+    repo_full_name: String,
+    access: Option<GraphQlRepoAccess>,
+) -> ReactionTokenRepoDiagnosis {
+    let Some(access) = access else {
+        return ReactionTokenRepoDiagnosis {
+            repo_full_name,
+            repo_visible: false,
+            can_react: false,
+            viewer_permission: None,
+            suggested_fix: Some(
+                "PAT cannot see this repository; add it to the fine-grained token's repository access, or use a classic PAT with repo scope"
+                    .to_owned(),
+            ),
+        };
+    };
+    // Without a release to ask, read access is what reactions need.
+    let can_react = match access.releases.nodes.into_iter().flatten().next() {
+        Some(release) => release.viewer_can_react,
+        None => access.viewer_permission.is_some(),
+    };
+    ReactionTokenRepoDiagnosis {
+        repo_full_name,
+        repo_visible: true,
+        can_react,
+        viewer_permission: access.viewer_permission,
+        suggested_fix: (!can_react).then(|| {
+            "PAT can see this repository but GitHub does not let it react; the repository may be archived or locked"
+                .to_owned()
+        }),
+    }
+}
+
+pub async fn diagnose_reaction_token(
+    State(state): State<Arc<AppState>>,
+    AuthedUser(user_id): AuthedUser,
+    ApiJson(req): ApiJson<ReactionTokenDiagnoseRequest>,
+) -> Result<Json<ReactionTokenDiagnoseResponse>, ApiError> {
+    let repo_full_names = match req
+        .repo_full_name
+        .as_deref()
+        .map(str::trim)
+        .filter(|value| !value.is_empty())
+    {
+        Some(raw) => {
+            let Some(repo_full_name) = normalize_repo_scope_item(raw) else {
+                return Err(ApiError::bad_request(
+                    "repo_full_name must look like owner/name",
+                ));
+            };
+            vec![repo_full_name]
+        }
+        None => sqlx::query_scalar::<_, String>(
+            r#"
+            SELECT full_name
+            FROM starred_repos
+            WHERE user_id = ?
+            ORDER BY is_private DESC, stargazed_at DESC
+            LIMIT ?
+            "#,
+        )
+        .bind(&user_id)
+        .bind(REACTION_TOKEN_DIAGNOSE_SAMPLE_SIZE)
+        .fetch_all(&state.pool)
+        .await
+        .map_err(ApiError::internal)?,
+    };
+
+    let token = match load_reaction_pat_token(state.as_ref(), &user_id).await {
+        Ok(Some(token)) => token,
+        Ok(None) => {
+            return Err(ApiError::new(
+                StatusCode::FORBIDDEN,
+                codes::PAT_REQUIRED,
+                "no reaction PAT is configured",
+            ));
+        }
+        Err(err) => {
+            if err.code() == codes::PAT_INVALID {
+                let _ = persist_reaction_pat_check_result(
+                    state.as_ref(),
+                    &user_id,
+                    "invalid",
+                    Some("PAT is invalid or expired"),
+                )
+                .await;
+            }
+            return Err(err);
+        }
+    };
+
+    let mut items = Vec::with_capacity(repo_full_names.len());
+    for repo_full_name in repo_full_names {
+        let Some((owner, name)) = split_public_repo_full_name(&repo_full_name) else {
+            continue;
+        };
+        let access = github_rate_limit::track(
+            state.as_ref(),
+            &user_id,
+            fetch_reaction_repo_access(state.as_ref(), &token, owner, name),
+        )
+        .await;
+        match access {
+            Ok(access) => items.push(diagnose_reaction_repo_access(repo_full_name, access)),
+            Err(err) if err.code() == codes::REAUTH_REQUIRED => {
+                let message = "PAT is invalid or expired";
+                let _ = persist_reaction_pat_check_result(
+                    state.as_ref(),
+                    &user_id,
+                    "invalid",
+                    Some(message),
+                )
+                .await;
+                items.push(ReactionTokenRepoDiagnosis {
+                    repo_full_name,
+                    repo_visible: false,
+                    can_react: false,
+                    viewer_permission: None,
+                    suggested_fix: Some(format!("{message}; create a new token and save it")),
+                });
+                // Every other repository would fail the same way.
+                return Ok(Json(ReactionTokenDiagnoseResponse {
+                    token_valid: false,
+                    items,
+                }));
+            }
+            Err(err) if err.code() == codes::FORBIDDEN => {
+                items.push(ReactionTokenRepoDiagnosis {
+                    repo_full_name,
+                    repo_visible: false,
+                    can_react: false,
+                    viewer_permission: None,
+                    suggested_fix: Some(
+                        "GitHub blocks this token here; authorize it for the organization's SAML SSO or ask an owner to allow it"
+                            .to_owned(),
+                    ),
+                });
+            }
+            Err(err) => return Err(err),
+        }
+    }
+
+    Ok(Json(ReactionTokenDiagnoseResponse {
+        token_valid: true,
+        items,
+    }))
+}
+
+pub async fn upsert_reaction_token(
+    State(state): State<Arc<AppState>>,
+    AuthedUser(user_id): AuthedUser,
+    // Holds the cached OAuth scope check for the reaction token.
+    session: Session,
+    ApiJson(req): ApiJson<ReactionTokenRequest>,
+) -> Result<Json<ReactionTokenStatusResponse>, ApiError> {
+    let token = req.token.trim();
+    if token.is_empty() {
+        return Err(ApiError::bad_request("token is required"));
+    }
+
+    let checked =
+        check_reaction_pat_with_github(state.as_ref(), token, Some(user_id.as_str())).await?;
+    if checked.state != "valid" {
+        return Err(ApiError::new(
+            StatusCode::UNPROCESSABLE_ENTITY,
+            codes::PAT_INVALID,
+            checked.message,
+        ));
+    }
+    let owner = checked.owner.clone().ok_or_else(|| {
+        ApiError::new(
+            StatusCode::UNPROCESSABLE_ENTITY,
+            codes::PAT_INVALID,
+            "PAT owner is not bound to the current OctoRill account",
+        )
+    })?;
+
+    let now = chrono::Utc::now().to_rfc3339();
+    let encrypted = state
+        .encryption_key
+        .encrypt_str(token)
+        .map_err(ApiError::internal)?;
+    let masked = mask_pat_token(token);
+
+    sqlx::query(
+        r#"
+        INSERT INTO reaction_pat_tokens (
+          user_id, token_ciphertext, token_nonce, masked_token,
+          last_check_state, last_check_message, last_checked_at, updated_at,
+          owner_github_connection_id, owner_github_user_id, owner_login
+        )
+        VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+        ON CONFLICT(user_id) DO UPDATE SET
+          token_ciphertext = excluded.token_ciphertext,
+          token_nonce = excluded.token_nonce,
+          masked_token = excluded.masked_token,
+          last_check_state = excluded.last_check_state,
+          last_check_message = excluded.last_check_message,
+          last_checked_at = excluded.last_checked_at,
+          updated_at = excluded.updated_at,
+          owner_github_connection_id = excluded.owner_github_connection_id,
+          owner_github_user_id = excluded.owner_github_user_id,
+          owner_login = excluded.owner_login
+        "#,
+    )
+    .bind(user_id.as_str())
+    .bind(encrypted.ciphertext)
+    .bind(encrypted.nonce)
+    .bind(&masked)
+    .bind("valid")
+    .bind("token is valid")
+    .bind(&now)
+    .bind(&now)
+    .bind(owner.github_connection_id.as_str())
+    .bind(owner.github_user_id)
+    .bind(owner.login.as_str())
+    .execute(&state.pool)
+    .await
+    .map_err(ApiError::internal)?;
+    let (oauth_token, _) = select_reaction_oauth_token(state.as_ref(), &session, &user_id).await;
+
+    Ok(Json(ReactionTokenStatusResponse {
+        configured: true,
+        oauth_ready: oauth_token.is_some(),
+        masked_token: Some(masked),
+        check: ReactionTokenCheckSummary {
+            state: "valid".to_owned(),
+            message: Some("token is valid".to_owned()),
+            checked_at: Some(now),
+        },
+        owner: Some(owner),
+    }))
+}
+
+#[derive(Debug, sqlx::FromRow)]
+pub(super) struct CachedReleaseReactionRow {
+    pub(super) release_id: i64,
+    pub(super) react_plus1: Option<i64>,
+    pub(super) react_laugh: Option<i64>,
+    pub(super) react_heart: Option<i64>,
+    pub(super) react_hooray: Option<i64>,
+    pub(super) react_rocket: Option<i64>,
+    pub(super) react_eyes: Option<i64>,
+    pub(super) viewer_plus1: i64,
+    pub(super) viewer_laugh: i64,
+    pub(super) viewer_heart: i64,
+    pub(super) viewer_hooray: i64,
+    pub(super) viewer_rocket: i64,
+    pub(super) viewer_eyes: i64,
+    pub(super) fetched_at: String,
+}
+
+#[derive(Debug, Clone)]
+pub(super) struct CachedReleaseReactions {
+    pub(super) counts: ReleaseReactionCounts,
+    pub(super) viewer: ReleaseReactionViewer,
+    pub(super) is_fresh: bool,
+}
+
+pub(super) fn reaction_viewer_cache_is_fresh(
+    fetched_at: &str,
+    now: chrono::DateTime<chrono::Utc>,
+    ttl_secs: usize,
+) -> bool {
+    let Ok(fetched_at) = chrono::DateTime::parse_from_rfc3339(fetched_at) else {
+        return false;
+    };
+    let age = now.signed_duration_since(fetched_at.with_timezone(&chrono::Utc));
+    age < chrono::Duration::seconds(i64::try_from(ttl_secs).unwrap_or(i64::MAX))
+}
+
+pub(super) async fn load_release_asset_counts(
+    state: &AppState,
+    release_ids: &[i64],
+) -> Result<std::collections::HashMap<i64, i64>, ApiError> {
+    if release_ids.is_empty() {
+        return Ok(std::collections::HashMap::new());
+    }
+
+    let placeholders = (0..release_ids.len())
+        .map(|_| "?")
+        .collect::<Vec<_>>()
+        .join(", ");
+    let sql = format!(
+        r#"
+        SELECT release_id, COUNT(*)
+        FROM release_assets
+        WHERE release_id IN ({placeholders})
+        GROUP BY release_id
+        "#
+    );
+    let mut query = sqlx::query_as::<_, (i64, i64)>(&sql);
+    for release_id in release_ids {
+        query = query.bind(release_id);
+    }
+    let rows = query
+        .fetch_all(&state.pool)
+        .await
+        .map_err(ApiError::internal)?;
+    Ok(rows.into_iter().collect())
+}
+
+pub(super) async fn load_cached_release_reactions(
+    state: &AppState,
+    user_id: &str,
+    release_ids: &[i64],
+) -> Result<std::collections::HashMap<i64, CachedReleaseReactions>, ApiError> {
+    if release_ids.is_empty() {
+        return Ok(std::collections::HashMap::new());
+    }
+
+    let placeholders = (0..release_ids.len())
+        .map(|_| "?")
+        .collect::<Vec<_>>()
+        .join(", ");
+    let sql = format!(
+        r#"
+        SELECT
+          rv.release_id,
+          rr.react_plus1, rr.react_laugh, rr.react_heart,
+          rr.react_hooray, rr.react_rocket, rr.react_eyes,
+          rv.viewer_plus1, rv.viewer_laugh, rv.viewer_heart,
+          rv.viewer_hooray, rv.viewer_rocket, rv.viewer_eyes,
+          rv.fetched_at
+        FROM release_reaction_viewer rv
+        JOIN repo_releases rr ON rr.release_id = rv.release_id
+        WHERE rv.user_id = ? AND rv.release_id IN ({placeholders})
+        "#
+    );
+
+    let mut query = sqlx::query_as::<_, CachedReleaseReactionRow>(&sql).bind(user_id);
+    for release_id in release_ids {
+        query = query.bind(release_id);
+    }
+    let rows = query
+        .fetch_all(&state.pool)
+        .await
+        .map_err(ApiError::internal)?;
+
+    let now = chrono::Utc::now();
+    let ttl_secs = state.config.reaction_viewer_ttl_secs;
+    Ok(rows
+        .into_iter()
+        .map(|row| {
+            let cached = CachedReleaseReactions {
+                counts: ReleaseReactionCounts {
+                    plus1: row.react_plus1.unwrap_or(0),
+                    laugh: row.react_laugh.unwrap_or(0),
+                    heart: row.react_heart.unwrap_or(0),
+                    hooray: row.react_hooray.unwrap_or(0),
+                    rocket: row.react_rocket.unwrap_or(0),
+                    eyes: row.react_eyes.unwrap_or(0),
+                },
+                viewer: ReleaseReactionViewer {
+                    plus1: row.viewer_plus1 != 0,
+                    laugh: row.viewer_laugh != 0,
+                    heart: row.viewer_heart != 0,
+                    hooray: row.viewer_hooray != 0,
+                    rocket: row.viewer_rocket != 0,
+                    eyes: row.viewer_eyes != 0,
+                },
+                is_fresh: reaction_viewer_cache_is_fresh(&row.fetched_at, now, ttl_secs),
+            };
+            (row.release_id, cached)
+        })
+        .collect())
+}
+
+pub(super) fn apply_group_to_reactions(
+    counts: &mut ReleaseReactionCounts,
+    viewer: &mut ReleaseReactionViewer,
+    content: &str,
+    total_count: i64,
+    viewer_has_reacted: bool,
+) {
+    match content {
+        "THUMBS_UP" => {
+            counts.plus1 = total_count;
+            viewer.plus1 = viewer_has_reacted;
+        }
+        "LAUGH" => {
+            counts.laugh = total_count;
+            viewer.laugh = viewer_has_reacted;
+        }
+        "HEART" => {
+            counts.heart = total_count;
+            viewer.heart = viewer_has_reacted;
+        }
+        "HOORAY" => {
+            counts.hooray = total_count;
+            viewer.hooray = viewer_has_reacted;
+        }
+        "ROCKET" => {
+            counts.rocket = total_count;
+            viewer.rocket = viewer_has_reacted;
+        }
+        "EYES" => {
+            counts.eyes = total_count;
+            viewer.eyes = viewer_has_reacted;
+        }
+        _ => {}
+    }
+}
+
+pub(super) fn counts_from_groups(groups: &[GraphQlReactionGroup]) -> ReleaseReactionCounts {
+    let mut counts = ReleaseReactionCounts::default();
+    let mut viewer = ReleaseReactionViewer::default();
+    for group in groups {
+        apply_group_to_reactions(
+            &mut counts,
+            &mut viewer,
+            group.content.as_str(),
+            group.reactors.total_count,
+            group.viewer_has_reacted,
+        );
+    }
+    counts
+}
+
+pub(super) fn viewer_from_groups(groups: &[GraphQlReactionGroup]) -> ReleaseReactionViewer {
+    let mut counts = ReleaseReactionCounts::default();
+    let mut viewer = ReleaseReactionViewer::default();
+    for group in groups {
+        apply_group_to_reactions(
+            &mut counts,
+            &mut viewer,
+            group.content.as_str(),
+            group.reactors.total_count,
+            group.viewer_has_reacted,
+        );
+    }
+    viewer
+}
+
+#[derive(Debug, Deserialize)]
+pub(super) struct GraphQlResponse<T> {
+    pub(super) data: Option<T>,
+    pub(super) errors: Option<Vec<GraphQlError>>,
+}
+
+#[derive(Debug, Deserialize)]
+pub(super) struct GraphQlError {
+    pub(super) message: String,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(super) struct GraphQlReleaseReactionsData {
+    pub(super) nodes: Vec<Option<GraphQlReleaseNode>>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(super) struct GraphQlReleaseNode {
+    pub(super) id: String,
+    pub(super) reaction_groups: Vec<GraphQlReactionGroup>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(super) struct GraphQlReactionGroup {
+    pub(super) content: String,
+    pub(super) viewer_has_reacted: bool,
+    pub(super) reactors: GraphQlReactors,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(super) struct GraphQlReactors {
+    pub(super) total_count: i64,
+}
+
+#[derive(Debug, Clone)]
+pub(super) struct LiveReleaseReactions {
+    pub(super) counts: ReleaseReactionCounts,
+    pub(super) viewer: ReleaseReactionViewer,
+}
+
+/// GitHub rejects `nodes(ids:)` lookups with more than 50 ids.
+pub(super) const GITHUB_GRAPHQL_NODES_MAX_IDS: usize = 50;
+
+pub(super) fn graphql_node_id_chunks(node_ids: &[String]) -> Vec<&[String]> {
+    node_ids.chunks(GITHUB_GRAPHQL_NODES_MAX_IDS).collect()
+}
+
+pub(super) async fn fetch_live_release_reactions(
+    state: &AppState,
+    access_token: &str,
+    node_ids: &[String],
+) -> Result<std::collections::HashMap<String, LiveReleaseReactions>, ApiError> {
+    let mut out = std::collections::HashMap::new();
+    for chunk in graphql_node_id_chunks(node_ids) {
+        out.extend(fetch_live_release_reactions_chunk(state, access_token, chunk).await?);
+    }
+    Ok(out)
+}
+
+pub(super) async fn fetch_live_release_reactions_chunk(
+    state: &AppState,
+    access_token: &str,
+    node_ids: &[String],
+) -> Result<std::collections::HashMap<String, LiveReleaseReactions>, ApiError> {
+    if node_ids.is_empty() {
+        return Ok(std::collections::HashMap::new());
+    }
+
+    let query = r#"
+      query($ids: [ID!]!) {
+        nodes(ids: $ids) {
+          ... on Release {
+            id
+            reactionGroups {
+              content
+              viewerHasReacted
+              reactors(first: 1) {
+                totalCount
+              }
+            }
+          }
+        }
+      }
+    "#;
+
+    let payload = serde_json::json!({
+        "query": query,
+        "variables": { "ids": node_ids },
+    });
+
+    let resp = state
+        .http
+        .post(state.github_graphql_url.clone())
+        .bearer_auth(access_token)
+        .header(reqwest::header::USER_AGENT, "OctoRill")
+        .header(reqwest::header::ACCEPT, "application/vnd.github+json")
+        .header("X-GitHub-Api-Version", "2022-11-28")
+        .json(&payload)
+        .send()
+        .await
+        .map_err(ApiError::internal)?;
+    github_rate_limit::observe(github_rate_limit::GitHubTokenKind::GraphQl, resp.headers());
+
+    let status = resp.status();
+    if status == reqwest::StatusCode::UNAUTHORIZED || status == reqwest::StatusCode::FORBIDDEN {
+        let headers = resp.headers().clone();
+        let body = resp.text().await.map_err(ApiError::internal)?;
+        if let Some(err) = github_graphql_http_error(status, &headers, &body) {
+            return Err(err);
+        }
+        return Err(ApiError::internal(format!(
+            "github graphql returned {status}: {body}"
+        )));
+    }
+
+    let resp = resp
+        .error_for_status()
+        .map_err(ApiError::internal)?
+        .json::<GraphQlResponse<GraphQlReleaseReactionsData>>()
+        .await
+        .map_err(ApiError::internal)?;
+
+    let GraphQlResponse { data, errors } = resp;
+    if let Some(errors) = errors
+        && !errors.is_empty()
+    {
+        // `nodes(ids: ...)` can legitimately return partial data with per-node auth errors
+        // (e.g. some private releases are inaccessible). Keep usable nodes instead of
+        // downgrading the whole page to reauth-required.
+        if data.is_none() {
+            if let Some(err) = github_graphql_errors_to_api_error(&errors) {
+                return Err(err);
+            }
+            let msg = errors
+                .into_iter()
+                .map(|e| e.message)
+                .collect::<Vec<_>>()
+                .join("; ");
+            return Err(ApiError::internal(format!("github graphql error: {msg}")));
+        }
+    }
+
+    let mut out = std::collections::HashMap::new();
+    let nodes = data.map(|d| d.nodes).unwrap_or_default();
+    for node in nodes.into_iter().flatten() {
+        out.insert(
+            node.id,
+            LiveReleaseReactions {
+                counts: counts_from_groups(&node.reaction_groups),
+                viewer: viewer_from_groups(&node.reaction_groups),
+            },
+        );
+    }
+    Ok(out)
+}
+
+pub(super) async fn write_release_reactions(
+    conn: &mut sqlx::SqliteConnection,
+    user_id: &str,
+    release_id: i64,
+    reaction: &LiveReleaseReactions,
+    now: &str,
+) -> Result<(), sqlx::Error> {
+    let counts = &reaction.counts;
+    sqlx::query(
+        r#"
+        UPDATE repo_releases
+        SET react_plus1 = ?,
+            react_laugh = ?,
+            react_heart = ?,
+            react_hooray = ?,
+            react_rocket = ?,
+            react_eyes = ?,
+            updated_at = ?
+        WHERE release_id = ?
+        "#,
+    )
+    .bind(counts.plus1)
+    .bind(counts.laugh)
+    .bind(counts.heart)
+    .bind(counts.hooray)
+    .bind(counts.rocket)
+    .bind(counts.eyes)
+    .bind(now)
+    .bind(release_id)
+    .execute(&mut *conn)
+    .await?;
+
+    let viewer = &reaction.viewer;
+    sqlx::query(
+        r#"
+        INSERT INTO release_reaction_viewer (
+          user_id, release_id,
+          viewer_plus1, viewer_laugh, viewer_heart, viewer_hooray, viewer_rocket, viewer_eyes,
+          fetched_at
+        )
+        VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?)
+        ON CONFLICT(user_id, release_id) DO UPDATE SET
+          viewer_plus1 = excluded.viewer_plus1,
+          viewer_laugh = excluded.viewer_laugh,
+          viewer_heart = excluded.viewer_heart,
+          viewer_hooray = excluded.viewer_hooray,
+          viewer_rocket = excluded.viewer_rocket,
+          viewer_eyes = excluded.viewer_eyes,
+          fetched_at = excluded.fetched_at
+        "#,
+    )
+    .bind(user_id)
+    .bind(release_id)
+    .bind(i64::from(viewer.plus1))
+    .bind(i64::from(viewer.laugh))
+    .bind(i64::from(viewer.heart))
+    .bind(i64::from(viewer.hooray))
+    .bind(i64::from(viewer.rocket))
+    .bind(i64::from(viewer.eyes))
+    .bind(now)
+    .execute(&mut *conn)
+    .await
+    .map(|_| ())
+}
+
+pub(super) async fn persist_release_reactions(
+    state: &AppState,
+    user_id: &str,
+    release_id: i64,
+    reaction: &LiveReleaseReactions,
+) -> Result<(), ApiError> {
+    state
+        .sqlite_writer
+        .write_foreground("feed_reaction_counts_persist", |_| async move {
+            let now = chrono::Utc::now().to_rfc3339();
+            let mut tx = state.pool.begin().await?;
+            write_release_reactions(&mut tx, user_id, release_id, reaction, now.as_str()).await?;
+            tx.commit().await.map_err(anyhow::Error::from)
+        })
+        .await
+        .map_err(ApiError::internal)?;
+    Ok(())
+}
+
+/// Persists a whole refresh batch in one transaction; skipped under writer pressure.
+pub(super) async fn persist_release_reactions_batch_best_effort(
+    state: &AppState,
+    user_id: &str,
+    updates: &[(i64, &LiveReleaseReactions)],
+) -> Result<bool, ApiError> {
+    if updates.is_empty() {
+        return Ok(true);
+    }
+
+    match state
+        .sqlite_writer
+        .try_write("feed_reaction_counts_persist_best_effort", || async move {
+            let now = chrono::Utc::now().to_rfc3339();
+            let mut tx = state.pool.begin().await?;
+            for (release_id, reaction) in updates {
+                write_release_reactions(&mut tx, user_id, *release_id, reaction, now.as_str())
+                    .await?;
+            }
+            tx.commit().await.map_err(anyhow::Error::from)
+        })
+        .await
+    {
+        Ok(Some(())) => Ok(true),
+        Ok(None) => {
+            tracing::warn!(
+                event = "feed.reactions.persist",
+                release_count = updates.len(),
+                downgrade_reason = "sqlite_writer_busy",
+                "skipped release reaction persist under sqlite writer pressure"
+            );
+            Ok(false)
+        }
+        Err(err) if crate::sqlite_write::is_sqlite_busy_error(err.as_ref()) => {
+            tracing::warn!(
+                event = "feed.reactions.persist",
+                release_count = updates.len(),
+                downgrade_reason = "sqlite_busy",
+                error_chain = %crate::observability::error_chain_summary(err.as_ref()),
+                "skipped release reaction persist after sqlite busy"
+            );
+            Ok(false)
+        }
+        Err(err) => Err(ApiError::internal(err)),
+    }
+}
+
+pub(super) fn build_feed_reaction_refresh_item(
+    row: &ReleaseReactionRow,
+    reaction: &LiveReleaseReactions,
+) -> FeedReactionRefreshItem {
+    FeedReactionRefreshItem {
+        release_id: row.release_id.to_string(),
+        reactions: ReleaseReactions {
+            counts: reaction.counts.clone(),
+            viewer: reaction.viewer.clone(),
+            status: "ready".to_owned(),
+        },
+    }
+}
+
+pub(super) fn cached_feed_reaction_refresh_item(
+    release_id: i64,
+    cached: &CachedReleaseReactions,
+) -> FeedReactionRefreshItem {
+    FeedReactionRefreshItem {
+        release_id: release_id.to_string(),
+        reactions: ReleaseReactions {
+            counts: cached.counts.clone(),
+            viewer: cached.viewer.clone(),
+            status: "ready".to_owned(),
+        },
+    }
+}
+
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct ToggleReleaseReactionRequest {
+    pub(super) release_id: String,
+    #[schemars(with = "ReleaseReactionContent")]
+    pub(super) content: String,
+    /// Whether the client believes the viewer already reacted. When set the
+    /// matching mutation runs directly instead of reading GitHub first.
+    #[serde(default)]
+    pub(super) currently_reacted: Option<bool>,
+}
+
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct ToggleReleaseReactionResponse {
+    pub(super) release_id: String,
+    pub(super) reactions: ReleaseReactions,
+}
+
+#[derive(Debug, sqlx::FromRow)]
+pub(super) struct ReleaseReactionRow {
+    pub(super) release_id: i64,
+    pub(super) node_id: Option<String>,
+}
+
+pub async fn refresh_feed_reactions(
+    State(state): State<Arc<AppState>>,
+    AuthedUser(user_id): AuthedUser,
+    // Holds the cached OAuth scope check for the reaction token.
+    session: Session,
+    Query(q): Query<FeedReactionRefreshQuery>,
+    ApiJson(req): ApiJson<FeedReactionRefreshRequest>,
+) -> Result<Json<FeedReactionRefreshResponse>, ApiError> {
+    let started_at = Instant::now();
+    let release_ids = parse_unique_release_ids(&req.release_ids, 100)?;
+    let force_refresh = q.refresh_reactions.unwrap_or(false);
+
+    let db_started_at = Instant::now();
+    let rows = fetch_visible_release_reaction_rows(state.as_ref(), &user_id, &release_ids).await?;
+    // Viewer state cached within the TTL is served locally; only stale or missing
+    // releases go back to GitHub unless the client explicitly forces a refresh.
+    let cached = if force_refresh {
+        std::collections::HashMap::new()
+    } else {
+        let visible_ids = rows.iter().map(|row| row.release_id).collect::<Vec<_>>();
+        let mut cached =
+            load_cached_release_reactions(state.as_ref(), &user_id, &visible_ids).await?;
+        cached.retain(|_, entry| entry.is_fresh);
+        cached
+    };
+    let db_elapsed = db_started_at.elapsed();
+
+    let mut node_ids: Vec<String> = rows
+        .iter()
+        .filter(|row| !cached.contains_key(&row.release_id))
+        .filter_map(|row| row.node_id.as_deref().map(str::trim))
+        .filter(|node_id| !node_id.is_empty())
+        .map(ToOwned::to_owned)
+        .collect();
+    node_ids.sort();
+    node_ids.dedup();
+
+    let cached_items = || {
+        rows.iter()
+            .filter_map(|row| {
+                cached
+                    .get(&row.release_id)
+                    .map(|entry| cached_feed_reaction_refresh_item(row.release_id, entry))
+            })
+            .collect::<Vec<_>>()
+    };
+
+    if node_ids.is_empty() {
+        let items = cached_items();
+        tracing::info!(
+            db_ms = db_elapsed.as_millis() as u64,
+            github_ms = 0_u64,
+            persist_ms = 0_u64,
+            total_ms = started_at.elapsed().as_millis() as u64,
+            release_count = release_ids.len(),
+            refreshed_count = 0_usize,
+            cached_count = items.len(),
+            "feed reaction refresh skipped without stale release node ids"
+        );
+        return Ok(Json(FeedReactionRefreshResponse {
+            items,
+            warnings: Vec::new(),
+        }));
+    }
+
+    let token = match select_reaction_token(state.as_ref(), &session, &user_id).await? {
+        ReactionTokenSelection::Ready(token) => token,
+        ReactionTokenSelection::Missing { oauth_lacks_scope } => {
+            tracing::info!(
+                db_ms = db_elapsed.as_millis() as u64,
+                github_ms = 0_u64,
+                persist_ms = 0_u64,
+                total_ms = started_at.elapsed().as_millis() as u64,
+                release_count = release_ids.len(),
+                refreshed_count = 0_usize,
+                oauth_lacks_scope,
+                "feed reaction refresh skipped without a usable GitHub token"
+            );
+            return Ok(Json(FeedReactionRefreshResponse {
+                items: cached_items(),
+                warnings: Vec::new(),
+            }));
+        }
+    };
+
+    let github_started_at = Instant::now();
+    let live = match github_rate_limit::track(
+        state.as_ref(),
+        &user_id,
+        fetch_live_release_reactions(state.as_ref(), token.secret(), &node_ids),
+    )
+    .await
+    {
+        Ok(live) => live,
+        Err(err) => {
+            let warning = feed_reaction_warning(&err, &token);
+            if err.code() == codes::REAUTH_REQUIRED {
+                let _ = reaction_token_auth_error(state.as_ref(), &session, &user_id, &token).await;
+            }
+            tracing::warn!(
+                ?err,
+                warning = warning.code.as_str(),
+                release_count = release_ids.len(),
+                "feed reaction refresh fell back to stored counts"
+            );
+            let items =
+                stale_feed_reaction_refresh_items(state.as_ref(), &user_id, &rows, &cached).await?;
+            return Ok(Json(FeedReactionRefreshResponse {
+                items,
+                warnings: vec![warning],
+            }));
+        }
+    };
+    let github_elapsed = github_started_at.elapsed();
+
+    let mut items = Vec::new();
+    let mut updates = Vec::new();
+    for row in &rows {
+        if let Some(entry) = cached.get(&row.release_id) {
+            items.push(cached_feed_reaction_refresh_item(row.release_id, entry));
+            continue;
+        }
+        let Some(node_id) = row
+            .node_id
+            .as_deref()
+            .map(str::trim)
+            .filter(|node_id| !node_id.is_empty())
+        else {
+            continue;
+        };
+        let Some(reaction) = live.get(node_id) else {
+            continue;
+        };
+        items.push(build_feed_reaction_refresh_item(row, reaction));
+        updates.push((row.release_id, reaction));
+    }
+
+    let persist_started_at = Instant::now();
+    let refreshed_count = updates.len();
+    let persisted =
+        persist_release_reactions_batch_best_effort(state.as_ref(), &user_id, &updates).await?;
+    let persist_skipped = if persisted { 0 } else { refreshed_count };
+    let persist_elapsed = persist_started_at.elapsed();
+
+    tracing::info!(
+        db_ms = db_elapsed.as_millis() as u64,
+        github_ms = github_elapsed.as_millis() as u64,
+        persist_ms = persist_elapsed.as_millis() as u64,
+        total_ms = started_at.elapsed().as_millis() as u64,
+        release_count = release_ids.len(),
+        refreshed_count,
+        cached_count = cached.len(),
+        persist_skipped,
+        "feed reaction refresh completed outside feed hot path"
+    );
+
+    Ok(Json(FeedReactionRefreshResponse {
+        items,
+        warnings: Vec::new(),
+    }))
+}
+
+/// Maps a failed live reaction fetch to the warning shown next to the feed.
+pub(super) fn feed_reaction_warning(err: &ApiError, token: &ReactionToken) -> FeedWarning {
+    let (code, message) = match (err.code(), token) {
+        ("reauth_required", ReactionToken::OAuth(_)) => (
+            "reauth_required",
+            "GitHub login lacks public_repo or repo scope; reaction counts may be out of date",
+        ),
+        ("forbidden", ReactionToken::OAuth(_)) => (
+            "forbidden",
+            "GitHub login cannot read these releases; reaction counts may be out of date",
+        ),
+        ("reauth_required" | "pat_invalid", _) => ("pat_invalid", "PAT is invalid or expired"),
+        ("rate_limited", _) => (
+            "rate_limited",
+            "GitHub rate limit reached; reaction counts may be out of date",
+        ),
+        ("forbidden", _) => (
+            "pat_forbidden",
+            "PAT cannot read these releases; reaction counts may be out of date",
+        ),
+        _ => (
+            "github_unavailable",
+            "GitHub reactions are unavailable; reaction counts may be out of date",
+        ),
+    };
+    FeedWarning {
+        code: code.to_owned(),
+        message: message.to_owned(),
+    }
+}
+
+/// Fallback when GitHub is unavailable: fresh cache entries stay `ready`,
+/// everything else reports the stored counts (and any older viewer state)
+/// as `stale`.
+pub(super) async fn stale_feed_reaction_refresh_items(
+    state: &AppState,
+    user_id: &str,
+    rows: &[ReleaseReactionRow],
+    fresh: &std::collections::HashMap<i64, CachedReleaseReactions>,
+) -> Result<Vec<FeedReactionRefreshItem>, ApiError> {
+    let stale_ids = rows
+        .iter()
+        .map(|row| row.release_id)
+        .filter(|release_id| !fresh.contains_key(release_id))
+        .collect::<Vec<_>>();
+    let viewer_cache = load_cached_release_reactions(state, user_id, &stale_ids).await?;
+    let stored_counts = load_release_reaction_counts(state, &stale_ids).await?;
+
+    Ok(rows
+        .iter()
+        .filter_map(|row| {
+            if let Some(entry) = fresh.get(&row.release_id) {
+                return Some(cached_feed_reaction_refresh_item(row.release_id, entry));
+            }
+            let counts = stored_counts.get(&row.release_id)?.clone();
+            let viewer = viewer_cache
+                .get(&row.release_id)
+                .map(|entry| entry.viewer.clone())
+                .unwrap_or_default();
+            Some(FeedReactionRefreshItem {
+                release_id: row.release_id.to_string(),
+                reactions: ReleaseReactions {
+                    counts,
+                    viewer,
+                    status: "stale".to_owned(),
+                },
+            })
+        })
+        .collect())
+}
+
+pub(super) async fn load_release_reaction_counts(
+    state: &AppState,
+    release_ids: &[i64],
+) -> Result<std::collections::HashMap<i64, ReleaseReactionCounts>, ApiError> {
+    if release_ids.is_empty() {
+        return Ok(std::collections::HashMap::new());
+    }
+
+    let mut query = QueryBuilder::<sqlx::Sqlite>::new(
+        r#"
+        SELECT
+          release_id,
+          react_plus1, react_laugh, react_heart,
+          react_hooray, react_rocket, react_eyes
+        FROM repo_releases
+        WHERE release_id IN (
+        "#,
+    );
+    let mut separated = query.separated(", ");
+    for release_id in release_ids {
+        separated.push_bind(*release_id);
+    }
+    query.push(")");
+    let rows = query
+        .build()
+        .fetch_all(&state.pool)
+        .await
+        .map_err(ApiError::internal)?;
+
+    let mut out = std::collections::HashMap::new();
+    for row in rows {
+        let count = |column: &str| {
+            row.try_get::<Option<i64>, _>(column)
+                .map(|value| value.unwrap_or(0))
+                .map_err(ApiError::internal)
+        };
+        out.insert(
+            row.try_get::<i64, _>("release_id")
+                .map_err(ApiError::internal)?,
+            ReleaseReactionCounts {
+                plus1: count("react_plus1")?,
+                laugh: count("react_laugh")?,
+                heart: count("react_heart")?,
+                hooray: count("react_hooray")?,
+                rocket: count("react_rocket")?,
+                eyes: count("react_eyes")?,
+            },
+        );
+    }
+    Ok(out)
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(super) struct AddReactionData {
+    pub(super) add_reaction: Option<GraphQlMutationPayload>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(super) struct RemoveReactionData {
+    pub(super) remove_reaction: Option<GraphQlMutationPayload>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(super) struct GraphQlMutationPayload {
+    pub(super) subject: Option<GraphQlReleaseSubject>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(super) struct GraphQlReleaseSubject {
+    pub(super) id: String,
+    pub(super) reaction_groups: Vec<GraphQlReactionGroup>,
+}
+
+pub(super) async fn mutate_release_reaction(
+    state: &AppState,
+    access_token: &str,
+    node_id: &str,
+    content: ReleaseReactionContent,
+    currently_reacted: bool,
+) -> Result<LiveReleaseReactions, ApiError> {
+    let (query, key) = if currently_reacted {
+        (
+            r#"
+            mutation($input: RemoveReactionInput!) {
+              removeReaction(input: $input) {
+                subject {
+                  ... on Release {
+                    id
+                    reactionGroups {
+                      content
+                      viewerHasReacted
+                      reactors(first: 1) {
+                        totalCount
+                      }
+                    }
+                  }
+                }
+              }
+            }
+            "#,
+            "removeReaction",
+        )
+    } else {
+        (
+            r#"
+            mutation($input: AddReactionInput!) {
+              addReaction(input: $input) {
+                subject {
+                  ... on Release {
+                    id
+                    reactionGroups {
+                      content
+                      viewerHasReacted
+                      reactors(first: 1) {
+                        totalCount
+                      }
+                    }
+                  }
+                }
+              }
+            }
+            "#,
+            "addReaction",
+        )
+    };
+
+    let payload = serde_json::json!({
+        "query": query,
+        "variables": {
+            "input": {
+                "subjectId": node_id,
+                "content": content.as_graphql_enum(),
+            }
+        }
+    });
+
+    let resp = state
+        .http
+        .post(state.github_graphql_url.clone())
+        .bearer_auth(access_token)
+        .header(reqwest::header::USER_AGENT, "OctoRill")
+        .header(reqwest::header::ACCEPT, "application/vnd.github+json")
+        .header("X-GitHub-Api-Version", "2022-11-28")
+        .json(&payload)
+        .send()
+        .await
+        .map_err(ApiError::internal)?;
+    github_rate_limit::observe(github_rate_limit::GitHubTokenKind::GraphQl, resp.headers());
+
+    let status = resp.status();
+    if status == reqwest::StatusCode::UNAUTHORIZED || status == reqwest::StatusCode::FORBIDDEN {
+        let headers = resp.headers().clone();
+        let body = resp.text().await.map_err(ApiError::internal)?;
+        if let Some(err) = github_graphql_http_error(status, &headers, &body) {
+            return Err(err);
+        }
+        return Err(ApiError::internal(format!(
+            "github graphql returned {status}: {body}"
+        )));
+    }
+
+    let resp = resp.error_for_status().map_err(ApiError::internal)?;
+
+    let body = resp.text().await.map_err(ApiError::internal)?;
+    if key == "removeReaction" {
+        let parsed = serde_json::from_str::<GraphQlResponse<RemoveReactionData>>(&body)
+            .map_err(ApiError::internal)?;
+        if let Some(errors) = parsed.errors
+            && !errors.is_empty()
+        {
+            if let Some(err) = github_graphql_errors_to_api_error(&errors) {
+                return Err(err);
+            }
+            if errors
+                .iter()
+                .any(|e| is_reaction_state_conflict_message(&e.message))
+            {
+                return Err(reaction_state_conflict_error());
+            }
+            let msg = errors
+                .into_iter()
+                .map(|e| e.message)
+                .collect::<Vec<_>>()
+                .join("; ");
+            return Err(ApiError::internal(format!("github graphql error: {msg}")));
+        }
+        let Some(data) = parsed.data else {
+            return Err(ApiError::internal("missing graphql data"));
+        };
+        let Some(payload) = data.remove_reaction else {
+            return Err(ApiError::internal("missing removeReaction payload"));
+        };
+        let Some(subject) = payload.subject else {
+            return Err(ApiError::internal("missing mutation subject"));
+        };
+        let _subject_id = subject.id;
+        return Ok(LiveReleaseReactions {
+            counts: counts_from_groups(&subject.reaction_groups),
+            viewer: viewer_from_groups(&subject.reaction_groups),
+        });
+    }
+
+    let parsed = serde_json::from_str::<GraphQlResponse<AddReactionData>>(&body)
+        .map_err(ApiError::internal)?;
+    if let Some(errors) = parsed.errors
+        && !errors.is_empty()
+    {
+        if let Some(err) = github_graphql_errors_to_api_error(&errors) {
+            return Err(err);
+        }
+        if errors
+            .iter()
+            .any(|e| is_reaction_state_conflict_message(&e.message))
+        {
+            return Err(reaction_state_conflict_error());
+        }
+        let msg = errors
+            .into_iter()
+            .map(|e| e.message)
+            .collect::<Vec<_>>()
+            .join("; ");
+        return Err(ApiError::internal(format!("github graphql error: {msg}")));
+    }
+    let Some(data) = parsed.data else {
+        return Err(ApiError::internal("missing graphql data"));
+    };
+    let Some(payload) = data.add_reaction else {
+        return Err(ApiError::internal("missing addReaction payload"));
+    };
+    let Some(subject) = payload.subject else {
+        return Err(ApiError::internal("missing mutation subject"));
+    };
+    let _subject_id = subject.id;
+    Ok(LiveReleaseReactions {
+        counts: counts_from_groups(&subject.reaction_groups),
+        viewer: viewer_from_groups(&subject.reaction_groups),
+    })
+}
+
+pub async fn toggle_release_reaction(
+    State(state): State<Arc<AppState>>,
+    AuthedUser(user_id): AuthedUser,
+    // Holds the cached OAuth scope check for the reaction token.
+    session: Session,
+    ApiJson(req): ApiJson<ToggleReleaseReactionRequest>,
+) -> Result<Json<ToggleReleaseReactionResponse>, ApiError> {
+    let Some(content) = ReleaseReactionContent::from_client_str(req.content.trim()) else {
+        return Err(ApiError::bad_request("invalid reaction content"));
+    };
+    let release_id = parse_release_id_param(&req.release_id)?;
+
+    let token = match select_reaction_token(state.as_ref(), &session, &user_id).await? {
+        ReactionTokenSelection::Ready(token) => token,
+        ReactionTokenSelection::Missing { oauth_lacks_scope } => {
+            let message = if oauth_lacks_scope {
+                "release reactions require public_repo or repo scope on the GitHub login, or a GitHub PAT"
+            } else {
+                "release reactions require a GitHub PAT"
+            };
+            return Err(ApiError::new(
+                StatusCode::FORBIDDEN,
+                codes::PAT_REQUIRED,
+                message,
+            ));
+        }
+    };
+
+    let row = sqlx::query_as::<_, ReleaseReactionRow>(
+        r#"
+        SELECT rr.release_id, rr.node_id
+        FROM repo_releases rr
+        JOIN user_release_visible_repos sr
+          ON sr.user_id = ? AND sr.repo_id = rr.repo_id
+        WHERE rr.release_id = ?
+        "#,
+    )
+    .bind(&user_id)
+    .bind(release_id)
+    .fetch_optional(&state.pool)
+    .await
+    .map_err(ApiError::internal)?;
+
+    let Some(row) = row else {
+        return Err(ApiError::new(
+            StatusCode::NOT_FOUND,
+            codes::NOT_FOUND,
+            "release not found",
+        ));
+    };
+
+    let Some(node_id) = row
+        .node_id
+        .as_deref()
+        .map(str::trim)
+        .filter(|s| !s.is_empty())
+    else {
+        return Err(ApiError::new(
+            StatusCode::CONFLICT,
+            codes::SYNC_REQUIRED,
+            "release reaction data is not ready; sync releases first",
+        ));
+    };
+
+    let updated = match req.currently_reacted {
+        // Trust the client's view and mutate straight away; a conflict means
+        // GitHub already sits in the other state, so the opposite mutation is
+        // what a fresh read would have picked.
+        Some(believed_reacted) => {
+            match github_rate_limit::track(
+                state.as_ref(),
+                &user_id,
+                mutate_release_reaction(
+                    state.as_ref(),
+                    token.secret(),
+                    node_id,
+                    content,
+                    believed_reacted,
+                ),
+            )
+            .await
+            {
+                Err(err) if err.code() == codes::REACTION_STATE_CONFLICT => {
+                    github_rate_limit::track(
+                        state.as_ref(),
+                        &user_id,
+                        mutate_release_reaction(
+                            state.as_ref(),
+                            token.secret(),
+                            node_id,
+                            content,
+                            !believed_reacted,
+                        ),
+                    )
+                    .await
+                }
+                other => other,
+            }
+        }
+        None => {
+            let current = match github_rate_limit::track(
+                state.as_ref(),
+                &user_id,
+                fetch_live_release_reactions(state.as_ref(), token.secret(), &[node_id.to_owned()]),
+            )
+            .await
+            {
+                Ok(v) => v,
+                Err(err) if err.code() == codes::REAUTH_REQUIRED => {
+                    return Err(reaction_token_auth_error(
+                        state.as_ref(),
+                        &session,
+                        &user_id,
+                        &token,
+                    )
+                    .await);
+                }
+                Err(err) => return Err(err),
+            };
+            let Some(current_reactions) = current.get(node_id) else {
+                return Err(match token {
+                    ReactionToken::OAuth(_) => ApiError::new(
+                        StatusCode::FORBIDDEN,
+                        codes::FORBIDDEN,
+                        "GitHub login cannot access this release repository; private repositories need repo scope",
+                    ),
+                    ReactionToken::Pat(_) => ApiError::new(
+                        StatusCode::FORBIDDEN,
+                        codes::PAT_FORBIDDEN,
+                        "PAT cannot access this release repository; check token repository access",
+                    ),
+                });
+            };
+            let currently_reacted = match content {
+                ReleaseReactionContent::Plus1 => current_reactions.viewer.plus1,
+                ReleaseReactionContent::Laugh => current_reactions.viewer.laugh,
+                ReleaseReactionContent::Heart => current_reactions.viewer.heart,
+                ReleaseReactionContent::Hooray => current_reactions.viewer.hooray,
+                ReleaseReactionContent::Rocket => current_reactions.viewer.rocket,
+                ReleaseReactionContent::Eyes => current_reactions.viewer.eyes,
+            };
+            github_rate_limit::track(
+                state.as_ref(),
+                &user_id,
+                mutate_release_reaction(
+                    state.as_ref(),
+                    token.secret(),
+                    node_id,
+                    content,
+                    currently_reacted,
+                ),
+            )
+            .await
+        }
+    };
+    let updated = match updated {
+        Ok(v) => v,
+        Err(err) if err.code() == codes::REAUTH_REQUIRED => {
+            return Err(
+                reaction_token_auth_error(state.as_ref(), &session, &user_id, &token).await,
+            );
+        }
+        Err(err) => return Err(err),
+    };
+    if matches!(token, ReactionToken::Pat(_)) {
+        let _ = persist_reaction_pat_check_result(
+            state.as_ref(),
+            &user_id,
+            "valid",
+            Some("PAT is valid"),
+        )
+        .await;
+    }
+    persist_release_reactions(state.as_ref(), &user_id, row.release_id, &updated).await?;
+
+    Ok(Json(ToggleReleaseReactionResponse {
+        release_id: row.release_id.to_string(),
+        reactions: ReleaseReactions {
+            counts: updated.counts,
+            viewer: updated.viewer,
+            status: "ready".to_owned(),
+        },
+    }))
+}
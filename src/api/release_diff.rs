@@ -0,0 +1,380 @@
+use super::*;
+
+/// Output cap for the optional diff summary; a handful of bullets.
+pub(super) const RELEASE_DIFF_SUMMARY_MAX_OUTPUT_TOKENS: u32 = 400;
+/// Characters of section text sent to the model for one diff summary.
+pub(super) const RELEASE_DIFF_SUMMARY_MAX_INPUT_CHARS: usize = 12_000;
+pub(super) const RELEASE_DIFF_ENTITY_TYPE: &str = "release_diff";
+
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct ReleaseDiffQuery {
+    /// Skip prereleases when picking the previous release.
+    pub(super) stable_only: Option<bool>,
+    /// Also summarize the delta with the LLM; cached per release pair.
+    pub(super) summarize: Option<bool>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, JsonSchema)]
+pub struct ReleaseDiffSection {
+    /// Heading text without the `#` marker; null for text before the first
+    /// heading.
+    pub(super) heading: Option<String>,
+    /// 1-6 for headings, 0 for the leading text.
+    pub(super) level: u8,
+    pub(super) body: String,
+}
+
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct ReleaseDiffPrevious {
+    pub(super) release_id: String,
+    pub(super) tag_name: String,
+    pub(super) name: Option<String>,
+    pub(super) html_url: String,
+    pub(super) published_at: Option<String>,
+    pub(super) is_prerelease: bool,
+}
+
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct ReleaseDiffSummary {
+    pub(super) lang: String,
+    #[schemars(extend("enum" = ["ready", "disabled"]))]
+    pub(super) status: String,
+    pub(super) summary: Option<String>,
+}
+
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct ReleaseDiffResponse {
+    pub(super) release_id: String,
+    pub(super) has_previous: bool,
+    pub(super) previous: Option<ReleaseDiffPrevious>,
+    /// Sections of this release with no identical section in the previous one.
+    pub(super) added_sections: Vec<ReleaseDiffSection>,
+    /// Sections of the previous release that are gone from this one.
+    pub(super) removed_sections: Vec<ReleaseDiffSection>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(super) summary: Option<ReleaseDiffSummary>,
+}
+
+#[derive(Debug, sqlx::FromRow)]
+pub(super) struct PreviousReleaseRow {
+    pub(super) release_id: i64,
+    pub(super) tag_name: String,
+    pub(super) name: Option<String>,
+    pub(super) body: Option<String>,
+    pub(super) html_url: String,
+    pub(super) published_at: Option<String>,
+    pub(super) is_prerelease: i64,
+}
+
+fn markdown_heading(line: &str) -> Option<(u8, &str)> {
+    let trimmed = line.trim_start();
+    if line.len() - trimmed.len() > 3 {
+        return None;
+    }
+    let level = trimmed.chars().take_while(|c| *c == '#').count();
+    if !(1..=6).contains(&level) {
+        return None;
+    }
+    let rest = &trimmed[level..];
+    if !rest.is_empty() && !rest.starts_with([' ', '\t']) {
+        return None;
+    }
+    let text = rest.trim().trim_end_matches('#').trim_end();
+    u8::try_from(level).ok().map(|level| (level, text))
+}
+
+/// Splits a release body at ATX headings outside fenced code blocks. Blank
+/// sections are dropped and bodies are trimmed so whitespace-only edits do
+/// not show up as changes.
+pub(super) fn split_release_sections(markdown: &str) -> Vec<ReleaseDiffSection> {
+    let mut sections = Vec::new();
+    let mut current = ReleaseDiffSection {
+        heading: None,
+        level: 0,
+        body: String::new(),
+    };
+    let mut fence: Option<&str> = None;
+    let normalized = markdown.replace("\r\n", "\n");
+    for line in normalized.lines() {
+        let trimmed = line.trim_start();
+        if let Some(marker) = fence {
+            if trimmed.starts_with(marker) {
+                fence = None;
+            }
+        } else if trimmed.starts_with("```") || trimmed.starts_with("~~~") {
+            fence = Some(&trimmed[..3]);
+        } else if let Some((level, heading)) = markdown_heading(line) {
+            sections.push(std::mem::replace(
+                &mut current,
+                ReleaseDiffSection {
+                    heading: Some(heading.to_owned()),
+                    level,
+                    body: String::new(),
+                },
+            ));
+            continue;
+        }
+        current.body.push_str(line.trim_end());
+        current.body.push('\n');
+    }
+    sections.push(current);
+
+    sections
+        .into_iter()
+        .map(|mut section| {
+            section.body = section.body.trim().to_owned();
+            section
+        })
+        .filter(|section| section.heading.is_some() || !section.body.is_empty())
+        .collect()
+}
+
+/// Sections only in `current` and sections only in `previous`. A section
+/// matches when both its heading and its body are identical, so a shared
+/// `## Bug fixes` heading with new entries counts as added and removed.
+pub(super) fn diff_release_sections(
+    previous: &str,
+    current: &str,
+) -> (Vec<ReleaseDiffSection>, Vec<ReleaseDiffSection>) {
+    let previous = split_release_sections(previous);
+    let current = split_release_sections(current);
+    let key = |section: &ReleaseDiffSection| (section.heading.clone(), section.body.clone());
+    let previous_keys = previous.iter().map(key).collect::<HashSet<_>>();
+    let current_keys = current.iter().map(key).collect::<HashSet<_>>();
+    let added = current
+        .iter()
+        .filter(|section| !previous_keys.contains(&key(section)))
+        .cloned()
+        .collect();
+    let removed = previous
+        .into_iter()
+        .filter(|section| !current_keys.contains(&key(section)))
+        .collect();
+    (added, removed)
+}
+
+/// Latest non-draft release of the same repo ordered before `release_id`,
+/// using the same ordering as the `previous_tag_name` of release detail.
+pub(super) async fn fetch_previous_release(
+    state: &AppState,
+    release_id: i64,
+    stable_only: bool,
+) -> Result<Option<PreviousReleaseRow>, ApiError> {
+    sqlx::query_as::<_, PreviousReleaseRow>(
+        r#"
+        WITH cur AS (
+          SELECT repo_id, release_id, COALESCE(published_at, created_at, updated_at) AS sort_at
+          FROM repo_releases
+          WHERE release_id = ?
+        )
+        SELECT p.release_id, p.tag_name, p.name, p.body, p.html_url, p.published_at,
+               p.is_prerelease
+        FROM repo_releases p
+        JOIN cur ON cur.repo_id = p.repo_id
+        WHERE p.release_id <> cur.release_id
+          AND p.is_draft = 0
+          AND (? = 0 OR p.is_prerelease = 0)
+          AND (
+            COALESCE(p.published_at, p.created_at, p.updated_at) < cur.sort_at
+            OR (
+              COALESCE(p.published_at, p.created_at, p.updated_at) = cur.sort_at
+              AND p.release_id < cur.release_id
+            )
+          )
+        ORDER BY COALESCE(p.published_at, p.created_at, p.updated_at) DESC, p.release_id DESC
+        LIMIT 1
+        "#,
+    )
+    .bind(release_id)
+    .bind(stable_only)
+    .fetch_optional(&state.pool)
+    .await
+    .map_err(ApiError::internal)
+}
+
+fn render_sections_for_prompt(sections: &[ReleaseDiffSection]) -> String {
+    sections
+        .iter()
+        .map(|section| match section.heading.as_deref() {
+            Some(heading) => format!("### {heading}\n{}", section.body),
+            None => section.body.clone(),
+        })
+        .collect::<Vec<_>>()
+        .join("\n\n")
+}
+
+pub(super) fn release_diff_source_hash(
+    previous_release_id: i64,
+    added: &[ReleaseDiffSection],
+    removed: &[ReleaseDiffSection],
+) -> String {
+    let source = format!(
+        "v=1\nkind=release_diff\nprevious={previous_release_id}\nadded={}\nremoved={}\n",
+        render_sections_for_prompt(added),
+        render_sections_for_prompt(removed),
+    );
+    ai::sha256_hex(&source)
+}
+
+async fn summarize_release_diff(
+    state: &AppState,
+    user_id: &str,
+    release_id: i64,
+    previous_release_id: i64,
+    added: &[ReleaseDiffSection],
+    removed: &[ReleaseDiffSection],
+) -> Result<ReleaseDiffSummary, ApiError> {
+    if state.config.ai.is_none() {
+        return Ok(ReleaseDiffSummary {
+            lang: "zh-CN".to_owned(),
+            status: "disabled".to_owned(),
+            summary: None,
+        });
+    }
+
+    let source_hash = release_diff_source_hash(previous_release_id, added, removed);
+    let entity_id = release_id.to_string();
+    let cached = sqlx::query_scalar::<_, Option<String>>(
+        r#"
+        SELECT summary
+        FROM ai_translations
+        WHERE user_id = ?
+          AND entity_type = ?
+          AND entity_id = ?
+          AND lang = 'zh-CN'
+          AND source_hash = ?
+          AND status = 'ready'
+        LIMIT 1
+        "#,
+    )
+    .bind(user_id)
+    .bind(RELEASE_DIFF_ENTITY_TYPE)
+    .bind(&entity_id)
+    .bind(&source_hash)
+    .fetch_optional(&state.pool)
+    .await
+    .map_err(ApiError::internal)?;
+    if let Some(summary) = cached {
+        return Ok(ReleaseDiffSummary {
+            lang: "zh-CN".to_owned(),
+            status: "ready".to_owned(),
+            summary,
+        });
+    }
+
+    let requested_at = chrono::Utc::now().to_rfc3339();
+    let delta = format!(
+        "新增内容：\n{}\n\n移除内容：\n{}",
+        render_sections_for_prompt(added),
+        render_sections_for_prompt(removed),
+    );
+    let delta = truncate_chars(&delta, RELEASE_DIFF_SUMMARY_MAX_INPUT_CHARS);
+    let prompt = format!(
+        "{delta}\n\n以上是同一仓库相邻两个 GitHub Release notes 之间按章节比较出的差异。请用中文写不超过 5 条的 Markdown 列表，说明这个版本相比上一个版本变了什么，要求：\n1) 优先破坏性变更与新功能；\n2) 保留代码标识符原样；\n3) 不新增事实；\n4) 只输出列表，不要解释。",
+    );
+    let raw = run_with_api_llm_context(
+        "api.release_diff",
+        Some(user_id.to_owned()),
+        ai::chat_completion(
+            state,
+            "你是一个严谨的技术文档助手，负责概括两个软件版本之间的变化。",
+            &prompt,
+            RELEASE_DIFF_SUMMARY_MAX_OUTPUT_TOKENS,
+        ),
+    )
+    .await
+    .map_err(ai_call_api_error)?;
+    let summary = strip_markdown_code_fence(&raw).trim().to_owned();
+
+    upsert_translation(
+        state,
+        user_id,
+        &requested_at,
+        TranslationUpsert {
+            entity_type: RELEASE_DIFF_ENTITY_TYPE,
+            entity_id: &entity_id,
+            lang: "zh-CN",
+            source_hash: &source_hash,
+            title: None,
+            summary: Some(summary.as_str()),
+        },
+    )
+    .await?;
+
+    Ok(ReleaseDiffSummary {
+        lang: "zh-CN".to_owned(),
+        status: "ready".to_owned(),
+        summary: Some(summary),
+    })
+}
+
+/// What changed since the previous release of the same repo, compared
+/// section by section on markdown headings.
+pub async fn get_release_diff_prev(
+    State(state): State<Arc<AppState>>,
+    AuthedUser(user_id): AuthedUser,
+    Path(release_id_raw): Path<String>,
+    Query(query): Query<ReleaseDiffQuery>,
+) -> Result<Json<ReleaseDiffResponse>, ApiError> {
+    let release_id = parse_release_id_param(&release_id_raw)?;
+
+    let row = fetch_release_detail_row_by_release_id(state.as_ref(), &user_id, release_id)
+        .await?
+        .ok_or_else(|| {
+            ApiError::new(StatusCode::NOT_FOUND, codes::NOT_FOUND, "release not found")
+        })?;
+    ensure_release_detail_visible(state.as_ref(), &user_id, &row).await?;
+
+    let Some(previous) = fetch_previous_release(
+        state.as_ref(),
+        release_id,
+        query.stable_only.unwrap_or(false),
+    )
+    .await?
+    else {
+        return Ok(Json(ReleaseDiffResponse {
+            release_id: release_id.to_string(),
+            has_previous: false,
+            previous: None,
+            added_sections: Vec::new(),
+            removed_sections: Vec::new(),
+            summary: None,
+        }));
+    };
+
+    let (added, removed) = diff_release_sections(
+        previous.body.as_deref().unwrap_or_default(),
+        row.body.as_deref().unwrap_or_default(),
+    );
+    let summary = if query.summarize.unwrap_or(false) && !(added.is_empty() && removed.is_empty()) {
+        Some(
+            summarize_release_diff(
+                state.as_ref(),
+                &user_id,
+                release_id,
+                previous.release_id,
+                &added,
+                &removed,
+            )
+            .await?,
+        )
+    } else {
+        None
+    };
+
+    Ok(Json(ReleaseDiffResponse {
+        release_id: release_id.to_string(),
+        has_previous: true,
+        previous: Some(ReleaseDiffPrevious {
+            release_id: previous.release_id.to_string(),
+            tag_name: previous.tag_name,
+            name: previous.name,
+            html_url: previous.html_url,
+            published_at: previous.published_at,
+            is_prerelease: previous.is_prerelease != 0,
+        }),
+        added_sections: added,
+        removed_sections: removed,
+        summary,
+    }))
+}
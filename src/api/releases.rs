@@ -0,0 +1,1930 @@
+use super::*;
+
+#[derive(Debug, Serialize, sqlx::FromRow)]
+pub struct ReleaseItem {
+    pub(super) full_name: String,
+    pub(super) tag_name: String,
+    pub(super) name: Option<String>,
+    pub(super) published_at: Option<String>,
+    pub(super) html_url: String,
+    pub(super) is_prerelease: i64,
+    pub(super) is_draft: i64,
+}
+
+pub async fn list_releases(
+    State(state): State<Arc<AppState>>,
+    AuthedUser(user_id): AuthedUser,
+) -> Result<Json<Vec<ReleaseItem>>, ApiError> {
+    let items = sqlx::query_as::<_, ReleaseItem>(
+        r#"
+        SELECT sr.full_name, r.tag_name, r.name, r.published_at, r.html_url, r.is_prerelease, r.is_draft
+        FROM repo_releases r
+        JOIN user_release_visible_repos sr
+          ON sr.user_id = ? AND sr.repo_id = r.repo_id
+        WHERE NOT EXISTS (
+          SELECT 1
+          FROM muted_repos m
+          WHERE m.user_id = sr.user_id
+            AND m.repo_id = r.repo_id
+        )
+        ORDER BY COALESCE(r.published_at, r.created_at) DESC
+        LIMIT 200
+        "#,
+    )
+     .bind(&user_id)
+    .fetch_all(&state.pool)
+    .await
+    .map_err(ApiError::internal)?;
+
+    Ok(Json(items))
+}
+
+#[derive(Debug, Serialize)]
+pub struct ReleaseDetailResponse {
+    pub(super) release_id: String,
+    pub(super) repo_full_name: Option<String>,
+    pub(super) repo_owner_login: Option<String>,
+    pub(super) repo_owner_avatar_url: Option<String>,
+    pub(super) repo_description: Option<String>,
+    pub(super) repo_html_url: Option<String>,
+    pub(super) repo_visual: Option<RepoVisual>,
+    pub(super) tag_name: String,
+    pub(super) previous_tag_name: Option<String>,
+    pub(super) name: Option<String>,
+    pub(super) body: Option<String>,
+    pub(super) html_url: String,
+    pub(super) published_at: Option<String>,
+    pub(super) is_prerelease: i64,
+    pub(super) is_draft: i64,
+    pub(super) translated: Option<TranslatedItem>,
+    pub(super) smart: Option<SmartItem>,
+    pub(super) assets: Vec<ReleaseAssetItem>,
+    /// Set when the assets of a release synced before assets were stored
+    /// could not be fetched from GitHub; `assets` is empty then.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(super) assets_warning: Option<FeedWarning>,
+}
+
+#[derive(Debug, Serialize, sqlx::FromRow)]
+pub struct ReleaseAssetItem {
+    pub(super) name: String,
+    /// Bytes; the client humanizes it.
+    pub(super) size: i64,
+    pub(super) download_count: i64,
+    pub(super) browser_download_url: String,
+    pub(super) content_type: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub(super) struct GitHubReleaseAssetsPayload {
+    #[serde(default)]
+    pub(super) assets: Vec<sync::GitHubReleaseAsset>,
+}
+
+#[derive(Debug, sqlx::FromRow)]
+pub(super) struct ReleaseDetailRow {
+    pub(super) repo_id: i64,
+    pub(super) release_id: i64,
+    pub(super) repo_full_name: Option<String>,
+    pub(super) repo_owner_login: Option<String>,
+    pub(super) owner_avatar_url: Option<String>,
+    pub(super) repo_description: Option<String>,
+    pub(super) repo_html_url: Option<String>,
+    pub(super) open_graph_image_url: Option<String>,
+    pub(super) uses_custom_open_graph_image: Option<i64>,
+    pub(super) tag_name: String,
+    pub(super) name: Option<String>,
+    pub(super) body: Option<String>,
+    pub(super) html_url: String,
+    pub(super) published_at: Option<String>,
+    pub(super) is_prerelease: i64,
+    pub(super) is_draft: i64,
+    pub(super) previous_tag_name: Option<String>,
+    pub(super) trans_source_hash: Option<String>,
+    pub(super) trans_status: Option<String>,
+    pub(super) trans_title: Option<String>,
+    pub(super) trans_summary: Option<String>,
+    pub(super) trans_error_text: Option<String>,
+    pub(super) trans_work_status: Option<String>,
+    pub(super) summary_source_hash: Option<String>,
+    pub(super) summary_title: Option<String>,
+    pub(super) summary_summary: Option<String>,
+    pub(super) smart_source_hash: Option<String>,
+    pub(super) smart_status: Option<String>,
+    pub(super) smart_title: Option<String>,
+    pub(super) smart_summary: Option<String>,
+    pub(super) smart_error_text: Option<String>,
+    pub(super) smart_work_status: Option<String>,
+}
+
+pub(super) async fn fetch_release_detail_row_by_release_id(
+    state: &AppState,
+    user_id: &str,
+    release_id: i64,
+) -> Result<Option<ReleaseDetailRow>, ApiError> {
+    sqlx::query_as::<_, ReleaseDetailRow>(
+        r#"
+        SELECT
+          r.repo_id,
+          r.release_id,
+          sr.full_name AS repo_full_name,
+          sr.owner_login AS repo_owner_login,
+          sr.owner_avatar_url AS owner_avatar_url,
+          sr.description AS repo_description,
+          sr.html_url AS repo_html_url,
+          sr.open_graph_image_url AS open_graph_image_url,
+          sr.uses_custom_open_graph_image AS uses_custom_open_graph_image,
+          r.tag_name,
+          r.name,
+          r.body,
+          r.html_url,
+          r.published_at,
+          r.is_prerelease,
+          r.is_draft,
+          rp.previous_tag_name,
+          t.source_hash AS trans_source_hash,
+          t.status AS trans_status,
+          t.title AS trans_title,
+          t.summary AS trans_summary,
+          t.error_text AS trans_error_text,
+          tw.status AS trans_work_status,
+          ds.source_hash AS summary_source_hash,
+          ds.title AS summary_title,
+          ds.summary AS summary_summary,
+          s.source_hash AS smart_source_hash,
+          s.status AS smart_status,
+          s.title AS smart_title,
+          s.summary AS smart_summary,
+          s.error_text AS smart_error_text,
+          sw.status AS smart_work_status
+        FROM repo_releases r
+        LEFT JOIN (
+          SELECT
+            release_id,
+            LAG(tag_name) OVER (
+              PARTITION BY repo_id
+              ORDER BY COALESCE(published_at, created_at, updated_at) ASC, release_id ASC
+            ) AS previous_tag_name
+          FROM repo_releases
+        ) rp
+          ON rp.release_id = r.release_id
+        LEFT JOIN user_release_visible_repos sr
+          ON sr.user_id = ? AND sr.repo_id = r.repo_id
+        LEFT JOIN ai_translations t
+          ON t.user_id = ?
+          AND t.entity_type = 'release_detail'
+          AND t.entity_id = CAST(r.release_id AS TEXT)
+          AND t.lang = 'zh-CN'
+          AND t.status IN ('ready', 'disabled', 'missing', 'error')
+        LEFT JOIN translation_work_items tw
+          ON tw.id = t.active_work_item_id
+        LEFT JOIN ai_translations ds
+          ON ds.user_id = ?
+          AND ds.entity_type = 'release_detail_summary'
+          AND ds.entity_id = CAST(r.release_id AS TEXT)
+          AND ds.lang = 'zh-CN'
+          AND ds.status = 'ready'
+        LEFT JOIN ai_translations s
+          ON s.user_id = ?
+          AND s.entity_type = 'release_smart'
+          AND s.entity_id = CAST(r.release_id AS TEXT)
+          AND s.lang = 'zh-CN'
+          AND s.status IN ('ready', 'disabled', 'missing', 'error')
+        LEFT JOIN translation_work_items sw
+          ON sw.id = s.active_work_item_id
+        WHERE r.release_id = ?
+        LIMIT 1
+        "#,
+    )
+    .bind(user_id)
+    .bind(user_id)
+    .bind(user_id)
+    .bind(user_id)
+    .bind(release_id)
+    .fetch_optional(&state.pool)
+    .await
+    .map_err(ApiError::internal)
+}
+
+pub(super) async fn fetch_release_detail_row_by_locator(
+    state: &AppState,
+    user_id: &str,
+    locator: &ReleaseLocator,
+) -> Result<Option<ReleaseDetailRow>, ApiError> {
+    let [github_prefix, www_prefix] = build_github_release_url_prefixes(locator);
+    sqlx::query_as::<_, ReleaseDetailRow>(
+        r#"
+        SELECT
+          r.repo_id,
+          r.release_id,
+          sr.full_name AS repo_full_name,
+          sr.owner_login AS repo_owner_login,
+          sr.owner_avatar_url AS owner_avatar_url,
+          sr.description AS repo_description,
+          sr.html_url AS repo_html_url,
+          sr.open_graph_image_url AS open_graph_image_url,
+          sr.uses_custom_open_graph_image AS uses_custom_open_graph_image,
+          r.tag_name,
+          r.name,
+          r.body,
+          r.html_url,
+          r.published_at,
+          r.is_prerelease,
+          r.is_draft,
+          rp.previous_tag_name,
+          t.source_hash AS trans_source_hash,
+          t.status AS trans_status,
+          t.title AS trans_title,
+          t.summary AS trans_summary,
+          t.error_text AS trans_error_text,
+          tw.status AS trans_work_status,
+          ds.source_hash AS summary_source_hash,
+          ds.title AS summary_title,
+          ds.summary AS summary_summary,
+          s.source_hash AS smart_source_hash,
+          s.status AS smart_status,
+          s.title AS smart_title,
+          s.summary AS smart_summary,
+          s.error_text AS smart_error_text,
+          sw.status AS smart_work_status
+        FROM repo_releases r
+        LEFT JOIN (
+          SELECT
+            release_id,
+            LAG(tag_name) OVER (
+              PARTITION BY repo_id
+              ORDER BY COALESCE(published_at, created_at, updated_at) ASC, release_id ASC
+            ) AS previous_tag_name
+          FROM repo_releases
+        ) rp
+          ON rp.release_id = r.release_id
+        LEFT JOIN user_release_visible_repos sr
+          ON sr.user_id = ? AND sr.repo_id = r.repo_id
+        LEFT JOIN ai_translations t
+          ON t.user_id = ?
+          AND t.entity_type = 'release_detail'
+          AND t.entity_id = CAST(r.release_id AS TEXT)
+          AND t.lang = 'zh-CN'
+          AND t.status IN ('ready', 'disabled', 'missing', 'error')
+        LEFT JOIN translation_work_items tw
+          ON tw.id = t.active_work_item_id
+        LEFT JOIN ai_translations ds
+          ON ds.user_id = ?
+          AND ds.entity_type = 'release_detail_summary'
+          AND ds.entity_id = CAST(r.release_id AS TEXT)
+          AND ds.lang = 'zh-CN'
+          AND ds.status = 'ready'
+        LEFT JOIN ai_translations s
+          ON s.user_id = ?
+          AND s.entity_type = 'release_smart'
+          AND s.entity_id = CAST(r.release_id AS TEXT)
+          AND s.lang = 'zh-CN'
+          AND s.status IN ('ready', 'disabled', 'missing', 'error')
+        LEFT JOIN translation_work_items sw
+          ON sw.id = s.active_work_item_id
+        WHERE r.tag_name = ?
+          AND (instr(lower(r.html_url), ?) = 1 OR instr(lower(r.html_url), ?) = 1)
+        ORDER BY r.published_at DESC, r.created_at DESC, r.release_id DESC
+        LIMIT 1
+        "#,
+    )
+    .bind(user_id)
+    .bind(user_id)
+    .bind(user_id)
+    .bind(user_id)
+    .bind(&locator.tag)
+    .bind(github_prefix.to_ascii_lowercase())
+    .bind(www_prefix.to_ascii_lowercase())
+    .fetch_optional(&state.pool)
+    .await
+    .map_err(ApiError::internal)
+}
+
+pub(super) async fn build_release_detail_response(
+    state: &AppState,
+    user_id: &str,
+    row: ReleaseDetailRow,
+) -> Result<ReleaseDetailResponse, ApiError> {
+    ensure_release_detail_visible(state, user_id, &row).await?;
+
+    let original_title = row
+        .name
+        .as_deref()
+        .map(str::trim)
+        .filter(|s| !s.is_empty())
+        .unwrap_or(&row.tag_name)
+        .to_owned();
+    let original_body = row.body.clone().unwrap_or_default();
+    let resolved_full_name = resolve_release_full_name(&row.html_url, row.repo_id);
+    let source_hash =
+        release_detail_source_hash(&resolved_full_name, &original_title, &original_body);
+    let smart_body = release_feed_body(row.body.as_deref());
+    let smart_source_hash = crate::translations::release_smart_feed_source_hash(
+        row.release_id.to_string().as_str(),
+        &resolved_full_name,
+        &original_title,
+        smart_body.as_deref(),
+        &row.tag_name,
+        row.previous_tag_name.as_deref(),
+    );
+    let translation_fresh = row.trans_source_hash.as_deref() == Some(source_hash.as_str());
+    let smart_fresh = row.smart_source_hash.as_deref() == Some(smart_source_hash.as_str());
+
+    let refresh_in_flight = !translation_fresh
+        && row.trans_status.as_deref() == Some("ready")
+        && matches!(
+            row.trans_work_status.as_deref(),
+            Some("queued" | "batched" | "running")
+        );
+    let smart_refresh_in_flight = !smart_fresh
+        && row.smart_status.as_deref() == Some("ready")
+        && matches!(
+            row.smart_work_status.as_deref(),
+            Some("queued" | "batched" | "running")
+        );
+
+    let translated = if state.config.ai.is_none() {
+        Some(translated_item("disabled", None, None, None, None))
+    } else {
+        match (translation_fresh, row.trans_status.as_deref()) {
+            (true, Some("ready"))
+                if release_detail_translation_ready(
+                    Some(original_body.as_str()),
+                    row.trans_summary.as_deref(),
+                ) =>
+            {
+                Some(translated_item(
+                    "ready",
+                    row.trans_title.clone(),
+                    row.trans_summary.clone(),
+                    None,
+                    None,
+                ))
+            }
+            (true, Some("ready")) => Some(translated_item(
+                "error",
+                None,
+                None,
+                None,
+                Some(RELEASE_DETAIL_MARKDOWN_MISMATCH_ERROR),
+            )),
+            (false, Some("ready")) if refresh_in_flight => {
+                translated_ready_item(row.trans_title.clone(), row.trans_summary.clone(), None)
+                    .or_else(|| Some(translated_missing_item(false)))
+            }
+            (true, Some(status)) => {
+                translated_terminal_item(status, row.trans_error_text.as_deref())
+            }
+            _ => Some(translated_missing_item(true)),
+        }
+    };
+    let summary_fresh = row.summary_source_hash.as_deref() == Some(source_hash.as_str());
+    let translated = match translated {
+        Some(mut item) if item.status == "ready" => {
+            item.mode = Some(ReleaseDetailTranslationMode::Full.as_str().to_owned());
+            Some(item)
+        }
+        // Without a usable full translation, a fresh summary is the best content on hand.
+        other if summary_fresh => {
+            translated_ready_item(row.summary_title.clone(), row.summary_summary.clone(), None)
+                .map(|mut item| {
+                    item.mode = Some(ReleaseDetailTranslationMode::Summary.as_str().to_owned());
+                    item
+                })
+                .or(other)
+        }
+        other => other,
+    };
+    let translated = translated.map(|mut item| {
+        if state.config.ai.is_some() {
+            item.detected_lang = release_detected_lang(state, &original_title, &original_body);
+        }
+        item
+    });
+
+    let smart = if state.config.ai.is_none() {
+        Some(smart_item("disabled", None, None, None, None))
+    } else if smart_fresh {
+        if let Some(status) = row.smart_status.as_deref()
+            && status != "ready"
+        {
+            smart_terminal_item(status, row.smart_error_text.as_deref())
+        } else {
+            smart_ready_item(row.smart_title.clone(), row.smart_summary.clone(), None)
+                .or_else(|| Some(smart_missing_item(None)))
+        }
+    } else if smart_refresh_in_flight {
+        smart_ready_item(
+            row.smart_title.clone(),
+            row.smart_summary.clone(),
+            Some(true),
+        )
+        .or_else(|| Some(smart_missing_item(Some(false))))
+    } else {
+        Some(smart_missing_item(None))
+    };
+
+    let (assets, assets_warning) = load_release_detail_assets(
+        state,
+        user_id,
+        row.release_id,
+        row.repo_full_name.as_deref().unwrap_or(&resolved_full_name),
+    )
+    .await?;
+
+    let repo_owner_avatar_url = normalize_visual_url(row.owner_avatar_url.clone());
+    let repo_visual = repo_visual_from_parts(
+        row.owner_avatar_url,
+        row.open_graph_image_url,
+        row.uses_custom_open_graph_image.unwrap_or(0) != 0,
+    );
+
+    Ok(ReleaseDetailResponse {
+        release_id: row.release_id.to_string(),
+        repo_full_name: row.repo_full_name.or(Some(resolved_full_name)),
+        repo_owner_login: row.repo_owner_login,
+        repo_owner_avatar_url,
+        repo_description: row.repo_description,
+        repo_html_url: row.repo_html_url,
+        repo_visual,
+        tag_name: row.tag_name,
+        previous_tag_name: row.previous_tag_name,
+        name: row.name,
+        body: row.body,
+        html_url: row.html_url,
+        published_at: row.published_at,
+        is_prerelease: row.is_prerelease,
+        is_draft: row.is_draft,
+        translated,
+        smart,
+        assets,
+        assets_warning,
+    })
+}
+
+/// Serves the stored assets of a release. Releases synced before assets were
+/// stored get theirs fetched once with the user's GitHub tokens; a failed
+/// fetch degrades to a warning instead of failing the whole detail.
+pub(super) async fn load_release_detail_assets(
+    state: &AppState,
+    user_id: &str,
+    release_id: i64,
+    repo_full_name: &str,
+) -> Result<(Vec<ReleaseAssetItem>, Option<FeedWarning>), ApiError> {
+    let assets_synced_at = sqlx::query_scalar::<_, Option<String>>(
+        r#"SELECT assets_synced_at FROM repo_releases WHERE release_id = ?"#,
+    )
+    .bind(release_id)
+    .fetch_optional(&state.pool)
+    .await
+    .map_err(ApiError::internal)?
+    .flatten();
+
+    let mut warning = None;
+    if assets_synced_at.is_none() {
+        match github_rate_limit::track(
+            state,
+            user_id,
+            fetch_release_assets_with_user_tokens(state, user_id, repo_full_name, release_id),
+        )
+        .await
+        {
+            Ok(assets) => {
+                let now = chrono::Utc::now().to_rfc3339();
+                state
+                    .sqlite_writer
+                    .write_foreground("release_assets_fill", |_| {
+                        sync::store_release_assets(state, release_id, &assets, now.as_str())
+                    })
+                    .await
+                    .map_err(ApiError::internal)?;
+            }
+            Err(err) => {
+                tracing::warn!(
+                    user_id,
+                    release_id,
+                    repo_full_name,
+                    code = err.code(),
+                    "release assets fetch failed; serving detail without assets"
+                );
+                warning = Some(FeedWarning {
+                    code: err.code().to_owned(),
+                    message: if err.code() == codes::RATE_LIMITED {
+                        "GitHub rate limit reached; release assets are not available yet".to_owned()
+                    } else {
+                        "release assets could not be loaded from GitHub".to_owned()
+                    },
+                });
+            }
+        }
+    }
+
+    let assets = load_stored_release_assets(state, release_id).await?;
+    Ok((assets, warning))
+}
+
+pub(super) async fn load_stored_release_assets(
+    state: &AppState,
+    release_id: i64,
+) -> Result<Vec<ReleaseAssetItem>, ApiError> {
+    sqlx::query_as::<_, ReleaseAssetItem>(
+        r#"
+        SELECT name, size, download_count, browser_download_url, content_type
+        FROM release_assets
+        WHERE release_id = ?
+        ORDER BY name, asset_id
+        "#,
+    )
+    .bind(release_id)
+    .fetch_all(&state.pool)
+    .await
+    .map_err(ApiError::internal)
+}
+
+/// One REST call per linked GitHub account until one can see the release, so
+/// private repo assets only come back through a token that has access.
+pub(super) async fn fetch_release_assets_with_user_tokens(
+    state: &AppState,
+    user_id: &str,
+    repo_full_name: &str,
+    release_id: i64,
+) -> Result<Vec<sync::GitHubReleaseAsset>, ApiError> {
+    let url = state
+        .github_rest_api_base
+        .join(&format!("repos/{repo_full_name}/releases/{release_id}"))
+        .map_err(ApiError::internal)?;
+    let connections = state
+        .load_github_connections(user_id)
+        .await
+        .map_err(|err| ApiError::internal(format!("load github connections failed: {err}")))?;
+
+    let mut last_err = ApiError::new(
+        StatusCode::NOT_FOUND,
+        codes::NOT_FOUND,
+        "release is not visible to any linked GitHub account",
+    );
+    for connection in connections {
+        let resp = state
+            .http
+            .get(url.clone())
+            .bearer_auth(connection.access_token.as_str())
+            .header(reqwest::header::USER_AGENT, "OctoRill")
+            .header(reqwest::header::ACCEPT, "application/vnd.github+json")
+            .header("X-GitHub-Api-Version", "2022-11-28")
+            .send()
+            .await
+            .map_err(ApiError::internal)?;
+        github_rate_limit::observe_response(&resp);
+        let status = resp.status();
+        if status == reqwest::StatusCode::NOT_FOUND {
+            continue;
+        }
+        if !status.is_success() {
+            let headers = resp.headers().clone();
+            let body = resp.text().await.unwrap_or_default();
+            let err = github_rest_http_error("release", status, &headers, &body);
+            if err.code() == codes::REAUTH_REQUIRED || err.code() == codes::FORBIDDEN {
+                last_err = err;
+                continue;
+            }
+            return Err(err);
+        }
+        let payload = resp
+            .json::<GitHubReleaseAssetsPayload>()
+            .await
+            .map_err(ApiError::internal)?;
+        return Ok(payload.assets);
+    }
+    Err(last_err)
+}
+
+pub async fn get_release_detail(
+    State(state): State<Arc<AppState>>,
+    AuthedUser(user_id): AuthedUser,
+    Path(release_id_raw): Path<String>,
+) -> Result<Json<ReleaseDetailResponse>, ApiError> {
+    let release_id = parse_release_id_param(&release_id_raw)?;
+
+    let row = fetch_release_detail_row_by_release_id(state.as_ref(), &user_id, release_id)
+        .await?
+        .ok_or_else(|| {
+            ApiError::new(StatusCode::NOT_FOUND, codes::NOT_FOUND, "release not found")
+        })?;
+
+    Ok(Json(
+        build_release_detail_response(state.as_ref(), &user_id, row).await?,
+    ))
+}
+
+pub async fn get_release_detail_by_repo_tag(
+    State(state): State<Arc<AppState>>,
+    AuthedUser(user_id): AuthedUser,
+    Path((owner_raw, repo_raw, tag_raw)): Path<(String, String, String)>,
+) -> Result<Json<ReleaseDetailResponse>, ApiError> {
+    let locator = ReleaseLocator {
+        owner: owner_raw,
+        repo: repo_raw,
+        tag: tag_raw,
+    };
+
+    let row = fetch_release_detail_row_by_locator(state.as_ref(), &user_id, &locator)
+        .await?
+        .ok_or_else(|| {
+            ApiError::new(StatusCode::NOT_FOUND, codes::NOT_FOUND, "release not found")
+        })?;
+
+    Ok(Json(
+        build_release_detail_response(state.as_ref(), &user_id, row).await?,
+    ))
+}
+
+/// Releases outside the user's visible repos are still readable through a
+/// brief that linked them.
+pub(super) async fn ensure_release_detail_visible(
+    state: &AppState,
+    user_id: &str,
+    row: &ReleaseDetailRow,
+) -> Result<(), ApiError> {
+    let locator = parse_release_locator_from_github_release_url(&row.html_url);
+    if row.repo_full_name.is_none()
+        && !user_has_brief_access_to_release(state, user_id, row.release_id, locator.as_ref())
+            .await?
+    {
+        return Err(ApiError::new(
+            StatusCode::NOT_FOUND,
+            codes::NOT_FOUND,
+            "release not found",
+        ));
+    }
+    Ok(())
+}
+
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct RenderedReleaseQuery {
+    /// Also render the translated body; only `zh-CN` is available.
+    pub(super) lang: Option<String>,
+}
+
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct RenderedReleaseResponse {
+    pub(super) release_id: String,
+    pub(super) html: String,
+    pub(super) sanitization: SanitizationReport,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(super) translated: Option<RenderedReleaseTranslation>,
+}
+
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct SanitizationReport {
+    pub(super) stripped_elements: i64,
+}
+
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct RenderedReleaseTranslation {
+    pub(super) lang: String,
+    /// `missing` when no current translation exists yet.
+    #[schemars(extend("enum" = ["ready", "missing"]))]
+    pub(super) status: String,
+    pub(super) html: Option<String>,
+    pub(super) sanitization: Option<SanitizationReport>,
+}
+
+pub(super) async fn render_release_markdown_parts(
+    state: &AppState,
+    markdown: &str,
+    repo_html_url: &str,
+    git_ref: &str,
+) -> Result<(String, SanitizationReport), ApiError> {
+    let rendered = release_render::render_release_markdown_cached(
+        &state.pool,
+        markdown,
+        repo_html_url,
+        git_ref,
+    )
+    .await
+    .map_err(ApiError::internal)?;
+    Ok((
+        rendered.html,
+        SanitizationReport {
+            stripped_elements: rendered.stripped_elements,
+        },
+    ))
+}
+
+/// Release body as sanitized HTML, with repo-relative links resolved against
+/// the release tag. Rendering is cached by source hash.
+pub async fn get_rendered_release(
+    State(state): State<Arc<AppState>>,
+    AuthedUser(user_id): AuthedUser,
+    Path(release_id_raw): Path<String>,
+    Query(query): Query<RenderedReleaseQuery>,
+) -> Result<Json<RenderedReleaseResponse>, ApiError> {
+    let release_id = parse_release_id_param(&release_id_raw)?;
+    if let Some(lang) = query.lang.as_deref()
+        && lang != "zh-CN"
+    {
+        return Err(ApiError::new(
+            StatusCode::BAD_REQUEST,
+            codes::UNSUPPORTED_LANGUAGE,
+            "only zh-CN is supported",
+        ));
+    }
+
+    let row = fetch_release_detail_row_by_release_id(state.as_ref(), &user_id, release_id)
+        .await?
+        .ok_or_else(|| {
+            ApiError::new(StatusCode::NOT_FOUND, codes::NOT_FOUND, "release not found")
+        })?;
+    ensure_release_detail_visible(state.as_ref(), &user_id, &row).await?;
+
+    let resolved_full_name = resolve_release_full_name(&row.html_url, row.repo_id);
+    let repo_html_url = row
+        .repo_html_url
+        .clone()
+        .unwrap_or_else(|| format!("https://github.com/{resolved_full_name}"));
+    let original_body = row.body.clone().unwrap_or_default();
+    let (html, sanitization) = render_release_markdown_parts(
+        state.as_ref(),
+        &original_body,
+        &repo_html_url,
+        &row.tag_name,
+    )
+    .await?;
+
+    let translated = match query.lang {
+        Some(lang) => {
+            let original_title = row
+                .name
+                .as_deref()
+                .map(str::trim)
+                .filter(|s| !s.is_empty())
+                .unwrap_or(&row.tag_name);
+            let source_hash =
+                release_detail_source_hash(&resolved_full_name, original_title, &original_body);
+            let summary = row
+                .trans_summary
+                .as_deref()
+                .filter(|_| row.trans_status.as_deref() == Some("ready"))
+                .filter(|_| row.trans_source_hash.as_deref() == Some(source_hash.as_str()));
+            Some(match summary {
+                Some(summary) => {
+                    let (html, sanitization) = render_release_markdown_parts(
+                        state.as_ref(),
+                        summary,
+                        &repo_html_url,
+                        &row.tag_name,
+                    )
+                    .await?;
+                    RenderedReleaseTranslation {
+                        lang,
+                        status: "ready".to_owned(),
+                        html: Some(html),
+                        sanitization: Some(sanitization),
+                    }
+                }
+                None => RenderedReleaseTranslation {
+                    lang,
+                    status: "missing".to_owned(),
+                    html: None,
+                    sanitization: None,
+                },
+            })
+        }
+        None => None,
+    };
+
+    Ok(Json(RenderedReleaseResponse {
+        release_id: row.release_id.to_string(),
+        html,
+        sanitization,
+        translated,
+    }))
+}
+
+#[derive(Debug, Deserialize)]
+pub struct PublicReleaseQuery {
+    pub(super) content: Option<String>,
+    pub(super) lang: Option<String>,
+    pub(super) source: Option<String>,
+    pub(super) cursor: Option<String>,
+    pub(super) limit: Option<i64>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct PublicReleaseListItem {
+    pub(super) release_id: String,
+    pub(super) repo_full_name: String,
+    pub(super) repo_visual: Option<RepoVisual>,
+    pub(super) tag_name: String,
+    pub(super) previous_tag_name: Option<String>,
+    pub(super) name: Option<String>,
+    pub(super) body: Option<String>,
+    pub(super) html_url: String,
+    pub(super) published_at: Option<String>,
+    pub(super) is_prerelease: i64,
+    pub(super) is_draft: i64,
+    pub(super) translated: Option<TranslatedItem>,
+    pub(super) smart: Option<SmartItem>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct PublicReleaseListResponse {
+    pub(super) status: String,
+    pub(super) repo_full_name: String,
+    pub(super) next_cursor: Option<String>,
+    pub(super) items: Vec<PublicReleaseListItem>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct PublicReleasePendingResponse {
+    pub(super) status: &'static str,
+    pub(super) message: &'static str,
+    pub(super) reason: &'static str,
+    pub(super) retry_after_seconds: i64,
+    pub(super) repo_full_name: String,
+    pub(super) last_requested_at: String,
+}
+
+#[derive(Debug, sqlx::FromRow)]
+pub(super) struct PublicReleaseUsageRow {
+    pub(super) repo_id: Option<i64>,
+    pub(super) full_name: String,
+    pub(super) last_requested_at: String,
+    pub(super) last_sync_status: String,
+}
+
+#[derive(Debug, sqlx::FromRow)]
+pub(super) struct PublicReleaseLocalRepoRow {
+    pub(super) repo_id: i64,
+    pub(super) full_name: String,
+    pub(super) is_private: i64,
+    pub(super) metadata_updated_at: Option<String>,
+}
+
+#[derive(Debug, sqlx::FromRow)]
+pub(super) struct PublicReleaseRow {
+    pub(super) release_id: i64,
+    pub(super) sort_ts: String,
+    pub(super) repo_full_name: String,
+    pub(super) owner_avatar_url: Option<String>,
+    pub(super) open_graph_image_url: Option<String>,
+    pub(super) uses_custom_open_graph_image: Option<i64>,
+    pub(super) tag_name: String,
+    pub(super) name: Option<String>,
+    pub(super) body: Option<String>,
+    pub(super) html_url: String,
+    pub(super) published_at: Option<String>,
+    pub(super) is_prerelease: i64,
+    pub(super) is_draft: i64,
+    pub(super) previous_tag_name: Option<String>,
+    pub(super) trans_source_hash: Option<String>,
+    pub(super) trans_status: Option<String>,
+    pub(super) trans_title: Option<String>,
+    pub(super) trans_summary: Option<String>,
+    pub(super) trans_error_text: Option<String>,
+    pub(super) smart_source_hash: Option<String>,
+    pub(super) smart_status: Option<String>,
+    pub(super) smart_title: Option<String>,
+    pub(super) smart_summary: Option<String>,
+    pub(super) smart_error_text: Option<String>,
+}
+
+#[derive(Debug)]
+pub(super) struct PublicReleaseCursor {
+    pub(super) sort_ts: String,
+    pub(super) release_id: i64,
+}
+
+pub(super) fn validate_public_release_query(query: &PublicReleaseQuery) -> Result<(), ApiError> {
+    let lang = query.lang.as_deref().unwrap_or("zh-CN");
+    if lang != "zh-CN" {
+        return Err(ApiError::new(
+            StatusCode::BAD_REQUEST,
+            codes::UNSUPPORTED_LANGUAGE,
+            "only zh-CN is supported",
+        ));
+    }
+
+    let content = query.content.as_deref().unwrap_or("all");
+    if !matches!(content, "original" | "translated" | "polished" | "all") {
+        return Err(ApiError::bad_request(
+            "content must be original, translated, polished, or all",
+        ));
+    }
+
+    Ok(())
+}
+
+pub(super) fn public_source_is_page(query: &PublicReleaseQuery) -> bool {
+    query.source.as_deref() == Some("page")
+}
+
+pub(super) fn parse_public_release_cursor(raw: &str) -> Result<PublicReleaseCursor, ApiError> {
+    if raw.len() > FEED_CURSOR_MAX_LEN {
+        return Err(invalid_cursor(format!(
+            "cursor exceeds {FEED_CURSOR_MAX_LEN} bytes"
+        )));
+    }
+    let Some((sort_ts, release_id_raw)) = raw.trim().split_once('|') else {
+        return Err(invalid_cursor("expected sort_ts|release_id"));
+    };
+    let sort_ts = sort_ts.trim();
+    validate_cursor_sort_ts(sort_ts)?;
+    let release_id = release_id_raw
+        .trim()
+        .parse::<i64>()
+        .map_err(|_| invalid_cursor("malformed cursor id"))?;
+    Ok(PublicReleaseCursor {
+        sort_ts: sort_ts.to_owned(),
+        release_id,
+    })
+}
+
+pub(super) fn public_release_cursor(row: &PublicReleaseRow) -> String {
+    format!("{}|{}", row.sort_ts, row.release_id)
+}
+
+pub(super) fn normalize_public_repo_path(
+    owner: String,
+    repo: String,
+) -> Result<(String, String), ApiError> {
+    let owner = owner.trim();
+    let repo = repo.trim();
+    let valid = |value: &str| {
+        !value.is_empty()
+            && value.len() <= 100
+            && value
+                .chars()
+                .all(|ch| ch.is_ascii_alphanumeric() || matches!(ch, '-' | '_' | '.'))
+    };
+    if !valid(owner) || !valid(repo) {
+        return Err(ApiError::bad_request("invalid repository path"));
+    }
+    Ok((owner.to_owned(), repo.to_owned()))
+}
+
+pub(super) async fn upsert_public_release_usage(
+    state: &AppState,
+    owner: String,
+    repo: String,
+    detail: bool,
+    page_source: bool,
+) -> Result<PublicReleaseUsageRow, ApiError> {
+    let (owner, repo) = normalize_public_repo_path(owner, repo)?;
+    let full_name = format!("{owner}/{repo}");
+    let full_name_lower = full_name.to_ascii_lowercase();
+    let now = chrono::Utc::now().to_rfc3339();
+
+    let (api_list_inc, api_detail_inc, page_list_inc, page_detail_inc) = match (detail, page_source)
+    {
+        (false, false) => (1_i64, 0_i64, 0_i64, 0_i64),
+        (true, false) => (0_i64, 1_i64, 0_i64, 0_i64),
+        (false, true) => (0_i64, 0_i64, 1_i64, 0_i64),
+        (true, true) => (0_i64, 0_i64, 0_i64, 1_i64),
+    };
+
+    sqlx::query(
+        r#"
+        INSERT INTO public_repo_release_usage (
+          id, owner_login, repo_name, full_name, full_name_lower,
+          first_registered_at, last_requested_at, last_list_requested_at,
+          last_detail_requested_at, api_list_requests, api_detail_requests,
+          page_list_requests, page_detail_requests, created_at, updated_at
+        )
+        VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+        ON CONFLICT(full_name_lower) DO UPDATE SET
+          last_requested_at = excluded.last_requested_at,
+          last_list_requested_at = COALESCE(excluded.last_list_requested_at, public_repo_release_usage.last_list_requested_at),
+          last_detail_requested_at = COALESCE(excluded.last_detail_requested_at, public_repo_release_usage.last_detail_requested_at),
+          api_list_requests = public_repo_release_usage.api_list_requests + excluded.api_list_requests,
+          api_detail_requests = public_repo_release_usage.api_detail_requests + excluded.api_detail_requests,
+          page_list_requests = public_repo_release_usage.page_list_requests + excluded.page_list_requests,
+          page_detail_requests = public_repo_release_usage.page_detail_requests + excluded.page_detail_requests,
+          updated_at = excluded.updated_at
+        "#,
+    )
+    .bind(local_id::generate_local_id())
+    .bind(owner.as_str())
+    .bind(repo.as_str())
+    .bind(full_name.as_str())
+    .bind(full_name_lower.as_str())
+    .bind(now.as_str())
+    .bind(now.as_str())
+    .bind((!detail).then_some(now.as_str()))
+    .bind(detail.then_some(now.as_str()))
+    .bind(api_list_inc)
+    .bind(api_detail_inc)
+    .bind(page_list_inc)
+    .bind(page_detail_inc)
+    .bind(now.as_str())
+    .bind(now.as_str())
+    .execute(&state.pool)
+    .await
+    .map_err(ApiError::internal)?;
+
+    let row = sqlx::query_as::<_, PublicReleaseUsageRow>(
+        r#"
+        SELECT repo_id, full_name, last_requested_at, last_sync_status
+        FROM public_repo_release_usage
+        WHERE full_name_lower = ?
+        LIMIT 1
+        "#,
+    )
+    .bind(full_name_lower.as_str())
+    .fetch_one(&state.pool)
+    .await
+    .map_err(ApiError::internal)?;
+
+    if row.repo_id.is_none()
+        && row.last_sync_status != "inaccessible"
+        && let Some(resolved) =
+            resolve_public_release_usage_from_local_metadata(state, full_name_lower.as_str())
+                .await?
+    {
+        return Ok(resolved);
+    }
+
+    Ok(row)
+}
+
+pub(super) fn split_public_repo_full_name(full_name: &str) -> Option<(&str, &str)> {
+    let (owner, repo) = full_name.split_once('/')?;
+    let owner = owner.trim();
+    let repo = repo.trim();
+    (!owner.is_empty() && !repo.is_empty()).then_some((owner, repo))
+}
+
+pub(super) fn public_local_metadata_is_fresh(updated_at: Option<&str>) -> bool {
+    const PUBLIC_LOCAL_METADATA_MAX_AGE_HOURS: i64 = 24;
+    let Some(updated_at) = updated_at else {
+        return false;
+    };
+    let Ok(updated_at) = chrono::DateTime::parse_from_rfc3339(updated_at) else {
+        return false;
+    };
+    chrono::Utc::now().signed_duration_since(updated_at.with_timezone(&chrono::Utc))
+        <= chrono::Duration::hours(PUBLIC_LOCAL_METADATA_MAX_AGE_HOURS)
+}
+
+pub(super) async fn resolve_public_release_usage_from_local_metadata(
+    state: &AppState,
+    full_name_lower: &str,
+) -> Result<Option<PublicReleaseUsageRow>, ApiError> {
+    let Some(repo) = sqlx::query_as::<_, PublicReleaseLocalRepoRow>(
+        r#"
+        SELECT repo_id, full_name, is_private, updated_at AS metadata_updated_at
+        FROM starred_repos
+        WHERE lower(full_name) = ?
+        ORDER BY updated_at DESC
+        LIMIT 1
+        "#,
+    )
+    .bind(full_name_lower)
+    .fetch_optional(&state.pool)
+    .await
+    .map_err(ApiError::internal)?
+    else {
+        return Ok(None);
+    };
+    if repo.is_private != 0 {
+        return Ok(None);
+    }
+    if !public_local_metadata_is_fresh(repo.metadata_updated_at.as_deref()) {
+        return Ok(None);
+    }
+
+    let release_count: i64 = sqlx::query_scalar(
+        r#"
+        SELECT COUNT(*)
+        FROM repo_releases
+        WHERE repo_id = ?
+          AND is_draft = 0
+        "#,
+    )
+    .bind(repo.repo_id)
+    .fetch_one(&state.pool)
+    .await
+    .map_err(ApiError::internal)?;
+
+    let Some((owner, repo_name)) = split_public_repo_full_name(repo.full_name.as_str()) else {
+        return Ok(None);
+    };
+    let now = chrono::Utc::now().to_rfc3339();
+    let next_status = if release_count > 0 {
+        "ready"
+    } else {
+        "pending"
+    };
+
+    sqlx::query(
+        r#"
+        UPDATE public_repo_release_usage
+        SET repo_id = ?,
+            owner_login = ?,
+            repo_name = ?,
+            full_name = ?,
+            full_name_lower = ?,
+            last_sync_status = ?,
+            last_sync_error = NULL,
+            updated_at = ?
+        WHERE full_name_lower = ?
+        "#,
+    )
+    .bind(repo.repo_id)
+    .bind(owner)
+    .bind(repo_name)
+    .bind(repo.full_name.as_str())
+    .bind(full_name_lower)
+    .bind(next_status)
+    .bind(now.as_str())
+    .bind(full_name_lower)
+    .execute(&state.pool)
+    .await
+    .map_err(ApiError::internal)?;
+
+    if release_count == 0
+        && sync::enqueue_public_repo_release_sync(state, repo.repo_id, repo.full_name.as_str())
+            .await
+            .map_err(ApiError::internal)?
+    {
+        sqlx::query(
+            r#"
+            UPDATE public_repo_release_usage
+            SET last_sync_status = 'ready',
+                last_sync_error = NULL,
+                updated_at = ?
+            WHERE full_name_lower = ?
+            "#,
+        )
+        .bind(now.as_str())
+        .bind(full_name_lower)
+        .execute(&state.pool)
+        .await
+        .map_err(ApiError::internal)?;
+    }
+
+    sqlx::query_as::<_, PublicReleaseUsageRow>(
+        r#"
+        SELECT repo_id, full_name, last_requested_at, last_sync_status
+        FROM public_repo_release_usage
+        WHERE full_name_lower = ?
+        LIMIT 1
+        "#,
+    )
+    .bind(full_name_lower)
+    .fetch_one(&state.pool)
+    .await
+    .map(Some)
+    .map_err(ApiError::internal)
+}
+
+pub(super) fn public_pending_response(row: &PublicReleaseUsageRow) -> Response {
+    let retry_after_seconds = 60_i64;
+    let body = Json(PublicReleasePendingResponse {
+        status: "pending_sync",
+        message: "Release data is being prepared. Retry after the suggested delay.",
+        reason: if row.repo_id.is_none() {
+            "repository_registered_metadata_pending"
+        } else {
+            "repository_registered_release_sync_pending"
+        },
+        retry_after_seconds,
+        repo_full_name: row.full_name.clone(),
+        last_requested_at: row.last_requested_at.clone(),
+    });
+    let mut response = (StatusCode::ACCEPTED, body).into_response();
+    response
+        .headers_mut()
+        .insert(header::RETRY_AFTER, HeaderValue::from_static("60"));
+    response
+}
+
+pub(super) fn ensure_public_release_usage_accessible(
+    row: &PublicReleaseUsageRow,
+) -> Result<(), ApiError> {
+    if row.last_sync_status == "inaccessible" {
+        return Err(ApiError::new(
+            StatusCode::NOT_FOUND,
+            codes::REPOSITORY_INACCESSIBLE,
+            "repository is not publicly accessible",
+        ));
+    }
+    Ok(())
+}
+
+pub(super) fn public_release_usage_should_retry_without_rows(row: &PublicReleaseUsageRow) -> bool {
+    row.last_sync_status == "pending" || row.last_sync_status == "failed"
+}
+
+pub(super) fn public_translation_item(
+    row: &PublicReleaseRow,
+    content: &str,
+) -> Option<TranslatedItem> {
+    if content == "original" || content == "polished" {
+        return None;
+    }
+    let original_title = row
+        .name
+        .as_deref()
+        .map(str::trim)
+        .filter(|value| !value.is_empty())
+        .unwrap_or(&row.tag_name)
+        .to_owned();
+    let original_body = row.body.clone().unwrap_or_default();
+    let source_hash =
+        release_detail_source_hash(&row.repo_full_name, &original_title, &original_body);
+    if row.trans_source_hash.as_deref() != Some(source_hash.as_str()) {
+        return Some(translated_missing_item(true));
+    }
+    match row.trans_status.as_deref() {
+        Some("ready") => {
+            translated_ready_item(row.trans_title.clone(), row.trans_summary.clone(), None)
+                .or_else(|| Some(translated_missing_item(true)))
+        }
+        Some(status) => translated_terminal_item(status, row.trans_error_text.as_deref()),
+        None => Some(translated_missing_item(true)),
+    }
+}
+
+pub(super) fn public_smart_item(row: &PublicReleaseRow, content: &str) -> Option<SmartItem> {
+    if content == "original" || content == "translated" {
+        return None;
+    }
+    let original_title = row
+        .name
+        .as_deref()
+        .map(str::trim)
+        .filter(|value| !value.is_empty())
+        .unwrap_or(&row.tag_name)
+        .to_owned();
+    let smart_body = release_feed_body(row.body.as_deref());
+    let source_hash = crate::translations::release_smart_feed_source_hash(
+        row.release_id.to_string().as_str(),
+        &row.repo_full_name,
+        &original_title,
+        smart_body.as_deref(),
+        &row.tag_name,
+        row.previous_tag_name.as_deref(),
+    );
+    if row.smart_source_hash.as_deref() != Some(source_hash.as_str()) {
+        return Some(smart_missing_item(None));
+    }
+    match row.smart_status.as_deref() {
+        Some("ready") => smart_ready_item(row.smart_title.clone(), row.smart_summary.clone(), None)
+            .or_else(|| Some(smart_missing_item(None))),
+        Some(status) => smart_terminal_item(status, row.smart_error_text.as_deref()),
+        None => Some(smart_missing_item(None)),
+    }
+}
+
+pub(super) fn public_list_item_from_row(
+    row: PublicReleaseRow,
+    content: &str,
+) -> PublicReleaseListItem {
+    let translated = public_translation_item(&row, content);
+    let smart = public_smart_item(&row, content);
+    let repo_visual = repo_visual_from_parts(
+        row.owner_avatar_url.clone(),
+        row.open_graph_image_url.clone(),
+        row.uses_custom_open_graph_image.unwrap_or(0) != 0,
+    );
+    PublicReleaseListItem {
+        release_id: row.release_id.to_string(),
+        repo_full_name: row.repo_full_name,
+        repo_visual,
+        tag_name: row.tag_name,
+        previous_tag_name: row.previous_tag_name,
+        name: row.name,
+        body: if content == "translated" || content == "polished" {
+            None
+        } else {
+            row.body
+        },
+        html_url: row.html_url,
+        published_at: row.published_at,
+        is_prerelease: row.is_prerelease,
+        is_draft: row.is_draft,
+        translated,
+        smart,
+    }
+}
+
+pub(super) async fn load_public_release_rows(
+    state: &AppState,
+    repo_id: i64,
+    tag: Option<&str>,
+    cursor: Option<&PublicReleaseCursor>,
+    limit: i64,
+) -> Result<Vec<PublicReleaseRow>, ApiError> {
+    let mut query = sqlx::QueryBuilder::<sqlx::Sqlite>::new(
+        r#"
+        SELECT
+          r.release_id,
+          COALESCE(r.published_at, r.created_at, r.updated_at, '') AS sort_ts,
+          COALESCE(pu.full_name, printf('unknown/%d', r.repo_id)) AS repo_full_name,
+          COALESCE(sr.owner_avatar_url, ob.owner_avatar_url) AS owner_avatar_url,
+          COALESCE(sr.open_graph_image_url, ob.open_graph_image_url) AS open_graph_image_url,
+          COALESCE(sr.uses_custom_open_graph_image, ob.uses_custom_open_graph_image, 0) AS uses_custom_open_graph_image,
+          r.tag_name,
+          r.name,
+          r.body,
+          r.html_url,
+          r.published_at,
+          r.is_prerelease,
+          r.is_draft,
+          rp.previous_tag_name,
+          t.source_hash AS trans_source_hash,
+          t.status AS trans_status,
+          t.title AS trans_title,
+          t.summary AS trans_summary,
+          t.error_text AS trans_error_text,
+          s.source_hash AS smart_source_hash,
+          s.status AS smart_status,
+          s.title AS smart_title,
+          s.summary AS smart_summary,
+          s.error_text AS smart_error_text
+        FROM repo_releases r
+        JOIN public_repo_release_usage pu
+          ON pu.repo_id = r.repo_id
+        LEFT JOIN (
+          SELECT
+            repo_id,
+            MAX(owner_avatar_url) AS owner_avatar_url,
+            MAX(open_graph_image_url) AS open_graph_image_url,
+            MAX(uses_custom_open_graph_image) AS uses_custom_open_graph_image
+          FROM starred_repos
+          WHERE is_private = 0
+          GROUP BY repo_id
+        ) sr
+          ON sr.repo_id = r.repo_id
+        LEFT JOIN (
+          SELECT
+            repo_id,
+            MAX(owner_avatar_url) AS owner_avatar_url,
+            MAX(open_graph_image_url) AS open_graph_image_url,
+            MAX(uses_custom_open_graph_image) AS uses_custom_open_graph_image
+          FROM owned_repo_star_baselines
+          GROUP BY repo_id
+        ) ob
+          ON ob.repo_id = r.repo_id
+        LEFT JOIN (
+          SELECT
+            release_id,
+            LAG(tag_name) OVER (
+              PARTITION BY repo_id
+              ORDER BY COALESCE(published_at, created_at, updated_at) ASC, release_id ASC
+            ) AS previous_tag_name
+          FROM repo_releases
+        ) rp
+          ON rp.release_id = r.release_id
+        LEFT JOIN ai_translations t
+          ON t.id = (
+            SELECT t2.id
+            FROM ai_translations t2
+            WHERE t2.entity_type = 'release_detail'
+              AND t2.entity_id = CAST(r.release_id AS TEXT)
+              AND t2.lang = 'zh-CN'
+              AND t2.status IN ('ready', 'disabled', 'missing', 'error')
+            ORDER BY CASE WHEN t2.status = 'ready' THEN 0 ELSE 1 END, t2.updated_at DESC
+            LIMIT 1
+          )
+        LEFT JOIN ai_translations s
+          ON s.id = (
+            SELECT s2.id
+            FROM ai_translations s2
+            WHERE s2.entity_type = 'release_smart'
+              AND s2.entity_id = CAST(r.release_id AS TEXT)
+              AND s2.lang = 'zh-CN'
+              AND s2.status IN ('ready', 'disabled', 'missing', 'error')
+            ORDER BY CASE WHEN s2.status = 'ready' THEN 0 ELSE 1 END, s2.updated_at DESC
+            LIMIT 1
+          )
+        WHERE r.repo_id =
+        "#,
+    );
+    query.push_bind(repo_id);
+    query.push(" AND r.is_draft = 0");
+    if let Some(tag) = tag {
+        query.push(" AND r.tag_name = ");
+        query.push_bind(tag);
+    }
+    if let Some(cursor) = cursor {
+        query.push(" AND (COALESCE(r.published_at, r.created_at, r.updated_at, '') < ");
+        query.push_bind(cursor.sort_ts.as_str());
+        query.push(" OR (COALESCE(r.published_at, r.created_at, r.updated_at, '') = ");
+        query.push_bind(cursor.sort_ts.as_str());
+        query.push(" AND r.release_id < ");
+        query.push_bind(cursor.release_id);
+        query.push("))");
+    }
+    query.push(
+        " ORDER BY COALESCE(r.published_at, r.created_at, r.updated_at, '') DESC, r.release_id DESC LIMIT ",
+    );
+    query.push_bind(limit);
+    query
+        .build_query_as()
+        .fetch_all(&state.pool)
+        .await
+        .map_err(ApiError::internal)
+}
+
+pub async fn public_list_repo_releases(
+    State(state): State<Arc<AppState>>,
+    Path((owner, repo)): Path<(String, String)>,
+    Query(query): Query<PublicReleaseQuery>,
+) -> Result<Response, ApiError> {
+    validate_public_release_query(&query)?;
+    let usage = upsert_public_release_usage(
+        state.as_ref(),
+        owner,
+        repo,
+        false,
+        public_source_is_page(&query),
+    )
+    .await?;
+    ensure_public_release_usage_accessible(&usage)?;
+    let Some(repo_id) = usage.repo_id else {
+        return Ok(public_pending_response(&usage));
+    };
+    let limit = query.limit.unwrap_or(6).clamp(1, 30);
+    let cursor = query
+        .cursor
+        .as_deref()
+        .map(str::trim)
+        .filter(|value| !value.is_empty())
+        .map(parse_public_release_cursor)
+        .transpose()?;
+    let mut rows = load_public_release_rows(
+        state.as_ref(),
+        repo_id,
+        None,
+        cursor.as_ref(),
+        limit.saturating_add(1),
+    )
+    .await?;
+    if rows.is_empty() && public_release_usage_should_retry_without_rows(&usage) {
+        return Ok(public_pending_response(&usage));
+    }
+    let content = query.content.as_deref().unwrap_or("all");
+    let next_cursor = if rows.len() > limit as usize {
+        let next = rows
+            .get(limit.saturating_sub(1) as usize)
+            .map(public_release_cursor);
+        rows.truncate(limit as usize);
+        next
+    } else {
+        None
+    };
+    Ok(Json(PublicReleaseListResponse {
+        status: "ready".to_owned(),
+        repo_full_name: usage.full_name,
+        next_cursor,
+        items: rows
+            .into_iter()
+            .map(|row| public_list_item_from_row(row, content))
+            .collect(),
+    })
+    .into_response())
+}
+
+pub async fn public_get_repo_release_detail(
+    State(state): State<Arc<AppState>>,
+    Path((owner, repo, tag)): Path<(String, String, String)>,
+    Query(query): Query<PublicReleaseQuery>,
+) -> Result<Response, ApiError> {
+    validate_public_release_query(&query)?;
+    let usage = upsert_public_release_usage(
+        state.as_ref(),
+        owner,
+        repo,
+        true,
+        public_source_is_page(&query),
+    )
+    .await?;
+    ensure_public_release_usage_accessible(&usage)?;
+    let Some(repo_id) = usage.repo_id else {
+        return Ok(public_pending_response(&usage));
+    };
+    let mut rows =
+        load_public_release_rows(state.as_ref(), repo_id, Some(tag.as_str()), None, 1).await?;
+    let Some(row) = rows.pop() else {
+        if public_release_usage_should_retry_without_rows(&usage) {
+            return Ok(public_pending_response(&usage));
+        }
+        return Err(ApiError::new(
+            StatusCode::NOT_FOUND,
+            codes::RELEASE_NOT_FOUND_OR_NOT_CACHED,
+            "release not found or not cached",
+        ));
+    };
+    let content = query.content.as_deref().unwrap_or("all");
+    let translated = public_translation_item(&row, content);
+    let smart = public_smart_item(&row, content);
+    let repo_visual = repo_visual_from_parts(
+        row.owner_avatar_url.clone(),
+        row.open_graph_image_url.clone(),
+        row.uses_custom_open_graph_image.unwrap_or(0) != 0,
+    );
+    let repo_owner_login = row
+        .repo_full_name
+        .split_once('/')
+        .map(|(owner, _)| owner.to_owned());
+    // Anonymous readers have no token to fetch missing assets with.
+    let assets = load_stored_release_assets(state.as_ref(), row.release_id).await?;
+    Ok(Json(ReleaseDetailResponse {
+        release_id: row.release_id.to_string(),
+        repo_owner_login,
+        repo_owner_avatar_url: normalize_visual_url(row.owner_avatar_url.clone()),
+        repo_description: None,
+        repo_html_url: Some(format!("https://github.com/{}", row.repo_full_name)),
+        repo_full_name: Some(row.repo_full_name),
+        repo_visual,
+        tag_name: row.tag_name,
+        previous_tag_name: row.previous_tag_name,
+        name: row.name,
+        body: if content == "translated" || content == "polished" {
+            None
+        } else {
+            row.body
+        },
+        html_url: row.html_url,
+        published_at: row.published_at,
+        is_prerelease: row.is_prerelease,
+        is_draft: row.is_draft,
+        translated,
+        smart,
+        assets,
+        assets_warning: None,
+    })
+    .into_response())
+}
+
+#[derive(Debug, Deserialize)]
+pub struct AdminPublicReposQuery {
+    pub(super) page: Option<i64>,
+    pub(super) page_size: Option<i64>,
+    pub(super) query: Option<String>,
+}
+
+#[derive(Debug, Serialize, sqlx::FromRow)]
+pub struct AdminPublicRepoItem {
+    pub(super) id: String,
+    pub(super) repo_id: Option<i64>,
+    pub(super) full_name: String,
+    pub(super) first_registered_at: String,
+    pub(super) last_requested_at: String,
+    pub(super) last_list_requested_at: Option<String>,
+    pub(super) last_detail_requested_at: Option<String>,
+    pub(super) api_list_requests: i64,
+    pub(super) api_detail_requests: i64,
+    pub(super) page_list_requests: i64,
+    pub(super) page_detail_requests: i64,
+    pub(super) last_sync_status: String,
+    pub(super) last_sync_error: Option<String>,
+    pub(super) release_count: i64,
+    pub(super) translated_ready_count: i64,
+    pub(super) translated_missing_count: i64,
+    pub(super) polished_ready_count: i64,
+    pub(super) polished_missing_count: i64,
+    pub(super) created_at: String,
+    pub(super) updated_at: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct AdminPublicReposResponse {
+    pub(super) items: Vec<AdminPublicRepoItem>,
+    pub(super) page: i64,
+    pub(super) page_size: i64,
+    pub(super) total: i64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(super) cache_cleanup: Option<AdminPublicRepoCacheCleanup>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct AdminPublicRepoCacheCleanup {
+    pub(super) repo_id: Option<i64>,
+    pub(super) full_name: String,
+    pub(crate) deleted_release_count: i64,
+    pub(super) deleted_ai_cache_count: u64,
+    pub(super) skipped_reason: Option<String>,
+}
+
+pub async fn admin_list_public_release_repos(
+    State(state): State<Arc<AppState>>,
+    Query(query): Query<AdminPublicReposQuery>,
+    _: AdminUser,
+) -> Result<Json<AdminPublicReposResponse>, ApiError> {
+    let page = query.page.unwrap_or(1);
+    if page < 1 {
+        return Err(ApiError::bad_request("page must be >= 1"));
+    }
+    let page_size = query.page_size.unwrap_or(20).clamp(1, 100);
+    let offset = admin_users_offset(page, page_size)?;
+    let query_text = query.query.unwrap_or_default().trim().to_ascii_lowercase();
+    let query_like = format!("%{query_text}%");
+
+    let total = sqlx::query_scalar::<_, i64>(
+        r#"
+        SELECT COUNT(*)
+        FROM public_repo_release_usage
+        WHERE ? = '' OR full_name_lower LIKE ?
+        "#,
+    )
+    .bind(query_text.as_str())
+    .bind(query_like.as_str())
+    .fetch_one(&state.pool)
+    .await
+    .map_err(ApiError::internal)?;
+
+    let items = sqlx::query_as::<_, AdminPublicRepoItem>(
+        r#"
+        SELECT
+          pu.id,
+          pu.repo_id,
+          pu.full_name,
+          pu.first_registered_at,
+          pu.last_requested_at,
+          pu.last_list_requested_at,
+          pu.last_detail_requested_at,
+          pu.api_list_requests,
+          pu.api_detail_requests,
+          pu.page_list_requests,
+          pu.page_detail_requests,
+          pu.last_sync_status,
+          pu.last_sync_error,
+          COUNT(DISTINCT r.release_id) AS release_count,
+          COUNT(DISTINCT CASE WHEN td.status = 'ready' THEN r.release_id END) AS translated_ready_count,
+          COUNT(DISTINCT CASE WHEN COALESCE(td.status, 'missing') != 'ready' THEN r.release_id END) AS translated_missing_count,
+          COUNT(DISTINCT CASE WHEN sm.status = 'ready' THEN r.release_id END) AS polished_ready_count,
+          COUNT(DISTINCT CASE WHEN COALESCE(sm.status, 'missing') != 'ready' THEN r.release_id END) AS polished_missing_count,
+          pu.created_at,
+          pu.updated_at
+        FROM public_repo_release_usage pu
+        LEFT JOIN repo_releases r
+          ON r.repo_id = pu.repo_id
+        LEFT JOIN ai_translations td
+          ON td.id = (
+            SELECT td2.id
+            FROM ai_translations td2
+            WHERE td2.entity_type = 'release_detail'
+              AND td2.entity_id = CAST(r.release_id AS TEXT)
+              AND td2.lang = 'zh-CN'
+            ORDER BY CASE WHEN td2.status = 'ready' THEN 0 ELSE 1 END, td2.updated_at DESC
+            LIMIT 1
+          )
+        LEFT JOIN ai_translations sm
+          ON sm.id = (
+            SELECT sm2.id
+            FROM ai_translations sm2
+            WHERE sm2.entity_type = 'release_smart'
+              AND sm2.entity_id = CAST(r.release_id AS TEXT)
+              AND sm2.lang = 'zh-CN'
+            ORDER BY CASE WHEN sm2.status = 'ready' THEN 0 ELSE 1 END, sm2.updated_at DESC
+            LIMIT 1
+          )
+        WHERE ? = '' OR pu.full_name_lower LIKE ?
+        GROUP BY pu.id
+        ORDER BY pu.last_requested_at DESC, pu.full_name ASC
+        LIMIT ? OFFSET ?
+        "#,
+    )
+    .bind(query_text.as_str())
+    .bind(query_like.as_str())
+    .bind(page_size)
+    .bind(offset)
+    .fetch_all(&state.pool)
+    .await
+    .map_err(ApiError::internal)?;
+
+    Ok(Json(AdminPublicReposResponse {
+        items,
+        page,
+        page_size,
+        total,
+        cache_cleanup: None,
+    }))
+}
+
+pub async fn admin_delete_public_release_repo(
+    State(state): State<Arc<AppState>>,
+    AdminUser(acting_user_id): AdminUser,
+    Path(usage_id): Path<String>,
+) -> Result<Json<AdminPublicReposResponse>, ApiError> {
+    let usage_id = parse_local_id_param(usage_id, "public_repo_usage_id")?;
+
+    let deleted_usage = sqlx::query_as::<_, (Option<i64>, String)>(
+        r#"
+        SELECT repo_id, full_name
+        FROM public_repo_release_usage
+        WHERE id = ?
+        "#,
+    )
+    .bind(usage_id.as_str())
+    .fetch_optional(&state.pool)
+    .await
+    .map_err(ApiError::internal)?;
+
+    let Some((repo_id, full_name)) = deleted_usage else {
+        return Err(ApiError::new(
+            StatusCode::NOT_FOUND,
+            codes::NOT_FOUND,
+            "public repo usage not found",
+        ));
+    };
+
+    let deleted = sqlx::query(
+        r#"
+        DELETE FROM public_repo_release_usage
+        WHERE id = ?
+        "#,
+    )
+    .bind(usage_id.as_str())
+    .execute(&state.pool)
+    .await
+    .map_err(ApiError::internal)?
+    .rows_affected();
+    if deleted == 0 {
+        return Err(ApiError::new(
+            StatusCode::NOT_FOUND,
+            codes::NOT_FOUND,
+            "public repo usage not found",
+        ));
+    }
+
+    let cache_cleanup =
+        cleanup_public_release_repo_cache_if_unused(state.as_ref(), repo_id, full_name).await?;
+
+    let Json(mut response) = admin_list_public_release_repos(
+        State(state),
+        Query(AdminPublicReposQuery {
+            page: Some(1),
+            page_size: Some(20),
+            query: None,
+        }),
+        AdminUser(acting_user_id),
+    )
+    .await?;
+    response.cache_cleanup = Some(cache_cleanup);
+    Ok(Json(response))
+}
+
+pub(crate) async fn cleanup_public_release_repo_cache_if_unused(
+    state: &AppState,
+    repo_id: Option<i64>,
+    full_name: String,
+) -> Result<AdminPublicRepoCacheCleanup, ApiError> {
+    let Some(repo_id) = repo_id else {
+        return Ok(AdminPublicRepoCacheCleanup {
+            repo_id,
+            full_name,
+            deleted_release_count: 0,
+            deleted_ai_cache_count: 0,
+            skipped_reason: Some("repo_not_resolved".to_owned()),
+        });
+    };
+
+    let remaining_public_usage = sqlx::query_scalar::<_, i64>(
+        r#"
+        SELECT COUNT(*)
+        FROM public_repo_release_usage
+        WHERE repo_id = ?
+        "#,
+    )
+    .bind(repo_id)
+    .fetch_one(&state.pool)
+    .await
+    .map_err(ApiError::internal)?;
+    if remaining_public_usage > 0 {
+        return Ok(AdminPublicRepoCacheCleanup {
+            repo_id: Some(repo_id),
+            full_name,
+            deleted_release_count: 0,
+            deleted_ai_cache_count: 0,
+            skipped_reason: Some("still_used_by_public_endpoint".to_owned()),
+        });
+    }
+
+    let user_visible_usage = sqlx::query_scalar::<_, i64>(
+        r#"
+        SELECT COUNT(*)
+        FROM user_release_visible_repos
+        WHERE repo_id = ?
+        "#,
+    )
+    .bind(repo_id)
+    .fetch_one(&state.pool)
+    .await
+    .map_err(ApiError::internal)?;
+    if user_visible_usage > 0 {
+        return Ok(AdminPublicRepoCacheCleanup {
+            repo_id: Some(repo_id),
+            full_name,
+            deleted_release_count: 0,
+            deleted_ai_cache_count: 0,
+            skipped_reason: Some("still_used_by_user_release_visibility".to_owned()),
+        });
+    }
+
+    let brief_usage = sqlx::query_scalar::<_, i64>(
+        r#"
+        SELECT COUNT(*)
+        FROM brief_release_memberships m
+        JOIN repo_releases r ON r.release_id = m.release_id
+        WHERE r.repo_id = ?
+        "#,
+    )
+    .bind(repo_id)
+    .fetch_one(&state.pool)
+    .await
+    .map_err(ApiError::internal)?;
+    if brief_usage > 0 {
+        return Ok(AdminPublicRepoCacheCleanup {
+            repo_id: Some(repo_id),
+            full_name,
+            deleted_release_count: 0,
+            deleted_ai_cache_count: 0,
+            skipped_reason: Some("still_used_by_brief_membership".to_owned()),
+        });
+    }
+
+    let release_ids = sqlx::query_scalar::<_, i64>(
+        r#"
+        SELECT release_id
+        FROM repo_releases
+        WHERE repo_id = ?
+        "#,
+    )
+    .bind(repo_id)
+    .fetch_all(&state.pool)
+    .await
+    .map_err(ApiError::internal)?;
+
+    let mut tx = state.pool.begin().await.map_err(ApiError::internal)?;
+    let mut deleted_ai_cache_count = 0u64;
+    let mut deleted_release_count = 0i64;
+    for release_id in release_ids {
+        let release_id_text = release_id.to_string();
+        deleted_ai_cache_count += sqlx::query(
+            r#"
+            DELETE FROM ai_translations
+            WHERE entity_type IN ('release_detail', 'release_smart')
+              AND entity_id = ?
+            "#,
+        )
+        .bind(release_id_text.as_str())
+        .execute(&mut *tx)
+        .await
+        .map_err(ApiError::internal)?
+        .rows_affected();
+        sqlx::query(
+            r#"
+            DELETE FROM release_assets
+            WHERE release_id = ?
+            "#,
+        )
+        .bind(release_id)
+        .execute(&mut *tx)
+        .await
+        .map_err(ApiError::internal)?;
+        deleted_release_count += sqlx::query(
+            r#"
+            DELETE FROM repo_releases
+            WHERE release_id = ?
+            "#,
+        )
+        .bind(release_id)
+        .execute(&mut *tx)
+        .await
+        .map_err(ApiError::internal)?
+        .rows_affected() as i64;
+    }
+    sqlx::query(
+        r#"
+        DELETE FROM repo_release_work_items
+        WHERE repo_id = ?
+        "#,
+    )
+    .bind(repo_id)
+    .execute(&mut *tx)
+    .await
+    .map_err(ApiError::internal)?;
+    sqlx::query(
+        r#"
+        DELETE FROM repo_release_sync_state
+        WHERE repo_id = ?
+        "#,
+    )
+    .bind(repo_id)
+    .execute(&mut *tx)
+    .await
+    .map_err(ApiError::internal)?;
+    tx.commit().await.map_err(ApiError::internal)?;
+
+    Ok(AdminPublicRepoCacheCleanup {
+        repo_id: Some(repo_id),
+        full_name,
+        deleted_release_count,
+        deleted_ai_cache_count,
+        skipped_reason: None,
+    })
+}
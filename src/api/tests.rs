@@ -0,0 +1,16746 @@
+use std::collections::BTreeMap;
+
+use chrono::{Datelike, TimeZone};
+
+use super::{
+    ACCESS_SYNC_REASON_INACTIVE_OVER_1H, ADMIN_DASHBOARD_PREAGGREGATE_DAYS,
+    ADMIN_SYNC_SUBSCRIPTION_EVENT_LIMIT, ADMIN_TASK_DETAIL_EVENT_LIMIT,
+    AdminBriefFeedbackDistribution, AdminBriefFeedbackQuery, AdminDashboardQuery,
+    AdminLlmCallListScope, AdminLlmCallsExportQuery, AdminLlmCallsQuery,
+    AdminLlmRuntimeConfigUpdateRequest, AdminLlmUsageSummaryQuery, AdminRealtimeTaskDetailItem,
+    AdminRealtimeTasksQuery, AdminRepoGovernanceListQuery, AdminSyncSubscriptionEventItem,
+    AdminTaskEventItem, AdminTranslationCachePurgeQuery, AdminUser, AdminUserBulkRequest,
+    AdminUserPatchRequest, AdminUserTaskRequest, AdminUserUpdateGuard, AdminUsersQuery, AuthedUser,
+    BRIEF_RELEASE_REF_LOCATOR_BATCH_LIMIT, BookmarksQuery, BriefFeedbackRequest,
+    BriefGenerateRequest, BriefGenerateTarget, CreateBookmarkRequest, DashboardUpdatesQuery,
+    DashboardUpdatesToken, FEED_EXCERPT_MAX_CHARS, FEED_EXCERPT_MAX_LINES, FEED_EXCERPT_MIN_CHARS,
+    FeedCursor, FeedExcerpt, FeedQuery, FeedReactionRefreshQuery, FeedReactionRefreshRequest,
+    FeedReactionRefreshResponse, FeedRow, FeedWarning, GitHubCompareCommit,
+    GitHubCompareCommitDetail, GitHubCompareFile, GitHubCompareResponse, GraphQlError,
+    LLM_CALL_ORDER_BY_CREATED_DESC, LiveReleaseReactions, MarkFeedSeenRequest,
+    MarkdownStructureCheck, NotificationCounts, NotificationListItems, NotificationListQuery,
+    PublicReleaseQuery, RELEASE_EXCERPT_TABLE_MAX_ROWS, RELEASE_FEED_BODY_MAX_CHARS, ReactionToken,
+    ReactionTokenDiagnoseRequest, ReactionTokenDiagnoseResponse, ReactionTokenSelection,
+    ReleaseDetailTranslationMode, ReleaseDiffQuery, ReleaseReactionCounts, ReleaseReactionRow,
+    ReleaseReactionViewer, RenderedReleaseQuery, RepoPreferencesRequest, RepoReleasesQuery,
+    ReturnModeQuery, SMART_NO_VALUABLE_VERSION_INFO, StarredListResponse, StarredQuery,
+    SyncReleasesQuery, ToggleReleaseReactionRequest, TranslateBatchItem,
+    TranslationCachePurgeQuery, TranslationCacheRow, TranslationUpsert, admin_bulk_update_users,
+    admin_dashboard, admin_delete_public_release_repo, admin_delete_translations,
+    admin_download_realtime_task_log, admin_enqueue_user_task, admin_export_llm_calls,
+    admin_get_brief_feedback, admin_get_llm_call_detail, admin_get_llm_scheduler_status,
+    admin_get_llm_usage_summary, admin_get_realtime_task_detail, admin_get_user_stats,
+    admin_list_llm_calls, admin_list_realtime_tasks, admin_list_repo_governance, admin_list_users,
+    admin_patch_llm_runtime_config, admin_patch_user, admin_retry_realtime_task,
+    admin_users_offset, ai_call_api_error, ai_error_is_non_retryable, append_batch_item_event,
+    archive_notification, attach_translate_releases_batch_stream, brief_contains_release_link,
+    brief_feedback_user_ref, brief_translation_source_hash, build_compare_digest,
+    build_feed_reaction_refresh_item, build_task_diagnostics, compact_dashboard_signatures,
+    create_bookmark, dashboard_updates, delete_bookmark, delete_translations,
+    diagnose_reaction_token, diff_release_sections, encode_dashboard_updates_token,
+    ensure_account_enabled, execute_sync_all_sync_with, extract_brief_release_ids,
+    extract_partial_release_batch_summaries, extract_translation_fields,
+    extract_translation_link_refs, feed_item_from_row, feed_kind_rank, get_brief_sources,
+    get_counters, get_release_detail, get_release_detail_by_repo_tag, get_release_diff_prev,
+    get_rendered_release, get_repo_unseen_release_count, github_access_restricted_error,
+    github_graphql_errors_to_api_error, github_graphql_http_error, github_rate_limited_error,
+    github_reauth_required_error, graphql_node_id_chunks, guard_admin_user_update, has_repo_scope,
+    last_active_is_stale, list_bookmarks, list_briefs, list_feed, list_feed_page,
+    list_notifications, list_releases, list_repo_releases, list_starred, llm_call_order_by_clause,
+    load_admin_dashboard_today_live_snapshot, load_cached_release_reactions,
+    load_notification_detail, load_pending_access_sync_reason, looks_like_json_blob,
+    map_public_compare_fallback_error, mark_feed_seen, mark_feed_seen_at,
+    mark_translation_requested, markdown_structure_preserved, me, me_delete_github_connection,
+    me_delete_passkey, mute_repo, normalize_markdown_translation_output,
+    normalize_translation_fields, notification_translation_source_hash,
+    parse_batch_notification_translation_payload, parse_batch_release_detail_translation_payload,
+    parse_batch_release_translation_payload, parse_feed_types, parse_llm_models,
+    parse_positive_admin_concurrency, parse_public_release_cursor, parse_release_id_param,
+    parse_release_smart_summary_payload, parse_repo_full_name_from_release_url,
+    parse_translation_json, parse_unique_release_ids, parse_unique_thread_ids,
+    patch_repo_preferences, persist_release_reactions_batch_best_effort, prepare_release_batch,
+    preserve_chunk_edge_newlines, previous_release_feed_translation_source_hash,
+    public_get_repo_release_detail, public_list_repo_releases, reaction_token_status,
+    refresh_admin_dashboard_rollups, refresh_feed_reactions, register_batch_stream_waker,
+    release_cache_entry_reusable, release_detail_source_hash, release_detail_translation_ready,
+    release_feed_body, release_feed_body_is_over_limit, release_feed_translation_source_hash,
+    release_reactions_status, repair_release_translations, require_active_user_id,
+    resolve_brief_generate_target, resolve_release_full_name, run_translation_batch_groups,
+    select_reaction_token, should_retry_public_compare_without_auth, smart_error_is_retryable,
+    split_markdown_chunks, submit_brief_feedback, sync_all, sync_notifications, sync_releases,
+    sync_starred, toggle_release_reaction, translate_brief_for_user,
+    translate_notifications_batch_internal, translate_release, translate_release_detail_for_user,
+    translate_releases_batch_for_user, translate_releases_batch_stream_worker,
+    translate_response_from_batch_item, translation_links_preserved, unmute_repo,
+    upsert_translation, validate_pat_token_shape,
+};
+use crate::ai;
+use crate::error::{ApiError, ApiJson};
+use proptest::{prop_assert_eq, proptest, sample::select};
+use std::{
+    fs,
+    net::SocketAddr,
+    sync::{
+        Arc,
+        atomic::{AtomicBool, AtomicUsize, Ordering},
+    },
+};
+
+use crate::{
+    admin_audit,
+    config::{AiConfig, AppConfig, GitHubOAuthConfig},
+    crypto::EncryptionKey,
+    jobs, observability,
+    release_links::ReleaseLocator,
+    state::{AppState, build_oauth_client},
+    sync, translation_retry,
+};
+use axum::{
+    Json, Router,
+    body::to_bytes,
+    extract::{Path, Query, State},
+    http::{StatusCode, header},
+    response::{IntoResponse, Response},
+    routing::{get, post},
+};
+use reqwest::header::{HeaderMap, HeaderValue};
+use serde_json::{Value, json};
+use sqlx::{
+    Row, SqlitePool,
+    sqlite::{SqliteConnectOptions, SqlitePoolOptions},
+};
+use tower_sessions::{MemoryStore, Session};
+use url::Url;
+
+fn test_user_id(id: i64) -> String {
+    crate::local_id::test_local_id(&format!("user-{id}"))
+}
+
+fn test_feed_row(node_id: Option<&str>) -> FeedRow {
+    FeedRow {
+        kind: "release".to_owned(),
+        sort_ts: "2026-01-01T00:00:00Z".to_owned(),
+        ts: "2026-01-01T00:00:00Z".to_owned(),
+        id_key: "1".to_owned(),
+        entity_id: "1".to_owned(),
+        release_id: Some(1),
+        release_node_id: node_id.map(str::to_owned),
+        repo_full_name: None,
+        repo_owner_login: None,
+        owner_avatar_url: None,
+        repo_description: None,
+        repo_html_url: None,
+        open_graph_image_url: None,
+        uses_custom_open_graph_image: None,
+        release_tag_name: None,
+        release_previous_tag_name: None,
+        title: None,
+        subtitle: None,
+        reason: None,
+        subject_type: None,
+        html_url: None,
+        unread: None,
+        actor_login: None,
+        actor_avatar_url: None,
+        actor_html_url: None,
+        release_body: None,
+        react_plus1: None,
+        react_laugh: None,
+        react_heart: None,
+        react_hooray: None,
+        react_rocket: None,
+        react_eyes: None,
+        repo_source: None,
+        trans_source_hash: None,
+        trans_status: None,
+        trans_title: None,
+        trans_summary: None,
+        trans_error_text: None,
+        trans_work_status: None,
+        detail_trans_source_hash: None,
+        detail_trans_status: None,
+        detail_trans_title: None,
+        detail_trans_summary: None,
+        detail_trans_error_text: None,
+        detail_trans_work_status: None,
+        smart_source_hash: None,
+        smart_status: None,
+        smart_title: None,
+        smart_summary: None,
+        smart_error_text: None,
+        smart_work_status: None,
+        bookmark_id: None,
+    }
+}
+
+#[test]
+fn parse_positive_admin_concurrency_rejects_values_above_max_permits() {
+    let overflow =
+        i64::try_from(tokio::sync::Semaphore::MAX_PERMITS).expect("max permits fits in i64") + 1;
+    let err = parse_positive_admin_concurrency(overflow, "max_concurrency")
+        .expect_err("overflow concurrency should fail");
+
+    assert!(
+        err.to_string().contains(&format!(
+            "max_concurrency must be a positive integer <= {}",
+            tokio::sync::Semaphore::MAX_PERMITS
+        )),
+        "unexpected error: {err}"
+    );
+}
+
+fn test_task_detail_item(
+    task_type: &str,
+    status: &str,
+    payload_json: &str,
+    result_json: Option<&str>,
+    error_message: Option<&str>,
+) -> AdminRealtimeTaskDetailItem {
+    AdminRealtimeTaskDetailItem {
+        id: "task-test".to_owned(),
+        task_type: task_type.to_owned(),
+        status: status.to_owned(),
+        source: "tests".to_owned(),
+        priority: "normal".to_owned(),
+        requested_by: Some(test_user_id(1)),
+        parent_task_id: None,
+        request_id: None,
+        cancel_requested: false,
+        error_message: error_message.map(str::to_owned),
+        payload_json: payload_json.to_owned(),
+        result_json: result_json.map(str::to_owned),
+        log_file_path: None,
+        created_at: "2026-02-27T00:00:00Z".to_owned(),
+        started_at: Some("2026-02-27T00:00:01Z".to_owned()),
+        finished_at: Some("2026-02-27T00:00:02Z".to_owned()),
+        updated_at: "2026-02-27T00:00:02Z".to_owned(),
+    }
+}
+
+fn test_task_event(id: i64, event_type: &str, payload_json: &str) -> AdminTaskEventItem {
+    AdminTaskEventItem {
+        id: id.to_string(),
+        event_type: event_type.to_owned(),
+        payload_json: payload_json.to_owned(),
+        created_at: format!("2026-02-27T00:00:{:02}Z", (id % 60)),
+    }
+}
+
+#[test]
+fn build_sync_access_refresh_diagnostics_surfaces_star_failure_details() {
+    let log_path = std::env::temp_dir().join(format!(
+        "octo-rill-access-refresh-diagnostics-{}.ndjson",
+        crate::local_id::generate_local_id()
+    ));
+    std::fs::write(&log_path, "{}\n").expect("seed access refresh log");
+    let mut task = test_task_detail_item(
+        jobs::TASK_SYNC_ACCESS_REFRESH,
+        jobs::STATUS_FAILED,
+        r#"{"user_id":"scope22222222222"}"#,
+        None,
+        Some("sync starred graphql: timed out after 30s"),
+    );
+    task.log_file_path = Some(log_path.to_string_lossy().into_owned());
+    let events = vec![test_task_event(
+        1,
+        "task.progress",
+        r#"{"stage":"star_failed","operation":"sync starred graphql","error_kind":"timeout","error_stage":"timeout","retryable":true,"timeout_ms":30000,"elapsed_ms":30004,"attempts":1,"retry_limit":3,"error_chain":"sync starred graphql: timed out after 30s"}"#,
+    )];
+
+    let (outcome, diagnostics) = super::build_sync_access_refresh_diagnostics(&task, &events);
+
+    assert_eq!(outcome.code, "failed");
+    assert!(outcome.message.contains("stage=timeout"));
+    let failure = diagnostics.failure.expect("failure diagnostics");
+    assert_eq!(failure.operation.as_deref(), Some("sync starred graphql"));
+    assert_eq!(failure.error_kind.as_deref(), Some("timeout"));
+    assert_eq!(failure.error_stage.as_deref(), Some("timeout"));
+    assert_eq!(failure.timeout_ms, Some(30_000));
+    assert_eq!(failure.elapsed_ms, Some(30_004));
+    assert_eq!(
+        diagnostics.log_download_path.as_deref(),
+        Some("/api/admin/jobs/realtime/task-test/log")
+    );
+
+    std::fs::remove_file(log_path).expect("cleanup access refresh log");
+}
+
+#[test]
+fn build_sync_access_refresh_diagnostics_marks_best_effort_partial_success() {
+    let task = test_task_detail_item(
+        jobs::TASK_SYNC_ACCESS_REFRESH,
+        jobs::STATUS_SUCCEEDED,
+        r#"{"user_id":"scope22222222222"}"#,
+        Some(
+            r#"{"starred":{"repos":5},"release":{"repos":5,"releases":8},"social":{"repo_stars":3,"followers":2,"events":1},"social_error":"followers timeout","notifications":{"notifications":7},"notifications_error":null}"#,
+        ),
+        None,
+    );
+
+    let (outcome, diagnostics) = super::build_sync_access_refresh_diagnostics(&task, &[]);
+
+    assert_eq!(outcome.code, "partial");
+    assert_eq!(diagnostics.star_repos, 5);
+    assert_eq!(diagnostics.release_repos, 5);
+    assert_eq!(diagnostics.releases, 8);
+    assert_eq!(
+        diagnostics.social_error.as_deref(),
+        Some("followers timeout")
+    );
+}
+
+#[test]
+fn build_sync_access_refresh_diagnostics_does_not_mislabel_non_star_failures() {
+    let task = test_task_detail_item(
+        jobs::TASK_SYNC_ACCESS_REFRESH,
+        jobs::STATUS_FAILED,
+        r#"{"user_id":"scope22222222222"}"#,
+        None,
+        Some("owned repo visibility refresh failed"),
+    );
+
+    let (outcome, diagnostics) = super::build_sync_access_refresh_diagnostics(&task, &[]);
+
+    assert_eq!(outcome.code, "failed");
+    assert_eq!(outcome.label, "访问同步失败");
+    assert!(diagnostics.failure.is_none());
+}
+
+async fn setup_pool() -> SqlitePool {
+    let database_path = std::env::temp_dir().join(format!(
+        "octo-rill-test-{}.db",
+        crate::local_id::generate_local_id(),
+    ));
+    let options = SqliteConnectOptions::new()
+        .filename(&database_path)
+        .create_if_missing(true);
+    let pool = SqlitePoolOptions::new()
+        .max_connections(1)
+        .connect_with(options)
+        .await
+        .expect("create sqlite memory db");
+    sqlx::migrate!("./migrations")
+        .run(&pool)
+        .await
+        .expect("run migrations");
+    super::ensure_owned_repo_visual_columns(&pool)
+        .await
+        .expect("ensure owned repo visual columns");
+
+    let now = "2026-02-23T00:00:00Z";
+    sqlx::query(
+        r#"
+            INSERT INTO users (id, github_user_id, login, created_at, updated_at)
+            VALUES (?, 30215105, 'IvanLi-CN', ?, ?)
+            "#,
+    )
+    .bind(test_user_id(1))
+    .bind(now)
+    .bind(now)
+    .execute(&pool)
+    .await
+    .expect("seed user");
+
+    seed_github_connection(&pool, test_user_id(1).as_str(), 30215105, "IvanLi-CN", now).await;
+
+    pool
+}
+
+fn setup_state(pool: SqlitePool) -> Arc<AppState> {
+    let encryption_key = EncryptionKey::from_base64("AAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAA=")
+        .expect("build encryption key");
+    let config = AppConfig {
+        bind_addr: "127.0.0.1:58090"
+            .parse::<SocketAddr>()
+            .expect("parse bind addr"),
+        public_base_url: Url::parse("http://127.0.0.1:58090").expect("parse public base url"),
+        database_url: "sqlite::memory:".to_owned(),
+        sqlite_pool_max_connections: 8,
+        static_dir: None,
+        task_log_dir: std::env::temp_dir().join("octo-rill-task-logs-tests"),
+        job_worker_concurrency: 4,
+        reaction_viewer_ttl_secs: 600,
+        release_backfill_limit: 10,
+        star_purge_grace_days: 14,
+        notification_archive_days: 90,
+        notification_retention_days: 365,
+        task_retention_days: 30,
+        backup_dir: std::env::temp_dir().join("octo-rill-backups-tests"),
+        backup_retention_count: 7,
+        shutdown_grace_secs: 30,
+        task_timeouts: crate::jobs::TaskTimeouts::default(),
+        encryption_key: encryption_key.clone(),
+        github: GitHubOAuthConfig {
+            client_id: "test-client-id".to_owned(),
+            client_secret: "test-client-secret".to_owned(),
+            redirect_url: Url::parse("http://127.0.0.1:58090/auth/callback")
+                .expect("parse github redirect"),
+        },
+        github_app: None,
+        linuxdo: None,
+        ai: None,
+        ai_max_concurrency: 1,
+        ai_translate_concurrency: 2,
+        ai_skip_translation_cjk_percent: 30,
+        ai_daily_token_budget_per_user: None,
+        ai_request_interval_ms: 0,
+        ai_daily_at_local: None,
+        app_default_time_zone: crate::briefs::DEFAULT_DAILY_BRIEF_TIME_ZONE.to_owned(),
+        smtp: None,
+        logging: crate::observability::LoggingThresholds::default(),
+        metrics_token: None,
+        trust_request_id_header: false,
+        trust_forwarded_headers: false,
+        readyz_check_github: false,
+        rate_limits: crate::rate_limit::RateLimits::default(),
+    };
+    let github_oauth = build_oauth_client(&config).expect("build oauth client");
+    let webauthn = crate::state::build_webauthn(&config).expect("build webauthn");
+    Arc::new(AppState {
+        llm_scheduler: Arc::new(crate::ai::LlmScheduler::new(config.ai_max_concurrency)),
+        translation_scheduler: Arc::new(crate::translations::TranslationSchedulerController::new(
+            crate::translations::TranslationRuntimeConfig::default(),
+        )),
+        config,
+        pool,
+        sqlite_writer: crate::sqlite_write::SqliteWriteCoordinator::new(),
+        http: reqwest::Client::new(),
+        github_rest_http: reqwest::Client::new(),
+        github_rest_api_base: Url::parse("https://api.github.com/")
+            .expect("parse github rest api base"),
+        github_graphql_url: Url::parse("https://api.github.com/graphql")
+            .expect("parse github graphql url"),
+        github_oauth,
+        linuxdo_oauth: None,
+        webauthn,
+        encryption_key,
+        runtime_owner_id: "api-test-runtime-owner".to_owned(),
+        feed_events: crate::feed_events::FeedEventHub::default(),
+        admin_mutation_limiter: crate::admin_audit::AdminMutationLimiter::default(),
+        translation_flights: crate::ai::TranslationSingleflight::default(),
+        request_limiter: crate::rate_limit::RequestRateLimiter::default(),
+        github_probe: crate::health::GitHubProbe::default(),
+        github_app_tokens: None,
+    })
+}
+
+fn setup_state_with_ai_base_url(pool: SqlitePool, base_url: Url) -> Arc<AppState> {
+    let encryption_key = EncryptionKey::from_base64("AAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAA=")
+        .expect("build encryption key");
+    let config = AppConfig {
+        bind_addr: "127.0.0.1:58090"
+            .parse::<SocketAddr>()
+            .expect("parse bind addr"),
+        public_base_url: Url::parse("http://127.0.0.1:58090").expect("parse public base url"),
+        database_url: "sqlite::memory:".to_owned(),
+        sqlite_pool_max_connections: 8,
+        static_dir: None,
+        task_log_dir: std::env::temp_dir().join("octo-rill-task-logs-tests"),
+        job_worker_concurrency: 4,
+        reaction_viewer_ttl_secs: 600,
+        release_backfill_limit: 10,
+        star_purge_grace_days: 14,
+        notification_archive_days: 90,
+        notification_retention_days: 365,
+        task_retention_days: 30,
+        backup_dir: std::env::temp_dir().join("octo-rill-backups-tests"),
+        backup_retention_count: 7,
+        shutdown_grace_secs: 30,
+        task_timeouts: crate::jobs::TaskTimeouts::default(),
+        encryption_key: encryption_key.clone(),
+        github: GitHubOAuthConfig {
+            client_id: "test-client-id".to_owned(),
+            client_secret: "test-client-secret".to_owned(),
+            redirect_url: Url::parse("http://127.0.0.1:58090/auth/callback")
+                .expect("parse github redirect"),
+        },
+        github_app: None,
+        linuxdo: None,
+        ai: Some(AiConfig {
+            provider: crate::config::AiProvider::OpenAi,
+            base_url,
+            model: "test-model".to_owned(),
+            api_key: "test-key".to_owned(),
+            fallback_models: Vec::new(),
+            startup_probe: crate::config::AiStartupProbe::Off,
+        }),
+        ai_max_concurrency: 1,
+        ai_translate_concurrency: 2,
+        ai_skip_translation_cjk_percent: 30,
+        ai_daily_token_budget_per_user: None,
+        ai_request_interval_ms: 0,
+        ai_daily_at_local: None,
+        app_default_time_zone: crate::briefs::DEFAULT_DAILY_BRIEF_TIME_ZONE.to_owned(),
+        smtp: None,
+        logging: crate::observability::LoggingThresholds::default(),
+        metrics_token: None,
+        trust_request_id_header: false,
+        trust_forwarded_headers: false,
+        readyz_check_github: false,
+        rate_limits: crate::rate_limit::RateLimits::default(),
+    };
+    let github_oauth = build_oauth_client(&config).expect("build oauth client");
+    let webauthn = crate::state::build_webauthn(&config).expect("build webauthn");
+    Arc::new(AppState {
+        llm_scheduler: Arc::new(crate::ai::LlmScheduler::new(config.ai_max_concurrency)),
+        translation_scheduler: Arc::new(crate::translations::TranslationSchedulerController::new(
+            crate::translations::TranslationRuntimeConfig::default(),
+        )),
+        config,
+        pool,
+        sqlite_writer: crate::sqlite_write::SqliteWriteCoordinator::new(),
+        http: reqwest::Client::new(),
+        github_rest_http: reqwest::Client::new(),
+        github_rest_api_base: Url::parse("https://api.github.com/")
+            .expect("parse github rest api base"),
+        github_graphql_url: Url::parse("https://api.github.com/graphql")
+            .expect("parse github graphql url"),
+        github_oauth,
+        linuxdo_oauth: None,
+        webauthn,
+        encryption_key,
+        runtime_owner_id: "api-test-runtime-owner".to_owned(),
+        feed_events: crate::feed_events::FeedEventHub::default(),
+        admin_mutation_limiter: crate::admin_audit::AdminMutationLimiter::default(),
+        translation_flights: crate::ai::TranslationSingleflight::default(),
+        request_limiter: crate::rate_limit::RequestRateLimiter::default(),
+        github_probe: crate::health::GitHubProbe::default(),
+        github_app_tokens: None,
+    })
+}
+
+fn setup_state_with_ai(pool: SqlitePool) -> Arc<AppState> {
+    setup_state_with_ai_base_url(
+        pool,
+        Url::parse("https://api.example.test/v1/").expect("parse ai base url"),
+    )
+}
+
+async fn spawn_test_ai_server(app: Router) -> Url {
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0")
+        .await
+        .expect("bind test ai server");
+    let addr = listener.local_addr().expect("resolve test ai server addr");
+    tokio::spawn(async move {
+        axum::serve(listener, app).await.expect("serve test ai app");
+    });
+    Url::parse(&format!("http://{addr}/")).expect("parse test ai base url")
+}
+
+async fn setup_session(user_id: i64) -> Session {
+    let store = Arc::new(MemoryStore::default());
+    let session = Session::new(None, store, None);
+    session
+        .insert("user_id", test_user_id(user_id))
+        .await
+        .expect("insert session user id");
+    session
+}
+
+async fn seed_github_connection(
+    pool: &SqlitePool,
+    user_id: &str,
+    github_user_id: i64,
+    login: &str,
+    linked_at: &str,
+) {
+    sqlx::query(
+        r#"
+            INSERT INTO github_connections (
+              id,
+              user_id,
+              github_user_id,
+              login,
+              access_token_ciphertext,
+              access_token_nonce,
+              scopes,
+              linked_at,
+              updated_at
+            )
+            VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?)
+            "#,
+    )
+    .bind(crate::local_id::generate_local_id())
+    .bind(user_id)
+    .bind(github_user_id)
+    .bind(login)
+    .bind(vec![0_u8])
+    .bind(vec![0_u8])
+    .bind("read:user")
+    .bind(linked_at)
+    .bind(linked_at)
+    .execute(pool)
+    .await
+    .expect("seed github connection");
+}
+
+async fn seed_passkey(
+    pool: &SqlitePool,
+    user_id: &str,
+    user_handle_uuid: &str,
+    credential_id: &str,
+) -> String {
+    let passkey_id = crate::local_id::generate_local_id();
+    sqlx::query(
+        r#"
+            UPDATE users
+            SET passkey_user_handle_uuid = ?, updated_at = '2026-02-23T00:00:00Z'
+            WHERE id = ?
+            "#,
+    )
+    .bind(user_handle_uuid)
+    .bind(user_id)
+    .execute(pool)
+    .await
+    .expect("seed passkey user handle");
+    sqlx::query(
+        r#"
+            INSERT INTO user_passkeys (
+              id,
+              user_id,
+              credential_id,
+              label,
+              passkey_json,
+              created_at,
+              last_used_at
+            )
+            VALUES (?, ?, ?, ?, ?, ?, ?)
+            "#,
+    )
+    .bind(passkey_id.as_str())
+    .bind(user_id)
+    .bind(credential_id)
+    .bind("通行密钥 · 2026-02-23 00:00 UTC")
+    .bind("{}")
+    .bind("2026-02-23T00:00:00Z")
+    .bind(Option::<String>::None)
+    .execute(pool)
+    .await
+    .expect("seed passkey");
+    passkey_id
+}
+
+async fn seed_user(pool: &SqlitePool, id: i64, login: &str, is_admin: i64, is_disabled: i64) {
+    let created_at = format!("2026-02-23T00:00:{id:02}Z");
+    let local_id = test_user_id(id);
+    sqlx::query(
+            r#"
+            INSERT INTO users (id, github_user_id, login, is_admin, is_disabled, created_at, updated_at)
+            VALUES (?, ?, ?, ?, ?, ?, ?)
+            "#,
+        )
+        .bind(&local_id)
+        .bind(30000000_i64 + id)
+        .bind(login)
+        .bind(is_admin)
+        .bind(is_disabled)
+        .bind(created_at.as_str())
+        .bind(created_at.as_str())
+        .execute(pool)
+        .await
+        .expect("seed test user");
+
+    seed_github_connection(
+        pool,
+        local_id.as_str(),
+        30000000_i64 + id,
+        login,
+        &created_at,
+    )
+    .await;
+}
+
+async fn set_last_active_at(pool: &SqlitePool, user_id: &str, last_active_at: Option<&str>) {
+    sqlx::query(
+        r#"
+            UPDATE users
+            SET last_active_at = ?
+            WHERE id = ?
+            "#,
+    )
+    .bind(last_active_at)
+    .bind(user_id)
+    .execute(pool)
+    .await
+    .expect("set last_active_at");
+}
+
+#[tokio::test]
+async fn stale_visit_marker_survives_until_me_bootstraps_access_sync() {
+    let pool = setup_pool().await;
+    let state = setup_state(pool.clone());
+    let session = setup_session(1).await;
+    let user_id = test_user_id(1);
+
+    set_last_active_at(&pool, user_id.as_str(), Some("2026-02-22T21:30:00Z")).await;
+
+    let resolved = require_active_user_id(state.as_ref(), &session)
+        .await
+        .expect("require active user");
+    assert_eq!(resolved, user_id);
+    assert_eq!(
+        load_pending_access_sync_reason(&session)
+            .await
+            .expect("load pending reason")
+            .as_deref(),
+        Some(ACCESS_SYNC_REASON_INACTIVE_OVER_1H)
+    );
+    let touched_last_active_at = sqlx::query_scalar::<_, Option<String>>(
+        r#"
+            SELECT last_active_at
+            FROM users
+            WHERE id = ?
+            "#,
+    )
+    .bind(user_id.as_str())
+    .fetch_one(&pool)
+    .await
+    .expect("load touched last_active_at");
+    assert!(touched_last_active_at.is_some());
+    assert_ne!(
+        touched_last_active_at.as_deref(),
+        Some("2026-02-22T21:30:00Z")
+    );
+    assert!(!last_active_is_stale(touched_last_active_at.as_deref()));
+
+    let Json(resp) = me(State(state.clone()), session)
+        .await
+        .expect("bootstrap me");
+    assert_eq!(resp.access_sync.reason, ACCESS_SYNC_REASON_INACTIVE_OVER_1H);
+    assert_eq!(
+        resp.access_sync.task_type.as_deref(),
+        Some(jobs::TASK_SYNC_ACCESS_REFRESH)
+    );
+    assert!(resp.access_sync.task_id.is_some());
+
+    let queued = sqlx::query_scalar::<_, i64>(
+        r#"
+            SELECT COUNT(*)
+            FROM job_tasks
+            WHERE requested_by = ?
+              AND task_type = ?
+              AND status = 'queued'
+            "#,
+    )
+    .bind(user_id.as_str())
+    .bind(jobs::TASK_SYNC_ACCESS_REFRESH)
+    .fetch_one(&pool)
+    .await
+    .expect("count queued access sync tasks");
+    assert_eq!(queued, 1);
+}
+
+#[test]
+fn last_active_is_stale_uses_one_hour_window() {
+    let recent_last_active_at = (chrono::Utc::now() - chrono::Duration::minutes(20)).to_rfc3339();
+    let stale_last_active_at = (chrono::Utc::now() - chrono::Duration::minutes(61)).to_rfc3339();
+
+    assert!(!last_active_is_stale(Some(recent_last_active_at.as_str())));
+    assert!(last_active_is_stale(Some(stale_last_active_at.as_str())));
+}
+
+#[tokio::test]
+async fn sync_all_task_id_reuses_inflight_access_refresh_task() {
+    let pool = setup_pool().await;
+    let state = setup_state(pool.clone());
+    let user_id = test_user_id(1);
+
+    let response = sync_all(
+        State(state.clone()),
+        AuthedUser(test_user_id(1)),
+        Query(ReturnModeQuery {
+            return_mode: Some("task_id".to_owned()),
+        }),
+    )
+    .await
+    .expect("enqueue first sync_all");
+    let body = to_bytes(response.into_body(), usize::MAX)
+        .await
+        .expect("read first response body");
+    let first: serde_json::Value =
+        serde_json::from_slice(&body).expect("parse first task response");
+    let first_task_id = first
+        .get("task_id")
+        .and_then(|value| value.as_str())
+        .expect("first task id")
+        .to_owned();
+
+    let response = sync_all(
+        State(state.clone()),
+        AuthedUser(test_user_id(1)),
+        Query(ReturnModeQuery {
+            return_mode: Some("task_id".to_owned()),
+        }),
+    )
+    .await
+    .expect("enqueue second sync_all");
+    let body = to_bytes(response.into_body(), usize::MAX)
+        .await
+        .expect("read second response body");
+    let second: serde_json::Value =
+        serde_json::from_slice(&body).expect("parse second task response");
+    let second_task_id = second
+        .get("task_id")
+        .and_then(|value| value.as_str())
+        .expect("second task id");
+    assert_eq!(second_task_id, first_task_id);
+
+    let queued = sqlx::query_scalar::<_, i64>(
+        r#"
+            SELECT COUNT(*)
+            FROM job_tasks
+            WHERE requested_by = ?
+              AND task_type = ?
+              AND status IN ('queued', 'running')
+            "#,
+    )
+    .bind(user_id.as_str())
+    .bind(jobs::TASK_SYNC_ACCESS_REFRESH)
+    .fetch_one(&pool)
+    .await
+    .expect("count access refresh tasks");
+    assert_eq!(queued, 1);
+}
+
+#[tokio::test]
+async fn sync_all_sync_mode_includes_notifications_and_social_error() {
+    let pool = setup_pool().await;
+    let state = setup_state(pool);
+
+    let body = execute_sync_all_sync_with(
+        state.as_ref(),
+        test_user_id(1).as_str(),
+        |_state, _user_id| Box::pin(async { Ok(sync::SyncStarredResult { repos: 2 }) }),
+        |_state, _user_id| {
+            Box::pin(async {
+                Ok(sync::SyncReleasesResult {
+                    repos: 3,
+                    releases: 5,
+                    skipped_muted: 0,
+                    repos_checked: 0,
+                    repos_skipped_not_modified: 0,
+                    new_releases: 0,
+                    rate_limit_remaining: None,
+                    stopped_early_rate_limit: false,
+                    failed_repos: Vec::new(),
+                    retried_requests: 0,
+                })
+            })
+        },
+        |_state, _user_id| {
+            Box::pin(async {
+                (
+                    sync::SyncSocialActivityResult::default(),
+                    Some("social boom".to_owned()),
+                )
+            })
+        },
+        |_state, _user_id| {
+            Box::pin(async {
+                Ok(sync::SyncNotificationsResult {
+                    notifications: 7,
+                    since: Some("2026-04-11T11:00:00Z".to_owned()),
+                    retried_requests: 0,
+                })
+            })
+        },
+    )
+    .await
+    .expect("sync all sync body");
+
+    assert_eq!(body["starred"]["repos"], json!(2));
+    assert_eq!(body["releases"]["repos"], json!(3));
+    assert_eq!(body["releases"]["releases"], json!(5));
+    assert_eq!(body["social"]["events"], json!(0));
+    assert_eq!(body["notifications"]["notifications"], json!(7));
+    assert_eq!(
+        body["notifications"]["since"],
+        json!("2026-04-11T11:00:00Z")
+    );
+    assert_eq!(body["social_error"], json!("social boom"));
+}
+
+async fn task_id_from_response(response: Response) -> String {
+    let body = to_bytes(response.into_body(), usize::MAX)
+        .await
+        .expect("read task response body");
+    let payload: serde_json::Value =
+        serde_json::from_slice(&body).expect("parse task response body");
+    payload
+        .get("task_id")
+        .and_then(|value| value.as_str())
+        .expect("task id")
+        .to_owned()
+}
+
+#[tokio::test]
+async fn sync_starred_task_id_reuses_inflight_task() {
+    let pool = setup_pool().await;
+    let state = setup_state(pool.clone());
+    let user_id = test_user_id(1);
+
+    let first_task_id = task_id_from_response(
+        sync_starred(
+            State(state.clone()),
+            AuthedUser(test_user_id(1)),
+            Query(ReturnModeQuery {
+                return_mode: Some("task_id".to_owned()),
+            }),
+        )
+        .await
+        .expect("enqueue first sync_starred"),
+    )
+    .await;
+
+    let second_task_id = task_id_from_response(
+        sync_starred(
+            State(state.clone()),
+            AuthedUser(test_user_id(1)),
+            Query(ReturnModeQuery {
+                return_mode: Some("task_id".to_owned()),
+            }),
+        )
+        .await
+        .expect("enqueue second sync_starred"),
+    )
+    .await;
+
+    assert_eq!(second_task_id, first_task_id);
+
+    let queued = sqlx::query_scalar::<_, i64>(
+        r#"
+            SELECT COUNT(*)
+            FROM job_tasks
+            WHERE requested_by = ?
+              AND task_type = ?
+              AND status IN ('queued', 'running')
+            "#,
+    )
+    .bind(user_id.as_str())
+    .bind(jobs::TASK_SYNC_STARRED)
+    .fetch_one(&pool)
+    .await
+    .expect("count sync starred tasks");
+    assert_eq!(queued, 1);
+}
+
+#[tokio::test]
+async fn sync_releases_task_id_reuses_inflight_task() {
+    let pool = setup_pool().await;
+    let state = setup_state(pool.clone());
+    let user_id = test_user_id(1);
+
+    let first_task_id = task_id_from_response(
+        sync_releases(
+            State(state.clone()),
+            AuthedUser(test_user_id(1)),
+            Query(ReturnModeQuery {
+                return_mode: Some("task_id".to_owned()),
+            }),
+            Query(SyncReleasesQuery::default()),
+        )
+        .await
+        .expect("enqueue first sync_releases"),
+    )
+    .await;
+
+    let second_task_id = task_id_from_response(
+        sync_releases(
+            State(state.clone()),
+            AuthedUser(test_user_id(1)),
+            Query(ReturnModeQuery {
+                return_mode: Some("task_id".to_owned()),
+            }),
+            Query(SyncReleasesQuery::default()),
+        )
+        .await
+        .expect("enqueue second sync_releases"),
+    )
+    .await;
+
+    assert_eq!(second_task_id, first_task_id);
+
+    let queued = sqlx::query_scalar::<_, i64>(
+        r#"
+            SELECT COUNT(*)
+            FROM job_tasks
+            WHERE requested_by = ?
+              AND task_type = ?
+              AND status IN ('queued', 'running')
+            "#,
+    )
+    .bind(user_id.as_str())
+    .bind(jobs::TASK_SYNC_RELEASES)
+    .fetch_one(&pool)
+    .await
+    .expect("count sync releases tasks");
+    assert_eq!(queued, 1);
+}
+
+#[tokio::test]
+async fn sync_notifications_task_id_reuses_inflight_task() {
+    let pool = setup_pool().await;
+    let state = setup_state(pool.clone());
+    let user_id = test_user_id(1);
+
+    let first_task_id = task_id_from_response(
+        sync_notifications(
+            State(state.clone()),
+            AuthedUser(test_user_id(1)),
+            Query(ReturnModeQuery {
+                return_mode: Some("task_id".to_owned()),
+            }),
+        )
+        .await
+        .expect("enqueue first sync_notifications"),
+    )
+    .await;
+
+    let second_task_id = task_id_from_response(
+        sync_notifications(
+            State(state.clone()),
+            AuthedUser(test_user_id(1)),
+            Query(ReturnModeQuery {
+                return_mode: Some("task_id".to_owned()),
+            }),
+        )
+        .await
+        .expect("enqueue second sync_notifications"),
+    )
+    .await;
+
+    assert_eq!(second_task_id, first_task_id);
+
+    let queued = sqlx::query_scalar::<_, i64>(
+        r#"
+            SELECT COUNT(*)
+            FROM job_tasks
+            WHERE requested_by = ?
+              AND task_type = ?
+              AND status IN ('queued', 'running')
+            "#,
+    )
+    .bind(user_id.as_str())
+    .bind(jobs::TASK_SYNC_NOTIFICATIONS)
+    .fetch_one(&pool)
+    .await
+    .expect("count sync notifications tasks");
+    assert_eq!(queued, 1);
+}
+
+#[tokio::test]
+async fn me_reuses_inflight_access_refresh_task() {
+    let pool = setup_pool().await;
+    let state = setup_state(pool.clone());
+    let session = setup_session(1).await;
+    let user_id = test_user_id(1);
+
+    set_last_active_at(&pool, user_id.as_str(), Some("2026-02-22T21:30:00Z")).await;
+    seed_access_refresh_task(
+        &pool,
+        "task-access-running",
+        user_id.as_str(),
+        jobs::STATUS_RUNNING,
+    )
+    .await;
+
+    let Json(resp) = me(State(state), session).await.expect("bootstrap me");
+    assert_eq!(resp.access_sync.reason, "reused_inflight");
+    assert_eq!(
+        resp.access_sync.task_type.as_deref(),
+        Some(jobs::TASK_SYNC_ACCESS_REFRESH)
+    );
+    assert_eq!(
+        resp.access_sync.task_id.as_deref(),
+        Some("task-access-running")
+    );
+    assert_eq!(
+        resp.access_sync.event_path.as_deref(),
+        Some("/api/tasks/task-access-running/events")
+    );
+
+    let inflight = sqlx::query_scalar::<_, i64>(
+        r#"
+            SELECT COUNT(*)
+            FROM job_tasks
+            WHERE requested_by = ?
+              AND task_type = ?
+              AND status IN ('queued', 'running')
+            "#,
+    )
+    .bind(user_id.as_str())
+    .bind(jobs::TASK_SYNC_ACCESS_REFRESH)
+    .fetch_one(&pool)
+    .await
+    .expect("count inflight access refresh tasks");
+    assert_eq!(inflight, 1);
+}
+
+#[tokio::test]
+async fn me_delete_passkey_clears_stale_handle_after_removing_last_passkey() {
+    let pool = setup_pool().await;
+    let state = setup_state(pool.clone());
+    let user_id = test_user_id(1);
+    let passkey_id = seed_passkey(
+        &pool,
+        user_id.as_str(),
+        "0c4ad957-f3cd-4dd3-a31b-56658fd149ea",
+        "cred-last",
+    )
+    .await;
+
+    let Json(resp) = me_delete_passkey(State(state), AuthedUser(test_user_id(1)), Path(passkey_id))
+        .await
+        .expect("delete last passkey");
+
+    assert!(resp.items.is_empty());
+    let persisted_handle = sqlx::query_scalar::<_, Option<String>>(
+        r#"
+            SELECT passkey_user_handle_uuid
+            FROM users
+            WHERE id = ?
+            "#,
+    )
+    .bind(user_id.as_str())
+    .fetch_one(&pool)
+    .await
+    .expect("load user handle");
+    assert!(persisted_handle.is_none());
+}
+
+#[tokio::test]
+async fn me_delete_passkey_keeps_handle_when_other_passkeys_remain() {
+    let pool = setup_pool().await;
+    let state = setup_state(pool.clone());
+    let user_id = test_user_id(1);
+    let passkey_id = seed_passkey(
+        &pool,
+        user_id.as_str(),
+        "83a58fc5-4c34-499e-9ca0-9eaeecfcc173",
+        "cred-keep-1",
+    )
+    .await;
+    let _second_passkey_id = seed_passkey(
+        &pool,
+        user_id.as_str(),
+        "83a58fc5-4c34-499e-9ca0-9eaeecfcc173",
+        "cred-keep-2",
+    )
+    .await;
+
+    let Json(resp) = me_delete_passkey(State(state), AuthedUser(test_user_id(1)), Path(passkey_id))
+        .await
+        .expect("delete one passkey");
+
+    assert_eq!(resp.items.len(), 1);
+    let persisted_handle = sqlx::query_scalar::<_, Option<String>>(
+        r#"
+            SELECT passkey_user_handle_uuid
+            FROM users
+            WHERE id = ?
+            "#,
+    )
+    .bind(user_id.as_str())
+    .fetch_one(&pool)
+    .await
+    .expect("load user handle");
+    assert_eq!(
+        persisted_handle.as_deref(),
+        Some("83a58fc5-4c34-499e-9ca0-9eaeecfcc173")
+    );
+}
+
+#[tokio::test]
+async fn me_delete_github_connection_purges_account_rows_and_promotes_primary() {
+    let pool = setup_pool().await;
+    let state = setup_state(pool.clone());
+    let user_id = test_user_id(1);
+    seed_github_connection(
+        &pool,
+        user_id.as_str(),
+        40000001,
+        "octo-work",
+        "2026-02-24T00:00:00Z",
+    )
+    .await;
+    let connection_ids = sqlx::query_scalar::<_, String>(
+        r#"
+            SELECT id
+            FROM github_connections
+            WHERE user_id = ?
+            ORDER BY linked_at ASC, id ASC
+            "#,
+    )
+    .bind(user_id.as_str())
+    .fetch_all(&pool)
+    .await
+    .expect("load github connections");
+    let (personal_id, work_id) = (connection_ids[0].clone(), connection_ids[1].clone());
+    sqlx::query(r#"UPDATE github_connections SET is_primary = 1 WHERE id = ?"#)
+        .bind(personal_id.as_str())
+        .execute(&pool)
+        .await
+        .expect("mark primary connection");
+
+    seed_star(&pool, 1).await;
+    seed_star(&pool, 2).await;
+    seed_star(&pool, 3).await;
+    for (repo_id, connection_id) in [
+        (1_i64, personal_id.as_str()),
+        (2, work_id.as_str()),
+        (3, personal_id.as_str()),
+        (3, work_id.as_str()),
+    ] {
+        sqlx::query(
+            r#"
+            INSERT INTO starred_repo_sources (user_id, repo_id, github_connection_id)
+            VALUES (?, ?, ?)
+            "#,
+        )
+        .bind(user_id.as_str())
+        .bind(repo_id)
+        .bind(connection_id)
+        .execute(&pool)
+        .await
+        .expect("seed star source");
+    }
+    seed_notification(
+        &pool,
+        user_id.as_str(),
+        "thread-personal",
+        "2026-02-23T01:00:00Z",
+    )
+    .await;
+    seed_notification(
+        &pool,
+        user_id.as_str(),
+        "thread-work",
+        "2026-02-23T02:00:00Z",
+    )
+    .await;
+    for (thread_id, connection_id) in [
+        ("thread-personal", personal_id.as_str()),
+        ("thread-work", work_id.as_str()),
+    ] {
+        sqlx::query(
+            r#"UPDATE notifications SET source_github_connection_id = ? WHERE thread_id = ?"#,
+        )
+        .bind(connection_id)
+        .bind(thread_id)
+        .execute(&pool)
+        .await
+        .expect("tag synced notification");
+    }
+
+    let Json(resp) = me_delete_github_connection(
+        State(state.clone()),
+        AuthedUser(test_user_id(1)),
+        Path(personal_id.clone()),
+    )
+    .await
+    .expect("unlink personal account");
+
+    assert_eq!(resp.items.len(), 1);
+    assert_eq!(resp.items[0].id, work_id);
+    assert!(resp.items[0].is_primary);
+    let starred = sqlx::query_scalar::<_, i64>(
+        r#"SELECT repo_id FROM starred_repos WHERE user_id = ? ORDER BY repo_id"#,
+    )
+    .bind(user_id.as_str())
+    .fetch_all(&pool)
+    .await
+    .expect("load starred repos");
+    assert_eq!(starred, vec![2, 3]);
+    let sources = sqlx::query_scalar::<_, i64>(
+        r#"SELECT COUNT(*) FROM starred_repo_sources WHERE github_connection_id = ?"#,
+    )
+    .bind(personal_id.as_str())
+    .fetch_one(&pool)
+    .await
+    .expect("count unlinked star sources");
+    assert_eq!(sources, 0);
+    let threads = sqlx::query_scalar::<_, String>(
+        r#"SELECT thread_id FROM notifications WHERE user_id = ? ORDER BY thread_id"#,
+    )
+    .bind(user_id.as_str())
+    .fetch_all(&pool)
+    .await
+    .expect("load notifications");
+    assert_eq!(threads, vec!["thread-work".to_owned()]);
+
+    let err = me_delete_github_connection(State(state), AuthedUser(test_user_id(1)), Path(work_id))
+        .await
+        .expect_err("last account cannot be unlinked");
+    assert_eq!(err.code(), "last_github_connection_guard");
+}
+
+async fn seed_repo_release(pool: &SqlitePool, repo_id: i64, release_id: i64) {
+    let now = "2026-02-23T00:00:00Z";
+    sqlx::query(
+        r#"
+            INSERT INTO repo_releases (
+              id,
+              repo_id,
+              release_id,
+              node_id,
+              tag_name,
+              name,
+              body,
+              html_url,
+              published_at,
+              created_at,
+              is_prerelease,
+              is_draft,
+              updated_at,
+              react_plus1,
+              react_laugh,
+              react_heart,
+              react_hooray,
+              react_rocket,
+              react_eyes
+            )
+            VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, 0, 0, ?, 0, 0, 0, 0, 0, 0)
+            "#,
+    )
+    .bind(format!("repo-release-{repo_id}-{release_id}"))
+    .bind(repo_id)
+    .bind(release_id)
+    .bind(format!("node-{release_id}"))
+    .bind("v1.2.3")
+    .bind("Release v1.2.3")
+    .bind("- item")
+    .bind("https://github.com/openai/codex/releases/tag/v1.2.3")
+    .bind(now)
+    .bind(now)
+    .bind(now)
+    .execute(pool)
+    .await
+    .expect("seed shared repo release");
+}
+
+async fn seed_public_release_usage(
+    pool: &SqlitePool,
+    repo_id: Option<i64>,
+    status: &str,
+) -> String {
+    let now = "2026-02-23T00:00:00Z";
+    let usage_id = crate::local_id::test_local_id("public-release-usage");
+    sqlx::query(
+        r#"
+            INSERT INTO public_repo_release_usage (
+              id, repo_id, owner_login, repo_name, full_name, full_name_lower,
+              first_registered_at, last_requested_at, created_at, updated_at,
+              last_sync_status
+            )
+            VALUES (?, ?, 'openai', 'codex', 'openai/codex', 'openai/codex', ?, ?, ?, ?, ?)
+            "#,
+    )
+    .bind(usage_id.as_str())
+    .bind(repo_id)
+    .bind(now)
+    .bind(now)
+    .bind(now)
+    .bind(now)
+    .bind(status)
+    .execute(pool)
+    .await
+    .expect("seed public release usage");
+    usage_id
+}
+
+async fn response_json(response: Response) -> Value {
+    let body = to_bytes(response.into_body(), usize::MAX)
+        .await
+        .expect("read response body");
+    serde_json::from_slice(&body).expect("parse response json")
+}
+
+async fn seed_star(pool: &SqlitePool, repo_id: i64) {
+    seed_star_for_user_with_privacy(pool, 1, repo_id, false).await;
+}
+
+async fn seed_star_with_privacy(pool: &SqlitePool, repo_id: i64, is_private: bool) {
+    seed_star_for_user_with_privacy(pool, 1, repo_id, is_private).await;
+}
+
+async fn seed_star_for_user_with_privacy(
+    pool: &SqlitePool,
+    user_index: i64,
+    repo_id: i64,
+    is_private: bool,
+) {
+    let now = chrono::Utc::now().to_rfc3339();
+    sqlx::query(
+        r#"
+            INSERT INTO starred_repos (
+              id, user_id, repo_id, full_name, owner_login, name,
+              description, html_url, stargazed_at, is_private, updated_at,
+              owner_avatar_url, open_graph_image_url, uses_custom_open_graph_image
+            )
+            VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+            "#,
+    )
+    .bind(format!("star-{user_index}-{repo_id}"))
+    .bind(test_user_id(user_index))
+    .bind(repo_id)
+    .bind("openai/codex")
+    .bind("openai")
+    .bind("codex")
+    .bind("octo rill test")
+    .bind("https://github.com/openai/codex")
+    .bind(now.as_str())
+    .bind(if is_private { 1_i64 } else { 0_i64 })
+    .bind(now.as_str())
+    .bind("https://avatars.githubusercontent.com/u/14957082")
+    .bind("https://repository-images.githubusercontent.com/14957082/codex")
+    .bind(1_i64)
+    .execute(pool)
+    .await
+    .expect("seed starred");
+}
+
+async fn seed_owned_repo_baseline(pool: &SqlitePool, repo_id: i64, full_name: &str) {
+    let now = "2026-02-23T00:00:00Z";
+    sqlx::query(
+        r#"
+            INSERT INTO owned_repo_star_baselines (
+              id,
+              user_id,
+              repo_id,
+              repo_full_name,
+              initialized_at,
+              updated_at,
+              members_snapshot_initialized,
+              owner_avatar_url,
+              open_graph_image_url,
+              uses_custom_open_graph_image
+            )
+            VALUES (?, ?, ?, ?, ?, ?, 1, ?, ?, ?)
+            "#,
+    )
+    .bind(format!("owned-repo-{repo_id}"))
+    .bind(test_user_id(1))
+    .bind(repo_id)
+    .bind(full_name)
+    .bind(now)
+    .bind(now)
+    .bind("https://avatars.githubusercontent.com/u/30215105")
+    .bind("https://repository-images.githubusercontent.com/30215105/octo-rill")
+    .bind(1_i64)
+    .execute(pool)
+    .await
+    .expect("seed owned repo baseline");
+}
+
+async fn set_include_own_releases(pool: &SqlitePool, enabled: bool) {
+    sqlx::query(
+        r#"
+            UPDATE users
+            SET include_own_releases = ?, updated_at = '2026-02-23T00:00:00Z'
+            WHERE id = ?
+            "#,
+    )
+    .bind(if enabled { 1_i64 } else { 0_i64 })
+    .bind(test_user_id(1))
+    .execute(pool)
+    .await
+    .expect("update include_own_releases");
+}
+
+struct SeedSocialEventArgs<'a> {
+    kind: &'a str,
+    event_id: &'a str,
+    repo_id: Option<i64>,
+    repo_full_name: Option<&'a str>,
+    repo_owner_avatar_url: Option<&'a str>,
+    repo_open_graph_image_url: Option<&'a str>,
+    repo_uses_custom_open_graph_image: Option<bool>,
+    title: Option<&'a str>,
+    body: Option<&'a str>,
+    html_url: Option<&'a str>,
+    actor_login: &'a str,
+    occurred_at: &'a str,
+}
+
+async fn seed_social_event(pool: &SqlitePool, user_id: &str, args: SeedSocialEventArgs<'_>) {
+    sqlx::query(
+        r#"
+            INSERT INTO social_activity_events (
+              id,
+              user_id,
+              kind,
+              repo_id,
+              repo_full_name,
+              repo_owner_avatar_url,
+              repo_open_graph_image_url,
+              repo_uses_custom_open_graph_image,
+              title,
+              body,
+              html_url,
+              actor_github_user_id,
+              actor_login,
+              actor_avatar_url,
+              actor_html_url,
+              occurred_at,
+              detected_at,
+              created_at,
+              updated_at
+            )
+            VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+            "#,
+    )
+    .bind(args.event_id)
+    .bind(user_id)
+    .bind(args.kind)
+    .bind(args.repo_id)
+    .bind(args.repo_full_name)
+    .bind(args.repo_owner_avatar_url)
+    .bind(args.repo_open_graph_image_url)
+    .bind(
+        args.repo_uses_custom_open_graph_image
+            .map(|uses_custom| if uses_custom { 1_i64 } else { 0_i64 }),
+    )
+    .bind(args.title)
+    .bind(args.body)
+    .bind(args.html_url)
+    .bind(90_000_i64 + i64::from(args.actor_login.bytes().map(i16::from).sum::<i16>()))
+    .bind(args.actor_login)
+    .bind(format!("https://avatars.example/{}.png", args.actor_login))
+    .bind(format!("https://github.com/{}", args.actor_login))
+    .bind(args.occurred_at)
+    .bind(args.occurred_at)
+    .bind(args.occurred_at)
+    .bind(args.occurred_at)
+    .execute(pool)
+    .await
+    .expect("seed social activity event");
+}
+
+async fn seed_brief(pool: &SqlitePool, user_id: &str, date: &str, content_markdown: &str) {
+    let created_at = format!("{date}T08:00:00Z");
+    sqlx::query(
+        r#"
+            INSERT INTO briefs (id, user_id, date, content_markdown, created_at, updated_at)
+            VALUES (?, ?, ?, ?, ?, ?)
+            "#,
+    )
+    .bind(format!("brief-{date}"))
+    .bind(user_id)
+    .bind(date)
+    .bind(content_markdown)
+    .bind(&created_at)
+    .bind(created_at.clone())
+    .execute(pool)
+    .await
+    .expect("seed brief");
+}
+
+async fn setup_state_with_github_subject_server(
+    pool: SqlitePool,
+    status: StatusCode,
+    body: Value,
+) -> (Arc<AppState>, Arc<AtomicUsize>) {
+    let calls = Arc::new(AtomicUsize::new(0));
+    let route_calls = Arc::clone(&calls);
+    let base_url = spawn_test_ai_server(Router::new().route(
+        "/repos/openai/codex/pulls/{number}",
+        get(move || {
+            let calls = Arc::clone(&route_calls);
+            let body = body.clone();
+            async move {
+                calls.fetch_add(1, Ordering::SeqCst);
+                (status, Json(body))
+            }
+        }),
+    ))
+    .await;
+    let state = setup_state(pool.clone());
+    let encrypted = state
+        .encryption_key
+        .encrypt_str("ghp_test_token")
+        .expect("encrypt github token");
+    sqlx::query(r#"DELETE FROM github_connections WHERE user_id = ?"#)
+        .bind(test_user_id(1))
+        .execute(&pool)
+        .await
+        .expect("clear placeholder github connections");
+    sqlx::query(
+            r#"
+            INSERT INTO github_connections (
+              id, user_id, github_user_id, login, access_token_ciphertext, access_token_nonce,
+              scopes, linked_at, updated_at
+            )
+            VALUES (?, ?, 10, 'octo', ?, ?, 'notifications', '2026-02-23T00:00:00Z', '2026-02-23T00:00:00Z')
+            "#,
+        )
+        .bind(crate::local_id::generate_local_id())
+        .bind(test_user_id(1))
+        .bind(encrypted.ciphertext)
+        .bind(encrypted.nonce)
+        .execute(&pool)
+        .await
+        .expect("seed github connection");
+    let state = Arc::new(AppState {
+        github_rest_api_base: base_url,
+        ..state.as_ref().clone()
+    });
+    (state, calls)
+}
+
+async fn seed_notification_subject_url(pool: &SqlitePool, thread_id: &str, subject_url: &str) {
+    sqlx::query(
+        r#"
+            UPDATE notifications
+            SET subject_url = ?, html_url = NULL
+            WHERE thread_id = ?
+            "#,
+    )
+    .bind(subject_url)
+    .bind(thread_id)
+    .execute(pool)
+    .await
+    .expect("seed notification subject url");
+}
+
+async fn seed_notification(pool: &SqlitePool, user_id: &str, thread_id: &str, updated_at: &str) {
+    seed_notification_with_timestamp(pool, user_id, thread_id, Some(updated_at)).await;
+}
+
+async fn seed_notification_with_timestamp(
+    pool: &SqlitePool,
+    user_id: &str,
+    thread_id: &str,
+    updated_at: Option<&str>,
+) {
+    sqlx::query(
+        r#"
+            INSERT INTO notifications (
+              id,
+              user_id,
+              thread_id,
+              repo_full_name,
+              subject_title,
+              subject_type,
+              reason,
+              updated_at,
+              unread,
+              html_url
+            )
+            VALUES (?, ?, ?, 'openai/codex', ?, 'PullRequest', 'state_change', ?, 1, ?)
+            "#,
+    )
+    .bind(format!("notification-{thread_id}"))
+    .bind(user_id)
+    .bind(thread_id)
+    .bind(format!("Thread {thread_id}"))
+    .bind(updated_at)
+    .bind(format!("https://github.com/notifications/{thread_id}"))
+    .execute(pool)
+    .await
+    .expect("seed notification");
+}
+
+async fn seed_release_detail_translation(
+    pool: &SqlitePool,
+    user_id: &str,
+    entity_id: &str,
+    source_hash: &str,
+    title: Option<&str>,
+    summary: Option<&str>,
+) {
+    let now = "2026-02-23T00:00:00Z";
+    sqlx::query(
+            r#"
+            INSERT INTO ai_translations (
+              id, user_id, entity_type, entity_id, lang, source_hash, status, title, summary, error_text, active_work_item_id, created_at, updated_at
+            )
+            VALUES (?, ?, 'release_detail', ?, 'zh-CN', ?, 'ready', ?, ?, NULL, NULL, ?, ?)
+            "#,
+        )
+        .bind(crate::local_id::generate_local_id())
+        .bind(user_id)
+        .bind(entity_id)
+        .bind(source_hash)
+        .bind(title)
+        .bind(summary)
+        .bind(now)
+        .bind(now)
+        .execute(pool)
+        .await
+        .expect("seed release detail translation");
+}
+
+async fn seed_release_translation(
+    pool: &SqlitePool,
+    user_id: &str,
+    entity_id: &str,
+    source_hash: &str,
+    title: Option<&str>,
+    summary: Option<&str>,
+) {
+    let now = "2026-02-23T00:00:00Z";
+    sqlx::query(
+            r#"
+            INSERT INTO ai_translations (
+              id, user_id, entity_type, entity_id, lang, source_hash, status, title, summary, error_text, active_work_item_id, created_at, updated_at
+            )
+            VALUES (?, ?, 'release', ?, 'zh-CN', ?, 'ready', ?, ?, NULL, NULL, ?, ?)
+            "#,
+        )
+        .bind(crate::local_id::generate_local_id())
+        .bind(user_id)
+        .bind(entity_id)
+        .bind(source_hash)
+        .bind(title)
+        .bind(summary)
+        .bind(now)
+        .bind(now)
+        .execute(pool)
+        .await
+        .expect("seed release translation");
+}
+
+async fn seed_access_refresh_task(
+    pool: &SqlitePool,
+    task_id: &str,
+    requested_by: &str,
+    status: &str,
+) {
+    let now = "2026-02-23T00:00:00Z";
+    let started_at = match status {
+        jobs::STATUS_RUNNING | jobs::STATUS_SUCCEEDED | jobs::STATUS_FAILED => Some(now),
+        _ => None,
+    };
+    let finished_at = match status {
+        jobs::STATUS_SUCCEEDED | jobs::STATUS_FAILED | jobs::STATUS_CANCELED => Some(now),
+        _ => None,
+    };
+    sqlx::query(
+        r#"
+            INSERT INTO job_tasks (
+              id,
+              task_type,
+              status,
+              source,
+              requested_by,
+              parent_task_id,
+              payload_json,
+              result_json,
+              error_message,
+              cancel_requested,
+              created_at,
+              started_at,
+              finished_at,
+              updated_at
+            )
+            VALUES (?, ?, ?, 'tests', ?, NULL, '{}', NULL, NULL, 0, ?, ?, ?, ?)
+            "#,
+    )
+    .bind(task_id)
+    .bind(jobs::TASK_SYNC_ACCESS_REFRESH)
+    .bind(status)
+    .bind(requested_by)
+    .bind(now)
+    .bind(started_at)
+    .bind(finished_at)
+    .bind(now)
+    .execute(pool)
+    .await
+    .expect("seed access refresh task");
+}
+
+async fn seed_admin_dashboard_task(
+    pool: &SqlitePool,
+    task_id: &str,
+    task_type: &str,
+    status: &str,
+    requested_by: &str,
+    created_at: &str,
+) {
+    let started_at = match status {
+        jobs::STATUS_RUNNING | jobs::STATUS_SUCCEEDED | jobs::STATUS_FAILED => Some(created_at),
+        _ => None,
+    };
+    let finished_at = match status {
+        jobs::STATUS_SUCCEEDED | jobs::STATUS_FAILED | jobs::STATUS_CANCELED => Some(created_at),
+        _ => None,
+    };
+    sqlx::query(
+        r#"
+            INSERT INTO job_tasks (
+              id,
+              task_type,
+              status,
+              source,
+              requested_by,
+              parent_task_id,
+              payload_json,
+              result_json,
+              error_message,
+              cancel_requested,
+              created_at,
+              started_at,
+              finished_at,
+              updated_at
+            )
+            VALUES (?, ?, ?, 'tests', ?, NULL, '{}', NULL, NULL, 0, ?, ?, ?, ?)
+            "#,
+    )
+    .bind(task_id)
+    .bind(task_type)
+    .bind(status)
+    .bind(requested_by)
+    .bind(created_at)
+    .bind(started_at)
+    .bind(finished_at)
+    .bind(created_at)
+    .execute(pool)
+    .await
+    .expect("seed admin dashboard task");
+}
+
+async fn seed_llm_call(
+    pool: &SqlitePool,
+    call_id: &str,
+    status: &str,
+    source: &str,
+    requested_by: Option<String>,
+) {
+    let now = chrono::Utc::now().to_rfc3339();
+    seed_llm_call_with_created_at(pool, call_id, status, source, requested_by, &now).await;
+}
+
+async fn seed_llm_call_with_created_at(
+    pool: &SqlitePool,
+    call_id: &str,
+    status: &str,
+    source: &str,
+    requested_by: Option<String>,
+    created_at: &str,
+) {
+    let started_at = match status {
+        "queued" => None,
+        _ => Some(created_at),
+    };
+    let finished_at = match status {
+        "queued" | "running" => None,
+        _ => Some(created_at),
+    };
+    sqlx::query(
+            r#"
+            INSERT INTO llm_calls (
+              id,
+              status,
+              source,
+              model,
+              requested_by,
+              parent_task_id,
+              parent_task_type,
+              max_tokens,
+              attempt_count,
+              scheduler_wait_ms,
+              first_token_wait_ms,
+              duration_ms,
+              input_tokens,
+              output_tokens,
+              cached_input_tokens,
+              total_tokens,
+              input_messages_json,
+              output_messages_json,
+              prompt_text,
+              response_text,
+              error_text,
+              created_at,
+              started_at,
+              finished_at,
+              updated_at
+            )
+            VALUES (?, ?, ?, 'gpt-4o-mini', ?, NULL, NULL, 900, 1, 120, 340, 800, 120, 55, 20, 175, '[{"role":"system","content":"s"},{"role":"user","content":"u"}]', '[{"role":"assistant","content":"ok"}]', 'prompt', 'ok', NULL, ?, ?, ?, ?)
+            "#,
+        )
+        .bind(call_id)
+        .bind(status)
+        .bind(source)
+        .bind(requested_by)
+        .bind(created_at)
+        .bind(started_at)
+        .bind(finished_at)
+        .bind(created_at)
+        .execute(pool)
+        .await
+        .expect("seed llm call");
+}
+
+#[test]
+fn has_repo_scope_accepts_comma_delimited_scopes() {
+    assert!(has_repo_scope("read:user,user:email,repo,notifications"));
+}
+
+#[test]
+fn has_repo_scope_accepts_space_delimited_scopes() {
+    assert!(has_repo_scope("read:user user:email repo notifications"));
+}
+
+#[test]
+fn has_repo_scope_rejects_missing_repo_scope() {
+    assert!(!has_repo_scope("read:user,user:email,notifications"));
+}
+
+#[test]
+fn ensure_account_enabled_rejects_disabled_user() {
+    let err = ensure_account_enabled(true).expect_err("disabled user should fail");
+    assert_eq!(err.code(), "account_disabled");
+}
+
+#[test]
+fn guard_admin_user_update_rejects_disabling_self() {
+    let err = guard_admin_user_update(AdminUserUpdateGuard {
+        acting_user_id: test_user_id(7),
+        target_user_id: test_user_id(7),
+        target_is_admin: true,
+        target_is_disabled: false,
+        next_is_admin: true,
+        next_is_disabled: true,
+        admin_count: 2,
+        active_admin_count: 2,
+    })
+    .expect_err("disabling self must fail");
+    assert_eq!(err.code(), "cannot_disable_self");
+}
+
+#[test]
+fn guard_admin_user_update_rejects_demoting_last_admin() {
+    let err = guard_admin_user_update(AdminUserUpdateGuard {
+        acting_user_id: test_user_id(1),
+        target_user_id: test_user_id(2),
+        target_is_admin: true,
+        target_is_disabled: false,
+        next_is_admin: false,
+        next_is_disabled: false,
+        admin_count: 1,
+        active_admin_count: 1,
+    })
+    .expect_err("last admin demotion must fail");
+    assert_eq!(err.code(), "last_admin_guard");
+}
+
+#[test]
+fn guard_admin_user_update_rejects_disabling_last_active_admin() {
+    let err = guard_admin_user_update(AdminUserUpdateGuard {
+        acting_user_id: test_user_id(1),
+        target_user_id: test_user_id(2),
+        target_is_admin: true,
+        target_is_disabled: false,
+        next_is_admin: true,
+        next_is_disabled: true,
+        admin_count: 2,
+        active_admin_count: 1,
+    })
+    .expect_err("last active admin disable must fail");
+    assert_eq!(err.code(), "last_admin_guard");
+}
+
+#[test]
+fn admin_users_offset_supports_first_page() {
+    assert_eq!(admin_users_offset(1, 20).expect("first page offset"), 0);
+}
+
+#[test]
+fn admin_users_offset_rejects_overflow_page() {
+    let err = admin_users_offset(i64::MAX, 100).expect_err("overflow offset must be rejected");
+    assert_eq!(err.code(), "bad_request");
+}
+
+#[test]
+fn job_error_maps_not_found() {
+    let err = ApiError::from(jobs::JobError::NotFound);
+    assert_eq!(err.code(), "not_found");
+    assert_eq!(err.into_response().status(), StatusCode::NOT_FOUND);
+}
+
+#[test]
+fn job_error_maps_invalid_state() {
+    let err = ApiError::from(jobs::JobError::InvalidState {
+        reason: "only finished tasks can be retried",
+    });
+    assert_eq!(err.code(), "invalid_task_state");
+    assert_eq!(err.to_string(), "only finished tasks can be retried");
+    assert_eq!(err.into_response().status(), StatusCode::CONFLICT);
+}
+
+#[test]
+fn job_error_maps_conflict_and_internal() {
+    let err = ApiError::from(jobs::JobError::Conflict);
+    assert_eq!(err.code(), "task_conflict");
+    assert_eq!(err.into_response().status(), StatusCode::CONFLICT);
+
+    let err = ApiError::from(jobs::JobError::from(anyhow::anyhow!("database is locked")));
+    assert_eq!(err.code(), "internal_error");
+    assert_eq!(err.to_string(), "database is locked");
+    assert_eq!(
+        err.into_response().status(),
+        StatusCode::INTERNAL_SERVER_ERROR
+    );
+}
+
+#[tokio::test]
+async fn admin_retry_realtime_task_rejects_missing_and_unfinished_tasks() {
+    let pool = setup_pool().await;
+    sqlx::query(r#"UPDATE users SET is_admin = 1 WHERE id = ?"#)
+        .bind(test_user_id(1))
+        .execute(&pool)
+        .await
+        .expect("promote seeded user to admin");
+    let state = setup_state(pool);
+
+    let err = admin_retry_realtime_task(
+        State(state.clone()),
+        AdminUser(test_user_id(1)),
+        Path(test_user_id(404)),
+    )
+    .await
+    .expect_err("missing task");
+    assert_eq!(err.code(), "not_found");
+
+    let queued = jobs::enqueue_task(
+        state.as_ref(),
+        jobs::NewTask {
+            task_type: jobs::TASK_SYNC_ALL.to_owned(),
+            payload: json!({}),
+            source: "manual".to_owned(),
+            requested_by: None,
+            parent_task_id: None,
+            priority: jobs::TaskPriority::High,
+        },
+    )
+    .await
+    .expect("enqueue task");
+    let err = admin_retry_realtime_task(
+        State(state),
+        AdminUser(test_user_id(1)),
+        Path(queued.task_id),
+    )
+    .await
+    .expect_err("queued task cannot be retried");
+    assert_eq!(err.code(), "invalid_task_state");
+    assert_eq!(err.into_response().status(), StatusCode::CONFLICT);
+}
+
+#[tokio::test]
+async fn admin_list_users_rejects_non_admin_session() {
+    let pool = setup_pool().await;
+    seed_user(&pool, 2, "viewer", 0, 0).await;
+    let state = setup_state(pool);
+
+    let err = extract_user::<AdminUser>(&state, setup_session(2).await)
+        .await
+        .err()
+        .expect("non-admin user should be rejected");
+
+    assert_eq!(err.code(), "forbidden_admin_only");
+}
+
+#[tokio::test]
+async fn admin_list_users_clears_session_for_disabled_user() {
+    let pool = setup_pool().await;
+    sqlx::query(r#"UPDATE users SET is_disabled = 1 WHERE id = ?"#)
+        .bind(test_user_id(1))
+        .execute(&pool)
+        .await
+        .expect("disable seeded admin");
+    let state = setup_state(pool);
+    let session = setup_session(1).await;
+    let probe = session.clone();
+
+    let err = extract_user::<AdminUser>(&state, session)
+        .await
+        .err()
+        .expect("disabled user should be blocked");
+
+    assert_eq!(err.code(), "account_disabled");
+    let remaining = probe
+        .get::<String>("user_id")
+        .await
+        .expect("read session user id");
+    assert!(remaining.is_none(), "disabled session should be cleared");
+}
+
+#[tokio::test]
+async fn admin_list_users_returns_repo_totals_and_include_own_releases() {
+    let pool = setup_pool().await;
+    sqlx::query(r#"UPDATE users SET is_admin = 1 WHERE id = ?"#)
+        .bind(test_user_id(1))
+        .execute(&pool)
+        .await
+        .expect("promote seeded user to admin");
+    seed_star_for_user_with_privacy(&pool, 1, 42, false).await;
+    seed_star_for_user_with_privacy(&pool, 1, 43, true).await;
+    seed_owned_repo_baseline(&pool, 43, "IvanLi-CN/private-owned").await;
+    seed_owned_repo_baseline(&pool, 44, "IvanLi-CN/octo-rill").await;
+    set_include_own_releases(&pool, true).await;
+
+    seed_user(&pool, 2, "viewer", 0, 0).await;
+    seed_star_for_user_with_privacy(&pool, 2, 77, false).await;
+
+    let state = setup_state(pool);
+
+    let Json(response) = admin_list_users(
+        State(state),
+        AdminUser(test_user_id(1)),
+        Query(AdminUsersQuery {
+            query: None,
+            role: None,
+            status: None,
+            page: None,
+            page_size: None,
+            include_stats: None,
+        }),
+    )
+    .await
+    .expect("admin list users should succeed");
+
+    let admin = response
+        .items
+        .iter()
+        .find(|item| item.id == test_user_id(1))
+        .expect("seeded admin item");
+    assert_eq!(admin.repo_total, 3);
+    assert!(admin.include_own_releases);
+
+    let viewer = response
+        .items
+        .iter()
+        .find(|item| item.id == test_user_id(2))
+        .expect("seeded viewer item");
+    assert_eq!(viewer.repo_total, 1);
+    assert!(!viewer.include_own_releases);
+}
+
+async fn seed_admin_user_stats_fixture(pool: &SqlitePool) {
+    sqlx::query(r#"UPDATE users SET is_admin = 1 WHERE id = ?"#)
+        .bind(test_user_id(1))
+        .execute(pool)
+        .await
+        .expect("promote seeded user to admin");
+    seed_user(pool, 2, "heavy", 0, 0).await;
+    let heavy = test_user_id(2);
+
+    seed_star_for_user_with_privacy(pool, 2, 42, false).await;
+    seed_star_for_user_with_privacy(pool, 2, 43, false).await;
+    seed_repo_release(pool, 42, 4201).await;
+    seed_repo_release(pool, 42, 4202).await;
+    seed_repo_release(pool, 43, 4301).await;
+    seed_repo_release(pool, 99, 9901).await;
+    seed_notification_with_timestamp(pool, &heavy, "stats-thread-1", None).await;
+    seed_release_detail_translation(pool, &heavy, "4201", "hash", Some("t"), Some("s")).await;
+    seed_brief(pool, &heavy, "2026-02-22", "brief").await;
+    seed_llm_call(
+        pool,
+        "stats-llm-1",
+        "succeeded",
+        "tests",
+        Some(heavy.clone()),
+    )
+    .await;
+    seed_llm_call(pool, "stats-llm-2", "failed", "tests", Some(heavy.clone())).await;
+    seed_llm_call(
+        pool,
+        "stats-llm-admin",
+        "succeeded",
+        "tests",
+        Some(test_user_id(1)),
+    )
+    .await;
+
+    for (task_id, task_type, status, finished_at) in [
+        (
+            "stats-sync-1",
+            jobs::TASK_SYNC_STARRED,
+            "succeeded",
+            "2026-02-20T00:00:00Z",
+        ),
+        (
+            "stats-sync-2",
+            jobs::TASK_SYNC_STARRED,
+            "succeeded",
+            "2026-02-21T00:00:00Z",
+        ),
+        (
+            "stats-sync-3",
+            jobs::TASK_SYNC_RELEASES,
+            "failed",
+            "2026-02-22T00:00:00Z",
+        ),
+    ] {
+        sqlx::query(
+            r#"
+                INSERT INTO job_tasks (
+                  id, task_type, status, source, requested_by, parent_task_id, payload_json,
+                  cancel_requested, created_at, started_at, finished_at, updated_at
+                )
+                VALUES (?, ?, ?, 'tests', NULL, NULL, ?, 0, ?, ?, ?, ?)
+                "#,
+        )
+        .bind(task_id)
+        .bind(task_type)
+        .bind(status)
+        .bind(json!({ "user_id": heavy }).to_string())
+        .bind(finished_at)
+        .bind(finished_at)
+        .bind(finished_at)
+        .bind(finished_at)
+        .execute(pool)
+        .await
+        .expect("seed sync task");
+    }
+
+    sqlx::query(
+        r#"
+            INSERT INTO reaction_pat_tokens (
+              user_id, token_ciphertext, token_nonce, masked_token, last_check_state, updated_at
+            )
+            VALUES (?, x'00', x'00', 'ghp_****', 'valid', '2026-02-23T00:00:00Z')
+            "#,
+    )
+    .bind(heavy.as_str())
+    .execute(pool)
+    .await
+    .expect("seed reaction pat");
+}
+
+#[tokio::test]
+async fn admin_get_user_stats_reports_seeded_footprint() {
+    let pool = setup_pool().await;
+    seed_admin_user_stats_fixture(&pool).await;
+    let state = setup_state(pool);
+
+    let Json(stats) = admin_get_user_stats(
+        State(state.clone()),
+        AdminUser(test_user_id(1)),
+        Path(test_user_id(2)),
+    )
+    .await
+    .expect("load heavy user stats");
+
+    assert_eq!(stats.starred_repos, 2);
+    assert_eq!(stats.releases, 3);
+    assert_eq!(stats.release_body_bytes, 3 * "- item".len() as i64);
+    assert_eq!(stats.notifications, 1);
+    assert_eq!(stats.ai_translations, 1);
+    assert_eq!(stats.briefs, 1);
+    assert_eq!(stats.llm_calls, 2);
+    assert_eq!(stats.llm_total_tokens, 2 * 175);
+    assert_eq!(
+        stats.last_synced_at,
+        BTreeMap::from([(
+            jobs::TASK_SYNC_STARRED.to_owned(),
+            "2026-02-21T00:00:00Z".to_owned()
+        )])
+    );
+    assert!(stats.reaction_pat_configured);
+
+    let err = extract_user::<AdminUser>(&state, setup_session(2).await)
+        .await
+        .err()
+        .expect("non-admin cannot read stats");
+    assert_eq!(err.code(), "forbidden_admin_only");
+
+    let err = admin_get_user_stats(
+        State(state),
+        AdminUser(test_user_id(1)),
+        Path(crate::local_id::test_local_id("missing-user")),
+    )
+    .await
+    .expect_err("unknown user");
+    assert_eq!(err.code(), "not_found");
+}
+
+#[tokio::test]
+async fn admin_list_users_include_stats_attaches_page_stats() {
+    let pool = setup_pool().await;
+    seed_admin_user_stats_fixture(&pool).await;
+    let state = setup_state(pool);
+    let list = |include_stats: Option<bool>| {
+        let state = state.clone();
+        async move {
+            let Json(response) = admin_list_users(
+                State(state),
+                AdminUser(test_user_id(1)),
+                Query(AdminUsersQuery {
+                    query: None,
+                    role: None,
+                    status: None,
+                    page: None,
+                    page_size: None,
+                    include_stats,
+                }),
+            )
+            .await
+            .expect("admin list users should succeed");
+            response
+        }
+    };
+
+    let plain = list(None).await;
+    assert!(plain.items.iter().all(|item| item.stats.is_none()));
+
+    let with_stats = list(Some(true)).await;
+    let stats_for = |user_id: String| {
+        with_stats
+            .items
+            .iter()
+            .find(|item| item.id == user_id)
+            .and_then(|item| item.stats.clone())
+            .expect("user stats")
+    };
+    let admin = stats_for(test_user_id(1));
+    assert_eq!(admin.starred_repos, 0);
+    assert_eq!(admin.llm_calls, 1);
+    assert!(!admin.reaction_pat_configured);
+    let heavy = stats_for(test_user_id(2));
+    assert_eq!(heavy.releases, 3);
+    assert_eq!(heavy.llm_total_tokens, 2 * 175);
+    assert!(heavy.reaction_pat_configured);
+}
+
+#[tokio::test]
+async fn admin_list_users_excludes_disabled_user_repos_from_effective_pool_totals() {
+    let pool = setup_pool().await;
+    sqlx::query(r#"UPDATE users SET is_admin = 1 WHERE id = ?"#)
+        .bind(test_user_id(1))
+        .execute(&pool)
+        .await
+        .expect("promote seeded user to admin");
+    seed_star_for_user_with_privacy(&pool, 1, 42, false).await;
+    set_include_own_releases(&pool, true).await;
+
+    seed_user(&pool, 2, "disabled-viewer", 0, 0).await;
+    seed_star_for_user_with_privacy(&pool, 2, 77, false).await;
+    sqlx::query(
+        r#"
+            UPDATE users
+            SET is_disabled = 1, updated_at = '2026-02-24T00:00:00Z'
+            WHERE id = ?
+            "#,
+    )
+    .bind(test_user_id(2))
+    .execute(&pool)
+    .await
+    .expect("disable viewer");
+
+    let state = setup_state(pool);
+
+    let Json(response) = admin_list_users(
+        State(state),
+        AdminUser(test_user_id(1)),
+        Query(AdminUsersQuery {
+            query: None,
+            role: None,
+            status: None,
+            page: None,
+            page_size: None,
+            include_stats: None,
+        }),
+    )
+    .await
+    .expect("admin list users should succeed");
+
+    let disabled_user = response
+        .items
+        .iter()
+        .find(|item| item.id == test_user_id(2))
+        .expect("disabled viewer item");
+    assert_eq!(disabled_user.repo_total, 0);
+    assert!(disabled_user.is_disabled);
+}
+
+struct RepoGovernanceSnapshotSeed<'a> {
+    repo_id: i64,
+    repo_full_name: &'a str,
+    priority_rank: i64,
+    target_window: i64,
+    urgency_score: f64,
+    urgency_bucket: &'a str,
+    actual_last_success_at: Option<&'a str>,
+}
+
+async fn seed_repo_governance_snapshot(pool: &SqlitePool, seed: RepoGovernanceSnapshotSeed<'_>) {
+    sqlx::query(
+            r#"
+            INSERT INTO repo_refresh_governance_snapshots (
+              repo_id,
+              repo_full_name,
+              is_private,
+              watcher_user_count,
+              watcher_repo_total_sum,
+              cached_stargazer_count,
+              priority_rank,
+              target_window,
+              target_interval_minutes,
+              urgency_score,
+              urgency_bucket,
+              system_last_selected_at,
+              system_last_success_at,
+              actual_last_success_at,
+              actual_last_success_source,
+              updated_at
+            )
+            VALUES (?, ?, 0, 1, 1, NULL, ?, ?, ?, ?, ?, NULL, NULL, ?, 'system', '2026-02-24T00:00:00Z')
+            "#,
+        )
+        .bind(seed.repo_id)
+        .bind(seed.repo_full_name)
+        .bind(seed.priority_rank)
+        .bind(seed.target_window)
+        .bind(seed.target_window * 10)
+        .bind(seed.urgency_score)
+        .bind(seed.urgency_bucket)
+        .bind(seed.actual_last_success_at)
+        .execute(pool)
+        .await
+        .expect("seed repo governance snapshot");
+}
+
+#[tokio::test]
+async fn admin_list_repo_governance_filters_target_windows_and_urgency_range() {
+    let pool = setup_pool().await;
+    sqlx::query(r#"UPDATE users SET is_admin = 1 WHERE id = ?"#)
+        .bind(test_user_id(1))
+        .execute(&pool)
+        .await
+        .expect("promote seeded user to admin");
+    seed_repo_governance_snapshot(
+        &pool,
+        RepoGovernanceSnapshotSeed {
+            repo_id: 1,
+            repo_full_name: "owner/match-window-one",
+            priority_rank: 1,
+            target_window: 1,
+            urgency_score: 3.5,
+            urgency_bucket: "critical",
+            actual_last_success_at: Some("2026-02-20T00:00:00Z"),
+        },
+    )
+    .await;
+    seed_repo_governance_snapshot(
+        &pool,
+        RepoGovernanceSnapshotSeed {
+            repo_id: 2,
+            repo_full_name: "owner/outside-urgency",
+            priority_rank: 2,
+            target_window: 1,
+            urgency_score: 1.2,
+            urgency_bucket: "healthy",
+            actual_last_success_at: Some("2026-02-20T00:00:00Z"),
+        },
+    )
+    .await;
+    seed_repo_governance_snapshot(
+        &pool,
+        RepoGovernanceSnapshotSeed {
+            repo_id: 3,
+            repo_full_name: "owner/match-window-three",
+            priority_rank: 3,
+            target_window: 3,
+            urgency_score: 2.2,
+            urgency_bucket: "due",
+            actual_last_success_at: Some("2026-02-20T00:00:00Z"),
+        },
+    )
+    .await;
+    seed_repo_governance_snapshot(
+        &pool,
+        RepoGovernanceSnapshotSeed {
+            repo_id: 4,
+            repo_full_name: "owner/outside-window",
+            priority_rank: 4,
+            target_window: 4,
+            urgency_score: 3.8,
+            urgency_bucket: "critical",
+            actual_last_success_at: Some("2026-02-20T00:00:00Z"),
+        },
+    )
+    .await;
+
+    let state = setup_state(pool);
+
+    let Json(response) = admin_list_repo_governance(
+        State(state),
+        AdminUser(test_user_id(1)),
+        Query(AdminRepoGovernanceListQuery {
+            page: None,
+            page_size: Some(10),
+            query: Some("owner/".to_owned()),
+            aging: Some("all".to_owned()),
+            target_windows: Some("1,3".to_owned()),
+            urgency_min: Some(2.0),
+            urgency_max: Some(4.0),
+        }),
+    )
+    .await
+    .expect("admin repo governance list should succeed");
+
+    assert_eq!(response.total, 2);
+    assert_eq!(
+        response
+            .items
+            .iter()
+            .map(|item| item.repo_full_name.as_str())
+            .collect::<Vec<_>>(),
+        vec!["owner/match-window-one", "owner/match-window-three"]
+    );
+    assert_eq!(
+        response
+            .target_window_options
+            .iter()
+            .map(|option| (option.target_window, option.repo_count))
+            .collect::<Vec<_>>(),
+        vec![(1, 2), (3, 1), (4, 1)]
+    );
+}
+
+#[tokio::test]
+async fn admin_list_repo_governance_rejects_invalid_urgency_range() {
+    let pool = setup_pool().await;
+    sqlx::query(r#"UPDATE users SET is_admin = 1 WHERE id = ?"#)
+        .bind(test_user_id(1))
+        .execute(&pool)
+        .await
+        .expect("promote seeded user to admin");
+    let state = setup_state(pool);
+
+    let err = admin_list_repo_governance(
+        State(state),
+        AdminUser(test_user_id(1)),
+        Query(AdminRepoGovernanceListQuery {
+            page: None,
+            page_size: None,
+            query: None,
+            aging: Some("all".to_owned()),
+            target_windows: Some("1,3".to_owned()),
+            urgency_min: Some(4.0),
+            urgency_max: Some(2.0),
+        }),
+    )
+    .await
+    .expect_err("invalid urgency range should fail");
+
+    assert_eq!(err.code(), "bad_request");
+}
+
+#[tokio::test]
+async fn admin_patch_user_rejects_demoting_last_admin_via_handler() {
+    let pool = setup_pool().await;
+    sqlx::query(r#"UPDATE users SET is_admin = 1 WHERE id = ?"#)
+        .bind(test_user_id(1))
+        .execute(&pool)
+        .await
+        .expect("promote seeded user to admin");
+    let state = setup_state(pool);
+
+    let err = admin_patch_user(
+        State(state),
+        AdminUser(test_user_id(1)),
+        Path(test_user_id(1)),
+        ApiJson(AdminUserPatchRequest {
+            is_admin: Some(false),
+            is_disabled: None,
+        }),
+    )
+    .await
+    .expect_err("last admin demotion should fail");
+
+    assert_eq!(err.code(), "last_admin_guard");
+}
+
+#[tokio::test]
+async fn admin_patch_user_returns_repo_totals_and_include_own_releases() {
+    let pool = setup_pool().await;
+    sqlx::query(r#"UPDATE users SET is_admin = 1 WHERE id = ?"#)
+        .bind(test_user_id(1))
+        .execute(&pool)
+        .await
+        .expect("promote seeded user to admin");
+    seed_star_for_user_with_privacy(&pool, 1, 42, false).await;
+    seed_owned_repo_baseline(&pool, 42, "IvanLi-CN/octo-rill").await;
+    set_include_own_releases(&pool, true).await;
+
+    seed_user(&pool, 2, "viewer", 0, 0).await;
+    seed_star_for_user_with_privacy(&pool, 2, 77, false).await;
+
+    let state = setup_state(pool);
+
+    let Json(updated) = admin_patch_user(
+        State(state),
+        AdminUser(test_user_id(1)),
+        Path(test_user_id(2)),
+        ApiJson(AdminUserPatchRequest {
+            is_admin: Some(true),
+            is_disabled: None,
+        }),
+    )
+    .await
+    .expect("admin patch should succeed");
+
+    assert_eq!(updated.id, test_user_id(2));
+    assert_eq!(updated.repo_total, 1);
+    assert!(!updated.include_own_releases);
+    assert!(updated.is_admin);
+}
+
+#[tokio::test]
+async fn admin_patch_user_writes_audit_row_with_before_and_after_flags() {
+    let pool = setup_pool().await;
+    sqlx::query(r#"UPDATE users SET is_admin = 1 WHERE id = ?"#)
+        .bind(test_user_id(1))
+        .execute(&pool)
+        .await
+        .expect("promote seeded user to admin");
+    seed_user(&pool, 2, "viewer", 0, 0).await;
+    seed_user(&pool, 3, "bystander", 0, 0).await;
+    let state = setup_state(pool);
+
+    for target in [2, 3] {
+        let Json(patched) = admin_patch_user(
+            State(state.clone()),
+            AdminUser(test_user_id(1)),
+            Path(test_user_id(target)),
+            ApiJson(AdminUserPatchRequest {
+                is_admin: Some(true),
+                is_disabled: Some(true),
+            }),
+        )
+        .await
+        .expect("admin patch should succeed");
+        assert!(patched.is_disabled);
+    }
+
+    let Json(audit) = admin_audit::admin_list_audit(
+        State(state),
+        AdminUser(test_user_id(1)),
+        Query(
+            serde_json::from_value(json!({ "target_user_id": test_user_id(2) }))
+                .expect("audit query"),
+        ),
+    )
+    .await
+    .expect("list audit");
+
+    assert_eq!(audit.total, 1);
+    let entry = &audit.items[0];
+    assert_eq!(entry.acting_user_id, test_user_id(1));
+    assert_eq!(entry.target_login.as_deref(), Some("viewer"));
+    assert_eq!(entry.action, admin_audit::ADMIN_AUDIT_USER_UPDATE);
+    assert_eq!(
+        serde_json::from_str::<Value>(&entry.before_json).expect("before json"),
+        json!({ "is_admin": false, "is_disabled": false })
+    );
+    assert_eq!(
+        serde_json::from_str::<Value>(&entry.after_json).expect("after json"),
+        json!({ "is_admin": true, "is_disabled": true })
+    );
+}
+
+#[tokio::test]
+async fn admin_bulk_update_users_rolls_back_when_demoting_every_admin() {
+    let pool = setup_pool().await;
+    sqlx::query(r#"UPDATE users SET is_admin = 1 WHERE id = ?"#)
+        .bind(test_user_id(1))
+        .execute(&pool)
+        .await
+        .expect("promote seeded user to admin");
+    seed_user(&pool, 2, "second-admin", 1, 0).await;
+    seed_user(&pool, 3, "third-admin", 1, 0).await;
+    let state = setup_state(pool.clone());
+
+    let err = admin_bulk_update_users(
+        State(state),
+        AdminUser(test_user_id(1)),
+        ApiJson(AdminUserBulkRequest {
+            user_ids: vec![test_user_id(2), test_user_id(3), test_user_id(1)],
+            is_admin: Some(false),
+            is_disabled: None,
+        }),
+    )
+    .await
+    .expect_err("demoting every admin should fail");
+
+    assert_eq!(err.code(), "last_admin_guard");
+    assert!(
+        err.to_string().contains(test_user_id(1).as_str()),
+        "unexpected error: {err}"
+    );
+    let admins = sqlx::query_scalar::<_, i64>(r#"SELECT COUNT(*) FROM users WHERE is_admin = 1"#)
+        .fetch_one(&pool)
+        .await
+        .expect("count admins");
+    assert_eq!(admins, 3, "earlier demotions in the batch roll back");
+    let audit_rows = sqlx::query_scalar::<_, i64>(r#"SELECT COUNT(*) FROM admin_audit_log"#)
+        .fetch_one(&pool)
+        .await
+        .expect("count audit rows");
+    assert_eq!(audit_rows, 0);
+}
+
+#[tokio::test]
+async fn admin_bulk_update_users_applies_mixed_enable_and_disable() {
+    let pool = setup_pool().await;
+    sqlx::query(r#"UPDATE users SET is_admin = 1 WHERE id = ?"#)
+        .bind(test_user_id(1))
+        .execute(&pool)
+        .await
+        .expect("promote seeded user to admin");
+    seed_user(&pool, 2, "already-disabled", 0, 1).await;
+    seed_user(&pool, 3, "enabled", 0, 0).await;
+    let state = setup_state(pool);
+    let bulk = |is_disabled: bool| {
+        let state = state.clone();
+        async move {
+            admin_bulk_update_users(
+                State(state),
+                AdminUser(test_user_id(1)),
+                ApiJson(AdminUserBulkRequest {
+                    user_ids: vec![test_user_id(2), test_user_id(3), test_user_id(2)],
+                    is_admin: None,
+                    is_disabled: Some(is_disabled),
+                }),
+            )
+            .await
+            .expect("bulk update should succeed")
+            .0
+            .items
+        }
+    };
+
+    let disabled = bulk(true).await;
+    assert_eq!(
+        disabled
+            .iter()
+            .map(|item| (item.id.clone(), item.is_disabled))
+            .collect::<Vec<_>>(),
+        vec![(test_user_id(2), true), (test_user_id(3), true)]
+    );
+    let enabled = bulk(false).await;
+    assert!(enabled.iter().all(|item| !item.is_disabled));
+    assert_eq!(enabled.len(), 2);
+}
+
+#[tokio::test]
+async fn admin_bulk_update_users_limits_batch_size() {
+    let pool = setup_pool().await;
+    sqlx::query(r#"UPDATE users SET is_admin = 1 WHERE id = ?"#)
+        .bind(test_user_id(1))
+        .execute(&pool)
+        .await
+        .expect("promote seeded user to admin");
+    let state = setup_state(pool);
+
+    let err = admin_bulk_update_users(
+        State(state),
+        AdminUser(test_user_id(1)),
+        ApiJson(AdminUserBulkRequest {
+            user_ids: (0..101).map(test_user_id).collect(),
+            is_admin: None,
+            is_disabled: Some(true),
+        }),
+    )
+    .await
+    .expect_err("oversized batch should fail");
+
+    assert_eq!(err.code(), "bad_request");
+}
+
+#[tokio::test]
+async fn admin_patch_user_rate_limits_each_admin() {
+    let pool = setup_pool().await;
+    sqlx::query(r#"UPDATE users SET is_admin = 1 WHERE id = ?"#)
+        .bind(test_user_id(1))
+        .execute(&pool)
+        .await
+        .expect("promote seeded user to admin");
+    seed_user(&pool, 2, "viewer", 0, 0).await;
+    let state = setup_state(pool);
+    let patch = |is_disabled: bool| {
+        let state = state.clone();
+        async move {
+            admin_patch_user(
+                State(state),
+                AdminUser(test_user_id(1)),
+                Path(test_user_id(2)),
+                ApiJson(AdminUserPatchRequest {
+                    is_admin: None,
+                    is_disabled: Some(is_disabled),
+                }),
+            )
+            .await
+        }
+    };
+
+    for round in 0..admin_audit::ADMIN_MUTATIONS_PER_WINDOW {
+        let Json(patched) = patch(round % 2 == 0).await.expect("within rate limit");
+        assert_eq!(patched.is_disabled, round % 2 == 0);
+    }
+    let err = patch(false).await.expect_err("rate limit exceeded");
+
+    assert_eq!(err.code(), "admin_rate_limited");
+    assert_eq!(err.into_response().status(), StatusCode::TOO_MANY_REQUESTS);
+}
+
+#[tokio::test]
+async fn admin_enqueue_user_task_rejects_non_admin_session() {
+    let pool = setup_pool().await;
+    seed_user(&pool, 2, "viewer", 0, 0).await;
+    let state = setup_state(pool);
+
+    let err = extract_user::<AdminUser>(&state, setup_session(2).await)
+        .await
+        .err()
+        .expect("non-admin user should be rejected");
+
+    assert_eq!(err.code(), "forbidden_admin_only");
+}
+
+#[tokio::test]
+async fn admin_enqueue_user_task_targets_user_and_records_acting_admin() {
+    let pool = setup_pool().await;
+    sqlx::query(r#"UPDATE users SET is_admin = 1 WHERE id = ?"#)
+        .bind(test_user_id(1))
+        .execute(&pool)
+        .await
+        .expect("promote seeded user to admin");
+    seed_user(&pool, 2, "viewer", 0, 0).await;
+    let state = setup_state(pool);
+
+    let Json(created) = admin_enqueue_user_task(
+        State(state.clone()),
+        AdminUser(test_user_id(1)),
+        Path(test_user_id(2)),
+        ApiJson(AdminUserTaskRequest {
+            task_type: jobs::TASK_BRIEF_GENERATE.to_owned(),
+        }),
+    )
+    .await
+    .expect("enqueue admin user task");
+
+    assert_eq!(created.status, jobs::STATUS_QUEUED);
+    assert_eq!(created.user_id, test_user_id(2));
+    let row = sqlx::query(
+        r#"
+            SELECT task_type, source, requested_by, payload_json
+            FROM job_tasks
+            WHERE id = ?
+            "#,
+    )
+    .bind(created.task_id.as_str())
+    .fetch_one(&state.pool)
+    .await
+    .expect("load created task");
+    assert_eq!(row.get::<String, _>("task_type"), jobs::TASK_BRIEF_GENERATE);
+    assert_eq!(row.get::<String, _>("source"), "admin.manual");
+    assert_eq!(
+        row.get::<Option<String>, _>("requested_by"),
+        Some(test_user_id(1))
+    );
+    let payload: Value =
+        serde_json::from_str(&row.get::<String, _>("payload_json")).expect("parse payload");
+    assert_eq!(payload["user_id"], json!(test_user_id(2)));
+}
+
+#[tokio::test]
+async fn admin_enqueue_user_task_rejects_unknown_type_and_disabled_target() {
+    let pool = setup_pool().await;
+    sqlx::query(r#"UPDATE users SET is_admin = 1 WHERE id = ?"#)
+        .bind(test_user_id(1))
+        .execute(&pool)
+        .await
+        .expect("promote seeded user to admin");
+    seed_user(&pool, 2, "disabled-viewer", 0, 1).await;
+    let state = setup_state(pool);
+
+    let err = admin_enqueue_user_task(
+        State(state.clone()),
+        AdminUser(test_user_id(1)),
+        Path(test_user_id(1)),
+        ApiJson(AdminUserTaskRequest {
+            task_type: "sync.everything".to_owned(),
+        }),
+    )
+    .await
+    .expect_err("unknown task type should fail");
+    assert_eq!(err.code(), "bad_request");
+
+    let err = admin_enqueue_user_task(
+        State(state),
+        AdminUser(test_user_id(1)),
+        Path(test_user_id(2)),
+        ApiJson(AdminUserTaskRequest {
+            task_type: jobs::TASK_SYNC_RELEASES.to_owned(),
+        }),
+    )
+    .await
+    .expect_err("disabled target should fail");
+    assert_eq!(err.code(), "target_user_disabled");
+}
+
+#[tokio::test]
+async fn admin_list_llm_calls_rejects_non_admin_session() {
+    let pool = setup_pool().await;
+    seed_user(&pool, 2, "viewer", 0, 0).await;
+    let state = setup_state(pool);
+
+    let err = extract_user::<AdminUser>(&state, setup_session(2).await)
+        .await
+        .err()
+        .expect("non-admin user should be rejected");
+
+    assert_eq!(err.code(), "forbidden_admin_only");
+}
+
+#[tokio::test]
+async fn admin_list_llm_calls_filters_status_and_source() {
+    let pool = setup_pool().await;
+    sqlx::query(r#"UPDATE users SET is_admin = 1 WHERE id = ?"#)
+        .bind(test_user_id(1))
+        .execute(&pool)
+        .await
+        .expect("promote seeded user to admin");
+    seed_llm_call(
+        &pool,
+        "call-failed",
+        "failed",
+        "api.translate_releases_batch",
+        Some(test_user_id(1)),
+    )
+    .await;
+    seed_llm_call(
+        &pool,
+        "call-ok",
+        "succeeded",
+        "api.translate_release",
+        Some(test_user_id(1)),
+    )
+    .await;
+
+    let state = setup_state(pool);
+
+    let resp = admin_list_llm_calls(
+        State(state),
+        AdminUser(test_user_id(1)),
+        Query(AdminLlmCallsQuery {
+            status: Some("failed".to_owned()),
+            source: Some("api.translate_releases_batch".to_owned()),
+            requested_by: Some(test_user_id(1)),
+            parent_task_id: None,
+            request_id: None,
+            started_from: None,
+            started_to: None,
+            sort: Some("status_grouped".to_owned()),
+            page: Some(1),
+            page_size: Some(20),
+        }),
+    )
+    .await
+    .expect("admin llm call list should pass")
+    .0;
+
+    assert_eq!(resp.total, 1);
+    assert_eq!(resp.items.len(), 1);
+    assert_eq!(resp.items[0].id, "call-failed");
+    assert_eq!(resp.items[0].status, "failed");
+}
+
+#[tokio::test]
+async fn admin_list_realtime_tasks_keeps_newest_created_first() {
+    let pool = setup_pool().await;
+    sqlx::query(r#"UPDATE users SET is_admin = 1 WHERE id = ?"#)
+        .bind(test_user_id(1))
+        .execute(&pool)
+        .await
+        .expect("promote seeded user to admin");
+    for (task_id, status, task_type, result_json, created_at) in [
+        (
+            "task-old-running",
+            "running",
+            "sync.releases",
+            "{}",
+            "2026-02-26T01:00:00Z",
+        ),
+        (
+            "task-new-failed",
+            "failed",
+            "sync.releases",
+            "{}",
+            "2026-02-26T05:00:00Z",
+        ),
+        (
+            "task-mid-succeeded",
+            "succeeded",
+            "sync.releases",
+            "{}",
+            "2026-02-26T03:00:00Z",
+        ),
+        (
+            "task-skipped-subscriptions",
+            "succeeded",
+            jobs::TASK_SYNC_SUBSCRIPTIONS,
+            r#"{"skipped":true,"skip_reason":"previous_run_active"}"#,
+            "2026-02-26T04:00:00Z",
+        ),
+    ] {
+        sqlx::query(
+            r#"
+                INSERT INTO job_tasks (
+                  id,
+                  task_type,
+                  status,
+                  source,
+                  requested_by,
+                  parent_task_id,
+                  payload_json,
+                  result_json,
+                  error_message,
+                  cancel_requested,
+                  created_at,
+                  started_at,
+                  finished_at,
+                  updated_at
+                )
+                VALUES (?, ?, ?, 'tests', ?, NULL, '{}', ?, NULL, 0, ?, ?, ?, ?)
+                "#,
+        )
+        .bind(task_id)
+        .bind(task_type)
+        .bind(status)
+        .bind(test_user_id(1))
+        .bind(result_json)
+        .bind(created_at)
+        .bind(created_at)
+        .bind(created_at)
+        .bind(created_at)
+        .execute(&pool)
+        .await
+        .expect("seed realtime task");
+    }
+
+    let state = setup_state(pool);
+
+    let resp = admin_list_realtime_tasks(
+        State(state),
+        AdminUser(test_user_id(1)),
+        Query(AdminRealtimeTasksQuery {
+            status: Some("all".to_owned()),
+            task_type: None,
+            exclude_task_type: None,
+            task_group: None,
+            priority: None,
+            request_id: None,
+            page: Some(1),
+            page_size: Some(20),
+        }),
+    )
+    .await
+    .expect("admin realtime task list should succeed")
+    .0;
+
+    let ids = resp
+        .items
+        .iter()
+        .map(|item| item.id.as_str())
+        .collect::<Vec<_>>();
+    assert_eq!(
+        ids,
+        vec![
+            "task-new-failed",
+            "task-skipped-subscriptions",
+            "task-mid-succeeded",
+            "task-old-running"
+        ]
+    );
+    let skipped_item = resp
+        .items
+        .iter()
+        .find(|item| item.id == "task-skipped-subscriptions")
+        .expect("skipped subscription task item");
+    assert!(skipped_item.skipped);
+}
+
+#[tokio::test]
+async fn admin_list_realtime_tasks_filters_by_priority() {
+    let pool = setup_pool().await;
+    sqlx::query(r#"UPDATE users SET is_admin = 1 WHERE id = ?"#)
+        .bind(test_user_id(1))
+        .execute(&pool)
+        .await
+        .expect("promote seeded user to admin");
+    for (task_id, priority) in [("task-high", "high"), ("task-low", "low")] {
+        sqlx::query(
+            r#"
+                INSERT INTO job_tasks (
+                  id, task_type, status, source, payload_json, priority, created_at, updated_at
+                )
+                VALUES (?, ?, 'queued', 'tests', '{}', ?, '2026-02-26T00:00:00Z',
+                  '2026-02-26T00:00:00Z')
+                "#,
+        )
+        .bind(task_id)
+        .bind(jobs::TASK_SYNC_STARRED)
+        .bind(priority)
+        .execute(&pool)
+        .await
+        .expect("seed prioritized task");
+    }
+    let state = setup_state(pool);
+    let query = |priority: &str| {
+        Query(AdminRealtimeTasksQuery {
+            status: None,
+            task_type: None,
+            exclude_task_type: None,
+            task_group: None,
+            priority: Some(priority.to_owned()),
+            request_id: None,
+            page: None,
+            page_size: None,
+        })
+    };
+
+    let resp = admin_list_realtime_tasks(
+        State(state.clone()),
+        AdminUser(test_user_id(1)),
+        query("high"),
+    )
+    .await
+    .expect("filter by priority")
+    .0;
+    assert_eq!(resp.total, 1);
+    assert_eq!(resp.items[0].id, "task-high");
+    assert_eq!(resp.items[0].priority, "high");
+
+    let err = admin_list_realtime_tasks(State(state), AdminUser(test_user_id(1)), query("urgent"))
+        .await
+        .expect_err("unknown priority should be rejected");
+    assert_eq!(err.code(), "bad_request");
+}
+
+#[tokio::test]
+async fn admin_list_realtime_tasks_attaches_subscription_partial_diagnostics() {
+    let pool = setup_pool().await;
+    sqlx::query(r#"UPDATE users SET is_admin = 1 WHERE id = ?"#)
+        .bind(test_user_id(1))
+        .execute(&pool)
+        .await
+        .expect("promote seeded user to admin");
+    sqlx::query(
+            r#"
+            INSERT INTO job_tasks (
+              id,
+              task_type,
+              status,
+              source,
+              requested_by,
+              parent_task_id,
+              payload_json,
+              result_json,
+              error_message,
+              cancel_requested,
+              created_at,
+              started_at,
+              finished_at,
+              updated_at
+            )
+            VALUES (?, ?, 'succeeded', 'tests', ?, NULL, ?, ?, NULL, 0, ?, ?, ?, ?)
+            "#,
+        )
+        .bind("task-subscription-partial")
+        .bind(jobs::TASK_SYNC_SUBSCRIPTIONS)
+        .bind(test_user_id(1))
+        .bind(r#"{"trigger":"schedule","schedule_key":"2026-03-06T14:30"}"#)
+        .bind(
+            r#"{"skipped":false,"star":{"total_users":12,"succeeded_users":12,"failed_users":0,"total_repos":340},"release":{"total_repos":128,"succeeded_repos":120,"failed_repos":8,"candidate_failures":4,"fetched_count":900,"inserted_count":12,"updated_count":3,"unchanged_count":885,"pages_fetched":128},"social":{"total_users":12,"succeeded_users":11,"failed_users":1,"repo_stars":20,"followers":5,"events":44},"notifications":{"total_users":12,"succeeded_users":11,"failed_users":1,"notifications":17},"releases_written":900,"critical_events":0}"#,
+        )
+        .bind("2026-03-06T14:30:00Z")
+        .bind("2026-03-06T14:30:01Z")
+        .bind("2026-03-06T14:37:00Z")
+        .bind("2026-03-06T14:37:00Z")
+        .execute(&pool)
+        .await
+        .expect("seed partial subscription task");
+
+    let state = setup_state(pool);
+    let resp = admin_list_realtime_tasks(
+        State(state),
+        AdminUser(test_user_id(1)),
+        Query(AdminRealtimeTasksQuery {
+            status: Some("all".to_owned()),
+            task_type: Some(jobs::TASK_SYNC_SUBSCRIPTIONS.to_owned()),
+            exclude_task_type: None,
+            task_group: None,
+            priority: None,
+            request_id: None,
+            page: Some(1),
+            page_size: Some(20),
+        }),
+    )
+    .await
+    .expect("admin realtime task list should succeed")
+    .0;
+
+    let item = resp
+        .items
+        .iter()
+        .find(|item| item.id == "task-subscription-partial")
+        .expect("subscription task item");
+    let diagnostics = item.diagnostics.as_ref().expect("list diagnostics");
+    assert_eq!(diagnostics.business_outcome.code, "partial");
+    let sync = diagnostics
+        .sync_subscriptions
+        .as_ref()
+        .expect("sync subscriptions diagnostics");
+    assert_eq!(sync.release.failed_repos, 8);
+    assert_eq!(sync.social.failed_users, 1);
+    assert_eq!(sync.notifications.failed_users, 1);
+}
+
+#[tokio::test]
+async fn refresh_admin_dashboard_rollups_backfills_recent_rows() {
+    let pool = setup_pool().await;
+    sqlx::query(r#"UPDATE users SET is_admin = 1 WHERE id = ?"#)
+        .bind(test_user_id(1))
+        .execute(&pool)
+        .await
+        .expect("promote seeded user to admin");
+    seed_user(&pool, 2, "operator", 0, 0).await;
+
+    let time_zone = chrono_tz::Asia::Shanghai;
+    let now_local = chrono::Utc::now().with_timezone(&time_zone);
+    let today_local = now_local.date_naive();
+    let task_time = time_zone
+        .with_ymd_and_hms(
+            today_local.year(),
+            today_local.month(),
+            today_local.day(),
+            9,
+            0,
+            0,
+        )
+        .single()
+        .expect("build local dashboard task time")
+        .with_timezone(&chrono::Utc)
+        .to_rfc3339();
+
+    set_last_active_at(&pool, test_user_id(1).as_str(), Some(task_time.as_str())).await;
+    set_last_active_at(&pool, test_user_id(2).as_str(), Some(task_time.as_str())).await;
+
+    seed_admin_dashboard_task(
+        &pool,
+        "dashboard-translate-queued",
+        jobs::TASK_TRANSLATE_RELEASE_BATCH,
+        jobs::STATUS_QUEUED,
+        test_user_id(1).as_str(),
+        task_time.as_str(),
+    )
+    .await;
+    seed_admin_dashboard_task(
+        &pool,
+        "dashboard-summary-running",
+        jobs::TASK_SUMMARIZE_RELEASE_SMART_BATCH,
+        jobs::STATUS_RUNNING,
+        test_user_id(1).as_str(),
+        task_time.as_str(),
+    )
+    .await;
+    seed_admin_dashboard_task(
+        &pool,
+        "dashboard-summary-failed",
+        jobs::TASK_SUMMARIZE_RELEASE_SMART_BATCH,
+        jobs::STATUS_FAILED,
+        test_user_id(1).as_str(),
+        task_time.as_str(),
+    )
+    .await;
+    seed_admin_dashboard_task(
+        &pool,
+        "dashboard-brief-succeeded",
+        jobs::TASK_BRIEF_DAILY_SLOT,
+        jobs::STATUS_SUCCEEDED,
+        test_user_id(2).as_str(),
+        task_time.as_str(),
+    )
+    .await;
+
+    let state = setup_state(pool.clone());
+    refresh_admin_dashboard_rollups(state.as_ref(), ADMIN_DASHBOARD_PREAGGREGATE_DAYS)
+        .await
+        .expect("dashboard rollup refresh should succeed");
+
+    let today_rollup_rows = sqlx::query_scalar::<_, i64>(
+        r#"
+            SELECT COUNT(*)
+            FROM admin_dashboard_daily_rollups
+            WHERE time_zone = 'Asia/Shanghai'
+              AND rollup_date = ?
+            "#,
+    )
+    .bind(today_local.format("%Y-%m-%d").to_string())
+    .fetch_one(&pool)
+    .await
+    .expect("count persisted dashboard rollups");
+    assert_eq!(today_rollup_rows, 3);
+}
+
+#[tokio::test]
+async fn admin_dashboard_uses_today_live_overlay_and_window_switch() {
+    let pool = setup_pool().await;
+    sqlx::query(r#"UPDATE users SET is_admin = 1 WHERE id = ?"#)
+        .bind(test_user_id(1))
+        .execute(&pool)
+        .await
+        .expect("promote seeded user to admin");
+    seed_user(&pool, 2, "operator", 0, 0).await;
+
+    let time_zone = chrono_tz::Asia::Shanghai;
+    let now_local = chrono::Utc::now().with_timezone(&time_zone);
+    let today_local = now_local.date_naive();
+    let yesterday_local = today_local
+        .pred_opt()
+        .expect("build previous dashboard day");
+    let yesterday_time = time_zone
+        .with_ymd_and_hms(
+            yesterday_local.year(),
+            yesterday_local.month(),
+            yesterday_local.day(),
+            9,
+            0,
+            0,
+        )
+        .single()
+        .expect("build yesterday task time")
+        .with_timezone(&chrono::Utc)
+        .to_rfc3339();
+    let today_window_start = time_zone
+        .with_ymd_and_hms(
+            today_local.year(),
+            today_local.month(),
+            today_local.day(),
+            0,
+            0,
+            0,
+        )
+        .single()
+        .expect("build today window start");
+    let safe_today_local = today_window_start + (now_local - today_window_start) / 2;
+    let today_time = safe_today_local.with_timezone(&chrono::Utc).to_rfc3339();
+
+    set_last_active_at(&pool, test_user_id(1).as_str(), Some(today_time.as_str())).await;
+    set_last_active_at(&pool, test_user_id(2).as_str(), Some(today_time.as_str())).await;
+
+    seed_admin_dashboard_task(
+        &pool,
+        "dashboard-yesterday-translate",
+        jobs::TASK_TRANSLATE_RELEASE_BATCH,
+        jobs::STATUS_SUCCEEDED,
+        test_user_id(1).as_str(),
+        yesterday_time.as_str(),
+    )
+    .await;
+    seed_admin_dashboard_task(
+        &pool,
+        "dashboard-today-translate",
+        jobs::TASK_TRANSLATE_RELEASE_BATCH,
+        jobs::STATUS_QUEUED,
+        test_user_id(1).as_str(),
+        today_time.as_str(),
+    )
+    .await;
+    seed_admin_dashboard_task(
+        &pool,
+        "dashboard-today-summary",
+        jobs::TASK_SUMMARIZE_RELEASE_SMART_BATCH,
+        jobs::STATUS_RUNNING,
+        test_user_id(1).as_str(),
+        today_time.as_str(),
+    )
+    .await;
+    seed_admin_dashboard_task(
+        &pool,
+        "dashboard-today-brief",
+        jobs::TASK_BRIEF_DAILY_SLOT,
+        jobs::STATUS_SUCCEEDED,
+        test_user_id(2).as_str(),
+        today_time.as_str(),
+    )
+    .await;
+
+    sqlx::query(
+        r#"
+            INSERT INTO admin_dashboard_daily_rollups (
+              rollup_date,
+              time_zone,
+              task_type,
+              total_users,
+              active_users,
+              queued_count,
+              running_count,
+              succeeded_count,
+              failed_count,
+              canceled_count,
+              updated_at
+            )
+            VALUES (?, 'Asia/Shanghai', ?, 2, 0, 9, 0, 0, 0, 0, ?)
+            "#,
+    )
+    .bind(today_local.format("%Y-%m-%d").to_string())
+    .bind(jobs::TASK_TRANSLATE_RELEASE_BATCH)
+    .bind(today_time.as_str())
+    .execute(&pool)
+    .await
+    .expect("seed stale today rollup");
+
+    let state = setup_state(pool.clone());
+    refresh_admin_dashboard_rollups(state.as_ref(), ADMIN_DASHBOARD_PREAGGREGATE_DAYS)
+        .await
+        .expect("dashboard rollup refresh should succeed");
+    let Json(resp) = admin_dashboard(
+        State(state),
+        AdminUser(test_user_id(1)),
+        Query(AdminDashboardQuery {
+            window: Some("30d".to_owned()),
+        }),
+    )
+    .await
+    .expect("admin dashboard should succeed");
+
+    let expected_active_users_today = sqlx::query_scalar::<_, i64>(
+        r#"
+            SELECT COUNT(*)
+            FROM users
+            WHERE last_active_at IS NOT NULL
+              AND julianday(last_active_at) >= julianday(?)
+              AND julianday(last_active_at) <= julianday(?)
+            "#,
+    )
+    .bind(today_window_start.with_timezone(&chrono::Utc).to_rfc3339())
+    .bind(resp.generated_at.as_str())
+    .fetch_one(&pool)
+    .await
+    .expect("count expected active users for response window");
+
+    assert_eq!(resp.time_zone, "Asia/Shanghai");
+    assert_eq!(resp.summary.total_users, 2);
+    assert_eq!(resp.summary.active_users_today, expected_active_users_today);
+    assert_eq!(resp.summary.ongoing_tasks_total, 2);
+    assert_eq!(resp.summary.ongoing_by_task.translations, 1);
+    assert_eq!(resp.summary.ongoing_by_task.summaries, 1);
+    assert_eq!(resp.summary.ongoing_by_task.briefs, 0);
+    assert_eq!(
+        resp.today_live.date,
+        today_local.format("%Y-%m-%d").to_string()
+    );
+    assert_eq!(resp.status_breakdown.queued_total, 1);
+    assert_eq!(resp.status_breakdown.running_total, 1);
+    assert_eq!(resp.status_breakdown.succeeded_total, 1);
+    assert_eq!(resp.status_breakdown.failed_total, 0);
+    assert_eq!(resp.status_breakdown.total, 3);
+    assert_eq!(resp.status_breakdown.items.len(), 3);
+    assert_eq!(resp.window_meta.selected_window, "30d");
+    assert_eq!(resp.window_meta.point_count, 30);
+    assert_eq!(resp.trend_points.len(), 30);
+
+    let today_point = resp
+        .trend_points
+        .iter()
+        .find(|point| point.date == today_local.format("%Y-%m-%d").to_string())
+        .expect("today trend point should exist");
+    assert_eq!(today_point.translations_total, 1);
+    assert_eq!(today_point.summaries_total, 1);
+    assert_eq!(today_point.briefs_total, 1);
+}
+
+#[tokio::test]
+async fn admin_dashboard_surfaces_partial_business_results_and_llm_hotspots() {
+    let pool = setup_pool().await;
+    sqlx::query(r#"UPDATE users SET is_admin = 1 WHERE id = ?"#)
+        .bind(test_user_id(1))
+        .execute(&pool)
+        .await
+        .expect("promote seeded user to admin");
+
+    let time_zone = chrono_tz::Asia::Shanghai;
+    let now_local = chrono::Utc::now().with_timezone(&time_zone);
+    let today_local = now_local.date_naive();
+    let today_window_start = time_zone
+        .with_ymd_and_hms(
+            today_local.year(),
+            today_local.month(),
+            today_local.day(),
+            0,
+            0,
+            0,
+        )
+        .single()
+        .expect("build dashboard day start");
+    let safe_today_local = if now_local > today_window_start + chrono::Duration::minutes(5) {
+        now_local - chrono::Duration::minutes(5)
+    } else {
+        today_window_start + chrono::Duration::seconds(1)
+    };
+    let today_time = safe_today_local.with_timezone(&chrono::Utc).to_rfc3339();
+
+    sqlx::query(
+            r#"
+            INSERT INTO job_tasks (
+              id,
+              task_type,
+              status,
+              source,
+              requested_by,
+              parent_task_id,
+              payload_json,
+              result_json,
+              error_message,
+              cancel_requested,
+              created_at,
+              started_at,
+              finished_at,
+              updated_at
+            )
+            VALUES (
+              'dashboard-business-partial',
+              ?,
+              'succeeded',
+              'tests',
+              ?,
+              NULL,
+              ?,
+              ?,
+              NULL,
+              0,
+              ?,
+              ?,
+              ?,
+              ?
+            )
+            "#,
+        )
+        .bind(jobs::TASK_SUMMARIZE_RELEASE_SMART_BATCH)
+        .bind(test_user_id(1))
+        .bind(r#"{"user_id":1,"release_ids":[101,102,103]}"#)
+        .bind(
+            r#"{"items":[
+              {"id":"101","lang":"zh-CN","status":"ready","title":"A","summary":"alpha","error":null},
+              {"id":"102","lang":"zh-CN","status":"ready","title":"B","summary":"beta","error":null},
+              {"id":"103","lang":"zh-CN","status":"error","title":null,"summary":null,"error":"upstream 403"}
+            ]}"#,
+        )
+        .bind(today_time.as_str())
+        .bind(today_time.as_str())
+        .bind(today_time.as_str())
+        .bind(today_time.as_str())
+        .execute(&pool)
+        .await
+        .expect("insert partial business task");
+    sqlx::query(
+            r#"
+            INSERT INTO job_tasks (
+              id,
+              task_type,
+              status,
+              source,
+              requested_by,
+              parent_task_id,
+              payload_json,
+              result_json,
+              error_message,
+              cancel_requested,
+              created_at,
+              started_at,
+              finished_at,
+              updated_at
+            )
+            VALUES (
+              'dashboard-business-failed',
+              ?,
+              'succeeded',
+              'tests',
+              ?,
+              NULL,
+              ?,
+              ?,
+              NULL,
+              0,
+              ?,
+              ?,
+              ?,
+              ?
+            )
+            "#,
+        )
+        .bind(jobs::TASK_TRANSLATE_RELEASE_BATCH)
+        .bind(test_user_id(1))
+        .bind(r#"{"user_id":1,"release_ids":[201,202]}"#)
+        .bind(
+            r#"{"items":[
+              {"id":"201","lang":"zh-CN","status":"error","title":null,"summary":null,"error":"translation failed"},
+              {"id":"202","lang":"zh-CN","status":"error","title":null,"summary":null,"error":"translation failed"}
+            ]}"#,
+        )
+        .bind(today_time.as_str())
+        .bind(today_time.as_str())
+        .bind(today_time.as_str())
+        .bind(today_time.as_str())
+        .execute(&pool)
+        .await
+        .expect("insert failed translation task");
+    sqlx::query(
+        r#"
+            INSERT INTO job_tasks (
+              id,
+              task_type,
+              status,
+              source,
+              requested_by,
+              parent_task_id,
+              payload_json,
+              result_json,
+              error_message,
+              cancel_requested,
+              created_at,
+              started_at,
+              finished_at,
+              updated_at
+            )
+            VALUES (
+              'dashboard-business-canceled',
+              ?,
+              'canceled',
+              'tests',
+              ?,
+              NULL,
+              ?,
+              NULL,
+              NULL,
+              1,
+              ?,
+              ?,
+              ?,
+              ?
+            )
+            "#,
+    )
+    .bind(jobs::TASK_SUMMARIZE_RELEASE_SMART_BATCH)
+    .bind(test_user_id(1))
+    .bind(r#"{"user_id":1,"release_ids":[104]}"#)
+    .bind(today_time.as_str())
+    .bind(today_time.as_str())
+    .bind(today_time.as_str())
+    .bind(today_time.as_str())
+    .execute(&pool)
+    .await
+    .expect("insert canceled summary task");
+    sqlx::query(
+        r#"
+            INSERT INTO admin_dashboard_daily_rollups (
+              rollup_date,
+              time_zone,
+              task_type,
+              total_users,
+              active_users,
+              queued_count,
+              running_count,
+              succeeded_count,
+              failed_count,
+              canceled_count,
+              business_ok_count,
+              business_partial_count,
+              business_failed_count,
+              business_disabled_count,
+              updated_at
+            )
+            VALUES (?, 'Asia/Shanghai', ?, 1, 1, 0, 0, 1, 0, 0, 0, 0, 0, 0, ?)
+            "#,
+    )
+    .bind(today_local.format("%Y-%m-%d").to_string())
+    .bind(jobs::TASK_TRANSLATE_RELEASE_BATCH)
+    .bind(today_time.as_str())
+    .execute(&pool)
+    .await
+    .expect("seed stale translation rollup");
+    sqlx::query(
+        r#"
+            INSERT INTO admin_dashboard_daily_rollups (
+              rollup_date,
+              time_zone,
+              task_type,
+              total_users,
+              active_users,
+              queued_count,
+              running_count,
+              succeeded_count,
+              failed_count,
+              canceled_count,
+              business_ok_count,
+              business_partial_count,
+              business_failed_count,
+              business_disabled_count,
+              updated_at
+            )
+            VALUES (?, 'Asia/Shanghai', ?, 1, 1, 0, 0, 1, 0, 0, 0, 0, 0, 0, ?)
+            "#,
+    )
+    .bind(today_local.format("%Y-%m-%d").to_string())
+    .bind(jobs::TASK_SUMMARIZE_RELEASE_SMART_BATCH)
+    .bind(today_time.as_str())
+    .execute(&pool)
+    .await
+    .expect("seed stale summary rollup");
+
+    seed_llm_call_with_created_at(
+        &pool,
+        "dashboard-llm-hotspot-1",
+        "failed",
+        "job.sync.subscriptions.auto_smart",
+        Some(test_user_id(1)),
+        today_time.as_str(),
+    )
+    .await;
+    sqlx::query(
+        r#"
+            UPDATE llm_calls
+            SET error_text = '429 No available accounts for this model tier'
+            WHERE id = 'dashboard-llm-hotspot-1'
+            "#,
+    )
+    .execute(&pool)
+    .await
+    .expect("update llm hotspot reason");
+    let historic_created_at = (chrono::Utc::now() - chrono::Duration::hours(30)).to_rfc3339();
+    seed_llm_call_with_created_at(
+        &pool,
+        "dashboard-llm-hotspot-late-failure",
+        "failed",
+        "job.sync.subscriptions.auto_translate",
+        Some(test_user_id(1)),
+        historic_created_at.as_str(),
+    )
+    .await;
+    sqlx::query(
+        r#"
+            UPDATE llm_calls
+            SET error_text = 'late surfaced 403',
+                finished_at = ?,
+                updated_at = ?
+            WHERE id = 'dashboard-llm-hotspot-late-failure'
+            "#,
+    )
+    .bind(today_time.as_str())
+    .bind(today_time.as_str())
+    .execute(&pool)
+    .await
+    .expect("update late failure timestamps");
+    let future_time = (chrono::Utc::now() + chrono::Duration::hours(2)).to_rfc3339();
+    seed_llm_call_with_created_at(
+        &pool,
+        "dashboard-llm-hotspot-future",
+        "failed",
+        "job.sync.subscriptions.auto_translate",
+        Some(test_user_id(1)),
+        future_time.as_str(),
+    )
+    .await;
+    sqlx::query(
+        r#"
+            UPDATE llm_calls
+            SET error_text = 'future dated failure should be ignored'
+            WHERE id = 'dashboard-llm-hotspot-future'
+            "#,
+    )
+    .execute(&pool)
+    .await
+    .expect("update future llm hotspot reason");
+
+    let state = setup_state(pool.clone());
+    refresh_admin_dashboard_rollups(state.as_ref(), ADMIN_DASHBOARD_PREAGGREGATE_DAYS)
+        .await
+        .expect("dashboard rollup refresh should succeed");
+    let Json(resp) = admin_dashboard(
+        State(state),
+        AdminUser(test_user_id(1)),
+        Query(AdminDashboardQuery {
+            window: Some("7d".to_owned()),
+        }),
+    )
+    .await
+    .expect("admin dashboard should succeed");
+
+    let summary_item = resp
+        .status_breakdown
+        .items
+        .iter()
+        .find(|item| item.task_type == jobs::TASK_SUMMARIZE_RELEASE_SMART_BATCH)
+        .expect("summary task item");
+    let translation_item = resp
+        .status_breakdown
+        .items
+        .iter()
+        .find(|item| item.task_type == jobs::TASK_TRANSLATE_RELEASE_BATCH)
+        .expect("translation task item");
+    assert_eq!(summary_item.business_counts.partial, 1);
+    assert_eq!(summary_item.business_counts.failed, 0);
+    assert_eq!(translation_item.business_counts.failed, 1);
+    assert_eq!(translation_item.business_counts.partial, 0);
+    assert_eq!(resp.status_breakdown.business_counts.partial, 1);
+    assert_eq!(resp.status_breakdown.business_counts.failed, 1);
+    assert_eq!(summary_item.canceled, 1);
+
+    let today_point = resp
+        .trend_points
+        .iter()
+        .find(|point| point.date == today_local.format("%Y-%m-%d").to_string())
+        .expect("today trend point should exist");
+    assert_eq!(today_point.translations_business_failed, 1);
+    assert_eq!(today_point.translations_partial, 0);
+    assert_eq!(today_point.summaries_partial, 1);
+    assert_eq!(today_point.summaries_business_failed, 0);
+
+    assert_eq!(resp.llm_health.calls_24h, 2);
+    assert_eq!(resp.llm_health.failed_24h, 2);
+    assert_ne!(
+        resp.llm_health.last_failure_at.as_deref(),
+        Some(future_time.as_str())
+    );
+    assert_eq!(
+        resp.llm_health.top_failure_reasons[0].label,
+        "429 No available accounts for this model tier"
+    );
+    assert!(
+        resp.llm_health
+            .top_failure_reasons
+            .iter()
+            .any(|item| item.label == "late surfaced 403")
+    );
+    assert!(
+        resp.llm_health
+            .top_failure_sources
+            .iter()
+            .any(|item| item.label == "job.sync.subscriptions.auto_translate")
+    );
+
+    let summary_rollup_counts = sqlx::query_as::<_, (i64, i64, i64, i64)>(
+        r#"
+            SELECT
+              business_ok_count,
+              business_partial_count,
+              business_failed_count,
+              business_disabled_count
+            FROM admin_dashboard_daily_rollups
+            WHERE time_zone = 'Asia/Shanghai'
+              AND rollup_date = ?
+              AND task_type = ?
+            LIMIT 1
+            "#,
+    )
+    .bind(today_local.format("%Y-%m-%d").to_string())
+    .bind(jobs::TASK_SUMMARIZE_RELEASE_SMART_BATCH)
+    .fetch_one(&pool)
+    .await
+    .expect("load summary rollup counts");
+    assert_eq!(summary_rollup_counts, (0, 1, 0, 0));
+    let translation_rollup_counts = sqlx::query_as::<_, (i64, i64, i64, i64)>(
+        r#"
+            SELECT
+              business_ok_count,
+              business_partial_count,
+              business_failed_count,
+              business_disabled_count
+            FROM admin_dashboard_daily_rollups
+            WHERE time_zone = 'Asia/Shanghai'
+              AND rollup_date = ?
+              AND task_type = ?
+            LIMIT 1
+            "#,
+    )
+    .bind(today_local.format("%Y-%m-%d").to_string())
+    .bind(jobs::TASK_TRANSLATE_RELEASE_BATCH)
+    .fetch_one(&pool)
+    .await
+    .expect("load translation rollup counts");
+    assert_eq!(translation_rollup_counts, (0, 0, 1, 0));
+}
+
+#[tokio::test]
+async fn admin_dashboard_today_live_snapshot_excludes_future_activity() {
+    let pool = setup_pool().await;
+    seed_user(&pool, 2, "operator", 0, 0).await;
+
+    let time_zone = chrono_tz::Asia::Shanghai;
+    let now_utc = time_zone
+        .with_ymd_and_hms(2026, 4, 22, 10, 0, 0)
+        .single()
+        .expect("build live snapshot time")
+        .with_timezone(&chrono::Utc);
+    let in_window_time = (now_utc - chrono::Duration::minutes(5)).to_rfc3339();
+    let future_time = (now_utc + chrono::Duration::seconds(1)).to_rfc3339();
+
+    set_last_active_at(
+        &pool,
+        test_user_id(1).as_str(),
+        Some(in_window_time.as_str()),
+    )
+    .await;
+    set_last_active_at(&pool, test_user_id(2).as_str(), Some(future_time.as_str())).await;
+
+    seed_admin_dashboard_task(
+        &pool,
+        "dashboard-live-queued",
+        jobs::TASK_TRANSLATE_RELEASE_BATCH,
+        jobs::STATUS_QUEUED,
+        test_user_id(1).as_str(),
+        in_window_time.as_str(),
+    )
+    .await;
+    seed_admin_dashboard_task(
+        &pool,
+        "dashboard-future-succeeded",
+        jobs::TASK_SUMMARIZE_RELEASE_SMART_BATCH,
+        jobs::STATUS_SUCCEEDED,
+        test_user_id(2).as_str(),
+        future_time.as_str(),
+    )
+    .await;
+
+    let state = setup_state(pool);
+    let (today_live, status_breakdown) =
+        load_admin_dashboard_today_live_snapshot(state.as_ref(), time_zone, now_utc)
+            .await
+            .expect("load live snapshot");
+
+    assert_eq!(today_live.total_users, 2);
+    assert_eq!(today_live.active_users, 1);
+    assert_eq!(today_live.queued_tasks, 1);
+    assert_eq!(today_live.running_tasks, 0);
+    assert_eq!(today_live.ongoing_tasks_total, 1);
+    assert_eq!(status_breakdown.queued_total, 1);
+    assert_eq!(status_breakdown.running_total, 0);
+    assert_eq!(status_breakdown.total, 1);
+    assert_eq!(status_breakdown.items.len(), 3);
+    let translation_item = status_breakdown
+        .items
+        .iter()
+        .find(|item| item.task_type == jobs::TASK_TRANSLATE_RELEASE_BATCH)
+        .expect("translation item should exist");
+    assert_eq!(translation_item.total, 1);
+    let summary_item = status_breakdown
+        .items
+        .iter()
+        .find(|item| item.task_type == jobs::TASK_SUMMARIZE_RELEASE_SMART_BATCH)
+        .expect("summary item should exist");
+    assert_eq!(summary_item.total, 0);
+}
+
+#[tokio::test]
+async fn admin_list_llm_calls_sort_uses_admin_sort_index() {
+    let pool = setup_pool().await;
+    let plan_rows = sqlx::query(
+        r#"
+            EXPLAIN QUERY PLAN
+            SELECT
+              id,
+              status,
+              created_at
+            FROM llm_calls
+            ORDER BY
+              CASE
+                WHEN status = 'running' THEN 0
+                WHEN status = 'queued' THEN 1
+                ELSE 2
+              END,
+              julianday(created_at) DESC,
+              created_at DESC,
+              id DESC
+            LIMIT ? OFFSET ?
+            "#,
+    )
+    .bind(20_i64)
+    .bind(0_i64)
+    .fetch_all(&pool)
+    .await
+    .expect("load query plan");
+
+    let details = plan_rows
+        .iter()
+        .map(|row| row.get::<String, _>(3))
+        .collect::<Vec<_>>();
+    assert!(
+        details
+            .iter()
+            .any(|detail| detail.contains("idx_llm_calls_admin_sort"))
+    );
+    assert!(
+        !details
+            .iter()
+            .any(|detail| detail.contains("USE TEMP B-TREE FOR ORDER BY"))
+    );
+}
+
+#[tokio::test]
+async fn admin_list_llm_calls_grouped_source_filter_uses_source_admin_sort_index() {
+    let pool = setup_pool().await;
+    let plan_rows = sqlx::query(
+        r#"
+            EXPLAIN QUERY PLAN
+            SELECT
+              id,
+              status,
+              created_at
+            FROM llm_calls
+            WHERE source = ?
+            ORDER BY
+              CASE
+                WHEN status = 'running' THEN 0
+                WHEN status = 'queued' THEN 1
+                ELSE 2
+              END,
+              julianday(created_at) DESC,
+              created_at DESC,
+              id DESC
+            LIMIT ? OFFSET ?
+            "#,
+    )
+    .bind("api.translate_releases_batch")
+    .bind(20_i64)
+    .bind(0_i64)
+    .fetch_all(&pool)
+    .await
+    .expect("load query plan");
+
+    let details = plan_rows
+        .iter()
+        .map(|row| row.get::<String, _>(3))
+        .collect::<Vec<_>>();
+    assert!(
+        details
+            .iter()
+            .any(|detail| detail.contains("idx_llm_calls_source_admin_sort"))
+    );
+    assert!(
+        !details
+            .iter()
+            .any(|detail| detail.contains("USE TEMP B-TREE FOR ORDER BY"))
+    );
+}
+
+#[tokio::test]
+async fn admin_jobs_overview_recent_finished_counts_use_finished_status_index() {
+    let pool = setup_pool().await;
+    let plan_rows = sqlx::query(
+        r#"
+            EXPLAIN QUERY PLAN
+            SELECT COUNT(*)
+            FROM job_tasks
+            WHERE status = 'succeeded'
+              AND finished_at IS NOT NULL
+              AND finished_at >= ?
+            "#,
+    )
+    .bind("2026-04-27T00:00:00Z")
+    .fetch_all(&pool)
+    .await
+    .expect("load query plan");
+
+    let details = plan_rows
+        .iter()
+        .map(|row| row.get::<String, _>(3))
+        .collect::<Vec<_>>();
+    assert!(
+        details
+            .iter()
+            .any(|detail| detail.contains("idx_job_tasks_finished_status"))
+    );
+}
+
+#[tokio::test]
+async fn admin_list_realtime_tasks_query_plan_avoids_temp_btree_sort() {
+    let pool = setup_pool().await;
+    let plan_rows = sqlx::query(
+        r#"
+            EXPLAIN QUERY PLAN
+            SELECT
+              id,
+              task_type,
+              status,
+              created_at
+            FROM job_tasks
+            WHERE status = ?
+            ORDER BY created_at DESC, id DESC
+            LIMIT ? OFFSET ?
+            "#,
+    )
+    .bind("running")
+    .bind(20_i64)
+    .bind(0_i64)
+    .fetch_all(&pool)
+    .await
+    .expect("load query plan");
+
+    let details = plan_rows
+        .iter()
+        .map(|row| row.get::<String, _>(3))
+        .collect::<Vec<_>>();
+    assert!(
+        details
+            .iter()
+            .any(|detail| detail.contains("idx_job_tasks_status_created_at"))
+            || details
+                .iter()
+                .any(|detail| detail.contains("idx_job_tasks_realtime_created"))
+    );
+    assert!(
+        !details
+            .iter()
+            .any(|detail| detail.contains("USE TEMP B-TREE FOR ORDER BY"))
+    );
+}
+
+#[tokio::test]
+async fn admin_dashboard_llm_health_query_plan_uses_coalesced_timestamp_index() {
+    let pool = setup_pool().await;
+    let plan_rows = sqlx::query(
+        r#"
+            EXPLAIN QUERY PLAN
+            SELECT COUNT(*)
+            FROM llm_calls
+            WHERE COALESCE(finished_at, updated_at, created_at) >= ?
+              AND COALESCE(finished_at, updated_at, created_at) <= ?
+            "#,
+    )
+    .bind("2026-04-26T00:00:00Z")
+    .bind("2026-04-27T00:00:00Z")
+    .fetch_all(&pool)
+    .await
+    .expect("load query plan");
+
+    let details = plan_rows
+        .iter()
+        .map(|row| row.get::<String, _>(3))
+        .collect::<Vec<_>>();
+    assert!(
+        details
+            .iter()
+            .any(|detail| detail.contains("idx_llm_calls_finished_updated_created"))
+    );
+}
+
+#[tokio::test]
+async fn release_smart_batch_source_query_plan_avoids_global_release_sort() {
+    let pool = setup_pool().await;
+    sqlx::query(
+        r#"
+            CREATE INDEX IF NOT EXISTS idx_repo_releases_repo_sort_ts
+              ON repo_releases(
+                repo_id,
+                COALESCE(published_at, created_at, updated_at),
+                release_id
+              )
+            "#,
+    )
+    .execute(&pool)
+    .await
+    .expect("create repo release sort ts index");
+    seed_star(&pool, 42).await;
+    seed_repo_release(&pool, 42, 120).await;
+    sqlx::query(
+        r#"
+            INSERT INTO repo_releases (
+              id,
+              repo_id,
+              release_id,
+              node_id,
+              tag_name,
+              name,
+              body,
+              html_url,
+              published_at,
+              created_at,
+              is_prerelease,
+              is_draft,
+              updated_at,
+              react_plus1,
+              react_laugh,
+              react_heart,
+              react_hooray,
+              react_rocket,
+              react_eyes
+            )
+            VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, 0, 0, ?, 0, 0, 0, 0, 0, 0)
+            "#,
+    )
+    .bind("repo-release-42-121")
+    .bind(42_i64)
+    .bind(121_i64)
+    .bind("node-121")
+    .bind("v1.2.4")
+    .bind("Release v1.2.4")
+    .bind("- item 2")
+    .bind("https://github.com/openai/codex/releases/tag/v1.2.4")
+    .bind("2026-02-24T00:00:00Z")
+    .bind("2026-02-24T00:00:00Z")
+    .bind("2026-02-24T00:00:00Z")
+    .execute(&pool)
+    .await
+    .expect("seed second shared repo release");
+    let plan_rows = sqlx::query(
+        r#"
+            EXPLAIN QUERY PLAN
+            WITH target_releases AS (
+              SELECT
+                r.repo_id AS repo_id,
+                r.release_id AS release_id,
+                r.tag_name AS tag_name,
+                r.name AS name,
+                r.body AS body,
+                COALESCE(r.published_at, r.created_at, r.updated_at) AS sort_ts
+              FROM repo_releases r
+              WHERE r.release_id IN (?, ?)
+            )
+            SELECT
+              tr.release_id AS release_id,
+              sr.full_name AS full_name,
+              tr.tag_name AS tag_name,
+              tr.name AS name,
+              tr.body AS body,
+              (
+                SELECT prev.tag_name
+                FROM repo_releases prev
+                WHERE prev.repo_id = tr.repo_id
+                  AND (
+                    COALESCE(prev.published_at, prev.created_at, prev.updated_at) < tr.sort_ts
+                    OR (
+                      COALESCE(prev.published_at, prev.created_at, prev.updated_at) = tr.sort_ts
+                      AND prev.release_id < tr.release_id
+                    )
+                  )
+                ORDER BY
+                  COALESCE(prev.published_at, prev.created_at, prev.updated_at) DESC,
+                  prev.release_id DESC
+                LIMIT 1
+              ) AS previous_tag_name
+            FROM target_releases tr
+            JOIN user_release_visible_repos sr
+              ON sr.user_id = ? AND sr.repo_id = tr.repo_id
+            "#,
+    )
+    .bind(120_i64)
+    .bind(121_i64)
+    .bind(test_user_id(1))
+    .fetch_all(&pool)
+    .await
+    .expect("load query plan");
+
+    let details = plan_rows
+        .iter()
+        .map(|row| row.get::<String, _>(3))
+        .collect::<Vec<_>>();
+    assert!(
+        details
+            .iter()
+            .any(|detail| detail.contains("idx_repo_releases_repo_sort_ts"))
+    );
+    assert!(
+        !details
+            .iter()
+            .any(|detail| detail.contains("USE TEMP B-TREE FOR ORDER BY"))
+    );
+}
+
+#[test]
+fn llm_call_order_by_clause_uses_created_desc_when_status_is_fixed() {
+    let scope = AdminLlmCallListScope {
+        status: Some("running"),
+        source: "",
+        requested_by: None,
+        parent_task_id: "",
+        request_id: "",
+        started_from: None,
+        started_to: None,
+    };
+
+    assert_eq!(
+        llm_call_order_by_clause(&scope, "status_grouped"),
+        LLM_CALL_ORDER_BY_CREATED_DESC
+    );
+}
+
+#[tokio::test]
+async fn admin_export_llm_calls_streams_filtered_csv() {
+    let pool = setup_pool().await;
+    sqlx::query(r#"UPDATE users SET is_admin = 1 WHERE id = ?"#)
+        .bind(test_user_id(1))
+        .execute(&pool)
+        .await
+        .expect("promote seeded user to admin");
+    for (id, status, created_at) in [
+        ("call-export-old", "succeeded", "2026-02-20T10:00:00Z"),
+        ("call-export-a", "succeeded", "2026-02-26T10:00:00Z"),
+        ("call-export-b", "failed", "2026-02-26T11:00:00Z"),
+    ] {
+        seed_llm_call_with_created_at(
+            &pool,
+            id,
+            status,
+            "api.translate_release",
+            Some(test_user_id(1)),
+            created_at,
+        )
+        .await;
+    }
+    sqlx::query(r#"UPDATE llm_calls SET parent_task_type = 'brief, "daily"' WHERE id = ?"#)
+        .bind("call-export-b")
+        .execute(&pool)
+        .await
+        .expect("set quoted parent task type");
+
+    let state = setup_state(pool);
+    let response = admin_export_llm_calls(
+        State(state.clone()),
+        AdminUser(test_user_id(1)),
+        Query(AdminLlmCallsExportQuery {
+            format: Some("csv".to_owned()),
+            status: None,
+            source: None,
+            requested_by: None,
+            parent_task_id: None,
+            request_id: None,
+            started_from: Some("2026-02-26T00:00:00Z".to_owned()),
+            started_to: None,
+        }),
+    )
+    .await
+    .expect("export llm calls");
+    assert_eq!(
+        response.headers().get(header::CONTENT_TYPE),
+        Some(&HeaderValue::from_static("text/csv; charset=utf-8"))
+    );
+    let body = to_bytes(response.into_body(), usize::MAX)
+        .await
+        .expect("read csv body");
+    let csv = String::from_utf8(body.to_vec()).expect("utf8 csv");
+    let lines = csv.lines().collect::<Vec<_>>();
+    assert_eq!(lines.len(), 3);
+    let header = lines[0].split(',').collect::<Vec<_>>();
+    assert_eq!(header[0], "id");
+    assert_eq!(header[15], "total_tokens");
+    assert_eq!(header.len(), 19);
+    assert!(lines[1].starts_with("call-export-b,failed,api.translate_release,gpt-4o-mini,"));
+    assert!(lines[1].contains(r#","brief, ""daily""","#));
+    assert!(lines[2].starts_with("call-export-a,succeeded,"));
+    assert!(lines[2].contains(",800,120,55,20,175,2026-02-26T10:00:00Z,"));
+
+    let err = admin_export_llm_calls(
+        State(state),
+        AdminUser(test_user_id(1)),
+        Query(AdminLlmCallsExportQuery {
+            format: Some("xlsx".to_owned()),
+            status: None,
+            source: None,
+            requested_by: None,
+            parent_task_id: None,
+            request_id: None,
+            started_from: None,
+            started_to: None,
+        }),
+    )
+    .await
+    .expect_err("only csv is supported");
+    assert_eq!(err.code(), "bad_request");
+}
+
+#[tokio::test]
+async fn admin_llm_usage_summary_buckets_by_utc_day_and_hour() {
+    let pool = setup_pool().await;
+    sqlx::query(r#"UPDATE users SET is_admin = 1 WHERE id = ?"#)
+        .bind(test_user_id(1))
+        .execute(&pool)
+        .await
+        .expect("promote seeded user to admin");
+    for (id, status, source, created_at) in [
+        (
+            "call-sum-1",
+            "succeeded",
+            "api.translate_release",
+            "2026-02-26T23:30:00Z",
+        ),
+        (
+            "call-sum-2",
+            "failed",
+            "api.translate_release",
+            "2026-02-27T00:10:00Z",
+        ),
+        // 00:20 UTC written with an offset still lands in the next UTC day.
+        (
+            "call-sum-3",
+            "succeeded",
+            "jobs.brief",
+            "2026-02-27T08:20:00+08:00",
+        ),
+        (
+            "call-sum-4",
+            "running",
+            "jobs.brief",
+            "2026-02-27T00:40:00Z",
+        ),
+    ] {
+        seed_llm_call_with_created_at(&pool, id, status, source, None, created_at).await;
+    }
+    sqlx::query(r#"UPDATE llm_calls SET duration_ms = NULL WHERE id = 'call-sum-4'"#)
+        .execute(&pool)
+        .await
+        .expect("clear running duration");
+    let state = setup_state(pool);
+    let summary = |bucket: &str| {
+        let state = state.clone();
+        let bucket = bucket.to_owned();
+        async move {
+            admin_get_llm_usage_summary(
+                State(state),
+                AdminUser(test_user_id(1)),
+                Query(AdminLlmUsageSummaryQuery {
+                    bucket: Some(bucket),
+                    started_from: Some("2026-02-26T00:00:00Z".to_owned()),
+                    started_to: Some("2026-02-27T23:59:59Z".to_owned()),
+                }),
+            )
+            .await
+            .expect("load usage summary")
+            .0
+        }
+    };
+
+    let daily = summary("day").await;
+    assert_eq!(daily.bucket, "day");
+    let starts = daily
+        .buckets
+        .iter()
+        .map(|bucket| bucket.bucket_start.as_str())
+        .collect::<Vec<_>>();
+    assert_eq!(starts, vec!["2026-02-26T00:00:00Z", "2026-02-27T00:00:00Z"]);
+    assert_eq!(daily.buckets[0].totals.call_count, 1);
+    let next_day = &daily.buckets[1];
+    assert_eq!(next_day.totals.call_count, 3);
+    assert_eq!(next_day.totals.failed_count, 1);
+    assert_eq!(next_day.totals.failure_rate, 0.5);
+    assert_eq!(next_day.totals.total_tokens, 525);
+    assert_eq!(next_day.totals.avg_duration_ms, Some(800.0));
+    let sources = next_day
+        .sources
+        .iter()
+        .map(|source| (source.source.as_str(), source.stats.call_count))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        sources,
+        vec![("api.translate_release", 1), ("jobs.brief", 2)]
+    );
+
+    let hourly = summary("hour").await;
+    let starts = hourly
+        .buckets
+        .iter()
+        .map(|bucket| (bucket.bucket_start.as_str(), bucket.totals.call_count))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        starts,
+        vec![("2026-02-26T23:00:00Z", 1), ("2026-02-27T00:00:00Z", 3)]
+    );
+}
+
+#[tokio::test]
+async fn admin_list_llm_calls_orders_running_then_queued_then_terminal() {
+    let pool = setup_pool().await;
+    sqlx::query(r#"UPDATE users SET is_admin = 1 WHERE id = ?"#)
+        .bind(test_user_id(1))
+        .execute(&pool)
+        .await
+        .expect("promote seeded user to admin");
+    seed_llm_call_with_created_at(
+        &pool,
+        "call-running-old",
+        "running",
+        "api.translate_releases_batch",
+        Some(test_user_id(1)),
+        "2026-02-26T02:00:00Z",
+    )
+    .await;
+    seed_llm_call_with_created_at(
+        &pool,
+        "call-running-new",
+        "running",
+        "api.translate_releases_batch",
+        Some(test_user_id(1)),
+        "2026-02-26T03:00:00Z",
+    )
+    .await;
+    seed_llm_call_with_created_at(
+        &pool,
+        "call-queued",
+        "queued",
+        "api.translate_releases_batch",
+        Some(test_user_id(1)),
+        "2026-02-26T05:00:00Z",
+    )
+    .await;
+    seed_llm_call_with_created_at(
+        &pool,
+        "call-failed",
+        "failed",
+        "api.translate_releases_batch",
+        Some(test_user_id(1)),
+        "2026-02-26T06:00:00Z",
+    )
+    .await;
+
+    let state = setup_state(pool);
+
+    let resp = admin_list_llm_calls(
+        State(state),
+        AdminUser(test_user_id(1)),
+        Query(AdminLlmCallsQuery {
+            status: Some("all".to_owned()),
+            source: Some("api.translate_releases_batch".to_owned()),
+            requested_by: Some(test_user_id(1)),
+            parent_task_id: None,
+            request_id: None,
+            started_from: None,
+            started_to: None,
+            sort: Some("status_grouped".to_owned()),
+            page: Some(1),
+            page_size: Some(20),
+        }),
+    )
+    .await
+    .expect("admin llm call list should sort by runtime priority")
+    .0;
+
+    let ids = resp
+        .items
+        .into_iter()
+        .map(|item| item.id)
+        .collect::<Vec<_>>();
+    assert_eq!(
+        ids,
+        vec![
+            "call-running-new",
+            "call-running-old",
+            "call-queued",
+            "call-failed",
+        ]
+    );
+}
+
+#[tokio::test]
+async fn admin_list_llm_calls_defaults_to_created_desc() {
+    let pool = setup_pool().await;
+    sqlx::query(r#"UPDATE users SET is_admin = 1 WHERE id = ?"#)
+        .bind(test_user_id(1))
+        .execute(&pool)
+        .await
+        .expect("promote seeded user to admin");
+    seed_llm_call_with_created_at(
+        &pool,
+        "call-running-oldest",
+        "running",
+        "api.translate_releases_batch",
+        Some(test_user_id(1)),
+        "2026-02-26T02:00:00Z",
+    )
+    .await;
+    seed_llm_call_with_created_at(
+        &pool,
+        "call-failed-newest",
+        "failed",
+        "api.translate_releases_batch",
+        Some(test_user_id(1)),
+        "2026-02-26T06:00:00Z",
+    )
+    .await;
+    seed_llm_call_with_created_at(
+        &pool,
+        "call-queued-middle",
+        "queued",
+        "api.translate_releases_batch",
+        Some(test_user_id(1)),
+        "2026-02-26T04:00:00Z",
+    )
+    .await;
+
+    let state = setup_state(pool);
+
+    let resp = admin_list_llm_calls(
+        State(state),
+        AdminUser(test_user_id(1)),
+        Query(AdminLlmCallsQuery {
+            status: Some("all".to_owned()),
+            source: Some("api.translate_releases_batch".to_owned()),
+            requested_by: Some(test_user_id(1)),
+            parent_task_id: None,
+            request_id: None,
+            started_from: None,
+            started_to: None,
+            sort: None,
+            page: Some(1),
+            page_size: Some(20),
+        }),
+    )
+    .await
+    .expect("admin llm call list should keep reverse chronological order by default")
+    .0;
+
+    let ids = resp
+        .items
+        .into_iter()
+        .map(|item| item.id)
+        .collect::<Vec<_>>();
+    assert_eq!(
+        ids,
+        vec![
+            "call-failed-newest",
+            "call-queued-middle",
+            "call-running-oldest",
+        ]
+    );
+}
+
+#[tokio::test]
+async fn admin_list_llm_calls_uses_override_snapshot_fields() {
+    let pool = setup_pool().await;
+    sqlx::query(r#"UPDATE users SET is_admin = 1 WHERE id = ?"#)
+        .bind(test_user_id(1))
+        .execute(&pool)
+        .await
+        .expect("promote seeded user to admin");
+    seed_llm_call_with_created_at(
+        &pool,
+        "call-running-snapshot",
+        "running",
+        "api.translate_releases_batch",
+        Some(test_user_id(1)),
+        "2026-02-26T02:00:00Z",
+    )
+    .await;
+
+    let state = setup_state(pool);
+    state
+        .llm_scheduler
+        .set_admin_override(crate::ai::LlmCallAdminOverride {
+            id: "call-running-snapshot".to_owned(),
+            status: "succeeded".to_owned(),
+            attempt_count: 2,
+            scheduler_wait_ms: 180,
+            first_token_wait_ms: Some(95),
+            duration_ms: Some(450),
+            input_tokens: Some(220),
+            output_tokens: Some(88),
+            cached_input_tokens: Some(12),
+            total_tokens: Some(308),
+            output_messages_json: Some(r#"[{"role":"assistant","content":"override"}]"#.to_owned()),
+            response_text: Some("override response".to_owned()),
+            error_text: None,
+            started_at: Some("2026-02-26T02:00:01Z".to_owned()),
+            finished_at: Some("2026-02-26T02:00:09Z".to_owned()),
+            updated_at: "2026-02-26T02:00:09Z".to_owned(),
+        })
+        .await;
+
+    let resp = admin_list_llm_calls(
+        State(state),
+        AdminUser(test_user_id(1)),
+        Query(AdminLlmCallsQuery {
+            status: Some("all".to_owned()),
+            source: Some("api.translate_releases_batch".to_owned()),
+            requested_by: Some(test_user_id(1)),
+            parent_task_id: None,
+            request_id: None,
+            started_from: None,
+            started_to: None,
+            sort: None,
+            page: Some(1),
+            page_size: Some(20),
+        }),
+    )
+    .await
+    .expect("admin llm call list should expose override snapshot fields")
+    .0;
+
+    let item = resp
+        .items
+        .into_iter()
+        .next()
+        .expect("list item should exist");
+    assert_eq!(item.status, "succeeded");
+    assert_eq!(item.attempt_count, 2);
+    assert_eq!(item.scheduler_wait_ms, 180);
+    assert_eq!(item.first_token_wait_ms, Some(95));
+    assert_eq!(item.duration_ms, Some(450));
+    assert_eq!(item.input_tokens, Some(220));
+    assert_eq!(item.output_tokens, Some(88));
+    assert_eq!(item.cached_input_tokens, Some(12));
+    assert_eq!(item.total_tokens, Some(308));
+    assert_eq!(item.started_at.as_deref(), Some("2026-02-26T02:00:01Z"));
+    assert_eq!(item.finished_at.as_deref(), Some("2026-02-26T02:00:09Z"));
+    assert_eq!(item.updated_at, "2026-02-26T02:00:09Z");
+}
+
+#[tokio::test]
+async fn admin_list_llm_calls_uses_overrides_for_filter_sort_and_pagination() {
+    let pool = setup_pool().await;
+    sqlx::query(r#"UPDATE users SET is_admin = 1 WHERE id = ?"#)
+        .bind(test_user_id(1))
+        .execute(&pool)
+        .await
+        .expect("promote seeded user to admin");
+    seed_llm_call_with_created_at(
+        &pool,
+        "call-running-old",
+        "running",
+        "api.translate_releases_batch",
+        Some(test_user_id(1)),
+        "2026-02-26T02:00:00Z",
+    )
+    .await;
+    seed_llm_call_with_created_at(
+        &pool,
+        "call-queued-middle",
+        "queued",
+        "api.translate_releases_batch",
+        Some(test_user_id(1)),
+        "2026-02-26T04:00:00Z",
+    )
+    .await;
+    seed_llm_call_with_created_at(
+        &pool,
+        "call-failed-newest",
+        "failed",
+        "api.translate_releases_batch",
+        Some(test_user_id(1)),
+        "2026-02-26T06:00:00Z",
+    )
+    .await;
+
+    let state = setup_state(pool);
+    state
+        .llm_scheduler
+        .set_admin_override(crate::ai::LlmCallAdminOverride {
+            id: "call-failed-newest".to_owned(),
+            status: "queued".to_owned(),
+            attempt_count: 3,
+            scheduler_wait_ms: 200,
+            first_token_wait_ms: None,
+            duration_ms: None,
+            input_tokens: None,
+            output_tokens: None,
+            cached_input_tokens: None,
+            total_tokens: None,
+            output_messages_json: None,
+            response_text: None,
+            error_text: None,
+            started_at: Some("2026-02-26T06:00:01Z".to_owned()),
+            finished_at: None,
+            updated_at: "2026-02-26T06:00:05Z".to_owned(),
+        })
+        .await;
+
+    let first_page = admin_list_llm_calls(
+        State(Arc::clone(&state)),
+        AdminUser(test_user_id(1)),
+        Query(AdminLlmCallsQuery {
+            status: Some("queued".to_owned()),
+            source: Some("api.translate_releases_batch".to_owned()),
+            requested_by: Some(test_user_id(1)),
+            parent_task_id: None,
+            request_id: None,
+            started_from: None,
+            started_to: None,
+            sort: Some("status_grouped".to_owned()),
+            page: Some(1),
+            page_size: Some(1),
+        }),
+    )
+    .await
+    .expect("page one should include override-backed queued call")
+    .0;
+    assert_eq!(first_page.total, 2);
+    assert_eq!(first_page.items.len(), 1);
+    assert_eq!(first_page.items[0].id, "call-failed-newest");
+    assert_eq!(first_page.items[0].status, "queued");
+
+    let second_page = admin_list_llm_calls(
+        State(state),
+        AdminUser(test_user_id(1)),
+        Query(AdminLlmCallsQuery {
+            status: Some("queued".to_owned()),
+            source: Some("api.translate_releases_batch".to_owned()),
+            requested_by: Some(test_user_id(1)),
+            parent_task_id: None,
+            request_id: None,
+            started_from: None,
+            started_to: None,
+            sort: Some("status_grouped".to_owned()),
+            page: Some(2),
+            page_size: Some(1),
+        }),
+    )
+    .await
+    .expect("page two should keep override-aware pagination stable")
+    .0;
+    assert_eq!(second_page.total, 2);
+    assert_eq!(second_page.items.len(), 1);
+    assert_eq!(second_page.items[0].id, "call-queued-middle");
+}
+
+#[tokio::test]
+async fn admin_list_llm_calls_orders_mixed_rfc3339_by_true_created_time() {
+    let pool = setup_pool().await;
+    sqlx::query(r#"UPDATE users SET is_admin = 1 WHERE id = ?"#)
+        .bind(test_user_id(1))
+        .execute(&pool)
+        .await
+        .expect("promote seeded user to admin");
+    for (call_id, created_at) in [
+        ("call-zulu", "2026-02-26T05:00:00Z"),
+        ("call-subsec", "2026-02-26T05:00:00.500+00:00"),
+        ("call-offset", "2026-02-26T05:00:00+00:00"),
+    ] {
+        seed_llm_call_with_created_at(
+            &pool,
+            call_id,
+            "failed",
+            "api.translate_releases_batch",
+            Some(test_user_id(1)),
+            created_at,
+        )
+        .await;
+    }
+
+    let state = setup_state(pool);
+    let default_resp = admin_list_llm_calls(
+        State(Arc::clone(&state)),
+        AdminUser(test_user_id(1)),
+        Query(AdminLlmCallsQuery {
+            status: Some("all".to_owned()),
+            source: Some("api.translate_releases_batch".to_owned()),
+            requested_by: Some(test_user_id(1)),
+            parent_task_id: None,
+            request_id: None,
+            started_from: None,
+            started_to: None,
+            sort: None,
+            page: Some(1),
+            page_size: Some(20),
+        }),
+    )
+    .await
+    .expect("default llm call list should sort by actual created_at time")
+    .0;
+    let grouped_resp = admin_list_llm_calls(
+        State(state),
+        AdminUser(test_user_id(1)),
+        Query(AdminLlmCallsQuery {
+            status: Some("all".to_owned()),
+            source: Some("api.translate_releases_batch".to_owned()),
+            requested_by: Some(test_user_id(1)),
+            parent_task_id: None,
+            request_id: None,
+            started_from: None,
+            started_to: None,
+            sort: Some("status_grouped".to_owned()),
+            page: Some(1),
+            page_size: Some(20),
+        }),
+    )
+    .await
+    .expect("grouped llm call list should sort by actual created_at time")
+    .0;
+
+    let expected = vec!["call-subsec", "call-zulu", "call-offset"];
+    assert_eq!(
+        default_resp
+            .items
+            .iter()
+            .map(|item| item.id.as_str())
+            .collect::<Vec<_>>(),
+        expected
+    );
+    assert_eq!(
+        grouped_resp
+            .items
+            .iter()
+            .map(|item| item.id.as_str())
+            .collect::<Vec<_>>(),
+        expected
+    );
+}
+
+#[tokio::test]
+async fn admin_list_llm_calls_accepts_zulu_started_filters() {
+    let pool = setup_pool().await;
+    sqlx::query(r#"UPDATE users SET is_admin = 1 WHERE id = ?"#)
+        .bind(test_user_id(1))
+        .execute(&pool)
+        .await
+        .expect("promote seeded user to admin");
+    seed_llm_call(
+        &pool,
+        "call-zulu",
+        "succeeded",
+        "api.translate_releases_batch",
+        Some(test_user_id(1)),
+    )
+    .await;
+
+    let state = setup_state(pool);
+    let started_from = (chrono::Utc::now() - chrono::Duration::hours(1))
+        .to_rfc3339_opts(chrono::SecondsFormat::Secs, true);
+    let started_to = (chrono::Utc::now() + chrono::Duration::hours(1))
+        .to_rfc3339_opts(chrono::SecondsFormat::Secs, true);
+
+    let resp = admin_list_llm_calls(
+        State(state),
+        AdminUser(test_user_id(1)),
+        Query(AdminLlmCallsQuery {
+            status: Some("all".to_owned()),
+            source: Some("api.translate_releases_batch".to_owned()),
+            requested_by: Some(test_user_id(1)),
+            parent_task_id: None,
+            request_id: None,
+            started_from: Some(started_from),
+            started_to: Some(started_to),
+            sort: None,
+            page: Some(1),
+            page_size: Some(20),
+        }),
+    )
+    .await
+    .expect("admin llm call list should accept zulu time filters")
+    .0;
+
+    assert_eq!(resp.total, 1);
+    assert_eq!(resp.items.len(), 1);
+    assert_eq!(resp.items[0].id, "call-zulu");
+}
+
+#[tokio::test]
+async fn admin_list_llm_calls_started_filters_include_override_started_at() {
+    let pool = setup_pool().await;
+    sqlx::query(r#"UPDATE users SET is_admin = 1 WHERE id = ?"#)
+        .bind(test_user_id(1))
+        .execute(&pool)
+        .await
+        .expect("promote seeded user to admin");
+    seed_llm_call_with_created_at(
+        &pool,
+        "call-override-started",
+        "running",
+        "api.translate_releases_batch",
+        Some(test_user_id(1)),
+        "2026-02-26T02:00:00Z",
+    )
+    .await;
+
+    let state = setup_state(pool);
+    let override_started_at = chrono::Utc::now().to_rfc3339_opts(chrono::SecondsFormat::Secs, true);
+    state
+        .llm_scheduler
+        .set_admin_override(crate::ai::LlmCallAdminOverride {
+            id: "call-override-started".to_owned(),
+            status: "running".to_owned(),
+            attempt_count: 1,
+            scheduler_wait_ms: 0,
+            first_token_wait_ms: None,
+            duration_ms: None,
+            input_tokens: None,
+            output_tokens: None,
+            cached_input_tokens: None,
+            total_tokens: None,
+            output_messages_json: None,
+            response_text: None,
+            error_text: None,
+            started_at: Some(override_started_at.clone()),
+            finished_at: None,
+            updated_at: override_started_at.clone(),
+        })
+        .await;
+
+    let started_from = (chrono::DateTime::parse_from_rfc3339(&override_started_at)
+        .expect("parse override started_at")
+        - chrono::Duration::minutes(1))
+    .to_rfc3339();
+    let started_to = (chrono::DateTime::parse_from_rfc3339(&override_started_at)
+        .expect("parse override started_at")
+        + chrono::Duration::minutes(1))
+    .to_rfc3339();
+
+    let resp = admin_list_llm_calls(
+        State(state),
+        AdminUser(test_user_id(1)),
+        Query(AdminLlmCallsQuery {
+            status: Some("all".to_owned()),
+            source: Some("api.translate_releases_batch".to_owned()),
+            requested_by: Some(test_user_id(1)),
+            parent_task_id: None,
+            request_id: None,
+            started_from: Some(started_from),
+            started_to: Some(started_to),
+            sort: Some("status_grouped".to_owned()),
+            page: Some(1),
+            page_size: Some(20),
+        }),
+    )
+    .await
+    .expect("override-backed llm call should respect started_at filters")
+    .0;
+
+    assert_eq!(resp.total, 1);
+    assert_eq!(resp.items.len(), 1);
+    assert_eq!(resp.items[0].id, "call-override-started");
+    assert_eq!(
+        resp.items[0].started_at.as_deref(),
+        Some(override_started_at.as_str())
+    );
+}
+
+#[tokio::test]
+async fn admin_list_llm_calls_filters_parent_task_id() {
+    let pool = setup_pool().await;
+    sqlx::query(r#"UPDATE users SET is_admin = 1 WHERE id = ?"#)
+        .bind(test_user_id(1))
+        .execute(&pool)
+        .await
+        .expect("promote seeded user to admin");
+    seed_llm_call(
+        &pool,
+        "call-parent-a",
+        "succeeded",
+        "api.translate_releases_batch",
+        Some(test_user_id(1)),
+    )
+    .await;
+    seed_llm_call(
+        &pool,
+        "call-parent-b",
+        "succeeded",
+        "api.translate_releases_batch",
+        Some(test_user_id(1)),
+    )
+    .await;
+    let now = chrono::Utc::now().to_rfc3339();
+    let parent_task_a = crate::local_id::test_local_id("task-123");
+    let parent_task_b = crate::local_id::test_local_id("task-456");
+    for task_id in [&parent_task_a, &parent_task_b] {
+        sqlx::query(
+                r#"
+                INSERT INTO job_tasks (
+                  id,
+                  task_type,
+                  status,
+                  source,
+                  requested_by,
+                  parent_task_id,
+                  payload_json,
+                  result_json,
+                  error_message,
+                  cancel_requested,
+                  created_at,
+                  started_at,
+                  finished_at,
+                  updated_at
+                )
+                VALUES (?, 'sync.releases', 'succeeded', 'tests', ?, NULL, '{}', '{}', NULL, 0, ?, ?, ?, ?)
+                "#,
+            )
+            .bind(task_id)
+            .bind(test_user_id(1))
+            .bind(now.as_str())
+            .bind(now.as_str())
+            .bind(now.as_str())
+            .bind(now.as_str())
+            .execute(&pool)
+            .await
+            .expect("seed parent task");
+    }
+    sqlx::query(
+        r#"
+            UPDATE llm_calls
+            SET parent_task_id = ?, parent_task_type = ?
+            WHERE id = ?
+            "#,
+    )
+    .bind(&parent_task_a)
+    .bind("translate.release.batch")
+    .bind("call-parent-a")
+    .execute(&pool)
+    .await
+    .expect("set parent task for call-parent-a");
+    sqlx::query(
+        r#"
+            UPDATE llm_calls
+            SET parent_task_id = ?, parent_task_type = ?
+            WHERE id = ?
+            "#,
+    )
+    .bind(&parent_task_b)
+    .bind("sync.releases")
+    .bind("call-parent-b")
+    .execute(&pool)
+    .await
+    .expect("set parent task for call-parent-b");
+
+    let state = setup_state(pool);
+
+    let resp = admin_list_llm_calls(
+        State(state),
+        AdminUser(test_user_id(1)),
+        Query(AdminLlmCallsQuery {
+            status: Some("all".to_owned()),
+            source: None,
+            requested_by: Some(test_user_id(1)),
+            parent_task_id: Some(parent_task_a.clone()),
+            request_id: None,
+            started_from: None,
+            started_to: None,
+            sort: None,
+            page: Some(1),
+            page_size: Some(20),
+        }),
+    )
+    .await
+    .expect("admin llm call list should filter by parent_task_id")
+    .0;
+
+    assert_eq!(resp.total, 1);
+    assert_eq!(resp.items.len(), 1);
+    assert_eq!(resp.items[0].id, "call-parent-a");
+    assert_eq!(
+        resp.items[0].parent_task_id.as_deref(),
+        Some(parent_task_a.as_str())
+    );
+}
+
+#[tokio::test]
+async fn admin_get_llm_call_detail_returns_not_found() {
+    let pool = setup_pool().await;
+    sqlx::query(r#"UPDATE users SET is_admin = 1 WHERE id = ?"#)
+        .bind(test_user_id(1))
+        .execute(&pool)
+        .await
+        .expect("promote seeded user to admin");
+    let state = setup_state(pool);
+
+    let err = admin_get_llm_call_detail(
+        State(state),
+        AdminUser(test_user_id(1)),
+        Path(crate::local_id::test_local_id("missing-call")),
+    )
+    .await
+    .expect_err("missing llm call should fail");
+    assert_eq!(err.code(), "not_found");
+}
+
+#[tokio::test]
+async fn admin_get_llm_call_detail_includes_tokens_and_messages() {
+    let pool = setup_pool().await;
+    sqlx::query(r#"UPDATE users SET is_admin = 1 WHERE id = ?"#)
+        .bind(test_user_id(1))
+        .execute(&pool)
+        .await
+        .expect("promote seeded user to admin");
+    seed_llm_call(
+        &pool,
+        &crate::local_id::test_local_id("call-detail"),
+        "succeeded",
+        "api.translate_releases_batch",
+        Some(test_user_id(1)),
+    )
+    .await;
+
+    let state = setup_state(pool);
+    let resp = admin_get_llm_call_detail(
+        State(state),
+        AdminUser(test_user_id(1)),
+        Path(crate::local_id::test_local_id("call-detail")),
+    )
+    .await
+    .expect("llm call detail should pass")
+    .0;
+
+    assert_eq!(resp.id, crate::local_id::test_local_id("call-detail"));
+    assert_eq!(resp.first_token_wait_ms, Some(340));
+    assert_eq!(resp.input_tokens, Some(120));
+    assert_eq!(resp.output_tokens, Some(55));
+    assert_eq!(resp.cached_input_tokens, Some(20));
+    assert_eq!(resp.total_tokens, Some(175));
+    assert!(
+        resp.input_messages_json
+            .as_deref()
+            .is_some_and(|value| value.contains("\"role\":\"user\""))
+    );
+    assert!(
+        resp.output_messages_json
+            .as_deref()
+            .is_some_and(|value| value.contains("\"assistant\""))
+    );
+}
+
+#[tokio::test]
+async fn admin_get_llm_call_detail_uses_admin_override_snapshot() {
+    let pool = setup_pool().await;
+    sqlx::query(r#"UPDATE users SET is_admin = 1 WHERE id = ?"#)
+        .bind(test_user_id(1))
+        .execute(&pool)
+        .await
+        .expect("promote seeded user to admin");
+    let call_id = crate::local_id::test_local_id("call-detail-override");
+    seed_llm_call(
+        &pool,
+        &call_id,
+        "running",
+        "api.translate_releases_batch",
+        Some(test_user_id(1)),
+    )
+    .await;
+
+    let state = setup_state(pool);
+    state
+        .llm_scheduler
+        .set_admin_override(crate::ai::LlmCallAdminOverride {
+            id: call_id.clone(),
+            status: "succeeded".to_owned(),
+            attempt_count: 4,
+            scheduler_wait_ms: 260,
+            first_token_wait_ms: Some(75),
+            duration_ms: Some(910),
+            input_tokens: Some(300),
+            output_tokens: Some(120),
+            cached_input_tokens: Some(40),
+            total_tokens: Some(420),
+            output_messages_json: Some(
+                r#"[{"role":"assistant","content":"override detail"}]"#.to_owned(),
+            ),
+            response_text: Some("override detail response".to_owned()),
+            error_text: None,
+            started_at: Some("2026-02-26T03:00:01Z".to_owned()),
+            finished_at: Some("2026-02-26T03:00:08Z".to_owned()),
+            updated_at: "2026-02-26T03:00:08Z".to_owned(),
+        })
+        .await;
+
+    let resp = admin_get_llm_call_detail(State(state), AdminUser(test_user_id(1)), Path(call_id))
+        .await
+        .expect("llm call detail should expose override snapshot")
+        .0;
+
+    assert_eq!(resp.status, "succeeded");
+    assert_eq!(resp.attempt_count, 4);
+    assert_eq!(resp.scheduler_wait_ms, 260);
+    assert_eq!(resp.first_token_wait_ms, Some(75));
+    assert_eq!(resp.duration_ms, Some(910));
+    assert_eq!(resp.input_tokens, Some(300));
+    assert_eq!(resp.output_tokens, Some(120));
+    assert_eq!(resp.cached_input_tokens, Some(40));
+    assert_eq!(resp.total_tokens, Some(420));
+    assert_eq!(
+        resp.output_messages_json.as_deref(),
+        Some(r#"[{"role":"assistant","content":"override detail"}]"#)
+    );
+    assert_eq!(
+        resp.response_text.as_deref(),
+        Some("override detail response")
+    );
+    assert_eq!(resp.started_at.as_deref(), Some("2026-02-26T03:00:01Z"));
+    assert_eq!(resp.finished_at.as_deref(), Some("2026-02-26T03:00:08Z"));
+    assert_eq!(resp.updated_at, "2026-02-26T03:00:08Z");
+}
+
+#[tokio::test]
+async fn admin_get_llm_scheduler_status_reads_aggregates() {
+    let pool = setup_pool().await;
+    sqlx::query(r#"UPDATE users SET is_admin = 1 WHERE id = ?"#)
+        .bind(test_user_id(1))
+        .execute(&pool)
+        .await
+        .expect("promote seeded user to admin");
+    seed_llm_call(
+        &pool,
+        &crate::local_id::test_local_id("call-1"),
+        "failed",
+        "api.translate_releases_batch",
+        Some(test_user_id(1)),
+    )
+    .await;
+    seed_llm_call(
+        &pool,
+        &crate::local_id::test_local_id("call-2"),
+        "succeeded",
+        "api.translate_releases_batch",
+        Some(test_user_id(1)),
+    )
+    .await;
+
+    let state = setup_state(pool);
+    let resp = admin_get_llm_scheduler_status(State(state), AdminUser(test_user_id(1)))
+        .await
+        .expect("status should succeed")
+        .0;
+
+    assert_eq!(resp.max_concurrency, 1);
+    assert_eq!(resp.available_slots, 1);
+    assert_eq!(resp.calls_24h, 2);
+    assert_eq!(resp.failed_24h, 1);
+    assert!(resp.avg_wait_ms_24h.is_some());
+}
+
+#[tokio::test]
+async fn admin_get_llm_scheduler_status_syncs_persisted_runtime_settings() {
+    let pool = setup_pool().await;
+    sqlx::query(r#"UPDATE users SET is_admin = 1 WHERE id = ?"#)
+        .bind(test_user_id(1))
+        .execute(&pool)
+        .await
+        .expect("promote seeded user to admin");
+    let now = "2026-03-28T11:00:00Z";
+    sqlx::query(
+        r#"
+            INSERT INTO admin_runtime_settings (
+              id,
+              llm_max_concurrency,
+              ai_model_context_limit,
+              translation_general_worker_concurrency,
+              translation_dedicated_worker_concurrency,
+              created_at,
+              updated_at
+            )
+            VALUES (1, 4, 65536, 3, 1, ?, ?)
+            "#,
+    )
+    .bind(now)
+    .bind(now)
+    .execute(&pool)
+    .await
+    .expect("insert runtime settings");
+
+    let state = setup_state(pool);
+    assert_eq!(state.llm_scheduler.max_concurrency(), 1);
+
+    let resp = admin_get_llm_scheduler_status(State(state.clone()), AdminUser(test_user_id(1)))
+        .await
+        .expect("status should succeed")
+        .0;
+
+    assert_eq!(resp.max_concurrency, 4);
+    assert_eq!(resp.ai_model_context_limit, Some(65_536));
+    assert_eq!(resp.effective_model_input_limit, 65_536);
+    assert_eq!(resp.effective_model_input_limit_source, "admin_override");
+    assert_eq!(resp.available_slots, 4);
+    assert_eq!(state.llm_scheduler.max_concurrency(), 4);
+}
+
+#[tokio::test]
+async fn admin_patch_llm_runtime_config_preserves_saved_model_limit_when_field_is_omitted() {
+    let pool = setup_pool().await;
+    sqlx::query(r#"UPDATE users SET is_admin = 1 WHERE id = ?"#)
+        .bind(test_user_id(1))
+        .execute(&pool)
+        .await
+        .expect("promote seeded user to admin");
+    let now = "2026-03-28T11:00:00Z";
+    sqlx::query(
+        r#"
+            INSERT INTO admin_runtime_settings (
+              id,
+              llm_max_concurrency,
+              ai_model_context_limit,
+              translation_general_worker_concurrency,
+              translation_dedicated_worker_concurrency,
+              created_at,
+              updated_at
+            )
+            VALUES (1, 4, 65536, 3, 1, ?, ?)
+            "#,
+    )
+    .bind(now)
+    .bind(now)
+    .execute(&pool)
+    .await
+    .expect("insert runtime settings");
+
+    let state = setup_state(pool.clone());
+    let resp = admin_patch_llm_runtime_config(
+        State(state),
+        AdminUser(test_user_id(1)),
+        ApiJson(AdminLlmRuntimeConfigUpdateRequest {
+            max_concurrency: 2,
+            ai_model_context_limit: None,
+            llm_models: None,
+        }),
+    )
+    .await
+    .expect("patch runtime settings")
+    .0;
+
+    assert_eq!(resp.max_concurrency, 2);
+    assert_eq!(resp.ai_model_context_limit, Some(65_536));
+    assert_eq!(resp.effective_model_input_limit, 65_536);
+    assert_eq!(resp.effective_model_input_limit_source, "admin_override");
+}
+
+#[tokio::test]
+async fn migration_backfills_earliest_user_as_admin() {
+    let database_path = std::env::temp_dir().join(format!(
+        "octo-rill-test-{}.db",
+        crate::local_id::generate_local_id(),
+    ));
+    let options = SqliteConnectOptions::new()
+        .filename(&database_path)
+        .create_if_missing(true);
+    let pool = SqlitePoolOptions::new()
+        .max_connections(1)
+        .connect_with(options)
+        .await
+        .expect("create sqlite memory db");
+
+    sqlx::raw_sql(
+        r#"
+            CREATE TABLE users (
+              id INTEGER PRIMARY KEY AUTOINCREMENT,
+              github_user_id INTEGER NOT NULL UNIQUE,
+              login TEXT NOT NULL,
+              name TEXT,
+              avatar_url TEXT,
+              email TEXT,
+              created_at TEXT NOT NULL,
+              updated_at TEXT NOT NULL
+            );
+            "#,
+    )
+    .execute(&pool)
+    .await
+    .expect("create legacy users table");
+
+    sqlx::query(
+        r#"
+            INSERT INTO users (id, github_user_id, login, created_at, updated_at)
+            VALUES (2, 200, 'later', '2026-02-25T09:00:00Z', '2026-02-25T09:00:00Z'),
+                   (1, 100, 'earlier', '2026-02-25T08:00:00Z', '2026-02-25T08:00:00Z')
+            "#,
+    )
+    .execute(&pool)
+    .await
+    .expect("insert legacy users");
+
+    sqlx::raw_sql(include_str!(
+        "../../migrations/0006_user_admin_and_disable.sql"
+    ))
+    .execute(&pool)
+    .await
+    .expect("apply admin migration");
+
+    #[derive(Debug, sqlx::FromRow)]
+    struct UserAdminState {
+        id: i64,
+        is_admin: i64,
+        is_disabled: i64,
+    }
+
+    let rows = sqlx::query_as::<_, UserAdminState>(
+        r#"
+            SELECT id, is_admin, is_disabled
+            FROM users
+            ORDER BY id ASC
+            "#,
+    )
+    .fetch_all(&pool)
+    .await
+    .expect("query migrated users");
+
+    assert_eq!(rows.len(), 2);
+    assert_eq!(rows[0].id, 1);
+    assert_eq!(rows[0].is_admin, 1);
+    assert_eq!(rows[0].is_disabled, 0);
+    assert_eq!(rows[1].id, 2);
+    assert_eq!(rows[1].is_admin, 0);
+}
+
+#[test]
+fn parse_release_id_param_requires_integer_string() {
+    assert_eq!(parse_release_id_param("123").expect("release id"), 123);
+    assert!(parse_release_id_param("12a").is_err());
+    assert!(parse_release_id_param("   ").is_err());
+}
+
+#[test]
+fn parse_release_id_param_rejects_oversized_values() {
+    let err = parse_release_id_param(&"1".repeat(10 * 1024)).expect_err("oversized id");
+    assert_eq!(err.code(), "bad_request");
+    assert_eq!(err.to_string(), "release_id must be at most 64 characters");
+}
+
+#[test]
+fn validate_pat_token_shape_checks_length_and_charset() {
+    assert!(validate_pat_token_shape("ghp_AbC123").is_ok());
+    assert!(validate_pat_token_shape("github_pat_11AAAA_bbbb").is_ok());
+    assert!(validate_pat_token_shape("ghp_bad token").is_err());
+    assert!(validate_pat_token_shape("ghp_\u{1F600}").is_err());
+    assert!(validate_pat_token_shape(&"a".repeat(256)).is_err());
+}
+
+#[test]
+fn parse_unique_release_ids_rejects_oversized_requests() {
+    let raw_ids = (1..=61).map(|id| id.to_string()).collect::<Vec<_>>();
+    let err = parse_unique_release_ids(&raw_ids, 60).expect_err("oversized release batch rejected");
+    assert_eq!(err.code(), "bad_request");
+}
+
+#[test]
+fn parse_unique_thread_ids_rejects_oversized_requests() {
+    let raw_ids = (1..=61)
+        .map(|id| format!("thread-{id}"))
+        .collect::<Vec<_>>();
+    let err = parse_unique_thread_ids(&raw_ids, 60).expect_err("oversized thread batch rejected");
+    assert_eq!(err.code(), "bad_request");
+}
+
+#[test]
+fn parse_llm_models_rejects_duplicates_after_normalization() {
+    let err = parse_llm_models(vec!["gpt-4o-mini".to_owned(), "  gpt-4o-mini  ".to_owned()])
+        .expect_err("duplicate model ids should be rejected");
+    assert_eq!(err.code(), "bad_request");
+}
+
+#[test]
+fn api_non_retryable_error_keeps_rate_limit_retryable_for_fallback() {
+    assert!(!ai_error_is_non_retryable(&anyhow::Error::from(
+        ai::AiError {
+            provider: crate::config::AiProvider::OpenAi,
+            status: reqwest::StatusCode::TOO_MANY_REQUESTS,
+            code: None,
+            message: "upstream rate limit".to_owned(),
+        }
+    )));
+}
+
+#[test]
+fn ai_budget_exceeded_is_non_retryable_and_maps_to_429() {
+    let err = anyhow::Error::from(ai::AiBudgetExceeded {
+        used: 10_100,
+        budget: 10_000,
+    })
+    .context("brief generation failed");
+    assert!(ai_error_is_non_retryable(&err));
+    assert!(!ai::is_ai_budget_exceeded_error(&anyhow::anyhow!(
+        "{}: mentioned in an upstream message",
+        ai::AI_BUDGET_EXCEEDED_ERROR
+    )));
+
+    let api_err = ai_call_api_error(err);
+    assert_eq!(api_err.code(), "ai_budget_exceeded");
+    assert_eq!(
+        api_err.into_response().status(),
+        StatusCode::TOO_MANY_REQUESTS
+    );
+}
+
+#[test]
+fn translate_response_from_batch_item_keeps_ready_status() {
+    let item = TranslateBatchItem {
+        id: "1".to_owned(),
+        lang: "zh-CN".to_owned(),
+        status: "ready".to_owned(),
+        title: Some("标题".to_owned()),
+        summary: Some("摘要".to_owned()),
+        error: None,
+    };
+    let response =
+        translate_response_from_batch_item(item).expect("ready batch item should succeed");
+    let json = serde_json::to_value(response).expect("serialize response");
+    assert_eq!(json.get("status").and_then(|v| v.as_str()), Some("ready"));
+    assert_eq!(json.get("title").and_then(|v| v.as_str()), Some("标题"));
+}
+
+#[test]
+fn translate_response_from_batch_item_maps_error_to_internal() {
+    let item = TranslateBatchItem {
+        id: "1".to_owned(),
+        lang: "zh-CN".to_owned(),
+        status: "error".to_owned(),
+        title: None,
+        summary: None,
+        error: Some("release detail translation failed to preserve markdown structure".to_owned()),
+    };
+    let err = translate_response_from_batch_item(item).expect_err("error item should fail");
+    assert_eq!(err.code(), "internal_error");
+    assert_eq!(err.to_string(), "Markdown 结构校验失败");
+}
+
+#[test]
+fn translate_response_from_batch_item_maps_upstream_rejection_to_summary() {
+    let item = TranslateBatchItem {
+        id: "1".to_owned(),
+        lang: "zh-CN".to_owned(),
+        status: "error".to_owned(),
+        title: None,
+        summary: None,
+        error: Some("ai returned 401: bad key".to_owned()),
+    };
+    let err = translate_response_from_batch_item(item).expect_err("error item should fail");
+    assert_eq!(err.code(), "internal_error");
+    assert_eq!(err.to_string(), "上游模型拒绝请求");
+}
+
+#[test]
+fn translate_response_from_batch_item_maps_missing_to_not_found() {
+    let item = TranslateBatchItem {
+        id: "1".to_owned(),
+        lang: "zh-CN".to_owned(),
+        status: "missing".to_owned(),
+        title: None,
+        summary: None,
+        error: Some("release not found".to_owned()),
+    };
+    let err = translate_response_from_batch_item(item).expect_err("missing item should fail");
+    assert_eq!(err.code(), "not_found");
+}
+
+#[test]
+fn parse_local_id_param_accepts_trimmed_nanoid() {
+    let id = crate::local_id::test_local_id("api-parse-local-id");
+    assert_eq!(
+        super::parse_local_id_param(format!("  {id}  "), "task_id").expect("local id"),
+        id
+    );
+}
+
+#[test]
+fn parse_local_id_param_rejects_malformed_value() {
+    let err = super::parse_local_id_param("bad-id".to_owned(), "task_id").expect_err("invalid");
+    assert_eq!(err.code(), "bad_request");
+    assert_eq!(err.to_string(), "invalid task_id");
+}
+
+#[test]
+fn task_diagnostics_translate_batch_surfaces_business_failure() {
+    let task = test_task_detail_item(
+        jobs::TASK_TRANSLATE_RELEASE_BATCH,
+        jobs::STATUS_SUCCEEDED,
+        r#"{"user_id":1,"release_ids":[291058027,291042015]}"#,
+        Some(r#"{"total":2,"ready":0,"missing":0,"disabled":0,"error":2}"#),
+        None,
+    );
+    // Intentionally out of order to verify diagnostics sorts by event id.
+    let events = vec![
+        test_task_event(
+            11,
+            "task.progress",
+            r#"{"stage":"release","release_id":"291042015","item_status":"error","item_error":"translation failed"}"#,
+        ),
+        test_task_event(
+            10,
+            "task.progress",
+            r#"{"stage":"release","release_id":"291058027","item_status":"error","item_error":"translation failed"}"#,
+        ),
+        test_task_event(
+            9,
+            "task.progress",
+            r#"{"stage":"collect","total_releases":2}"#,
+        ),
+    ];
+
+    let diagnostics = build_task_diagnostics(&task, &events, &[]).expect("diagnostics");
+    assert_eq!(diagnostics.business_outcome.code, "failed");
+    let translate_diag = diagnostics
+        .translate_release_batch
+        .expect("translate batch diagnostics");
+    assert_eq!(translate_diag.release_total, 2);
+    assert_eq!(translate_diag.summary.error, 2);
+    assert_eq!(translate_diag.progress.processed, 2);
+    assert_eq!(
+        translate_diag.progress.last_stage.as_deref(),
+        Some("release")
+    );
+    assert_eq!(translate_diag.items.len(), 2);
+    assert_eq!(translate_diag.items[0].release_id, "291058027");
+    assert_eq!(
+        translate_diag.items[0].item_error.as_deref(),
+        Some("translation failed")
+    );
+}
+
+#[test]
+fn task_diagnostics_translate_batch_item_only_result_surfaces_partial() {
+    let task = test_task_detail_item(
+        jobs::TASK_TRANSLATE_RELEASE_BATCH,
+        jobs::STATUS_SUCCEEDED,
+        r#"{"user_id":1,"release_ids":[291058027,291042015,291040000]}"#,
+        Some(
+            r#"{"items":[
+                  {"id":"291058027","lang":"zh-CN","status":"ready","title":"A","summary":"alpha","error":null},
+                  {"id":"291042015","lang":"zh-CN","status":"missing","title":null,"summary":null,"error":"not found"},
+                  {"id":"291040000","lang":"zh-CN","status":"error","title":null,"summary":null,"error":"translation failed"}
+                ]}"#,
+        ),
+        None,
+    );
+
+    let diagnostics = build_task_diagnostics(&task, &[], &[]).expect("diagnostics");
+    assert_eq!(diagnostics.business_outcome.code, "partial");
+    let translate_diag = diagnostics
+        .translate_release_batch
+        .expect("translate batch diagnostics");
+    assert_eq!(
+        translate_diag.summary,
+        super::AdminTranslateReleaseBatchSummary {
+            total: 3,
+            ready: 1,
+            missing: 1,
+            disabled: 0,
+            error: 1,
+        }
+    );
+    assert_eq!(translate_diag.progress.processed, 3);
+    assert_eq!(translate_diag.items.len(), 3);
+    assert_eq!(translate_diag.items[0].release_id, "291058027");
+    assert_eq!(translate_diag.items[0].item_status, "ready");
+    assert_eq!(translate_diag.items[1].release_id, "291042015");
+    assert_eq!(translate_diag.items[1].item_status, "missing");
+    assert_eq!(translate_diag.items[2].release_id, "291040000");
+    assert_eq!(translate_diag.items[2].item_status, "error");
+    assert_eq!(
+        translate_diag.items[2].item_error.as_deref(),
+        Some("translation failed")
+    );
+}
+
+#[test]
+fn task_diagnostics_translate_batch_running_shows_processing() {
+    let task = test_task_detail_item(
+        jobs::TASK_TRANSLATE_RELEASE_BATCH,
+        jobs::STATUS_RUNNING,
+        r#"{"user_id":1,"release_ids":[291058027]}"#,
+        None,
+        None,
+    );
+    let events = vec![test_task_event(
+        1,
+        "task.progress",
+        r#"{"stage":"collect","total_releases":1}"#,
+    )];
+
+    let diagnostics = build_task_diagnostics(&task, &events, &[]).expect("diagnostics");
+    assert_eq!(diagnostics.business_outcome.code, "unknown");
+    assert_eq!(diagnostics.business_outcome.label, "处理中");
+}
+
+#[test]
+fn task_diagnostics_daily_slot_aggregates_user_states() {
+    let task = test_task_detail_item(
+        jobs::TASK_BRIEF_DAILY_SLOT,
+        jobs::STATUS_SUCCEEDED,
+        r#"{"hour_utc":0}"#,
+        Some(r#"{"total":2,"succeeded":1,"failed":1}"#),
+        None,
+    );
+    let events = vec![
+        test_task_event(1, "task.progress", r#"{"stage":"collect","total_users":2}"#),
+        test_task_event(
+            2,
+            "task.progress",
+            r#"{"stage":"generate","index":1,"total":2,"user_id":1,"key_date":"2026-02-27"}"#,
+        ),
+        test_task_event(
+            3,
+            "task.progress",
+            r#"{"stage":"user_succeeded","user_id":1,"key_date":"2026-02-27","content_length":1200}"#,
+        ),
+        test_task_event(
+            4,
+            "task.progress",
+            r#"{"stage":"generate","index":2,"total":2,"user_id":2,"key_date":"2026-02-27"}"#,
+        ),
+        test_task_event(
+            5,
+            "task.progress",
+            r#"{"stage":"user_failed","user_id":2,"error":"ai timeout"}"#,
+        ),
+        test_task_event(
+            6,
+            "task.progress",
+            r#"{"stage":"summary","total":2,"succeeded":1,"failed":1,"canceled":false}"#,
+        ),
+    ];
+
+    let diagnostics = build_task_diagnostics(&task, &events, &[]).expect("diagnostics");
+    assert_eq!(diagnostics.business_outcome.code, "partial");
+    let slot_diag = diagnostics
+        .brief_daily_slot
+        .expect("daily slot diagnostics");
+    assert_eq!(slot_diag.summary.total_users, 2);
+    assert_eq!(slot_diag.summary.progressed_users, 2);
+    assert_eq!(slot_diag.summary.succeeded_users, 1);
+    assert_eq!(slot_diag.summary.failed_users, 1);
+    assert_eq!(slot_diag.users.len(), 2);
+    assert_eq!(slot_diag.users[0].user_id, "1");
+    assert_eq!(slot_diag.users[0].state, "succeeded");
+    assert_eq!(slot_diag.users[1].user_id, "2");
+    assert_eq!(slot_diag.users[1].state, "failed");
+    assert_eq!(slot_diag.users[1].error.as_deref(), Some("ai timeout"));
+}
+
+#[test]
+fn task_diagnostics_daily_slot_canceled_shows_canceled_outcome() {
+    let task = test_task_detail_item(
+        jobs::TASK_BRIEF_DAILY_SLOT,
+        jobs::STATUS_CANCELED,
+        r#"{"hour_utc":8}"#,
+        Some(r#"{"total":4,"succeeded":1,"failed":1,"canceled":true}"#),
+        None,
+    );
+    let events = vec![test_task_event(
+        1,
+        "task.progress",
+        r#"{"stage":"summary","total":4,"succeeded":1,"failed":1,"canceled":true}"#,
+    )];
+
+    let diagnostics = build_task_diagnostics(&task, &events, &[]).expect("diagnostics");
+    assert_eq!(diagnostics.business_outcome.code, "partial");
+    assert_eq!(diagnostics.business_outcome.label, "已取消");
+}
+
+#[test]
+fn task_diagnostics_sync_subscriptions_warning_events_do_not_force_partial_outcome() {
+    let task = test_task_detail_item(
+        jobs::TASK_SYNC_SUBSCRIPTIONS,
+        jobs::STATUS_SUCCEEDED,
+        r#"{"trigger":"schedule","schedule_key":"2026-03-06T14:30"}"#,
+        Some(
+            r#"{"skipped":false,"skip_reason":null,"star":{"total_users":12,"succeeded_users":12,"failed_users":0,"total_repos":8},"release":{"total_repos":8,"succeeded_repos":8,"failed_repos":0,"candidate_failures":2},"social":{"total_users":12,"succeeded_users":12,"failed_users":0,"repo_stars":18,"followers":4,"events":22},"notifications":{"total_users":12,"succeeded_users":12,"failed_users":0,"notifications":35},"releases_written":42,"critical_events":0}"#,
+        ),
+        None,
+    );
+    let subscription_events = vec![AdminSyncSubscriptionEventItem {
+        id: "1".to_owned(),
+        stage: "release".to_owned(),
+        event_type: "rate_limited".to_owned(),
+        severity: "warning".to_owned(),
+        recoverable: true,
+        attempt: 1,
+        user_id: Some("7".to_owned()),
+        repo_id: Some(9),
+        repo_full_name: Some("octo/alpha".to_owned()),
+        message: Some("retryable release sync error for octo/alpha with user #7".to_owned()),
+        created_at: "2026-03-06T14:30:01Z".to_owned(),
+    }];
+
+    let diagnostics =
+        build_task_diagnostics(&task, &[], &subscription_events).expect("diagnostics");
+    assert_eq!(diagnostics.business_outcome.code, "ok");
+    assert_eq!(diagnostics.business_outcome.label, "业务成功");
+    let sync_diag = diagnostics
+        .sync_subscriptions
+        .expect("sync subscriptions diagnostics");
+    assert_eq!(sync_diag.critical_events, 0);
+    assert_eq!(sync_diag.social.succeeded_users, 12);
+    assert_eq!(sync_diag.notifications.notifications, 35);
+    assert_eq!(sync_diag.recent_events.len(), 1);
+    assert_eq!(sync_diag.recent_events[0].severity, "warning");
+}
+
+#[test]
+fn task_diagnostics_sync_subscriptions_running_uses_progress_events() {
+    let task = test_task_detail_item(
+        jobs::TASK_SYNC_SUBSCRIPTIONS,
+        jobs::STATUS_RUNNING,
+        r#"{"trigger":"schedule","schedule_key":"interval:10:1777954200"}"#,
+        None,
+        None,
+    );
+    let events = vec![
+        test_task_event(
+            5,
+            "task.progress",
+            r#"{"stage":"release_summary","total_repos":12255,"succeeded_repos":4482,"failed_repos":7773,"candidate_failures":6,"releases_written":102599}"#,
+        ),
+        test_task_event(
+            3,
+            "task.progress",
+            r#"{"stage":"star_summary","total_users":13,"succeeded_users":13,"failed_users":0,"total_repos":13801}"#,
+        ),
+        test_task_event(
+            4,
+            "task.progress",
+            r#"{"stage":"repo_collect","total_repos":12255}"#,
+        ),
+        test_task_event(
+            1,
+            "task.progress",
+            r#"{"stage":"collect","total_users":13}"#,
+        ),
+        test_task_event(2, "task.running", r#"{"status":"running"}"#),
+    ];
+
+    let diagnostics = build_task_diagnostics(&task, &events, &[]).expect("diagnostics");
+    assert_eq!(diagnostics.business_outcome.code, "unknown");
+    let sync_diag = diagnostics
+        .sync_subscriptions
+        .expect("sync subscriptions diagnostics");
+    assert_eq!(sync_diag.trigger.as_deref(), Some("schedule"));
+    assert_eq!(
+        sync_diag.schedule_key.as_deref(),
+        Some("interval:10:1777954200")
+    );
+    assert_eq!(sync_diag.star.total_users, 13);
+    assert_eq!(sync_diag.star.succeeded_users, 13);
+    assert_eq!(sync_diag.star.total_repos, 13801);
+    assert_eq!(sync_diag.release.total_repos, 12255);
+    assert_eq!(sync_diag.release.succeeded_repos, 4482);
+    assert_eq!(sync_diag.release.failed_repos, 7773);
+    assert_eq!(sync_diag.release.candidate_failures, 6);
+    assert_eq!(sync_diag.releases_written, 102599);
+}
+
+#[test]
+fn task_diagnostics_sync_subscriptions_running_uses_release_attached_progress() {
+    let task = test_task_detail_item(
+        jobs::TASK_SYNC_SUBSCRIPTIONS,
+        jobs::STATUS_RUNNING,
+        r#"{"trigger":"schedule","schedule_key":"interval:10:1777954800"}"#,
+        None,
+        None,
+    );
+    let events = vec![
+        test_task_event(
+            3,
+            "task.progress",
+            r#"{"stage":"release_attached","repos":128,"queued":68,"reused_running":2,"reused_fresh":58}"#,
+        ),
+        test_task_event(
+            2,
+            "task.progress",
+            r#"{"stage":"repo_collect","total_repos":128}"#,
+        ),
+        test_task_event(
+            1,
+            "task.progress",
+            r#"{"stage":"star_summary","total_users":12,"succeeded_users":11,"failed_users":1,"total_repos":340}"#,
+        ),
+    ];
+
+    let diagnostics = build_task_diagnostics(&task, &events, &[]).expect("diagnostics");
+    let sync_diag = diagnostics
+        .sync_subscriptions
+        .expect("sync subscriptions diagnostics");
+    assert_eq!(sync_diag.release.total_repos, 128);
+    assert_eq!(sync_diag.release.succeeded_repos, 0);
+    assert_eq!(sync_diag.release.failed_repos, 0);
+    assert_eq!(sync_diag.releases_written, 0);
+}
+
+#[test]
+fn task_diagnostics_sync_subscriptions_running_uses_release_progress() {
+    let task = test_task_detail_item(
+        jobs::TASK_SYNC_SUBSCRIPTIONS,
+        jobs::STATUS_RUNNING,
+        r#"{"trigger":"schedule","schedule_key":"interval:10:1777955400"}"#,
+        None,
+        None,
+    );
+    let events = vec![
+        test_task_event(
+            1,
+            "task.progress",
+            r#"{"stage":"star_summary","total_users":12,"succeeded_users":11,"failed_users":1,"total_repos":340}"#,
+        ),
+        test_task_event(
+            2,
+            "task.progress",
+            r#"{"stage":"repo_collect","total_repos":128}"#,
+        ),
+        test_task_event(
+            3,
+            "task.progress",
+            r#"{"stage":"release_progress","total_repos":128,"succeeded_repos":58,"failed_repos":2,"pending_repos":68,"candidate_failures":3,"releases_written":680}"#,
+        ),
+    ];
+
+    let diagnostics = build_task_diagnostics(&task, &events, &[]).expect("diagnostics");
+    let sync_diag = diagnostics
+        .sync_subscriptions
+        .expect("sync subscriptions diagnostics");
+    assert_eq!(sync_diag.release.total_repos, 128);
+    assert_eq!(sync_diag.release.succeeded_repos, 58);
+    assert_eq!(sync_diag.release.failed_repos, 2);
+    assert_eq!(sync_diag.release.candidate_failures, 3);
+    assert_eq!(sync_diag.releases_written, 680);
+}
+
+#[test]
+fn task_diagnostics_sync_subscriptions_result_overrides_stale_release_progress() {
+    let task = test_task_detail_item(
+        jobs::TASK_SYNC_SUBSCRIPTIONS,
+        jobs::STATUS_SUCCEEDED,
+        r#"{"trigger":"schedule","schedule_key":"interval:10:1777956000"}"#,
+        Some(
+            r#"{"skipped":false,"star":{"total_users":12,"succeeded_users":12,"failed_users":0,"total_repos":340},"release":{"total_repos":128,"succeeded_repos":120,"failed_repos":8,"candidate_failures":4},"social":{"total_users":12,"succeeded_users":12,"failed_users":0,"repo_stars":20,"followers":5,"events":44},"notifications":{"total_users":12,"succeeded_users":11,"failed_users":1,"notifications":17},"releases_written":900,"critical_events":0}"#,
+        ),
+        None,
+    );
+    let events = vec![test_task_event(
+        1,
+        "task.progress",
+        r#"{"stage":"release_progress","total_repos":128,"succeeded_repos":58,"failed_repos":2,"pending_repos":68,"candidate_failures":3,"releases_written":680}"#,
+    )];
+
+    let diagnostics = build_task_diagnostics(&task, &events, &[]).expect("diagnostics");
+    let sync_diag = diagnostics
+        .sync_subscriptions
+        .expect("sync subscriptions diagnostics");
+    assert_eq!(sync_diag.release.succeeded_repos, 120);
+    assert_eq!(sync_diag.release.failed_repos, 8);
+    assert_eq!(sync_diag.release.candidate_failures, 4);
+    assert_eq!(sync_diag.releases_written, 900);
+}
+
+#[test]
+fn task_diagnostics_sync_subscriptions_surfaces_recent_events_and_log_download() {
+    let log_dir = std::env::temp_dir().join(format!(
+        "octo-rill-sync-diagnostics-{}",
+        crate::local_id::generate_local_id()
+    ));
+    fs::create_dir_all(&log_dir).expect("create log dir");
+    let log_path = log_dir.join("task-test.ndjson");
+    fs::write(
+        &log_path,
+        r#"{"event":"started"}
+"#,
+    )
+    .expect("write task log");
+
+    let mut task = test_task_detail_item(
+        jobs::TASK_SYNC_SUBSCRIPTIONS,
+        jobs::STATUS_SUCCEEDED,
+        r#"{"trigger":"schedule","schedule_key":"2026-03-06T14:30"}"#,
+        Some(
+            r#"{"skipped":false,"skip_reason":null,"star":{"total_users":12,"succeeded_users":10,"failed_users":2,"total_repos":8},"release":{"total_repos":8,"succeeded_repos":7,"failed_repos":1,"candidate_failures":3},"social":{"total_users":10,"succeeded_users":9,"failed_users":1,"repo_stars":48,"followers":19,"events":67},"notifications":{"total_users":10,"succeeded_users":8,"failed_users":2,"notifications":192},"releases_written":42,"critical_events":2}"#,
+        ),
+        None,
+    );
+    task.log_file_path = Some(log_path.to_string_lossy().into_owned());
+    let subscription_events = vec![
+        AdminSyncSubscriptionEventItem {
+            id: "7".to_owned(),
+            stage: "release".to_owned(),
+            event_type: "repo_unreachable".to_owned(),
+            severity: "error".to_owned(),
+            recoverable: false,
+            attempt: 0,
+            user_id: None,
+            repo_id: Some(9001),
+            repo_full_name: Some("octo/private-repo".to_owned()),
+            message: Some("all candidates failed for octo/private-repo".to_owned()),
+            created_at: "2026-03-06T14:31:40Z".to_owned(),
+        },
+        AdminSyncSubscriptionEventItem {
+            id: "6".to_owned(),
+            stage: "star".to_owned(),
+            event_type: "network_error".to_owned(),
+            severity: "warning".to_owned(),
+            recoverable: true,
+            attempt: 1,
+            user_id: Some("23".to_owned()),
+            repo_id: None,
+            repo_full_name: None,
+            message: Some("failed to refresh starred repositories for user #23".to_owned()),
+            created_at: "2026-03-06T14:30:10Z".to_owned(),
+        },
+    ];
+
+    let diagnostics =
+        build_task_diagnostics(&task, &[], &subscription_events).expect("diagnostics");
+    assert_eq!(diagnostics.business_outcome.code, "partial");
+    let sync_diag = diagnostics
+        .sync_subscriptions
+        .expect("sync subscriptions diagnostics");
+    assert_eq!(sync_diag.trigger.as_deref(), Some("schedule"));
+    assert_eq!(sync_diag.schedule_key.as_deref(), Some("2026-03-06T14:30"));
+    assert!(sync_diag.log_available);
+    assert_eq!(
+        sync_diag.log_download_path.as_deref(),
+        Some("/api/admin/jobs/realtime/task-test/log")
+    );
+    assert_eq!(sync_diag.star.succeeded_users, 10);
+    assert_eq!(sync_diag.release.failed_repos, 1);
+    assert_eq!(sync_diag.social.failed_users, 1);
+    assert_eq!(sync_diag.notifications.failed_users, 2);
+    assert_eq!(sync_diag.releases_written, 42);
+    assert_eq!(sync_diag.critical_events, 2);
+    assert_eq!(sync_diag.recent_events.len(), 2);
+    assert_eq!(sync_diag.recent_events[0].event_type, "repo_unreachable");
+
+    fs::remove_file(&log_path).ok();
+    fs::remove_dir_all(&log_dir).ok();
+}
+
+fn fixed_local_id(prefix: char, n: usize) -> String {
+    const ALPHABET: &[u8] = crate::local_id::LOCAL_ID_ALPHABET;
+    let mut value = n;
+    let mut suffix = ['2'; 15];
+    for idx in (0..15).rev() {
+        suffix[idx] = ALPHABET[value % ALPHABET.len()] as char;
+        value /= ALPHABET.len();
+    }
+    let mut id = String::with_capacity(16);
+    id.push(prefix);
+    for ch in suffix {
+        id.push(ch);
+    }
+    id
+}
+
+fn indexed_created_at(idx: usize) -> String {
+    let hours = 14 + (idx / 3600);
+    let minutes = (idx / 60) % 60;
+    let seconds = idx % 60;
+    format!("2026-03-06T{hours:02}:{minutes:02}:{seconds:02}Z")
+}
+
+#[tokio::test]
+async fn admin_get_realtime_task_detail_uses_latest_task_events_window() {
+    let pool = setup_pool().await;
+    seed_user(&pool, 2, "admin", 1, 0).await;
+    let state = setup_state(pool.clone());
+    let task_id = crate::local_id::test_local_id("task-detail-events-window");
+    let now = "2026-03-06T14:30:00Z";
+    sqlx::query(
+        r#"
+            INSERT INTO job_tasks (
+              id,
+              task_type,
+              status,
+              source,
+              requested_by,
+              parent_task_id,
+              payload_json,
+              result_json,
+              error_message,
+              cancel_requested,
+              created_at,
+              started_at,
+              finished_at,
+              updated_at,
+              log_file_path
+            ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+            "#,
+    )
+    .bind(task_id.as_str())
+    .bind(jobs::TASK_TRANSLATE_RELEASE_BATCH)
+    .bind(jobs::STATUS_SUCCEEDED)
+    .bind("tests")
+    .bind(test_user_id(2))
+    .bind(Option::<String>::None)
+    .bind(r#"{"user_id":"scope22222222222","release_ids":[1]}"#)
+    .bind(r#"{"total":1,"ready":1,"missing":0,"disabled":0,"error":0}"#)
+    .bind(Option::<String>::None)
+    .bind(0_i64)
+    .bind(now)
+    .bind(Some(now))
+    .bind(Some(now))
+    .bind(now)
+    .bind(Option::<String>::None)
+    .execute(&pool)
+    .await
+    .expect("insert task row");
+
+    for idx in 0..ADMIN_TASK_DETAIL_EVENT_LIMIT {
+        let created_at = indexed_created_at(idx as usize);
+        sqlx::query(
+            r#"
+                INSERT INTO job_task_events (id, task_id, event_type, payload_json, created_at)
+                VALUES (?, ?, 'task.progress', ?, ?)
+                "#,
+        )
+        .bind(fixed_local_id('z', idx as usize).as_str())
+        .bind(task_id.as_str())
+        .bind(format!(
+            r#"{{"stage":"release","release_id":"{idx}","item_status":"ready"}}"#
+        ))
+        .bind(created_at)
+        .execute(&pool)
+        .await
+        .expect("insert early event");
+    }
+
+    sqlx::query(
+        r#"
+            INSERT INTO job_task_events (id, task_id, event_type, payload_json, created_at)
+            VALUES (?, ?, 'task.progress', ?, ?)
+            "#,
+    )
+    .bind("2222222222222222")
+    .bind(task_id.as_str())
+    .bind(r#"{"stage":"release","release_id":"latest","item_status":"ready"}"#)
+    .bind("2026-03-06T14:31:00Z")
+    .execute(&pool)
+    .await
+    .expect("insert latest event");
+
+    let response = admin_get_realtime_task_detail(
+        State(state),
+        AdminUser(test_user_id(2)),
+        Path(task_id.clone()),
+    )
+    .await
+    .expect("task detail")
+    .0;
+
+    assert_eq!(response.event_meta.returned, ADMIN_TASK_DETAIL_EVENT_LIMIT);
+    assert!(response.event_meta.truncated);
+    assert!(
+        response
+            .events
+            .iter()
+            .any(|event| event.payload_json.contains("latest"))
+    );
+    let diagnostics = response.diagnostics.expect("diagnostics");
+    let translate = diagnostics
+        .translate_release_batch
+        .expect("translate diagnostics");
+    assert!(
+        translate
+            .items
+            .iter()
+            .any(|item| item.release_id == "latest")
+    );
+}
+
+#[tokio::test]
+async fn admin_get_realtime_task_detail_maps_brief_history_recompute_progress() {
+    let pool = setup_pool().await;
+    seed_user(&pool, 2, "admin", 1, 0).await;
+    let state = setup_state(pool.clone());
+    let task_id = crate::local_id::test_local_id("task-detail-brief-history");
+    let now = "2026-03-06T14:30:00Z";
+    sqlx::query(
+        r#"
+            INSERT INTO job_tasks (
+              id,
+              task_type,
+              status,
+              source,
+              requested_by,
+              parent_task_id,
+              payload_json,
+              result_json,
+              error_message,
+              cancel_requested,
+              created_at,
+              started_at,
+              finished_at,
+              updated_at,
+              log_file_path
+            ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+            "#,
+    )
+    .bind(task_id.as_str())
+    .bind(jobs::TASK_BRIEF_HISTORY_RECOMPUTE)
+    .bind(jobs::STATUS_RUNNING)
+    .bind("tests")
+    .bind(test_user_id(2))
+    .bind(Option::<String>::None)
+    .bind("{}")
+    .bind(r#"{"total":3,"processed":2,"succeeded":1,"failed":1,"canceled":false}"#)
+    .bind(Option::<String>::None)
+    .bind(0_i64)
+    .bind(now)
+    .bind(Some(now))
+    .bind(Option::<String>::None)
+    .bind(now)
+    .bind(Option::<String>::None)
+    .execute(&pool)
+    .await
+    .expect("insert task row");
+
+    for (event_id, payload_json, created_at) in [
+        (
+            "1111111111111111",
+            r#"{"stage":"recompute","brief_id":"brief_old"}"#,
+            "2026-03-06T14:30:01Z",
+        ),
+        (
+            "2222222222222222",
+            r#"{"stage":"brief_failed","brief_id":"brief_latest","error":"boom"}"#,
+            "2026-03-06T14:30:02Z",
+        ),
+    ] {
+        sqlx::query(
+            r#"
+                INSERT INTO job_task_events (id, task_id, event_type, payload_json, created_at)
+                VALUES (?, ?, 'task.progress', ?, ?)
+                "#,
+        )
+        .bind(event_id)
+        .bind(task_id.as_str())
+        .bind(payload_json)
+        .bind(created_at)
+        .execute(&pool)
+        .await
+        .expect("insert task event");
+    }
+
+    let response =
+        admin_get_realtime_task_detail(State(state), AdminUser(test_user_id(2)), Path(task_id))
+            .await
+            .expect("task detail")
+            .0;
+
+    let diagnostics = response.diagnostics.expect("diagnostics");
+    let brief_history = diagnostics
+        .brief_history_recompute
+        .expect("brief history diagnostics");
+    assert_eq!(brief_history.total, 3);
+    assert_eq!(brief_history.processed, 2);
+    assert_eq!(brief_history.succeeded, 1);
+    assert_eq!(brief_history.failed, 1);
+    assert_eq!(
+        brief_history.current_brief_id.as_deref(),
+        Some("brief_latest")
+    );
+    assert_eq!(brief_history.last_error.as_deref(), Some("boom"));
+}
+
+#[tokio::test]
+async fn admin_get_realtime_task_detail_uses_summary_event_for_failed_brief_history_recompute() {
+    let pool = setup_pool().await;
+    seed_user(&pool, 2, "admin", 1, 0).await;
+    let state = setup_state(pool.clone());
+    let task_id = crate::local_id::test_local_id("task-detail-brief-history-failed");
+    let now = "2026-03-06T14:30:00Z";
+    sqlx::query(
+        r#"
+            INSERT INTO job_tasks (
+              id,
+              task_type,
+              status,
+              source,
+              requested_by,
+              parent_task_id,
+              payload_json,
+              result_json,
+              error_message,
+              cancel_requested,
+              created_at,
+              started_at,
+              finished_at,
+              updated_at,
+              log_file_path
+            ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+            "#,
+    )
+    .bind(task_id.as_str())
+    .bind(jobs::TASK_BRIEF_HISTORY_RECOMPUTE)
+    .bind(jobs::STATUS_FAILED)
+    .bind("tests")
+    .bind(test_user_id(2))
+    .bind(Option::<String>::None)
+    .bind("{}")
+    .bind(Option::<String>::None)
+    .bind(Some("all failed"))
+    .bind(0_i64)
+    .bind(now)
+    .bind(Some(now))
+    .bind(Some("2026-03-06T14:31:00Z"))
+    .bind("2026-03-06T14:31:00Z")
+    .bind(Option::<String>::None)
+    .execute(&pool)
+    .await
+    .expect("insert failed task row");
+
+    for (event_id, payload_json, created_at) in [
+        (
+            "3333333333333333",
+            r#"{"stage":"brief_failed","brief_id":"brief_legacy_01","error":"boom"}"#,
+            "2026-03-06T14:30:02Z",
+        ),
+        (
+            "4444444444444444",
+            r#"{"stage":"summary","total":4,"processed":4,"succeeded":0,"failed":4,"canceled":false}"#,
+            "2026-03-06T14:31:00Z",
+        ),
+    ] {
+        sqlx::query(
+            r#"
+                INSERT INTO job_task_events (id, task_id, event_type, payload_json, created_at)
+                VALUES (?, ?, 'task.progress', ?, ?)
+                "#,
+        )
+        .bind(event_id)
+        .bind(task_id.as_str())
+        .bind(payload_json)
+        .bind(created_at)
+        .execute(&pool)
+        .await
+        .expect("insert task event");
+    }
+
+    let response =
+        admin_get_realtime_task_detail(State(state), AdminUser(test_user_id(2)), Path(task_id))
+            .await
+            .expect("task detail")
+            .0;
+
+    let diagnostics = response.diagnostics.expect("diagnostics");
+    let brief_history = diagnostics
+        .brief_history_recompute
+        .expect("brief history diagnostics");
+    assert_eq!(brief_history.total, 4);
+    assert_eq!(brief_history.processed, 4);
+    assert_eq!(brief_history.succeeded, 0);
+    assert_eq!(brief_history.failed, 4);
+    assert_eq!(brief_history.current_brief_id, None);
+    assert_eq!(brief_history.last_error.as_deref(), Some("boom"));
+}
+
+#[tokio::test]
+async fn admin_get_realtime_task_detail_maps_brief_refresh_content_progress() {
+    let pool = setup_pool().await;
+    seed_user(&pool, 2, "admin", 1, 0).await;
+    let state = setup_state(pool.clone());
+    let task_id = crate::local_id::test_local_id("task-detail-brief-refresh");
+    let now = "2026-03-06T14:30:00Z";
+    sqlx::query(
+        r#"
+            INSERT INTO job_tasks (
+              id,
+              task_type,
+              status,
+              source,
+              requested_by,
+              parent_task_id,
+              payload_json,
+              result_json,
+              error_message,
+              cancel_requested,
+              created_at,
+              started_at,
+              finished_at,
+              updated_at,
+              log_file_path
+            ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+            "#,
+    )
+    .bind(task_id.as_str())
+    .bind(jobs::TASK_BRIEF_REFRESH_CONTENT)
+    .bind(jobs::STATUS_RUNNING)
+    .bind("tests")
+    .bind(test_user_id(2))
+    .bind(Option::<String>::None)
+    .bind("{}")
+    .bind(r#"{"total":5,"processed":3,"succeeded":2,"failed":1,"canceled":false}"#)
+    .bind(Option::<String>::None)
+    .bind(0_i64)
+    .bind(now)
+    .bind(Some(now))
+    .bind(Option::<String>::None)
+    .bind(now)
+    .bind(Option::<String>::None)
+    .execute(&pool)
+    .await
+    .expect("insert task row");
+
+    for (event_id, payload_json, created_at) in [
+        (
+            "5555555555555555",
+            r#"{"stage":"refresh","brief_id":"brief_old"}"#,
+            "2026-03-06T14:30:01Z",
+        ),
+        (
+            "6666666666666666",
+            r#"{"stage":"brief_failed","brief_id":"brief_latest","error":"boom"}"#,
+            "2026-03-06T14:30:02Z",
+        ),
+    ] {
+        sqlx::query(
+            r#"
+                INSERT INTO job_task_events (id, task_id, event_type, payload_json, created_at)
+                VALUES (?, ?, 'task.progress', ?, ?)
+                "#,
+        )
+        .bind(event_id)
+        .bind(task_id.as_str())
+        .bind(payload_json)
+        .bind(created_at)
+        .execute(&pool)
+        .await
+        .expect("insert task event");
+    }
+
+    let response =
+        admin_get_realtime_task_detail(State(state), AdminUser(test_user_id(2)), Path(task_id))
+            .await
+            .expect("task detail")
+            .0;
+
+    let diagnostics = response.diagnostics.expect("diagnostics");
+    let brief_refresh = diagnostics
+        .brief_refresh_content
+        .expect("brief refresh diagnostics");
+    assert_eq!(brief_refresh.total, 5);
+    assert_eq!(brief_refresh.processed, 3);
+    assert_eq!(brief_refresh.succeeded, 2);
+    assert_eq!(brief_refresh.failed, 1);
+    assert_eq!(
+        brief_refresh.current_brief_id.as_deref(),
+        Some("brief_latest")
+    );
+    assert_eq!(brief_refresh.last_error.as_deref(), Some("boom"));
+}
+
+#[tokio::test]
+async fn admin_get_realtime_task_detail_reads_brief_refresh_collect_total_while_running() {
+    let pool = setup_pool().await;
+    seed_user(&pool, 2, "admin", 1, 0).await;
+    let state = setup_state(pool.clone());
+    let task_id = crate::local_id::test_local_id("task-detail-brief-refresh-collect");
+    let now = "2026-03-06T14:30:00Z";
+    sqlx::query(
+        r#"
+            INSERT INTO job_tasks (
+              id,
+              task_type,
+              status,
+              source,
+              requested_by,
+              parent_task_id,
+              payload_json,
+              result_json,
+              error_message,
+              cancel_requested,
+              created_at,
+              started_at,
+              finished_at,
+              updated_at,
+              log_file_path
+            ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+            "#,
+    )
+    .bind(task_id.as_str())
+    .bind(jobs::TASK_BRIEF_REFRESH_CONTENT)
+    .bind(jobs::STATUS_RUNNING)
+    .bind("tests")
+    .bind(test_user_id(2))
+    .bind(Option::<String>::None)
+    .bind("{}")
+    .bind(Option::<String>::None)
+    .bind(Option::<String>::None)
+    .bind(0_i64)
+    .bind(now)
+    .bind(Some(now))
+    .bind(Option::<String>::None)
+    .bind(now)
+    .bind(Option::<String>::None)
+    .execute(&pool)
+    .await
+    .expect("insert task row");
+
+    for (event_id, payload_json, created_at) in [
+        (
+            "7777777777777777",
+            r#"{"stage":"collect","total_briefs":5}"#,
+            "2026-03-06T14:30:01Z",
+        ),
+        (
+            "8888888888888888",
+            r#"{"stage":"refresh","brief_id":"brief_running","index":2,"total":5}"#,
+            "2026-03-06T14:30:02Z",
+        ),
+        (
+            "9999999999999999",
+            r#"{"stage":"brief_succeeded","brief_id":"brief_done","index":1,"total":5}"#,
+            "2026-03-06T14:30:03Z",
+        ),
+    ] {
+        sqlx::query(
+            r#"
+                INSERT INTO job_task_events (id, task_id, event_type, payload_json, created_at)
+                VALUES (?, ?, 'task.progress', ?, ?)
+                "#,
+        )
+        .bind(event_id)
+        .bind(task_id.as_str())
+        .bind(payload_json)
+        .bind(created_at)
+        .execute(&pool)
+        .await
+        .expect("insert task event");
+    }
+
+    let response =
+        admin_get_realtime_task_detail(State(state), AdminUser(test_user_id(2)), Path(task_id))
+            .await
+            .expect("task detail")
+            .0;
+
+    let diagnostics = response.diagnostics.expect("diagnostics");
+    let brief_refresh = diagnostics
+        .brief_refresh_content
+        .expect("brief refresh diagnostics");
+    assert_eq!(brief_refresh.total, 5);
+    assert_eq!(brief_refresh.processed, 2);
+    assert_eq!(brief_refresh.succeeded, 1);
+    assert_eq!(brief_refresh.failed, 0);
+    assert_eq!(
+        brief_refresh.current_brief_id.as_deref(),
+        Some("brief_done")
+    );
+    assert_eq!(brief_refresh.last_error, None);
+}
+
+#[tokio::test]
+async fn admin_get_realtime_task_detail_uses_latest_subscription_events_window() {
+    let pool = setup_pool().await;
+    seed_user(&pool, 2, "admin", 1, 0).await;
+    let state = setup_state(pool.clone());
+    let task_id = crate::local_id::test_local_id("task-detail-subscription-window");
+    let now = "2026-03-06T14:30:00Z";
+    sqlx::query(
+            r#"
+            INSERT INTO job_tasks (
+              id,
+              task_type,
+              status,
+              source,
+              requested_by,
+              parent_task_id,
+              payload_json,
+              result_json,
+              error_message,
+              cancel_requested,
+              created_at,
+              started_at,
+              finished_at,
+              updated_at,
+              log_file_path
+            ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+            "#,
+        )
+        .bind(task_id.as_str())
+        .bind(jobs::TASK_SYNC_SUBSCRIPTIONS)
+        .bind(jobs::STATUS_SUCCEEDED)
+        .bind("tests")
+        .bind(test_user_id(2))
+        .bind(Option::<String>::None)
+        .bind(r#"{"trigger":"schedule","schedule_key":"2026-03-06T14:30"}"#)
+        .bind(r#"{"skipped":false,"star":{"total_users":1,"succeeded_users":1,"failed_users":0,"total_repos":1},"release":{"total_repos":1,"succeeded_repos":1,"failed_repos":0,"candidate_failures":0},"social":{"total_users":1,"succeeded_users":1,"failed_users":0,"repo_stars":2,"followers":1,"events":3},"notifications":{"total_users":1,"succeeded_users":1,"failed_users":0,"notifications":4},"releases_written":1,"critical_events":1}"#)
+        .bind(Option::<String>::None)
+        .bind(0_i64)
+        .bind(now)
+        .bind(Some(now))
+        .bind(Some(now))
+        .bind(now)
+        .bind(Option::<String>::None)
+        .execute(&pool)
+        .await
+        .expect("insert task row");
+
+    for idx in 0..ADMIN_SYNC_SUBSCRIPTION_EVENT_LIMIT {
+        let created_at = indexed_created_at(idx as usize);
+        sqlx::query(
+            r#"
+                INSERT INTO sync_subscription_events (
+                  id, task_id, stage, event_type, severity, recoverable, attempt,
+                  user_id, repo_id, repo_full_name, payload_json, created_at
+                ) VALUES (?, ?, 'release', ?, 'warning', 1, 1, ?, ?, ?, ?, ?)
+                "#,
+        )
+        .bind(fixed_local_id('z', idx as usize).as_str())
+        .bind(task_id.as_str())
+        .bind(format!("event-{idx}"))
+        .bind(test_user_id(2))
+        .bind(9000_i64 + idx)
+        .bind(format!("octo/repo-{idx}"))
+        .bind(format!(r#"{{"message":"event-{idx}"}}"#))
+        .bind(created_at)
+        .execute(&pool)
+        .await
+        .expect("insert early subscription event");
+    }
+
+    sqlx::query(
+        r#"
+            INSERT INTO sync_subscription_events (
+              id, task_id, stage, event_type, severity, recoverable, attempt,
+              user_id, repo_id, repo_full_name, payload_json, created_at
+            ) VALUES (?, ?, 'release', 'latest-event', 'error', 0, 0, ?, ?, ?, ?, ?)
+            "#,
+    )
+    .bind("2222222222222222")
+    .bind(task_id.as_str())
+    .bind(test_user_id(2))
+    .bind(9999_i64)
+    .bind("octo/latest")
+    .bind(r#"{"message":"latest-event"}"#)
+    .bind("2026-03-06T14:31:00Z")
+    .execute(&pool)
+    .await
+    .expect("insert latest subscription event");
+
+    let response =
+        admin_get_realtime_task_detail(State(state), AdminUser(test_user_id(2)), Path(task_id))
+            .await
+            .expect("task detail")
+            .0;
+
+    let diagnostics = response.diagnostics.expect("diagnostics");
+    let sync = diagnostics.sync_subscriptions.expect("sync diagnostics");
+    assert_eq!(
+        sync.recent_events.len(),
+        ADMIN_SYNC_SUBSCRIPTION_EVENT_LIMIT as usize
+    );
+    assert!(
+        sync.recent_events
+            .iter()
+            .any(|event| event.event_type == "latest-event")
+    );
+    assert_eq!(sync.social.events, 3);
+    assert_eq!(sync.notifications.notifications, 4);
+}
+
+#[tokio::test]
+async fn admin_get_realtime_task_detail_reads_truncated_sync_progress_for_diagnostics() {
+    let pool = setup_pool().await;
+    seed_user(&pool, 2, "admin", 1, 0).await;
+    let state = setup_state(pool.clone());
+    let task_id = crate::local_id::test_local_id("sync-detail-truncated-progress");
+    let now = "2026-03-06T14:30:00Z";
+    sqlx::query(
+        r#"
+            INSERT INTO job_tasks (
+              id,
+              task_type,
+              status,
+              source,
+              requested_by,
+              parent_task_id,
+              payload_json,
+              result_json,
+              error_message,
+              cancel_requested,
+              created_at,
+              started_at,
+              finished_at,
+              updated_at,
+              log_file_path
+            ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+            "#,
+    )
+    .bind(task_id.as_str())
+    .bind(jobs::TASK_SYNC_SUBSCRIPTIONS)
+    .bind(jobs::STATUS_RUNNING)
+    .bind("tests")
+    .bind(Option::<String>::None)
+    .bind(Option::<String>::None)
+    .bind(r#"{"trigger":"schedule","schedule_key":"interval:10:1777954800"}"#)
+    .bind(Option::<String>::None)
+    .bind(Option::<String>::None)
+    .bind(0_i64)
+    .bind(now)
+    .bind(Some(now))
+    .bind(Option::<String>::None)
+    .bind(now)
+    .bind(Option::<String>::None)
+    .execute(&pool)
+    .await
+    .expect("insert task row");
+
+    let early_events = [
+        (
+            "3333333333333331",
+            r#"{"stage":"star_summary","total_users":12,"succeeded_users":11,"failed_users":1,"total_repos":340}"#,
+            "2026-03-06T14:30:01Z",
+        ),
+        (
+            "3333333333333332",
+            r#"{"stage":"repo_collect","total_repos":128}"#,
+            "2026-03-06T14:30:02Z",
+        ),
+        (
+            "3333333333333333",
+            r#"{"stage":"release_attached","repos":128,"queued":68,"reused_running":2,"reused_fresh":58}"#,
+            "2026-03-06T14:30:03Z",
+        ),
+    ];
+    for (event_id, payload, created_at) in early_events {
+        sqlx::query(
+            r#"
+                INSERT INTO job_task_events (id, task_id, event_type, payload_json, created_at)
+                VALUES (?, ?, 'task.progress', ?, ?)
+                "#,
+        )
+        .bind(event_id)
+        .bind(task_id.as_str())
+        .bind(payload)
+        .bind(created_at)
+        .execute(&pool)
+        .await
+        .expect("insert early progress event");
+    }
+
+    for idx in 0..ADMIN_TASK_DETAIL_EVENT_LIMIT {
+        sqlx::query(
+            r#"
+                INSERT INTO job_task_events (id, task_id, event_type, payload_json, created_at)
+                VALUES (?, ?, 'task.progress', ?, ?)
+                "#,
+        )
+        .bind(fixed_local_id('y', idx as usize).as_str())
+        .bind(task_id.as_str())
+        .bind(format!(r#"{{"stage":"heartbeat","sequence":{idx}}}"#))
+        .bind(indexed_created_at((idx + 60) as usize))
+        .execute(&pool)
+        .await
+        .expect("insert later noise event");
+    }
+
+    let response =
+        admin_get_realtime_task_detail(State(state), AdminUser(test_user_id(2)), Path(task_id))
+            .await
+            .expect("task detail")
+            .0;
+
+    assert!(response.event_meta.truncated);
+    assert!(
+        !response
+            .events
+            .iter()
+            .any(|event| event.payload_json.contains("star_summary"))
+    );
+    let sync = response
+        .diagnostics
+        .expect("diagnostics")
+        .sync_subscriptions
+        .expect("sync diagnostics");
+    assert_eq!(sync.star.total_users, 12);
+    assert_eq!(sync.star.succeeded_users, 11);
+    assert_eq!(sync.release.total_repos, 128);
+    assert_eq!(sync.release.succeeded_repos, 0);
+}
+
+#[tokio::test]
+async fn admin_download_realtime_task_log_returns_ndjson_attachment() {
+    let pool = setup_pool().await;
+    seed_user(&pool, 2, "admin", 1, 0).await;
+    let state = setup_state(pool.clone());
+    fs::create_dir_all(&state.config.task_log_dir).expect("create task log dir");
+    let log_path = state
+        .config
+        .task_log_dir
+        .join(format!("{}.ndjson", crate::local_id::generate_local_id()));
+    let log_body = r#"{"event":"started"}
+{"event":"finished"}
+"#;
+    fs::write(&log_path, log_body).expect("write task log");
+
+    let now = "2026-03-06T14:30:00Z";
+    sqlx::query(
+        r#"
+            INSERT INTO job_tasks (
+              id,
+              task_type,
+              status,
+              source,
+              requested_by,
+              parent_task_id,
+              payload_json,
+              result_json,
+              error_message,
+              cancel_requested,
+              created_at,
+              started_at,
+              finished_at,
+              updated_at,
+              log_file_path
+            ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+            "#,
+    )
+    .bind(crate::local_id::test_local_id("task-log-download"))
+    .bind(jobs::TASK_SYNC_SUBSCRIPTIONS)
+    .bind(jobs::STATUS_SUCCEEDED)
+    .bind("scheduler")
+    .bind(test_user_id(2))
+    .bind(Option::<String>::None)
+    .bind(r#"{"trigger":"schedule","schedule_key":"2026-03-06T14:30"}"#)
+    .bind(Option::<String>::None)
+    .bind(Option::<String>::None)
+    .bind(0_i64)
+    .bind(now)
+    .bind(Some(now))
+    .bind(Some(now))
+    .bind(now)
+    .bind(log_path.to_string_lossy().to_string())
+    .execute(&pool)
+    .await
+    .expect("insert task row");
+
+    let response = admin_download_realtime_task_log(
+        State(state.clone()),
+        AdminUser(test_user_id(2)),
+        Path(crate::local_id::test_local_id("task-log-download")),
+    )
+    .await
+    .expect("download task log")
+    .into_response();
+
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(
+        response.headers().get(header::CONTENT_TYPE),
+        Some(&header::HeaderValue::from_static("application/x-ndjson"))
+    );
+    let expected_disposition = header::HeaderValue::from_str(&format!(
+        r#"attachment; filename="{}.ndjson""#,
+        crate::local_id::test_local_id("task-log-download")
+    ))
+    .expect("content disposition header");
+    assert_eq!(
+        response.headers().get(header::CONTENT_DISPOSITION),
+        Some(&expected_disposition)
+    );
+
+    let body = to_bytes(response.into_body(), usize::MAX)
+        .await
+        .expect("read task log body");
+    assert_eq!(std::str::from_utf8(&body).expect("body utf8"), log_body);
+
+    fs::remove_file(&log_path).ok();
+}
+
+#[test]
+fn task_diagnostics_brief_generate_canceled_shows_canceled_outcome() {
+    let task = test_task_detail_item(
+        jobs::TASK_BRIEF_GENERATE,
+        jobs::STATUS_CANCELED,
+        r#"{"user_id":7,"key_date":"2026-02-27"}"#,
+        Some(r#"{"content_length":200}"#),
+        None,
+    );
+
+    let diagnostics = build_task_diagnostics(&task, &[], &[]).expect("diagnostics");
+    assert_eq!(diagnostics.business_outcome.code, "partial");
+    assert_eq!(diagnostics.business_outcome.label, "已取消");
+    let brief_generate = diagnostics
+        .brief_generate
+        .expect("brief generate diagnostics");
+    assert_eq!(brief_generate.target_user_id, Some("7".to_owned()));
+    assert_eq!(brief_generate.content_length, Some(200));
+}
+
+#[test]
+fn release_cache_entry_reusable_accepts_title_only_cache() {
+    let cache = TranslationCacheRow {
+        entity_id: "1".to_owned(),
+        source_hash: "hash".to_owned(),
+        status: "ready".to_owned(),
+        title: Some("标题".to_owned()),
+        summary: None,
+        error_text: None,
+    };
+    assert!(release_cache_entry_reusable(&cache, "body excerpt"));
+}
+
+#[test]
+fn release_cache_entry_reusable_rejects_json_blob_summary() {
+    let cache = TranslationCacheRow {
+        entity_id: "1".to_owned(),
+        source_hash: "hash".to_owned(),
+        status: "ready".to_owned(),
+        title: Some("标题".to_owned()),
+        summary: Some("{\"title_zh\":\"标题\"}".to_owned()),
+        error_text: None,
+    };
+    assert!(looks_like_json_blob(cache.summary.as_deref().unwrap_or("")));
+    assert!(!release_cache_entry_reusable(&cache, "body excerpt"));
+}
+
+#[test]
+fn feed_item_from_row_keeps_terminal_missing_non_retryable() {
+    let mut row = test_feed_row(Some("R_node"));
+    row.repo_full_name = Some("openai/codex".to_owned());
+    row.title = Some("Release v1.2.3".to_owned());
+    row.release_body = Some("- item".to_owned());
+    let body = release_feed_body(row.release_body.as_deref()).expect("release body");
+    let source = format!(
+        "v=6\nkind=release\nrepo={}\ntitle={}\nbody={}\n",
+        row.repo_full_name.as_deref().unwrap_or(""),
+        row.title.as_deref().unwrap_or(""),
+        body,
+    );
+    row.trans_source_hash = Some(ai::sha256_hex(&source));
+    row.trans_status = Some("missing".to_owned());
+
+    let item = feed_item_from_row(row, true, None, FeedExcerpt::default());
+    let translated = item.translated.expect("translated item");
+    assert_eq!(translated.status, "missing");
+    assert_eq!(translated.auto_translate, None);
+}
+
+#[test]
+fn feed_item_from_row_keeps_terminal_error_non_retryable() {
+    let mut row = test_feed_row(Some("R_node"));
+    row.repo_full_name = Some("openai/codex".to_owned());
+    row.title = Some("Release v1.2.3".to_owned());
+    row.release_body = Some("- item".to_owned());
+    let body = release_feed_body(row.release_body.as_deref()).expect("release body");
+    let source = format!(
+        "v=6\nkind=release\nrepo={}\ntitle={}\nbody={}\n",
+        row.repo_full_name.as_deref().unwrap_or(""),
+        row.title.as_deref().unwrap_or(""),
+        body,
+    );
+    row.trans_source_hash = Some(ai::sha256_hex(&source));
+    row.trans_status = Some("error".to_owned());
+    row.trans_error_text =
+        Some("release translation failed to preserve markdown structure".to_owned());
+
+    let item = feed_item_from_row(row, true, None, FeedExcerpt::default());
+    let translated = item.translated.expect("translated item");
+    assert_eq!(translated.status, "error");
+    assert_eq!(
+        translated.error_code.as_deref(),
+        Some("markdown_structure_mismatch")
+    );
+    assert_eq!(
+        translated.error_summary.as_deref(),
+        Some("Markdown 结构校验失败")
+    );
+    assert_eq!(
+        translated.error_detail.as_deref(),
+        Some("release translation failed to preserve markdown structure")
+    );
+    assert_eq!(translated.auto_translate, Some(false));
+}
+
+#[test]
+fn feed_item_from_row_retries_upstream_rejected_translation_errors() {
+    let mut row = test_feed_row(Some("R_node"));
+    row.repo_full_name = Some("openai/codex".to_owned());
+    row.title = Some("Release v1.2.3".to_owned());
+    row.release_body = Some("- item".to_owned());
+    let body = release_feed_body(row.release_body.as_deref()).expect("release body");
+    let source = format!(
+        "v=6\nkind=release\nrepo={}\ntitle={}\nbody={}\n",
+        row.repo_full_name.as_deref().unwrap_or(""),
+        row.title.as_deref().unwrap_or(""),
+        body,
+    );
+    row.trans_source_hash = Some(ai::sha256_hex(&source));
+    row.trans_status = Some("error".to_owned());
+    row.trans_error_text = Some("AI returned 403 Forbidden: Chat upstream returned 403".to_owned());
+
+    let item = feed_item_from_row(row, true, None, FeedExcerpt::default());
+    let translated = item.translated.expect("translated item");
+    assert_eq!(translated.status, "missing");
+    assert_eq!(translated.auto_translate, None);
+    assert!(translated.title.is_none());
+    assert!(translated.summary.is_none());
+}
+
+#[test]
+fn feed_item_from_row_uses_release_detail_translation_for_truncated_release() {
+    let mut row = test_feed_row(Some("R_node"));
+    row.repo_full_name = Some("openai/codex".to_owned());
+    row.title = Some("Release v1.2.4".to_owned());
+    row.release_body = Some("a".repeat(RELEASE_FEED_BODY_MAX_CHARS + 1));
+    row.detail_trans_source_hash = Some(release_detail_source_hash(
+        row.repo_full_name.as_deref().unwrap_or(""),
+        row.title.as_deref().unwrap_or(""),
+        row.release_body.as_deref().unwrap_or(""),
+    ));
+    row.detail_trans_status = Some("ready".to_owned());
+    row.detail_trans_title = Some("中文标题".to_owned());
+    row.detail_trans_summary = Some("- 分块译文".to_owned());
+
+    let item = feed_item_from_row(row, true, None, FeedExcerpt::default());
+    let translated = item.translated.expect("translated item");
+    assert_eq!(translated.status, "ready");
+    assert_eq!(translated.title.as_deref(), Some("中文标题"));
+    assert_eq!(translated.summary.as_deref(), Some("- 分块译文"));
+    assert_eq!(translated.auto_translate, None);
+    assert!(item.body_truncated);
+}
+
+#[test]
+fn feed_item_from_row_falls_back_to_legacy_ready_translation_when_detail_failed() {
+    let mut row = test_feed_row(Some("R_node"));
+    row.repo_full_name = Some("openai/codex".to_owned());
+    row.title = Some("Release v1.2.5".to_owned());
+    row.release_body = Some("- item".to_owned());
+    let body = release_feed_body(row.release_body.as_deref()).expect("release body");
+    let source = format!(
+        "v=6\nkind=release\nrepo={}\ntitle={}\nbody={}\n",
+        row.repo_full_name.as_deref().unwrap_or(""),
+        row.title.as_deref().unwrap_or(""),
+        body,
+    );
+    row.trans_source_hash = Some(ai::sha256_hex(&source));
+    row.trans_status = Some("ready".to_owned());
+    row.trans_title = Some("旧译文标题".to_owned());
+    row.trans_summary = Some("- 旧译文".to_owned());
+    row.detail_trans_source_hash = Some(release_detail_source_hash(
+        row.repo_full_name.as_deref().unwrap_or(""),
+        row.title.as_deref().unwrap_or(""),
+        row.release_body.as_deref().unwrap_or(""),
+    ));
+    row.detail_trans_status = Some("error".to_owned());
+
+    let item = feed_item_from_row(row, true, None, FeedExcerpt::default());
+    let translated = item.translated.expect("translated item");
+    assert_eq!(translated.status, "ready");
+    assert_eq!(translated.title.as_deref(), Some("旧译文标题"));
+    assert_eq!(translated.summary.as_deref(), Some("- 旧译文"));
+}
+
+#[test]
+fn feed_excerpt_clamps_requested_limits() {
+    assert_eq!(
+        FeedExcerpt::from_query(None, None, None).expect("default"),
+        FeedExcerpt::default()
+    );
+    assert_eq!(
+        FeedExcerpt::from_query(Some("preview"), Some(0), Some(5)).expect("low"),
+        FeedExcerpt::Limited {
+            max_chars: FEED_EXCERPT_MIN_CHARS,
+            max_lines: Some(1),
+        }
+    );
+    assert_eq!(
+        FeedExcerpt::from_query(None, Some(i64::MAX), Some(-1)).expect("out of range"),
+        FeedExcerpt::Limited {
+            max_chars: FEED_EXCERPT_MIN_CHARS,
+            max_lines: Some(FEED_EXCERPT_MAX_LINES),
+        }
+    );
+    assert_eq!(
+        FeedExcerpt::from_query(None, None, Some(1_000_000)).expect("high"),
+        FeedExcerpt::Limited {
+            max_chars: FEED_EXCERPT_MAX_CHARS,
+            max_lines: None,
+        }
+    );
+    assert_eq!(
+        FeedExcerpt::from_query(Some(" full "), Some(3), Some(300)).expect("full"),
+        FeedExcerpt::Full
+    );
+    let err =
+        FeedExcerpt::from_query(Some("everything"), None, None).expect_err("unknown mode rejected");
+    assert_eq!(err.code(), "bad_request");
+}
+
+#[test]
+fn feed_excerpt_full_returns_whole_body() {
+    let long_body = format!("  {}\r\n", "a".repeat(RELEASE_FEED_BODY_MAX_CHARS + 500));
+    let (body, truncated) = FeedExcerpt::Full.apply(Some(long_body.as_str()));
+    assert_eq!(
+        body.expect("body").chars().count(),
+        RELEASE_FEED_BODY_MAX_CHARS + 500
+    );
+    assert!(!truncated);
+
+    let (body, truncated) = FeedExcerpt::default().apply(Some(long_body.as_str()));
+    assert_eq!(
+        body.expect("body").chars().count(),
+        RELEASE_FEED_BODY_MAX_CHARS
+    );
+    assert!(truncated);
+
+    let limited = FeedExcerpt::Limited {
+        max_chars: FEED_EXCERPT_MAX_CHARS,
+        max_lines: Some(2),
+    };
+    let (body, truncated) = limited.apply(Some("- one\n- two\n- three"));
+    assert_eq!(body.as_deref(), Some("- one\n- two"));
+    assert!(truncated);
+    assert_eq!(FeedExcerpt::Full.apply(Some("  \n ")), (None, false));
+}
+
+#[test]
+fn feed_item_from_row_keeps_translation_hash_across_excerpt_limits() {
+    let lines = (1..=30).map(|n| format!("- item {n}")).collect::<Vec<_>>();
+    let translated_lines = (1..=30).map(|n| format!("- 条目 {n}")).collect::<Vec<_>>();
+    let make_row = || {
+        let mut row = test_feed_row(Some("R_node"));
+        row.repo_full_name = Some("openai/codex".to_owned());
+        row.title = Some("Release v1.2.5".to_owned());
+        row.release_body = Some(lines.join("\n"));
+        row.trans_source_hash = Some(release_feed_translation_source_hash(
+            "openai/codex",
+            "Release v1.2.5",
+            release_feed_body(row.release_body.as_deref()).as_deref(),
+        ));
+        row.trans_status = Some("ready".to_owned());
+        row.trans_title = Some("发布 v1.2.5".to_owned());
+        row.trans_summary = Some(translated_lines.join("\n"));
+        row
+    };
+
+    for excerpt in [
+        FeedExcerpt::default(),
+        FeedExcerpt::from_query(None, Some(2), Some(200)).expect("short"),
+        FeedExcerpt::Full,
+    ] {
+        let item = feed_item_from_row(make_row(), true, None, excerpt);
+        let translated = item.translated.expect("translated item");
+        assert_eq!(translated.status, "ready", "{excerpt:?}");
+        assert_eq!(
+            translated.summary.as_deref(),
+            Some(translated_lines.join("\n").as_str())
+        );
+        if excerpt == FeedExcerpt::Full || excerpt == FeedExcerpt::default() {
+            assert_eq!(item.body.as_deref(), Some(lines.join("\n").as_str()));
+            assert!(!item.body_truncated);
+        } else {
+            assert_eq!(item.body.as_deref(), Some("- item 1\n- item 2"));
+            assert!(item.body_truncated);
+        }
+    }
+}
+
+#[test]
+fn feed_item_from_row_falls_back_to_legacy_ready_translation_when_detail_ready_is_invalid() {
+    let mut row = test_feed_row(Some("R_node"));
+    row.repo_full_name = Some("openai/codex".to_owned());
+    row.title = Some("Release v1.2.5".to_owned());
+    row.release_body = Some("- item".to_owned());
+    let body = release_feed_body(row.release_body.as_deref()).expect("release body");
+    let source = format!(
+        "v=6\nkind=release\nrepo={}\ntitle={}\nbody={}\n",
+        row.repo_full_name.as_deref().unwrap_or(""),
+        row.title.as_deref().unwrap_or(""),
+        body,
+    );
+    row.trans_source_hash = Some(ai::sha256_hex(&source));
+    row.trans_status = Some("ready".to_owned());
+    row.trans_title = Some("旧译文标题".to_owned());
+    row.trans_summary = Some("- 旧译文".to_owned());
+    row.detail_trans_source_hash = Some(release_detail_source_hash(
+        row.repo_full_name.as_deref().unwrap_or(""),
+        row.title.as_deref().unwrap_or(""),
+        row.release_body.as_deref().unwrap_or(""),
+    ));
+    row.detail_trans_status = Some("ready".to_owned());
+    row.detail_trans_title = Some("坏掉的详情译文".to_owned());
+    row.detail_trans_summary = None;
+
+    let item = feed_item_from_row(row, true, None, FeedExcerpt::default());
+    let translated = item.translated.expect("translated item");
+    assert_eq!(translated.status, "ready");
+    assert_eq!(translated.title.as_deref(), Some("旧译文标题"));
+    assert_eq!(translated.summary.as_deref(), Some("- 旧译文"));
+    assert_eq!(translated.auto_translate, None);
+}
+
+#[test]
+fn feed_item_from_row_does_not_fall_back_to_legacy_ready_translation_when_truncated_detail_failed()
+{
+    let mut row = test_feed_row(Some("R_node"));
+    row.repo_full_name = Some("openai/codex".to_owned());
+    row.title = Some("Release v1.2.6".to_owned());
+    row.release_body = Some("a".repeat(RELEASE_FEED_BODY_MAX_CHARS + 1));
+    let body = release_feed_body(row.release_body.as_deref()).expect("release body");
+    let source = format!(
+        "v=6
+kind=release
+repo={}
+title={}
+body={}
+",
+        row.repo_full_name.as_deref().unwrap_or(""),
+        row.title.as_deref().unwrap_or(""),
+        body,
+    );
+    row.trans_source_hash = Some(ai::sha256_hex(&source));
+    row.trans_status = Some("ready".to_owned());
+    row.trans_title = Some("旧译文标题".to_owned());
+    row.trans_summary = Some("- 旧译文".to_owned());
+    row.detail_trans_source_hash = Some(release_detail_source_hash(
+        row.repo_full_name.as_deref().unwrap_or(""),
+        row.title.as_deref().unwrap_or(""),
+        row.release_body.as_deref().unwrap_or(""),
+    ));
+    row.detail_trans_status = Some("error".to_owned());
+
+    let item = feed_item_from_row(row, true, None, FeedExcerpt::default());
+    let translated = item.translated.expect("translated item");
+    assert!(item.body_truncated);
+    assert_eq!(translated.status, "error");
+    assert_eq!(translated.auto_translate, Some(false));
+    assert!(translated.title.is_none());
+    assert!(translated.summary.is_none());
+}
+
+#[test]
+fn feed_item_from_row_marks_invalid_detail_ready_as_terminal_error_when_truncated() {
+    let mut row = test_feed_row(Some("R_node"));
+    row.repo_full_name = Some("openai/codex".to_owned());
+    row.title = Some("Release v1.2.6".to_owned());
+    row.release_body = Some("a".repeat(RELEASE_FEED_BODY_MAX_CHARS + 1));
+    row.detail_trans_source_hash = Some(release_detail_source_hash(
+        row.repo_full_name.as_deref().unwrap_or(""),
+        row.title.as_deref().unwrap_or(""),
+        row.release_body.as_deref().unwrap_or(""),
+    ));
+    row.detail_trans_status = Some("ready".to_owned());
+    row.detail_trans_title = Some("坏掉的详情译文".to_owned());
+    row.detail_trans_summary = None;
+
+    let item = feed_item_from_row(row, true, None, FeedExcerpt::default());
+    let translated = item.translated.expect("translated item");
+    assert!(item.body_truncated);
+    assert_eq!(translated.status, "error");
+    assert_eq!(translated.auto_translate, Some(false));
+    assert_eq!(
+        translated.error_code.as_deref(),
+        Some("markdown_structure_mismatch")
+    );
+    assert_eq!(
+        translated.error_summary.as_deref(),
+        Some("Markdown 结构校验失败")
+    );
+    assert_eq!(
+        translated.error_detail.as_deref(),
+        Some("release detail translation failed to preserve markdown structure")
+    );
+    assert!(translated.title.is_none());
+    assert!(translated.summary.is_none());
+}
+
+#[test]
+fn feed_item_from_row_keeps_ready_translation_visible_while_refresh_pending() {
+    let mut row = test_feed_row(Some("R_node"));
+    row.repo_full_name = Some("openai/codex".to_owned());
+    row.title = Some("Release v1.2.4".to_owned());
+    row.release_body = Some("- new item".to_owned());
+    row.trans_source_hash = Some("older-hash".to_owned());
+    row.trans_status = Some("ready".to_owned());
+    row.trans_title = Some("旧标题".to_owned());
+    row.trans_summary = Some("- 旧摘要".to_owned());
+    row.trans_work_status = Some("queued".to_owned());
+
+    let item = feed_item_from_row(row, true, None, FeedExcerpt::default());
+    let translated = item.translated.expect("translated item");
+    assert_eq!(translated.status, "ready");
+    assert_eq!(translated.title.as_deref(), Some("旧标题"));
+    assert_eq!(translated.summary.as_deref(), Some("- 旧摘要"));
+    assert_eq!(translated.auto_translate, Some(true));
+}
+
+#[test]
+fn feed_item_from_row_maps_smart_insufficient_to_terminal_state() {
+    let mut row = test_feed_row(Some("R_node"));
+    row.repo_full_name = Some("openai/codex".to_owned());
+    row.release_tag_name = Some("v1.2.4".to_owned());
+    row.release_previous_tag_name = Some("v1.2.3".to_owned());
+    row.title = Some("Release v1.2.4".to_owned());
+    row.release_body = Some("See full changelog below.".to_owned());
+    row.smart_source_hash = Some(crate::translations::release_smart_feed_source_hash(
+        row.entity_id.as_str(),
+        row.repo_full_name.as_deref().unwrap_or(""),
+        row.title.as_deref().unwrap_or(""),
+        row.release_body.as_deref(),
+        row.release_tag_name.as_deref().unwrap_or(""),
+        row.release_previous_tag_name.as_deref(),
+    ));
+    row.smart_status = Some("missing".to_owned());
+    row.smart_error_text = Some(SMART_NO_VALUABLE_VERSION_INFO.to_owned());
+
+    let item = feed_item_from_row(row, true, None, FeedExcerpt::default());
+    let smart = item.smart.expect("smart item");
+    assert_eq!(smart.status, "insufficient");
+    assert_eq!(smart.auto_translate, Some(false));
+    assert!(smart.title.is_none());
+    assert!(smart.summary.is_none());
+}
+
+#[test]
+fn smart_error_is_retryable_for_public_scope_upgrade() {
+    assert!(smart_error_is_retryable(Some(
+        "repo scope required; re-login via GitHub OAuth",
+    )));
+    assert!(smart_error_is_retryable(Some(
+        "AI returned 403 Forbidden: Chat upstream returned 403",
+    )));
+    assert!(!smart_error_is_retryable(Some(
+        "private repository compare requires repo scope; re-login via GitHub OAuth",
+    )));
+}
+
+#[test]
+fn feed_item_from_row_retries_retryable_smart_errors() {
+    let mut row = test_feed_row(Some("R_node"));
+    row.repo_full_name = Some("openai/codex".to_owned());
+    row.release_tag_name = Some("v1.2.4".to_owned());
+    row.release_previous_tag_name = Some("v1.2.3".to_owned());
+    row.title = Some("Release v1.2.4".to_owned());
+    row.release_body = Some("See full changelog below.".to_owned());
+    row.smart_source_hash = Some(crate::translations::release_smart_feed_source_hash(
+        row.entity_id.as_str(),
+        row.repo_full_name.as_deref().unwrap_or(""),
+        row.title.as_deref().unwrap_or(""),
+        row.release_body.as_deref(),
+        row.release_tag_name.as_deref().unwrap_or(""),
+        row.release_previous_tag_name.as_deref(),
+    ));
+    row.smart_status = Some("error".to_owned());
+    row.smart_error_text = Some("AI returned 403 Forbidden: Chat upstream returned 403".to_owned());
+
+    let item = feed_item_from_row(row, true, None, FeedExcerpt::default());
+    let smart = item.smart.expect("smart item");
+    assert_eq!(smart.status, "missing");
+    assert_eq!(smart.auto_translate, Some(true));
+    assert!(smart.title.is_none());
+    assert!(smart.summary.is_none());
+}
+
+#[test]
+fn feed_item_from_row_exposes_repo_visual_metadata() {
+    let mut row = test_feed_row(Some("R_node"));
+    row.repo_full_name = Some("openai/codex".to_owned());
+    row.owner_avatar_url = Some("https://avatars.githubusercontent.com/u/14957082".to_owned());
+    row.open_graph_image_url =
+        Some("https://repository-images.githubusercontent.com/14957082/codex".to_owned());
+    row.uses_custom_open_graph_image = Some(1);
+
+    let item = feed_item_from_row(row, true, None, FeedExcerpt::default());
+    let repo_visual = item.repo_visual.expect("repo visual");
+    assert_eq!(
+        repo_visual.owner_avatar_url.as_deref(),
+        Some("https://avatars.githubusercontent.com/u/14957082")
+    );
+    assert_eq!(
+        repo_visual.open_graph_image_url.as_deref(),
+        Some("https://repository-images.githubusercontent.com/14957082/codex")
+    );
+    assert!(repo_visual.uses_custom_open_graph_image);
+}
+
+#[test]
+fn feed_item_from_row_omits_repo_visual_when_metadata_missing() {
+    let mut row = test_feed_row(Some("R_node"));
+    row.repo_full_name = Some("openai/codex".to_owned());
+
+    let item = feed_item_from_row(row, true, None, FeedExcerpt::default());
+    assert!(item.repo_visual.is_none());
+}
+
+#[test]
+fn feed_item_from_row_maps_social_actor_payload() {
+    let mut row = test_feed_row(None);
+    row.kind = "repo_star_received".to_owned();
+    row.release_id = None;
+    row.repo_full_name = Some("openai/codex".to_owned());
+    row.entity_id = "star-event-1".to_owned();
+    row.id_key = "star-event-1".to_owned();
+    row.actor_login = Some("octocat".to_owned());
+    row.actor_avatar_url = Some("https://avatars.example/octocat.png".to_owned());
+    row.actor_html_url = Some("https://github.com/octocat".to_owned());
+    row.html_url = Some("https://github.com/octocat".to_owned());
+
+    let item = feed_item_from_row(row, true, None, FeedExcerpt::default());
+    assert_eq!(item.kind, "repo_star_received");
+    assert!(item.translated.is_none());
+    assert!(item.smart.is_none());
+    assert!(item.reactions.is_none());
+    let actor = item.actor.expect("social actor");
+    assert_eq!(actor.login, "octocat");
+    assert_eq!(
+        actor.avatar_url.as_deref(),
+        Some("https://avatars.example/octocat.png")
+    );
+    assert_eq!(
+        actor.html_url.as_deref(),
+        Some("https://github.com/octocat")
+    );
+    assert_eq!(item.html_url.as_deref(), Some("https://github.com/octocat"));
+}
+
+#[test]
+fn parse_feed_types_accepts_social_variants() {
+    let selection = parse_feed_types(Some("release,stars,follower")).expect("selection");
+    assert!(selection.releases);
+    assert!(selection.stars);
+    assert!(selection.followers);
+    assert!(!selection.ambient);
+}
+
+#[test]
+fn parse_feed_types_rejects_ambient_activity_as_dedicated_tab() {
+    assert!(parse_feed_types(Some("announcements")).is_err());
+    assert!(parse_feed_types(Some("forks")).is_err());
+}
+
+#[test]
+fn parse_release_smart_summary_payload_accepts_relaxed_payload_and_sanitizes_bullets() {
+    let raw = r#"
+        {
+          "valuable": "yes",
+          "title_cn": "版本变化速览",
+          "bullets": [
+            "新增 CLI 子命令",
+            "修复日志链接换行\n问题",
+            "详情见 https://example.com/changelog"
+          ]
+        }
+        "#;
+
+    let payload = parse_release_smart_summary_payload(raw).expect("smart payload");
+
+    assert!(payload.valuable);
+    assert_eq!(payload.title_zh.as_deref(), Some("版本变化速览"));
+    assert_eq!(
+        payload.summary_bullets,
+        vec![
+            "新增 CLI 子命令".to_owned(),
+            "修复日志链接换行 问题".to_owned(),
+            "详情见".to_owned(),
+        ]
+    );
+}
+
+#[test]
+fn build_compare_digest_filters_noise_and_keeps_meaningful_patch_excerpt() {
+    let digest = build_compare_digest(&GitHubCompareResponse {
+            status: Some("ahead".to_owned()),
+            ahead_by: Some(2),
+            behind_by: Some(0),
+            total_commits: Some(2),
+            commits: vec![
+                GitHubCompareCommit {
+                    sha: "abcdef123456".to_owned(),
+                    commit: GitHubCompareCommitDetail {
+                        message: "feat: add release smart fallback\n\nextra".to_owned(),
+                    },
+                },
+                GitHubCompareCommit {
+                    sha: "fedcba654321".to_owned(),
+                    commit: GitHubCompareCommitDetail {
+                        message: "fix: keep markdown bullets readable".to_owned(),
+                    },
+                },
+            ],
+            files: vec![
+                GitHubCompareFile {
+                    filename: "web/bun.lock".to_owned(),
+                    status: Some("modified".to_owned()),
+                    additions: Some(10),
+                    deletions: Some(2),
+                    changes: Some(12),
+                    patch: Some("+lockfileVersion: 1".to_owned()),
+                },
+                GitHubCompareFile {
+                    filename: "src/api.rs".to_owned(),
+                    status: Some("modified".to_owned()),
+                    additions: Some(12),
+                    deletions: Some(3),
+                    changes: Some(15),
+                    patch: Some(
+                        "@@ -1,3 +1,6 @@\n-use old\n+use new\n context\n+let digest = build();\n-http://example.com\n+summary.push(\"ok\");\n".to_owned(),
+                    ),
+                },
+                GitHubCompareFile {
+                    filename: "dist/app.min.js".to_owned(),
+                    status: Some("modified".to_owned()),
+                    additions: Some(100),
+                    deletions: Some(40),
+                    changes: Some(140),
+                    patch: Some("+compiled".to_owned()),
+                },
+            ],
+        })
+        .expect("compare digest");
+
+    assert!(digest.contains("compare_status: ahead"));
+    assert!(digest.contains("commit_subjects:"));
+    assert!(digest.contains("feat: add release smart fallback"));
+    assert!(digest.contains("changed_files:"));
+    assert!(digest.contains("src/api.rs [modified]"));
+    assert!(digest.contains("patch_excerpts:"));
+    assert!(digest.contains("### src/api.rs"));
+    assert!(!digest.contains("web/bun.lock"));
+    assert!(!digest.contains("dist/app.min.js"));
+}
+
+#[test]
+fn release_detail_translation_ready_requires_summary_for_non_empty_body() {
+    let body = "- item";
+    assert!(!release_detail_translation_ready(Some(body), None));
+    assert!(release_detail_translation_ready(Some(body), Some("- 条目")));
+}
+
+#[test]
+fn release_detail_translation_ready_allows_empty_body_without_summary() {
+    assert!(release_detail_translation_ready(Some(""), None));
+    assert!(release_detail_translation_ready(Some("   \n"), None));
+    assert!(release_detail_translation_ready(None, None));
+}
+
+#[test]
+fn release_detail_source_hash_changes_with_content() {
+    let hash1 = release_detail_source_hash("acme/repo", "v1.0.0", "line one");
+    let hash2 = release_detail_source_hash("acme/repo", "v1.0.0", "line two");
+    assert_ne!(hash1, hash2);
+}
+
+#[test]
+fn release_detail_source_hash_normalizes_line_endings_and_whitespace() {
+    let hash1 = release_detail_source_hash(
+        " acme/repo ",
+        " v1.0.0 ",
+        "line one
+line two
+",
+    );
+    let hash2 = release_detail_source_hash(
+        "acme/repo",
+        "v1.0.0",
+        "line one
+line two",
+    );
+    assert_eq!(hash1, hash2);
+}
+
+#[tokio::test]
+async fn mark_translation_requested_keeps_existing_ready_translation_visible() {
+    let pool = setup_pool().await;
+    let state = setup_state(pool.clone());
+    seed_user(&pool, 7, "race-user", 0, 0).await;
+
+    let user_id = test_user_id(7);
+    let entity_id = "42";
+    let ready_hash = "ready-source";
+    let pending_hash = "pending-source";
+
+    upsert_translation(
+        state.as_ref(),
+        user_id.as_str(),
+        "2099-03-30T00:00:01Z",
+        TranslationUpsert {
+            entity_type: "release",
+            entity_id,
+            lang: "zh-CN",
+            source_hash: ready_hash,
+            title: Some("旧标题"),
+            summary: Some("旧摘要"),
+        },
+    )
+    .await
+    .expect("seed ready translation");
+
+    mark_translation_requested(
+        state.as_ref(),
+        user_id.as_str(),
+        "2099-03-30T00:00:02Z",
+        TranslationUpsert {
+            entity_type: "release",
+            entity_id,
+            lang: "zh-CN",
+            source_hash: pending_hash,
+            title: None,
+            summary: None,
+        },
+    )
+    .await
+    .expect("mark refresh request");
+
+    let row: (String, String, Option<String>, Option<String>) = sqlx::query_as(
+        r#"
+            SELECT source_hash, status, title, summary
+            FROM ai_translations
+            WHERE user_id = ? AND entity_type = 'release' AND entity_id = ? AND lang = 'zh-CN'
+            LIMIT 1
+            "#,
+    )
+    .bind(user_id.as_str())
+    .bind(entity_id)
+    .fetch_one(&pool)
+    .await
+    .expect("load preserved ready translation row");
+    assert_eq!(row.0, ready_hash);
+    assert_eq!(row.1, "ready");
+    assert_eq!(row.2.as_deref(), Some("旧标题"));
+    assert_eq!(row.3.as_deref(), Some("旧摘要"));
+
+    upsert_translation(
+        state.as_ref(),
+        user_id.as_str(),
+        "2099-03-30T00:00:03Z",
+        TranslationUpsert {
+            entity_type: "release",
+            entity_id,
+            lang: "zh-CN",
+            source_hash: pending_hash,
+            title: Some("新标题"),
+            summary: Some("新摘要"),
+        },
+    )
+    .await
+    .expect("refresh result should land");
+
+    let row: (String, String, Option<String>, Option<String>) = sqlx::query_as(
+        r#"
+            SELECT source_hash, status, title, summary
+            FROM ai_translations
+            WHERE user_id = ? AND entity_type = 'release' AND entity_id = ? AND lang = 'zh-CN'
+            LIMIT 1
+            "#,
+    )
+    .bind(user_id.as_str())
+    .bind(entity_id)
+    .fetch_one(&pool)
+    .await
+    .expect("load completed translation row");
+    assert_eq!(row.0, pending_hash);
+    assert_eq!(row.1, "ready");
+    assert_eq!(row.2.as_deref(), Some("新标题"));
+    assert_eq!(row.3.as_deref(), Some("新摘要"));
+}
+
+#[test]
+fn brief_contains_release_link_matches_exact_id() {
+    let markdown = "- [v1.2.3](/?tab=briefs&release=123)";
+    assert!(brief_contains_release_link(markdown, 123, None));
+    assert!(!brief_contains_release_link(markdown, 12, None));
+}
+
+#[test]
+fn brief_contains_release_link_accepts_query_order_variant() {
+    let markdown = "- [v1.2.3](/?release=123&tab=briefs)";
+    assert!(brief_contains_release_link(markdown, 123, None));
+}
+
+#[test]
+fn brief_contains_release_link_accepts_canonical_repo_tag_path() {
+    let markdown = "- [v1.2.3](/acme/rocket/releases/tag/v1.2.3?from=briefs)";
+    let locator = ReleaseLocator {
+        owner: "acme".to_owned(),
+        repo: "rocket".to_owned(),
+        tag: "v1.2.3".to_owned(),
+    };
+    assert!(brief_contains_release_link(markdown, 999, Some(&locator)));
+}
+
+#[test]
+fn extract_brief_release_ids_preserves_order_without_duplicates() {
+    let markdown = "\
+- [a](/?tab=briefs&release=123)\n\
+- [b](/?release=456&tab=briefs)\n\
+- [dup](/?tab=briefs&release=123)\n";
+    assert_eq!(extract_brief_release_ids(markdown), vec![123, 456]);
+}
+
+#[test]
+fn resolve_brief_generate_target_clamps_window_hours() {
+    let now = chrono::Utc
+        .with_ymd_and_hms(2026, 4, 12, 12, 0, 0)
+        .single()
+        .expect("valid datetime");
+    let resolve_hours = |window_hours| {
+        resolve_brief_generate_target(
+            BriefGenerateRequest {
+                window_hours: Some(window_hours),
+                ..BriefGenerateRequest::default()
+            },
+            now,
+        )
+        .expect("resolve window hours")
+    };
+
+    assert_eq!(
+        resolve_hours(6),
+        BriefGenerateTarget::Range {
+            start_utc: now - chrono::Duration::hours(6),
+            end_utc: now,
+        }
+    );
+    assert_eq!(
+        resolve_hours(0),
+        BriefGenerateTarget::Range {
+            start_utc: now - chrono::Duration::hours(1),
+            end_utc: now,
+        }
+    );
+    assert_eq!(
+        resolve_hours(500),
+        BriefGenerateTarget::Range {
+            start_utc: now - chrono::Duration::hours(72),
+            end_utc: now,
+        }
+    );
+    assert_eq!(
+        resolve_brief_generate_target(BriefGenerateRequest::default(), now)
+            .expect("resolve default"),
+        BriefGenerateTarget::Current
+    );
+}
+
+#[test]
+fn resolve_brief_generate_target_validates_explicit_windows() {
+    let now = chrono::Utc::now();
+    let explicit = |start: &str, end: &str| {
+        resolve_brief_generate_target(
+            BriefGenerateRequest {
+                window_start: Some(start.to_owned()),
+                window_end: Some(end.to_owned()),
+                ..BriefGenerateRequest::default()
+            },
+            now,
+        )
+    };
+
+    assert_eq!(
+        explicit("2026-04-10T08:00:00+08:00", "2026-04-12T00:00:00Z")
+            .expect("valid explicit window"),
+        BriefGenerateTarget::Range {
+            start_utc: chrono::Utc
+                .with_ymd_and_hms(2026, 4, 10, 0, 0, 0)
+                .single()
+                .expect("valid datetime"),
+            end_utc: chrono::Utc
+                .with_ymd_and_hms(2026, 4, 12, 0, 0, 0)
+                .single()
+                .expect("valid datetime"),
+        }
+    );
+    for (start, end) in [
+        ("2026-04-01T00:00:00Z", "2026-04-08T00:00:01Z"),
+        ("2026-04-12T00:00:00Z", "2026-04-12T00:00:00Z"),
+        ("yesterday", "2026-04-12T00:00:00Z"),
+    ] {
+        let err = explicit(start, end).expect_err("invalid explicit window");
+        assert_eq!(err.code(), "bad_request");
+    }
+
+    let half_open = resolve_brief_generate_target(
+        BriefGenerateRequest {
+            window_start: Some("2026-04-10T00:00:00Z".to_owned()),
+            ..BriefGenerateRequest::default()
+        },
+        now,
+    );
+    assert!(half_open.is_err());
+    let mixed = resolve_brief_generate_target(
+        BriefGenerateRequest {
+            date: Some("2026-04-10".to_owned()),
+            window_hours: Some(6),
+            ..BriefGenerateRequest::default()
+        },
+        now,
+    );
+    assert!(mixed.is_err());
+}
+
+#[tokio::test]
+async fn release_visibility_view_hides_owned_repo_until_opted_in() {
+    let pool = setup_pool().await;
+    seed_repo_release(&pool, 42, 120).await;
+    seed_owned_repo_baseline(&pool, 42, "IvanLi-CN/octo-rill").await;
+
+    let count_without_opt_in: i64 = sqlx::query_scalar(
+        r#"
+            SELECT COUNT(*)
+            FROM repo_releases r
+            JOIN user_release_visible_repos vr
+              ON vr.user_id = ? AND vr.repo_id = r.repo_id
+            "#,
+    )
+    .bind(test_user_id(1))
+    .fetch_one(&pool)
+    .await
+    .expect("count releases without opt-in");
+    assert_eq!(count_without_opt_in, 0);
+
+    set_include_own_releases(&pool, true).await;
+    let count_with_opt_in: i64 = sqlx::query_scalar(
+        r#"
+            SELECT COUNT(*)
+            FROM repo_releases r
+            JOIN user_release_visible_repos vr
+              ON vr.user_id = ? AND vr.repo_id = r.repo_id
+            "#,
+    )
+    .bind(test_user_id(1))
+    .fetch_one(&pool)
+    .await
+    .expect("count releases with opt-in");
+    assert_eq!(count_with_opt_in, 1);
+}
+
+#[tokio::test]
+async fn list_feed_fields_basic_omits_excerpt_and_summaries() {
+    let pool = setup_pool().await;
+    seed_repo_release(&pool, 42, 120).await;
+    seed_star(&pool, 42).await;
+    let state = setup_state(pool);
+    let feed_json = |fields: Option<&str>| {
+        let state = state.clone();
+        let fields = fields.map(str::to_owned);
+        async move {
+            let response = list_feed(
+                State(state),
+                AuthedUser(test_user_id(1)),
+                Query(FeedQuery {
+                    cursor: None,
+                    limit: Some(30),
+                    types: Some("releases".to_owned()),
+                    scope: None,
+                    items: None,
+                    org: None,
+                    unseen_only: None,
+                    language: None,
+                    topic: None,
+                    exclude_archived: None,
+                    excerpt: None,
+                    excerpt_lines: None,
+                    excerpt_chars: None,
+                    mark_seen: None,
+                    fields,
+                }),
+            )
+            .await?;
+            let bytes = to_bytes(response.into_body(), usize::MAX)
+                .await
+                .expect("read feed body");
+            Ok::<_, ApiError>(
+                serde_json::from_slice::<serde_json::Value>(&bytes).expect("feed json"),
+            )
+        }
+    };
+
+    let full = feed_json(None).await.expect("full feed");
+    let full_item = &full["items"][0];
+    assert_eq!(full_item["kind"], "release");
+    assert!(full_item["body"].is_string());
+    assert!(full_item.get("translated").is_some());
+
+    let basic = feed_json(Some("basic")).await.expect("basic feed");
+    let basic_item = &basic["items"][0];
+    assert_eq!(basic_item["id"], full_item["id"]);
+    assert_eq!(basic_item["title"], full_item["title"]);
+    assert!(basic_item.get("body").is_none());
+    assert!(basic_item.get("body_truncated").is_none());
+    assert!(basic_item.get("repo_description").is_none());
+    if let Some(translated) = basic_item["translated"].as_object() {
+        assert!(!translated.contains_key("summary"));
+        assert!(translated.contains_key("status"));
+    }
+    assert!(
+        serde_json::to_vec(&basic).expect("encode basic").len()
+            < serde_json::to_vec(&full).expect("encode full").len()
+    );
+
+    let err = feed_json(Some("everything"))
+        .await
+        .expect_err("unknown fields value");
+    assert_eq!(err.code(), "bad_request");
+}
+
+#[tokio::test]
+async fn list_feed_returns_mixed_items_and_supports_social_filters() {
+    let pool = setup_pool().await;
+    let user_id = test_user_id(1);
+    seed_repo_release(&pool, 42, 120).await;
+    seed_star(&pool, 42).await;
+    seed_social_event(
+        &pool,
+        user_id.as_str(),
+        SeedSocialEventArgs {
+            kind: "repo_star_received",
+            event_id: "social-star-1",
+            repo_id: Some(42),
+            repo_full_name: Some("openai/codex"),
+            repo_owner_avatar_url: None,
+            repo_open_graph_image_url: None,
+            repo_uses_custom_open_graph_image: None,
+            title: None,
+            body: None,
+            html_url: None,
+            actor_login: "octocat",
+            occurred_at: "2026-02-23T10:00:00Z",
+        },
+    )
+    .await;
+    seed_social_event(
+        &pool,
+        user_id.as_str(),
+        SeedSocialEventArgs {
+            kind: "follower_received",
+            event_id: "social-follow-1",
+            repo_id: None,
+            repo_full_name: None,
+            repo_owner_avatar_url: None,
+            repo_open_graph_image_url: None,
+            repo_uses_custom_open_graph_image: None,
+            title: None,
+            body: None,
+            html_url: None,
+            actor_login: "monalisa",
+            occurred_at: "2026-02-23T09:00:00Z",
+        },
+    )
+    .await;
+    seed_social_event(
+        &pool,
+        user_id.as_str(),
+        SeedSocialEventArgs {
+            kind: "announcement",
+            event_id: "social-announcement-1",
+            repo_id: Some(42),
+            repo_full_name: Some("openai/codex"),
+            repo_owner_avatar_url: None,
+            repo_open_graph_image_url: None,
+            repo_uses_custom_open_graph_image: None,
+            title: Some("Roadmap discussion announcement"),
+            body: Some("- dashboard discussion announcement"),
+            html_url: Some("https://github.com/openai/codex/discussions/42"),
+            actor_login: "maintainer",
+            occurred_at: "2026-02-23T11:00:00Z",
+        },
+    )
+    .await;
+    seed_social_event(
+        &pool,
+        user_id.as_str(),
+        SeedSocialEventArgs {
+            kind: "repo_forked",
+            event_id: "social-fork-1",
+            repo_id: Some(43),
+            repo_full_name: Some("octocat/codex-fork"),
+            repo_owner_avatar_url: None,
+            repo_open_graph_image_url: None,
+            repo_uses_custom_open_graph_image: None,
+            title: Some("octocat/codex-fork"),
+            body: Some("Forked repository"),
+            html_url: Some("https://github.com/octocat/codex-fork"),
+            actor_login: "octocat",
+            occurred_at: "2026-02-23T10:30:00Z",
+        },
+    )
+    .await;
+    let state = setup_state(pool);
+
+    let Json(feed) = list_feed_page(
+        State(state.clone()),
+        AuthedUser(test_user_id(1)),
+        Query(FeedQuery {
+            cursor: None,
+            limit: Some(30),
+            types: None,
+            scope: None,
+            items: None,
+            org: None,
+            unseen_only: None,
+            language: None,
+            topic: None,
+            exclude_archived: None,
+            excerpt: None,
+            excerpt_lines: None,
+            excerpt_chars: None,
+            mark_seen: None,
+            fields: None,
+        }),
+    )
+    .await
+    .expect("list mixed feed");
+
+    let kinds = feed
+        .items
+        .iter()
+        .map(|item| item.kind.as_str())
+        .collect::<Vec<_>>();
+    assert_eq!(
+        kinds,
+        vec![
+            "announcement",
+            "repo_forked",
+            "repo_star_received",
+            "follower_received",
+            "release",
+        ]
+    );
+    assert_eq!(
+        feed.items[0].title.as_deref(),
+        Some("Roadmap discussion announcement")
+    );
+    assert_eq!(feed.items[0].subject_type.as_deref(), Some("discussion"));
+    assert_eq!(
+        feed.items[0].html_url.as_deref(),
+        Some("https://github.com/openai/codex/discussions/42")
+    );
+    assert_eq!(
+        feed.items[0]
+            .actor
+            .as_ref()
+            .expect("social actor")
+            .login
+            .as_str(),
+        "maintainer"
+    );
+
+    let Json(first_page) = list_feed_page(
+        State(state.clone()),
+        AuthedUser(test_user_id(1)),
+        Query(FeedQuery {
+            cursor: None,
+            limit: Some(2),
+            types: None,
+            scope: None,
+            items: None,
+            org: None,
+            unseen_only: None,
+            language: None,
+            topic: None,
+            exclude_archived: None,
+            excerpt: None,
+            excerpt_lines: None,
+            excerpt_chars: None,
+            mark_seen: None,
+            fields: None,
+        }),
+    )
+    .await
+    .expect("list first mixed feed page");
+    assert_eq!(
+        first_page
+            .items
+            .iter()
+            .map(|item| item.kind.as_str())
+            .collect::<Vec<_>>(),
+        vec!["announcement", "repo_forked"]
+    );
+    let cursor = first_page.next_cursor.expect("first page cursor");
+    let Json(second_page) = list_feed_page(
+        State(state.clone()),
+        AuthedUser(test_user_id(1)),
+        Query(FeedQuery {
+            cursor: Some(cursor),
+            limit: Some(30),
+            types: None,
+            scope: None,
+            items: None,
+            org: None,
+            unseen_only: None,
+            language: None,
+            topic: None,
+            exclude_archived: None,
+            excerpt: None,
+            excerpt_lines: None,
+            excerpt_chars: None,
+            mark_seen: None,
+            fields: None,
+        }),
+    )
+    .await
+    .expect("list second mixed feed page");
+    assert_eq!(
+        second_page
+            .items
+            .iter()
+            .map(|item| item.kind.as_str())
+            .collect::<Vec<_>>(),
+        vec!["repo_star_received", "follower_received", "release"]
+    );
+
+    let Json(stars_only) = list_feed_page(
+        State(state.clone()),
+        AuthedUser(test_user_id(1)),
+        Query(FeedQuery {
+            cursor: None,
+            limit: Some(30),
+            types: Some("stars".to_owned()),
+            scope: None,
+            items: None,
+            org: None,
+            unseen_only: None,
+            language: None,
+            topic: None,
+            exclude_archived: None,
+            excerpt: None,
+            excerpt_lines: None,
+            excerpt_chars: None,
+            mark_seen: None,
+            fields: None,
+        }),
+    )
+    .await
+    .expect("list stars feed");
+
+    assert_eq!(stars_only.items.len(), 1);
+    assert_eq!(stars_only.items[0].kind, "repo_star_received");
+
+    let Json(releases_only) = list_feed_page(
+        State(state.clone()),
+        AuthedUser(test_user_id(1)),
+        Query(FeedQuery {
+            cursor: None,
+            limit: Some(30),
+            types: Some("releases".to_owned()),
+            scope: None,
+            items: None,
+            org: None,
+            unseen_only: None,
+            language: None,
+            topic: None,
+            exclude_archived: None,
+            excerpt: None,
+            excerpt_lines: None,
+            excerpt_chars: None,
+            mark_seen: None,
+            fields: None,
+        }),
+    )
+    .await
+    .expect("list releases feed");
+    assert_eq!(releases_only.items.len(), 1);
+    assert_eq!(releases_only.items[0].kind, "release");
+
+    let announcements_only = list_feed_page(
+        State(state.clone()),
+        AuthedUser(test_user_id(1)),
+        Query(FeedQuery {
+            cursor: None,
+            limit: Some(30),
+            types: Some("announcements".to_owned()),
+            scope: None,
+            items: None,
+            org: None,
+            unseen_only: None,
+            language: None,
+            topic: None,
+            exclude_archived: None,
+            excerpt: None,
+            excerpt_lines: None,
+            excerpt_chars: None,
+            mark_seen: None,
+            fields: None,
+        }),
+    )
+    .await;
+    assert!(announcements_only.is_err());
+}
+
+#[tokio::test]
+async fn list_feed_serves_cached_reactions_without_live_viewer_lookup() {
+    let pool = setup_pool().await;
+    seed_repo_release(&pool, 42, 120).await;
+    seed_star(&pool, 42).await;
+    sqlx::query(
+        r#"
+            UPDATE repo_releases
+            SET react_plus1 = 7,
+                react_heart = 3
+            WHERE release_id = 120
+            "#,
+    )
+    .execute(&pool)
+    .await
+    .expect("seed cached reactions");
+    let state = setup_state(pool);
+
+    let Json(feed) = list_feed_page(
+        State(state),
+        AuthedUser(test_user_id(1)),
+        Query(FeedQuery {
+            cursor: None,
+            limit: Some(30),
+            types: Some("releases".to_owned()),
+            scope: None,
+            items: None,
+            org: None,
+            unseen_only: None,
+            language: None,
+            topic: None,
+            exclude_archived: None,
+            excerpt: None,
+            excerpt_lines: None,
+            excerpt_chars: None,
+            mark_seen: None,
+            fields: None,
+        }),
+    )
+    .await
+    .expect("list release feed");
+
+    let item = feed.items.first().expect("release feed item");
+    assert_eq!(item.kind, "release");
+    let reactions = item.reactions.as_ref().expect("cached reactions");
+    assert_eq!(reactions.counts.plus1, 7);
+    assert_eq!(reactions.counts.heart, 3);
+    assert!(!reactions.viewer.plus1);
+    assert!(!reactions.viewer.heart);
+}
+
+#[tokio::test]
+async fn list_repo_releases_pages_one_starred_repo_newest_first() {
+    let pool = setup_pool().await;
+    seed_star(&pool, 42).await;
+    seed_repo_release(&pool, 42, 201).await;
+    seed_repo_release(&pool, 42, 202).await;
+    seed_repo_release(&pool, 43, 301).await;
+    for (release_id, tag, published_at) in [
+        (201, "v1.0.0", "2026-02-20T00:00:00Z"),
+        (202, "v1.1.0", "2026-02-22T00:00:00Z"),
+        (301, "v9.0.0", "2026-02-23T00:00:00Z"),
+    ] {
+        sqlx::query(
+            r#"
+                UPDATE repo_releases
+                SET tag_name = ?, name = ?, published_at = ?
+                WHERE release_id = ?
+                "#,
+        )
+        .bind(tag)
+        .bind(tag)
+        .bind(published_at)
+        .bind(release_id)
+        .execute(&pool)
+        .await
+        .expect("seed release history");
+    }
+    let state = setup_state(pool);
+
+    let Json(first) = list_repo_releases(
+        State(state.clone()),
+        AuthedUser(test_user_id(1)),
+        Path("42".to_owned()),
+        Query(RepoReleasesQuery {
+            cursor: None,
+            limit: Some(1),
+        }),
+    )
+    .await
+    .expect("list first repo releases page");
+
+    assert_eq!(first.repo.full_name, "openai/codex");
+    assert_eq!(first.repo.latest_tag.as_deref(), Some("v1.1.0"));
+    assert_eq!(first.items.len(), 1);
+    assert_eq!(first.items[0].id, "202");
+    assert_eq!(first.items[0].title.as_deref(), Some("v1.1.0"));
+    let cursor = first.next_cursor.clone().expect("next cursor");
+
+    let Json(second) = list_repo_releases(
+        State(state.clone()),
+        AuthedUser(test_user_id(1)),
+        Path("42".to_owned()),
+        Query(RepoReleasesQuery {
+            cursor: Some(cursor),
+            limit: Some(1),
+        }),
+    )
+    .await
+    .expect("list second repo releases page");
+    assert_eq!(
+        second
+            .items
+            .iter()
+            .map(|item| item.id.as_str())
+            .collect::<Vec<_>>(),
+        vec!["201"]
+    );
+
+    let err = list_repo_releases(
+        State(state),
+        AuthedUser(test_user_id(1)),
+        Path("43".to_owned()),
+        Query(RepoReleasesQuery {
+            cursor: None,
+            limit: None,
+        }),
+    )
+    .await
+    .expect_err("unstarred repo is hidden");
+    assert_eq!(err.code(), "repo_not_found");
+}
+
+#[tokio::test]
+async fn counters_count_releases_after_feed_seen_baseline() {
+    let pool = setup_pool().await;
+    seed_star(&pool, 42).await;
+    for (release_id, published_at) in [
+        (301, "2026-02-20T00:00:00Z"),
+        (302, "2026-02-22T00:00:00Z"),
+        (303, "2026-02-24T00:00:00Z"),
+    ] {
+        seed_repo_release(&pool, 42, release_id).await;
+        sqlx::query("UPDATE repo_releases SET published_at = ? WHERE release_id = ?")
+            .bind(published_at)
+            .bind(release_id)
+            .execute(&pool)
+            .await
+            .expect("set release date");
+    }
+    let user_id = test_user_id(1);
+    seed_notification(&pool, &user_id, "counter-1", "2026-02-23T00:00:00Z").await;
+    seed_notification(&pool, &user_id, "counter-2", "2026-02-23T00:00:00Z").await;
+    sqlx::query("UPDATE notifications SET unread = 0 WHERE thread_id = 'counter-2'")
+        .execute(&pool)
+        .await
+        .expect("mark one notification read");
+    let state = setup_state(pool.clone());
+
+    let Json(counters) = get_counters(State(state.clone()), AuthedUser(test_user_id(1)))
+        .await
+        .expect("load counters");
+    assert_eq!(counters.unread_notifications, 1);
+    assert_eq!(counters.new_releases_since, 0, "no baseline yet");
+    assert_eq!(counters.last_feed_seen_at, None);
+
+    sqlx::query("UPDATE users SET feed_last_seen_at = '2026-02-21T00:00:00Z' WHERE id = ?")
+        .bind(&user_id)
+        .execute(&pool)
+        .await
+        .expect("set seen baseline");
+    let Json(counters) = get_counters(State(state.clone()), AuthedUser(test_user_id(1)))
+        .await
+        .expect("load counters after baseline");
+    assert_eq!(counters.new_releases_since, 2);
+
+    let feed_page = |mark_seen| FeedQuery {
+        cursor: None,
+        limit: Some(1),
+        types: None,
+        scope: None,
+        items: None,
+        org: None,
+        unseen_only: None,
+        language: None,
+        topic: None,
+        exclude_archived: None,
+        excerpt: None,
+        excerpt_lines: None,
+        excerpt_chars: None,
+        mark_seen,
+        fields: None,
+    };
+    let Json(page) = list_feed_page(
+        State(state.clone()),
+        AuthedUser(test_user_id(1)),
+        Query(feed_page(None)),
+    )
+    .await
+    .expect("list feed without marking");
+    assert_eq!(page.items.len(), 1);
+    let Json(counters) = get_counters(State(state.clone()), AuthedUser(test_user_id(1)))
+        .await
+        .expect("load counters after plain feed load");
+    assert_eq!(counters.new_releases_since, 2);
+
+    let Json(page) = list_feed_page(
+        State(state.clone()),
+        AuthedUser(test_user_id(1)),
+        Query(feed_page(Some(true))),
+    )
+    .await
+    .expect("list feed and mark seen");
+    assert_eq!(page.items.len(), 1);
+    let Json(counters) = get_counters(State(state.clone()), AuthedUser(test_user_id(1)))
+        .await
+        .expect("load counters after mark_seen");
+    assert_eq!(counters.new_releases_since, 0);
+    let seen_at = counters.last_feed_seen_at.expect("baseline advanced");
+    assert!(seen_at.as_str() > "2026-02-24T00:00:00Z", "{seen_at}");
+
+    sqlx::query("UPDATE users SET feed_last_seen_at = '2026-02-23T00:00:00Z' WHERE id = ?")
+        .bind(&user_id)
+        .execute(&pool)
+        .await
+        .expect("rewind seen baseline");
+    let Json(counters) = mark_feed_seen_at(State(state), AuthedUser(test_user_id(1)))
+        .await
+        .expect("mark feed seen at");
+    assert_eq!(counters.new_releases_since, 0);
+    assert_eq!(counters.unread_notifications, 1);
+}
+
+#[tokio::test]
+async fn feed_unseen_only_pages_past_seen_releases_and_counts_them() {
+    let pool = setup_pool().await;
+    seed_star(&pool, 42).await;
+    for (offset, release_id) in (201..=205).enumerate() {
+        seed_repo_release(&pool, 42, release_id).await;
+        sqlx::query("UPDATE repo_releases SET published_at = ? WHERE release_id = ?")
+            .bind(format!("2026-02-2{offset}T00:00:00Z"))
+            .bind(release_id)
+            .execute(&pool)
+            .await
+            .expect("spread release dates");
+    }
+    let state = setup_state(pool);
+
+    let Json(marked) = mark_feed_seen(
+        State(state.clone()),
+        AuthedUser(test_user_id(1)),
+        ApiJson(MarkFeedSeenRequest {
+            release_ids: vec!["202".to_owned(), "204".to_owned(), "204".to_owned()],
+        }),
+    )
+    .await
+    .expect("mark releases seen");
+    assert_eq!(marked.marked, 2);
+
+    let unseen_page = |cursor: Option<String>| FeedQuery {
+        cursor,
+        limit: Some(2),
+        types: None,
+        scope: None,
+        items: None,
+        org: None,
+        unseen_only: Some(true),
+        language: None,
+        topic: None,
+        exclude_archived: None,
+        excerpt: None,
+        excerpt_lines: None,
+        excerpt_chars: None,
+        mark_seen: None,
+        fields: None,
+    };
+    let Json(first) = list_feed_page(
+        State(state.clone()),
+        AuthedUser(test_user_id(1)),
+        Query(unseen_page(None)),
+    )
+    .await
+    .expect("list first unseen page");
+    let Json(second) = list_feed_page(
+        State(state.clone()),
+        AuthedUser(test_user_id(1)),
+        Query(unseen_page(first.next_cursor.clone())),
+    )
+    .await
+    .expect("list second unseen page");
+    let ids = first
+        .items
+        .iter()
+        .chain(second.items.iter())
+        .map(|item| item.id.as_str())
+        .collect::<Vec<_>>();
+    assert_eq!(ids, vec!["205", "203", "201"]);
+    assert_eq!(second.next_cursor, None);
+
+    let Json(count) = get_repo_unseen_release_count(
+        State(state),
+        AuthedUser(test_user_id(1)),
+        Path("42".to_owned()),
+    )
+    .await
+    .expect("count unseen releases");
+    assert_eq!(count.unseen_count, 3);
+}
+
+#[tokio::test]
+async fn bookmarks_flag_feed_items_and_survive_release_purge() {
+    let pool = setup_pool().await;
+    seed_star(&pool, 42).await;
+    seed_repo_release(&pool, 42, 201).await;
+    let state = setup_state(pool.clone());
+
+    let err = create_bookmark(
+        State(state.clone()),
+        AuthedUser(test_user_id(1)),
+        ApiJson(CreateBookmarkRequest {
+            kind: "release".to_owned(),
+            entity_id: "201".to_owned(),
+            note: Some("x".repeat(2001)),
+        }),
+    )
+    .await
+    .expect_err("overlong note is rejected");
+    assert_eq!(err.code(), "bad_request");
+
+    let Json(bookmark) = create_bookmark(
+        State(state.clone()),
+        AuthedUser(test_user_id(1)),
+        ApiJson(CreateBookmarkRequest {
+            kind: "release".to_owned(),
+            entity_id: "201".to_owned(),
+            note: Some("  read after lunch  ".to_owned()),
+        }),
+    )
+    .await
+    .expect("bookmark release");
+    assert_eq!(bookmark.note.as_deref(), Some("read after lunch"));
+    assert!(bookmark.release_available);
+    assert!(bookmark.item.bookmarked);
+
+    let Json(feed) = list_feed_page(
+        State(state.clone()),
+        AuthedUser(test_user_id(1)),
+        Query(FeedQuery {
+            cursor: None,
+            limit: Some(30),
+            types: None,
+            scope: None,
+            items: None,
+            org: None,
+            unseen_only: None,
+            language: None,
+            topic: None,
+            exclude_archived: None,
+            excerpt: None,
+            excerpt_lines: None,
+            excerpt_chars: None,
+            mark_seen: None,
+            fields: None,
+        }),
+    )
+    .await
+    .expect("list feed");
+    let item = feed
+        .items
+        .iter()
+        .find(|item| item.id == "201")
+        .expect("bookmarked release in feed");
+    assert!(item.bookmarked);
+    assert_eq!(item.bookmark_id.as_deref(), Some(bookmark.id.as_str()));
+
+    sqlx::query("DELETE FROM repo_releases WHERE release_id = ?")
+        .bind(201_i64)
+        .execute(&pool)
+        .await
+        .expect("purge release");
+    sqlx::query("DELETE FROM starred_repos WHERE repo_id = ?")
+        .bind(42_i64)
+        .execute(&pool)
+        .await
+        .expect("unstar repo");
+
+    let Json(listed) = list_bookmarks(
+        State(state.clone()),
+        AuthedUser(test_user_id(1)),
+        Query(BookmarksQuery {
+            cursor: None,
+            limit: None,
+        }),
+    )
+    .await
+    .expect("list bookmarks");
+    assert_eq!(listed.items.len(), 1);
+    let snapshot = &listed.items[0];
+    assert!(!snapshot.release_available);
+    assert_eq!(snapshot.item.title.as_deref(), Some("Release v1.2.3"));
+    assert_eq!(
+        snapshot.item.repo_full_name.as_deref(),
+        Some("openai/codex")
+    );
+    assert_eq!(
+        snapshot.item.html_url.as_deref(),
+        Some("https://github.com/openai/codex/releases/tag/v1.2.3")
+    );
+
+    let Json(deleted) = delete_bookmark(
+        State(state.clone()),
+        AuthedUser(test_user_id(1)),
+        Path(bookmark.id.clone()),
+    )
+    .await
+    .expect("delete bookmark");
+    assert!(!deleted.bookmarked);
+    let err = delete_bookmark(State(state), AuthedUser(test_user_id(1)), Path(bookmark.id))
+        .await
+        .expect_err("bookmark already deleted");
+    assert_eq!(err.code(), "bookmark_not_found");
+}
+
+#[tokio::test]
+async fn dashboard_updates_token_stays_below_common_request_line_limits() {
+    let feed = (0..30)
+        .map(|i| {
+            format!(
+                "release:{}|sort=2026-04-30T10:00:{i:02}Z|{}",
+                1_000_000_000_i64 + i,
+                "a".repeat(256)
+            )
+        })
+        .collect::<Vec<_>>();
+    let briefs = (0..50)
+        .map(|i| {
+            format!(
+                "brief:brief-2026-04-{i:02}|2026-04-30T10:00:00.000000Z|{}",
+                "b".repeat(128)
+            )
+        })
+        .collect::<Vec<_>>();
+    let notifications = (0..50)
+        .map(|i| {
+            format!(
+                "notification:github-thread-{}-{i}|2026-04-30T10:00:00.000000Z|1|{}",
+                "c".repeat(32),
+                "d".repeat(128)
+            )
+        })
+        .collect::<Vec<_>>();
+
+    let token = DashboardUpdatesToken {
+        feed: compact_dashboard_signatures(&feed),
+        briefs: compact_dashboard_signatures(&briefs),
+        notifications: compact_dashboard_signatures(&notifications),
+    };
+    let encoded = encode_dashboard_updates_token(&token).expect("encode compact token");
+
+    assert!(
+        encoded.len() < 4096,
+        "compact token should leave room for path and query overhead, got {} bytes",
+        encoded.len()
+    );
+}
+
+async fn extract_user<T>(state: &Arc<AppState>, session: Session) -> Result<T, ApiError>
+where
+    T: axum::extract::FromRequestParts<Arc<AppState>, Rejection = ApiError>,
+{
+    let (mut parts, ()) = axum::http::Request::builder()
+        .extension(session)
+        .body(())
+        .expect("build request")
+        .into_parts();
+    T::from_request_parts(&mut parts, state).await
+}
+
+#[tokio::test]
+async fn user_extractors_reject_anonymous_and_non_admin_sessions() {
+    let pool = setup_pool().await;
+    let state = setup_state(pool);
+    let anonymous = Session::new(None, Arc::new(MemoryStore::default()), None);
+
+    let err = extract_user::<AuthedUser>(&state, anonymous.clone())
+        .await
+        .err()
+        .expect("anonymous session is rejected");
+    assert_eq!(err.code(), "unauthorized");
+    let err = extract_user::<AdminUser>(&state, anonymous)
+        .await
+        .err()
+        .expect("anonymous session is not an admin");
+    assert_eq!(err.code(), "unauthorized");
+
+    let AuthedUser(user_id) = extract_user(&state, setup_session(1).await)
+        .await
+        .unwrap_or_else(|_| panic!("member session resolves"));
+    assert_eq!(user_id, test_user_id(1));
+    let err = extract_user::<AdminUser>(&state, setup_session(1).await)
+        .await
+        .err()
+        .expect("member is not an admin");
+    assert_eq!(err.code(), "forbidden_admin_only");
+}
+
+#[tokio::test]
+async fn dashboard_updates_reports_changed_lists_after_token() {
+    let pool = setup_pool().await;
+    let user_id = test_user_id(1);
+    seed_repo_release(&pool, 42, 120).await;
+    seed_star(&pool, 42).await;
+    seed_brief(&pool, user_id.as_str(), "2026-02-23", "## 初始日报").await;
+    seed_notification(
+        &pool,
+        user_id.as_str(),
+        "thread-initial",
+        "2026-02-23T08:00:00Z",
+    )
+    .await;
+    let state = setup_state(pool.clone());
+
+    let Json(initial) = dashboard_updates(
+        State(state.clone()),
+        AuthedUser(test_user_id(1)),
+        Query(DashboardUpdatesQuery {
+            token: None,
+            feed_type: Some("all".to_owned()),
+            include: Some("feed,briefs,notifications".to_owned()),
+            scope: None,
+            items: None,
+            org: None,
+        }),
+    )
+    .await
+    .expect("initial dashboard updates");
+    assert!(!initial.lists.feed.as_ref().expect("feed list").changed);
+
+    seed_repo_release(&pool, 42, 121).await;
+    sqlx::query(
+        r#"
+            UPDATE repo_releases
+            SET published_at = '2026-02-24T08:00:00Z',
+                created_at = '2026-02-24T08:00:00Z',
+                updated_at = '2026-02-24T08:00:00Z',
+                tag_name = 'v1.2.4',
+                name = 'Release v1.2.4',
+                html_url = 'https://github.com/openai/codex/releases/tag/v1.2.4'
+            WHERE release_id = 121
+            "#,
+    )
+    .execute(&pool)
+    .await
+    .expect("retime new release");
+    seed_brief(&pool, user_id.as_str(), "2026-02-24", "## 新日报").await;
+    seed_notification(
+        &pool,
+        user_id.as_str(),
+        "thread-new",
+        "2026-02-24T08:00:00Z",
+    )
+    .await;
+
+    let Json(changed) = dashboard_updates(
+        State(state),
+        AuthedUser(test_user_id(1)),
+        Query(DashboardUpdatesQuery {
+            token: Some(initial.token),
+            feed_type: Some("all".to_owned()),
+            include: Some("feed,briefs,notifications".to_owned()),
+            scope: None,
+            items: None,
+            org: None,
+        }),
+    )
+    .await
+    .expect("changed dashboard updates");
+
+    let feed = changed.lists.feed.as_ref().expect("feed update");
+    assert!(feed.changed);
+    assert_eq!(feed.new_count, 1);
+    assert_eq!(
+        feed.latest_keys.first().map(String::as_str),
+        Some("release:121")
+    );
+
+    let briefs = changed.lists.briefs.as_ref().expect("brief update");
+    assert!(briefs.changed);
+    assert_eq!(briefs.new_count, 1);
+    assert_eq!(
+        briefs.latest_keys.first().map(String::as_str),
+        Some("brief:brief-2026-02-24")
+    );
+
+    let notifications = changed
+        .lists
+        .notifications
+        .as_ref()
+        .expect("notification update");
+    assert!(notifications.changed);
+    assert_eq!(notifications.new_count, 1);
+    assert_eq!(
+        notifications.latest_keys.first().map(String::as_str),
+        Some("notification:thread-new")
+    );
+}
+
+#[tokio::test]
+async fn dashboard_updates_reports_existing_feed_item_processing_change() {
+    let pool = setup_pool().await;
+    let user_id = test_user_id(1);
+    seed_repo_release(&pool, 42, 120).await;
+    seed_repo_release(&pool, 42, 121).await;
+    sqlx::query(
+        r#"
+            UPDATE repo_releases
+            SET published_at = '2026-02-24T08:00:00Z',
+                created_at = '2026-02-24T08:00:00Z',
+                updated_at = '2026-02-24T08:00:00Z',
+                tag_name = 'v1.2.4',
+                name = 'Release v1.2.4',
+                html_url = 'https://github.com/openai/codex/releases/tag/v1.2.4'
+            WHERE release_id = 121
+            "#,
+    )
+    .execute(&pool)
+    .await
+    .expect("retime leading release");
+    seed_star(&pool, 42).await;
+    let state = setup_state(pool.clone());
+
+    let Json(initial) = dashboard_updates(
+        State(state.clone()),
+        AuthedUser(test_user_id(1)),
+        Query(DashboardUpdatesQuery {
+            token: None,
+            feed_type: Some("releases".to_owned()),
+            include: Some("feed".to_owned()),
+            scope: None,
+            items: None,
+            org: None,
+        }),
+    )
+    .await
+    .expect("initial dashboard updates");
+
+    seed_release_translation(
+        &pool,
+        user_id.as_str(),
+        "120",
+        "release-120-hash",
+        Some("Release 120 中文标题"),
+        Some("Release 120 中文摘要"),
+    )
+    .await;
+
+    let Json(changed) = dashboard_updates(
+        State(state),
+        AuthedUser(test_user_id(1)),
+        Query(DashboardUpdatesQuery {
+            token: Some(initial.token),
+            feed_type: Some("releases".to_owned()),
+            include: Some("feed".to_owned()),
+            scope: None,
+            items: None,
+            org: None,
+        }),
+    )
+    .await
+    .expect("processed dashboard updates");
+
+    let feed = changed.lists.feed.as_ref().expect("feed update");
+    assert!(feed.changed);
+    assert_eq!(feed.new_count, 1);
+    assert_eq!(
+        feed.latest_keys.first().map(String::as_str),
+        Some("release:120")
+    );
+}
+
+#[tokio::test]
+async fn dashboard_updates_handles_notifications_without_updated_at() {
+    let pool = setup_pool().await;
+    let user_id = test_user_id(1);
+    seed_notification_with_timestamp(&pool, user_id.as_str(), "thread-without-updated-at", None)
+        .await;
+    let state = setup_state(pool);
+
+    let Json(initial) = dashboard_updates(
+        State(state),
+        AuthedUser(test_user_id(1)),
+        Query(DashboardUpdatesQuery {
+            token: None,
+            feed_type: Some("all".to_owned()),
+            include: Some("notifications".to_owned()),
+            scope: None,
+            items: None,
+            org: None,
+        }),
+    )
+    .await
+    .expect("dashboard updates with nullable notification timestamp");
+
+    let notifications = initial
+        .lists
+        .notifications
+        .as_ref()
+        .expect("notification update");
+    assert!(!notifications.changed);
+    assert_eq!(
+        notifications.latest_keys.first().map(String::as_str),
+        Some("notification:thread-without-updated-at")
+    );
+}
+
+#[tokio::test]
+async fn dashboard_updates_respects_feed_type_filter() {
+    let pool = setup_pool().await;
+    let user_id = test_user_id(1);
+    seed_repo_release(&pool, 42, 120).await;
+    seed_star(&pool, 42).await;
+    seed_social_event(
+        &pool,
+        user_id.as_str(),
+        SeedSocialEventArgs {
+            kind: "repo_star_received",
+            event_id: "social-star-update",
+            repo_id: Some(500),
+            repo_full_name: Some("IvanLi-CN/octo-rill"),
+            repo_owner_avatar_url: None,
+            repo_open_graph_image_url: None,
+            repo_uses_custom_open_graph_image: None,
+            title: None,
+            body: None,
+            html_url: Some("https://github.com/IvanLi-CN/octo-rill/stargazers"),
+            actor_login: "octocat",
+            occurred_at: "2026-02-24T08:00:00Z",
+        },
+    )
+    .await;
+    let state = setup_state(pool);
+
+    let Json(stars) = dashboard_updates(
+        State(state),
+        AuthedUser(test_user_id(1)),
+        Query(DashboardUpdatesQuery {
+            token: None,
+            feed_type: Some("stars".to_owned()),
+            include: Some("feed".to_owned()),
+            scope: None,
+            items: None,
+            org: None,
+        }),
+    )
+    .await
+    .expect("stars dashboard updates");
+
+    let feed = stars.lists.feed.expect("feed update");
+    assert_eq!(
+        feed.latest_keys,
+        vec!["repo_star_received:social-star-update"]
+    );
+    assert!(stars.lists.briefs.is_none());
+    assert!(stars.lists.notifications.is_none());
+}
+
+#[tokio::test]
+async fn refresh_feed_reactions_without_pat_is_non_blocking_empty_result() {
+    let pool = setup_pool().await;
+    seed_repo_release(&pool, 42, 120).await;
+    seed_star(&pool, 42).await;
+    let state = setup_state(pool);
+
+    let Json(resp) = refresh_feed_reactions(
+        State(state),
+        AuthedUser(test_user_id(1)),
+        setup_session(1).await,
+        Query(FeedReactionRefreshQuery::default()),
+        ApiJson(FeedReactionRefreshRequest {
+            release_ids: vec!["120".to_owned()],
+        }),
+    )
+    .await
+    .expect("refresh feed reactions");
+
+    assert!(resp.items.is_empty());
+}
+
+#[tokio::test]
+async fn refresh_feed_reactions_serves_fresh_viewer_cache_without_graphql() {
+    let pool = setup_pool().await;
+    seed_repo_release(&pool, 42, 120).await;
+    seed_star(&pool, 42).await;
+
+    let graphql_calls = Arc::new(AtomicUsize::new(0));
+    let route_calls = Arc::clone(&graphql_calls);
+    let graphql_url = spawn_test_ai_server(Router::new().route(
+        "/graphql",
+        post(move || {
+            let route_calls = Arc::clone(&route_calls);
+            async move {
+                route_calls.fetch_add(1, Ordering::SeqCst);
+                Json(serde_json::json!({
+                    "data": {
+                        "nodes": [{
+                            "id": "node-120",
+                            "reactionGroups": [{
+                                "content": "HEART",
+                                "viewerHasReacted": true,
+                                "reactors": { "totalCount": 4 }
+                            }]
+                        }]
+                    }
+                }))
+            }
+        }),
+    ))
+    .await
+    .join("graphql")
+    .expect("graphql url");
+    let mut inner = (*setup_state(pool.clone())).clone();
+    inner.github_graphql_url = graphql_url;
+    let state = Arc::new(inner);
+
+    let encrypted = state
+        .encryption_key
+        .encrypt_str("ghp_test_token")
+        .expect("encrypt pat");
+    sqlx::query(
+        r#"
+            INSERT INTO reaction_pat_tokens (
+              user_id, token_ciphertext, token_nonce, masked_token, last_check_state, updated_at
+            )
+            VALUES (?, ?, ?, 'ghp_****', 'valid', ?)
+            "#,
+    )
+    .bind(test_user_id(1))
+    .bind(encrypted.ciphertext)
+    .bind(encrypted.nonce)
+    .bind(chrono::Utc::now().to_rfc3339())
+    .execute(&pool)
+    .await
+    .expect("seed reaction pat");
+
+    let refresh = |refresh_reactions: Option<bool>| {
+        let state = Arc::clone(&state);
+        async move {
+            let Json(resp) = refresh_feed_reactions(
+                State(state),
+                AuthedUser(test_user_id(1)),
+                setup_session(1).await,
+                Query(FeedReactionRefreshQuery { refresh_reactions }),
+                ApiJson(FeedReactionRefreshRequest {
+                    release_ids: vec!["120".to_owned()],
+                }),
+            )
+            .await
+            .expect("refresh feed reactions");
+            resp
+        }
+    };
+
+    let first = refresh(None).await;
+    assert_eq!(graphql_calls.load(Ordering::SeqCst), 1);
+    assert_eq!(first.items.len(), 1);
+    assert!(first.items[0].reactions.viewer.heart);
+
+    let second = refresh(None).await;
+    assert_eq!(graphql_calls.load(Ordering::SeqCst), 1);
+    assert_eq!(second.items.len(), 1);
+    assert!(second.items[0].reactions.viewer.heart);
+    assert_eq!(second.items[0].reactions.counts.heart, 4);
+
+    refresh(Some(true)).await;
+    assert_eq!(graphql_calls.load(Ordering::SeqCst), 2);
+
+    sqlx::query("UPDATE release_reaction_viewer SET fetched_at = ?")
+        .bind((chrono::Utc::now() - chrono::Duration::hours(1)).to_rfc3339())
+        .execute(&pool)
+        .await
+        .expect("age viewer cache");
+    let cached = load_cached_release_reactions(state.as_ref(), &test_user_id(1), &[120])
+        .await
+        .expect("load cached reactions");
+    assert!(!cached[&120].is_fresh);
+    refresh(None).await;
+    assert_eq!(graphql_calls.load(Ordering::SeqCst), 3);
+}
+
+async fn setup_feed_reaction_state_with_graphql(
+    pool: &SqlitePool,
+    graphql: Router,
+) -> Arc<AppState> {
+    let graphql_url = spawn_test_ai_server(graphql)
+        .await
+        .join("graphql")
+        .expect("graphql url");
+    let mut inner = (*setup_state(pool.clone())).clone();
+    inner.github_graphql_url = graphql_url;
+    let state = Arc::new(inner);
+
+    let encrypted = state
+        .encryption_key
+        .encrypt_str("ghp_test_token")
+        .expect("encrypt pat");
+    sqlx::query(
+        r#"
+            INSERT INTO reaction_pat_tokens (
+              user_id, token_ciphertext, token_nonce, masked_token, last_check_state, updated_at
+            )
+            VALUES (?, ?, ?, 'ghp_****', 'valid', ?)
+            "#,
+    )
+    .bind(test_user_id(1))
+    .bind(encrypted.ciphertext)
+    .bind(encrypted.nonce)
+    .bind(chrono::Utc::now().to_rfc3339())
+    .execute(pool)
+    .await
+    .expect("seed reaction pat");
+    state
+}
+
+async fn refresh_feed_reactions_for_release_120(
+    state: Arc<AppState>,
+) -> FeedReactionRefreshResponse {
+    let Json(resp) = refresh_feed_reactions(
+        State(state),
+        AuthedUser(test_user_id(1)),
+        setup_session(1).await,
+        Query(FeedReactionRefreshQuery::default()),
+        ApiJson(FeedReactionRefreshRequest {
+            release_ids: vec!["120".to_owned()],
+        }),
+    )
+    .await
+    .expect("refresh feed reactions");
+    resp
+}
+
+#[tokio::test]
+async fn refresh_feed_reactions_rate_limited_returns_stale_counts_with_warning() {
+    let pool = setup_pool().await;
+    seed_repo_release(&pool, 42, 120).await;
+    seed_star(&pool, 42).await;
+    sqlx::query("UPDATE repo_releases SET react_heart = 3, react_rocket = 1")
+        .execute(&pool)
+        .await
+        .expect("seed stored reaction counts");
+    let state = setup_feed_reaction_state_with_graphql(
+        &pool,
+        Router::new().route(
+            "/graphql",
+            post(|| async {
+                (
+                    StatusCode::FORBIDDEN,
+                    [("x-ratelimit-remaining", "0")],
+                    "API rate limit exceeded",
+                )
+            }),
+        ),
+    )
+    .await;
+
+    let resp = refresh_feed_reactions_for_release_120(state).await;
+
+    assert_eq!(
+        resp.warnings,
+        vec![FeedWarning {
+            code: "rate_limited".to_owned(),
+            message: "GitHub rate limit reached; reaction counts may be out of date".to_owned(),
+        }]
+    );
+    assert_eq!(resp.items.len(), 1);
+    assert_eq!(resp.items[0].release_id, "120");
+    assert_eq!(resp.items[0].reactions.status, "stale");
+    assert_eq!(resp.items[0].reactions.counts.heart, 3);
+    assert_eq!(resp.items[0].reactions.counts.rocket, 1);
+    let check_state = sqlx::query_scalar::<_, String>(
+        "SELECT last_check_state FROM reaction_pat_tokens WHERE user_id = ?",
+    )
+    .bind(test_user_id(1))
+    .fetch_one(&pool)
+    .await
+    .expect("load pat check state");
+    assert_eq!(
+        check_state, "valid",
+        "rate limits do not mark the PAT broken"
+    );
+}
+
+#[tokio::test]
+async fn refresh_feed_reactions_invalid_pat_warns_and_persists_check_result() {
+    let pool = setup_pool().await;
+    seed_repo_release(&pool, 42, 120).await;
+    seed_star(&pool, 42).await;
+    let state = setup_feed_reaction_state_with_graphql(
+        &pool,
+        Router::new().route(
+            "/graphql",
+            post(|| async { (StatusCode::UNAUTHORIZED, "Bad credentials") }),
+        ),
+    )
+    .await;
+
+    let resp = refresh_feed_reactions_for_release_120(state).await;
+
+    assert_eq!(
+        resp.warnings
+            .iter()
+            .map(|warning| warning.code.as_str())
+            .collect::<Vec<_>>(),
+        vec!["pat_invalid"]
+    );
+    assert_eq!(resp.items[0].reactions.status, "stale");
+    let check_state = sqlx::query_scalar::<_, String>(
+        "SELECT last_check_state FROM reaction_pat_tokens WHERE user_id = ?",
+    )
+    .bind(test_user_id(1))
+    .fetch_one(&pool)
+    .await
+    .expect("load pat check state");
+    assert_eq!(check_state, "invalid");
+}
+
+async fn diagnose_reaction_token_with_graphql(
+    pool: &SqlitePool,
+    graphql: Router,
+    repo_full_name: Option<&str>,
+) -> Result<ReactionTokenDiagnoseResponse, ApiError> {
+    let state = setup_feed_reaction_state_with_graphql(pool, graphql).await;
+    diagnose_reaction_token(
+        State(state),
+        AuthedUser(test_user_id(1)),
+        ApiJson(ReactionTokenDiagnoseRequest {
+            repo_full_name: repo_full_name.map(str::to_owned),
+        }),
+    )
+    .await
+    .map(|Json(resp)| resp)
+}
+
+async fn load_reaction_pat_check(pool: &SqlitePool) -> (String, Option<String>) {
+    sqlx::query_as::<_, (String, Option<String>)>(
+        "SELECT last_check_state, last_check_message FROM reaction_pat_tokens WHERE user_id = ?",
+    )
+    .bind(test_user_id(1))
+    .fetch_one(pool)
+    .await
+    .expect("load pat check state")
+}
+
+#[tokio::test]
+async fn diagnose_reaction_token_samples_private_stars_when_repo_is_visible() {
+    let pool = setup_pool().await;
+    seed_star_with_privacy(&pool, 42, true).await;
+    let seen_vars = Arc::new(std::sync::Mutex::new(Vec::new()));
+    let route_vars = Arc::clone(&seen_vars);
+
+    let resp = diagnose_reaction_token_with_graphql(
+        &pool,
+        Router::new().route(
+            "/graphql",
+            post(move |Json(body): Json<serde_json::Value>| {
+                let route_vars = Arc::clone(&route_vars);
+                async move {
+                    route_vars
+                        .lock()
+                        .expect("graphql vars")
+                        .push(body["variables"].clone());
+                    Json(serde_json::json!({
+                        "data": {
+                            "repository": {
+                                "viewerPermission": "READ",
+                                "releases": {
+                                    "nodes": [{
+                                        "viewerCanReact": true,
+                                        "reactionGroups": [{ "content": "HEART" }]
+                                    }]
+                                }
+                            }
+                        }
+                    }))
+                }
+            }),
+        ),
+        None,
+    )
+    .await
+    .expect("diagnose reaction token");
+
+    assert!(resp.token_valid);
+    assert_eq!(resp.items.len(), 1);
+    let item = &resp.items[0];
+    assert_eq!(item.repo_full_name, "openai/codex");
+    assert!(item.repo_visible);
+    assert!(item.can_react);
+    assert_eq!(item.viewer_permission.as_deref(), Some("READ"));
+    assert_eq!(item.suggested_fix, None);
+    assert_eq!(
+        *seen_vars.lock().expect("graphql vars"),
+        vec![serde_json::json!({ "owner": "openai", "name": "codex" })]
+    );
+    assert_eq!(load_reaction_pat_check(&pool).await.0, "valid");
+}
+
+#[tokio::test]
+async fn diagnose_reaction_token_reports_repo_hidden_from_pat() {
+    let pool = setup_pool().await;
+
+    let resp = diagnose_reaction_token_with_graphql(
+        &pool,
+        Router::new().route(
+            "/graphql",
+            post(|| async {
+                Json(serde_json::json!({
+                    "data": { "repository": null },
+                    "errors": [{
+                        "type": "NOT_FOUND",
+                        "path": ["repository"],
+                        "message": "Could not resolve to a Repository with the name 'acme/secret'."
+                    }]
+                }))
+            }),
+        ),
+        Some(" acme/secret "),
+    )
+    .await
+    .expect("diagnose reaction token");
+
+    assert!(resp.token_valid);
+    assert_eq!(resp.items.len(), 1);
+    let item = &resp.items[0];
+    assert_eq!(item.repo_full_name, "acme/secret");
+    assert!(!item.repo_visible);
+    assert!(!item.can_react);
+    assert!(
+        item.suggested_fix
+            .as_deref()
+            .is_some_and(|fix| fix.contains("repository access"))
+    );
+    assert_eq!(load_reaction_pat_check(&pool).await.0, "valid");
+}
+
+#[tokio::test]
+async fn diagnose_reaction_token_reports_org_restrictions_as_forbidden() {
+    let pool = setup_pool().await;
+
+    let resp = diagnose_reaction_token_with_graphql(
+        &pool,
+        Router::new().route(
+            "/graphql",
+            post(|| async {
+                (
+                    StatusCode::FORBIDDEN,
+                    "Resource protected by organization SAML enforcement",
+                )
+            }),
+        ),
+        Some("acme/secret"),
+    )
+    .await
+    .expect("diagnose reaction token");
+
+    assert!(resp.token_valid);
+    let item = &resp.items[0];
+    assert!(!item.repo_visible);
+    assert!(!item.can_react);
+    assert!(
+        item.suggested_fix
+            .as_deref()
+            .is_some_and(|fix| fix.contains("SAML SSO"))
+    );
+    assert_eq!(load_reaction_pat_check(&pool).await.0, "valid");
+}
+
+#[tokio::test]
+async fn diagnose_reaction_token_marks_rejected_pat_invalid() {
+    let pool = setup_pool().await;
+
+    let resp = diagnose_reaction_token_with_graphql(
+        &pool,
+        Router::new().route(
+            "/graphql",
+            post(|| async { (StatusCode::UNAUTHORIZED, "Bad credentials") }),
+        ),
+        Some("acme/secret"),
+    )
+    .await
+    .expect("diagnose reaction token");
+
+    assert!(!resp.token_valid);
+    assert!(!resp.items[0].repo_visible);
+    assert_eq!(
+        load_reaction_pat_check(&pool).await,
+        (
+            "invalid".to_owned(),
+            Some("PAT is invalid or expired".to_owned())
+        )
+    );
+}
+
+#[tokio::test]
+async fn diagnose_reaction_token_rejects_malformed_repo_name() {
+    let pool = setup_pool().await;
+    let err = diagnose_reaction_token_with_graphql(
+        &pool,
+        Router::new().route(
+            "/graphql",
+            post(|| async { StatusCode::INTERNAL_SERVER_ERROR }),
+        ),
+        Some("no-slash"),
+    )
+    .await
+    .expect_err("malformed repo name");
+    assert_eq!(err.code(), "bad_request");
+}
+
+#[tokio::test]
+async fn refresh_feed_reactions_success_has_no_warnings() {
+    let pool = setup_pool().await;
+    seed_repo_release(&pool, 42, 120).await;
+    seed_star(&pool, 42).await;
+    let state = setup_feed_reaction_state_with_graphql(
+        &pool,
+        Router::new().route(
+            "/graphql",
+            post(|| async {
+                Json(serde_json::json!({
+                    "data": {
+                        "nodes": [{
+                            "id": "node-120",
+                            "reactionGroups": [{
+                                "content": "HEART",
+                                "viewerHasReacted": false,
+                                "reactors": { "totalCount": 2 }
+                            }]
+                        }]
+                    }
+                }))
+            }),
+        ),
+    )
+    .await;
+
+    let resp = refresh_feed_reactions_for_release_120(state).await;
+
+    assert!(resp.warnings.is_empty());
+    assert_eq!(resp.items.len(), 1);
+    assert_eq!(resp.items[0].reactions.status, "ready");
+    assert_eq!(resp.items[0].reactions.counts.heart, 2);
+    assert!(
+        serde_json::to_value(&resp)
+            .expect("serialize response")
+            .get("warnings")
+            .is_none()
+    );
+}
+
+/// Mock GitHub for the reaction token ladder: `/user` answers with the
+/// given `x-oauth-scopes`, `/graphql` records the bearer token it saw.
+async fn setup_reaction_token_ladder_state(
+    pool: &SqlitePool,
+    oauth_scopes: &'static str,
+    graphql_status: StatusCode,
+    with_pat: bool,
+) -> (
+    Arc<AppState>,
+    Arc<AtomicUsize>,
+    Arc<tokio::sync::Mutex<Vec<String>>>,
+) {
+    let user_calls = Arc::new(AtomicUsize::new(0));
+    let graphql_tokens = Arc::new(tokio::sync::Mutex::new(Vec::<String>::new()));
+    let route_user_calls = Arc::clone(&user_calls);
+    let route_graphql_tokens = Arc::clone(&graphql_tokens);
+    let base_url = spawn_test_ai_server(
+        Router::new()
+            .route(
+                "/user",
+                get(move || {
+                    let user_calls = Arc::clone(&route_user_calls);
+                    async move {
+                        user_calls.fetch_add(1, Ordering::SeqCst);
+                        (
+                            [("x-oauth-scopes", oauth_scopes)],
+                            Json(serde_json::json!({ "id": 10, "login": "octo" })),
+                        )
+                    }
+                }),
+            )
+            .route(
+                "/graphql",
+                post(move |headers: axum::http::HeaderMap| {
+                    let graphql_tokens = Arc::clone(&route_graphql_tokens);
+                    async move {
+                        let token = headers
+                            .get(axum::http::header::AUTHORIZATION)
+                            .and_then(|value| value.to_str().ok())
+                            .unwrap_or_default()
+                            .trim_start_matches("Bearer ")
+                            .to_owned();
+                        graphql_tokens.lock().await.push(token);
+                        (
+                            graphql_status,
+                            Json(serde_json::json!({
+                                "data": {
+                                    "nodes": [{
+                                        "id": "node-120",
+                                        "reactionGroups": [{
+                                            "content": "HEART",
+                                            "viewerHasReacted": false,
+                                            "reactors": { "totalCount": 2 }
+                                        }]
+                                    }]
+                                }
+                            })),
+                        )
+                    }
+                }),
+            ),
+    )
+    .await;
+
+    let mut inner = (*setup_state(pool.clone())).clone();
+    inner.github_graphql_url = base_url.join("graphql").expect("graphql url");
+    inner.github_rest_api_base = base_url;
+    let state = Arc::new(inner);
+
+    let oauth = state
+        .encryption_key
+        .encrypt_str("gho_oauth_token")
+        .expect("encrypt oauth token");
+    sqlx::query(r#"DELETE FROM github_connections WHERE user_id = ?"#)
+        .bind(test_user_id(1))
+        .execute(pool)
+        .await
+        .expect("clear placeholder github connections");
+    sqlx::query(
+        r#"
+            INSERT INTO github_connections (
+              id, user_id, github_user_id, login, access_token_ciphertext, access_token_nonce,
+              scopes, linked_at, updated_at
+            )
+            VALUES (?, ?, 10, 'octo', ?, ?, ?, '2026-02-23T00:00:00Z', '2026-02-23T00:00:00Z')
+            "#,
+    )
+    .bind(crate::local_id::generate_local_id())
+    .bind(test_user_id(1))
+    .bind(oauth.ciphertext)
+    .bind(oauth.nonce)
+    .bind(oauth_scopes)
+    .execute(pool)
+    .await
+    .expect("seed github connection");
+
+    if with_pat {
+        let pat = state
+            .encryption_key
+            .encrypt_str("ghp_test_token")
+            .expect("encrypt pat");
+        sqlx::query(
+            r#"
+                INSERT INTO reaction_pat_tokens (
+                  user_id, token_ciphertext, token_nonce, masked_token, last_check_state, updated_at
+                )
+                VALUES (?, ?, ?, 'ghp_****', 'valid', ?)
+                "#,
+        )
+        .bind(test_user_id(1))
+        .bind(pat.ciphertext)
+        .bind(pat.nonce)
+        .bind(chrono::Utc::now().to_rfc3339())
+        .execute(pool)
+        .await
+        .expect("seed reaction pat");
+    }
+
+    (state, user_calls, graphql_tokens)
+}
+
+#[tokio::test]
+async fn select_reaction_token_prefers_scoped_oauth_and_caches_scope_check() {
+    let pool = setup_pool().await;
+    let (state, user_calls, _) =
+        setup_reaction_token_ladder_state(&pool, "read:user, public_repo", StatusCode::OK, true)
+            .await;
+    let session = setup_session(1).await;
+
+    for _ in 0..2 {
+        let selection = select_reaction_token(state.as_ref(), &session, &test_user_id(1))
+            .await
+            .expect("select reaction token");
+        assert!(matches!(
+            selection,
+            ReactionTokenSelection::Ready(ReactionToken::OAuth(ref token))
+                if token == "gho_oauth_token"
+        ));
+    }
+    assert_eq!(
+        user_calls.load(Ordering::SeqCst),
+        1,
+        "scope check is cached per session"
+    );
+}
+
+#[tokio::test]
+async fn select_reaction_token_falls_back_to_pat_without_oauth_scope() {
+    let pool = setup_pool().await;
+    let (state, _, _) =
+        setup_reaction_token_ladder_state(&pool, "read:user", StatusCode::OK, true).await;
+
+    let selection =
+        select_reaction_token(state.as_ref(), &setup_session(1).await, &test_user_id(1))
+            .await
+            .expect("select reaction token");
+
+    assert!(matches!(
+        selection,
+        ReactionTokenSelection::Ready(ReactionToken::Pat(ref token))
+            if token == "ghp_test_token"
+    ));
+}
+
+#[tokio::test]
+async fn toggle_release_reaction_requires_pat_only_when_oauth_scope_is_missing() {
+    let pool = setup_pool().await;
+    seed_repo_release(&pool, 42, 120).await;
+    seed_star(&pool, 42).await;
+    let (state, _, graphql_tokens) =
+        setup_reaction_token_ladder_state(&pool, "read:user", StatusCode::OK, false).await;
+
+    let session = setup_session(1).await;
+    let selection = select_reaction_token(state.as_ref(), &session, &test_user_id(1))
+        .await
+        .expect("select reaction token");
+    assert!(matches!(
+        selection,
+        ReactionTokenSelection::Missing {
+            oauth_lacks_scope: true
+        }
+    ));
+
+    let err = toggle_release_reaction(
+        State(Arc::clone(&state)),
+        AuthedUser(test_user_id(1)),
+        session,
+        ApiJson(ToggleReleaseReactionRequest {
+            release_id: "120".to_owned(),
+            content: "heart".to_owned(),
+            currently_reacted: None,
+        }),
+    )
+    .await
+    .expect_err("toggle without usable token");
+    assert_eq!(err.code(), "pat_required");
+    assert!(graphql_tokens.lock().await.is_empty());
+
+    let Json(status) = reaction_token_status(
+        State(state),
+        AuthedUser(test_user_id(1)),
+        setup_session(1).await,
+    )
+    .await
+    .expect("reaction token status");
+    assert!(!status.oauth_ready);
+}
+
+#[tokio::test]
+async fn toggle_release_reaction_with_client_state_falls_back_on_conflict() {
+    let pool = setup_pool().await;
+    seed_repo_release(&pool, 42, 120).await;
+    seed_star(&pool, 42).await;
+    let (state, _, _) =
+        setup_reaction_token_ladder_state(&pool, "repo", StatusCode::OK, false).await;
+
+    let mutations = Arc::new(tokio::sync::Mutex::new(Vec::<String>::new()));
+    let route_mutations = Arc::clone(&mutations);
+    let graphql_url = spawn_test_ai_server(Router::new().route(
+        "/graphql",
+        post(move |Json(body): Json<serde_json::Value>| {
+            let mutations = Arc::clone(&route_mutations);
+            async move {
+                let query = body["query"].as_str().unwrap_or_default();
+                if query.contains("reactionGroups") && !query.contains("mutation") {
+                    mutations.lock().await.push("read".to_owned());
+                    return Json(serde_json::json!({ "data": { "nodes": [] } }));
+                }
+                if query.contains("removeReaction") {
+                    mutations.lock().await.push("removeReaction".to_owned());
+                    return Json(serde_json::json!({
+                        "data": { "removeReaction": null },
+                        "errors": [{ "message": "Reaction does not exist" }]
+                    }));
+                }
+                mutations.lock().await.push("addReaction".to_owned());
+                Json(serde_json::json!({
+                    "data": {
+                        "addReaction": {
+                            "subject": {
+                                "id": "node-120",
+                                "reactionGroups": [{
+                                    "content": "HEART",
+                                    "viewerHasReacted": true,
+                                    "reactors": { "totalCount": 3 }
+                                }]
+                            }
+                        }
+                    }
+                }))
+            }
+        }),
+    ))
+    .await
+    .join("graphql")
+    .expect("graphql url");
+    let mut inner = (*state).clone();
+    inner.github_graphql_url = graphql_url;
+    let state = Arc::new(inner);
+
+    let Json(resp) = toggle_release_reaction(
+        State(Arc::clone(&state)),
+        AuthedUser(test_user_id(1)),
+        setup_session(1).await,
+        ApiJson(ToggleReleaseReactionRequest {
+            release_id: "120".to_owned(),
+            content: "heart".to_owned(),
+            currently_reacted: Some(true),
+        }),
+    )
+    .await
+    .expect("toggle with stale client state");
+
+    assert_eq!(
+        *mutations.lock().await,
+        vec!["removeReaction".to_owned(), "addReaction".to_owned()]
+    );
+    assert_eq!(resp.release_id, "120");
+    assert_eq!(resp.reactions.counts.heart, 3);
+    assert!(resp.reactions.viewer.heart);
+    assert_eq!(resp.reactions.status, "ready");
+}
+
+#[tokio::test]
+async fn refresh_feed_reactions_uses_oauth_token_without_pat() {
+    let pool = setup_pool().await;
+    seed_repo_release(&pool, 42, 120).await;
+    seed_star(&pool, 42).await;
+    let (state, _, graphql_tokens) =
+        setup_reaction_token_ladder_state(&pool, "repo", StatusCode::OK, false).await;
+
+    let resp = refresh_feed_reactions_for_release_120(Arc::clone(&state)).await;
+
+    assert!(resp.warnings.is_empty());
+    assert_eq!(resp.items.len(), 1);
+    assert_eq!(resp.items[0].reactions.counts.heart, 2);
+    assert_eq!(*graphql_tokens.lock().await, vec!["gho_oauth_token"]);
+
+    let Json(status) = reaction_token_status(
+        State(state),
+        AuthedUser(test_user_id(1)),
+        setup_session(1).await,
+    )
+    .await
+    .expect("reaction token status");
+    assert!(status.oauth_ready);
+    assert!(!status.configured);
+}
+
+#[tokio::test]
+async fn refresh_feed_reactions_oauth_auth_failure_asks_for_reauth_not_pat() {
+    let pool = setup_pool().await;
+    seed_repo_release(&pool, 42, 120).await;
+    seed_star(&pool, 42).await;
+    let (state, _, _) =
+        setup_reaction_token_ladder_state(&pool, "public_repo", StatusCode::UNAUTHORIZED, true)
+            .await;
+
+    let resp = refresh_feed_reactions_for_release_120(state).await;
+
+    assert_eq!(
+        resp.warnings
+            .iter()
+            .map(|warning| warning.code.as_str())
+            .collect::<Vec<_>>(),
+        vec!["reauth_required"]
+    );
+    let check_state = sqlx::query_scalar::<_, String>(
+        "SELECT last_check_state FROM reaction_pat_tokens WHERE user_id = ?",
+    )
+    .bind(test_user_id(1))
+    .fetch_one(&pool)
+    .await
+    .expect("load pat check state");
+    assert_eq!(check_state, "valid", "OAuth failures leave the PAT alone");
+}
+
+#[tokio::test]
+async fn refresh_feed_reactions_skips_persist_failure_under_sqlite_write_pressure() {
+    let pool = setup_pool().await;
+    seed_repo_release(&pool, 42, 120).await;
+    let state = setup_state(pool.clone());
+    let row = ReleaseReactionRow {
+        release_id: 120,
+        node_id: Some("node-120".to_owned()),
+    };
+    let reaction = LiveReleaseReactions {
+        counts: ReleaseReactionCounts {
+            plus1: 7,
+            laugh: 0,
+            heart: 2,
+            hooray: 1,
+            rocket: 0,
+            eyes: 3,
+        },
+        viewer: ReleaseReactionViewer {
+            plus1: true,
+            laugh: false,
+            heart: false,
+            hooray: false,
+            rocket: false,
+            eyes: true,
+        },
+    };
+
+    let (writer_guard, held_tx) = state
+        .sqlite_writer
+        .begin_immediate(&state.pool, "test_feed_reaction_hold")
+        .await
+        .expect("hold sqlite writer");
+
+    let item = build_feed_reaction_refresh_item(&row, &reaction);
+    let persisted = persist_release_reactions_batch_best_effort(
+        state.as_ref(),
+        test_user_id(1).as_str(),
+        &[(row.release_id, &reaction)],
+    )
+    .await
+    .expect("persist refresh batch under write pressure");
+
+    held_tx.commit().await.expect("commit held tx");
+    drop(writer_guard);
+
+    assert!(!persisted);
+    assert_eq!(item.release_id, "120");
+    assert_eq!(item.reactions.counts.plus1, 7);
+    assert!(item.reactions.viewer.plus1);
+
+    let stored_plus1: i64 =
+        sqlx::query_scalar("SELECT react_plus1 FROM repo_releases WHERE release_id = ?")
+            .bind(120_i64)
+            .fetch_one(&pool)
+            .await
+            .expect("load stored reaction count");
+    assert_eq!(stored_plus1, 0);
+}
+
+#[tokio::test]
+async fn persist_release_reactions_batch_writes_every_release_in_one_pass() {
+    let pool = setup_pool().await;
+    seed_repo_release(&pool, 42, 120).await;
+    seed_repo_release(&pool, 42, 121).await;
+    let state = setup_state(pool.clone());
+    let first = LiveReleaseReactions {
+        counts: ReleaseReactionCounts {
+            plus1: 3,
+            ..ReleaseReactionCounts::default()
+        },
+        viewer: ReleaseReactionViewer {
+            plus1: true,
+            ..ReleaseReactionViewer::default()
+        },
+    };
+    let second = LiveReleaseReactions {
+        counts: ReleaseReactionCounts {
+            rocket: 5,
+            ..ReleaseReactionCounts::default()
+        },
+        viewer: ReleaseReactionViewer::default(),
+    };
+
+    let persisted = persist_release_reactions_batch_best_effort(
+        state.as_ref(),
+        test_user_id(1).as_str(),
+        &[(120, &first), (121, &second)],
+    )
+    .await
+    .expect("persist refresh batch");
+    assert!(persisted);
+
+    let stored: Vec<(i64, i64, i64)> = sqlx::query_as(
+        "SELECT release_id, react_plus1, react_rocket FROM repo_releases ORDER BY release_id",
+    )
+    .fetch_all(&pool)
+    .await
+    .expect("load stored reaction counts");
+    assert_eq!(stored, vec![(120, 3, 0), (121, 0, 5)]);
+    let cached_viewers: i64 =
+        sqlx::query_scalar("SELECT COUNT(*) FROM release_reaction_viewer WHERE user_id = ?")
+            .bind(test_user_id(1))
+            .fetch_one(&pool)
+            .await
+            .expect("count cached viewers");
+    assert_eq!(cached_viewers, 2);
+}
+
+#[test]
+fn graphql_node_id_chunks_never_exceed_github_limit() {
+    let node_ids = (0..120)
+        .map(|idx| format!("node-{idx}"))
+        .collect::<Vec<_>>();
+    let chunks = graphql_node_id_chunks(&node_ids);
+    assert_eq!(
+        chunks.iter().map(|chunk| chunk.len()).collect::<Vec<_>>(),
+        vec![50, 50, 20]
+    );
+    assert!(graphql_node_id_chunks(&[]).is_empty());
+}
+
+#[tokio::test]
+async fn list_feed_preserves_repo_visuals_for_historical_social_events_without_baseline() {
+    let pool = setup_pool().await;
+    let user_id = test_user_id(1);
+    seed_social_event(
+        &pool,
+        user_id.as_str(),
+        SeedSocialEventArgs {
+            kind: "repo_star_received",
+            event_id: "social-star-history-1",
+            repo_id: Some(42),
+            repo_full_name: Some("openai/codex"),
+            repo_owner_avatar_url: Some("https://avatars.githubusercontent.com/u/14957082"),
+            repo_open_graph_image_url: Some(
+                "https://repository-images.githubusercontent.com/14957082/codex",
+            ),
+            repo_uses_custom_open_graph_image: Some(true),
+            title: None,
+            body: None,
+            html_url: None,
+            actor_login: "octocat",
+            occurred_at: "2026-02-23T10:00:00Z",
+        },
+    )
+    .await;
+    let state = setup_state(pool);
+
+    let Json(feed) = list_feed_page(
+        State(state),
+        AuthedUser(test_user_id(1)),
+        Query(FeedQuery {
+            cursor: None,
+            limit: Some(30),
+            types: Some("stars".to_owned()),
+            scope: None,
+            items: None,
+            org: None,
+            unseen_only: None,
+            language: None,
+            topic: None,
+            exclude_archived: None,
+            excerpt: None,
+            excerpt_lines: None,
+            excerpt_chars: None,
+            mark_seen: None,
+            fields: None,
+        }),
+    )
+    .await
+    .expect("list stars feed");
+
+    let item = feed.items.first().expect("historical social item");
+    assert_eq!(item.kind, "repo_star_received");
+    let repo_visual = item.repo_visual.as_ref().expect("repo visual");
+    assert_eq!(
+        repo_visual.owner_avatar_url.as_deref(),
+        Some("https://avatars.githubusercontent.com/u/14957082")
+    );
+    assert_eq!(
+        repo_visual.open_graph_image_url.as_deref(),
+        Some("https://repository-images.githubusercontent.com/14957082/codex")
+    );
+    assert!(repo_visual.uses_custom_open_graph_image);
+}
+
+#[tokio::test]
+async fn list_releases_reads_shared_repo_cache_for_starred_user() {
+    let pool = setup_pool().await;
+    seed_repo_release(&pool, 42, 120).await;
+    seed_star(&pool, 42).await;
+    let state = setup_state(pool);
+
+    let Json(items) = list_releases(State(state), AuthedUser(test_user_id(1)))
+        .await
+        .expect("list releases");
+
+    assert_eq!(items.len(), 1);
+    assert_eq!(items[0].full_name, "openai/codex");
+    assert_eq!(items[0].tag_name, "v1.2.3");
+    assert_eq!(items[0].name.as_deref(), Some("Release v1.2.3"));
+}
+
+#[tokio::test]
+async fn list_releases_hides_owned_only_repo_when_opt_in_disabled() {
+    let pool = setup_pool().await;
+    seed_repo_release(&pool, 42, 120).await;
+    seed_owned_repo_baseline(&pool, 42, "IvanLi-CN/octo-rill").await;
+    let state = setup_state(pool);
+
+    let Json(items) = list_releases(State(state), AuthedUser(test_user_id(1)))
+        .await
+        .expect("list releases");
+
+    assert!(items.is_empty());
+}
+
+#[tokio::test]
+async fn list_releases_includes_owned_only_repo_when_opted_in() {
+    let pool = setup_pool().await;
+    seed_repo_release(&pool, 42, 120).await;
+    seed_owned_repo_baseline(&pool, 42, "IvanLi-CN/octo-rill").await;
+    set_include_own_releases(&pool, true).await;
+    let state = setup_state(pool);
+
+    let Json(items) = list_releases(State(state), AuthedUser(test_user_id(1)))
+        .await
+        .expect("list releases");
+
+    assert_eq!(items.len(), 1);
+    assert_eq!(items[0].full_name, "IvanLi-CN/octo-rill");
+    assert_eq!(items[0].tag_name, "v1.2.3");
+}
+
+async fn seed_starred_repo_metadata(
+    pool: &SqlitePool,
+    repo_id: i64,
+    language: Option<&str>,
+    topics: &[&str],
+    is_archived: bool,
+) {
+    sqlx::query(
+        r#"
+            UPDATE starred_repos
+            SET language = ?, topics_json = ?, is_archived = ?
+            WHERE repo_id = ?
+            "#,
+    )
+    .bind(language)
+    .bind(serde_json::to_string(topics).expect("topics json"))
+    .bind(i64::from(is_archived))
+    .bind(repo_id)
+    .execute(pool)
+    .await
+    .expect("seed starred repo metadata");
+}
+
+#[tokio::test]
+async fn list_feed_filters_releases_by_language_topic_and_archived() {
+    let pool = setup_pool().await;
+    for (repo_id, release_id) in [(42, 120), (43, 130), (44, 140)] {
+        seed_repo_release(&pool, repo_id, release_id).await;
+        seed_star(&pool, repo_id).await;
+    }
+    seed_starred_repo_metadata(&pool, 42, Some("Rust"), &["cli", "tui"], false).await;
+    seed_starred_repo_metadata(&pool, 43, Some("Rust"), &["web"], true).await;
+    seed_starred_repo_metadata(&pool, 44, Some("Go"), &["CLI"], false).await;
+    let state = setup_state(pool);
+
+    let feed_ids = |language: Option<&str>, topic: Option<&str>, exclude_archived| {
+        let state = state.clone();
+        let query = FeedQuery {
+            cursor: None,
+            limit: Some(30),
+            types: Some("releases".to_owned()),
+            scope: None,
+            items: None,
+            org: None,
+            unseen_only: None,
+            language: language.map(ToOwned::to_owned),
+            topic: topic.map(ToOwned::to_owned),
+            exclude_archived,
+            excerpt: None,
+            excerpt_lines: None,
+            excerpt_chars: None,
+            mark_seen: None,
+            fields: None,
+        };
+        async move {
+            let Json(feed) =
+                list_feed_page(State(state), AuthedUser(test_user_id(1)), Query(query))
+                    .await
+                    .expect("list filtered feed");
+            let mut ids = feed
+                .items
+                .into_iter()
+                .map(|item| item.id)
+                .collect::<Vec<_>>();
+            ids.sort();
+            ids
+        }
+    };
+
+    assert_eq!(feed_ids(None, None, None).await, vec!["120", "130", "140"]);
+    assert_eq!(feed_ids(Some("rust"), None, None).await, vec!["120", "130"]);
+    assert_eq!(feed_ids(None, Some("cli"), None).await, vec!["120", "140"]);
+    assert_eq!(
+        feed_ids(None, None, Some(true)).await,
+        vec!["120", "140"],
+        "archived repos are only dropped on request"
+    );
+    assert_eq!(
+        feed_ids(Some("Rust"), Some("cli"), Some(true)).await,
+        vec!["120"]
+    );
+    assert!(feed_ids(Some("Haskell"), None, None).await.is_empty());
+}
+
+fn cursor_timestamp(secs: i64) -> String {
+    chrono::DateTime::from_timestamp(secs, 0)
+        .expect("timestamp in range")
+        .to_rfc3339_opts(chrono::SecondsFormat::Secs, true)
+}
+
+proptest! {
+    #[test]
+    fn feed_cursor_round_trips_release_keys(
+        secs in 0_i64..4_102_444_800,
+        release_id in 0_i64..i64::MAX,
+    ) {
+        let cursor = FeedCursor {
+            sort_ts: cursor_timestamp(secs),
+            kind: "release".to_owned(),
+            kind_rank: 5,
+            id_key: format!("{release_id:020}"),
+        };
+        prop_assert_eq!(FeedCursor::decode(&cursor.encode()).ok(), Some(cursor));
+    }
+
+    #[test]
+    fn feed_cursor_round_trips_social_keys(
+        secs in 0_i64..4_102_444_800,
+        kind in select(vec![
+            "release_update",
+            "announcement",
+            "repo_forked",
+            "repo_star_received",
+            "follower_received",
+        ]),
+        id_key in "[A-Za-z0-9_.:-]{1,64}",
+    ) {
+        let cursor = FeedCursor {
+            sort_ts: cursor_timestamp(secs),
+            kind: kind.to_owned(),
+            kind_rank: feed_kind_rank(kind).expect("known kind"),
+            id_key,
+        };
+        prop_assert_eq!(FeedCursor::decode(&cursor.encode()).ok(), Some(cursor));
+    }
+
+    #[test]
+    fn feed_cursor_accepts_legacy_numeric_sort_keys(
+        sort_ts in "[0-9]{1,20}",
+        release_id in 0_i64..i64::MAX,
+    ) {
+        let raw = format!("{sort_ts}|release|{release_id:020}");
+        let cursor = FeedCursor::decode(&raw).expect("legacy cursor");
+        prop_assert_eq!(cursor.encode(), raw);
+    }
+
+    #[test]
+    fn feed_cursor_decode_rejects_arbitrary_input_without_panicking(raw in "\\PC{0,400}") {
+        if let Err(err) = FeedCursor::decode(&raw) {
+            prop_assert_eq!(err.code(), "bad_request");
+        }
+    }
+
+    #[test]
+    fn public_release_cursor_round_trips(
+        secs in 0_i64..4_102_444_800,
+        release_id in 0_i64..i64::MAX,
+    ) {
+        let sort_ts = cursor_timestamp(secs);
+        let cursor = parse_public_release_cursor(&format!("{sort_ts}|{release_id}"))
+            .expect("public cursor");
+        prop_assert_eq!(cursor.sort_ts, sort_ts);
+        prop_assert_eq!(cursor.release_id, release_id);
+    }
+}
+
+#[test]
+fn feed_cursor_rejects_malformed_input() {
+    let release_key = format!("{:020}", 120);
+    let oversized = format!("2026-02-23T10:00:00Z|release|{}", "0".repeat(300));
+    let segmented = format!(
+        "2026-02-23T10:00:00Z|release|{release_key}{}",
+        ";".repeat(500)
+    );
+    for raw in [
+        oversized.as_str(),
+        segmented.as_str(),
+        "2026-02-23T10:00:00Z|release",
+        "2026-02-23T10:00:00Z|release|00000000000000000120|extra",
+        "yesterday|release|00000000000000000120",
+        "2026-02-23T10:00:00Z|comment|00000000000000000120",
+        "2026-02-23T10:00:00Z|release|120",
+        "2026-02-23T10:00:00Z|repo_forked|social fork",
+    ] {
+        let err = FeedCursor::decode(raw).expect_err(raw);
+        assert_eq!(err.code(), "bad_request", "{raw}");
+        assert!(
+            err.to_string().contains("restart paging without a cursor"),
+            "unexpected error: {err}"
+        );
+    }
+
+    let bookmark = format!("2026-02-23T10:00:00Z|release|{}", test_user_id(1));
+    assert!(FeedCursor::decode_bookmark(&bookmark).is_ok());
+    assert!(
+        FeedCursor::decode_bookmark(&format!("2026-02-23T10:00:00Z|release|{release_key}"))
+            .is_err()
+    );
+    assert!(parse_public_release_cursor("2026-02-23T10:00:00Z|not-a-number").is_err());
+    assert!(parse_public_release_cursor("a|b|120").is_err());
+}
+
+#[tokio::test]
+async fn list_feed_rejects_invalid_cursor_with_bad_request() {
+    let state = setup_state(setup_pool().await);
+
+    let err = list_feed_page(
+        State(state),
+        AuthedUser(test_user_id(1)),
+        Query(FeedQuery {
+            cursor: Some(format!("{}|release|1", "9".repeat(4096))),
+            limit: None,
+            types: None,
+            scope: None,
+            items: None,
+            org: None,
+            unseen_only: None,
+            language: None,
+            topic: None,
+            exclude_archived: None,
+            excerpt: None,
+            excerpt_lines: None,
+            excerpt_chars: None,
+            mark_seen: None,
+            fields: None,
+        }),
+    )
+    .await
+    .expect_err("oversized cursor should fail");
+
+    assert_eq!(err.code(), "bad_request");
+    assert!(err.to_string().contains("cursor exceeds 256 bytes"));
+}
+
+#[tokio::test]
+async fn list_feed_exposes_repo_owner_metadata() {
+    let pool = setup_pool().await;
+    seed_repo_release(&pool, 42, 120).await;
+    seed_star(&pool, 42).await;
+    let state = setup_state(pool);
+
+    let Json(feed) = list_feed_page(
+        State(state),
+        AuthedUser(test_user_id(1)),
+        Query(FeedQuery {
+            cursor: None,
+            limit: Some(30),
+            types: Some("releases".to_owned()),
+            scope: None,
+            items: None,
+            org: None,
+            unseen_only: None,
+            language: None,
+            topic: None,
+            exclude_archived: None,
+            excerpt: None,
+            excerpt_lines: None,
+            excerpt_chars: None,
+            mark_seen: None,
+            fields: None,
+        }),
+    )
+    .await
+    .expect("list feed");
+
+    let item = feed.items.first().expect("release item");
+    assert_eq!(item.repo_owner_login.as_deref(), Some("openai"));
+    assert_eq!(
+        item.repo_owner_avatar_url.as_deref(),
+        Some("https://avatars.githubusercontent.com/u/14957082")
+    );
+    assert_eq!(item.repo_description.as_deref(), Some("octo rill test"));
+    assert_eq!(
+        item.repo_html_url.as_deref(),
+        Some("https://github.com/openai/codex")
+    );
+    assert_eq!(
+        item.html_url.as_deref(),
+        Some("https://github.com/openai/codex/releases/tag/v1.2.3")
+    );
+}
+
+#[tokio::test]
+async fn list_feed_unions_followed_repos_with_starred_ones() {
+    let pool = setup_pool().await;
+    seed_star(&pool, 42).await;
+    for (repo_id, release_id) in [(42, 120), (43, 130), (44, 140)] {
+        seed_repo_release(&pool, repo_id, release_id).await;
+    }
+    for (repo_id, full_name) in [(42, "openai/codex"), (43, "octo/flow")] {
+        let (owner, name) = full_name.split_once('/').expect("full name");
+        sqlx::query(
+            r#"
+                INSERT INTO followed_repos (
+                  user_id, repo_id, full_name, owner_login, name, html_url,
+                  import_source, import_ref, created_at, updated_at
+                )
+                VALUES (?, ?, ?, ?, ?, ?, 'list', 'octo/tools', ?, ?)
+                "#,
+        )
+        .bind(test_user_id(1))
+        .bind(repo_id)
+        .bind(full_name)
+        .bind(owner)
+        .bind(name)
+        .bind(format!("https://github.com/{full_name}"))
+        .bind("2026-02-01T00:00:00Z")
+        .bind("2026-02-01T00:00:00Z")
+        .execute(&pool)
+        .await
+        .expect("seed followed repo");
+    }
+    let state = setup_state(pool);
+
+    let Json(feed) = list_feed_page(
+        State(state),
+        AuthedUser(test_user_id(1)),
+        Query(FeedQuery {
+            cursor: None,
+            limit: Some(30),
+            types: Some("releases".to_owned()),
+            scope: None,
+            items: None,
+            org: None,
+            unseen_only: None,
+            language: None,
+            topic: None,
+            exclude_archived: None,
+            excerpt: None,
+            excerpt_lines: None,
+            excerpt_chars: None,
+            mark_seen: None,
+            fields: None,
+        }),
+    )
+    .await
+    .expect("list feed");
+
+    // A repo both starred and followed shows up once, as starred; repos
+    // from neither source stay out.
+    let mut sources = feed
+        .items
+        .iter()
+        .map(|item| {
+            (
+                item.id.as_str(),
+                item.repo_full_name.as_deref(),
+                item.source.as_deref(),
+            )
+        })
+        .collect::<Vec<_>>();
+    sources.sort();
+    assert_eq!(
+        sources,
+        vec![
+            ("120", Some("openai/codex"), Some("starred")),
+            ("130", Some("octo/flow"), Some("followed")),
+        ]
+    );
+}
+
+#[test]
+fn feed_item_serializes_missing_repo_owner_metadata_as_null() {
+    let item = feed_item_from_row(
+        test_feed_row(Some("R_node")),
+        true,
+        None,
+        FeedExcerpt::default(),
+    );
+    let value = serde_json::to_value(&item).expect("serialize feed item");
+
+    for field in [
+        "repo_owner_login",
+        "repo_owner_avatar_url",
+        "repo_description",
+        "repo_html_url",
+    ] {
+        assert_eq!(value[field], Value::Null, "{field}");
+        assert!(value.get(field).is_some(), "{field} should be present");
+    }
+}
+
+#[tokio::test]
+async fn list_starred_exposes_metadata_and_groups_by_language() {
+    let pool = setup_pool().await;
+    for repo_id in [42, 43, 44, 45] {
+        seed_star(&pool, repo_id).await;
+    }
+    seed_starred_repo_metadata(&pool, 42, Some("Rust"), &["cli"], false).await;
+    seed_starred_repo_metadata(&pool, 43, Some("Rust"), &[], true).await;
+    seed_starred_repo_metadata(&pool, 44, Some("Go"), &[], false).await;
+    let state = setup_state(pool);
+
+    let Json(flat) = list_starred(
+        State(state.clone()),
+        AuthedUser(test_user_id(1)),
+        Query(StarredQuery::default()),
+    )
+    .await
+    .expect("list starred");
+    let StarredListResponse::Flat(flat) = flat else {
+        panic!("expected flat starred list");
+    };
+    let rust_cli = flat
+        .iter()
+        .find(|repo| repo.repo_id == 42)
+        .expect("repo 42");
+    assert_eq!(rust_cli.language.as_deref(), Some("Rust"));
+    assert_eq!(rust_cli.topics, vec!["cli".to_owned()]);
+    assert_eq!(rust_cli.is_archived, Some(false));
+    let unsynced = flat
+        .iter()
+        .find(|repo| repo.repo_id == 45)
+        .expect("repo 45");
+    assert_eq!(unsynced.language, None);
+    assert!(unsynced.topics.is_empty());
+    assert_eq!(unsynced.is_archived, None);
+
+    let Json(grouped) = list_starred(
+        State(state.clone()),
+        AuthedUser(test_user_id(1)),
+        Query(StarredQuery {
+            group_by: Some("language".to_owned()),
+            ..StarredQuery::default()
+        }),
+    )
+    .await
+    .expect("list starred grouped");
+    let grouped = serde_json::to_value(grouped).expect("serialize grouped");
+    let summary = grouped["groups"]
+        .as_array()
+        .expect("groups")
+        .iter()
+        .map(|group| (group["language"].clone(), group["count"].clone()))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        summary,
+        vec![
+            (json!("Rust"), json!(2)),
+            (json!("Go"), json!(1)),
+            (Value::Null, json!(1)),
+        ]
+    );
+
+    let err = list_starred(
+        State(state),
+        AuthedUser(test_user_id(1)),
+        Query(StarredQuery {
+            group_by: Some("owner".to_owned()),
+            ..StarredQuery::default()
+        }),
+    )
+    .await
+    .expect_err("unsupported group_by");
+    assert_eq!(err.code(), "bad_request");
+}
+
+async fn seed_starred_listing_fixture(pool: &SqlitePool) {
+    for (repo_id, is_private) in [(42, false), (43, true), (44, false), (45, false)] {
+        seed_star_with_privacy(pool, repo_id, is_private).await;
+    }
+    for (repo_id, full_name, description, stargazed_at) in [
+        (
+            42,
+            "openai/codex",
+            "Lightweight coding agent",
+            "2026-02-20T00:00:00Z",
+        ),
+        (
+            43,
+            "acme/secret-tool",
+            "Internal release bot",
+            "2026-02-21T00:00:00Z",
+        ),
+        (
+            44,
+            "rust-lang/rust",
+            "Empowering everyone",
+            "2026-02-22T00:00:00Z",
+        ),
+        (
+            45,
+            "tokio-rs/tokio",
+            "Async runtime for Rust",
+            "2026-02-22T00:00:00Z",
+        ),
+    ] {
+        sqlx::query(
+            r#"
+                UPDATE starred_repos
+                SET full_name = ?, description = ?, stargazed_at = ?
+                WHERE repo_id = ?
+                "#,
+        )
+        .bind(full_name)
+        .bind(description)
+        .bind(stargazed_at)
+        .bind(repo_id)
+        .execute(pool)
+        .await
+        .expect("update starred fixture");
+    }
+}
+
+async fn list_starred_paged(state: Arc<AppState>, query: StarredQuery) -> (Vec<i64>, Value) {
+    let Json(resp) = list_starred(State(state), AuthedUser(test_user_id(1)), Query(query))
+        .await
+        .expect("list starred");
+    let StarredListResponse::Paged { items, .. } = &resp else {
+        panic!("expected paged starred list");
+    };
+    let ids = items.iter().map(|repo| repo.repo_id).collect();
+    (ids, serde_json::to_value(&resp).expect("serialize starred"))
+}
+
+#[tokio::test]
+async fn list_starred_pages_with_stable_order_and_clamped_size() {
+    let pool = setup_pool().await;
+    seed_starred_listing_fixture(&pool).await;
+    let state = setup_state(pool);
+
+    let (first, value) = list_starred_paged(
+        state.clone(),
+        StarredQuery {
+            page: Some(1),
+            page_size: Some(2),
+            ..StarredQuery::default()
+        },
+    )
+    .await;
+    assert_eq!(first, vec![45, 44], "same stargazed_at breaks ties by id");
+    assert_eq!(value["total"], json!(4));
+    assert_eq!(value["page"], json!(1));
+    assert_eq!(value["page_size"], json!(2));
+
+    let (second, _) = list_starred_paged(
+        state.clone(),
+        StarredQuery {
+            page: Some(2),
+            page_size: Some(2),
+            ..StarredQuery::default()
+        },
+    )
+    .await;
+    assert_eq!(second, vec![43, 42]);
+
+    let (_, clamped_low) = list_starred_paged(
+        state.clone(),
+        StarredQuery {
+            page_size: Some(0),
+            ..StarredQuery::default()
+        },
+    )
+    .await;
+    assert_eq!(clamped_low["page_size"], json!(1));
+    assert_eq!(clamped_low["items"].as_array().map(Vec::len), Some(1));
+
+    let (_, clamped_high) = list_starred_paged(
+        state.clone(),
+        StarredQuery {
+            page_size: Some(5_000),
+            ..StarredQuery::default()
+        },
+    )
+    .await;
+    assert_eq!(clamped_high["page_size"], json!(200));
+
+    let err = list_starred(
+        State(state.clone()),
+        AuthedUser(test_user_id(1)),
+        Query(StarredQuery {
+            page: Some(0),
+            ..StarredQuery::default()
+        }),
+    )
+    .await
+    .expect_err("page zero");
+    assert_eq!(err.code(), "bad_request");
+
+    let err = list_starred(
+        State(state),
+        AuthedUser(test_user_id(1)),
+        Query(StarredQuery {
+            page: Some(1),
+            group_by: Some("language".to_owned()),
+            ..StarredQuery::default()
+        }),
+    )
+    .await
+    .expect_err("grouping is not paged");
+    assert_eq!(err.code(), "bad_request");
+}
+
+#[tokio::test]
+async fn list_starred_filters_by_query_and_visibility() {
+    let pool = setup_pool().await;
+    seed_starred_listing_fixture(&pool).await;
+    let state = setup_state(pool);
+
+    let (by_name, value) = list_starred_paged(
+        state.clone(),
+        StarredQuery {
+            page: Some(1),
+            query: Some("  TOKIO ".to_owned()),
+            ..StarredQuery::default()
+        },
+    )
+    .await;
+    assert_eq!(by_name, vec![45]);
+    assert_eq!(value["total"], json!(1));
+
+    let (by_description, _) = list_starred_paged(
+        state.clone(),
+        StarredQuery {
+            page: Some(1),
+            query: Some("rust".to_owned()),
+            ..StarredQuery::default()
+        },
+    )
+    .await;
+    assert_eq!(by_description, vec![45, 44]);
+
+    let (private, _) = list_starred_paged(
+        state.clone(),
+        StarredQuery {
+            page: Some(1),
+            visibility: Some("private".to_owned()),
+            ..StarredQuery::default()
+        },
+    )
+    .await;
+    assert_eq!(private, vec![43]);
+
+    let (public, value) = list_starred_paged(
+        state.clone(),
+        StarredQuery {
+            page: Some(1),
+            visibility: Some("public".to_owned()),
+            ..StarredQuery::default()
+        },
+    )
+    .await;
+    assert_eq!(public, vec![45, 44, 42]);
+    assert_eq!(value["total"], json!(3));
+
+    let err = list_starred(
+        State(state),
+        AuthedUser(test_user_id(1)),
+        Query(StarredQuery {
+            visibility: Some("internal".to_owned()),
+            ..StarredQuery::default()
+        }),
+    )
+    .await
+    .expect_err("unknown visibility");
+    assert_eq!(err.code(), "bad_request");
+}
+
+#[tokio::test]
+async fn list_starred_without_paging_params_keeps_flat_array() {
+    let pool = setup_pool().await;
+    seed_starred_listing_fixture(&pool).await;
+    let state = setup_state(pool);
+
+    let Json(resp) = list_starred(
+        State(state),
+        AuthedUser(test_user_id(1)),
+        Query(StarredQuery {
+            visibility: Some("public".to_owned()),
+            ..StarredQuery::default()
+        }),
+    )
+    .await
+    .expect("list starred");
+    let value = serde_json::to_value(&resp).expect("serialize starred");
+    let ids = value
+        .as_array()
+        .expect("legacy flat array")
+        .iter()
+        .map(|repo| repo["repo_id"].clone())
+        .collect::<Vec<_>>();
+    assert_eq!(ids, vec![json!(45), json!(44), json!(42)]);
+}
+
+#[tokio::test]
+async fn list_releases_hides_muted_repo_until_unmuted() {
+    let pool = setup_pool().await;
+    seed_repo_release(&pool, 42, 120).await;
+    seed_star(&pool, 42).await;
+    let state = setup_state(pool);
+
+    let Json(muted) = mute_repo(
+        State(state.clone()),
+        AuthedUser(test_user_id(1)),
+        Path("42".to_owned()),
+    )
+    .await
+    .expect("mute repo");
+    assert!(muted.is_muted);
+
+    let Json(items) = list_releases(State(state.clone()), AuthedUser(test_user_id(1)))
+        .await
+        .expect("list releases while muted");
+    assert!(items.is_empty());
+
+    let Json(starred) = list_starred(
+        State(state.clone()),
+        AuthedUser(test_user_id(1)),
+        Query(StarredQuery::default()),
+    )
+    .await
+    .expect("list starred while muted");
+    let StarredListResponse::Flat(starred) = starred else {
+        panic!("expected flat starred list");
+    };
+    assert_eq!(starred.len(), 1);
+    assert!(starred[0].is_muted);
+
+    let release_rows =
+        sqlx::query_scalar::<_, i64>(r#"SELECT COUNT(*) FROM repo_releases WHERE repo_id = 42"#)
+            .fetch_one(&state.pool)
+            .await
+            .expect("count releases");
+    assert_eq!(release_rows, 1);
+
+    let Json(unmuted) = unmute_repo(
+        State(state.clone()),
+        AuthedUser(test_user_id(1)),
+        Path("42".to_owned()),
+    )
+    .await
+    .expect("unmute repo");
+    assert!(!unmuted.is_muted);
+
+    let Json(items) = list_releases(State(state), AuthedUser(test_user_id(1)))
+        .await
+        .expect("list releases after unmute");
+    assert_eq!(items.len(), 1);
+}
+
+#[tokio::test]
+async fn mute_repo_rejects_repo_outside_watched_set() {
+    let pool = setup_pool().await;
+    let state = setup_state(pool);
+
+    let err = mute_repo(
+        State(state),
+        AuthedUser(test_user_id(1)),
+        Path("42".to_owned()),
+    )
+    .await
+    .expect_err("unknown repo should fail");
+
+    assert_eq!(err.code(), "repo_not_found");
+}
+
+#[tokio::test]
+async fn get_rendered_release_sanitizes_body_and_caches_html() {
+    let pool = setup_pool().await;
+    seed_repo_release(&pool, 42, 120).await;
+    seed_star(&pool, 42).await;
+    sqlx::query(r#"UPDATE repo_releases SET body = ? WHERE release_id = 120"#)
+        .bind("Fixes #123\n\n![demo](./docs/demo.png)\n\n<script>alert(1)</script>\n")
+        .execute(&pool)
+        .await
+        .expect("seed release body");
+    let state = setup_state(pool.clone());
+    let render = |lang: Option<&str>| {
+        let state = state.clone();
+        let lang = lang.map(ToOwned::to_owned);
+        async move {
+            get_rendered_release(
+                State(state),
+                AuthedUser(test_user_id(1)),
+                Path("120".to_owned()),
+                Query(RenderedReleaseQuery { lang }),
+            )
+            .await
+        }
+    };
+
+    let Json(rendered) = render(None).await.expect("render release");
+    assert_eq!(rendered.release_id, "120");
+    assert!(!rendered.html.contains("script"), "{}", rendered.html);
+    assert!(
+        rendered
+            .html
+            .contains(r#"href="https://github.com/openai/codex/issues/123""#),
+        "{}",
+        rendered.html
+    );
+    assert!(
+        rendered
+            .html
+            .contains(r#"src="https://github.com/openai/codex/raw/v1.2.3/docs/demo.png""#),
+        "{}",
+        rendered.html
+    );
+    assert_eq!(rendered.sanitization.stripped_elements, 1);
+    assert!(rendered.translated.is_none());
+
+    let Json(with_translation) = render(Some("zh-CN")).await.expect("render again");
+    assert_eq!(with_translation.html, rendered.html);
+    let translated = with_translation.translated.expect("translation slot");
+    assert_eq!(translated.status, "missing");
+    assert!(translated.html.is_none());
+    let cached = sqlx::query_scalar::<_, i64>(r#"SELECT COUNT(*) FROM release_render_cache"#)
+        .fetch_one(&pool)
+        .await
+        .expect("count render cache");
+    assert_eq!(cached, 1, "repeat renders reuse the cached html");
+
+    let err = render(Some("fr")).await.expect_err("unsupported lang");
+    assert_eq!(err.code(), "unsupported_language");
+}
+
+#[test]
+fn diff_release_sections_splits_on_headings_outside_code_fences() {
+    let previous = "Intro\n\n## Features\n- a\n\n## Fixes\n- old fix\n";
+    let current = "Intro\r\n\r\n## Features\r\n- a\r\n\r\n## Fixes\r\n- new fix\r\n\r\n```sh\r\n# not a heading\r\n```\r\n\r\n### Breaking ###\r\n- drop v1 api\r\n";
+
+    let (added, removed) = diff_release_sections(previous, current);
+
+    let headings = |sections: &[super::ReleaseDiffSection]| {
+        sections
+            .iter()
+            .map(|section| (section.heading.clone(), section.level))
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(
+        headings(&added),
+        vec![
+            (Some("Fixes".to_owned()), 2),
+            (Some("Breaking".to_owned()), 3),
+        ]
+    );
+    assert_eq!(added[0].body, "- new fix\n\n```sh\n# not a heading\n```");
+    assert_eq!(headings(&removed), vec![(Some("Fixes".to_owned()), 2)]);
+    assert_eq!(removed[0].body, "- old fix");
+
+    let (added, removed) = diff_release_sections(previous, previous);
+    assert!(added.is_empty() && removed.is_empty());
+}
+
+#[tokio::test]
+async fn get_release_diff_prev_picks_previous_release_of_same_repo() {
+    let pool = setup_pool().await;
+    seed_star(&pool, 42).await;
+    for (release_id, tag, published_at, is_prerelease, body) in [
+        (
+            201,
+            "v1.0.0",
+            "2026-02-01T00:00:00Z",
+            0,
+            "## Features\n- first\n",
+        ),
+        (
+            202,
+            "v1.1.0-rc.1",
+            "2026-02-05T00:00:00Z",
+            1,
+            "## Features\n- rc\n",
+        ),
+        (
+            203,
+            "v1.1.0",
+            "2026-02-10T00:00:00Z",
+            0,
+            "## Features\n- second\n",
+        ),
+    ] {
+        seed_repo_release(&pool, 42, release_id).await;
+        sqlx::query(
+            r#"
+            UPDATE repo_releases
+            SET tag_name = ?, published_at = ?, is_prerelease = ?, body = ?
+            WHERE release_id = ?
+            "#,
+        )
+        .bind(tag)
+        .bind(published_at)
+        .bind(is_prerelease)
+        .bind(body)
+        .bind(release_id)
+        .execute(&pool)
+        .await
+        .expect("shape release");
+    }
+    // Another repo's newer-but-earlier release must never be picked.
+    seed_repo_release(&pool, 43, 199).await;
+    sqlx::query(
+        r#"UPDATE repo_releases SET published_at = '2026-02-09T00:00:00Z' WHERE release_id = 199"#,
+    )
+    .execute(&pool)
+    .await
+    .expect("shape other repo release");
+    let state = setup_state(pool);
+    let diff = |release_id: &str, stable_only: Option<bool>| {
+        let state = state.clone();
+        let release_id = release_id.to_owned();
+        async move {
+            get_release_diff_prev(
+                State(state),
+                AuthedUser(test_user_id(1)),
+                Path(release_id),
+                Query(ReleaseDiffQuery {
+                    stable_only,
+                    summarize: None,
+                }),
+            )
+            .await
+            .expect("release diff")
+            .0
+        }
+    };
+
+    let latest = diff("203", None).await;
+    assert!(latest.has_previous);
+    let previous = latest.previous.expect("previous release");
+    assert_eq!(previous.release_id, "202");
+    assert!(previous.is_prerelease);
+    assert_eq!(latest.added_sections[0].body, "- second");
+    assert_eq!(latest.removed_sections[0].body, "- rc");
+    assert!(latest.summary.is_none());
+
+    let stable = diff("203", Some(true)).await;
+    assert_eq!(stable.previous.expect("previous stable").release_id, "201");
+    assert_eq!(stable.removed_sections[0].body, "- first");
+
+    let first = diff("201", None).await;
+    assert!(!first.has_previous);
+    assert!(first.previous.is_none());
+    assert!(first.added_sections.is_empty() && first.removed_sections.is_empty());
+}
+
+/// Links one GitHub account per token, in order, and points REST calls
+/// at `github`. The mock records the bearer token of every asset call.
+async fn setup_release_assets_state(
+    pool: &SqlitePool,
+    tokens: &[&str],
+    github: Router,
+) -> Arc<AppState> {
+    let mut inner = (*setup_state(pool.clone())).clone();
+    inner.github_rest_api_base = spawn_test_ai_server(github).await;
+    let state = Arc::new(inner);
+    sqlx::query(r#"DELETE FROM github_connections WHERE user_id = ?"#)
+        .bind(test_user_id(1))
+        .execute(pool)
+        .await
+        .expect("clear placeholder github connections");
+    for (idx, token) in tokens.iter().enumerate() {
+        let encrypted = state
+            .encryption_key
+            .encrypt_str(token)
+            .expect("encrypt github token");
+        sqlx::query(
+            r#"
+                INSERT INTO github_connections (
+                  id, user_id, github_user_id, login, access_token_ciphertext, access_token_nonce,
+                  scopes, linked_at, updated_at
+                )
+                VALUES (?, ?, ?, ?, ?, ?, 'repo', ?, ?)
+                "#,
+        )
+        .bind(crate::local_id::generate_local_id())
+        .bind(test_user_id(1))
+        .bind(10 + idx as i64)
+        .bind(format!("octo-{idx}"))
+        .bind(encrypted.ciphertext)
+        .bind(encrypted.nonce)
+        .bind(format!("2026-02-23T00:00:0{idx}Z"))
+        .bind(format!("2026-02-23T00:00:0{idx}Z"))
+        .execute(pool)
+        .await
+        .expect("seed github connection");
+    }
+    state
+}
+
+fn release_assets_mock(
+    allowed_token: &'static str,
+    seen_tokens: Arc<tokio::sync::Mutex<Vec<String>>>,
+) -> Router {
+    Router::new().route(
+            "/repos/openai/codex/releases/120",
+            get(move |headers: axum::http::HeaderMap| {
+                let seen_tokens = Arc::clone(&seen_tokens);
+                async move {
+                    let token = headers
+                        .get(axum::http::header::AUTHORIZATION)
+                        .and_then(|value| value.to_str().ok())
+                        .unwrap_or_default()
+                        .trim_start_matches("Bearer ")
+                        .to_owned();
+                    let allowed = token == allowed_token;
+                    seen_tokens.lock().await.push(token);
+                    if !allowed {
+                        return (StatusCode::NOT_FOUND, Json(json!({ "message": "Not Found" })));
+                    }
+                    (
+                        StatusCode::OK,
+                        Json(json!({
+                            "id": 120,
+                            "tag_name": "v1.2.3",
+                            "assets": [{
+                                "id": 7,
+                                "name": "codex-x86_64-unknown-linux-musl.tar.gz",
+                                "size": 12_582_912,
+                                "download_count": 42,
+                                "browser_download_url": "https://github.com/openai/codex/releases/download/v1.2.3/codex-x86_64-unknown-linux-musl.tar.gz",
+                                "content_type": "application/gzip"
+                            }]
+                        })),
+                    )
+                }
+            }),
+        )
+}
+
+#[tokio::test]
+async fn get_release_detail_fills_missing_assets_with_a_token_that_sees_the_private_repo() {
+    let pool = setup_pool().await;
+    seed_repo_release(&pool, 42, 120).await;
+    seed_star_with_privacy(&pool, 42, true).await;
+    let seen_tokens = Arc::new(tokio::sync::Mutex::new(Vec::new()));
+    let state = setup_release_assets_state(
+        &pool,
+        &["gho_public_only", "gho_private_access"],
+        release_assets_mock("gho_private_access", Arc::clone(&seen_tokens)),
+    )
+    .await;
+
+    let detail = |state: Arc<AppState>| async move {
+        let Json(detail) = get_release_detail(
+            State(state),
+            AuthedUser(test_user_id(1)),
+            Path("120".to_owned()),
+        )
+        .await
+        .expect("get release detail");
+        detail
+    };
+    let first = detail(Arc::clone(&state)).await;
+    assert_eq!(first.assets.len(), 1);
+    assert_eq!(
+        first.assets[0].name,
+        "codex-x86_64-unknown-linux-musl.tar.gz"
+    );
+    assert_eq!(first.assets[0].size, 12_582_912);
+    assert_eq!(first.assets[0].download_count, 42);
+    assert!(first.assets_warning.is_none());
+    assert_eq!(
+        *seen_tokens.lock().await,
+        vec!["gho_public_only", "gho_private_access"]
+    );
+
+    // Stored now: the second read does not go back to GitHub.
+    let second = detail(state).await;
+    assert_eq!(second.assets.len(), 1);
+    assert_eq!(seen_tokens.lock().await.len(), 2);
+
+    let feed = feed_assets_count_for_release_120(&pool).await;
+    assert_eq!(feed, Some(1));
+}
+
+async fn feed_assets_count_for_release_120(pool: &SqlitePool) -> Option<i64> {
+    let state = setup_state(pool.clone());
+    let Json(feed) = list_feed_page(
+        State(state),
+        AuthedUser(test_user_id(1)),
+        Query(FeedQuery {
+            cursor: None,
+            limit: Some(10),
+            types: Some("releases".to_owned()),
+            scope: None,
+            items: None,
+            org: None,
+            unseen_only: None,
+            language: None,
+            topic: None,
+            exclude_archived: None,
+            excerpt: None,
+            excerpt_lines: None,
+            excerpt_chars: None,
+            mark_seen: None,
+            fields: None,
+        }),
+    )
+    .await
+    .expect("list feed");
+    feed.items
+        .iter()
+        .find(|item| item.id == "120")
+        .and_then(|item| item.assets_count)
+}
+
+#[tokio::test]
+async fn get_release_detail_leaves_private_assets_hidden_without_access() {
+    let pool = setup_pool().await;
+    seed_repo_release(&pool, 42, 120).await;
+    seed_star_with_privacy(&pool, 42, true).await;
+    let seen_tokens = Arc::new(tokio::sync::Mutex::new(Vec::new()));
+    let state = setup_release_assets_state(
+        &pool,
+        &["gho_public_only"],
+        release_assets_mock("gho_private_access", Arc::clone(&seen_tokens)),
+    )
+    .await;
+
+    let Json(detail) = get_release_detail(
+        State(state),
+        AuthedUser(test_user_id(1)),
+        Path("120".to_owned()),
+    )
+    .await
+    .expect("get release detail");
+
+    assert!(detail.assets.is_empty());
+    assert_eq!(
+        detail.assets_warning.map(|warning| warning.code),
+        Some("not_found".to_owned())
+    );
+    let synced_at = sqlx::query_scalar::<_, Option<String>>(
+        "SELECT assets_synced_at FROM repo_releases WHERE release_id = 120",
+    )
+    .fetch_one(&pool)
+    .await
+    .expect("load assets_synced_at");
+    assert_eq!(synced_at, None, "a later read retries the fetch");
+}
+
+#[tokio::test]
+async fn get_release_detail_maps_rate_limited_asset_fetch_to_warning() {
+    let pool = setup_pool().await;
+    seed_repo_release(&pool, 42, 120).await;
+    seed_star(&pool, 42).await;
+    let state = setup_release_assets_state(
+        &pool,
+        &["gho_token"],
+        Router::new().route(
+            "/repos/openai/codex/releases/120",
+            get(|| async {
+                (
+                    StatusCode::FORBIDDEN,
+                    [("x-ratelimit-remaining", "0")],
+                    "API rate limit exceeded",
+                )
+            }),
+        ),
+    )
+    .await;
+
+    let Json(detail) = get_release_detail(
+        State(state),
+        AuthedUser(test_user_id(1)),
+        Path("120".to_owned()),
+    )
+    .await
+    .expect("get release detail");
+
+    assert!(detail.assets.is_empty());
+    assert_eq!(
+        detail.assets_warning.map(|warning| warning.code),
+        Some("rate_limited".to_owned())
+    );
+}
+
+#[tokio::test]
+async fn get_release_detail_reads_shared_repo_cache_for_starred_user() {
+    let pool = setup_pool().await;
+    seed_repo_release(&pool, 42, 120).await;
+    seed_star(&pool, 42).await;
+    let state = setup_state(pool);
+
+    let Json(detail) = get_release_detail(
+        State(state),
+        AuthedUser(test_user_id(1)),
+        Path("120".to_owned()),
+    )
+    .await
+    .expect("get release detail");
+
+    assert_eq!(detail.release_id, "120");
+    assert_eq!(detail.repo_full_name.as_deref(), Some("openai/codex"));
+    assert_eq!(detail.repo_owner_login.as_deref(), Some("openai"));
+    assert_eq!(
+        detail.repo_owner_avatar_url.as_deref(),
+        Some("https://avatars.githubusercontent.com/u/14957082")
+    );
+    assert_eq!(detail.repo_description.as_deref(), Some("octo rill test"));
+    assert_eq!(
+        detail.repo_html_url.as_deref(),
+        Some("https://github.com/openai/codex")
+    );
+    let repo_visual = detail.repo_visual.expect("repo visual");
+    assert_eq!(
+        repo_visual.owner_avatar_url.as_deref(),
+        Some("https://avatars.githubusercontent.com/u/14957082")
+    );
+    assert_eq!(
+        repo_visual.open_graph_image_url.as_deref(),
+        Some("https://repository-images.githubusercontent.com/14957082/codex")
+    );
+    assert!(repo_visual.uses_custom_open_graph_image);
+    assert_eq!(detail.tag_name, "v1.2.3");
+    assert_eq!(detail.name.as_deref(), Some("Release v1.2.3"));
+    assert_eq!(detail.body.as_deref(), Some("- item"));
+    assert_eq!(
+        detail.html_url,
+        "https://github.com/openai/codex/releases/tag/v1.2.3"
+    );
+}
+
+#[tokio::test]
+async fn public_release_list_first_access_returns_retryable_pending() {
+    let pool = setup_pool().await;
+    let state = setup_state(pool);
+
+    let response = public_list_repo_releases(
+        State(state),
+        Path(("openai".to_owned(), "codex".to_owned())),
+        Query(PublicReleaseQuery {
+            content: None,
+            lang: None,
+            source: None,
+            cursor: None,
+            limit: None,
+        }),
+    )
+    .await
+    .expect("public list pending");
+
+    assert_eq!(response.status(), StatusCode::ACCEPTED);
+    assert_eq!(
+        response.headers().get(header::RETRY_AFTER),
+        Some(&header::HeaderValue::from_static("60"))
+    );
+    let body = response_json(response).await;
+    assert_eq!(body["status"], json!("pending_sync"));
+    assert_eq!(
+        body["reason"],
+        json!("repository_registered_metadata_pending")
+    );
+    assert_eq!(body["repo_full_name"], json!("openai/codex"));
+}
+
+#[tokio::test]
+async fn public_release_list_first_access_reuses_public_release_cache() {
+    let pool = setup_pool().await;
+    seed_star(&pool, 42).await;
+    seed_repo_release(&pool, 42, 120).await;
+    let state = setup_state(pool.clone());
+
+    let response = public_list_repo_releases(
+        State(state),
+        Path(("openai".to_owned(), "codex".to_owned())),
+        Query(PublicReleaseQuery {
+            content: None,
+            lang: None,
+            source: Some("page".to_owned()),
+            cursor: None,
+            limit: None,
+        }),
+    )
+    .await
+    .expect("public list ready from cache");
+
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = response_json(response).await;
+    assert_eq!(body["status"], json!("ready"));
+    assert_eq!(body["repo_full_name"], json!("openai/codex"));
+    assert_eq!(body["items"].as_array().unwrap().len(), 1);
+    assert_eq!(body["items"][0]["release_id"], json!("120"));
+
+    let row: (Option<i64>, String, Option<String>) = sqlx::query_as(
+        r#"
+            SELECT repo_id, last_sync_status, last_sync_error
+            FROM public_repo_release_usage
+            WHERE full_name_lower = 'openai/codex'
+            "#,
+    )
+    .fetch_one(&pool)
+    .await
+    .expect("public usage row");
+    assert_eq!(row.0, Some(42));
+    assert_eq!(row.1, "ready");
+    assert_eq!(row.2, None);
+}
+
+#[tokio::test]
+async fn public_release_detail_first_access_reuses_public_release_cache() {
+    let pool = setup_pool().await;
+    seed_star(&pool, 42).await;
+    seed_repo_release(&pool, 42, 120).await;
+    let state = setup_state(pool);
+
+    let response = public_get_repo_release_detail(
+        State(state),
+        Path(("openai".to_owned(), "codex".to_owned(), "v1.2.3".to_owned())),
+        Query(PublicReleaseQuery {
+            content: None,
+            lang: None,
+            source: Some("page".to_owned()),
+            cursor: None,
+            limit: None,
+        }),
+    )
+    .await
+    .expect("public detail ready from cache");
+
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = response_json(response).await;
+    assert_eq!(body["release_id"], json!("120"));
+    assert_eq!(body["repo_full_name"], json!("openai/codex"));
+    assert_eq!(body["tag_name"], json!("v1.2.3"));
+}
+
+#[tokio::test]
+async fn public_release_list_first_access_with_public_metadata_queues_release_sync() {
+    let pool = setup_pool().await;
+    seed_star(&pool, 42).await;
+    let state = setup_state(pool.clone());
+
+    let response = public_list_repo_releases(
+        State(state),
+        Path(("openai".to_owned(), "codex".to_owned())),
+        Query(PublicReleaseQuery {
+            content: None,
+            lang: None,
+            source: None,
+            cursor: None,
+            limit: None,
+        }),
+    )
+    .await
+    .expect("public list release pending");
+
+    assert_eq!(response.status(), StatusCode::ACCEPTED);
+    let body = response_json(response).await;
+    assert_eq!(
+        body["reason"],
+        json!("repository_registered_release_sync_pending")
+    );
+    let row: (Option<i64>, String) = sqlx::query_as(
+        r#"
+            SELECT repo_id, last_sync_status
+            FROM public_repo_release_usage
+            WHERE full_name_lower = 'openai/codex'
+            "#,
+    )
+    .fetch_one(&pool)
+    .await
+    .expect("public usage row");
+    assert_eq!(row.0, Some(42));
+    assert_eq!(row.1, "pending");
+
+    let work_item: (i64, String, String) = sqlx::query_as(
+        r#"
+            SELECT repo_id, status, request_origin
+            FROM repo_release_work_items
+            WHERE repo_id = 42
+            "#,
+    )
+    .fetch_one(&pool)
+    .await
+    .expect("repo release work item");
+    assert_eq!(work_item.0, 42);
+    assert_eq!(work_item.1, jobs::STATUS_QUEUED);
+    assert_eq!(work_item.2, "interactive");
+}
+
+#[tokio::test]
+async fn public_release_list_first_access_marks_fresh_empty_release_cache_ready() {
+    let pool = setup_pool().await;
+    seed_star(&pool, 42).await;
+    let now = chrono::Utc::now().to_rfc3339();
+    sqlx::query(
+        r#"
+            INSERT INTO repo_release_work_items (
+              id, repo_id, repo_full_name, status, request_origin, priority,
+              has_new_repo_watchers, deadline_at, last_release_count,
+              last_candidate_failures, last_success_at, created_at, updated_at
+            )
+            VALUES (
+              'repo-release-work-empty', 42, 'openai/codex', 'succeeded',
+              'system', 0, 0, ?, 0, 0, ?, ?, ?
+            )
+            "#,
+    )
+    .bind(now.as_str())
+    .bind(now.as_str())
+    .bind(now.as_str())
+    .bind(now.as_str())
+    .execute(&pool)
+    .await
+    .expect("seed fresh empty release work item");
+    let state = setup_state(pool.clone());
+
+    let response = public_list_repo_releases(
+        State(state),
+        Path(("openai".to_owned(), "codex".to_owned())),
+        Query(PublicReleaseQuery {
+            content: None,
+            lang: None,
+            source: None,
+            cursor: None,
+            limit: None,
+        }),
+    )
+    .await
+    .expect("fresh empty release cache should be ready");
+
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = response_json(response).await;
+    assert_eq!(body["status"], json!("ready"));
+    assert_eq!(body["items"].as_array().unwrap().len(), 0);
+    let row: (Option<i64>, String) = sqlx::query_as(
+        r#"
+            SELECT repo_id, last_sync_status
+            FROM public_repo_release_usage
+            WHERE full_name_lower = 'openai/codex'
+            "#,
+    )
+    .fetch_one(&pool)
+    .await
+    .expect("public usage row");
+    assert_eq!(row.0, Some(42));
+    assert_eq!(row.1, "ready");
+}
+
+#[tokio::test]
+async fn public_release_list_first_access_does_not_reuse_private_metadata() {
+    let pool = setup_pool().await;
+    seed_star_with_privacy(&pool, 42, true).await;
+    seed_repo_release(&pool, 42, 120).await;
+    let state = setup_state(pool.clone());
+
+    let response = public_list_repo_releases(
+        State(state),
+        Path(("openai".to_owned(), "codex".to_owned())),
+        Query(PublicReleaseQuery {
+            content: None,
+            lang: None,
+            source: None,
+            cursor: None,
+            limit: None,
+        }),
+    )
+    .await
+    .expect("private metadata should remain metadata pending");
+
+    assert_eq!(response.status(), StatusCode::ACCEPTED);
+    let body = response_json(response).await;
+    assert_eq!(
+        body["reason"],
+        json!("repository_registered_metadata_pending")
+    );
+    let row: (Option<i64>, String) = sqlx::query_as(
+        r#"
+            SELECT repo_id, last_sync_status
+            FROM public_repo_release_usage
+            WHERE full_name_lower = 'openai/codex'
+            "#,
+    )
+    .fetch_one(&pool)
+    .await
+    .expect("public usage row");
+    assert_eq!(row.0, None);
+    assert_eq!(row.1, "pending");
+}
+
+#[tokio::test]
+async fn public_release_list_first_access_does_not_reuse_stale_public_metadata() {
+    let pool = setup_pool().await;
+    seed_star(&pool, 42).await;
+    seed_repo_release(&pool, 42, 120).await;
+    sqlx::query(
+        r#"
+            UPDATE starred_repos
+            SET updated_at = '2026-02-23T00:00:00Z'
+            WHERE repo_id = 42
+            "#,
+    )
+    .execute(&pool)
+    .await
+    .expect("stale starred metadata");
+    let state = setup_state(pool.clone());
+
+    let response = public_list_repo_releases(
+        State(state),
+        Path(("openai".to_owned(), "codex".to_owned())),
+        Query(PublicReleaseQuery {
+            content: None,
+            lang: None,
+            source: None,
+            cursor: None,
+            limit: None,
+        }),
+    )
+    .await
+    .expect("stale metadata should remain metadata pending");
+
+    assert_eq!(response.status(), StatusCode::ACCEPTED);
+    let body = response_json(response).await;
+    assert_eq!(
+        body["reason"],
+        json!("repository_registered_metadata_pending")
+    );
+    let row: (Option<i64>, String) = sqlx::query_as(
+        r#"
+            SELECT repo_id, last_sync_status
+            FROM public_repo_release_usage
+            WHERE full_name_lower = 'openai/codex'
+            "#,
+    )
+    .fetch_one(&pool)
+    .await
+    .expect("public usage row");
+    assert_eq!(row.0, None);
+    assert_eq!(row.1, "pending");
+}
+
+#[tokio::test]
+async fn public_release_list_first_access_uses_newest_privacy_metadata() {
+    let pool = setup_pool().await;
+    sqlx::query(
+        r#"
+            INSERT INTO users (id, github_user_id, login, created_at, updated_at)
+            VALUES (?, 30215106, 'second-user', ?, ?)
+            "#,
+    )
+    .bind(test_user_id(2))
+    .bind(chrono::Utc::now().to_rfc3339())
+    .bind(chrono::Utc::now().to_rfc3339())
+    .execute(&pool)
+    .await
+    .expect("seed second user");
+    seed_star_for_user_with_privacy(&pool, 2, 42, false).await;
+    sqlx::query(
+        r#"
+            UPDATE starred_repos
+            SET updated_at = '2026-02-23T00:00:00Z'
+            WHERE user_id = ? AND repo_id = 42
+            "#,
+    )
+    .bind(test_user_id(2))
+    .execute(&pool)
+    .await
+    .expect("make public metadata older");
+    seed_star_with_privacy(&pool, 42, true).await;
+    seed_repo_release(&pool, 42, 120).await;
+    let state = setup_state(pool.clone());
+
+    let response = public_list_repo_releases(
+        State(state),
+        Path(("openai".to_owned(), "codex".to_owned())),
+        Query(PublicReleaseQuery {
+            content: None,
+            lang: None,
+            source: None,
+            cursor: None,
+            limit: None,
+        }),
+    )
+    .await
+    .expect("newer private metadata should win");
+
+    assert_eq!(response.status(), StatusCode::ACCEPTED);
+    let body = response_json(response).await;
+    assert_eq!(
+        body["reason"],
+        json!("repository_registered_metadata_pending")
+    );
+    let row: (Option<i64>, String) = sqlx::query_as(
+        r#"
+            SELECT repo_id, last_sync_status
+            FROM public_repo_release_usage
+            WHERE full_name_lower = 'openai/codex'
+            "#,
+    )
+    .fetch_one(&pool)
+    .await
+    .expect("public usage row");
+    assert_eq!(row.0, None);
+    assert_eq!(row.1, "pending");
+}
+
+#[tokio::test]
+async fn public_release_list_first_access_does_not_reuse_owned_only_baseline() {
+    let pool = setup_pool().await;
+    set_include_own_releases(&pool, true).await;
+    seed_owned_repo_baseline(&pool, 42, "openai/codex").await;
+    seed_repo_release(&pool, 42, 120).await;
+    let state = setup_state(pool.clone());
+
+    let response = public_list_repo_releases(
+        State(state),
+        Path(("openai".to_owned(), "codex".to_owned())),
+        Query(PublicReleaseQuery {
+            content: None,
+            lang: None,
+            source: None,
+            cursor: None,
+            limit: None,
+        }),
+    )
+    .await
+    .expect("owned-only baseline should not prove public visibility");
+
+    assert_eq!(response.status(), StatusCode::ACCEPTED);
+    let body = response_json(response).await;
+    assert_eq!(
+        body["reason"],
+        json!("repository_registered_metadata_pending")
+    );
+    let row: (Option<i64>, String) = sqlx::query_as(
+        r#"
+            SELECT repo_id, last_sync_status
+            FROM public_repo_release_usage
+            WHERE full_name_lower = 'openai/codex'
+            "#,
+    )
+    .fetch_one(&pool)
+    .await
+    .expect("public usage row");
+    assert_eq!(row.0, None);
+    assert_eq!(row.1, "pending");
+}
+
+#[tokio::test]
+async fn public_release_list_rejects_unsupported_language() {
+    let pool = setup_pool().await;
+    let state = setup_state(pool);
+
+    let err = public_list_repo_releases(
+        State(state),
+        Path(("openai".to_owned(), "codex".to_owned())),
+        Query(PublicReleaseQuery {
+            content: None,
+            lang: Some("en-US".to_owned()),
+            source: None,
+            cursor: None,
+            limit: None,
+        }),
+    )
+    .await
+    .expect_err("unsupported language should fail");
+
+    let response = err.into_response();
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    let body = response_json(response).await;
+    assert_eq!(body["error"]["code"], json!("unsupported_language"));
+}
+
+#[tokio::test]
+async fn public_release_list_failed_initial_sync_stays_retryable() {
+    let pool = setup_pool().await;
+    seed_public_release_usage(&pool, Some(42), "failed").await;
+    let state = setup_state(pool);
+
+    let response = public_list_repo_releases(
+        State(state),
+        Path(("openai".to_owned(), "codex".to_owned())),
+        Query(PublicReleaseQuery {
+            content: None,
+            lang: None,
+            source: None,
+            cursor: None,
+            limit: None,
+        }),
+    )
+    .await
+    .expect("failed initial sync should remain retryable");
+
+    assert_eq!(response.status(), StatusCode::ACCEPTED);
+    assert_eq!(
+        response.headers().get(header::RETRY_AFTER),
+        Some(&header::HeaderValue::from_static("60"))
+    );
+    let body = response_json(response).await;
+    assert_eq!(body["status"], json!("pending_sync"));
+    assert_eq!(
+        body["reason"],
+        json!("repository_registered_release_sync_pending")
+    );
+}
+
+#[tokio::test]
+async fn public_release_list_uses_cursor_pagination_without_total_count() {
+    let pool = setup_pool().await;
+    seed_public_release_usage(&pool, Some(42), "ready").await;
+    for idx in 0..4_i64 {
+        let release_id = 120 + idx;
+        let tag = format!("v1.2.{idx}");
+        let published_at = format!("2026-02-2{}T00:00:00Z", 3 - idx);
+        sqlx::query(
+            r#"
+                INSERT INTO repo_releases (
+                  id, repo_id, release_id, node_id, tag_name, name, body,
+                  html_url, published_at, created_at, is_prerelease, is_draft,
+                  updated_at, react_plus1, react_laugh, react_heart,
+                  react_hooray, react_rocket, react_eyes
+                )
+                VALUES (?, 42, ?, ?, ?, ?, '- item', ?, ?, ?, 0, 0, ?, 0, 0, 0, 0, 0, 0)
+                "#,
+        )
+        .bind(format!("repo-release-42-{release_id}"))
+        .bind(release_id)
+        .bind(format!("node-{release_id}"))
+        .bind(tag.as_str())
+        .bind(format!("Release {tag}"))
+        .bind(format!(
+            "https://github.com/openai/codex/releases/tag/{tag}"
+        ))
+        .bind(published_at.as_str())
+        .bind(published_at.as_str())
+        .bind(published_at.as_str())
+        .execute(&pool)
+        .await
+        .expect("seed public release page item");
+    }
+    let state = setup_state(pool);
+
+    let first = public_list_repo_releases(
+        State(state.clone()),
+        Path(("openai".to_owned(), "codex".to_owned())),
+        Query(PublicReleaseQuery {
+            content: None,
+            lang: None,
+            source: None,
+            cursor: None,
+            limit: Some(2),
+        }),
+    )
+    .await
+    .expect("first public page");
+    assert_eq!(first.status(), StatusCode::OK);
+    let first_body = response_json(first).await;
+    assert_eq!(first_body["items"].as_array().unwrap().len(), 2);
+    assert!(first_body.get("total").is_none());
+    let cursor = first_body["next_cursor"]
+        .as_str()
+        .expect("first page cursor")
+        .to_owned();
+
+    let second = public_list_repo_releases(
+        State(state),
+        Path(("openai".to_owned(), "codex".to_owned())),
+        Query(PublicReleaseQuery {
+            content: None,
+            lang: None,
+            source: None,
+            cursor: Some(cursor),
+            limit: Some(2),
+        }),
+    )
+    .await
+    .expect("second public page");
+    let second_body = response_json(second).await;
+    assert_eq!(second_body["items"].as_array().unwrap().len(), 2);
+    assert_eq!(second_body["next_cursor"], json!(null));
+}
+
+#[tokio::test]
+async fn public_release_detail_reads_shared_repo_releases_cache() {
+    let pool = setup_pool().await;
+    seed_public_release_usage(&pool, Some(42), "ready").await;
+    seed_repo_release(&pool, 42, 120).await;
+    let state = setup_state(pool);
+
+    let response = public_get_repo_release_detail(
+        State(state),
+        Path(("openai".to_owned(), "codex".to_owned(), "v1.2.3".to_owned())),
+        Query(PublicReleaseQuery {
+            content: Some("all".to_owned()),
+            lang: Some("zh-CN".to_owned()),
+            source: None,
+            cursor: None,
+            limit: None,
+        }),
+    )
+    .await
+    .expect("public detail");
+
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = response_json(response).await;
+    assert_eq!(body["release_id"], json!("120"));
+    assert_eq!(body["repo_full_name"], json!("openai/codex"));
+    assert_eq!(body["tag_name"], json!("v1.2.3"));
+    assert_eq!(body["body"], json!("- item"));
+}
+
+#[tokio::test]
+async fn public_release_detail_excludes_draft_release_cache() {
+    let pool = setup_pool().await;
+    seed_public_release_usage(&pool, Some(42), "ready").await;
+    seed_repo_release(&pool, 42, 120).await;
+    sqlx::query("UPDATE repo_releases SET is_draft = 1 WHERE release_id = 120")
+        .execute(&pool)
+        .await
+        .expect("mark draft release");
+    let state = setup_state(pool);
+
+    let err = public_get_repo_release_detail(
+        State(state),
+        Path(("openai".to_owned(), "codex".to_owned(), "v1.2.3".to_owned())),
+        Query(PublicReleaseQuery {
+            content: Some("all".to_owned()),
+            lang: Some("zh-CN".to_owned()),
+            source: None,
+            cursor: None,
+            limit: None,
+        }),
+    )
+    .await
+    .expect_err("draft release must not be publicly returned");
+
+    let response = err.into_response();
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    let body = response_json(response).await;
+    assert_eq!(
+        body["error"]["code"],
+        json!("release_not_found_or_not_cached")
+    );
+}
+
+#[tokio::test]
+async fn public_release_list_blocks_inaccessible_repo_even_with_cache() {
+    let pool = setup_pool().await;
+    seed_public_release_usage(&pool, Some(42), "inaccessible").await;
+    seed_repo_release(&pool, 42, 120).await;
+    let state = setup_state(pool);
+
+    let err = public_list_repo_releases(
+        State(state),
+        Path(("openai".to_owned(), "codex".to_owned())),
+        Query(PublicReleaseQuery {
+            content: None,
+            lang: None,
+            source: None,
+            cursor: None,
+            limit: None,
+        }),
+    )
+    .await
+    .expect_err("inaccessible public repo should not serve cached releases");
+
+    let response = err.into_response();
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    let body = response_json(response).await;
+    assert_eq!(body["error"]["code"], json!("repository_inaccessible"));
+}
+
+#[tokio::test]
+async fn public_release_list_blocks_unresolved_inaccessible_repo() {
+    let pool = setup_pool().await;
+    seed_public_release_usage(&pool, None, "inaccessible").await;
+    seed_star(&pool, 42).await;
+    seed_repo_release(&pool, 42, 120).await;
+    let state = setup_state(pool);
+
+    let err = public_list_repo_releases(
+        State(state),
+        Path(("openai".to_owned(), "codex".to_owned())),
+        Query(PublicReleaseQuery {
+            content: None,
+            lang: None,
+            source: None,
+            cursor: None,
+            limit: None,
+        }),
+    )
+    .await
+    .expect_err("unresolved inaccessible public repo should not stay pending");
+
+    let response = err.into_response();
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    let body = response_json(response).await;
+    assert_eq!(body["error"]["code"], json!("repository_inaccessible"));
+}
+
+#[tokio::test]
+async fn admin_delete_public_release_usage_cleans_cache_when_repo_is_unused() {
+    let pool = setup_pool().await;
+    let usage_id = seed_public_release_usage(&pool, Some(42), "ready").await;
+    seed_repo_release(&pool, 42, 120).await;
+    sqlx::query(
+            r#"
+            INSERT INTO ai_translations (
+              id, user_id, entity_type, entity_id, lang, source_hash,
+              title, summary, created_at, updated_at, status
+            )
+            VALUES
+              ('translation-release-detail-120', ?, 'release_detail', '120', 'zh-CN', 'hash', '标题', '摘要', '2026-02-23T00:00:00Z', '2026-02-23T00:00:00Z', 'ready'),
+              ('translation-release-smart-120', ?, 'release_smart', '120', 'zh-CN', 'hash', '标题', '摘要', '2026-02-23T00:00:00Z', '2026-02-23T00:00:00Z', 'ready')
+            "#,
+        )
+        .bind(test_user_id(1))
+        .bind(test_user_id(1))
+        .execute(&pool)
+        .await
+        .expect("seed ai translations");
+    sqlx::query(
+            r#"
+            INSERT INTO repo_release_sync_state (
+              repo_id, etag, last_modified, last_success_at, last_attempt_at,
+              last_not_modified_at, last_error_text, backoff_until, updated_at
+            )
+            VALUES (42, 'etag-stale', 'Mon, 23 Feb 2026 00:00:00 GMT', '2026-02-23T00:00:00Z', '2026-02-23T00:00:00Z', NULL, NULL, NULL, '2026-02-23T00:00:00Z')
+            "#,
+        )
+        .execute(&pool)
+        .await
+        .expect("seed release sync state");
+    sqlx::query(r#"UPDATE users SET is_admin = 1 WHERE id = ?"#)
+        .bind(test_user_id(1))
+        .execute(&pool)
+        .await
+        .expect("mark user admin");
+    let state = setup_state(pool.clone());
+
+    let Json(list) =
+        admin_delete_public_release_repo(State(state), AdminUser(test_user_id(1)), Path(usage_id))
+            .await
+            .expect("delete public release usage");
+
+    assert_eq!(list.total, 0);
+    assert_eq!(
+        list.cache_cleanup
+            .as_ref()
+            .map(|cleanup| cleanup.deleted_release_count),
+        Some(1)
+    );
+    assert_eq!(
+        list.cache_cleanup
+            .as_ref()
+            .map(|cleanup| cleanup.deleted_ai_cache_count),
+        Some(2)
+    );
+    let release_count =
+        sqlx::query_scalar::<_, i64>("SELECT COUNT(*) FROM repo_releases WHERE release_id = 120")
+            .fetch_one(&pool)
+            .await
+            .expect("count repo releases");
+    assert_eq!(release_count, 0);
+    let ai_cache_count = sqlx::query_scalar::<_, i64>(
+        "SELECT COUNT(*) FROM ai_translations WHERE entity_id = '120'",
+    )
+    .fetch_one(&pool)
+    .await
+    .expect("count ai cache");
+    assert_eq!(ai_cache_count, 0);
+    let sync_state_count = sqlx::query_scalar::<_, i64>(
+        "SELECT COUNT(*) FROM repo_release_sync_state WHERE repo_id = 42",
+    )
+    .fetch_one(&pool)
+    .await
+    .expect("count release sync state");
+    assert_eq!(sync_state_count, 0);
+}
+
+#[tokio::test]
+async fn admin_delete_public_release_usage_keeps_cache_when_user_visibility_uses_repo() {
+    let pool = setup_pool().await;
+    let usage_id = seed_public_release_usage(&pool, Some(42), "ready").await;
+    seed_repo_release(&pool, 42, 120).await;
+    seed_star(&pool, 42).await;
+    sqlx::query(r#"UPDATE users SET is_admin = 1 WHERE id = ?"#)
+        .bind(test_user_id(1))
+        .execute(&pool)
+        .await
+        .expect("mark user admin");
+    let state = setup_state(pool.clone());
+
+    let Json(list) =
+        admin_delete_public_release_repo(State(state), AdminUser(test_user_id(1)), Path(usage_id))
+            .await
+            .expect("delete public release usage");
+
+    assert_eq!(list.total, 0);
+    assert_eq!(
+        list.cache_cleanup
+            .as_ref()
+            .and_then(|cleanup| cleanup.skipped_reason.as_deref()),
+        Some("still_used_by_user_release_visibility")
+    );
+    let release_count =
+        sqlx::query_scalar::<_, i64>("SELECT COUNT(*) FROM repo_releases WHERE release_id = 120")
+            .fetch_one(&pool)
+            .await
+            .expect("count repo releases");
+    assert_eq!(release_count, 1);
+}
+
+#[tokio::test]
+async fn admin_delete_public_release_usage_keeps_cache_when_brief_uses_release() {
+    let pool = setup_pool().await;
+    let usage_id = seed_public_release_usage(&pool, Some(42), "ready").await;
+    seed_repo_release(&pool, 42, 120).await;
+    seed_brief(&pool, test_user_id(1).as_str(), "2026-02-23", "brief").await;
+    sqlx::query(
+        r#"
+            INSERT INTO brief_release_memberships (
+              brief_id, release_id, release_ts_utc, ordinal, created_at
+            )
+            VALUES ('brief-2026-02-23', 120, '2026-02-23T00:00:00Z', 0, '2026-02-23T00:00:00Z')
+            "#,
+    )
+    .execute(&pool)
+    .await
+    .expect("seed brief release membership");
+    sqlx::query(r#"UPDATE users SET is_admin = 1 WHERE id = ?"#)
+        .bind(test_user_id(1))
+        .execute(&pool)
+        .await
+        .expect("mark user admin");
+    let state = setup_state(pool.clone());
+
+    let Json(list) =
+        admin_delete_public_release_repo(State(state), AdminUser(test_user_id(1)), Path(usage_id))
+            .await
+            .expect("delete public release usage");
+
+    assert_eq!(list.total, 0);
+    assert_eq!(
+        list.cache_cleanup
+            .as_ref()
+            .and_then(|cleanup| cleanup.skipped_reason.as_deref()),
+        Some("still_used_by_brief_membership")
+    );
+    let release_count =
+        sqlx::query_scalar::<_, i64>("SELECT COUNT(*) FROM repo_releases WHERE release_id = 120")
+            .fetch_one(&pool)
+            .await
+            .expect("count repo releases");
+    assert_eq!(release_count, 1);
+    let membership_count = sqlx::query_scalar::<_, i64>(
+        "SELECT COUNT(*) FROM brief_release_memberships WHERE release_id = 120",
+    )
+    .fetch_one(&pool)
+    .await
+    .expect("count brief memberships");
+    assert_eq!(membership_count, 1);
+}
+
+#[tokio::test]
+async fn get_release_detail_by_repo_tag_keeps_literal_percent_sequences() {
+    let pool = setup_pool().await;
+    seed_repo_release(&pool, 42, 120).await;
+    seed_star(&pool, 42).await;
+    sqlx::query(
+        r#"
+            UPDATE repo_releases
+            SET tag_name = ?, name = ?, html_url = ?
+            WHERE release_id = ?
+            "#,
+    )
+    .bind("release%2F2026.04")
+    .bind("Release release%2F2026.04")
+    .bind("https://github.com/openai/codex/releases/tag/release%252F2026.04")
+    .bind(120_i64)
+    .execute(&pool)
+    .await
+    .expect("update release tag");
+    let state = setup_state(pool);
+
+    let Json(detail) = get_release_detail_by_repo_tag(
+        State(state),
+        AuthedUser(test_user_id(1)),
+        Path((
+            "openai".to_owned(),
+            "codex".to_owned(),
+            "release%2F2026.04".to_owned(),
+        )),
+    )
+    .await
+    .expect("get release detail by repo tag");
+
+    assert_eq!(detail.release_id, "120");
+    assert_eq!(detail.tag_name, "release%2F2026.04");
+    assert_eq!(
+        detail.html_url,
+        "https://github.com/openai/codex/releases/tag/release%252F2026.04"
+    );
+}
+
+#[tokio::test]
+async fn list_briefs_resolves_canonical_repo_tag_links_across_multiple_fallback_rows() {
+    let pool = setup_pool().await;
+    let user_id = test_user_id(1);
+    seed_repo_release(&pool, 42, 120).await;
+    sqlx::query(
+        r#"
+            INSERT INTO repo_releases (
+              id,
+              repo_id,
+              release_id,
+              node_id,
+              tag_name,
+              name,
+              body,
+              html_url,
+              published_at,
+              created_at,
+              is_prerelease,
+              is_draft,
+              updated_at,
+              react_plus1,
+              react_laugh,
+              react_heart,
+              react_hooray,
+              react_rocket,
+              react_eyes
+            )
+            VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, 0, 0, ?, 0, 0, 0, 0, 0, 0)
+            "#,
+    )
+    .bind("repo-release-42-121")
+    .bind(42_i64)
+    .bind(121_i64)
+    .bind("node-121")
+    .bind("v1.2.4")
+    .bind("Release v1.2.4")
+    .bind("- item 121")
+    .bind("https://github.com/openai/codex/releases/tag/v1.2.4")
+    .bind("2026-02-24T08:00:00Z")
+    .bind("2026-02-24T08:00:00Z")
+    .bind("2026-02-24T08:00:00Z")
+    .execute(&pool)
+    .await
+    .expect("seed second shared repo release");
+    seed_brief(
+        &pool,
+        user_id.as_str(),
+        "2026-02-23",
+        "- [v1.2.3](/openai/codex/releases/tag/v1.2.3?from=briefs)",
+    )
+    .await;
+    seed_brief(
+        &pool,
+        user_id.as_str(),
+        "2026-02-24",
+        "- [v1.2.4](/openai/codex/releases/tag/v1.2.4?from=briefs)",
+    )
+    .await;
+    let state = setup_state(pool);
+
+    let Json(items) = list_briefs(State(state), AuthedUser(test_user_id(1)))
+        .await
+        .expect("list briefs");
+
+    assert_eq!(items.len(), 2);
+    assert_eq!(items[0].date, "2026-02-24");
+    assert_eq!(items[0].release_ids, vec!["121".to_owned()]);
+    assert_eq!(items[1].date, "2026-02-23");
+    assert_eq!(items[1].release_ids, vec!["120".to_owned()]);
+}
+
+#[test]
+fn brief_translation_source_hash_changes_with_content() {
+    let original = brief_translation_source_hash("## 今日\n- 发布 v1.2.3\n");
+    assert_eq!(
+        original,
+        brief_translation_source_hash("## 今日\r\n- 发布 v1.2.3\r\n")
+    );
+    assert_ne!(
+        original,
+        brief_translation_source_hash("## 今日\n- 发布 v1.2.4\n")
+    );
+}
+
+#[tokio::test]
+async fn get_brief_sources_returns_recorded_releases_in_order() {
+    let pool = setup_pool().await;
+    seed_star(&pool, 77).await;
+    seed_repo_release(&pool, 77, 701).await;
+    seed_repo_release(&pool, 77, 702).await;
+    seed_brief(&pool, &test_user_id(1), "2026-02-23", "## brief").await;
+    seed_brief(&pool, &test_user_id(1), "2026-02-22", "## legacy").await;
+    sqlx::query(r#"UPDATE briefs SET sources_tracked = 1 WHERE id = 'brief-2026-02-23'"#)
+        .execute(&pool)
+        .await
+        .expect("mark sources tracked");
+    for (release_id, ordinal, included) in [(702_i64, 0_i64, 1_i64), (701, 1, 0)] {
+        sqlx::query(
+            r#"
+                INSERT INTO brief_sources (
+                  brief_id, release_id, section, ordinal, included, created_at
+                )
+                VALUES ('brief-2026-02-23', ?, 'openai/codex', ?, ?, '2026-02-23T08:00:00Z')
+                "#,
+        )
+        .bind(release_id)
+        .bind(ordinal)
+        .bind(included)
+        .execute(&pool)
+        .await
+        .expect("seed brief source");
+    }
+    let state = setup_state(pool);
+
+    let Json(sources) = get_brief_sources(
+        State(state.clone()),
+        AuthedUser(test_user_id(1)),
+        Path("2026-02-23".to_owned()),
+    )
+    .await
+    .expect("load brief sources");
+    assert!(sources.sources_available);
+    assert_eq!(sources.brief_id, "brief-2026-02-23");
+    let listed = sources
+        .items
+        .iter()
+        .map(|source| {
+            (
+                source.item.id.as_str(),
+                source.section.as_str(),
+                source.included,
+            )
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(
+        listed,
+        vec![
+            ("702", "openai/codex", true),
+            ("701", "openai/codex", false),
+        ]
+    );
+    assert_eq!(sources.items[0].item.kind, "release");
+    assert_eq!(
+        sources.items[0].item.title.as_deref(),
+        Some("Release v1.2.3")
+    );
+
+    let Json(legacy) = get_brief_sources(
+        State(state.clone()),
+        AuthedUser(test_user_id(1)),
+        Path("2026-02-22".to_owned()),
+    )
+    .await
+    .expect("load legacy brief sources");
+    assert!(!legacy.sources_available);
+    assert!(legacy.items.is_empty());
+
+    let err = get_brief_sources(
+        State(state),
+        AuthedUser(test_user_id(1)),
+        Path("2026-02-21".to_owned()),
+    )
+    .await
+    .expect_err("missing brief");
+    assert_eq!(err.code(), "not_found");
+}
+
+#[tokio::test]
+async fn translate_brief_reports_disabled_without_ai() {
+    let pool = setup_pool().await;
+    seed_brief(&pool, &test_user_id(1), "2026-02-23", "- 发布 v1.2.3").await;
+    let state = setup_state(pool.clone());
+
+    let translated = translate_brief_for_user(state.as_ref(), &test_user_id(1), "2026-02-23", "en")
+        .await
+        .expect("translate brief without ai");
+    assert_eq!(translated.status, "disabled");
+    assert_eq!(translated.lang, "en");
+    assert!(translated.summary.is_none());
+
+    let err = translate_brief_for_user(state.as_ref(), &test_user_id(1), "2026-02-23", "fr")
+        .await
+        .expect_err("unsupported lang");
+    assert_eq!(err.code(), "bad_request");
+    let err = translate_brief_for_user(state.as_ref(), &test_user_id(1), "2026-02-24", "en")
+        .await
+        .expect_err("missing brief");
+    assert_eq!(err.code(), "not_found");
+}
+
+#[tokio::test]
+async fn translate_brief_caches_per_lang_and_goes_stale_when_brief_changes() {
+    let pool = setup_pool().await;
+    let user_id = test_user_id(1);
+    seed_brief(&pool, &user_id, "2026-02-23", "## 今日\n- 发布 `v1.2.3`\n").await;
+    let calls = Arc::new(AtomicUsize::new(0));
+    let base_url = spawn_test_ai_server(Router::new().route(
+        "/chat/completions",
+        post({
+            let calls = calls.clone();
+            move |Json(payload): Json<Value>| {
+                let calls = calls.clone();
+                async move {
+                    calls.fetch_add(1, Ordering::SeqCst);
+                    let system_prompt = payload["messages"][0]["content"]
+                        .as_str()
+                        .unwrap_or_default();
+                    assert!(system_prompt.contains("English"));
+                    Json(json!({
+                        "choices": [{"message": {"content": "## Today\n- Released `v1.2.3`\n"}}],
+                        "usage": {"prompt_tokens": 10, "completion_tokens": 10, "total_tokens": 20}
+                    }))
+                }
+            }
+        }),
+    ))
+    .await;
+    let state = setup_state_with_ai_base_url(pool.clone(), base_url);
+
+    let translated = translate_brief_for_user(state.as_ref(), &user_id, "2026-02-23", "en")
+        .await
+        .expect("translate brief");
+    assert_eq!(translated.status, "ready");
+    assert_eq!(
+        translated.summary.as_deref(),
+        Some("## Today\n- Released `v1.2.3`\n")
+    );
+    translate_brief_for_user(state.as_ref(), &user_id, "2026-02-23", "en")
+        .await
+        .expect("cached brief translation");
+    assert_eq!(calls.load(Ordering::SeqCst), 1);
+
+    let Json(items) = list_briefs(State(state.clone()), AuthedUser(test_user_id(1)))
+        .await
+        .expect("list briefs");
+    assert_eq!(items[0].translations.len(), 1);
+    assert_eq!(items[0].translations[0].lang, "en");
+    assert_eq!(items[0].translations[0].status, "ready");
+
+    sqlx::query(r#"UPDATE briefs SET content_markdown = ? WHERE user_id = ?"#)
+        .bind("## 今日\n- 发布 `v1.2.4`\n")
+        .bind(user_id.as_str())
+        .execute(&pool)
+        .await
+        .expect("regenerate brief");
+    let Json(items) = list_briefs(State(state.clone()), AuthedUser(test_user_id(1)))
+        .await
+        .expect("list briefs after regenerate");
+    assert_eq!(items[0].translations[0].status, "stale");
+
+    translate_brief_for_user(state.as_ref(), &user_id, "2026-02-23", "en")
+        .await
+        .expect("retranslate regenerated brief");
+    assert_eq!(calls.load(Ordering::SeqCst), 2);
+}
+
+#[tokio::test]
+async fn list_briefs_dedupes_repeated_markdown_fallback_release_matches() {
+    let pool = setup_pool().await;
+    let user_id = test_user_id(1);
+    seed_repo_release(&pool, 42, 120).await;
+    seed_brief(
+        &pool,
+        user_id.as_str(),
+        "2026-02-23",
+        "\
+- [legacy](/?release=120&tab=briefs)\n\
+- [canonical](/openai/codex/releases/tag/v1.2.3?from=briefs)\n\
+",
+    )
+    .await;
+    let state = setup_state(pool);
+
+    let Json(items) = list_briefs(State(state), AuthedUser(test_user_id(1)))
+        .await
+        .expect("list briefs");
+
+    assert_eq!(items.len(), 1);
+    assert_eq!(items[0].release_ids, vec!["120".to_owned()]);
+    assert_eq!(items[0].release_count, 1);
+}
+
+#[tokio::test]
+async fn list_briefs_batches_markdown_fallback_resolution_under_sqlite_bind_limit() {
+    let pool = setup_pool().await;
+    let user_id = test_user_id(1);
+
+    let mut first_brief_markdown = String::new();
+    let mut second_brief_markdown = String::new();
+    let split_index = BRIEF_RELEASE_REF_LOCATOR_BATCH_LIMIT / 2;
+    let total_refs = BRIEF_RELEASE_REF_LOCATOR_BATCH_LIMIT + 40;
+
+    for index in 0..total_refs {
+        let release_id = 10_000 + index as i64;
+        let tag = format!("v1.2.{index}");
+        sqlx::query(
+            r#"
+                INSERT INTO repo_releases (
+                  id,
+                  repo_id,
+                  release_id,
+                  node_id,
+                  tag_name,
+                  name,
+                  body,
+                  html_url,
+                  published_at,
+                  created_at,
+                  is_prerelease,
+                  is_draft,
+                  updated_at,
+                  react_plus1,
+                  react_laugh,
+                  react_heart,
+                  react_hooray,
+                  react_rocket,
+                  react_eyes
+                )
+                VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, 0, 0, ?, 0, 0, 0, 0, 0, 0)
+                "#,
+        )
+        .bind(format!("repo-release-42-{release_id}"))
+        .bind(42_i64)
+        .bind(release_id)
+        .bind(format!("node-{release_id}"))
+        .bind(&tag)
+        .bind(format!("Release {tag}"))
+        .bind(format!("- item {release_id}"))
+        .bind(format!(
+            "https://github.com/openai/codex/releases/tag/{tag}"
+        ))
+        .bind("2026-02-24T08:00:00Z")
+        .bind("2026-02-24T08:00:00Z")
+        .bind("2026-02-24T08:00:00Z")
+        .execute(&pool)
+        .await
+        .expect("seed repo release");
+
+        let line = format!("- [{tag}](/openai/codex/releases/tag/{tag}?from=briefs)\n");
+        if index < split_index {
+            first_brief_markdown.push_str(&line);
+        } else {
+            second_brief_markdown.push_str(&line);
+        }
+    }
+
+    seed_brief(&pool, user_id.as_str(), "2026-02-23", &first_brief_markdown).await;
+    seed_brief(
+        &pool,
+        user_id.as_str(),
+        "2026-02-24",
+        &second_brief_markdown,
+    )
+    .await;
+    let state = setup_state(pool);
+
+    let Json(items) = list_briefs(State(state), AuthedUser(test_user_id(1)))
+        .await
+        .expect("list briefs");
+
+    assert_eq!(items.len(), 2);
+    assert_eq!(items[0].release_count, total_refs - split_index);
+    assert_eq!(items[1].release_count, split_index);
+    assert_eq!(
+        items[0].release_ids.first().map(String::as_str),
+        Some("10150")
+    );
+    assert_eq!(
+        items[0].release_ids.last().map(String::as_str),
+        Some("10339")
+    );
+    assert_eq!(
+        items[1].release_ids.first().map(String::as_str),
+        Some("10000")
+    );
+    assert_eq!(
+        items[1].release_ids.last().map(String::as_str),
+        Some("10149")
+    );
+}
+
+#[tokio::test]
+async fn get_release_detail_rejects_owned_only_repo_when_opt_in_disabled() {
+    let pool = setup_pool().await;
+    seed_repo_release(&pool, 42, 120).await;
+    seed_owned_repo_baseline(&pool, 42, "IvanLi-CN/octo-rill").await;
+    let state = setup_state(pool);
+
+    let err = get_release_detail(
+        State(state),
+        AuthedUser(test_user_id(1)),
+        Path("120".to_owned()),
+    )
+    .await
+    .expect_err("owned-only release should stay hidden");
+
+    assert_eq!(err.code(), "not_found");
+}
+
+#[tokio::test]
+async fn get_release_detail_reads_owned_only_repo_when_opted_in() {
+    let pool = setup_pool().await;
+    seed_repo_release(&pool, 42, 120).await;
+    seed_owned_repo_baseline(&pool, 42, "IvanLi-CN/octo-rill").await;
+    set_include_own_releases(&pool, true).await;
+    let state = setup_state(pool);
+
+    let Json(detail) = get_release_detail(
+        State(state),
+        AuthedUser(test_user_id(1)),
+        Path("120".to_owned()),
+    )
+    .await
+    .expect("get owned release detail");
+
+    assert_eq!(
+        detail.repo_full_name.as_deref(),
+        Some("IvanLi-CN/octo-rill")
+    );
+    let repo_visual = detail.repo_visual.expect("repo visual");
+    assert_eq!(
+        repo_visual.owner_avatar_url.as_deref(),
+        Some("https://avatars.githubusercontent.com/u/30215105")
+    );
+    assert_eq!(
+        repo_visual.open_graph_image_url.as_deref(),
+        Some("https://repository-images.githubusercontent.com/30215105/octo-rill")
+    );
+    assert!(repo_visual.uses_custom_open_graph_image);
+}
+
+#[tokio::test]
+async fn get_release_detail_allows_historical_brief_link_without_current_star() {
+    let pool = setup_pool().await;
+    let user_id = test_user_id(1);
+    seed_repo_release(&pool, 42, 120).await;
+    seed_brief(
+        &pool,
+        user_id.as_str(),
+        "2026-02-23",
+        "- [v1.2.3](/?tab=briefs&release=120)",
+    )
+    .await;
+    let state = setup_state(pool);
+
+    let Json(detail) = get_release_detail(
+        State(state),
+        AuthedUser(test_user_id(1)),
+        Path("120".to_owned()),
+    )
+    .await
+    .expect("get release detail from brief link");
+
+    assert_eq!(detail.release_id, "120");
+    assert_eq!(detail.repo_full_name.as_deref(), Some("openai/codex"));
+    assert!(detail.repo_visual.is_none());
+    assert!(detail.repo_owner_login.is_none());
+    assert!(detail.repo_html_url.is_none());
+}
+
+#[tokio::test]
+async fn get_release_detail_allows_content_refresh_failed_brief_link_without_membership() {
+    let pool = setup_pool().await;
+    let user_id = test_user_id(1);
+    seed_repo_release(&pool, 42, 120).await;
+    sqlx::query(
+        r#"
+            INSERT INTO briefs (
+              id, user_id, date, generation_source, content_markdown, created_at, updated_at
+            )
+            VALUES (?, ?, ?, ?, ?, ?, ?)
+            "#,
+    )
+    .bind("brief-content-refresh-failed")
+    .bind(user_id.as_str())
+    .bind("2026-02-23")
+    .bind("content_refresh_failed")
+    .bind("- [v1.2.3](/?tab=briefs&release=120)")
+    .bind("2026-02-23T08:00:00Z")
+    .bind("2026-02-23T08:00:00Z")
+    .execute(&pool)
+    .await
+    .expect("insert failed refresh brief");
+    let state = setup_state(pool);
+
+    let Json(detail) = get_release_detail(
+        State(state),
+        AuthedUser(test_user_id(1)),
+        Path("120".to_owned()),
+    )
+    .await
+    .expect("get release detail from failed refresh brief link");
+
+    assert_eq!(detail.release_id, "120");
+    assert_eq!(detail.repo_full_name.as_deref(), Some("openai/codex"));
+    assert!(detail.repo_visual.is_none());
+}
+
+#[tokio::test]
+async fn translate_release_rejects_malformed_json_with_error_envelope() {
+    use tower::ServiceExt;
+
+    let pool = setup_pool().await;
+    let app =
+        Router::new()
+            .route("/api/translate/release", post(translate_release))
+            .with_state(setup_state(pool))
+            .layer(axum::middleware::from_fn(
+                |session: Session,
+                 request: axum::extract::Request,
+                 next: axum::middleware::Next| async move {
+                    session
+                        .insert("user_id", test_user_id(1))
+                        .await
+                        .expect("insert session user id");
+                    next.run(request).await
+                },
+            ))
+            .layer(tower_sessions::SessionManagerLayer::new(
+                MemoryStore::default(),
+            ));
+    let response = app
+        .oneshot(
+            axum::http::Request::builder()
+                .method(axum::http::Method::POST)
+                .uri("/api/translate/release")
+                .header(header::CONTENT_TYPE, "application/json")
+                .body(axum::body::Body::from(r#"{"release_id":"#))
+                .expect("build request"),
+        )
+        .await
+        .expect("translate release response");
+
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    let body = to_bytes(response.into_body(), usize::MAX)
+        .await
+        .expect("read body");
+    let body: Value = serde_json::from_slice(&body).expect("json error body");
+    assert_eq!(body["ok"], json!(false));
+    assert_eq!(body["error"]["code"], "invalid_json");
+    assert!(body["error"]["message"].as_str().is_some());
+}
+
+#[tokio::test]
+async fn translate_release_detail_allows_historical_brief_link_without_current_star() {
+    let pool = setup_pool().await;
+    let user_id = test_user_id(1);
+    seed_repo_release(&pool, 42, 120).await;
+    seed_brief(
+        &pool,
+        user_id.as_str(),
+        "2026-02-23",
+        "- [v1.2.3](/?tab=briefs&release=120)",
+    )
+    .await;
+    let state = setup_state_with_ai(pool.clone());
+    let source_hash = release_detail_source_hash("openai/codex", "Release v1.2.3", "- item");
+    seed_release_detail_translation(
+        &pool,
+        user_id.as_str(),
+        "120",
+        source_hash.as_str(),
+        Some("版本 v1.2.3"),
+        Some("- 条目"),
+    )
+    .await;
+
+    let translated = translate_release_detail_for_user(
+        state.as_ref(),
+        user_id.as_str(),
+        "120",
+        ReleaseDetailTranslationMode::Full,
+    )
+    .await
+    .expect("translate release detail from brief link");
+
+    assert_eq!(translated.status, "ready");
+    assert_eq!(translated.title.as_deref(), Some("版本 v1.2.3"));
+    assert_eq!(translated.summary.as_deref(), Some("- 条目"));
+}
+
+#[tokio::test]
+async fn translate_release_detail_accepts_fenced_markdown_chunk_output() {
+    let pool = setup_pool().await;
+    let user_id = test_user_id(1);
+    seed_repo_release(&pool, 42, 120).await;
+    seed_star(&pool, 42).await;
+    sqlx::query(
+        r#"
+            UPDATE repo_releases
+            SET body = ?, name = 'Release v1.2.3', tag_name = 'v1.2.3'
+            WHERE release_id = ?
+            "#,
+    )
+    .bind("- keep `code`\n")
+    .bind(120_i64)
+    .execute(&pool)
+    .await
+    .expect("update release detail body");
+
+    let base_url = spawn_test_ai_server(Router::new().route(
+        "/chat/completions",
+        post(move |Json(payload): Json<Value>| async move {
+            let system_prompt = payload["messages"][0]["content"]
+                .as_str()
+                .unwrap_or_default();
+            let content = if system_prompt.contains("只把 GitHub Release 标题翻译成自然中文")
+            {
+                "版本 v1.2.3".to_owned()
+            } else {
+                "```markdown\n- 保留 `code`\n```".to_owned()
+            };
+            let response = serde_json::json!({
+                "choices": [{"message": {"content": content}}],
+                "usage": {"prompt_tokens": 10, "completion_tokens": 10, "total_tokens": 20}
+            });
+            (
+                StatusCode::OK,
+                [(header::CONTENT_TYPE, "application/json")],
+                Json(response),
+            )
+        }),
+    ))
+    .await;
+    let state = setup_state_with_ai_base_url(pool.clone(), base_url);
+
+    let translated = translate_release_detail_for_user(
+        state.as_ref(),
+        user_id.as_str(),
+        "120",
+        ReleaseDetailTranslationMode::Full,
+    )
+    .await
+    .expect("translate release detail");
+
+    assert_eq!(translated.status, "ready");
+    assert_eq!(translated.title.as_deref(), Some("版本 v1.2.3"));
+    assert_eq!(translated.summary.as_deref(), Some("- 保留 `code`\n"));
+}
+
+#[tokio::test]
+async fn translate_release_detail_caches_each_mode_separately() {
+    let pool = setup_pool().await;
+    let user_id = test_user_id(1);
+    seed_repo_release(&pool, 42, 120).await;
+    seed_star(&pool, 42).await;
+    let state = setup_state_with_ai(pool.clone());
+    let source_hash = release_detail_source_hash("openai/codex", "Release v1.2.3", "- item");
+    seed_release_detail_translation(
+        &pool,
+        user_id.as_str(),
+        "120",
+        source_hash.as_str(),
+        Some("版本 v1.2.3"),
+        Some("- 条目"),
+    )
+    .await;
+    sqlx::query(
+            r#"
+            INSERT INTO ai_translations (
+              id, user_id, entity_type, entity_id, lang, source_hash, status, title, summary, error_text, active_work_item_id, created_at, updated_at
+            )
+            VALUES (?, ?, 'release_detail_summary', '120', 'zh-CN', ?, 'ready', ?, ?, NULL, NULL, ?, ?)
+            "#,
+        )
+        .bind(crate::local_id::generate_local_id())
+        .bind(user_id.as_str())
+        .bind(source_hash.as_str())
+        .bind("版本 v1.2.3")
+        .bind("- 要点")
+        .bind("2026-02-23T00:00:00Z")
+        .bind("2026-02-23T00:00:00Z")
+        .execute(&pool)
+        .await
+        .expect("seed release detail summary");
+
+    let full = translate_release_detail_for_user(
+        state.as_ref(),
+        user_id.as_str(),
+        "120",
+        ReleaseDetailTranslationMode::Full,
+    )
+    .await
+    .expect("full translation from cache");
+    let summary = translate_release_detail_for_user(
+        state.as_ref(),
+        user_id.as_str(),
+        "120",
+        ReleaseDetailTranslationMode::Summary,
+    )
+    .await
+    .expect("summary from cache");
+    assert_eq!(full.summary.as_deref(), Some("- 条目"));
+    assert_eq!(summary.summary.as_deref(), Some("- 要点"));
+
+    let Json(detail) = get_release_detail(
+        State(state.clone()),
+        AuthedUser(test_user_id(1)),
+        Path("120".to_owned()),
+    )
+    .await
+    .expect("get release detail");
+    let translated = detail.translated.expect("translated detail");
+    assert_eq!(translated.mode.as_deref(), Some("full"));
+    assert_eq!(translated.summary.as_deref(), Some("- 条目"));
+
+    sqlx::query("DELETE FROM ai_translations WHERE entity_type = 'release_detail'")
+        .execute(&pool)
+        .await
+        .expect("drop full translation");
+    let Json(detail) = get_release_detail(
+        State(state),
+        AuthedUser(test_user_id(1)),
+        Path("120".to_owned()),
+    )
+    .await
+    .expect("get release detail with summary only");
+    let translated = detail.translated.expect("translated detail");
+    assert_eq!(translated.status, "ready");
+    assert_eq!(translated.mode.as_deref(), Some("summary"));
+    assert_eq!(translated.summary.as_deref(), Some("- 要点"));
+}
+
+#[tokio::test]
+async fn translate_release_detail_short_circuits_without_ai_in_both_modes() {
+    let pool = setup_pool().await;
+    let user_id = test_user_id(1);
+    seed_repo_release(&pool, 42, 120).await;
+    seed_star(&pool, 42).await;
+    let state = setup_state(pool.clone());
+
+    for mode in [
+        ReleaseDetailTranslationMode::Full,
+        ReleaseDetailTranslationMode::Summary,
+    ] {
+        let translated =
+            translate_release_detail_for_user(state.as_ref(), user_id.as_str(), "120", mode)
+                .await
+                .expect("translate release detail without ai");
+        assert_eq!(translated.status, "disabled");
+        assert!(translated.summary.is_none());
+    }
+    let rows = sqlx::query_scalar::<_, i64>("SELECT COUNT(*) FROM ai_translations")
+        .fetch_one(&pool)
+        .await
+        .expect("count translations");
+    assert_eq!(rows, 0);
+}
+
+#[test]
+fn release_detail_translation_mode_rejects_unknown_values() {
+    assert_eq!(
+        ReleaseDetailTranslationMode::parse(None).expect("default mode"),
+        ReleaseDetailTranslationMode::Full
+    );
+    assert_eq!(
+        ReleaseDetailTranslationMode::parse(Some(" summary ")).expect("summary mode"),
+        ReleaseDetailTranslationMode::Summary
+    );
+    let err = ReleaseDetailTranslationMode::parse(Some("digest")).expect_err("unknown mode");
+    assert_eq!(err.code(), "bad_request");
+}
+
+#[tokio::test]
+async fn translate_release_detail_shares_inflight_translation_between_callers() {
+    let pool = setup_pool().await;
+    let user_id = test_user_id(1);
+    seed_repo_release(&pool, 42, 120).await;
+    seed_star(&pool, 42).await;
+    sqlx::query(
+        r#"
+            UPDATE repo_releases
+            SET body = ?, name = 'Release v1.2.3', tag_name = 'v1.2.3'
+            WHERE release_id = ?
+            "#,
+    )
+    .bind("- keep `code`\n")
+    .bind(120_i64)
+    .execute(&pool)
+    .await
+    .expect("update release detail body");
+
+    let title_calls = Arc::new(AtomicUsize::new(0));
+    let route_title_calls = title_calls.clone();
+    let base_url = spawn_test_ai_server(Router::new().route(
+        "/chat/completions",
+        post(move |Json(payload): Json<Value>| {
+            let title_calls = route_title_calls.clone();
+            async move {
+                let system_prompt = payload["messages"][0]["content"]
+                    .as_str()
+                    .unwrap_or_default();
+                let content = if system_prompt.contains("只把 GitHub Release 标题翻译成自然中文")
+                {
+                    title_calls.fetch_add(1, Ordering::SeqCst);
+                    tokio::time::sleep(std::time::Duration::from_millis(200)).await;
+                    "版本 v1.2.3".to_owned()
+                } else {
+                    "- 保留 `code`".to_owned()
+                };
+                let response = serde_json::json!({
+                    "choices": [{"message": {"content": content}}],
+                    "usage": {"prompt_tokens": 10, "completion_tokens": 10, "total_tokens": 20}
+                });
+                (
+                    StatusCode::OK,
+                    [(header::CONTENT_TYPE, "application/json")],
+                    Json(response),
+                )
+            }
+        }),
+    ))
+    .await;
+    let state = setup_state_with_ai_base_url(pool.clone(), base_url);
+
+    let (first, second) = tokio::join!(
+        translate_release_detail_for_user(
+            state.as_ref(),
+            user_id.as_str(),
+            "120",
+            ReleaseDetailTranslationMode::Full
+        ),
+        translate_release_detail_for_user(
+            state.as_ref(),
+            user_id.as_str(),
+            "120",
+            ReleaseDetailTranslationMode::Full
+        ),
+    );
+    let first = first.expect("first translate release detail");
+    let second = second.expect("second translate release detail");
+
+    assert_eq!(title_calls.load(Ordering::SeqCst), 1);
+    assert_eq!(first.status, "ready");
+    assert_eq!(second.status, "ready");
+    assert_eq!(first.summary, second.summary);
+    assert_eq!(first.summary.as_deref(), Some("- 保留 `code`\n"));
+}
+
+#[tokio::test]
+async fn prepare_release_batch_routes_release_to_detail_translation_path() {
+    let pool = setup_pool().await;
+    let user_id = test_user_id(1);
+    seed_repo_release(&pool, 42, 120).await;
+    seed_star(&pool, 42).await;
+    let long_body = "a".repeat(RELEASE_FEED_BODY_MAX_CHARS + 24);
+    sqlx::query(
+        r#"
+            UPDATE repo_releases
+            SET body = ?
+            WHERE release_id = ?
+            "#,
+    )
+    .bind(long_body.as_str())
+    .bind(120_i64)
+    .execute(&pool)
+    .await
+    .expect("update repo release body");
+    let state = setup_state_with_ai(pool);
+
+    let prepared = prepare_release_batch(state.as_ref(), user_id.as_str(), &[120])
+        .await
+        .expect("prepare release batch");
+
+    assert_eq!(
+        prepared
+            .detail_pending_candidates
+            .iter()
+            .map(|candidate| candidate.release_id)
+            .collect::<Vec<_>>(),
+        vec![120]
+    );
+    assert!(prepared.terminal.is_empty());
+}
+
+#[tokio::test]
+async fn prepare_release_batch_upgrades_previous_version_release_hash() {
+    let pool = setup_pool().await;
+    let user_id = test_user_id(1);
+    seed_repo_release(&pool, 42, 120).await;
+    seed_star(&pool, 42).await;
+    let body = release_feed_body(Some("- item"));
+    let previous_hash = previous_release_feed_translation_source_hash(
+        "openai/codex",
+        "Release v1.2.3",
+        body.as_deref(),
+    );
+    let current_hash =
+        release_feed_translation_source_hash("openai/codex", "Release v1.2.3", body.as_deref());
+    assert_ne!(previous_hash, current_hash);
+    sqlx::query(
+            r#"
+            INSERT INTO ai_translations (
+              id, user_id, entity_type, entity_id, lang, source_hash,
+              title, summary, created_at, updated_at, status
+            )
+            VALUES ('translation-release-120', ?, 'release', '120', 'zh-CN', ?, '标题', '摘要', '2026-02-23T00:00:00Z', '2026-02-23T00:00:00Z', 'ready')
+            "#,
+        )
+        .bind(user_id.as_str())
+        .bind(previous_hash.as_str())
+        .execute(&pool)
+        .await
+        .expect("seed previous-version translation");
+    let state = setup_state_with_ai(pool.clone());
+
+    let prepared = prepare_release_batch(state.as_ref(), user_id.as_str(), &[120])
+        .await
+        .expect("prepare release batch");
+
+    assert_eq!(
+        prepared.translated.get(&120),
+        Some(&(Some("标题".to_owned()), Some("摘要".to_owned())))
+    );
+    assert!(prepared.detail_pending_candidates.is_empty());
+    let stored_hash = sqlx::query_scalar::<_, String>(
+        "SELECT source_hash FROM ai_translations WHERE id = 'translation-release-120'",
+    )
+    .fetch_one(&pool)
+    .await
+    .expect("load upgraded hash");
+    assert_eq!(stored_hash, current_hash);
+}
+
+const CHINESE_RELEASE_NOTES: &str = "## 新功能\n\n- 新增 `--watch` 参数，支持在 Windows 上监听配置文件变化\n- 优化了 SQLite 连接池的初始化速度\n\n## 修复\n\n- 修复 macOS 下托盘图标偶尔消失的问题";
+
+async fn set_release_body(pool: &SqlitePool, release_id: i64, body: &str) {
+    sqlx::query(r#"UPDATE repo_releases SET body = ? WHERE release_id = ?"#)
+        .bind(body)
+        .bind(release_id)
+        .execute(pool)
+        .await
+        .expect("update release body");
+}
+
+#[tokio::test]
+async fn prepare_release_batch_serves_chinese_notes_as_is_and_caches_decision() {
+    let pool = setup_pool().await;
+    let user_id = test_user_id(1);
+    seed_repo_release(&pool, 42, 120).await;
+    seed_star(&pool, 42).await;
+    set_release_body(&pool, 120, CHINESE_RELEASE_NOTES).await;
+    let state = setup_state_with_ai(pool.clone());
+
+    let prepared = prepare_release_batch(state.as_ref(), user_id.as_str(), &[120])
+        .await
+        .expect("prepare release batch");
+
+    assert!(prepared.detail_pending_candidates.is_empty());
+    let expected = (
+        Some("Release v1.2.3".to_owned()),
+        Some(CHINESE_RELEASE_NOTES.to_owned()),
+    );
+    assert_eq!(prepared.translated.get(&120), Some(&expected));
+    let (status, summary) = sqlx::query_as::<_, (String, Option<String>)>(
+        r#"
+            SELECT status, summary
+            FROM ai_translations
+            WHERE entity_type = 'release_detail' AND entity_id = '120'
+            "#,
+    )
+    .fetch_one(&pool)
+    .await
+    .expect("load cached original");
+    assert_eq!(status, "ready");
+    assert_eq!(summary.as_deref(), Some(CHINESE_RELEASE_NOTES));
+
+    let again = prepare_release_batch(state.as_ref(), user_id.as_str(), &[120])
+        .await
+        .expect("prepare release batch again");
+    assert!(again.detail_pending_candidates.is_empty());
+    assert_eq!(again.translated.get(&120), Some(&expected));
+
+    let Json(detail) = get_release_detail(
+        State(state),
+        AuthedUser(test_user_id(1)),
+        Path("120".to_owned()),
+    )
+    .await
+    .expect("get release detail");
+    let translated = detail.translated.expect("translated detail");
+    assert_eq!(translated.status, "ready");
+    assert_eq!(translated.detected_lang.as_deref(), Some("zh"));
+    assert_eq!(translated.summary.as_deref(), Some(CHINESE_RELEASE_NOTES));
+}
+
+#[tokio::test]
+async fn prepare_release_batch_still_translates_english_notes_with_cjk_strings() {
+    let pool = setup_pool().await;
+    let user_id = test_user_id(1);
+    seed_repo_release(&pool, 42, 120).await;
+    seed_star(&pool, 42).await;
+    set_release_body(
+            &pool,
+            120,
+            "- Added a Simplified Chinese locale (简体中文) to the settings page\n- Fixed rendering of 日本語 filenames in the download dialog",
+        )
+        .await;
+    let state = setup_state_with_ai(pool);
+
+    let prepared = prepare_release_batch(state.as_ref(), user_id.as_str(), &[120])
+        .await
+        .expect("prepare release batch");
+
+    assert_eq!(
+        prepared
+            .detail_pending_candidates
+            .iter()
+            .map(|candidate| candidate.release_id)
+            .collect::<Vec<_>>(),
+        vec![120]
+    );
+    assert!(prepared.translated.is_empty());
+}
+
+#[tokio::test]
+async fn translate_notifications_batch_keeps_chinese_titles_without_llm() {
+    let pool = setup_pool().await;
+    let user_id = test_user_id(1);
+    seed_notification(&pool, user_id.as_str(), "t1", "2026-02-23T00:00:00Z").await;
+    sqlx::query("UPDATE notifications SET subject_title = ? WHERE thread_id = 't1'")
+        .bind("修复设置页面在深色模式下的显示问题")
+        .execute(&pool)
+        .await
+        .expect("update notification title");
+    // The AI base URL is unreachable, so any LLM call would end in `error`.
+    let state = setup_state_with_ai(pool);
+
+    let items = translate_notifications_batch_internal(
+        state.as_ref(),
+        user_id.as_str(),
+        &["t1".to_owned()],
+    )
+    .await
+    .expect("translate notifications");
+
+    assert_eq!(items.len(), 1);
+    assert_eq!(items[0].status, "ready");
+    assert_eq!(
+        items[0].title.as_deref(),
+        Some("修复设置页面在深色模式下的显示问题")
+    );
+    assert_eq!(items[0].summary, None);
+}
+
+#[tokio::test]
+async fn delete_translations_forces_release_detail_retranslation() {
+    let pool = setup_pool().await;
+    let user_id = test_user_id(1);
+    seed_repo_release(&pool, 42, 120).await;
+    seed_star(&pool, 42).await;
+    let state = setup_state_with_ai(pool.clone());
+    let prepared = prepare_release_batch(state.as_ref(), user_id.as_str(), &[120])
+        .await
+        .expect("prepare release batch");
+    let candidate = prepared
+        .detail_pending_candidates
+        .first()
+        .expect("release needs translation");
+    upsert_translation(
+        state.as_ref(),
+        user_id.as_str(),
+        "2026-02-23T00:00:00Z",
+        TranslationUpsert {
+            entity_type: "release_detail",
+            entity_id: &candidate.entity_id,
+            lang: "zh-CN",
+            source_hash: &candidate.source_hash,
+            title: Some("标题"),
+            summary: Some("- 条目"),
+        },
+    )
+    .await
+    .expect("seed release detail translation");
+    let prepared = prepare_release_batch(state.as_ref(), user_id.as_str(), &[120])
+        .await
+        .expect("prepare cached release batch");
+    assert!(prepared.translated.contains_key(&120));
+
+    let err = delete_translations(
+        State(state.clone()),
+        AuthedUser(test_user_id(1)),
+        Query(TranslationCachePurgeQuery {
+            entity_type: "repo".to_owned(),
+            entity_id: "120".to_owned(),
+        }),
+    )
+    .await
+    .expect_err("unknown entity type");
+    assert_eq!(err.code(), "bad_request");
+
+    let Json(purged) = delete_translations(
+        State(state.clone()),
+        AuthedUser(test_user_id(1)),
+        Query(TranslationCachePurgeQuery {
+            entity_type: "release_detail".to_owned(),
+            entity_id: "120".to_owned(),
+        }),
+    )
+    .await
+    .expect("purge release detail translation");
+    assert_eq!(purged.deleted, 1);
+    assert_eq!(purged.shared_deleted, 0);
+
+    let prepared = prepare_release_batch(state.as_ref(), user_id.as_str(), &[120])
+        .await
+        .expect("prepare purged release batch");
+    assert!(prepared.translated.is_empty());
+    assert_eq!(
+        prepared
+            .detail_pending_candidates
+            .iter()
+            .map(|candidate| candidate.release_id)
+            .collect::<Vec<_>>(),
+        vec![120]
+    );
+}
+
+#[tokio::test]
+async fn admin_delete_translations_purges_shared_cache_and_audits() {
+    let pool = setup_pool().await;
+    seed_user(&pool, 2, "octo-admin", 1, 0).await;
+    sqlx::query(
+            r#"
+            INSERT INTO ai_translations (
+              id, user_id, entity_type, entity_id, lang, source_hash,
+              title, summary, created_at, updated_at, status
+            )
+            VALUES ('translation-release-detail-120', ?, 'release_detail', '120', 'zh-CN', 'hash', '标题', '摘要', '2026-02-23T00:00:00Z', '2026-02-23T00:00:00Z', 'ready')
+            "#,
+        )
+        .bind(test_user_id(1))
+        .execute(&pool)
+        .await
+        .expect("seed user translation");
+    sqlx::query(
+            r#"
+            INSERT INTO ai_translations_shared (
+              entity_type, entity_id, lang, source_hash,
+              title, summary, created_at, updated_at
+            )
+            VALUES ('release_detail', '120', 'zh-CN', 'hash', '标题', '摘要', '2026-02-23T00:00:00Z', '2026-02-23T00:00:00Z')
+            "#,
+        )
+        .execute(&pool)
+        .await
+        .expect("seed shared translation");
+    let state = setup_state(pool.clone());
+
+    let Json(purged) = admin_delete_translations(
+        State(state),
+        AdminUser(test_user_id(2)),
+        Query(AdminTranslationCachePurgeQuery {
+            entity_type: "release_detail".to_owned(),
+            entity_id: "120".to_owned(),
+            user_id: None,
+        }),
+    )
+    .await
+    .expect("admin purge");
+
+    assert_eq!(purged.deleted, 1);
+    assert_eq!(purged.shared_deleted, 1);
+    let (action, target) = sqlx::query_as::<_, (String, Option<String>)>(
+        "SELECT action, target_user_id FROM admin_audit_log",
+    )
+    .fetch_one(&pool)
+    .await
+    .expect("load audit row");
+    assert_eq!(action, admin_audit::ADMIN_AUDIT_TRANSLATION_CACHE_PURGE);
+    assert_eq!(target, None);
+}
+
+#[tokio::test]
+async fn translate_releases_batch_for_user_batches_short_release_details() {
+    let pool = setup_pool().await;
+    let user_id = test_user_id(1);
+    seed_repo_release(&pool, 42, 120).await;
+    seed_repo_release(&pool, 42, 121).await;
+    seed_star(&pool, 42).await;
+    sqlx::query(
+        r#"
+            UPDATE repo_releases
+            SET body = CASE release_id
+              WHEN 120 THEN ?
+              WHEN 121 THEN ?
+              ELSE body
+            END,
+                name = CASE release_id
+              WHEN 120 THEN 'Release v1.2.3'
+              WHEN 121 THEN 'Release v1.2.4'
+              ELSE name
+            END,
+                tag_name = CASE release_id
+              WHEN 120 THEN 'v1.2.3'
+              WHEN 121 THEN 'v1.2.4'
+              ELSE tag_name
+            END
+            WHERE release_id IN (120, 121)
+            "#,
+    )
+    .bind("- first item")
+    .bind("- second item")
+    .execute(&pool)
+    .await
+    .expect("update release bodies");
+
+    let call_count = Arc::new(AtomicUsize::new(0));
+    let seen_payloads = Arc::new(tokio::sync::Mutex::new(Vec::<Value>::new()));
+    let route_call_count = Arc::clone(&call_count);
+    let route_payloads = Arc::clone(&seen_payloads);
+    let base_url = spawn_test_ai_server(Router::new().route(
+        "/chat/completions",
+        post(move |Json(payload): Json<Value>| {
+            let route_call_count = Arc::clone(&route_call_count);
+            let route_payloads = Arc::clone(&route_payloads);
+            async move {
+                route_call_count.fetch_add(1, Ordering::SeqCst);
+                route_payloads.lock().await.push(payload);
+                let content = serde_json::json!({
+                    "items": [
+                        {
+                            "release_id": 120,
+                            "title_zh": "版本 1.2.3",
+                            "summary_md": "- 第一条"
+                        },
+                        {
+                            "release_id": 121,
+                            "title_zh": "版本 1.2.4",
+                            "summary_md": "- 第二条"
+                        }
+                    ]
+                })
+                .to_string();
+                let response = serde_json::json!({
+                    "choices": [{"message": {"content": content}}],
+                    "usage": {"prompt_tokens": 10, "completion_tokens": 10, "total_tokens": 20}
+                });
+                (
+                    StatusCode::OK,
+                    [(header::CONTENT_TYPE, "application/json")],
+                    Json(response),
+                )
+            }
+        }),
+    ))
+    .await;
+    let state = setup_state_with_ai_base_url(pool.clone(), base_url);
+
+    let translated =
+        translate_releases_batch_for_user(state.as_ref(), user_id.as_str(), &[120, 121])
+            .await
+            .expect("translate release batch");
+
+    assert_eq!(translated.items.len(), 2);
+    assert_eq!(translated.items[0].status, "ready");
+    assert_eq!(translated.items[0].title.as_deref(), Some("版本 1.2.3"));
+    assert_eq!(translated.items[0].summary.as_deref(), Some("- 第一条"));
+    assert_eq!(translated.items[1].status, "ready");
+    assert_eq!(translated.items[1].title.as_deref(), Some("版本 1.2.4"));
+    assert_eq!(translated.items[1].summary.as_deref(), Some("- 第二条"));
+    assert_eq!(call_count.load(Ordering::SeqCst), 1);
+
+    let payloads = seen_payloads.lock().await;
+    let prompt = payloads[0]["messages"][1]["content"]
+        .as_str()
+        .expect("user prompt should be present");
+    assert!(prompt.contains("release_id: 120"));
+    assert!(prompt.contains("release_id: 121"));
+}
+
+async fn spawn_counting_release_batch_ai_server(call_count: Arc<AtomicUsize>) -> Url {
+    spawn_test_ai_server(Router::new().route(
+        "/chat/completions",
+        post(move |Json(_payload): Json<Value>| {
+            let call_count = Arc::clone(&call_count);
+            async move {
+                call_count.fetch_add(1, Ordering::SeqCst);
+                let content = serde_json::json!({
+                    "items": [{
+                        "release_id": 120,
+                        "title_zh": "版本 1.2.3",
+                        "summary_md": "- 第一条"
+                    }]
+                })
+                .to_string();
+                let response = serde_json::json!({
+                    "choices": [{"message": {"content": content}}],
+                    "usage": {"prompt_tokens": 10, "completion_tokens": 10, "total_tokens": 20}
+                });
+                (
+                    StatusCode::OK,
+                    [(header::CONTENT_TYPE, "application/json")],
+                    Json(response),
+                )
+            }
+        }),
+    ))
+    .await
+}
+
+async fn set_short_release_body(pool: &SqlitePool, release_id: i64) {
+    sqlx::query(r#"UPDATE repo_releases SET body = '- first item' WHERE release_id = ?"#)
+        .bind(release_id)
+        .execute(pool)
+        .await
+        .expect("update release body");
+}
+
+async fn count_shared_release_translations(pool: &SqlitePool, release_id: i64) -> i64 {
+    sqlx::query_scalar(
+        r#"
+            SELECT COUNT(*)
+            FROM ai_translations_shared
+            WHERE entity_type = 'release_detail' AND entity_id = ?
+            "#,
+    )
+    .bind(release_id.to_string())
+    .fetch_one(pool)
+    .await
+    .expect("count shared translations")
+}
+
+#[tokio::test]
+async fn translate_releases_batch_for_user_reuses_shared_translation_for_public_repo() {
+    let pool = setup_pool().await;
+    seed_user(&pool, 2, "viewer", 0, 0).await;
+    seed_repo_release(&pool, 42, 120).await;
+    seed_star(&pool, 42).await;
+    seed_star_for_user_with_privacy(&pool, 2, 42, false).await;
+    set_short_release_body(&pool, 120).await;
+
+    let call_count = Arc::new(AtomicUsize::new(0));
+    let base_url = spawn_counting_release_batch_ai_server(Arc::clone(&call_count)).await;
+    let state = setup_state_with_ai_base_url(pool.clone(), base_url);
+
+    let first = translate_releases_batch_for_user(state.as_ref(), &test_user_id(1), &[120])
+        .await
+        .expect("translate for first user");
+    assert_eq!(first.items[0].status, "ready");
+    assert_eq!(call_count.load(Ordering::SeqCst), 1);
+    assert_eq!(count_shared_release_translations(&pool, 120).await, 1);
+
+    let second = translate_releases_batch_for_user(state.as_ref(), &test_user_id(2), &[120])
+        .await
+        .expect("translate for second user");
+    assert_eq!(second.items[0].status, "ready");
+    assert_eq!(second.items[0].title.as_deref(), Some("版本 1.2.3"));
+    assert_eq!(second.items[0].summary.as_deref(), Some("- 第一条"));
+    assert_eq!(call_count.load(Ordering::SeqCst), 1);
+
+    let user_row: (String, Option<String>) = sqlx::query_as(
+        r#"
+            SELECT status, summary
+            FROM ai_translations
+            WHERE user_id = ? AND entity_type = 'release_detail' AND entity_id = '120'
+            "#,
+    )
+    .bind(test_user_id(2))
+    .fetch_one(&pool)
+    .await
+    .expect("second user translation row");
+    assert_eq!(user_row.0, "ready");
+    assert_eq!(user_row.1.as_deref(), Some("- 第一条"));
+}
+
+#[tokio::test]
+async fn translate_releases_batch_for_user_keeps_private_repo_translations_out_of_shared_cache() {
+    let pool = setup_pool().await;
+    seed_user(&pool, 2, "viewer", 0, 0).await;
+    seed_repo_release(&pool, 42, 120).await;
+    seed_star_for_user_with_privacy(&pool, 1, 42, true).await;
+    seed_star_for_user_with_privacy(&pool, 2, 42, true).await;
+    set_short_release_body(&pool, 120).await;
+
+    let call_count = Arc::new(AtomicUsize::new(0));
+    let base_url = spawn_counting_release_batch_ai_server(Arc::clone(&call_count)).await;
+    let state = setup_state_with_ai_base_url(pool.clone(), base_url);
+
+    for user_index in [1, 2] {
+        let translated =
+            translate_releases_batch_for_user(state.as_ref(), &test_user_id(user_index), &[120])
+                .await
+                .expect("translate private release");
+        assert_eq!(translated.items[0].status, "ready");
+    }
+
+    assert_eq!(call_count.load(Ordering::SeqCst), 2);
+    assert_eq!(count_shared_release_translations(&pool, 120).await, 0);
+}
+
+#[tokio::test]
+async fn translate_releases_batch_for_user_skips_opted_out_repos() {
+    let pool = setup_pool().await;
+    let user_id = test_user_id(1);
+    seed_repo_release(&pool, 42, 120).await;
+    seed_repo_release(&pool, 43, 121).await;
+    seed_star(&pool, 42).await;
+    seed_star(&pool, 43).await;
+    set_short_release_body(&pool, 120).await;
+    set_short_release_body(&pool, 121).await;
+
+    let call_count = Arc::new(AtomicUsize::new(0));
+    let seen_payloads = Arc::new(tokio::sync::Mutex::new(Vec::<Value>::new()));
+    let route_call_count = Arc::clone(&call_count);
+    let route_payloads = Arc::clone(&seen_payloads);
+    let base_url = spawn_test_ai_server(Router::new().route(
+        "/chat/completions",
+        post(move |Json(payload): Json<Value>| {
+            let route_call_count = Arc::clone(&route_call_count);
+            let route_payloads = Arc::clone(&route_payloads);
+            async move {
+                route_call_count.fetch_add(1, Ordering::SeqCst);
+                route_payloads.lock().await.push(payload);
+                let content = serde_json::json!({
+                    "items": [{
+                        "release_id": 120,
+                        "title_zh": "版本 1.2.3",
+                        "summary_md": "- 第一条"
+                    }]
+                })
+                .to_string();
+                let response = serde_json::json!({
+                    "choices": [{"message": {"content": content}}],
+                    "usage": {"prompt_tokens": 10, "completion_tokens": 10, "total_tokens": 20}
+                });
+                (
+                    StatusCode::OK,
+                    [(header::CONTENT_TYPE, "application/json")],
+                    Json(response),
+                )
+            }
+        }),
+    ))
+    .await;
+    let state = setup_state_with_ai_base_url(pool.clone(), base_url);
+
+    let Json(prefs) = patch_repo_preferences(
+        State(state.clone()),
+        Path("43".to_owned()),
+        AuthedUser(user_id.clone()),
+        ApiJson(RepoPreferencesRequest {
+            translation_enabled: Some(false),
+        }),
+    )
+    .await
+    .expect("opt repo out of translation");
+    assert!(!prefs.translation_enabled);
+
+    let Json(starred) = list_starred(
+        State(state.clone()),
+        AuthedUser(test_user_id(1)),
+        Query(StarredQuery::default()),
+    )
+    .await
+    .expect("list starred");
+    let StarredListResponse::Flat(starred) = starred else {
+        panic!("expected flat starred list");
+    };
+    let enabled = starred
+        .iter()
+        .map(|repo| (repo.repo_id, repo.translation_enabled))
+        .collect::<std::collections::HashMap<_, _>>();
+    assert_eq!(enabled.get(&42), Some(&true));
+    assert_eq!(enabled.get(&43), Some(&false));
+
+    let translated = translate_releases_batch_for_user(state.as_ref(), &user_id, &[120, 121])
+        .await
+        .expect("translate release batch");
+    assert_eq!(translated.items.len(), 2);
+    assert_eq!(translated.items[0].status, "ready");
+    assert_eq!(translated.items[1].id, "121");
+    assert_eq!(translated.items[1].status, "opted_out");
+    assert_eq!(translated.items[1].summary, None);
+
+    assert_eq!(call_count.load(Ordering::SeqCst), 1);
+    let payloads = seen_payloads.lock().await;
+    let prompt = payloads[0]["messages"][1]["content"]
+        .as_str()
+        .expect("user prompt should be present");
+    assert!(prompt.contains("release_id: 120"));
+    assert!(!prompt.contains("release_id: 121"));
+
+    let opted_out_rows = sqlx::query_scalar::<_, i64>(
+        r#"SELECT COUNT(*) FROM ai_translations WHERE entity_id = '121'"#,
+    )
+    .fetch_one(&pool)
+    .await
+    .expect("count opted-out translation rows");
+    assert_eq!(opted_out_rows, 0);
+}
+
+#[tokio::test]
+async fn failed_release_translation_is_queued_and_cleared_by_retry_worker() {
+    let pool = setup_pool().await;
+    seed_repo_release(&pool, 42, 120).await;
+    seed_star(&pool, 42).await;
+    set_short_release_body(&pool, 120).await;
+
+    let failing_url = spawn_test_ai_server(Router::new().route(
+        "/chat/completions",
+        post(|_payload: Json<Value>| async {
+            (
+                StatusCode::BAD_REQUEST,
+                [(header::CONTENT_TYPE, "application/json")],
+                Json(json!({"error": {"message": "provider hiccup"}})),
+            )
+        }),
+    ))
+    .await;
+    let failing = setup_state_with_ai_base_url(pool.clone(), failing_url);
+    let started = chrono::Utc::now();
+    let translated = translate_releases_batch_for_user(failing.as_ref(), &test_user_id(1), &[120])
+        .await
+        .expect("translate with failing upstream");
+    assert_eq!(translated.items[0].status, "error");
+
+    let (attempts, next_attempt_at): (i64, String) = sqlx::query_as(
+        r#"
+            SELECT attempts, next_attempt_at
+            FROM translation_retry_queue
+            WHERE user_id = ? AND entity_type = 'release' AND entity_id = '120'
+            "#,
+    )
+    .bind(test_user_id(1))
+    .fetch_one(&pool)
+    .await
+    .expect("queued retry");
+    assert_eq!(attempts, 1);
+    let next_attempt_at = chrono::DateTime::parse_from_rfc3339(&next_attempt_at)
+        .expect("parse next attempt")
+        .with_timezone(&chrono::Utc);
+    assert!(next_attempt_at > started);
+
+    // Not due yet: the worker leaves the entry alone.
+    let result = translation_retry::run_due_translation_retries(failing.as_ref(), started)
+        .await
+        .expect("run retries before due");
+    assert_eq!(result.due, 0);
+
+    let call_count = Arc::new(AtomicUsize::new(0));
+    let working_url = spawn_counting_release_batch_ai_server(Arc::clone(&call_count)).await;
+    let working = setup_state_with_ai_base_url(pool.clone(), working_url);
+    let result = translation_retry::run_due_translation_retries(
+        working.as_ref(),
+        next_attempt_at + chrono::Duration::seconds(1),
+    )
+    .await
+    .expect("run due retries");
+    assert_eq!((result.due, result.succeeded, result.failed), (1, 1, 0));
+    assert_eq!(call_count.load(Ordering::SeqCst), 1);
+
+    let queued: i64 = sqlx::query_scalar(r#"SELECT COUNT(*) FROM translation_retry_queue"#)
+        .fetch_one(&pool)
+        .await
+        .expect("count queued retries");
+    assert_eq!(queued, 0);
+    let cached: (String, Option<String>) = sqlx::query_as(
+        r#"
+            SELECT status, summary
+            FROM ai_translations
+            WHERE user_id = ? AND entity_type = 'release_detail' AND entity_id = '120'
+            "#,
+    )
+    .bind(test_user_id(1))
+    .fetch_one(&pool)
+    .await
+    .expect("cached translation");
+    assert_eq!(cached, ("ready".to_owned(), Some("- 第一条".to_owned())));
+}
+
+#[tokio::test]
+async fn sync_release_translation_logs_llm_call_with_request_id() {
+    let pool = setup_pool().await;
+    seed_repo_release(&pool, 42, 120).await;
+    seed_star(&pool, 42).await;
+    set_short_release_body(&pool, 120).await;
+
+    let call_count = Arc::new(AtomicUsize::new(0));
+    let base_url = spawn_counting_release_batch_ai_server(Arc::clone(&call_count)).await;
+    let state = setup_state_with_ai_base_url(pool.clone(), base_url);
+    let translated = observability::with_request_id(
+        Some("req-translate-1".to_owned()),
+        translate_releases_batch_for_user(state.as_ref(), &test_user_id(1), &[120]),
+    )
+    .await
+    .expect("translate release");
+    assert_eq!(translated.items[0].status, "ready");
+    assert_eq!(call_count.load(Ordering::SeqCst), 1);
+
+    let request_ids: Vec<Option<String>> =
+        sqlx::query_scalar(r#"SELECT request_id FROM llm_calls"#)
+            .fetch_all(&pool)
+            .await
+            .expect("load llm call request ids");
+    assert_eq!(request_ids, vec![Some("req-translate-1".to_owned())]);
+}
+
+#[tokio::test]
+async fn repair_release_translations_rewrites_json_blob_cache_rows() {
+    let pool = setup_pool().await;
+    seed_repo_release(&pool, 42, 120).await;
+    seed_repo_release(&pool, 42, 121).await;
+    seed_star(&pool, 42).await;
+    set_short_release_body(&pool, 120).await;
+    let corrupted = r#"{"title_zh":"版本 1.2.3","summary_md":"- 第一条"}"#;
+    let source_hash = release_detail_source_hash("openai/codex", "Release v1.2.3", "- first item");
+    let now = "2026-02-23T00:00:00Z";
+    for (entity_id, source_hash) in [("120", source_hash.as_str()), ("121", "stale")] {
+        sqlx::query(
+            r#"
+                INSERT INTO ai_translations (
+                  id, user_id, entity_type, entity_id, lang, source_hash, status, title, summary,
+                  created_at, updated_at
+                )
+                VALUES (?, ?, 'release_detail', ?, 'zh-CN', ?, 'ready', NULL, ?, ?, ?)
+                "#,
+        )
+        .bind(crate::local_id::generate_local_id())
+        .bind(test_user_id(1))
+        .bind(entity_id)
+        .bind(source_hash)
+        .bind(corrupted)
+        .bind(now)
+        .bind(now)
+        .execute(&pool)
+        .await
+        .expect("seed corrupted translation");
+    }
+    sqlx::query(
+        r#"
+            INSERT INTO ai_translations_shared (
+              entity_type, entity_id, lang, source_hash, title, summary, created_at, updated_at
+            )
+            VALUES ('release_detail', '120', 'zh-CN', ?, NULL, ?, ?, ?)
+            "#,
+    )
+    .bind(source_hash.as_str())
+    .bind(corrupted)
+    .bind(now)
+    .bind(now)
+    .execute(&pool)
+    .await
+    .expect("seed corrupted shared translation");
+
+    let call_count = Arc::new(AtomicUsize::new(0));
+    let base_url = spawn_counting_release_batch_ai_server(Arc::clone(&call_count)).await;
+    let state = setup_state_with_ai_base_url(pool.clone(), base_url);
+
+    let result = repair_release_translations(state.as_ref(), Some(test_user_id(1).as_str()))
+        .await
+        .expect("repair translations");
+    assert_eq!(result.scanned, 2);
+    assert_eq!(result.flagged, 1);
+    assert_eq!(result.fixed, 1);
+    assert_eq!(result.failed, 0);
+    assert_eq!(result.skipped, 0);
+    assert_eq!(call_count.load(Ordering::SeqCst), 1);
+
+    let rows: Vec<(String, String, Option<String>)> = sqlx::query_as(
+        r#"
+            SELECT entity_id, status, summary
+            FROM ai_translations
+            WHERE user_id = ? AND entity_type = 'release_detail'
+            ORDER BY entity_id
+            "#,
+    )
+    .bind(test_user_id(1))
+    .fetch_all(&pool)
+    .await
+    .expect("load repaired translations");
+    assert_eq!(
+        rows,
+        vec![
+            (
+                "120".to_owned(),
+                "ready".to_owned(),
+                Some("- 第一条".to_owned())
+            ),
+            (
+                "121".to_owned(),
+                "ready".to_owned(),
+                Some(corrupted.to_owned())
+            ),
+        ]
+    );
+    let shared_summary: Option<String> = sqlx::query_scalar(
+        r#"
+            SELECT summary
+            FROM ai_translations_shared
+            WHERE entity_type = 'release_detail' AND entity_id = '120'
+            "#,
+    )
+    .fetch_one(&pool)
+    .await
+    .expect("load shared translation");
+    assert_eq!(shared_summary.as_deref(), Some("- 第一条"));
+}
+
+#[tokio::test]
+async fn list_notifications_groups_by_subject_with_counts() {
+    let pool = setup_pool().await;
+    let user_id = test_user_id(1);
+    for (thread_id, repo, title, subject_type, reason, updated_at, unread) in [
+        (
+            "t1",
+            Some("openai/codex"),
+            "Fix parser",
+            "PullRequest",
+            "review_requested",
+            "2026-02-23T08:00:00Z",
+            0,
+        ),
+        (
+            "t2",
+            Some("openai/codex"),
+            "Fix parser",
+            "PullRequest",
+            "comment",
+            "2026-02-23T09:00:00Z",
+            1,
+        ),
+        (
+            "t3",
+            Some("openai/codex"),
+            "Fix parser",
+            "PullRequest",
+            "comment",
+            "2026-02-23T07:00:00Z",
+            0,
+        ),
+        (
+            "t4",
+            Some("openai/codex"),
+            "Crash on start",
+            "Issue",
+            "mention",
+            "2026-02-23T06:00:00Z",
+            0,
+        ),
+        (
+            "t5",
+            None,
+            "Security advisory",
+            "RepositoryAdvisory",
+            "security_alert",
+            "2026-02-23T05:00:00Z",
+            1,
+        ),
+        (
+            "t6",
+            None,
+            "Security advisory",
+            "RepositoryAdvisory",
+            "security_alert",
+            "2026-02-23T04:00:00Z",
+            1,
+        ),
+    ] {
+        sqlx::query(
+            r#"
+                INSERT INTO notifications (
+                  id, user_id, thread_id, repo_full_name, subject_title, subject_type, reason,
+                  updated_at, unread, html_url
+                )
+                VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, NULL)
+                "#,
+        )
+        .bind(format!("notification-{thread_id}"))
+        .bind(user_id.as_str())
+        .bind(thread_id)
+        .bind(repo)
+        .bind(title)
+        .bind(subject_type)
+        .bind(reason)
+        .bind(updated_at)
+        .bind(unread)
+        .execute(&pool)
+        .await
+        .expect("seed notification");
+    }
+    let state = setup_state(pool);
+
+    let Json(flat) = list_notifications(
+        State(state.clone()),
+        AuthedUser(test_user_id(1)),
+        Query(NotificationListQuery::default()),
+    )
+    .await
+    .expect("list flat notifications");
+    assert_eq!(flat.group, "none");
+    let NotificationListItems::Flat(items) = &flat.items else {
+        panic!("expected flat items");
+    };
+    assert_eq!(items.len(), 6);
+
+    let Json(grouped) = list_notifications(
+        State(state),
+        AuthedUser(test_user_id(1)),
+        Query(NotificationListQuery {
+            group: Some("subject".to_owned()),
+            ..Default::default()
+        }),
+    )
+    .await
+    .expect("list grouped notifications");
+    assert_eq!(grouped.group, "subject");
+    let NotificationListItems::Subject(groups) = &grouped.items else {
+        panic!("expected subject groups");
+    };
+    let summary = groups
+        .iter()
+        .map(|group| {
+            (
+                group.thread_id.as_str(),
+                group.events_count,
+                group.unread,
+                group.reasons.join(","),
+            )
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(
+        summary,
+        vec![
+            ("t2", 3, 1, "comment,review_requested".to_owned()),
+            ("t4", 1, 0, "mention".to_owned()),
+            ("t5", 1, 1, "security_alert".to_owned()),
+            ("t6", 1, 1, "security_alert".to_owned()),
+        ]
+    );
+    assert_eq!(
+        groups[0].updated_at.as_deref(),
+        Some("2026-02-23T09:00:00Z")
+    );
+
+    let expected_counts = NotificationCounts {
+        total: 6,
+        unread: 3,
+        by_subject_type: BTreeMap::from([
+            ("Issue".to_owned(), 1),
+            ("PullRequest".to_owned(), 3),
+            ("RepositoryAdvisory".to_owned(), 2),
+        ]),
+    };
+    assert_eq!(grouped.counts, expected_counts);
+    assert_eq!(flat.counts, expected_counts);
+}
+
+#[tokio::test]
+async fn list_notifications_rejects_unknown_group() {
+    let pool = setup_pool().await;
+    let state = setup_state(pool);
+    let err = list_notifications(
+        State(state),
+        AuthedUser(test_user_id(1)),
+        Query(NotificationListQuery {
+            group: Some("repo".to_owned()),
+            ..Default::default()
+        }),
+    )
+    .await
+    .expect_err("unknown group rejected");
+    assert_eq!(err.code(), "bad_request");
+}
+
+#[tokio::test]
+async fn list_notifications_filters_by_reason_and_ignores_muted_unread() {
+    let pool = setup_pool().await;
+    let user_id = test_user_id(1);
+    for (thread_id, reason, updated_at) in [
+        ("t-mention", "mention", "2026-02-23T09:00:00Z"),
+        ("t-review", "review_requested", "2026-02-23T08:00:00Z"),
+        ("t-ci", "ci_activity", "2026-02-23T07:00:00Z"),
+        ("t-sub", "subscribed", "2026-02-23T06:00:00Z"),
+    ] {
+        sqlx::query(
+            r#"
+                INSERT INTO notifications (
+                  id, user_id, thread_id, repo_full_name, subject_title, subject_type, reason,
+                  updated_at, unread, html_url
+                )
+                VALUES (?, ?, ?, 'octo/rill', ?, 'PullRequest', ?, ?, 1, NULL)
+                "#,
+        )
+        .bind(format!("notification-{thread_id}"))
+        .bind(user_id.as_str())
+        .bind(thread_id)
+        .bind(thread_id)
+        .bind(reason)
+        .bind(updated_at)
+        .execute(&pool)
+        .await
+        .expect("seed notification");
+    }
+    sqlx::query(r#"UPDATE users SET muted_notification_reasons = ? WHERE id = ?"#)
+        .bind(r#"["ci_activity"]"#)
+        .bind(user_id.as_str())
+        .execute(&pool)
+        .await
+        .expect("mute ci_activity");
+    let state = setup_state(pool);
+
+    let Json(flat) = list_notifications(
+        State(state.clone()),
+        AuthedUser(test_user_id(1)),
+        Query(NotificationListQuery {
+            reasons: Some("mention, review_requested".to_owned()),
+            ..Default::default()
+        }),
+    )
+    .await
+    .expect("list filtered notifications");
+    let NotificationListItems::Flat(items) = &flat.items else {
+        panic!("expected flat items");
+    };
+    assert_eq!(
+        items
+            .iter()
+            .map(|item| item.thread_id.as_str())
+            .collect::<Vec<_>>(),
+        vec!["t-mention", "t-review"]
+    );
+    assert_eq!(flat.counts.total, 4);
+    assert_eq!(flat.counts.unread, 3, "muted ci_activity is not unread");
+
+    let Json(grouped) = list_notifications(
+        State(state.clone()),
+        AuthedUser(test_user_id(1)),
+        Query(NotificationListQuery {
+            group: Some("subject".to_owned()),
+            reasons: Some("subscribed".to_owned()),
+            ..Default::default()
+        }),
+    )
+    .await
+    .expect("list filtered groups");
+    let NotificationListItems::Subject(groups) = &grouped.items else {
+        panic!("expected subject groups");
+    };
+    assert_eq!(groups.len(), 1);
+    assert_eq!(groups[0].thread_id, "t-sub");
+
+    let err = list_notifications(
+        State(state),
+        AuthedUser(test_user_id(1)),
+        Query(NotificationListQuery {
+            reasons: Some("mention,bogus".to_owned()),
+            ..Default::default()
+        }),
+    )
+    .await
+    .expect_err("unknown reason rejected");
+    assert_eq!(err.code(), "bad_request");
+}
+
+#[tokio::test]
+async fn archived_notifications_leave_the_default_list_and_counts() {
+    let pool = setup_pool().await;
+    let user_id = test_user_id(1);
+    seed_notification(&pool, user_id.as_str(), "t-kept", "2026-02-23T09:00:00Z").await;
+    seed_notification(
+        &pool,
+        user_id.as_str(),
+        "t-archived",
+        "2026-02-23T08:00:00Z",
+    )
+    .await;
+    let state = setup_state(pool);
+
+    let Json(detail) = archive_notification(
+        State(state.clone()),
+        Path(" t-archived ".to_owned()),
+        AuthedUser(user_id.clone()),
+    )
+    .await
+    .expect("archive notification");
+    assert!(detail.item.archived_at.is_some());
+
+    let Json(flat) = list_notifications(
+        State(state.clone()),
+        AuthedUser(test_user_id(1)),
+        Query(NotificationListQuery::default()),
+    )
+    .await
+    .expect("list notifications");
+    let NotificationListItems::Flat(items) = &flat.items else {
+        panic!("expected flat items");
+    };
+    assert_eq!(
+        items
+            .iter()
+            .map(|item| item.thread_id.as_str())
+            .collect::<Vec<_>>(),
+        vec!["t-kept"]
+    );
+    assert_eq!(flat.counts.total, 1);
+    assert_eq!(flat.counts.unread, 1, "archived rows are not unread");
+
+    let Json(grouped) = list_notifications(
+        State(state.clone()),
+        AuthedUser(test_user_id(1)),
+        Query(NotificationListQuery {
+            group: Some("subject".to_owned()),
+            ..Default::default()
+        }),
+    )
+    .await
+    .expect("list notification groups");
+    let NotificationListItems::Subject(groups) = &grouped.items else {
+        panic!("expected subject groups");
+    };
+    assert_eq!(groups.len(), 1);
+    assert_eq!(groups[0].thread_id, "t-kept");
+
+    let Json(all) = list_notifications(
+        State(state.clone()),
+        AuthedUser(test_user_id(1)),
+        Query(NotificationListQuery {
+            include_archived: Some(true),
+            ..Default::default()
+        }),
+    )
+    .await
+    .expect("list archived notifications");
+    let NotificationListItems::Flat(items) = &all.items else {
+        panic!("expected flat items");
+    };
+    assert_eq!(items.len(), 2);
+    assert!(
+        items
+            .iter()
+            .any(|item| item.thread_id == "t-archived" && item.archived_at.is_some())
+    );
+
+    let err = archive_notification(
+        State(state),
+        Path("missing".to_owned()),
+        AuthedUser(user_id),
+    )
+    .await
+    .expect_err("unknown thread");
+    assert_eq!(err.code(), "not_found");
+}
+
+#[tokio::test]
+async fn notification_detail_resolves_subject_and_caches_it() {
+    let pool = setup_pool().await;
+    let user_id = test_user_id(1);
+    seed_notification(&pool, user_id.as_str(), "thread-1", "2026-02-23T08:00:00Z").await;
+    seed_notification_subject_url(
+        &pool,
+        "thread-1",
+        "https://api.github.com/repos/openai/codex/pulls/7",
+    )
+    .await;
+    let source_hash = notification_translation_source_hash(
+        Some("openai/codex"),
+        Some("Thread thread-1"),
+        Some("state_change"),
+        Some("PullRequest"),
+    );
+    sqlx::query(
+        r#"
+            INSERT INTO ai_translations (
+              id, user_id, entity_type, entity_id, lang, source_hash, title, summary, status,
+              created_at, updated_at
+            )
+            VALUES (?, ?, 'notification', 'thread-1', 'zh-CN', ?, '线程 1', NULL, 'ready', ?, ?)
+            "#,
+    )
+    .bind(crate::local_id::generate_local_id())
+    .bind(user_id.as_str())
+    .bind(source_hash.as_str())
+    .bind("2026-02-23T08:00:00Z")
+    .bind("2026-02-23T08:00:00Z")
+    .execute(&pool)
+    .await
+    .expect("seed notification translation");
+    let (state, calls) = setup_state_with_github_subject_server(
+        pool.clone(),
+        StatusCode::OK,
+        serde_json::json!({
+            "html_url": "https://github.com/openai/codex/pull/7",
+            "body": "Adds the thing.",
+            "state": "closed",
+            "merged_at": "2026-02-23T07:00:00Z"
+        }),
+    )
+    .await;
+
+    let detail = load_notification_detail(state.as_ref(), user_id.as_str(), "thread-1")
+        .await
+        .expect("load notification detail");
+    assert_eq!(
+        detail.item.html_url.as_deref(),
+        Some("https://github.com/openai/codex/pull/7")
+    );
+    assert_eq!(detail.subject_state.as_deref(), Some("merged"));
+    assert_eq!(detail.subject_body.as_deref(), Some("Adds the thing."));
+    assert_eq!(
+        detail
+            .translation
+            .as_ref()
+            .and_then(|translation| translation.title.as_deref()),
+        Some("线程 1")
+    );
+
+    let cached = load_notification_detail(state.as_ref(), user_id.as_str(), "thread-1")
+        .await
+        .expect("load cached notification detail");
+    assert_eq!(cached.subject_state.as_deref(), Some("merged"));
+    assert_eq!(calls.load(Ordering::SeqCst), 1);
+}
+
+#[tokio::test]
+async fn notification_detail_marks_deleted_subject_as_gone() {
+    let pool = setup_pool().await;
+    let user_id = test_user_id(1);
+    seed_notification(&pool, user_id.as_str(), "thread-2", "2026-02-23T08:00:00Z").await;
+    seed_notification_subject_url(
+        &pool,
+        "thread-2",
+        "https://api.github.com/repos/openai/codex/pulls/8",
+    )
+    .await;
+    let (state, calls) = setup_state_with_github_subject_server(
+        pool.clone(),
+        StatusCode::NOT_FOUND,
+        serde_json::json!({"message": "Not Found"}),
+    )
+    .await;
+
+    let detail = load_notification_detail(state.as_ref(), user_id.as_str(), "thread-2")
+        .await
+        .expect("load notification detail");
+
+    assert_eq!(detail.subject_state.as_deref(), Some("gone"));
+    assert_eq!(
+        detail.item.subject_title.as_deref(),
+        Some("Thread thread-2")
+    );
+    assert!(detail.subject_body.is_none());
+    assert!(detail.translation.is_none());
+    assert_eq!(calls.load(Ordering::SeqCst), 1);
+
+    let err = load_notification_detail(state.as_ref(), user_id.as_str(), "missing-thread")
+        .await
+        .expect_err("unknown thread should 404");
+    assert_eq!(err.code(), "not_found");
+}
+
+#[tokio::test]
+async fn release_detail_chunk_retry_skips_chunks_finished_before_failure() {
+    let pool = setup_pool().await;
+    let user_id = test_user_id(1);
+    let fail_middle_chunk = Arc::new(AtomicBool::new(true));
+    let prompts = Arc::new(tokio::sync::Mutex::new(Vec::<String>::new()));
+    let route_fail_middle_chunk = Arc::clone(&fail_middle_chunk);
+    let route_prompts = Arc::clone(&prompts);
+    let base_url = spawn_test_ai_server(Router::new().route(
+        "/chat/completions",
+        post(move |Json(payload): Json<Value>| {
+            let fail_middle_chunk = Arc::clone(&route_fail_middle_chunk);
+            let prompts = Arc::clone(&route_prompts);
+            async move {
+                let prompt = payload["messages"][1]["content"]
+                    .as_str()
+                    .unwrap_or_default()
+                    .to_owned();
+                prompts.lock().await.push(prompt.clone());
+                if prompt.contains("- bravo") && fail_middle_chunk.load(Ordering::SeqCst) {
+                    return (
+                        StatusCode::FORBIDDEN,
+                        [(header::CONTENT_TYPE, "application/json")],
+                        Json(serde_json::json!({"error": {"message": "blocked"}})),
+                    );
+                }
+                let (chunk_index, summary) = if prompt.contains("- alpha") {
+                    (1, "- 甲")
+                } else if prompt.contains("- bravo") {
+                    (2, "- 乙")
+                } else {
+                    (3, "- 丙")
+                };
+                let content = serde_json::json!({
+                    "items": [{"chunk_index": chunk_index, "summary_md": summary}]
+                })
+                .to_string();
+                (
+                    StatusCode::OK,
+                    [(header::CONTENT_TYPE, "application/json")],
+                    Json(serde_json::json!({
+                        "choices": [{"message": {"content": content}}],
+                        "usage": {"prompt_tokens": 10, "completion_tokens": 10, "total_tokens": 20}
+                    })),
+                )
+            }
+        }),
+    ))
+    .await;
+    let state = setup_state_with_ai_base_url(pool.clone(), base_url);
+
+    let chunks = vec![
+        "- alpha\n".to_owned(),
+        "- bravo\n".to_owned(),
+        "- charlie\n".to_owned(),
+    ];
+    // Small enough that every chunk is sent in its own request.
+    let budget = super::ReleaseDetailChunkBudget {
+        max_chars: 64,
+        input_budget: 400,
+        max_output_tokens: 200,
+        model_input_limit: 2048,
+        fallback_source: "test",
+    };
+    let progress = super::ReleaseDetailChunkProgress {
+        user_id: user_id.as_str(),
+        entity_id: "120",
+        source_hash: "hash-v2",
+    };
+    let count_chunks = || async {
+        sqlx::query_as::<_, (String, i64)>(
+            r#"
+                SELECT source_hash, chunk_index
+                FROM ai_translation_chunks
+                WHERE user_id = ? AND entity_id = '120'
+                ORDER BY source_hash, chunk_index
+                "#,
+        )
+        .bind(user_id.as_str())
+        .fetch_all(&pool)
+        .await
+        .expect("load chunk rows")
+    };
+
+    super::translate_release_detail_chunks_batched(
+        state.as_ref(),
+        budget,
+        progress,
+        "acme/repo",
+        "v1.2.3",
+        &chunks,
+    )
+    .await
+    .expect_err("middle chunk should fail");
+    assert_eq!(count_chunks().await, vec![("hash-v2".to_owned(), 0)]);
+
+    sqlx::query(
+            r#"
+            INSERT INTO ai_translation_chunks (
+              user_id, entity_id, source_hash, chunk_index, chunk_hash, translated, created_at, updated_at
+            )
+            VALUES (?, '120', 'hash-v1', 0, 'stale', '- 旧', '2026-02-23T00:00:00Z', '2026-02-23T00:00:00Z')
+            "#,
+        )
+        .bind(user_id.as_str())
+        .execute(&pool)
+        .await
+        .expect("insert stale chunk");
+    fail_middle_chunk.store(false, Ordering::SeqCst);
+    let first_run_prompts = prompts.lock().await.len();
+
+    let translated = super::translate_release_detail_chunks_batched(
+        state.as_ref(),
+        budget,
+        progress,
+        "acme/repo",
+        "v1.2.3",
+        &chunks,
+    )
+    .await
+    .expect("retry should finish remaining chunks");
+
+    assert_eq!(translated, vec!["- 甲\n", "- 乙\n", "- 丙\n"]);
+    let retry_prompts = prompts.lock().await[first_run_prompts..].to_vec();
+    assert_eq!(retry_prompts.len(), 2);
+    assert!(
+        retry_prompts
+            .iter()
+            .all(|prompt| !prompt.contains("- alpha"))
+    );
+    assert_eq!(
+        count_chunks().await,
+        vec![
+            ("hash-v2".to_owned(), 0),
+            ("hash-v2".to_owned(), 1),
+            ("hash-v2".to_owned(), 2),
+        ]
+    );
+}
+
+#[tokio::test]
+async fn translate_releases_batch_for_user_falls_back_when_batched_markdown_is_invalid() {
+    let pool = setup_pool().await;
+    let user_id = test_user_id(1);
+    seed_repo_release(&pool, 42, 120).await;
+    seed_star(&pool, 42).await;
+    sqlx::query(
+        r#"
+            UPDATE repo_releases
+            SET body = ?, name = 'Release v1.2.3', tag_name = 'v1.2.3'
+            WHERE release_id = ?
+            "#,
+    )
+    .bind("## Heading\n- item")
+    .bind(120_i64)
+    .execute(&pool)
+    .await
+    .expect("update release body");
+
+    let batch_calls = Arc::new(AtomicUsize::new(0));
+    let detail_chunk_calls = Arc::new(AtomicUsize::new(0));
+    let title_calls = Arc::new(AtomicUsize::new(0));
+    let route_batch_calls = Arc::clone(&batch_calls);
+    let route_detail_chunk_calls = Arc::clone(&detail_chunk_calls);
+    let route_title_calls = Arc::clone(&title_calls);
+    let base_url = spawn_test_ai_server(Router::new().route(
+        "/chat/completions",
+        post(move |Json(payload): Json<Value>| {
+            let route_batch_calls = Arc::clone(&route_batch_calls);
+            let route_detail_chunk_calls = Arc::clone(&route_detail_chunk_calls);
+            let route_title_calls = Arc::clone(&route_title_calls);
+            async move {
+                let system_prompt = payload["messages"][0]["content"]
+                    .as_str()
+                    .unwrap_or_default();
+                let content = if system_prompt.contains("批量翻译助手") {
+                    route_batch_calls.fetch_add(1, Ordering::SeqCst);
+                    serde_json::json!({
+                        "items": [
+                            {
+                                "release_id": 120,
+                                "title_zh": "批量标题",
+                                "summary_md": "Heading item"
+                            }
+                        ]
+                    })
+                    .to_string()
+                } else if system_prompt.contains("只把 GitHub Release 标题翻译成自然中文")
+                {
+                    route_title_calls.fetch_add(1, Ordering::SeqCst);
+                    "版本 v1.2.3".to_owned()
+                } else {
+                    route_detail_chunk_calls.fetch_add(1, Ordering::SeqCst);
+                    "## 标题\n- 条目".to_owned()
+                };
+                let response = serde_json::json!({
+                    "choices": [{"message": {"content": content}}],
+                    "usage": {"prompt_tokens": 10, "completion_tokens": 10, "total_tokens": 20}
+                });
+                (
+                    StatusCode::OK,
+                    [(header::CONTENT_TYPE, "application/json")],
+                    Json(response),
+                )
+            }
+        }),
+    ))
+    .await;
+    let state = setup_state_with_ai_base_url(pool.clone(), base_url);
+
+    let translated = translate_releases_batch_for_user(state.as_ref(), user_id.as_str(), &[120])
+        .await
+        .expect("translate release batch");
+
+    assert_eq!(translated.items.len(), 1);
+    assert_eq!(translated.items[0].status, "ready");
+    assert_eq!(translated.items[0].title.as_deref(), Some("版本 v1.2.3"));
+    assert_eq!(
+        translated.items[0].summary.as_deref(),
+        Some("## 标题\n- 条目")
+    );
+    assert_eq!(batch_calls.load(Ordering::SeqCst), 1);
+    assert!(detail_chunk_calls.load(Ordering::SeqCst) >= 1);
+    assert_eq!(title_calls.load(Ordering::SeqCst), 1);
+}
+
+#[tokio::test]
+async fn translate_releases_batch_for_user_reuses_cached_release_detail_for_truncated_release() {
+    let pool = setup_pool().await;
+    let user_id = test_user_id(1);
+    seed_repo_release(&pool, 42, 120).await;
+    seed_star(&pool, 42).await;
+    let long_body = "a".repeat(RELEASE_FEED_BODY_MAX_CHARS + 24);
+    sqlx::query(
+        r#"
+            UPDATE repo_releases
+            SET body = ?
+            WHERE release_id = ?
+            "#,
+    )
+    .bind(long_body.as_str())
+    .bind(120_i64)
+    .execute(&pool)
+    .await
+    .expect("update repo release body");
+    let state = setup_state_with_ai(pool.clone());
+    let source_hash =
+        release_detail_source_hash("openai/codex", "Release v1.2.3", long_body.as_str());
+    seed_release_detail_translation(
+        &pool,
+        user_id.as_str(),
+        "120",
+        source_hash.as_str(),
+        Some("版本 v1.2.3"),
+        Some(
+            "- 第一段
+- 第二段",
+        ),
+    )
+    .await;
+
+    let translated = translate_releases_batch_for_user(state.as_ref(), user_id.as_str(), &[120])
+        .await
+        .expect("translate cached long release batch");
+
+    assert_eq!(translated.items.len(), 1);
+    let item = &translated.items[0];
+    assert_eq!(item.id, "120");
+    assert_eq!(item.status, "ready");
+    assert_eq!(item.title.as_deref(), Some("版本 v1.2.3"));
+    assert_eq!(
+        item.summary.as_deref(),
+        Some(
+            "- 第一段
+- 第二段"
+        )
+    );
+
+    let release_cache_count = sqlx::query_scalar::<_, i64>(
+        r#"
+            SELECT COUNT(*)
+            FROM ai_translations
+            WHERE user_id = ?
+              AND entity_type = 'release'
+              AND entity_id = '120'
+            "#,
+    )
+    .bind(user_id.as_str())
+    .fetch_one(&pool)
+    .await
+    .expect("count release feed cache entries");
+    assert_eq!(release_cache_count, 0);
+}
+
+#[tokio::test]
+async fn translate_releases_batch_for_user_reuses_legacy_release_cache_for_short_release() {
+    let pool = setup_pool().await;
+    let user_id = test_user_id(1);
+    seed_repo_release(&pool, 42, 120).await;
+    seed_star(&pool, 42).await;
+
+    let legacy_body = release_feed_body(Some("- item")).expect("legacy feed body");
+    let legacy_hash = crate::api::release_feed_translation_source_hash(
+        "openai/codex",
+        "Release v1.2.3",
+        Some(&legacy_body),
+    );
+    seed_release_translation(
+        &pool,
+        user_id.as_str(),
+        "120",
+        legacy_hash.as_str(),
+        Some("版本 v1.2.3"),
+        Some("- 已缓存条目"),
+    )
+    .await;
+
+    let batch_calls = Arc::new(AtomicUsize::new(0));
+    let detail_chunk_calls = Arc::new(AtomicUsize::new(0));
+    let title_calls = Arc::new(AtomicUsize::new(0));
+    let route_batch_calls = Arc::clone(&batch_calls);
+    let route_detail_chunk_calls = Arc::clone(&detail_chunk_calls);
+    let route_title_calls = Arc::clone(&title_calls);
+    let base_url = spawn_test_ai_server(Router::new().route(
+        "/chat/completions",
+        post(move |_payload: Json<Value>| {
+            let route_batch_calls = Arc::clone(&route_batch_calls);
+            let route_detail_chunk_calls = Arc::clone(&route_detail_chunk_calls);
+            let route_title_calls = Arc::clone(&route_title_calls);
+            async move {
+                route_batch_calls.fetch_add(1, Ordering::SeqCst);
+                route_detail_chunk_calls.fetch_add(1, Ordering::SeqCst);
+                route_title_calls.fetch_add(1, Ordering::SeqCst);
+                (
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    [(header::CONTENT_TYPE, "application/json")],
+                    Json(json!({"error": "legacy cache should avoid upstream calls"})),
+                )
+            }
+        }),
+    ))
+    .await;
+    let state = setup_state_with_ai_base_url(pool, base_url);
+
+    let translated = translate_releases_batch_for_user(state.as_ref(), user_id.as_str(), &[120])
+        .await
+        .expect("reuse legacy short release cache");
+
+    assert_eq!(translated.items.len(), 1);
+    let item = &translated.items[0];
+    assert_eq!(item.id, "120");
+    assert_eq!(item.status, "ready");
+    assert_eq!(item.title.as_deref(), Some("版本 v1.2.3"));
+    assert_eq!(item.summary.as_deref(), Some("- 已缓存条目"));
+    assert_eq!(batch_calls.load(Ordering::SeqCst), 0);
+    assert_eq!(detail_chunk_calls.load(Ordering::SeqCst), 0);
+    assert_eq!(title_calls.load(Ordering::SeqCst), 0);
+}
+
+#[tokio::test]
+async fn translate_releases_batch_for_user_skips_batch_calls_that_exceed_batch_budget() {
+    let pool = setup_pool().await;
+    let user_id = test_user_id(1);
+    seed_repo_release(&pool, 42, 120).await;
+    seed_star(&pool, 42).await;
+    sqlx::query(
+        r#"
+            INSERT INTO admin_runtime_settings (
+              id,
+              llm_max_concurrency,
+              ai_model_context_limit,
+              translation_general_worker_concurrency,
+              translation_dedicated_worker_concurrency,
+              created_at,
+              updated_at
+            )
+            VALUES (1, 1, 2048, 3, 1, '2026-03-28T11:00:00Z', '2026-03-28T11:00:00Z')
+            "#,
+    )
+    .execute(&pool)
+    .await
+    .expect("insert runtime settings");
+    sqlx::query(
+        r#"
+            UPDATE repo_releases
+            SET body = ?
+            WHERE release_id = ?
+            "#,
+    )
+    .bind("a".repeat(1_200))
+    .bind(120_i64)
+    .execute(&pool)
+    .await
+    .expect("update release body");
+
+    let batch_calls = Arc::new(AtomicUsize::new(0));
+    let detail_chunk_calls = Arc::new(AtomicUsize::new(0));
+    let title_calls = Arc::new(AtomicUsize::new(0));
+    let route_batch_calls = Arc::clone(&batch_calls);
+    let route_detail_chunk_calls = Arc::clone(&detail_chunk_calls);
+    let route_title_calls = Arc::clone(&title_calls);
+    let base_url = spawn_test_ai_server(Router::new().route(
+        "/chat/completions",
+        post(move |Json(payload): Json<Value>| {
+            let route_batch_calls = Arc::clone(&route_batch_calls);
+            let route_detail_chunk_calls = Arc::clone(&route_detail_chunk_calls);
+            let route_title_calls = Arc::clone(&route_title_calls);
+            async move {
+                let system_prompt = payload["messages"][0]["content"]
+                    .as_str()
+                    .unwrap_or_default();
+                let user_prompt = payload["messages"][1]["content"]
+                    .as_str()
+                    .unwrap_or_default();
+                let content = if system_prompt.contains("批量翻译助手")
+                    && user_prompt.contains("release_id:")
+                {
+                    route_batch_calls.fetch_add(1, Ordering::SeqCst);
+                    return (
+                        StatusCode::INTERNAL_SERVER_ERROR,
+                        [(header::CONTENT_TYPE, "application/json")],
+                        Json(json!({"error": "batch should not be used"})),
+                    );
+                } else if system_prompt.contains("只把 GitHub Release 标题翻译成自然中文")
+                {
+                    route_title_calls.fetch_add(1, Ordering::SeqCst);
+                    "版本 v1.2.3".to_owned()
+                } else {
+                    route_detail_chunk_calls.fetch_add(1, Ordering::SeqCst);
+                    user_prompt
+                        .split("Release notes chunk (Markdown):\n")
+                        .nth(1)
+                        .and_then(|rest| rest.split("\n\n请把这段 GitHub Release notes").next())
+                        .unwrap_or_default()
+                        .to_owned()
+                };
+                let response = serde_json::json!({
+                    "choices": [{"message": {"content": content}}],
+                    "usage": {"prompt_tokens": 10, "completion_tokens": 10, "total_tokens": 20}
+                });
+                (
+                    StatusCode::OK,
+                    [(header::CONTENT_TYPE, "application/json")],
+                    Json(response),
+                )
+            }
+        }),
+    ))
+    .await;
+    let state = setup_state_with_ai_base_url(pool.clone(), base_url);
+
+    let translated = translate_releases_batch_for_user(state.as_ref(), user_id.as_str(), &[120])
+        .await
+        .expect("translate release batch");
+
+    let expected_body = "a".repeat(1_200);
+    assert_eq!(translated.items.len(), 1);
+    assert_eq!(translated.items[0].status, "ready");
+    assert_eq!(translated.items[0].title.as_deref(), Some("版本 v1.2.3"));
+    assert_eq!(
+        translated.items[0].summary.as_deref(),
+        Some(expected_body.as_str())
+    );
+    assert_eq!(batch_calls.load(Ordering::SeqCst), 0);
+    assert!(detail_chunk_calls.load(Ordering::SeqCst) >= 1);
+    assert_eq!(title_calls.load(Ordering::SeqCst), 1);
+}
+
+#[tokio::test]
+async fn translate_releases_batch_for_user_skips_per_release_retry_after_non_retryable_batch_error()
+{
+    let pool = setup_pool().await;
+    let user_id = test_user_id(1);
+    seed_repo_release(&pool, 42, 120).await;
+    seed_star(&pool, 42).await;
+    sqlx::query(
+        r#"
+            UPDATE repo_releases
+            SET body = ?, name = 'Release v1.2.3', tag_name = 'v1.2.3'
+            WHERE release_id = ?
+            "#,
+    )
+    .bind("- item")
+    .bind(120_i64)
+    .execute(&pool)
+    .await
+    .expect("update release body");
+
+    let batch_calls = Arc::new(AtomicUsize::new(0));
+    let fallback_calls = Arc::new(AtomicUsize::new(0));
+    let route_batch_calls = Arc::clone(&batch_calls);
+    let route_fallback_calls = Arc::clone(&fallback_calls);
+    let base_url = spawn_test_ai_server(Router::new().route(
+        "/chat/completions",
+        post(move |Json(payload): Json<Value>| {
+            let route_batch_calls = Arc::clone(&route_batch_calls);
+            let route_fallback_calls = Arc::clone(&route_fallback_calls);
+            async move {
+                let system_prompt = payload["messages"][0]["content"]
+                    .as_str()
+                    .unwrap_or_default();
+                if system_prompt.contains("批量翻译助手") {
+                    route_batch_calls.fetch_add(1, Ordering::SeqCst);
+                    return (
+                        StatusCode::UNAUTHORIZED,
+                        [(header::CONTENT_TYPE, "application/json")],
+                        Json(json!({"error": {"message": "bad key"}})),
+                    );
+                }
+                route_fallback_calls.fetch_add(1, Ordering::SeqCst);
+                let response = serde_json::json!({
+                    "choices": [{"message": {"content": "should not be called"}}],
+                    "usage": {"prompt_tokens": 10, "completion_tokens": 10, "total_tokens": 20}
+                });
+                (
+                    StatusCode::OK,
+                    [(header::CONTENT_TYPE, "application/json")],
+                    Json(response),
+                )
+            }
+        }),
+    ))
+    .await;
+    let state = setup_state_with_ai_base_url(pool.clone(), base_url);
+
+    let translated = translate_releases_batch_for_user(state.as_ref(), user_id.as_str(), &[120])
+        .await
+        .expect("translate release batch");
+    let replay = translate_releases_batch_for_user(state.as_ref(), user_id.as_str(), &[120])
+        .await
+        .expect("translate cached release batch failure");
+
+    assert_eq!(translated.items.len(), 1);
+    assert_eq!(translated.items[0].status, "error");
+    assert_eq!(replay.items.len(), 1);
+    assert_eq!(replay.items[0].status, "error");
+    assert_eq!(batch_calls.load(Ordering::SeqCst), 1);
+    assert_eq!(fallback_calls.load(Ordering::SeqCst), 0);
+
+    let row = sqlx::query(
+        r#"
+            SELECT status, error_text
+            FROM ai_translations
+            WHERE user_id = ?
+              AND entity_type = 'release_detail'
+              AND entity_id = ?
+              AND lang = 'zh-CN'
+            LIMIT 1
+            "#,
+    )
+    .bind(user_id.as_str())
+    .bind("120")
+    .fetch_one(&pool)
+    .await
+    .expect("load persisted terminal detail translation");
+    assert_eq!(row.get::<String, _>("status"), "error");
+    assert!(
+        row.get::<Option<String>, _>("error_text")
+            .as_deref()
+            .is_some_and(|value| value.contains("401") || value.contains("bad key"))
+    );
+}
+
+#[tokio::test]
+async fn get_release_detail_preserves_terminal_missing_state() {
+    let pool = setup_pool().await;
+    let user_id = test_user_id(1);
+    seed_repo_release(&pool, 42, 120).await;
+    seed_star(&pool, 42).await;
+    let state = setup_state_with_ai(pool.clone());
+    let source_hash = release_detail_source_hash("openai/codex", "Release v1.2.3", "- item");
+
+    sqlx::query(
+            r#"
+            INSERT INTO ai_translations (
+              id, user_id, entity_type, entity_id, lang, source_hash, status, title, summary, error_text, active_work_item_id, created_at, updated_at
+            )
+            VALUES (?, ?, 'release_detail', ?, 'zh-CN', ?, 'missing', NULL, NULL, NULL, NULL, ?, ?)
+            "#,
+        )
+        .bind(crate::local_id::generate_local_id())
+        .bind(user_id.as_str())
+        .bind("120")
+        .bind(source_hash.as_str())
+        .bind("2026-02-23T00:00:00Z")
+        .bind("2026-02-23T00:00:00Z")
+        .execute(&pool)
+        .await
+        .expect("seed terminal missing detail translation");
+
+    let Json(detail) = get_release_detail(
+        State(state),
+        AuthedUser(test_user_id(1)),
+        Path("120".to_owned()),
+    )
+    .await
+    .expect("get release detail");
+
+    let translated = detail.translated.expect("translated detail");
+    assert_eq!(translated.status, "missing");
+    assert_eq!(translated.auto_translate, Some(false));
+}
+
+#[tokio::test]
+async fn get_release_detail_keeps_ready_translation_visible_while_refresh_pending() {
+    let pool = setup_pool().await;
+    let user_id = test_user_id(1);
+    seed_repo_release(&pool, 42, 120).await;
+    seed_star(&pool, 42).await;
+    let state = setup_state_with_ai(pool.clone());
+    let work_item_id = "refresh-work-item";
+    let stale_source_hash = release_detail_source_hash("openai/codex", "Release v1.2.3", "- older");
+
+    sqlx::query(
+            r#"
+            INSERT INTO translation_work_items (
+              id, dedupe_key, scope_user_id, kind, variant, entity_id, target_lang, protocol_version,
+              model_profile, source_hash, source_blocks_json, target_slots_json, token_estimate,
+              deadline_at, status, cache_hit, created_at, updated_at
+            )
+            VALUES (?, ?, ?, 'release_detail', 'full', ?, 'zh-CN', 4, 'default', ?, '[]', '[]', 0, ?, 'queued', 0, ?, ?)
+            "#,
+        )
+        .bind(work_item_id)
+        .bind("dedupe-refresh-work-item")
+        .bind(user_id.as_str())
+        .bind("120")
+        .bind(stale_source_hash.as_str())
+        .bind("2026-02-23T00:00:05Z")
+        .bind("2026-02-23T00:00:00Z")
+        .bind("2026-02-23T00:00:00Z")
+        .execute(&pool)
+        .await
+        .expect("seed queued detail refresh work item");
+
+    sqlx::query(
+            r#"
+            INSERT INTO ai_translations (
+              id, user_id, entity_type, entity_id, lang, source_hash, status, title, summary, error_text, active_work_item_id, created_at, updated_at
+            )
+            VALUES (?, ?, 'release_detail', ?, 'zh-CN', ?, 'ready', ?, ?, NULL, ?, ?, ?)
+            "#,
+        )
+        .bind(crate::local_id::generate_local_id())
+        .bind(user_id.as_str())
+        .bind("120")
+        .bind(stale_source_hash.as_str())
+        .bind("旧标题")
+        .bind("- 旧摘要")
+        .bind(work_item_id)
+        .bind("2026-02-23T00:00:00Z")
+        .bind("2026-02-23T00:00:00Z")
+        .execute(&pool)
+        .await
+        .expect("seed stale ready detail translation");
+
+    let Json(detail) = get_release_detail(
+        State(state),
+        AuthedUser(test_user_id(1)),
+        Path("120".to_owned()),
+    )
+    .await
+    .expect("get release detail");
+
+    let translated = detail.translated.expect("translated detail");
+    assert_eq!(translated.status, "ready");
+    assert_eq!(translated.title.as_deref(), Some("旧标题"));
+    assert_eq!(translated.summary.as_deref(), Some("- 旧摘要"));
+}
+
+#[tokio::test]
+async fn get_release_detail_treats_invalid_fresh_ready_as_error() {
+    let pool = setup_pool().await;
+    let user_id = test_user_id(1);
+    seed_repo_release(&pool, 42, 120).await;
+    seed_star(&pool, 42).await;
+    let state = setup_state_with_ai(pool.clone());
+    let source_hash = release_detail_source_hash("openai/codex", "Release v1.2.3", "- item");
+
+    sqlx::query(
+            r#"
+            INSERT INTO ai_translations (
+              id, user_id, entity_type, entity_id, lang, source_hash, status, title, summary, error_text, active_work_item_id, created_at, updated_at
+            )
+            VALUES (?, ?, 'release_detail', ?, 'zh-CN', ?, 'ready', ?, NULL, NULL, NULL, ?, ?)
+            "#,
+        )
+        .bind(crate::local_id::generate_local_id())
+        .bind(user_id.as_str())
+        .bind("120")
+        .bind(source_hash.as_str())
+        .bind("空摘要标题")
+        .bind("2026-02-23T00:00:00Z")
+        .bind("2026-02-23T00:00:00Z")
+        .execute(&pool)
+        .await
+        .expect("seed invalid ready detail translation");
+
+    let Json(detail) = get_release_detail(
+        State(state),
+        AuthedUser(test_user_id(1)),
+        Path("120".to_owned()),
+    )
+    .await
+    .expect("get release detail");
+
+    let translated = detail.translated.expect("translated detail");
+    assert_eq!(translated.status, "error");
+    assert_eq!(
+        translated.error_code.as_deref(),
+        Some("markdown_structure_mismatch")
+    );
+    assert_eq!(
+        translated.error_summary.as_deref(),
+        Some("Markdown 结构校验失败")
+    );
+    assert_eq!(
+        translated.error_detail.as_deref(),
+        Some("release detail translation failed to preserve markdown structure")
+    );
+    assert!(translated.title.is_none());
+    assert!(translated.summary.is_none());
+}
+
+#[tokio::test]
+async fn get_release_detail_rejects_unstarred_release_without_brief_link() {
+    let pool = setup_pool().await;
+    seed_repo_release(&pool, 42, 120).await;
+    let state = setup_state(pool);
+
+    let err = get_release_detail(
+        State(state),
+        AuthedUser(test_user_id(1)),
+        Path("120".to_owned()),
+    )
+    .await
+    .expect_err("release detail should stay hidden");
+
+    assert_eq!(err.into_response().status(), StatusCode::NOT_FOUND);
+}
+
+#[test]
+fn github_graphql_http_error_marks_rate_limit_403() {
+    let mut headers = HeaderMap::new();
+    headers.insert("x-ratelimit-remaining", HeaderValue::from_static("0"));
+    let err = github_graphql_http_error(reqwest::StatusCode::FORBIDDEN, &headers, "")
+        .expect("expected mapped error");
+    assert_eq!(err.code(), "rate_limited");
+}
+
+#[test]
+fn github_graphql_http_error_marks_auth_403() {
+    let headers = HeaderMap::new();
+    let err = github_graphql_http_error(
+        reqwest::StatusCode::FORBIDDEN,
+        &headers,
+        "Requires authentication",
+    )
+    .expect("expected mapped error");
+    assert_eq!(err.code(), "reauth_required");
+}
+
+#[test]
+fn github_graphql_http_error_marks_org_restriction_403() {
+    let headers = HeaderMap::new();
+    let err = github_graphql_http_error(
+        reqwest::StatusCode::FORBIDDEN,
+        &headers,
+        "OAuth App access restrictions are enabled",
+    )
+    .expect("expected mapped error");
+    assert_eq!(err.code(), "forbidden");
+}
+
+#[test]
+fn github_graphql_errors_to_api_error_marks_org_restriction() {
+    let errors = vec![GraphQlError {
+        message: "OAuth App access restrictions are enabled".to_owned(),
+    }];
+    let err = github_graphql_errors_to_api_error(&errors).expect("expected mapped error");
+    assert_eq!(err.code(), "forbidden");
+}
+
+#[test]
+fn public_compare_fallback_retries_on_reauth_required() {
+    assert!(should_retry_public_compare_without_auth(
+        &github_reauth_required_error(),
+    ));
+}
+
+#[test]
+fn public_compare_fallback_retries_on_access_restricted() {
+    assert!(should_retry_public_compare_without_auth(
+        &github_access_restricted_error(),
+    ));
+}
+
+#[test]
+fn public_compare_fallback_skips_other_terminal_errors() {
+    assert!(!should_retry_public_compare_without_auth(
+        &github_rate_limited_error(),
+    ));
+}
+
+#[test]
+fn public_compare_fallback_preserves_access_restricted_error_on_private_repo_failure() {
+    let auth_err = github_access_restricted_error();
+    let public_err = ApiError::new(StatusCode::NOT_FOUND, "not_found", "compare not found");
+    let mapped = map_public_compare_fallback_error(auth_err, public_err);
+    assert_eq!(mapped.code(), "forbidden");
+}
+
+#[test]
+fn public_compare_fallback_maps_reauth_failure_to_private_scope_required() {
+    let auth_err = github_reauth_required_error();
+    let public_err = ApiError::new(StatusCode::NOT_FOUND, "not_found", "compare not found");
+    let mapped = map_public_compare_fallback_error(auth_err, public_err);
+    assert_eq!(mapped.code(), "reauth_required");
+    assert!(
+        mapped
+            .to_string()
+            .contains("private repository compare requires repo scope")
+    );
+}
+
+#[test]
+fn release_reactions_status_requires_per_item_live_data() {
+    let row = test_feed_row(Some("R_node"));
+    assert_eq!(release_reactions_status(&row), "ready");
+}
+
+#[test]
+fn release_reactions_status_ready_with_live_data() {
+    let row = test_feed_row(Some("R_node"));
+    assert_eq!(release_reactions_status(&row), "ready");
+}
+
+#[test]
+fn release_reactions_status_sync_required_without_node_id() {
+    let row = test_feed_row(None);
+    assert_eq!(release_reactions_status(&row), "sync_required");
+}
+
+#[test]
+fn release_feed_body_keeps_markdown_structure() {
+    let body = r#"
+# Changelog
+
+- Added **markdown** rendering
+- Keep `inline code` markers
+1. Ordered item
+
+```bash
+echo kept_in_code_block
+```
+"#;
+
+    let body = release_feed_body(Some(body)).expect("feed body");
+    assert!(body.starts_with("# Changelog\n\n- Added **markdown** rendering"));
+    assert!(body.contains("- Keep `inline code` markers"));
+    assert!(body.contains("1. Ordered item"));
+    assert!(body.ends_with("```bash\necho kept_in_code_block\n```"));
+}
+
+#[test]
+fn release_feed_body_keeps_newlines() {
+    let body = "First line\r\nSecond line\r\n\r\nThird line";
+    assert_eq!(
+        release_feed_body(Some(body)).as_deref(),
+        Some("First line\nSecond line\n\nThird line")
+    );
+}
+
+const TABLE_HEAVY_RELEASE_BODY: &str = r#"
+## Dependency updates
+
+| Module | From | To |
+| --- | --- | --- |
+| golang.org/x/net | v0.20.0 | v0.21.0 |
+| golang.org/x/sys | v0.15.0 | v0.16.0 |
+| golang.org/x/text | v0.13.0 | v0.14.0 |
+| google.golang.org/grpc | v1.60.0 | v1.61.0 |
+| github.com/spf13/cobra | v1.7.0 | v1.8.0 |
+| github.com/stretchr/testify | v1.8.3 | v1.8.4 |
+| go.uber.org/zap | v1.25.0 | v1.26.0 |
+
+See the [full changelog](https://github.com/octo/go-tool/compare/v1.4.0...v1.5.0).
+"#;
+
+const COMMENT_HEAVY_RELEASE_BODY: &str = r#"
+<!-- Release notes generated using configuration in .github/release.yml at main -->
+## What's Changed
+<!--
+  bot: do not edit below this line
+  template: default
+-->
+* Fix panic on empty config by @octo in https://github.com/octo/tool/pull/12 <!-- pr-12 -->
+* Add `--json` output by @rill in [#13](https://github.com/octo/tool/pull/13)
+
+```html
+<!-- comments inside code are shown as written -->
+```
+"#;
+
+fn fixture_feed_row(body: &str) -> FeedRow {
+    let mut row = test_feed_row(Some("R_node"));
+    row.repo_full_name = Some("octo/go-tool".to_owned());
+    row.title = Some("v1.5.0".to_owned());
+    row.release_body = Some(body.to_owned());
+    row
+}
+
+#[test]
+fn feed_item_keeps_leading_table_rows_intact() {
+    let item = feed_item_from_row(
+        fixture_feed_row(TABLE_HEAVY_RELEASE_BODY),
+        false,
+        None,
+        FeedExcerpt::default(),
+    );
+    let body = item.body.expect("feed body");
+    let table_lines = body
+        .lines()
+        .filter(|line| line.starts_with('|'))
+        .collect::<Vec<_>>();
+
+    assert_eq!(table_lines.len(), 2 + RELEASE_EXCERPT_TABLE_MAX_ROWS);
+    assert_eq!(table_lines[0], "| Module | From | To |");
+    assert_eq!(table_lines[1], "| --- | --- | --- |");
+    assert!(body.contains("| github.com/spf13/cobra | v1.7.0 | v1.8.0 |"));
+    assert!(!body.contains("testify"));
+    assert!(body.ends_with(
+        "See the [full changelog](https://github.com/octo/go-tool/compare/v1.4.0...v1.5.0)."
+    ));
+    assert!(
+        item.body_truncated,
+        "dropped table rows mark the body truncated"
+    );
+}
+
+#[test]
+fn feed_item_skips_html_comments_outside_code() {
+    let item = feed_item_from_row(
+        fixture_feed_row(COMMENT_HEAVY_RELEASE_BODY),
+        false,
+        None,
+        FeedExcerpt::default(),
+    );
+
+    assert_eq!(
+        item.body.as_deref(),
+        Some(
+            "## What's Changed\n\
+             * Fix panic on empty config by @octo in https://github.com/octo/tool/pull/12\n\
+             * Add `--json` output by @rill in [#13](https://github.com/octo/tool/pull/13)\n\
+             \n\
+             ```html\n\
+             <!-- comments inside code are shown as written -->\n\
+             ```"
+        )
+    );
+    assert!(!item.body_truncated);
+}
+
+#[test]
+fn release_feed_body_truncates_on_line_boundaries_without_counting_link_targets() {
+    let url = format!("https://example.com/{}", "a".repeat(200));
+    let body = (0..40)
+        .map(|idx| format!("- change {idx:02} {} [notes]({url})", "x".repeat(40)))
+        .collect::<Vec<_>>()
+        .join("\n");
+    assert!(body.chars().count() > RELEASE_FEED_BODY_MAX_CHARS);
+
+    // Each bullet shows ~60 chars, so all 40 fit once link targets are not counted.
+    let excerpt = release_feed_body(Some(&body)).expect("feed body");
+    assert_eq!(excerpt, body);
+    assert!(!release_feed_body_is_over_limit(Some(&body)));
+
+    let long_body = (0..60)
+        .map(|idx| format!("- entry {idx:02} {}", "y".repeat(80)))
+        .collect::<Vec<_>>()
+        .join("\n");
+    let excerpt = release_feed_body(Some(&long_body)).expect("feed body");
+    assert!(excerpt.chars().count() <= RELEASE_FEED_BODY_MAX_CHARS);
+    assert!(excerpt.lines().all(|line| line.ends_with(&"y".repeat(80))));
+    assert!(release_feed_body_is_over_limit(Some(&long_body)));
+}
+
+#[tokio::test]
+async fn prepare_release_batch_does_not_reuse_v5_translation_of_reshaped_body() {
+    let pool = setup_pool().await;
+    let user_id = test_user_id(1);
+    seed_repo_release(&pool, 42, 120).await;
+    seed_star(&pool, 42).await;
+    set_release_body(&pool, 120, COMMENT_HEAVY_RELEASE_BODY).await;
+    // v5 hashed the body with its HTML comments; v6 drops them, so that
+    // translation no longer matches what the feed shows.
+    let v5_hash = ai::sha256_hex(&format!(
+        "v=5\nkind=release\nrepo=openai/codex\ntitle=Release v1.2.3\nbody={}\n",
+        COMMENT_HEAVY_RELEASE_BODY.trim()
+    ));
+    sqlx::query(
+            r#"
+            INSERT INTO ai_translations (
+              id, user_id, entity_type, entity_id, lang, source_hash,
+              title, summary, created_at, updated_at, status
+            )
+            VALUES ('translation-release-120', ?, 'release', '120', 'zh-CN', ?, '标题', '摘要', '2026-02-23T00:00:00Z', '2026-02-23T00:00:00Z', 'ready')
+            "#,
+        )
+        .bind(user_id.as_str())
+        .bind(v5_hash.as_str())
+        .execute(&pool)
+        .await
+        .expect("seed v5 translation");
+    let state = setup_state_with_ai(pool.clone());
+
+    let prepared = prepare_release_batch(state.as_ref(), user_id.as_str(), &[120])
+        .await
+        .expect("prepare release batch");
+
+    assert!(!prepared.translated.contains_key(&120));
+    let stored_hash = sqlx::query_scalar::<_, String>(
+        "SELECT source_hash FROM ai_translations WHERE id = 'translation-release-120'",
+    )
+    .fetch_one(&pool)
+    .await
+    .expect("load stored hash");
+    assert_eq!(stored_hash, v5_hash);
+}
+
+#[test]
+fn parse_translation_json_accepts_fenced_json() {
+    let raw = r#"```json
+{"title_zh":"标题","summary_md":"- **加粗**\\n- `code`"}
+```"#;
+    let parsed = parse_translation_json(raw).expect("parse translation json");
+    assert_eq!(parsed.title_zh.as_deref(), Some("标题"));
+    assert_eq!(parsed.summary_md.as_deref(), Some("- **加粗**\n- `code`"));
+}
+
+#[tokio::test]
+async fn run_translation_batch_groups_overlaps_groups_up_to_concurrency() {
+    use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+
+    let in_flight = AtomicUsize::new(0);
+    let max_in_flight = AtomicUsize::new(0);
+    let abort = AtomicBool::new(false);
+
+    let mut finished = run_translation_batch_groups((0..6).collect(), 3, &abort, |group: usize| {
+        let in_flight = &in_flight;
+        let max_in_flight = &max_in_flight;
+        // Stands in for one batch chat completion call.
+        async move {
+            let current = in_flight.fetch_add(1, Ordering::SeqCst) + 1;
+            max_in_flight.fetch_max(current, Ordering::SeqCst);
+            tokio::time::sleep(std::time::Duration::from_millis(20)).await;
+            in_flight.fetch_sub(1, Ordering::SeqCst);
+            group
+        }
+    })
+    .await;
+
+    finished.sort_unstable();
+    assert_eq!(finished, (0..6).collect::<Vec<_>>());
+    assert_eq!(max_in_flight.load(Ordering::SeqCst), 3);
+}
+
+#[tokio::test]
+async fn run_translation_batch_groups_abort_skips_groups_not_yet_started() {
+    use std::sync::atomic::{AtomicBool, Ordering};
+
+    let launched = std::sync::Mutex::new(Vec::new());
+    let abort = AtomicBool::new(false);
+
+    let finished = run_translation_batch_groups((0..6).collect(), 2, &abort, |group: usize| {
+        let launched = &launched;
+        let abort = &abort;
+        async move {
+            launched.lock().expect("launched lock").push(group);
+            if group == 0 {
+                // Simulates a non-retryable upstream error on the first group.
+                tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+                abort.store(true, Ordering::Release);
+            } else {
+                tokio::time::sleep(std::time::Duration::from_millis(40)).await;
+            }
+            group
+        }
+    })
+    .await;
+
+    let mut launched = launched.into_inner().expect("launched lock");
+    launched.sort_unstable();
+    assert_eq!(launched, vec![0, 1]);
+    assert_eq!(finished.len(), 2);
+}
+
+#[test]
+fn extract_partial_release_batch_summaries_reads_unfinished_items() {
+    let raw = r#"{"items":[{"release_id":11,"title_zh":"一","summary_md":"- 修复 \"崩溃\"\n- 完成"},{"release_id":12,"title_zh":"二","summary_md":"- 新增é 功\n- 进行"#;
+
+    let partials = extract_partial_release_batch_summaries(raw);
+
+    assert_eq!(
+        partials,
+        vec![
+            (11, "- 修复 \"崩溃\"\n- 完成".to_owned()),
+            (12, "- 新增é 功\n- 进行".to_owned()),
+        ]
+    );
+    assert_eq!(
+        extract_partial_release_batch_summaries(
+            r#"{"items":[{"release_id":13,"summary_md":"a\u00"#
+        ),
+        vec![(13, "a".to_owned())]
+    );
+    assert!(
+        extract_partial_release_batch_summaries(r#"{"items":[{"release_id":14,"ti"#).is_empty()
+    );
+}
+
+#[test]
+fn parse_batch_release_translation_payload_accepts_relaxed_fields() {
+    let raw = r#"```json
+{"items":[{"id":"123","title":"标题","summary":"- 第一行\\n- 第二行"}]}
+```"#;
+    let parsed = parse_batch_release_translation_payload(raw).expect("parse release batch payload");
+    assert_eq!(parsed.items.len(), 1);
+    assert_eq!(parsed.items[0].release_id, 123);
+    assert_eq!(parsed.items[0].title_zh.as_deref(), Some("标题"));
+    assert_eq!(
+        parsed.items[0].summary_md.as_deref(),
+        Some("- 第一行\n- 第二行")
+    );
+}
+
+#[test]
+fn normalize_translation_fields_extracts_embedded_json_blob() {
+    let (title, summary) = normalize_translation_fields(
+        None,
+        Some(r#"{"title_zh":"发布 1.2.3","summary_md":"- 第一行\\n- 第二行"}"#.to_owned()),
+    );
+    assert_eq!(title.as_deref(), Some("发布 1.2.3"));
+    assert_eq!(summary.as_deref(), Some("- 第一行\n- 第二行"));
+}
+
+#[test]
+fn normalize_translation_fields_discards_malformed_json_blob() {
+    let (title, summary) = normalize_translation_fields(
+        Some("原始标题".to_owned()),
+        Some(r#"{"title_zh":"发布 1.2.3","summary_md":""#.to_owned()),
+    );
+    assert_eq!(title.as_deref(), Some("原始标题"));
+    assert_eq!(summary, None);
+}
+
+#[test]
+fn extract_translation_fields_rejects_malformed_json_blob() {
+    let (title, summary) = extract_translation_fields(r#"{"title_zh":"发布 1.2.3","summary_md":""#);
+    assert_eq!(title, None);
+    assert_eq!(summary, None);
+}
+
+#[test]
+fn looks_like_json_blob_accepts_fenced_json() {
+    let raw = "```json\n{\"title_zh\":\"标题\",\"summary_md\":\"- 一行\"}\n```";
+    assert!(looks_like_json_blob(raw));
+}
+
+#[test]
+fn parse_batch_notification_translation_payload_accepts_array_root() {
+    let raw = r#"[{"id":"thread-1","title_cn":"标题","body":"- 第一行\\n- 第二行"}]"#;
+    let parsed = parse_batch_notification_translation_payload(raw)
+        .expect("parse notification batch payload");
+    assert_eq!(parsed.items.len(), 1);
+    assert_eq!(parsed.items[0].thread_id, "thread-1");
+    assert_eq!(parsed.items[0].title_zh.as_deref(), Some("标题"));
+    assert_eq!(
+        parsed.items[0].summary_md.as_deref(),
+        Some("- 第一行\n- 第二行")
+    );
+}
+
+#[test]
+fn parse_batch_release_detail_payload_accepts_string_index() {
+    let raw = r#"{"translations":[{"index":"2","summary":"- a\\n- b"}]}"#;
+    let parsed = parse_batch_release_detail_translation_payload(raw)
+        .expect("parse release detail batch payload");
+    assert_eq!(parsed.items.len(), 1);
+    assert_eq!(parsed.items[0].chunk_index, 2);
+    assert_eq!(parsed.items[0].summary_md, "- a\n- b");
+}
+
+#[test]
+fn markdown_structure_requires_inline_markers() {
+    let source = "- **Nightly** build from `main`\n- Keep **bold** marker";
+    let translated_missing = "- 夜间构建来自 main\n- 请保留强调";
+    let translated_ok = "- **夜间**构建来自 `main`\n- 请保留 **强调** 标记";
+    for check in [
+        MarkdownStructureCheck::Strict,
+        MarkdownStructureCheck::Relaxed,
+    ] {
+        assert!(!markdown_structure_preserved(
+            source,
+            translated_missing,
+            check
+        ));
+        assert!(markdown_structure_preserved(source, translated_ok, check));
+    }
+}
+
+#[test]
+fn markdown_structure_relaxed_accepts_merged_wrapped_paragraph() {
+    let source = "## Highlights\nThis release rewrites the sync engine so that large\naccounts no longer time out while fetching starred\nrepositories.\n- Faster sync";
+    let translated =
+        "## 亮点\n此版本重写了同步引擎，大型账号在拉取星标仓库时不再超时。\n- 同步更快";
+    assert!(!markdown_structure_preserved(
+        source,
+        translated,
+        MarkdownStructureCheck::Strict
+    ));
+    assert!(markdown_structure_preserved(
+        source,
+        translated,
+        MarkdownStructureCheck::Relaxed
+    ));
+}
+
+#[test]
+fn markdown_structure_relaxed_accepts_merged_list_continuation_line() {
+    let source =
+        "- Fix a crash when the token expires while\n  a sync is still running\n- Bump deps";
+    let translated = "- 修复同步仍在运行时令牌过期导致的崩溃\n- 升级依赖";
+    assert!(markdown_structure_preserved(
+        source,
+        translated,
+        MarkdownStructureCheck::Relaxed
+    ));
+}
+
+#[test]
+fn markdown_structure_ignores_trailing_whitespace_and_blank_lines_between_items() {
+    let source =
+        "### Fixes  \r\n\r\n- First fix   \n\n- Second fix\t\n\n1. Step one\n\n2. Step two\n";
+    let translated = "### 修复\n- 第一个修复\n- 第二个修复\n1. 第一步\n2. 第二步";
+    for check in [
+        MarkdownStructureCheck::Strict,
+        MarkdownStructureCheck::Relaxed,
+    ] {
+        assert!(markdown_structure_preserved(source, translated, check));
+    }
+}
+
+#[test]
+fn markdown_structure_relaxed_rejects_lost_headings_or_list_items() {
+    let source = "## Changes\n- One\n- Two\n- Three";
+    let lost_heading = "变更\n- 一\n- 二\n- 三";
+    let merged_items = "## 变更\n- 一、二、三";
+    let list_as_prose = "## 变更\n一\n二\n三";
+    for translated in [lost_heading, merged_items, list_as_prose] {
+        assert!(!markdown_structure_preserved(
+            source,
+            translated,
+            MarkdownStructureCheck::Relaxed
+        ));
+    }
+}
+
+#[test]
+fn markdown_structure_relaxed_limits_plain_line_drift() {
+    let source = "one\ntwo\nthree\nfour\nfive\nsix\nseven\neight\nnine\nten";
+    let within_tolerance = "一\n二\n三\n四\n五\n六\n七";
+    let beyond_tolerance = "一二三四五六七八九十";
+    assert!(markdown_structure_preserved(
+        source,
+        within_tolerance,
+        MarkdownStructureCheck::Relaxed
+    ));
+    assert!(!markdown_structure_preserved(
+        source,
+        beyond_tolerance,
+        MarkdownStructureCheck::Relaxed
+    ));
+}
+
+#[test]
+fn extract_translation_link_refs_covers_link_forms_in_both_scripts() {
+    let source = "- Docs: [guide](https://example.com/guide#setup) and <https://example.com/faq>\n\
+                  - See https://github.com/o/r/pull/12, fixes #1234 (also #56).\n\
+                  - Not refs: `a#1`, &#123;, v1.2#3";
+    let refs = extract_translation_link_refs(source);
+    assert_eq!(
+        refs.into_iter().collect::<Vec<_>>(),
+        vec![
+            "#1234",
+            "#56",
+            "https://example.com/faq",
+            "https://example.com/guide#setup",
+            "https://github.com/o/r/pull/12",
+        ]
+    );
+
+    let translated = "- 文档：[指南](https://example.com/guide#setup)与<https://example.com/faq>\n\
+                      - 参见https://github.com/o/r/pull/12，修复了#1234（另见＃56）。";
+    assert_eq!(
+        extract_translation_link_refs(translated),
+        extract_translation_link_refs(source)
+    );
+}
+
+#[test]
+fn translation_links_preserved_rejects_invented_links() {
+    let source = "- Fix login loop (#88)\n- Docs at https://example.com/docs";
+    let invented_url = "- 修复登录循环 (#88)\n- 文档见 https://example.com/docs/zh";
+    let invented_issue = "- 修复登录循环 (#88, #89)\n- 文档见 https://example.com/docs";
+    let ok = "- 修复登录循环（#88）\n- 文档见 https://example.com/docs/";
+    assert!(!translation_links_preserved(source, invented_url));
+    assert!(!translation_links_preserved(source, invented_issue));
+    assert!(translation_links_preserved(source, ok));
+}
+
+#[test]
+fn translation_links_preserved_tolerates_one_dropped_link() {
+    let source = "- [#1](https://github.com/o/r/issues/1) fixed\n- see #2 and #3";
+    let dropped_one = "- [#1](https://github.com/o/r/issues/1) 已修复\n- 另见 #2";
+    let dropped_two = "- [#1](https://github.com/o/r/issues/1) 已修复\n- 另见相关问题";
+    assert!(translation_links_preserved(source, dropped_one));
+    assert!(!translation_links_preserved(source, dropped_two));
+    assert!(translation_links_preserved("纯文本", "plain text"));
+}
+
+#[test]
+fn split_markdown_chunks_preserves_order() {
+    let md = "line1\nline2\nline3\nline4";
+    let chunks = split_markdown_chunks(md, 12);
+    assert!(chunks.len() >= 2);
+    let rebuilt = chunks.join("");
+    assert_eq!(rebuilt, md);
+}
+
+#[test]
+fn split_markdown_chunks_splits_overlong_single_line() {
+    let md = "a".repeat(25);
+    let chunks = split_markdown_chunks(&md, 8);
+    assert!(chunks.len() >= 4);
+    assert!(chunks.iter().all(|chunk| chunk.chars().count() <= 8));
+    assert_eq!(chunks.join(""), md);
+}
+
+#[test]
+fn split_markdown_chunks_reopens_oversized_code_fence() {
+    let code = (0..100)
+        .map(|idx| format!("let value_{idx:03} = compute({idx:03});\n"))
+        .collect::<String>();
+    let md = format!("## Changes\n\n```rust\n{code}```\n\nDone.\n");
+    assert!(code.chars().count() >= 3000);
+
+    let chunks = split_markdown_chunks(&md, 1000);
+    assert!(chunks.len() >= 4);
+    assert_eq!(chunks[0], "## Changes\n\n");
+    for chunk in &chunks[1..chunks.len() - 1] {
+        assert!(chunk.starts_with("```rust\n"), "chunk reopens: {chunk:?}");
+        assert!(chunk.ends_with("```\n"), "chunk closes: {chunk:?}");
+        assert_eq!(chunk.matches("```").count(), 2);
+        assert!(chunk.chars().count() <= 1000);
+    }
+    assert_eq!(chunks.last().map(String::as_str), Some("\nDone.\n"));
+    assert_eq!(chunks.join("").replace("```\n```rust\n", ""), md);
+}
+
+#[test]
+fn split_markdown_chunks_keeps_fitting_blocks_whole() {
+    let intro = "x".repeat(40);
+    let table = "| a | b |\n| --- | --- |\n| 1 | 2 |\n| 3 | 4 |\n";
+    let md = format!("{intro}\n{table}tail\n```\nfn main() {{}}\n```\n");
+
+    // A plain line walk would cut this table after its header row.
+    let chunks = split_markdown_chunks(&md, 60);
+    assert_eq!(chunks[0], format!("{intro}\n"));
+    assert_eq!(chunks[1], format!("{table}tail\n"));
+    assert_eq!(chunks[2], "```\nfn main() {}\n```\n");
+    assert_eq!(chunks.join(""), md);
+}
+
+#[test]
+fn parse_repo_full_name_from_release_url_extracts_owner_repo() {
+    let full_name =
+        parse_repo_full_name_from_release_url("https://github.com/acme/rocket/releases/tag/v1.8.0");
+    assert_eq!(full_name.as_deref(), Some("acme/rocket"));
+}
+
+#[test]
+fn resolve_release_full_name_falls_back_when_url_invalid() {
+    let full_name = resolve_release_full_name("https://example.com/not-github", 42);
+    assert_eq!(full_name, "unknown/42");
+}
+
+#[test]
+fn preserve_chunk_edge_newlines_keeps_chunk_boundaries() {
+    assert_eq!(
+        preserve_chunk_edge_newlines("line\n", "译文".to_owned()),
+        "译文\n"
+    );
+    assert_eq!(
+        preserve_chunk_edge_newlines("line", "译文".to_owned()),
+        "译文"
+    );
+    assert_eq!(
+        preserve_chunk_edge_newlines("line\n", "译文\n".to_owned()),
+        "译文\n"
+    );
+    assert_eq!(
+        preserve_chunk_edge_newlines("\nline\n\n", "line\n".to_owned()),
+        "\nline\n\n"
+    );
+}
+
+#[test]
+fn normalize_markdown_translation_output_strips_outer_fence_only() {
+    let normalized = normalize_markdown_translation_output(
+        "- item\n",
+        "```markdown\n- 条目\n```\n\n".to_owned(),
+    );
+    assert_eq!(normalized, "- 条目\n");
+}
+
+#[test]
+fn normalize_markdown_translation_output_keeps_fenced_code_only_chunks() {
+    let normalized = normalize_markdown_translation_output(
+        "```rust\nfn main() {}\n```\n",
+        "```rust\nfn main() {}\n```\n\n".to_owned(),
+    );
+    assert_eq!(normalized, "```rust\nfn main() {}\n```\n");
+}
+
+#[test]
+fn normalize_markdown_translation_output_strips_outer_fence_for_multi_block_chunks() {
+    let source = "```rust\nfn main() {}\n```\n\n说明文字\n\n```json\n{}\n```\n";
+    let normalized = normalize_markdown_translation_output(
+        source,
+        "```markdown\n```rust\nfn main() {}\n```\n\n说明文字\n\n```json\n{}\n```\n```\n".to_owned(),
+    );
+    assert_eq!(normalized, source);
+}
+
+#[test]
+fn normalize_markdown_translation_output_strips_outer_wrapper_for_code_only_chunks() {
+    let source = "```rust\nfn main() {}\n```\n";
+    let normalized = normalize_markdown_translation_output(
+        source,
+        "```markdown\n```rust\nfn main() {}\n```\n```\n".to_owned(),
+    );
+    assert_eq!(normalized, source);
+}
+
+#[test]
+fn normalize_markdown_translation_output_keeps_valid_multi_block_fences() {
+    let source = "```rust\nfn main() {}\n```\n\n说明文字\n\n```json\n{}\n```\n";
+    let normalized = normalize_markdown_translation_output(source, source.to_owned());
+    assert_eq!(normalized, source);
+}
+
+#[test]
+fn normalize_markdown_translation_output_preserves_leading_indentation() {
+    let source = "    缩进代码块\n";
+    let normalized = normalize_markdown_translation_output(source, source.to_owned());
+    assert_eq!(normalized, source);
+}
+
+#[test]
+fn normalize_markdown_translation_output_preserves_indentation_when_unwrapping_outer_fence() {
+    let source = "    缩进代码块  \n";
+    let normalized = normalize_markdown_translation_output(
+        source,
+        "```markdown\n    缩进代码块  \n```\n".to_owned(),
+    );
+    assert_eq!(normalized, source);
+}
+
+#[test]
+fn normalize_markdown_translation_output_preserves_blank_line_padding() {
+    let source = "\n说明文字\n\n";
+    let normalized = normalize_markdown_translation_output(
+        source,
+        "```markdown\n\n说明文字\n\n```\n".to_owned(),
+    );
+    assert_eq!(normalized, source);
+}
+
+#[test]
+fn normalize_markdown_translation_output_strips_wrapped_fence_with_surrounding_spaces() {
+    let source = "- 条目\n";
+    let normalized =
+        normalize_markdown_translation_output(source, " ```markdown\n- 条目\n``` ".to_owned());
+    assert_eq!(normalized, source);
+}
+
+#[tokio::test]
+async fn persist_daily_brief_profile_rejects_missing_scheduler_slots() {
+    let pool = setup_pool().await;
+    sqlx::query(
+        r#"
+            UPDATE daily_brief_hour_slots
+            SET enabled = 0, updated_at = '2026-04-14T00:00:00Z'
+            WHERE hour_utc = 12
+            "#,
+    )
+    .execute(&pool)
+    .await
+    .expect("disable scheduler slot");
+    let state = setup_state(pool);
+
+    let err = super::persist_daily_brief_profile(
+        state.as_ref(),
+        test_user_id(1).as_str(),
+        super::DailyBriefProfilePatchRequest {
+            daily_brief_local_time: "08:00".to_owned(),
+            daily_brief_time_zone: "America/New_York".to_owned(),
+            include_own_releases: None,
+            brief_email_enabled: None,
+            brief_email: None,
+            sync_frequency_minutes: None,
+            muted_notification_reasons: None,
+            skip_muted_notifications: None,
+        },
+    )
+    .await
+    .expect_err("profile update should fail when required slot is disabled");
+
+    assert_eq!(err.code(), "bad_request");
+    assert!(err.to_string().contains("12:00Z"));
+}
+
+#[tokio::test]
+async fn persist_daily_brief_profile_accepts_supported_scheduler_slots() {
+    let pool = setup_pool().await;
+    let state = setup_state(pool.clone());
+
+    let profile = super::persist_daily_brief_profile(
+        state.as_ref(),
+        test_user_id(1).as_str(),
+        super::DailyBriefProfilePatchRequest {
+            daily_brief_local_time: "08:00".to_owned(),
+            daily_brief_time_zone: "America/New_York".to_owned(),
+            include_own_releases: None,
+            brief_email_enabled: None,
+            brief_email: None,
+            sync_frequency_minutes: None,
+            muted_notification_reasons: None,
+            skip_muted_notifications: None,
+        },
+    )
+    .await
+    .expect("profile update should succeed");
+
+    assert_eq!(profile.daily_brief_local_time, "08:00");
+    assert_eq!(profile.daily_brief_time_zone, "America/New_York");
+    assert!(!profile.include_own_releases);
+
+    let row = sqlx::query_as::<_, (Option<String>, Option<String>, i64)>(
+        r#"
+            SELECT daily_brief_local_time, daily_brief_time_zone, include_own_releases
+            FROM users
+            WHERE id = ?
+            "#,
+    )
+    .bind(test_user_id(1))
+    .fetch_one(&pool)
+    .await
+    .expect("load persisted profile");
+
+    assert_eq!(row.0.as_deref(), Some("08:00"));
+    assert_eq!(row.1.as_deref(), Some("America/New_York"));
+    assert_eq!(row.2, 0);
+}
+
+#[tokio::test]
+async fn persist_sync_runtime_config_updates_global_interval() {
+    let pool = setup_pool().await;
+    let state = setup_state(pool.clone());
+
+    let settings = super::persist_sync_runtime_config(
+        state.as_ref(),
+        super::SyncRuntimeConfigPatchRequest {
+            sync_auto_fetch_interval_minutes: 10,
+            retry_recent_failures_interval_minutes: Some(15),
+            repo_release_worker_concurrency: Some(12),
+            repo_refresh_system_budget_per_window: None,
+        },
+    )
+    .await
+    .expect("sync settings update should succeed");
+
+    assert_eq!(settings.sync_auto_fetch_interval_minutes, 10);
+    assert_eq!(settings.retry_recent_failures_interval_minutes, 15);
+    assert_eq!(settings.repo_release_worker_concurrency, 12);
+
+    let row = sqlx::query_as::<_, (i64, i64, i64)>(
+            r#"
+            SELECT sync_auto_fetch_interval_minutes, retry_recent_failures_interval_minutes, repo_release_worker_concurrency
+            FROM admin_runtime_settings
+            WHERE id = 1
+            "#,
+        )
+        .fetch_one(&pool)
+        .await
+        .expect("load sync runtime config");
+
+    assert_eq!(row.0, 10);
+    assert_eq!(row.1, 15);
+    assert_eq!(row.2, 12);
+}
+
+#[tokio::test]
+async fn persist_sync_runtime_config_rejects_interval_out_of_range() {
+    let pool = setup_pool().await;
+    let state = setup_state(pool);
+
+    for interval in [0, 121] {
+        let err = super::persist_sync_runtime_config(
+            state.as_ref(),
+            super::SyncRuntimeConfigPatchRequest {
+                sync_auto_fetch_interval_minutes: interval,
+                retry_recent_failures_interval_minutes: None,
+                repo_release_worker_concurrency: None,
+                repo_refresh_system_budget_per_window: None,
+            },
+        )
+        .await
+        .expect_err("sync settings update should reject invalid interval");
+
+        assert_eq!(err.code(), "bad_request");
+        assert!(
+            err.to_string()
+                .contains("sync_auto_fetch_interval_minutes must be between 1 and 120")
+        );
+    }
+}
+
+#[tokio::test]
+async fn persist_sync_runtime_config_rejects_retry_interval_out_of_range() {
+    let pool = setup_pool().await;
+    let state = setup_state(pool.clone());
+
+    let err = super::persist_sync_runtime_config(
+        state.as_ref(),
+        super::SyncRuntimeConfigPatchRequest {
+            sync_auto_fetch_interval_minutes: 10,
+            retry_recent_failures_interval_minutes: Some(0),
+            repo_release_worker_concurrency: None,
+            repo_refresh_system_budget_per_window: None,
+        },
+    )
+    .await
+    .expect_err("sync settings update should reject invalid retry interval");
+
+    assert_eq!(err.code(), "bad_request");
+    assert!(
+        err.to_string()
+            .contains("retry_recent_failures_interval_minutes must be between 1 and 120")
+    );
+}
+
+#[tokio::test]
+async fn persist_sync_runtime_config_rejects_worker_concurrency_without_partial_update() {
+    let pool = setup_pool().await;
+    let state = setup_state(pool.clone());
+
+    let err = super::persist_sync_runtime_config(
+        state.as_ref(),
+        super::SyncRuntimeConfigPatchRequest {
+            sync_auto_fetch_interval_minutes: 10,
+            retry_recent_failures_interval_minutes: None,
+            repo_release_worker_concurrency: Some(33),
+            repo_refresh_system_budget_per_window: None,
+        },
+    )
+    .await
+    .expect_err("sync settings update should reject invalid worker count");
+
+    assert_eq!(err.code(), "bad_request");
+    assert!(
+        err.to_string()
+            .contains("repo_release_worker_concurrency must be between 1 and 32")
+    );
+
+    let row = sqlx::query_as::<_, (i64, i64)>(
+        r#"
+            SELECT sync_auto_fetch_interval_minutes, repo_release_worker_concurrency
+            FROM admin_runtime_settings
+            WHERE id = 1
+            "#,
+    )
+    .fetch_optional(&pool)
+    .await
+    .expect("load sync runtime config");
+
+    assert!(
+        row.is_none(),
+        "invalid patch must not seed or mutate settings"
+    );
+}
+
+#[tokio::test]
+async fn load_daily_brief_profile_defaults_include_own_releases_to_false() {
+    let pool = setup_pool().await;
+    let state = setup_state(pool);
+
+    let profile = super::load_daily_brief_profile(state.as_ref(), test_user_id(1).as_str())
+        .await
+        .expect("load profile");
+
+    assert!(!profile.include_own_releases);
+}
+
+#[tokio::test]
+async fn load_sync_runtime_config_includes_recent_task_durations() {
+    let pool = setup_pool().await;
+    let state = setup_state(pool.clone());
+
+    let skipped_task_id = crate::local_id::test_local_id("subscription-history-skipped");
+    sqlx::query(
+        r#"
+            INSERT INTO job_tasks (
+              id,
+              task_type,
+              status,
+              source,
+              requested_by,
+              parent_task_id,
+              payload_json,
+              result_json,
+              error_message,
+              cancel_requested,
+              created_at,
+              started_at,
+              finished_at,
+              updated_at
+            )
+            VALUES (?, ?, 'succeeded', 'scheduler', NULL, NULL, '{}', ?, NULL, 0, ?, ?, ?, ?)
+            "#,
+    )
+    .bind(skipped_task_id.as_str())
+    .bind(jobs::TASK_SYNC_SUBSCRIPTIONS)
+    .bind(r#"{"skipped":true,"skip_reason":"previous_run_active"}"#)
+    .bind("2026-04-27T08:40:00Z")
+    .bind("2026-04-27T08:40:01Z")
+    .bind("2026-04-27T08:40:01Z")
+    .bind("2026-04-27T08:40:01Z")
+    .execute(&pool)
+    .await
+    .expect("seed skipped subscription sync history");
+
+    for (idx, task_id, started_at, finished_at) in [
+        (
+            1,
+            crate::local_id::test_local_id("subscription-history-1"),
+            "2026-04-27T08:00:01Z",
+            "2026-04-27T08:00:06Z",
+        ),
+        (
+            2,
+            crate::local_id::test_local_id("subscription-history-2"),
+            "2026-04-27T08:10:01Z",
+            "2026-04-27T08:10:11Z",
+        ),
+        (
+            3,
+            crate::local_id::test_local_id("subscription-history-3"),
+            "2026-04-27T08:20:01Z",
+            "2026-04-27T08:20:16Z",
+        ),
+        (
+            4,
+            crate::local_id::test_local_id("subscription-history-4"),
+            "2026-04-27T08:30:01Z",
+            "2026-04-27T08:30:21Z",
+        ),
+    ] {
+        sqlx::query(
+            r#"
+                INSERT INTO job_tasks (
+                  id,
+                  task_type,
+                  status,
+                  source,
+                  requested_by,
+                  parent_task_id,
+                  payload_json,
+                  result_json,
+                  error_message,
+                  cancel_requested,
+                  created_at,
+                  started_at,
+                  finished_at,
+                  updated_at
+                )
+                VALUES (?, ?, 'succeeded', 'scheduler', NULL, NULL, '{}', '{}', NULL, 0, ?, ?, ?, ?)
+                "#,
+        )
+        .bind(task_id.as_str())
+        .bind(jobs::TASK_SYNC_SUBSCRIPTIONS)
+        .bind(format!("2026-04-27T08:{:02}:00Z", idx * 10 - 10))
+        .bind(started_at)
+        .bind(finished_at)
+        .bind(finished_at)
+        .execute(&pool)
+        .await
+        .expect("seed subscription sync history");
+    }
+
+    let newest_task_id = crate::local_id::test_local_id("subscription-history-4");
+    for (task_type, child_id, created_at, started_at, finished_at) in [
+        (
+            jobs::TASK_TRANSLATE_RELEASE_BATCH,
+            crate::local_id::test_local_id("subscription-history-4-translate"),
+            "2026-04-27T08:30:22Z",
+            "2026-04-27T08:30:23Z",
+            "2026-04-27T08:34:10Z",
+        ),
+        (
+            jobs::TASK_SUMMARIZE_RELEASE_SMART_BATCH,
+            crate::local_id::test_local_id("subscription-history-4-smart"),
+            "2026-04-27T08:30:22Z",
+            "2026-04-27T08:30:24Z",
+            "2026-04-27T08:36:40Z",
+        ),
+    ] {
+        sqlx::query(
+                r#"
+                INSERT INTO job_tasks (
+                  id,
+                  task_type,
+                  status,
+                  source,
+                  requested_by,
+                  parent_task_id,
+                  payload_json,
+                  result_json,
+                  error_message,
+                  cancel_requested,
+                  created_at,
+                  started_at,
+                  finished_at,
+                  updated_at
+                )
+                VALUES (?, ?, 'succeeded', 'sync.subscriptions', NULL, ?, '{}', '{}', NULL, 0, ?, ?, ?, ?)
+                "#,
+            )
+            .bind(child_id.as_str())
+            .bind(task_type)
+            .bind(newest_task_id.as_str())
+            .bind(created_at)
+            .bind(started_at)
+            .bind(finished_at)
+            .bind(finished_at)
+            .execute(&pool)
+            .await
+            .expect("seed subscription sync child task");
+    }
+
+    let settings = super::load_sync_runtime_config(state.as_ref())
+        .await
+        .expect("load sync settings");
+
+    assert_eq!(settings.recent_sync_tasks.len(), 3);
+    assert!(
+        settings
+            .recent_sync_tasks
+            .iter()
+            .all(|task| task.id != skipped_task_id)
+    );
+    assert!(settings.recent_sync_tasks.iter().all(|task| !task.skipped));
+    assert_eq!(settings.recent_sync_tasks[0].id, newest_task_id);
+    let duration_ms = settings.recent_sync_tasks[0].duration_ms.expect("duration");
+    assert!((399_999..=400_000).contains(&duration_ms));
+    assert_eq!(
+        settings.recent_sync_tasks[0].finished_at.as_deref(),
+        Some("2026-04-27T08:36:40Z")
+    );
+    assert_eq!(
+        settings.recent_sync_tasks[2].id,
+        crate::local_id::test_local_id("subscription-history-2")
+    );
+}
+
+#[tokio::test]
+async fn persist_daily_brief_profile_validates_muted_notification_reasons() {
+    let pool = setup_pool().await;
+    let state = setup_state(pool.clone());
+    let patch = |reasons: &[&str]| super::DailyBriefProfilePatchRequest {
+        daily_brief_local_time: "09:00".to_owned(),
+        daily_brief_time_zone: "Asia/Shanghai".to_owned(),
+        include_own_releases: None,
+        brief_email_enabled: None,
+        brief_email: None,
+        sync_frequency_minutes: None,
+        muted_notification_reasons: Some(reasons.iter().map(|r| (*r).to_owned()).collect()),
+        skip_muted_notifications: Some(true),
+    };
+
+    let profile = super::persist_daily_brief_profile(
+        state.as_ref(),
+        test_user_id(1).as_str(),
+        patch(&["ci_activity", " subscribed ", "ci_activity"]),
+    )
+    .await
+    .expect("profile update should succeed");
+    assert_eq!(
+        profile.muted_notification_reasons,
+        vec!["ci_activity".to_owned(), "subscribed".to_owned()]
+    );
+    assert!(profile.skip_muted_notifications);
+
+    let err = super::persist_daily_brief_profile(
+        state.as_ref(),
+        test_user_id(1).as_str(),
+        patch(&["ci_activity", "spam"]),
+    )
+    .await
+    .expect_err("unknown reason rejected");
+    assert_eq!(err.code(), "bad_request");
+
+    let profile = super::load_daily_brief_profile(state.as_ref(), test_user_id(1).as_str())
+        .await
+        .expect("load profile");
+    assert_eq!(
+        profile.muted_notification_reasons,
+        vec!["ci_activity".to_owned(), "subscribed".to_owned()]
+    );
+}
+
+#[tokio::test]
+async fn persist_daily_brief_profile_updates_include_own_releases_when_present() {
+    let pool = setup_pool().await;
+    let state = setup_state(pool.clone());
+
+    let profile = super::persist_daily_brief_profile(
+        state.as_ref(),
+        test_user_id(1).as_str(),
+        super::DailyBriefProfilePatchRequest {
+            daily_brief_local_time: "09:00".to_owned(),
+            daily_brief_time_zone: "Asia/Shanghai".to_owned(),
+            include_own_releases: Some(true),
+            brief_email_enabled: None,
+            brief_email: None,
+            sync_frequency_minutes: None,
+            muted_notification_reasons: None,
+            skip_muted_notifications: None,
+        },
+    )
+    .await
+    .expect("profile update should succeed");
+
+    assert!(profile.include_own_releases);
+
+    let include_own_releases = sqlx::query_scalar::<_, i64>(
+        r#"
+            SELECT include_own_releases
+            FROM users
+            WHERE id = ?
+            "#,
+    )
+    .bind(test_user_id(1))
+    .fetch_one(&pool)
+    .await
+    .expect("load include_own_releases");
+
+    assert_eq!(include_own_releases, 1);
+}
+
+#[tokio::test]
+async fn persist_daily_brief_profile_preserves_include_own_releases_when_omitted() {
+    let pool = setup_pool().await;
+    set_include_own_releases(&pool, true).await;
+    let state = setup_state(pool.clone());
+
+    let profile = super::persist_daily_brief_profile(
+        state.as_ref(),
+        test_user_id(1).as_str(),
+        super::DailyBriefProfilePatchRequest {
+            daily_brief_local_time: "10:00".to_owned(),
+            daily_brief_time_zone: "Asia/Tokyo".to_owned(),
+            include_own_releases: None,
+            brief_email_enabled: None,
+            brief_email: None,
+            sync_frequency_minutes: None,
+            muted_notification_reasons: None,
+            skip_muted_notifications: None,
+        },
+    )
+    .await
+    .expect("profile update should preserve include_own_releases");
+
+    assert!(profile.include_own_releases);
+}
+
+#[tokio::test]
+async fn notification_channel_crud_rejects_plain_http_and_hides_url() {
+    let pool = setup_pool().await;
+    let state = setup_state(pool.clone());
+
+    let err = super::create_notification_channel(
+        State(state.clone()),
+        AuthedUser(test_user_id(1)),
+        ApiJson(super::NotificationChannelCreateRequest {
+            kind: "slack".to_owned(),
+            url: "http://hooks.slack.com/services/T/B/secret".to_owned(),
+            enabled: None,
+            events: None,
+        }),
+    )
+    .await
+    .expect_err("plain http channel should be rejected");
+    assert_eq!(err.code(), "bad_request");
+
+    let Json(created) = super::create_notification_channel(
+        State(state.clone()),
+        AuthedUser(test_user_id(1)),
+        ApiJson(super::NotificationChannelCreateRequest {
+            kind: "Discord".to_owned(),
+            url: "https://discord.com/api/webhooks/1/secret".to_owned(),
+            enabled: None,
+            events: Some(vec!["releases.new".to_owned()]),
+        }),
+    )
+    .await
+    .expect("create channel");
+    assert_eq!(created.kind, "discord");
+    assert_eq!(created.url_host, "discord.com");
+    assert_eq!(created.events, vec!["releases.new".to_owned()]);
+    let encoded = serde_json::to_string(&created).expect("encode channel");
+    assert!(!encoded.contains("secret"));
+
+    let Json(patched) = super::patch_notification_channel(
+        State(state.clone()),
+        AuthedUser(test_user_id(1)),
+        Path(created.id.clone()),
+        ApiJson(super::NotificationChannelPatchRequest {
+            kind: None,
+            url: None,
+            enabled: Some(false),
+            events: None,
+        }),
+    )
+    .await
+    .expect("patch channel");
+    assert!(!patched.enabled);
+    assert_eq!(patched.url_host, "discord.com");
+
+    let Json(remaining) = super::delete_notification_channel(
+        State(state.clone()),
+        AuthedUser(test_user_id(1)),
+        Path(created.id.clone()),
+    )
+    .await
+    .expect("delete channel");
+    assert!(remaining.is_empty());
+
+    let err = super::delete_notification_channel(
+        State(state),
+        AuthedUser(test_user_id(1)),
+        Path(created.id),
+    )
+    .await
+    .expect_err("second delete should miss");
+    assert_eq!(err.code(), "notification_channel_not_found");
+}
+
+async fn start_release_batch_stream_task(state: &AppState, release_ids: &[i64]) -> String {
+    crate::jobs::start_inline_task(
+        state,
+        crate::jobs::NewTask {
+            task_type: crate::jobs::TASK_TRANSLATE_RELEASE_BATCH.to_owned(),
+            payload: json!({
+                "user_id": test_user_id(1),
+                "release_ids": release_ids,
+            }),
+            source: "api.translate_releases_batch_stream".to_owned(),
+            requested_by: Some(test_user_id(1)),
+            parent_task_id: None,
+            priority: crate::jobs::TaskPriority::High,
+        },
+    )
+    .await
+    .expect("start batch stream task")
+    .task_id
+}
+
+fn ndjson_line(chunk: &[u8]) -> Value {
+    serde_json::from_slice(chunk).expect("ndjson line")
+}
+
+#[tokio::test]
+async fn attach_batch_stream_replays_a_finished_task() {
+    let pool = setup_pool().await;
+    let state = setup_state(pool);
+    let task_id = start_release_batch_stream_task(state.as_ref(), &[101, 102]).await;
+
+    let (tx, mut rx) = tokio::sync::mpsc::channel(64);
+    translate_releases_batch_stream_worker(
+        state.clone(),
+        test_user_id(1),
+        vec![101, 102],
+        task_id.clone(),
+        tx,
+    )
+    .await;
+    let mut original = Vec::new();
+    while let Some(chunk) = rx.recv().await {
+        original.push(ndjson_line(&chunk.expect("stream chunk")));
+    }
+    assert_eq!(original.len(), 3);
+    assert_eq!(original[2]["event"], "done");
+
+    let response = attach_translate_releases_batch_stream(
+        State(state),
+        Path(task_id),
+        AuthedUser(test_user_id(1)),
+    )
+    .await
+    .expect("attach finished batch stream");
+    let body = to_bytes(response.into_body(), usize::MAX)
+        .await
+        .expect("read attached stream");
+    let replayed = body
+        .split(|byte| *byte == b'\n')
+        .filter(|line| !line.is_empty())
+        .map(ndjson_line)
+        .collect::<Vec<_>>();
+
+    assert_eq!(replayed, original);
+}
+
+#[tokio::test]
+async fn attach_batch_stream_mid_task_continues_without_duplicates() {
+    use futures::StreamExt;
+
+    let pool = setup_pool().await;
+    let state = setup_state(pool);
+    let task_id = start_release_batch_stream_task(state.as_ref(), &[101, 102]).await;
+    let waker = register_batch_stream_waker(&task_id);
+    let ready_item = |id: &str| TranslateBatchItem {
+        id: id.to_owned(),
+        lang: "zh-CN".to_owned(),
+        status: "ready".to_owned(),
+        title: Some(format!("标题 {id}")),
+        summary: Some(format!("摘要 {id}")),
+        error: None,
+    };
+    append_batch_item_event(state.as_ref(), &task_id, &ready_item("101"))
+        .await
+        .expect("record first item");
+
+    let response = attach_translate_releases_batch_stream(
+        State(state.clone()),
+        Path(task_id.clone()),
+        AuthedUser(test_user_id(1)),
+    )
+    .await
+    .expect("attach running batch stream");
+    let mut stream = response.into_body().into_data_stream();
+    let mut next_line = async || {
+        let chunk = tokio::time::timeout(std::time::Duration::from_secs(5), stream.next())
+            .await
+            .expect("stream line in time")?
+            .expect("stream chunk");
+        Some(ndjson_line(&chunk))
+    };
+
+    let first = next_line().await.expect("replayed item");
+    assert_eq!(first["event"], "item");
+    assert_eq!(first["item"]["id"], "101");
+    assert_eq!(first["item"]["title"], "标题 101");
+
+    append_batch_item_event(state.as_ref(), &task_id, &ready_item("102"))
+        .await
+        .expect("record second item");
+    let second = next_line().await.expect("live item");
+    assert_eq!(second["event"], "item");
+    assert_eq!(second["item"]["id"], "102");
+
+    crate::jobs::complete_task(
+        state.as_ref(),
+        &task_id,
+        crate::jobs::STATUS_SUCCEEDED,
+        None,
+        None,
+    )
+    .await
+    .expect("complete task");
+    crate::jobs::append_task_event(
+        state.as_ref(),
+        &task_id,
+        "task.completed",
+        json!({ "task_id": task_id, "status": crate::jobs::STATUS_SUCCEEDED }),
+    )
+    .await
+    .expect("record completion");
+    drop(waker);
+
+    let done = next_line().await.expect("done line");
+    assert_eq!(done["event"], "done");
+    assert!(next_line().await.is_none(), "stream ends after done");
+}
+
+#[tokio::test]
+async fn attach_batch_stream_is_limited_to_requester_and_admins() {
+    let pool = setup_pool().await;
+    seed_user(&pool, 2, "other", 0, 0).await;
+    seed_user(&pool, 3, "admin", 1, 0).await;
+    let state = setup_state(pool);
+    let task_id = start_release_batch_stream_task(state.as_ref(), &[101]).await;
+
+    let Err(err) = attach_translate_releases_batch_stream(
+        State(state.clone()),
+        Path(task_id.clone()),
+        AuthedUser(test_user_id(2)),
+    )
+    .await
+    else {
+        panic!("another user must not attach");
+    };
+    assert_eq!(err.code(), "not_found");
+
+    attach_translate_releases_batch_stream(
+        State(state),
+        Path(task_id),
+        AuthedUser(test_user_id(3)),
+    )
+    .await
+    .expect("admin attaches");
+}
+
+async fn seed_brief_feedback(
+    pool: &SqlitePool,
+    user_id: &str,
+    brief_id: &str,
+    rating: &str,
+    note: Option<&str>,
+    updated_at: &str,
+) {
+    sqlx::query(
+        r#"
+        INSERT INTO brief_feedback (user_id, brief_id, rating, note, created_at, updated_at)
+        VALUES (?, ?, ?, ?, ?, ?)
+        "#,
+    )
+    .bind(user_id)
+    .bind(brief_id)
+    .bind(rating)
+    .bind(note)
+    .bind(updated_at)
+    .bind(updated_at)
+    .execute(pool)
+    .await
+    .expect("seed brief feedback");
+}
+
+#[tokio::test]
+async fn brief_feedback_upserts_one_rating_per_user_and_brief() {
+    let pool = setup_pool().await;
+    let user_id = test_user_id(1);
+    let other_user_id = test_user_id(2);
+    seed_user(&pool, 2, "other", 0, 0).await;
+    seed_brief(&pool, user_id.as_str(), "2026-02-20", "- item").await;
+    sqlx::query(
+        r#"
+        INSERT INTO briefs (id, user_id, date, content_markdown, created_at, updated_at)
+        VALUES ('brief-other-2026-02-21', ?, '2026-02-21', '- other',
+          '2026-02-21T08:00:00Z', '2026-02-21T08:00:00Z')
+        "#,
+    )
+    .bind(other_user_id.as_str())
+    .execute(&pool)
+    .await
+    .expect("seed other brief");
+    let state = setup_state(pool.clone());
+    let rate = |user_id: &str, date: &str, rating: &str, note: Option<&str>| {
+        submit_brief_feedback(
+            State(state.clone()),
+            Path(date.to_owned()),
+            AuthedUser(user_id.to_owned()),
+            ApiJson(BriefFeedbackRequest {
+                rating: rating.to_owned(),
+                note: note.map(str::to_owned),
+            }),
+        )
+    };
+
+    let Json(first) = rate(user_id.as_str(), "2026-02-20", "up", None)
+        .await
+        .expect("rate up");
+    assert_eq!(first.brief_id, "brief-2026-02-20");
+    assert_eq!(first.feedback.rating, "up");
+
+    let Json(changed) = rate(
+        user_id.as_str(),
+        "2026-02-20",
+        " down ",
+        Some("  missed the breaking change  "),
+    )
+    .await
+    .expect("change mind");
+    assert_eq!(changed.feedback.rating, "down");
+    assert_eq!(
+        changed.feedback.note.as_deref(),
+        Some("missed the breaking change")
+    );
+    let created_at = sqlx::query_scalar::<_, String>(
+        "SELECT created_at FROM brief_feedback WHERE user_id = ? AND brief_id = 'brief-2026-02-20'",
+    )
+    .bind(user_id.as_str())
+    .fetch_one(&pool)
+    .await
+    .expect("load created_at");
+    assert_eq!(created_at, first.feedback.updated_at);
+
+    let Json(cleared) = rate(user_id.as_str(), "2026-02-20", "up", Some("  "))
+        .await
+        .expect("rate up again");
+    assert_eq!(
+        cleared.feedback.note, None,
+        "a blank note clears the old one"
+    );
+
+    let Json(other) = rate(other_user_id.as_str(), "2026-02-21", "down", None)
+        .await
+        .expect("other user rates own brief");
+    assert_eq!(other.brief_id, "brief-other-2026-02-21");
+    let counts = sqlx::query_as::<_, (String, i64)>(
+        "SELECT user_id, COUNT(*) FROM brief_feedback GROUP BY user_id ORDER BY user_id",
+    )
+    .fetch_all(&pool)
+    .await
+    .expect("count feedback");
+    let mut expected = vec![(user_id.clone(), 1), (other_user_id.clone(), 1)];
+    expected.sort();
+    assert_eq!(counts, expected);
+
+    let err = rate(other_user_id.as_str(), "2026-02-20", "up", None)
+        .await
+        .expect_err("someone else's brief");
+    assert_eq!(err.code(), "not_found");
+    let err = rate(user_id.as_str(), "2026-01-01", "up", None)
+        .await
+        .expect_err("missing brief");
+    assert_eq!(err.code(), "not_found");
+    let err = rate(user_id.as_str(), "2026-02-20", "meh", None)
+        .await
+        .expect_err("unknown rating");
+    assert_eq!(err.code(), "bad_request");
+
+    let Json(briefs) = list_briefs(State(state.clone()), AuthedUser(test_user_id(1)))
+        .await
+        .expect("list briefs");
+    let feedback = briefs[0].feedback.as_ref().expect("own feedback listed");
+    assert_eq!(feedback.rating, "up");
+    assert_eq!(feedback.note, None);
+}
+
+#[tokio::test]
+async fn admin_brief_feedback_aggregates_ratings_since_cutoff() {
+    let pool = setup_pool().await;
+    let user_id = test_user_id(1);
+    seed_user(&pool, 2, "admin", 1, 0).await;
+    for date in ["2026-02-18", "2026-02-19", "2026-02-20", "2026-02-21"] {
+        seed_brief(&pool, user_id.as_str(), date, "- item").await;
+    }
+    let feedback = [
+        (
+            "brief-2026-02-18",
+            "down",
+            Some("too old to count"),
+            "2026-02-10T00:00:00Z",
+        ),
+        ("brief-2026-02-19", "up", None, "2026-02-19T09:00:00Z"),
+        (
+            "brief-2026-02-20",
+            "down",
+            Some("too long"),
+            "2026-02-20T09:00:00Z",
+        ),
+        (
+            "brief-2026-02-21",
+            "up",
+            Some("great"),
+            "2026-02-21T09:00:00Z",
+        ),
+    ];
+    for (brief_id, rating, note, updated_at) in feedback {
+        seed_brief_feedback(&pool, user_id.as_str(), brief_id, rating, note, updated_at).await;
+    }
+    let state = setup_state(pool);
+
+    let Json(summary) = admin_get_brief_feedback(
+        State(state.clone()),
+        Query(AdminBriefFeedbackQuery {
+            since: Some("2026-02-15T00:00:00Z".to_owned()),
+        }),
+        AdminUser(test_user_id(2)),
+    )
+    .await
+    .expect("brief feedback summary");
+
+    assert_eq!(
+        summary.distribution,
+        AdminBriefFeedbackDistribution {
+            total: 3,
+            up: 2,
+            down: 1,
+            up_ratio: Some(2.0 / 3.0),
+            with_note: 2,
+        }
+    );
+    let notes = summary
+        .recent_notes
+        .iter()
+        .map(|note| {
+            (
+                note.brief_date.as_str(),
+                note.rating.as_str(),
+                note.note.as_str(),
+            )
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(
+        notes,
+        vec![
+            ("2026-02-21", "up", "great"),
+            ("2026-02-20", "down", "too long")
+        ]
+    );
+    let user_ref = brief_feedback_user_ref(user_id.as_str());
+    assert!(
+        summary
+            .recent_notes
+            .iter()
+            .all(|note| note.user_ref == user_ref && !note.user_ref.contains(user_id.as_str()))
+    );
+
+    let Json(empty) = admin_get_brief_feedback(
+        State(state),
+        Query(AdminBriefFeedbackQuery {
+            since: Some("2026-03-01T00:00:00Z".to_owned()),
+        }),
+        AdminUser(test_user_id(2)),
+    )
+    .await
+    .expect("empty summary");
+    assert_eq!(
+        empty.distribution,
+        AdminBriefFeedbackDistribution::default()
+    );
+    assert!(empty.recent_notes.is_empty());
+}
@@ -0,0 +1,5163 @@
+use super::*;
+
+#[allow(dead_code)]
+#[derive(Debug, Deserialize)]
+pub struct TranslateReleaseRequest {
+    pub(super) release_id: String,
+}
+
+#[allow(dead_code)]
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct TranslateReleasesBatchRequest {
+    pub(super) release_ids: Vec<String>,
+}
+
+#[allow(dead_code)]
+#[derive(Debug, Deserialize)]
+pub struct TranslateReleaseDetailRequest {
+    pub(super) release_id: String,
+    /// `full` (default) or `summary`.
+    #[serde(default)]
+    pub(super) mode: Option<String>,
+}
+
+#[allow(dead_code)]
+#[derive(Debug, Deserialize)]
+pub struct TranslateReleaseDetailBatchRequest {
+    pub(super) release_ids: Vec<String>,
+    /// Applied to every id in the batch.
+    #[serde(default)]
+    pub(super) mode: Option<String>,
+}
+
+/// What a release detail translation produces. Each mode is cached under
+/// its own entity type so a summary never overwrites a full translation.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ReleaseDetailTranslationMode {
+    /// Chunk-by-chunk translation of the whole body.
+    #[default]
+    Full,
+    /// One condensed bullet summary in the target language.
+    Summary,
+}
+
+impl ReleaseDetailTranslationMode {
+    pub fn parse(raw: Option<&str>) -> Result<Self, ApiError> {
+        match raw.map(str::trim).filter(|value| !value.is_empty()) {
+            None | Some("full") => Ok(Self::Full),
+            Some("summary") => Ok(Self::Summary),
+            Some(_) => Err(ApiError::bad_request("invalid mode, expected full|summary")),
+        }
+    }
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Full => "full",
+            Self::Summary => "summary",
+        }
+    }
+
+    pub(super) fn entity_type(self) -> &'static str {
+        match self {
+            Self::Full => "release_detail",
+            Self::Summary => "release_detail_summary",
+        }
+    }
+}
+
+#[allow(dead_code)]
+#[derive(Debug, Deserialize)]
+pub struct TranslateNotificationRequest {
+    pub(super) thread_id: String,
+}
+
+#[allow(dead_code)]
+#[derive(Debug, Deserialize)]
+pub struct TranslateNotificationsBatchRequest {
+    pub(super) thread_ids: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct TranslateResponse {
+    pub lang: String,
+    pub status: String, // ready | disabled | opted_out | missing | error
+    pub title: Option<String>,
+    pub summary: Option<String>,
+}
+
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct TranslateBatchResponse {
+    pub items: Vec<TranslateBatchItem>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, JsonSchema)]
+pub struct TranslateBatchItem {
+    pub id: String,
+    pub lang: String,
+    /// `processing` only appears on the streaming variant. `opted_out` means
+    /// the viewer turned translation off for the release's repo.
+    #[schemars(extend("enum" = ["ready", "disabled", "opted_out", "missing", "error", "processing"]))]
+    pub status: String,
+    pub title: Option<String>,
+    pub summary: Option<String>,
+    pub error: Option<String>,
+}
+
+#[allow(dead_code)]
+#[derive(Debug, Serialize)]
+pub(super) struct TranslateBatchStreamEvent {
+    pub(super) event: &'static str, // item | partial | done | error
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(super) item: Option<TranslateBatchItem>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(super) error: Option<String>,
+}
+
+#[allow(dead_code)]
+pub(super) async fn send_batch_stream_event(
+    tx: &mpsc::Sender<Result<Bytes, Infallible>>,
+    mut event: TranslateBatchStreamEvent,
+) -> bool {
+    if let Some(item) = event.item.take() {
+        event.item = Some(translate_batch_item_for_public(item));
+    }
+    let payload = match serde_json::to_string(&event) {
+        Ok(mut line) => {
+            line.push('\n');
+            line
+        }
+        Err(err) => {
+            tracing::warn!(?err, "serialize batch stream event failed");
+            return false;
+        }
+    };
+    tx.send(Ok(Bytes::from(payload))).await.is_ok()
+}
+
+/// Forwards batch stream lines until the worker finishes, or ends the stream
+/// with a final `error` line once the server starts shutting down.
+pub(super) fn batch_stream_until_shutdown(
+    mut rx: mpsc::Receiver<Result<Bytes, Infallible>>,
+) -> impl futures::Stream<Item = Result<Bytes, Infallible>> {
+    async_stream::stream! {
+        let shutdown = runtime::shutdown_token();
+        loop {
+            let next = tokio::select! {
+                chunk = rx.recv() => Some(chunk),
+                _ = shutdown.cancelled() => None,
+            };
+            match next {
+                Some(Some(chunk)) => yield chunk,
+                Some(None) => break,
+                None => {
+                    yield Ok(batch_stream_line(&TranslateBatchStreamEvent {
+                        event: "error",
+                        item: None,
+                        error: Some(jobs::TASK_SERVER_SHUTDOWN_ERROR.to_owned()),
+                    }));
+                    break;
+                }
+            }
+        }
+    }
+}
+
+static BATCH_STREAM_WAKERS: std::sync::OnceLock<
+    std::sync::Mutex<HashMap<String, tokio::sync::broadcast::Sender<()>>>,
+> = std::sync::OnceLock::new();
+
+// Attached streams also re-read on this interval, which covers batch tasks
+// running on another instance where no in-process wake-up arrives.
+const BATCH_STREAM_ATTACH_POLL_INTERVAL: std::time::Duration =
+    std::time::Duration::from_millis(500);
+const BATCH_STREAM_ATTACH_PAGE_SIZE: i64 = 200;
+const BATCH_STREAM_TASK_ID_HEADER: &str = "x-task-id";
+
+fn batch_stream_wakers()
+-> &'static std::sync::Mutex<HashMap<String, tokio::sync::broadcast::Sender<()>>> {
+    BATCH_STREAM_WAKERS.get_or_init(|| std::sync::Mutex::new(HashMap::new()))
+}
+
+/// Keeps a running batch task's wake-up channel registered so re-attached
+/// streams hear about new item events; dropping it closes the channel, which
+/// tells them to do a final read.
+pub(super) struct BatchStreamWakerGuard {
+    task_id: String,
+}
+
+impl Drop for BatchStreamWakerGuard {
+    fn drop(&mut self) {
+        batch_stream_wakers()
+            .lock()
+            .expect("batch stream wakers poisoned")
+            .remove(&self.task_id);
+    }
+}
+
+pub(super) fn register_batch_stream_waker(task_id: &str) -> BatchStreamWakerGuard {
+    batch_stream_wakers()
+        .lock()
+        .expect("batch stream wakers poisoned")
+        .insert(task_id.to_owned(), tokio::sync::broadcast::channel(16).0);
+    BatchStreamWakerGuard {
+        task_id: task_id.to_owned(),
+    }
+}
+
+fn subscribe_batch_stream_waker(task_id: &str) -> Option<tokio::sync::broadcast::Receiver<()>> {
+    batch_stream_wakers()
+        .lock()
+        .expect("batch stream wakers poisoned")
+        .get(task_id)
+        .map(tokio::sync::broadcast::Sender::subscribe)
+}
+
+/// Records a finished batch item as a task event, which is what re-attached
+/// streams replay, and wakes the streams attached in this process.
+pub(super) async fn append_batch_item_event(
+    state: &AppState,
+    task_id: &str,
+    item: &TranslateBatchItem,
+) -> anyhow::Result<()> {
+    jobs::append_task_event(
+        state,
+        task_id,
+        "task.progress",
+        json!({
+            "task_id": task_id,
+            "stage": "release",
+            "release_id": item.id,
+            "item_status": item.status,
+            "item_error": item.error.clone(),
+            "item": translate_batch_item_for_public(item.clone()),
+        }),
+    )
+    .await?;
+    if let Some(sender) = batch_stream_wakers()
+        .lock()
+        .expect("batch stream wakers poisoned")
+        .get(task_id)
+    {
+        let _ = sender.send(());
+    }
+    Ok(())
+}
+
+fn batch_stream_line(event: &TranslateBatchStreamEvent) -> Bytes {
+    let mut line = serde_json::to_string(event).unwrap_or_default();
+    line.push('\n');
+    Bytes::from(line)
+}
+
+#[derive(Debug, sqlx::FromRow)]
+pub(super) struct BatchStreamTaskEventRow {
+    pub(super) seq: i64,
+    pub(super) event_type: String,
+    pub(super) payload_json: String,
+}
+
+/// Maps a recorded task event back to the stream line it stood for: item
+/// progress becomes `item`, completion becomes `done` or `error`, and every
+/// other event has no line.
+pub(super) fn batch_stream_event_from_task_event(
+    row: &BatchStreamTaskEventRow,
+) -> Option<TranslateBatchStreamEvent> {
+    let payload = serde_json::from_str::<Value>(&row.payload_json).ok()?;
+    match row.event_type.as_str() {
+        "task.progress" if payload.get("stage").and_then(Value::as_str) == Some("release") => {
+            let item = payload
+                .get("item")
+                .and_then(|item| serde_json::from_value::<TranslateBatchItem>(item.clone()).ok())
+                .or_else(|| {
+                    // Tasks recorded before items were stored only kept the status.
+                    Some(translate_batch_item_for_public(TranslateBatchItem {
+                        id: json_value_to_string(payload.get("release_id")?)?,
+                        lang: "zh-CN".to_owned(),
+                        status: payload.get("item_status")?.as_str()?.to_owned(),
+                        title: None,
+                        summary: None,
+                        error: payload
+                            .get("item_error")
+                            .and_then(Value::as_str)
+                            .map(str::to_owned),
+                    }))
+                })?;
+            Some(TranslateBatchStreamEvent {
+                event: "item",
+                item: Some(item),
+                error: None,
+            })
+        }
+        "task.completed" => Some(batch_stream_terminal_event(
+            payload
+                .get("status")
+                .and_then(Value::as_str)
+                .unwrap_or_default(),
+            payload.get("error").and_then(Value::as_str),
+        )),
+        _ => None,
+    }
+}
+
+fn batch_stream_terminal_event(status: &str, error: Option<&str>) -> TranslateBatchStreamEvent {
+    let error = match status {
+        jobs::STATUS_SUCCEEDED => None,
+        jobs::STATUS_CANCELED => Some(jobs::TASK_CANCELED_ERROR.to_owned()),
+        _ => Some(error.unwrap_or("batch translation task failed").to_owned()),
+    };
+    TranslateBatchStreamEvent {
+        event: if error.is_some() { "error" } else { "done" },
+        item: None,
+        error,
+    }
+}
+
+async fn load_batch_stream_task_events(
+    state: &AppState,
+    task_id: &str,
+    after_seq: i64,
+) -> Result<Vec<BatchStreamTaskEventRow>, sqlx::Error> {
+    sqlx::query_as::<_, BatchStreamTaskEventRow>(
+        r#"
+        SELECT rowid AS seq, event_type, payload_json
+        FROM job_task_events
+        WHERE task_id = ? AND rowid > ?
+        ORDER BY rowid ASC
+        LIMIT ?
+        "#,
+    )
+    .bind(task_id)
+    .bind(after_seq)
+    .bind(BATCH_STREAM_ATTACH_PAGE_SIZE)
+    .fetch_all(&state.pool)
+    .await
+}
+
+async fn next_batch_stream_wake(waker: &mut Option<tokio::sync::broadcast::Receiver<()>>) {
+    match waker {
+        Some(receiver) => {
+            if let Err(tokio::sync::broadcast::error::RecvError::Closed) = receiver.recv().await {
+                *waker = None;
+            }
+        }
+        None => std::future::pending().await,
+    }
+}
+
+/// Stream lines for a batch task that is being re-attached: every item
+/// already recorded is replayed in order, then new items follow as the
+/// worker records them, ending with the task's `done` or `error` line.
+/// Events are read by rowid, so nothing is sent twice.
+pub(super) fn batch_stream_attach(
+    state: Arc<AppState>,
+    task_id: String,
+) -> impl futures::Stream<Item = Result<Bytes, Infallible>> {
+    async_stream::stream! {
+        let shutdown = runtime::shutdown_token();
+        let mut waker = subscribe_batch_stream_waker(&task_id);
+        let mut last_seq = 0_i64;
+        let mut terminal_status_seen = false;
+        loop {
+            let rows = match load_batch_stream_task_events(state.as_ref(), &task_id, last_seq).await {
+                Ok(rows) => rows,
+                Err(err) => {
+                    tracing::warn!(?err, task_id, "load batch stream task events failed");
+                    yield Ok(batch_stream_line(&TranslateBatchStreamEvent {
+                        event: "error",
+                        item: None,
+                        error: Some(codes::INTERNAL_ERROR.to_owned()),
+                    }));
+                    break;
+                }
+            };
+            let page_full = rows.len() as i64 == BATCH_STREAM_ATTACH_PAGE_SIZE;
+            let mut finished = false;
+            for row in rows {
+                last_seq = row.seq;
+                if let Some(event) = batch_stream_event_from_task_event(&row) {
+                    finished = event.event != "item";
+                    yield Ok(batch_stream_line(&event));
+                    if finished {
+                        break;
+                    }
+                }
+            }
+            if finished {
+                break;
+            }
+            if page_full {
+                continue;
+            }
+
+            let task = sqlx::query_as::<_, (String, Option<String>)>(
+                r#"SELECT status, error_message FROM job_tasks WHERE id = ? LIMIT 1"#,
+            )
+            .bind(&task_id)
+            .fetch_optional(&state.pool)
+            .await
+            .ok()
+            .flatten();
+            match task {
+                Some((status, error)) if jobs::is_terminal_status(&status) => {
+                    // The worker records completion right after the status; if
+                    // that event never shows up the stored status decides.
+                    if terminal_status_seen {
+                        yield Ok(batch_stream_line(&batch_stream_terminal_event(
+                            &status,
+                            error.as_deref(),
+                        )));
+                        break;
+                    }
+                    terminal_status_seen = true;
+                    tokio::time::sleep(std::time::Duration::from_millis(120)).await;
+                    continue;
+                }
+                Some(_) => {}
+                None => break,
+            }
+
+            let shutting_down = tokio::select! {
+                _ = shutdown.cancelled() => true,
+                _ = next_batch_stream_wake(&mut waker) => false,
+                _ = tokio::time::sleep(BATCH_STREAM_ATTACH_POLL_INTERVAL) => false,
+            };
+            if shutting_down {
+                yield Ok(batch_stream_line(&TranslateBatchStreamEvent {
+                    event: "error",
+                    item: None,
+                    error: Some(jobs::TASK_SERVER_SHUTDOWN_ERROR.to_owned()),
+                }));
+                break;
+            }
+        }
+    }
+}
+
+pub(super) fn translate_batch_item_for_public(mut item: TranslateBatchItem) -> TranslateBatchItem {
+    if item.status == "error" {
+        item.error =
+            crate::translations::translation_error_summary(item.error.as_deref()).or(item.error);
+    }
+    item
+}
+
+pub(super) fn translate_batch_items_for_public(
+    items: Vec<TranslateBatchItem>,
+) -> Vec<TranslateBatchItem> {
+    items
+        .into_iter()
+        .map(translate_batch_item_for_public)
+        .collect()
+}
+
+#[allow(dead_code)]
+pub(super) fn accumulate_batch_item_stats(
+    item: &TranslateBatchItem,
+    ready_count: &mut usize,
+    disabled_count: &mut usize,
+    missing_count: &mut usize,
+    error_count: &mut usize,
+) {
+    match item.status.as_str() {
+        "ready" => *ready_count += 1,
+        "disabled" => *disabled_count += 1,
+        "missing" => *missing_count += 1,
+        "error" => *error_count += 1,
+        _ => {}
+    }
+}
+
+pub(super) fn translate_response_from_batch_item(
+    item: TranslateBatchItem,
+) -> Result<TranslateResponse, ApiError> {
+    let status = match item.status.as_str() {
+        "disabled" => "disabled",
+        "ready" => "ready",
+        "missing" => {
+            return Err(ApiError::new(
+                StatusCode::NOT_FOUND,
+                codes::NOT_FOUND,
+                item.error
+                    .unwrap_or_else(|| "translation target not found".to_owned()),
+            ));
+        }
+        "error" => {
+            return Err(ApiError::internal(
+                crate::translations::translation_error_summary(item.error.as_deref())
+                    .or(item.error)
+                    .unwrap_or_else(|| "translation failed".to_owned()),
+            ));
+        }
+        other => {
+            return Err(ApiError::internal(format!(
+                "unexpected translation status: {other}"
+            )));
+        }
+    };
+
+    Ok(TranslateResponse {
+        lang: item.lang,
+        status: status.to_owned(),
+        title: item.title,
+        summary: item.summary,
+    })
+}
+
+#[derive(Clone, Copy)]
+pub(super) struct TranslationUpsert<'a> {
+    pub(super) entity_type: &'a str,
+    pub(super) entity_id: &'a str,
+    pub(super) lang: &'a str,
+    pub(super) source_hash: &'a str,
+    pub(super) title: Option<&'a str>,
+    pub(super) summary: Option<&'a str>,
+}
+
+pub(super) const TRANSLATION_CACHE_ENTITY_TYPES: [&str; 7] = [
+    "release",
+    "release_detail",
+    "release_detail_summary",
+    "release_smart",
+    "release_diff",
+    "notification",
+    "brief",
+];
+
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct TranslationCachePurgeQuery {
+    pub(super) entity_type: String,
+    pub(super) entity_id: String,
+}
+
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct AdminTranslationCachePurgeQuery {
+    pub(super) entity_type: String,
+    pub(super) entity_id: String,
+    /// Limits the purge to one user; without it every user's row and the
+    /// shared cache row are dropped.
+    pub(super) user_id: Option<String>,
+}
+
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct TranslationCachePurgeResponse {
+    pub(super) deleted: u64,
+    pub(super) shared_deleted: u64,
+}
+
+pub(super) fn parse_translation_cache_target(
+    entity_type: &str,
+    entity_id: &str,
+) -> Result<(&'static str, String), ApiError> {
+    let entity_type = TRANSLATION_CACHE_ENTITY_TYPES
+        .into_iter()
+        .find(|known| *known == entity_type.trim())
+        .ok_or_else(|| ApiError::bad_request("unsupported entity_type"))?;
+    let entity_id = entity_id.trim();
+    if entity_id.is_empty() {
+        return Err(ApiError::bad_request("entity_id is required"));
+    }
+    Ok((entity_type, entity_id.to_owned()))
+}
+
+/// Drops the caller's cached translations of one entity so the next request
+/// translates it again. The shared cache is left alone; only admins can
+/// purge rows other users may be reading.
+pub async fn delete_translations(
+    State(state): State<Arc<AppState>>,
+    AuthedUser(user_id): AuthedUser,
+    Query(query): Query<TranslationCachePurgeQuery>,
+) -> Result<Json<TranslationCachePurgeResponse>, ApiError> {
+    let (entity_type, entity_id) =
+        parse_translation_cache_target(&query.entity_type, &query.entity_id)?;
+
+    let deleted = sqlx::query(
+        r#"
+        DELETE FROM ai_translations
+        WHERE user_id = ? AND entity_type = ? AND entity_id = ?
+        "#,
+    )
+    .bind(user_id.as_str())
+    .bind(entity_type)
+    .bind(entity_id.as_str())
+    .execute(&state.pool)
+    .await
+    .map_err(ApiError::internal)?
+    .rows_affected();
+
+    Ok(Json(TranslationCachePurgeResponse {
+        deleted,
+        shared_deleted: 0,
+    }))
+}
+
+/// Re-translates the caller's cached release translations that hold a JSON
+/// blob or no longer follow the source Markdown layout.
+pub async fn repair_translations(
+    State(state): State<Arc<AppState>>,
+    Query(mode_query): Query<ReturnModeQuery>,
+    AuthedUser(user_id): AuthedUser,
+) -> Result<Response, ApiError> {
+    let mode = ReturnMode::from_query(&mode_query)?;
+
+    if matches!(mode, ReturnMode::Sync) {
+        let res = repair_release_translations(state.as_ref(), Some(user_id.as_str())).await?;
+        return Ok(Json(res).into_response());
+    }
+
+    enqueue_singleton_or_stream_task(
+        state,
+        mode,
+        jobs::NewTask {
+            task_type: jobs::TASK_TRANSLATION_REPAIR.to_owned(),
+            payload: json!({ "user_id": user_id.clone() }),
+            source: "api.repair_translations".to_owned(),
+            requested_by: Some(user_id.clone()),
+            parent_task_id: None,
+            priority: jobs::TaskPriority::Low,
+        },
+    )
+    .await
+}
+
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct AdminTranslationRepairQuery {
+    /// Limits the repair to one user; without it every user's cache is scanned.
+    pub(super) user_id: Option<String>,
+}
+
+pub async fn admin_repair_translations(
+    State(state): State<Arc<AppState>>,
+    Query(query): Query<AdminTranslationRepairQuery>,
+    AdminUser(acting_user_id): AdminUser,
+) -> Result<Response, ApiError> {
+    let target_user_id = query
+        .user_id
+        .map(|raw| parse_local_id_param(raw, "user_id"))
+        .transpose()?;
+    if let Some(target_user_id) = target_user_id.as_deref() {
+        sqlx::query_scalar::<_, String>(r#"SELECT id FROM users WHERE id = ? LIMIT 1"#)
+            .bind(target_user_id)
+            .fetch_optional(&state.pool)
+            .await
+            .map_err(ApiError::internal)?
+            .ok_or_else(|| {
+                ApiError::new(StatusCode::NOT_FOUND, codes::NOT_FOUND, "user not found")
+            })?;
+    }
+    state
+        .admin_mutation_limiter
+        .check(acting_user_id.as_str())?;
+
+    enqueue_or_stream_task(
+        state,
+        ReturnMode::TaskId,
+        jobs::NewTask {
+            task_type: jobs::TASK_TRANSLATION_REPAIR.to_owned(),
+            payload: json!({ "user_id": target_user_id }),
+            source: "api.admin_repair_translations".to_owned(),
+            requested_by: Some(acting_user_id),
+            parent_task_id: None,
+            priority: jobs::TaskPriority::Low,
+        },
+    )
+    .await
+}
+
+pub async fn admin_delete_translations(
+    State(state): State<Arc<AppState>>,
+    AdminUser(acting_user_id): AdminUser,
+    Query(query): Query<AdminTranslationCachePurgeQuery>,
+) -> Result<Json<TranslationCachePurgeResponse>, ApiError> {
+    let (entity_type, entity_id) =
+        parse_translation_cache_target(&query.entity_type, &query.entity_id)?;
+    let target_user_id = query
+        .user_id
+        .map(|raw| parse_local_id_param(raw, "user_id"))
+        .transpose()?;
+    state
+        .admin_mutation_limiter
+        .check(acting_user_id.as_str())?;
+
+    let mut tx = state.pool.begin().await.map_err(ApiError::internal)?;
+    let deleted = sqlx::query(
+        r#"
+        DELETE FROM ai_translations
+        WHERE entity_type = ? AND entity_id = ? AND (? IS NULL OR user_id = ?)
+        "#,
+    )
+    .bind(entity_type)
+    .bind(entity_id.as_str())
+    .bind(target_user_id.as_deref())
+    .bind(target_user_id.as_deref())
+    .execute(&mut *tx)
+    .await
+    .map_err(ApiError::internal)?
+    .rows_affected();
+    let shared_deleted = if target_user_id.is_none() {
+        sqlx::query(
+            r#"
+            DELETE FROM ai_translations_shared
+            WHERE entity_type = ? AND entity_id = ?
+            "#,
+        )
+        .bind(entity_type)
+        .bind(entity_id.as_str())
+        .execute(&mut *tx)
+        .await
+        .map_err(ApiError::internal)?
+        .rows_affected()
+    } else {
+        0
+    };
+
+    admin_audit::record(
+        &mut tx,
+        acting_user_id.as_str(),
+        target_user_id.as_deref(),
+        admin_audit::ADMIN_AUDIT_TRANSLATION_CACHE_PURGE,
+        &json!({ "entity_type": entity_type, "entity_id": entity_id }),
+        &json!({ "deleted": deleted, "shared_deleted": shared_deleted }),
+    )
+    .await?;
+    tx.commit().await.map_err(ApiError::internal)?;
+
+    Ok(Json(TranslationCachePurgeResponse {
+        deleted,
+        shared_deleted,
+    }))
+}
+
+pub(super) async fn upsert_translation(
+    state: &AppState,
+    user_id: &str,
+    requested_at: &str,
+    t: TranslationUpsert<'_>,
+) -> Result<(), ApiError> {
+    let now = chrono::Utc::now().to_rfc3339();
+    sqlx::query(
+        r#"
+        INSERT INTO ai_translations (
+          id, user_id, entity_type, entity_id, lang, source_hash, status, title, summary,
+          error_text, active_work_item_id, created_at, updated_at
+        )
+        VALUES (?, ?, ?, ?, ?, ?, 'ready', ?, ?, NULL, NULL, ?, ?)
+        ON CONFLICT(user_id, entity_type, entity_id, lang) DO UPDATE SET
+          source_hash = excluded.source_hash,
+          status = excluded.status,
+          title = excluded.title,
+          summary = excluded.summary,
+          error_text = excluded.error_text,
+          active_work_item_id = excluded.active_work_item_id,
+          updated_at = excluded.updated_at
+        WHERE ai_translations.source_hash = excluded.source_hash
+           OR ai_translations.updated_at <= ?
+        "#,
+    )
+    .bind(crate::local_id::generate_local_id())
+    .bind(user_id)
+    .bind(t.entity_type)
+    .bind(t.entity_id)
+    .bind(t.lang)
+    .bind(t.source_hash)
+    .bind(t.title)
+    .bind(t.summary)
+    .bind(&now)
+    .bind(&now)
+    .bind(requested_at)
+    .execute(&state.pool)
+    .await
+    .map_err(ApiError::internal)?;
+    Ok(())
+}
+
+pub(super) async fn mark_translation_requested(
+    state: &AppState,
+    user_id: &str,
+    requested_at: &str,
+    t: TranslationUpsert<'_>,
+) -> Result<(), ApiError> {
+    sqlx::query(
+        r#"
+        INSERT INTO ai_translations (
+          id, user_id, entity_type, entity_id, lang, source_hash, status, title, summary,
+          error_text, active_work_item_id, created_at, updated_at
+        )
+        VALUES (?, ?, ?, ?, ?, ?, 'running', NULL, NULL, NULL, NULL, ?, ?)
+        ON CONFLICT(user_id, entity_type, entity_id, lang) DO UPDATE SET
+          source_hash = excluded.source_hash,
+          status = excluded.status,
+          title = NULL,
+          summary = NULL,
+          error_text = NULL,
+          active_work_item_id = NULL,
+          updated_at = excluded.updated_at
+        WHERE ai_translations.source_hash = excluded.source_hash
+          AND ai_translations.status NOT IN ('ready', 'disabled', 'missing')
+        "#,
+    )
+    .bind(crate::local_id::generate_local_id())
+    .bind(user_id)
+    .bind(t.entity_type)
+    .bind(t.entity_id)
+    .bind(t.lang)
+    .bind(t.source_hash)
+    .bind(requested_at)
+    .bind(requested_at)
+    .execute(&state.pool)
+    .await
+    .map_err(ApiError::internal)?;
+    Ok(())
+}
+
+#[derive(Debug, Clone)]
+pub(super) struct ReleaseBatchCandidate {
+    pub(super) release_id: i64,
+    pub(super) entity_id: String,
+    pub(super) full_name: String,
+    pub(super) title: String,
+    pub(super) body: String,
+    pub(super) source_hash: String,
+    pub(super) legacy_source_hash: Option<String>,
+    pub(super) previous_legacy_source_hash: Option<String>,
+    /// Public repo releases may read and feed `ai_translations_shared`.
+    pub(super) shareable: bool,
+}
+
+#[derive(Debug, Clone)]
+pub(super) struct ReleaseBatchTerminalState {
+    pub(super) status: String,
+    pub(super) error: Option<String>,
+}
+
+pub(super) const RELEASE_BATCH_MAX_TOKENS: u32 = 1_400;
+pub(super) const RELEASE_BATCH_OVERHEAD_TOKENS: u32 = 260;
+// Streamed partial summaries are only re-sent once they grew this much.
+pub(super) const RELEASE_BATCH_PARTIAL_MIN_GROWTH_CHARS: usize = 24;
+pub(super) const NOTIFICATION_BATCH_MAX_TOKENS: u32 = 1_100;
+pub(super) const NOTIFICATION_BATCH_OVERHEAD_TOKENS: u32 = 220;
+
+pub(super) fn ai_call_api_error(err: anyhow::Error) -> ApiError {
+    if let Some(exceeded) = ai::AiBudgetExceeded::find(&err) {
+        return ApiError::ai_budget_exceeded(exceeded.to_string());
+    }
+    ApiError::internal(err)
+}
+
+pub(super) fn ai_error_is_non_retryable(err: &anyhow::Error) -> bool {
+    ai::is_ai_budget_exceeded_error(err) || ai::ai_error_is_non_retryable(err)
+}
+
+pub(super) fn normalize_translation_fields(
+    raw_title: Option<String>,
+    raw_summary: Option<String>,
+) -> (Option<String>, Option<String>) {
+    let mut title = raw_title
+        .as_deref()
+        .map(str::trim)
+        .filter(|s| !s.is_empty())
+        .map(str::to_owned);
+    let mut summary = raw_summary
+        .as_deref()
+        .map(str::trim)
+        .filter(|s| !s.is_empty())
+        .map(str::to_owned);
+
+    // Guard against model outputs where summary field accidentally embeds a full JSON blob.
+    if let Some(raw) = summary.as_deref() {
+        let trimmed = raw.trim_start();
+        let looks_like_embedded_json = trimmed.starts_with('{')
+            || trimmed.starts_with("\"{")
+            || trimmed.contains("\"summary_md\"")
+            || trimmed.contains("\"title_zh\"")
+            || trimmed.contains("\"body_md\"");
+        if looks_like_embedded_json {
+            if let Some((blob_title, blob_summary)) = extract_translation_from_json_blob(raw) {
+                if title.is_none() {
+                    title = blob_title;
+                }
+                summary = blob_summary;
+            } else {
+                summary = None;
+            }
+        }
+    }
+
+    (title, summary)
+}
+
+#[derive(Debug, sqlx::FromRow)]
+pub(super) struct ReleaseBatchSourceRow {
+    pub(super) release_id: i64,
+    pub(super) full_name: String,
+    pub(super) tag_name: String,
+    pub(super) name: Option<String>,
+    pub(super) body: Option<String>,
+    pub(super) is_private: i64,
+    pub(super) translation_opted_out: bool,
+}
+
+#[derive(Debug, sqlx::FromRow)]
+pub(super) struct TranslationCacheRow {
+    pub(super) entity_id: String,
+    pub(super) source_hash: String,
+    pub(super) status: String,
+    pub(super) title: Option<String>,
+    pub(super) summary: Option<String>,
+    pub(super) error_text: Option<String>,
+}
+
+pub(crate) fn looks_like_json_blob(raw: &str) -> bool {
+    let trimmed = raw.trim_start();
+    if trimmed.starts_with('{') || trimmed.starts_with("\"{") {
+        return true;
+    }
+
+    let unfenced = strip_markdown_code_fence(trimmed).trim_start();
+    if unfenced.starts_with('{') || unfenced.starts_with("\"{") {
+        return true;
+    }
+
+    trimmed.contains("\"summary_md\"")
+        || trimmed.contains("\"title_zh\"")
+        || trimmed.contains("\"body_md\"")
+}
+
+#[cfg(test)]
+pub(super) fn release_cache_entry_reusable(cache: &TranslationCacheRow, body: &str) -> bool {
+    if cache.summary.as_deref().is_some_and(looks_like_json_blob) {
+        return false;
+    }
+    let summary_is_usable = cache
+        .summary
+        .as_deref()
+        .is_some_and(|s| markdown_structure_preserved(body, s, MarkdownStructureCheck::Relaxed));
+    let title_only_cache = cache.summary.is_none() && cache.title.is_some();
+    summary_is_usable || title_only_cache
+}
+
+pub(super) fn build_release_batch_prompt(batch: &[ReleaseBatchCandidate]) -> String {
+    let mut prompt = String::from(
+        "你会收到多条 GitHub Release，请逐条翻译标题与 Markdown 正文。\n输出严格 JSON（不要 markdown code block）：\n{\"items\":[{\"release_id\":123,\"title_zh\":\"...\",\"summary_md\":\"...\"}]}\n要求：\n1) 每个输入 release_id 必须在输出里出现；\n2) title_zh 要自然简洁；\n3) summary_md 必须保留原 Markdown 结构与代码/列表/标题层级，不要补充 URL 或额外事实；\n4) 若正文为空，可返回空字符串作为 summary_md。\n",
+    );
+    for item in batch {
+        prompt.push_str(
+            "
+---
+",
+        );
+        prompt.push_str(&format!(
+            "release_id: {}
+repo: {}
+title: {}
+body_markdown:
+{}
+",
+            item.release_id, item.full_name, item.title, item.body,
+        ));
+    }
+    prompt
+}
+
+pub(super) fn estimate_release_batch_candidate_tokens(item: &ReleaseBatchCandidate) -> u32 {
+    ai::estimate_text_tokens(item.full_name.as_str())
+        .saturating_add(ai::estimate_text_tokens(item.title.as_str()))
+        .saturating_add(ai::estimate_text_tokens(item.body.as_str()))
+        .saturating_add(48)
+}
+
+pub(super) fn release_candidate_can_batch(
+    item: &ReleaseBatchCandidate,
+    chunk_char_budget: usize,
+    batch_input_budget: u32,
+) -> bool {
+    split_markdown_chunks(item.body.as_str(), chunk_char_budget).len() <= 1
+        && estimate_release_batch_candidate_tokens(item)
+            .saturating_add(RELEASE_BATCH_OVERHEAD_TOKENS)
+            <= batch_input_budget
+}
+
+/// Best-effort reader for a release batch reply that is still streaming.
+/// Returns `(release_id, summary_so_far)` for every item whose `summary_md`
+/// string has started, decoding the JSON escapes received so far.
+pub(super) fn extract_partial_release_batch_summaries(raw: &str) -> Vec<(i64, String)> {
+    const RELEASE_ID_KEY: &str = "\"release_id\"";
+    const SUMMARY_KEY: &str = "\"summary_md\"";
+
+    let item_starts = raw
+        .match_indices(RELEASE_ID_KEY)
+        .map(|(idx, _)| idx)
+        .filter(|idx| *idx == 0 || raw.as_bytes()[idx - 1] != b'\\')
+        .collect::<Vec<_>>();
+    let mut out = Vec::new();
+    for (position, start) in item_starts.iter().enumerate() {
+        let end = item_starts.get(position + 1).copied().unwrap_or(raw.len());
+        let segment = &raw[start + RELEASE_ID_KEY.len()..end];
+        let id_text = segment
+            .trim_start()
+            .strip_prefix(':')
+            .map(|rest| rest.trim_start().trim_start_matches('"'))
+            .unwrap_or_default();
+        let digits = id_text
+            .chars()
+            .take_while(char::is_ascii_digit)
+            .collect::<String>();
+        let Ok(release_id) = digits.parse::<i64>() else {
+            continue;
+        };
+        let Some(summary_at) = segment.find(SUMMARY_KEY) else {
+            continue;
+        };
+        let Some(value) = segment[summary_at + SUMMARY_KEY.len()..]
+            .trim_start()
+            .strip_prefix(':')
+            .and_then(|rest| rest.trim_start().strip_prefix('"'))
+        else {
+            continue;
+        };
+        out.push((
+            release_id,
+            decode_partial_json_string(value).replace("\\n", "\n"),
+        ));
+    }
+    out
+}
+
+/// Decodes a JSON string body up to its closing quote or the end of input,
+/// dropping a trailing escape sequence that has not fully arrived yet.
+pub(super) fn decode_partial_json_string(value: &str) -> String {
+    let mut out = String::with_capacity(value.len());
+    let mut chars = value.chars();
+    while let Some(ch) = chars.next() {
+        match ch {
+            '"' => break,
+            '\\' => match chars.next() {
+                Some('n') => out.push('\n'),
+                Some('t') => out.push('\t'),
+                Some('r') => out.push('\r'),
+                Some('b') => out.push('\u{8}'),
+                Some('f') => out.push('\u{c}'),
+                Some('u') => {
+                    let hex = chars.by_ref().take(4).collect::<String>();
+                    match u32::from_str_radix(&hex, 16).ok().and_then(char::from_u32) {
+                        Some(decoded) if hex.len() == 4 => out.push(decoded),
+                        _ => break,
+                    }
+                }
+                Some(other) => out.push(other),
+                None => break,
+            },
+            other => out.push(other),
+        }
+    }
+    out
+}
+
+/// Runs one release batch translation call. With a `partials` sink the reply
+/// is streamed and each item's growing summary is reported as a
+/// `processing` item before the final result is parsed.
+pub(super) async fn release_batch_chat_completion(
+    state: &AppState,
+    prompt: &str,
+    partials: Option<&mpsc::UnboundedSender<TranslateBatchItem>>,
+) -> anyhow::Result<String> {
+    const SYSTEM: &str =
+        "你是一个批量翻译助手，负责把 GitHub Release 标题与 Markdown 正文翻译成自然中文。";
+    let Some(partials) = partials else {
+        return ai::chat_completion(state, SYSTEM, prompt, RELEASE_BATCH_MAX_TOKENS).await;
+    };
+
+    let (delta_tx, mut delta_rx) = mpsc::unbounded_channel();
+    let call =
+        ai::chat_completion_stream(state, SYSTEM, prompt, RELEASE_BATCH_MAX_TOKENS, delta_tx);
+    let relay = async {
+        let mut raw = String::new();
+        let mut reported = HashMap::<i64, usize>::new();
+        while let Some(delta) = delta_rx.recv().await {
+            match delta {
+                ai::ChatCompletionDelta::Content(fragment) => raw.push_str(&fragment),
+                ai::ChatCompletionDelta::Restart => {
+                    raw.clear();
+                    reported.clear();
+                }
+            }
+            // Coalesce whatever else is already buffered before rescanning.
+            while let Ok(delta) = delta_rx.try_recv() {
+                match delta {
+                    ai::ChatCompletionDelta::Content(fragment) => raw.push_str(&fragment),
+                    ai::ChatCompletionDelta::Restart => {
+                        raw.clear();
+                        reported.clear();
+                    }
+                }
+            }
+            for (release_id, summary) in extract_partial_release_batch_summaries(&raw) {
+                let summary_chars = summary.chars().count();
+                let last_reported = reported.get(&release_id).copied();
+                if last_reported.is_some_and(|last| {
+                    summary_chars < last.saturating_add(RELEASE_BATCH_PARTIAL_MIN_GROWTH_CHARS)
+                }) {
+                    continue;
+                }
+                reported.insert(release_id, summary_chars);
+                let _ = partials.send(TranslateBatchItem {
+                    id: release_id.to_string(),
+                    lang: "zh-CN".to_owned(),
+                    status: "processing".to_owned(),
+                    title: None,
+                    summary: Some(summary),
+                    error: None,
+                });
+            }
+        }
+    };
+    let (result, ()) = tokio::join!(call, relay);
+    result.map(|output| {
+        tracing::debug!(
+            first_token_wait_ms = output.first_token_wait_ms,
+            output_tokens = output.usage.output_tokens,
+            total_tokens = output.usage.total_tokens,
+            "release detail batch translation streamed"
+        );
+        output.content
+    })
+}
+
+/// Runs token-packed translation groups with at most `concurrency` in flight
+/// and returns their outcomes in completion order. Once `abort` is set (by a
+/// group that hit a non-retryable upstream error), groups that have not
+/// started yet are skipped; groups already in flight run to completion.
+pub(super) async fn run_translation_batch_groups<G, T, F, Fut>(
+    groups: Vec<G>,
+    concurrency: usize,
+    abort: &AtomicBool,
+    run_group: F,
+) -> Vec<T>
+where
+    F: Fn(G) -> Fut,
+    Fut: Future<Output = T>,
+{
+    let run_group = &run_group;
+    let tasks = groups
+        .into_iter()
+        .map(|group| async move {
+            if abort.load(Ordering::Acquire) {
+                return None;
+            }
+            Some(run_group(group).await)
+        })
+        .collect::<Vec<_>>();
+    futures::stream::iter(tasks)
+        .buffer_unordered(concurrency.max(1))
+        .collect::<Vec<_>>()
+        .await
+        .into_iter()
+        .flatten()
+        .collect()
+}
+
+#[derive(Debug, Default)]
+pub(super) struct ReleaseBatchGroupOutcome {
+    pub(super) batch: Vec<ReleaseBatchCandidate>,
+    pub(super) translated: HashMap<i64, (Option<String>, Option<String>)>,
+    pub(super) non_retryable_error_text: Option<String>,
+}
+
+pub(super) async fn translate_release_batch_group(
+    state: &AppState,
+    batch: Vec<ReleaseBatchCandidate>,
+    partials: Option<&mpsc::UnboundedSender<TranslateBatchItem>>,
+    abort: &AtomicBool,
+) -> ReleaseBatchGroupOutcome {
+    if jobs::current_task_cancel_requested(state).await {
+        abort.store(true, Ordering::Release);
+        return ReleaseBatchGroupOutcome {
+            batch,
+            ..ReleaseBatchGroupOutcome::default()
+        };
+    }
+    let prompt = build_release_batch_prompt(&batch);
+    let raw = release_batch_chat_completion(state, &prompt, partials).await;
+    let mut outcome = ReleaseBatchGroupOutcome {
+        batch,
+        ..ReleaseBatchGroupOutcome::default()
+    };
+
+    match raw {
+        Ok(raw) => {
+            if let Some(payload) = parse_batch_release_translation_payload(&raw) {
+                for item in payload.items {
+                    let Some(candidate) = outcome
+                        .batch
+                        .iter()
+                        .find(|candidate| candidate.release_id == item.release_id)
+                    else {
+                        continue;
+                    };
+                    let (title, summary) =
+                        normalize_translation_fields(item.title_zh, item.summary_md);
+                    let summary = summary.map(|value| {
+                        normalize_markdown_translation_output(candidate.body.as_str(), value)
+                    });
+                    let markdown_ok = candidate.body.trim().is_empty()
+                        || summary.as_deref().is_some_and(|value| {
+                            markdown_structure_preserved(
+                                candidate.body.as_str(),
+                                value,
+                                MarkdownStructureCheck::Relaxed,
+                            ) && translation_links_preserved(candidate.body.as_str(), value)
+                        });
+                    if (title.is_some() || summary.is_some())
+                        && release_detail_translation_ready(
+                            Some(candidate.body.as_str()),
+                            summary.as_deref(),
+                        )
+                        && markdown_ok
+                    {
+                        outcome
+                            .translated
+                            .insert(candidate.release_id, (title, summary));
+                    }
+                }
+            } else {
+                tracing::warn!(
+                    "release detail batch translation response parse failed; fallback to single"
+                );
+            }
+        }
+        Err(err) => {
+            if jobs::is_task_canceled_error(&err) {
+                abort.store(true, Ordering::Release);
+            } else if ai_error_is_non_retryable(&err) {
+                abort.store(true, Ordering::Release);
+                outcome.non_retryable_error_text = Some(err.to_string());
+                tracing::warn!(
+                    ?err,
+                    "release detail batch translation upstream error is non-retryable; skipping remaining batch calls"
+                );
+            } else {
+                tracing::warn!(
+                    ?err,
+                    "release detail batch translation failed; fallback to single"
+                );
+            }
+        }
+    }
+
+    outcome
+}
+
+pub(super) async fn translate_pending_release_batch_candidates(
+    state: &AppState,
+    user_id: &str,
+    pending: &[ReleaseBatchCandidate],
+    partials: Option<&mpsc::UnboundedSender<TranslateBatchItem>>,
+) -> Result<Vec<TranslateBatchItem>, ApiError> {
+    if pending.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let batch_budget = ai::compute_input_budget_with_source(state, RELEASE_BATCH_MAX_TOKENS).await;
+    let chunk_budget = release_detail_chunk_budget(state).await;
+    let mut batchable = Vec::new();
+    let mut fallback = HashMap::<i64, ReleaseBatchCandidate>::new();
+    for candidate in pending {
+        if release_candidate_can_batch(candidate, chunk_budget.max_chars, batch_budget.input_budget)
+        {
+            batchable.push(candidate.clone());
+        } else {
+            fallback.insert(candidate.release_id, candidate.clone());
+        }
+    }
+
+    let estimated = batchable
+        .iter()
+        .map(estimate_release_batch_candidate_tokens)
+        .collect::<Vec<_>>();
+    let groups = ai::pack_batch_indices(
+        &estimated,
+        batch_budget.input_budget,
+        RELEASE_BATCH_OVERHEAD_TOKENS,
+    );
+    if !batchable.is_empty() {
+        let split_count = groups.len().saturating_sub(1);
+        let saved_calls = batchable.len().saturating_sub(groups.len());
+        let estimated_tokens = estimated.iter().copied().sum::<u32>();
+        tracing::info!(
+            batch_size = batchable.len(),
+            estimated_tokens,
+            split_count,
+            saved_calls,
+            fallback_source = batch_budget.fallback_source,
+            input_budget = batch_budget.input_budget,
+            model_input_limit = batch_budget.model_input_limit,
+            "release detail batch plan"
+        );
+    }
+
+    let concurrency = state.config.ai_translate_concurrency;
+    let abort = AtomicBool::new(false);
+    let batchable = &batchable;
+    let outcomes = run_translation_batch_groups(groups, concurrency, &abort, |batch_indices| {
+        let batch = batch_indices
+            .iter()
+            .map(|idx| batchable[*idx].clone())
+            .collect::<Vec<_>>();
+        translate_release_batch_group(state, batch, partials, &abort)
+    })
+    .await;
+
+    if jobs::current_task_cancel_requested(state).await {
+        return Err(ApiError::task_canceled());
+    }
+
+    let mut translated = HashMap::<i64, (Option<String>, Option<String>)>::new();
+    let mut non_retryable_error_text: Option<String> = None;
+    for outcome in outcomes {
+        if let Some(error_text) = outcome.non_retryable_error_text {
+            non_retryable_error_text.get_or_insert(error_text);
+        }
+        for candidate in &outcome.batch {
+            if !outcome.translated.contains_key(&candidate.release_id) {
+                fallback.insert(candidate.release_id, candidate.clone());
+            }
+        }
+        translated.extend(outcome.translated);
+    }
+
+    let requested_at = chrono::Utc::now().to_rfc3339();
+    let translated = &translated;
+    let requested_at = requested_at.as_str();
+    let non_retryable_error_text = non_retryable_error_text.as_deref();
+    let tasks = pending
+        .iter()
+        .map(|candidate| {
+            finish_release_batch_candidate(
+                state,
+                user_id,
+                requested_at,
+                candidate,
+                translated.get(&candidate.release_id).cloned(),
+                non_retryable_error_text,
+            )
+        })
+        .collect::<Vec<_>>();
+    let items: Vec<TranslateBatchItem> = futures::stream::iter(tasks)
+        .buffer_unordered(concurrency.max(1))
+        .try_collect()
+        .await?;
+    if let Err(err) = translation_retry::record_release_batch_outcome(
+        state,
+        user_id,
+        &items,
+        non_retryable_error_text,
+    )
+    .await
+    {
+        tracing::warn!(?err, user_id, "failed to record translation retry queue");
+    }
+    Ok(items)
+}
+
+/// Persists one pending release's batch result, or falls back to a single
+/// translation when the batch did not produce a usable one.
+pub(super) async fn finish_release_batch_candidate(
+    state: &AppState,
+    user_id: &str,
+    requested_at: &str,
+    candidate: &ReleaseBatchCandidate,
+    translated: Option<(Option<String>, Option<String>)>,
+    non_retryable_error_text: Option<&str>,
+) -> Result<TranslateBatchItem, ApiError> {
+    if let Some((title, summary)) = translated {
+        upsert_translation(
+            state,
+            user_id,
+            requested_at,
+            TranslationUpsert {
+                entity_type: "release_detail",
+                entity_id: &candidate.entity_id,
+                lang: "zh-CN",
+                source_hash: &candidate.source_hash,
+                title: title.as_deref(),
+                summary: summary.as_deref(),
+            },
+        )
+        .await?;
+        if candidate.shareable {
+            upsert_shared_translation(
+                state,
+                TranslationUpsert {
+                    entity_type: "release_detail",
+                    entity_id: &candidate.entity_id,
+                    lang: "zh-CN",
+                    source_hash: &candidate.source_hash,
+                    title: title.as_deref(),
+                    summary: summary.as_deref(),
+                },
+            )
+            .await?;
+        }
+        return Ok(TranslateBatchItem {
+            id: candidate.release_id.to_string(),
+            lang: "zh-CN".to_owned(),
+            status: "ready".to_owned(),
+            title,
+            summary,
+            error: None,
+        });
+    }
+
+    if let Some(error_text) = non_retryable_error_text {
+        upsert_translation_terminal_status(
+            state,
+            user_id,
+            requested_at,
+            TranslationUpsert {
+                entity_type: "release_detail",
+                entity_id: &candidate.entity_id,
+                lang: "zh-CN",
+                source_hash: &candidate.source_hash,
+                title: None,
+                summary: None,
+            },
+            "error",
+            Some(error_text),
+        )
+        .await?;
+        return Ok(TranslateBatchItem {
+            id: candidate.release_id.to_string(),
+            lang: "zh-CN".to_owned(),
+            status: "error".to_owned(),
+            title: None,
+            summary: None,
+            error: Some(error_text.to_owned()),
+        });
+    }
+
+    match translate_release_detail_internal(
+        state,
+        user_id,
+        candidate.release_id,
+        ReleaseDetailTranslationMode::Full,
+    )
+    .await
+    {
+        Ok(translated) => Ok(TranslateBatchItem {
+            id: candidate.release_id.to_string(),
+            lang: translated.lang,
+            status: translated.status,
+            title: translated.title,
+            summary: translated.summary,
+            error: None,
+        }),
+        Err(err) if err.code() == codes::NOT_FOUND => Ok(TranslateBatchItem {
+            id: candidate.release_id.to_string(),
+            lang: "zh-CN".to_owned(),
+            status: "missing".to_owned(),
+            title: None,
+            summary: None,
+            error: Some("release not found".to_owned()),
+        }),
+        Err(err) => {
+            let error_text = err.to_string();
+            tracing::warn!(
+                release_id = candidate.release_id,
+                error_code = err.code(),
+                "release detail translation failed inside batch"
+            );
+            Ok(TranslateBatchItem {
+                id: candidate.release_id.to_string(),
+                lang: "zh-CN".to_owned(),
+                status: "error".to_owned(),
+                title: None,
+                summary: None,
+                error: Some(error_text),
+            })
+        }
+    }
+}
+
+/// Rewrites release feed cache rows hashed under the previous source version
+/// to the current hash after their content was found unchanged. Each entry
+/// is `(entity_id, previous_hash, current_hash)`.
+pub(super) async fn upgrade_release_translation_source_hashes(
+    state: &AppState,
+    user_id: &str,
+    upgrades: &[(&str, &str, &str)],
+) -> Result<(), ApiError> {
+    let now = chrono::Utc::now().to_rfc3339();
+    for (entity_id, previous_hash, current_hash) in upgrades {
+        sqlx::query(
+            r#"
+            UPDATE ai_translations
+            SET source_hash = ?, updated_at = ?
+            WHERE user_id = ?
+              AND entity_type = 'release'
+              AND entity_id = ?
+              AND lang = 'zh-CN'
+              AND source_hash = ?
+            "#,
+        )
+        .bind(current_hash)
+        .bind(now.as_str())
+        .bind(user_id)
+        .bind(entity_id)
+        .bind(previous_hash)
+        .execute(&state.pool)
+        .await
+        .map_err(ApiError::internal)?;
+    }
+    Ok(())
+}
+
+#[derive(Debug)]
+pub(super) struct PreparedReleaseBatch {
+    pub(super) detail_pending_candidates: Vec<ReleaseBatchCandidate>,
+    pub(super) translated: HashMap<i64, (Option<String>, Option<String>)>,
+    pub(super) terminal: HashMap<i64, ReleaseBatchTerminalState>,
+    pub(super) missing: HashSet<i64>,
+}
+
+pub(super) async fn prepare_release_batch(
+    state: &AppState,
+    user_id: &str,
+    release_ids: &[i64],
+) -> Result<PreparedReleaseBatch, ApiError> {
+    if state.config.ai.is_none() {
+        return Ok(PreparedReleaseBatch {
+            detail_pending_candidates: Vec::new(),
+            translated: HashMap::new(),
+            terminal: HashMap::new(),
+            missing: HashSet::new(),
+        });
+    }
+
+    let mut source_query = sqlx::QueryBuilder::<sqlx::Sqlite>::new(
+        r#"
+        SELECT
+          r.release_id,
+          sr.full_name,
+          r.tag_name,
+          r.name,
+          r.body,
+          sr.is_private,
+          EXISTS (
+            SELECT 1
+            FROM repo_translation_opt_outs o
+            WHERE o.user_id = sr.user_id
+              AND o.repo_id = sr.repo_id
+          ) AS translation_opted_out
+        FROM repo_releases r
+        JOIN user_release_visible_repos sr
+          ON sr.user_id = "#,
+    );
+    source_query.push_bind(user_id);
+    source_query.push(" AND sr.repo_id = r.repo_id AND r.release_id IN (");
+    {
+        let mut separated = source_query.separated(", ");
+        for release_id in release_ids {
+            separated.push_bind(release_id);
+        }
+    }
+    source_query.push(")");
+
+    let source_rows = source_query
+        .build_query_as::<ReleaseBatchSourceRow>()
+        .fetch_all(&state.pool)
+        .await
+        .map_err(ApiError::internal)?;
+
+    let mut source_by_id = HashMap::new();
+    for row in source_rows {
+        source_by_id.insert(row.release_id, row);
+    }
+
+    let mut candidates = Vec::new();
+    let mut missing = HashSet::new();
+    let mut terminal = HashMap::<i64, ReleaseBatchTerminalState>::new();
+    for release_id in release_ids {
+        let Some(row) = source_by_id.get(release_id) else {
+            missing.insert(*release_id);
+            continue;
+        };
+        // Opted-out repos never reach the cache or the LLM; the distinct
+        // status tells clients not to retry.
+        if row.translation_opted_out {
+            terminal.insert(
+                *release_id,
+                ReleaseBatchTerminalState {
+                    status: "opted_out".to_owned(),
+                    error: None,
+                },
+            );
+            continue;
+        }
+        let title = row
+            .name
+            .as_deref()
+            .filter(|s| !s.trim().is_empty())
+            .unwrap_or(&row.tag_name)
+            .to_owned();
+        let body = row
+            .body
+            .as_deref()
+            .map(|value| value.replace("\r\n", "\n"))
+            .map(|value| value.trim().to_owned())
+            .unwrap_or_default();
+        let legacy_body = release_feed_body(Some(body.as_str()));
+        let legacy_eligible = !release_feed_body_is_over_limit(Some(body.as_str()));
+        let legacy_source_hash = legacy_eligible.then(|| {
+            release_feed_translation_source_hash(
+                row.full_name.as_str(),
+                title.as_str(),
+                legacy_body.as_deref(),
+            )
+        });
+        let previous_legacy_source_hash = legacy_eligible.then(|| {
+            previous_release_feed_translation_source_hash(
+                row.full_name.as_str(),
+                title.as_str(),
+                legacy_body.as_deref(),
+            )
+        });
+        let candidate = ReleaseBatchCandidate {
+            release_id: *release_id,
+            entity_id: release_id.to_string(),
+            full_name: row.full_name.trim().to_owned(),
+            title: title.trim().to_owned(),
+            body: body.clone(),
+            source_hash: release_detail_source_hash(
+                row.full_name.as_str(),
+                title.as_str(),
+                body.as_str(),
+            ),
+            legacy_source_hash,
+            previous_legacy_source_hash,
+            shareable: row.is_private == 0,
+        };
+        candidates.push(candidate);
+    }
+
+    let mut detail_cache_by_entity: HashMap<String, TranslationCacheRow> = HashMap::new();
+    if !candidates.is_empty() {
+        let mut cache_query = sqlx::QueryBuilder::<sqlx::Sqlite>::new(
+            r#"
+            SELECT entity_id, source_hash, status, title, summary, error_text
+            FROM ai_translations
+            WHERE user_id = "#,
+        );
+        cache_query.push_bind(user_id);
+        cache_query.push(" AND entity_type = 'release_detail' AND lang = 'zh-CN' AND status IN ('ready', 'disabled', 'missing', 'error') AND entity_id IN (");
+        {
+            let mut separated = cache_query.separated(", ");
+            for item in &candidates {
+                separated.push_bind(&item.entity_id);
+            }
+        }
+        cache_query.push(")");
+
+        let cache_rows = cache_query
+            .build_query_as::<TranslationCacheRow>()
+            .fetch_all(&state.pool)
+            .await
+            .map_err(ApiError::internal)?;
+        for row in cache_rows {
+            detail_cache_by_entity.insert(row.entity_id.clone(), row);
+        }
+    }
+
+    let mut legacy_cache_by_entity: HashMap<String, TranslationCacheRow> = HashMap::new();
+    if candidates
+        .iter()
+        .any(|candidate| candidate.legacy_source_hash.is_some())
+    {
+        let mut cache_query = sqlx::QueryBuilder::<sqlx::Sqlite>::new(
+            r#"
+            SELECT entity_id, source_hash, status, title, summary, error_text
+            FROM ai_translations
+            WHERE user_id = "#,
+        );
+        cache_query.push_bind(user_id);
+        cache_query.push(" AND entity_type = 'release' AND lang = 'zh-CN' AND status IN ('ready', 'disabled', 'missing', 'error') AND entity_id IN (");
+        {
+            let mut separated = cache_query.separated(", ");
+            for item in &candidates {
+                separated.push_bind(&item.entity_id);
+            }
+        }
+        cache_query.push(")");
+
+        let cache_rows = cache_query
+            .build_query_as::<TranslationCacheRow>()
+            .fetch_all(&state.pool)
+            .await
+            .map_err(ApiError::internal)?;
+        for row in cache_rows {
+            legacy_cache_by_entity.insert(row.entity_id.clone(), row);
+        }
+    }
+
+    let mut detail_pending_candidates = Vec::new();
+    let mut translated = HashMap::<i64, (Option<String>, Option<String>)>::new();
+    let mut legacy_hash_upgrades = Vec::new();
+
+    for item in &candidates {
+        if let Some(cache) = detail_cache_by_entity.get(&item.entity_id)
+            && cache.source_hash == item.source_hash
+        {
+            if matches!(cache.status.as_str(), "disabled" | "missing" | "error") {
+                terminal.insert(
+                    item.release_id,
+                    ReleaseBatchTerminalState {
+                        status: cache.status.clone(),
+                        error: cache.error_text.clone(),
+                    },
+                );
+                continue;
+            }
+            let (title, summary) =
+                normalize_translation_fields(cache.title.clone(), cache.summary.clone());
+            if release_detail_translation_ready(Some(item.body.as_str()), summary.as_deref()) {
+                translated.insert(item.release_id, (title, summary));
+                continue;
+            }
+        }
+        if let Some(legacy_source_hash) = item.legacy_source_hash.as_deref()
+            && let Some(cache) = legacy_cache_by_entity.get(&item.entity_id)
+            && (cache.source_hash == legacy_source_hash
+                || item.previous_legacy_source_hash.as_deref() == Some(cache.source_hash.as_str()))
+        {
+            let reused = if cache.status == "disabled" {
+                terminal.insert(
+                    item.release_id,
+                    ReleaseBatchTerminalState {
+                        status: cache.status.clone(),
+                        error: cache.error_text.clone(),
+                    },
+                );
+                true
+            } else if cache.status == "ready" {
+                let (title, summary) =
+                    normalize_translation_fields(cache.title.clone(), cache.summary.clone());
+                let usable = summary
+                    .as_deref()
+                    .is_some_and(|value| !value.trim().is_empty());
+                if usable {
+                    translated.insert(item.release_id, (title, summary));
+                }
+                usable
+            } else {
+                false
+            };
+            if reused {
+                if cache.source_hash != legacy_source_hash {
+                    legacy_hash_upgrades.push((
+                        item.entity_id.as_str(),
+                        cache.source_hash.as_str(),
+                        legacy_source_hash,
+                    ));
+                }
+                continue;
+            }
+        }
+        detail_pending_candidates.push(item.clone());
+    }
+
+    upgrade_release_translation_source_hashes(state, user_id, &legacy_hash_upgrades).await?;
+
+    let cache_hits_user = translated.len();
+    // Notes already written in Chinese are their own translation. Caching the
+    // original under the detail hash means later batches hit the cache above.
+    let (already_chinese, still_pending): (Vec<_>, Vec<_>) =
+        detail_pending_candidates.into_iter().partition(|item| {
+            crate::lang_detect::is_already_chinese(
+                format!("{}\n{}", item.title, item.body).as_str(),
+                state.config.ai_skip_translation_cjk_percent,
+            )
+        });
+    detail_pending_candidates = still_pending;
+    if !already_chinese.is_empty() {
+        let requested_at = chrono::Utc::now().to_rfc3339();
+        for item in &already_chinese {
+            let summary = (!item.body.is_empty()).then(|| item.body.clone());
+            upsert_translation(
+                state,
+                user_id,
+                requested_at.as_str(),
+                TranslationUpsert {
+                    entity_type: "release_detail",
+                    entity_id: &item.entity_id,
+                    lang: "zh-CN",
+                    source_hash: &item.source_hash,
+                    title: Some(item.title.as_str()),
+                    summary: summary.as_deref(),
+                },
+            )
+            .await?;
+            translated.insert(item.release_id, (Some(item.title.clone()), summary));
+        }
+    }
+    let shared_hits =
+        load_shared_release_detail_translations(state, &detail_pending_candidates).await?;
+    let mut cache_hits_shared = 0_usize;
+    if !shared_hits.is_empty() {
+        let requested_at = chrono::Utc::now().to_rfc3339();
+        let mut still_pending = Vec::with_capacity(detail_pending_candidates.len());
+        for item in detail_pending_candidates {
+            let Some((title, summary)) = shared_hits.get(&item.entity_id).cloned() else {
+                still_pending.push(item);
+                continue;
+            };
+            upsert_translation(
+                state,
+                user_id,
+                requested_at.as_str(),
+                TranslationUpsert {
+                    entity_type: "release_detail",
+                    entity_id: &item.entity_id,
+                    lang: "zh-CN",
+                    source_hash: &item.source_hash,
+                    title: title.as_deref(),
+                    summary: summary.as_deref(),
+                },
+            )
+            .await?;
+            translated.insert(item.release_id, (title, summary));
+            cache_hits_shared += 1;
+        }
+        detail_pending_candidates = still_pending;
+    }
+    observability::record_cache_lookups(
+        "release_translation",
+        cache_hits_user + cache_hits_shared,
+        detail_pending_candidates.len(),
+    );
+    if !candidates.is_empty() {
+        tracing::info!(
+            candidates = candidates.len(),
+            cache_hits_user,
+            cache_hits_shared,
+            skipped_original = already_chinese.len(),
+            pending = detail_pending_candidates.len(),
+            "release detail batch cache plan"
+        );
+    }
+
+    Ok(PreparedReleaseBatch {
+        detail_pending_candidates,
+        translated,
+        terminal,
+        missing,
+    })
+}
+
+/// Ready shared translations for the shareable candidates whose source hash
+/// still matches; private-repo candidates are never looked up.
+pub(super) async fn load_shared_release_detail_translations(
+    state: &AppState,
+    candidates: &[ReleaseBatchCandidate],
+) -> Result<HashMap<String, (Option<String>, Option<String>)>, ApiError> {
+    let shareable = candidates
+        .iter()
+        .filter(|candidate| candidate.shareable)
+        .collect::<Vec<_>>();
+    if shareable.is_empty() {
+        return Ok(HashMap::new());
+    }
+
+    let mut query = sqlx::QueryBuilder::<sqlx::Sqlite>::new(
+        r#"
+        SELECT entity_id, source_hash, title, summary
+        FROM ai_translations_shared
+        WHERE entity_type = 'release_detail' AND lang = 'zh-CN' AND entity_id IN (
+        "#,
+    );
+    {
+        let mut separated = query.separated(", ");
+        for candidate in &shareable {
+            separated.push_bind(&candidate.entity_id);
+        }
+    }
+    query.push(")");
+    let rows = query
+        .build_query_as::<(String, String, Option<String>, Option<String>)>()
+        .fetch_all(&state.pool)
+        .await
+        .map_err(ApiError::internal)?;
+    let rows = rows
+        .into_iter()
+        .map(|(entity_id, source_hash, title, summary)| (entity_id, (source_hash, title, summary)))
+        .collect::<HashMap<_, _>>();
+
+    let mut hits = HashMap::new();
+    for candidate in shareable {
+        let Some((source_hash, title, summary)) = rows.get(&candidate.entity_id) else {
+            continue;
+        };
+        if *source_hash != candidate.source_hash {
+            continue;
+        }
+        let (title, summary) = normalize_translation_fields(title.clone(), summary.clone());
+        if release_detail_translation_ready(Some(candidate.body.as_str()), summary.as_deref()) {
+            hits.insert(candidate.entity_id.clone(), (title, summary));
+        }
+    }
+    Ok(hits)
+}
+
+pub(super) async fn upsert_shared_translation(
+    state: &AppState,
+    t: TranslationUpsert<'_>,
+) -> Result<(), ApiError> {
+    let now = chrono::Utc::now().to_rfc3339();
+    sqlx::query(
+        r#"
+        INSERT INTO ai_translations_shared (
+          entity_type, entity_id, lang, source_hash, title, summary, created_at, updated_at
+        )
+        VALUES (?, ?, ?, ?, ?, ?, ?, ?)
+        ON CONFLICT(entity_type, entity_id, lang) DO UPDATE SET
+          source_hash = excluded.source_hash,
+          title = excluded.title,
+          summary = excluded.summary,
+          updated_at = excluded.updated_at
+        "#,
+    )
+    .bind(t.entity_type)
+    .bind(t.entity_id)
+    .bind(t.lang)
+    .bind(t.source_hash)
+    .bind(t.title)
+    .bind(t.summary)
+    .bind(now.as_str())
+    .bind(now.as_str())
+    .execute(&state.pool)
+    .await
+    .map_err(ApiError::internal)?;
+    Ok(())
+}
+
+pub(super) fn build_release_batch_item(
+    release_id: i64,
+    missing: &HashSet<i64>,
+    terminal: &HashMap<i64, ReleaseBatchTerminalState>,
+    translated: &HashMap<i64, (Option<String>, Option<String>)>,
+) -> TranslateBatchItem {
+    if missing.contains(&release_id) {
+        return TranslateBatchItem {
+            id: release_id.to_string(),
+            lang: "zh-CN".to_owned(),
+            status: "missing".to_owned(),
+            title: None,
+            summary: None,
+            error: Some("release not found".to_owned()),
+        };
+    }
+
+    if let Some(terminal_state) = terminal.get(&release_id) {
+        return TranslateBatchItem {
+            id: release_id.to_string(),
+            lang: "zh-CN".to_owned(),
+            status: terminal_state.status.clone(),
+            title: None,
+            summary: None,
+            error: terminal_state.error.clone().or_else(|| {
+                (terminal_state.status == "missing")
+                    .then_some("translation result missing".to_owned())
+            }),
+        };
+    }
+
+    if let Some((title, summary)) = translated.get(&release_id) {
+        return TranslateBatchItem {
+            id: release_id.to_string(),
+            lang: "zh-CN".to_owned(),
+            status: "ready".to_owned(),
+            title: title.clone(),
+            summary: summary.clone(),
+            error: None,
+        };
+    }
+
+    TranslateBatchItem {
+        id: release_id.to_string(),
+        lang: "zh-CN".to_owned(),
+        status: "error".to_owned(),
+        title: None,
+        summary: None,
+        error: Some("translation failed".to_owned()),
+    }
+}
+
+pub(super) async fn upsert_translation_terminal_status(
+    state: &AppState,
+    user_id: &str,
+    requested_at: &str,
+    t: TranslationUpsert<'_>,
+    status: &str,
+    error_text: Option<&str>,
+) -> Result<(), ApiError> {
+    let now = chrono::Utc::now().to_rfc3339();
+    sqlx::query(
+        r#"
+        INSERT INTO ai_translations (
+          id, user_id, entity_type, entity_id, lang, source_hash, status, title, summary,
+          error_text, active_work_item_id, created_at, updated_at
+        )
+        VALUES (?, ?, ?, ?, ?, ?, ?, NULL, NULL, ?, NULL, ?, ?)
+        ON CONFLICT(user_id, entity_type, entity_id, lang) DO UPDATE SET
+          source_hash = excluded.source_hash,
+          status = excluded.status,
+          title = excluded.title,
+          summary = excluded.summary,
+          error_text = excluded.error_text,
+          active_work_item_id = excluded.active_work_item_id,
+          updated_at = excluded.updated_at
+        WHERE ai_translations.source_hash = excluded.source_hash
+           OR ai_translations.updated_at <= ?
+        "#,
+    )
+    .bind(crate::local_id::generate_local_id())
+    .bind(user_id)
+    .bind(t.entity_type)
+    .bind(t.entity_id)
+    .bind(t.lang)
+    .bind(t.source_hash)
+    .bind(status)
+    .bind(error_text)
+    .bind(&now)
+    .bind(&now)
+    .bind(requested_at)
+    .execute(&state.pool)
+    .await
+    .map_err(ApiError::internal)?;
+    Ok(())
+}
+
+pub(super) async fn translate_releases_batch_internal(
+    state: &AppState,
+    user_id: &str,
+    release_ids: &[i64],
+) -> Result<Vec<TranslateBatchItem>, ApiError> {
+    if state.config.ai.is_none() {
+        return Ok(release_ids
+            .iter()
+            .map(|release_id| TranslateBatchItem {
+                id: release_id.to_string(),
+                lang: "zh-CN".to_owned(),
+                status: "disabled".to_owned(),
+                title: None,
+                summary: None,
+                error: None,
+            })
+            .collect());
+    }
+
+    let mut prepared = prepare_release_batch(state, user_id, release_ids).await?;
+    if !prepared.detail_pending_candidates.is_empty() {
+        for item in translate_pending_release_batch_candidates(
+            state,
+            user_id,
+            &prepared.detail_pending_candidates,
+            None,
+        )
+        .await?
+        {
+            let Ok(release_id) = item.id.parse::<i64>() else {
+                continue;
+            };
+            match item.status.as_str() {
+                "ready" => {
+                    prepared
+                        .translated
+                        .insert(release_id, (item.title, item.summary));
+                }
+                "disabled" | "missing" | "error" => {
+                    prepared.terminal.insert(
+                        release_id,
+                        ReleaseBatchTerminalState {
+                            status: item.status,
+                            error: item.error,
+                        },
+                    );
+                }
+                _ => {}
+            }
+        }
+    }
+    Ok(release_ids
+        .iter()
+        .map(|release_id| {
+            build_release_batch_item(
+                *release_id,
+                &prepared.missing,
+                &prepared.terminal,
+                &prepared.translated,
+            )
+        })
+        .collect())
+}
+
+pub async fn translate_releases_batch_for_user(
+    state: &AppState,
+    user_id: &str,
+    release_ids: &[i64],
+) -> Result<TranslateBatchResponse, ApiError> {
+    let items = translate_releases_batch_internal(state, user_id, release_ids).await?;
+    Ok(TranslateBatchResponse { items })
+}
+
+/// Flagged translations one repair run re-translates; the rest wait for the
+/// next run.
+pub(super) const TRANSLATION_REPAIR_MAX_ITEMS: usize = 200;
+/// Releases handed to the batch translator per call. It packs them into
+/// token-sized model requests on its own.
+pub(super) const TRANSLATION_REPAIR_BATCH_SIZE: usize = 20;
+pub(super) const TRANSLATION_REPAIR_SCAN_PAGE: i64 = 500;
+
+#[derive(Debug, Default, Serialize, JsonSchema)]
+pub struct TranslationRepairResult {
+    /// Ready release translations whose source is still visible.
+    pub scanned: usize,
+    /// Rows holding a JSON blob or breaking the source Markdown layout.
+    pub flagged: usize,
+    pub fixed: usize,
+    pub failed: usize,
+    /// Flagged rows left alone because the run hit its cap or AI is disabled.
+    pub skipped: usize,
+}
+
+#[derive(Debug, sqlx::FromRow)]
+pub(super) struct TranslationRepairRow {
+    pub(super) id: String,
+    pub(super) user_id: String,
+    pub(super) entity_type: String,
+    pub(super) entity_id: String,
+    pub(super) source_hash: String,
+    pub(super) summary: Option<String>,
+    pub(super) release_id: i64,
+    pub(super) full_name: String,
+    pub(super) tag_name: String,
+    pub(super) name: Option<String>,
+    pub(super) body: Option<String>,
+    pub(super) is_private: i64,
+}
+
+/// Whether a cached release translation is damaged for the source it was
+/// produced from. Rows whose source changed since are not judged here; the
+/// regular path re-translates them when they are next requested.
+pub(super) fn release_translation_needs_repair(row: &TranslationRepairRow) -> bool {
+    let title = row
+        .name
+        .as_deref()
+        .filter(|s| !s.trim().is_empty())
+        .unwrap_or(&row.tag_name);
+    let body = row
+        .body
+        .as_deref()
+        .map(|value| value.replace("\r\n", "\n"))
+        .map(|value| value.trim().to_owned())
+        .unwrap_or_default();
+    let source = match row.entity_type.as_str() {
+        "release_detail" => {
+            if release_detail_source_hash(&row.full_name, title, &body) != row.source_hash {
+                return false;
+            }
+            body
+        }
+        _ => {
+            let feed_body = release_feed_body(Some(body.as_str()));
+            if release_feed_translation_source_hash(&row.full_name, title, feed_body.as_deref())
+                != row.source_hash
+            {
+                return false;
+            }
+            feed_body.unwrap_or_default()
+        }
+    };
+    let Some(summary) = row.summary.as_deref().filter(|s| !s.trim().is_empty()) else {
+        return false;
+    };
+    looks_like_json_blob(summary)
+        || !markdown_structure_preserved(&source, summary, MarkdownStructureCheck::Relaxed)
+}
+
+pub(super) async fn load_translation_repair_page(
+    state: &AppState,
+    user_id: Option<&str>,
+    after_id: &str,
+) -> Result<Vec<TranslationRepairRow>, ApiError> {
+    sqlx::query_as::<_, TranslationRepairRow>(
+        r#"
+        SELECT
+          t.id,
+          t.user_id,
+          t.entity_type,
+          t.entity_id,
+          t.source_hash,
+          t.summary,
+          r.release_id,
+          sr.full_name,
+          r.tag_name,
+          r.name,
+          r.body,
+          sr.is_private
+        FROM ai_translations t
+        JOIN repo_releases r ON r.release_id = CAST(t.entity_id AS INTEGER)
+        JOIN user_release_visible_repos sr
+          ON sr.user_id = t.user_id AND sr.repo_id = r.repo_id
+        WHERE t.entity_type IN ('release', 'release_detail')
+          AND t.lang = 'zh-CN'
+          AND t.status = 'ready'
+          AND t.summary IS NOT NULL
+          AND (? IS NULL OR t.user_id = ?)
+          AND t.id > ?
+        ORDER BY t.id
+        LIMIT ?
+        "#,
+    )
+    .bind(user_id)
+    .bind(user_id)
+    .bind(after_id)
+    .bind(TRANSLATION_REPAIR_SCAN_PAGE)
+    .fetch_all(&state.pool)
+    .await
+    .map_err(ApiError::internal)
+}
+
+/// Drops the damaged rows so the batch translator cannot reuse them. A shared
+/// row is only dropped when it carries the same broken summary.
+pub(super) async fn drop_damaged_release_translations(
+    state: &AppState,
+    rows: &[TranslationRepairRow],
+) -> Result<(), ApiError> {
+    let mut tx = state.pool.begin().await.map_err(ApiError::internal)?;
+    for row in rows {
+        sqlx::query(r#"DELETE FROM ai_translations WHERE id = ?"#)
+            .bind(row.id.as_str())
+            .execute(&mut *tx)
+            .await
+            .map_err(ApiError::internal)?;
+        if row.entity_type == "release_detail" && row.is_private == 0 {
+            sqlx::query(
+                r#"
+                DELETE FROM ai_translations_shared
+                WHERE entity_type = 'release_detail'
+                  AND entity_id = ?
+                  AND lang = 'zh-CN'
+                  AND source_hash = ?
+                  AND summary IS ?
+                "#,
+            )
+            .bind(row.entity_id.as_str())
+            .bind(row.source_hash.as_str())
+            .bind(row.summary.as_deref())
+            .execute(&mut *tx)
+            .await
+            .map_err(ApiError::internal)?;
+        }
+    }
+    tx.commit().await.map_err(ApiError::internal)
+}
+
+/// Finds cached release translations that hold a JSON blob or lost the
+/// source Markdown layout and translates them again through the batch
+/// pipeline. Without `user_id` every user's cache is scanned.
+pub async fn repair_release_translations(
+    state: &AppState,
+    user_id: Option<&str>,
+) -> Result<TranslationRepairResult, ApiError> {
+    let mut result = TranslationRepairResult::default();
+    let mut flagged_by_user = BTreeMap::<String, Vec<TranslationRepairRow>>::new();
+    let mut after_id = String::new();
+    loop {
+        let page = load_translation_repair_page(state, user_id, after_id.as_str()).await?;
+        let Some(last) = page.last() else {
+            break;
+        };
+        after_id = last.id.clone();
+        let page_len = page.len();
+        for row in page {
+            result.scanned += 1;
+            if !release_translation_needs_repair(&row) {
+                continue;
+            }
+            result.flagged += 1;
+            if state.config.ai.is_none() || result.flagged > TRANSLATION_REPAIR_MAX_ITEMS {
+                result.skipped += 1;
+                continue;
+            }
+            flagged_by_user
+                .entry(row.user_id.clone())
+                .or_default()
+                .push(row);
+        }
+        if page_len < TRANSLATION_REPAIR_SCAN_PAGE as usize {
+            break;
+        }
+    }
+
+    for (user_id, rows) in flagged_by_user {
+        if jobs::current_task_cancel_requested(state).await {
+            result.skipped += rows.len();
+            continue;
+        }
+        drop_damaged_release_translations(state, &rows).await?;
+
+        let mut release_ids = rows.iter().map(|row| row.release_id).collect::<Vec<_>>();
+        release_ids.sort_unstable();
+        release_ids.dedup();
+        let mut ready = HashSet::new();
+        for chunk in release_ids.chunks(TRANSLATION_REPAIR_BATCH_SIZE) {
+            match translate_releases_batch_internal(state, user_id.as_str(), chunk).await {
+                Ok(items) => ready.extend(
+                    items
+                        .into_iter()
+                        .filter(|item| item.status == "ready")
+                        .filter_map(|item| item.id.parse::<i64>().ok()),
+                ),
+                Err(err) => {
+                    tracing::warn!(
+                        user_id,
+                        code = err.code(),
+                        "translation repair: batch translate failed"
+                    );
+                }
+            }
+        }
+        for row in &rows {
+            if ready.contains(&row.release_id) {
+                result.fixed += 1;
+            } else {
+                result.failed += 1;
+            }
+        }
+    }
+
+    Ok(result)
+}
+
+#[derive(Debug, Clone)]
+pub(super) struct ReleaseSmartBatchCandidate {
+    pub(super) release_id: i64,
+    pub(super) entity_id: String,
+    pub(super) full_name: String,
+    pub(super) tag_name: String,
+    pub(super) previous_tag_name: Option<String>,
+    pub(super) title: String,
+    pub(super) body: String,
+    pub(super) source_hash: String,
+}
+
+#[derive(Debug)]
+pub(super) struct PreparedReleaseSmartBatch {
+    pub(super) candidates: Vec<ReleaseSmartBatchCandidate>,
+    pub(super) pending: Vec<ReleaseSmartBatchCandidate>,
+    pub(super) smart: HashMap<i64, (Option<String>, Option<String>)>,
+    pub(super) terminal: HashMap<i64, ReleaseBatchTerminalState>,
+    pub(super) missing: HashSet<i64>,
+}
+
+#[derive(Debug, sqlx::FromRow)]
+pub(super) struct ReleaseSmartBatchSourceRow {
+    pub(super) release_id: i64,
+    pub(super) full_name: String,
+    pub(super) tag_name: String,
+    pub(super) name: Option<String>,
+    pub(super) body: Option<String>,
+    pub(super) previous_tag_name: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub(super) struct ReleaseSmartSummaryPayload {
+    pub(super) valuable: bool,
+    pub(super) title_zh: Option<String>,
+    pub(super) summary_bullets: Vec<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(super) struct GitHubCompareResponse {
+    pub(super) status: Option<String>,
+    pub(super) ahead_by: Option<i64>,
+    pub(super) behind_by: Option<i64>,
+    pub(super) total_commits: Option<i64>,
+    pub(super) commits: Vec<GitHubCompareCommit>,
+    pub(super) files: Vec<GitHubCompareFile>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(super) struct GitHubCompareCommit {
+    pub(super) sha: String,
+    pub(super) commit: GitHubCompareCommitDetail,
+}
+
+#[derive(Debug, Deserialize)]
+pub(super) struct GitHubCompareCommitDetail {
+    pub(super) message: String,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(super) struct GitHubCompareFile {
+    pub(super) filename: String,
+    pub(super) status: Option<String>,
+    pub(super) additions: Option<i64>,
+    pub(super) deletions: Option<i64>,
+    pub(super) changes: Option<i64>,
+    pub(super) patch: Option<String>,
+}
+
+pub(super) fn value_as_bool(raw: &serde_json::Value) -> Option<bool> {
+    raw.as_bool().or_else(|| match raw.as_str()?.trim() {
+        "true" | "yes" | "1" => Some(true),
+        "false" | "no" | "0" => Some(false),
+        _ => None,
+    })
+}
+
+pub(super) fn sanitize_smart_bullet_text(raw: &str) -> String {
+    let normalized = raw.replace(['\r', '\n'], " ");
+    let mut out = Vec::new();
+    for token in normalized.split_whitespace() {
+        let candidate = token.trim_matches(|c: char| {
+            matches!(
+                c,
+                ')' | '(' | '[' | ']' | '<' | '>' | ',' | ';' | '"' | '\'' | '.'
+            )
+        });
+        if candidate.starts_with("https://") || candidate.starts_with("http://") {
+            continue;
+        }
+        out.push(token.to_owned());
+    }
+    out.join(" ").trim().to_owned()
+}
+
+pub(super) fn render_smart_summary_markdown(bullets: &[String]) -> Option<String> {
+    let lines = bullets
+        .iter()
+        .map(|bullet| sanitize_smart_bullet_text(bullet))
+        .map(|bullet| bullet.trim().to_owned())
+        .filter(|bullet| !bullet.is_empty())
+        .take(4)
+        .map(|bullet| format!("- {bullet}"))
+        .collect::<Vec<_>>();
+    if lines.is_empty() {
+        None
+    } else {
+        Some(lines.join("\n"))
+    }
+}
+
+pub(super) fn parse_release_smart_summary_payload(raw: &str) -> Option<ReleaseSmartSummaryPayload> {
+    let value = parse_json_value_relaxed(raw)?;
+    let obj = value.as_object()?;
+    let valuable = obj.get("valuable").and_then(value_as_bool)?;
+    let title_zh = object_field_as_string(obj, &["title_zh", "title", "title_cn"]);
+    let summary_bullets = obj
+        .get("summary_bullets")
+        .or_else(|| obj.get("bullets"))
+        .and_then(serde_json::Value::as_array)
+        .map(|items| {
+            items
+                .iter()
+                .filter_map(value_as_id_string)
+                .map(|bullet| sanitize_smart_bullet_text(bullet.as_str()))
+                .filter(|bullet| !bullet.is_empty())
+                .take(4)
+                .collect::<Vec<_>>()
+        })
+        .unwrap_or_default();
+    Some(ReleaseSmartSummaryPayload {
+        valuable,
+        title_zh,
+        summary_bullets,
+    })
+}
+
+pub(super) fn release_smart_body_prompt(item: &ReleaseSmartBatchCandidate) -> String {
+    format!(
+        "Repo: {repo}\nRelease title: {title}\nRelease tag: {tag}\nSource: release body\n\nRelease body:\n{body}\n\n请判断这段 GitHub Release 正文是否足以生成“方便了解项目版本变化”的中文要点卡片。\n输出严格 JSON（不要 markdown code block）：\n{{\"valuable\":true,\"title_zh\":\"...\",\"summary_bullets\":[\"...\",\"...\"]}}\n\n硬性要求：\n1) 只能根据输入证据，不得脑补；\n2) valuable=true 时，summary_bullets 必须是 1-4 条简洁中文要点，聚焦真实版本变化；\n3) valuable=false 时，summary_bullets 必须是空数组，title_zh 可为 null；\n4) 若正文只有模板、链接、空话、营销句、版本占位或“查看 commits”等无实质变更说明，必须返回 valuable=false；\n5) 不输出 URL，不写长段落，不逐句直译原文。",
+        repo = item.full_name,
+        title = item.title,
+        tag = item.tag_name,
+        body = item.body,
+    )
+}
+
+pub(super) fn release_smart_diff_prompt(
+    item: &ReleaseSmartBatchCandidate,
+    compare_range: &str,
+    digest: &str,
+) -> String {
+    format!(
+        "Repo: {repo}\nRelease title: {title}\nRelease tag: {tag}\nCompare range: {compare_range}\nSource: compare digest\n\n说明：release body 已经被判定为不足以支撑版本变化摘要，请只根据下列 compare 摘要判断是否能提炼出对人类有用的版本变化。\n\nCompare digest:\n{digest}\n\n输出严格 JSON（不要 markdown code block）：\n{{\"valuable\":true,\"title_zh\":\"...\",\"summary_bullets\":[\"...\",\"...\"]}}\n\n硬性要求：\n1) 只能依据给定 compare digest，总结 1-4 条中文要点；\n2) 优先总结功能、修复、性能、兼容性、运维/构建变更等可读变化；\n3) 若 digest 只体现 lockfile、minified、generated、版本号或噪声文件，且无法说明实际版本变化，必须返回 valuable=false；\n4) valuable=false 时 summary_bullets 必须为空数组；\n5) 不输出 URL，不得臆测未提供的行为影响。",
+        repo = item.full_name,
+        title = item.title,
+        tag = item.tag_name,
+        compare_range = compare_range,
+        digest = digest,
+    )
+}
+
+pub(super) fn compare_file_is_noise(file: &GitHubCompareFile) -> bool {
+    let name = file.filename.to_ascii_lowercase();
+    let lockfiles = [
+        "pnpm-lock.yaml",
+        "package-lock.json",
+        "yarn.lock",
+        "cargo.lock",
+        "composer.lock",
+        "gemfile.lock",
+        "go.sum",
+        "poetry.lock",
+        "pipfile.lock",
+        "bun.lock",
+        "bun.lockb",
+        "uv.lock",
+    ];
+    if lockfiles.iter().any(|suffix| name.ends_with(suffix)) {
+        return true;
+    }
+    if name.ends_with(".min.js")
+        || name.ends_with(".min.css")
+        || name.ends_with(".map")
+        || name.contains("/dist/")
+        || name.contains("/build/")
+        || name.contains("/generated/")
+        || name.contains(".generated.")
+    {
+        return true;
+    }
+    let binary_exts = [
+        ".png", ".jpg", ".jpeg", ".gif", ".webp", ".ico", ".pdf", ".zip", ".gz", ".tar", ".jar",
+        ".wasm", ".woff", ".woff2", ".ttf", ".otf", ".mp4", ".mov",
+    ];
+    binary_exts.iter().any(|suffix| name.ends_with(suffix))
+        || file.patch.is_none() && name.ends_with(".svg")
+}
+
+pub(super) fn summarize_patch_excerpt(patch: &str) -> Option<String> {
+    let mut lines = Vec::new();
+    for line in patch.lines() {
+        let trimmed = line.trim_end();
+        if trimmed.starts_with("@@") || trimmed.is_empty() {
+            continue;
+        }
+        if !(trimmed.starts_with('+') || trimmed.starts_with('-')) {
+            continue;
+        }
+        if trimmed.starts_with("+++") || trimmed.starts_with("---") {
+            continue;
+        }
+        lines.push(trimmed.to_owned());
+        if lines.len() >= 12 {
+            break;
+        }
+    }
+    if lines.is_empty() {
+        None
+    } else {
+        Some(truncate_chars(&lines.join("\n"), 800).into_owned())
+    }
+}
+
+pub(super) fn build_compare_digest(compare: &GitHubCompareResponse) -> Option<String> {
+    let mut lines = Vec::new();
+    if let Some(status) = compare.status.as_deref() {
+        lines.push(format!("compare_status: {status}"));
+    }
+    if let Some(ahead_by) = compare.ahead_by {
+        lines.push(format!("ahead_by: {ahead_by}"));
+    }
+    if let Some(behind_by) = compare.behind_by {
+        lines.push(format!("behind_by: {behind_by}"));
+    }
+    if let Some(total_commits) = compare.total_commits {
+        lines.push(format!("total_commits: {total_commits}"));
+    }
+
+    let commit_lines = compare
+        .commits
+        .iter()
+        .filter_map(|commit| {
+            let subject = commit.commit.message.lines().next()?.trim();
+            if subject.is_empty() {
+                return None;
+            }
+            Some(format!(
+                "- {}: {}",
+                &commit.sha.chars().take(7).collect::<String>(),
+                truncate_chars(subject, 140).into_owned()
+            ))
+        })
+        .take(12)
+        .collect::<Vec<_>>();
+    if !commit_lines.is_empty() {
+        lines.push("commit_subjects:".to_owned());
+        lines.extend(commit_lines);
+    }
+
+    let mut file_lines = Vec::new();
+    let mut excerpt_lines = Vec::new();
+    for file in compare
+        .files
+        .iter()
+        .filter(|file| !compare_file_is_noise(file))
+        .take(8)
+    {
+        file_lines.push(format!(
+            "- {} [{}] (+{} / -{} / Δ{})",
+            file.filename,
+            file.status.as_deref().unwrap_or("modified"),
+            file.additions.unwrap_or(0),
+            file.deletions.unwrap_or(0),
+            file.changes.unwrap_or(0),
+        ));
+        if let Some(patch) = file.patch.as_deref().and_then(summarize_patch_excerpt) {
+            excerpt_lines.push(format!("### {}\n{}", file.filename, patch));
+        }
+    }
+    if !file_lines.is_empty() {
+        lines.push("changed_files:".to_owned());
+        lines.extend(file_lines);
+    }
+    if !excerpt_lines.is_empty() {
+        lines.push("patch_excerpts:".to_owned());
+        lines.extend(excerpt_lines);
+    }
+
+    if lines.is_empty() {
+        None
+    } else {
+        Some(lines.join("\n"))
+    }
+}
+
+pub(super) fn github_rest_http_error(
+    operation: &str,
+    status: reqwest::StatusCode,
+    headers: &reqwest::header::HeaderMap,
+    body: &str,
+) -> ApiError {
+    if status == reqwest::StatusCode::UNAUTHORIZED {
+        return github_reauth_required_error();
+    }
+    if status == reqwest::StatusCode::FORBIDDEN {
+        let remaining = headers
+            .get("x-ratelimit-remaining")
+            .and_then(|v| v.to_str().ok())
+            .map(str::trim);
+        if remaining == Some("0") || is_rate_limit_message(body) {
+            return github_rate_limited_error();
+        }
+        if is_reauth_message(body) {
+            return github_reauth_required_error();
+        }
+        if is_access_restricted_message(body) {
+            return github_access_restricted_error();
+        }
+    }
+    ApiError::internal(format!("github {operation} returned {status}: {body}"))
+}
+
+pub(super) async fn fetch_release_compare_digest_request(
+    state: &AppState,
+    repo_full_name: &str,
+    base_tag: &str,
+    head_tag: &str,
+    access_token: Option<&str>,
+) -> Result<Option<String>, ApiError> {
+    let compare_ref = format!(
+        "{}...{}",
+        urlencoding::encode(base_tag),
+        urlencoding::encode(head_tag)
+    );
+    let url = format!("https://api.github.com/repos/{repo_full_name}/compare/{compare_ref}");
+    let mut request = state
+        .http
+        .get(url)
+        .header(reqwest::header::USER_AGENT, "OctoRill")
+        .header(reqwest::header::ACCEPT, "application/vnd.github+json")
+        .header("X-GitHub-Api-Version", "2022-11-28");
+    if let Some(token) = access_token {
+        request = request.bearer_auth(token);
+    }
+    let response = request.send().await.map_err(ApiError::internal)?;
+    let status = response.status();
+    if !status.is_success() {
+        let headers = response.headers().clone();
+        let body = response.text().await.unwrap_or_default();
+        return Err(github_rest_http_error("compare", status, &headers, &body));
+    }
+    let payload = response
+        .json::<GitHubCompareResponse>()
+        .await
+        .map_err(ApiError::internal)?;
+    Ok(build_compare_digest(&payload))
+}
+
+pub(super) async fn fetch_release_compare_digest(
+    state: &AppState,
+    user_id: &str,
+    repo_full_name: &str,
+    base_tag: &str,
+    head_tag: &str,
+) -> Result<Option<String>, ApiError> {
+    let connections = state
+        .load_github_connections(user_id)
+        .await
+        .map_err(|err| ApiError::internal(format!("load github connections failed: {err}")))?;
+
+    let mut last_auth_err: Option<ApiError> = None;
+    for connection in connections {
+        match fetch_release_compare_digest_request(
+            state,
+            repo_full_name,
+            base_tag,
+            head_tag,
+            Some(connection.access_token.as_str()),
+        )
+        .await
+        {
+            Ok(digest) => return Ok(digest),
+            Err(err) if should_retry_public_compare_without_auth(&err) => {
+                last_auth_err = Some(err);
+            }
+            Err(err) => return Err(err),
+        }
+    }
+
+    if let Some(auth_err) = last_auth_err {
+        match fetch_release_compare_digest_request(state, repo_full_name, base_tag, head_tag, None)
+            .await
+        {
+            Ok(digest) => Ok(digest),
+            Err(public_err) => Err(map_public_compare_fallback_error(auth_err, public_err)),
+        }
+    } else {
+        fetch_release_compare_digest_request(state, repo_full_name, base_tag, head_tag, None).await
+    }
+}
+
+pub(super) fn should_retry_public_compare_without_auth(err: &ApiError) -> bool {
+    matches!(err.code(), "reauth_required" | "forbidden")
+}
+
+pub(super) fn map_public_compare_fallback_error(
+    auth_err: ApiError,
+    public_err: ApiError,
+) -> ApiError {
+    if public_err.code() == codes::RATE_LIMITED || public_err.code() == codes::FORBIDDEN {
+        return public_err;
+    }
+    if auth_err.code() == codes::REAUTH_REQUIRED {
+        return github_private_repo_scope_required_error();
+    }
+    auth_err
+}
+
+pub(super) async fn summarize_release_smart_candidate_with_ai(
+    state: &AppState,
+    user_id: &str,
+    item: &ReleaseSmartBatchCandidate,
+) -> Result<Option<(Option<String>, Option<String>)>, ApiError> {
+    let body_prompt = release_smart_body_prompt(item);
+    let raw = ai::chat_completion(
+        state,
+        "你是一个严谨的版本变化整理助手，专门把 GitHub Release 证据整理成便于人类快速理解的中文要点卡片。只能根据给定证据输出，不得脑补。",
+        &body_prompt,
+        700,
+    )
+    .await
+    .map_err(ai_call_api_error)?;
+    let parsed = parse_release_smart_summary_payload(&raw)
+        .ok_or_else(|| ApiError::internal("release smart body summary json decode failed"))?;
+    if parsed.valuable {
+        let summary = render_smart_summary_markdown(&parsed.summary_bullets)
+            .ok_or_else(|| ApiError::internal("release smart body summary bullets missing"))?;
+        return Ok(Some((parsed.title_zh, Some(summary))));
+    }
+
+    let Some(previous_tag_name) = item
+        .previous_tag_name
+        .as_deref()
+        .map(str::trim)
+        .filter(|value| !value.is_empty())
+    else {
+        return Ok(None);
+    };
+    let compare_range = format!("{previous_tag_name}...{}", item.tag_name);
+    let digest = fetch_release_compare_digest(
+        state,
+        user_id,
+        item.full_name.as_str(),
+        previous_tag_name,
+        item.tag_name.as_str(),
+    )
+    .await?;
+    let Some(digest) = digest else {
+        return Ok(None);
+    };
+
+    let diff_prompt = release_smart_diff_prompt(item, &compare_range, &digest);
+    let raw = ai::chat_completion(
+        state,
+        "你是一个严谨的版本变化整理助手，专门把 GitHub compare 摘要整理成便于人类快速理解的中文版本变化要点。只能根据给定证据输出，不得脑补。",
+        &diff_prompt,
+        700,
+    )
+    .await
+    .map_err(ai_call_api_error)?;
+    let parsed = parse_release_smart_summary_payload(&raw)
+        .ok_or_else(|| ApiError::internal("release smart diff summary json decode failed"))?;
+    if !parsed.valuable {
+        return Ok(None);
+    }
+    let summary = render_smart_summary_markdown(&parsed.summary_bullets)
+        .ok_or_else(|| ApiError::internal("release smart diff summary bullets missing"))?;
+    Ok(Some((parsed.title_zh, Some(summary))))
+}
+
+pub(super) async fn prepare_release_smart_batch(
+    state: &AppState,
+    user_id: &str,
+    release_ids: &[i64],
+) -> Result<PreparedReleaseSmartBatch, ApiError> {
+    if state.config.ai.is_none() {
+        return Ok(PreparedReleaseSmartBatch {
+            candidates: Vec::new(),
+            pending: Vec::new(),
+            smart: HashMap::new(),
+            terminal: HashMap::new(),
+            missing: HashSet::new(),
+        });
+    }
+
+    let mut source_query = sqlx::QueryBuilder::<sqlx::Sqlite>::new(
+        r#"
+        WITH target_releases AS (
+          SELECT
+            r.repo_id AS repo_id,
+            r.release_id AS release_id,
+            r.tag_name AS tag_name,
+            r.name AS name,
+            r.body AS body,
+            COALESCE(r.published_at, r.created_at, r.updated_at) AS sort_ts
+          FROM repo_releases r
+          WHERE r.release_id IN (
+        "#,
+    );
+    {
+        let mut separated = source_query.separated(", ");
+        for release_id in release_ids {
+            separated.push_bind(release_id);
+        }
+    }
+    source_query.push(
+        r#"
+          )
+        )
+        SELECT
+          tr.release_id AS release_id,
+          sr.full_name AS full_name,
+          tr.tag_name AS tag_name,
+          tr.name AS name,
+          tr.body AS body,
+          (
+            SELECT prev.tag_name
+            FROM repo_releases prev
+            WHERE prev.repo_id = tr.repo_id
+              AND (
+                COALESCE(prev.published_at, prev.created_at, prev.updated_at) < tr.sort_ts
+                OR (
+                  COALESCE(prev.published_at, prev.created_at, prev.updated_at) = tr.sort_ts
+                  AND prev.release_id < tr.release_id
+                )
+              )
+            ORDER BY
+              COALESCE(prev.published_at, prev.created_at, prev.updated_at) DESC,
+              prev.release_id DESC
+            LIMIT 1
+          ) AS previous_tag_name
+        FROM target_releases tr
+        JOIN user_release_visible_repos sr
+          ON sr.user_id =
+        "#,
+    );
+    source_query.push_bind(user_id);
+    source_query.push(" AND sr.repo_id = tr.repo_id");
+
+    let source_rows = source_query
+        .build_query_as::<ReleaseSmartBatchSourceRow>()
+        .fetch_all(&state.pool)
+        .await
+        .map_err(ApiError::internal)?;
+
+    let mut source_by_id = HashMap::new();
+    for row in source_rows {
+        source_by_id.insert(row.release_id, row);
+    }
+
+    let mut candidates = Vec::new();
+    let mut missing = HashSet::new();
+    for release_id in release_ids {
+        let Some(row) = source_by_id.get(release_id) else {
+            missing.insert(*release_id);
+            continue;
+        };
+        let entity_id = release_id.to_string();
+        let title = row
+            .name
+            .as_deref()
+            .map(str::trim)
+            .filter(|value| !value.is_empty())
+            .unwrap_or(&row.tag_name)
+            .to_owned();
+        let body = release_feed_body(row.body.as_deref()).unwrap_or_default();
+        let source_hash = crate::translations::release_smart_feed_source_hash(
+            entity_id.as_str(),
+            row.full_name.as_str(),
+            title.as_str(),
+            Some(body.as_str()),
+            row.tag_name.as_str(),
+            row.previous_tag_name.as_deref(),
+        );
+        candidates.push(ReleaseSmartBatchCandidate {
+            release_id: *release_id,
+            entity_id,
+            full_name: row.full_name.clone(),
+            tag_name: row.tag_name.clone(),
+            previous_tag_name: row.previous_tag_name.clone(),
+            title,
+            body,
+            source_hash,
+        });
+    }
+
+    let mut cache_by_entity = HashMap::<String, TranslationCacheRow>::new();
+    if !candidates.is_empty() {
+        let mut cache_query = sqlx::QueryBuilder::<sqlx::Sqlite>::new(
+            r#"
+            SELECT entity_id, source_hash, status, title, summary, error_text
+            FROM ai_translations
+            WHERE user_id = "#,
+        );
+        cache_query.push_bind(user_id);
+        cache_query.push(" AND entity_type = 'release_smart' AND lang = 'zh-CN' AND status IN ('ready', 'disabled', 'missing', 'error') AND entity_id IN (");
+        {
+            let mut separated = cache_query.separated(", ");
+            for item in &candidates {
+                separated.push_bind(&item.entity_id);
+            }
+        }
+        cache_query.push(")");
+
+        let cache_rows = cache_query
+            .build_query_as::<TranslationCacheRow>()
+            .fetch_all(&state.pool)
+            .await
+            .map_err(ApiError::internal)?;
+        for row in cache_rows {
+            cache_by_entity.insert(row.entity_id.clone(), row);
+        }
+    }
+
+    let mut pending = Vec::new();
+    let mut smart = HashMap::<i64, (Option<String>, Option<String>)>::new();
+    let mut terminal = HashMap::<i64, ReleaseBatchTerminalState>::new();
+
+    for item in &candidates {
+        if let Some(cache) = cache_by_entity.get(&item.entity_id)
+            && cache.source_hash == item.source_hash
+        {
+            if cache.status == "disabled"
+                || (cache.status == "missing"
+                    && cache.error_text.as_deref() == Some(SMART_NO_VALUABLE_VERSION_INFO))
+            {
+                terminal.insert(
+                    item.release_id,
+                    ReleaseBatchTerminalState {
+                        status: cache.status.clone(),
+                        error: cache.error_text.clone(),
+                    },
+                );
+                continue;
+            }
+            if cache.status == "error" {
+                if smart_error_is_retryable(cache.error_text.as_deref()) {
+                    pending.push(item.clone());
+                    continue;
+                }
+                terminal.insert(
+                    item.release_id,
+                    ReleaseBatchTerminalState {
+                        status: cache.status.clone(),
+                        error: cache.error_text.clone(),
+                    },
+                );
+                continue;
+            }
+            if cache.status == "ready" {
+                let ready = smart_ready_item(cache.title.clone(), cache.summary.clone(), None);
+                if let Some(ready) = ready {
+                    smart.insert(item.release_id, (ready.title, ready.summary));
+                    continue;
+                }
+            }
+        }
+        pending.push(item.clone());
+    }
+
+    Ok(PreparedReleaseSmartBatch {
+        candidates,
+        pending,
+        smart,
+        terminal,
+        missing,
+    })
+}
+
+pub(super) async fn mark_release_smart_requested(
+    state: &AppState,
+    user_id: &str,
+    requested_at: &str,
+    candidates: &[ReleaseSmartBatchCandidate],
+) -> Result<(), ApiError> {
+    for item in candidates {
+        mark_translation_requested(
+            state,
+            user_id,
+            requested_at,
+            TranslationUpsert {
+                entity_type: "release_smart",
+                entity_id: &item.entity_id,
+                lang: "zh-CN",
+                source_hash: &item.source_hash,
+                title: None,
+                summary: None,
+            },
+        )
+        .await?;
+    }
+    Ok(())
+}
+
+pub(super) async fn upsert_release_smart_results(
+    state: &AppState,
+    user_id: &str,
+    requested_at: &str,
+    candidates: &[ReleaseSmartBatchCandidate],
+    smart: &HashMap<i64, (Option<String>, Option<String>)>,
+) -> Result<(), ApiError> {
+    for item in candidates {
+        if let Some((title, summary)) = smart.get(&item.release_id) {
+            upsert_translation(
+                state,
+                user_id,
+                requested_at,
+                TranslationUpsert {
+                    entity_type: "release_smart",
+                    entity_id: &item.entity_id,
+                    lang: "zh-CN",
+                    source_hash: &item.source_hash,
+                    title: title.as_deref(),
+                    summary: summary.as_deref(),
+                },
+            )
+            .await?;
+        }
+    }
+    Ok(())
+}
+
+pub(super) async fn upsert_release_smart_terminal_states(
+    state: &AppState,
+    user_id: &str,
+    requested_at: &str,
+    candidates: &[ReleaseSmartBatchCandidate],
+    terminal: &HashMap<i64, ReleaseBatchTerminalState>,
+) -> Result<(), ApiError> {
+    for item in candidates {
+        let Some(terminal_state) = terminal.get(&item.release_id) else {
+            continue;
+        };
+        upsert_translation_terminal_status(
+            state,
+            user_id,
+            requested_at,
+            TranslationUpsert {
+                entity_type: "release_smart",
+                entity_id: &item.entity_id,
+                lang: "zh-CN",
+                source_hash: &item.source_hash,
+                title: None,
+                summary: None,
+            },
+            terminal_state.status.as_str(),
+            terminal_state.error.as_deref(),
+        )
+        .await?;
+    }
+    Ok(())
+}
+
+pub(super) fn build_release_smart_batch_item(
+    release_id: i64,
+    missing: &HashSet<i64>,
+    terminal: &HashMap<i64, ReleaseBatchTerminalState>,
+    smart: &HashMap<i64, (Option<String>, Option<String>)>,
+) -> TranslateBatchItem {
+    if missing.contains(&release_id) {
+        return TranslateBatchItem {
+            id: release_id.to_string(),
+            lang: "zh-CN".to_owned(),
+            status: "missing".to_owned(),
+            title: None,
+            summary: None,
+            error: Some("release not found".to_owned()),
+        };
+    }
+
+    if let Some(terminal_state) = terminal.get(&release_id) {
+        return TranslateBatchItem {
+            id: release_id.to_string(),
+            lang: "zh-CN".to_owned(),
+            status: terminal_state.status.clone(),
+            title: None,
+            summary: None,
+            error: terminal_state.error.clone().or_else(|| {
+                (terminal_state.status == "missing")
+                    .then_some("translation result missing".to_owned())
+            }),
+        };
+    }
+
+    if let Some((title, summary)) = smart.get(&release_id) {
+        return TranslateBatchItem {
+            id: release_id.to_string(),
+            lang: "zh-CN".to_owned(),
+            status: "ready".to_owned(),
+            title: title.clone(),
+            summary: summary.clone(),
+            error: None,
+        };
+    }
+
+    TranslateBatchItem {
+        id: release_id.to_string(),
+        lang: "zh-CN".to_owned(),
+        status: "error".to_owned(),
+        title: None,
+        summary: None,
+        error: Some("release smart summary failed".to_owned()),
+    }
+}
+
+pub(super) async fn summarize_releases_smart_batch_internal(
+    state: &AppState,
+    user_id: &str,
+    release_ids: &[i64],
+) -> Result<Vec<TranslateBatchItem>, ApiError> {
+    if state.config.ai.is_none() {
+        return Ok(release_ids
+            .iter()
+            .map(|release_id| TranslateBatchItem {
+                id: release_id.to_string(),
+                lang: "zh-CN".to_owned(),
+                status: "disabled".to_owned(),
+                title: None,
+                summary: None,
+                error: None,
+            })
+            .collect());
+    }
+
+    let requested_at = chrono::Utc::now().to_rfc3339();
+    let mut prepared = prepare_release_smart_batch(state, user_id, release_ids).await?;
+    mark_release_smart_requested(state, user_id, requested_at.as_str(), &prepared.pending).await?;
+
+    for item in &prepared.pending {
+        match summarize_release_smart_candidate_with_ai(state, user_id, item).await {
+            Ok(Some(result)) => {
+                prepared.smart.insert(item.release_id, result);
+            }
+            Ok(None) => {
+                prepared.terminal.insert(
+                    item.release_id,
+                    ReleaseBatchTerminalState {
+                        status: "missing".to_owned(),
+                        error: Some(SMART_NO_VALUABLE_VERSION_INFO.to_owned()),
+                    },
+                );
+            }
+            Err(err) => {
+                prepared.terminal.insert(
+                    item.release_id,
+                    ReleaseBatchTerminalState {
+                        status: "error".to_owned(),
+                        error: Some(err.to_string()),
+                    },
+                );
+            }
+        }
+    }
+
+    upsert_release_smart_results(
+        state,
+        user_id,
+        requested_at.as_str(),
+        &prepared.candidates,
+        &prepared.smart,
+    )
+    .await?;
+    upsert_release_smart_terminal_states(
+        state,
+        user_id,
+        requested_at.as_str(),
+        &prepared.candidates,
+        &prepared.terminal,
+    )
+    .await?;
+
+    Ok(release_ids
+        .iter()
+        .map(|release_id| {
+            build_release_smart_batch_item(
+                *release_id,
+                &prepared.missing,
+                &prepared.terminal,
+                &prepared.smart,
+            )
+        })
+        .collect())
+}
+
+pub async fn summarize_releases_smart_batch_for_user(
+    state: &AppState,
+    user_id: &str,
+    release_ids: &[i64],
+) -> Result<TranslateBatchResponse, ApiError> {
+    let items = summarize_releases_smart_batch_internal(state, user_id, release_ids).await?;
+    Ok(TranslateBatchResponse { items })
+}
+
+#[allow(dead_code)]
+pub(super) async fn translate_releases_batch_stream_worker(
+    state: Arc<AppState>,
+    user_id: String,
+    release_ids: Vec<i64>,
+    task_id: String,
+    tx: mpsc::Sender<Result<Bytes, Infallible>>,
+) {
+    let heartbeat = jobs::spawn_task_lease_heartbeat(state.clone(), task_id.clone());
+    let _cancellation = jobs::register_task_cancellation(&task_id);
+    let _attached_streams = register_batch_stream_waker(&task_id);
+    let mut ready_count = 0usize;
+    let mut disabled_count = 0usize;
+    let mut missing_count = 0usize;
+    let mut error_count = 0usize;
+
+    let context = ai::LlmCallContext {
+        source: "api.translate_releases_batch_stream".to_owned(),
+        requested_by: Some(user_id.clone()),
+        parent_task_id: Some(task_id.clone()),
+        parent_task_type: Some(jobs::TASK_TRANSLATE_RELEASE_BATCH.to_owned()),
+        parent_translation_batch_id: None,
+    };
+
+    let result = ai::with_llm_call_context(context, async {
+        jobs::append_task_event(
+            state.as_ref(),
+            task_id.as_str(),
+            "task.progress",
+            json!({
+                "task_id": task_id.as_str(),
+                "stage": "collect",
+                "total_releases": release_ids.len(),
+            }),
+        )
+        .await
+        .map_err(ApiError::internal)?;
+
+        if state.config.ai.is_none() {
+            for release_id in &release_ids {
+                let item = TranslateBatchItem {
+                    id: release_id.to_string(),
+                    lang: "zh-CN".to_owned(),
+                    status: "disabled".to_owned(),
+                    title: None,
+                    summary: None,
+                    error: None,
+                };
+                if !send_batch_stream_event(
+                    &tx,
+                    TranslateBatchStreamEvent {
+                        event: "item",
+                        item: Some(item.clone()),
+                        error: None,
+                    },
+                )
+                .await
+                {
+                    return Err(ApiError::internal("stream client disconnected"));
+                }
+
+                accumulate_batch_item_stats(
+                    &item,
+                    &mut ready_count,
+                    &mut disabled_count,
+                    &mut missing_count,
+                    &mut error_count,
+                );
+                append_batch_item_event(state.as_ref(), task_id.as_str(), &item)
+                    .await
+                    .map_err(ApiError::internal)?;
+            }
+            if !send_batch_stream_event(
+                &tx,
+                TranslateBatchStreamEvent {
+                    event: "done",
+                    item: None,
+                    error: None,
+                },
+            )
+            .await
+            {
+                return Err(ApiError::internal("stream client disconnected"));
+            }
+            return Ok(());
+        }
+
+        let mut prepared = prepare_release_batch(state.as_ref(), &user_id, &release_ids).await?;
+        let detail_pending_ids = prepared
+            .detail_pending_candidates
+            .iter()
+            .map(|candidate| candidate.release_id)
+            .collect::<HashSet<_>>();
+
+        for release_id in &release_ids {
+            if detail_pending_ids.contains(release_id) {
+                continue;
+            }
+            let item = build_release_batch_item(
+                *release_id,
+                &prepared.missing,
+                &prepared.terminal,
+                &prepared.translated,
+            );
+            if !send_batch_stream_event(
+                &tx,
+                TranslateBatchStreamEvent {
+                    event: "item",
+                    item: Some(item.clone()),
+                    error: None,
+                },
+            )
+            .await
+            {
+                return Err(ApiError::internal("stream client disconnected"));
+            }
+
+            accumulate_batch_item_stats(
+                &item,
+                &mut ready_count,
+                &mut disabled_count,
+                &mut missing_count,
+                &mut error_count,
+            );
+            append_batch_item_event(state.as_ref(), task_id.as_str(), &item)
+                .await
+                .map_err(ApiError::internal)?;
+        }
+
+        if !prepared.detail_pending_candidates.is_empty() {
+            for candidate in &prepared.detail_pending_candidates {
+                if !send_batch_stream_event(
+                    &tx,
+                    TranslateBatchStreamEvent {
+                        event: "item",
+                        item: Some(TranslateBatchItem {
+                            id: candidate.release_id.to_string(),
+                            lang: "zh-CN".to_owned(),
+                            status: "processing".to_owned(),
+                            title: None,
+                            summary: None,
+                            error: None,
+                        }),
+                        error: None,
+                    },
+                )
+                .await
+                {
+                    return Err(ApiError::internal("stream client disconnected"));
+                }
+            }
+
+            let (partial_tx, mut partial_rx) = mpsc::unbounded_channel::<TranslateBatchItem>();
+            let translating = {
+                let state = state.as_ref();
+                let user_id = user_id.as_str();
+                let candidates = &prepared.detail_pending_candidates;
+                async move {
+                    let result = translate_pending_release_batch_candidates(
+                        state,
+                        user_id,
+                        candidates,
+                        Some(&partial_tx),
+                    )
+                    .await;
+                    drop(partial_tx);
+                    result
+                }
+            };
+            let forwarding = async {
+                let mut connected = true;
+                while let Some(item) = partial_rx.recv().await {
+                    if connected {
+                        connected = send_batch_stream_event(
+                            &tx,
+                            TranslateBatchStreamEvent {
+                                event: "partial",
+                                item: Some(item),
+                                error: None,
+                            },
+                        )
+                        .await;
+                    }
+                }
+                connected
+            };
+            let (translated, connected) = tokio::join!(translating, forwarding);
+            if !connected {
+                return Err(ApiError::internal("stream client disconnected"));
+            }
+
+            for item in translated? {
+                if let Ok(release_id) = item.id.parse::<i64>() {
+                    match item.status.as_str() {
+                        "ready" => {
+                            prepared
+                                .translated
+                                .insert(release_id, (item.title.clone(), item.summary.clone()));
+                        }
+                        "disabled" | "missing" | "error" => {
+                            prepared.terminal.insert(
+                                release_id,
+                                ReleaseBatchTerminalState {
+                                    status: item.status.clone(),
+                                    error: item.error.clone(),
+                                },
+                            );
+                        }
+                        _ => {}
+                    }
+                }
+                if !send_batch_stream_event(
+                    &tx,
+                    TranslateBatchStreamEvent {
+                        event: "item",
+                        item: Some(item.clone()),
+                        error: None,
+                    },
+                )
+                .await
+                {
+                    return Err(ApiError::internal("stream client disconnected"));
+                }
+                accumulate_batch_item_stats(
+                    &item,
+                    &mut ready_count,
+                    &mut disabled_count,
+                    &mut missing_count,
+                    &mut error_count,
+                );
+                append_batch_item_event(state.as_ref(), task_id.as_str(), &item)
+                    .await
+                    .map_err(ApiError::internal)?;
+            }
+        }
+
+        if !send_batch_stream_event(
+            &tx,
+            TranslateBatchStreamEvent {
+                event: "done",
+                item: None,
+                error: None,
+            },
+        )
+        .await
+        {
+            return Err(ApiError::internal("stream client disconnected"));
+        }
+        Ok::<(), ApiError>(())
+    })
+    .await;
+
+    let canceled = matches!(&result, Err(err) if err.code() == codes::TASK_CANCELED)
+        || jobs::task_cancel_requested(state.as_ref(), task_id.as_str()).await;
+    match result {
+        _ if canceled => {
+            let _ = jobs::complete_task(
+                state.as_ref(),
+                task_id.as_str(),
+                jobs::STATUS_CANCELED,
+                None,
+                None,
+            )
+            .await;
+            heartbeat.stop().await;
+            let _ = jobs::append_task_event(
+                state.as_ref(),
+                task_id.as_str(),
+                "task.completed",
+                json!({
+                    "task_id": task_id.as_str(),
+                    "status": jobs::STATUS_CANCELED,
+                }),
+            )
+            .await;
+            let _ = send_batch_stream_event(
+                &tx,
+                TranslateBatchStreamEvent {
+                    event: "error",
+                    item: None,
+                    error: Some(jobs::TASK_CANCELED_ERROR.to_owned()),
+                },
+            )
+            .await;
+        }
+        Ok(()) => {
+            let summary = json!({
+                "total": release_ids.len(),
+                "ready": ready_count,
+                "disabled": disabled_count,
+                "missing": missing_count,
+                "error": error_count,
+            });
+            let _ = jobs::complete_task(
+                state.as_ref(),
+                task_id.as_str(),
+                jobs::STATUS_SUCCEEDED,
+                Some(summary.clone()),
+                None,
+            )
+            .await;
+            heartbeat.stop().await;
+            let _ = jobs::append_task_event(
+                state.as_ref(),
+                task_id.as_str(),
+                "task.completed",
+                json!({
+                    "task_id": task_id.as_str(),
+                    "status": jobs::STATUS_SUCCEEDED,
+                    "summary": summary,
+                }),
+            )
+            .await;
+        }
+        Err(err) => {
+            let error_message = format!("{}: stream worker failed", err.code());
+            let _ = jobs::complete_task(
+                state.as_ref(),
+                task_id.as_str(),
+                jobs::STATUS_FAILED,
+                None,
+                Some(error_message.clone()),
+            )
+            .await;
+            let _ = jobs::append_task_event(
+                state.as_ref(),
+                task_id.as_str(),
+                "task.completed",
+                json!({
+                    "task_id": task_id.as_str(),
+                    "status": jobs::STATUS_FAILED,
+                    "error": error_message,
+                }),
+            )
+            .await;
+            heartbeat.stop().await;
+            let _ = send_batch_stream_event(
+                &tx,
+                TranslateBatchStreamEvent {
+                    event: "error",
+                    item: None,
+                    error: Some(error_message),
+                },
+            )
+            .await;
+        }
+    }
+}
+
+#[allow(dead_code)]
+pub async fn translate_releases_batch(
+    State(state): State<Arc<AppState>>,
+    AuthedUser(user_id): AuthedUser,
+    ApiJson(req): ApiJson<TranslateReleasesBatchRequest>,
+) -> Result<Json<TranslateBatchResponse>, ApiError> {
+    let release_ids = parse_unique_release_ids(&req.release_ids, 60)?;
+    let items = run_with_api_llm_context(
+        "api.translate_releases_batch",
+        Some(user_id.clone()),
+        translate_releases_batch_internal(state.as_ref(), user_id.as_str(), &release_ids),
+    )
+    .await?;
+    Ok(Json(TranslateBatchResponse {
+        items: translate_batch_items_for_public(items),
+    }))
+}
+
+#[allow(dead_code)]
+pub async fn translate_releases_batch_stream(
+    State(state): State<Arc<AppState>>,
+    AuthedUser(user_id): AuthedUser,
+    ApiJson(req): ApiJson<TranslateReleasesBatchRequest>,
+) -> Result<Response, ApiError> {
+    let release_ids = parse_unique_release_ids(&req.release_ids, 60)?;
+    let tracking_task = jobs::start_inline_task(
+        state.as_ref(),
+        jobs::NewTask {
+            task_type: jobs::TASK_TRANSLATE_RELEASE_BATCH.to_owned(),
+            payload: json!({
+                "user_id": user_id.clone(),
+                "release_ids": release_ids.clone(),
+            }),
+            source: "api.translate_releases_batch_stream".to_owned(),
+            requested_by: Some(user_id.clone()),
+            parent_task_id: None,
+            priority: jobs::TaskPriority::High,
+        },
+    )
+    .await
+    .map_err(ApiError::internal)?;
+
+    let (tx, rx) = mpsc::channel::<Result<Bytes, Infallible>>(64);
+    let state_cloned = state.clone();
+    let tracking_task_id = tracking_task.task_id;
+    let task_id_header = HeaderValue::from_str(&tracking_task_id).map_err(ApiError::internal)?;
+
+    tokio::spawn(async move {
+        translate_releases_batch_stream_worker(
+            state_cloned,
+            user_id,
+            release_ids,
+            tracking_task_id,
+            tx,
+        )
+        .await;
+    });
+
+    let body = Body::from_stream(batch_stream_until_shutdown(rx));
+    let mut response = Response::new(body);
+    *response.status_mut() = StatusCode::OK;
+    response.headers_mut().insert(
+        header::CONTENT_TYPE,
+        HeaderValue::from_static("application/x-ndjson; charset=utf-8"),
+    );
+    response
+        .headers_mut()
+        .insert(header::CACHE_CONTROL, HeaderValue::from_static("no-cache"));
+    // Lets the client re-attach through `/translations/batch/{task_id}/stream`.
+    response
+        .headers_mut()
+        .insert(BATCH_STREAM_TASK_ID_HEADER, task_id_header);
+    Ok(response)
+}
+
+/// Re-attaches to a release batch stream started by
+/// `translate_releases_batch_stream`, e.g. after the original connection
+/// dropped. Only the user who started the task, or an admin, may attach.
+pub async fn attach_translate_releases_batch_stream(
+    State(state): State<Arc<AppState>>,
+    Path(task_id): Path<String>,
+    AuthedUser(user_id): AuthedUser,
+) -> Result<Response, ApiError> {
+    let task_id = parse_local_id_param(task_id, "task_id")?;
+    let task = sqlx::query_as::<_, (String, Option<String>)>(
+        r#"
+        SELECT task_type, requested_by
+        FROM job_tasks
+        WHERE id = ?
+        LIMIT 1
+        "#,
+    )
+    .bind(task_id.as_str())
+    .fetch_optional(&state.pool)
+    .await
+    .map_err(ApiError::internal)?;
+    let task_not_found =
+        || ApiError::new(StatusCode::NOT_FOUND, codes::NOT_FOUND, "task not found");
+    let Some((task_type, requested_by)) = task else {
+        return Err(task_not_found());
+    };
+    if task_type != jobs::TASK_TRANSLATE_RELEASE_BATCH {
+        return Err(task_not_found());
+    }
+    if requested_by.as_deref() != Some(user_id.as_str()) {
+        let is_admin =
+            sqlx::query_scalar::<_, i64>(r#"SELECT is_admin FROM users WHERE id = ? LIMIT 1"#)
+                .bind(user_id.as_str())
+                .fetch_optional(&state.pool)
+                .await
+                .map_err(ApiError::internal)?
+                .unwrap_or(0);
+        if is_admin == 0 {
+            return Err(task_not_found());
+        }
+    }
+
+    let body = Body::from_stream(batch_stream_attach(state, task_id));
+    let mut response = Response::new(body);
+    *response.status_mut() = StatusCode::OK;
+    response.headers_mut().insert(
+        header::CONTENT_TYPE,
+        HeaderValue::from_static("application/x-ndjson; charset=utf-8"),
+    );
+    response
+        .headers_mut()
+        .insert(header::CACHE_CONTROL, HeaderValue::from_static("no-cache"));
+    Ok(response)
+}
+
+pub async fn translate_release_for_user(
+    state: &AppState,
+    user_id: &str,
+    release_id_raw: &str,
+) -> Result<TranslateResponse, ApiError> {
+    let release_id = parse_release_id_param(release_id_raw)?;
+    let mut items = translate_releases_batch_internal(state, user_id, &[release_id]).await?;
+    let Some(item) = items.pop() else {
+        return Err(ApiError::internal("missing translation result"));
+    };
+    translate_response_from_batch_item(item)
+}
+
+#[allow(dead_code)]
+pub async fn translate_release(
+    State(state): State<Arc<AppState>>,
+    AuthedUser(user_id): AuthedUser,
+    Query(mode_query): Query<ReturnModeQuery>,
+    ApiJson(req): ApiJson<TranslateReleaseRequest>,
+) -> Result<Response, ApiError> {
+    let release_id = req.release_id.trim().to_owned();
+    let mode = ReturnMode::from_query(&mode_query)?;
+
+    if matches!(mode, ReturnMode::Sync) {
+        let translated = run_with_api_llm_context(
+            "api.translate_release.sync",
+            Some(user_id.clone()),
+            translate_release_for_user(state.as_ref(), user_id.as_str(), release_id.as_str()),
+        )
+        .await?;
+        return Ok(Json(translated).into_response());
+    }
+
+    enqueue_or_stream_task(
+        state,
+        mode,
+        jobs::NewTask {
+            task_type: jobs::TASK_TRANSLATE_RELEASE.to_owned(),
+            payload: json!({
+                "user_id": user_id.clone(),
+                "release_id": release_id,
+            }),
+            source: "api.translate_release".to_owned(),
+            requested_by: Some(user_id.clone()),
+            parent_task_id: None,
+            priority: jobs::TaskPriority::High,
+        },
+    )
+    .await
+}
+
+pub(super) async fn translate_release_detail_chunk(
+    state: &AppState,
+    budget: ReleaseDetailChunkBudget,
+    repo_full_name: &str,
+    original_title: &str,
+    chunk: &str,
+    current: usize,
+    total: usize,
+) -> Result<String, ApiError> {
+    let prompt = format!(
+        "Repo: {repo}\nTitle: {title}\nChunk: {current}/{total}\n\nRelease notes chunk (Markdown):\n{chunk}\n\n请把这段 GitHub Release notes 翻译成中文 Markdown，要求：\n1) 保留原有 Markdown 结构（标题/列表/表格/引用/代码块）；\n2) 保留链接 URL 与代码；\n3) 不新增、不删减信息；\n4) 只输出翻译后的 Markdown，不要解释。",
+        repo = repo_full_name,
+        title = original_title,
+        current = current,
+        total = total,
+        chunk = chunk,
+    );
+
+    let translated = ai::chat_completion(
+        state,
+        "你是一个严谨的技术文档翻译助手，负责把 GitHub Release notes 翻译成中文并保持 Markdown 结构。",
+        &prompt,
+        budget.max_output_tokens,
+    )
+    .await
+    .map_err(ai_call_api_error)?;
+    let translated = normalize_markdown_translation_output(chunk, translated);
+    let structure_ok =
+        markdown_structure_preserved(chunk, &translated, RELEASE_DETAIL_CHUNK_STRUCTURE_CHECK);
+    if structure_ok && translation_links_preserved(chunk, &translated) {
+        return Ok(translated);
+    }
+    let retry_reason = if structure_ok {
+        "链接与原文不一致"
+    } else {
+        "结构不一致"
+    };
+
+    let retry_prompt = format!(
+        "Repo: {repo}\nTitle: {title}\nChunk: {current}/{total}\n\nRelease notes chunk (Markdown):\n{chunk}\n\n上一次译文（{retry_reason}，需重译）：\n{translated}\n\n请重新翻译，并严格满足：\n1) 译文非空行数必须与原文完全一致；\n2) 每行保留相同 Markdown 前缀（#, -, 1., >）；\n3) 保留链接 URL 与代码；\n4) 不新增、不删减信息；\n5) 只输出翻译后的 Markdown，不要解释。",
+        repo = repo_full_name,
+        title = original_title,
+        current = current,
+        total = total,
+        chunk = chunk,
+        translated = translated,
+    );
+    let retry = ai::chat_completion(
+        state,
+        "你是一个严谨的技术文档翻译助手，负责把 GitHub Release notes 翻译成中文并保持 Markdown 结构。",
+        &retry_prompt,
+        budget.max_output_tokens,
+    )
+    .await
+    .map_err(ai_call_api_error)?;
+    let retry = normalize_markdown_translation_output(chunk, retry);
+    if !markdown_structure_preserved(chunk, &retry, RELEASE_DETAIL_CHUNK_STRUCTURE_CHECK) {
+        return Err(ApiError::internal(
+            "release detail translation failed to preserve markdown structure",
+        ));
+    }
+    if !translation_links_preserved(chunk, &retry) {
+        return Err(ApiError::internal(
+            "release detail translation changed or dropped source links",
+        ));
+    }
+    Ok(retry)
+}
+
+pub(super) fn build_release_detail_batch_prompt(
+    repo_full_name: &str,
+    original_title: &str,
+    chunks: &[(usize, String)],
+    total: usize,
+) -> String {
+    let mut prompt = format!(
+        "Repo: {repo}\nTitle: {title}\nTotal chunks: {total}\n\n请把下面多个 Markdown chunk 翻译为中文，输出严格 JSON（不要 markdown code block）：\n\
+{{\"items\":[{{\"chunk_index\":1,\"summary_md\":\"...\"}}]}}\n\
+要求：\n\
+1) chunk_index 必须与输入一致；\n\
+2) 每个 chunk 的 Markdown 结构必须保持；\n\
+3) 保留 URL 与代码，不新增信息。\n",
+        repo = repo_full_name,
+        title = original_title,
+        total = total
+    );
+
+    for (chunk_index, chunk) in chunks {
+        prompt.push_str("\n---\n");
+        prompt.push_str(&format!("chunk_index: {}\n", chunk_index));
+        prompt.push_str("chunk_markdown:\n");
+        prompt.push_str(chunk);
+        prompt.push('\n');
+    }
+    prompt
+}
+
+/// Identifies the persisted chunk results of one release detail translation.
+#[derive(Debug, Clone, Copy)]
+pub(super) struct ReleaseDetailChunkProgress<'a> {
+    pub(super) user_id: &'a str,
+    pub(super) entity_id: &'a str,
+    pub(super) source_hash: &'a str,
+}
+
+/// Drops chunk results left over from an older version of the release body.
+pub(super) async fn delete_stale_release_detail_chunks(
+    state: &AppState,
+    progress: ReleaseDetailChunkProgress<'_>,
+) -> Result<(), ApiError> {
+    sqlx::query(
+        r#"
+        DELETE FROM ai_translation_chunks
+        WHERE user_id = ? AND entity_id = ? AND source_hash <> ?
+        "#,
+    )
+    .bind(progress.user_id)
+    .bind(progress.entity_id)
+    .bind(progress.source_hash)
+    .execute(&state.pool)
+    .await
+    .map_err(ApiError::internal)?;
+    Ok(())
+}
+
+pub(super) async fn clear_release_detail_chunks(
+    state: &AppState,
+    progress: ReleaseDetailChunkProgress<'_>,
+) -> Result<(), ApiError> {
+    sqlx::query(r#"DELETE FROM ai_translation_chunks WHERE user_id = ? AND entity_id = ?"#)
+        .bind(progress.user_id)
+        .bind(progress.entity_id)
+        .execute(&state.pool)
+        .await
+        .map_err(ApiError::internal)?;
+    Ok(())
+}
+
+/// Loads finished chunks for the current source hash. A stored chunk only
+/// counts when it was produced from the same chunk text and still keeps the
+/// source Markdown structure; anything else is translated again.
+pub(super) async fn load_release_detail_chunks(
+    state: &AppState,
+    progress: ReleaseDetailChunkProgress<'_>,
+    chunks: &[String],
+) -> Result<Vec<Option<String>>, ApiError> {
+    let rows = sqlx::query_as::<_, (i64, String, String)>(
+        r#"
+        SELECT chunk_index, chunk_hash, translated
+        FROM ai_translation_chunks
+        WHERE user_id = ? AND entity_id = ? AND source_hash = ?
+        "#,
+    )
+    .bind(progress.user_id)
+    .bind(progress.entity_id)
+    .bind(progress.source_hash)
+    .fetch_all(&state.pool)
+    .await
+    .map_err(ApiError::internal)?;
+
+    let mut done = vec![None; chunks.len()];
+    for (chunk_index, chunk_hash, translated) in rows {
+        let Ok(idx) = usize::try_from(chunk_index) else {
+            continue;
+        };
+        let Some(source) = chunks.get(idx) else {
+            continue;
+        };
+        if chunk_hash == ai::sha256_hex(source)
+            && markdown_structure_preserved(
+                source,
+                &translated,
+                RELEASE_DETAIL_CHUNK_STRUCTURE_CHECK,
+            )
+        {
+            done[idx] = Some(translated);
+        }
+    }
+    Ok(done)
+}
+
+pub(super) async fn store_release_detail_chunk(
+    state: &AppState,
+    progress: ReleaseDetailChunkProgress<'_>,
+    chunk_index: usize,
+    source: &str,
+    translated: &str,
+) -> Result<(), ApiError> {
+    let now = chrono::Utc::now().to_rfc3339();
+    sqlx::query(
+        r#"
+        INSERT INTO ai_translation_chunks (
+          user_id, entity_id, source_hash, chunk_index, chunk_hash, translated, created_at, updated_at
+        )
+        VALUES (?, ?, ?, ?, ?, ?, ?, ?)
+        ON CONFLICT(user_id, entity_id, source_hash, chunk_index) DO UPDATE SET
+          chunk_hash = excluded.chunk_hash,
+          translated = excluded.translated,
+          updated_at = excluded.updated_at
+        "#,
+    )
+    .bind(progress.user_id)
+    .bind(progress.entity_id)
+    .bind(progress.source_hash)
+    .bind(chunk_index as i64)
+    .bind(ai::sha256_hex(source))
+    .bind(translated)
+    .bind(now.as_str())
+    .bind(now.as_str())
+    .execute(&state.pool)
+    .await
+    .map_err(ApiError::internal)?;
+    Ok(())
+}
+
+/// Emits `chunks_done/chunks_total` when running as a release detail task.
+pub(super) async fn report_release_detail_chunk_progress(
+    state: &AppState,
+    progress: ReleaseDetailChunkProgress<'_>,
+    chunks_done: usize,
+    chunks_total: usize,
+) {
+    if ai::current_parent_task_type().as_deref() != Some(jobs::TASK_TRANSLATE_RELEASE_DETAIL) {
+        return;
+    }
+    let Some(task_id) = ai::current_parent_task_id() else {
+        return;
+    };
+    if let Err(err) = jobs::append_task_event(
+        state,
+        task_id.as_str(),
+        "task.progress",
+        json!({
+            "task_id": task_id.as_str(),
+            "stage": "chunk",
+            "release_id": progress.entity_id,
+            "chunks_done": chunks_done,
+            "chunks_total": chunks_total,
+        }),
+    )
+    .await
+    {
+        tracing::warn!(?err, "release detail chunk progress event failed");
+    }
+}
+
+pub(super) async fn translate_release_detail_chunks_batched(
+    state: &AppState,
+    budget: ReleaseDetailChunkBudget,
+    progress: ReleaseDetailChunkProgress<'_>,
+    repo_full_name: &str,
+    original_title: &str,
+    chunks: &[String],
+) -> Result<Vec<String>, ApiError> {
+    if chunks.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    delete_stale_release_detail_chunks(state, progress).await?;
+    let mut done = load_release_detail_chunks(state, progress, chunks).await?;
+    let pending = (0..chunks.len())
+        .filter(|idx| done[*idx].is_none())
+        .collect::<Vec<_>>();
+    let mut chunks_done = chunks.len() - pending.len();
+    report_release_detail_chunk_progress(state, progress, chunks_done, chunks.len()).await;
+
+    const CHUNK_BATCH_OVERHEAD_TOKENS: u32 = 320;
+    let input_budget = budget.input_budget;
+    let estimated = pending
+        .iter()
+        .map(|idx| ai::estimate_text_tokens(&chunks[*idx]).saturating_add(48))
+        .collect::<Vec<_>>();
+    let grouped = ai::pack_batch_indices(&estimated, input_budget, CHUNK_BATCH_OVERHEAD_TOKENS);
+    let split_count = grouped.len().saturating_sub(1);
+    let saved_calls = pending.len().saturating_sub(grouped.len());
+    let estimated_tokens = estimated.iter().copied().sum::<u32>();
+    tracing::info!(
+        batch_size = pending.len(),
+        resumed_chunks = chunks_done,
+        estimated_tokens,
+        split_count,
+        saved_calls,
+        fallback_source = budget.fallback_source,
+        input_budget = budget.input_budget,
+        model_input_limit = budget.model_input_limit,
+        max_output_tokens = budget.max_output_tokens,
+        "release detail chunk batch plan"
+    );
+
+    for batch_positions in grouped {
+        if jobs::current_task_cancel_requested(state).await {
+            return Err(ApiError::task_canceled());
+        }
+        let batch_indices = batch_positions
+            .iter()
+            .map(|position| pending[*position])
+            .collect::<Vec<_>>();
+        let batch_chunks = batch_indices
+            .iter()
+            .map(|idx| (idx + 1, chunks[*idx].clone()))
+            .collect::<Vec<_>>();
+
+        let prompt = build_release_detail_batch_prompt(
+            repo_full_name,
+            original_title,
+            &batch_chunks,
+            chunks.len(),
+        );
+        let raw = ai::chat_completion(
+            state,
+            "你是一个严谨的技术文档翻译助手，负责把 GitHub Release notes chunk 批量翻译成中文并保持 Markdown 结构。",
+            &prompt,
+            budget.max_output_tokens,
+        )
+        .await;
+
+        let mut parsed = HashMap::<usize, String>::new();
+        match raw {
+            Ok(raw) => {
+                if let Some(payload) = parse_batch_release_detail_translation_payload(&raw) {
+                    for item in payload.items {
+                        if item.chunk_index == 0 || !batch_indices.contains(&(item.chunk_index - 1))
+                        {
+                            continue;
+                        }
+                        let source = &chunks[item.chunk_index - 1];
+                        parsed.insert(
+                            item.chunk_index - 1,
+                            normalize_markdown_translation_output(source, item.summary_md),
+                        );
+                    }
+                } else {
+                    tracing::warn!(
+                        "release detail chunk batch response parse failed; fallback to single chunks"
+                    );
+                }
+            }
+            Err(err) => {
+                if ai_error_is_non_retryable(&err) {
+                    tracing::warn!(
+                        ?err,
+                        "release detail chunk batch error is non-retryable; skipping single fallback"
+                    );
+                    return Err(ApiError::internal(
+                        "release detail translation unavailable: upstream model/channel rejected request",
+                    ));
+                }
+                tracing::warn!(
+                    ?err,
+                    "release detail chunk batch failed; fallback to single"
+                );
+            }
+        }
+
+        for idx in batch_indices {
+            let source = &chunks[idx];
+            let mut out = parsed.remove(&idx);
+
+            if out.as_deref().is_none_or(|candidate| {
+                !markdown_structure_preserved(
+                    source,
+                    candidate,
+                    RELEASE_DETAIL_CHUNK_STRUCTURE_CHECK,
+                ) || !translation_links_preserved(source, candidate)
+            }) {
+                out = Some(
+                    translate_release_detail_chunk(
+                        state,
+                        budget,
+                        repo_full_name,
+                        original_title,
+                        source,
+                        idx + 1,
+                        chunks.len(),
+                    )
+                    .await?,
+                );
+            }
+
+            let out = out.unwrap_or_default();
+            store_release_detail_chunk(state, progress, idx, source, &out).await?;
+            done[idx] = Some(out);
+            chunks_done += 1;
+            report_release_detail_chunk_progress(state, progress, chunks_done, chunks.len()).await;
+        }
+    }
+
+    Ok(done.into_iter().map(Option::unwrap_or_default).collect())
+}
+
+/// Output cap for summary mode; ten bullets fit comfortably.
+pub(super) const RELEASE_DETAIL_SUMMARY_MAX_OUTPUT_TOKENS: u32 = 800;
+
+/// Summary mode sends the body in one prompt, truncated to whatever input
+/// budget the model leaves after the prompt and the summary itself.
+pub(super) async fn summarize_release_detail_body(
+    state: &AppState,
+    repo_full_name: &str,
+    original_title: &str,
+    original_body: &str,
+) -> Result<String, ApiError> {
+    let budget_info = ai::compute_input_budget_with_source(state, 0).await;
+    let input_budget = budget_info
+        .input_budget
+        .saturating_sub(RELEASE_DETAIL_CHUNK_PROMPT_OVERHEAD_TOKENS)
+        .saturating_sub(RELEASE_DETAIL_SUMMARY_MAX_OUTPUT_TOKENS)
+        .max(1);
+    let max_chars = usize::try_from(input_budget.saturating_mul(4)).unwrap_or(usize::MAX);
+    let body = truncate_chars(original_body, max_chars);
+    let prompt = format!(
+        "Repo: {repo}\nTitle: {title}\n\nRelease notes (Markdown):\n{body}\n\n请用中文把这份 GitHub Release notes 浓缩为不超过 10 条的 Markdown 列表，要求：\n1) 优先保留破坏性变更、新功能与重要修复；\n2) 保留代码标识符原样；\n3) 不新增事实；\n4) 只输出列表，不要解释。",
+        repo = repo_full_name,
+        title = original_title,
+        body = body,
+    );
+    let summary = ai::chat_completion(
+        state,
+        "你是一个严谨的技术文档助手，负责把 GitHub Release notes 浓缩为简短的中文要点。",
+        &prompt,
+        RELEASE_DETAIL_SUMMARY_MAX_OUTPUT_TOKENS,
+    )
+    .await
+    .map_err(ai_call_api_error)?;
+    Ok(strip_markdown_code_fence(&summary).trim().to_owned())
+}
+
+pub(super) async fn translate_release_detail_internal(
+    state: &AppState,
+    user_id: &str,
+    release_id: i64,
+    mode: ReleaseDetailTranslationMode,
+) -> Result<TranslateResponse, ApiError> {
+    if state.config.ai.is_none() {
+        return Ok(TranslateResponse {
+            lang: "zh-CN".to_owned(),
+            status: "disabled".to_owned(),
+            title: None,
+            summary: None,
+        });
+    }
+
+    #[derive(Debug, sqlx::FromRow)]
+    struct ReleaseDetailSourceRow {
+        repo_id: i64,
+        starred_repo_id: Option<i64>,
+        html_url: String,
+        tag_name: String,
+        name: Option<String>,
+        body: Option<String>,
+    }
+
+    let row = sqlx::query_as::<_, ReleaseDetailSourceRow>(
+        r#"
+        SELECT r.repo_id, sr.repo_id AS starred_repo_id, r.html_url, r.tag_name, r.name, r.body
+        FROM repo_releases r
+        LEFT JOIN user_release_visible_repos sr
+          ON sr.user_id = ? AND sr.repo_id = r.repo_id
+        WHERE r.release_id = ?
+        LIMIT 1
+        "#,
+    )
+    .bind(user_id)
+    .bind(release_id)
+    .fetch_optional(&state.pool)
+    .await
+    .map_err(ApiError::internal)?;
+
+    let Some(row) = row else {
+        return Err(ApiError::new(
+            StatusCode::NOT_FOUND,
+            codes::NOT_FOUND,
+            "release not found",
+        ));
+    };
+
+    let release_locator = parse_release_locator_from_github_release_url(&row.html_url);
+    if row.starred_repo_id.is_none()
+        && !user_has_brief_access_to_release(state, user_id, release_id, release_locator.as_ref())
+            .await?
+    {
+        return Err(ApiError::new(
+            StatusCode::NOT_FOUND,
+            codes::NOT_FOUND,
+            "release not found",
+        ));
+    }
+
+    if repo_translation_opted_out(&state.pool, user_id, row.repo_id)
+        .await
+        .map_err(ApiError::internal)?
+    {
+        return Ok(TranslateResponse {
+            lang: "zh-CN".to_owned(),
+            status: "opted_out".to_owned(),
+            title: None,
+            summary: None,
+        });
+    }
+
+    let original_title = row
+        .name
+        .as_deref()
+        .map(str::trim)
+        .filter(|s| !s.is_empty())
+        .unwrap_or(&row.tag_name)
+        .to_owned();
+    let original_body = row.body.unwrap_or_default();
+    let repo_full_name = resolve_release_full_name(&row.html_url, row.repo_id);
+
+    let source_hash = release_detail_source_hash(&repo_full_name, &original_title, &original_body);
+    let entity_id = release_id.to_string();
+    let requested_at = chrono::Utc::now().to_rfc3339();
+
+    #[derive(Debug, sqlx::FromRow)]
+    struct TranslationRow {
+        source_hash: String,
+        status: String,
+        title: Option<String>,
+        summary: Option<String>,
+        error_text: Option<String>,
+    }
+    let cached = sqlx::query_as::<_, TranslationRow>(
+        r#"
+        SELECT source_hash, status, title, summary, error_text
+        FROM ai_translations
+        WHERE user_id = ?
+          AND entity_type = ?
+          AND entity_id = ?
+          AND lang = 'zh-CN'
+          AND status IN ('ready', 'disabled', 'missing', 'error')
+        LIMIT 1
+        "#,
+    )
+    .bind(user_id)
+    .bind(mode.entity_type())
+    .bind(&entity_id)
+    .fetch_optional(&state.pool)
+    .await
+    .map_err(ApiError::internal)?;
+
+    if let Some(cached) = cached
+        && cached.source_hash == source_hash
+    {
+        match cached.status.as_str() {
+            "ready"
+                if release_detail_translation_ready(
+                    Some(original_body.as_str()),
+                    cached.summary.as_deref(),
+                ) =>
+            {
+                return Ok(TranslateResponse {
+                    lang: "zh-CN".to_owned(),
+                    status: "ready".to_owned(),
+                    title: cached.title,
+                    summary: cached.summary,
+                });
+            }
+            "disabled" => {
+                return Ok(TranslateResponse {
+                    lang: "zh-CN".to_owned(),
+                    status: cached.status,
+                    title: None,
+                    summary: None,
+                });
+            }
+            "missing" => {
+                return Err(ApiError::new(
+                    StatusCode::NOT_FOUND,
+                    codes::NOT_FOUND,
+                    cached
+                        .error_text
+                        .unwrap_or_else(|| "release not found".to_owned()),
+                ));
+            }
+            "error" => {
+                return Err(ApiError::internal(
+                    cached
+                        .error_text
+                        .unwrap_or_else(|| "release detail translation failed".to_owned()),
+                ));
+            }
+            _ => {}
+        }
+    }
+
+    // Concurrent requests for the same content (e.g. two tabs) share one
+    // translation instead of each paying for it.
+    let flight_key = ai::TranslationFlightKey {
+        user_id: user_id.to_owned(),
+        entity_type: mode.entity_type(),
+        entity_id: entity_id.clone(),
+        lang: "zh-CN".to_owned(),
+        source_hash: source_hash.clone(),
+    };
+    state
+        .translation_flights
+        .run(flight_key, || async {
+            mark_translation_requested(
+                state,
+                user_id,
+                requested_at.as_str(),
+                TranslationUpsert {
+                    entity_type: mode.entity_type(),
+                    entity_id: &entity_id,
+                    lang: "zh-CN",
+                    source_hash: &source_hash,
+                    title: None,
+                    summary: None,
+                },
+            )
+            .await?;
+
+            let translated_title = ai::chat_completion(
+                state,
+                "你是一个翻译助手，只把 GitHub Release 标题翻译成自然中文。输出纯文本，不要解释。",
+                &format!(
+                    "Repo: {}\nOriginal title: {}\n\n输出中文标题：",
+                    repo_full_name, original_title
+                ),
+                120,
+            )
+            .await
+            .ok()
+            .and_then(|s| {
+                let title = s.trim();
+                if title.is_empty() {
+                    None
+                } else {
+                    Some(title.to_owned())
+                }
+            });
+
+            let chunk_progress = ReleaseDetailChunkProgress {
+                user_id,
+                entity_id: &entity_id,
+                source_hash: &source_hash,
+            };
+            let body_markdown = if original_body.trim().is_empty() {
+                String::new()
+            } else if mode == ReleaseDetailTranslationMode::Summary {
+                summarize_release_detail_body(
+                    state,
+                    &repo_full_name,
+                    &original_title,
+                    &original_body,
+                )
+                .await?
+            } else {
+                let chunk_budget = release_detail_chunk_budget(state).await;
+                tracing::info!(
+                    chunk_char_budget = chunk_budget.max_chars,
+                    chunk_input_budget = chunk_budget.input_budget,
+                    chunk_output_budget = chunk_budget.max_output_tokens,
+                    fallback_source = chunk_budget.fallback_source,
+                    model_input_limit = chunk_budget.model_input_limit,
+                    "release detail chunk budget resolved"
+                );
+                let chunks = split_markdown_chunks(&original_body, chunk_budget.max_chars);
+                let translated_chunks = translate_release_detail_chunks_batched(
+                    state,
+                    chunk_budget,
+                    chunk_progress,
+                    &repo_full_name,
+                    &original_title,
+                    &chunks,
+                )
+                .await?;
+                translated_chunks.join("")
+            };
+            let translated_summary = (!body_markdown.trim().is_empty()).then_some(body_markdown);
+            if !release_detail_translation_ready(
+                Some(original_body.as_str()),
+                translated_summary.as_deref(),
+            ) {
+                return Err(ApiError::internal(
+                    "release detail translation produced empty summary",
+                ));
+            }
+
+            upsert_translation(
+                state,
+                user_id,
+                requested_at.as_str(),
+                TranslationUpsert {
+                    entity_type: mode.entity_type(),
+                    entity_id: &entity_id,
+                    lang: "zh-CN",
+                    source_hash: &source_hash,
+                    title: translated_title.as_deref(),
+                    summary: translated_summary.as_deref(),
+                },
+            )
+            .await?;
+            if mode == ReleaseDetailTranslationMode::Full {
+                clear_release_detail_chunks(state, chunk_progress).await?;
+            }
+
+            Ok(TranslateResponse {
+                lang: "zh-CN".to_owned(),
+                status: "ready".to_owned(),
+                title: translated_title,
+                summary: translated_summary,
+            })
+        })
+        .await
+}
+
+#[allow(dead_code)]
+pub async fn translate_release_detail(
+    State(state): State<Arc<AppState>>,
+    AuthedUser(user_id): AuthedUser,
+    Query(mode_query): Query<ReturnModeQuery>,
+    ApiJson(req): ApiJson<TranslateReleaseDetailRequest>,
+) -> Result<Response, ApiError> {
+    let release_id = req.release_id.trim().to_owned();
+    let translation_mode = ReleaseDetailTranslationMode::parse(req.mode.as_deref())?;
+    let mode = ReturnMode::from_query(&mode_query)?;
+
+    if matches!(mode, ReturnMode::Sync) {
+        let translated = run_with_api_llm_context(
+            "api.translate_release_detail.sync",
+            Some(user_id.clone()),
+            translate_release_detail_for_user(
+                state.as_ref(),
+                user_id.as_str(),
+                release_id.as_str(),
+                translation_mode,
+            ),
+        )
+        .await?;
+        return Ok(Json(translated).into_response());
+    }
+
+    enqueue_or_stream_task(
+        state,
+        mode,
+        jobs::NewTask {
+            task_type: jobs::TASK_TRANSLATE_RELEASE_DETAIL.to_owned(),
+            payload: json!({
+                "user_id": user_id.clone(),
+                "release_id": release_id,
+                "mode": translation_mode.as_str(),
+            }),
+            source: "api.translate_release_detail".to_owned(),
+            requested_by: Some(user_id.clone()),
+            parent_task_id: None,
+            priority: jobs::TaskPriority::High,
+        },
+    )
+    .await
+}
+
+pub async fn translate_release_detail_for_user(
+    state: &AppState,
+    user_id: &str,
+    release_id_raw: &str,
+    mode: ReleaseDetailTranslationMode,
+) -> Result<TranslateResponse, ApiError> {
+    let release_id = parse_release_id_param(release_id_raw)?;
+    let mut items =
+        translate_release_detail_batch_internal(state, user_id, &[release_id], mode).await?;
+    let Some(item) = items.pop() else {
+        return Err(ApiError::internal("missing translation result"));
+    };
+    translate_response_from_batch_item(item)
+}
+pub(super) async fn translate_release_detail_batch_internal(
+    state: &AppState,
+    user_id: &str,
+    release_ids: &[i64],
+    mode: ReleaseDetailTranslationMode,
+) -> Result<Vec<TranslateBatchItem>, ApiError> {
+    let mut items = Vec::with_capacity(release_ids.len());
+    for release_id in release_ids {
+        match translate_release_detail_internal(state, user_id, *release_id, mode).await {
+            Ok(translated) => items.push(TranslateBatchItem {
+                id: release_id.to_string(),
+                lang: translated.lang,
+                status: translated.status,
+                title: translated.title,
+                summary: translated.summary,
+                error: None,
+            }),
+            Err(err) if err.code() == codes::NOT_FOUND => items.push(TranslateBatchItem {
+                id: release_id.to_string(),
+                lang: "zh-CN".to_owned(),
+                status: "missing".to_owned(),
+                title: None,
+                summary: None,
+                error: Some("release not found".to_owned()),
+            }),
+            Err(err) => {
+                let error_text = err.to_string();
+                tracing::warn!(
+                    release_id,
+                    error_code = err.code(),
+                    "release detail translation failed inside batch"
+                );
+                items.push(TranslateBatchItem {
+                    id: release_id.to_string(),
+                    lang: "zh-CN".to_owned(),
+                    status: "error".to_owned(),
+                    title: None,
+                    summary: None,
+                    error: Some(error_text),
+                });
+            }
+        }
+    }
+    Ok(items)
+}
+
+#[allow(dead_code)]
+pub async fn translate_release_detail_batch(
+    State(state): State<Arc<AppState>>,
+    AuthedUser(user_id): AuthedUser,
+    ApiJson(req): ApiJson<TranslateReleaseDetailBatchRequest>,
+) -> Result<Json<TranslateBatchResponse>, ApiError> {
+    let release_ids = parse_unique_release_ids(&req.release_ids, 20)?;
+    let mode = ReleaseDetailTranslationMode::parse(req.mode.as_deref())?;
+    let items = run_with_api_llm_context(
+        "api.translate_release_detail_batch",
+        Some(user_id.clone()),
+        translate_release_detail_batch_internal(
+            state.as_ref(),
+            user_id.as_str(),
+            &release_ids,
+            mode,
+        ),
+    )
+    .await?;
+    Ok(Json(TranslateBatchResponse {
+        items: translate_batch_items_for_public(items),
+    }))
+}
+
+#[derive(Debug, Clone)]
+pub(super) struct NotificationBatchCandidate {
+    pub(super) thread_id: String,
+    pub(super) repo_full_name: String,
+    pub(super) subject_title: String,
+    pub(super) reason: String,
+    pub(super) subject_type: String,
+    pub(super) source_hash: String,
+}
+
+pub(super) fn build_notification_batch_prompt(items: &[NotificationBatchCandidate]) -> String {
+    let mut prompt = String::from(
+        "你会收到多条 GitHub Inbox 通知，请逐条翻译并给出简短建议。\n\
+输出严格 JSON（不要 markdown code block）：\n\
+{\"items\":[{\"thread_id\":\"123\",\"title_zh\":\"...\",\"summary_md\":\"- ...\"}]}\n\
+要求：\n\
+1) 每个输入 thread_id 必须在输出里出现；\n\
+2) summary_md 1-3 条；\n\
+3) 不输出 URL，不新增事实。\n",
+    );
+    for item in items {
+        prompt.push_str("\n---\n");
+        prompt.push_str(&format!("thread_id: {}\n", item.thread_id));
+        prompt.push_str(&format!("repo: {}\n", item.repo_full_name));
+        prompt.push_str(&format!("title: {}\n", item.subject_title));
+        prompt.push_str(&format!("reason: {}\n", item.reason));
+        prompt.push_str(&format!("type: {}\n", item.subject_type));
+    }
+    prompt
+}
+
+pub(super) async fn translate_notification_single_candidate_with_ai(
+    state: &AppState,
+    item: &NotificationBatchCandidate,
+) -> Option<(Option<String>, Option<String>)> {
+    let prompt = format!(
+        "Repo: {repo}\nOriginal title: {title}\nReason: {reason}\nType: {subject_type}\n\n请把这条 Inbox 通知翻译/解释为中文，输出严格 JSON（不要 markdown code block）：\n{{\"title_zh\": \"...\", \"summary_md\": \"- ...\"}}\n\n要求：summary_md 1-3 条；给出建议动作；不包含任何 URL。",
+        repo = item.repo_full_name,
+        title = item.subject_title,
+        reason = item.reason,
+        subject_type = item.subject_type,
+    );
+
+    let raw = ai::chat_completion(
+        state,
+        "你是一个助理，负责把 GitHub Notifications 条目转写为中文标题与简短建议（Markdown）。不要包含任何 URL。",
+        &prompt,
+        500,
+    )
+    .await
+    .ok()?;
+    let parsed = parse_translation_json(&raw);
+    let title = parsed
+        .as_ref()
+        .and_then(|p| p.title_zh.as_deref())
+        .map(str::trim)
+        .filter(|s| !s.is_empty())
+        .map(str::to_owned);
+    let summary = parsed
+        .as_ref()
+        .and_then(|p| p.summary_md.as_deref())
+        .map(str::trim)
+        .filter(|s| !s.is_empty())
+        .map(str::to_owned)
+        .or_else(|| {
+            let s = raw.trim();
+            if s.is_empty() {
+                None
+            } else {
+                Some(s.to_owned())
+            }
+        });
+    if title.is_none() && summary.is_none() {
+        None
+    } else {
+        Some((title, summary))
+    }
+}
+
+pub(super) async fn translate_notification_batch_group(
+    state: &AppState,
+    batch: Vec<NotificationBatchCandidate>,
+    concurrency: usize,
+    abort: &AtomicBool,
+) -> HashMap<String, (Option<String>, Option<String>)> {
+    if jobs::current_task_cancel_requested(state).await {
+        abort.store(true, Ordering::Release);
+        return HashMap::new();
+    }
+    let prompt = build_notification_batch_prompt(&batch);
+    let raw = ai::chat_completion(
+        state,
+        "你是一个批量翻译助手，负责把 GitHub Notifications 条目转写为中文标题与建议。",
+        &prompt,
+        NOTIFICATION_BATCH_MAX_TOKENS,
+    )
+    .await;
+
+    let mut translated = HashMap::new();
+    match raw {
+        Ok(raw) => {
+            if let Some(payload) = parse_batch_notification_translation_payload(&raw) {
+                for item in payload.items {
+                    if !batch
+                        .iter()
+                        .any(|candidate| candidate.thread_id == item.thread_id)
+                    {
+                        continue;
+                    }
+                    let (title, summary) =
+                        normalize_translation_fields(item.title_zh, item.summary_md);
+                    if title.is_some() || summary.is_some() {
+                        translated.insert(item.thread_id, (title, summary));
+                    }
+                }
+            } else {
+                tracing::warn!(
+                    "notification batch translation response parse failed; fallback to single"
+                );
+            }
+        }
+        Err(err) => {
+            if ai_error_is_non_retryable(&err) {
+                abort.store(true, Ordering::Release);
+                tracing::warn!(
+                    ?err,
+                    "notification batch translation upstream error is non-retryable; skipping single fallback"
+                );
+            } else {
+                tracing::warn!(
+                    ?err,
+                    "notification batch translation failed; fallback to single"
+                );
+            }
+        }
+    }
+
+    if abort.load(Ordering::Acquire) {
+        return translated;
+    }
+    let tasks = batch
+        .iter()
+        .filter(|item| !translated.contains_key(&item.thread_id))
+        .map(|item| async move {
+            translate_notification_single_candidate_with_ai(state, item)
+                .await
+                .map(|res| (item.thread_id.clone(), res))
+        })
+        .collect::<Vec<_>>();
+    let singles = futures::stream::iter(tasks)
+        .buffer_unordered(concurrency.max(1))
+        .collect::<Vec<_>>()
+        .await;
+    translated.extend(singles.into_iter().flatten());
+    translated
+}
+
+pub(super) async fn translate_notification_candidates_with_ai(
+    state: &AppState,
+    pending: &[NotificationBatchCandidate],
+) -> HashMap<String, (Option<String>, Option<String>)> {
+    if pending.is_empty() {
+        return HashMap::new();
+    }
+
+    let budget_info =
+        ai::compute_input_budget_with_source(state, NOTIFICATION_BATCH_MAX_TOKENS).await;
+    let budget = budget_info.input_budget;
+    let estimated = pending
+        .iter()
+        .map(|item| {
+            ai::estimate_text_tokens(&item.repo_full_name)
+                .saturating_add(ai::estimate_text_tokens(&item.subject_title))
+                .saturating_add(ai::estimate_text_tokens(&item.reason))
+                .saturating_add(ai::estimate_text_tokens(&item.subject_type))
+                .saturating_add(32)
+        })
+        .collect::<Vec<_>>();
+    let groups = ai::pack_batch_indices(&estimated, budget, NOTIFICATION_BATCH_OVERHEAD_TOKENS);
+    let split_count = groups.len().saturating_sub(1);
+    let saved_calls = pending.len().saturating_sub(groups.len());
+    let estimated_tokens = estimated.iter().copied().sum::<u32>();
+    tracing::info!(
+        batch_size = pending.len(),
+        estimated_tokens,
+        split_count,
+        saved_calls,
+        fallback_source = budget_info.fallback_source,
+        input_budget = budget_info.input_budget,
+        model_input_limit = budget_info.model_input_limit,
+        "notification translation batch plan"
+    );
+
+    let concurrency = state.config.ai_translate_concurrency;
+    let abort = AtomicBool::new(false);
+    let outcomes = run_translation_batch_groups(groups, concurrency, &abort, |batch_indices| {
+        let batch = batch_indices
+            .iter()
+            .map(|idx| pending[*idx].clone())
+            .collect::<Vec<_>>();
+        translate_notification_batch_group(state, batch, concurrency, &abort)
+    })
+    .await;
+
+    let mut translated = HashMap::new();
+    for outcome in outcomes {
+        translated.extend(outcome);
+    }
+    translated
+}
+
+#[derive(Debug, sqlx::FromRow)]
+pub(super) struct NotificationBatchSourceRow {
+    pub(super) thread_id: String,
+    pub(super) repo_full_name: Option<String>,
+    pub(super) subject_title: Option<String>,
+    pub(super) reason: Option<String>,
+    pub(super) subject_type: Option<String>,
+}
+
+pub(super) fn notification_translation_source_hash(
+    repo_full_name: Option<&str>,
+    subject_title: Option<&str>,
+    reason: Option<&str>,
+    subject_type: Option<&str>,
+) -> String {
+    let source = format!(
+        "kind=notification\nrepo={}\ntitle={}\nreason={}\nsubject_type={}\n",
+        repo_full_name.unwrap_or(""),
+        subject_title.unwrap_or(""),
+        reason.unwrap_or(""),
+        subject_type.unwrap_or(""),
+    );
+    ai::sha256_hex(&source)
+}
+
+pub(super) async fn translate_notifications_batch_internal(
+    state: &AppState,
+    user_id: &str,
+    thread_ids: &[String],
+) -> Result<Vec<TranslateBatchItem>, ApiError> {
+    if state.config.ai.is_none() {
+        return Ok(thread_ids
+            .iter()
+            .map(|thread_id| TranslateBatchItem {
+                id: thread_id.clone(),
+                lang: "zh-CN".to_owned(),
+                status: "disabled".to_owned(),
+                title: None,
+                summary: None,
+                error: None,
+            })
+            .collect());
+    }
+
+    let requested_at = chrono::Utc::now().to_rfc3339();
+    let mut source_query = sqlx::QueryBuilder::<sqlx::Sqlite>::new(
+        r#"
+        SELECT thread_id, repo_full_name, subject_title, reason, subject_type
+        FROM notifications
+        WHERE user_id = "#,
+    );
+    source_query.push_bind(user_id);
+    source_query.push(" AND thread_id IN (");
+    {
+        let mut separated = source_query.separated(", ");
+        for thread_id in thread_ids {
+            separated.push_bind(thread_id);
+        }
+    }
+    source_query.push(")");
+
+    let rows = source_query
+        .build_query_as::<NotificationBatchSourceRow>()
+        .fetch_all(&state.pool)
+        .await
+        .map_err(ApiError::internal)?;
+    let mut rows_by_id = HashMap::new();
+    for row in rows {
+        rows_by_id.insert(row.thread_id.clone(), row);
+    }
+
+    let mut candidates = Vec::new();
+    let mut missing = HashSet::new();
+    for thread_id in thread_ids {
+        let Some(row) = rows_by_id.get(thread_id) else {
+            missing.insert(thread_id.clone());
+            continue;
+        };
+        candidates.push(NotificationBatchCandidate {
+            thread_id: thread_id.clone(),
+            repo_full_name: row
+                .repo_full_name
+                .clone()
+                .unwrap_or_else(|| "(unknown repo)".to_owned()),
+            subject_title: row
+                .subject_title
+                .clone()
+                .unwrap_or_else(|| "(no title)".to_owned()),
+            reason: row.reason.clone().unwrap_or_default(),
+            subject_type: row.subject_type.clone().unwrap_or_default(),
+            source_hash: notification_translation_source_hash(
+                row.repo_full_name.as_deref(),
+                row.subject_title.as_deref(),
+                row.reason.as_deref(),
+                row.subject_type.as_deref(),
+            ),
+        });
+    }
+
+    let mut cache_by_id = HashMap::<String, TranslationCacheRow>::new();
+    if !candidates.is_empty() {
+        let mut cache_query = sqlx::QueryBuilder::<sqlx::Sqlite>::new(
+            r#"
+            SELECT entity_id, source_hash, status, title, summary
+            FROM ai_translations
+            WHERE user_id = "#,
+        );
+        cache_query.push_bind(user_id);
+        cache_query.push(" AND entity_type = 'notification' AND lang = 'zh-CN' AND status IN ('ready', 'disabled', 'missing') AND entity_id IN (");
+        {
+            let mut separated = cache_query.separated(", ");
+            for item in &candidates {
+                separated.push_bind(&item.thread_id);
+            }
+        }
+        cache_query.push(")");
+
+        let cache_rows = cache_query
+            .build_query_as::<TranslationCacheRow>()
+            .fetch_all(&state.pool)
+            .await
+            .map_err(ApiError::internal)?;
+        for row in cache_rows {
+            cache_by_id.insert(row.entity_id.clone(), row);
+        }
+    }
+
+    let mut translated = HashMap::<String, (Option<String>, Option<String>)>::new();
+    let mut terminal = HashMap::<String, String>::new();
+    let mut pending = Vec::new();
+    for item in &candidates {
+        if let Some(cache) = cache_by_id.get(&item.thread_id)
+            && cache.source_hash == item.source_hash
+        {
+            if matches!(cache.status.as_str(), "disabled" | "missing") {
+                terminal.insert(item.thread_id.clone(), cache.status.clone());
+                continue;
+            }
+            if let Some(raw) = cache.summary.as_deref()
+                && looks_like_json_blob(raw)
+                && let Some((t_title, t_summary)) = extract_translation_from_json_blob(raw)
+            {
+                let out_title = t_title.or_else(|| cache.title.clone());
+                let out_summary = t_summary.or_else(|| cache.summary.clone());
+                if out_title.is_some() || out_summary.is_some() {
+                    translated.insert(item.thread_id.clone(), (out_title, out_summary));
+                    continue;
+                }
+            }
+
+            let cache_is_json_blob = cache.summary.as_deref().is_some_and(looks_like_json_blob);
+            if !cache_is_json_blob {
+                translated.insert(
+                    item.thread_id.clone(),
+                    (cache.title.clone(), cache.summary.clone()),
+                );
+                continue;
+            }
+        }
+        if crate::lang_detect::is_already_chinese(
+            item.subject_title.as_str(),
+            state.config.ai_skip_translation_cjk_percent,
+        ) {
+            translated.insert(
+                item.thread_id.clone(),
+                (Some(item.subject_title.clone()), None),
+            );
+            continue;
+        }
+        pending.push(item.clone());
+    }
+
+    for item in &pending {
+        mark_translation_requested(
+            state,
+            user_id,
+            requested_at.as_str(),
+            TranslationUpsert {
+                entity_type: "notification",
+                entity_id: &item.thread_id,
+                lang: "zh-CN",
+                source_hash: &item.source_hash,
+                title: None,
+                summary: None,
+            },
+        )
+        .await?;
+    }
+
+    let pending_translated = translate_notification_candidates_with_ai(state, &pending).await;
+    for (thread_id, value) in pending_translated {
+        translated.insert(thread_id, value);
+    }
+
+    for item in &candidates {
+        if let Some((title, summary)) = translated.get(&item.thread_id) {
+            upsert_translation(
+                state,
+                user_id,
+                requested_at.as_str(),
+                TranslationUpsert {
+                    entity_type: "notification",
+                    entity_id: &item.thread_id,
+                    lang: "zh-CN",
+                    source_hash: &item.source_hash,
+                    title: title.as_deref(),
+                    summary: summary.as_deref(),
+                },
+            )
+            .await?;
+        }
+    }
+
+    let mut out = Vec::new();
+    for thread_id in thread_ids {
+        if missing.contains(thread_id) {
+            out.push(TranslateBatchItem {
+                id: thread_id.clone(),
+                lang: "zh-CN".to_owned(),
+                status: "missing".to_owned(),
+                title: None,
+                summary: None,
+                error: Some("notification not found".to_owned()),
+            });
+            continue;
+        }
+        if let Some(status) = terminal.get(thread_id) {
+            out.push(TranslateBatchItem {
+                id: thread_id.clone(),
+                lang: "zh-CN".to_owned(),
+                status: status.clone(),
+                title: None,
+                summary: None,
+                error: if status == "missing" {
+                    Some("translation result missing".to_owned())
+                } else {
+                    None
+                },
+            });
+            continue;
+        }
+        if let Some((title, summary)) = translated.get(thread_id) {
+            out.push(TranslateBatchItem {
+                id: thread_id.clone(),
+                lang: "zh-CN".to_owned(),
+                status: "ready".to_owned(),
+                title: title.clone(),
+                summary: summary.clone(),
+                error: None,
+            });
+        } else {
+            out.push(TranslateBatchItem {
+                id: thread_id.clone(),
+                lang: "zh-CN".to_owned(),
+                status: "error".to_owned(),
+                title: None,
+                summary: None,
+                error: Some("translation failed".to_owned()),
+            });
+        }
+    }
+
+    Ok(out)
+}
+
+#[allow(dead_code)]
+pub async fn translate_notifications_batch(
+    State(state): State<Arc<AppState>>,
+    AuthedUser(user_id): AuthedUser,
+    ApiJson(req): ApiJson<TranslateNotificationsBatchRequest>,
+) -> Result<Json<TranslateBatchResponse>, ApiError> {
+    let thread_ids = parse_unique_thread_ids(&req.thread_ids, 60)?;
+    let items = run_with_api_llm_context(
+        "api.translate_notifications_batch",
+        Some(user_id.clone()),
+        translate_notifications_batch_internal(state.as_ref(), user_id.as_str(), &thread_ids),
+    )
+    .await?;
+    Ok(Json(TranslateBatchResponse {
+        items: translate_batch_items_for_public(items),
+    }))
+}
+
+#[allow(dead_code)]
+pub async fn translate_notification(
+    State(state): State<Arc<AppState>>,
+    AuthedUser(user_id): AuthedUser,
+    Query(mode_query): Query<ReturnModeQuery>,
+    ApiJson(req): ApiJson<TranslateNotificationRequest>,
+) -> Result<Response, ApiError> {
+    let thread_id = req.thread_id.trim().to_owned();
+    let mode = ReturnMode::from_query(&mode_query)?;
+
+    if matches!(mode, ReturnMode::Sync) {
+        let translated = run_with_api_llm_context(
+            "api.translate_notification.sync",
+            Some(user_id.clone()),
+            translate_notification_for_user(state.as_ref(), user_id, thread_id.as_str()),
+        )
+        .await?;
+        return Ok(Json(translated).into_response());
+    }
+
+    enqueue_or_stream_task(
+        state,
+        mode,
+        jobs::NewTask {
+            task_type: jobs::TASK_TRANSLATE_NOTIFICATION.to_owned(),
+            payload: json!({
+                "user_id": user_id.clone(),
+                "thread_id": thread_id,
+            }),
+            source: "api.translate_notification".to_owned(),
+            requested_by: Some(user_id.clone()),
+            parent_task_id: None,
+            priority: jobs::TaskPriority::High,
+        },
+    )
+    .await
+}
+
+pub async fn translate_notification_for_user(
+    state: &AppState,
+    user_id: String,
+    thread_id_raw: &str,
+) -> Result<TranslateResponse, ApiError> {
+    let thread_id = thread_id_raw.trim().to_owned();
+    if thread_id.is_empty() {
+        return Err(ApiError::bad_request("thread_id is required"));
+    }
+    ensure_id_param_len(&thread_id, "thread_id")?;
+
+    let mut items =
+        translate_notifications_batch_internal(state, &user_id, std::slice::from_ref(&thread_id))
+            .await?;
+    let Some(item) = items.pop() else {
+        return Err(ApiError::internal("missing translation result"));
+    };
+    translate_response_from_batch_item(item)
+}
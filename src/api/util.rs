@@ -0,0 +1,1190 @@
+use super::*;
+
+pub(super) fn has_repo_scope(scopes: &str) -> bool {
+    scopes
+        .split(|c: char| c == ',' || c.is_whitespace())
+        .map(str::trim)
+        .any(|scope| scope == "repo")
+}
+
+pub(super) fn github_reauth_required_error() -> ApiError {
+    ApiError::new(
+        StatusCode::FORBIDDEN,
+        codes::REAUTH_REQUIRED,
+        "repo scope required; re-login via GitHub OAuth",
+    )
+}
+
+pub(super) fn github_private_repo_scope_required_error() -> ApiError {
+    ApiError::new(
+        StatusCode::FORBIDDEN,
+        codes::REAUTH_REQUIRED,
+        "private repository compare requires repo scope; re-login via GitHub OAuth",
+    )
+}
+
+pub(super) fn github_rate_limited_error() -> ApiError {
+    ApiError::new(
+        StatusCode::TOO_MANY_REQUESTS,
+        codes::RATE_LIMITED,
+        "github rate limit exceeded; retry later",
+    )
+}
+
+pub(super) fn github_access_restricted_error() -> ApiError {
+    ApiError::new(
+        StatusCode::FORBIDDEN,
+        codes::FORBIDDEN,
+        "github denied reaction access for this repository (OAuth app restrictions or org policy)",
+    )
+}
+
+pub(super) fn is_rate_limit_message(msg: &str) -> bool {
+    let lower = msg.to_ascii_lowercase();
+    lower.contains("rate limit")
+}
+
+pub(super) fn is_reauth_message(msg: &str) -> bool {
+    let lower = msg.to_ascii_lowercase();
+    lower.contains("bad credentials") || lower.contains("requires authentication")
+}
+
+pub(super) fn is_access_restricted_message(msg: &str) -> bool {
+    let lower = msg.to_ascii_lowercase();
+    lower.contains("resource not accessible by integration")
+        || lower.contains("saml")
+        || lower.contains("oauth app access restrictions")
+}
+
+/// GitHub's answer when the viewer's reaction state differs from what the
+/// mutation assumed, e.g. removing a reaction that was never added.
+pub(super) fn is_reaction_state_conflict_message(msg: &str) -> bool {
+    let lower = msg.to_ascii_lowercase();
+    lower.contains("already reacted") || lower.contains("reaction does not exist")
+}
+
+pub(super) fn reaction_state_conflict_error() -> ApiError {
+    ApiError::new(
+        StatusCode::CONFLICT,
+        codes::REACTION_STATE_CONFLICT,
+        "GitHub reaction state differs from the client's view",
+    )
+}
+
+pub(super) fn github_graphql_http_error(
+    status: reqwest::StatusCode,
+    headers: &reqwest::header::HeaderMap,
+    body: &str,
+) -> Option<ApiError> {
+    if status == reqwest::StatusCode::UNAUTHORIZED {
+        return Some(github_reauth_required_error());
+    }
+    if status != reqwest::StatusCode::FORBIDDEN {
+        return None;
+    }
+
+    let remaining = headers
+        .get("x-ratelimit-remaining")
+        .and_then(|v| v.to_str().ok())
+        .map(str::trim);
+    if remaining == Some("0") || is_rate_limit_message(body) {
+        return Some(github_rate_limited_error());
+    }
+
+    if is_reauth_message(body) {
+        return Some(github_reauth_required_error());
+    }
+    if is_access_restricted_message(body) {
+        return Some(github_access_restricted_error());
+    }
+
+    None
+}
+
+pub(super) fn github_graphql_errors_to_api_error(errors: &[GraphQlError]) -> Option<ApiError> {
+    if errors.iter().any(|e| is_rate_limit_message(&e.message)) {
+        return Some(github_rate_limited_error());
+    }
+    if errors.iter().any(|e| is_reauth_message(&e.message)) {
+        return Some(github_reauth_required_error());
+    }
+    if errors
+        .iter()
+        .any(|e| is_access_restricted_message(&e.message))
+    {
+        return Some(github_access_restricted_error());
+    }
+    None
+}
+
+pub(super) fn truncate_chars<'a>(s: &'a str, max_chars: usize) -> std::borrow::Cow<'a, str> {
+    if s.chars().count() > max_chars {
+        std::borrow::Cow::Owned(s.chars().take(max_chars).collect())
+    } else {
+        std::borrow::Cow::Borrowed(s)
+    }
+}
+
+/// Table rows kept after the header/separator before the rest is elided.
+pub(super) const RELEASE_EXCERPT_TABLE_MAX_ROWS: usize = 5;
+
+/// Removes `<!-- ... -->` spans from a line. The flag reports a comment that
+/// opens on this line and continues on the next ones.
+pub(super) fn strip_html_comments(line: &str) -> (String, bool) {
+    let mut out = String::new();
+    let mut rest = line;
+    while let Some(start) = rest.find("<!--") {
+        out.push_str(&rest[..start]);
+        match rest[start + 4..].find("-->") {
+            Some(end) => rest = &rest[start + 4 + end + 3..],
+            None => return (out, true),
+        }
+    }
+    out.push_str(rest);
+    (out, false)
+}
+
+/// Counts the characters a reader sees: `[text](url)` counts as `text`.
+pub(super) fn excerpt_visible_chars(line: &str) -> usize {
+    let mut count = 0usize;
+    let mut rest = line;
+    while let Some(open) = rest.find("](") {
+        let Some(close) = rest[open + 2..].find(')') else {
+            break;
+        };
+        count += rest[..open].chars().count();
+        rest = &rest[open + 2 + close + 1..];
+    }
+    count + rest.chars().count()
+}
+
+/// Keeps whole lines while they fit in the visible budget, so links and table
+/// rows are never cut in half. A single oversized first line is still clipped.
+/// The flag reports whether anything was left out.
+pub(super) fn truncate_excerpt_lines<S: AsRef<str>>(
+    lines: &[S],
+    max_chars: usize,
+) -> (String, bool) {
+    let mut kept: Vec<&str> = Vec::new();
+    let mut used = 0usize;
+    for line in lines {
+        let line = line.as_ref();
+        let cost = excerpt_visible_chars(line) + usize::from(!kept.is_empty());
+        if used + cost > max_chars {
+            break;
+        }
+        used += cost;
+        kept.push(line);
+    }
+    let truncated = kept.len() < lines.len();
+    while kept.last().is_some_and(|line| line.trim().is_empty()) {
+        kept.pop();
+    }
+    match lines.first() {
+        Some(first) if kept.is_empty() => {
+            let first = first.as_ref();
+            let clipped = truncate_chars(first, max_chars);
+            let truncated = clipped.len() < first.len() || lines.len() > 1;
+            (clipped.into_owned(), truncated)
+        }
+        _ => (kept.join("\n"), truncated),
+    }
+}
+
+/// Cursors travel through clients verbatim, so anything longer is not ours.
+pub(super) const FEED_CURSOR_MAX_LEN: usize = 256;
+pub(super) const FEED_CURSOR_ID_KEY_MAX_LEN: usize = 64;
+pub(super) const INVALID_CURSOR_HINT: &str = "restart paging without a cursor";
+
+/// Keyset position in the feed, bookmark and repo release lists, encoded as
+/// `sort_ts|kind|id_key`. Releases key on their zero-padded id; bookmarks key
+/// on the bookmark's local id.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(super) struct FeedCursor {
+    pub(super) sort_ts: String,
+    pub(super) kind: String,
+    pub(super) kind_rank: i64,
+    pub(super) id_key: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) enum FeedCursorIdKey {
+    Feed,
+    Bookmark,
+}
+
+impl FeedCursor {
+    pub(super) fn from_row(row: &FeedRow) -> Self {
+        Self {
+            sort_ts: row.sort_ts.clone(),
+            kind: row.kind.clone(),
+            kind_rank: feed_kind_rank(&row.kind).unwrap_or(0),
+            id_key: row.id_key.clone(),
+        }
+    }
+
+    pub(super) fn encode(&self) -> String {
+        format!("{}|{}|{}", self.sort_ts, self.kind, self.id_key)
+    }
+
+    pub(super) fn decode(raw: &str) -> Result<Self, ApiError> {
+        Self::decode_with(raw, FeedCursorIdKey::Feed)
+    }
+
+    pub(super) fn decode_bookmark(raw: &str) -> Result<Self, ApiError> {
+        Self::decode_with(raw, FeedCursorIdKey::Bookmark)
+    }
+
+    pub(super) fn decode_with(raw: &str, id_key_kind: FeedCursorIdKey) -> Result<Self, ApiError> {
+        if raw.len() > FEED_CURSOR_MAX_LEN {
+            return Err(invalid_cursor(format!(
+                "cursor exceeds {FEED_CURSOR_MAX_LEN} bytes"
+            )));
+        }
+        let mut parts = raw.splitn(4, '|').map(str::trim);
+        let (Some(sort_ts), Some(kind), Some(id_key), None) =
+            (parts.next(), parts.next(), parts.next(), parts.next())
+        else {
+            return Err(invalid_cursor("expected sort_ts|kind|id_key"));
+        };
+        validate_cursor_sort_ts(sort_ts)?;
+        let kind_rank =
+            feed_kind_rank(kind).ok_or_else(|| invalid_cursor("unknown cursor kind"))?;
+        let id_key_valid = match id_key_kind {
+            FeedCursorIdKey::Bookmark => local_id::is_local_id(id_key),
+            FeedCursorIdKey::Feed if kind == "release" => is_release_id_key(id_key),
+            FeedCursorIdKey::Feed => is_opaque_cursor_id_key(id_key),
+        };
+        if !id_key_valid {
+            return Err(invalid_cursor("malformed cursor id"));
+        }
+
+        Ok(Self {
+            sort_ts: sort_ts.to_owned(),
+            kind: kind.to_owned(),
+            kind_rank,
+            id_key: id_key.to_owned(),
+        })
+    }
+}
+
+pub(super) fn invalid_cursor(reason: impl std::fmt::Display) -> ApiError {
+    ApiError::bad_request(format!("invalid cursor: {reason}; {INVALID_CURSOR_HINT}"))
+}
+
+/// Sort keys are RFC3339 timestamps; older cursors may carry a bare numeric key.
+pub(super) fn validate_cursor_sort_ts(sort_ts: &str) -> Result<(), ApiError> {
+    let legacy_numeric =
+        !sort_ts.is_empty() && sort_ts.len() <= 20 && sort_ts.bytes().all(|b| b.is_ascii_digit());
+    if legacy_numeric || chrono::DateTime::parse_from_rfc3339(sort_ts).is_ok() {
+        Ok(())
+    } else {
+        Err(invalid_cursor("sort_ts is not a timestamp"))
+    }
+}
+
+/// Release rows key on `printf('%020d', release_id)`.
+pub(super) fn is_release_id_key(id_key: &str) -> bool {
+    id_key.len() == 20 && id_key.bytes().all(|b| b.is_ascii_digit())
+}
+
+pub(super) fn is_opaque_cursor_id_key(id_key: &str) -> bool {
+    !id_key.is_empty()
+        && id_key.len() <= FEED_CURSOR_ID_KEY_MAX_LEN
+        && id_key
+            .bytes()
+            .all(|b| b.is_ascii_alphanumeric() || matches!(b, b'-' | b'_' | b'.' | b':'))
+}
+
+#[derive(Debug, Deserialize)]
+pub(super) struct TranslationJson {
+    pub(super) title_zh: Option<String>,
+    pub(super) summary_md: Option<String>,
+    pub(super) body_md: Option<String>,
+}
+
+pub(super) fn strip_markdown_code_fence(raw: &str) -> &str {
+    let trimmed = raw.trim();
+    let Some(rest) = trimmed.strip_prefix("```") else {
+        return trimmed;
+    };
+    let Some((_, body_with_closing)) = rest.split_once('\n') else {
+        return trimmed;
+    };
+    let Some(closing_start) = body_with_closing.rfind("\n```") else {
+        return trimmed;
+    };
+    let closing = &body_with_closing[closing_start + 1..];
+    if closing.trim() != "```" {
+        return trimmed;
+    }
+    &body_with_closing[..closing_start]
+}
+
+pub(super) fn count_leading_newlines(value: &str) -> usize {
+    value.chars().take_while(|ch| *ch == '\n').count()
+}
+
+pub(super) fn count_trailing_newlines(value: &str) -> usize {
+    value.chars().rev().take_while(|ch| *ch == '\n').count()
+}
+
+pub(super) fn preserve_chunk_edge_newlines(source_chunk: &str, translated_chunk: String) -> String {
+    let source_leading = count_leading_newlines(source_chunk);
+    let source_trailing = count_trailing_newlines(source_chunk);
+    let translated_leading = count_leading_newlines(&translated_chunk);
+    let translated_trailing = count_trailing_newlines(&translated_chunk);
+    let core_start = translated_leading.min(translated_chunk.len());
+    let core_end = translated_chunk
+        .len()
+        .saturating_sub(translated_trailing)
+        .max(core_start);
+    let core = &translated_chunk[core_start..core_end];
+
+    format!(
+        "{}{}{}",
+        "\n".repeat(source_leading),
+        core,
+        "\n".repeat(source_trailing)
+    )
+}
+
+pub(super) fn normalize_markdown_translation_output(source: &str, raw: String) -> String {
+    let normalized =
+        if markdown_structure_preserved(source, raw.as_str(), MarkdownStructureCheck::Strict) {
+            raw
+        } else {
+            let stripped = strip_markdown_code_fence(raw.as_str());
+            if stripped != raw.as_str()
+                && markdown_structure_preserved(source, stripped, MarkdownStructureCheck::Relaxed)
+            {
+                stripped.to_owned()
+            } else {
+                raw
+            }
+        };
+    preserve_chunk_edge_newlines(source, normalized)
+}
+
+pub(super) fn extract_json_object_span(raw: &str) -> Option<&str> {
+    let start = raw.find('{')?;
+    let end = raw.rfind('}')?;
+    if end < start {
+        return None;
+    }
+    Some(raw[start..=end].trim())
+}
+
+pub(super) fn extract_json_array_span(raw: &str) -> Option<&str> {
+    let start = raw.find('[')?;
+    let end = raw.rfind(']')?;
+    if end < start {
+        return None;
+    }
+    Some(raw[start..=end].trim())
+}
+
+pub(super) fn parse_json_value_relaxed(raw: &str) -> Option<serde_json::Value> {
+    fn parse_direct(raw: &str) -> Option<serde_json::Value> {
+        serde_json::from_str::<serde_json::Value>(raw)
+            .ok()
+            .or_else(|| {
+                let inner = serde_json::from_str::<String>(raw).ok()?;
+                serde_json::from_str::<serde_json::Value>(&inner).ok()
+            })
+    }
+
+    let trimmed = raw.trim();
+    parse_direct(trimmed)
+        .or_else(|| parse_direct(strip_markdown_code_fence(trimmed)))
+        .or_else(|| extract_json_object_span(trimmed).and_then(parse_direct))
+        .or_else(|| extract_json_array_span(trimmed).and_then(parse_direct))
+}
+
+pub(super) fn value_as_i64(raw: &serde_json::Value) -> Option<i64> {
+    if let Some(v) = raw.as_i64() {
+        return Some(v);
+    }
+    if let Some(v) = raw.as_u64() {
+        return i64::try_from(v).ok();
+    }
+    raw.as_str().and_then(|s| s.trim().parse::<i64>().ok())
+}
+
+pub(super) fn value_as_usize(raw: &serde_json::Value) -> Option<usize> {
+    if let Some(v) = raw.as_u64() {
+        return usize::try_from(v).ok();
+    }
+    if let Some(v) = raw.as_i64() {
+        return usize::try_from(v).ok();
+    }
+    raw.as_str().and_then(|s| s.trim().parse::<usize>().ok())
+}
+
+pub(super) fn value_as_id_string(raw: &serde_json::Value) -> Option<String> {
+    match raw {
+        serde_json::Value::String(v) => {
+            let v = v.trim();
+            if v.is_empty() {
+                None
+            } else {
+                Some(v.to_owned())
+            }
+        }
+        serde_json::Value::Number(_) => Some(raw.to_string()),
+        _ => None,
+    }
+}
+
+pub(super) fn object_field_as_string(
+    obj: &serde_json::Map<String, serde_json::Value>,
+    keys: &[&str],
+) -> Option<String> {
+    for key in keys {
+        let Some(raw) = obj.get(*key) else {
+            continue;
+        };
+        let Some(value) = value_as_id_string(raw) else {
+            continue;
+        };
+        let value = value.trim();
+        if !value.is_empty() {
+            return Some(value.to_owned());
+        }
+    }
+    None
+}
+
+pub(super) fn extract_items_array(value: &serde_json::Value) -> Option<&[serde_json::Value]> {
+    match value {
+        serde_json::Value::Array(items) => Some(items.as_slice()),
+        serde_json::Value::Object(map) => map
+            .get("items")
+            .and_then(serde_json::Value::as_array)
+            .map(Vec::as_slice)
+            .or_else(|| {
+                map.get("data")
+                    .and_then(serde_json::Value::as_array)
+                    .map(Vec::as_slice)
+            })
+            .or_else(|| {
+                map.get("translations")
+                    .and_then(serde_json::Value::as_array)
+                    .map(Vec::as_slice)
+            }),
+        _ => None,
+    }
+}
+
+pub(super) fn parse_translation_json(raw: &str) -> Option<TranslationJson> {
+    fn parse_direct(raw: &str) -> Option<TranslationJson> {
+        let parsed = serde_json::from_str::<TranslationJson>(raw)
+            .ok()
+            .or_else(|| {
+                let inner = serde_json::from_str::<String>(raw).ok()?;
+                serde_json::from_str::<TranslationJson>(&inner).ok()
+            })?;
+
+        Some(TranslationJson {
+            title_zh: parsed.title_zh,
+            summary_md: parsed.summary_md.map(|s| s.replace("\\n", "\n")),
+            body_md: parsed.body_md.map(|s| s.replace("\\n", "\n")),
+        })
+    }
+
+    let trimmed = raw.trim();
+    parse_direct(trimmed)
+        .or_else(|| parse_direct(strip_markdown_code_fence(trimmed)))
+        .or_else(|| extract_json_object_span(trimmed).and_then(parse_direct))
+}
+
+pub(super) fn extract_translation_from_json_blob(
+    raw: &str,
+) -> Option<(Option<String>, Option<String>)> {
+    let parsed = parse_translation_json(raw)?;
+    let title = parsed
+        .title_zh
+        .as_deref()
+        .map(|s| s.trim())
+        .filter(|s| !s.is_empty())
+        .map(|s| s.to_owned());
+    let summary = parsed
+        .summary_md
+        .as_deref()
+        .map(|s| s.trim())
+        .filter(|s| !s.is_empty())
+        .map(|s| s.to_owned());
+
+    if title.is_none() && summary.is_none() {
+        None
+    } else {
+        Some((title, summary))
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub(super) struct BatchReleaseTranslationPayload {
+    pub(super) items: Vec<BatchReleaseTranslationItem>,
+}
+
+#[derive(Debug, Deserialize)]
+pub(super) struct BatchReleaseTranslationItem {
+    pub(super) release_id: i64,
+    pub(super) title_zh: Option<String>,
+    pub(super) summary_md: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub(super) struct BatchNotificationTranslationPayload {
+    pub(super) items: Vec<BatchNotificationTranslationItem>,
+}
+
+#[derive(Debug, Deserialize)]
+pub(super) struct BatchNotificationTranslationItem {
+    pub(super) thread_id: String,
+    pub(super) title_zh: Option<String>,
+    pub(super) summary_md: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub(super) struct BatchReleaseDetailTranslationPayload {
+    pub(super) items: Vec<BatchReleaseDetailTranslationItem>,
+}
+
+#[derive(Debug, Deserialize)]
+pub(super) struct BatchReleaseDetailTranslationItem {
+    pub(super) chunk_index: usize,
+    pub(super) summary_md: String,
+}
+
+#[allow(dead_code)]
+pub(super) fn parse_unique_release_ids(
+    raw_ids: &[String],
+    max_items: usize,
+) -> Result<Vec<i64>, ApiError> {
+    if raw_ids.is_empty() {
+        return Err(ApiError::bad_request("release_ids is required"));
+    }
+    if raw_ids.len() > max_items {
+        return Err(ApiError::bad_request(format!(
+            "release_ids supports at most {max_items} items"
+        )));
+    }
+    let mut out = Vec::new();
+    let mut seen = std::collections::HashSet::new();
+    for raw in raw_ids {
+        let release_id = parse_release_id_param(raw)?;
+        if seen.insert(release_id) {
+            out.push(release_id);
+        }
+    }
+    if out.is_empty() {
+        return Err(ApiError::bad_request("release_ids is required"));
+    }
+    Ok(out)
+}
+
+#[allow(dead_code)]
+pub(super) fn parse_unique_thread_ids(
+    raw_ids: &[String],
+    max_items: usize,
+) -> Result<Vec<String>, ApiError> {
+    if raw_ids.is_empty() {
+        return Err(ApiError::bad_request("thread_ids is required"));
+    }
+    if raw_ids.len() > max_items {
+        return Err(ApiError::bad_request(format!(
+            "thread_ids supports at most {max_items} items"
+        )));
+    }
+    let mut out = Vec::new();
+    let mut seen = std::collections::HashSet::new();
+    for raw in raw_ids {
+        let thread_id = raw.trim();
+        if thread_id.is_empty() {
+            continue;
+        }
+        ensure_id_param_len(thread_id, "thread_id")?;
+        if seen.insert(thread_id.to_owned()) {
+            out.push(thread_id.to_owned());
+        }
+    }
+    if out.is_empty() {
+        return Err(ApiError::bad_request("thread_ids is required"));
+    }
+    Ok(out)
+}
+
+pub(super) fn parse_batch_release_translation_payload(
+    raw: &str,
+) -> Option<BatchReleaseTranslationPayload> {
+    let value = parse_json_value_relaxed(raw)?;
+    let items = extract_items_array(&value)?;
+    let mut out = Vec::new();
+
+    for entry in items {
+        let Some(obj) = entry.as_object() else {
+            continue;
+        };
+        let Some(release_id) = obj
+            .get("release_id")
+            .and_then(value_as_i64)
+            .or_else(|| obj.get("id").and_then(value_as_i64))
+        else {
+            continue;
+        };
+
+        let title_zh = object_field_as_string(obj, &["title_zh", "title", "title_cn"]);
+        let summary_md = object_field_as_string(
+            obj,
+            &["summary_md", "summary", "body_md", "body", "summary_zh"],
+        )
+        .map(|s| s.replace("\\n", "\n"));
+        out.push(BatchReleaseTranslationItem {
+            release_id,
+            title_zh,
+            summary_md,
+        });
+    }
+
+    if out.is_empty() {
+        None
+    } else {
+        Some(BatchReleaseTranslationPayload { items: out })
+    }
+}
+
+pub(super) fn parse_batch_notification_translation_payload(
+    raw: &str,
+) -> Option<BatchNotificationTranslationPayload> {
+    let value = parse_json_value_relaxed(raw)?;
+    let items = extract_items_array(&value)?;
+    let mut out = Vec::new();
+
+    for entry in items {
+        let Some(obj) = entry.as_object() else {
+            continue;
+        };
+        let Some(thread_id) = obj
+            .get("thread_id")
+            .and_then(value_as_id_string)
+            .or_else(|| obj.get("id").and_then(value_as_id_string))
+        else {
+            continue;
+        };
+        let title_zh = object_field_as_string(obj, &["title_zh", "title", "title_cn"]);
+        let summary_md = object_field_as_string(
+            obj,
+            &["summary_md", "summary", "body_md", "body", "summary_zh"],
+        )
+        .map(|s| s.replace("\\n", "\n"));
+        out.push(BatchNotificationTranslationItem {
+            thread_id,
+            title_zh,
+            summary_md,
+        });
+    }
+
+    if out.is_empty() {
+        None
+    } else {
+        Some(BatchNotificationTranslationPayload { items: out })
+    }
+}
+
+pub(super) fn parse_batch_release_detail_translation_payload(
+    raw: &str,
+) -> Option<BatchReleaseDetailTranslationPayload> {
+    let value = parse_json_value_relaxed(raw)?;
+    let items = extract_items_array(&value)?;
+    let mut out = Vec::new();
+
+    for entry in items {
+        let Some(obj) = entry.as_object() else {
+            continue;
+        };
+        let Some(chunk_index) = obj
+            .get("chunk_index")
+            .and_then(value_as_usize)
+            .or_else(|| obj.get("index").and_then(value_as_usize))
+            .or_else(|| obj.get("id").and_then(value_as_usize))
+        else {
+            continue;
+        };
+        let Some(summary_md) = object_field_as_string(
+            obj,
+            &["summary_md", "summary", "body_md", "body", "summary_zh"],
+        ) else {
+            continue;
+        };
+        out.push(BatchReleaseDetailTranslationItem {
+            chunk_index,
+            summary_md: summary_md.replace("\\n", "\n"),
+        });
+    }
+
+    if out.is_empty() {
+        None
+    } else {
+        Some(BatchReleaseDetailTranslationPayload { items: out })
+    }
+}
+
+pub(super) fn line_prefix_kind(line: &str) -> &'static str {
+    let t = line.trim();
+    if t.is_empty() {
+        return "blank";
+    }
+    if t.starts_with('#') {
+        return "heading";
+    }
+    if t.starts_with("> ") || t == ">" {
+        return "blockquote";
+    }
+    if t.starts_with("- ") || t.starts_with("* ") || t.starts_with("+ ") {
+        return "ul";
+    }
+    if let Some((head, tail)) = t.split_once('.')
+        && !head.is_empty()
+        && head.chars().all(|c| c.is_ascii_digit())
+        && tail.starts_with(' ')
+    {
+        return "ol";
+    }
+    "plain"
+}
+
+/// How closely a translation has to follow the source Markdown layout.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) enum MarkdownStructureCheck {
+    /// Same non-empty line count with matching prefixes line by line.
+    Strict,
+    /// Headings, lists and blockquotes must line up, but wrapped plain-text
+    /// lines may merge or split within [`MARKDOWN_PLAIN_LINE_TOLERANCE`]
+    /// (at least [`MARKDOWN_PLAIN_LINE_MIN_DRIFT`] lines).
+    Relaxed,
+}
+
+/// Detail chunks are translated line by line, so they keep the strict check.
+pub(super) const RELEASE_DETAIL_CHUNK_STRUCTURE_CHECK: MarkdownStructureCheck =
+    MarkdownStructureCheck::Strict;
+pub(super) const MARKDOWN_PLAIN_LINE_TOLERANCE: f64 = 0.3;
+// Short paragraphs wrapped over a few lines often collapse into one line.
+pub(super) const MARKDOWN_PLAIN_LINE_MIN_DRIFT: usize = 2;
+
+pub(super) fn inline_markers_preserved(source: &str, translated: &str) -> bool {
+    let src_bold_pairs = source.matches("**").count() / 2;
+    let dst_bold_pairs = translated.matches("**").count() / 2;
+    if src_bold_pairs > 0 && dst_bold_pairs == 0 {
+        return false;
+    }
+
+    let src_code_pairs = source.matches('`').count() / 2;
+    let dst_code_pairs = translated.matches('`').count() / 2;
+    !(src_code_pairs > 0 && dst_code_pairs == 0)
+}
+
+pub(super) fn markdown_structure_preserved(
+    source: &str,
+    translated: &str,
+    check: MarkdownStructureCheck,
+) -> bool {
+    let normalized_source = source.replace("\r\n", "\n");
+    let src_lines: Vec<&str> = normalized_source
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .collect();
+
+    let normalized_translated = translated.replace("\r\n", "\n");
+    let dst_lines: Vec<&str> = normalized_translated
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .collect();
+
+    if src_lines.is_empty() {
+        return true;
+    }
+    if dst_lines.is_empty() {
+        return false;
+    }
+
+    match check {
+        MarkdownStructureCheck::Strict => {
+            src_lines.len() == dst_lines.len()
+                && src_lines.iter().zip(dst_lines.iter()).all(|(s, d)| {
+                    line_prefix_kind(s) == line_prefix_kind(d) && inline_markers_preserved(s, d)
+                })
+        }
+        MarkdownStructureCheck::Relaxed => {
+            relaxed_markdown_structure_preserved(&src_lines, &dst_lines)
+        }
+    }
+}
+
+/// Source links a translation may lose before it is rejected; models now and
+/// then fold a repeated reference into the surrounding sentence.
+pub(super) const TRANSLATION_MAX_DROPPED_LINKS: usize = 1;
+
+/// URLs and bare issue references (`#1234`) in Markdown text. Covers
+/// `[text](url)`, `<url>` autolinks and bare URLs; a URL ends at whitespace,
+/// Markdown/HTML delimiters or the first non-ASCII character, so CJK text
+/// written right after it is not swallowed.
+pub(super) fn extract_translation_link_refs(text: &str) -> BTreeSet<String> {
+    let text = text.replace('＃', "#");
+    let mut refs = BTreeSet::new();
+    let mut rest = text.as_str();
+    let mut prose = String::with_capacity(text.len());
+    while let Some(start) = next_url_start(rest) {
+        prose.push_str(&rest[..start]);
+        let candidate = &rest[start..];
+        let end = candidate
+            .char_indices()
+            .find(|(_, ch)| {
+                !ch.is_ascii_graphic()
+                    || matches!(ch, '(' | ')' | '<' | '>' | '[' | ']' | '"' | '\'' | '`')
+            })
+            .map_or(candidate.len(), |(idx, _)| idx);
+        let url = candidate[..end].trim_end_matches(['.', ',', ';', ':', '!', '?', '*', '_']);
+        let url = url.trim_end_matches('/');
+        if url.len() > "https://".len() {
+            refs.insert(url.to_owned());
+        }
+        rest = &candidate[end..];
+    }
+    prose.push_str(rest);
+
+    let chars = prose.char_indices().collect::<Vec<_>>();
+    for (pos, &(idx, ch)) in chars.iter().enumerate() {
+        if ch != '#' {
+            continue;
+        }
+        if pos > 0 {
+            let prev = chars[pos - 1].1;
+            if prev.is_ascii_alphanumeric() || matches!(prev, '&' | '#' | '/') {
+                continue;
+            }
+        }
+        let digits = prose[idx + 1..]
+            .chars()
+            .take_while(char::is_ascii_digit)
+            .collect::<String>();
+        let next = prose[idx + 1 + digits.len()..].chars().next();
+        if !digits.is_empty() && !next.is_some_and(|next| next.is_ascii_alphanumeric()) {
+            refs.insert(format!("#{digits}"));
+        }
+    }
+    refs
+}
+
+fn next_url_start(text: &str) -> Option<usize> {
+    text.match_indices("http").map(|(idx, _)| idx).find(|&idx| {
+        let tail = &text[idx..];
+        tail.starts_with("https://") || tail.starts_with("http://")
+    })
+}
+
+/// Rejects translations that introduce a URL or issue reference missing
+/// from the source, or drop more than [`TRANSLATION_MAX_DROPPED_LINKS`] of
+/// the source's. Runs alongside [`markdown_structure_preserved`], which
+/// treats link syntax as plain text.
+pub(super) fn translation_links_preserved(source: &str, translated: &str) -> bool {
+    let src_refs = extract_translation_link_refs(source);
+    let dst_refs = extract_translation_link_refs(translated);
+    if !dst_refs.is_subset(&src_refs) {
+        return false;
+    }
+    src_refs.difference(&dst_refs).count() <= TRANSLATION_MAX_DROPPED_LINKS
+}
+
+pub(super) fn relaxed_markdown_structure_preserved(src_lines: &[&str], dst_lines: &[&str]) -> bool {
+    let (src_structural, src_plain): (Vec<&str>, Vec<&str>) = src_lines
+        .iter()
+        .copied()
+        .partition(|line| line_prefix_kind(line) != "plain");
+    let (dst_structural, dst_plain): (Vec<&str>, Vec<&str>) = dst_lines
+        .iter()
+        .copied()
+        .partition(|line| line_prefix_kind(line) != "plain");
+
+    if src_structural.len() != dst_structural.len()
+        || !src_structural
+            .iter()
+            .zip(dst_structural.iter())
+            .all(|(s, d)| {
+                line_prefix_kind(s) == line_prefix_kind(d) && inline_markers_preserved(s, d)
+            })
+    {
+        return false;
+    }
+
+    let tolerance = ((src_plain.len() as f64 * MARKDOWN_PLAIN_LINE_TOLERANCE).ceil() as usize)
+        .max(MARKDOWN_PLAIN_LINE_MIN_DRIFT);
+    if src_plain.len().abs_diff(dst_plain.len()) > tolerance {
+        return false;
+    }
+    inline_markers_preserved(&src_plain.join("\n"), &dst_plain.join("\n"))
+}
+
+/// Splits markdown into chunks of at most `max_chars` on line boundaries.
+///
+/// Fenced code blocks and contiguous table rows are kept whole whenever they
+/// fit, so the translator never sees half a fence or a headless table. A fence
+/// that alone exceeds the budget is split between lines and every piece is
+/// closed and reopened with the original fence lines. The invariant is that
+/// `join("")` reproduces the normalized input exactly, except that each such
+/// forced split adds one synthetic closing line plus a copy of the opening
+/// line. Lines inside a fence are never cut, so a single overlong code line
+/// may exceed the budget.
+pub(super) fn split_markdown_chunks(input: &str, max_chars: usize) -> Vec<String> {
+    if input.is_empty() {
+        return vec![String::new()];
+    }
+    if max_chars == 0 {
+        return vec![input.replace("\r\n", "\n")];
+    }
+
+    let normalized = input.replace("\r\n", "\n");
+    let mut chunks = Vec::new();
+    let mut current = String::new();
+    let mut current_len = 0usize;
+
+    for unit in markdown_chunk_units(&normalized) {
+        let unit_len = unit
+            .lines
+            .iter()
+            .map(|line| line.chars().count())
+            .sum::<usize>();
+
+        if unit_len > max_chars {
+            if !current.is_empty() {
+                chunks.push(std::mem::take(&mut current));
+                current_len = 0;
+            }
+            match unit.fence {
+                Some(fence) => split_oversized_fence(&unit.lines, fence, max_chars, &mut chunks),
+                None => split_lines_by_budget(&unit.lines, max_chars, &mut chunks),
+            }
+            continue;
+        }
+
+        if !current.is_empty() && current_len + unit_len > max_chars {
+            chunks.push(std::mem::take(&mut current));
+            current_len = 0;
+        }
+
+        current.extend(unit.lines.iter().copied());
+        current_len += unit_len;
+    }
+
+    if !current.is_empty() {
+        chunks.push(current);
+    }
+    if chunks.is_empty() {
+        chunks.push(String::new());
+    }
+    chunks
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) struct MarkdownFence {
+    pub(super) marker: char,
+    pub(super) len: usize,
+}
+
+/// Lines the chunker must try not to separate: a fenced code block, a run of
+/// table rows, or a single ordinary line.
+pub(super) struct MarkdownChunkUnit<'a> {
+    pub(super) lines: Vec<&'a str>,
+    pub(super) fence: Option<MarkdownFence>,
+}
+
+pub(super) fn markdown_chunk_units(input: &str) -> Vec<MarkdownChunkUnit<'_>> {
+    let mut units = Vec::new();
+    let mut lines = input.split_inclusive('\n').peekable();
+
+    while let Some(line) = lines.next() {
+        if let Some(fence) = markdown_fence_open(line) {
+            let mut block = vec![line];
+            for next in lines.by_ref() {
+                block.push(next);
+                if markdown_fence_closes(next, fence) {
+                    break;
+                }
+            }
+            units.push(MarkdownChunkUnit {
+                lines: block,
+                fence: Some(fence),
+            });
+        } else if is_markdown_table_row(line) {
+            let mut block = vec![line];
+            while let Some(next) = lines.next_if(|next| is_markdown_table_row(next)) {
+                block.push(next);
+            }
+            units.push(MarkdownChunkUnit {
+                lines: block,
+                fence: None,
+            });
+        } else {
+            units.push(MarkdownChunkUnit {
+                lines: vec![line],
+                fence: None,
+            });
+        }
+    }
+    units
+}
+
+pub(super) fn markdown_fence_open(line: &str) -> Option<MarkdownFence> {
+    let indent = line.len() - line.trim_start_matches(' ').len();
+    if indent > 3 {
+        return None;
+    }
+    let rest = &line[indent..];
+    let marker = rest.chars().next().filter(|ch| matches!(ch, '`' | '~'))?;
+    let len = rest.chars().take_while(|ch| *ch == marker).count();
+    if len < 3 {
+        return None;
+    }
+    // Backtick fences can't carry backticks in their info string.
+    if marker == '`' && rest[len..].contains('`') {
+        return None;
+    }
+    Some(MarkdownFence { marker, len })
+}
+
+pub(super) fn markdown_fence_closes(line: &str, fence: MarkdownFence) -> bool {
+    let trimmed = line.trim_start_matches(' ');
+    if line.len() - trimmed.len() > 3 {
+        return false;
+    }
+    let len = trimmed.chars().take_while(|ch| *ch == fence.marker).count();
+    len >= fence.len && trimmed[len..].trim().is_empty()
+}
+
+pub(super) fn is_markdown_table_row(line: &str) -> bool {
+    line.trim_start().starts_with('|')
+}
+
+pub(super) fn split_lines_by_budget(lines: &[&str], max_chars: usize, chunks: &mut Vec<String>) {
+    let mut current = String::new();
+    let mut current_len = 0usize;
+
+    for line in lines {
+        let line_len = line.chars().count();
+
+        if line_len > max_chars {
+            if !current.is_empty() {
+                chunks.push(std::mem::take(&mut current));
+                current_len = 0;
+            }
+
+            let chars: Vec<char> = line.chars().collect();
+            for part in chars.chunks(max_chars) {
+                chunks.push(part.iter().collect());
+            }
+            continue;
+        }
+
+        if !current.is_empty() && current_len + line_len > max_chars {
+            chunks.push(std::mem::take(&mut current));
+            current_len = 0;
+        }
+
+        current.push_str(line);
+        current_len += line_len;
+    }
+
+    if !current.is_empty() {
+        chunks.push(current);
+    }
+}
+
+pub(super) fn split_oversized_fence(
+    lines: &[&str],
+    fence: MarkdownFence,
+    max_chars: usize,
+    chunks: &mut Vec<String>,
+) {
+    let Some((opening, body)) = lines.split_first() else {
+        return;
+    };
+    let closing = format!("{}\n", fence.marker.to_string().repeat(fence.len));
+    let opening_len = opening.chars().count();
+    let closing_len = closing.chars().count();
+
+    let mut current = (*opening).to_owned();
+    let mut current_len = opening_len;
+    let mut has_body = false;
+    for line in body {
+        let line_len = line.chars().count();
+        if has_body && current_len + line_len + closing_len > max_chars {
+            current.push_str(&closing);
+            chunks.push(std::mem::replace(&mut current, (*opening).to_owned()));
+            current_len = opening_len;
+        }
+        current.push_str(line);
+        current_len += line_len;
+        has_body = true;
+    }
+    chunks.push(current);
+}
+
+pub(super) const RELEASE_DETAIL_CHUNK_PROMPT_OVERHEAD_TOKENS: u32 = 320;
+
+#[derive(Debug, Clone, Copy)]
+pub(super) struct ReleaseDetailChunkBudget {
+    pub(super) max_chars: usize,
+    pub(super) input_budget: u32,
+    pub(super) max_output_tokens: u32,
+    pub(super) model_input_limit: u32,
+    pub(super) fallback_source: &'static str,
+}
+
+pub(super) async fn release_detail_chunk_budget(state: &AppState) -> ReleaseDetailChunkBudget {
+    let budget_info = ai::compute_input_budget_with_source(state, 0).await;
+    let io_budget = budget_info
+        .input_budget
+        .saturating_sub(RELEASE_DETAIL_CHUNK_PROMPT_OVERHEAD_TOKENS)
+        .max(2);
+    let input_budget = (io_budget / 2).max(1);
+    let max_output_tokens = io_budget.saturating_sub(input_budget).max(1);
+
+    ReleaseDetailChunkBudget {
+        max_chars: usize::try_from(input_budget.saturating_mul(4)).unwrap_or(usize::MAX),
+        input_budget,
+        max_output_tokens,
+        model_input_limit: budget_info.model_input_limit,
+        fallback_source: budget_info.fallback_source,
+    }
+}
+
+#[cfg(test)]
+pub(super) fn extract_translation_fields(raw: &str) -> (Option<String>, Option<String>) {
+    let parsed = parse_translation_json(raw);
+    let out_title = parsed
+        .as_ref()
+        .and_then(|p| p.title_zh.as_deref())
+        .map(|s| s.trim())
+        .filter(|s| !s.is_empty())
+        .map(|s| s.to_owned());
+    let out_summary = parsed
+        .as_ref()
+        .and_then(|p| p.summary_md.as_deref().or(p.body_md.as_deref()))
+        .map(|s| s.trim())
+        .filter(|s| !s.is_empty())
+        .map(|s| s.to_owned())
+        .or_else(|| {
+            let s = raw.trim();
+            // Fallback to raw text only when it's plain markdown/plaintext.
+            // If the model output looks like a JSON blob but failed to parse,
+            // treat it as invalid instead of persisting broken cache content.
+            if s.is_empty() || looks_like_json_blob(s) {
+                None
+            } else {
+                Some(s.to_owned())
+            }
+        });
+    (out_title, out_summary)
+}
@@ -0,0 +1,685 @@
+use std::sync::Arc;
+
+use axum::{
+    Json,
+    body::Body,
+    extract::{Path, Query, State},
+    http::{HeaderMap, HeaderValue, StatusCode, header},
+    response::Response,
+};
+use base64::{Engine as _, engine::general_purpose::URL_SAFE_NO_PAD};
+use chrono::{DateTime, SecondsFormat, Utc};
+use rand::Rng;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    ai, api, conditional,
+    error::{ApiError, codes},
+    state::AppState,
+};
+
+const FEED_TOKEN_BYTES: usize = 32;
+const ATOM_FEED_MAX_ENTRIES: i64 = 50;
+const ATOM_CONTENT_TYPE: &str = "application/atom+xml; charset=utf-8";
+const HTTP_DATE_FORMAT: &str = "%a, %d %b %Y %H:%M:%S GMT";
+
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct FeedTokenResponse {
+    token: String,
+    feed_url: String,
+}
+
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct AtomFeedQuery {
+    /// Prefer ready translations in this language; only zh-CN is supported.
+    #[serde(default)]
+    lang: Option<String>,
+}
+
+#[derive(Debug, sqlx::FromRow)]
+struct FeedTokenOwnerRow {
+    user_id: String,
+    created_at: String,
+    is_disabled: i64,
+}
+
+#[derive(Debug, sqlx::FromRow)]
+struct AtomReleaseRow {
+    full_name: String,
+    tag_name: String,
+    name: Option<String>,
+    body: Option<String>,
+    html_url: String,
+    published_at: Option<String>,
+    created_at: Option<String>,
+    trans_title: Option<String>,
+    trans_summary: Option<String>,
+    trans_updated_at: Option<String>,
+}
+
+struct AtomEntry {
+    id: String,
+    title: String,
+    repo: String,
+    link: String,
+    published: DateTime<Utc>,
+    updated: DateTime<Utc>,
+    content: Option<String>,
+}
+
+/// Mints a fresh feed token for the caller. Only its hash is kept, so the
+/// plaintext is shown once; any previously issued feed URL stops resolving.
+pub async fn rotate_feed_token(
+    State(state): State<Arc<AppState>>,
+    api::AuthedUser(user_id): api::AuthedUser,
+) -> Result<Json<FeedTokenResponse>, ApiError> {
+    let mut bytes = [0u8; FEED_TOKEN_BYTES];
+    rand::rng().fill_bytes(&mut bytes);
+    let token = URL_SAFE_NO_PAD.encode(bytes);
+    let now = Utc::now().to_rfc3339_opts(SecondsFormat::Secs, true);
+    sqlx::query(
+        r#"
+        INSERT INTO feed_tokens (user_id, token_hash, created_at)
+        VALUES (?, ?, ?)
+        ON CONFLICT(user_id) DO UPDATE SET
+          token_hash = excluded.token_hash,
+          created_at = excluded.created_at
+        "#,
+    )
+    .bind(&user_id)
+    .bind(ai::sha256_hex(&token))
+    .bind(&now)
+    .execute(&state.pool)
+    .await
+    .map_err(ApiError::internal)?;
+
+    let feed_url = feed_self_url(state.as_ref(), &token, None);
+    Ok(Json(FeedTokenResponse { token, feed_url }))
+}
+
+/// Atom feed of the token owner's recent releases. Feed readers can't hold a
+/// session, so the secret path segment is the only credential.
+pub async fn releases_atom(
+    State(state): State<Arc<AppState>>,
+    Path(token): Path<String>,
+    Query(query): Query<AtomFeedQuery>,
+    headers: HeaderMap,
+) -> Result<Response, ApiError> {
+    let lang = match query.lang.as_deref().map(str::trim) {
+        None | Some("") => None,
+        Some("zh-CN") => Some("zh-CN"),
+        Some(_) => {
+            return Err(ApiError::new(
+                StatusCode::BAD_REQUEST,
+                codes::UNSUPPORTED_LANGUAGE,
+                "only zh-CN is supported",
+            ));
+        }
+    };
+
+    let owner = sqlx::query_as::<_, FeedTokenOwnerRow>(
+        r#"
+        SELECT ft.user_id, ft.created_at, u.is_disabled
+        FROM feed_tokens ft
+        JOIN users u ON u.id = ft.user_id
+        WHERE ft.token_hash = ?
+        "#,
+    )
+    .bind(ai::sha256_hex(&token))
+    .fetch_optional(&state.pool)
+    .await
+    .map_err(ApiError::internal)?
+    .filter(|owner| owner.is_disabled == 0)
+    .ok_or_else(|| ApiError::new(StatusCode::NOT_FOUND, codes::NOT_FOUND, "feed not found"))?;
+
+    let rows = sqlx::query_as::<_, AtomReleaseRow>(
+        r#"
+        SELECT
+          sr.full_name,
+          r.tag_name,
+          r.name,
+          r.body,
+          r.html_url,
+          r.published_at,
+          r.created_at,
+          t.title AS trans_title,
+          t.summary AS trans_summary,
+          t.updated_at AS trans_updated_at
+        FROM repo_releases r
+        JOIN user_release_visible_repos sr
+          ON sr.user_id = ? AND sr.repo_id = r.repo_id
+        LEFT JOIN ai_translations t
+          ON t.user_id = sr.user_id
+          AND t.entity_type = 'release'
+          AND t.entity_id = CAST(r.release_id AS TEXT)
+          AND t.lang = ?
+          AND t.status = 'ready'
+        WHERE NOT EXISTS (
+          SELECT 1
+          FROM muted_repos m
+          WHERE m.user_id = sr.user_id
+            AND m.repo_id = r.repo_id
+        )
+        ORDER BY COALESCE(r.published_at, r.created_at) DESC, r.release_id DESC
+        LIMIT ?
+        "#,
+    )
+    .bind(&owner.user_id)
+    .bind(lang)
+    .bind(ATOM_FEED_MAX_ENTRIES)
+    .fetch_all(&state.pool)
+    .await
+    .map_err(ApiError::internal)?;
+
+    let entries = rows
+        .into_iter()
+        .filter_map(atom_entry_from_row)
+        .collect::<Vec<_>>();
+    // Release rows are re-stamped on every sync, so freshness is derived from
+    // publish and translation times only; otherwise every poll would miss.
+    let last_modified = entries
+        .iter()
+        .map(|entry| entry.updated)
+        .max()
+        .or_else(|| parse_timestamp(&owner.created_at))
+        .unwrap_or(DateTime::<Utc>::UNIX_EPOCH);
+
+    let xml = render_atom_feed(
+        &format!("urn:octo-rill:releases:{}", owner.user_id),
+        &feed_self_url(state.as_ref(), &token, lang),
+        state.config.public_base_url.as_str(),
+        lang,
+        last_modified,
+        &entries,
+    );
+    let etag = format!("\"{}\"", ai::sha256_hex(&xml));
+    let last_modified_header = last_modified.format(HTTP_DATE_FORMAT).to_string();
+
+    let not_modified = is_not_modified(&headers, &etag, last_modified);
+    let mut response = if not_modified {
+        let mut response = Response::new(Body::empty());
+        *response.status_mut() = StatusCode::NOT_MODIFIED;
+        response
+    } else {
+        let mut response = Response::new(Body::from(xml));
+        response.headers_mut().insert(
+            header::CONTENT_TYPE,
+            HeaderValue::from_static(ATOM_CONTENT_TYPE),
+        );
+        response
+    };
+    let response_headers = response.headers_mut();
+    response_headers.insert(
+        header::ETAG,
+        HeaderValue::from_str(&etag).map_err(ApiError::internal)?,
+    );
+    response_headers.insert(
+        header::LAST_MODIFIED,
+        HeaderValue::from_str(&last_modified_header).map_err(ApiError::internal)?,
+    );
+    response_headers.insert(
+        header::CACHE_CONTROL,
+        HeaderValue::from_static("private, no-cache"),
+    );
+    Ok(response)
+}
+
+fn feed_self_url(state: &AppState, token: &str, lang: Option<&str>) -> String {
+    let mut url = state.config.public_base_url.clone();
+    url.set_path(&format!("/feeds/{token}/releases.atom"));
+    url.set_query(None);
+    if let Some(lang) = lang {
+        url.query_pairs_mut().append_pair("lang", lang);
+    }
+    url.to_string()
+}
+
+fn atom_entry_from_row(row: AtomReleaseRow) -> Option<AtomEntry> {
+    let published = row
+        .published_at
+        .as_deref()
+        .or(row.created_at.as_deref())
+        .and_then(parse_timestamp)?;
+    let updated = row
+        .trans_updated_at
+        .as_deref()
+        .and_then(parse_timestamp)
+        .map_or(published, |translated| translated.max(published));
+    let title = row
+        .trans_title
+        .as_deref()
+        .or(row.name.as_deref())
+        .map(str::trim)
+        .filter(|title| !title.is_empty())
+        .unwrap_or(row.tag_name.as_str());
+    let content = row
+        .trans_summary
+        .as_deref()
+        .map(str::trim)
+        .filter(|summary| !summary.is_empty())
+        .map(str::to_owned)
+        .or_else(|| api::release_feed_body(row.body.as_deref()));
+
+    Some(AtomEntry {
+        id: row.html_url.clone(),
+        title: format!("{}: {}", row.full_name, title),
+        repo: row.full_name,
+        link: row.html_url,
+        published,
+        updated,
+        content,
+    })
+}
+
+fn parse_timestamp(raw: &str) -> Option<DateTime<Utc>> {
+    DateTime::parse_from_rfc3339(raw)
+        .ok()
+        .map(|value| value.with_timezone(&Utc))
+}
+
+fn atom_timestamp(value: DateTime<Utc>) -> String {
+    value.to_rfc3339_opts(SecondsFormat::Secs, true)
+}
+
+fn render_atom_feed(
+    feed_id: &str,
+    self_url: &str,
+    site_url: &str,
+    lang: Option<&str>,
+    updated: DateTime<Utc>,
+    entries: &[AtomEntry],
+) -> String {
+    let mut xml = String::from("<?xml version=\"1.0\" encoding=\"utf-8\"?>\n");
+    xml.push_str("<feed xmlns=\"http://www.w3.org/2005/Atom\"");
+    if let Some(lang) = lang {
+        xml.push_str(&format!(" xml:lang=\"{}\"", escape_xml(lang)));
+    }
+    xml.push_str(">\n");
+    xml.push_str(&format!("  <id>{}</id>\n", escape_xml(feed_id)));
+    xml.push_str("  <title>OctoRill releases</title>\n");
+    xml.push_str(&format!(
+        "  <updated>{}</updated>\n",
+        atom_timestamp(updated)
+    ));
+    xml.push_str(&format!(
+        "  <link rel=\"self\" type=\"application/atom+xml\" href=\"{}\"/>\n",
+        escape_xml(self_url)
+    ));
+    xml.push_str(&format!(
+        "  <link rel=\"alternate\" type=\"text/html\" href=\"{}\"/>\n",
+        escape_xml(site_url)
+    ));
+    xml.push_str("  <generator>OctoRill</generator>\n");
+
+    for entry in entries {
+        xml.push_str("  <entry>\n");
+        xml.push_str(&format!("    <id>{}</id>\n", escape_xml(&entry.id)));
+        xml.push_str(&format!(
+            "    <title type=\"text\">{}</title>\n",
+            escape_xml(&entry.title)
+        ));
+        xml.push_str(&format!(
+            "    <link rel=\"alternate\" type=\"text/html\" href=\"{}\"/>\n",
+            escape_xml(&entry.link)
+        ));
+        xml.push_str(&format!(
+            "    <published>{}</published>\n",
+            atom_timestamp(entry.published)
+        ));
+        xml.push_str(&format!(
+            "    <updated>{}</updated>\n",
+            atom_timestamp(entry.updated)
+        ));
+        xml.push_str(&format!(
+            "    <author><name>{}</name></author>\n",
+            escape_xml(&entry.repo)
+        ));
+        xml.push_str(&format!(
+            "    <category term=\"{}\"/>\n",
+            escape_xml(&entry.repo)
+        ));
+        if let Some(content) = entry.content.as_deref() {
+            xml.push_str(&format!(
+                "    <content type=\"text\">{}</content>\n",
+                escape_xml(content)
+            ));
+        }
+        xml.push_str("  </entry>\n");
+    }
+    xml.push_str("</feed>\n");
+    xml
+}
+
+/// Escapes markup characters and drops code points XML 1.0 cannot carry, which
+/// occasionally show up in release bodies pasted from terminals.
+fn escape_xml(raw: &str) -> String {
+    let mut escaped = String::with_capacity(raw.len());
+    for ch in raw.chars() {
+        match ch {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            '\t' | '\n' | '\r' => escaped.push(ch),
+            ch if ch < ' ' || ch == '\u{FFFE}' || ch == '\u{FFFF}' => {}
+            ch => escaped.push(ch),
+        }
+    }
+    escaped
+}
+
+/// `If-None-Match` wins over `If-Modified-Since`, as RFC 9110 requires.
+fn is_not_modified(headers: &HeaderMap, etag: &str, last_modified: DateTime<Utc>) -> bool {
+    if headers.contains_key(header::IF_NONE_MATCH) {
+        return conditional::if_none_match_matches(headers, etag);
+    }
+
+    headers
+        .get(header::IF_MODIFIED_SINCE)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| DateTime::parse_from_rfc2822(value).ok())
+        .is_some_and(|since| last_modified.timestamp() <= since.timestamp())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use sqlx::{
+        SqlitePool,
+        sqlite::{SqliteConnectOptions, SqlitePoolOptions},
+    };
+
+    use crate::config::AppConfig;
+
+    const ATOM_NS: &str = "http://www.w3.org/2005/Atom";
+
+    fn test_user_id() -> String {
+        crate::local_id::test_local_id("atom-feed-user")
+    }
+
+    async fn setup_pool() -> SqlitePool {
+        let database_path = std::env::temp_dir().join(format!(
+            "octo-rill-atom-feed-test-{}.db",
+            crate::local_id::generate_local_id(),
+        ));
+        let options = SqliteConnectOptions::new()
+            .filename(&database_path)
+            .create_if_missing(true);
+        let pool = SqlitePoolOptions::new()
+            .max_connections(1)
+            .connect_with(options)
+            .await
+            .expect("create sqlite db");
+        sqlx::migrate!("./migrations")
+            .run(&pool)
+            .await
+            .expect("run migrations");
+        seed_user_data(&pool).await;
+        pool
+    }
+
+    async fn seed_user_data(pool: &SqlitePool) {
+        for statement in [
+            r#"
+            INSERT INTO users (id, github_user_id, login, created_at, updated_at)
+            VALUES ('user-atom-test', 404, 'atom-test', '2026-02-01T00:00:00Z', '2026-02-01T00:00:00Z')
+            "#,
+            r#"
+            INSERT INTO starred_repos (
+              id, user_id, repo_id, full_name, owner_login, name, description, html_url,
+              stargazed_at, is_private, updated_at
+            )
+            VALUES (
+              'star-atom-1', 'user-atom-test', 42, 'octo/rill', 'octo', 'rill', 'feeds',
+              'https://github.com/octo/rill', '2026-02-01T00:00:00Z', 0, '2026-02-01T00:00:00Z'
+            )
+            "#,
+            r#"
+            INSERT INTO repo_releases (
+              id, repo_id, release_id, tag_name, name, body, html_url, published_at, created_at,
+              is_prerelease, is_draft, updated_at
+            )
+            VALUES
+              ('rel-atom-1', 42, 1001, 'v1.0.0', 'First', 'fixes <script> & "quotes"',
+               'https://github.com/octo/rill/releases/tag/v1.0.0', '2026-02-10T00:00:00Z',
+               '2026-02-10T00:00:00Z', 0, 0, '2026-04-01T00:00:00Z'),
+              ('rel-atom-2', 42, 1002, 'v1.1.0', NULL, 'second body',
+               'https://github.com/octo/rill/releases/tag/v1.1.0', '2026-03-10T00:00:00Z',
+               '2026-03-10T00:00:00Z', 0, 0, '2026-04-01T00:00:00Z'),
+              ('rel-atom-other', 77, 2001, 'v9.0.0', NULL, 'not starred',
+               'https://github.com/other/repo/releases/tag/v9.0.0', '2026-03-10T00:00:00Z',
+               '2026-03-10T00:00:00Z', 0, 0, '2026-04-01T00:00:00Z')
+            "#,
+            r#"
+            INSERT INTO ai_translations (
+              id, user_id, entity_type, entity_id, lang, source_hash, title, summary, status,
+              created_at, updated_at
+            )
+            VALUES (
+              'tr-atom-1', 'user-atom-test', 'release', '1001', 'zh-CN', 'hash', '首个版本',
+              '初始内容', 'ready', '2026-02-11T00:00:00Z', '2026-02-11T00:00:00Z'
+            )
+            "#,
+        ] {
+            sqlx::query(&statement.replace("user-atom-test", test_user_id().as_str()))
+                .execute(pool)
+                .await
+                .expect("seed atom feed data");
+        }
+    }
+
+    fn setup_state(pool: SqlitePool) -> Arc<AppState> {
+        Arc::new(AppState::for_tests(pool, AppConfig::for_tests()))
+    }
+
+    async fn rotate(state: &Arc<AppState>) -> FeedTokenResponse {
+        let Json(response) =
+            rotate_feed_token(State(state.clone()), api::AuthedUser(test_user_id()))
+                .await
+                .expect("rotate feed token");
+        response
+    }
+
+    async fn fetch_feed(
+        state: &Arc<AppState>,
+        token: &str,
+        lang: Option<&str>,
+        headers: HeaderMap,
+    ) -> Result<(Response, String), ApiError> {
+        let response = releases_atom(
+            State(state.clone()),
+            Path(token.to_owned()),
+            Query(AtomFeedQuery {
+                lang: lang.map(str::to_owned),
+            }),
+            headers,
+        )
+        .await?;
+        let (parts, body) = response.into_parts();
+        let bytes = axum::body::to_bytes(body, usize::MAX)
+            .await
+            .expect("collect feed body");
+        Ok((
+            Response::from_parts(parts, Body::empty()),
+            String::from_utf8(bytes.to_vec()).expect("utf-8 feed body"),
+        ))
+    }
+
+    fn child_text<'a>(node: roxmltree::Node<'a, 'a>, name: &str) -> Option<&'a str> {
+        node.children()
+            .find(|child| child.has_tag_name((ATOM_NS, name)))
+            .and_then(|child| child.text())
+    }
+
+    #[tokio::test]
+    async fn feed_token_accepts_current_token_and_rejects_rotated_or_unknown() {
+        let state = setup_state(setup_pool().await);
+        let first = rotate(&state).await;
+        assert_eq!(
+            first.feed_url,
+            format!("http://127.0.0.1:58090/feeds/{}/releases.atom", first.token)
+        );
+
+        let stored: String = sqlx::query_scalar("SELECT token_hash FROM feed_tokens")
+            .fetch_one(&state.pool)
+            .await
+            .expect("load token hash");
+        assert_ne!(stored, first.token, "only the hash is persisted");
+
+        let (response, _) = fetch_feed(&state, &first.token, None, HeaderMap::new())
+            .await
+            .expect("current token is accepted");
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let second = rotate(&state).await;
+        assert_ne!(first.token, second.token);
+        let err = fetch_feed(&state, &first.token, None, HeaderMap::new())
+            .await
+            .expect_err("rotated token is rejected");
+        assert_eq!(err.code(), "not_found");
+        fetch_feed(&state, &second.token, None, HeaderMap::new())
+            .await
+            .expect("new token is accepted");
+
+        let err = fetch_feed(&state, "not-a-token", None, HeaderMap::new())
+            .await
+            .expect_err("unknown token is rejected");
+        assert_eq!(err.code(), "not_found");
+
+        sqlx::query("UPDATE users SET is_disabled = 1")
+            .execute(&state.pool)
+            .await
+            .expect("disable user");
+        let err = fetch_feed(&state, &second.token, None, HeaderMap::new())
+            .await
+            .expect_err("disabled owner is rejected");
+        assert_eq!(err.code(), "not_found");
+    }
+
+    #[tokio::test]
+    async fn releases_atom_renders_parseable_feed_with_translated_titles() {
+        let state = setup_state(setup_pool().await);
+        let token = rotate(&state).await.token;
+
+        let (response, xml) = fetch_feed(&state, &token, None, HeaderMap::new())
+            .await
+            .expect("original feed");
+        assert_eq!(
+            response.headers().get(header::CONTENT_TYPE),
+            Some(&HeaderValue::from_static(ATOM_CONTENT_TYPE))
+        );
+        let document = roxmltree::Document::parse(&xml).expect("parse atom xml");
+        let feed = document.root_element();
+        assert!(feed.has_tag_name((ATOM_NS, "feed")));
+        assert_eq!(child_text(feed, "updated"), Some("2026-03-10T00:00:00Z"));
+        let entries = feed
+            .children()
+            .filter(|node| node.has_tag_name((ATOM_NS, "entry")))
+            .collect::<Vec<_>>();
+        assert_eq!(entries.len(), 2, "unstarred repos stay out of the feed");
+        assert_eq!(child_text(entries[0], "title"), Some("octo/rill: v1.1.0"));
+        assert_eq!(child_text(entries[1], "title"), Some("octo/rill: First"));
+        assert_eq!(
+            child_text(entries[1], "content"),
+            Some(r#"fixes <script> & "quotes""#)
+        );
+        assert_eq!(
+            child_text(entries[1], "published"),
+            Some("2026-02-10T00:00:00Z")
+        );
+        let link = entries[1]
+            .children()
+            .find(|node| node.has_tag_name((ATOM_NS, "link")))
+            .and_then(|node| node.attribute("href"));
+        assert_eq!(
+            link,
+            Some("https://github.com/octo/rill/releases/tag/v1.0.0")
+        );
+
+        let (_, translated) = fetch_feed(&state, &token, Some("zh-CN"), HeaderMap::new())
+            .await
+            .expect("translated feed");
+        let document = roxmltree::Document::parse(&translated).expect("parse translated xml");
+        let entry = document
+            .root_element()
+            .children()
+            .filter(|node| node.has_tag_name((ATOM_NS, "entry")))
+            .nth(1)
+            .expect("translated entry");
+        assert_eq!(child_text(entry, "title"), Some("octo/rill: 首个版本"));
+        assert_eq!(child_text(entry, "content"), Some("初始内容"));
+        assert_eq!(child_text(entry, "updated"), Some("2026-02-11T00:00:00Z"));
+
+        let err = fetch_feed(&state, &token, Some("fr"), HeaderMap::new())
+            .await
+            .expect_err("unsupported lang");
+        assert_eq!(err.code(), "unsupported_language");
+    }
+
+    #[tokio::test]
+    async fn releases_atom_answers_conditional_requests_with_304() {
+        let state = setup_state(setup_pool().await);
+        let token = rotate(&state).await.token;
+
+        let (response, _) = fetch_feed(&state, &token, None, HeaderMap::new())
+            .await
+            .expect("initial fetch");
+        let etag = response
+            .headers()
+            .get(header::ETAG)
+            .cloned()
+            .expect("etag header");
+        let last_modified = response
+            .headers()
+            .get(header::LAST_MODIFIED)
+            .cloned()
+            .expect("last-modified header");
+        assert_eq!(last_modified, "Tue, 10 Mar 2026 00:00:00 GMT");
+
+        let mut headers = HeaderMap::new();
+        headers.insert(header::IF_NONE_MATCH, etag.clone());
+        let (response, body) = fetch_feed(&state, &token, None, headers)
+            .await
+            .expect("etag revalidation");
+        assert_eq!(response.status(), StatusCode::NOT_MODIFIED);
+        assert!(body.is_empty());
+        assert_eq!(response.headers().get(header::ETAG), Some(&etag));
+
+        let mut headers = HeaderMap::new();
+        headers.insert(header::IF_MODIFIED_SINCE, last_modified);
+        let (response, _) = fetch_feed(&state, &token, None, headers)
+            .await
+            .expect("date revalidation");
+        assert_eq!(response.status(), StatusCode::NOT_MODIFIED);
+
+        // A resync re-stamps release rows without changing the feed.
+        sqlx::query("UPDATE repo_releases SET updated_at = '2026-05-01T00:00:00Z'")
+            .execute(&state.pool)
+            .await
+            .expect("touch releases");
+        let mut headers = HeaderMap::new();
+        headers.insert(header::IF_NONE_MATCH, etag.clone());
+        let (response, _) = fetch_feed(&state, &token, None, headers)
+            .await
+            .expect("etag after resync");
+        assert_eq!(response.status(), StatusCode::NOT_MODIFIED);
+
+        sqlx::query(
+            "UPDATE repo_releases SET published_at = '2026-04-02T00:00:00Z' WHERE release_id = 1001",
+        )
+        .execute(&state.pool)
+        .await
+        .expect("republish release");
+        let mut headers = HeaderMap::new();
+        headers.insert(header::IF_NONE_MATCH, etag.clone());
+        let (response, body) = fetch_feed(&state, &token, None, headers)
+            .await
+            .expect("etag after change");
+        assert_eq!(response.status(), StatusCode::OK);
+        assert!(!body.is_empty());
+        assert_ne!(response.headers().get(header::ETAG), Some(&etag));
+    }
+}
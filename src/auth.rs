@@ -8,7 +8,7 @@ use axum::{
 };
 use oauth2::{AuthorizationCode, CsrfToken, Scope, TokenResponse};
 use serde::{Deserialize, Serialize};
-use sqlx::{Sqlite, Transaction};
+use sqlx::{Sqlite, SqlitePool, Transaction};
 use tower_sessions::Session;
 use tracing::info;
 use webauthn_rs::prelude::{
@@ -17,11 +17,11 @@ use webauthn_rs::prelude::{
 };
 
 use crate::{
-    api::require_active_user_id,
-    briefs,
+    api::{AuthedUser, require_active_user_id},
+    bootstrap, briefs,
     config::AppConfig,
     crypto::EncryptedSecret,
-    error::ApiError,
+    error::{ApiError, ApiJson, codes},
     github, linuxdo, local_id,
     passkeys::{
         AttachPendingPasskeyOutcome, PendingPasskeyAuthenticationSession,
@@ -396,7 +396,7 @@ async fn upsert_github_connection(
     {
         return Err(ApiError::new(
             StatusCode::CONFLICT,
-            "github_already_bound",
+            codes::GITHUB_ALREADY_BOUND,
             "github account already bound to another user",
         ));
     }
@@ -421,10 +421,20 @@ async fn upsert_github_connection(
           access_token_ciphertext,
           access_token_nonce,
           scopes,
+          is_primary,
           linked_at,
           updated_at
         )
-        VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+        VALUES (
+          ?, ?, ?, ?, ?, ?, ?, ?, ?, ?,
+          NOT EXISTS (
+            SELECT 1
+            FROM github_connections
+            WHERE user_id = ?
+              AND is_primary = 1
+          ),
+          ?, ?
+        )
         ON CONFLICT(id) DO UPDATE SET
           github_user_id = excluded.github_user_id,
           login = excluded.login,
@@ -447,6 +457,7 @@ async fn upsert_github_connection(
     .bind(ciphertext)
     .bind(nonce)
     .bind(scopes)
+    .bind(user_id)
     .bind(now)
     .bind(now)
     .execute(&mut **tx)
@@ -626,7 +637,10 @@ fn should_clear_pending_passkey_after_linuxdo_rollback(
     )
 }
 
-async fn finalize_passkey_authentication_session(
+/// Signs the session in as `user_id` and records the session id against the
+/// user, so account deletion can drop exactly the sessions it issued.
+async fn log_in_session(
+    pool: &SqlitePool,
     session: &Session,
     user_id: &str,
 ) -> Result<(), ApiError> {
@@ -634,6 +648,35 @@ async fn finalize_passkey_authentication_session(
         .insert(SESSION_KEY_USER_ID, user_id)
         .await
         .map_err(ApiError::internal)?;
+    // A fresh session only gets its id once the record is first stored.
+    session.save().await.map_err(ApiError::internal)?;
+    let Some(session_id) = session.id() else {
+        return Ok(());
+    };
+    sqlx::query(
+        r#"
+        INSERT INTO user_sessions (session_id, user_id, created_at)
+        VALUES (?, ?, ?)
+        ON CONFLICT(session_id) DO UPDATE SET
+          user_id = excluded.user_id,
+          created_at = excluded.created_at
+        "#,
+    )
+    .bind(session_id.to_string())
+    .bind(user_id)
+    .bind(chrono::Utc::now().to_rfc3339())
+    .execute(pool)
+    .await
+    .map_err(ApiError::internal)?;
+    Ok(())
+}
+
+async fn finalize_passkey_authentication_session(
+    pool: &SqlitePool,
+    session: &Session,
+    user_id: &str,
+) -> Result<(), ApiError> {
+    log_in_session(pool, session, user_id).await?;
     clear_pending_linuxdo(session).await;
     clear_pending_passkey_credential(session).await;
     Ok(())
@@ -697,7 +740,7 @@ async fn upsert_linuxdo_connection_record(
     {
         return Err(ApiError::new(
             StatusCode::CONFLICT,
-            "linuxdo_already_bound",
+            codes::LINUXDO_ALREADY_BOUND,
             "linuxdo account already bound to another user",
         ));
     }
@@ -718,7 +761,7 @@ async fn upsert_linuxdo_connection_record(
     {
         return Err(ApiError::new(
             StatusCode::CONFLICT,
-            "linuxdo_account_conflict",
+            codes::LINUXDO_ACCOUNT_CONFLICT,
             "account already bound to another linuxdo user",
         ));
     }
@@ -799,6 +842,7 @@ async fn finalize_github_auth(
         .map_err(ApiError::internal)?;
     let mut tx = state.pool.begin().await.map_err(ApiError::internal)?;
     let mut login_user_after_commit: Option<String> = None;
+    let mut created_user = false;
     let mut consume_pending_passkey = false;
 
     let effective_mode = if should_use_github_connect_mode(
@@ -820,7 +864,7 @@ async fn finalize_github_auth(
                 let _ = tx.rollback().await;
                 return Err(ApiError::new(
                     StatusCode::UNAUTHORIZED,
-                    "unauthorized",
+                    codes::UNAUTHORIZED,
                     "github connect requires an authenticated user",
                 ));
             };
@@ -831,7 +875,7 @@ async fn finalize_github_auth(
                 session.clear().await;
                 return Err(ApiError::new(
                     StatusCode::FORBIDDEN,
-                    "account_disabled",
+                    codes::ACCOUNT_DISABLED,
                     "account is disabled",
                 ));
             }
@@ -856,7 +900,7 @@ async fn finalize_github_auth(
                     None,
                     None,
                 ),
-                Err(err) if err.code() == "github_already_bound" => {
+                Err(err) if err.code() == codes::GITHUB_ALREADY_BOUND => {
                     let _ = tx.rollback().await;
                     return Ok(Redirect::to(
                         settings_redirect(
@@ -882,6 +926,9 @@ async fn finalize_github_auth(
             } else if let Some(existing_user_id) = session_user_id.as_deref() {
                 existing_user_id.to_owned()
             } else {
+                created_user = load_existing_brief_preferences(&mut tx, None, user.id)
+                    .await?
+                    .is_none();
                 upsert_github_user_for_account(
                     &mut tx,
                     None,
@@ -905,7 +952,7 @@ async fn finalize_github_auth(
                 session.clear().await;
                 return Err(ApiError::new(
                     StatusCode::FORBIDDEN,
-                    "account_disabled",
+                    codes::ACCOUNT_DISABLED,
                     "account is disabled",
                 ));
             }
@@ -924,7 +971,7 @@ async fn finalize_github_auth(
 
             match connection {
                 Ok(()) => {}
-                Err(err) if err.code() == "github_already_bound" => {
+                Err(err) if err.code() == codes::GITHUB_ALREADY_BOUND => {
                     let _ = tx.rollback().await;
                     let redirect = if pending_linuxdo.is_some() {
                         bind_github_redirect(
@@ -1005,11 +1052,12 @@ async fn finalize_github_auth(
     };
 
     tx.commit().await.map_err(ApiError::internal)?;
+    if created_user && let Some(user_id) = login_user_after_commit.as_deref() {
+        bootstrap::enqueue_user_bootstrap_best_effort(state.as_ref(), user_id, "auth.first_login")
+            .await;
+    }
     if let Some(user_id) = login_user_after_commit {
-        session
-            .insert(SESSION_KEY_USER_ID, user_id)
-            .await
-            .map_err(ApiError::internal)?;
+        log_in_session(&state.pool, session, user_id.as_str()).await?;
     }
     clear_pending_linuxdo(session).await;
     if consume_pending_passkey {
@@ -1047,10 +1095,10 @@ pub async fn github_login(
 
 pub async fn github_connect(
     State(state): State<Arc<AppState>>,
+    _: AuthedUser,
+    // Carries the OAuth state and connect mode to the callback.
     session: Session,
 ) -> Result<impl IntoResponse, ApiError> {
-    let _ = require_active_user_id(state.as_ref(), &session).await?;
-
     let (auth_url, csrf_token) = state
         .github_oauth
         .authorize_url(CsrfToken::new_random)
@@ -1162,7 +1210,7 @@ pub async fn linuxdo_login(
     let Some(client) = state.linuxdo_oauth.as_ref() else {
         return Err(ApiError::new(
             StatusCode::SERVICE_UNAVAILABLE,
-            "linuxdo_oauth_not_configured",
+            codes::LINUXDO_OAUTH_NOT_CONFIGURED,
             "linuxdo oauth is not configured",
         ));
     };
@@ -1275,10 +1323,7 @@ pub async fn linuxdo_callback(
         let passkey_status_after_login =
             consume_pending_passkey_after_login(state.as_ref(), &session, owner.user_id.as_str())
                 .await?;
-        session
-            .insert(SESSION_KEY_USER_ID, owner.user_id)
-            .await
-            .map_err(ApiError::internal)?;
+        log_in_session(&state.pool, &session, owner.user_id.as_str()).await?;
         clear_pending_linuxdo(&session).await;
         return Ok(Redirect::to(
             post_linuxdo_login_redirect(&state.config, passkey_status_after_login).as_str(),
@@ -1293,7 +1338,7 @@ pub async fn linuxdo_callback(
             session.clear().await;
             return Err(ApiError::new(
                 StatusCode::FORBIDDEN,
-                "account_disabled",
+                codes::ACCOUNT_DISABLED,
                 "account is disabled",
             ));
         }
@@ -1357,6 +1402,7 @@ pub struct PasskeyAuthenticateVerifyResponse {
 
 pub async fn passkey_register_options(
     State(state): State<Arc<AppState>>,
+    // Raw session: registration also runs signed out, as passkey sign-up.
     session: Session,
 ) -> Result<axum::Json<CreationChallengeResponse>, ApiError> {
     let now = chrono::Utc::now().to_rfc3339();
@@ -1398,7 +1444,7 @@ pub async fn passkey_register_options(
             .ok_or_else(|| {
                 ApiError::new(
                     StatusCode::CONFLICT,
-                    "passkey_github_required",
+                    codes::PASSKEY_GITHUB_REQUIRED,
                     "at least one github connection is required before adding a passkey",
                 )
             })?;
@@ -1460,8 +1506,9 @@ pub async fn passkey_register_options(
 
 pub async fn passkey_register_verify(
     State(state): State<Arc<AppState>>,
+    // Raw session: the pending registration decides whether a user is needed.
     session: Session,
-    axum::Json(req): axum::Json<PasskeyRegisterVerifyRequest>,
+    ApiJson(req): ApiJson<PasskeyRegisterVerifyRequest>,
 ) -> Result<axum::Json<PasskeyRegisterVerifyResponse>, ApiError> {
     let pending = session
         .get::<PendingPasskeyRegistrationSession>(SESSION_KEY_PENDING_PASSKEY_REGISTRATION)
@@ -1470,7 +1517,7 @@ pub async fn passkey_register_verify(
         .ok_or_else(|| {
             ApiError::new(
                 StatusCode::BAD_REQUEST,
-                "passkey_registration_missing",
+                codes::PASSKEY_REGISTRATION_MISSING,
                 "passkey registration was not started or has already been consumed",
             )
         })?;
@@ -1479,7 +1526,7 @@ pub async fn passkey_register_verify(
         clear_pending_passkey_registration(&session).await;
         return Err(ApiError::new(
             StatusCode::BAD_REQUEST,
-            "passkey_registration_expired",
+            codes::PASSKEY_REGISTRATION_EXPIRED,
             "passkey registration has expired, please try again",
         ));
     }
@@ -1491,7 +1538,7 @@ pub async fn passkey_register_verify(
         .map_err(|err| {
             ApiError::new(
                 StatusCode::BAD_REQUEST,
-                "passkey_registration_failed",
+                codes::PASSKEY_REGISTRATION_FAILED,
                 err.to_string(),
             )
         })?;
@@ -1510,7 +1557,7 @@ pub async fn passkey_register_verify(
             if current_user_id != user_id {
                 return Err(ApiError::new(
                     StatusCode::UNAUTHORIZED,
-                    "unauthorized",
+                    codes::UNAUTHORIZED,
                     "session user changed during passkey registration",
                 ));
             }
@@ -1542,7 +1589,7 @@ pub async fn passkey_register_verify(
                     let _ = tx.rollback().await;
                     Err(ApiError::new(
                         StatusCode::CONFLICT,
-                        "passkey_already_bound",
+                        codes::PASSKEY_ALREADY_BOUND,
                         "this passkey is already bound to another account",
                     ))
                 }
@@ -1550,7 +1597,7 @@ pub async fn passkey_register_verify(
                     let _ = tx.rollback().await;
                     Err(ApiError::new(
                         StatusCode::CONFLICT,
-                        "passkey_retry_required",
+                        codes::PASSKEY_RETRY_REQUIRED,
                         "this account already uses a different passkey handle; please retry from settings",
                     ))
                 }
@@ -1596,7 +1643,7 @@ pub async fn passkey_authenticate_options(
 pub async fn passkey_authenticate_verify(
     State(state): State<Arc<AppState>>,
     session: Session,
-    axum::Json(req): axum::Json<PasskeyAuthenticateVerifyRequest>,
+    ApiJson(req): ApiJson<PasskeyAuthenticateVerifyRequest>,
 ) -> Result<axum::Json<PasskeyAuthenticateVerifyResponse>, ApiError> {
     let pending = session
         .get::<PendingPasskeyAuthenticationSession>(SESSION_KEY_PENDING_PASSKEY_AUTHENTICATION)
@@ -1605,7 +1652,7 @@ pub async fn passkey_authenticate_verify(
         .ok_or_else(|| {
             ApiError::new(
                 StatusCode::BAD_REQUEST,
-                "passkey_authentication_missing",
+                codes::PASSKEY_AUTHENTICATION_MISSING,
                 "passkey authentication was not started or has already been consumed",
             )
         })?;
@@ -1614,7 +1661,7 @@ pub async fn passkey_authenticate_verify(
         clear_pending_passkey_authentication(&session).await;
         return Err(ApiError::new(
             StatusCode::BAD_REQUEST,
-            "passkey_authentication_expired",
+            codes::PASSKEY_AUTHENTICATION_EXPIRED,
             "passkey authentication has expired, please try again",
         ));
     }
@@ -1626,7 +1673,7 @@ pub async fn passkey_authenticate_verify(
         .map_err(|err| {
             ApiError::new(
                 StatusCode::UNAUTHORIZED,
-                "passkey_authentication_failed",
+                codes::PASSKEY_AUTHENTICATION_FAILED,
                 err.to_string(),
             )
         })?;
@@ -1636,7 +1683,7 @@ pub async fn passkey_authenticate_verify(
         .ok_or_else(|| {
             ApiError::new(
                 StatusCode::UNAUTHORIZED,
-                "passkey_user_not_found",
+                codes::PASSKEY_USER_NOT_FOUND,
                 "passkey does not map to an existing account",
             )
         })?;
@@ -1644,7 +1691,7 @@ pub async fn passkey_authenticate_verify(
     if !user_has_github_connection(state.as_ref(), user_id.as_str()).await? {
         return Err(ApiError::new(
             StatusCode::CONFLICT,
-            "passkey_github_required",
+            codes::PASSKEY_GITHUB_REQUIRED,
             "a github connection is required before passkey login can complete",
         ));
     }
@@ -1653,7 +1700,7 @@ pub async fn passkey_authenticate_verify(
     if stored_passkeys.is_empty() {
         return Err(ApiError::new(
             StatusCode::UNAUTHORIZED,
-            "passkey_not_found",
+            codes::PASSKEY_NOT_FOUND,
             "no passkeys are registered for this account",
         ));
     }
@@ -1665,7 +1712,7 @@ pub async fn passkey_authenticate_verify(
         let _ = tx.rollback().await;
         return Err(ApiError::new(
             StatusCode::FORBIDDEN,
-            "account_disabled",
+            codes::ACCOUNT_DISABLED,
             "account is disabled",
         ));
     }
@@ -1684,7 +1731,7 @@ pub async fn passkey_authenticate_verify(
         .map_err(|err| {
             ApiError::new(
                 StatusCode::UNAUTHORIZED,
-                "passkey_authentication_failed",
+                codes::PASSKEY_AUTHENTICATION_FAILED,
                 err.to_string(),
             )
         })?;
@@ -1696,7 +1743,7 @@ pub async fn passkey_authenticate_verify(
         .ok_or_else(|| {
             ApiError::new(
                 StatusCode::UNAUTHORIZED,
-                "passkey_not_found",
+                codes::PASSKEY_NOT_FOUND,
                 "the selected passkey is not registered for this account",
             )
         })?;
@@ -1711,7 +1758,7 @@ pub async fn passkey_authenticate_verify(
     .await?;
 
     tx.commit().await.map_err(ApiError::internal)?;
-    finalize_passkey_authentication_session(&session, user_id.as_str()).await?;
+    finalize_passkey_authentication_session(&state.pool, &session, user_id.as_str()).await?;
 
     Ok(axum::Json(PasskeyAuthenticateVerifyResponse {
         status: "authenticated".to_owned(),
@@ -1731,7 +1778,7 @@ pub async fn logout(
 mod tests {
     use super::{
         SESSION_KEY_PENDING_LINUXDO, SESSION_KEY_PENDING_PASSKEY_CREDENTIAL, SESSION_KEY_USER_ID,
-        clear_pending_linuxdo, clear_pending_passkey_credential,
+        clear_pending_linuxdo, clear_pending_passkey_credential, finalize_github_auth,
         finalize_passkey_authentication_session, post_github_login_redirect,
         post_linuxdo_bind_redirect, post_linuxdo_login_redirect, promote_first_admin,
         should_clear_pending_passkey_after_linuxdo_rollback, should_use_github_connect_mode,
@@ -1786,6 +1833,16 @@ mod tests {
             static_dir: None,
             task_log_dir: PathBuf::from("/tmp/octo-rill-auth-tests"),
             job_worker_concurrency: 1,
+            reaction_viewer_ttl_secs: 600,
+            release_backfill_limit: 10,
+            star_purge_grace_days: 14,
+            notification_archive_days: 90,
+            notification_retention_days: 365,
+            task_retention_days: 30,
+            backup_dir: std::env::temp_dir().join("octo-rill-backups-tests"),
+            backup_retention_count: 7,
+            shutdown_grace_secs: 30,
+            task_timeouts: crate::jobs::TaskTimeouts::default(),
             encryption_key: EncryptionKey::from_base64(
                 "AAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAA=",
             )
@@ -1796,15 +1853,61 @@ mod tests {
                 redirect_url: Url::parse("http://127.0.0.1:58090/auth/github/callback")
                     .expect("parse github redirect url"),
             },
+            github_app: None,
             linuxdo: None,
             ai: None,
             ai_max_concurrency: 1,
+            ai_translate_concurrency: 2,
+            ai_skip_translation_cjk_percent: 30,
+            ai_daily_token_budget_per_user: None,
+            ai_request_interval_ms: 0,
             ai_daily_at_local: None,
             app_default_time_zone: "Asia/Shanghai".to_owned(),
+            smtp: None,
             logging: crate::observability::LoggingThresholds::default(),
+            metrics_token: None,
+            trust_request_id_header: false,
+            trust_forwarded_headers: false,
+            readyz_check_github: false,
+            rate_limits: crate::rate_limit::RateLimits::default(),
         }
     }
 
+    fn setup_state(pool: SqlitePool) -> Arc<crate::state::AppState> {
+        let config = test_config();
+        let encryption_key = config.encryption_key.clone();
+        let github_oauth = crate::state::build_oauth_client(&config).expect("build oauth client");
+        let webauthn = crate::state::build_webauthn(&config).expect("build webauthn");
+        Arc::new(crate::state::AppState {
+            llm_scheduler: Arc::new(crate::ai::LlmScheduler::new(config.ai_max_concurrency)),
+            translation_scheduler: Arc::new(
+                crate::translations::TranslationSchedulerController::new(
+                    crate::translations::TranslationRuntimeConfig::default(),
+                ),
+            ),
+            config,
+            pool,
+            sqlite_writer: crate::sqlite_write::SqliteWriteCoordinator::new(),
+            http: reqwest::Client::new(),
+            github_rest_http: reqwest::Client::new(),
+            github_rest_api_base: Url::parse("https://api.github.com/")
+                .expect("parse github rest api base"),
+            github_graphql_url: Url::parse("https://api.github.com/graphql")
+                .expect("parse github graphql url"),
+            github_oauth,
+            linuxdo_oauth: None,
+            webauthn,
+            encryption_key,
+            runtime_owner_id: "auth-test-runtime-owner".to_owned(),
+            feed_events: crate::feed_events::FeedEventHub::default(),
+            admin_mutation_limiter: crate::admin_audit::AdminMutationLimiter::default(),
+            translation_flights: crate::ai::TranslationSingleflight::default(),
+            request_limiter: crate::rate_limit::RequestRateLimiter::default(),
+            github_probe: crate::health::GitHubProbe::default(),
+            github_app_tokens: None,
+        })
+    }
+
     #[test]
     fn post_github_login_redirect_prefers_passkeys_section_for_passkey_recovery() {
         let config = test_config();
@@ -1922,6 +2025,7 @@ mod tests {
 
     #[tokio::test]
     async fn finalize_passkey_authentication_session_logs_in_and_clears_pending_bind_context() {
+        let pool = setup_pool().await;
         let session = setup_session();
         session
             .insert_value(
@@ -1935,10 +2039,19 @@ mod tests {
             .await
             .expect("insert pending linuxdo");
 
-        finalize_passkey_authentication_session(&session, "user_123")
+        finalize_passkey_authentication_session(&pool, &session, "user_123")
             .await
             .expect("finalize passkey auth session");
 
+        let recorded_user = sqlx::query_scalar::<_, String>(
+            "SELECT user_id FROM user_sessions WHERE session_id = ?",
+        )
+        .bind(session.id().expect("saved session id").to_string())
+        .fetch_one(&pool)
+        .await
+        .expect("load recorded session");
+        assert_eq!(recorded_user, "user_123");
+
         assert_eq!(
             session
                 .get::<String>(SESSION_KEY_USER_ID)
@@ -1963,6 +2076,64 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn finalize_github_auth_enqueues_bootstrap_only_for_new_users() {
+        let pool = setup_pool().await;
+        let state = setup_state(pool.clone());
+        let github_user = crate::github::GitHubUser {
+            id: 104,
+            login: "first-login".to_owned(),
+            name: None,
+            avatar_url: None,
+            email: None,
+        };
+
+        let session = setup_session();
+        let _ = finalize_github_auth(
+            &state,
+            &session,
+            &github_user,
+            None,
+            "token-1",
+            "read:user",
+            None,
+        )
+        .await
+        .expect("first login");
+        let user_id = session
+            .get::<String>(SESSION_KEY_USER_ID)
+            .await
+            .expect("read user id")
+            .expect("logged in");
+        let bootstrap_tasks = || {
+            sqlx::query_scalar::<_, i64>(
+                "SELECT COUNT(*) FROM job_tasks WHERE task_type = ? AND requested_by = ?",
+            )
+            .bind(crate::jobs::TASK_BOOTSTRAP_USER)
+            .bind(user_id.as_str())
+            .fetch_one(&pool)
+        };
+        assert_eq!(bootstrap_tasks().await.expect("count tasks"), 1);
+
+        sqlx::query("UPDATE job_tasks SET status = ?")
+            .bind(crate::jobs::STATUS_SUCCEEDED)
+            .execute(&pool)
+            .await
+            .expect("finish bootstrap");
+        let _ = finalize_github_auth(
+            &state,
+            &setup_session(),
+            &github_user,
+            None,
+            "token-2",
+            "read:user",
+            None,
+        )
+        .await
+        .expect("second login");
+        assert_eq!(bootstrap_tasks().await.expect("count tasks"), 1);
+    }
+
     #[tokio::test]
     async fn promote_first_admin_assigns_only_once() {
         let pool = setup_pool().await;
@@ -0,0 +1,480 @@
+use std::{
+    path::{Path as FsPath, PathBuf},
+    sync::Arc,
+    time::Instant,
+};
+
+use anyhow::{Context, Result};
+use axum::{
+    Json,
+    body::{Body, Bytes},
+    extract::{Path, State},
+    http::{HeaderValue, StatusCode, header},
+    response::Response,
+};
+use schemars::JsonSchema;
+use serde::Serialize;
+use sqlx::{ConnectOptions, Connection};
+use tokio::io::AsyncReadExt;
+
+use crate::{api, error::ApiError, state::AppState};
+
+/// Snapshot files are `octo-rill-<YYYYMMDD>T<HHMMSSmmm>Z.sqlite3`; nothing
+/// else in the backup directory is listed or served.
+const BACKUP_FILE_PREFIX: &str = "octo-rill-";
+const BACKUP_FILE_SUFFIX: &str = ".sqlite3";
+const BACKUP_TIMESTAMP_FORMAT: &str = "%Y%m%dT%H%M%S%3fZ";
+/// Suffix of a snapshot still being written; renamed once complete.
+const BACKUP_PARTIAL_SUFFIX: &str = ".partial";
+
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct BackupResponse {
+    name: String,
+    path: String,
+    size_bytes: u64,
+    duration_ms: u64,
+    finished_at: String,
+    /// Older snapshots deleted to stay within the retention count.
+    pruned: Vec<String>,
+}
+
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct BackupItem {
+    name: String,
+    size_bytes: u64,
+    /// File modification time.
+    created_at: Option<String>,
+}
+
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct BackupListResponse {
+    /// Newest first.
+    items: Vec<BackupItem>,
+}
+
+/// True only for names this module generates: fixed prefix and suffix around
+/// a digits-only timestamp, so no separator or `..` can slip through.
+fn is_backup_file_name(name: &str) -> bool {
+    let Some(stamp) = name
+        .strip_prefix(BACKUP_FILE_PREFIX)
+        .and_then(|rest| rest.strip_suffix(BACKUP_FILE_SUFFIX))
+    else {
+        return false;
+    };
+    let bytes = stamp.as_bytes();
+    bytes.len() == 19
+        && bytes[8] == b'T'
+        && bytes[18] == b'Z'
+        && bytes[..8].iter().all(u8::is_ascii_digit)
+        && bytes[9..18].iter().all(u8::is_ascii_digit)
+}
+
+fn backup_file_name(now: chrono::DateTime<chrono::Utc>) -> String {
+    format!(
+        "{BACKUP_FILE_PREFIX}{}{BACKUP_FILE_SUFFIX}",
+        now.format(BACKUP_TIMESTAMP_FORMAT)
+    )
+}
+
+/// Snapshot names in the backup directory, oldest first. The timestamp makes
+/// name order and age order the same.
+async fn list_backup_names(dir: &FsPath) -> Result<Vec<String>> {
+    let mut entries = match tokio::fs::read_dir(dir).await {
+        Ok(entries) => entries,
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(err) => return Err(err).context("failed to read backup directory"),
+    };
+    let mut names = Vec::new();
+    while let Some(entry) = entries
+        .next_entry()
+        .await
+        .context("failed to read backup directory entry")?
+    {
+        if let Some(name) = entry.file_name().to_str()
+            && is_backup_file_name(name)
+        {
+            names.push(name.to_owned());
+        }
+    }
+    names.sort();
+    Ok(names)
+}
+
+/// Deletes the oldest snapshots beyond the configured retention count.
+async fn prune_backups(state: &AppState) -> Result<Vec<String>> {
+    let dir = state.config.backup_dir.as_path();
+    let names = list_backup_names(dir).await?;
+    let excess = names
+        .len()
+        .saturating_sub(state.config.backup_retention_count);
+    let mut pruned = Vec::with_capacity(excess);
+    for name in names.into_iter().take(excess) {
+        tokio::fs::remove_file(dir.join(&name))
+            .await
+            .with_context(|| format!("failed to delete backup {name}"))?;
+        pruned.push(name);
+    }
+    Ok(pruned)
+}
+
+/// Writes a consistent snapshot with `VACUUM INTO` on a connection of its
+/// own. It only reads the database, so writers in WAL mode keep going and no
+/// pool connection is held for the duration.
+pub async fn create_backup(state: &AppState) -> Result<BackupResponse> {
+    let main_file = sqlx::query_scalar::<_, String>(
+        r#"SELECT file FROM pragma_database_list WHERE name = 'main'"#,
+    )
+    .fetch_one(&state.pool)
+    .await
+    .context("failed to resolve database file")?;
+    if main_file.is_empty() {
+        anyhow::bail!("an in-memory database has no file to back up");
+    }
+
+    let dir = state.config.backup_dir.as_path();
+    tokio::fs::create_dir_all(dir)
+        .await
+        .with_context(|| format!("failed to create backup directory {}", dir.display()))?;
+    let started = Instant::now();
+    let name = backup_file_name(chrono::Utc::now());
+    let path = dir.join(&name);
+    let partial_path = PathBuf::from(format!("{}{BACKUP_PARTIAL_SUFFIX}", path.display()));
+
+    let mut conn = state
+        .pool
+        .connect_options()
+        .connect()
+        .await
+        .context("failed to open backup connection")?;
+    let vacuum = sqlx::query("VACUUM INTO ?")
+        .bind(partial_path.to_string_lossy().into_owned())
+        .execute(&mut conn)
+        .await;
+    if let Err(err) = conn.close().await {
+        tracing::warn!(?err, "failed to close backup connection");
+    }
+    if let Err(err) = vacuum {
+        let _ = tokio::fs::remove_file(&partial_path).await;
+        return Err(err).context("vacuum into backup failed");
+    }
+    tokio::fs::rename(&partial_path, &path)
+        .await
+        .context("failed to finalize backup file")?;
+
+    let size_bytes = tokio::fs::metadata(&path)
+        .await
+        .context("failed to stat backup file")?
+        .len();
+    let pruned = prune_backups(state).await?;
+    let response = BackupResponse {
+        name,
+        path: path.display().to_string(),
+        size_bytes,
+        duration_ms: u64::try_from(started.elapsed().as_millis()).unwrap_or(u64::MAX),
+        finished_at: chrono::Utc::now().to_rfc3339(),
+        pruned,
+    };
+    tracing::info!(
+        name = response.name.as_str(),
+        size_bytes,
+        duration_ms = response.duration_ms,
+        pruned = response.pruned.len(),
+        "database backup finished"
+    );
+    Ok(response)
+}
+
+pub async fn admin_create_backup(
+    State(state): State<Arc<AppState>>,
+    api::AdminUser(acting_user_id): api::AdminUser,
+) -> Result<Json<BackupResponse>, ApiError> {
+    state
+        .admin_mutation_limiter
+        .check(acting_user_id.as_str())?;
+    let response = create_backup(state.as_ref())
+        .await
+        .map_err(ApiError::internal)?;
+    Ok(Json(response))
+}
+
+pub async fn admin_list_backups(
+    State(state): State<Arc<AppState>>,
+    _: api::AdminUser,
+) -> Result<Json<BackupListResponse>, ApiError> {
+    let dir = state.config.backup_dir.as_path();
+    let names = list_backup_names(dir).await.map_err(ApiError::internal)?;
+    let mut items = Vec::with_capacity(names.len());
+    for name in names.into_iter().rev() {
+        // A snapshot pruned between listing and stat is simply skipped.
+        let Ok(metadata) = tokio::fs::metadata(dir.join(&name)).await else {
+            continue;
+        };
+        items.push(BackupItem {
+            created_at: metadata
+                .modified()
+                .ok()
+                .map(|modified| chrono::DateTime::<chrono::Utc>::from(modified).to_rfc3339()),
+            size_bytes: metadata.len(),
+            name,
+        });
+    }
+    Ok(Json(BackupListResponse { items }))
+}
+
+pub async fn admin_download_backup(
+    State(state): State<Arc<AppState>>,
+    _: api::AdminUser,
+    Path(name): Path<String>,
+) -> Result<Response, ApiError> {
+    if !is_backup_file_name(&name) {
+        return Err(ApiError::bad_request("invalid backup name"));
+    }
+
+    let file = tokio::fs::File::open(state.config.backup_dir.join(&name))
+        .await
+        .map_err(|err| {
+            if err.kind() == std::io::ErrorKind::NotFound {
+                ApiError::new(
+                    StatusCode::NOT_FOUND,
+                    crate::error::codes::NOT_FOUND,
+                    "backup not found",
+                )
+            } else {
+                ApiError::internal(err)
+            }
+        })?;
+    let size_bytes = file.metadata().await.map_err(ApiError::internal)?.len();
+
+    let stream = async_stream::stream! {
+        let mut file = file;
+        let mut buffer = vec![0_u8; 64 * 1024];
+        loop {
+            match file.read(&mut buffer).await {
+                Ok(0) => break,
+                Ok(read) => yield Ok::<Bytes, std::io::Error>(Bytes::copy_from_slice(&buffer[..read])),
+                Err(err) => {
+                    yield Err::<Bytes, std::io::Error>(err);
+                    break;
+                }
+            }
+        }
+    };
+
+    let mut response = Response::new(Body::from_stream(stream));
+    response.headers_mut().insert(
+        header::CONTENT_TYPE,
+        HeaderValue::from_static("application/vnd.sqlite3"),
+    );
+    response
+        .headers_mut()
+        .insert(header::CONTENT_LENGTH, HeaderValue::from(size_bytes));
+    response.headers_mut().insert(
+        header::CONTENT_DISPOSITION,
+        HeaderValue::from_str(&format!(r#"attachment; filename="{name}""#))
+            .map_err(ApiError::internal)?,
+    );
+    Ok(response)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use sqlx::{
+        SqlitePool,
+        sqlite::{SqliteConnectOptions, SqlitePoolOptions},
+    };
+    use tower_sessions::{MemoryStore, Session};
+
+    use crate::config::AppConfig;
+
+    fn admin_id() -> String {
+        crate::local_id::test_local_id("backup-admin")
+    }
+
+    fn member_id() -> String {
+        crate::local_id::test_local_id("backup-member")
+    }
+
+    async fn setup_pool() -> SqlitePool {
+        let database_path = std::env::temp_dir().join(format!(
+            "octo-rill-backup-test-{}.db",
+            crate::local_id::generate_local_id(),
+        ));
+        let options = SqliteConnectOptions::new()
+            .filename(&database_path)
+            .create_if_missing(true);
+        let pool = SqlitePoolOptions::new()
+            .max_connections(1)
+            .connect_with(options)
+            .await
+            .expect("create sqlite db");
+        sqlx::migrate!("./migrations")
+            .run(&pool)
+            .await
+            .expect("run migrations");
+        for (id, github_user_id, is_admin) in [(admin_id(), 1, 1), (member_id(), 2, 0)] {
+            sqlx::query(
+                r#"
+                INSERT INTO users (id, github_user_id, login, is_admin, created_at, updated_at)
+                VALUES (?, ?, ?, ?, '2026-02-01T00:00:00Z', '2026-02-01T00:00:00Z')
+                "#,
+            )
+            .bind(&id)
+            .bind(github_user_id)
+            .bind(format!("user-{github_user_id}"))
+            .bind(is_admin)
+            .execute(&pool)
+            .await
+            .expect("seed user");
+        }
+        pool
+    }
+
+    fn setup_state(pool: SqlitePool, backup_retention_count: usize) -> Arc<AppState> {
+        let config = AppConfig {
+            backup_dir: std::env::temp_dir().join(format!(
+                "octo-rill-backups-test-{}",
+                crate::local_id::generate_local_id(),
+            )),
+            backup_retention_count,
+            ..AppConfig::for_tests()
+        };
+        Arc::new(AppState::for_tests(pool, config))
+    }
+
+    async fn session_for(user_id: String) -> Session {
+        let session = Session::new(None, Arc::new(MemoryStore::default()), None);
+        session
+            .insert("user_id", user_id)
+            .await
+            .expect("insert session user id");
+        session
+    }
+
+    #[tokio::test]
+    async fn backup_is_listed_and_downloads_exact_bytes() {
+        let pool = setup_pool().await;
+        let state = setup_state(pool, 7);
+
+        let Json(backup) = admin_create_backup(State(state.clone()), api::AdminUser(admin_id()))
+            .await
+            .expect("create backup");
+        assert!(is_backup_file_name(&backup.name), "{}", backup.name);
+        assert!(backup.size_bytes > 0);
+        assert!(backup.pruned.is_empty());
+
+        let snapshot = SqlitePoolOptions::new()
+            .connect_with(SqliteConnectOptions::new().filename(&backup.path))
+            .await
+            .expect("open snapshot");
+        let users = sqlx::query_scalar::<_, i64>("SELECT COUNT(*) FROM users")
+            .fetch_one(&snapshot)
+            .await
+            .expect("count snapshot users");
+        assert_eq!(users, 2);
+        snapshot.close().await;
+
+        let Json(listed) = admin_list_backups(State(state.clone()), api::AdminUser(admin_id()))
+            .await
+            .expect("list backups");
+        assert_eq!(listed.items.len(), 1);
+        assert_eq!(listed.items[0].name, backup.name);
+        assert_eq!(listed.items[0].size_bytes, backup.size_bytes);
+
+        let response = admin_download_backup(
+            State(state.clone()),
+            api::AdminUser(admin_id()),
+            Path(backup.name.clone()),
+        )
+        .await
+        .expect("download backup");
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .expect("read download body");
+        let on_disk = tokio::fs::read(&backup.path).await.expect("read backup");
+        assert_eq!(body.as_ref(), on_disk.as_slice());
+
+        let err = api::require_admin_user_id(state.as_ref(), &session_for(member_id()).await)
+            .await
+            .expect_err("members cannot list backups");
+        assert_eq!(err.code(), "forbidden_admin_only");
+    }
+
+    #[tokio::test]
+    async fn download_rejects_names_outside_the_generated_pattern() {
+        let pool = setup_pool().await;
+        let state = setup_state(pool, 7);
+        tokio::fs::create_dir_all(&state.config.backup_dir)
+            .await
+            .expect("create backup dir");
+        tokio::fs::write(state.config.backup_dir.join("notes.sqlite3"), b"secret")
+            .await
+            .expect("write unrelated file");
+
+        for name in [
+            "../octo-rill.db",
+            "..%2Focto-rill-20260201T000000000Z.sqlite3",
+            "octo-rill-20260201T000000000Z.sqlite3/../../etc/passwd",
+            "octo-rill-2026020lT000000000Z.sqlite3",
+            "octo-rill-20260201T000000000Z.sqlite3.partial",
+            "notes.sqlite3",
+        ] {
+            let err = admin_download_backup(
+                State(state.clone()),
+                api::AdminUser(admin_id()),
+                Path(name.to_owned()),
+            )
+            .await
+            .expect_err("malicious name must be rejected");
+            assert_eq!(err.code(), "bad_request", "{name}");
+        }
+
+        let err = admin_download_backup(
+            State(state.clone()),
+            api::AdminUser(admin_id()),
+            Path("octo-rill-20260201T000000000Z.sqlite3".to_owned()),
+        )
+        .await
+        .expect_err("missing snapshot");
+        assert_eq!(err.code(), "not_found");
+
+        let Json(listed) = admin_list_backups(State(state), api::AdminUser(admin_id()))
+            .await
+            .expect("list backups");
+        assert!(listed.items.is_empty(), "unrelated files stay hidden");
+    }
+
+    #[tokio::test]
+    async fn create_backup_keeps_only_the_newest_snapshots() {
+        let pool = setup_pool().await;
+        let state = setup_state(pool, 2);
+        tokio::fs::create_dir_all(&state.config.backup_dir)
+            .await
+            .expect("create backup dir");
+        for name in [
+            "octo-rill-20260101T000000000Z.sqlite3",
+            "octo-rill-20260102T000000000Z.sqlite3",
+        ] {
+            tokio::fs::write(state.config.backup_dir.join(name), b"old")
+                .await
+                .expect("seed old snapshot");
+        }
+
+        let backup = create_backup(state.as_ref()).await.expect("create backup");
+        assert_eq!(
+            backup.pruned,
+            vec!["octo-rill-20260101T000000000Z.sqlite3".to_owned()]
+        );
+        let names = list_backup_names(&state.config.backup_dir)
+            .await
+            .expect("list names");
+        assert_eq!(
+            names,
+            vec![
+                "octo-rill-20260102T000000000Z.sqlite3".to_owned(),
+                backup.name,
+            ]
+        );
+    }
+}
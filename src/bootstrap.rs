@@ -0,0 +1,434 @@
+use std::sync::Arc;
+
+use anyhow::{Context, Result};
+use axum::{Json, extract::State};
+use schemars::JsonSchema;
+use serde::Serialize;
+use serde_json::{Value, json};
+
+use crate::{api, error::ApiError, jobs, state::AppState, sync};
+
+/// Bootstrap stages in run order, each recorded as a child task of the
+/// bootstrap task so progress stays traceable in the task center.
+const BOOTSTRAP_STAGES: &[(&str, &str)] = &[
+    ("starred", jobs::TASK_SYNC_STARRED),
+    ("releases", jobs::TASK_SYNC_RELEASES),
+    ("notifications", jobs::TASK_SYNC_NOTIFICATIONS),
+];
+
+const STAGE_PENDING: &str = "pending";
+const STAGE_RUNNING: &str = "running";
+const STAGE_DONE: &str = "done";
+const STAGE_FAILED: &str = "failed";
+
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct BootstrapStageStatus {
+    /// `starred`, `releases` or `notifications`.
+    stage: String,
+    /// `pending`, `running`, `done` or `failed`.
+    state: String,
+    task_id: Option<String>,
+    error: Option<String>,
+}
+
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct BootstrapStatusResponse {
+    /// Latest bootstrap task of the user; null when none ever ran.
+    task_id: Option<String>,
+    task_status: Option<String>,
+    stages: Vec<BootstrapStageStatus>,
+}
+
+/// Queues the bootstrap chain for `user_id`, handing back the in-flight task
+/// instead when one is already queued or running.
+pub async fn enqueue_user_bootstrap(
+    state: &AppState,
+    user_id: &str,
+    source: &str,
+) -> Result<jobs::EnqueuedTask> {
+    jobs::enqueue_singleton_task_for_requester(
+        state,
+        jobs::NewTask {
+            task_type: jobs::TASK_BOOTSTRAP_USER.to_owned(),
+            payload: json!({ "user_id": user_id }),
+            source: source.to_owned(),
+            requested_by: Some(user_id.to_owned()),
+            parent_task_id: None,
+            priority: jobs::TaskPriority::High,
+        },
+    )
+    .await
+}
+
+/// Login must not fail because the bootstrap could not be queued; the user
+/// can still start it from the onboarding screen.
+pub async fn enqueue_user_bootstrap_best_effort(state: &AppState, user_id: &str, source: &str) {
+    if let Err(err) = enqueue_user_bootstrap(state, user_id, source).await {
+        tracing::warn!(?err, user_id, source, "failed to enqueue user bootstrap");
+    }
+}
+
+async fn run_bootstrap_stage(
+    state: &AppState,
+    task_id: &str,
+    task_type: &str,
+    user_id: &str,
+) -> Result<Value> {
+    match task_type {
+        jobs::TASK_SYNC_STARRED => {
+            let res = sync::sync_starred_for_task(state, task_id, user_id).await?;
+            Ok(serde_json::to_value(res)?)
+        }
+        jobs::TASK_SYNC_RELEASES => {
+            let res = sync::sync_releases_for_task(state, task_id, user_id, false).await?;
+            Ok(serde_json::to_value(res)?)
+        }
+        jobs::TASK_SYNC_NOTIFICATIONS => {
+            let res = sync::sync_notifications(state, user_id).await?;
+            Ok(serde_json::to_value(res)?)
+        }
+        other => anyhow::bail!("unknown bootstrap stage task type: {other}"),
+    }
+}
+
+/// Runs every stage even after an earlier one failed, so a broken
+/// notification scope does not hide the starred and release results.
+pub async fn execute_bootstrap_user_task(
+    state: &AppState,
+    task_id: &str,
+    user_id: &str,
+) -> Result<Value> {
+    let mut stages = serde_json::Map::new();
+    let mut failed = Vec::new();
+    for (stage, task_type) in BOOTSTRAP_STAGES {
+        if jobs::task_cancel_requested(state, task_id).await {
+            return Ok(json!({ "canceled": true, "stages": stages }));
+        }
+        let child = jobs::start_inline_task(
+            state,
+            jobs::NewTask {
+                task_type: (*task_type).to_owned(),
+                payload: json!({ "user_id": user_id }),
+                source: "bootstrap".to_owned(),
+                requested_by: Some(user_id.to_owned()),
+                parent_task_id: Some(task_id.to_owned()),
+                priority: jobs::TaskPriority::High,
+            },
+        )
+        .await?;
+        match run_bootstrap_stage(state, child.task_id.as_str(), task_type, user_id).await {
+            Ok(result) => {
+                jobs::complete_task(
+                    state,
+                    child.task_id.as_str(),
+                    jobs::STATUS_SUCCEEDED,
+                    Some(result.clone()),
+                    None,
+                )
+                .await?;
+                stages.insert((*stage).to_owned(), result);
+            }
+            Err(err) => {
+                tracing::warn!(?err, user_id, stage, "bootstrap stage failed, continuing");
+                jobs::complete_task(
+                    state,
+                    child.task_id.as_str(),
+                    jobs::STATUS_FAILED,
+                    None,
+                    Some(err.to_string()),
+                )
+                .await?;
+                failed.push(*stage);
+            }
+        }
+    }
+    if !failed.is_empty() {
+        anyhow::bail!("bootstrap stages failed: {}", failed.join(", "));
+    }
+    Ok(Value::Object(stages))
+}
+
+fn stage_state(child_status: &str, parent_finished: bool) -> &'static str {
+    match child_status {
+        jobs::STATUS_SUCCEEDED => STAGE_DONE,
+        jobs::STATUS_QUEUED => STAGE_PENDING,
+        // A child still marked running under a finished parent was cut off
+        // by a timeout or restart and will never complete.
+        jobs::STATUS_RUNNING if !parent_finished => STAGE_RUNNING,
+        _ => STAGE_FAILED,
+    }
+}
+
+pub async fn load_bootstrap_status(
+    state: &AppState,
+    user_id: &str,
+) -> Result<BootstrapStatusResponse> {
+    #[derive(Debug, sqlx::FromRow)]
+    struct TaskRow {
+        id: String,
+        task_type: String,
+        status: String,
+        error_message: Option<String>,
+    }
+
+    let parent = sqlx::query_as::<_, TaskRow>(
+        r#"
+        SELECT id, task_type, status, error_message
+        FROM job_tasks
+        WHERE task_type = ?
+          AND requested_by = ?
+        ORDER BY created_at DESC, id DESC
+        LIMIT 1
+        "#,
+    )
+    .bind(jobs::TASK_BOOTSTRAP_USER)
+    .bind(user_id)
+    .fetch_optional(&state.pool)
+    .await
+    .context("failed to load bootstrap task")?;
+
+    let children = match parent.as_ref() {
+        Some(parent) => sqlx::query_as::<_, TaskRow>(
+            r#"
+            SELECT id, task_type, status, error_message
+            FROM job_tasks
+            WHERE parent_task_id = ?
+            ORDER BY created_at DESC, id DESC
+            "#,
+        )
+        .bind(parent.id.as_str())
+        .fetch_all(&state.pool)
+        .await
+        .context("failed to load bootstrap stage tasks")?,
+        None => Vec::new(),
+    };
+
+    let parent_finished = parent.as_ref().is_some_and(|parent| {
+        !matches!(
+            parent.status.as_str(),
+            jobs::STATUS_QUEUED | jobs::STATUS_RUNNING
+        )
+    });
+    let stages = BOOTSTRAP_STAGES
+        .iter()
+        .map(|(stage, task_type)| {
+            let child = children.iter().find(|child| child.task_type == *task_type);
+            match child {
+                Some(child) => BootstrapStageStatus {
+                    stage: (*stage).to_owned(),
+                    state: stage_state(child.status.as_str(), parent_finished).to_owned(),
+                    task_id: Some(child.id.clone()),
+                    error: child.error_message.clone(),
+                },
+                // A finished parent that never reached this stage was
+                // canceled or stopped before it.
+                None => BootstrapStageStatus {
+                    stage: (*stage).to_owned(),
+                    state: if parent_finished
+                        && parent
+                            .as_ref()
+                            .is_some_and(|parent| parent.status != jobs::STATUS_SUCCEEDED)
+                    {
+                        STAGE_FAILED
+                    } else {
+                        STAGE_PENDING
+                    }
+                    .to_owned(),
+                    task_id: None,
+                    error: None,
+                },
+            }
+        })
+        .collect();
+
+    Ok(BootstrapStatusResponse {
+        task_id: parent.as_ref().map(|parent| parent.id.clone()),
+        task_status: parent.map(|parent| parent.status),
+        stages,
+    })
+}
+
+pub async fn get_bootstrap_status(
+    State(state): State<Arc<AppState>>,
+    api::AuthedUser(user_id): api::AuthedUser,
+) -> Result<Json<BootstrapStatusResponse>, ApiError> {
+    let status = load_bootstrap_status(state.as_ref(), user_id.as_str())
+        .await
+        .map_err(ApiError::internal)?;
+    Ok(Json(status))
+}
+
+/// Reruns the bootstrap chain. While one is queued or running, its status is
+/// returned instead of starting another.
+pub async fn start_bootstrap(
+    State(state): State<Arc<AppState>>,
+    api::AuthedUser(user_id): api::AuthedUser,
+) -> Result<Json<BootstrapStatusResponse>, ApiError> {
+    enqueue_user_bootstrap(state.as_ref(), user_id.as_str(), "api.bootstrap")
+        .await
+        .map_err(ApiError::internal)?;
+    let status = load_bootstrap_status(state.as_ref(), user_id.as_str())
+        .await
+        .map_err(ApiError::internal)?;
+    Ok(Json(status))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use sqlx::{
+        SqlitePool,
+        sqlite::{SqliteConnectOptions, SqlitePoolOptions},
+    };
+
+    use crate::config::AppConfig;
+
+    fn member_id() -> String {
+        crate::local_id::test_local_id("bootstrap-member")
+    }
+
+    async fn setup_pool() -> SqlitePool {
+        let database_path = std::env::temp_dir().join(format!(
+            "octo-rill-bootstrap-test-{}.db",
+            crate::local_id::generate_local_id(),
+        ));
+        let options = SqliteConnectOptions::new()
+            .filename(&database_path)
+            .create_if_missing(true);
+        let pool = SqlitePoolOptions::new()
+            .max_connections(1)
+            .connect_with(options)
+            .await
+            .expect("create sqlite db");
+        sqlx::migrate!("./migrations")
+            .run(&pool)
+            .await
+            .expect("run migrations");
+        pool
+    }
+
+    async fn seed_user(pool: &SqlitePool, user_id: &str, github_user_id: i64) {
+        sqlx::query(
+            r#"
+            INSERT INTO users (id, github_user_id, login, created_at, updated_at)
+            VALUES (?, ?, ?, '2026-02-01T00:00:00Z', '2026-02-01T00:00:00Z')
+            "#,
+        )
+        .bind(user_id)
+        .bind(github_user_id)
+        .bind(format!("user-{github_user_id}"))
+        .execute(pool)
+        .await
+        .expect("seed user");
+    }
+
+    fn setup_state(pool: SqlitePool) -> Arc<AppState> {
+        Arc::new(AppState::for_tests(pool, AppConfig::for_tests()))
+    }
+
+    async fn start_child(state: &AppState, parent_task_id: &str, task_type: &str) -> String {
+        jobs::start_inline_task(
+            state,
+            jobs::NewTask {
+                task_type: task_type.to_owned(),
+                payload: json!({ "user_id": member_id() }),
+                source: "bootstrap".to_owned(),
+                requested_by: Some(member_id()),
+                parent_task_id: Some(parent_task_id.to_owned()),
+                priority: jobs::TaskPriority::High,
+            },
+        )
+        .await
+        .expect("start child task")
+        .task_id
+    }
+
+    #[tokio::test]
+    async fn bootstrap_status_reflects_child_task_states() {
+        let pool = setup_pool().await;
+        seed_user(&pool, &member_id(), 1).await;
+        let state = setup_state(pool);
+
+        let Json(empty) = get_bootstrap_status(State(state.clone()), api::AuthedUser(member_id()))
+            .await
+            .expect("status without bootstrap");
+        assert!(empty.task_id.is_none());
+        assert!(
+            empty
+                .stages
+                .iter()
+                .all(|stage| stage.state == STAGE_PENDING)
+        );
+
+        let parent = enqueue_user_bootstrap(state.as_ref(), &member_id(), "test")
+            .await
+            .expect("enqueue bootstrap");
+        let starred = start_child(state.as_ref(), &parent.task_id, jobs::TASK_SYNC_STARRED).await;
+        jobs::complete_task(state.as_ref(), &starred, jobs::STATUS_SUCCEEDED, None, None)
+            .await
+            .expect("finish starred");
+        let releases = start_child(state.as_ref(), &parent.task_id, jobs::TASK_SYNC_RELEASES).await;
+        jobs::complete_task(
+            state.as_ref(),
+            &releases,
+            jobs::STATUS_FAILED,
+            None,
+            Some("github unavailable".to_owned()),
+        )
+        .await
+        .expect("fail releases");
+        start_child(
+            state.as_ref(),
+            &parent.task_id,
+            jobs::TASK_SYNC_NOTIFICATIONS,
+        )
+        .await;
+
+        let Json(status) = get_bootstrap_status(State(state.clone()), api::AuthedUser(member_id()))
+            .await
+            .expect("bootstrap status");
+        assert_eq!(status.task_id.as_deref(), Some(parent.task_id.as_str()));
+        let states = status
+            .stages
+            .iter()
+            .map(|stage| (stage.stage.as_str(), stage.state.as_str()))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            states,
+            vec![
+                ("starred", STAGE_DONE),
+                ("releases", STAGE_FAILED),
+                ("notifications", STAGE_RUNNING),
+            ]
+        );
+        assert_eq!(
+            status.stages[1].error.as_deref(),
+            Some("github unavailable")
+        );
+    }
+
+    #[tokio::test]
+    async fn start_bootstrap_returns_in_flight_task() {
+        let pool = setup_pool().await;
+        seed_user(&pool, &member_id(), 1).await;
+        let state = setup_state(pool.clone());
+
+        let Json(first) = start_bootstrap(State(state.clone()), api::AuthedUser(member_id()))
+            .await
+            .expect("first bootstrap");
+        let Json(second) = start_bootstrap(State(state.clone()), api::AuthedUser(member_id()))
+            .await
+            .expect("second bootstrap");
+        assert!(first.task_id.is_some());
+        assert_eq!(first.task_id, second.task_id);
+
+        let count =
+            sqlx::query_scalar::<_, i64>("SELECT COUNT(*) FROM job_tasks WHERE task_type = ?")
+                .bind(jobs::TASK_BOOTSTRAP_USER)
+                .fetch_one(&pool)
+                .await
+                .expect("count bootstrap tasks");
+        assert_eq!(count, 1);
+    }
+}
@@ -1,4 +1,4 @@
-use std::{collections::BTreeSet, str::FromStr};
+use std::{collections::BTreeSet, str::FromStr, time::Duration as StdDuration};
 
 use anyhow::{Context, Result};
 use chrono::{
@@ -6,13 +6,27 @@ use chrono::{
     TimeZone, Timelike, Utc,
 };
 use chrono_tz::Tz;
+use lettre::{
+    AsyncSmtpTransport, AsyncTransport, Message, Tokio1Executor, message::MultiPart,
+    transport::smtp::authentication::Credentials,
+};
+use pulldown_cmark::{Event, Options, Parser, html};
 use sqlx::FromRow;
 
-use crate::{config::AppConfig, state::AppState};
+use crate::{
+    ai::StoredBrief,
+    config::{AppConfig, SmtpConfig, SmtpTls},
+    state::AppState,
+};
 
 pub const DEFAULT_DAILY_BRIEF_TIME_ZONE: &str = "Asia/Shanghai";
 const SUPPORTED_TIME_ZONE_SAMPLE_YEAR: i32 = 2026;
 const SUPPORTED_TIME_ZONE_SCAN_DAYS: i64 = 400;
+pub const BRIEF_DELIVERY_CHANNEL_EMAIL: &str = "email";
+const BRIEF_EMAIL_SEND_TIMEOUT: StdDuration = StdDuration::from_secs(30);
+pub const ON_DEMAND_BRIEF_MIN_WINDOW_HOURS: i64 = 1;
+pub const ON_DEMAND_BRIEF_MAX_WINDOW_HOURS: i64 = 72;
+pub const ON_DEMAND_BRIEF_MAX_WINDOW_DAYS: i64 = 7;
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct DailyBriefPreferences {
@@ -134,6 +148,9 @@ fn resolve_tz(raw: &str) -> Result<Tz> {
     Tz::from_str(raw).with_context(|| format!("invalid IANA time zone: {raw}"))
 }
 
+/// Pins a wall-clock time to one instant: ambiguous fall-back times take the
+/// earlier offset and times inside a spring-forward gap move to the first
+/// valid minute after it.
 fn resolve_local_datetime(time_zone: Tz, naive: NaiveDateTime) -> DateTime<Tz> {
     match time_zone.from_local_datetime(&naive) {
         LocalResult::Single(dt) => dt,
@@ -334,6 +351,31 @@ pub fn compute_current_daily_window(
     compute_daily_window_for_key_date(preferences, key_date)
 }
 
+/// Builds a window for an explicit UTC range. The snapshot is labelled by the
+/// local date and time at which the range ends in the user's time zone.
+pub fn compute_daily_window_for_range(
+    preferences: &DailyBriefPreferences,
+    start_utc: DateTime<Utc>,
+    end_utc: DateTime<Utc>,
+) -> Result<DailyWindow> {
+    if end_utc <= start_utc {
+        anyhow::bail!("brief window end must be after its start");
+    }
+    let time_zone = resolve_tz(&preferences.time_zone)?;
+    let end_local = end_utc.with_timezone(&time_zone);
+    let key_date = end_local.date_naive();
+
+    Ok(DailyWindow {
+        key_date,
+        display_date: key_date.to_string(),
+        start_utc,
+        end_utc,
+        end_local: end_local.fixed_offset(),
+        effective_time_zone: preferences.time_zone.clone(),
+        effective_local_boundary: end_local.format("%H:%M").to_string(),
+    })
+}
+
 pub fn current_utc_offset_minutes(
     preferences: &DailyBriefPreferences,
     now_utc: DateTime<Utc>,
@@ -500,6 +542,258 @@ pub async fn backfill_legacy_daily_brief_preferences(state: &AppState) -> Result
     Ok(updated)
 }
 
+#[derive(Debug, FromRow)]
+struct BriefEmailRecipientRow {
+    brief_email_enabled: i64,
+    recipient: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BriefDeliveryOutcome {
+    pub status: &'static str,
+    pub recipient: Option<String>,
+    pub error: Option<String>,
+}
+
+/// Renders brief markdown into a standalone HTML email body. Raw HTML in the
+/// markdown is escaped rather than passed through, since brief content
+/// embeds third-party release notes.
+pub fn render_brief_email_html(date: &str, markdown: &str) -> String {
+    let parser = Parser::new_ext(
+        markdown,
+        Options::ENABLE_TABLES | Options::ENABLE_STRIKETHROUGH | Options::ENABLE_TASKLISTS,
+    )
+    .map(|event| match event {
+        Event::Html(raw) | Event::InlineHtml(raw) => Event::Text(raw),
+        other => other,
+    });
+    let mut body = String::new();
+    html::push_html(&mut body, parser);
+    format!(
+        "<!doctype html>\n<html><head><meta charset=\"utf-8\"><title>OctoRill 日报 {date}</title></head>\n<body style=\"font-family:-apple-system,BlinkMacSystemFont,'Segoe UI',sans-serif;line-height:1.6;max-width:720px;margin:0 auto;padding:16px;\">\n{body}</body></html>\n",
+        date = escape_html_text(date),
+    )
+}
+
+fn escape_html_text(raw: &str) -> String {
+    raw.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+async fn send_brief_email(
+    smtp: &SmtpConfig,
+    recipient: &str,
+    subject: &str,
+    markdown: &str,
+    html_body: String,
+) -> Result<()> {
+    let message = Message::builder()
+        .from(smtp.from.parse().context("invalid SMTP_FROM address")?)
+        .to(recipient.parse().context("invalid recipient address")?)
+        .subject(subject)
+        .multipart(MultiPart::alternative_plain_html(
+            markdown.to_owned(),
+            html_body,
+        ))
+        .context("failed to build brief email")?;
+
+    let builder = match smtp.tls {
+        SmtpTls::Tls => AsyncSmtpTransport::<Tokio1Executor>::relay(&smtp.host)
+            .context("failed to configure SMTP TLS transport")?,
+        SmtpTls::StartTls => AsyncSmtpTransport::<Tokio1Executor>::starttls_relay(&smtp.host)
+            .context("failed to configure SMTP STARTTLS transport")?,
+        SmtpTls::None => AsyncSmtpTransport::<Tokio1Executor>::builder_dangerous(&smtp.host),
+    };
+    let mut builder = builder
+        .port(smtp.port)
+        .timeout(Some(BRIEF_EMAIL_SEND_TIMEOUT));
+    if let (Some(username), Some(password)) = (smtp.username.as_ref(), smtp.password.as_ref()) {
+        builder = builder.credentials(Credentials::new(username.clone(), password.clone()));
+    }
+    builder
+        .build()
+        .send(message)
+        .await
+        .context("SMTP send failed")?;
+    Ok(())
+}
+
+#[allow(clippy::too_many_arguments)]
+async fn record_brief_delivery(
+    state: &AppState,
+    user_id: &str,
+    brief_id: &str,
+    recipient: Option<&str>,
+    status: &str,
+    error_text: Option<&str>,
+    html_body: Option<&str>,
+    sent_at: Option<&str>,
+) -> Result<()> {
+    let now = Utc::now().to_rfc3339();
+    sqlx::query(
+        r#"
+        INSERT INTO brief_deliveries (
+          id, user_id, brief_id, channel, recipient, status, error_text, html_body, sent_at,
+          created_at, updated_at
+        )
+        VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+        ON CONFLICT(brief_id, channel) DO UPDATE SET
+          recipient = excluded.recipient,
+          status = excluded.status,
+          error_text = excluded.error_text,
+          html_body = excluded.html_body,
+          sent_at = excluded.sent_at,
+          updated_at = excluded.updated_at
+        "#,
+    )
+    .bind(crate::local_id::generate_local_id())
+    .bind(user_id)
+    .bind(brief_id)
+    .bind(BRIEF_DELIVERY_CHANNEL_EMAIL)
+    .bind(recipient)
+    .bind(status)
+    .bind(error_text)
+    .bind(html_body)
+    .bind(sent_at)
+    .bind(now.as_str())
+    .bind(now.as_str())
+    .execute(&state.pool)
+    .await
+    .with_context(|| format!("failed to record brief delivery for brief {brief_id}"))?;
+    Ok(())
+}
+
+/// Emails a freshly generated brief when the user opted in. Returns `None`
+/// when nothing was attempted (opted out, or already sent for this brief).
+pub async fn deliver_brief_email(
+    state: &AppState,
+    user_id: &str,
+    brief: &StoredBrief,
+) -> Result<Option<BriefDeliveryOutcome>> {
+    let row = sqlx::query_as::<_, BriefEmailRecipientRow>(
+        r#"
+        SELECT
+          brief_email_enabled,
+          COALESCE(NULLIF(TRIM(brief_email), ''), NULLIF(TRIM(email), '')) AS recipient
+        FROM users
+        WHERE id = ?
+        LIMIT 1
+        "#,
+    )
+    .bind(user_id)
+    .fetch_optional(&state.pool)
+    .await
+    .context("failed to load brief email preference")?;
+    let Some(row) = row.filter(|row| row.brief_email_enabled != 0) else {
+        return Ok(None);
+    };
+
+    let already_sent = sqlx::query_scalar::<_, i64>(
+        r#"
+        SELECT COUNT(*)
+        FROM brief_deliveries
+        WHERE brief_id = ? AND channel = ? AND status = 'sent'
+        "#,
+    )
+    .bind(brief.id.as_str())
+    .bind(BRIEF_DELIVERY_CHANNEL_EMAIL)
+    .fetch_one(&state.pool)
+    .await
+    .context("failed to check existing brief delivery")?;
+    if already_sent > 0 {
+        return Ok(None);
+    }
+
+    let recipient = row.recipient;
+    let outcome = match (state.config.smtp.as_ref(), recipient.as_deref()) {
+        (None, _) => BriefDeliveryOutcome {
+            status: "skipped",
+            recipient: recipient.clone(),
+            error: Some("SMTP is not configured".to_owned()),
+        },
+        (Some(_), None) => BriefDeliveryOutcome {
+            status: "failed",
+            recipient: None,
+            error: Some("no email address on file".to_owned()),
+        },
+        (Some(smtp), Some(address)) => {
+            let html_body = render_brief_email_html(&brief.date, &brief.content_markdown);
+            if smtp.dry_run {
+                record_brief_delivery(
+                    state,
+                    user_id,
+                    &brief.id,
+                    Some(address),
+                    "dry_run",
+                    None,
+                    Some(html_body.as_str()),
+                    None,
+                )
+                .await?;
+                return Ok(Some(BriefDeliveryOutcome {
+                    status: "dry_run",
+                    recipient: Some(address.to_owned()),
+                    error: None,
+                }));
+            }
+            let subject = format!("OctoRill 日报 {}", brief.date);
+            match send_brief_email(smtp, address, &subject, &brief.content_markdown, html_body)
+                .await
+            {
+                Ok(()) => BriefDeliveryOutcome {
+                    status: "sent",
+                    recipient: Some(address.to_owned()),
+                    error: None,
+                },
+                Err(err) => BriefDeliveryOutcome {
+                    status: "failed",
+                    recipient: Some(address.to_owned()),
+                    error: Some(format!("{err:#}")),
+                },
+            }
+        }
+    };
+
+    let sent_at = (outcome.status == "sent").then(|| Utc::now().to_rfc3339());
+    record_brief_delivery(
+        state,
+        user_id,
+        &brief.id,
+        outcome.recipient.as_deref(),
+        outcome.status,
+        outcome.error.as_deref(),
+        None,
+        sent_at.as_deref(),
+    )
+    .await?;
+    Ok(Some(outcome))
+}
+
+/// Delivery never fails brief generation; errors are only logged.
+pub async fn deliver_brief_email_best_effort(state: &AppState, user_id: &str, brief: &StoredBrief) {
+    match deliver_brief_email(state, user_id, brief).await {
+        Ok(Some(outcome)) if outcome.status == "failed" => {
+            tracing::warn!(
+                user_id,
+                brief_id = %brief.id,
+                error = outcome.error.as_deref().unwrap_or_default(),
+                "brief email delivery failed"
+            );
+        }
+        Ok(_) => {}
+        Err(err) => {
+            tracing::warn!(
+                user_id,
+                brief_id = %brief.id,
+                error = %format!("{err:#}"),
+                "failed to record brief email delivery"
+            );
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -562,6 +856,16 @@ mod tests {
             static_dir: None,
             task_log_dir: std::env::temp_dir().join("octo-rill-briefs-task-logs-tests"),
             job_worker_concurrency: 4,
+            reaction_viewer_ttl_secs: 600,
+            release_backfill_limit: 10,
+            star_purge_grace_days: 14,
+            notification_archive_days: 90,
+            notification_retention_days: 365,
+            task_retention_days: 30,
+            backup_dir: std::env::temp_dir().join("octo-rill-backups-tests"),
+            backup_retention_count: 7,
+            shutdown_grace_secs: 30,
+            task_timeouts: crate::jobs::TaskTimeouts::default(),
             encryption_key: encryption_key.clone(),
             github: GitHubOAuthConfig {
                 client_id: "test-client-id".to_owned(),
@@ -569,12 +873,23 @@ mod tests {
                 redirect_url: Url::parse("http://127.0.0.1:58090/auth/callback")
                     .expect("parse github redirect"),
             },
+            github_app: None,
             linuxdo: None,
             ai: None,
             ai_max_concurrency: 1,
+            ai_translate_concurrency: 2,
+            ai_skip_translation_cjk_percent: 30,
+            ai_daily_token_budget_per_user: None,
+            ai_request_interval_ms: 0,
             ai_daily_at_local: None,
             app_default_time_zone: DEFAULT_DAILY_BRIEF_TIME_ZONE.to_owned(),
+            smtp: None,
             logging: crate::observability::LoggingThresholds::default(),
+            metrics_token: None,
+            trust_request_id_header: false,
+            trust_forwarded_headers: false,
+            readyz_check_github: false,
+            rate_limits: crate::rate_limit::RateLimits::default(),
         };
         let github_oauth = build_oauth_client(&config).expect("build oauth client");
         let webauthn = crate::state::build_webauthn(&config).expect("build webauthn");
@@ -599,6 +914,12 @@ mod tests {
             webauthn,
             encryption_key,
             runtime_owner_id: "briefs-test-runtime-owner".to_owned(),
+            feed_events: crate::feed_events::FeedEventHub::default(),
+            admin_mutation_limiter: crate::admin_audit::AdminMutationLimiter::default(),
+            translation_flights: crate::ai::TranslationSingleflight::default(),
+            request_limiter: crate::rate_limit::RequestRateLimiter::default(),
+            github_probe: crate::health::GitHubProbe::default(),
+            github_app_tokens: None,
         })
     }
 
@@ -647,6 +968,73 @@ mod tests {
         assert_eq!(window.effective_local_boundary, "03:00");
     }
 
+    #[test]
+    fn compute_window_uses_earlier_offset_for_dst_overlap() {
+        let preferences = DailyBriefPreferences {
+            local_time: NaiveTime::from_hms_opt(1, 0, 0).expect("01:00"),
+            time_zone: "America/New_York".to_owned(),
+        };
+
+        let window = compute_daily_window_for_key_date(
+            &preferences,
+            NaiveDate::from_ymd_opt(2026, 11, 1).expect("fall-back date"),
+        )
+        .expect("window");
+
+        assert_eq!(window.end_utc.to_rfc3339(), "2026-11-01T05:00:00+00:00");
+        assert_eq!(window.start_utc.to_rfc3339(), "2026-10-31T05:00:00+00:00");
+        assert_eq!(window.effective_local_boundary, "01:00");
+    }
+
+    #[test]
+    fn key_date_for_now_follows_user_time_zone_not_utc_date() {
+        let preferences = DailyBriefPreferences {
+            local_time: NaiveTime::from_hms_opt(8, 0, 0).expect("08:00"),
+            time_zone: "Asia/Shanghai".to_owned(),
+        };
+        let before_boundary = Utc
+            .with_ymd_and_hms(2026, 4, 11, 23, 30, 0)
+            .single()
+            .expect("valid datetime");
+        let after_boundary = Utc
+            .with_ymd_and_hms(2026, 4, 12, 0, 30, 0)
+            .single()
+            .expect("valid datetime");
+
+        assert_eq!(
+            key_date_for_now(&preferences, before_boundary).expect("key date"),
+            NaiveDate::from_ymd_opt(2026, 4, 11).unwrap()
+        );
+        let window =
+            compute_current_daily_window(&preferences, after_boundary).expect("current window");
+        assert_eq!(
+            window.key_date,
+            NaiveDate::from_ymd_opt(2026, 4, 12).unwrap()
+        );
+        assert_eq!(window.end_utc.to_rfc3339(), "2026-04-12T00:00:00+00:00");
+    }
+
+    #[test]
+    fn compute_window_for_range_labels_by_local_end() {
+        let preferences = DailyBriefPreferences {
+            local_time: NaiveTime::from_hms_opt(8, 0, 0).expect("08:00"),
+            time_zone: "Asia/Shanghai".to_owned(),
+        };
+        let end_utc = Utc
+            .with_ymd_and_hms(2026, 4, 12, 17, 30, 0)
+            .single()
+            .expect("valid datetime");
+
+        let window =
+            compute_daily_window_for_range(&preferences, end_utc - Duration::hours(6), end_utc)
+                .expect("window");
+
+        assert_eq!(window.display_date, "2026-04-13");
+        assert_eq!(window.effective_local_boundary, "01:30");
+        assert_eq!(window.start_utc.to_rfc3339(), "2026-04-12T11:30:00+00:00");
+        assert!(compute_daily_window_for_range(&preferences, end_utc, end_utc).is_err());
+    }
+
     #[test]
     fn validate_hour_aligned_time_zone_rejects_half_hour_offsets() {
         let reference_utc = Utc
@@ -787,4 +1175,161 @@ mod tests {
         assert_eq!(row.0, None);
         assert_eq!(row.1, None);
     }
+
+    fn sample_brief() -> StoredBrief {
+        StoredBrief {
+            id: "brief-email-test".to_owned(),
+            date: "2026-02-26".to_owned(),
+            window_start: "2026-02-25T00:00:00Z".to_owned(),
+            window_end: "2026-02-26T00:00:00Z".to_owned(),
+            effective_time_zone: DEFAULT_DAILY_BRIEF_TIME_ZONE.to_owned(),
+            effective_local_boundary: "08:00".to_owned(),
+            content_markdown: "## Releases\n\n- **octo/rill** v1.0 <script>x</script>".to_owned(),
+            release_ids: vec![1],
+            coverage: None,
+            sources: Vec::new(),
+        }
+    }
+
+    fn smtp_config(port: u16, dry_run: bool) -> SmtpConfig {
+        SmtpConfig {
+            host: "127.0.0.1".to_owned(),
+            port,
+            username: None,
+            password: None,
+            from: "OctoRill <brief@example.com>".to_owned(),
+            tls: SmtpTls::None,
+            dry_run,
+        }
+    }
+
+    fn with_smtp(state: &Arc<AppState>, smtp: Option<SmtpConfig>) -> Arc<AppState> {
+        let mut config = state.config.clone();
+        config.smtp = smtp;
+        Arc::new(AppState {
+            config,
+            ..state.as_ref().clone()
+        })
+    }
+
+    async fn enable_brief_email(pool: &SqlitePool, address: Option<&str>) {
+        sqlx::query(
+            r#"
+            UPDATE users
+            SET brief_email_enabled = 1, brief_email = ?
+            WHERE id = 'user-briefs-test'
+            "#,
+        )
+        .bind(address)
+        .execute(pool)
+        .await
+        .expect("enable brief email");
+    }
+
+    async fn load_delivery(
+        pool: &SqlitePool,
+    ) -> (String, Option<String>, Option<String>, Option<String>) {
+        sqlx::query_as(
+            r#"
+            SELECT status, recipient, error_text, html_body
+            FROM brief_deliveries
+            WHERE brief_id = 'brief-email-test' AND channel = 'email'
+            "#,
+        )
+        .fetch_one(pool)
+        .await
+        .expect("load brief delivery")
+    }
+
+    #[test]
+    fn render_brief_email_html_renders_markdown_and_escapes_raw_html() {
+        let html = render_brief_email_html("2026-02-26", "## Title\n\n<b>raw</b> **bold**");
+
+        assert!(html.contains("<h2>Title</h2>"));
+        assert!(html.contains("<strong>bold</strong>"));
+        assert!(html.contains("&lt;b&gt;raw&lt;/b&gt;"));
+        assert!(html.contains("OctoRill 日报 2026-02-26"));
+    }
+
+    #[tokio::test]
+    async fn deliver_brief_email_skips_users_who_did_not_opt_in() {
+        let pool = setup_pool().await;
+        let state = with_smtp(&setup_state(pool.clone()), Some(smtp_config(25, true)));
+
+        let outcome = deliver_brief_email(state.as_ref(), "user-briefs-test", &sample_brief())
+            .await
+            .expect("deliver brief email");
+
+        assert_eq!(outcome, None);
+        let rows: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM brief_deliveries")
+            .fetch_one(&pool)
+            .await
+            .expect("count deliveries");
+        assert_eq!(rows, 0);
+    }
+
+    #[tokio::test]
+    async fn deliver_brief_email_dry_run_records_rendered_html_without_sending() {
+        let pool = setup_pool().await;
+        enable_brief_email(&pool, Some("reader@example.com")).await;
+        // Port 9 would refuse the connection, so a dry run must never dial it.
+        let state = with_smtp(&setup_state(pool.clone()), Some(smtp_config(9, true)));
+
+        let outcome = deliver_brief_email(state.as_ref(), "user-briefs-test", &sample_brief())
+            .await
+            .expect("deliver brief email")
+            .expect("delivery outcome");
+
+        assert_eq!(outcome.status, "dry_run");
+        let (status, recipient, error_text, html_body) = load_delivery(&pool).await;
+        assert_eq!(status, "dry_run");
+        assert_eq!(recipient.as_deref(), Some("reader@example.com"));
+        assert_eq!(error_text, None);
+        let html_body = html_body.expect("dry run html body");
+        assert!(html_body.contains("<h2>Releases</h2>"));
+        assert!(!html_body.contains("<script>"));
+    }
+
+    #[tokio::test]
+    async fn deliver_brief_email_records_failure_without_erroring() {
+        let pool = setup_pool().await;
+        enable_brief_email(&pool, Some("reader@example.com")).await;
+        let closed_port = {
+            let listener = std::net::TcpListener::bind("127.0.0.1:0").expect("bind probe port");
+            listener.local_addr().expect("probe addr").port()
+        };
+        let state = with_smtp(
+            &setup_state(pool.clone()),
+            Some(smtp_config(closed_port, false)),
+        );
+
+        deliver_brief_email_best_effort(state.as_ref(), "user-briefs-test", &sample_brief()).await;
+
+        let (status, recipient, error_text, html_body) = load_delivery(&pool).await;
+        assert_eq!(status, "failed");
+        assert_eq!(recipient.as_deref(), Some("reader@example.com"));
+        assert!(
+            error_text
+                .expect("failure error")
+                .contains("SMTP send failed")
+        );
+        assert_eq!(html_body, None);
+    }
+
+    #[tokio::test]
+    async fn deliver_brief_email_marks_missing_smtp_config_as_skipped() {
+        let pool = setup_pool().await;
+        enable_brief_email(&pool, None).await;
+        let state = setup_state(pool.clone());
+
+        let outcome = deliver_brief_email(state.as_ref(), "user-briefs-test", &sample_brief())
+            .await
+            .expect("deliver brief email")
+            .expect("delivery outcome");
+
+        assert_eq!(outcome.status, "skipped");
+        let (status, _, error_text, _) = load_delivery(&pool).await;
+        assert_eq!(status, "skipped");
+        assert_eq!(error_text.as_deref(), Some("SMTP is not configured"));
+    }
 }
@@ -0,0 +1,512 @@
+use std::sync::Arc;
+
+use axum::{
+    body::Body,
+    extract::{Request, State},
+    http::{HeaderMap, HeaderValue, Method, StatusCode, header},
+    middleware::Next,
+    response::Response,
+};
+use tower_sessions::Session;
+
+use crate::{ai, api, error::ApiError, state::AppState};
+
+/// Which list a conditional request targets; each reads its freshness from
+/// different tables.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ListFreshness {
+    Feed,
+    Starred,
+    Briefs,
+}
+
+impl ListFreshness {
+    fn as_str(self) -> &'static str {
+        match self {
+            Self::Feed => "feed",
+            Self::Starred => "starred",
+            Self::Briefs => "briefs",
+        }
+    }
+
+    /// One aggregate query: count and newest timestamp of every table the
+    /// list renders from. Any insert, update or delete moves one of them.
+    fn signature_sql(self) -> &'static str {
+        match self {
+            Self::Feed => {
+                r#"
+                SELECT
+                  (SELECT COALESCE(updated_at, '') FROM users WHERE id = ?)
+                  || '|' || (
+                    SELECT COUNT(*) || ':' || COALESCE(MAX(r.updated_at), '')
+                    FROM repo_releases r
+                    JOIN user_release_visible_repos v ON v.repo_id = r.repo_id
+                    WHERE v.user_id = ?
+                  )
+                  || '|' || (
+                    SELECT COUNT(*) || ':' || COALESCE(MAX(updated_at), '')
+                    FROM social_activity_events
+                    WHERE user_id = ?
+                  )
+                  || '|' || (
+                    SELECT COUNT(*) || ':' || COALESCE(MAX(updated_at), '')
+                    FROM ai_translations
+                    WHERE user_id = ?
+                  )
+                  || '|' || (
+                    SELECT COUNT(*) || ':' || COALESCE(MAX(updated_at), '')
+                    FROM bookmarks
+                    WHERE user_id = ?
+                  )
+                  || '|' || (
+                    SELECT COUNT(*) || ':' || COALESCE(MAX(created_at), '')
+                    FROM muted_repos
+                    WHERE user_id = ?
+                  )
+                  || '|' || (
+                    SELECT COUNT(*) || ':' || COALESCE(MAX(created_at), '')
+                    FROM repo_translation_opt_outs
+                    WHERE user_id = ?
+                  )
+                  || '|' || (
+                    SELECT COUNT(*) || ':' || COALESCE(MAX(seen_at), '')
+                    FROM release_seen
+                    WHERE user_id = ?
+                  )
+                "#
+            }
+            Self::Starred => {
+                r#"
+                SELECT
+                  (
+                    SELECT COUNT(*) || ':' || COALESCE(MAX(updated_at), '')
+                    FROM starred_repos
+                    WHERE user_id = ?
+                  )
+                  || '|' || (
+                    SELECT COUNT(*) || ':' || COALESCE(MAX(created_at), '')
+                    FROM muted_repos
+                    WHERE user_id = ?
+                  )
+                  || '|' || (
+                    SELECT COUNT(*) || ':' || COALESCE(MAX(created_at), '')
+                    FROM repo_translation_opt_outs
+                    WHERE user_id = ?
+                  )
+                "#
+            }
+            Self::Briefs => {
+                r#"
+                SELECT
+                  (SELECT COALESCE(updated_at, '') FROM users WHERE id = ?)
+                  || '|' || (
+                    SELECT COUNT(*) || ':' || COALESCE(MAX(created_at), '') || ':'
+                      || COALESCE(MAX(updated_at), '')
+                    FROM briefs
+                    WHERE user_id = ?
+                  )
+                  || '|' || (
+                    SELECT COUNT(*) || ':' || COALESCE(MAX(updated_at), '')
+                    FROM ai_translations
+                    WHERE user_id = ? AND entity_type = 'brief'
+                  )
+                  || '|' || (
+                    SELECT COUNT(*) || ':' || COALESCE(MAX(updated_at), '')
+                    FROM brief_feedback
+                    WHERE user_id = ?
+                  )
+                "#
+            }
+        }
+    }
+
+    fn bind_count(self) -> usize {
+        match self {
+            Self::Feed => 8,
+            Self::Starred => 3,
+            Self::Briefs => 4,
+        }
+    }
+}
+
+pub async fn feed_etag(
+    State(state): State<Arc<AppState>>,
+    session: Session,
+    request: Request,
+    next: Next,
+) -> Response {
+    conditional_list(state.as_ref(), &session, ListFreshness::Feed, request, next).await
+}
+
+pub async fn starred_etag(
+    State(state): State<Arc<AppState>>,
+    session: Session,
+    request: Request,
+    next: Next,
+) -> Response {
+    conditional_list(
+        state.as_ref(),
+        &session,
+        ListFreshness::Starred,
+        request,
+        next,
+    )
+    .await
+}
+
+pub async fn briefs_etag(
+    State(state): State<Arc<AppState>>,
+    session: Session,
+    request: Request,
+    next: Next,
+) -> Response {
+    conditional_list(
+        state.as_ref(),
+        &session,
+        ListFreshness::Briefs,
+        request,
+        next,
+    )
+    .await
+}
+
+/// Answers `304` before the handler runs when the list is unchanged, so the
+/// joins, translation hashing and live reaction fetch are skipped. Anything
+/// that is not a plain first-page read passes straight through; so do
+/// unauthenticated requests, leaving the handler to reject them.
+async fn conditional_list(
+    state: &AppState,
+    session: &Session,
+    list: ListFreshness,
+    request: Request,
+    next: Next,
+) -> Response {
+    if request.method() != Method::GET || !is_first_page(request.uri().query()) {
+        return next.run(request).await;
+    }
+    let Ok(user_id) = api::require_active_user_id(state, session).await else {
+        return next.run(request).await;
+    };
+    let etag = match list_etag(state, &user_id, list, request.uri().query()).await {
+        Ok(etag) => etag,
+        Err(err) => {
+            tracing::warn!(
+                list = list.as_str(),
+                error_code = err.code(),
+                "list etag lookup failed; serving full response"
+            );
+            return next.run(request).await;
+        }
+    };
+
+    let mut response = if if_none_match_matches(request.headers(), &etag) {
+        let mut response = Response::new(Body::empty());
+        *response.status_mut() = StatusCode::NOT_MODIFIED;
+        response
+    } else {
+        next.run(request).await
+    };
+    if matches!(response.status(), StatusCode::OK | StatusCode::NOT_MODIFIED)
+        && let Ok(value) = HeaderValue::from_str(&etag)
+    {
+        let headers = response.headers_mut();
+        headers.insert(header::ETAG, value);
+        headers.insert(
+            header::CACHE_CONTROL,
+            HeaderValue::from_static("private, no-cache"),
+        );
+    }
+    response
+}
+
+/// Later pages are cheap cursor reads and are rarely refetched; `mark_seen`
+/// has a side effect a `304` would swallow.
+fn is_first_page(query: Option<&str>) -> bool {
+    let Some(query) = query else {
+        return true;
+    };
+    !query.split('&').any(|pair| {
+        let (key, value) = pair.split_once('=').unwrap_or((pair, ""));
+        match key {
+            "cursor" => !value.is_empty(),
+            "page" => !value.is_empty() && value != "1",
+            "mark_seen" => value == "true" || value == "1",
+            _ => false,
+        }
+    })
+}
+
+/// Weak validator: live reaction counts can differ between two bodies that
+/// carry the same tag.
+pub async fn list_etag(
+    state: &AppState,
+    user_id: &str,
+    list: ListFreshness,
+    query: Option<&str>,
+) -> Result<String, ApiError> {
+    let mut signature = sqlx::query_scalar::<_, Option<String>>(list.signature_sql());
+    for _ in 0..list.bind_count() {
+        signature = signature.bind(user_id);
+    }
+    let signature = signature
+        .fetch_one(&state.pool)
+        .await
+        .map_err(ApiError::internal)?
+        .unwrap_or_default();
+    let digest = ai::sha256_hex(&format!(
+        "{}\n{}\n{}\n{}\n{}",
+        env!("CARGO_PKG_VERSION"),
+        list.as_str(),
+        user_id,
+        query.unwrap_or_default(),
+        signature
+    ));
+    Ok(format!("W/\"{}\"", &digest[..32]))
+}
+
+/// Weak comparison per RFC 9110: the `W/` prefix is ignored on both sides.
+pub fn if_none_match_matches(headers: &HeaderMap, etag: &str) -> bool {
+    let Some(if_none_match) = headers
+        .get(header::IF_NONE_MATCH)
+        .and_then(|value| value.to_str().ok())
+    else {
+        return false;
+    };
+    let etag = etag.strip_prefix("W/").unwrap_or(etag);
+    if_none_match.split(',').map(str::trim).any(|candidate| {
+        candidate == "*" || candidate.strip_prefix("W/").unwrap_or(candidate) == etag
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use axum::{Router, middleware, routing::get};
+    use sqlx::{SqlitePool, sqlite::SqlitePoolOptions};
+    use tower::ServiceExt;
+    use tower_sessions::MemoryStore;
+
+    use crate::config::AppConfig;
+
+    fn user_id() -> String {
+        crate::local_id::test_local_id("conditional-user")
+    }
+
+    async fn setup_pool() -> SqlitePool {
+        let pool = SqlitePoolOptions::new()
+            .max_connections(1)
+            .connect("sqlite::memory:")
+            .await
+            .expect("create sqlite memory db");
+        sqlx::migrate!("./migrations")
+            .run(&pool)
+            .await
+            .expect("run migrations");
+        sqlx::query(
+            r#"
+            INSERT INTO users (id, github_user_id, login, created_at, updated_at)
+            VALUES (?, 1, 'octo', '2026-02-01T00:00:00Z', '2026-02-01T00:00:00Z')
+            "#,
+        )
+        .bind(user_id())
+        .execute(&pool)
+        .await
+        .expect("seed user");
+        sqlx::query(
+            r#"
+            INSERT INTO starred_repos (
+              id, user_id, repo_id, full_name, owner_login, name, html_url, stargazed_at,
+              is_private, updated_at
+            )
+            VALUES (
+              'star-conditional-1', ?, 42, 'openai/codex', 'openai', 'codex',
+              'https://github.com/openai/codex', '2026-02-01T00:00:00Z', 0,
+              '2026-02-01T00:00:00Z'
+            )
+            "#,
+        )
+        .bind(user_id())
+        .execute(&pool)
+        .await
+        .expect("seed star");
+        pool
+    }
+
+    fn setup_state(pool: SqlitePool) -> Arc<AppState> {
+        Arc::new(AppState::for_tests(pool, AppConfig::for_tests()))
+    }
+
+    async fn user_session() -> Session {
+        let session = Session::new(None, Arc::new(MemoryStore::default()), None);
+        session
+            .insert("user_id", user_id())
+            .await
+            .expect("insert session user id");
+        session
+    }
+
+    fn starred_router(state: Arc<AppState>, calls: Arc<AtomicUsize>) -> Router {
+        Router::new().route(
+            "/starred",
+            get(move || {
+                let calls = calls.clone();
+                async move {
+                    calls.fetch_add(1, Ordering::SeqCst);
+                    "[]"
+                }
+            })
+            .layer(middleware::from_fn_with_state(state, starred_etag)),
+        )
+    }
+
+    async fn send(router: &Router, uri: &str, if_none_match: Option<&HeaderValue>) -> Response {
+        let mut request = Request::builder()
+            .uri(uri)
+            .body(Body::empty())
+            .expect("build request");
+        request.extensions_mut().insert(user_session().await);
+        if let Some(etag) = if_none_match {
+            request
+                .headers_mut()
+                .insert(header::IF_NONE_MATCH, etag.clone());
+        }
+        router.clone().oneshot(request).await.expect("send request")
+    }
+
+    #[tokio::test]
+    async fn unchanged_list_answers_not_modified_without_running_the_handler() {
+        let pool = setup_pool().await;
+        let state = setup_state(pool.clone());
+        let calls = Arc::new(AtomicUsize::new(0));
+        let router = starred_router(state, calls.clone());
+
+        let response = send(&router, "/starred", None).await;
+        assert_eq!(response.status(), StatusCode::OK);
+        let etag = response
+            .headers()
+            .get(header::ETAG)
+            .cloned()
+            .expect("etag header");
+
+        let response = send(&router, "/starred", Some(&etag)).await;
+        assert_eq!(response.status(), StatusCode::NOT_MODIFIED);
+        assert_eq!(response.headers().get(header::ETAG), Some(&etag));
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+
+        sqlx::query("UPDATE starred_repos SET updated_at = '2026-02-02T00:00:00Z'")
+            .execute(&pool)
+            .await
+            .expect("touch star");
+        let response = send(&router, "/starred", Some(&etag)).await;
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_ne!(response.headers().get(header::ETAG), Some(&etag));
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+
+        let response = send(&router, "/starred?page=2", Some(&etag)).await;
+        assert_eq!(response.status(), StatusCode::OK);
+        assert!(response.headers().get(header::ETAG).is_none());
+    }
+
+    #[tokio::test]
+    async fn feed_and_briefs_etags_move_with_each_underlying_table() {
+        let pool = setup_pool().await;
+        let state = setup_state(pool.clone());
+        let user_id = user_id();
+        let feed_etag = || list_etag(state.as_ref(), &user_id, ListFreshness::Feed, None);
+        let briefs_etag = || list_etag(state.as_ref(), &user_id, ListFreshness::Briefs, None);
+
+        let changes = [
+            r#"
+            INSERT INTO repo_releases (
+              id, repo_id, release_id, node_id, tag_name, name, body, html_url,
+              published_at, created_at, is_prerelease, is_draft, updated_at
+            )
+            VALUES (
+              'release-conditional-1', 42, 120, 'node-120', 'v1.0.0', 'v1.0.0', '- item',
+              'https://github.com/openai/codex/releases/tag/v1.0.0',
+              '2026-02-01T00:00:00Z', '2026-02-01T00:00:00Z', 0, 0, '2026-02-01T00:00:00Z'
+            )
+            "#,
+            "UPDATE repo_releases SET updated_at = '2026-02-03T00:00:00Z'",
+            r#"
+            INSERT INTO ai_translations (
+              id, user_id, entity_type, entity_id, lang, source_hash, status,
+              created_at, updated_at
+            )
+            VALUES (
+              'translation-conditional-1', ?, 'release', '120', 'zh-CN', 'hash', 'ready',
+              '2026-02-01T00:00:00Z', '2026-02-01T00:00:00Z'
+            )
+            "#,
+            r#"
+            INSERT INTO bookmarks (id, user_id, kind, entity_id, created_at, updated_at)
+            VALUES (
+              'bookmark-conditional-1', ?, 'release', '120',
+              '2026-02-01T00:00:00Z', '2026-02-01T00:00:00Z'
+            )
+            "#,
+            r#"
+            INSERT INTO release_seen (user_id, release_id, seen_at)
+            VALUES (?, 120, '2026-02-01T00:00:00Z')
+            "#,
+            r#"
+            INSERT INTO muted_repos (user_id, repo_id, created_at)
+            VALUES (?, 42, '2026-02-01T00:00:00Z')
+            "#,
+            r#"
+            INSERT INTO repo_translation_opt_outs (user_id, repo_id, created_at)
+            VALUES (?, 42, '2026-02-01T00:00:00Z')
+            "#,
+        ];
+        let mut previous = feed_etag().await.expect("initial feed etag");
+        for change in changes {
+            let mut query = sqlx::query(change);
+            if change.contains('?') {
+                query = query.bind(user_id.as_str());
+            }
+            query.execute(&pool).await.expect("apply change");
+            let next = feed_etag().await.expect("feed etag");
+            assert_ne!(next, previous, "feed etag unchanged after: {change}");
+            previous = next;
+        }
+        assert_eq!(feed_etag().await.expect("stable feed etag"), previous);
+
+        let before = briefs_etag().await.expect("initial briefs etag");
+        sqlx::query(
+            r#"
+            INSERT INTO briefs (id, user_id, date, content_markdown, created_at, updated_at)
+            VALUES ('brief-conditional-1', ?, '2026-02-01', '- item',
+              '2026-02-01T00:00:00Z', '2026-02-01T00:00:00Z')
+            "#,
+        )
+        .bind(user_id.as_str())
+        .execute(&pool)
+        .await
+        .expect("seed brief");
+        let with_brief = briefs_etag().await.expect("briefs etag");
+        assert_ne!(with_brief, before);
+        sqlx::query(
+            r#"
+            INSERT INTO brief_feedback (user_id, brief_id, rating, created_at, updated_at)
+            VALUES (?, 'brief-conditional-1', 'up', '2026-02-02T00:00:00Z', '2026-02-02T00:00:00Z')
+            "#,
+        )
+        .bind(user_id.as_str())
+        .execute(&pool)
+        .await
+        .expect("seed brief feedback");
+        assert_ne!(briefs_etag().await.expect("briefs etag"), with_brief);
+    }
+
+    #[test]
+    fn later_pages_and_mark_seen_skip_conditional_handling() {
+        assert!(is_first_page(None));
+        assert!(is_first_page(Some("limit=30&cursor=")));
+        assert!(is_first_page(Some("page=1&page_size=50")));
+        assert!(!is_first_page(Some("limit=30&cursor=abc")));
+        assert!(!is_first_page(Some("page=3")));
+        assert!(!is_first_page(Some("mark_seen=true")));
+    }
+}
@@ -18,6 +18,27 @@ fn ensure_trailing_slash(mut url: Url) -> Url {
     url
 }
 
+/// Parses `AI_BASE_URL` into the API root that request paths are joined
+/// onto. A pasted `.../chat/completions` or `.../messages` endpoint is cut
+/// back to its root.
+fn normalize_ai_base_url(raw: &str) -> Result<Url> {
+    let mut url = Url::parse(raw.trim()).context("invalid AI_BASE_URL")?;
+    if !matches!(url.scheme(), "http" | "https") || url.host_str().is_none() {
+        anyhow::bail!("invalid AI_BASE_URL (expected an http or https URL)");
+    }
+    url.set_query(None);
+    url.set_fragment(None);
+    let path = url.path().trim_end_matches('/');
+    if let Some(root) = path
+        .strip_suffix("/chat/completions")
+        .or_else(|| path.strip_suffix("/messages"))
+    {
+        let root = root.to_owned();
+        url.set_path(&root);
+    }
+    Ok(ensure_trailing_slash(url))
+}
+
 fn parse_positive_usize_env(name: &str, blank_is_unset: bool) -> Result<Option<usize>> {
     let Some(raw) = env::var_os(name) else {
         return Ok(None);
@@ -58,6 +79,106 @@ fn parse_bounded_positive_usize_env(
     Ok(parsed)
 }
 
+fn non_blank_env(name: &str) -> Option<String> {
+    env::var(name)
+        .ok()
+        .map(|value| value.trim().to_owned())
+        .filter(|value| !value.is_empty())
+}
+
+fn parse_model_list(raw: &str) -> Vec<String> {
+    let mut models = Vec::<String>::new();
+    for model in raw
+        .split(',')
+        .map(str::trim)
+        .filter(|model| !model.is_empty())
+    {
+        if !models.iter().any(|existing| existing == model) {
+            models.push(model.to_owned());
+        }
+    }
+    models
+}
+
+fn parse_bool_env(name: &str) -> Result<bool> {
+    match non_blank_env(name).map(|value| value.to_ascii_lowercase()) {
+        None => Ok(false),
+        Some(value) => match value.as_str() {
+            "1" | "true" | "yes" | "on" => Ok(true),
+            "0" | "false" | "no" | "off" => Ok(false),
+            _ => anyhow::bail!("invalid {name} (expected true or false)"),
+        },
+    }
+}
+
+fn parse_smtp_config() -> Result<Option<SmtpConfig>> {
+    let host = non_blank_env("SMTP_HOST");
+    let from = non_blank_env("SMTP_FROM");
+    let (host, from) = match (host, from) {
+        (None, None) => return Ok(None),
+        (Some(host), Some(from)) => (host, from),
+        _ => anyhow::bail!("SMTP_HOST and SMTP_FROM must be set together"),
+    };
+
+    let port = match non_blank_env("SMTP_PORT") {
+        Some(raw) => raw
+            .parse::<u16>()
+            .ok()
+            .filter(|port| *port > 0)
+            .context("invalid SMTP_PORT (expected port number)")?,
+        None => 587,
+    };
+    let tls = match non_blank_env("SMTP_TLS")
+        .map(|value| value.to_ascii_lowercase())
+        .as_deref()
+    {
+        None if port == 465 => SmtpTls::Tls,
+        None | Some("starttls") => SmtpTls::StartTls,
+        Some("tls") => SmtpTls::Tls,
+        Some("none") => SmtpTls::None,
+        Some(_) => anyhow::bail!("invalid SMTP_TLS (expected starttls, tls, or none)"),
+    };
+
+    let username = non_blank_env("SMTP_USERNAME");
+    let password = non_blank_env("SMTP_PASSWORD");
+    if username.is_some() != password.is_some() {
+        anyhow::bail!("SMTP_USERNAME and SMTP_PASSWORD must be set together");
+    }
+
+    Ok(Some(SmtpConfig {
+        host,
+        port,
+        username,
+        password,
+        from,
+        tls,
+        dry_run: parse_bool_env("SMTP_DRY_RUN")?,
+    }))
+}
+
+fn parse_github_app_config() -> Result<Option<GitHubAppConfig>> {
+    let app_id = non_blank_env("GITHUB_APP_ID");
+    let key_path = non_blank_env("GITHUB_APP_PRIVATE_KEY_PATH");
+    let (app_id, key_path) = match (app_id, key_path) {
+        (None, None) => return Ok(None),
+        (Some(app_id), Some(key_path)) => (app_id, key_path),
+        _ => anyhow::bail!("GITHUB_APP_ID and GITHUB_APP_PRIVATE_KEY_PATH must be set together"),
+    };
+    if !app_id.bytes().all(|byte| byte.is_ascii_digit()) {
+        anyhow::bail!("invalid GITHUB_APP_ID (expected numeric app id)");
+    }
+    let private_key_pem = std::fs::read_to_string(&key_path)
+        .with_context(|| format!("failed to read GITHUB_APP_PRIVATE_KEY_PATH ({key_path})"))?;
+    crate::github::parse_app_private_key(&private_key_pem)
+        .context("invalid GITHUB_APP_PRIVATE_KEY_PATH (expected an RSA private key PEM)")?;
+
+    Ok(Some(GitHubAppConfig {
+        app_id,
+        private_key_pem,
+        webhook_secret: non_blank_env("GITHUB_APP_WEBHOOK_SECRET"),
+    }))
+}
+
 fn validate_app_default_time_zone(raw: &str) -> Result<String> {
     let canonical = raw.trim().to_owned();
     chrono_tz::Tz::from_str(&canonical)
@@ -98,14 +219,56 @@ pub struct AppConfig {
     pub static_dir: Option<PathBuf>,
     pub task_log_dir: PathBuf,
     pub job_worker_concurrency: usize,
+    pub reaction_viewer_ttl_secs: usize,
+    /// Newest releases fetched for a repo right after it is starred.
+    pub release_backfill_limit: usize,
+    /// Days an unstarred repo's releases and translations are kept before
+    /// they are purged; starring it again in time cancels the purge.
+    pub star_purge_grace_days: usize,
+    /// Days without an update after which a notification counts as archived
+    /// and drops out of the default inbox.
+    pub notification_archive_days: usize,
+    /// Days after which archived notifications and their translations are
+    /// deleted by the maintenance prune.
+    pub notification_retention_days: usize,
+    pub task_retention_days: usize,
+    /// Directory `VACUUM INTO` writes database snapshots to.
+    pub backup_dir: PathBuf,
+    /// Newest snapshots kept in `backup_dir`; older ones are deleted after
+    /// each new backup.
+    pub backup_retention_count: usize,
+    pub shutdown_grace_secs: usize,
+    pub task_timeouts: crate::jobs::TaskTimeouts,
     pub encryption_key: EncryptionKey,
     pub github: GitHubOAuthConfig,
+    /// Optional GitHub App whose installation tokens sync prefers over user
+    /// OAuth tokens for the repos an installation can see.
+    pub github_app: Option<GitHubAppConfig>,
     pub linuxdo: Option<LinuxDoOAuthConfig>,
     pub ai: Option<AiConfig>,
     pub ai_max_concurrency: usize,
+    pub ai_translate_concurrency: usize,
+    /// Minimum share (percent) of Han characters among letters for a source
+    /// to count as already Chinese and skip translation.
+    pub ai_skip_translation_cjk_percent: usize,
+    pub ai_daily_token_budget_per_user: Option<usize>,
+    pub ai_request_interval_ms: u64,
     pub ai_daily_at_local: Option<chrono::NaiveTime>,
     pub app_default_time_zone: String,
+    pub smtp: Option<SmtpConfig>,
     pub logging: LoggingThresholds,
+    /// Bearer token guarding `/metrics`; the endpoint is off when unset.
+    pub metrics_token: Option<String>,
+    /// Keep an inbound `x-request-id` instead of minting one; only safe
+    /// behind a proxy that sets or strips the header.
+    pub trust_request_id_header: bool,
+    /// Key rate limits on the proxy-reported client address from
+    /// `X-Forwarded-For` / `X-Real-IP`; only safe behind a proxy that sets
+    /// those headers.
+    pub trust_forwarded_headers: bool,
+    /// Whether `/readyz` also reports GitHub API reachability.
+    pub readyz_check_github: bool,
+    pub rate_limits: crate::rate_limit::RateLimits,
 }
 
 #[derive(Clone)]
@@ -115,6 +278,14 @@ pub struct GitHubOAuthConfig {
     pub redirect_url: Url,
 }
 
+#[derive(Clone)]
+pub struct GitHubAppConfig {
+    pub app_id: String,
+    pub private_key_pem: String,
+    /// Verifies `/webhooks/github` deliveries; the endpoint is off when unset.
+    pub webhook_secret: Option<String>,
+}
+
 #[derive(Clone)]
 pub struct LinuxDoOAuthConfig {
     pub client_id: String,
@@ -124,17 +295,123 @@ pub struct LinuxDoOAuthConfig {
 
 #[derive(Clone)]
 pub struct AiConfig {
+    pub provider: AiProvider,
     pub base_url: Url,
     pub model: String,
     pub api_key: String,
+    /// Models tried in order when the primary model is rejected upstream.
+    pub fallback_models: Vec<String>,
+    pub startup_probe: AiStartupProbe,
+}
+
+/// Wire format spoken by the AI upstream.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum AiProvider {
+    /// `/chat/completions`, as served by OpenAI and compatible proxies.
+    #[default]
+    OpenAi,
+    /// Anthropic's native `/messages` API.
+    Anthropic,
+}
+
+impl AiProvider {
+    fn parse(raw: &str) -> Result<Self> {
+        match raw.trim().to_ascii_lowercase().as_str() {
+            "" | "openai" => Ok(Self::OpenAi),
+            "anthropic" => Ok(Self::Anthropic),
+            _ => anyhow::bail!("invalid AI_PROVIDER (expected openai or anthropic)"),
+        }
+    }
+
+    fn default_base_url(self) -> &'static str {
+        match self {
+            Self::OpenAi => "https://api.openai.com/v1/",
+            Self::Anthropic => "https://api.anthropic.com/v1/",
+        }
+    }
+
+    fn default_model(self) -> &'static str {
+        match self {
+            Self::OpenAi => "gpt-4o-mini",
+            Self::Anthropic => "claude-3-5-haiku-latest",
+        }
+    }
+}
+
+/// Whether startup checks the AI upstream before serving, and what a failed
+/// check does.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum AiStartupProbe {
+    #[default]
+    Off,
+    Warn,
+    Strict,
+}
+
+impl AiConfig {
+    /// OpenAI-compatible APIs live under a version segment such as `/v1/`;
+    /// a base URL without one usually hits the provider's website instead.
+    pub fn base_url_warning(&self) -> Option<String> {
+        let last_segment = self
+            .base_url
+            .path_segments()
+            .and_then(|mut segments| segments.rfind(|segment| !segment.is_empty()));
+        let versioned = last_segment.is_some_and(|segment| {
+            segment
+                .strip_prefix('v')
+                .and_then(|rest| rest.chars().next())
+                .is_some_and(|ch| ch.is_ascii_digit())
+        });
+        (!versioned).then(|| {
+            format!(
+                "AI_BASE_URL {} does not end with a version segment such as /v1/",
+                self.base_url
+            )
+        })
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SmtpTls {
+    StartTls,
+    Tls,
+    None,
+}
+
+#[derive(Clone)]
+pub struct SmtpConfig {
+    pub host: String,
+    pub port: u16,
+    pub username: Option<String>,
+    pub password: Option<String>,
+    pub from: String,
+    pub tls: SmtpTls,
+    pub dry_run: bool,
+}
+
+impl fmt::Debug for SmtpConfig {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SmtpConfig")
+            .field("host", &self.host)
+            .field("port", &self.port)
+            .field("username", &self.username)
+            .field("password", &self.password.as_ref().map(|_| "<redacted>"))
+            .field("from", &self.from)
+            .field("tls", &self.tls)
+            .field("dry_run", &self.dry_run)
+            .finish()
+    }
 }
 
 impl fmt::Debug for AiConfig {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         f.debug_struct("AiConfig")
+            .field("provider", &self.provider)
             .field("base_url", &self.base_url)
             .field("model", &self.model)
             .field("api_key", &"<redacted>")
+            .field("fallback_models", &self.fallback_models)
+            .field("startup_probe", &self.startup_probe)
             .finish()
     }
 }
@@ -172,24 +449,149 @@ impl fmt::Debug for AppConfig {
             .field("static_dir", &self.static_dir)
             .field("task_log_dir", &self.task_log_dir)
             .field("job_worker_concurrency", &self.job_worker_concurrency)
+            .field("reaction_viewer_ttl_secs", &self.reaction_viewer_ttl_secs)
+            .field("release_backfill_limit", &self.release_backfill_limit)
+            .field("star_purge_grace_days", &self.star_purge_grace_days)
+            .field("notification_archive_days", &self.notification_archive_days)
+            .field(
+                "notification_retention_days",
+                &self.notification_retention_days,
+            )
+            .field("task_retention_days", &self.task_retention_days)
+            .field("backup_dir", &self.backup_dir)
+            .field("backup_retention_count", &self.backup_retention_count)
+            .field("shutdown_grace_secs", &self.shutdown_grace_secs)
+            .field("task_timeouts", &self.task_timeouts)
             .field("github", &self.github)
             .field("linuxdo", &self.linuxdo)
             .field("ai", &self.ai)
             .field("ai_max_concurrency", &self.ai_max_concurrency)
+            .field("ai_translate_concurrency", &self.ai_translate_concurrency)
+            .field(
+                "ai_skip_translation_cjk_percent",
+                &self.ai_skip_translation_cjk_percent,
+            )
+            .field(
+                "ai_daily_token_budget_per_user",
+                &self.ai_daily_token_budget_per_user,
+            )
+            .field("ai_request_interval_ms", &self.ai_request_interval_ms)
             .field("ai_daily_at_local", &self.ai_daily_at_local)
             .field("app_default_time_zone", &self.app_default_time_zone)
+            .field("smtp", &self.smtp)
             .field("logging", &self.logging)
+            .field(
+                "metrics_token",
+                &self.metrics_token.as_ref().map(|_| "<redacted>"),
+            )
+            .field("trust_request_id_header", &self.trust_request_id_header)
+            .field("trust_forwarded_headers", &self.trust_forwarded_headers)
+            .field("readyz_check_github", &self.readyz_check_github)
+            .field("rate_limits", &self.rate_limits)
             .field("encryption_key", &"<redacted>")
             .finish()
     }
 }
 
+#[cfg(test)]
+impl AppConfig {
+    /// Loopback config with every optional integration off. Tests override
+    /// the fields they exercise with struct update syntax.
+    pub fn for_tests() -> Self {
+        Self {
+            bind_addr: "127.0.0.1:58090"
+                .parse::<SocketAddr>()
+                .expect("parse bind addr"),
+            public_base_url: Url::parse("http://127.0.0.1:58090").expect("parse public base url"),
+            database_url: "sqlite::memory:".to_owned(),
+            sqlite_pool_max_connections: 8,
+            static_dir: None,
+            task_log_dir: std::env::temp_dir().join("octo-rill-task-logs-tests"),
+            job_worker_concurrency: 4,
+            reaction_viewer_ttl_secs: 600,
+            release_backfill_limit: 10,
+            star_purge_grace_days: 14,
+            notification_archive_days: 90,
+            notification_retention_days: 365,
+            task_retention_days: 30,
+            backup_dir: std::env::temp_dir().join("octo-rill-backups-tests"),
+            backup_retention_count: 7,
+            shutdown_grace_secs: 30,
+            task_timeouts: crate::jobs::TaskTimeouts::default(),
+            encryption_key: EncryptionKey::from_base64(
+                "AAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAA=",
+            )
+            .expect("build encryption key"),
+            github: GitHubOAuthConfig {
+                client_id: "test-client-id".to_owned(),
+                client_secret: "test-client-secret".to_owned(),
+                redirect_url: Url::parse("http://127.0.0.1:58090/auth/callback")
+                    .expect("parse github redirect"),
+            },
+            github_app: None,
+            linuxdo: None,
+            ai: None,
+            ai_max_concurrency: 1,
+            ai_translate_concurrency: 2,
+            ai_skip_translation_cjk_percent: 30,
+            ai_daily_token_budget_per_user: None,
+            ai_request_interval_ms: 0,
+            ai_daily_at_local: None,
+            app_default_time_zone: crate::briefs::DEFAULT_DAILY_BRIEF_TIME_ZONE.to_owned(),
+            smtp: None,
+            logging: LoggingThresholds::default(),
+            metrics_token: None,
+            trust_request_id_header: false,
+            trust_forwarded_headers: false,
+            readyz_check_github: false,
+            rate_limits: crate::rate_limit::RateLimits::default(),
+        }
+    }
+}
+
+/// Every problem found while reading the environment, so a misconfigured
+/// deployment learns about all of them from one failed start.
+#[derive(Debug, Default)]
+struct ConfigProblems(Vec<String>);
+
+impl ConfigProblems {
+    fn check<T>(&mut self, result: Result<T>) -> Option<T> {
+        match result {
+            Ok(value) => Some(value),
+            Err(err) => {
+                self.0.push(format!("{err:#}"));
+                None
+            }
+        }
+    }
+
+    fn finish(self) -> Result<()> {
+        match self.0.as_slice() {
+            [] => Ok(()),
+            [only] => Err(anyhow::anyhow!("{only}")),
+            problems => Err(anyhow::anyhow!(
+                "invalid configuration ({} problems):\n  - {}",
+                problems.len(),
+                problems.join("\n  - ")
+            )),
+        }
+    }
+}
+
 impl AppConfig {
+    /// Reads the whole environment before failing; the error lists every
+    /// invalid or missing setting rather than just the first one.
     pub fn from_env() -> Result<Self> {
-        let bind_addr: SocketAddr = env::var("OCTORILL_BIND_ADDR")
-            .unwrap_or_else(|_| "127.0.0.1:58090".to_owned())
-            .parse()
-            .context("invalid OCTORILL_BIND_ADDR (expected ip:port)")?;
+        let mut problems = ConfigProblems::default();
+
+        let bind_addr: SocketAddr = problems
+            .check(
+                env::var("OCTORILL_BIND_ADDR")
+                    .unwrap_or_else(|_| "127.0.0.1:58090".to_owned())
+                    .parse()
+                    .context("invalid OCTORILL_BIND_ADDR (expected ip:port)"),
+            )
+            .unwrap_or_else(|| SocketAddr::from(([127, 0, 0, 1], 58090)));
 
         let default_host = match bind_addr.ip() {
             IpAddr::V4(v4) if v4.is_unspecified() => "127.0.0.1".to_owned(),
@@ -198,42 +600,108 @@ impl AppConfig {
         };
         let public_base_url = env::var("OCTORILL_PUBLIC_BASE_URL")
             .unwrap_or_else(|_| format!("http://{}:{}", default_host, bind_addr.port()));
-        let public_base_url =
-            Url::parse(&public_base_url).context("invalid OCTORILL_PUBLIC_BASE_URL")?;
+        let public_base_url = problems
+            .check(Url::parse(&public_base_url).context("invalid OCTORILL_PUBLIC_BASE_URL"));
 
         let database_url =
             env::var("DATABASE_URL").unwrap_or_else(|_| "sqlite:./.data/octo-rill.db".to_owned());
-        let sqlite_pool_max_connections =
-            parse_bounded_positive_usize_env("OCTORILL_SQLITE_POOL_MAX_CONNECTIONS", false, 32)?
-                .unwrap_or_else(|| {
-                    if database_url == "sqlite::memory:" {
-                        1
-                    } else {
-                        8
-                    }
-                });
+        let sqlite_pool_max_connections = problems
+            .check(parse_bounded_positive_usize_env(
+                "OCTORILL_SQLITE_POOL_MAX_CONNECTIONS",
+                false,
+                32,
+            ))
+            .flatten()
+            .unwrap_or_else(|| {
+                if database_url == "sqlite::memory:" {
+                    1
+                } else {
+                    8
+                }
+            });
 
         let task_log_dir = env::var("OCTORILL_TASK_LOG_DIR")
             .ok()
             .map(PathBuf::from)
             .filter(|candidate| !candidate.as_os_str().is_empty())
             .unwrap_or_else(|| PathBuf::from(".data/task-logs"));
+        let backup_dir = env::var("OCTORILL_BACKUP_DIR")
+            .ok()
+            .map(PathBuf::from)
+            .filter(|candidate| !candidate.as_os_str().is_empty())
+            .unwrap_or_else(|| PathBuf::from(".data/backups"));
 
-        let job_worker_concurrency =
-            parse_positive_usize_env("OCTORILL_TASK_WORKERS", false)?.unwrap_or(4);
+        let mut bounded = |name: &str, max: usize, default: usize| {
+            problems
+                .check(parse_bounded_positive_usize_env(name, true, max))
+                .flatten()
+                .unwrap_or(default)
+        };
+        let reaction_viewer_ttl_secs = bounded("OCTORILL_REACTION_VIEWER_TTL_SECS", 86_400, 600);
+        let release_backfill_limit = bounded("OCTORILL_RELEASE_BACKFILL_LIMIT", 100, 10);
+        let star_purge_grace_days = bounded("OCTORILL_STAR_PURGE_GRACE_DAYS", 365, 14);
+        let notification_archive_days = bounded("OCTORILL_NOTIFICATION_ARCHIVE_DAYS", 3_650, 90);
+        let notification_retention_days =
+            bounded("OCTORILL_NOTIFICATION_RETENTION_DAYS", 3_650, 365)
+                .max(notification_archive_days);
+        let task_retention_days = bounded("OCTORILL_TASK_RETENTION_DAYS", 3_650, 30);
+        let backup_retention_count = bounded("OCTORILL_BACKUP_RETENTION", 365, 7);
+        let shutdown_grace_secs = bounded("OCTORILL_SHUTDOWN_GRACE_SECS", 3_600, 30);
+        let task_timeouts = {
+            let defaults = crate::jobs::TaskTimeouts::default();
+            let mut parse = |name: &str, default: std::time::Duration| {
+                let secs = bounded(name, 86_400, default.as_secs() as usize);
+                std::time::Duration::from_secs(secs as u64)
+            };
+            crate::jobs::TaskTimeouts {
+                sync: parse("OCTORILL_TASK_TIMEOUT_SYNC_SECS", defaults.sync),
+                translate: parse("OCTORILL_TASK_TIMEOUT_TRANSLATE_SECS", defaults.translate),
+                brief: parse("OCTORILL_TASK_TIMEOUT_BRIEF_SECS", defaults.brief),
+            }
+        };
+        let ai_translate_concurrency = bounded("AI_TRANSLATE_CONCURRENCY", 16, 2);
+        let ai_skip_translation_cjk_percent = bounded("AI_SKIP_TRANSLATION_CJK_PERCENT", 100, 30);
+        let logging = LoggingThresholds {
+            http_slow_ms: bounded("OCTORILL_HTTP_SLOW_MS", 60_000, 1_000),
+            upstream_slow_ms: bounded("OCTORILL_UPSTREAM_SLOW_MS", 60_000, 2_000),
+            sqlite_write_slow_ms: bounded("OCTORILL_SQLITE_WRITE_SLOW_MS", 60_000, 250),
+        };
+        let rate_limits = {
+            let defaults = crate::rate_limit::RateLimits::default();
+            let mut parse =
+                |name: &str, default: u32| bounded(name, 100_000, default as usize) as u32;
+            crate::rate_limit::RateLimits {
+                user_per_minute: parse(
+                    "OCTORILL_RATE_LIMIT_USER_PER_MIN",
+                    defaults.user_per_minute,
+                ),
+                auth_ip_per_minute: parse(
+                    "OCTORILL_RATE_LIMIT_AUTH_IP_PER_MIN",
+                    defaults.auth_ip_per_minute,
+                ),
+            }
+        };
 
-        let encryption_key = env::var("OCTORILL_ENCRYPTION_KEY_BASE64")
-            .context("OCTORILL_ENCRYPTION_KEY_BASE64 is required")?;
-        let encryption_key = EncryptionKey::from_base64(&encryption_key)?;
+        let job_worker_concurrency = problems
+            .check(parse_positive_usize_env("OCTORILL_TASK_WORKERS", false))
+            .flatten()
+            .unwrap_or(4);
+
+        let encryption_key = problems.check(
+            env::var("OCTORILL_ENCRYPTION_KEY_BASE64")
+                .context("OCTORILL_ENCRYPTION_KEY_BASE64 is required")
+                .and_then(|raw| EncryptionKey::from_base64(&raw)),
+        );
 
         let github_client_id =
-            env::var("GITHUB_CLIENT_ID").context("GITHUB_CLIENT_ID is required")?;
-        let github_client_secret =
-            env::var("GITHUB_CLIENT_SECRET").context("GITHUB_CLIENT_SECRET is required")?;
-        let github_redirect_url = env::var("GITHUB_OAUTH_REDIRECT_URL")
-            .context("GITHUB_OAUTH_REDIRECT_URL is required")?;
-        let github_redirect_url =
-            Url::parse(&github_redirect_url).context("invalid GITHUB_OAUTH_REDIRECT_URL")?;
+            problems.check(env::var("GITHUB_CLIENT_ID").context("GITHUB_CLIENT_ID is required"));
+        let github_client_secret = problems
+            .check(env::var("GITHUB_CLIENT_SECRET").context("GITHUB_CLIENT_SECRET is required"));
+        let github_redirect_url = problems.check(
+            env::var("GITHUB_OAUTH_REDIRECT_URL")
+                .context("GITHUB_OAUTH_REDIRECT_URL is required")
+                .and_then(|raw| Url::parse(&raw).context("invalid GITHUB_OAUTH_REDIRECT_URL")),
+        );
 
         let linuxdo = {
             let client_id = env::var("LINUXDO_CLIENT_ID")
@@ -251,83 +719,132 @@ impl AppConfig {
 
             match (client_id, client_secret, redirect_url) {
                 (None, None, None) => None,
-                (Some(client_id), Some(client_secret), Some(redirect_url)) => {
-                    Some(LinuxDoOAuthConfig {
+                (Some(client_id), Some(client_secret), Some(redirect_url)) => problems
+                    .check(Url::parse(&redirect_url).context("invalid LINUXDO_OAUTH_REDIRECT_URL"))
+                    .map(|redirect_url| LinuxDoOAuthConfig {
                         client_id,
                         client_secret,
-                        redirect_url: Url::parse(&redirect_url)
-                            .context("invalid LINUXDO_OAUTH_REDIRECT_URL")?,
-                    })
-                }
+                        redirect_url,
+                    }),
                 _ => {
-                    anyhow::bail!(
+                    problems.check::<()>(Err(anyhow::anyhow!(
                         "LINUXDO_CLIENT_ID, LINUXDO_CLIENT_SECRET, and LINUXDO_OAUTH_REDIRECT_URL must be set together"
-                    )
+                    )));
+                    None
                 }
             }
         };
 
-        let ai = {
-            let api_key = env::var("AI_API_KEY")
-                .ok()
-                .map(|v| v.trim().to_owned())
-                .filter(|v| !v.is_empty());
-
-            api_key.map(|api_key| {
+        let ai = match non_blank_env("AI_API_KEY") {
+            None => None,
+            Some(api_key) => {
+                let provider = problems
+                    .check(
+                        env::var("AI_PROVIDER")
+                            .map(|raw| AiProvider::parse(&raw))
+                            .unwrap_or(Ok(AiProvider::OpenAi)),
+                    )
+                    .unwrap_or_default();
                 let base_url = env::var("AI_BASE_URL")
-                    .unwrap_or_else(|_| "https://api.openai.com/v1/".to_owned());
-                let base_url = Url::parse(&base_url).context("invalid AI_BASE_URL")?;
-                let base_url = ensure_trailing_slash(base_url);
-                let model = env::var("AI_MODEL").unwrap_or_else(|_| "gpt-4o-mini".to_owned());
-                Ok::<_, anyhow::Error>(AiConfig {
-                    base_url,
-                    model,
-                    api_key,
+                    .unwrap_or_else(|_| provider.default_base_url().to_owned());
+                let base_url = problems.check(normalize_ai_base_url(&base_url));
+                let model = problems.check(match env::var("AI_MODEL") {
+                    Ok(raw) if raw.trim().is_empty() => {
+                        Err(anyhow::anyhow!("invalid AI_MODEL (must not be blank)"))
+                    }
+                    Ok(raw) => Ok(raw.trim().to_owned()),
+                    Err(_) => Ok(provider.default_model().to_owned()),
+                });
+                // Strict mode implies probing; there is nothing to be strict about otherwise.
+                let strict = problems
+                    .check(parse_bool_env("AI_STARTUP_PROBE_STRICT"))
+                    .unwrap_or(false);
+                let warn = problems
+                    .check(parse_bool_env("AI_STARTUP_PROBE"))
+                    .unwrap_or(false);
+                let startup_probe = if strict {
+                    AiStartupProbe::Strict
+                } else if warn {
+                    AiStartupProbe::Warn
+                } else {
+                    AiStartupProbe::Off
+                };
+                base_url.zip(model).map(|(base_url, model)| {
+                    let fallback_models = non_blank_env("AI_FALLBACK_MODELS")
+                        .map(|raw| parse_model_list(&raw))
+                        .unwrap_or_default()
+                        .into_iter()
+                        .filter(|fallback| *fallback != model)
+                        .collect();
+                    AiConfig {
+                        provider,
+                        base_url,
+                        model,
+                        api_key,
+                        fallback_models,
+                        startup_probe,
+                    }
                 })
-            })
-        }
-        .transpose()?;
-
-        let ai_max_concurrency = parse_bounded_positive_usize_env(
-            "AI_MAX_CONCURRENCY",
-            true,
-            tokio::sync::Semaphore::MAX_PERMITS,
-        )?
-        .unwrap_or(1);
+            }
+        };
 
-        let ai_daily_at_local = env::var("AI_DAILY_AT_LOCAL")
-            .ok()
-            .map(|v| v.trim().to_owned())
-            .filter(|v| !v.is_empty())
-            .map(|raw| {
-                chrono::NaiveTime::parse_from_str(&raw, "%H:%M")
-                    .context("invalid AI_DAILY_AT_LOCAL (expected HH:MM)")
-            })
-            .transpose()?
+        let ai_max_concurrency = problems
+            .check(parse_bounded_positive_usize_env(
+                "AI_MAX_CONCURRENCY",
+                true,
+                tokio::sync::Semaphore::MAX_PERMITS,
+            ))
+            .flatten()
+            .unwrap_or(1);
+        let ai_daily_token_budget_per_user = problems
+            .check(parse_positive_usize_env(
+                "AI_DAILY_TOKEN_BUDGET_PER_USER",
+                true,
+            ))
+            .flatten();
+        let ai_request_interval_ms = problems
+            .check(parse_bounded_positive_usize_env(
+                "AI_REQUEST_INTERVAL_MS",
+                true,
+                600_000,
+            ))
+            .flatten()
+            .map_or(0, |value| value as u64);
+
+        let ai_daily_at_local = problems
+            .check(
+                env::var("AI_DAILY_AT_LOCAL")
+                    .ok()
+                    .map(|v| v.trim().to_owned())
+                    .filter(|v| !v.is_empty())
+                    .map(|raw| {
+                        chrono::NaiveTime::parse_from_str(&raw, "%H:%M")
+                            .context("invalid AI_DAILY_AT_LOCAL (expected HH:MM)")
+                    })
+                    .transpose(),
+            )
+            .flatten()
             .or_else(|| chrono::NaiveTime::from_hms_opt(8, 0, 0));
 
         let legacy_runtime_time_zone = iana_time_zone::get_timezone().ok();
-        let app_default_time_zone = resolve_app_default_time_zone(
-            env::var("APP_DEFAULT_TIME_ZONE").ok(),
-            legacy_runtime_time_zone.as_deref(),
-        )?;
-
-        let logging = LoggingThresholds {
-            http_slow_ms: parse_bounded_positive_usize_env("OCTORILL_HTTP_SLOW_MS", true, 60_000)?
-                .unwrap_or(1_000),
-            upstream_slow_ms: parse_bounded_positive_usize_env(
-                "OCTORILL_UPSTREAM_SLOW_MS",
-                true,
-                60_000,
-            )?
-            .unwrap_or(2_000),
-            sqlite_write_slow_ms: parse_bounded_positive_usize_env(
-                "OCTORILL_SQLITE_WRITE_SLOW_MS",
-                true,
-                60_000,
-            )?
-            .unwrap_or(250),
-        };
+        let app_default_time_zone = problems
+            .check(resolve_app_default_time_zone(
+                env::var("APP_DEFAULT_TIME_ZONE").ok(),
+                legacy_runtime_time_zone.as_deref(),
+            ))
+            .unwrap_or_else(|| crate::briefs::DEFAULT_DAILY_BRIEF_TIME_ZONE.to_owned());
+
+        let smtp = problems.check(parse_smtp_config()).flatten();
+        let github_app = problems.check(parse_github_app_config()).flatten();
+        let trust_request_id_header = problems
+            .check(parse_bool_env("OCTORILL_TRUST_REQUEST_ID_HEADER"))
+            .unwrap_or(false);
+        let trust_forwarded_headers = problems
+            .check(parse_bool_env("OCTORILL_TRUST_FORWARDED_HEADERS"))
+            .unwrap_or(false);
+        let readyz_check_github = problems
+            .check(parse_bool_env("OCTORILL_READYZ_CHECK_GITHUB"))
+            .unwrap_or(false);
 
         let static_dir = {
             let candidate = PathBuf::from("web/dist");
@@ -338,6 +855,26 @@ impl AppConfig {
             }
         };
 
+        problems.finish()?;
+        // Only reachable when every required value parsed; a missing one was
+        // reported by `finish` above.
+        let (
+            Some(public_base_url),
+            Some(encryption_key),
+            Some(github_client_id),
+            Some(github_client_secret),
+            Some(github_redirect_url),
+        ) = (
+            public_base_url,
+            encryption_key,
+            github_client_id,
+            github_client_secret,
+            github_redirect_url,
+        )
+        else {
+            anyhow::bail!("invalid configuration");
+        };
+
         Ok(Self {
             bind_addr,
             public_base_url,
@@ -346,18 +883,39 @@ impl AppConfig {
             static_dir,
             task_log_dir,
             job_worker_concurrency,
+            reaction_viewer_ttl_secs,
+            release_backfill_limit,
+            star_purge_grace_days,
+            notification_archive_days,
+            notification_retention_days,
+            task_retention_days,
+            backup_dir,
+            backup_retention_count,
+            shutdown_grace_secs,
+            task_timeouts,
             encryption_key,
             github: GitHubOAuthConfig {
                 client_id: github_client_id,
                 client_secret: github_client_secret,
                 redirect_url: github_redirect_url,
             },
+            github_app,
             linuxdo,
             ai,
             ai_max_concurrency,
+            ai_translate_concurrency,
+            ai_skip_translation_cjk_percent,
+            ai_daily_token_budget_per_user,
+            ai_request_interval_ms,
             ai_daily_at_local,
             app_default_time_zone,
+            smtp,
             logging,
+            metrics_token: non_blank_env("OCTORILL_METRICS_TOKEN"),
+            trust_request_id_header,
+            trust_forwarded_headers,
+            readyz_check_github,
+            rate_limits,
         })
     }
 }
@@ -385,17 +943,57 @@ mod tests {
                 "http://127.0.0.1:58090/auth/callback",
             );
             env::remove_var("AI_API_KEY");
+            env::remove_var("AI_PROVIDER");
+            env::remove_var("AI_BASE_URL");
+            env::remove_var("AI_MODEL");
+            env::remove_var("AI_FALLBACK_MODELS");
+            env::remove_var("AI_STARTUP_PROBE");
+            env::remove_var("AI_STARTUP_PROBE_STRICT");
             env::remove_var("AI_MAX_CONCURRENCY");
+            env::remove_var("AI_TRANSLATE_CONCURRENCY");
+            env::remove_var("AI_SKIP_TRANSLATION_CJK_PERCENT");
+            env::remove_var("AI_DAILY_TOKEN_BUDGET_PER_USER");
+            env::remove_var("AI_REQUEST_INTERVAL_MS");
             env::remove_var("APP_DEFAULT_TIME_ZONE");
             env::remove_var("DATABASE_URL");
             env::remove_var("OCTORILL_TASK_WORKERS");
+            env::remove_var("OCTORILL_REACTION_VIEWER_TTL_SECS");
+            env::remove_var("OCTORILL_RELEASE_BACKFILL_LIMIT");
+            env::remove_var("OCTORILL_STAR_PURGE_GRACE_DAYS");
+            env::remove_var("OCTORILL_NOTIFICATION_ARCHIVE_DAYS");
+            env::remove_var("OCTORILL_NOTIFICATION_RETENTION_DAYS");
+            env::remove_var("OCTORILL_TASK_RETENTION_DAYS");
+            env::remove_var("OCTORILL_BACKUP_DIR");
+            env::remove_var("OCTORILL_BACKUP_RETENTION");
+            env::remove_var("OCTORILL_TASK_TIMEOUT_SYNC_SECS");
+            env::remove_var("OCTORILL_TASK_TIMEOUT_TRANSLATE_SECS");
+            env::remove_var("OCTORILL_TASK_TIMEOUT_BRIEF_SECS");
             env::remove_var("OCTORILL_SQLITE_POOL_MAX_CONNECTIONS");
             env::remove_var("OCTORILL_HTTP_SLOW_MS");
             env::remove_var("OCTORILL_UPSTREAM_SLOW_MS");
             env::remove_var("OCTORILL_SQLITE_WRITE_SLOW_MS");
+            env::remove_var("OCTORILL_METRICS_TOKEN");
+            env::remove_var("OCTORILL_TRUST_REQUEST_ID_HEADER");
+            env::remove_var("OCTORILL_TRUST_FORWARDED_HEADERS");
+            env::remove_var("OCTORILL_READYZ_CHECK_GITHUB");
+            env::remove_var("OCTORILL_BIND_ADDR");
+            env::remove_var("OCTORILL_PUBLIC_BASE_URL");
+            env::remove_var("AI_DAILY_AT_LOCAL");
+            env::remove_var("OCTORILL_RATE_LIMIT_USER_PER_MIN");
+            env::remove_var("OCTORILL_RATE_LIMIT_AUTH_IP_PER_MIN");
+            env::remove_var("GITHUB_APP_ID");
+            env::remove_var("GITHUB_APP_PRIVATE_KEY_PATH");
+            env::remove_var("GITHUB_APP_WEBHOOK_SECRET");
             env::remove_var("LINUXDO_CLIENT_ID");
             env::remove_var("LINUXDO_CLIENT_SECRET");
             env::remove_var("LINUXDO_OAUTH_REDIRECT_URL");
+            env::remove_var("SMTP_HOST");
+            env::remove_var("SMTP_PORT");
+            env::remove_var("SMTP_USERNAME");
+            env::remove_var("SMTP_PASSWORD");
+            env::remove_var("SMTP_FROM");
+            env::remove_var("SMTP_TLS");
+            env::remove_var("SMTP_DRY_RUN");
         }
     }
 
@@ -409,6 +1007,289 @@ mod tests {
         assert_eq!(config.ai_max_concurrency, 1);
     }
 
+    #[test]
+    fn from_env_reports_every_problem_together() {
+        let _guard = env_lock().lock().expect("lock env");
+        set_required_env();
+        unsafe {
+            env::remove_var("OCTORILL_ENCRYPTION_KEY_BASE64");
+            env::set_var("OCTORILL_PUBLIC_BASE_URL", "not a url");
+            env::set_var("AI_DAILY_AT_LOCAL", "25:99");
+        }
+
+        let err = AppConfig::from_env().expect_err("invalid config should fail");
+        let message = err.to_string();
+        assert!(
+            message.starts_with("invalid configuration (3 problems):"),
+            "{message}"
+        );
+        assert!(message.contains("OCTORILL_ENCRYPTION_KEY_BASE64 is required"));
+        assert!(message.contains("invalid OCTORILL_PUBLIC_BASE_URL"));
+        assert!(message.contains("invalid AI_DAILY_AT_LOCAL"));
+
+        unsafe {
+            env::remove_var("OCTORILL_PUBLIC_BASE_URL");
+            env::remove_var("AI_DAILY_AT_LOCAL");
+        }
+        let err = AppConfig::from_env().expect_err("missing key should fail");
+        assert!(
+            err.to_string()
+                .starts_with("OCTORILL_ENCRYPTION_KEY_BASE64 is required"),
+            "a single problem is reported on its own: {err}"
+        );
+    }
+
+    #[test]
+    fn from_env_reads_github_app_config() {
+        let _guard = env_lock().lock().expect("lock env");
+        set_required_env();
+        let key_path = env::temp_dir().join(format!(
+            "octo-rill-github-app-key-{}.pem",
+            crate::local_id::generate_local_id()
+        ));
+        std::fs::write(&key_path, crate::github::tests::TEST_APP_PRIVATE_KEY_PEM)
+            .expect("write key");
+        unsafe {
+            env::set_var("GITHUB_APP_ID", "12345");
+        }
+
+        let err = AppConfig::from_env().expect_err("app id without key should fail");
+        assert!(
+            err.to_string()
+                .contains("GITHUB_APP_ID and GITHUB_APP_PRIVATE_KEY_PATH must be set together"),
+            "{err}"
+        );
+
+        unsafe {
+            env::set_var("GITHUB_APP_PRIVATE_KEY_PATH", &key_path);
+            env::set_var("GITHUB_APP_WEBHOOK_SECRET", "hook-secret");
+        }
+        let config = AppConfig::from_env().expect("config");
+        let app = config.github_app.expect("github app config");
+        assert_eq!(app.app_id, "12345");
+        assert_eq!(app.webhook_secret.as_deref(), Some("hook-secret"));
+
+        std::fs::write(&key_path, "not a key").expect("overwrite key");
+        let err = AppConfig::from_env().expect_err("bad key should fail");
+        assert!(
+            err.to_string()
+                .contains("invalid GITHUB_APP_PRIVATE_KEY_PATH"),
+            "{err}"
+        );
+
+        unsafe {
+            env::remove_var("GITHUB_APP_ID");
+            env::remove_var("GITHUB_APP_PRIVATE_KEY_PATH");
+            env::remove_var("GITHUB_APP_WEBHOOK_SECRET");
+        }
+        let _ = std::fs::remove_file(&key_path);
+    }
+
+    #[test]
+    fn from_env_reads_ai_translate_concurrency() {
+        let _guard = env_lock().lock().expect("lock env");
+        set_required_env();
+
+        let config = AppConfig::from_env().expect("build config");
+        assert_eq!(config.ai_translate_concurrency, 2);
+
+        unsafe {
+            env::set_var("AI_TRANSLATE_CONCURRENCY", "4");
+        }
+        let config = AppConfig::from_env().expect("build config");
+        assert_eq!(config.ai_translate_concurrency, 4);
+
+        unsafe {
+            env::set_var("AI_TRANSLATE_CONCURRENCY", "0");
+        }
+        let err = AppConfig::from_env().expect_err("zero concurrency should fail");
+        assert!(err.to_string().contains("AI_TRANSLATE_CONCURRENCY"));
+    }
+
+    #[test]
+    fn from_env_reads_ai_skip_translation_cjk_percent() {
+        let _guard = env_lock().lock().expect("lock env");
+        set_required_env();
+
+        let config = AppConfig::from_env().expect("build config");
+        assert_eq!(config.ai_skip_translation_cjk_percent, 30);
+
+        unsafe {
+            env::set_var("AI_SKIP_TRANSLATION_CJK_PERCENT", "55");
+        }
+        let config = AppConfig::from_env().expect("build config");
+        assert_eq!(config.ai_skip_translation_cjk_percent, 55);
+
+        unsafe {
+            env::set_var("AI_SKIP_TRANSLATION_CJK_PERCENT", "101");
+        }
+        let err = AppConfig::from_env().expect_err("percent above 100 should fail");
+        assert!(err.to_string().contains("AI_SKIP_TRANSLATION_CJK_PERCENT"));
+    }
+
+    #[test]
+    fn from_env_reads_ai_fallback_models_in_order() {
+        let _guard = env_lock().lock().expect("lock env");
+        set_required_env();
+        unsafe {
+            env::set_var("AI_API_KEY", "test-key");
+            env::set_var(
+                "AI_FALLBACK_MODELS",
+                " openai/gpt-4o-mini, google/gemini-2.5-flash,,openai/gpt-4o-mini ",
+            );
+        }
+
+        let config = AppConfig::from_env().expect("build config");
+        let ai = config.ai.expect("ai config");
+        assert_eq!(
+            ai.fallback_models,
+            vec![
+                "openai/gpt-4o-mini".to_owned(),
+                "google/gemini-2.5-flash".to_owned()
+            ]
+        );
+        unsafe {
+            env::remove_var("AI_API_KEY");
+            env::remove_var("AI_FALLBACK_MODELS");
+        }
+    }
+
+    #[test]
+    fn normalize_ai_base_url_trims_endpoint_paths_and_rejects_non_http() {
+        let normalize = |raw: &str| normalize_ai_base_url(raw).map(|url| url.to_string());
+
+        assert_eq!(
+            normalize(" https://api.openai.com/v1 ").expect("plain root"),
+            "https://api.openai.com/v1/"
+        );
+        assert_eq!(
+            normalize("https://gateway.example/openai/v1/chat/completions/?x=1#frag")
+                .expect("pasted endpoint"),
+            "https://gateway.example/openai/v1/"
+        );
+        assert_eq!(
+            normalize("https://api.anthropic.com/v1/messages").expect("anthropic endpoint"),
+            "https://api.anthropic.com/v1/"
+        );
+        assert!(normalize("ftp://api.example/v1").is_err());
+        assert!(normalize("not a url").is_err());
+    }
+
+    #[test]
+    fn ai_base_url_warning_flags_missing_version_segment() {
+        let ai = |raw: &str| AiConfig {
+            provider: AiProvider::OpenAi,
+            base_url: normalize_ai_base_url(raw).expect("valid url"),
+            model: "gpt-4o-mini".to_owned(),
+            api_key: "test-key".to_owned(),
+            fallback_models: Vec::new(),
+            startup_probe: AiStartupProbe::Off,
+        };
+
+        assert_eq!(ai("https://api.openai.com/v1").base_url_warning(), None);
+        assert_eq!(
+            ai("https://gateway.example/v1beta").base_url_warning(),
+            None
+        );
+        assert_eq!(
+            ai("https://api.example.com/openai").base_url_warning(),
+            Some(
+                "AI_BASE_URL https://api.example.com/openai/ does not end with a version segment such as /v1/"
+                    .to_owned()
+            )
+        );
+        assert!(ai("https://api.example.com").base_url_warning().is_some());
+    }
+
+    #[test]
+    fn from_env_normalizes_ai_model_and_startup_probe() {
+        let _guard = env_lock().lock().expect("lock env");
+        set_required_env();
+        unsafe {
+            env::set_var("AI_API_KEY", "test-key");
+            env::set_var("AI_MODEL", " gpt-4.1-mini ");
+            env::set_var("AI_FALLBACK_MODELS", "gpt-4.1-mini, gpt-4o-mini");
+            env::set_var("AI_STARTUP_PROBE", "true");
+        }
+
+        let ai = AppConfig::from_env()
+            .expect("build config")
+            .ai
+            .expect("ai config");
+        assert_eq!(ai.model, "gpt-4.1-mini");
+        assert_eq!(ai.fallback_models, vec!["gpt-4o-mini".to_owned()]);
+        assert_eq!(ai.startup_probe, AiStartupProbe::Warn);
+
+        unsafe {
+            env::set_var("AI_STARTUP_PROBE_STRICT", "true");
+        }
+        let ai = AppConfig::from_env()
+            .expect("build config")
+            .ai
+            .expect("ai config");
+        assert_eq!(ai.startup_probe, AiStartupProbe::Strict);
+
+        unsafe {
+            env::set_var("AI_MODEL", "  ");
+        }
+        let err = AppConfig::from_env().expect_err("blank model should fail");
+        assert!(err.to_string().contains("AI_MODEL"));
+    }
+
+    #[test]
+    fn from_env_reads_ai_provider_and_its_defaults() {
+        let _guard = env_lock().lock().expect("lock env");
+        set_required_env();
+        unsafe {
+            env::set_var("AI_API_KEY", "test-key");
+        }
+
+        let ai = AppConfig::from_env()
+            .expect("build config")
+            .ai
+            .expect("ai config");
+        assert_eq!(ai.provider, AiProvider::OpenAi);
+        assert_eq!(ai.base_url.as_str(), "https://api.openai.com/v1/");
+
+        unsafe {
+            env::set_var("AI_PROVIDER", " Anthropic ");
+        }
+        let ai = AppConfig::from_env()
+            .expect("build config")
+            .ai
+            .expect("ai config");
+        assert_eq!(ai.provider, AiProvider::Anthropic);
+        assert_eq!(ai.base_url.as_str(), "https://api.anthropic.com/v1/");
+        assert_eq!(ai.model, "claude-3-5-haiku-latest");
+
+        unsafe {
+            env::set_var("AI_PROVIDER", "gemini");
+        }
+        let err = AppConfig::from_env().expect_err("unknown provider should fail");
+        assert!(err.to_string().contains("AI_PROVIDER"));
+    }
+
+    #[test]
+    fn from_env_reads_ai_daily_token_budget_per_user() {
+        let _guard = env_lock().lock().expect("lock env");
+        set_required_env();
+
+        let config = AppConfig::from_env().expect("build config");
+        assert_eq!(config.ai_daily_token_budget_per_user, None);
+
+        unsafe {
+            env::set_var("AI_DAILY_TOKEN_BUDGET_PER_USER", "200000");
+        }
+        let config = AppConfig::from_env().expect("build config");
+        assert_eq!(config.ai_daily_token_budget_per_user, Some(200_000));
+
+        unsafe {
+            env::set_var("AI_DAILY_TOKEN_BUDGET_PER_USER", "0");
+        }
+        let err = AppConfig::from_env().expect_err("zero budget should fail");
+        assert!(err.to_string().contains("AI_DAILY_TOKEN_BUDGET_PER_USER"));
+    }
+
     #[test]
     fn from_env_defaults_sqlite_pool_max_connections_to_eight() {
         let _guard = env_lock().lock().expect("lock env");
@@ -432,6 +1313,78 @@ mod tests {
         assert_eq!(config.sqlite_pool_max_connections, 1);
     }
 
+    #[test]
+    fn from_env_reads_task_retention_days() {
+        let _guard = env_lock().lock().expect("lock env");
+        set_required_env();
+
+        let config = AppConfig::from_env().expect("build config");
+        assert_eq!(config.task_retention_days, 30);
+
+        unsafe {
+            env::set_var("OCTORILL_TASK_RETENTION_DAYS", "7");
+        }
+        let config = AppConfig::from_env().expect("build config");
+        assert_eq!(config.task_retention_days, 7);
+
+        unsafe {
+            env::set_var("OCTORILL_TASK_RETENTION_DAYS", "0");
+        }
+        AppConfig::from_env().expect_err("zero retention should fail");
+    }
+
+    #[test]
+    fn from_env_reads_backup_settings() {
+        let _guard = env_lock().lock().expect("lock env");
+        set_required_env();
+
+        let config = AppConfig::from_env().expect("build config");
+        assert_eq!(config.backup_dir, PathBuf::from(".data/backups"));
+        assert_eq!(config.backup_retention_count, 7);
+
+        unsafe {
+            env::set_var("OCTORILL_BACKUP_DIR", "/var/backups/octo-rill");
+            env::set_var("OCTORILL_BACKUP_RETENTION", "30");
+        }
+        let config = AppConfig::from_env().expect("build config");
+        assert_eq!(config.backup_dir, PathBuf::from("/var/backups/octo-rill"));
+        assert_eq!(config.backup_retention_count, 30);
+    }
+
+    #[test]
+    fn from_env_reads_task_timeout_overrides() {
+        let _guard = env_lock().lock().expect("lock env");
+        set_required_env();
+
+        let config = AppConfig::from_env().expect("build config");
+        assert_eq!(
+            config.task_timeouts.sync,
+            std::time::Duration::from_secs(15 * 60)
+        );
+        assert_eq!(
+            config.task_timeouts.translate,
+            std::time::Duration::from_secs(10 * 60)
+        );
+        assert_eq!(
+            config.task_timeouts.brief,
+            std::time::Duration::from_secs(20 * 60)
+        );
+
+        unsafe {
+            env::set_var("OCTORILL_TASK_TIMEOUT_BRIEF_SECS", "90");
+        }
+        let config = AppConfig::from_env().expect("build config");
+        assert_eq!(
+            config.task_timeouts.brief,
+            std::time::Duration::from_secs(90)
+        );
+
+        unsafe {
+            env::set_var("OCTORILL_TASK_TIMEOUT_BRIEF_SECS", "0");
+        }
+        AppConfig::from_env().expect_err("zero timeout should fail");
+    }
+
     #[test]
     fn from_env_defaults_logging_thresholds() {
         let _guard = env_lock().lock().expect("lock env");
@@ -461,6 +1414,32 @@ mod tests {
         assert_eq!(config.logging.sqlite_write_slow_ms, 400);
     }
 
+    #[test]
+    fn from_env_reads_rate_limit_overrides() {
+        let _guard = env_lock().lock().expect("lock env");
+        set_required_env();
+
+        let config = AppConfig::from_env().expect("build config");
+        assert_eq!(config.rate_limits.user_per_minute, 120);
+        assert_eq!(config.rate_limits.auth_ip_per_minute, 30);
+        assert!(!config.trust_forwarded_headers);
+
+        unsafe {
+            env::set_var("OCTORILL_RATE_LIMIT_USER_PER_MIN", "600");
+            env::set_var("OCTORILL_RATE_LIMIT_AUTH_IP_PER_MIN", "10");
+            env::set_var("OCTORILL_TRUST_FORWARDED_HEADERS", "true");
+        }
+        let config = AppConfig::from_env().expect("build config");
+        assert_eq!(config.rate_limits.user_per_minute, 600);
+        assert_eq!(config.rate_limits.auth_ip_per_minute, 10);
+        assert!(config.trust_forwarded_headers);
+
+        unsafe {
+            env::set_var("OCTORILL_RATE_LIMIT_USER_PER_MIN", "0");
+        }
+        AppConfig::from_env().expect_err("zero rate should fail");
+    }
+
     #[test]
     fn from_env_accepts_sqlite_pool_max_connections_override() {
         let _guard = env_lock().lock().expect("lock env");
@@ -644,6 +1623,47 @@ mod tests {
         );
     }
 
+    #[test]
+    fn from_env_reads_smtp_config_with_port_derived_tls() {
+        let _guard = env_lock().lock().expect("lock env");
+        set_required_env();
+        unsafe {
+            env::set_var("SMTP_HOST", "smtp.example.com");
+            env::set_var("SMTP_PORT", "465");
+            env::set_var("SMTP_USERNAME", "mailer");
+            env::set_var("SMTP_PASSWORD", "secret");
+            env::set_var("SMTP_FROM", "OctoRill <brief@example.com>");
+            env::set_var("SMTP_DRY_RUN", "true");
+        }
+
+        let config = AppConfig::from_env().expect("build config");
+        let smtp = config.smtp.expect("smtp config");
+
+        assert_eq!(smtp.host, "smtp.example.com");
+        assert_eq!(smtp.port, 465);
+        assert_eq!(smtp.tls, SmtpTls::Tls);
+        assert!(smtp.dry_run);
+        assert!(!format!("{smtp:?}").contains("secret"));
+        set_required_env();
+    }
+
+    #[test]
+    fn from_env_rejects_partial_smtp_config() {
+        let _guard = env_lock().lock().expect("lock env");
+        set_required_env();
+        unsafe {
+            env::set_var("SMTP_HOST", "smtp.example.com");
+        }
+
+        let err = AppConfig::from_env().expect_err("partial smtp should fail");
+
+        assert!(
+            err.to_string()
+                .contains("SMTP_HOST and SMTP_FROM must be set together")
+        );
+        set_required_env();
+    }
+
     #[test]
     fn resolve_app_default_time_zone_prefers_legacy_runtime_when_env_unset() {
         let resolved = resolve_app_default_time_zone(None, Some("America/New_York"))
@@ -0,0 +1,245 @@
+use axum::{
+    Json,
+    extract::Request,
+    http::{HeaderMap, Method, StatusCode},
+    middleware::Next,
+    response::Response,
+};
+use base64::{Engine as _, engine::general_purpose::URL_SAFE_NO_PAD};
+use rand::Rng;
+use schemars::JsonSchema;
+use serde::Serialize;
+use tower_sessions::Session;
+
+use crate::error::{ApiError, codes};
+
+/// Header mutating requests must echo the session token in.
+pub const CSRF_HEADER: &str = "x-csrf-token";
+const SESSION_CSRF_TOKEN: &str = "csrf_token";
+const CSRF_TOKEN_BYTES: usize = 32;
+
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct CsrfTokenResponse {
+    token: String,
+}
+
+/// Hands out the token bound to the caller's session, minting one on first
+/// use. Works before login so passkey sign-in can be protected too.
+pub async fn csrf_token(session: Session) -> Result<Json<CsrfTokenResponse>, ApiError> {
+    Ok(Json(CsrfTokenResponse {
+        token: session_csrf_token(&session).await?,
+    }))
+}
+
+async fn session_csrf_token(session: &Session) -> Result<String, ApiError> {
+    if let Some(token) = session
+        .get::<String>(SESSION_CSRF_TOKEN)
+        .await
+        .map_err(ApiError::internal)?
+    {
+        return Ok(token);
+    }
+
+    let mut bytes = [0u8; CSRF_TOKEN_BYTES];
+    rand::rng().fill_bytes(&mut bytes);
+    let token = URL_SAFE_NO_PAD.encode(bytes);
+    session
+        .insert(SESSION_CSRF_TOKEN, token.as_str())
+        .await
+        .map_err(ApiError::internal)?;
+    Ok(token)
+}
+
+/// Route layer for `/api`: every non-safe method must carry [`CSRF_HEADER`]
+/// matching the session token. OAuth callbacks live outside `/api` and are
+/// plain GETs, so they never reach this check.
+pub async fn require_csrf_token(
+    session: Session,
+    request: Request,
+    next: Next,
+) -> Result<Response, ApiError> {
+    if is_safe_method(request.method()) {
+        return Ok(next.run(request).await);
+    }
+
+    let expected = session
+        .get::<String>(SESSION_CSRF_TOKEN)
+        .await
+        .map_err(ApiError::internal)?;
+    if !csrf_token_matches(expected.as_deref(), request.headers()) {
+        return Err(ApiError::new(
+            StatusCode::FORBIDDEN,
+            codes::CSRF_INVALID,
+            "missing or invalid csrf token; fetch /api/csrf and retry",
+        ));
+    }
+    Ok(next.run(request).await)
+}
+
+fn is_safe_method(method: &Method) -> bool {
+    matches!(*method, Method::GET | Method::HEAD | Method::OPTIONS)
+}
+
+fn csrf_token_matches(expected: Option<&str>, headers: &HeaderMap) -> bool {
+    let Some(expected) = expected else {
+        return false;
+    };
+    let Some(provided) = headers
+        .get(CSRF_HEADER)
+        .and_then(|value| value.to_str().ok())
+    else {
+        return false;
+    };
+    constant_time_eq(expected, provided)
+}
+
+/// Compares secrets without exiting early on the first differing byte.
+/// Length is not secret.
+pub(crate) fn constant_time_eq(expected: &str, provided: &str) -> bool {
+    expected.len() == provided.len()
+        && expected
+            .bytes()
+            .zip(provided.bytes())
+            .fold(0u8, |diff, (a, b)| diff | (a ^ b))
+            == 0
+}
+
+#[cfg(test)]
+mod tests {
+    use std::convert::Infallible;
+
+    use axum::{
+        Router,
+        body::{Body, to_bytes},
+        http::{Request, StatusCode, header},
+        middleware,
+        response::sse::{Event, Sse},
+        routing::{get, post},
+    };
+    use serde_json::Value;
+    use tower::ServiceExt;
+    use tower_sessions::{MemoryStore, SessionManagerLayer};
+
+    use super::{CSRF_HEADER, csrf_token, require_csrf_token};
+
+    fn protected_router() -> Router {
+        let api = Router::new()
+            .route("/csrf", get(csrf_token))
+            .route("/mutate", post(|| async { "ok" }))
+            .route(
+                "/events",
+                get(|| async {
+                    Sse::new(futures::stream::iter([Ok::<_, Infallible>(
+                        Event::default().data("hello"),
+                    )]))
+                }),
+            )
+            .route_layer(middleware::from_fn(require_csrf_token));
+        Router::new()
+            .nest("/api", api)
+            .layer(SessionManagerLayer::new(MemoryStore::default()).with_secure(false))
+    }
+
+    async fn issue_token(app: &Router) -> (String, String) {
+        let response = app
+            .clone()
+            .oneshot(
+                Request::builder()
+                    .uri("/api/csrf")
+                    .body(Body::empty())
+                    .expect("build csrf request"),
+            )
+            .await
+            .expect("csrf response");
+        assert_eq!(response.status(), StatusCode::OK);
+        let cookie = response
+            .headers()
+            .get(header::SET_COOKIE)
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| value.split(';').next())
+            .expect("session cookie")
+            .to_owned();
+        let body = to_bytes(response.into_body(), usize::MAX)
+            .await
+            .expect("read csrf body");
+        let body: Value = serde_json::from_slice(&body).expect("parse csrf body");
+        let token = body["token"].as_str().expect("token").to_owned();
+        (cookie, token)
+    }
+
+    async fn mutate(
+        app: &Router,
+        cookie: Option<&str>,
+        token: Option<&str>,
+    ) -> (StatusCode, Value) {
+        let mut request = Request::builder().method("POST").uri("/api/mutate");
+        if let Some(cookie) = cookie {
+            request = request.header(header::COOKIE, cookie);
+        }
+        if let Some(token) = token {
+            request = request.header(CSRF_HEADER, token);
+        }
+        let response = app
+            .clone()
+            .oneshot(request.body(Body::empty()).expect("build mutate request"))
+            .await
+            .expect("mutate response");
+        let status = response.status();
+        let body = to_bytes(response.into_body(), usize::MAX)
+            .await
+            .expect("read mutate body");
+        (status, serde_json::from_slice(&body).unwrap_or(Value::Null))
+    }
+
+    #[tokio::test]
+    async fn mutation_requires_matching_session_token() {
+        let app = protected_router();
+
+        let (status, body) = mutate(&app, None, None).await;
+        assert_eq!(status, StatusCode::FORBIDDEN);
+        assert_eq!(body["error"]["code"], "csrf_invalid");
+
+        let (cookie, token) = issue_token(&app).await;
+        let (status, body) = mutate(&app, Some(&cookie), None).await;
+        assert_eq!(status, StatusCode::FORBIDDEN);
+        assert_eq!(body["error"]["code"], "csrf_invalid");
+
+        let (status, _) = mutate(&app, Some(&cookie), Some("forged-token")).await;
+        assert_eq!(status, StatusCode::FORBIDDEN);
+
+        let (_, other_token) = issue_token(&app).await;
+        let (status, _) = mutate(&app, Some(&cookie), Some(&other_token)).await;
+        assert_eq!(
+            status,
+            StatusCode::FORBIDDEN,
+            "tokens are bound to a session"
+        );
+
+        let (status, _) = mutate(&app, Some(&cookie), Some(&token)).await;
+        assert_eq!(status, StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn safe_methods_and_event_streams_skip_the_check() {
+        let app = protected_router();
+
+        let response = app
+            .oneshot(
+                Request::builder()
+                    .uri("/api/events")
+                    .body(Body::empty())
+                    .expect("build events request"),
+            )
+            .await
+            .expect("events response");
+
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(
+            response
+                .headers()
+                .get(header::CONTENT_TYPE)
+                .and_then(|value| value.to_str().ok()),
+            Some("text/event-stream")
+        );
+    }
+}
@@ -0,0 +1,380 @@
+use std::{sync::Arc, time::Instant};
+
+use anyhow::{Context, Result};
+use axum::{Json, extract::State, http::StatusCode};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use serde_json::{Value, json};
+use sqlx::SqliteConnection;
+
+use crate::{
+    api,
+    error::{ApiError, ApiJson, codes},
+    jobs,
+    sqlite_write::SqliteWritePriority,
+    state::AppState,
+};
+
+/// Rows `integrity_check` reports at most; a healthy database reports one.
+const INTEGRITY_CHECK_MAX_ERRORS: i64 = 100;
+const SOURCE_MANUAL: &str = "manual";
+const SOURCE_SCHEDULE: &str = "schedule";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum DbMaintenanceAction {
+    /// `PRAGMA wal_checkpoint(TRUNCATE)`: folds the WAL back into the
+    /// database file and shrinks it to zero bytes.
+    Checkpoint,
+    /// Rebuilds the database file to reclaim free pages.
+    Vacuum,
+    IntegrityCheck,
+    /// Refreshes the query planner statistics.
+    Analyze,
+}
+
+impl DbMaintenanceAction {
+    fn as_str(self) -> &'static str {
+        match self {
+            Self::Checkpoint => "checkpoint",
+            Self::Vacuum => "vacuum",
+            Self::IntegrityCheck => "integrity_check",
+            Self::Analyze => "analyze",
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, JsonSchema)]
+pub struct DbMaintenanceRequest {
+    action: DbMaintenanceAction,
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema, sqlx::FromRow)]
+pub struct WalCheckpointResult {
+    /// 1 when a reader or writer kept the checkpoint from completing.
+    busy: i64,
+    /// Frames in the WAL; -1 when the database is not in WAL mode.
+    log_frames: i64,
+    checkpointed_frames: i64,
+}
+
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct DbMaintenanceResponse {
+    action: DbMaintenanceAction,
+    ok: bool,
+    duration_ms: u64,
+    finished_at: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    wal_checkpoint: Option<WalCheckpointResult>,
+    /// `integrity_check` output; `["ok"]` when no problem was found.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    integrity_check: Option<Vec<String>>,
+    /// Database size before and after a vacuum.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    bytes_before: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    bytes_after: Option<i64>,
+}
+
+#[derive(Debug, Serialize, JsonSchema, sqlx::FromRow)]
+pub struct DbMaintenanceRunItem {
+    action: String,
+    #[schemars(extend("enum" = ["manual", "schedule"]))]
+    source: String,
+    ok: bool,
+    duration_ms: i64,
+    finished_at: String,
+}
+
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct DbMaintenanceStatusResponse {
+    /// Last run per action; actions that never ran are absent.
+    runs: Vec<DbMaintenanceRunItem>,
+}
+
+pub async fn admin_get_db_maintenance(
+    State(state): State<Arc<AppState>>,
+    _: api::AdminUser,
+) -> Result<Json<DbMaintenanceStatusResponse>, ApiError> {
+    let runs = sqlx::query_as::<_, DbMaintenanceRunItem>(
+        r#"
+        SELECT action, source, ok, duration_ms, finished_at
+        FROM db_maintenance_runs
+        ORDER BY action ASC
+        "#,
+    )
+    .fetch_all(&state.pool)
+    .await
+    .map_err(ApiError::internal)?;
+    Ok(Json(DbMaintenanceStatusResponse { runs }))
+}
+
+/// Runs one maintenance action right away. Vacuum rewrites the whole file,
+/// so it is refused while any task is running.
+pub async fn admin_run_db_maintenance(
+    State(state): State<Arc<AppState>>,
+    api::AdminUser(acting_user_id): api::AdminUser,
+    ApiJson(req): ApiJson<DbMaintenanceRequest>,
+) -> Result<Json<DbMaintenanceResponse>, ApiError> {
+    state
+        .admin_mutation_limiter
+        .check(acting_user_id.as_str())?;
+    if req.action == DbMaintenanceAction::Vacuum {
+        let running = count_running_tasks(state.as_ref())
+            .await
+            .map_err(ApiError::internal)?;
+        if running > 0 {
+            return Err(ApiError::new(
+                StatusCode::CONFLICT,
+                codes::TASKS_RUNNING,
+                format!("vacuum needs an idle database; {running} task(s) are running"),
+            ));
+        }
+    }
+
+    let response = run_action(state.as_ref(), req.action, SOURCE_MANUAL)
+        .await
+        .map_err(ApiError::internal)?;
+    Ok(Json(response))
+}
+
+/// Weekly job: truncate the WAL, then refresh planner statistics.
+pub async fn run_scheduled(state: &AppState) -> Result<Value> {
+    let checkpoint = run_action(state, DbMaintenanceAction::Checkpoint, SOURCE_SCHEDULE).await?;
+    let analyze = run_action(state, DbMaintenanceAction::Analyze, SOURCE_SCHEDULE).await?;
+    Ok(json!({ "checkpoint": checkpoint, "analyze": analyze }))
+}
+
+async fn count_running_tasks(state: &AppState) -> Result<i64> {
+    sqlx::query_scalar::<_, i64>(r#"SELECT COUNT(*) FROM job_tasks WHERE status = ?"#)
+        .bind(jobs::STATUS_RUNNING)
+        .fetch_one(&state.pool)
+        .await
+        .context("failed to count running tasks")
+}
+
+/// Holds the writer permit for the whole action so in-process writes queue
+/// behind it instead of failing with `SQLITE_BUSY`.
+async fn run_action(
+    state: &AppState,
+    action: DbMaintenanceAction,
+    source: &str,
+) -> Result<DbMaintenanceResponse> {
+    let _permit = state
+        .sqlite_writer
+        .acquire_with_priority("db_maintenance", SqliteWritePriority::Foreground)
+        .await?;
+    // A connection of its own: VACUUM fails inside an open transaction.
+    let mut conn = state
+        .pool
+        .acquire()
+        .await
+        .context("failed to acquire maintenance connection")?;
+
+    let started = Instant::now();
+    let mut response = DbMaintenanceResponse {
+        action,
+        ok: true,
+        duration_ms: 0,
+        finished_at: String::new(),
+        wal_checkpoint: None,
+        integrity_check: None,
+        bytes_before: None,
+        bytes_after: None,
+    };
+    match action {
+        DbMaintenanceAction::Checkpoint => {
+            let result = sqlx::query_as::<_, WalCheckpointResult>(
+                r#"
+                SELECT busy, log AS log_frames, checkpointed AS checkpointed_frames
+                FROM pragma_wal_checkpoint('TRUNCATE')
+                "#,
+            )
+            .fetch_one(&mut *conn)
+            .await
+            .context("wal checkpoint failed")?;
+            response.ok = result.busy == 0;
+            response.wal_checkpoint = Some(result);
+        }
+        DbMaintenanceAction::Vacuum => {
+            response.bytes_before = Some(database_bytes(&mut conn).await?);
+            sqlx::query("VACUUM")
+                .execute(&mut *conn)
+                .await
+                .context("vacuum failed")?;
+            response.bytes_after = Some(database_bytes(&mut conn).await?);
+        }
+        DbMaintenanceAction::IntegrityCheck => {
+            let rows = sqlx::query_scalar::<_, String>(&format!(
+                "PRAGMA integrity_check({INTEGRITY_CHECK_MAX_ERRORS})"
+            ))
+            .fetch_all(&mut *conn)
+            .await
+            .context("integrity check failed")?;
+            response.ok = rows.len() == 1 && rows[0] == "ok";
+            response.integrity_check = Some(rows);
+        }
+        DbMaintenanceAction::Analyze => {
+            sqlx::query("ANALYZE")
+                .execute(&mut *conn)
+                .await
+                .context("analyze failed")?;
+        }
+    }
+    response.duration_ms = u64::try_from(started.elapsed().as_millis()).unwrap_or(u64::MAX);
+    response.finished_at = chrono::Utc::now().to_rfc3339();
+
+    sqlx::query(
+        r#"
+        INSERT INTO db_maintenance_runs (action, source, ok, duration_ms, detail_json, finished_at)
+        VALUES (?, ?, ?, ?, ?, ?)
+        ON CONFLICT(action) DO UPDATE SET
+          source = excluded.source,
+          ok = excluded.ok,
+          duration_ms = excluded.duration_ms,
+          detail_json = excluded.detail_json,
+          finished_at = excluded.finished_at
+        "#,
+    )
+    .bind(action.as_str())
+    .bind(source)
+    .bind(response.ok)
+    .bind(i64::try_from(response.duration_ms).unwrap_or(i64::MAX))
+    .bind(serde_json::to_string(&response).context("failed to encode maintenance result")?)
+    .bind(response.finished_at.as_str())
+    .execute(&mut *conn)
+    .await
+    .context("failed to record maintenance run")?;
+
+    tracing::info!(
+        action = action.as_str(),
+        source,
+        ok = response.ok,
+        duration_ms = response.duration_ms,
+        "db maintenance finished"
+    );
+    Ok(response)
+}
+
+async fn database_bytes(conn: &mut SqliteConnection) -> Result<i64> {
+    sqlx::query_scalar::<_, i64>(
+        r#"
+        SELECT page_count * page_size
+        FROM pragma_page_count(), pragma_page_size()
+        "#,
+    )
+    .fetch_one(&mut *conn)
+    .await
+    .context("failed to read database size")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use sqlx::{SqlitePool, sqlite::SqlitePoolOptions};
+
+    use crate::config::AppConfig;
+
+    fn admin_id() -> String {
+        crate::local_id::test_local_id("db-maintenance-admin")
+    }
+
+    async fn setup_pool() -> SqlitePool {
+        let pool = SqlitePoolOptions::new()
+            .max_connections(1)
+            .connect("sqlite::memory:")
+            .await
+            .expect("create sqlite memory db");
+        sqlx::migrate!("./migrations")
+            .run(&pool)
+            .await
+            .expect("run migrations");
+        sqlx::query(
+            r#"
+            INSERT INTO users (id, github_user_id, login, is_admin, created_at, updated_at)
+            VALUES (?, 1, 'admin', 1, '2026-02-01T00:00:00Z', '2026-02-01T00:00:00Z')
+            "#,
+        )
+        .bind(admin_id())
+        .execute(&pool)
+        .await
+        .expect("seed admin");
+        pool
+    }
+
+    fn setup_state(pool: SqlitePool) -> Arc<AppState> {
+        Arc::new(AppState::for_tests(pool, AppConfig::for_tests()))
+    }
+
+    fn request(action: DbMaintenanceAction) -> ApiJson<DbMaintenanceRequest> {
+        ApiJson(DbMaintenanceRequest { action })
+    }
+
+    #[tokio::test]
+    async fn integrity_check_reports_ok_and_records_last_run() {
+        let pool = setup_pool().await;
+        let state = setup_state(pool);
+
+        let Json(response) = admin_run_db_maintenance(
+            State(state.clone()),
+            api::AdminUser(admin_id()),
+            request(DbMaintenanceAction::IntegrityCheck),
+        )
+        .await
+        .expect("run integrity check");
+        assert!(response.ok);
+        assert_eq!(response.integrity_check, Some(vec!["ok".to_owned()]));
+
+        let Json(status) = admin_get_db_maintenance(State(state), api::AdminUser(admin_id()))
+            .await
+            .expect("load maintenance status");
+        assert_eq!(status.runs.len(), 1);
+        assert_eq!(status.runs[0].action, "integrity_check");
+        assert_eq!(status.runs[0].source, "manual");
+        assert!(status.runs[0].ok);
+    }
+
+    #[tokio::test]
+    async fn vacuum_is_rejected_while_a_task_is_running() {
+        let pool = setup_pool().await;
+        sqlx::query(
+            r#"
+            INSERT INTO job_tasks (
+              id, task_type, status, source, payload_json, created_at, started_at, updated_at
+            )
+            VALUES (
+              'task-db-maintenance-1', 'sync.releases', 'running', 'api', '{}',
+              '2026-02-01T00:00:00Z', '2026-02-01T00:00:00Z', '2026-02-01T00:00:00Z'
+            )
+            "#,
+        )
+        .execute(&pool)
+        .await
+        .expect("seed running task");
+        let state = setup_state(pool.clone());
+
+        let err = admin_run_db_maintenance(
+            State(state.clone()),
+            api::AdminUser(admin_id()),
+            request(DbMaintenanceAction::Vacuum),
+        )
+        .await
+        .expect_err("vacuum while a task runs");
+        assert_eq!(err.code(), "tasks_running");
+
+        sqlx::query("UPDATE job_tasks SET status = 'succeeded'")
+            .execute(&pool)
+            .await
+            .expect("finish task");
+        let Json(response) = admin_run_db_maintenance(
+            State(state),
+            api::AdminUser(admin_id()),
+            request(DbMaintenanceAction::Vacuum),
+        )
+        .await
+        .expect("vacuum once idle");
+        assert!(response.ok);
+        assert!(response.bytes_after.is_some());
+    }
+}
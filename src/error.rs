@@ -1,11 +1,91 @@
 use axum::{
     Json,
+    extract::{FromRequest, Request, rejection::JsonRejection},
     http::StatusCode,
     response::{IntoResponse, Response},
 };
-use serde_json::json;
+use schemars::JsonSchema;
+use serde::Serialize;
 
-#[derive(Debug)]
+/// Every `code` an error response can carry. Clients branch on these, so
+/// renaming one is a breaking API change.
+pub mod codes {
+    pub const ACCOUNT_DISABLED: &str = "account_disabled";
+    pub const ADMIN_RATE_LIMITED: &str = "admin_rate_limited";
+    pub const AI_BUDGET_EXCEEDED: &str = "ai_budget_exceeded";
+    pub const BAD_REQUEST: &str = "bad_request";
+    pub const BOOKMARK_NOT_FOUND: &str = "bookmark_not_found";
+    pub const CANNOT_DELETE_SELF: &str = "cannot_delete_self";
+    pub const CANNOT_DISABLE_SELF: &str = "cannot_disable_self";
+    pub const CSRF_INVALID: &str = "csrf_invalid";
+    pub const FOLLOW_SOURCE_NOT_FOUND: &str = "follow_source_not_found";
+    pub const FORBIDDEN: &str = "forbidden";
+    pub const FORBIDDEN_ADMIN_ONLY: &str = "forbidden_admin_only";
+    pub const GITHUB_ALREADY_BOUND: &str = "github_already_bound";
+    pub const GITHUB_CONNECTION_NOT_FOUND: &str = "github_connection_not_found";
+    pub const GITHUB_UNAVAILABLE: &str = "github_unavailable";
+    pub const IMPERSONATION_READ_ONLY: &str = "impersonation_read_only";
+    pub const INTERNAL_ERROR: &str = "internal_error";
+    pub const INVALID_JSON: &str = "invalid_json";
+    pub const INVALID_TASK_STATE: &str = "invalid_task_state";
+    pub const LAST_ADMIN_GUARD: &str = "last_admin_guard";
+    pub const LAST_GITHUB_CONNECTION_GUARD: &str = "last_github_connection_guard";
+    pub const LINUXDO_ACCOUNT_CONFLICT: &str = "linuxdo_account_conflict";
+    pub const LINUXDO_ALREADY_BOUND: &str = "linuxdo_already_bound";
+    pub const LINUXDO_OAUTH_NOT_CONFIGURED: &str = "linuxdo_oauth_not_configured";
+    pub const METHOD_NOT_ALLOWED: &str = "method_not_allowed";
+    pub const METRICS_UNAVAILABLE: &str = "metrics_unavailable";
+    pub const NOT_FOUND: &str = "not_found";
+    pub const NOTIFICATION_CHANNEL_NOT_FOUND: &str = "notification_channel_not_found";
+    pub const PASSKEY_ALREADY_BOUND: &str = "passkey_already_bound";
+    pub const PASSKEY_AUTHENTICATION_EXPIRED: &str = "passkey_authentication_expired";
+    pub const PASSKEY_AUTHENTICATION_FAILED: &str = "passkey_authentication_failed";
+    pub const PASSKEY_AUTHENTICATION_MISSING: &str = "passkey_authentication_missing";
+    pub const PASSKEY_GITHUB_REQUIRED: &str = "passkey_github_required";
+    pub const PASSKEY_MISMATCH: &str = "passkey_mismatch";
+    pub const PASSKEY_NOT_FOUND: &str = "passkey_not_found";
+    pub const PASSKEY_REGISTRATION_EXPIRED: &str = "passkey_registration_expired";
+    pub const PASSKEY_REGISTRATION_FAILED: &str = "passkey_registration_failed";
+    pub const PASSKEY_REGISTRATION_MISSING: &str = "passkey_registration_missing";
+    pub const PASSKEY_RETRY_REQUIRED: &str = "passkey_retry_required";
+    pub const PASSKEY_USER_NOT_FOUND: &str = "passkey_user_not_found";
+    pub const PAT_FORBIDDEN: &str = "pat_forbidden";
+    pub const PAT_INVALID: &str = "pat_invalid";
+    pub const PAT_REQUIRED: &str = "pat_required";
+    pub const PAYLOAD_TOO_LARGE: &str = "payload_too_large";
+    pub const RATE_LIMITED: &str = "rate_limited";
+    pub const RATE_LIMITED_LOCAL: &str = "rate_limited_local";
+    pub const REACTION_STATE_CONFLICT: &str = "reaction_state_conflict";
+    pub const REAUTH_REQUIRED: &str = "reauth_required";
+    pub const RELEASE_NOT_FOUND_OR_NOT_CACHED: &str = "release_not_found_or_not_cached";
+    pub const REPO_NOT_FOUND: &str = "repo_not_found";
+    pub const REPOSITORY_INACCESSIBLE: &str = "repository_inaccessible";
+    pub const SYNC_ISSUE_NOT_FOUND: &str = "sync_issue_not_found";
+    pub const SYNC_REQUIRED: &str = "sync_required";
+    pub const TARGET_USER_DISABLED: &str = "target_user_disabled";
+    pub const TASK_CANCELED: &str = "task_canceled";
+    pub const TASK_CONFLICT: &str = "task_conflict";
+    pub const TASKS_RUNNING: &str = "tasks_running";
+    pub const UNAUTHORIZED: &str = "unauthorized";
+    pub const UNSUPPORTED_LANGUAGE: &str = "unsupported_language";
+    pub const UNSUPPORTED_MEDIA_TYPE: &str = "unsupported_media_type";
+}
+
+/// JSON body of every error response.
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct ApiErrorBody {
+    /// Always `false`.
+    pub ok: bool,
+    pub error: ApiErrorDetail,
+}
+
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct ApiErrorDetail {
+    pub code: &'static str,
+    pub message: String,
+}
+
+#[derive(Debug, Clone)]
 pub struct ApiError {
     status: StatusCode,
     code: &'static str,
@@ -25,14 +105,36 @@ impl ApiError {
         self.code
     }
 
+    /// Keeps status and code, naming the item the error is about.
+    pub fn with_message_prefix(mut self, prefix: impl std::fmt::Display) -> Self {
+        self.message = format!("{prefix}: {}", self.message);
+        self
+    }
+
     pub fn bad_request(message: impl Into<String>) -> Self {
-        Self::new(StatusCode::BAD_REQUEST, "bad_request", message)
+        Self::new(StatusCode::BAD_REQUEST, codes::BAD_REQUEST, message)
+    }
+
+    pub fn task_canceled() -> Self {
+        Self::new(
+            StatusCode::CONFLICT,
+            codes::TASK_CANCELED,
+            crate::jobs::TASK_CANCELED_ERROR,
+        )
+    }
+
+    pub fn ai_budget_exceeded(message: impl Into<String>) -> Self {
+        Self::new(
+            StatusCode::TOO_MANY_REQUESTS,
+            codes::AI_BUDGET_EXCEEDED,
+            message,
+        )
     }
 
     pub fn internal(err: impl std::fmt::Display) -> Self {
         Self::new(
             StatusCode::INTERNAL_SERVER_ERROR,
-            "internal_error",
+            codes::INTERNAL_ERROR,
             err.to_string(),
         )
     }
@@ -46,17 +148,68 @@ impl std::fmt::Display for ApiError {
 
 impl std::error::Error for ApiError {}
 
+impl From<crate::jobs::JobError> for ApiError {
+    fn from(err: crate::jobs::JobError) -> Self {
+        use crate::jobs::JobError;
+
+        match err {
+            JobError::NotFound => {
+                Self::new(StatusCode::NOT_FOUND, codes::NOT_FOUND, "task not found")
+            }
+            JobError::InvalidState { reason } => {
+                Self::new(StatusCode::CONFLICT, codes::INVALID_TASK_STATE, reason)
+            }
+            JobError::Conflict => Self::new(
+                StatusCode::CONFLICT,
+                codes::TASK_CONFLICT,
+                "task changed state concurrently; reload and retry",
+            ),
+            JobError::Internal(err) => Self::internal(err),
+        }
+    }
+}
+
+impl From<JsonRejection> for ApiError {
+    fn from(rejection: JsonRejection) -> Self {
+        let status = rejection.status();
+        let code = match status {
+            StatusCode::PAYLOAD_TOO_LARGE => codes::PAYLOAD_TOO_LARGE,
+            StatusCode::UNSUPPORTED_MEDIA_TYPE => codes::UNSUPPORTED_MEDIA_TYPE,
+            _ => codes::INVALID_JSON,
+        };
+        Self::new(status, code, rejection.body_text())
+    }
+}
+
+/// `Json` request extractor whose rejections come back as [`ApiErrorBody`]
+/// instead of axum's plain-text bodies.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ApiJson<T>(pub T);
+
+impl<T, S> FromRequest<S> for ApiJson<T>
+where
+    Json<T>: FromRequest<S, Rejection = JsonRejection>,
+    S: Send + Sync,
+{
+    type Rejection = ApiError;
+
+    async fn from_request(req: Request, state: &S) -> Result<Self, Self::Rejection> {
+        let Json(value) = Json::<T>::from_request(req, state).await?;
+        Ok(Self(value))
+    }
+}
+
 impl IntoResponse for ApiError {
     fn into_response(self) -> Response {
         (
             self.status,
-            Json(json!({
-                "ok": false,
-                "error": {
-                    "code": self.code,
-                    "message": self.message,
-                }
-            })),
+            Json(ApiErrorBody {
+                ok: false,
+                error: ApiErrorDetail {
+                    code: self.code,
+                    message: self.message,
+                },
+            }),
         )
             .into_response()
     }
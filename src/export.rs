@@ -0,0 +1,759 @@
+use std::sync::Arc;
+
+use anyhow::{Context, Result};
+use async_zip::{Compression, ZipEntryBuilder, tokio::write::ZipFileWriter};
+use axum::{
+    body::{Body, Bytes},
+    extract::{Query, State},
+    http::{HeaderValue, StatusCode, header},
+    response::Response,
+};
+use chrono::{DateTime, SecondsFormat, Utc};
+use serde::{Deserialize, Serialize};
+use tokio::io::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt, DuplexStream};
+use tokio_util::compat::FuturesAsyncWriteCompatExt;
+
+use crate::{api, error::ApiError, state::AppState};
+
+// Bounded pipe between the archive writer task and the response body, so a
+// slow client applies backpressure instead of the export piling up in memory.
+const EXPORT_PIPE_BUFFER_BYTES: usize = 64 * 1024;
+const EXPORT_RELEASE_PAGE_SIZE: i64 = 200;
+
+#[derive(Debug, Deserialize)]
+pub struct ExportQuery {
+    #[serde(default)]
+    format: Option<String>, // zip (default) | json
+    /// RFC3339 lower bound applied to each dataset's own timestamp.
+    #[serde(default)]
+    since: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ExportFormat {
+    Zip,
+    Json,
+}
+
+#[derive(Debug, Clone)]
+struct ExportScope {
+    user_id: String,
+    since: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, sqlx::FromRow)]
+struct ExportStarredRepo {
+    repo_id: i64,
+    full_name: String,
+    owner_login: String,
+    name: String,
+    description: Option<String>,
+    html_url: String,
+    stargazed_at: Option<String>,
+    is_private: bool,
+    updated_at: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, sqlx::FromRow)]
+struct ExportRelease {
+    release_id: i64,
+    repo_id: i64,
+    repo_full_name: Option<String>,
+    tag_name: String,
+    name: Option<String>,
+    body: Option<String>,
+    html_url: String,
+    published_at: Option<String>,
+    created_at: Option<String>,
+    is_prerelease: bool,
+    is_draft: bool,
+    updated_at: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, sqlx::FromRow)]
+struct ExportNotification {
+    thread_id: String,
+    repo_full_name: Option<String>,
+    subject_title: Option<String>,
+    subject_type: Option<String>,
+    reason: Option<String>,
+    updated_at: Option<String>,
+    unread: bool,
+    html_url: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, sqlx::FromRow)]
+struct ExportBrief {
+    id: String,
+    date: String,
+    window_start_utc: Option<String>,
+    window_end_utc: Option<String>,
+    effective_time_zone: Option<String>,
+    content_markdown: String,
+    created_at: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, sqlx::FromRow)]
+struct ExportTranslation {
+    entity_type: String,
+    entity_id: String,
+    lang: String,
+    title: Option<String>,
+    summary: Option<String>,
+    status: String,
+    updated_at: String,
+}
+
+fn parse_export_format(raw: Option<&str>) -> Result<ExportFormat, ApiError> {
+    match raw.map(str::trim).filter(|value| !value.is_empty()) {
+        None | Some("zip") => Ok(ExportFormat::Zip),
+        Some("json") => Ok(ExportFormat::Json),
+        Some(_) => Err(ApiError::bad_request("format must be zip or json")),
+    }
+}
+
+fn parse_export_since(raw: Option<&str>) -> Result<Option<String>, ApiError> {
+    let Some(raw) = raw.map(str::trim).filter(|value| !value.is_empty()) else {
+        return Ok(None);
+    };
+    let since = DateTime::parse_from_rfc3339(raw)
+        .map_err(|_| ApiError::bad_request("since must be an RFC3339 timestamp"))?;
+    Ok(Some(
+        since
+            .with_timezone(&Utc)
+            .to_rfc3339_opts(SecondsFormat::Secs, true),
+    ))
+}
+
+async fn load_starred(state: &AppState, scope: &ExportScope) -> Result<Vec<ExportStarredRepo>> {
+    sqlx::query_as::<_, ExportStarredRepo>(
+        r#"
+        SELECT
+          repo_id, full_name, owner_login, name, description, html_url, stargazed_at,
+          is_private, updated_at
+        FROM starred_repos
+        WHERE user_id = ?
+          AND (? IS NULL OR julianday(COALESCE(stargazed_at, updated_at)) >= julianday(?))
+        ORDER BY full_name ASC, repo_id ASC
+        "#,
+    )
+    .bind(scope.user_id.as_str())
+    .bind(scope.since.as_deref())
+    .bind(scope.since.as_deref())
+    .fetch_all(&state.pool)
+    .await
+    .context("failed to load starred repos for export")
+}
+
+async fn load_release_page(
+    state: &AppState,
+    scope: &ExportScope,
+    after_release_id: i64,
+) -> Result<Vec<ExportRelease>> {
+    sqlx::query_as::<_, ExportRelease>(
+        r#"
+        SELECT
+          r.release_id,
+          r.repo_id,
+          (
+            SELECT v.full_name
+            FROM user_release_visible_repos v
+            WHERE v.user_id = ?
+              AND v.repo_id = r.repo_id
+            LIMIT 1
+          ) AS repo_full_name,
+          r.tag_name,
+          r.name,
+          r.body,
+          r.html_url,
+          r.published_at,
+          r.created_at,
+          r.is_prerelease,
+          r.is_draft,
+          r.updated_at
+        FROM repo_releases r
+        WHERE r.repo_id IN (
+            SELECT repo_id
+            FROM user_release_visible_repos
+            WHERE user_id = ?
+          )
+          AND r.release_id > ?
+          AND (
+            ? IS NULL
+            OR julianday(COALESCE(r.published_at, r.created_at, r.updated_at)) >= julianday(?)
+          )
+        ORDER BY r.release_id ASC
+        LIMIT ?
+        "#,
+    )
+    .bind(scope.user_id.as_str())
+    .bind(scope.user_id.as_str())
+    .bind(after_release_id)
+    .bind(scope.since.as_deref())
+    .bind(scope.since.as_deref())
+    .bind(EXPORT_RELEASE_PAGE_SIZE)
+    .fetch_all(&state.pool)
+    .await
+    .context("failed to load releases for export")
+}
+
+async fn load_notifications(
+    state: &AppState,
+    scope: &ExportScope,
+) -> Result<Vec<ExportNotification>> {
+    sqlx::query_as::<_, ExportNotification>(
+        r#"
+        SELECT
+          thread_id, repo_full_name, subject_title, subject_type, reason, updated_at, unread,
+          html_url
+        FROM notifications
+        WHERE user_id = ?
+          AND (? IS NULL OR julianday(updated_at) >= julianday(?))
+        ORDER BY updated_at DESC, thread_id ASC
+        "#,
+    )
+    .bind(scope.user_id.as_str())
+    .bind(scope.since.as_deref())
+    .bind(scope.since.as_deref())
+    .fetch_all(&state.pool)
+    .await
+    .context("failed to load notifications for export")
+}
+
+/// Latest snapshot per brief date, oldest date first.
+async fn load_briefs(state: &AppState, scope: &ExportScope) -> Result<Vec<ExportBrief>> {
+    let rows = sqlx::query_as::<_, ExportBrief>(
+        r#"
+        SELECT
+          id, date, window_start_utc, window_end_utc, effective_time_zone, content_markdown,
+          created_at
+        FROM briefs
+        WHERE user_id = ?
+          AND (? IS NULL OR julianday(created_at) >= julianday(?))
+        ORDER BY date ASC, created_at DESC, id DESC
+        "#,
+    )
+    .bind(scope.user_id.as_str())
+    .bind(scope.since.as_deref())
+    .bind(scope.since.as_deref())
+    .fetch_all(&state.pool)
+    .await
+    .context("failed to load briefs for export")?;
+
+    let mut briefs: Vec<ExportBrief> = Vec::with_capacity(rows.len());
+    for row in rows {
+        if briefs.last().is_some_and(|last| last.date == row.date) {
+            continue;
+        }
+        briefs.push(row);
+    }
+    Ok(briefs)
+}
+
+async fn load_translations(
+    state: &AppState,
+    scope: &ExportScope,
+) -> Result<Vec<ExportTranslation>> {
+    sqlx::query_as::<_, ExportTranslation>(
+        r#"
+        SELECT entity_type, entity_id, lang, title, summary, status, updated_at
+        FROM ai_translations
+        WHERE user_id = ?
+          AND (? IS NULL OR julianday(updated_at) >= julianday(?))
+        ORDER BY entity_type ASC, entity_id ASC, lang ASC
+        "#,
+    )
+    .bind(scope.user_id.as_str())
+    .bind(scope.since.as_deref())
+    .bind(scope.since.as_deref())
+    .fetch_all(&state.pool)
+    .await
+    .context("failed to load translations for export")
+}
+
+/// Writes `releases.json` page by page; release bodies are the bulk of an
+/// export, so they are never held in memory all at once.
+async fn write_releases_json<W>(state: &AppState, scope: &ExportScope, writer: &mut W) -> Result<()>
+where
+    W: AsyncWrite + Unpin,
+{
+    writer.write_all(b"[").await?;
+    let mut after_release_id = i64::MIN;
+    let mut first = true;
+    loop {
+        let page = load_release_page(state, scope, after_release_id).await?;
+        for release in &page {
+            if !first {
+                writer.write_all(b",").await?;
+            }
+            first = false;
+            writer.write_all(&serde_json::to_vec(release)?).await?;
+        }
+        match page.last() {
+            Some(last) if page.len() as i64 == EXPORT_RELEASE_PAGE_SIZE => {
+                after_release_id = last.release_id;
+            }
+            _ => break,
+        }
+    }
+    writer.write_all(b"]").await?;
+    Ok(())
+}
+
+async fn write_zip_json_entry<T: Serialize>(
+    zip: &mut ZipFileWriter<DuplexStream>,
+    filename: &str,
+    value: &T,
+) -> Result<()> {
+    let entry = ZipEntryBuilder::new(filename.to_owned().into(), Compression::Deflate);
+    zip.write_entry_whole(entry, &serde_json::to_vec_pretty(value)?)
+        .await
+        .with_context(|| format!("failed to write {filename}"))
+}
+
+async fn write_zip_export(
+    state: &AppState,
+    scope: &ExportScope,
+    writer: DuplexStream,
+) -> Result<()> {
+    let mut zip = ZipFileWriter::with_tokio(writer);
+
+    write_zip_json_entry(&mut zip, "starred.json", &load_starred(state, scope).await?).await?;
+
+    let entry = ZipEntryBuilder::new("releases.json".to_owned().into(), Compression::Deflate);
+    let mut releases = zip
+        .write_entry_stream(entry)
+        .await
+        .context("failed to open releases.json")?
+        .compat_write();
+    write_releases_json(state, scope, &mut releases).await?;
+    releases
+        .into_inner()
+        .close()
+        .await
+        .context("failed to close releases.json")?;
+
+    write_zip_json_entry(
+        &mut zip,
+        "notifications.json",
+        &load_notifications(state, scope).await?,
+    )
+    .await?;
+    for brief in load_briefs(state, scope).await? {
+        let filename = format!("briefs/{}.md", brief.date);
+        let entry = ZipEntryBuilder::new(filename.clone().into(), Compression::Deflate);
+        zip.write_entry_whole(entry, brief.content_markdown.as_bytes())
+            .await
+            .with_context(|| format!("failed to write {filename}"))?;
+    }
+    write_zip_json_entry(
+        &mut zip,
+        "translations.json",
+        &load_translations(state, scope).await?,
+    )
+    .await?;
+
+    let mut writer = zip
+        .close()
+        .await
+        .context("failed to finish export archive")?
+        .into_inner();
+    writer.shutdown().await?;
+    Ok(())
+}
+
+async fn write_json_export(
+    state: &AppState,
+    scope: &ExportScope,
+    mut writer: DuplexStream,
+) -> Result<()> {
+    let header = format!(
+        r#"{{"exported_at":{},"since":{}"#,
+        serde_json::to_string(&Utc::now().to_rfc3339())?,
+        serde_json::to_string(&scope.since)?,
+    );
+    writer.write_all(header.as_bytes()).await?;
+    writer.write_all(b",\"starred\":").await?;
+    writer
+        .write_all(&serde_json::to_vec(&load_starred(state, scope).await?)?)
+        .await?;
+    writer.write_all(b",\"releases\":").await?;
+    write_releases_json(state, scope, &mut writer).await?;
+    writer.write_all(b",\"notifications\":").await?;
+    writer
+        .write_all(&serde_json::to_vec(
+            &load_notifications(state, scope).await?,
+        )?)
+        .await?;
+    writer.write_all(b",\"briefs\":").await?;
+    writer
+        .write_all(&serde_json::to_vec(&load_briefs(state, scope).await?)?)
+        .await?;
+    writer.write_all(b",\"translations\":").await?;
+    writer
+        .write_all(&serde_json::to_vec(
+            &load_translations(state, scope).await?,
+        )?)
+        .await?;
+    writer.write_all(b"}").await?;
+    writer.shutdown().await?;
+    Ok(())
+}
+
+fn export_body(
+    mut reader: impl AsyncRead + Unpin + Send + 'static,
+    done: tokio::sync::oneshot::Receiver<Result<(), String>>,
+) -> Body {
+    let stream = async_stream::stream! {
+        let mut buffer = [0_u8; 16 * 1024];
+        loop {
+            match reader.read(&mut buffer).await {
+                Ok(0) => break,
+                Ok(read) => yield Ok::<Bytes, std::io::Error>(Bytes::copy_from_slice(&buffer[..read])),
+                Err(err) => {
+                    yield Err::<Bytes, std::io::Error>(err);
+                    return;
+                }
+            }
+        }
+        // Surface writer failures as a body error so the client sees an
+        // aborted download instead of a silently truncated archive.
+        if let Ok(Err(message)) = done.await {
+            yield Err::<Bytes, std::io::Error>(std::io::Error::other(message));
+        }
+    };
+    Body::from_stream(stream)
+}
+
+pub async fn export_user_data(
+    State(state): State<Arc<AppState>>,
+    api::AuthedUser(user_id): api::AuthedUser,
+    Query(query): Query<ExportQuery>,
+) -> Result<Response, ApiError> {
+    let format = parse_export_format(query.format.as_deref())?;
+    let since = parse_export_since(query.since.as_deref())?;
+    let scope = ExportScope { user_id, since };
+
+    let (writer, reader) = tokio::io::duplex(EXPORT_PIPE_BUFFER_BYTES);
+    let (done_tx, done_rx) = tokio::sync::oneshot::channel();
+    let task_state = state.clone();
+    tokio::spawn(async move {
+        let result = match format {
+            ExportFormat::Zip => write_zip_export(task_state.as_ref(), &scope, writer).await,
+            ExportFormat::Json => write_json_export(task_state.as_ref(), &scope, writer).await,
+        };
+        if let Err(err) = &result {
+            tracing::warn!(
+                user_id = scope.user_id.as_str(),
+                error = %format!("{err:#}"),
+                "user data export aborted"
+            );
+        }
+        let _ = done_tx.send(result.map_err(|err| format!("{err:#}")));
+    });
+
+    let (content_type, extension) = match format {
+        ExportFormat::Zip => ("application/zip", "zip"),
+        ExportFormat::Json => ("application/json", "json"),
+    };
+    let filename = format!(
+        "octo-rill-export-{}.{extension}",
+        Utc::now().format("%Y%m%d")
+    );
+    let mut response = Response::new(export_body(reader, done_rx));
+    *response.status_mut() = StatusCode::OK;
+    response
+        .headers_mut()
+        .insert(header::CONTENT_TYPE, HeaderValue::from_static(content_type));
+    response.headers_mut().insert(
+        header::CONTENT_DISPOSITION,
+        HeaderValue::from_str(&format!(r#"attachment; filename="{}""#, filename))
+            .map_err(ApiError::internal)?,
+    );
+
+    Ok(response)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::BTreeMap;
+
+    use async_zip::base::read::mem::ZipFileReader;
+    use sqlx::{
+        SqlitePool,
+        sqlite::{SqliteConnectOptions, SqlitePoolOptions},
+    };
+
+    use crate::config::AppConfig;
+
+    fn test_user_id() -> String {
+        crate::local_id::test_local_id("export-user")
+    }
+
+    async fn setup_pool() -> SqlitePool {
+        let database_path = std::env::temp_dir().join(format!(
+            "octo-rill-export-test-{}.db",
+            crate::local_id::generate_local_id(),
+        ));
+        let options = SqliteConnectOptions::new()
+            .filename(&database_path)
+            .create_if_missing(true);
+        let pool = SqlitePoolOptions::new()
+            .max_connections(1)
+            .connect_with(options)
+            .await
+            .expect("create sqlite db");
+        sqlx::migrate!("./migrations")
+            .run(&pool)
+            .await
+            .expect("run migrations");
+        seed_user_data(&pool).await;
+        pool
+    }
+
+    async fn seed_user_data(pool: &SqlitePool) {
+        for statement in [
+            r#"
+            INSERT INTO users (id, github_user_id, login, created_at, updated_at)
+            VALUES ('user-export-test', 303, 'export-test', '2026-02-01T00:00:00Z', '2026-02-01T00:00:00Z')
+            "#,
+            r#"
+            INSERT INTO starred_repos (
+              id, user_id, repo_id, full_name, owner_login, name, description, html_url,
+              stargazed_at, is_private, updated_at
+            )
+            VALUES (
+              'star-export-1', 'user-export-test', 42, 'octo/rill', 'octo', 'rill', 'feeds',
+              'https://github.com/octo/rill', '2026-02-01T00:00:00Z', 0, '2026-02-01T00:00:00Z'
+            )
+            "#,
+            r#"
+            INSERT INTO repo_releases (
+              id, repo_id, release_id, tag_name, name, body, html_url, published_at, created_at,
+              is_prerelease, is_draft, updated_at
+            )
+            VALUES
+              ('rel-export-1', 42, 1001, 'v1.0.0', 'First', 'initial body',
+               'https://github.com/octo/rill/releases/tag/v1.0.0', '2026-02-10T00:00:00Z',
+               '2026-02-10T00:00:00Z', 0, 0, '2026-02-10T00:00:00Z'),
+              ('rel-export-2', 42, 1002, 'v1.1.0', NULL, 'second body',
+               'https://github.com/octo/rill/releases/tag/v1.1.0', '2026-03-10T00:00:00Z',
+               '2026-03-10T00:00:00Z', 1, 0, '2026-03-10T00:00:00Z'),
+              ('rel-export-other', 77, 2001, 'v9.0.0', NULL, 'not starred',
+               'https://github.com/other/repo/releases/tag/v9.0.0', '2026-03-10T00:00:00Z',
+               '2026-03-10T00:00:00Z', 0, 0, '2026-03-10T00:00:00Z')
+            "#,
+            r#"
+            INSERT INTO notifications (
+              id, user_id, thread_id, repo_full_name, subject_title, subject_type, reason,
+              updated_at, unread, html_url
+            )
+            VALUES (
+              'notif-export-1', 'user-export-test', 'thread-1', 'octo/rill', 'Bug report',
+              'Issue', 'subscribed', '2026-03-11T00:00:00Z', 1, 'https://github.com/octo/rill/issues/1'
+            )
+            "#,
+            r#"
+            INSERT INTO briefs (id, user_id, date, content_markdown, created_at, updated_at)
+            VALUES
+              ('brief-export-old', 'user-export-test', '2026-03-10', '## stale', '2026-03-10T01:00:00Z', '2026-03-10T01:00:00Z'),
+              ('brief-export-new', 'user-export-test', '2026-03-10', '## latest', '2026-03-10T02:00:00Z', '2026-03-10T02:00:00Z')
+            "#,
+            r#"
+            INSERT INTO ai_translations (
+              id, user_id, entity_type, entity_id, lang, source_hash, title, summary,
+              created_at, updated_at
+            )
+            VALUES (
+              'tr-export-1', 'user-export-test', 'release', '1001', 'zh-CN', 'hash', '首个版本',
+              '初始内容', '2026-02-10T00:00:00Z', '2026-02-10T00:00:00Z'
+            )
+            "#,
+            r#"
+            INSERT INTO reaction_pat_tokens (
+              user_id, token_ciphertext, token_nonce, masked_token, updated_at
+            )
+            VALUES ('user-export-test', X'DEADBEEF', X'00', 'ghp_****SECRETPAT', '2026-02-01T00:00:00Z')
+            "#,
+        ] {
+            sqlx::query(&statement.replace("user-export-test", test_user_id().as_str()))
+                .execute(pool)
+                .await
+                .expect("seed export data");
+        }
+    }
+
+    fn setup_state(pool: SqlitePool) -> Arc<AppState> {
+        Arc::new(AppState::for_tests(pool, AppConfig::for_tests()))
+    }
+
+    async fn export_bytes(
+        state: Arc<AppState>,
+        format: Option<&str>,
+        since: Option<&str>,
+    ) -> (Response, Vec<u8>) {
+        let response = export_user_data(
+            State(state),
+            api::AuthedUser(test_user_id()),
+            Query(ExportQuery {
+                format: format.map(str::to_owned),
+                since: since.map(str::to_owned),
+            }),
+        )
+        .await
+        .expect("export response");
+        let (parts, body) = response.into_parts();
+        let bytes = axum::body::to_bytes(body, usize::MAX)
+            .await
+            .expect("collect export body");
+        (Response::from_parts(parts, Body::empty()), bytes.to_vec())
+    }
+
+    async fn unzip_entries(bytes: Vec<u8>) -> BTreeMap<String, String> {
+        let reader = ZipFileReader::new(bytes).await.expect("open export zip");
+        let mut entries = BTreeMap::new();
+        for index in 0..reader.file().entries().len() {
+            let mut entry = reader
+                .reader_with_entry(index)
+                .await
+                .expect("open zip entry");
+            let name = entry
+                .entry()
+                .filename()
+                .as_str()
+                .expect("utf-8 entry name")
+                .to_owned();
+            let mut content = String::new();
+            entry
+                .read_to_string_checked(&mut content)
+                .await
+                .expect("read zip entry");
+            entries.insert(name, content);
+        }
+        entries
+    }
+
+    fn all_time_scope() -> ExportScope {
+        ExportScope {
+            user_id: test_user_id(),
+            since: None,
+        }
+    }
+
+    #[test]
+    fn parse_export_since_normalizes_to_utc() {
+        assert_eq!(
+            parse_export_since(Some("2026-03-01T08:00:00+08:00")).expect("since"),
+            Some("2026-03-01T00:00:00Z".to_owned())
+        );
+        assert!(parse_export_since(Some("yesterday")).is_err());
+        assert_eq!(parse_export_since(Some("  ")).expect("blank since"), None);
+        assert!(parse_export_format(Some("tar")).is_err());
+    }
+
+    #[tokio::test]
+    async fn zip_export_round_trips_user_rows() {
+        let state = setup_state(setup_pool().await);
+        let (response, bytes) = export_bytes(state.clone(), None, None).await;
+
+        assert_eq!(
+            response.headers().get(header::CONTENT_TYPE),
+            Some(&HeaderValue::from_static("application/zip"))
+        );
+        assert!(
+            response
+                .headers()
+                .get(header::CONTENT_DISPOSITION)
+                .and_then(|value| value.to_str().ok())
+                .is_some_and(|value| value.starts_with("attachment; filename=\"octo-rill-export-"))
+        );
+
+        let entries = unzip_entries(bytes).await;
+        assert_eq!(
+            entries.keys().map(String::as_str).collect::<Vec<_>>(),
+            vec![
+                "briefs/2026-03-10.md",
+                "notifications.json",
+                "releases.json",
+                "starred.json",
+                "translations.json",
+            ]
+        );
+
+        let scope = all_time_scope();
+        let releases: Vec<ExportRelease> =
+            serde_json::from_str(&entries["releases.json"]).expect("parse releases.json");
+        assert_eq!(
+            releases,
+            load_release_page(state.as_ref(), &scope, i64::MIN)
+                .await
+                .expect("load releases")
+        );
+        assert_eq!(
+            releases
+                .iter()
+                .map(|release| release.release_id)
+                .collect::<Vec<_>>(),
+            vec![1001, 1002]
+        );
+        assert_eq!(releases[0].repo_full_name.as_deref(), Some("octo/rill"));
+
+        let starred: Vec<ExportStarredRepo> =
+            serde_json::from_str(&entries["starred.json"]).expect("parse starred.json");
+        assert_eq!(
+            starred,
+            load_starred(state.as_ref(), &scope)
+                .await
+                .expect("load starred")
+        );
+        let notifications: Vec<ExportNotification> =
+            serde_json::from_str(&entries["notifications.json"]).expect("parse notifications.json");
+        assert_eq!(
+            notifications,
+            load_notifications(state.as_ref(), &scope)
+                .await
+                .expect("load notifications")
+        );
+        let translations: Vec<ExportTranslation> =
+            serde_json::from_str(&entries["translations.json"]).expect("parse translations.json");
+        assert_eq!(
+            translations,
+            load_translations(state.as_ref(), &scope)
+                .await
+                .expect("load translations")
+        );
+        assert_eq!(entries["briefs/2026-03-10.md"], "## latest");
+
+        assert!(
+            entries
+                .values()
+                .all(|content| !content.contains("SECRETPAT"))
+        );
+    }
+
+    #[tokio::test]
+    async fn json_export_applies_since_filter() {
+        let state = setup_state(setup_pool().await);
+        let (response, bytes) =
+            export_bytes(state, Some("json"), Some("2026-03-01T00:00:00Z")).await;
+
+        assert_eq!(
+            response.headers().get(header::CONTENT_TYPE),
+            Some(&HeaderValue::from_static("application/json"))
+        );
+        let export: serde_json::Value = serde_json::from_slice(&bytes).expect("parse json export");
+        assert_eq!(export["since"], "2026-03-01T00:00:00Z");
+        assert_eq!(export["starred"].as_array().map(Vec::len), Some(0));
+        let release_ids = export["releases"]
+            .as_array()
+            .expect("releases array")
+            .iter()
+            .map(|release| release["release_id"].as_i64().expect("release id"))
+            .collect::<Vec<_>>();
+        assert_eq!(release_ids, vec![1002]);
+        assert_eq!(export["notifications"].as_array().map(Vec::len), Some(1));
+        assert_eq!(export["briefs"][0]["id"], "brief-export-new");
+        assert_eq!(export["translations"].as_array().map(Vec::len), Some(0));
+    }
+}
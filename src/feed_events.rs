@@ -0,0 +1,247 @@
+use std::{
+    collections::HashMap,
+    convert::Infallible,
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use async_stream::stream;
+use axum::response::{
+    IntoResponse, Response,
+    sse::{Event, KeepAlive, Sse},
+};
+use serde::Serialize;
+use serde_json::json;
+use tokio::sync::broadcast;
+
+use crate::{runtime, state::AppState};
+
+// Per-user channel depth. Slow subscribers that fall behind only lose
+// refetch hints, so a small buffer is enough.
+const FEED_EVENT_CHANNEL_CAPACITY: usize = 64;
+const FEED_EVENTS_KEEP_ALIVE_INTERVAL: Duration = Duration::from_secs(30);
+const FEED_EVENTS_ACCOUNT_CHECK_INTERVAL: Duration = Duration::from_secs(30);
+
+pub const FEED_EVENT_RELEASE_INSERTED: &str = "release_inserted";
+pub const FEED_EVENT_RELEASE_UPDATED: &str = "release_updated";
+pub const FEED_EVENT_TRANSLATION_READY: &str = "translation_ready";
+
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+pub struct FeedEvent {
+    pub kind: &'static str,
+    pub release_id: i64,
+    pub repo_full_name: String,
+}
+
+/// Fan-out hub for user-facing feed events, keyed by user id.
+///
+/// Senders are created lazily on subscribe and pruned once the last
+/// receiver for a user is dropped, so idle users cost nothing.
+#[derive(Clone, Debug, Default)]
+pub struct FeedEventHub {
+    channels: Arc<Mutex<HashMap<String, broadcast::Sender<FeedEvent>>>>,
+}
+
+impl FeedEventHub {
+    pub fn subscribe(&self, user_id: &str) -> broadcast::Receiver<FeedEvent> {
+        let mut channels = self.channels.lock().expect("feed event hub poisoned");
+        channels.retain(|_, sender| sender.receiver_count() > 0);
+        channels
+            .entry(user_id.to_owned())
+            .or_insert_with(|| broadcast::channel(FEED_EVENT_CHANNEL_CAPACITY).0)
+            .subscribe()
+    }
+
+    pub fn has_subscribers(&self) -> bool {
+        let channels = self.channels.lock().expect("feed event hub poisoned");
+        channels.values().any(|sender| sender.receiver_count() > 0)
+    }
+
+    /// Delivers `event` to every live subscriber of `user_id` and returns
+    /// how many receivers got it. Users without subscribers are skipped and
+    /// their stale sender, if any, is dropped.
+    pub fn publish(&self, user_id: &str, event: FeedEvent) -> usize {
+        let mut channels = self.channels.lock().expect("feed event hub poisoned");
+        let Some(sender) = channels.get(user_id) else {
+            return 0;
+        };
+        match sender.send(event) {
+            Ok(delivered) => delivered,
+            Err(_) => {
+                channels.remove(user_id);
+                0
+            }
+        }
+    }
+
+    /// Drops the user's sender so every open stream observes `Closed` and
+    /// ends, e.g. right after the account is disabled.
+    pub fn close_user(&self, user_id: &str) {
+        let mut channels = self.channels.lock().expect("feed event hub poisoned");
+        channels.remove(user_id);
+    }
+
+    #[cfg(test)]
+    fn channel_count(&self) -> usize {
+        self.channels.lock().expect("feed event hub poisoned").len()
+    }
+}
+
+enum FeedStreamStep {
+    Event(FeedEvent),
+    Lagged(u64),
+    AccountChecked(bool),
+    Shutdown,
+    Closed,
+}
+
+async fn feed_user_is_active(state: &AppState, user_id: &str) -> bool {
+    match sqlx::query_scalar::<_, i64>(r#"SELECT is_disabled FROM users WHERE id = ? LIMIT 1"#)
+        .bind(user_id)
+        .fetch_optional(&state.pool)
+        .await
+    {
+        Ok(Some(is_disabled)) => is_disabled == 0,
+        Ok(None) => false,
+        // Transient read failures should not drop an otherwise healthy stream.
+        Err(_) => true,
+    }
+}
+
+pub fn feed_sse_response(state: Arc<AppState>, user_id: String) -> Response {
+    let mut receiver = state.feed_events.subscribe(user_id.as_str());
+    let events = stream! {
+        let mut account_check = tokio::time::interval_at(
+            tokio::time::Instant::now() + FEED_EVENTS_ACCOUNT_CHECK_INTERVAL,
+            FEED_EVENTS_ACCOUNT_CHECK_INTERVAL,
+        );
+
+        yield Ok::<Event, Infallible>(Event::default().comment("stream-ready"));
+
+        loop {
+            let step = tokio::select! {
+                received = receiver.recv() => match received {
+                    Ok(event) => FeedStreamStep::Event(event),
+                    Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                        FeedStreamStep::Lagged(skipped)
+                    }
+                    Err(broadcast::error::RecvError::Closed) => FeedStreamStep::Closed,
+                },
+                _ = account_check.tick() => {
+                    FeedStreamStep::AccountChecked(
+                        feed_user_is_active(state.as_ref(), user_id.as_str()).await,
+                    )
+                }
+                _ = runtime::shutdown_token().cancelled() => FeedStreamStep::Shutdown,
+            };
+
+            match step {
+                FeedStreamStep::Event(event) => {
+                    let data = serde_json::to_string(&event).unwrap_or_else(|_| "{}".to_owned());
+                    yield Ok::<Event, Infallible>(Event::default().event(event.kind).data(data));
+                }
+                FeedStreamStep::Lagged(skipped) => {
+                    // The client missed some hints; ask it to refetch instead.
+                    yield Ok::<Event, Infallible>(
+                        Event::default()
+                            .event("feed_lagged")
+                            .data(json!({ "skipped": skipped }).to_string()),
+                    );
+                }
+                FeedStreamStep::AccountChecked(true) => {}
+                FeedStreamStep::Shutdown => {
+                    yield Ok::<Event, Infallible>(
+                        Event::default()
+                            .event(runtime::SSE_SHUTDOWN_EVENT)
+                            .data(json!({ "reason": "server shutdown" }).to_string()),
+                    );
+                    break;
+                }
+                FeedStreamStep::AccountChecked(false) | FeedStreamStep::Closed => break,
+            }
+        }
+    };
+
+    Sse::new(events)
+        .keep_alive(
+            KeepAlive::new()
+                .interval(FEED_EVENTS_KEEP_ALIVE_INTERVAL)
+                .text("keep-alive"),
+        )
+        .into_response()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn release_event(release_id: i64) -> FeedEvent {
+        FeedEvent {
+            kind: FEED_EVENT_RELEASE_INSERTED,
+            release_id,
+            repo_full_name: "octo/rill".to_owned(),
+        }
+    }
+
+    #[tokio::test]
+    async fn publish_reaches_only_the_target_user() {
+        let hub = FeedEventHub::default();
+        let mut alice = hub.subscribe("alice");
+        let mut bob = hub.subscribe("bob");
+
+        assert_eq!(hub.publish("alice", release_event(1)), 1);
+
+        assert_eq!(alice.recv().await.expect("alice event"), release_event(1));
+        assert!(matches!(
+            bob.try_recv(),
+            Err(broadcast::error::TryRecvError::Empty)
+        ));
+    }
+
+    #[test]
+    fn dropped_receiver_is_pruned_on_publish() {
+        let hub = FeedEventHub::default();
+        let receiver = hub.subscribe("alice");
+        assert!(hub.has_subscribers());
+        drop(receiver);
+
+        assert!(!hub.has_subscribers());
+        assert_eq!(hub.publish("alice", release_event(1)), 0);
+        assert_eq!(hub.channel_count(), 0);
+    }
+
+    #[test]
+    fn dropped_receivers_are_pruned_on_next_subscribe() {
+        let hub = FeedEventHub::default();
+        drop(hub.subscribe("alice"));
+        drop(hub.subscribe("bob"));
+        let _carol = hub.subscribe("carol");
+
+        assert_eq!(hub.channel_count(), 1);
+        assert!(hub.has_subscribers());
+    }
+
+    #[test]
+    fn sender_survives_while_any_receiver_remains() {
+        let hub = FeedEventHub::default();
+        let first = hub.subscribe("alice");
+        let mut second = hub.subscribe("alice");
+        drop(first);
+
+        assert_eq!(hub.publish("alice", release_event(7)), 1);
+        assert_eq!(second.try_recv().expect("second event"), release_event(7));
+    }
+
+    #[tokio::test]
+    async fn close_user_ends_open_streams() {
+        let hub = FeedEventHub::default();
+        let mut receiver = hub.subscribe("alice");
+        hub.close_user("alice");
+
+        assert!(matches!(
+            receiver.recv().await,
+            Err(broadcast::error::RecvError::Closed)
+        ));
+        assert_eq!(hub.channel_count(), 0);
+    }
+}
@@ -0,0 +1,418 @@
+use std::{collections::HashSet, sync::Arc};
+
+use axum::{
+    Json,
+    extract::{Path, State},
+    http::StatusCode,
+};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    api,
+    error::{ApiError, ApiJson, codes},
+    github_rate_limit,
+    state::AppState,
+    sync::{self, FollowImportLookup, FollowImportSource},
+};
+
+/// GitHub caps logins at 39 characters.
+const GITHUB_LOGIN_MAX_LEN: usize = 39;
+const FOLLOW_LIST_NAME_MAX_LEN: usize = 100;
+
+#[derive(Debug, Deserialize, JsonSchema)]
+#[serde(tag = "source", rename_all = "snake_case")]
+pub enum FollowImportRequest {
+    /// Another user's public stars.
+    User { login: String },
+    /// A public star list; `list` matches the list's slug or name.
+    List { owner: String, list: String },
+}
+
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct FollowImportResponse {
+    /// Public repos read from GitHub, at most 500.
+    fetched: usize,
+    /// Repos that were not followed before.
+    added: usize,
+    /// Already-followed repos whose metadata was refreshed.
+    updated: usize,
+    /// The source holds more repos than one import takes.
+    truncated: bool,
+    stopped_early_rate_limit: bool,
+}
+
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct FollowRemoveResponse {
+    repo_id: i64,
+    followed: bool,
+    /// Cached releases dropped because nothing else watches the repo.
+    deleted_release_count: i64,
+}
+
+fn validate_github_login(raw: &str, field: &str) -> Result<String, ApiError> {
+    let login = raw.trim();
+    let valid = !login.is_empty()
+        && login.len() <= GITHUB_LOGIN_MAX_LEN
+        && !login.starts_with('-')
+        && login
+            .chars()
+            .all(|ch| ch.is_ascii_alphanumeric() || ch == '-');
+    if !valid {
+        return Err(ApiError::bad_request(format!(
+            "{field} must be a GitHub login"
+        )));
+    }
+    Ok(login.to_owned())
+}
+
+fn follow_import_source(req: FollowImportRequest) -> Result<FollowImportSource, ApiError> {
+    match req {
+        FollowImportRequest::User { login } => Ok(FollowImportSource::UserStars {
+            login: validate_github_login(&login, "login")?,
+        }),
+        FollowImportRequest::List { owner, list } => {
+            let owner = validate_github_login(&owner, "owner")?;
+            let list = list.trim();
+            if list.is_empty() || list.chars().count() > FOLLOW_LIST_NAME_MAX_LEN {
+                return Err(ApiError::bad_request(format!(
+                    "list must be 1-{FOLLOW_LIST_NAME_MAX_LEN} characters"
+                )));
+            }
+            Ok(FollowImportSource::List {
+                owner,
+                list: list.to_owned(),
+            })
+        }
+    }
+}
+
+fn follow_import_ref(source: &FollowImportSource) -> (&'static str, String) {
+    match source {
+        FollowImportSource::UserStars { login } => ("user", login.clone()),
+        FollowImportSource::List { owner, list } => ("list", format!("{owner}/{list}")),
+    }
+}
+
+/// Follows the public repos of another user's stars or of a star list.
+/// Followed repos feed releases like starred ones, but live in their own
+/// table so the star sync leaves them alone. Their releases arrive with the
+/// next release sync.
+pub async fn import_follows(
+    State(state): State<Arc<AppState>>,
+    api::AuthedUser(user_id): api::AuthedUser,
+    ApiJson(req): ApiJson<FollowImportRequest>,
+) -> Result<Json<FollowImportResponse>, ApiError> {
+    let source = follow_import_source(req)?;
+
+    let lookup = github_rate_limit::track(
+        state.as_ref(),
+        user_id.as_str(),
+        sync::fetch_follow_import(state.as_ref(), user_id.as_str(), &source),
+    )
+    .await
+    .map_err(|err| {
+        ApiError::new(
+            StatusCode::BAD_GATEWAY,
+            codes::GITHUB_UNAVAILABLE,
+            format!("github follow import failed: {err}"),
+        )
+    })?;
+    let fetch = match lookup {
+        FollowImportLookup::Found(fetch) => fetch,
+        FollowImportLookup::NotFound => {
+            return Err(ApiError::new(
+                StatusCode::NOT_FOUND,
+                codes::FOLLOW_SOURCE_NOT_FOUND,
+                "github user or public list not found",
+            ));
+        }
+    };
+
+    let (import_source, import_ref) = follow_import_ref(&source);
+    let now = chrono::Utc::now().to_rfc3339();
+    let mut tx = state.pool.begin().await.map_err(ApiError::internal)?;
+    let existing = sqlx::query_scalar::<_, i64>(
+        r#"
+        SELECT repo_id
+        FROM followed_repos
+        WHERE user_id = ?
+        "#,
+    )
+    .bind(user_id.as_str())
+    .fetch_all(&mut *tx)
+    .await
+    .map_err(ApiError::internal)?
+    .into_iter()
+    .collect::<HashSet<_>>();
+
+    let mut added = 0usize;
+    let mut updated = 0usize;
+    for repo in &fetch.repos {
+        sqlx::query(
+            r#"
+            INSERT INTO followed_repos (
+              user_id, repo_id, full_name, owner_login, name, description, html_url,
+              owner_avatar_url, import_source, import_ref, created_at, updated_at
+            )
+            VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+            ON CONFLICT(user_id, repo_id) DO UPDATE SET
+              full_name = excluded.full_name,
+              owner_login = excluded.owner_login,
+              name = excluded.name,
+              description = excluded.description,
+              html_url = excluded.html_url,
+              owner_avatar_url = excluded.owner_avatar_url,
+              updated_at = excluded.updated_at
+            "#,
+        )
+        .bind(user_id.as_str())
+        .bind(repo.repo_id)
+        .bind(repo.full_name.as_str())
+        .bind(repo.owner_login.as_str())
+        .bind(repo.name.as_str())
+        .bind(repo.description.as_deref())
+        .bind(repo.html_url.as_str())
+        .bind(repo.owner_avatar_url.as_deref())
+        .bind(import_source)
+        .bind(import_ref.as_str())
+        .bind(now.as_str())
+        .bind(now.as_str())
+        .execute(&mut *tx)
+        .await
+        .map_err(ApiError::internal)?;
+        if existing.contains(&repo.repo_id) {
+            updated += 1;
+        } else {
+            added += 1;
+        }
+    }
+    tx.commit().await.map_err(ApiError::internal)?;
+
+    Ok(Json(FollowImportResponse {
+        fetched: fetch.repos.len(),
+        added,
+        updated,
+        truncated: fetch.truncated,
+        stopped_early_rate_limit: fetch.stopped_early_rate_limit,
+    }))
+}
+
+/// Stops following a repo. Its cached releases go too unless another user,
+/// a star or the public release endpoint still needs them.
+pub async fn unfollow_repo(
+    State(state): State<Arc<AppState>>,
+    api::AuthedUser(user_id): api::AuthedUser,
+    Path(repo_id): Path<String>,
+) -> Result<Json<FollowRemoveResponse>, ApiError> {
+    let repo_id = repo_id
+        .trim()
+        .parse::<i64>()
+        .map_err(|_| ApiError::bad_request("repo_id must be an integer string"))?;
+
+    let full_name = sqlx::query_scalar::<_, String>(
+        r#"
+        DELETE FROM followed_repos
+        WHERE user_id = ?
+          AND repo_id = ?
+        RETURNING full_name
+        "#,
+    )
+    .bind(user_id.as_str())
+    .bind(repo_id)
+    .fetch_optional(&state.pool)
+    .await
+    .map_err(ApiError::internal)?
+    .ok_or_else(|| {
+        ApiError::new(
+            StatusCode::NOT_FOUND,
+            codes::REPO_NOT_FOUND,
+            "repository is not followed",
+        )
+    })?;
+
+    let cleanup =
+        api::cleanup_public_release_repo_cache_if_unused(state.as_ref(), Some(repo_id), full_name)
+            .await?;
+
+    Ok(Json(FollowRemoveResponse {
+        repo_id,
+        followed: false,
+        deleted_release_count: cleanup.deleted_release_count,
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use sqlx::{
+        SqlitePool,
+        sqlite::{SqliteConnectOptions, SqlitePoolOptions},
+    };
+
+    use crate::config::AppConfig;
+
+    fn member_id() -> String {
+        crate::local_id::test_local_id("follows-member")
+    }
+
+    fn other_id() -> String {
+        crate::local_id::test_local_id("follows-other")
+    }
+
+    async fn setup_pool() -> SqlitePool {
+        let database_path = std::env::temp_dir().join(format!(
+            "octo-rill-follows-test-{}.db",
+            crate::local_id::generate_local_id(),
+        ));
+        let options = SqliteConnectOptions::new()
+            .filename(&database_path)
+            .create_if_missing(true);
+        let pool = SqlitePoolOptions::new()
+            .max_connections(1)
+            .connect_with(options)
+            .await
+            .expect("create sqlite db");
+        sqlx::migrate!("./migrations")
+            .run(&pool)
+            .await
+            .expect("run migrations");
+        pool
+    }
+
+    async fn seed_user(pool: &SqlitePool, user_id: &str, github_user_id: i64) {
+        sqlx::query(
+            r#"
+            INSERT INTO users (id, github_user_id, login, created_at, updated_at)
+            VALUES (?, ?, ?, '2026-02-01T00:00:00Z', '2026-02-01T00:00:00Z')
+            "#,
+        )
+        .bind(user_id)
+        .bind(github_user_id)
+        .bind(format!("user-{github_user_id}"))
+        .execute(pool)
+        .await
+        .expect("seed user");
+    }
+
+    async fn seed_follow(pool: &SqlitePool, user_id: &str, repo_id: i64, full_name: &str) {
+        let (owner, name) = full_name.split_once('/').expect("full name");
+        sqlx::query(
+            r#"
+            INSERT INTO followed_repos (
+              user_id, repo_id, full_name, owner_login, name, html_url,
+              import_source, import_ref, created_at, updated_at
+            )
+            VALUES (?, ?, ?, ?, ?, ?, 'user', 'someone', '2026-02-01T00:00:00Z', '2026-02-01T00:00:00Z')
+            "#,
+        )
+        .bind(user_id)
+        .bind(repo_id)
+        .bind(full_name)
+        .bind(owner)
+        .bind(name)
+        .bind(format!("https://github.com/{full_name}"))
+        .execute(pool)
+        .await
+        .expect("seed follow");
+    }
+
+    async fn seed_release(pool: &SqlitePool, repo_id: i64, release_id: i64) {
+        sqlx::query(
+            r#"
+            INSERT INTO repo_releases (
+              id, repo_id, release_id, tag_name, name, body, html_url,
+              published_at, created_at, is_prerelease, is_draft, updated_at
+            )
+            VALUES (?, ?, ?, 'v1.0.0', 'v1.0.0', 'notes', ?, '2026-02-02T00:00:00Z',
+              '2026-02-02T00:00:00Z', 0, 0, '2026-02-02T00:00:00Z')
+            "#,
+        )
+        .bind(format!("release-{release_id}"))
+        .bind(repo_id)
+        .bind(release_id)
+        .bind(format!(
+            "https://github.com/octo/repo/releases/tag/{release_id}"
+        ))
+        .execute(pool)
+        .await
+        .expect("seed release");
+    }
+
+    fn setup_state(pool: SqlitePool) -> Arc<AppState> {
+        Arc::new(AppState::for_tests(pool, AppConfig::for_tests()))
+    }
+
+    #[test]
+    fn follow_import_source_validates_logins_and_lists() {
+        let source = follow_import_source(FollowImportRequest::List {
+            owner: " octo-org ".to_owned(),
+            list: " Rust Tools ".to_owned(),
+        })
+        .expect("valid list");
+        assert_eq!(
+            follow_import_ref(&source),
+            ("list", "octo-org/Rust Tools".to_owned())
+        );
+
+        for login in ["", "-octo", "octo/rill", &"a".repeat(40)] {
+            let err = follow_import_source(FollowImportRequest::User {
+                login: login.to_owned(),
+            })
+            .expect_err("invalid login");
+            assert_eq!(err.code(), "bad_request");
+        }
+    }
+
+    #[tokio::test]
+    async fn unfollow_keeps_releases_another_user_still_follows() {
+        let pool = setup_pool().await;
+        let member = member_id();
+        let other = other_id();
+        seed_user(&pool, &member, 1).await;
+        seed_user(&pool, &other, 2).await;
+        seed_follow(&pool, &member, 10, "octo/shared").await;
+        seed_follow(&pool, &other, 10, "octo/shared").await;
+        seed_follow(&pool, &member, 20, "octo/solo").await;
+        seed_release(&pool, 10, 1001).await;
+        seed_release(&pool, 20, 2001).await;
+        let state = setup_state(pool.clone());
+
+        let Json(shared) = unfollow_repo(
+            State(state.clone()),
+            api::AuthedUser(member.clone()),
+            Path("10".to_owned()),
+        )
+        .await
+        .expect("unfollow shared repo");
+        assert_eq!(shared.deleted_release_count, 0);
+
+        let Json(solo) = unfollow_repo(
+            State(state.clone()),
+            api::AuthedUser(member.clone()),
+            Path("20".to_owned()),
+        )
+        .await
+        .expect("unfollow solo repo");
+        assert!(!solo.followed);
+        assert_eq!(solo.deleted_release_count, 1);
+
+        let remaining = sqlx::query_scalar::<_, i64>(
+            "SELECT release_id FROM repo_releases ORDER BY release_id",
+        )
+        .fetch_all(&pool)
+        .await
+        .expect("load releases");
+        assert_eq!(remaining, vec![1001]);
+
+        let err = unfollow_repo(
+            State(state),
+            api::AuthedUser(member.clone()),
+            Path("20".to_owned()),
+        )
+        .await
+        .expect_err("repo no longer followed");
+        assert_eq!(err.code(), "repo_not_found");
+    }
+}
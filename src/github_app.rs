@@ -0,0 +1,746 @@
+use std::sync::Arc;
+
+use anyhow::{Context, Result};
+use axum::{
+    Json,
+    body::Bytes,
+    extract::State,
+    http::{HeaderMap, StatusCode},
+};
+use ring::hmac;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    api,
+    error::{ApiError, codes},
+    state::AppState,
+};
+
+/// Mint errors are cut to this many characters before they are stored.
+const TOKEN_ERROR_MAX_CHARS: usize = 500;
+
+#[derive(Debug, Serialize, JsonSchema, sqlx::FromRow)]
+pub struct GitHubAppInstallationItem {
+    installation_id: i64,
+    account_login: String,
+    account_type: Option<String>,
+    #[schemars(extend("enum" = ["all", "selected"]))]
+    repository_selection: String,
+    suspended_at: Option<String>,
+    /// Repos mapped from webhooks; `all` installations also cover the
+    /// account's repos that are not listed.
+    repo_count: i64,
+    last_token_minted_at: Option<String>,
+    /// Last mint failure; cleared by the next successful mint.
+    last_token_error: Option<String>,
+    last_token_error_at: Option<String>,
+    updated_at: String,
+}
+
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct GitHubAppInstallationsResponse {
+    /// `false` when `GITHUB_APP_ID` is unset and sync only uses user tokens.
+    configured: bool,
+    items: Vec<GitHubAppInstallationItem>,
+}
+
+#[derive(Debug, Deserialize)]
+struct WebhookAccount {
+    login: String,
+    #[serde(rename = "type")]
+    kind: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct WebhookInstallation {
+    id: i64,
+    account: Option<WebhookAccount>,
+    repository_selection: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct WebhookRepository {
+    id: i64,
+    full_name: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct InstallationWebhook {
+    action: String,
+    installation: WebhookInstallation,
+    #[serde(default)]
+    repositories: Vec<WebhookRepository>,
+    #[serde(default)]
+    repositories_added: Vec<WebhookRepository>,
+    #[serde(default)]
+    repositories_removed: Vec<WebhookRepository>,
+}
+
+/// Checks `X-Hub-Signature-256` (`sha256=<hex hmac>`) against the raw body.
+fn webhook_signature_valid(secret: &str, signature: Option<&str>, body: &[u8]) -> bool {
+    let Some(tag) = signature
+        .and_then(|value| value.trim().strip_prefix("sha256="))
+        .and_then(decode_hex)
+    else {
+        return false;
+    };
+    let key = hmac::Key::new(hmac::HMAC_SHA256, secret.as_bytes());
+    hmac::verify(&key, body, &tag).is_ok()
+}
+
+fn decode_hex(raw: &str) -> Option<Vec<u8>> {
+    if !raw.len().is_multiple_of(2) {
+        return None;
+    }
+    (0..raw.len())
+        .step_by(2)
+        .map(|idx| u8::from_str_radix(raw.get(idx..idx + 2)?, 16).ok())
+        .collect()
+}
+
+/// Receives `installation` and `installation_repositories` deliveries and
+/// keeps the installation → repo mapping current. Other events are
+/// acknowledged and ignored.
+pub async fn github_webhook(
+    State(state): State<Arc<AppState>>,
+    headers: HeaderMap,
+    body: Bytes,
+) -> Result<StatusCode, ApiError> {
+    let Some(secret) = state
+        .config
+        .github_app
+        .as_ref()
+        .and_then(|app| app.webhook_secret.as_deref())
+    else {
+        return Err(ApiError::new(
+            StatusCode::NOT_FOUND,
+            codes::NOT_FOUND,
+            "github app webhooks are not configured",
+        ));
+    };
+    let header = |name: &str| headers.get(name).and_then(|value| value.to_str().ok());
+    if !webhook_signature_valid(secret, header("x-hub-signature-256"), &body) {
+        return Err(ApiError::new(
+            StatusCode::UNAUTHORIZED,
+            codes::UNAUTHORIZED,
+            "invalid webhook signature",
+        ));
+    }
+
+    let event = header("x-github-event").unwrap_or_default();
+    if !matches!(event, "installation" | "installation_repositories") {
+        return Ok(StatusCode::NO_CONTENT);
+    }
+    let payload = serde_json::from_slice::<InstallationWebhook>(&body)
+        .map_err(|err| ApiError::bad_request(format!("invalid {event} payload: {err}")))?;
+    apply_installation_webhook(state.as_ref(), &payload)
+        .await
+        .map_err(ApiError::internal)?;
+    if payload.action == "deleted"
+        && let Some(tokens) = state.github_app_tokens.as_ref()
+    {
+        tokens.forget_installation(payload.installation.id).await;
+    }
+    tracing::info!(
+        event,
+        action = payload.action.as_str(),
+        installation_id = payload.installation.id,
+        "github app webhook applied"
+    );
+    Ok(StatusCode::NO_CONTENT)
+}
+
+async fn apply_installation_webhook(state: &AppState, payload: &InstallationWebhook) -> Result<()> {
+    let installation = &payload.installation;
+    let now = chrono::Utc::now().to_rfc3339();
+    let mut tx = state.pool.begin().await?;
+
+    if payload.action == "deleted" {
+        sqlx::query(r#"DELETE FROM github_app_installations WHERE installation_id = ?"#)
+            .bind(installation.id)
+            .execute(&mut *tx)
+            .await
+            .context("failed to delete github app installation")?;
+        tx.commit().await?;
+        return Ok(());
+    }
+
+    sqlx::query(
+        r#"
+        INSERT INTO github_app_installations (
+          installation_id, account_login, account_type, repository_selection,
+          created_at, updated_at
+        )
+        VALUES (?, ?, ?, COALESCE(?, 'selected'), ?, ?)
+        ON CONFLICT(installation_id) DO UPDATE SET
+          account_login = CASE
+            WHEN excluded.account_login = '' THEN github_app_installations.account_login
+            ELSE excluded.account_login
+          END,
+          account_type = COALESCE(excluded.account_type, github_app_installations.account_type),
+          repository_selection = COALESCE(?, github_app_installations.repository_selection),
+          updated_at = excluded.updated_at
+        "#,
+    )
+    .bind(installation.id)
+    .bind(
+        installation
+            .account
+            .as_ref()
+            .map(|account| account.login.as_str())
+            .unwrap_or_default(),
+    )
+    .bind(
+        installation
+            .account
+            .as_ref()
+            .and_then(|account| account.kind.as_deref()),
+    )
+    .bind(installation.repository_selection.as_deref())
+    .bind(now.as_str())
+    .bind(now.as_str())
+    .bind(installation.repository_selection.as_deref())
+    .execute(&mut *tx)
+    .await
+    .context("failed to upsert github app installation")?;
+
+    match payload.action.as_str() {
+        "suspend" => {
+            sqlx::query(
+                r#"UPDATE github_app_installations SET suspended_at = ? WHERE installation_id = ?"#,
+            )
+            .bind(now.as_str())
+            .bind(installation.id)
+            .execute(&mut *tx)
+            .await?;
+        }
+        "unsuspend" => {
+            sqlx::query(
+                r#"UPDATE github_app_installations SET suspended_at = NULL WHERE installation_id = ?"#,
+            )
+            .bind(installation.id)
+            .execute(&mut *tx)
+            .await?;
+        }
+        _ => {}
+    }
+
+    // `created` lists the full selection; `installation_repositories` only
+    // the delta.
+    if payload.action == "created" {
+        sqlx::query(r#"DELETE FROM github_app_installation_repos WHERE installation_id = ?"#)
+            .bind(installation.id)
+            .execute(&mut *tx)
+            .await?;
+    }
+    for repo in payload
+        .repositories
+        .iter()
+        .chain(payload.repositories_added.iter())
+    {
+        sqlx::query(
+            r#"
+            INSERT INTO github_app_installation_repos (installation_id, repo_id, full_name, created_at)
+            VALUES (?, ?, ?, ?)
+            ON CONFLICT(installation_id, repo_id) DO UPDATE SET full_name = excluded.full_name
+            "#,
+        )
+        .bind(installation.id)
+        .bind(repo.id)
+        .bind(repo.full_name.as_str())
+        .bind(now.as_str())
+        .execute(&mut *tx)
+        .await
+        .context("failed to map github app installation repo")?;
+    }
+    for repo in &payload.repositories_removed {
+        sqlx::query(
+            r#"
+            DELETE FROM github_app_installation_repos
+            WHERE installation_id = ?
+              AND repo_id = ?
+            "#,
+        )
+        .bind(installation.id)
+        .bind(repo.id)
+        .execute(&mut *tx)
+        .await?;
+    }
+
+    tx.commit().await?;
+    Ok(())
+}
+
+/// Active installation that can read `repo_full_name`, either through an
+/// explicit mapping or an `all` selection on the owning account.
+async fn installation_for_repo(
+    state: &AppState,
+    repo_id: i64,
+    repo_full_name: &str,
+) -> Result<Option<i64>> {
+    let owner = repo_full_name.split('/').next().unwrap_or_default();
+    sqlx::query_scalar::<_, i64>(
+        r#"
+        SELECT i.installation_id
+        FROM github_app_installations i
+        WHERE i.suspended_at IS NULL
+          AND (
+            EXISTS (
+              SELECT 1
+              FROM github_app_installation_repos r
+              WHERE r.installation_id = i.installation_id
+                AND r.repo_id = ?
+            )
+            OR (i.repository_selection = 'all' AND lower(i.account_login) = lower(?))
+          )
+        ORDER BY i.installation_id ASC
+        LIMIT 1
+        "#,
+    )
+    .bind(repo_id)
+    .bind(owner)
+    .fetch_optional(&state.pool)
+    .await
+    .context("failed to look up github app installation for repo")
+}
+
+/// Installation token for the repo when the GitHub App is configured and one
+/// of its installations covers it. Mint failures are recorded for the admin
+/// view and yield `None`, so callers fall back to user tokens.
+pub async fn installation_token_for_repo(
+    state: &AppState,
+    repo_id: i64,
+    repo_full_name: &str,
+) -> Option<String> {
+    let tokens = state.github_app_tokens.as_ref()?;
+    let installation_id = match installation_for_repo(state, repo_id, repo_full_name).await {
+        Ok(installation_id) => installation_id?,
+        Err(err) => {
+            tracing::warn!(?err, repo_id, "github app: installation lookup failed");
+            return None;
+        }
+    };
+
+    let minted = tokens
+        .installation_token(
+            &state.github_rest_http,
+            &state.github_rest_api_base,
+            installation_id,
+        )
+        .await;
+    let now = chrono::Utc::now().to_rfc3339();
+    let recorded = match &minted {
+        Ok(token) if !token.minted => Ok(()),
+        Ok(_) => sqlx::query(
+            r#"
+            UPDATE github_app_installations
+            SET last_token_minted_at = ?,
+                last_token_error = NULL,
+                last_token_error_at = NULL
+            WHERE installation_id = ?
+            "#,
+        )
+        .bind(now.as_str())
+        .bind(installation_id)
+        .execute(&state.pool)
+        .await
+        .map(|_| ()),
+        Err(err) => {
+            tracing::warn!(
+                event = "upstream.call",
+                operation = "github_app.installation_token",
+                installation_id,
+                repo = repo_full_name,
+                error_chain = %format!("{err:#}"),
+                "github app: installation token mint failed; falling back to user tokens"
+            );
+            let message = format!("{err:#}")
+                .chars()
+                .take(TOKEN_ERROR_MAX_CHARS)
+                .collect::<String>();
+            sqlx::query(
+                r#"
+                UPDATE github_app_installations
+                SET last_token_error = ?,
+                    last_token_error_at = ?
+                WHERE installation_id = ?
+                "#,
+            )
+            .bind(message)
+            .bind(now.as_str())
+            .bind(installation_id)
+            .execute(&state.pool)
+            .await
+            .map(|_| ())
+        }
+    };
+    if let Err(err) = recorded {
+        tracing::warn!(
+            ?err,
+            installation_id,
+            "github app: record mint outcome failed"
+        );
+    }
+    minted.ok().map(|token| token.token)
+}
+
+/// Known installations with their repo counts and last mint outcome.
+pub async fn admin_list_github_app_installations(
+    State(state): State<Arc<AppState>>,
+    _: api::AdminUser,
+) -> Result<Json<GitHubAppInstallationsResponse>, ApiError> {
+    let items = sqlx::query_as::<_, GitHubAppInstallationItem>(
+        r#"
+        SELECT
+          i.installation_id,
+          i.account_login,
+          i.account_type,
+          i.repository_selection,
+          i.suspended_at,
+          (
+            SELECT COUNT(*)
+            FROM github_app_installation_repos r
+            WHERE r.installation_id = i.installation_id
+          ) AS repo_count,
+          i.last_token_minted_at,
+          i.last_token_error,
+          i.last_token_error_at,
+          i.updated_at
+        FROM github_app_installations i
+        ORDER BY lower(i.account_login) ASC, i.installation_id ASC
+        "#,
+    )
+    .fetch_all(&state.pool)
+    .await
+    .map_err(ApiError::internal)?;
+
+    Ok(Json(GitHubAppInstallationsResponse {
+        configured: state.github_app_tokens.is_some(),
+        items,
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use axum::{Router, routing::post};
+    use sqlx::{SqlitePool, sqlite::SqlitePoolOptions};
+    use url::Url;
+
+    use crate::{
+        config::{AppConfig, GitHubAppConfig},
+        github::{GitHubAppTokenProvider, tests::TEST_APP_PRIVATE_KEY_PEM},
+    };
+
+    const WEBHOOK_SECRET: &str = "webhook-secret";
+
+    async fn setup_pool() -> SqlitePool {
+        let pool = SqlitePoolOptions::new()
+            .max_connections(1)
+            .connect("sqlite::memory:")
+            .await
+            .expect("create sqlite memory db");
+        sqlx::migrate!("./migrations")
+            .run(&pool)
+            .await
+            .expect("run migrations");
+        pool
+    }
+
+    fn setup_state(pool: SqlitePool, github_rest_api_base: Url) -> Arc<AppState> {
+        let config = AppConfig {
+            github_app: Some(GitHubAppConfig {
+                app_id: "12345".to_owned(),
+                private_key_pem: TEST_APP_PRIVATE_KEY_PEM.to_owned(),
+                webhook_secret: Some(WEBHOOK_SECRET.to_owned()),
+            }),
+            ..AppConfig::for_tests()
+        };
+        let mut state = AppState::for_tests(pool, config);
+        state.github_rest_api_base = github_rest_api_base;
+        state.github_app_tokens = Some(Arc::new(
+            GitHubAppTokenProvider::new("12345", TEST_APP_PRIVATE_KEY_PEM)
+                .expect("build token provider"),
+        ));
+        Arc::new(state)
+    }
+
+    fn signed_headers(event: &str, body: &[u8]) -> HeaderMap {
+        let key = hmac::Key::new(hmac::HMAC_SHA256, WEBHOOK_SECRET.as_bytes());
+        let tag = hmac::sign(&key, body);
+        let hex = tag
+            .as_ref()
+            .iter()
+            .map(|byte| format!("{byte:02x}"))
+            .collect::<String>();
+        let mut headers = HeaderMap::new();
+        headers.insert("x-github-event", event.parse().expect("event header"));
+        headers.insert(
+            "x-hub-signature-256",
+            format!("sha256={hex}").parse().expect("signature header"),
+        );
+        headers
+    }
+
+    async fn deliver(
+        state: &Arc<AppState>,
+        event: &str,
+        payload: serde_json::Value,
+    ) -> Result<StatusCode, ApiError> {
+        let body = Bytes::from(payload.to_string());
+        github_webhook(State(state.clone()), signed_headers(event, &body), body).await
+    }
+
+    async fn mapped_repo_ids(pool: &SqlitePool, installation_id: i64) -> Vec<i64> {
+        sqlx::query_scalar(
+            r#"
+            SELECT repo_id
+            FROM github_app_installation_repos
+            WHERE installation_id = ?
+            ORDER BY repo_id
+            "#,
+        )
+        .bind(installation_id)
+        .fetch_all(pool)
+        .await
+        .expect("load mapped repos")
+    }
+
+    #[test]
+    fn webhook_signature_valid_checks_hmac_of_body() {
+        let body = br#"{"action":"created"}"#;
+        let headers = signed_headers("installation", body);
+        let signature = headers
+            .get("x-hub-signature-256")
+            .and_then(|value| value.to_str().ok());
+
+        assert!(webhook_signature_valid(WEBHOOK_SECRET, signature, body));
+        assert!(!webhook_signature_valid("other-secret", signature, body));
+        assert!(!webhook_signature_valid(WEBHOOK_SECRET, signature, b"{}"));
+        assert!(!webhook_signature_valid(
+            WEBHOOK_SECRET,
+            Some("sha256=zz"),
+            body
+        ));
+        assert!(!webhook_signature_valid(WEBHOOK_SECRET, None, body));
+    }
+
+    #[tokio::test]
+    async fn github_webhook_tracks_installation_repositories() {
+        let pool = setup_pool().await;
+        let state = setup_state(
+            pool.clone(),
+            Url::parse("http://127.0.0.1:9/").expect("url"),
+        );
+
+        let status = deliver(
+            &state,
+            "installation",
+            serde_json::json!({
+                "action": "created",
+                "installation": {
+                    "id": 7,
+                    "account": {"login": "octo", "type": "Organization"},
+                    "repository_selection": "selected"
+                },
+                "repositories": [
+                    {"id": 42, "full_name": "octo/rill"},
+                    {"id": 43, "full_name": "octo/flow"}
+                ]
+            }),
+        )
+        .await
+        .expect("installation created");
+        assert_eq!(status, StatusCode::NO_CONTENT);
+        assert_eq!(mapped_repo_ids(&pool, 7).await, vec![42, 43]);
+
+        deliver(
+            &state,
+            "installation_repositories",
+            serde_json::json!({
+                "action": "removed",
+                "installation": {"id": 7, "account": {"login": "octo"}},
+                "repositories_removed": [{"id": 43, "full_name": "octo/flow"}]
+            }),
+        )
+        .await
+        .expect("repositories removed");
+        assert_eq!(mapped_repo_ids(&pool, 7).await, vec![42]);
+
+        deliver(
+            &state,
+            "installation",
+            serde_json::json!({"action": "suspend", "installation": {"id": 7}}),
+        )
+        .await
+        .expect("installation suspended");
+        assert_eq!(
+            installation_for_repo(state.as_ref(), 42, "octo/rill")
+                .await
+                .expect("lookup"),
+            None
+        );
+
+        deliver(
+            &state,
+            "installation",
+            serde_json::json!({"action": "unsuspend", "installation": {"id": 7}}),
+        )
+        .await
+        .expect("installation unsuspended");
+        assert_eq!(
+            installation_for_repo(state.as_ref(), 42, "octo/rill")
+                .await
+                .expect("lookup"),
+            Some(7)
+        );
+
+        deliver(
+            &state,
+            "installation",
+            serde_json::json!({"action": "deleted", "installation": {"id": 7}}),
+        )
+        .await
+        .expect("installation deleted");
+        assert!(mapped_repo_ids(&pool, 7).await.is_empty());
+    }
+
+    #[tokio::test]
+    async fn github_webhook_rejects_bad_signatures_and_ignores_other_events() {
+        let pool = setup_pool().await;
+        let state = setup_state(pool, Url::parse("http://127.0.0.1:9/").expect("url"));
+        let body = Bytes::from_static(br#"{"action":"created","installation":{"id":7}}"#);
+
+        let mut headers = signed_headers("installation", b"{}");
+        headers.insert("x-github-event", "installation".parse().expect("header"));
+        let err = github_webhook(State(state.clone()), headers, body.clone())
+            .await
+            .expect_err("signature over another body");
+        assert_eq!(err.code(), "unauthorized");
+
+        let status = github_webhook(
+            State(state.clone()),
+            signed_headers("push", &body),
+            body.clone(),
+        )
+        .await
+        .expect("push is ignored");
+        assert_eq!(status, StatusCode::NO_CONTENT);
+        let installations: i64 =
+            sqlx::query_scalar(r#"SELECT COUNT(*) FROM github_app_installations"#)
+                .fetch_one(&state.pool)
+                .await
+                .expect("count installations");
+        assert_eq!(installations, 0);
+    }
+
+    #[tokio::test]
+    async fn installation_token_for_repo_covers_all_selection_and_records_failures() {
+        let app = Router::new().route(
+            "/app/installations/{installation_id}/access_tokens",
+            post(
+                |axum::extract::Path(installation_id): axum::extract::Path<i64>| async move {
+                    if installation_id == 9 {
+                        return (
+                            StatusCode::NOT_FOUND,
+                            Json(serde_json::json!({"message": "Not Found"})),
+                        );
+                    }
+                    (
+                        StatusCode::CREATED,
+                        Json(serde_json::json!({
+                            "token": format!("ghs_{installation_id}"),
+                            "expires_at": (chrono::Utc::now() + chrono::Duration::hours(1))
+                                .to_rfc3339(),
+                        })),
+                    )
+                },
+            ),
+        );
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0")
+            .await
+            .expect("bind token server");
+        let addr = listener.local_addr().expect("token server addr");
+        tokio::spawn(async move {
+            axum::serve(listener, app)
+                .await
+                .expect("serve token server");
+        });
+        let pool = setup_pool().await;
+        let state = setup_state(
+            pool.clone(),
+            Url::parse(&format!("http://{addr}/")).expect("token server url"),
+        );
+
+        deliver(
+            &state,
+            "installation",
+            serde_json::json!({
+                "action": "created",
+                "installation": {
+                    "id": 8,
+                    "account": {"login": "Octo", "type": "User"},
+                    "repository_selection": "all"
+                },
+                "repositories": []
+            }),
+        )
+        .await
+        .expect("all-repos installation");
+        deliver(
+            &state,
+            "installation",
+            serde_json::json!({
+                "action": "created",
+                "installation": {
+                    "id": 9,
+                    "account": {"login": "broken"},
+                    "repository_selection": "selected"
+                },
+                "repositories": [{"id": 77, "full_name": "broken/repo"}]
+            }),
+        )
+        .await
+        .expect("broken installation");
+
+        assert_eq!(
+            installation_token_for_repo(state.as_ref(), 42, "octo/rill")
+                .await
+                .as_deref(),
+            Some("ghs_8")
+        );
+        assert_eq!(
+            installation_token_for_repo(state.as_ref(), 50, "someone/else").await,
+            None
+        );
+        assert_eq!(
+            installation_token_for_repo(state.as_ref(), 77, "broken/repo").await,
+            None
+        );
+
+        let rows = sqlx::query_as::<_, (i64, Option<String>, Option<String>)>(
+            r#"
+            SELECT installation_id, last_token_minted_at, last_token_error
+            FROM github_app_installations
+            ORDER BY installation_id
+            "#,
+        )
+        .fetch_all(&pool)
+        .await
+        .expect("load installations");
+        assert_eq!(rows.len(), 2);
+        assert!(rows[0].1.is_some());
+        assert_eq!(rows[0].2, None);
+        assert_eq!(rows[1].1, None);
+        assert!(
+            rows[1]
+                .2
+                .as_deref()
+                .is_some_and(|error| error.contains("404")),
+            "{:?}",
+            rows[1].2
+        );
+    }
+}
@@ -0,0 +1,275 @@
+use std::{
+    collections::HashMap,
+    future::Future,
+    sync::{Arc, Mutex},
+};
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use reqwest::header::HeaderMap;
+use serde::Serialize;
+
+use crate::{observability, state::AppState};
+
+/// Below this many remaining requests sync stops voluntarily instead of
+/// running into a hard rate-limit error half-way.
+pub const GITHUB_RATE_LIMIT_LOW_REMAINING: i64 = 100;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum GitHubTokenKind {
+    OAuth,
+    Pat,
+    GraphQl,
+}
+
+impl GitHubTokenKind {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::OAuth => "oauth",
+            Self::Pat => "pat",
+            Self::GraphQl => "graphql",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct GitHubRateLimit {
+    pub limit: i64,
+    pub remaining: i64,
+    pub reset_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, sqlx::FromRow)]
+pub struct GitHubRateLimitRow {
+    pub token_kind: String,
+    #[sqlx(rename = "limit_total")]
+    pub limit: i64,
+    pub remaining: i64,
+    pub reset_at: String,
+    pub updated_at: String,
+}
+
+/// Reads the `x-ratelimit-*` headers GitHub attaches to REST and GraphQL
+/// responses. Returns `None` unless all three are present and numeric.
+pub fn parse_rate_limit_headers(headers: &HeaderMap) -> Option<GitHubRateLimit> {
+    let number = |name: &str| {
+        headers
+            .get(name)
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| value.trim().parse::<i64>().ok())
+    };
+    let limit = number("x-ratelimit-limit")?;
+    let remaining = number("x-ratelimit-remaining")?;
+    let reset_at = DateTime::from_timestamp(number("x-ratelimit-reset")?, 0)?;
+    Some(GitHubRateLimit {
+        limit,
+        remaining,
+        reset_at,
+    })
+}
+
+type Observations = Arc<Mutex<HashMap<GitHubTokenKind, GitHubRateLimit>>>;
+
+tokio::task_local! {
+    static OBSERVED_RATE_LIMITS: Observations;
+}
+
+/// Notes the budget reported by `headers` in the current [`track`] scope.
+/// Outside a scope this is a no-op, so low-level request helpers can call it
+/// without knowing which user the token belongs to.
+pub fn observe(kind: GitHubTokenKind, headers: &HeaderMap) {
+    observability::record_github_request(kind.as_str());
+    let Some(rate_limit) = parse_rate_limit_headers(headers) else {
+        return;
+    };
+    let _ = OBSERVED_RATE_LIMITS.try_with(|observed| {
+        observed
+            .lock()
+            .expect("github rate limit observations poisoned")
+            .insert(kind, rate_limit);
+    });
+}
+
+/// Like [`observe`], classifying GraphQL by the response URL and everything
+/// else as an OAuth REST call.
+pub fn observe_response(response: &reqwest::Response) {
+    let kind = if response.url().path().ends_with("/graphql") {
+        GitHubTokenKind::GraphQl
+    } else {
+        GitHubTokenKind::OAuth
+    };
+    observe(kind, response.headers());
+}
+
+/// Runs `future` while collecting observed budgets, then stores them for
+/// `user_id`. Storage failures are logged and never fail the caller.
+pub async fn track<T, Fut>(state: &AppState, user_id: &str, future: Fut) -> T
+where
+    Fut: Future<Output = T>,
+{
+    let observed = Observations::default();
+    let output = OBSERVED_RATE_LIMITS
+        .scope(Arc::clone(&observed), future)
+        .await;
+    let observed = std::mem::take(
+        &mut *observed
+            .lock()
+            .expect("github rate limit observations poisoned"),
+    );
+    for (kind, rate_limit) in observed {
+        observability::record_github_rate_limit_remaining(
+            user_id,
+            kind.as_str(),
+            rate_limit.remaining,
+        );
+        if let Err(err) = upsert_rate_limit(state, user_id, kind, rate_limit).await {
+            tracing::warn!(
+                ?err,
+                user_id,
+                token_kind = kind.as_str(),
+                "github rate limit: store observed budget failed"
+            );
+        }
+    }
+    output
+}
+
+/// Carries the caller's [`track`] scope into a spawned task.
+pub fn inherit<Fut>(future: Fut) -> impl Future<Output = Fut::Output>
+where
+    Fut: Future,
+{
+    let observed = OBSERVED_RATE_LIMITS
+        .try_with(Arc::clone)
+        .unwrap_or_default();
+    OBSERVED_RATE_LIMITS.scope(observed, future)
+}
+
+/// Stores the budget from `headers` right away, for call sites that already
+/// know the user.
+pub async fn record(state: &AppState, user_id: &str, kind: GitHubTokenKind, headers: &HeaderMap) {
+    let Some(rate_limit) = parse_rate_limit_headers(headers) else {
+        return;
+    };
+    if let Err(err) = upsert_rate_limit(state, user_id, kind, rate_limit).await {
+        tracing::warn!(
+            ?err,
+            user_id,
+            token_kind = kind.as_str(),
+            "github rate limit: store budget failed"
+        );
+    }
+}
+
+async fn upsert_rate_limit(
+    state: &AppState,
+    user_id: &str,
+    kind: GitHubTokenKind,
+    rate_limit: GitHubRateLimit,
+) -> Result<()> {
+    let now = Utc::now().to_rfc3339();
+    sqlx::query(
+        r#"
+        INSERT INTO github_rate_limits (
+          user_id, token_kind, limit_total, remaining, reset_at, updated_at
+        )
+        VALUES (?, ?, ?, ?, ?, ?)
+        ON CONFLICT(user_id, token_kind) DO UPDATE SET
+          limit_total = excluded.limit_total,
+          remaining = excluded.remaining,
+          reset_at = excluded.reset_at,
+          updated_at = excluded.updated_at
+        "#,
+    )
+    .bind(user_id)
+    .bind(kind.as_str())
+    .bind(rate_limit.limit)
+    .bind(rate_limit.remaining)
+    .bind(rate_limit.reset_at.to_rfc3339())
+    .bind(now.as_str())
+    .execute(&state.pool)
+    .await
+    .context("upsert github rate limit")?;
+    Ok(())
+}
+
+pub async fn load_rate_limits(state: &AppState, user_id: &str) -> Result<Vec<GitHubRateLimitRow>> {
+    sqlx::query_as::<_, GitHubRateLimitRow>(
+        r#"
+        SELECT token_kind, limit_total, remaining, reset_at, updated_at
+        FROM github_rate_limits
+        WHERE user_id = ?
+        ORDER BY token_kind ASC
+        "#,
+    )
+    .bind(user_id)
+    .fetch_all(&state.pool)
+    .await
+    .context("load github rate limits")
+}
+
+/// Lowest remaining budget across the user's sync tokens whose window has
+/// not reset yet. `None` means nothing is known or every window has reset.
+pub async fn remaining_sync_budget(state: &AppState, user_id: &str) -> Result<Option<i64>> {
+    let now = Utc::now();
+    let remaining = load_rate_limits(state, user_id)
+        .await?
+        .into_iter()
+        .filter(|row| row.token_kind != GitHubTokenKind::Pat.as_str())
+        .filter(|row| {
+            DateTime::parse_from_rfc3339(row.reset_at.as_str())
+                .map(|reset_at| reset_at.with_timezone(&Utc) > now)
+                .unwrap_or(false)
+        })
+        .map(|row| row.remaining)
+        .min();
+    Ok(remaining)
+}
+
+#[cfg(test)]
+mod tests {
+    use reqwest::header::HeaderValue;
+
+    use super::*;
+
+    fn headers(entries: &[(&'static str, &str)]) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        for (name, value) in entries {
+            headers.insert(*name, HeaderValue::from_str(value).expect("header value"));
+        }
+        headers
+    }
+
+    #[test]
+    fn parses_complete_rate_limit_headers() {
+        let parsed = parse_rate_limit_headers(&headers(&[
+            ("x-ratelimit-limit", "5000"),
+            ("x-ratelimit-remaining", " 4321 "),
+            ("x-ratelimit-reset", "1780000000"),
+        ]))
+        .expect("rate limit");
+
+        assert_eq!(parsed.limit, 5000);
+        assert_eq!(parsed.remaining, 4321);
+        assert_eq!(parsed.reset_at.timestamp(), 1_780_000_000);
+    }
+
+    #[test]
+    fn ignores_partial_or_malformed_rate_limit_headers() {
+        assert!(
+            parse_rate_limit_headers(&headers(&[
+                ("x-ratelimit-limit", "5000"),
+                ("x-ratelimit-remaining", "12"),
+            ]))
+            .is_none()
+        );
+        assert!(
+            parse_rate_limit_headers(&headers(&[
+                ("x-ratelimit-limit", "5000"),
+                ("x-ratelimit-remaining", "soon"),
+                ("x-ratelimit-reset", "1780000000"),
+            ]))
+            .is_none()
+        );
+    }
+}
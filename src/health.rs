@@ -0,0 +1,309 @@
+//! Liveness and readiness probes for orchestrators.
+//!
+//! `/healthz` only proves the process answers. `/readyz` checks the things a
+//! request needs before it can succeed, each under a short timeout so the
+//! endpoint stays cheap enough to poll every few seconds.
+
+use std::{
+    collections::HashSet,
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+use axum::{
+    Json,
+    extract::State,
+    http::StatusCode,
+    response::{IntoResponse, Response},
+};
+use serde::Serialize;
+use serde_json::json;
+
+use crate::{state::AppState, version};
+
+/// Budget for each readiness check; they run concurrently, so the whole
+/// probe answers within roughly this long.
+const CHECK_TIMEOUT: Duration = Duration::from_millis(75);
+const GITHUB_PROBE_INTERVAL: Duration = Duration::from_secs(5 * 60);
+const GITHUB_PROBE_TIMEOUT: Duration = Duration::from_secs(10);
+/// A probe result older than this no longer says anything about GitHub.
+const GITHUB_PROBE_MAX_AGE: Duration = Duration::from_secs(3 * 5 * 60);
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ReadinessCheck {
+    pub name: &'static str,
+    pub ok: bool,
+    pub latency_ms: u64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ReadinessReport {
+    pub ready: bool,
+    pub checks: Vec<ReadinessCheck>,
+}
+
+#[derive(Debug, Clone)]
+struct GitHubProbeResult {
+    checked_at: Instant,
+    error: Option<String>,
+}
+
+/// Last GitHub reachability result. Filled by a background task so
+/// `/readyz` never waits on the network.
+#[derive(Clone, Default)]
+pub struct GitHubProbe {
+    last: Arc<Mutex<Option<GitHubProbeResult>>>,
+}
+
+impl GitHubProbe {
+    fn record(&self, error: Option<String>) {
+        *self.last.lock().expect("github probe poisoned") = Some(GitHubProbeResult {
+            checked_at: Instant::now(),
+            error,
+        });
+    }
+
+    fn check(&self) -> ReadinessCheck {
+        let last = self.last.lock().expect("github probe poisoned").clone();
+        let error = match last {
+            None => Some("not probed yet".to_owned()),
+            Some(result) if result.checked_at.elapsed() > GITHUB_PROBE_MAX_AGE => {
+                Some("last probe is stale".to_owned())
+            }
+            Some(result) => result.error,
+        };
+        ReadinessCheck {
+            name: "github",
+            ok: error.is_none(),
+            latency_ms: 0,
+            error,
+        }
+    }
+}
+
+pub async fn healthz() -> Json<serde_json::Value> {
+    let info = version::resolve_effective_version();
+    Json(json!({
+        "ok": true,
+        "version": info.version,
+        "source": info.source,
+    }))
+}
+
+pub async fn readyz(State(state): State<Arc<AppState>>) -> Response {
+    let report = check_readiness(state.as_ref()).await;
+    let status = if report.ready {
+        StatusCode::OK
+    } else {
+        StatusCode::SERVICE_UNAVAILABLE
+    };
+    (status, Json(report)).into_response()
+}
+
+pub async fn check_readiness(state: &AppState) -> ReadinessReport {
+    let (database, migrations, encryption) = tokio::join!(
+        timed("database", check_database(state)),
+        timed("migrations", check_migrations(state)),
+        timed("encryption", async { check_encryption(state) }),
+    );
+    let mut checks = vec![database, migrations, encryption];
+    if state.config.readyz_check_github {
+        checks.push(state.github_probe.check());
+    }
+    ReadinessReport {
+        ready: checks.iter().all(|check| check.ok),
+        checks,
+    }
+}
+
+async fn timed(
+    name: &'static str,
+    check: impl Future<Output = anyhow::Result<()>>,
+) -> ReadinessCheck {
+    let started = Instant::now();
+    let error = match tokio::time::timeout(CHECK_TIMEOUT, check).await {
+        Ok(Ok(())) => None,
+        Ok(Err(err)) => Some(format!("{err:#}")),
+        Err(_) => Some(format!("timed out after {}ms", CHECK_TIMEOUT.as_millis())),
+    };
+    ReadinessCheck {
+        name,
+        ok: error.is_none(),
+        latency_ms: started.elapsed().as_millis() as u64,
+        error,
+    }
+}
+
+async fn check_database(state: &AppState) -> anyhow::Result<()> {
+    sqlx::query_scalar::<_, i64>("SELECT 1")
+        .fetch_one(&state.pool)
+        .await?;
+    Ok(())
+}
+
+async fn check_migrations(state: &AppState) -> anyhow::Result<()> {
+    let applied =
+        sqlx::query_scalar::<_, i64>("SELECT version FROM _sqlx_migrations WHERE success = 1")
+            .fetch_all(&state.pool)
+            .await?
+            .into_iter()
+            .collect::<HashSet<_>>();
+    let pending = sqlx::migrate!("./migrations")
+        .iter()
+        .filter(|migration| !migration.migration_type.is_down_migration())
+        .filter(|migration| !applied.contains(&migration.version))
+        .map(|migration| migration.version.to_string())
+        .collect::<Vec<_>>();
+    if !pending.is_empty() {
+        anyhow::bail!("pending migrations: {}", pending.join(", "));
+    }
+    Ok(())
+}
+
+fn check_encryption(state: &AppState) -> anyhow::Result<()> {
+    const PROBE: &str = "octo-rill readiness probe";
+    let sealed = state.encryption_key.encrypt_str(PROBE)?;
+    let opened = state
+        .encryption_key
+        .decrypt_str(&sealed.ciphertext, &sealed.nonce)?;
+    anyhow::ensure!(opened == PROBE, "encryption round-trip mismatch");
+    Ok(())
+}
+
+/// Any HTTP answer counts as reachable; rate limiting or auth errors are
+/// GitHub's to report on real requests.
+async fn probe_github(state: &AppState) -> Result<(), String> {
+    state
+        .github_rest_http
+        .get(state.github_rest_api_base.clone())
+        .timeout(GITHUB_PROBE_TIMEOUT)
+        .send()
+        .await
+        .map(|_| ())
+        .map_err(|err| format!("github api unreachable: {err}"))
+}
+
+pub fn spawn_github_probe(state: Arc<AppState>) {
+    tokio::spawn(async move {
+        loop {
+            let error = probe_github(state.as_ref()).await.err();
+            if let Some(error) = error.as_deref() {
+                tracing::warn!(error, "readiness: github probe failed");
+            }
+            state.github_probe.record(error);
+            tokio::time::sleep(GITHUB_PROBE_INTERVAL).await;
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use sqlx::{SqlitePool, sqlite::SqlitePoolOptions};
+
+    use crate::config::AppConfig;
+
+    async fn setup_pool() -> SqlitePool {
+        let pool = SqlitePoolOptions::new()
+            .max_connections(1)
+            .connect("sqlite::memory:")
+            .await
+            .expect("create sqlite memory db");
+        sqlx::migrate!("./migrations")
+            .run(&pool)
+            .await
+            .expect("run migrations");
+        pool
+    }
+
+    fn setup_state(pool: SqlitePool, readyz_check_github: bool) -> Arc<AppState> {
+        let config = AppConfig {
+            readyz_check_github,
+            ..AppConfig::for_tests()
+        };
+        Arc::new(AppState::for_tests(pool, config))
+    }
+
+    fn check<'a>(report: &'a ReadinessReport, name: &str) -> &'a ReadinessCheck {
+        report
+            .checks
+            .iter()
+            .find(|check| check.name == name)
+            .unwrap_or_else(|| panic!("missing {name} check"))
+    }
+
+    #[tokio::test]
+    async fn readiness_passes_on_a_migrated_database() {
+        let state = setup_state(setup_pool().await, false);
+
+        let report = check_readiness(state.as_ref()).await;
+
+        assert!(report.ready, "{report:?}");
+        let names = report
+            .checks
+            .iter()
+            .map(|check| check.name)
+            .collect::<Vec<_>>();
+        assert_eq!(names, ["database", "migrations", "encryption"]);
+        let response = readyz(State(state)).await;
+        assert_eq!(response.status(), StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn readiness_reports_pending_migrations() {
+        let pool = setup_pool().await;
+        sqlx::query("DELETE FROM _sqlx_migrations WHERE version = 86")
+            .execute(&pool)
+            .await
+            .expect("forget a migration");
+        let state = setup_state(pool, false);
+
+        let report = check_readiness(state.as_ref()).await;
+
+        assert!(!report.ready);
+        let migrations = check(&report, "migrations");
+        assert!(!migrations.ok);
+        assert_eq!(migrations.error.as_deref(), Some("pending migrations: 86"));
+        assert!(check(&report, "database").ok);
+        let response = readyz(State(state)).await;
+        assert_eq!(response.status(), StatusCode::SERVICE_UNAVAILABLE);
+    }
+
+    #[tokio::test]
+    async fn readiness_reports_a_closed_database() {
+        let pool = setup_pool().await;
+        pool.close().await;
+        let state = setup_state(pool, false);
+
+        let report = check_readiness(state.as_ref()).await;
+
+        assert!(!report.ready);
+        assert!(!check(&report, "database").ok);
+        assert!(check(&report, "encryption").ok);
+    }
+
+    #[tokio::test]
+    async fn github_check_uses_the_last_background_probe() {
+        let state = setup_state(setup_pool().await, true);
+
+        let report = check_readiness(state.as_ref()).await;
+        assert!(!report.ready);
+        assert_eq!(
+            check(&report, "github").error.as_deref(),
+            Some("not probed yet")
+        );
+
+        state.github_probe.record(None);
+        assert!(check_readiness(state.as_ref()).await.ready);
+
+        state
+            .github_probe
+            .record(Some("github api unreachable: timed out".to_owned()));
+        let report = check_readiness(state.as_ref()).await;
+        assert!(!report.ready);
+        assert!(!check(&report, "github").ok);
+    }
+}
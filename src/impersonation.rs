@@ -0,0 +1,365 @@
+use std::sync::Arc;
+
+use axum::{
+    Json,
+    extract::{Path, Request, State},
+    http::{Method, StatusCode},
+    middleware::Next,
+    response::Response,
+};
+use schemars::JsonSchema;
+use serde::Serialize;
+use serde_json::json;
+use tower_sessions::Session;
+
+use crate::{
+    admin_audit, api,
+    error::{ApiError, codes},
+    state::AppState,
+};
+
+/// Session key holding the user an admin is currently viewing the app as.
+const SESSION_IMPERSONATING_USER_ID: &str = "impersonating_user_id";
+/// Path (relative to `/api`) of the start/stop endpoints.
+const IMPERSONATE_PATH: &str = "/admin/impersonate";
+
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct ImpersonationResponse {
+    admin_id: String,
+    /// The impersonated user; `None` once impersonation has ended.
+    user_id: Option<String>,
+}
+
+async fn impersonated_user_id(session: &Session) -> Result<Option<String>, ApiError> {
+    session
+        .get::<String>(SESSION_IMPERSONATING_USER_ID)
+        .await
+        .map_err(ApiError::internal)
+}
+
+/// Target of an active impersonation started by `admin_user_id`. A session
+/// whose admin lost the role or was disabled silently drops back to its own
+/// identity, as does one whose target has since been deleted.
+pub(crate) async fn resolve_impersonation(
+    state: &AppState,
+    session: &Session,
+    admin_user_id: &str,
+) -> Result<Option<String>, ApiError> {
+    let Some(target_user_id) = impersonated_user_id(session).await? else {
+        return Ok(None);
+    };
+    let valid = sqlx::query_scalar::<_, i64>(
+        r#"
+        SELECT COUNT(*)
+        FROM users admin, users target
+        WHERE admin.id = ?
+          AND admin.is_admin = 1
+          AND admin.is_disabled = 0
+          AND target.id = ?
+        "#,
+    )
+    .bind(admin_user_id)
+    .bind(target_user_id.as_str())
+    .fetch_one(&state.pool)
+    .await
+    .map_err(ApiError::internal)?;
+    if valid == 0 {
+        session
+            .remove::<String>(SESSION_IMPERSONATING_USER_ID)
+            .await
+            .map_err(ApiError::internal)?;
+        return Ok(None);
+    }
+    Ok(Some(target_user_id))
+}
+
+/// Route layer for `/api`: while impersonating only safe methods go through,
+/// apart from ending the impersonation itself.
+pub async fn reject_mutations_while_impersonating(
+    session: Session,
+    request: Request,
+    next: Next,
+) -> Result<Response, ApiError> {
+    if matches!(
+        *request.method(),
+        Method::GET | Method::HEAD | Method::OPTIONS
+    ) || (request.method() == Method::DELETE && request.uri().path() == IMPERSONATE_PATH)
+    {
+        return Ok(next.run(request).await);
+    }
+    if impersonated_user_id(&session).await?.is_some() {
+        return Err(ApiError::new(
+            StatusCode::FORBIDDEN,
+            codes::IMPERSONATION_READ_ONLY,
+            "impersonation is read-only; end it before making changes",
+        ));
+    }
+    Ok(next.run(request).await)
+}
+
+pub async fn admin_start_impersonation(
+    State(state): State<Arc<AppState>>,
+    api::AdminUser(admin_id): api::AdminUser,
+    // Records the impersonation target.
+    session: Session,
+    Path(target_user_id): Path<String>,
+) -> Result<Json<ImpersonationResponse>, ApiError> {
+    let target_user_id = api::parse_local_id_param(target_user_id, "user_id")?;
+    if target_user_id == admin_id {
+        return Err(ApiError::bad_request("cannot impersonate yourself"));
+    }
+    state.admin_mutation_limiter.check(admin_id.as_str())?;
+
+    let mut tx = state.pool.begin().await.map_err(ApiError::internal)?;
+    let target_is_disabled =
+        sqlx::query_scalar::<_, i64>(r#"SELECT is_disabled FROM users WHERE id = ?"#)
+            .bind(target_user_id.as_str())
+            .fetch_optional(&mut *tx)
+            .await
+            .map_err(ApiError::internal)?
+            .ok_or_else(|| {
+                ApiError::new(StatusCode::NOT_FOUND, codes::NOT_FOUND, "user not found")
+            })?;
+    admin_audit::record(
+        &mut tx,
+        admin_id.as_str(),
+        Some(target_user_id.as_str()),
+        admin_audit::ADMIN_AUDIT_IMPERSONATION_START,
+        &json!({}),
+        &json!({ "target_is_disabled": target_is_disabled != 0 }),
+    )
+    .await?;
+    tx.commit().await.map_err(ApiError::internal)?;
+
+    session
+        .insert(SESSION_IMPERSONATING_USER_ID, target_user_id.as_str())
+        .await
+        .map_err(ApiError::internal)?;
+    Ok(Json(ImpersonationResponse {
+        admin_id,
+        user_id: Some(target_user_id),
+    }))
+}
+
+/// Ends the impersonation. Resolves the admin from the raw session because
+/// every other identity lookup answers with the impersonated user.
+pub async fn admin_stop_impersonation(
+    State(state): State<Arc<AppState>>,
+    session: Session,
+) -> Result<Json<ImpersonationResponse>, ApiError> {
+    let admin_id = api::require_user_id(&session).await?;
+    let Some(target_user_id) = session
+        .remove::<String>(SESSION_IMPERSONATING_USER_ID)
+        .await
+        .map_err(ApiError::internal)?
+    else {
+        return Ok(Json(ImpersonationResponse {
+            admin_id,
+            user_id: None,
+        }));
+    };
+
+    let mut conn = state.pool.acquire().await.map_err(ApiError::internal)?;
+    admin_audit::record(
+        &mut conn,
+        admin_id.as_str(),
+        Some(target_user_id.as_str()),
+        admin_audit::ADMIN_AUDIT_IMPERSONATION_STOP,
+        &json!({}),
+        &json!({}),
+    )
+    .await?;
+    Ok(Json(ImpersonationResponse {
+        admin_id,
+        user_id: None,
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use axum::{
+        Extension, Router,
+        body::Body,
+        http::Request,
+        middleware,
+        routing::{delete, get, post},
+    };
+    use sqlx::{SqlitePool, sqlite::SqlitePoolOptions};
+    use tower::ServiceExt;
+    use tower_sessions::MemoryStore;
+
+    use crate::{config::AppConfig, sync_issues};
+
+    async fn setup_pool() -> SqlitePool {
+        let pool = SqlitePoolOptions::new()
+            .max_connections(1)
+            .connect("sqlite::memory:")
+            .await
+            .expect("create sqlite memory db");
+        sqlx::migrate!("./migrations")
+            .run(&pool)
+            .await
+            .expect("run migrations");
+        pool
+    }
+
+    async fn seed_user(
+        pool: &SqlitePool,
+        user_id: &str,
+        github_user_id: i64,
+        is_admin: i64,
+        is_disabled: i64,
+    ) {
+        sqlx::query(
+            r#"
+            INSERT INTO users (
+              id, github_user_id, login, is_admin, is_disabled, created_at, updated_at
+            )
+            VALUES (?, ?, ?, ?, ?, '2026-02-01T00:00:00Z', '2026-02-01T00:00:00Z')
+            "#,
+        )
+        .bind(user_id)
+        .bind(github_user_id)
+        .bind(format!("user-{github_user_id}"))
+        .bind(is_admin)
+        .bind(is_disabled)
+        .execute(pool)
+        .await
+        .expect("seed user");
+    }
+
+    async fn seed_issue(pool: &SqlitePool, id: &str, user_id: &str) {
+        sqlx::query(
+            r#"
+            INSERT INTO repo_sync_errors (
+              id, user_id, repo_id, repo_full_name, sync_type, error_code, message, occurred_at
+            )
+            VALUES (?, ?, 42, 'octo/gone', 'releases', 'repo_inaccessible',
+              'github returned 404 Not Found', '2026-02-02T00:00:00Z')
+            "#,
+        )
+        .bind(id)
+        .bind(user_id)
+        .execute(pool)
+        .await
+        .expect("seed sync issue");
+    }
+
+    fn setup_state(pool: SqlitePool) -> Arc<AppState> {
+        Arc::new(AppState::for_tests(pool, AppConfig::for_tests()))
+    }
+
+    /// `/api` slice with the read-only layer; the shared session stands in
+    /// for the session layer.
+    fn test_router(state: Arc<AppState>, session: Session) -> Router {
+        let api = Router::new()
+            .route(
+                "/admin/impersonate/{user_id}",
+                post(admin_start_impersonation),
+            )
+            .route(IMPERSONATE_PATH, delete(admin_stop_impersonation))
+            .route("/sync/issues", get(sync_issues::list_sync_issues))
+            .route(
+                "/sync/issues/{id}/dismiss",
+                post(sync_issues::dismiss_sync_issue),
+            )
+            .route_layer(middleware::from_fn(reject_mutations_while_impersonating));
+        Router::new()
+            .nest("/api", api)
+            .with_state(state)
+            .layer(Extension(session))
+    }
+
+    async fn call(app: &Router, method: &str, uri: &str) -> (StatusCode, serde_json::Value) {
+        let response = app
+            .clone()
+            .oneshot(
+                Request::builder()
+                    .method(method)
+                    .uri(uri)
+                    .body(Body::empty())
+                    .expect("build request"),
+            )
+            .await
+            .expect("response");
+        let status = response.status();
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .expect("read body");
+        (
+            status,
+            serde_json::from_slice(&body).unwrap_or(serde_json::Value::Null),
+        )
+    }
+
+    fn issue_ids(body: &serde_json::Value) -> Vec<&str> {
+        body["items"]
+            .as_array()
+            .expect("items")
+            .iter()
+            .map(|item| item["id"].as_str().expect("issue id"))
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn impersonation_reads_as_target_and_rejects_mutations() {
+        let pool = setup_pool().await;
+        let admin = crate::local_id::test_local_id("impersonation-admin");
+        let member = crate::local_id::test_local_id("impersonation-member");
+        seed_user(&pool, &admin, 1, 1, 0).await;
+        seed_user(&pool, &member, 2, 0, 1).await;
+        seed_issue(&pool, "issue-admin", &admin).await;
+        seed_issue(&pool, "issue-member", &member).await;
+        let session = Session::new(None, Arc::new(MemoryStore::default()), None);
+        session
+            .insert("user_id", admin.as_str())
+            .await
+            .expect("insert session user id");
+        let app = test_router(setup_state(pool.clone()), session.clone());
+
+        let (status, body) = call(&app, "POST", &format!("/api/admin/impersonate/{member}")).await;
+        assert_eq!(status, StatusCode::OK, "{body}");
+        assert_eq!(body["user_id"], member.as_str());
+
+        // The member is disabled, yet reads still resolve to their rows.
+        let (status, body) = call(&app, "GET", "/api/sync/issues").await;
+        assert_eq!(status, StatusCode::OK, "{body}");
+        assert_eq!(issue_ids(&body), vec!["issue-member"]);
+
+        let (status, body) = call(&app, "POST", "/api/sync/issues/issue-member/dismiss").await;
+        assert_eq!(status, StatusCode::FORBIDDEN);
+        assert_eq!(body["error"]["code"], "impersonation_read_only");
+
+        let (status, body) = call(&app, "DELETE", "/api/admin/impersonate").await;
+        assert_eq!(status, StatusCode::OK, "{body}");
+        assert!(body["user_id"].is_null());
+
+        let (status, body) = call(&app, "GET", "/api/sync/issues").await;
+        assert_eq!(status, StatusCode::OK, "{body}");
+        assert_eq!(issue_ids(&body), vec!["issue-admin"]);
+        assert_eq!(
+            session
+                .get::<String>("user_id")
+                .await
+                .expect("read session")
+                .as_deref(),
+            Some(admin.as_str())
+        );
+
+        let actions = sqlx::query_scalar::<_, String>(
+            r#"SELECT action FROM admin_audit_log ORDER BY created_at, rowid"#,
+        )
+        .fetch_all(&pool)
+        .await
+        .expect("load audit rows");
+        assert_eq!(
+            actions,
+            vec![
+                admin_audit::ADMIN_AUDIT_IMPERSONATION_START,
+                admin_audit::ADMIN_AUDIT_IMPERSONATION_STOP
+            ]
+        );
+    }
+}
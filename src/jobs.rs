@@ -20,9 +20,12 @@ use chrono_tz::Tz;
 use serde::{Deserialize, Serialize};
 use serde_json::{Value, json};
 use tokio::io::AsyncWriteExt;
+use tokio_util::sync::CancellationToken;
 
 use crate::{
-    admin_runtime, ai, api, briefs, local_id, runtime, state::AppState, sync, translations,
+    admin_runtime, ai, api, backup, bootstrap, briefs, db_maintenance, local_id,
+    notification_archive, notify, observability, runtime, star_purge, state::AppState, sync,
+    translation_retry, translations,
 };
 
 pub const STATUS_QUEUED: &str = "queued";
@@ -35,8 +38,11 @@ pub const TASK_SYNC_STARRED: &str = "sync.starred";
 pub const TASK_SYNC_RELEASES: &str = "sync.releases";
 pub const TASK_SYNC_NOTIFICATIONS: &str = "sync.notifications";
 pub const TASK_SYNC_ALL: &str = "sync.all";
+pub const TASK_BOOTSTRAP_USER: &str = "sync.bootstrap";
 pub const TASK_SYNC_ACCESS_REFRESH: &str = "sync.access_refresh";
 pub const TASK_SYNC_SUBSCRIPTIONS: &str = "sync.subscriptions";
+pub const TASK_SYNC_RELEASE_NODE_IDS: &str = "sync.release_node_ids";
+pub const TASK_BACKFILL_REPO_RELEASES: &str = "sync.backfill_repo_releases";
 pub const TASK_BRIEF_GENERATE: &str = "brief.generate";
 pub const TASK_BRIEF_DAILY_SLOT: &str = "brief.daily_slot";
 pub const TASK_BRIEF_HISTORY_RECOMPUTE: &str = "brief.history_recompute";
@@ -47,13 +53,56 @@ pub const TASK_TRANSLATE_RELEASE_BATCH: &str = "translate.release.batch";
 pub const TASK_SUMMARIZE_RELEASE_SMART_BATCH: &str = "summarize.release.smart.batch";
 pub const TASK_TRANSLATE_RELEASE_DETAIL: &str = "translate.release_detail";
 pub const TASK_TRANSLATE_NOTIFICATION: &str = "translate.notification";
+pub const TASK_NOTIFY_DISPATCH: &str = "notify.dispatch";
+pub const TASK_MAINTENANCE_PRUNE: &str = "maintenance.prune";
+pub const TASK_MAINTENANCE_DB: &str = "maintenance.db";
+pub const TASK_TRANSLATION_REPAIR: &str = "translation.repair";
+pub const TASK_TRANSLATION_RETRY: &str = "translation.retry";
+pub const TASK_MAINTENANCE_STAR_PURGE: &str = "maintenance.star_purge";
+pub const TASK_MAINTENANCE_BACKUP: &str = "maintenance.backup";
 
 pub const SCHEDULED_TASK_TYPES: &[&str] = &[
     TASK_BRIEF_DAILY_SLOT,
     TASK_SYNC_SUBSCRIPTIONS,
     TASK_RETRY_RECENT_FAILURES,
+    TASK_MAINTENANCE_PRUNE,
+    TASK_MAINTENANCE_DB,
+    TASK_TRANSLATION_RETRY,
+    TASK_MAINTENANCE_STAR_PURGE,
+    TASK_MAINTENANCE_BACKUP,
 ];
 
+/// Every window of this many claims hands at least one slot to each waiting
+/// priority, so a steady stream of high-priority work cannot starve the rest.
+const TASK_PRIORITY_FAIRNESS_WINDOW: usize = 5;
+
+/// Claim lane of a queued task. Workers take `High` first, then `Normal`,
+/// then `Low`, subject to [`TASK_PRIORITY_FAIRNESS_WINDOW`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum TaskPriority {
+    High,
+    Normal,
+    Low,
+}
+
+impl TaskPriority {
+    pub const ALL: [Self; 3] = [Self::High, Self::Normal, Self::Low];
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::High => "high",
+            Self::Normal => "normal",
+            Self::Low => "low",
+        }
+    }
+
+    pub fn parse(raw: &str) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|priority| priority.as_str() == raw)
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct NewTask {
     pub task_type: String,
@@ -61,6 +110,7 @@ pub struct NewTask {
     pub source: String,
     pub requested_by: Option<String>,
     pub parent_task_id: Option<String>,
+    pub priority: TaskPriority,
 }
 
 #[derive(Debug, Clone, Serialize)]
@@ -78,6 +128,7 @@ struct TaskRow {
     requested_by: Option<String>,
     payload_json: String,
     cancel_requested: i64,
+    request_id: Option<String>,
 }
 
 #[derive(Debug, sqlx::FromRow)]
@@ -123,13 +174,87 @@ struct DueDailySlotUser {
 
 const SUBSCRIPTION_SCHEDULE_NAME: &str = "sync.subscriptions";
 const RETRY_RECENT_FAILURES_SCHEDULE_NAME: &str = "retry.recent_failures";
+const MAINTENANCE_PRUNE_SCHEDULE_NAME: &str = "maintenance.prune";
+const MAINTENANCE_DB_SCHEDULE_NAME: &str = "maintenance.db";
+const TRANSLATION_RETRY_SCHEDULE_NAME: &str = "translation.retry";
+const TRANSLATION_RETRY_INTERVAL_MINUTES: i64 = 5;
+const STAR_PURGE_SCHEDULE_NAME: &str = "maintenance.star_purge";
+const MAINTENANCE_BACKUP_SCHEDULE_NAME: &str = "maintenance.backup";
+const MAINTENANCE_PRUNE_BATCH_SIZE: i64 = 500;
+pub const LLM_CALL_PAYLOAD_RETENTION_DAYS: i64 = 7;
 const ADMIN_DASHBOARD_ROLLUP_SCHEDULER_INTERVAL: Duration = Duration::from_secs(15 * 60);
+/// Allowed `users.sync_frequency_minutes` values; 0 turns scheduled sync off.
+pub const USER_SYNC_FREQUENCY_CHOICES: &[i64] = &[0, 30, 60, 180];
+pub const USER_SYNC_TASK_TYPES: &[&str] = &[TASK_SYNC_RELEASES, TASK_SYNC_NOTIFICATIONS];
+const USER_SYNC_SCHEDULER_INTERVAL: Duration = Duration::from_secs(3 * 60);
+/// Share of the frequency used to spread users' due times apart.
+const USER_SYNC_JITTER_DIVISOR: i64 = 4;
+const USER_SYNC_SCHEDULER_SOURCE: &str = "scheduler.user_sync";
 const RETRY_RECENT_FAILURES_MAX_ITEMS_PER_KIND: i64 = 100;
 const RETRY_RECENT_FAILURES_KIND_BUDGET: Duration = Duration::from_secs(10 * 60);
 #[cfg(test)]
 const SMART_NO_VALUABLE_VERSION_INFO: &str = "no_valuable_version_info";
 static TASK_CLAIM_LOCK: OnceLock<tokio::sync::Mutex<()>> = OnceLock::new();
 static TASK_SINGLETON_ENQUEUE_LOCK: OnceLock<tokio::sync::Mutex<()>> = OnceLock::new();
+static TASK_CANCELLATIONS: OnceLock<std::sync::Mutex<HashMap<String, TaskCancellation>>> =
+    OnceLock::new();
+pub const TASK_CANCELED_ERROR: &str = "task canceled";
+/// How often a registered task re-reads `cancel_requested` to pick up
+/// cancellations issued by another instance.
+const TASK_CANCEL_POLL_INTERVAL: Duration = Duration::from_secs(2);
+pub const TASK_SERVER_SHUTDOWN_ERROR: &str = "server shutdown";
+/// Prefix of the error recorded on tasks that ran past their timeout.
+pub const TASK_TIMEOUT_ERROR_PREFIX: &str = "timeout after ";
+const DEFAULT_SYNC_TASK_TIMEOUT: Duration = Duration::from_secs(15 * 60);
+const DEFAULT_TRANSLATE_TASK_TIMEOUT: Duration = Duration::from_secs(10 * 60);
+const DEFAULT_BRIEF_TASK_TIMEOUT: Duration = Duration::from_secs(20 * 60);
+// Sync tasks only mirror GitHub state, so an interrupted run is simply queued again.
+const STARTUP_REQUEUE_TASK_TYPES: &[&str] = &[
+    TASK_SYNC_STARRED,
+    TASK_SYNC_RELEASES,
+    TASK_SYNC_NOTIFICATIONS,
+    TASK_SYNC_ALL,
+    TASK_BOOTSTRAP_USER,
+    TASK_SYNC_RELEASE_NODE_IDS,
+    TASK_BACKFILL_REPO_RELEASES,
+];
+
+/// Wall-clock limits the worker enforces per task family. Task types outside
+/// these families (maintenance, retries, notify) run unbounded.
+#[derive(Clone, Debug)]
+pub struct TaskTimeouts {
+    pub sync: Duration,
+    pub translate: Duration,
+    pub brief: Duration,
+}
+
+impl Default for TaskTimeouts {
+    fn default() -> Self {
+        Self {
+            sync: DEFAULT_SYNC_TASK_TIMEOUT,
+            translate: DEFAULT_TRANSLATE_TASK_TIMEOUT,
+            brief: DEFAULT_BRIEF_TASK_TIMEOUT,
+        }
+    }
+}
+
+impl TaskTimeouts {
+    pub fn for_task_type(&self, task_type: &str) -> Option<Duration> {
+        if task_type.starts_with("sync.") {
+            Some(self.sync)
+        } else if task_type.starts_with("translate.") || task_type.starts_with("summarize.") {
+            Some(self.translate)
+        } else if task_type.starts_with("brief.") {
+            Some(self.brief)
+        } else {
+            None
+        }
+    }
+}
+
+fn task_timeout_error(limit: Duration) -> String {
+    format!("{TASK_TIMEOUT_ERROR_PREFIX}{}s", limit.as_secs())
+}
 
 pub fn is_scheduled_task_type(task_type: &str) -> bool {
     SCHEDULED_TASK_TYPES.contains(&task_type)
@@ -143,6 +268,110 @@ fn task_singleton_enqueue_lock() -> &'static tokio::sync::Mutex<()> {
     TASK_SINGLETON_ENQUEUE_LOCK.get_or_init(|| tokio::sync::Mutex::new(()))
 }
 
+fn task_cancellations() -> &'static std::sync::Mutex<HashMap<String, TaskCancellation>> {
+    TASK_CANCELLATIONS.get_or_init(|| std::sync::Mutex::new(HashMap::new()))
+}
+
+struct TaskCancellation {
+    token: CancellationToken,
+    polled_at: Option<Instant>,
+}
+
+/// Returned by work that stopped early because its task was canceled.
+#[derive(Debug, thiserror::Error)]
+#[error("{}", TASK_CANCELED_ERROR)]
+pub struct TaskCanceled;
+
+/// Keeps a running task's cancellation token registered so `cancel_task` can
+/// signal the worker in-process; the token is dropped with the guard.
+pub struct TaskCancellationGuard {
+    task_id: String,
+}
+
+impl Drop for TaskCancellationGuard {
+    fn drop(&mut self) {
+        task_cancellations()
+            .lock()
+            .expect("task cancellations poisoned")
+            .remove(&self.task_id);
+    }
+}
+
+pub fn register_task_cancellation(task_id: &str) -> TaskCancellationGuard {
+    task_cancellations()
+        .lock()
+        .expect("task cancellations poisoned")
+        .insert(
+            task_id.to_owned(),
+            TaskCancellation {
+                token: CancellationToken::new(),
+                polled_at: None,
+            },
+        );
+    TaskCancellationGuard {
+        task_id: task_id.to_owned(),
+    }
+}
+
+fn signal_task_cancellation(task_id: &str) {
+    if let Some(cancellation) = task_cancellations()
+        .lock()
+        .expect("task cancellations poisoned")
+        .get(task_id)
+    {
+        cancellation.token.cancel();
+    }
+}
+
+/// Whether `task_id` has been asked to stop. `cancel_task` fires the
+/// in-process token, so local cancellations are seen without a query. A
+/// registered task reads `cancel_requested` at most once per
+/// [`TASK_CANCEL_POLL_INTERVAL`] to catch cancellations issued by another
+/// instance; unregistered ids are always looked up.
+pub async fn task_cancel_requested(state: &AppState, task_id: &str) -> bool {
+    let token = {
+        let mut cancellations = task_cancellations()
+            .lock()
+            .expect("task cancellations poisoned");
+        match cancellations.get_mut(task_id) {
+            Some(cancellation) if cancellation.token.is_cancelled() => return true,
+            Some(cancellation)
+                if cancellation
+                    .polled_at
+                    .is_some_and(|at| at.elapsed() < TASK_CANCEL_POLL_INTERVAL) =>
+            {
+                return false;
+            }
+            Some(cancellation) => {
+                cancellation.polled_at = Some(Instant::now());
+                Some(cancellation.token.clone())
+            }
+            None => None,
+        }
+    };
+    let requested = is_task_cancel_requested(state, task_id)
+        .await
+        .unwrap_or(false);
+    if requested && let Some(token) = token {
+        token.cancel();
+    }
+    requested
+}
+
+/// Cancellation check for code running under a task's LLM call context, so
+/// deep worker loops can stop between steps without threading the task id.
+pub async fn current_task_cancel_requested(state: &AppState) -> bool {
+    match ai::current_parent_task_id() {
+        Some(task_id) => task_cancel_requested(state, task_id.as_str()).await,
+        None => false,
+    }
+}
+
+pub fn is_task_canceled_error(err: &anyhow::Error) -> bool {
+    err.chain()
+        .any(|cause| cause.downcast_ref::<TaskCanceled>().is_some())
+}
+
 pub fn spawn_task_workers(state: Arc<AppState>, count: usize) {
     for _ in 0..count.max(1) {
         spawn_task_worker(state.clone());
@@ -167,25 +396,92 @@ pub async fn recover_runtime_state_on_startup(state: &AppState) -> Result<()> {
 
 pub fn spawn_task_worker(state: Arc<AppState>) {
     tokio::spawn(async move {
-        loop {
-            match claim_next_queued_task(state.as_ref()).await {
+        let shutdown = runtime::shutdown_token();
+        while !shutdown.is_cancelled() {
+            let idle = match claim_next_queued_task(state.as_ref()).await {
                 Ok(Some(task)) => {
                     if let Err(err) = process_task(Arc::clone(&state), task).await {
                         tracing::warn!(?err, "task worker: process task failed");
                     }
+                    continue;
                 }
-                Ok(None) => {
-                    tokio::time::sleep(Duration::from_millis(450)).await;
-                }
+                Ok(None) => Duration::from_millis(450),
                 Err(err) => {
                     tracing::warn!(?err, "task worker: claim task failed");
-                    tokio::time::sleep(Duration::from_secs(2)).await;
+                    Duration::from_secs(2)
                 }
+            };
+            tokio::select! {
+                _ = shutdown.cancelled() => break,
+                _ = tokio::time::sleep(idle) => {}
             }
         }
     });
 }
 
+/// Number of tasks this process is executing right now.
+fn running_task_count() -> usize {
+    task_cancellations()
+        .lock()
+        .expect("task cancellations poisoned")
+        .len()
+}
+
+/// Waits up to `grace` for in-flight tasks to finish after shutdown stopped
+/// new dispatch. Returns how many were still running when time ran out.
+pub async fn drain_running_tasks(grace: Duration) -> usize {
+    let deadline = Instant::now() + grace;
+    loop {
+        let running = running_task_count();
+        if running == 0 || Instant::now() >= deadline {
+            return running;
+        }
+        tokio::time::sleep(Duration::from_millis(200)).await;
+    }
+}
+
+/// Fails the tasks this runtime owner still holds once the shutdown grace
+/// period is over, so they do not linger as running until the lease expires.
+pub async fn fail_running_tasks_on_shutdown(state: &AppState) -> Result<usize> {
+    let task_ids = sqlx::query_scalar::<_, String>(
+        r#"
+        SELECT id
+        FROM job_tasks
+        WHERE status = ? AND runtime_owner_id = ?
+        ORDER BY created_at ASC, id ASC
+        "#,
+    )
+    .bind(STATUS_RUNNING)
+    .bind(state.runtime_owner_id.as_str())
+    .fetch_all(&state.pool)
+    .await
+    .context("failed to load running tasks on shutdown")?;
+
+    for task_id in &task_ids {
+        finalize_task(
+            state,
+            task_id,
+            STATUS_FAILED,
+            None,
+            Some(TASK_SERVER_SHUTDOWN_ERROR.to_owned()),
+        )
+        .await?;
+        append_task_event(
+            state,
+            task_id,
+            "task.shutdown_failed",
+            json!({
+                "task_id": task_id,
+                "status": STATUS_FAILED,
+                "error": TASK_SERVER_SHUTDOWN_ERROR,
+            }),
+        )
+        .await?;
+    }
+
+    Ok(task_ids.len())
+}
+
 pub fn spawn_hourly_scheduler(state: Arc<AppState>) {
     tokio::spawn(async move {
         loop {
@@ -225,6 +521,78 @@ pub fn spawn_recent_failures_retry_scheduler(state: Arc<AppState>) {
     });
 }
 
+pub fn spawn_maintenance_prune_scheduler(state: Arc<AppState>) {
+    tokio::spawn(async move {
+        loop {
+            let now = Utc::now();
+            if let Err(err) = enqueue_maintenance_prune_if_due(state.as_ref(), now).await {
+                tracing::warn!(?err, "maintenance prune scheduler: enqueue due run failed");
+            }
+            tokio::time::sleep(Duration::from_secs(60)).await;
+        }
+    });
+}
+
+pub fn spawn_db_maintenance_scheduler(state: Arc<AppState>) {
+    tokio::spawn(async move {
+        loop {
+            let now = Utc::now();
+            if let Err(err) = enqueue_db_maintenance_if_due(state.as_ref(), now).await {
+                tracing::warn!(?err, "db maintenance scheduler: enqueue due run failed");
+            }
+            tokio::time::sleep(Duration::from_secs(10 * 60)).await;
+        }
+    });
+}
+
+pub fn spawn_backup_scheduler(state: Arc<AppState>) {
+    tokio::spawn(async move {
+        loop {
+            let now = Utc::now();
+            if let Err(err) = enqueue_backup_if_due(state.as_ref(), now).await {
+                tracing::warn!(?err, "backup scheduler: enqueue due run failed");
+            }
+            tokio::time::sleep(Duration::from_secs(10 * 60)).await;
+        }
+    });
+}
+
+pub fn spawn_translation_retry_scheduler(state: Arc<AppState>) {
+    tokio::spawn(async move {
+        loop {
+            let now = Utc::now();
+            if let Err(err) = enqueue_translation_retry_if_due(state.as_ref(), now).await {
+                tracing::warn!(?err, "translation retry scheduler: enqueue due run failed");
+            }
+            tokio::time::sleep(Duration::from_secs(60)).await;
+        }
+    });
+}
+
+pub fn spawn_star_purge_scheduler(state: Arc<AppState>) {
+    tokio::spawn(async move {
+        loop {
+            let now = Utc::now();
+            if let Err(err) = enqueue_star_purge_if_due(state.as_ref(), now).await {
+                tracing::warn!(?err, "star purge scheduler: enqueue due run failed");
+            }
+            tokio::time::sleep(Duration::from_secs(60)).await;
+        }
+    });
+}
+
+pub fn spawn_user_sync_scheduler(state: Arc<AppState>) {
+    tokio::spawn(async move {
+        loop {
+            let now = Utc::now();
+            if let Err(err) = enqueue_due_user_syncs(state.as_ref(), now).await {
+                tracing::warn!(?err, "user sync scheduler: enqueue due syncs failed");
+            }
+            tokio::time::sleep(USER_SYNC_SCHEDULER_INTERVAL).await;
+        }
+    });
+}
+
 pub fn spawn_admin_dashboard_rollup_scheduler(state: Arc<AppState>) {
     tokio::spawn(async move {
         loop {
@@ -290,6 +658,7 @@ pub async fn enqueue_hour_slot_if_due(
             source: "scheduler".to_owned(),
             requested_by: None,
             parent_task_id: None,
+            priority: TaskPriority::Normal,
         },
     )
     .await?;
@@ -345,6 +714,7 @@ pub async fn enqueue_subscription_run_if_due(
                 source: "scheduler".to_owned(),
                 requested_by: None,
                 parent_task_id: None,
+                priority: TaskPriority::Normal,
             },
         )
         .await?
@@ -409,6 +779,7 @@ pub async fn enqueue_recent_failures_retry_if_due(
                 source: "scheduler".to_owned(),
                 requested_by: None,
                 parent_task_id: None,
+                priority: TaskPriority::Normal,
             },
         )
         .await?
@@ -424,282 +795,986 @@ pub async fn enqueue_recent_failures_retry_if_due(
     Ok(Some(task.task_id))
 }
 
-pub async fn enqueue_brief_history_recompute_if_needed(state: &AppState) -> Result<Option<String>> {
-    if ai::legacy_brief_count(state).await? == 0 {
+pub async fn enqueue_maintenance_prune_if_due(
+    state: &AppState,
+    now: DateTime<Utc>,
+) -> Result<Option<String>> {
+    let schedule_key = current_maintenance_prune_schedule_key(now);
+    let row = sqlx::query_as::<_, DispatchStateRow>(
+        r#"
+        SELECT last_dispatch_key
+        FROM scheduled_task_dispatch_state
+        WHERE schedule_name = ?
+        LIMIT 1
+        "#,
+    )
+    .bind(MAINTENANCE_PRUNE_SCHEDULE_NAME)
+    .fetch_optional(&state.pool)
+    .await
+    .context("failed to query maintenance prune dispatch state")?;
+
+    if row
+        .as_ref()
+        .and_then(|current| current.last_dispatch_key.as_deref())
+        == Some(schedule_key.as_str())
+    {
         return Ok(None);
     }
-
-    if let Some(existing) = find_inflight_task_by_type(state, TASK_BRIEF_HISTORY_RECOMPUTE).await? {
-        return Ok(Some(existing.task_id));
+    // A slow prune simply absorbs the next hourly slot; there is nothing to catch up on.
+    if task_type_run_in_flight(state, TASK_MAINTENANCE_PRUNE).await? {
+        return Ok(None);
     }
 
     let task = enqueue_task(
         state,
         NewTask {
-            task_type: TASK_BRIEF_HISTORY_RECOMPUTE.to_owned(),
-            payload: json!({}),
-            source: "migration.bootstrap".to_owned(),
+            task_type: TASK_MAINTENANCE_PRUNE.to_owned(),
+            payload: json!({
+                "trigger": "schedule",
+                "schedule_key": schedule_key,
+            }),
+            source: "scheduler".to_owned(),
             requested_by: None,
             parent_task_id: None,
+            priority: TaskPriority::Low,
         },
     )
     .await?;
 
+    upsert_dispatch_state(
+        state,
+        MAINTENANCE_PRUNE_SCHEDULE_NAME,
+        &schedule_key,
+        &task.task_id,
+    )
+    .await?;
     Ok(Some(task.task_id))
 }
 
-pub async fn enqueue_brief_refresh_content_if_needed(state: &AppState) -> Result<Option<String>> {
-    if ai::brief_content_refresh_candidate_count(state).await? == 0 {
+pub(crate) fn current_maintenance_prune_schedule_key(now: DateTime<Utc>) -> String {
+    format!("hour:{}", now.format("%Y-%m-%dT%H"))
+}
+
+pub async fn enqueue_db_maintenance_if_due(
+    state: &AppState,
+    now: DateTime<Utc>,
+) -> Result<Option<String>> {
+    let schedule_key = current_db_maintenance_schedule_key(now);
+    let row = sqlx::query_as::<_, DispatchStateRow>(
+        r#"
+        SELECT last_dispatch_key
+        FROM scheduled_task_dispatch_state
+        WHERE schedule_name = ?
+        LIMIT 1
+        "#,
+    )
+    .bind(MAINTENANCE_DB_SCHEDULE_NAME)
+    .fetch_optional(&state.pool)
+    .await
+    .context("failed to query db maintenance dispatch state")?;
+
+    if row
+        .as_ref()
+        .and_then(|current| current.last_dispatch_key.as_deref())
+        == Some(schedule_key.as_str())
+    {
         return Ok(None);
     }
-
-    if let Some(existing) = find_inflight_task_by_type(state, TASK_BRIEF_REFRESH_CONTENT).await? {
-        return Ok(Some(existing.task_id));
+    if task_type_run_in_flight(state, TASK_MAINTENANCE_DB).await? {
+        return Ok(None);
     }
 
     let task = enqueue_task(
         state,
         NewTask {
-            task_type: TASK_BRIEF_REFRESH_CONTENT.to_owned(),
-            payload: json!({}),
-            source: "migration.bootstrap".to_owned(),
+            task_type: TASK_MAINTENANCE_DB.to_owned(),
+            payload: json!({
+                "trigger": "schedule",
+                "schedule_key": schedule_key,
+            }),
+            source: "scheduler".to_owned(),
             requested_by: None,
             parent_task_id: None,
+            priority: TaskPriority::Low,
         },
     )
     .await?;
 
+    upsert_dispatch_state(
+        state,
+        MAINTENANCE_DB_SCHEDULE_NAME,
+        &schedule_key,
+        &task.task_id,
+    )
+    .await?;
     Ok(Some(task.task_id))
 }
 
-pub(crate) fn current_subscription_schedule_key(
-    now: DateTime<Utc>,
-    interval_minutes: i64,
-) -> String {
-    let interval_minutes =
-        admin_runtime::normalize_sync_auto_fetch_interval_minutes(interval_minutes);
-    let bucket_start = now.timestamp().div_euclid(interval_minutes * 60) * interval_minutes * 60;
-    format!("interval:{interval_minutes}:{bucket_start}")
-}
-
-pub(crate) fn current_recent_failures_retry_schedule_key(
-    now: DateTime<Utc>,
-    interval_minutes: i64,
-) -> String {
-    let interval_minutes =
-        admin_runtime::normalize_retry_recent_failures_interval_minutes(interval_minutes);
-    let bucket_start = now.timestamp().div_euclid(interval_minutes * 60) * interval_minutes * 60;
-    format!("interval:{interval_minutes}:{bucket_start}")
+pub(crate) fn current_db_maintenance_schedule_key(now: DateTime<Utc>) -> String {
+    format!("week:{}", now.format("%G-W%V"))
 }
 
-async fn subscription_run_in_flight(state: &AppState) -> Result<bool> {
-    let count = sqlx::query_scalar::<_, i64>(
+/// Dispatches one database snapshot per UTC day.
+pub async fn enqueue_backup_if_due(state: &AppState, now: DateTime<Utc>) -> Result<Option<String>> {
+    let schedule_key = format!("day:{}", now.format("%Y-%m-%d"));
+    let row = sqlx::query_as::<_, DispatchStateRow>(
         r#"
-        SELECT COUNT(*)
-        FROM job_tasks
-        WHERE task_type = ?
-          AND status IN (?, ?)
+        SELECT last_dispatch_key
+        FROM scheduled_task_dispatch_state
+        WHERE schedule_name = ?
+        LIMIT 1
         "#,
     )
-    .bind(TASK_SYNC_SUBSCRIPTIONS)
-    .bind(STATUS_QUEUED)
-    .bind(STATUS_RUNNING)
-    .fetch_one(&state.pool)
+    .bind(MAINTENANCE_BACKUP_SCHEDULE_NAME)
+    .fetch_optional(&state.pool)
     .await
-    .context("failed to query in-flight subscription runs")?;
+    .context("failed to query backup dispatch state")?;
 
-    Ok(count > 0)
-}
+    if row
+        .as_ref()
+        .and_then(|current| current.last_dispatch_key.as_deref())
+        == Some(schedule_key.as_str())
+    {
+        return Ok(None);
+    }
+    if task_type_run_in_flight(state, TASK_MAINTENANCE_BACKUP).await? {
+        return Ok(None);
+    }
 
-async fn task_type_run_in_flight(state: &AppState, task_type: &str) -> Result<bool> {
-    let count = sqlx::query_scalar::<_, i64>(
-        r#"
-        SELECT COUNT(*)
-        FROM job_tasks
-        WHERE task_type = ?
-          AND status IN (?, ?)
-        "#,
+    let task = enqueue_task(
+        state,
+        NewTask {
+            task_type: TASK_MAINTENANCE_BACKUP.to_owned(),
+            payload: json!({
+                "trigger": "schedule",
+                "schedule_key": schedule_key,
+            }),
+            source: "scheduler".to_owned(),
+            requested_by: None,
+            parent_task_id: None,
+            priority: TaskPriority::Low,
+        },
     )
-    .bind(task_type)
-    .bind(STATUS_QUEUED)
-    .bind(STATUS_RUNNING)
-    .fetch_one(&state.pool)
-    .await
-    .with_context(|| format!("failed to query in-flight {task_type} runs"))?;
-
-    Ok(count > 0)
-}
+    .await?;
 
-async fn upsert_dispatch_state(
-    state: &AppState,
-    schedule_name: &str,
-    schedule_key: &str,
-    task_id: &str,
-) -> Result<()> {
-    let now = Utc::now().to_rfc3339();
-    state
-        .sqlite_writer
-        .write("scheduled_task_dispatch_state_upsert", |_| async {
-            sqlx::query(
-                r#"
-                INSERT INTO scheduled_task_dispatch_state (
-                  schedule_name,
-                  last_dispatch_key,
-                  last_task_id,
-                  updated_at
-                ) VALUES (?, ?, ?, ?)
-                ON CONFLICT(schedule_name) DO UPDATE SET
-                  last_dispatch_key = excluded.last_dispatch_key,
-                  last_task_id = excluded.last_task_id,
-                  updated_at = excluded.updated_at
-                "#,
-            )
-            .bind(schedule_name)
-            .bind(schedule_key)
-            .bind(task_id)
-            .bind(now.as_str())
-            .execute(&state.pool)
-            .await
-            .context("failed to upsert subscription dispatch state")?;
-            Ok::<_, anyhow::Error>(())
-        })
-        .await?;
-    Ok(())
+    upsert_dispatch_state(
+        state,
+        MAINTENANCE_BACKUP_SCHEDULE_NAME,
+        &schedule_key,
+        &task.task_id,
+    )
+    .await?;
+    Ok(Some(task.task_id))
 }
 
-async fn update_daily_brief_hour_slot_dispatch(
+/// Dispatches a retry run once per interval, and only while the queue holds
+/// due entries.
+pub async fn enqueue_translation_retry_if_due(
     state: &AppState,
-    hour_utc: i64,
-    dispatch_at: &str,
-) -> Result<()> {
-    state
-        .sqlite_writer
-        .write("daily_brief_hour_slot_dispatch", |_| async {
-            sqlx::query(
-                r#"
-                UPDATE daily_brief_hour_slots
-                SET last_dispatch_at = ?, updated_at = ?
-                WHERE hour_utc = ?
-                "#,
-            )
-            .bind(dispatch_at)
-            .bind(dispatch_at)
-            .bind(hour_utc)
-            .execute(&state.pool)
-            .await
-            .context("failed to update slot last_dispatch_at")?;
-            Ok::<_, anyhow::Error>(())
-        })
-        .await?;
-    Ok(())
-}
+    now: DateTime<Utc>,
+) -> Result<Option<String>> {
+    let bucket_start = now
+        .timestamp()
+        .div_euclid(TRANSLATION_RETRY_INTERVAL_MINUTES * 60)
+        * TRANSLATION_RETRY_INTERVAL_MINUTES
+        * 60;
+    let schedule_key = format!("interval:{TRANSLATION_RETRY_INTERVAL_MINUTES}:{bucket_start}");
+    let row = sqlx::query_as::<_, DispatchStateRow>(
+        r#"
+        SELECT last_dispatch_key
+        FROM scheduled_task_dispatch_state
+        WHERE schedule_name = ?
+        LIMIT 1
+        "#,
+    )
+    .bind(TRANSLATION_RETRY_SCHEDULE_NAME)
+    .fetch_optional(&state.pool)
+    .await
+    .context("failed to query translation retry dispatch state")?;
 
-async fn mark_brief_generation_source(
-    state: &AppState,
-    brief_id: &str,
-    generation_source: &'static str,
-    updated_at: &str,
-    lane: &'static str,
-) -> Result<()> {
-    state
-        .sqlite_writer
-        .write(lane, |_| async {
-            sqlx::query(
-                r#"
-                UPDATE briefs
-                SET generation_source = ?,
-                    updated_at = ?
-                WHERE id = ?
-                "#,
-            )
-            .bind(generation_source)
-            .bind(updated_at)
-            .bind(brief_id)
-            .execute(&state.pool)
-            .await
-            .with_context(|| format!("failed to mark brief {brief_id} as {generation_source}"))?;
-            Ok::<_, anyhow::Error>(())
-        })
-        .await?;
-    Ok(())
-}
+    if row
+        .as_ref()
+        .and_then(|current| current.last_dispatch_key.as_deref())
+        == Some(schedule_key.as_str())
+    {
+        return Ok(None);
+    }
+    if !translation_retry::has_due_translation_retries(state, now).await? {
+        return Ok(None);
+    }
+    if task_type_run_in_flight(state, TASK_TRANSLATION_RETRY).await? {
+        return Ok(None);
+    }
 
-async fn record_skipped_subscription_run(state: &AppState, payload: Value) -> Result<EnqueuedTask> {
-    let schedule_key = payload
-        .get("schedule_key")
-        .and_then(Value::as_str)
-        .unwrap_or_default()
-        .to_owned();
-    let task = start_inline_task(
+    let task = enqueue_task(
         state,
         NewTask {
-            task_type: TASK_SYNC_SUBSCRIPTIONS.to_owned(),
-            payload,
+            task_type: TASK_TRANSLATION_RETRY.to_owned(),
+            payload: json!({
+                "trigger": "schedule",
+                "schedule_key": schedule_key,
+            }),
             source: "scheduler".to_owned(),
             requested_by: None,
             parent_task_id: None,
+            priority: TaskPriority::Low,
         },
     )
     .await?;
 
-    append_task_event(
+    upsert_dispatch_state(
         state,
+        TRANSLATION_RETRY_SCHEDULE_NAME,
+        &schedule_key,
         &task.task_id,
-        "task.progress",
-        json!({
-            "task_id": task.task_id,
-            "stage": "skipped",
-            "schedule_key": schedule_key,
-            "skip_reason": "previous_run_active",
-        }),
     )
     .await?;
-    append_task_log_entry(
-        state,
-        &task.task_id,
-        json!({
-            "at": Utc::now().to_rfc3339(),
-            "level": "warning",
-            "stage": "scheduler",
-            "event_type": "run_skipped",
-            "task_id": task.task_id,
-            "schedule_key": schedule_key,
-            "message": "subscription sync run skipped because a previous run is still active",
-            "skip_reason": "previous_run_active"
-        }),
+    Ok(Some(task.task_id))
+}
+
+/// Dispatches a purge run at most once per hour, and only while some pending
+/// purge is past its grace period.
+pub async fn enqueue_star_purge_if_due(
+    state: &AppState,
+    now: DateTime<Utc>,
+) -> Result<Option<String>> {
+    let schedule_key = format!("hour:{}", now.format("%Y-%m-%dT%H"));
+    let row = sqlx::query_as::<_, DispatchStateRow>(
+        r#"
+        SELECT last_dispatch_key
+        FROM scheduled_task_dispatch_state
+        WHERE schedule_name = ?
+        LIMIT 1
+        "#,
     )
-    .await?;
+    .bind(STAR_PURGE_SCHEDULE_NAME)
+    .fetch_optional(&state.pool)
+    .await
+    .context("failed to query star purge dispatch state")?;
 
-    complete_task(
+    if row
+        .as_ref()
+        .and_then(|current| current.last_dispatch_key.as_deref())
+        == Some(schedule_key.as_str())
+    {
+        return Ok(None);
+    }
+    if !star_purge::has_due_star_purges(state, now).await? {
+        return Ok(None);
+    }
+    if task_type_run_in_flight(state, TASK_MAINTENANCE_STAR_PURGE).await? {
+        return Ok(None);
+    }
+
+    let task = enqueue_task(
         state,
-        &task.task_id,
-        STATUS_SUCCEEDED,
-        Some(sync::skipped_subscription_result(
-            &schedule_key,
-            "previous_run_active",
-        )),
-        None,
+        NewTask {
+            task_type: TASK_MAINTENANCE_STAR_PURGE.to_owned(),
+            payload: json!({
+                "trigger": "schedule",
+                "schedule_key": schedule_key,
+            }),
+            source: "scheduler".to_owned(),
+            requested_by: None,
+            parent_task_id: None,
+            priority: TaskPriority::Low,
+        },
     )
     .await?;
-    append_task_event(
+
+    upsert_dispatch_state(
         state,
+        STAR_PURGE_SCHEDULE_NAME,
+        &schedule_key,
         &task.task_id,
-        "task.completed",
-        json!({
-            "task_id": task.task_id,
-            "status": STATUS_SUCCEEDED,
-            "skipped": true,
-        }),
     )
     .await?;
+    Ok(Some(task.task_id))
+}
 
-    Ok(task)
+#[derive(Debug, sqlx::FromRow)]
+struct UserSyncScheduleRow {
+    id: String,
+    sync_frequency_minutes: i64,
+    last_releases_at: Option<String>,
+    last_notifications_at: Option<String>,
 }
 
-async fn record_skipped_recent_failures_retry(
-    state: &AppState,
-    payload: Value,
-) -> Result<EnqueuedTask> {
+impl UserSyncScheduleRow {
+    fn last_synced_at(&self, task_type: &str) -> Option<DateTime<Utc>> {
+        let value = match task_type {
+            TASK_SYNC_RELEASES => self.last_releases_at.as_deref(),
+            TASK_SYNC_NOTIFICATIONS => self.last_notifications_at.as_deref(),
+            _ => None,
+        }?;
+        DateTime::parse_from_rfc3339(value)
+            .ok()
+            .map(|value| value.with_timezone(&Utc))
+    }
+}
+
+/// Stable per-user offset in `[0, frequency / USER_SYNC_JITTER_DIVISOR)` so
+/// users who synced together drift apart instead of hitting GitHub in the
+/// same scheduler tick.
+pub(crate) fn user_sync_jitter(user_id: &str, frequency_minutes: i64) -> chrono::Duration {
+    let window_secs = frequency_minutes.max(0) * 60 / USER_SYNC_JITTER_DIVISOR;
+    if window_secs <= 0 {
+        return chrono::Duration::zero();
+    }
+    let digest = ai::sha256_hex(user_id);
+    let seed = u64::from_str_radix(&digest[..12], 16).unwrap_or_default();
+    chrono::Duration::seconds((seed % window_secs as u64) as i64)
+}
+
+/// When the next scheduled sync is due. `None` means scheduling is off;
+/// a user who never synced is due right away.
+pub(crate) fn user_sync_due_at(
+    user_id: &str,
+    frequency_minutes: i64,
+    last_synced_at: Option<DateTime<Utc>>,
+) -> Option<DateTime<Utc>> {
+    if frequency_minutes <= 0 {
+        return None;
+    }
+    Some(match last_synced_at {
+        Some(last) => {
+            last + chrono::Duration::minutes(frequency_minutes)
+                + user_sync_jitter(user_id, frequency_minutes)
+        }
+        None => DateTime::<Utc>::MIN_UTC,
+    })
+}
+
+/// Loads users with scheduled sync enabled together with the last time each
+/// sync type finished. Successful runs count, and so do failed runs started
+/// by this scheduler, so a user whose GitHub token is broken is retried once
+/// per period instead of every tick. `sync.all` covers both types.
+async fn load_user_sync_schedule_rows(state: &AppState) -> Result<Vec<UserSyncScheduleRow>> {
+    sqlx::query_as::<_, UserSyncScheduleRow>(
+        r#"
+        WITH finished AS (
+          SELECT
+            json_extract(payload_json, '$.user_id') AS user_id,
+            task_type,
+            MAX(finished_at) AS finished_at
+          FROM job_tasks
+          WHERE task_type IN (?, ?, ?)
+            AND finished_at IS NOT NULL
+            AND (status = ? OR (status = ? AND source = ?))
+          GROUP BY 1, 2
+        )
+        SELECT
+          u.id,
+          u.sync_frequency_minutes,
+          (
+            SELECT MAX(f.finished_at)
+            FROM finished f
+            WHERE f.user_id = u.id AND f.task_type IN (?, ?)
+          ) AS last_releases_at,
+          (
+            SELECT MAX(f.finished_at)
+            FROM finished f
+            WHERE f.user_id = u.id AND f.task_type IN (?, ?)
+          ) AS last_notifications_at
+        FROM users u
+        WHERE u.sync_frequency_minutes > 0
+          AND u.is_disabled = 0
+        ORDER BY u.id
+        "#,
+    )
+    .bind(TASK_SYNC_RELEASES)
+    .bind(TASK_SYNC_NOTIFICATIONS)
+    .bind(TASK_SYNC_ALL)
+    .bind(STATUS_SUCCEEDED)
+    .bind(STATUS_FAILED)
+    .bind(USER_SYNC_SCHEDULER_SOURCE)
+    .bind(TASK_SYNC_RELEASES)
+    .bind(TASK_SYNC_ALL)
+    .bind(TASK_SYNC_NOTIFICATIONS)
+    .bind(TASK_SYNC_ALL)
+    .fetch_all(&state.pool)
+    .await
+    .context("failed to load user sync schedule")
+}
+
+/// True when a sync of `task_type` (or a `sync.all`) for the user is already
+/// queued or running, whoever started it.
+async fn user_sync_in_flight(state: &AppState, task_type: &str, user_id: &str) -> Result<bool> {
+    let count = sqlx::query_scalar::<_, i64>(
+        r#"
+        SELECT COUNT(*)
+        FROM job_tasks
+        WHERE task_type IN (?, ?)
+          AND status IN (?, ?)
+          AND json_extract(payload_json, '$.user_id') = ?
+        "#,
+    )
+    .bind(task_type)
+    .bind(TASK_SYNC_ALL)
+    .bind(STATUS_QUEUED)
+    .bind(STATUS_RUNNING)
+    .bind(user_id)
+    .fetch_one(&state.pool)
+    .await
+    .with_context(|| format!("failed to query in-flight {task_type} for user"))?;
+
+    Ok(count > 0)
+}
+
+/// Enqueues release and notification syncs for users whose frequency has
+/// elapsed, skipping any type that is already queued or running for them.
+pub async fn enqueue_due_user_syncs(state: &AppState, now: DateTime<Utc>) -> Result<Vec<String>> {
+    let rows = load_user_sync_schedule_rows(state).await?;
+    let mut task_ids = Vec::new();
+    for row in rows {
+        for task_type in USER_SYNC_TASK_TYPES {
+            let due_at = user_sync_due_at(
+                &row.id,
+                row.sync_frequency_minutes,
+                row.last_synced_at(task_type),
+            );
+            if due_at.is_none_or(|due_at| due_at > now) {
+                continue;
+            }
+
+            let _guard = task_singleton_enqueue_lock().lock().await;
+            if user_sync_in_flight(state, task_type, &row.id).await? {
+                continue;
+            }
+            let task = enqueue_task(
+                state,
+                NewTask {
+                    task_type: (*task_type).to_owned(),
+                    payload: json!({
+                        "user_id": row.id,
+                        "trigger": "schedule",
+                        "sync_frequency_minutes": row.sync_frequency_minutes,
+                    }),
+                    source: USER_SYNC_SCHEDULER_SOURCE.to_owned(),
+                    // Lets a manual sync from the same user join this run.
+                    requested_by: Some(row.id.clone()),
+                    parent_task_id: None,
+                    priority: TaskPriority::Normal,
+                },
+            )
+            .await?;
+            task_ids.push(task.task_id);
+        }
+    }
+    Ok(task_ids)
+}
+
+/// Users with at least one scheduled sync type due before `until`.
+pub async fn count_users_due_for_sync(state: &AppState, until: DateTime<Utc>) -> Result<i64> {
+    let rows = load_user_sync_schedule_rows(state).await?;
+    let due = rows
+        .iter()
+        .filter(|row| {
+            USER_SYNC_TASK_TYPES.iter().any(|task_type| {
+                user_sync_due_at(
+                    &row.id,
+                    row.sync_frequency_minutes,
+                    row.last_synced_at(task_type),
+                )
+                .is_some_and(|due_at| due_at <= until)
+            })
+        })
+        .count();
+    Ok(i64::try_from(due).unwrap_or(i64::MAX))
+}
+
+#[derive(Debug, Clone, Default, Serialize, PartialEq, Eq)]
+pub struct PruneStats {
+    pub task_retention_days: i64,
+    pub llm_payload_retention_days: i64,
+    pub deleted_tasks: u64,
+    pub deleted_task_events: u64,
+    pub removed_log_files: u64,
+    pub truncated_llm_calls: u64,
+    pub archived_notifications: u64,
+    pub deleted_notifications: u64,
+    pub deleted_notification_translations: u64,
+}
+
+/// Deletes finished tasks past `task_retention_days` (events cascade with them)
+/// and drops the prompt/response bodies of old LLM calls while keeping their
+/// metrics. Queued and running rows are never touched. Notifications are
+/// archived and expired in the same run.
+pub async fn prune_task_history(state: &AppState, now: DateTime<Utc>) -> Result<PruneStats> {
+    let task_retention_days = i64::try_from(state.config.task_retention_days)
+        .context("task retention days out of range")?;
+    let task_cutoff = (now - chrono::Duration::days(task_retention_days)).to_rfc3339();
+    let llm_cutoff = (now - chrono::Duration::days(LLM_CALL_PAYLOAD_RETENTION_DAYS)).to_rfc3339();
+    let mut stats = PruneStats {
+        task_retention_days,
+        llm_payload_retention_days: LLM_CALL_PAYLOAD_RETENTION_DAYS,
+        ..PruneStats::default()
+    };
+
+    loop {
+        let rows = sqlx::query_as::<_, (String, Option<String>)>(
+            r#"
+            SELECT id, log_file_path
+            FROM job_tasks
+            WHERE status IN (?, ?, ?)
+              AND julianday(COALESCE(finished_at, updated_at, created_at)) < julianday(?)
+            ORDER BY created_at ASC, id ASC
+            LIMIT ?
+            "#,
+        )
+        .bind(STATUS_SUCCEEDED)
+        .bind(STATUS_FAILED)
+        .bind(STATUS_CANCELED)
+        .bind(task_cutoff.as_str())
+        .bind(MAINTENANCE_PRUNE_BATCH_SIZE)
+        .fetch_all(&state.pool)
+        .await
+        .context("failed to query expired job tasks")?;
+        if rows.is_empty() {
+            break;
+        }
+
+        let task_ids = rows.iter().map(|(id, _)| id.clone()).collect::<Vec<_>>();
+        let (deleted_tasks, deleted_events) = state
+            .sqlite_writer
+            .write("job_tasks_prune", |_| {
+                let task_ids = task_ids.clone();
+                async move {
+                    let placeholders = vec!["?"; task_ids.len()].join(", ");
+                    let mut tx = state.pool.begin().await?;
+                    let events_sql =
+                        format!("DELETE FROM job_task_events WHERE task_id IN ({placeholders})");
+                    let mut events_query = sqlx::query(&events_sql);
+                    for task_id in &task_ids {
+                        events_query = events_query.bind(task_id);
+                    }
+                    let deleted_events = events_query.execute(&mut *tx).await?.rows_affected();
+                    let tasks_sql = format!("DELETE FROM job_tasks WHERE id IN ({placeholders})");
+                    let mut tasks_query = sqlx::query(&tasks_sql);
+                    for task_id in &task_ids {
+                        tasks_query = tasks_query.bind(task_id);
+                    }
+                    let deleted_tasks = tasks_query.execute(&mut *tx).await?.rows_affected();
+                    tx.commit().await?;
+                    Ok::<_, anyhow::Error>((deleted_tasks, deleted_events))
+                }
+            })
+            .await
+            .context("failed to delete expired job tasks")?;
+        stats.deleted_tasks += deleted_tasks;
+        stats.deleted_task_events += deleted_events;
+
+        for log_file_path in rows.iter().filter_map(|(_, path)| path.as_deref()) {
+            match tokio::fs::remove_file(log_file_path).await {
+                Ok(()) => stats.removed_log_files += 1,
+                Err(err) if err.kind() == std::io::ErrorKind::NotFound => {}
+                Err(err) => {
+                    tracing::warn!(
+                        ?err,
+                        log_file_path,
+                        "maintenance prune: remove task log failed"
+                    );
+                }
+            }
+        }
+        if (rows.len() as i64) < MAINTENANCE_PRUNE_BATCH_SIZE {
+            break;
+        }
+    }
+
+    loop {
+        let truncated = state
+            .sqlite_writer
+            .write("llm_calls_prune_payloads", |_| async {
+                let result = sqlx::query(
+                    r#"
+                    UPDATE llm_calls
+                    SET prompt_text = '',
+                        response_text = NULL,
+                        input_messages_json = NULL,
+                        output_messages_json = NULL
+                    WHERE id IN (
+                      SELECT id
+                      FROM llm_calls
+                      WHERE status IN (?, ?)
+                        AND julianday(created_at) < julianday(?)
+                        AND (
+                          prompt_text <> ''
+                          OR response_text IS NOT NULL
+                          OR input_messages_json IS NOT NULL
+                          OR output_messages_json IS NOT NULL
+                        )
+                      LIMIT ?
+                    )
+                    "#,
+                )
+                .bind(STATUS_SUCCEEDED)
+                .bind(STATUS_FAILED)
+                .bind(llm_cutoff.as_str())
+                .bind(MAINTENANCE_PRUNE_BATCH_SIZE)
+                .execute(&state.pool)
+                .await?;
+                Ok::<_, anyhow::Error>(result.rows_affected())
+            })
+            .await
+            .context("failed to truncate expired llm call payloads")?;
+        stats.truncated_llm_calls += truncated;
+        if (truncated as i64) < MAINTENANCE_PRUNE_BATCH_SIZE {
+            break;
+        }
+    }
+
+    let notifications = notification_archive::prune_notifications(state, now).await?;
+    stats.archived_notifications = notifications.archived_notifications;
+    stats.deleted_notifications = notifications.deleted_notifications;
+    stats.deleted_notification_translations = notifications.deleted_translations;
+
+    Ok(stats)
+}
+
+/// Runs a prune immediately as an inline task so manual runs show up in the
+/// task history (and in the overview's last prune time) like scheduled ones.
+pub async fn run_maintenance_prune_now(
+    state: &AppState,
+    requested_by: Option<String>,
+) -> Result<(String, PruneStats)> {
+    let task = start_inline_task(
+        state,
+        NewTask {
+            task_type: TASK_MAINTENANCE_PRUNE.to_owned(),
+            payload: json!({ "trigger": "manual" }),
+            source: "api.admin_maintenance_prune".to_owned(),
+            requested_by,
+            parent_task_id: None,
+            priority: TaskPriority::Low,
+        },
+    )
+    .await?;
+
+    match prune_task_history(state, Utc::now()).await {
+        Ok(stats) => {
+            complete_task(
+                state,
+                &task.task_id,
+                STATUS_SUCCEEDED,
+                Some(serde_json::to_value(&stats).unwrap_or_else(|_| json!({"ok": true}))),
+                None,
+            )
+            .await?;
+            Ok((task.task_id, stats))
+        }
+        Err(err) => {
+            complete_task(
+                state,
+                &task.task_id,
+                STATUS_FAILED,
+                None,
+                Some(format!("{err:#}")),
+            )
+            .await?;
+            Err(err)
+        }
+    }
+}
+
+pub async fn load_last_maintenance_prune_at(state: &AppState) -> Result<Option<String>> {
+    let finished_at = sqlx::query_scalar::<_, Option<String>>(
+        r#"
+        SELECT finished_at
+        FROM job_tasks
+        WHERE task_type = ?
+          AND status = ?
+        ORDER BY finished_at DESC
+        LIMIT 1
+        "#,
+    )
+    .bind(TASK_MAINTENANCE_PRUNE)
+    .bind(STATUS_SUCCEEDED)
+    .fetch_optional(&state.pool)
+    .await
+    .context("failed to query last maintenance prune")?;
+    Ok(finished_at.flatten())
+}
+
+pub async fn enqueue_brief_history_recompute_if_needed(state: &AppState) -> Result<Option<String>> {
+    if ai::legacy_brief_count(state).await? == 0 {
+        return Ok(None);
+    }
+
+    if let Some(existing) = find_inflight_task_by_type(state, TASK_BRIEF_HISTORY_RECOMPUTE).await? {
+        return Ok(Some(existing.task_id));
+    }
+
+    let task = enqueue_task(
+        state,
+        NewTask {
+            task_type: TASK_BRIEF_HISTORY_RECOMPUTE.to_owned(),
+            payload: json!({}),
+            source: "migration.bootstrap".to_owned(),
+            requested_by: None,
+            parent_task_id: None,
+            priority: TaskPriority::Low,
+        },
+    )
+    .await?;
+
+    Ok(Some(task.task_id))
+}
+
+pub async fn enqueue_brief_refresh_content_if_needed(state: &AppState) -> Result<Option<String>> {
+    if ai::brief_content_refresh_candidate_count(state).await? == 0 {
+        return Ok(None);
+    }
+
+    if let Some(existing) = find_inflight_task_by_type(state, TASK_BRIEF_REFRESH_CONTENT).await? {
+        return Ok(Some(existing.task_id));
+    }
+
+    let task = enqueue_task(
+        state,
+        NewTask {
+            task_type: TASK_BRIEF_REFRESH_CONTENT.to_owned(),
+            payload: json!({}),
+            source: "migration.bootstrap".to_owned(),
+            requested_by: None,
+            parent_task_id: None,
+            priority: TaskPriority::Low,
+        },
+    )
+    .await?;
+
+    Ok(Some(task.task_id))
+}
+
+pub(crate) fn current_subscription_schedule_key(
+    now: DateTime<Utc>,
+    interval_minutes: i64,
+) -> String {
+    let interval_minutes =
+        admin_runtime::normalize_sync_auto_fetch_interval_minutes(interval_minutes);
+    let bucket_start = now.timestamp().div_euclid(interval_minutes * 60) * interval_minutes * 60;
+    format!("interval:{interval_minutes}:{bucket_start}")
+}
+
+pub(crate) fn current_recent_failures_retry_schedule_key(
+    now: DateTime<Utc>,
+    interval_minutes: i64,
+) -> String {
+    let interval_minutes =
+        admin_runtime::normalize_retry_recent_failures_interval_minutes(interval_minutes);
+    let bucket_start = now.timestamp().div_euclid(interval_minutes * 60) * interval_minutes * 60;
+    format!("interval:{interval_minutes}:{bucket_start}")
+}
+
+async fn subscription_run_in_flight(state: &AppState) -> Result<bool> {
+    let count = sqlx::query_scalar::<_, i64>(
+        r#"
+        SELECT COUNT(*)
+        FROM job_tasks
+        WHERE task_type = ?
+          AND status IN (?, ?)
+        "#,
+    )
+    .bind(TASK_SYNC_SUBSCRIPTIONS)
+    .bind(STATUS_QUEUED)
+    .bind(STATUS_RUNNING)
+    .fetch_one(&state.pool)
+    .await
+    .context("failed to query in-flight subscription runs")?;
+
+    Ok(count > 0)
+}
+
+async fn task_type_run_in_flight(state: &AppState, task_type: &str) -> Result<bool> {
+    let count = sqlx::query_scalar::<_, i64>(
+        r#"
+        SELECT COUNT(*)
+        FROM job_tasks
+        WHERE task_type = ?
+          AND status IN (?, ?)
+        "#,
+    )
+    .bind(task_type)
+    .bind(STATUS_QUEUED)
+    .bind(STATUS_RUNNING)
+    .fetch_one(&state.pool)
+    .await
+    .with_context(|| format!("failed to query in-flight {task_type} runs"))?;
+
+    Ok(count > 0)
+}
+
+async fn upsert_dispatch_state(
+    state: &AppState,
+    schedule_name: &str,
+    schedule_key: &str,
+    task_id: &str,
+) -> Result<()> {
+    let now = Utc::now().to_rfc3339();
+    state
+        .sqlite_writer
+        .write("scheduled_task_dispatch_state_upsert", |_| async {
+            sqlx::query(
+                r#"
+                INSERT INTO scheduled_task_dispatch_state (
+                  schedule_name,
+                  last_dispatch_key,
+                  last_task_id,
+                  updated_at
+                ) VALUES (?, ?, ?, ?)
+                ON CONFLICT(schedule_name) DO UPDATE SET
+                  last_dispatch_key = excluded.last_dispatch_key,
+                  last_task_id = excluded.last_task_id,
+                  updated_at = excluded.updated_at
+                "#,
+            )
+            .bind(schedule_name)
+            .bind(schedule_key)
+            .bind(task_id)
+            .bind(now.as_str())
+            .execute(&state.pool)
+            .await
+            .context("failed to upsert subscription dispatch state")?;
+            Ok::<_, anyhow::Error>(())
+        })
+        .await?;
+    Ok(())
+}
+
+async fn update_daily_brief_hour_slot_dispatch(
+    state: &AppState,
+    hour_utc: i64,
+    dispatch_at: &str,
+) -> Result<()> {
+    state
+        .sqlite_writer
+        .write("daily_brief_hour_slot_dispatch", |_| async {
+            sqlx::query(
+                r#"
+                UPDATE daily_brief_hour_slots
+                SET last_dispatch_at = ?, updated_at = ?
+                WHERE hour_utc = ?
+                "#,
+            )
+            .bind(dispatch_at)
+            .bind(dispatch_at)
+            .bind(hour_utc)
+            .execute(&state.pool)
+            .await
+            .context("failed to update slot last_dispatch_at")?;
+            Ok::<_, anyhow::Error>(())
+        })
+        .await?;
+    Ok(())
+}
+
+async fn mark_brief_generation_source(
+    state: &AppState,
+    brief_id: &str,
+    generation_source: &'static str,
+    updated_at: &str,
+    lane: &'static str,
+) -> Result<()> {
+    state
+        .sqlite_writer
+        .write(lane, |_| async {
+            sqlx::query(
+                r#"
+                UPDATE briefs
+                SET generation_source = ?,
+                    updated_at = ?
+                WHERE id = ?
+                "#,
+            )
+            .bind(generation_source)
+            .bind(updated_at)
+            .bind(brief_id)
+            .execute(&state.pool)
+            .await
+            .with_context(|| format!("failed to mark brief {brief_id} as {generation_source}"))?;
+            Ok::<_, anyhow::Error>(())
+        })
+        .await?;
+    Ok(())
+}
+
+async fn record_skipped_subscription_run(state: &AppState, payload: Value) -> Result<EnqueuedTask> {
+    let schedule_key = payload
+        .get("schedule_key")
+        .and_then(Value::as_str)
+        .unwrap_or_default()
+        .to_owned();
+    let task = start_inline_task(
+        state,
+        NewTask {
+            task_type: TASK_SYNC_SUBSCRIPTIONS.to_owned(),
+            payload,
+            source: "scheduler".to_owned(),
+            requested_by: None,
+            parent_task_id: None,
+            priority: TaskPriority::Normal,
+        },
+    )
+    .await?;
+
+    append_task_event(
+        state,
+        &task.task_id,
+        "task.progress",
+        json!({
+            "task_id": task.task_id,
+            "stage": "skipped",
+            "schedule_key": schedule_key,
+            "skip_reason": "previous_run_active",
+        }),
+    )
+    .await?;
+    append_task_log_entry(
+        state,
+        &task.task_id,
+        json!({
+            "at": Utc::now().to_rfc3339(),
+            "level": "warning",
+            "stage": "scheduler",
+            "event_type": "run_skipped",
+            "task_id": task.task_id,
+            "schedule_key": schedule_key,
+            "message": "subscription sync run skipped because a previous run is still active",
+            "skip_reason": "previous_run_active"
+        }),
+    )
+    .await?;
+
+    complete_task(
+        state,
+        &task.task_id,
+        STATUS_SUCCEEDED,
+        Some(sync::skipped_subscription_result(
+            &schedule_key,
+            "previous_run_active",
+        )),
+        None,
+    )
+    .await?;
+    append_task_event(
+        state,
+        &task.task_id,
+        "task.completed",
+        json!({
+            "task_id": task.task_id,
+            "status": STATUS_SUCCEEDED,
+            "skipped": true,
+        }),
+    )
+    .await?;
+
+    Ok(task)
+}
+
+async fn record_skipped_recent_failures_retry(
+    state: &AppState,
+    payload: Value,
+) -> Result<EnqueuedTask> {
     let schedule_key = payload
         .get("schedule_key")
         .and_then(Value::as_str)
@@ -718,6 +1793,7 @@ async fn record_skipped_recent_failures_retry(
             source: "scheduler".to_owned(),
             requested_by: None,
             parent_task_id: None,
+            priority: TaskPriority::Normal,
         },
     )
     .await?;
@@ -844,6 +1920,7 @@ async fn insert_task_record(
     let now = Utc::now().to_rfc3339();
     let payload_json = serde_json::to_string(&new_task.payload).context("serialize payload")?;
     let log_file_path = build_task_log_path(state, &new_task.task_type, &task_id)?;
+    let request_id = observability::current_request_id();
 
     state
         .sqlite_writer
@@ -863,8 +1940,10 @@ async fn insert_task_record(
                   started_at,
                   runtime_owner_id,
                   lease_heartbeat_at,
-                  updated_at
-                ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+                  updated_at,
+                  priority,
+                  request_id
+                ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
                 "#,
             )
             .bind(&task_id)
@@ -880,17 +1959,39 @@ async fn insert_task_record(
             .bind(runtime_owner_id)
             .bind(lease_heartbeat_at)
             .bind(now.as_str())
+            .bind(new_task.priority.as_str())
+            .bind(request_id.as_deref())
             .execute(&state.pool)
             .await
             .context("failed to insert job task")?;
             Ok::<_, anyhow::Error>(())
         })
         .await?;
+    observability::record_job_task_transition(&new_task.task_type, status);
 
     Ok(task_id)
 }
 
-pub async fn enqueue_task(state: &AppState, new_task: NewTask) -> Result<EnqueuedTask> {
+/// Failures of the task actions exposed to admins, typed so the HTTP layer
+/// does not have to match on message wording.
+#[derive(Debug, thiserror::Error)]
+pub enum JobError {
+    #[error("task not found")]
+    NotFound,
+    #[error("{reason}")]
+    InvalidState { reason: &'static str },
+    /// The task changed state between our writes and the final read, e.g. a
+    /// worker claimed or requeued it concurrently.
+    #[error("task changed state concurrently")]
+    Conflict,
+    #[error(transparent)]
+    Internal(#[from] anyhow::Error),
+}
+
+pub async fn enqueue_task(
+    state: &AppState,
+    new_task: NewTask,
+) -> std::result::Result<EnqueuedTask, JobError> {
     let task_id = insert_task_record(state, &new_task, STATUS_QUEUED, None, None, None).await?;
 
     append_task_event(
@@ -997,7 +2098,7 @@ pub async fn enqueue_singleton_task_for_requester(
     {
         return Ok(existing);
     }
-    enqueue_task(state, new_task).await
+    Ok(enqueue_task(state, new_task).await?)
 }
 
 pub async fn start_inline_task(state: &AppState, new_task: NewTask) -> Result<EnqueuedTask> {
@@ -1071,17 +2172,18 @@ pub async fn retry_task(
     state: &AppState,
     task_id: &str,
     requested_by: String,
-) -> Result<EnqueuedTask> {
+) -> std::result::Result<EnqueuedTask, JobError> {
     #[derive(Debug, sqlx::FromRow)]
     struct RetrySourceRow {
         task_type: String,
         payload_json: String,
         status: String,
+        priority: String,
     }
 
     let source = sqlx::query_as::<_, RetrySourceRow>(
         r#"
-        SELECT task_type, payload_json, status
+        SELECT task_type, payload_json, status, priority
         FROM job_tasks
         WHERE id = ?
         LIMIT 1
@@ -1091,10 +2193,12 @@ pub async fn retry_task(
     .fetch_optional(&state.pool)
     .await
     .context("failed to load retry source task")?
-    .ok_or_else(|| anyhow!("task not found"))?;
+    .ok_or(JobError::NotFound)?;
 
     if source.status == STATUS_RUNNING || source.status == STATUS_QUEUED {
-        return Err(anyhow!("only finished tasks can be retried"));
+        return Err(JobError::InvalidState {
+            reason: "only finished tasks can be retried",
+        });
     }
 
     let payload: Value =
@@ -1107,6 +2211,7 @@ pub async fn retry_task(
             source: "retry".to_owned(),
             requested_by: Some(requested_by),
             parent_task_id: Some(task_id.to_owned()),
+            priority: TaskPriority::parse(&source.priority).unwrap_or(TaskPriority::Normal),
         },
     )
     .await?;
@@ -1114,17 +2219,18 @@ pub async fn retry_task(
     Ok(new_task)
 }
 
-pub async fn cancel_task(state: &AppState, task_id: &str) -> Result<String> {
+pub async fn cancel_task(state: &AppState, task_id: &str) -> std::result::Result<String, JobError> {
     let now = Utc::now().to_rfc3339();
 
     let canceled_queued = state
         .sqlite_writer
         .write_foreground("job_task_cancel_queued", |_| async {
-            sqlx::query(
+            sqlx::query_scalar::<_, String>(
                 r#"
                     UPDATE job_tasks
                     SET status = ?, cancel_requested = 1, finished_at = ?, updated_at = ?
                     WHERE id = ? AND status = ?
+                    RETURNING task_type
                     "#,
             )
             .bind(STATUS_CANCELED)
@@ -1132,13 +2238,14 @@ pub async fn cancel_task(state: &AppState, task_id: &str) -> Result<String> {
             .bind(now.as_str())
             .bind(task_id)
             .bind(STATUS_QUEUED)
-            .execute(&state.pool)
+            .fetch_optional(&state.pool)
             .await
             .context("failed to cancel queued task")
         })
         .await?;
 
-    if canceled_queued.rows_affected() > 0 {
+    if let Some(task_type) = canceled_queued {
+        observability::record_job_task_transition(&task_type, STATUS_CANCELED);
         append_task_event(
             state,
             task_id,
@@ -1169,6 +2276,7 @@ pub async fn cancel_task(state: &AppState, task_id: &str) -> Result<String> {
         .await?;
 
     if requested_running.rows_affected() > 0 {
+        signal_task_cancellation(task_id);
         append_task_event(
             state,
             task_id,
@@ -1186,7 +2294,13 @@ pub async fn cancel_task(state: &AppState, task_id: &str) -> Result<String> {
             .await
             .context("failed to query task status")?;
 
-    existing.ok_or_else(|| anyhow!("task not found"))
+    match existing {
+        None => Err(JobError::NotFound),
+        Some(status) if status == STATUS_QUEUED || status == STATUS_RUNNING => {
+            Err(JobError::Conflict)
+        }
+        Some(status) => Ok(status),
+    }
 }
 
 pub async fn append_task_event(
@@ -1222,21 +2336,74 @@ pub async fn append_task_event(
     Ok(())
 }
 
-pub fn task_sse_response(state: Arc<AppState>, task_id: String) -> Response {
+// Reconnect delay suggested to EventSource clients.
+const SSE_RETRY_HINT: Duration = Duration::from_secs(3);
+// A resumed stream replays at most this many events, none older than the max age.
+const SSE_REPLAY_MAX_EVENTS: i64 = 1000;
+const SSE_REPLAY_MAX_AGE_SECS: i64 = 60 * 60;
+
+/// The `Last-Event-ID` header EventSource sends when it reconnects.
+pub fn last_event_id(headers: &axum::http::HeaderMap) -> Option<&str> {
+    headers
+        .get("last-event-id")
+        .and_then(|value| value.to_str().ok())
+        .map(str::trim)
+        .filter(|value| !value.is_empty())
+}
+
+/// Where a resumed stream over `table` continues: right after `last_seen_seq`,
+/// but never further back than the replay window and never past the newest row.
+async fn resume_event_seq(pool: &sqlx::SqlitePool, table: &str, last_seen_seq: i64) -> i64 {
+    let max_seq =
+        sqlx::query_scalar::<_, i64>(&format!("SELECT COALESCE(MAX(rowid), 0) FROM {table}"))
+            .fetch_one(pool)
+            .await
+            .unwrap_or(0);
+    let count_floor = max_seq.saturating_sub(SSE_REPLAY_MAX_EVENTS).max(0);
+    let cutoff = (Utc::now() - chrono::Duration::seconds(SSE_REPLAY_MAX_AGE_SECS)).to_rfc3339();
+    let age_floor = sqlx::query_scalar::<_, i64>(&format!(
+        "SELECT rowid - 1 FROM {table} WHERE rowid > ? AND created_at >= ? ORDER BY rowid ASC LIMIT 1"
+    ))
+    .bind(count_floor)
+    .bind(cutoff.as_str())
+    .fetch_optional(pool)
+    .await
+    .ok()
+    .flatten()
+    .unwrap_or(max_seq);
+    last_seen_seq.max(count_floor).max(age_floor).min(max_seq)
+}
+
+/// Final frame sent before a stream closes for server shutdown; clients
+/// reconnect with `Last-Event-ID` once the server is back.
+fn shutdown_sse_event() -> Event {
+    Event::default()
+        .event(runtime::SSE_SHUTDOWN_EVENT)
+        .data(json!({"reason": TASK_SERVER_SHUTDOWN_ERROR}).to_string())
+}
+
+/// Streams a task's events. Event ids are the event row sequence, so a
+/// reconnect with `Last-Event-ID` picks up after the last delivered event.
+pub fn task_sse_response(
+    state: Arc<AppState>,
+    task_id: String,
+    last_event_id: Option<&str>,
+) -> Response {
+    let resume_after = last_event_id.and_then(|value| value.parse::<i64>().ok());
     let events = stream! {
-        let mut last_event_seq = 0_i64;
+        let mut last_event_seq = resume_after.unwrap_or(0).max(0);
+        yield Ok::<Event, Infallible>(Event::default().retry(SSE_RETRY_HINT).comment("stream-ready"));
         loop {
             #[derive(Debug, sqlx::FromRow)]
             struct EventRow {
                 seq: i64,
-                id: String,
                 event_type: String,
                 payload_json: String,
             }
 
             let rows = sqlx::query_as::<_, EventRow>(
                 r#"
-                SELECT rowid AS seq, id, event_type, payload_json
+                SELECT rowid AS seq, event_type, payload_json
                 FROM job_task_events
                 WHERE task_id = ? AND rowid > ?
                 ORDER BY rowid ASC
@@ -1253,7 +2420,7 @@ pub fn task_sse_response(state: Arc<AppState>, task_id: String) -> Response {
                 last_event_seq = row.seq;
                 yield Ok::<Event, Infallible>(
                     Event::default()
-                        .id(row.id)
+                        .id(row.seq.to_string())
                         .event(row.event_type)
                         .data(row.payload_json),
                 );
@@ -1276,7 +2443,7 @@ pub fn task_sse_response(state: Arc<AppState>, task_id: String) -> Response {
                 tokio::time::sleep(Duration::from_millis(120)).await;
                 let rows = sqlx::query_as::<_, EventRow>(
                     r#"
-                    SELECT rowid AS seq, id, event_type, payload_json
+                    SELECT rowid AS seq, event_type, payload_json
                     FROM job_task_events
                     WHERE task_id = ? AND rowid > ?
                     ORDER BY rowid ASC
@@ -1292,7 +2459,7 @@ pub fn task_sse_response(state: Arc<AppState>, task_id: String) -> Response {
                 for row in rows {
                     yield Ok::<Event, Infallible>(
                         Event::default()
-                            .id(row.id)
+                            .id(row.seq.to_string())
                             .event(row.event_type)
                             .data(row.payload_json),
                     );
@@ -1300,7 +2467,14 @@ pub fn task_sse_response(state: Arc<AppState>, task_id: String) -> Response {
                 break;
             }
 
-            tokio::time::sleep(Duration::from_millis(500)).await;
+            let shutting_down = tokio::select! {
+                _ = runtime::shutdown_token().cancelled() => true,
+                _ = tokio::time::sleep(Duration::from_millis(500)) => false,
+            };
+            if shutting_down {
+                yield Ok::<Event, Infallible>(shutdown_sse_event());
+                break;
+            }
         }
     };
 
@@ -1437,7 +2611,23 @@ async fn load_translation_stream_rows(
         .await
 }
 
-pub fn admin_jobs_sse_response(state: Arc<AppState>) -> Response {
+/// Every admin stream event carries `<job event seq>:<llm event seq>` as its
+/// SSE id, so whichever event a client saw last, a reconnect resumes both
+/// event logs right after it.
+fn admin_stream_event_id(job_event_seq: i64, llm_event_seq: i64) -> String {
+    format!("{job_event_seq}:{llm_event_seq}")
+}
+
+fn parse_admin_stream_event_id(raw: &str) -> Option<(i64, i64)> {
+    let (job_event_seq, llm_event_seq) = raw.split_once(':')?;
+    Some((
+        job_event_seq.trim().parse().ok()?,
+        llm_event_seq.trim().parse().ok()?,
+    ))
+}
+
+pub fn admin_jobs_sse_response(state: Arc<AppState>, last_event_id: Option<&str>) -> Response {
+    let resume_from = last_event_id.and_then(parse_admin_stream_event_id);
     let events = stream! {
         #[derive(Debug, sqlx::FromRow)]
         struct EventRow {
@@ -1463,18 +2653,26 @@ pub fn admin_jobs_sse_response(state: Arc<AppState>) -> Response {
             created_at: String,
         }
 
-        let mut last_job_event_seq = sqlx::query_scalar::<_, i64>(
-            r#"SELECT COALESCE(MAX(rowid), 0) FROM job_task_events"#,
-        )
-        .fetch_one(&state.pool)
-        .await
-        .unwrap_or(0);
-        let mut last_llm_event_seq = sqlx::query_scalar::<_, i64>(
-            r#"SELECT COALESCE(MAX(rowid), 0) FROM llm_call_events"#,
-        )
-        .fetch_one(&state.pool)
-        .await
-        .unwrap_or(0);
+        let (mut last_job_event_seq, mut last_llm_event_seq) = match resume_from {
+            Some((job_event_seq, llm_event_seq)) => (
+                resume_event_seq(&state.pool, "job_task_events", job_event_seq).await,
+                resume_event_seq(&state.pool, "llm_call_events", llm_event_seq).await,
+            ),
+            None => (
+                sqlx::query_scalar::<_, i64>(
+                    r#"SELECT COALESCE(MAX(rowid), 0) FROM job_task_events"#,
+                )
+                .fetch_one(&state.pool)
+                .await
+                .unwrap_or(0),
+                sqlx::query_scalar::<_, i64>(
+                    r#"SELECT COALESCE(MAX(rowid), 0) FROM llm_call_events"#,
+                )
+                .fetch_one(&state.pool)
+                .await
+                .unwrap_or(0),
+            ),
+        };
         let mut last_translation_request_cursor = load_translation_stream_cursor(
             &state.pool,
             "translation_requests",
@@ -1492,7 +2690,7 @@ pub fn admin_jobs_sse_response(state: Arc<AppState>) -> Response {
 
         // Emit one lightweight frame immediately so proxies/browsers can
         // complete SSE handshake and update client connection state promptly.
-        yield Ok::<Event, Infallible>(Event::default().comment("stream-ready"));
+        yield Ok::<Event, Infallible>(Event::default().retry(SSE_RETRY_HINT).comment("stream-ready"));
 
         loop {
             let job_rows = sqlx::query_as::<_, EventRow>(
@@ -1530,7 +2728,7 @@ pub fn admin_jobs_sse_response(state: Arc<AppState>) -> Response {
                 let data = serde_json::to_string(&payload).unwrap_or_else(|_| "{}".to_owned());
                 yield Ok::<Event, Infallible>(
                     Event::default()
-                        .id(format!("job-{}", row.id))
+                        .id(admin_stream_event_id(last_job_event_seq, last_llm_event_seq))
                         .event("job.event")
                         .data(data),
                 );
@@ -1574,7 +2772,7 @@ pub fn admin_jobs_sse_response(state: Arc<AppState>) -> Response {
                 let data = serde_json::to_string(&payload).unwrap_or_else(|_| "{}".to_owned());
                 yield Ok::<Event, Infallible>(
                     Event::default()
-                        .id(format!("llm-{}", row.id))
+                        .id(admin_stream_event_id(last_job_event_seq, last_llm_event_seq))
                         .event("llm.call")
                         .data(data),
                 );
@@ -1588,7 +2786,7 @@ pub fn admin_jobs_sse_response(state: Arc<AppState>) -> Response {
                 let data = serde_json::to_string(&payload).unwrap_or_else(|_| "{}".to_owned());
                 yield Ok::<Event, Infallible>(
                     Event::default()
-                        .id(format!("llm-scheduler-{}", payload.event_id))
+                        .id(admin_stream_event_id(last_job_event_seq, last_llm_event_seq))
                         .event("llm.scheduler")
                         .data(data),
                 );
@@ -1619,7 +2817,7 @@ pub fn admin_jobs_sse_response(state: Arc<AppState>) -> Response {
                 let data = serde_json::to_string(&payload).unwrap_or_else(|_| "{}".to_owned());
                 yield Ok::<Event, Infallible>(
                     Event::default()
-                        .id(format!("translation-{}", event_id))
+                        .id(admin_stream_event_id(last_job_event_seq, last_llm_event_seq))
                         .event("translation.event")
                         .data(data),
                 );
@@ -1650,7 +2848,7 @@ pub fn admin_jobs_sse_response(state: Arc<AppState>) -> Response {
                 let data = serde_json::to_string(&payload).unwrap_or_else(|_| "{}".to_owned());
                 yield Ok::<Event, Infallible>(
                     Event::default()
-                        .id(format!("translation-{}", event_id))
+                        .id(admin_stream_event_id(last_job_event_seq, last_llm_event_seq))
                         .event("translation.event")
                         .data(data),
                 );
@@ -1678,13 +2876,20 @@ pub fn admin_jobs_sse_response(state: Arc<AppState>) -> Response {
                 let data = serde_json::to_string(&payload).unwrap_or_else(|_| "{}".to_owned());
                 yield Ok::<Event, Infallible>(
                     Event::default()
-                        .id(format!("translation-{}", event_id))
+                        .id(admin_stream_event_id(last_job_event_seq, last_llm_event_seq))
                         .event("translation.event")
                         .data(data),
                 );
             }
 
-            tokio::time::sleep(Duration::from_millis(500)).await;
+            let shutting_down = tokio::select! {
+                _ = runtime::shutdown_token().cancelled() => true,
+                _ = tokio::time::sleep(Duration::from_millis(500)) => false,
+            };
+            if shutting_down {
+                yield Ok::<Event, Infallible>(shutdown_sse_event());
+                break;
+            }
         }
     };
 
@@ -1697,6 +2902,22 @@ pub fn admin_jobs_sse_response(state: Arc<AppState>) -> Response {
         .into_response()
 }
 
+/// Picks the lane of the next claim: the highest waiting priority, unless a
+/// waiting lane got none of the last `TASK_PRIORITY_FAIRNESS_WINDOW - 1`
+/// claims, in which case the lowest such lane goes next.
+fn next_claim_priority(waiting: &[TaskPriority], recent: &[TaskPriority]) -> Option<TaskPriority> {
+    if recent.len() + 1 >= TASK_PRIORITY_FAIRNESS_WINDOW
+        && let Some(starved) = waiting
+            .iter()
+            .copied()
+            .filter(|priority| !recent.contains(priority))
+            .max()
+    {
+        return Some(starved);
+    }
+    waiting.iter().copied().min()
+}
+
 async fn claim_next_queued_task(state: &AppState) -> Result<Option<TaskRow>> {
     let _claim_guard = task_claim_lock().lock().await;
     let (sqlite_write, mut tx) = state
@@ -1705,11 +2926,10 @@ async fn claim_next_queued_task(state: &AppState) -> Result<Option<TaskRow>> {
         .await
         .context("begin task claim tx")?;
 
-    let task_id = sqlx::query_scalar::<_, String>(
-        r#"
-        SELECT id
-        FROM job_tasks
-        WHERE status = ?
+    // Subscription and retry sweeps run one at a time, so a queued one is not
+    // claimable while another of the same type is still running.
+    const CLAIMABLE: &str = r#"
+        status = ?
           AND (
             task_type NOT IN (?, ?)
             OR NOT EXISTS (
@@ -1719,17 +2939,58 @@ async fn claim_next_queued_task(state: &AppState) -> Result<Option<TaskRow>> {
                 AND running.status = ?
             )
           )
-        ORDER BY created_at ASC
-        LIMIT 1
-        "#,
-    )
+    "#;
+    let waiting = sqlx::query_scalar::<_, String>(&format!(
+        "SELECT DISTINCT priority FROM job_tasks WHERE {CLAIMABLE}"
+    ))
     .bind(STATUS_QUEUED)
     .bind(TASK_SYNC_SUBSCRIPTIONS)
     .bind(TASK_RETRY_RECENT_FAILURES)
     .bind(STATUS_RUNNING)
-    .fetch_optional(&mut *tx)
+    .fetch_all(&mut *tx)
+    .await
+    .context("select waiting task priorities")?
+    .iter()
+    .filter_map(|raw| TaskPriority::parse(raw))
+    .collect::<Vec<_>>();
+    let recent = sqlx::query_scalar::<_, String>(
+        r#"
+        SELECT priority
+        FROM job_tasks
+        WHERE started_at IS NOT NULL
+        ORDER BY started_at DESC
+        LIMIT ?
+        "#,
+    )
+    .bind(i64::try_from(TASK_PRIORITY_FAIRNESS_WINDOW - 1).unwrap_or(i64::MAX))
+    .fetch_all(&mut *tx)
     .await
-    .context("select queued task")?;
+    .context("select recently claimed task priorities")?
+    .iter()
+    .filter_map(|raw| TaskPriority::parse(raw))
+    .collect::<Vec<_>>();
+
+    let task_id = match next_claim_priority(&waiting, &recent) {
+        Some(priority) => sqlx::query_scalar::<_, String>(&format!(
+            r#"
+            SELECT id
+            FROM job_tasks
+            WHERE {CLAIMABLE}
+              AND priority = ?
+            ORDER BY created_at ASC
+            LIMIT 1
+            "#
+        ))
+        .bind(STATUS_QUEUED)
+        .bind(TASK_SYNC_SUBSCRIPTIONS)
+        .bind(TASK_RETRY_RECENT_FAILURES)
+        .bind(STATUS_RUNNING)
+        .bind(priority.as_str())
+        .fetch_optional(&mut *tx)
+        .await
+        .context("select queued task")?,
+        None => None,
+    };
 
     let Some(task_id) = task_id else {
         tx.commit().await.context("commit empty claim tx")?;
@@ -1762,7 +3023,7 @@ async fn claim_next_queued_task(state: &AppState) -> Result<Option<TaskRow>> {
 
     let task = sqlx::query_as::<_, TaskRow>(
         r#"
-        SELECT id, task_type, source, requested_by, payload_json, cancel_requested
+        SELECT id, task_type, source, requested_by, payload_json, cancel_requested, request_id
         FROM job_tasks
         WHERE id = ?
         LIMIT 1
@@ -1775,6 +3036,7 @@ async fn claim_next_queued_task(state: &AppState) -> Result<Option<TaskRow>> {
 
     tx.commit().await.context("commit claim tx")?;
     drop(sqlite_write);
+    observability::record_job_task_transition(&task.task_type, STATUS_RUNNING);
 
     append_task_event(
         state,
@@ -1788,6 +3050,20 @@ async fn claim_next_queued_task(state: &AppState) -> Result<Option<TaskRow>> {
 }
 
 async fn process_task(state: Arc<AppState>, task: TaskRow) -> Result<()> {
+    process_task_with(state, task, |state, task_id, task_type, payload| {
+        Box::pin(execute_task(state, task_id, task_type, payload))
+    })
+    .await
+}
+
+async fn process_task_with<Execute>(
+    state: Arc<AppState>,
+    task: TaskRow,
+    execute: Execute,
+) -> Result<()>
+where
+    Execute: for<'a> FnOnce(&'a AppState, &'a str, &'a str, &'a Value) -> TaskStepFuture<'a, Value>,
+{
     if task.cancel_requested != 0 {
         finalize_task(state.as_ref(), &task.id, STATUS_CANCELED, None, None).await?;
         append_task_event(
@@ -1811,11 +3087,38 @@ async fn process_task(state: Arc<AppState>, task: TaskRow) -> Result<()> {
         parent_translation_batch_id: None,
     };
     let heartbeat = spawn_task_lease_heartbeat(state.clone(), task.id.clone());
-    let result = ai::with_llm_call_context(
-        context,
-        execute_task(state.as_ref(), &task.id, &task.task_type, &payload),
-    )
-    .await;
+    let _cancellation = register_task_cancellation(&task.id);
+    // Child tasks and LLM calls inherit the request that queued this task.
+    let work = observability::with_request_id(
+        task.request_id.clone(),
+        ai::with_llm_call_context(
+            context,
+            execute(state.as_ref(), &task.id, &task.task_type, &payload),
+        ),
+    );
+    let result = match state.config.task_timeouts.for_task_type(&task.task_type) {
+        Some(limit) => match tokio::time::timeout(limit, work).await {
+            Ok(result) => result,
+            Err(_) => {
+                // Dropping the task body abandons its in-flight LLM calls, so
+                // their rows have to be closed here rather than by the caller.
+                let message = task_timeout_error(limit);
+                tracing::warn!(
+                    task_id = %task.id,
+                    task_type = %task.task_type,
+                    error = %message,
+                    "task worker: task timed out"
+                );
+                if let Err(err) =
+                    ai::fail_linked_llm_calls_for_task(state.as_ref(), &task.id, &message).await
+                {
+                    tracing::warn!(?err, task_id = %task.id, "task worker: fail timed out llm calls failed");
+                }
+                Err(anyhow!(message))
+            }
+        },
+        None => work.await,
+    };
 
     if is_task_cancel_requested(state.as_ref(), &task.id)
         .await
@@ -1885,12 +3188,35 @@ async fn execute_task(
     match task_type {
         TASK_SYNC_STARRED => {
             let user_id = payload_local_id(payload, "user_id")?;
-            let res = sync::sync_starred(state, user_id.as_str()).await?;
+            let res = sync::sync_starred_for_task(state, task_id, user_id.as_str()).await?;
             Ok(serde_json::to_value(res).unwrap_or_else(|_| json!({"ok": true})))
         }
         TASK_SYNC_RELEASES => {
             let user_id = payload_local_id(payload, "user_id")?;
-            let res = sync::sync_releases(state, user_id.as_str()).await?;
+            let force = payload
+                .get("force")
+                .and_then(Value::as_bool)
+                .unwrap_or(false);
+            let res = sync::sync_releases_for_task(state, task_id, user_id.as_str(), force).await?;
+            Ok(serde_json::to_value(res).unwrap_or_else(|_| json!({"ok": true})))
+        }
+        TASK_SYNC_RELEASE_NODE_IDS => {
+            let user_id = payload_local_id(payload, "user_id")?;
+            let res = sync::backfill_release_node_ids(state, user_id.as_str()).await?;
+            Ok(serde_json::to_value(res).unwrap_or_else(|_| json!({"ok": true})))
+        }
+        TASK_BACKFILL_REPO_RELEASES => {
+            let user_id = payload_local_id(payload, "user_id")?;
+            let repo_id = payload_i64(payload, "repo_id")?;
+            let repo_full_name = payload_string(payload, "repo_full_name")?;
+            let res = sync::backfill_repo_releases_for_task(
+                state,
+                task_id,
+                user_id.as_str(),
+                repo_id,
+                repo_full_name.as_str(),
+            )
+            .await?;
             Ok(serde_json::to_value(res).unwrap_or_else(|_| json!({"ok": true})))
         }
         TASK_SYNC_ACCESS_REFRESH => {
@@ -1903,6 +3229,10 @@ async fn execute_task(
             let res = sync::sync_notifications(state, user_id.as_str()).await?;
             Ok(serde_json::to_value(res).unwrap_or_else(|_| json!({"ok": true})))
         }
+        TASK_BOOTSTRAP_USER => {
+            let user_id = payload_local_id(payload, "user_id")?;
+            bootstrap::execute_bootstrap_user_task(state, task_id, user_id.as_str()).await
+        }
         TASK_SYNC_ALL => {
             let user_id = payload_local_id(payload, "user_id")?;
             execute_sync_all_task_with(
@@ -1923,12 +3253,26 @@ async fn execute_task(
         TASK_BRIEF_GENERATE => {
             let user_id = payload_local_id(payload, "user_id")?;
             let key_date = payload_date(payload, "key_date")?;
-            let snapshot = if let Some(key_date) = key_date {
+            let window_start = payload_datetime(payload, "window_start")?;
+            let window_end = payload_datetime(payload, "window_end")?;
+            let snapshot = if let (Some(start_utc), Some(end_utc)) = (window_start, window_end) {
+                ai::generate_brief_snapshot_for_range(state, user_id.as_str(), start_utc, end_utc)
+                    .await?
+            } else if let Some(key_date) = key_date {
                 ai::generate_daily_brief_snapshot_for_key_date(state, user_id.as_str(), key_date)
                     .await?
             } else {
                 ai::generate_daily_brief_snapshot_for_current(state, user_id.as_str()).await?
             };
+            briefs::deliver_brief_email_best_effort(state, user_id.as_str(), &snapshot).await;
+            notify::notify_brief_ready_best_effort(
+                state,
+                user_id.as_str(),
+                &snapshot,
+                "brief.generate.notify",
+                Some(task_id),
+            )
+            .await;
             Ok(json!({
                 "brief_id": snapshot.id,
                 "content_length": snapshot.content_markdown.chars().count(),
@@ -1938,6 +3282,7 @@ async fn execute_task(
                 "effective_time_zone": snapshot.effective_time_zone,
                 "effective_local_boundary": snapshot.effective_local_boundary,
                 "release_count": snapshot.release_ids.len(),
+                "coverage": snapshot.coverage,
             }))
         }
         TASK_BRIEF_DAILY_SLOT => execute_daily_slot_task(state, task_id, payload).await,
@@ -1954,6 +3299,17 @@ async fn execute_task(
                 .map_err(|err| anyhow!("translate_release failed: {}", err.code()))?;
             Ok(serde_json::to_value(res).unwrap_or_else(|_| json!({"ok": true})))
         }
+        TASK_TRANSLATION_REPAIR => {
+            // Admin runs may leave `user_id` null to cover every user.
+            let user_id = match payload.get("user_id") {
+                None | Some(Value::Null) => None,
+                Some(_) => Some(payload_local_id(payload, "user_id")?),
+            };
+            let res = api::repair_release_translations(state, user_id.as_deref())
+                .await
+                .map_err(|err| anyhow!("translation repair failed: {}", err.code()))?;
+            Ok(serde_json::to_value(res).unwrap_or_else(|_| json!({"ok": true})))
+        }
         TASK_TRANSLATE_RELEASE_BATCH => {
             let user_id = payload_local_id(payload, "user_id")?;
             let release_ids = payload_i64_array(payload, "release_ids")?;
@@ -1976,9 +3332,14 @@ async fn execute_task(
         TASK_TRANSLATE_RELEASE_DETAIL => {
             let user_id = payload_local_id(payload, "user_id")?;
             let release_id = payload_string(payload, "release_id")?;
-            let res = api::translate_release_detail_for_user(state, user_id.as_str(), &release_id)
-                .await
-                .map_err(|err| anyhow!("translate_release_detail failed: {}", err.code()))?;
+            let mode = api::ReleaseDetailTranslationMode::parse(
+                payload.get("mode").and_then(Value::as_str),
+            )
+            .map_err(|err| anyhow!("translate_release_detail failed: {}", err.code()))?;
+            let res =
+                api::translate_release_detail_for_user(state, user_id.as_str(), &release_id, mode)
+                    .await
+                    .map_err(|err| anyhow!("translate_release_detail failed: {}", err.code()))?;
             Ok(serde_json::to_value(res).unwrap_or_else(|_| json!({"ok": true})))
         }
         TASK_TRANSLATE_NOTIFICATION => {
@@ -1989,6 +3350,24 @@ async fn execute_task(
                 .map_err(|err| anyhow!("translate_notification failed: {}", err.code()))?;
             Ok(serde_json::to_value(res).unwrap_or_else(|_| json!({"ok": true})))
         }
+        TASK_NOTIFY_DISPATCH => notify::execute_notify_dispatch_task(state, task_id, payload).await,
+        TASK_MAINTENANCE_PRUNE => {
+            let res = prune_task_history(state, Utc::now()).await?;
+            Ok(serde_json::to_value(res).unwrap_or_else(|_| json!({"ok": true})))
+        }
+        TASK_MAINTENANCE_DB => db_maintenance::run_scheduled(state).await,
+        TASK_MAINTENANCE_BACKUP => {
+            let res = backup::create_backup(state).await?;
+            Ok(serde_json::to_value(res).unwrap_or_else(|_| json!({"ok": true})))
+        }
+        TASK_TRANSLATION_RETRY => {
+            let res = translation_retry::run_due_translation_retries(state, Utc::now()).await?;
+            Ok(serde_json::to_value(res).unwrap_or_else(|_| json!({"ok": true})))
+        }
+        TASK_MAINTENANCE_STAR_PURGE => {
+            let res = star_purge::run_due_star_purges(state, Utc::now()).await?;
+            Ok(serde_json::to_value(res).unwrap_or_else(|_| json!({"ok": true})))
+        }
         _ => Err(anyhow!("unsupported task_type: {task_type}")),
     }
 }
@@ -2145,6 +3524,16 @@ async fn execute_daily_slot_task(
         {
             Ok(snapshot) => {
                 succeeded += 1;
+                briefs::deliver_brief_email_best_effort(state, user.user_id.as_str(), &snapshot)
+                    .await;
+                notify::notify_brief_ready_best_effort(
+                    state,
+                    user.user_id.as_str(),
+                    &snapshot,
+                    "brief.daily_slot.notify",
+                    Some(task_id),
+                )
+                .await;
                 append_task_event(
                     state,
                     task_id,
@@ -3405,10 +4794,10 @@ async fn finalize_task(
         .transpose()
         .context("serialize task result")?;
 
-    state
+    let task_type = state
         .sqlite_writer
         .write("job_task_finalize", |_| async {
-            sqlx::query(
+            sqlx::query_scalar::<_, String>(
                 r#"
                 UPDATE job_tasks
                 SET status = ?,
@@ -3419,6 +4808,7 @@ async fn finalize_task(
                     lease_heartbeat_at = NULL,
                     updated_at = ?
                 WHERE id = ?
+                RETURNING task_type
                 "#,
             )
             .bind(status)
@@ -3427,12 +4817,14 @@ async fn finalize_task(
             .bind(now.as_str())
             .bind(now.as_str())
             .bind(task_id)
-            .execute(&state.pool)
+            .fetch_optional(&state.pool)
             .await
-            .context("failed to finalize task")?;
-            Ok(())
+            .context("failed to finalize task")
         })
         .await?;
+    if let Some(task_type) = task_type {
+        observability::record_job_task_transition(&task_type, status);
+    }
 
     Ok(())
 }
@@ -3455,20 +4847,52 @@ async fn heartbeat_task_lease(state: &AppState, task_id: &str) -> Result<()> {
             .bind(now.as_str())
             .bind(task_id)
             .bind(STATUS_RUNNING)
-            .bind(state.runtime_owner_id.as_str())
-            .execute(&state.pool)
+            .bind(state.runtime_owner_id.as_str())
+            .execute(&state.pool)
+            .await
+            .context("failed to heartbeat task lease")?;
+            Ok(())
+        })
+        .await?;
+    Ok(())
+}
+
+pub async fn recover_runtime_state(state: &AppState) -> Result<()> {
+    recover_runtime_state_with_mode(state, runtime::RuntimeRecoveryMode::Sweep).await
+}
+
+async fn requeue_interrupted_task(state: &AppState, task_id: &str) -> Result<()> {
+    let now = Utc::now().to_rfc3339();
+    let task_type = state
+        .sqlite_writer
+        .write("job_task_requeue", |_| async {
+            sqlx::query_scalar::<_, String>(
+                r#"
+                UPDATE job_tasks
+                SET status = ?,
+                    started_at = NULL,
+                    runtime_owner_id = NULL,
+                    lease_heartbeat_at = NULL,
+                    updated_at = ?
+                WHERE id = ? AND status = ?
+                RETURNING task_type
+                "#,
+            )
+            .bind(STATUS_QUEUED)
+            .bind(now.as_str())
+            .bind(task_id)
+            .bind(STATUS_RUNNING)
+            .fetch_optional(&state.pool)
             .await
-            .context("failed to heartbeat task lease")?;
-            Ok(())
+            .context("failed to requeue interrupted task")
         })
         .await?;
+    if let Some(task_type) = task_type {
+        observability::record_job_task_transition(&task_type, STATUS_QUEUED);
+    }
     Ok(())
 }
 
-pub async fn recover_runtime_state(state: &AppState) -> Result<()> {
-    recover_runtime_state_with_mode(state, runtime::RuntimeRecoveryMode::Sweep).await
-}
-
 async fn recover_runtime_state_with_mode(
     state: &AppState,
     mode: runtime::RuntimeRecoveryMode,
@@ -3476,6 +4900,7 @@ async fn recover_runtime_state_with_mode(
     #[derive(Debug, sqlx::FromRow)]
     struct StaleTaskRow {
         id: String,
+        task_type: String,
         runtime_owner_id: Option<String>,
         lease_heartbeat_at: Option<String>,
     }
@@ -3486,7 +4911,7 @@ async fn recover_runtime_state_with_mode(
         runtime::RuntimeRecoveryMode::Startup => {
             sqlx::query_as::<_, StaleTaskRow>(
                 r#"
-                SELECT id, runtime_owner_id, lease_heartbeat_at
+                SELECT id, task_type, runtime_owner_id, lease_heartbeat_at
                 FROM job_tasks
                 WHERE status = ?
                   AND (
@@ -3516,7 +4941,7 @@ async fn recover_runtime_state_with_mode(
         runtime::RuntimeRecoveryMode::Sweep => {
             sqlx::query_as::<_, StaleTaskRow>(
                 r#"
-                SELECT id, runtime_owner_id, lease_heartbeat_at
+                SELECT id, task_type, runtime_owner_id, lease_heartbeat_at
                 FROM job_tasks
                 WHERE status = ?
                   AND (
@@ -3536,6 +4961,24 @@ async fn recover_runtime_state_with_mode(
     .context("failed to load stale runtime tasks")?;
 
     for task in stale_tasks {
+        if mode == runtime::RuntimeRecoveryMode::Startup
+            && STARTUP_REQUEUE_TASK_TYPES.contains(&task.task_type.as_str())
+        {
+            requeue_interrupted_task(state, task.id.as_str()).await?;
+            append_task_event(
+                state,
+                task.id.as_str(),
+                "task.recovered_requeued",
+                json!({
+                    "task_id": task.id,
+                    "status": STATUS_QUEUED,
+                    "previous_runtime_owner_id": task.runtime_owner_id,
+                    "previous_lease_heartbeat_at": task.lease_heartbeat_at,
+                }),
+            )
+            .await?;
+            continue;
+        }
         finalize_task(
             state,
             task.id.as_str(),
@@ -3668,6 +5111,21 @@ fn payload_date(payload: &Value, key: &str) -> Result<Option<NaiveDate>> {
     Ok(Some(NaiveDate::parse_from_str(value, "%Y-%m-%d")?))
 }
 
+fn payload_datetime(payload: &Value, key: &str) -> Result<Option<DateTime<Utc>>> {
+    let Some(raw) = payload.get(key).and_then(Value::as_str) else {
+        return Ok(None);
+    };
+    let value = raw.trim();
+    if value.is_empty() {
+        return Ok(None);
+    }
+    Ok(Some(
+        DateTime::parse_from_rfc3339(value)
+            .with_context(|| format!("payload field {key} must be RFC3339"))?
+            .with_timezone(&Utc),
+    ))
+}
+
 fn payload_i64_array(payload: &Value, key: &str) -> Result<Vec<i64>> {
     let values = payload
         .get(key)
@@ -3709,32 +5167,42 @@ fn translate_batch_task_result_json(items: Vec<api::TranslateBatchItem>) -> Valu
     })
 }
 
-fn is_terminal_status(status: &str) -> bool {
+pub fn is_terminal_status(status: &str) -> bool {
     matches!(status, STATUS_SUCCEEDED | STATUS_FAILED | STATUS_CANCELED)
 }
 
 #[cfg(test)]
 mod tests {
-    use std::{net::SocketAddr, sync::Arc};
+    use std::sync::Arc;
 
     use super::{
         NewTask, RetryTranslationCandidateRow, SMART_NO_VALUABLE_VERSION_INFO, STATUS_FAILED,
-        STATUS_QUEUED, STATUS_RUNNING, TASK_BRIEF_DAILY_SLOT, TASK_BRIEF_HISTORY_RECOMPUTE,
-        TASK_BRIEF_REFRESH_CONTENT, TASK_RETRY_RECENT_FAILURES, TASK_SUMMARIZE_RELEASE_SMART_BATCH,
-        TASK_SYNC_ALL, TASK_SYNC_RELEASES, TASK_SYNC_SUBSCRIPTIONS, TranslationStreamCursor,
-        claim_next_queued_task, current_recent_failures_retry_schedule_key,
-        current_subscription_schedule_key, enqueue_brief_history_recompute_if_needed,
-        enqueue_brief_refresh_content_if_needed, enqueue_hour_slot_if_due,
+        STATUS_QUEUED, STATUS_RUNNING, STATUS_SUCCEEDED, TASK_BRIEF_DAILY_SLOT,
+        TASK_BRIEF_GENERATE, TASK_BRIEF_HISTORY_RECOMPUTE, TASK_BRIEF_REFRESH_CONTENT,
+        TASK_MAINTENANCE_BACKUP, TASK_MAINTENANCE_DB, TASK_MAINTENANCE_PRUNE,
+        TASK_MAINTENANCE_STAR_PURGE, TASK_RETRY_RECENT_FAILURES, TASK_SERVER_SHUTDOWN_ERROR,
+        TASK_SUMMARIZE_RELEASE_SMART_BATCH, TASK_SYNC_ALL, TASK_SYNC_NOTIFICATIONS,
+        TASK_SYNC_RELEASES, TASK_SYNC_STARRED, TASK_SYNC_SUBSCRIPTIONS, TASK_TRANSLATION_RETRY,
+        TaskPriority, TranslationStreamCursor, USER_SYNC_SCHEDULER_SOURCE, append_task_event,
+        claim_next_queued_task, count_users_due_for_sync,
+        current_recent_failures_retry_schedule_key, current_subscription_schedule_key,
+        enqueue_brief_history_recompute_if_needed, enqueue_brief_refresh_content_if_needed,
+        enqueue_due_user_syncs, enqueue_hour_slot_if_due, enqueue_maintenance_prune_if_due,
         enqueue_recent_failures_retry_if_due, enqueue_task, execute_brief_history_recompute_task,
         execute_brief_refresh_content_task, execute_daily_slot_task, execute_sync_all_task_with,
-        is_scheduled_task_type, load_due_daily_slot_users,
-        load_recent_failed_brief_retry_candidates, load_recent_failed_translation_retry_candidates,
-        load_translation_stream_cursor, load_translation_stream_rows, mark_brief_generation_source,
-        next_llm_scheduler_stream_event, payload_slot_hour_key, payload_slot_reference_utc,
-        recover_runtime_state, recover_runtime_state_on_startup, retry_candidate_is_retryable,
-        update_daily_brief_hour_slot_dispatch, upsert_dispatch_state,
+        fail_running_tasks_on_shutdown, is_scheduled_task_type, load_due_daily_slot_users,
+        load_last_maintenance_prune_at, load_recent_failed_brief_retry_candidates,
+        load_recent_failed_translation_retry_candidates, load_translation_stream_cursor,
+        load_translation_stream_rows, mark_brief_generation_source, next_claim_priority,
+        next_llm_scheduler_stream_event, parse_admin_stream_event_id, payload_slot_hour_key,
+        payload_slot_reference_utc, process_task_with, prune_task_history, recover_runtime_state,
+        recover_runtime_state_on_startup, resume_event_seq, retry_candidate_is_retryable,
+        run_maintenance_prune_now, task_sse_response, task_timeout_error,
+        update_daily_brief_hour_slot_dispatch, upsert_dispatch_state, user_sync_due_at,
+        user_sync_jitter,
     };
     use chrono::{Duration, TimeZone, Utc};
+    use futures::StreamExt;
     use serde_json::{Value, json};
     use sqlx::{
         Row, SqlitePool,
@@ -3743,9 +5211,8 @@ mod tests {
     use url::Url;
 
     use crate::{
-        config::{AiConfig, AppConfig, GitHubOAuthConfig},
-        crypto::EncryptionKey,
-        state::{AppState, build_oauth_client},
+        config::{AiConfig, AppConfig},
+        state::AppState,
         sync,
     };
 
@@ -4102,6 +5569,11 @@ mod tests {
         assert!(is_scheduled_task_type(TASK_BRIEF_DAILY_SLOT));
         assert!(is_scheduled_task_type(TASK_SYNC_SUBSCRIPTIONS));
         assert!(is_scheduled_task_type(TASK_RETRY_RECENT_FAILURES));
+        assert!(is_scheduled_task_type(TASK_MAINTENANCE_PRUNE));
+        assert!(is_scheduled_task_type(TASK_MAINTENANCE_DB));
+        assert!(is_scheduled_task_type(TASK_TRANSLATION_RETRY));
+        assert!(is_scheduled_task_type(TASK_MAINTENANCE_STAR_PURGE));
+        assert!(is_scheduled_task_type(TASK_MAINTENANCE_BACKUP));
         assert!(!is_scheduled_task_type("translate.release"));
         assert!(!is_scheduled_task_type(TASK_SUMMARIZE_RELEASE_SMART_BATCH));
     }
@@ -4255,6 +5727,8 @@ mod tests {
                     available_slots: 0,
                     waiting_calls: 3,
                     in_flight_calls: 2,
+                    paused: false,
+                    request_interval_ms: 0,
                 },
                 unchanged_at,
             )
@@ -4268,124 +5742,628 @@ mod tests {
                 available_slots: 3,
                 waiting_calls: 1,
                 in_flight_calls: 2,
+                paused: false,
+                request_interval_ms: 0,
             },
             changed_at,
         )
         .expect("scheduler change should emit event");
 
-        assert_eq!(last_max_concurrency, 5);
-        assert_eq!(payload.max_concurrency, 5);
-        assert_eq!(payload.available_slots, 3);
-        assert_eq!(payload.waiting_calls, 1);
-        assert_eq!(payload.in_flight_calls, 2);
-        assert_eq!(payload.event_type, "llm.scheduler.updated");
-        assert_eq!(payload.created_at, "2026-03-28T10:00:00+00:00");
+        assert_eq!(last_max_concurrency, 5);
+        assert_eq!(payload.max_concurrency, 5);
+        assert_eq!(payload.available_slots, 3);
+        assert_eq!(payload.waiting_calls, 1);
+        assert_eq!(payload.in_flight_calls, 2);
+        assert_eq!(payload.event_type, "llm.scheduler.updated");
+        assert_eq!(payload.created_at, "2026-03-28T10:00:00+00:00");
+    }
+
+    #[tokio::test]
+    async fn claim_next_queued_task_defers_subscription_when_one_is_running() {
+        let pool = setup_pool().await;
+        let state = setup_state(pool.clone());
+
+        seed_task(
+            &pool,
+            "sync-running",
+            TASK_SYNC_SUBSCRIPTIONS,
+            STATUS_RUNNING,
+            0,
+        )
+        .await;
+        seed_task(
+            &pool,
+            "sync-queued",
+            TASK_SYNC_SUBSCRIPTIONS,
+            STATUS_QUEUED,
+            1,
+        )
+        .await;
+        seed_task(
+            &pool,
+            "brief-queued",
+            TASK_BRIEF_DAILY_SLOT,
+            STATUS_QUEUED,
+            2,
+        )
+        .await;
+
+        let claimed = claim_next_queued_task(state.as_ref())
+            .await
+            .expect("claim queued task")
+            .expect("task claimed");
+        assert_eq!(claimed.id, "brief-queued");
+        assert_eq!(claimed.task_type, TASK_BRIEF_DAILY_SLOT);
+    }
+
+    #[tokio::test]
+    async fn enqueued_task_carries_request_id_of_enqueuing_request() {
+        let pool = setup_pool().await;
+        let state = setup_state(pool.clone());
+
+        let enqueued = crate::observability::with_request_id(
+            Some("req-enqueue-1".to_owned()),
+            enqueue_task(
+                state.as_ref(),
+                NewTask {
+                    task_type: TASK_SYNC_SUBSCRIPTIONS.to_owned(),
+                    payload: json!({}),
+                    source: "api".to_owned(),
+                    requested_by: None,
+                    parent_task_id: None,
+                    priority: TaskPriority::Normal,
+                },
+            ),
+        )
+        .await
+        .expect("enqueue task");
+
+        let claimed = claim_next_queued_task(state.as_ref())
+            .await
+            .expect("claim queued task")
+            .expect("task claimed");
+        assert_eq!(claimed.id, enqueued.task_id);
+        assert_eq!(claimed.request_id.as_deref(), Some("req-enqueue-1"));
+    }
+
+    #[tokio::test]
+    async fn claim_next_queued_task_defers_recent_failures_retry_when_one_is_running() {
+        let pool = setup_pool().await;
+        let state = setup_state(pool.clone());
+
+        seed_task(
+            &pool,
+            "retry-running",
+            TASK_RETRY_RECENT_FAILURES,
+            STATUS_RUNNING,
+            0,
+        )
+        .await;
+        seed_task(
+            &pool,
+            "retry-queued",
+            TASK_RETRY_RECENT_FAILURES,
+            STATUS_QUEUED,
+            1,
+        )
+        .await;
+        seed_task(
+            &pool,
+            "brief-queued",
+            TASK_BRIEF_DAILY_SLOT,
+            STATUS_QUEUED,
+            2,
+        )
+        .await;
+
+        let claimed = claim_next_queued_task(state.as_ref())
+            .await
+            .expect("claim queued task")
+            .expect("task claimed");
+        assert_eq!(claimed.id, "brief-queued");
+        assert_eq!(claimed.task_type, TASK_BRIEF_DAILY_SLOT);
+    }
+
+    #[tokio::test]
+    async fn claim_next_queued_task_allows_subscription_when_no_run_is_active() {
+        let pool = setup_pool().await;
+        let state = setup_state(pool.clone());
+
+        seed_task(
+            &pool,
+            "sync-queued",
+            TASK_SYNC_SUBSCRIPTIONS,
+            STATUS_QUEUED,
+            0,
+        )
+        .await;
+
+        let claimed = claim_next_queued_task(state.as_ref())
+            .await
+            .expect("claim queued task")
+            .expect("task claimed");
+        assert_eq!(claimed.id, "sync-queued");
+        assert_eq!(claimed.task_type, TASK_SYNC_SUBSCRIPTIONS);
+    }
+
+    #[test]
+    fn next_claim_priority_prefers_high_but_serves_starved_lanes() {
+        use TaskPriority::{High, Low, Normal};
+
+        assert_eq!(next_claim_priority(&[], &[]), None);
+        assert_eq!(next_claim_priority(&[Low, Normal, High], &[]), Some(High));
+        assert_eq!(next_claim_priority(&[Low, Normal], &[High; 4]), Some(Low));
+        assert_eq!(next_claim_priority(&[High, Normal], &[High; 3]), Some(High));
+        assert_eq!(
+            next_claim_priority(&[High, Normal, Low], &[Low, High, High, High]),
+            Some(Normal)
+        );
+        assert_eq!(
+            next_claim_priority(&[High, Low], &[Normal, Low, High, High]),
+            Some(High)
+        );
+    }
+
+    #[tokio::test]
+    async fn claim_next_queued_task_orders_by_priority_without_starving_low() {
+        let pool = setup_pool().await;
+        let state = setup_state(pool.clone());
+
+        let seeded = [
+            ("low-1", TaskPriority::Low),
+            ("normal-1", TaskPriority::Normal),
+            ("high-1", TaskPriority::High),
+            ("high-2", TaskPriority::High),
+            ("high-3", TaskPriority::High),
+            ("high-4", TaskPriority::High),
+            ("high-5", TaskPriority::High),
+            ("high-6", TaskPriority::High),
+        ];
+        for (offset, (task_id, priority)) in (0_i64..).zip(seeded) {
+            seed_task(&pool, task_id, TASK_SYNC_STARRED, STATUS_QUEUED, offset).await;
+            sqlx::query("UPDATE job_tasks SET priority = ? WHERE id = ?")
+                .bind(priority.as_str())
+                .bind(task_id)
+                .execute(&pool)
+                .await
+                .expect("set task priority");
+        }
+
+        let mut claimed = Vec::new();
+        while let Some(task) = claim_next_queued_task(state.as_ref())
+            .await
+            .expect("claim queued task")
+        {
+            claimed.push(task.id);
+        }
+        assert_eq!(
+            claimed,
+            vec![
+                "high-1", "high-2", "high-3", "high-4", "low-1", "normal-1", "high-5", "high-6",
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn recover_runtime_state_marks_stale_running_tasks_failed() {
+        let pool = setup_pool().await;
+        let state = setup_state(pool.clone());
+
+        seed_task(&pool, "stale-task", TASK_SYNC_RELEASES, STATUS_RUNNING, 0).await;
+        sqlx::query(
+            r#"
+            UPDATE job_tasks
+            SET runtime_owner_id = ?, lease_heartbeat_at = ?, updated_at = ?
+            WHERE id = ?
+            "#,
+        )
+        .bind("old-runtime-owner")
+        .bind("2026-03-06T00:00:00Z")
+        .bind("2026-03-06T00:00:00Z")
+        .bind("stale-task")
+        .execute(&pool)
+        .await
+        .expect("mark task stale");
+
+        recover_runtime_state(state.as_ref())
+            .await
+            .expect("recover runtime state");
+
+        let row = sqlx::query(
+            r#"
+            SELECT status, error_message, runtime_owner_id, lease_heartbeat_at
+            FROM job_tasks
+            WHERE id = ?
+            "#,
+        )
+        .bind("stale-task")
+        .fetch_one(&pool)
+        .await
+        .expect("load recovered task");
+
+        assert_eq!(row.get::<String, _>("status"), STATUS_FAILED);
+        assert_eq!(
+            row.get::<Option<String>, _>("error_message").as_deref(),
+            Some(crate::runtime::RUNTIME_LEASE_EXPIRED_ERROR)
+        );
+        assert_eq!(row.get::<Option<String>, _>("runtime_owner_id"), None);
+        assert_eq!(row.get::<Option<String>, _>("lease_heartbeat_at"), None);
+
+        let event_type = sqlx::query_scalar::<_, String>(
+            r#"
+            SELECT event_type
+            FROM job_task_events
+            WHERE task_id = ?
+            ORDER BY rowid DESC
+            LIMIT 1
+            "#,
+        )
+        .bind("stale-task")
+        .fetch_one(&pool)
+        .await
+        .expect("load recovery event");
+        assert_eq!(event_type, "task.recovered_failed");
+    }
+
+    #[tokio::test]
+    async fn recover_runtime_state_keeps_live_current_owner_tasks_running() {
+        let pool = setup_pool().await;
+        let state = setup_state(pool.clone());
+
+        seed_task(&pool, "live-task", TASK_SYNC_RELEASES, STATUS_RUNNING, 0).await;
+        let now = Utc::now().to_rfc3339();
+        sqlx::query(
+            r#"
+            UPDATE job_tasks
+            SET runtime_owner_id = ?, lease_heartbeat_at = ?, updated_at = ?
+            WHERE id = ?
+            "#,
+        )
+        .bind(state.runtime_owner_id.as_str())
+        .bind(now.as_str())
+        .bind(now.as_str())
+        .bind("live-task")
+        .execute(&pool)
+        .await
+        .expect("mark task live");
+
+        recover_runtime_state(state.as_ref())
+            .await
+            .expect("recover runtime state");
+
+        let status =
+            sqlx::query_scalar::<_, String>(r#"SELECT status FROM job_tasks WHERE id = ?"#)
+                .bind("live-task")
+                .fetch_one(&pool)
+                .await
+                .expect("load live task status");
+        assert_eq!(status, STATUS_RUNNING);
     }
 
     #[tokio::test]
-    async fn claim_next_queued_task_defers_subscription_when_one_is_running() {
+    async fn recover_runtime_state_keeps_live_foreign_owner_tasks_running() {
         let pool = setup_pool().await;
         let state = setup_state(pool.clone());
 
         seed_task(
             &pool,
-            "sync-running",
-            TASK_SYNC_SUBSCRIPTIONS,
+            "foreign-live-task",
+            TASK_SYNC_RELEASES,
             STATUS_RUNNING,
             0,
         )
         .await;
-        seed_task(
-            &pool,
-            "sync-queued",
-            TASK_SYNC_SUBSCRIPTIONS,
-            STATUS_QUEUED,
-            1,
-        )
-        .await;
-        seed_task(
-            &pool,
-            "brief-queued",
-            TASK_BRIEF_DAILY_SLOT,
-            STATUS_QUEUED,
-            2,
+        let now = Utc::now().to_rfc3339();
+        sqlx::query(
+            r#"
+            UPDATE job_tasks
+            SET runtime_owner_id = ?, lease_heartbeat_at = ?, updated_at = ?
+            WHERE id = ?
+            "#,
         )
-        .await;
+        .bind("other-runtime-owner")
+        .bind(now.as_str())
+        .bind(now.as_str())
+        .bind("foreign-live-task")
+        .execute(&pool)
+        .await
+        .expect("mark foreign-owner task live");
 
-        let claimed = claim_next_queued_task(state.as_ref())
+        recover_runtime_state(state.as_ref())
             .await
-            .expect("claim queued task")
-            .expect("task claimed");
-        assert_eq!(claimed.id, "brief-queued");
-        assert_eq!(claimed.task_type, TASK_BRIEF_DAILY_SLOT);
+            .expect("recover runtime state");
+
+        let row = sqlx::query(
+            r#"
+            SELECT status, runtime_owner_id, lease_heartbeat_at
+            FROM job_tasks
+            WHERE id = ?
+            "#,
+        )
+        .bind("foreign-live-task")
+        .fetch_one(&pool)
+        .await
+        .expect("load foreign-owner task status");
+
+        assert_eq!(row.get::<String, _>("status"), STATUS_RUNNING);
+        assert_eq!(
+            row.get::<Option<String>, _>("runtime_owner_id").as_deref(),
+            Some("other-runtime-owner")
+        );
+        assert_eq!(
+            row.get::<Option<String>, _>("lease_heartbeat_at")
+                .as_deref(),
+            Some(now.as_str())
+        );
     }
 
     #[tokio::test]
-    async fn claim_next_queued_task_defers_recent_failures_retry_when_one_is_running() {
+    async fn recover_runtime_state_on_startup_reclaims_foreign_owner_tasks_without_live_owner_lease()
+     {
         let pool = setup_pool().await;
         let state = setup_state(pool.clone());
 
         seed_task(
             &pool,
-            "retry-running",
-            TASK_RETRY_RECENT_FAILURES,
+            "startup-foreign-task",
+            TASK_SYNC_SUBSCRIPTIONS,
             STATUS_RUNNING,
             0,
         )
         .await;
+        let now = Utc::now().to_rfc3339();
+        sqlx::query(
+            r#"
+            UPDATE job_tasks
+            SET runtime_owner_id = ?, lease_heartbeat_at = ?, updated_at = ?
+            WHERE id = ?
+            "#,
+        )
+        .bind("other-runtime-owner")
+        .bind(now.as_str())
+        .bind(now.as_str())
+        .bind("startup-foreign-task")
+        .execute(&pool)
+        .await
+        .expect("mark startup foreign-owner task live");
+
+        recover_runtime_state_on_startup(state.as_ref())
+            .await
+            .expect("startup recover runtime state");
+
+        let row = sqlx::query(
+            r#"
+            SELECT status, error_message, runtime_owner_id, lease_heartbeat_at
+            FROM job_tasks
+            WHERE id = ?
+            "#,
+        )
+        .bind("startup-foreign-task")
+        .fetch_one(&pool)
+        .await
+        .expect("load recovered startup task");
+
+        assert_eq!(row.get::<String, _>("status"), STATUS_FAILED);
+        assert_eq!(
+            row.get::<Option<String>, _>("error_message").as_deref(),
+            Some(crate::runtime::RUNTIME_LEASE_EXPIRED_ERROR)
+        );
+        assert_eq!(row.get::<Option<String>, _>("runtime_owner_id"), None);
+        assert_eq!(row.get::<Option<String>, _>("lease_heartbeat_at"), None);
+    }
+
+    #[tokio::test]
+    async fn recover_runtime_state_on_startup_requeues_interrupted_sync_tasks() {
+        let pool = setup_pool().await;
+        let state = setup_state(pool.clone());
+
+        seed_task(&pool, "startup-sync", TASK_SYNC_STARRED, STATUS_RUNNING, 0).await;
         seed_task(
             &pool,
-            "retry-queued",
-            TASK_RETRY_RECENT_FAILURES,
-            STATUS_QUEUED,
+            "startup-brief",
+            TASK_BRIEF_GENERATE,
+            STATUS_RUNNING,
             1,
         )
         .await;
-        seed_task(
-            &pool,
-            "brief-queued",
-            TASK_BRIEF_DAILY_SLOT,
-            STATUS_QUEUED,
-            2,
+        sqlx::query(
+            r#"
+            UPDATE job_tasks
+            SET started_at = ?, runtime_owner_id = ?, lease_heartbeat_at = ?
+            WHERE id IN (?, ?)
+            "#,
         )
-        .await;
+        .bind("2026-03-06T00:00:00Z")
+        .bind("crashed-runtime-owner")
+        .bind("2026-03-06T00:00:00Z")
+        .bind("startup-sync")
+        .bind("startup-brief")
+        .execute(&pool)
+        .await
+        .expect("mark tasks interrupted");
 
-        let claimed = claim_next_queued_task(state.as_ref())
+        recover_runtime_state_on_startup(state.as_ref())
             .await
-            .expect("claim queued task")
-            .expect("task claimed");
-        assert_eq!(claimed.id, "brief-queued");
-        assert_eq!(claimed.task_type, TASK_BRIEF_DAILY_SLOT);
+            .expect("startup recover runtime state");
+
+        let sync_row = sqlx::query(
+            r#"
+            SELECT status, error_message, started_at, runtime_owner_id, lease_heartbeat_at
+            FROM job_tasks
+            WHERE id = ?
+            "#,
+        )
+        .bind("startup-sync")
+        .fetch_one(&pool)
+        .await
+        .expect("load requeued task");
+        assert_eq!(sync_row.get::<String, _>("status"), STATUS_QUEUED);
+        assert_eq!(sync_row.get::<Option<String>, _>("error_message"), None);
+        assert_eq!(sync_row.get::<Option<String>, _>("started_at"), None);
+        assert_eq!(sync_row.get::<Option<String>, _>("runtime_owner_id"), None);
+        assert_eq!(
+            sync_row.get::<Option<String>, _>("lease_heartbeat_at"),
+            None
+        );
+
+        let brief_status =
+            sqlx::query_scalar::<_, String>(r#"SELECT status FROM job_tasks WHERE id = ?"#)
+                .bind("startup-brief")
+                .fetch_one(&pool)
+                .await
+                .expect("load failed task");
+        assert_eq!(brief_status, STATUS_FAILED);
+
+        let event_types = sqlx::query_scalar::<_, String>(
+            r#"
+            SELECT event_type
+            FROM job_task_events
+            WHERE task_id IN (?, ?)
+            ORDER BY task_id ASC
+            "#,
+        )
+        .bind("startup-sync")
+        .bind("startup-brief")
+        .fetch_all(&pool)
+        .await
+        .expect("load recovery events");
+        assert_eq!(
+            event_types,
+            vec!["task.recovered_failed", "task.recovered_requeued"]
+        );
     }
 
     #[tokio::test]
-    async fn claim_next_queued_task_allows_subscription_when_no_run_is_active() {
+    async fn fail_running_tasks_on_shutdown_only_fails_tasks_of_this_runtime_owner() {
         let pool = setup_pool().await;
         let state = setup_state(pool.clone());
 
-        seed_task(
+        seed_task(&pool, "own-task", TASK_SYNC_RELEASES, STATUS_RUNNING, 0).await;
+        seed_task(&pool, "foreign-task", TASK_SYNC_RELEASES, STATUS_RUNNING, 1).await;
+        for (task_id, owner) in [
+            ("own-task", state.runtime_owner_id.as_str()),
+            ("foreign-task", "other-runtime-owner"),
+        ] {
+            sqlx::query(r#"UPDATE job_tasks SET runtime_owner_id = ? WHERE id = ?"#)
+                .bind(owner)
+                .bind(task_id)
+                .execute(&pool)
+                .await
+                .expect("assign runtime owner");
+        }
+
+        let failed = fail_running_tasks_on_shutdown(state.as_ref())
+            .await
+            .expect("fail running tasks on shutdown");
+        assert_eq!(failed, 1);
+
+        let own = sqlx::query(r#"SELECT status, error_message FROM job_tasks WHERE id = ?"#)
+            .bind("own-task")
+            .fetch_one(&pool)
+            .await
+            .expect("load own task");
+        assert_eq!(own.get::<String, _>("status"), STATUS_FAILED);
+        assert_eq!(
+            own.get::<Option<String>, _>("error_message").as_deref(),
+            Some(TASK_SERVER_SHUTDOWN_ERROR)
+        );
+
+        let foreign_status =
+            sqlx::query_scalar::<_, String>(r#"SELECT status FROM job_tasks WHERE id = ?"#)
+                .bind("foreign-task")
+                .fetch_one(&pool)
+                .await
+                .expect("load foreign task");
+        assert_eq!(foreign_status, STATUS_RUNNING);
+    }
+
+    #[tokio::test]
+    async fn process_task_fails_task_and_child_llm_calls_after_timeout() {
+        let pool = setup_pool().await;
+        let mut state = setup_state(pool.clone());
+        let limit = std::time::Duration::from_millis(50);
+        Arc::get_mut(&mut state)
+            .expect("state is not shared yet")
+            .config
+            .task_timeouts
+            .sync = limit;
+
+        seed_task(&pool, "slow-sync", TASK_SYNC_STARRED, STATUS_QUEUED, 0).await;
+        let task = claim_next_queued_task(state.as_ref())
+            .await
+            .expect("claim queued task")
+            .expect("task claimed");
+        seed_llm_call(
             &pool,
-            "sync-queued",
-            TASK_SYNC_SUBSCRIPTIONS,
-            STATUS_QUEUED,
-            0,
+            "slow-sync-call",
+            STATUS_RUNNING,
+            &Utc::now().to_rfc3339(),
         )
         .await;
+        sqlx::query(r#"UPDATE llm_calls SET parent_task_id = ? WHERE id = ?"#)
+            .bind("slow-sync")
+            .bind("slow-sync-call")
+            .execute(&pool)
+            .await
+            .expect("link llm call to task");
 
-        let claimed = claim_next_queued_task(state.as_ref())
+        process_task_with(state.clone(), task, |_, _, _, _| {
+            Box::pin(async {
+                tokio::time::sleep(std::time::Duration::from_secs(30)).await;
+                Ok(json!({"ok": true}))
+            })
+        })
+        .await
+        .expect("timeout is reported on the task, not the worker");
+
+        let task = sqlx::query(r#"SELECT status, error_message FROM job_tasks WHERE id = ?"#)
+            .bind("slow-sync")
+            .fetch_one(&pool)
             .await
-            .expect("claim queued task")
-            .expect("task claimed");
-        assert_eq!(claimed.id, "sync-queued");
-        assert_eq!(claimed.task_type, TASK_SYNC_SUBSCRIPTIONS);
+            .expect("load task");
+        assert_eq!(task.get::<String, _>("status"), STATUS_FAILED);
+        assert_eq!(
+            task.get::<Option<String>, _>("error_message"),
+            Some(task_timeout_error(limit))
+        );
+
+        let completed = sqlx::query_scalar::<_, String>(
+            r#"SELECT payload_json FROM job_task_events WHERE task_id = ? AND event_type = 'task.completed'"#,
+        )
+        .bind("slow-sync")
+        .fetch_one(&pool)
+        .await
+        .expect("load completed event");
+        let completed: Value = serde_json::from_str(&completed).expect("parse event payload");
+        assert_eq!(completed["status"], STATUS_FAILED);
+        assert_eq!(completed["error"], task_timeout_error(limit));
+
+        let call = sqlx::query(r#"SELECT status, error_text FROM llm_calls WHERE id = ?"#)
+            .bind("slow-sync-call")
+            .fetch_one(&pool)
+            .await
+            .expect("load llm call");
+        assert_eq!(call.get::<String, _>("status"), STATUS_FAILED);
+        assert_eq!(
+            call.get::<Option<String>, _>("error_text"),
+            Some(task_timeout_error(limit))
+        );
     }
 
     #[tokio::test]
-    async fn recover_runtime_state_marks_stale_running_tasks_failed() {
+    async fn recover_runtime_state_on_startup_keeps_live_foreign_owner_tasks_running() {
         let pool = setup_pool().await;
         let state = setup_state(pool.clone());
 
-        seed_task(&pool, "stale-task", TASK_SYNC_RELEASES, STATUS_RUNNING, 0).await;
+        seed_task(
+            &pool,
+            "startup-live-foreign-task",
+            TASK_SYNC_RELEASES,
+            STATUS_RUNNING,
+            0,
+        )
+        .await;
+        let now = Utc::now().to_rfc3339();
         sqlx::query(
             r#"
             UPDATE job_tasks
@@ -4393,797 +6371,1133 @@ mod tests {
             WHERE id = ?
             "#,
         )
-        .bind("old-runtime-owner")
-        .bind("2026-03-06T00:00:00Z")
-        .bind("2026-03-06T00:00:00Z")
-        .bind("stale-task")
+        .bind("other-runtime-owner")
+        .bind(now.as_str())
+        .bind(now.as_str())
+        .bind("startup-live-foreign-task")
         .execute(&pool)
         .await
-        .expect("mark task stale");
+        .expect("mark startup foreign-owner task live");
+        crate::runtime::upsert_runtime_owner_for_tests(
+            state.as_ref(),
+            "other-runtime-owner",
+            now.as_str(),
+        )
+        .await
+        .expect("upsert runtime owner");
 
-        recover_runtime_state(state.as_ref())
+        recover_runtime_state_on_startup(state.as_ref())
             .await
-            .expect("recover runtime state");
+            .expect("startup recover runtime state");
 
         let row = sqlx::query(
             r#"
-            SELECT status, error_message, runtime_owner_id, lease_heartbeat_at
+            SELECT status, runtime_owner_id, lease_heartbeat_at
             FROM job_tasks
             WHERE id = ?
             "#,
         )
-        .bind("stale-task")
+        .bind("startup-live-foreign-task")
         .fetch_one(&pool)
         .await
-        .expect("load recovered task");
+        .expect("load startup live foreign task");
 
-        assert_eq!(row.get::<String, _>("status"), STATUS_FAILED);
+        assert_eq!(row.get::<String, _>("status"), STATUS_RUNNING);
         assert_eq!(
-            row.get::<Option<String>, _>("error_message").as_deref(),
-            Some(crate::runtime::RUNTIME_LEASE_EXPIRED_ERROR)
+            row.get::<Option<String>, _>("runtime_owner_id").as_deref(),
+            Some("other-runtime-owner")
         );
-        assert_eq!(row.get::<Option<String>, _>("runtime_owner_id"), None);
-        assert_eq!(row.get::<Option<String>, _>("lease_heartbeat_at"), None);
+        assert_eq!(
+            row.get::<Option<String>, _>("lease_heartbeat_at")
+                .as_deref(),
+            Some(now.as_str())
+        );
+    }
 
-        let event_type = sqlx::query_scalar::<_, String>(
-            r#"
-            SELECT event_type
-            FROM job_task_events
-            WHERE task_id = ?
-            ORDER BY rowid DESC
-            LIMIT 1
-            "#,
+    #[tokio::test]
+    async fn translation_stream_cursor_defaults_for_empty_tables() {
+        let pool = setup_pool().await;
+
+        let cursor = load_translation_stream_cursor(&pool, "translation_requests")
+            .await
+            .expect("load empty translation cursor");
+
+        assert_eq!(cursor, TranslationStreamCursor::default());
+    }
+
+    #[tokio::test]
+    async fn translation_stream_rows_follow_updated_at_and_id_cursor() {
+        let pool = setup_pool().await;
+        seed_user(&pool, 1, "octo").await;
+        seed_translation_request(&pool, "req-1", "queued", "2026-03-07T00:00:00Z").await;
+        seed_translation_request(&pool, "req-2", "running", "2026-03-07T00:00:00Z").await;
+        seed_translation_request(&pool, "req-3", "completed", "2026-03-07T00:00:01Z").await;
+
+        let rows = load_translation_stream_rows(
+            &pool,
+            "translation_requests",
+            &TranslationStreamCursor {
+                updated_at: "2026-03-07T00:00:00Z".to_owned(),
+                entity_id: "req-1".to_owned(),
+            },
         )
-        .bind("stale-task")
-        .fetch_one(&pool)
         .await
-        .expect("load recovery event");
-        assert_eq!(event_type, "task.recovered_failed");
+        .expect("load translation rows from cursor");
+
+        let ids = rows.iter().map(|row| row.id.as_str()).collect::<Vec<_>>();
+        let statuses = rows
+            .iter()
+            .map(|row| row.status.as_str())
+            .collect::<Vec<_>>();
+
+        assert_eq!(ids, vec!["req-2", "req-3"]);
+        assert_eq!(statuses, vec!["running", "completed"]);
+    }
+
+    async fn task_event_seqs(pool: &SqlitePool, task_id: &str) -> Vec<i64> {
+        sqlx::query_scalar::<_, i64>(
+            r#"SELECT rowid FROM job_task_events WHERE task_id = ? ORDER BY rowid ASC"#,
+        )
+        .bind(task_id)
+        .fetch_all(pool)
+        .await
+        .expect("load task event seqs")
     }
 
     #[tokio::test]
-    async fn recover_runtime_state_keeps_live_current_owner_tasks_running() {
+    async fn task_sse_replays_events_after_last_event_id_then_streams_live_ones() {
         let pool = setup_pool().await;
         let state = setup_state(pool.clone());
+        let task_id = "task-sse-resume";
+        seed_task(&pool, task_id, TASK_SYNC_RELEASES, STATUS_RUNNING, 0).await;
+        for step in 1..=3 {
+            append_task_event(
+                state.as_ref(),
+                task_id,
+                "task.progress",
+                json!({ "step": step }),
+            )
+            .await
+            .expect("append task event");
+        }
+        let seqs = task_event_seqs(&pool, task_id).await;
 
-        seed_task(&pool, "live-task", TASK_SYNC_RELEASES, STATUS_RUNNING, 0).await;
-        let now = Utc::now().to_rfc3339();
-        sqlx::query(
-            r#"
-            UPDATE job_tasks
-            SET runtime_owner_id = ?, lease_heartbeat_at = ?, updated_at = ?
-            WHERE id = ?
-            "#,
+        let response = task_sse_response(
+            state.clone(),
+            task_id.to_owned(),
+            Some(seqs[1].to_string().as_str()),
+        );
+        let mut body = response.into_body().into_data_stream();
+        let mut text = String::new();
+        while !text.contains(r#"{"step":3}"#) {
+            let chunk = tokio::time::timeout(std::time::Duration::from_secs(5), body.next())
+                .await
+                .expect("replayed event arrives")
+                .expect("stream stays open")
+                .expect("read sse chunk");
+            text.push_str(std::str::from_utf8(&chunk).expect("utf8 sse chunk"));
+        }
+
+        append_task_event(
+            state.as_ref(),
+            task_id,
+            "task.progress",
+            json!({ "step": 4 }),
         )
-        .bind(state.runtime_owner_id.as_str())
-        .bind(now.as_str())
-        .bind(now.as_str())
-        .bind("live-task")
-        .execute(&pool)
         .await
-        .expect("mark task live");
+        .expect("append live task event");
+        sqlx::query("UPDATE job_tasks SET status = ? WHERE id = ?")
+            .bind(STATUS_SUCCEEDED)
+            .bind(task_id)
+            .execute(&pool)
+            .await
+            .expect("finish task");
+        while let Some(chunk) = tokio::time::timeout(std::time::Duration::from_secs(5), body.next())
+            .await
+            .expect("stream ends after terminal status")
+        {
+            text.push_str(std::str::from_utf8(&chunk.expect("read sse chunk")).expect("utf8"));
+        }
 
-        recover_runtime_state(state.as_ref())
+        let seqs = task_event_seqs(&pool, task_id).await;
+        assert!(text.contains("retry: 3000"));
+        assert!(!text.contains(r#"{"step":1}"#));
+        assert!(!text.contains(r#"{"step":2}"#));
+        let replayed = text
+            .find(&format!("id: {}\n", seqs[2]))
+            .expect("replayed event id");
+        let live = text
+            .find(&format!("id: {}\n", seqs[3]))
+            .expect("live event id");
+        assert!(replayed < live);
+    }
+
+    #[tokio::test]
+    async fn admin_stream_resume_stays_within_replay_window() {
+        let pool = setup_pool().await;
+        seed_task(
+            &pool,
+            "task-admin-resume",
+            TASK_SYNC_RELEASES,
+            STATUS_RUNNING,
+            0,
+        )
+        .await;
+        let stale = (Utc::now() - Duration::hours(2)).to_rfc3339();
+        let fresh = Utc::now().to_rfc3339();
+        for (index, created_at) in [&stale, &stale, &fresh, &fresh].into_iter().enumerate() {
+            sqlx::query(
+                r#"
+                INSERT INTO job_task_events (id, task_id, event_type, payload_json, created_at)
+                VALUES (?, 'task-admin-resume', 'task.progress', '{}', ?)
+                "#,
+            )
+            .bind(format!("admin-resume-event-{index}"))
+            .bind(created_at.as_str())
+            .execute(&pool)
             .await
-            .expect("recover runtime state");
+            .expect("seed admin stream event");
+        }
+        let seqs = task_event_seqs(&pool, "task-admin-resume").await;
 
-        let status =
-            sqlx::query_scalar::<_, String>(r#"SELECT status FROM job_tasks WHERE id = ?"#)
-                .bind("live-task")
-                .fetch_one(&pool)
-                .await
-                .expect("load live task status");
-        assert_eq!(status, STATUS_RUNNING);
+        assert_eq!(
+            resume_event_seq(&pool, "job_task_events", 0).await,
+            seqs[1],
+            "events older than the replay window are skipped"
+        );
+        assert_eq!(
+            resume_event_seq(&pool, "job_task_events", seqs[2]).await,
+            seqs[2]
+        );
+        assert_eq!(
+            resume_event_seq(&pool, "job_task_events", seqs[3] + 50).await,
+            seqs[3]
+        );
+        assert_eq!(parse_admin_stream_event_id("12:7"), Some((12, 7)));
+        assert_eq!(parse_admin_stream_event_id("job-abc"), None);
+    }
+
+    #[tokio::test]
+    async fn execute_sync_all_task_continues_notifications_when_social_sync_fails() {
+        let pool = setup_pool().await;
+        let state = setup_state(pool.clone());
+        seed_task(
+            &pool,
+            "task-sync-all-social-fail",
+            TASK_SYNC_ALL,
+            STATUS_RUNNING,
+            0,
+        )
+        .await;
+
+        let result = execute_sync_all_task_with(
+            state.as_ref(),
+            "task-sync-all-social-fail",
+            "1",
+            |_, _| Box::pin(async { Ok(sync::SyncStarredResult { repos: 4 }) }),
+            |_, _| {
+                Box::pin(async {
+                    Ok(sync::SyncReleasesResult {
+                        repos: 2,
+                        releases: 5,
+                        skipped_muted: 0,
+                        repos_checked: 0,
+                        repos_skipped_not_modified: 0,
+                        new_releases: 0,
+                        rate_limit_remaining: None,
+                        stopped_early_rate_limit: false,
+                        failed_repos: Vec::new(),
+                        retried_requests: 0,
+                    })
+                })
+            },
+            |_, _| Box::pin(async { Err(anyhow::anyhow!("social unavailable")) }),
+            |_, _| {
+                Box::pin(async {
+                    Ok(sync::SyncNotificationsResult {
+                        notifications: 7,
+                        since: Some("2026-03-07T00:00:00Z".to_owned()),
+                        retried_requests: 0,
+                    })
+                })
+            },
+        )
+        .await
+        .expect("sync_all result");
+
+        assert_eq!(result["starred"]["repos"], json!(4));
+        assert_eq!(result["releases"]["releases"], json!(5));
+        assert_eq!(result["social"]["events"], json!(0));
+        assert_eq!(result["notifications"]["notifications"], json!(7));
+        assert_eq!(result["social_error"], json!("social unavailable"));
+    }
+
+    async fn setup_pool() -> SqlitePool {
+        let database_path = std::env::temp_dir().join(format!(
+            "octo-rill-test-{}.db",
+            crate::local_id::generate_local_id(),
+        ));
+        let options = SqliteConnectOptions::new()
+            .filename(&database_path)
+            .create_if_missing(true);
+        let pool = SqlitePoolOptions::new()
+            .max_connections(1)
+            .connect_with(options)
+            .await
+            .expect("create sqlite memory db");
+        sqlx::migrate!("./migrations")
+            .run(&pool)
+            .await
+            .expect("run migrations");
+        pool
+    }
+
+    async fn setup_pool_with_max_connections_and_wal(
+        max_connections: u32,
+        busy_timeout: std::time::Duration,
+    ) -> SqlitePool {
+        let database_path = std::env::temp_dir().join(format!(
+            "octo-rill-jobs-busy-test-{}.db",
+            crate::local_id::generate_local_id(),
+        ));
+        let options = SqliteConnectOptions::new()
+            .filename(&database_path)
+            .create_if_missing(true)
+            .journal_mode(SqliteJournalMode::Wal)
+            .busy_timeout(busy_timeout);
+        let pool = SqlitePoolOptions::new()
+            .max_connections(max_connections)
+            .connect_with(options)
+            .await
+            .expect("create sqlite db");
+        sqlx::migrate!("./migrations")
+            .run(&pool)
+            .await
+            .expect("run migrations");
+        crate::api::ensure_owned_repo_visual_columns(&pool)
+            .await
+            .expect("ensure owned repo visual columns");
+        pool
     }
 
-    #[tokio::test]
-    async fn recover_runtime_state_keeps_live_foreign_owner_tasks_running() {
-        let pool = setup_pool().await;
-        let state = setup_state(pool.clone());
+    fn setup_state(pool: SqlitePool) -> Arc<AppState> {
+        Arc::new(AppState::for_tests(pool, AppConfig::for_tests()))
+    }
 
-        seed_task(
-            &pool,
-            "foreign-live-task",
-            TASK_SYNC_RELEASES,
-            STATUS_RUNNING,
-            0,
-        )
-        .await;
-        let now = Utc::now().to_rfc3339();
+    async fn seed_user(pool: &SqlitePool, id: i64, login: &str) {
+        let now = "2026-03-07T00:00:00Z";
         sqlx::query(
             r#"
-            UPDATE job_tasks
-            SET runtime_owner_id = ?, lease_heartbeat_at = ?, updated_at = ?
-            WHERE id = ?
+            INSERT INTO users (id, github_user_id, login, created_at, updated_at)
+            VALUES (?, ?, ?, ?, ?)
             "#,
         )
-        .bind("other-runtime-owner")
-        .bind(now.as_str())
-        .bind(now.as_str())
-        .bind("foreign-live-task")
-        .execute(&pool)
+        .bind(id.to_string())
+        .bind(40_000_000_i64 + id)
+        .bind(login)
+        .bind(now)
+        .bind(now)
+        .execute(pool)
         .await
-        .expect("mark foreign-owner task live");
+        .expect("seed user");
+    }
 
-        recover_runtime_state(state.as_ref())
-            .await
-            .expect("recover runtime state");
+    async fn seed_translation_request(
+        pool: &SqlitePool,
+        request_id: &str,
+        status: &str,
+        updated_at: &str,
+    ) {
+        let started_at = match status {
+            "queued" => None,
+            _ => Some(updated_at),
+        };
+        let finished_at = match status {
+            "completed" | "failed" => Some(updated_at),
+            _ => None,
+        };
+        let result_status = match status {
+            "completed" => Some("ready"),
+            "failed" => Some("error"),
+            _ => None,
+        };
+        let error_text = matches!(status, "failed").then_some("boom");
 
-        let row = sqlx::query(
+        sqlx::query(
             r#"
-            SELECT status, runtime_owner_id, lease_heartbeat_at
-            FROM job_tasks
-            WHERE id = ?
+            INSERT INTO translation_requests (
+              id, mode, source, request_origin, requested_by, scope_user_id, producer_ref, kind,
+              variant, entity_id, target_lang, max_wait_ms, source_hash, source_blocks_json,
+              target_slots_json, status, result_status, title_zh, summary_md, body_md, error_text,
+              created_at, started_at, finished_at, updated_at
+            ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
             "#,
         )
-        .bind("foreign-live-task")
-        .fetch_one(&pool)
+        .bind(request_id)
+        .bind("async")
+        .bind("feed.auto_translate")
+        .bind("user")
+        .bind(1_i64)
+        .bind(1_i64)
+        .bind(format!("feed.auto_translate:release:{request_id}"))
+        .bind("release_summary")
+        .bind("feed_card")
+        .bind(request_id)
+        .bind("zh-CN")
+        .bind(1000_i64)
+        .bind("hash")
+        .bind("[]")
+        .bind("[]")
+        .bind(status)
+        .bind(result_status)
+        .bind(matches!(status, "completed").then_some("标题"))
+        .bind(matches!(status, "completed").then_some("摘要"))
+        .bind(Option::<&str>::None)
+        .bind(error_text)
+        .bind(updated_at)
+        .bind(started_at)
+        .bind(finished_at)
+        .bind(updated_at)
+        .execute(pool)
         .await
-        .expect("load foreign-owner task status");
-
-        assert_eq!(row.get::<String, _>("status"), STATUS_RUNNING);
-        assert_eq!(
-            row.get::<Option<String>, _>("runtime_owner_id").as_deref(),
-            Some("other-runtime-owner")
-        );
-        assert_eq!(
-            row.get::<Option<String>, _>("lease_heartbeat_at")
-                .as_deref(),
-            Some(now.as_str())
-        );
+        .expect("seed translation request");
     }
 
     #[tokio::test]
-    async fn recover_runtime_state_on_startup_reclaims_foreign_owner_tasks_without_live_owner_lease()
-     {
+    async fn enqueue_task_waits_for_foreground_writer_backpressure() {
         let pool = setup_pool().await;
-        let state = setup_state(pool.clone());
-
-        seed_task(
-            &pool,
-            "startup-foreign-task",
-            TASK_SYNC_RELEASES,
-            STATUS_RUNNING,
-            0,
-        )
-        .await;
-        let now = Utc::now().to_rfc3339();
-        sqlx::query(
-            r#"
-            UPDATE job_tasks
-            SET runtime_owner_id = ?, lease_heartbeat_at = ?, updated_at = ?
-            WHERE id = ?
-            "#,
-        )
-        .bind("other-runtime-owner")
-        .bind(now.as_str())
-        .bind(now.as_str())
-        .bind("startup-foreign-task")
-        .execute(&pool)
-        .await
-        .expect("mark startup foreign-owner task live");
-
-        recover_runtime_state_on_startup(state.as_ref())
+        let state = setup_state(pool);
+        let held_writer = state
+            .sqlite_writer
+            .acquire_with_priority(
+                "test_background_pressure",
+                crate::sqlite_write::SqliteWritePriority::Background,
+            )
             .await
-            .expect("startup recover runtime state");
+            .expect("hold sqlite writer");
 
-        let row = sqlx::query(
-            r#"
-            SELECT status, error_message, runtime_owner_id, lease_heartbeat_at
-            FROM job_tasks
-            WHERE id = ?
-            "#,
-        )
-        .bind("startup-foreign-task")
-        .fetch_one(&pool)
-        .await
-        .expect("load recovered startup task");
+        let enqueue_state = state.clone();
+        let enqueue = tokio::spawn(async move {
+            enqueue_task(
+                enqueue_state.as_ref(),
+                NewTask {
+                    task_type: TASK_SYNC_ALL.to_owned(),
+                    payload: json!({"trigger": "test"}),
+                    source: "manual".to_owned(),
+                    requested_by: None,
+                    parent_task_id: None,
+                    priority: TaskPriority::High,
+                },
+            )
+            .await
+        });
 
-        assert_eq!(row.get::<String, _>("status"), STATUS_FAILED);
-        assert_eq!(
-            row.get::<Option<String>, _>("error_message").as_deref(),
-            Some(crate::runtime::RUNTIME_LEASE_EXPIRED_ERROR)
+        tokio::time::sleep(std::time::Duration::from_millis(20)).await;
+        assert!(
+            !enqueue.is_finished(),
+            "enqueue should wait behind the coordinated writer instead of bypassing it"
         );
-        assert_eq!(row.get::<Option<String>, _>("runtime_owner_id"), None);
-        assert_eq!(row.get::<Option<String>, _>("lease_heartbeat_at"), None);
+
+        drop(held_writer);
+        let task = enqueue
+            .await
+            .expect("join enqueue")
+            .expect("enqueue after writer release");
+        assert_eq!(task.task_type, TASK_SYNC_ALL);
+        assert_eq!(task.status, STATUS_QUEUED);
     }
 
     #[tokio::test]
-    async fn recover_runtime_state_on_startup_keeps_live_foreign_owner_tasks_running() {
-        let pool = setup_pool().await;
+    async fn update_daily_brief_hour_slot_dispatch_waits_for_sqlite_write_lock() {
+        let pool =
+            setup_pool_with_max_connections_and_wal(2, std::time::Duration::from_millis(10)).await;
         let state = setup_state(pool.clone());
 
-        seed_task(
-            &pool,
-            "startup-live-foreign-task",
-            TASK_SYNC_RELEASES,
-            STATUS_RUNNING,
-            0,
-        )
-        .await;
-        let now = Utc::now().to_rfc3339();
-        sqlx::query(
-            r#"
-            UPDATE job_tasks
-            SET runtime_owner_id = ?, lease_heartbeat_at = ?, updated_at = ?
-            WHERE id = ?
-            "#,
-        )
-        .bind("other-runtime-owner")
-        .bind(now.as_str())
-        .bind(now.as_str())
-        .bind("startup-live-foreign-task")
-        .execute(&pool)
-        .await
-        .expect("mark startup foreign-owner task live");
-        crate::runtime::upsert_runtime_owner_for_tests(
-            state.as_ref(),
-            "other-runtime-owner",
-            now.as_str(),
-        )
-        .await
-        .expect("upsert runtime owner");
+        let mut hold_tx = pool
+            .begin_with("BEGIN IMMEDIATE")
+            .await
+            .expect("begin hold writer tx");
+        sqlx::query("UPDATE daily_brief_hour_slots SET updated_at = ? WHERE hour_utc = ?")
+            .bind("2026-04-13T00:00:01Z")
+            .bind(0_i64)
+            .execute(&mut *hold_tx)
+            .await
+            .expect("hold sqlite writer lock");
 
-        recover_runtime_state_on_startup(state.as_ref())
+        let state_for_update = state.clone();
+        let update_task = tokio::spawn(async move {
+            update_daily_brief_hour_slot_dispatch(
+                state_for_update.as_ref(),
+                0,
+                "2026-04-13T00:00:00Z",
+            )
+            .await
+        });
+
+        tokio::time::sleep(std::time::Duration::from_millis(70)).await;
+        hold_tx.rollback().await.expect("rollback hold writer tx");
+
+        update_task
             .await
-            .expect("startup recover runtime state");
+            .expect("join slot dispatch task")
+            .expect("slot dispatch update should succeed after writer release");
 
-        let row = sqlx::query(
+        let dispatch_at: Option<String> = sqlx::query_scalar(
             r#"
-            SELECT status, runtime_owner_id, lease_heartbeat_at
-            FROM job_tasks
-            WHERE id = ?
+            SELECT last_dispatch_at
+            FROM daily_brief_hour_slots
+            WHERE hour_utc = ?
             "#,
         )
-        .bind("startup-live-foreign-task")
+        .bind(0_i64)
         .fetch_one(&pool)
         .await
-        .expect("load startup live foreign task");
-
-        assert_eq!(row.get::<String, _>("status"), STATUS_RUNNING);
-        assert_eq!(
-            row.get::<Option<String>, _>("runtime_owner_id").as_deref(),
-            Some("other-runtime-owner")
-        );
-        assert_eq!(
-            row.get::<Option<String>, _>("lease_heartbeat_at")
-                .as_deref(),
-            Some(now.as_str())
-        );
+        .expect("load slot dispatch state");
+        assert_eq!(dispatch_at.as_deref(), Some("2026-04-13T00:00:00Z"));
     }
 
     #[tokio::test]
-    async fn translation_stream_cursor_defaults_for_empty_tables() {
-        let pool = setup_pool().await;
+    async fn upsert_dispatch_state_waits_for_sqlite_write_lock() {
+        let pool =
+            setup_pool_with_max_connections_and_wal(2, std::time::Duration::from_millis(10)).await;
+        let state = setup_state(pool.clone());
 
-        let cursor = load_translation_stream_cursor(&pool, "translation_requests")
+        let mut hold_tx = pool
+            .begin_with("BEGIN IMMEDIATE")
             .await
-            .expect("load empty translation cursor");
+            .expect("begin hold writer tx");
+        sqlx::query("UPDATE daily_brief_hour_slots SET updated_at = ? WHERE hour_utc = ?")
+            .bind("2026-04-13T00:00:01Z")
+            .bind(0_i64)
+            .execute(&mut *hold_tx)
+            .await
+            .expect("hold sqlite writer lock");
 
-        assert_eq!(cursor, TranslationStreamCursor::default());
-    }
+        let state_for_upsert = state.clone();
+        let dispatch_task = tokio::spawn(async move {
+            upsert_dispatch_state(
+                state_for_upsert.as_ref(),
+                "test.schedule",
+                "interval:10:1772805600",
+                "task-dispatch-state",
+            )
+            .await
+        });
 
-    #[tokio::test]
-    async fn translation_stream_rows_follow_updated_at_and_id_cursor() {
-        let pool = setup_pool().await;
-        seed_user(&pool, 1, "octo").await;
-        seed_translation_request(&pool, "req-1", "queued", "2026-03-07T00:00:00Z").await;
-        seed_translation_request(&pool, "req-2", "running", "2026-03-07T00:00:00Z").await;
-        seed_translation_request(&pool, "req-3", "completed", "2026-03-07T00:00:01Z").await;
+        tokio::time::sleep(std::time::Duration::from_millis(70)).await;
+        hold_tx.rollback().await.expect("rollback hold writer tx");
 
-        let rows = load_translation_stream_rows(
-            &pool,
-            "translation_requests",
-            &TranslationStreamCursor {
-                updated_at: "2026-03-07T00:00:00Z".to_owned(),
-                entity_id: "req-1".to_owned(),
-            },
+        dispatch_task
+            .await
+            .expect("join dispatch state task")
+            .expect("dispatch state upsert should succeed after writer release");
+
+        let row = sqlx::query_as::<_, (String, String)>(
+            r#"
+            SELECT last_dispatch_key, last_task_id
+            FROM scheduled_task_dispatch_state
+            WHERE schedule_name = ?
+            "#,
         )
+        .bind("test.schedule")
+        .fetch_one(&pool)
         .await
-        .expect("load translation rows from cursor");
-
-        let ids = rows.iter().map(|row| row.id.as_str()).collect::<Vec<_>>();
-        let statuses = rows
-            .iter()
-            .map(|row| row.status.as_str())
-            .collect::<Vec<_>>();
-
-        assert_eq!(ids, vec!["req-2", "req-3"]);
-        assert_eq!(statuses, vec!["running", "completed"]);
+        .expect("load dispatch state");
+        assert_eq!(row.0, "interval:10:1772805600");
+        assert_eq!(row.1, "task-dispatch-state");
     }
 
     #[tokio::test]
-    async fn execute_sync_all_task_continues_notifications_when_social_sync_fails() {
-        let pool = setup_pool().await;
+    async fn mark_brief_generation_source_waits_for_sqlite_write_lock() {
+        let pool =
+            setup_pool_with_max_connections_and_wal(2, std::time::Duration::from_millis(10)).await;
         let state = setup_state(pool.clone());
-        seed_task(
-            &pool,
-            "task-sync-all-social-fail",
-            TASK_SYNC_ALL,
-            STATUS_RUNNING,
-            0,
-        )
-        .await;
-
-        let result = execute_sync_all_task_with(
-            state.as_ref(),
-            "task-sync-all-social-fail",
-            "1",
-            |_, _| Box::pin(async { Ok(sync::SyncStarredResult { repos: 4 }) }),
-            |_, _| {
-                Box::pin(async {
-                    Ok(sync::SyncReleasesResult {
-                        repos: 2,
-                        releases: 5,
-                    })
-                })
-            },
-            |_, _| Box::pin(async { Err(anyhow::anyhow!("social unavailable")) }),
-            |_, _| {
-                Box::pin(async {
-                    Ok(sync::SyncNotificationsResult {
-                        notifications: 7,
-                        since: Some("2026-03-07T00:00:00Z".to_owned()),
-                    })
-                })
-            },
+        seed_user(&pool, 90_100, "brief-writer-lock-user").await;
+        sqlx::query(
+            r#"
+            INSERT INTO briefs (
+              id, user_id, date, window_start_utc, window_end_utc,
+              effective_time_zone, effective_local_boundary, generation_source,
+              content_markdown, created_at, updated_at
+            ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+            "#,
         )
+        .bind("brief-writer-lock")
+        .bind("90100")
+        .bind("2026-04-13")
+        .bind("2026-04-12T00:00:00Z")
+        .bind("2026-04-13T00:00:00Z")
+        .bind("Asia/Shanghai")
+        .bind("08:00")
+        .bind("legacy")
+        .bind("placeholder")
+        .bind("2026-04-13T00:00:00Z")
+        .bind("2026-04-13T00:00:00Z")
+        .execute(&pool)
         .await
-        .expect("sync_all result");
-
-        assert_eq!(result["starred"]["repos"], json!(4));
-        assert_eq!(result["releases"]["releases"], json!(5));
-        assert_eq!(result["social"]["events"], json!(0));
-        assert_eq!(result["notifications"]["notifications"], json!(7));
-        assert_eq!(result["social_error"], json!("social unavailable"));
-    }
+        .expect("seed brief");
 
-    async fn setup_pool() -> SqlitePool {
-        let database_path = std::env::temp_dir().join(format!(
-            "octo-rill-test-{}.db",
-            crate::local_id::generate_local_id(),
-        ));
-        let options = SqliteConnectOptions::new()
-            .filename(&database_path)
-            .create_if_missing(true);
-        let pool = SqlitePoolOptions::new()
-            .max_connections(1)
-            .connect_with(options)
+        let mut hold_tx = pool
+            .begin_with("BEGIN IMMEDIATE")
             .await
-            .expect("create sqlite memory db");
-        sqlx::migrate!("./migrations")
-            .run(&pool)
+            .expect("begin hold writer tx");
+        sqlx::query("UPDATE briefs SET updated_at = ? WHERE id = ?")
+            .bind("2026-04-13T00:00:01Z")
+            .bind("brief-writer-lock")
+            .execute(&mut *hold_tx)
             .await
-            .expect("run migrations");
-        pool
-    }
+            .expect("hold sqlite writer lock");
 
-    async fn setup_pool_with_max_connections_and_wal(
-        max_connections: u32,
-        busy_timeout: std::time::Duration,
-    ) -> SqlitePool {
-        let database_path = std::env::temp_dir().join(format!(
-            "octo-rill-jobs-busy-test-{}.db",
-            crate::local_id::generate_local_id(),
-        ));
-        let options = SqliteConnectOptions::new()
-            .filename(&database_path)
-            .create_if_missing(true)
-            .journal_mode(SqliteJournalMode::Wal)
-            .busy_timeout(busy_timeout);
-        let pool = SqlitePoolOptions::new()
-            .max_connections(max_connections)
-            .connect_with(options)
-            .await
-            .expect("create sqlite db");
-        sqlx::migrate!("./migrations")
-            .run(&pool)
+        let state_for_mark = state.clone();
+        let mark_task = tokio::spawn(async move {
+            mark_brief_generation_source(
+                state_for_mark.as_ref(),
+                "brief-writer-lock",
+                "content_refresh_failed",
+                "2026-04-13T00:00:02Z",
+                "test_brief_failure_mark",
+            )
             .await
-            .expect("run migrations");
-        crate::api::ensure_owned_repo_visual_columns(&pool)
+        });
+
+        tokio::time::sleep(std::time::Duration::from_millis(70)).await;
+        hold_tx.rollback().await.expect("rollback hold writer tx");
+
+        mark_task
             .await
-            .expect("ensure owned repo visual columns");
-        pool
-    }
+            .expect("join brief mark task")
+            .expect("brief failure mark should succeed after writer release");
 
-    fn setup_state(pool: SqlitePool) -> Arc<AppState> {
-        let encryption_key =
-            EncryptionKey::from_base64("AAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAA=")
-                .expect("build encryption key");
-        let config = AppConfig {
-            bind_addr: "127.0.0.1:58090"
-                .parse::<SocketAddr>()
-                .expect("parse bind addr"),
-            public_base_url: Url::parse("http://127.0.0.1:58090").expect("parse public base url"),
-            database_url: "sqlite::memory:".to_owned(),
-            sqlite_pool_max_connections: 8,
-            static_dir: None,
-            task_log_dir: std::env::temp_dir().join("octo-rill-task-logs-jobs-tests"),
-            job_worker_concurrency: 4,
-            encryption_key: encryption_key.clone(),
-            github: GitHubOAuthConfig {
-                client_id: "test-client-id".to_owned(),
-                client_secret: "test-client-secret".to_owned(),
-                redirect_url: Url::parse("http://127.0.0.1:58090/auth/callback")
-                    .expect("parse github redirect"),
-            },
-            linuxdo: None,
-            ai: None,
-            ai_max_concurrency: 1,
-            ai_daily_at_local: None,
-            app_default_time_zone: crate::briefs::DEFAULT_DAILY_BRIEF_TIME_ZONE.to_owned(),
-            logging: crate::observability::LoggingThresholds::default(),
-        };
-        let github_oauth = build_oauth_client(&config).expect("build oauth client");
-        let webauthn = crate::state::build_webauthn(&config).expect("build webauthn");
-        Arc::new(AppState {
-            llm_scheduler: Arc::new(crate::ai::LlmScheduler::new(config.ai_max_concurrency)),
-            translation_scheduler: Arc::new(
-                crate::translations::TranslationSchedulerController::new(
-                    crate::translations::TranslationRuntimeConfig::default(),
-                ),
-            ),
-            config,
-            pool,
-            sqlite_writer: crate::sqlite_write::SqliteWriteCoordinator::new(),
-            http: reqwest::Client::new(),
-            github_rest_http: reqwest::Client::new(),
-            github_rest_api_base: Url::parse("https://api.github.com/")
-                .expect("parse github rest api base"),
-            github_graphql_url: Url::parse("https://api.github.com/graphql")
-                .expect("parse github graphql url"),
-            github_oauth,
-            linuxdo_oauth: None,
-            webauthn,
-            encryption_key,
-            runtime_owner_id: "jobs-test-runtime-owner".to_owned(),
-        })
+        let generation_source: String = sqlx::query_scalar(
+            r#"
+            SELECT generation_source
+            FROM briefs
+            WHERE id = ?
+            "#,
+        )
+        .bind("brief-writer-lock")
+        .fetch_one(&pool)
+        .await
+        .expect("load brief generation source");
+        assert_eq!(generation_source, "content_refresh_failed");
     }
 
-    async fn seed_user(pool: &SqlitePool, id: i64, login: &str) {
-        let now = "2026-03-07T00:00:00Z";
+    async fn seed_task(
+        pool: &SqlitePool,
+        task_id: &str,
+        task_type: &str,
+        status: &str,
+        offset_seconds: i64,
+    ) {
+        let created_at = format!("2026-03-06T00:00:{offset_seconds:02}Z");
         sqlx::query(
             r#"
-            INSERT INTO users (id, github_user_id, login, created_at, updated_at)
-            VALUES (?, ?, ?, ?, ?)
+            INSERT INTO job_tasks (
+              id,
+              task_type,
+              status,
+              source,
+              requested_by,
+              parent_task_id,
+              payload_json,
+              result_json,
+              error_message,
+              cancel_requested,
+              created_at,
+              started_at,
+              finished_at,
+              updated_at,
+              log_file_path
+            ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
             "#,
         )
-        .bind(id.to_string())
-        .bind(40_000_000_i64 + id)
-        .bind(login)
-        .bind(now)
-        .bind(now)
+        .bind(task_id)
+        .bind(task_type)
+        .bind(status)
+        .bind("test")
+        .bind(Option::<i64>::None)
+        .bind(Option::<String>::None)
+        .bind("{}")
+        .bind(Option::<String>::None)
+        .bind(Option::<String>::None)
+        .bind(0_i64)
+        .bind(created_at.as_str())
+        .bind((status == STATUS_RUNNING).then_some(created_at.as_str()))
+        .bind(Option::<String>::None)
+        .bind(created_at.as_str())
+        .bind(Option::<String>::None)
         .execute(pool)
         .await
-        .expect("seed user");
+        .expect("seed task");
     }
 
-    async fn seed_translation_request(
-        pool: &SqlitePool,
-        request_id: &str,
-        status: &str,
-        updated_at: &str,
-    ) {
-        let started_at = match status {
-            "queued" => None,
-            _ => Some(updated_at),
-        };
-        let finished_at = match status {
-            "completed" | "failed" => Some(updated_at),
-            _ => None,
-        };
-        let result_status = match status {
-            "completed" => Some("ready"),
-            "failed" => Some("error"),
-            _ => None,
-        };
-        let error_text = matches!(status, "failed").then_some("boom");
-
+    async fn seed_llm_call(pool: &SqlitePool, call_id: &str, status: &str, created_at: &str) {
         sqlx::query(
             r#"
-            INSERT INTO translation_requests (
-              id, mode, source, request_origin, requested_by, scope_user_id, producer_ref, kind,
-              variant, entity_id, target_lang, max_wait_ms, source_hash, source_blocks_json,
-              target_slots_json, status, result_status, title_zh, summary_md, body_md, error_text,
-              created_at, started_at, finished_at, updated_at
-            ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+            INSERT INTO llm_calls (
+              id, status, source, model, max_tokens, duration_ms,
+              prompt_text, response_text, input_messages_json, output_messages_json,
+              input_tokens, output_tokens, total_tokens, created_at, finished_at, updated_at
+            ) VALUES (?, ?, 'test', 'test-model', 256, 1200, 'prompt', 'response', '[]', '[]',
+              10, 20, 30, ?, ?, ?)
             "#,
         )
-        .bind(request_id)
-        .bind("async")
-        .bind("feed.auto_translate")
-        .bind("user")
-        .bind(1_i64)
-        .bind(1_i64)
-        .bind(format!("feed.auto_translate:release:{request_id}"))
-        .bind("release_summary")
-        .bind("feed_card")
-        .bind(request_id)
-        .bind("zh-CN")
-        .bind(1000_i64)
-        .bind("hash")
-        .bind("[]")
-        .bind("[]")
+        .bind(call_id)
         .bind(status)
-        .bind(result_status)
-        .bind(matches!(status, "completed").then_some("标题"))
-        .bind(matches!(status, "completed").then_some("摘要"))
-        .bind(Option::<&str>::None)
-        .bind(error_text)
-        .bind(updated_at)
-        .bind(started_at)
-        .bind(finished_at)
-        .bind(updated_at)
+        .bind(created_at)
+        .bind((status != STATUS_RUNNING).then_some(created_at))
+        .bind(created_at)
         .execute(pool)
         .await
-        .expect("seed translation request");
+        .expect("seed llm call");
     }
 
     #[tokio::test]
-    async fn enqueue_task_waits_for_foreground_writer_backpressure() {
+    async fn prune_task_history_only_touches_expired_rows() {
         let pool = setup_pool().await;
-        let state = setup_state(pool);
-        let held_writer = state
-            .sqlite_writer
-            .acquire_with_priority(
-                "test_background_pressure",
-                crate::sqlite_write::SqliteWritePriority::Background,
-            )
+        let state = setup_state(pool.clone());
+        let now = Utc
+            .with_ymd_and_hms(2026, 4, 20, 12, 0, 0)
+            .single()
+            .expect("valid datetime");
+
+        seed_task(&pool, "old-done", TASK_SYNC_STARRED, STATUS_SUCCEEDED, 0).await;
+        seed_task(&pool, "old-failed", TASK_SYNC_STARRED, STATUS_FAILED, 1).await;
+        seed_task(&pool, "old-running", TASK_SYNC_STARRED, STATUS_RUNNING, 2).await;
+        seed_task(&pool, "recent-done", TASK_SYNC_STARRED, STATUS_SUCCEEDED, 3).await;
+        let recent_at = (now - Duration::days(2)).to_rfc3339();
+        sqlx::query("UPDATE job_tasks SET created_at = ?, updated_at = ? WHERE id = 'recent-done'")
+            .bind(recent_at.as_str())
+            .bind(recent_at.as_str())
+            .execute(&pool)
             .await
-            .expect("hold sqlite writer");
+            .expect("refresh recent task");
+        for task_id in ["old-done", "old-failed", "recent-done"] {
+            append_task_event(state.as_ref(), task_id, "task.completed", json!({}))
+                .await
+                .expect("seed task event");
+        }
 
-        let enqueue_state = state.clone();
-        let enqueue = tokio::spawn(async move {
-            enqueue_task(
-                enqueue_state.as_ref(),
-                NewTask {
-                    task_type: TASK_SYNC_ALL.to_owned(),
-                    payload: json!({"trigger": "test"}),
-                    source: "manual".to_owned(),
-                    requested_by: None,
-                    parent_task_id: None,
-                },
-            )
+        let log_path = std::env::temp_dir().join(format!(
+            "octo-rill-prune-{}.log",
+            crate::local_id::generate_local_id()
+        ));
+        std::fs::write(&log_path, "log").expect("write task log");
+        sqlx::query("UPDATE job_tasks SET log_file_path = ? WHERE id = 'old-done'")
+            .bind(log_path.to_string_lossy().as_ref())
+            .execute(&pool)
             .await
-        });
+            .expect("attach task log");
 
-        tokio::time::sleep(std::time::Duration::from_millis(20)).await;
-        assert!(
-            !enqueue.is_finished(),
-            "enqueue should wait behind the coordinated writer instead of bypassing it"
+        let old_call_at = (now - Duration::days(10)).to_rfc3339();
+        let recent_call_at = (now - Duration::days(1)).to_rfc3339();
+        seed_llm_call(&pool, "call-old", STATUS_SUCCEEDED, old_call_at.as_str()).await;
+        seed_llm_call(
+            &pool,
+            "call-old-running",
+            STATUS_RUNNING,
+            old_call_at.as_str(),
+        )
+        .await;
+        seed_llm_call(
+            &pool,
+            "call-recent",
+            STATUS_SUCCEEDED,
+            recent_call_at.as_str(),
+        )
+        .await;
+
+        let stats = prune_task_history(state.as_ref(), now)
+            .await
+            .expect("prune task history");
+        assert_eq!(stats.task_retention_days, 30);
+        assert_eq!(stats.deleted_tasks, 2);
+        assert_eq!(stats.deleted_task_events, 2);
+        assert_eq!(stats.removed_log_files, 1);
+        assert_eq!(stats.truncated_llm_calls, 1);
+        assert!(!log_path.exists());
+
+        let remaining = sqlx::query_scalar::<_, String>("SELECT id FROM job_tasks ORDER BY id")
+            .fetch_all(&pool)
+            .await
+            .expect("load remaining tasks");
+        assert_eq!(remaining, vec!["old-running", "recent-done"]);
+        let remaining_events = sqlx::query_scalar::<_, String>(
+            "SELECT DISTINCT task_id FROM job_task_events ORDER BY task_id",
+        )
+        .fetch_all(&pool)
+        .await
+        .expect("load remaining events");
+        assert_eq!(remaining_events, vec!["recent-done"]);
+
+        let calls =
+            sqlx::query_as::<_, (String, String, Option<String>, Option<i64>, Option<i64>)>(
+                r#"
+            SELECT id, prompt_text, response_text, total_tokens, duration_ms
+            FROM llm_calls
+            ORDER BY id
+            "#,
+            )
+            .fetch_all(&pool)
+            .await
+            .expect("load llm calls");
+        assert_eq!(
+            calls,
+            vec![
+                (
+                    "call-old".to_owned(),
+                    String::new(),
+                    None,
+                    Some(30),
+                    Some(1200)
+                ),
+                (
+                    "call-old-running".to_owned(),
+                    "prompt".to_owned(),
+                    Some("response".to_owned()),
+                    Some(30),
+                    Some(1200),
+                ),
+                (
+                    "call-recent".to_owned(),
+                    "prompt".to_owned(),
+                    Some("response".to_owned()),
+                    Some(30),
+                    Some(1200),
+                ),
+            ]
         );
 
-        drop(held_writer);
-        let task = enqueue
+        let second = prune_task_history(state.as_ref(), now)
             .await
-            .expect("join enqueue")
-            .expect("enqueue after writer release");
-        assert_eq!(task.task_type, TASK_SYNC_ALL);
-        assert_eq!(task.status, STATUS_QUEUED);
+            .expect("prune again");
+        assert_eq!(second.deleted_tasks, 0);
+        assert_eq!(second.truncated_llm_calls, 0);
     }
 
     #[tokio::test]
-    async fn update_daily_brief_hour_slot_dispatch_waits_for_sqlite_write_lock() {
-        let pool =
-            setup_pool_with_max_connections_and_wal(2, std::time::Duration::from_millis(10)).await;
+    async fn prune_task_history_archives_and_expires_notifications() {
+        let pool = setup_pool().await;
         let state = setup_state(pool.clone());
+        let now = Utc
+            .with_ymd_and_hms(2026, 4, 20, 12, 0, 0)
+            .single()
+            .expect("valid datetime");
+        seed_user(&pool, 1, "owner").await;
+        seed_user(&pool, 2, "other").await;
 
-        let mut hold_tx = pool
-            .begin_with("BEGIN IMMEDIATE")
-            .await
-            .expect("begin hold writer tx");
-        sqlx::query("UPDATE daily_brief_hour_slots SET updated_at = ? WHERE hour_utc = ?")
-            .bind("2026-04-13T00:00:01Z")
-            .bind(0_i64)
-            .execute(&mut *hold_tx)
+        let days_ago = |days: i64| {
+            (now - Duration::days(days)).to_rfc3339_opts(chrono::SecondsFormat::Secs, true)
+        };
+        let archived_at = days_ago(5);
+        for (thread_id, age_days, archived) in [
+            ("fresh", 10, false),
+            ("stale", 100, false),
+            ("manual", 10, true),
+            ("expired", 400, true),
+            ("expired-unarchived", 400, false),
+        ] {
+            sqlx::query(
+                r#"
+                INSERT INTO notifications (id, user_id, thread_id, updated_at, unread, archived_at)
+                VALUES (?, '1', ?, ?, 1, ?)
+                "#,
+            )
+            .bind(format!("notification-{thread_id}"))
+            .bind(thread_id)
+            .bind(days_ago(age_days))
+            .bind(archived.then_some(archived_at.as_str()))
+            .execute(&pool)
             .await
-            .expect("hold sqlite writer lock");
-
-        let state_for_update = state.clone();
-        let update_task = tokio::spawn(async move {
-            update_daily_brief_hour_slot_dispatch(
-                state_for_update.as_ref(),
-                0,
-                "2026-04-13T00:00:00Z",
+            .expect("seed notification");
+        }
+        for (user_id, thread_id) in [("1", "expired"), ("1", "fresh"), ("2", "expired")] {
+            sqlx::query(
+                r#"
+                INSERT INTO ai_translations (
+                  id, user_id, entity_type, entity_id, lang, source_hash, created_at, updated_at
+                )
+                VALUES (?, ?, 'notification', ?, 'zh-CN', 'hash', ?, ?)
+                "#,
             )
+            .bind(format!("translation-{user_id}-{thread_id}"))
+            .bind(user_id)
+            .bind(thread_id)
+            .bind(archived_at.as_str())
+            .bind(archived_at.as_str())
+            .execute(&pool)
             .await
-        });
-
-        tokio::time::sleep(std::time::Duration::from_millis(70)).await;
-        hold_tx.rollback().await.expect("rollback hold writer tx");
+            .expect("seed notification translation");
+        }
 
-        update_task
+        let stats = prune_task_history(state.as_ref(), now)
             .await
-            .expect("join slot dispatch task")
-            .expect("slot dispatch update should succeed after writer release");
+            .expect("prune task history");
+        assert_eq!(stats.archived_notifications, 2);
+        assert_eq!(stats.deleted_notifications, 2);
+        assert_eq!(stats.deleted_notification_translations, 1);
 
-        let dispatch_at: Option<String> = sqlx::query_scalar(
-            r#"
-            SELECT last_dispatch_at
-            FROM daily_brief_hour_slots
-            WHERE hour_utc = ?
-            "#,
+        let remaining = sqlx::query_as::<_, (String, Option<String>)>(
+            "SELECT thread_id, archived_at FROM notifications ORDER BY thread_id",
         )
-        .bind(0_i64)
-        .fetch_one(&pool)
+        .fetch_all(&pool)
         .await
-        .expect("load slot dispatch state");
-        assert_eq!(dispatch_at.as_deref(), Some("2026-04-13T00:00:00Z"));
+        .expect("load remaining notifications");
+        assert_eq!(
+            remaining,
+            vec![
+                ("fresh".to_owned(), None),
+                ("manual".to_owned(), Some(archived_at.clone())),
+                ("stale".to_owned(), Some(now.to_rfc3339())),
+            ]
+        );
+        let translations =
+            sqlx::query_scalar::<_, String>("SELECT id FROM ai_translations ORDER BY id")
+                .fetch_all(&pool)
+                .await
+                .expect("load remaining translations");
+        assert_eq!(
+            translations,
+            vec!["translation-1-fresh", "translation-2-expired"]
+        );
+    }
+
+    #[tokio::test]
+    async fn enqueue_maintenance_prune_dispatches_once_per_hour() {
+        let pool = setup_pool().await;
+        let state = setup_state(pool.clone());
+        let now = Utc
+            .with_ymd_and_hms(2026, 4, 20, 12, 5, 0)
+            .single()
+            .expect("valid datetime");
+
+        let task_id = enqueue_maintenance_prune_if_due(state.as_ref(), now)
+            .await
+            .expect("enqueue prune")
+            .expect("prune task id");
+        sqlx::query("UPDATE job_tasks SET status = ? WHERE id = ?")
+            .bind(STATUS_SUCCEEDED)
+            .bind(&task_id)
+            .execute(&pool)
+            .await
+            .expect("finish prune task");
+
+        let same_hour =
+            enqueue_maintenance_prune_if_due(state.as_ref(), now + Duration::minutes(30))
+                .await
+                .expect("enqueue prune in same hour");
+        assert_eq!(same_hour, None);
+
+        let next_hour = enqueue_maintenance_prune_if_due(state.as_ref(), now + Duration::hours(1))
+            .await
+            .expect("enqueue prune next hour");
+        assert!(next_hour.is_some());
+    }
+
+    #[test]
+    fn user_sync_due_at_adds_frequency_and_stable_jitter() {
+        let last = Utc
+            .with_ymd_and_hms(2026, 4, 20, 12, 0, 0)
+            .single()
+            .expect("valid datetime");
+
+        assert_eq!(user_sync_due_at("1", 0, Some(last)), None);
+        assert_eq!(
+            user_sync_due_at("1", 60, None),
+            Some(chrono::DateTime::<Utc>::MIN_UTC)
+        );
+
+        let jitter = user_sync_jitter("1", 60);
+        assert!(jitter >= Duration::zero() && jitter < Duration::minutes(15));
+        assert_eq!(jitter, user_sync_jitter("1", 60));
+        assert_eq!(
+            user_sync_due_at("1", 60, Some(last)),
+            Some(last + Duration::minutes(60) + jitter)
+        );
+
+        let spread = (0..32)
+            .map(|idx| user_sync_jitter(&idx.to_string(), 60))
+            .collect::<std::collections::BTreeSet<_>>();
+        assert!(spread.len() > 16, "jitter should spread users apart");
+    }
+
+    async fn seed_user_sync_task(
+        pool: &SqlitePool,
+        task_id: &str,
+        task_type: &str,
+        status: &str,
+        user_id: &str,
+        finished_at: Option<&str>,
+    ) {
+        seed_task(pool, task_id, task_type, status, 0).await;
+        sqlx::query("UPDATE job_tasks SET payload_json = ?, finished_at = ? WHERE id = ?")
+            .bind(json!({ "user_id": user_id }).to_string())
+            .bind(finished_at)
+            .bind(task_id)
+            .execute(pool)
+            .await
+            .expect("set user sync task payload");
     }
 
     #[tokio::test]
-    async fn upsert_dispatch_state_waits_for_sqlite_write_lock() {
-        let pool =
-            setup_pool_with_max_connections_and_wal(2, std::time::Duration::from_millis(10)).await;
+    async fn enqueue_due_user_syncs_respects_frequency_and_last_success() {
+        let pool = setup_pool().await;
         let state = setup_state(pool.clone());
-
-        let mut hold_tx = pool
-            .begin_with("BEGIN IMMEDIATE")
-            .await
-            .expect("begin hold writer tx");
-        sqlx::query("UPDATE daily_brief_hour_slots SET updated_at = ? WHERE hour_utc = ?")
-            .bind("2026-04-13T00:00:01Z")
-            .bind(0_i64)
-            .execute(&mut *hold_tx)
-            .await
-            .expect("hold sqlite writer lock");
-
-        let state_for_upsert = state.clone();
-        let dispatch_task = tokio::spawn(async move {
-            upsert_dispatch_state(
-                state_for_upsert.as_ref(),
-                "test.schedule",
-                "interval:10:1772805600",
-                "task-dispatch-state",
-            )
+        seed_user(&pool, 1, "scheduled").await;
+        seed_user(&pool, 2, "manual").await;
+        sqlx::query("UPDATE users SET sync_frequency_minutes = 60 WHERE id = '1'")
+            .execute(&pool)
             .await
-        });
-
-        tokio::time::sleep(std::time::Duration::from_millis(70)).await;
-        hold_tx.rollback().await.expect("rollback hold writer tx");
+            .expect("enable scheduled sync");
+        let now = Utc
+            .with_ymd_and_hms(2026, 4, 20, 12, 0, 0)
+            .single()
+            .expect("valid datetime");
+        let recent = (now - Duration::minutes(10)).to_rfc3339();
+        seed_user_sync_task(
+            &pool,
+            "recent-releases",
+            TASK_SYNC_RELEASES,
+            STATUS_SUCCEEDED,
+            "1",
+            Some(recent.as_str()),
+        )
+        .await;
 
-        dispatch_task
+        let task_ids = enqueue_due_user_syncs(state.as_ref(), now)
             .await
-            .expect("join dispatch state task")
-            .expect("dispatch state upsert should succeed after writer release");
-
-        let row = sqlx::query_as::<_, (String, String)>(
-            r#"
-            SELECT last_dispatch_key, last_task_id
-            FROM scheduled_task_dispatch_state
-            WHERE schedule_name = ?
-            "#,
+            .expect("enqueue due user syncs");
+        assert_eq!(task_ids.len(), 1, "only notifications are due");
+        let row = sqlx::query(
+            "SELECT task_type, source, requested_by, payload_json FROM job_tasks WHERE id = ?",
         )
-        .bind("test.schedule")
+        .bind(&task_ids[0])
         .fetch_one(&pool)
         .await
-        .expect("load dispatch state");
-        assert_eq!(row.0, "interval:10:1772805600");
-        assert_eq!(row.1, "task-dispatch-state");
+        .expect("load enqueued task");
+        assert_eq!(row.get::<String, _>("task_type"), TASK_SYNC_NOTIFICATIONS);
+        assert_eq!(row.get::<String, _>("source"), USER_SYNC_SCHEDULER_SOURCE);
+        assert_eq!(
+            row.get::<Option<String>, _>("requested_by").as_deref(),
+            Some("1")
+        );
+        let payload: Value =
+            serde_json::from_str(&row.get::<String, _>("payload_json")).expect("payload json");
+        assert_eq!(payload["user_id"], "1");
+
+        assert_eq!(
+            count_users_due_for_sync(state.as_ref(), now + Duration::minutes(30))
+                .await
+                .expect("count due in 30 minutes"),
+            1,
+            "the queued notifications sync has no finished run yet"
+        );
+        sqlx::query("UPDATE job_tasks SET status = ?, finished_at = ? WHERE id = ?")
+            .bind(STATUS_SUCCEEDED)
+            .bind(now.to_rfc3339())
+            .bind(&task_ids[0])
+            .execute(&pool)
+            .await
+            .expect("finish notifications sync");
+        assert_eq!(
+            count_users_due_for_sync(state.as_ref(), now + Duration::minutes(30))
+                .await
+                .expect("count due in 30 minutes"),
+            0
+        );
+        assert_eq!(
+            count_users_due_for_sync(state.as_ref(), now + Duration::minutes(90))
+                .await
+                .expect("count due in 90 minutes"),
+            1
+        );
+
+        let later = enqueue_due_user_syncs(state.as_ref(), now + Duration::minutes(76))
+            .await
+            .expect("enqueue after frequency and max jitter");
+        assert_eq!(later.len(), 2);
     }
 
     #[tokio::test]
-    async fn mark_brief_generation_source_waits_for_sqlite_write_lock() {
-        let pool =
-            setup_pool_with_max_connections_and_wal(2, std::time::Duration::from_millis(10)).await;
+    async fn enqueue_due_user_syncs_skips_types_already_in_flight() {
+        let pool = setup_pool().await;
         let state = setup_state(pool.clone());
-        seed_user(&pool, 90_100, "brief-writer-lock-user").await;
-        sqlx::query(
-            r#"
-            INSERT INTO briefs (
-              id, user_id, date, window_start_utc, window_end_utc,
-              effective_time_zone, effective_local_boundary, generation_source,
-              content_markdown, created_at, updated_at
-            ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
-            "#,
+        seed_user(&pool, 1, "scheduled").await;
+        sqlx::query("UPDATE users SET sync_frequency_minutes = 30 WHERE id = '1'")
+            .execute(&pool)
+            .await
+            .expect("enable scheduled sync");
+        seed_user_sync_task(
+            &pool,
+            "manual-releases",
+            TASK_SYNC_RELEASES,
+            STATUS_RUNNING,
+            "1",
+            None,
         )
-        .bind("brief-writer-lock")
-        .bind("90100")
-        .bind("2026-04-13")
-        .bind("2026-04-12T00:00:00Z")
-        .bind("2026-04-13T00:00:00Z")
-        .bind("Asia/Shanghai")
-        .bind("08:00")
-        .bind("legacy")
-        .bind("placeholder")
-        .bind("2026-04-13T00:00:00Z")
-        .bind("2026-04-13T00:00:00Z")
-        .execute(&pool)
-        .await
-        .expect("seed brief");
+        .await;
+        let now = Utc
+            .with_ymd_and_hms(2026, 4, 20, 12, 0, 0)
+            .single()
+            .expect("valid datetime");
 
-        let mut hold_tx = pool
-            .begin_with("BEGIN IMMEDIATE")
+        let first = enqueue_due_user_syncs(state.as_ref(), now)
             .await
-            .expect("begin hold writer tx");
-        sqlx::query("UPDATE briefs SET updated_at = ? WHERE id = ?")
-            .bind("2026-04-13T00:00:01Z")
-            .bind("brief-writer-lock")
-            .execute(&mut *hold_tx)
+            .expect("enqueue due user syncs");
+        assert_eq!(first.len(), 1, "releases sync is already running");
+        let second = enqueue_due_user_syncs(state.as_ref(), now)
             .await
-            .expect("hold sqlite writer lock");
+            .expect("enqueue again");
+        assert!(
+            second.is_empty(),
+            "queued notifications sync is not duplicated"
+        );
 
-        let state_for_mark = state.clone();
-        let mark_task = tokio::spawn(async move {
-            mark_brief_generation_source(
-                state_for_mark.as_ref(),
-                "brief-writer-lock",
-                "content_refresh_failed",
-                "2026-04-13T00:00:02Z",
-                "test_brief_failure_mark",
-            )
+        seed_user_sync_task(&pool, "sync-all", TASK_SYNC_ALL, STATUS_QUEUED, "1", None).await;
+        sqlx::query("UPDATE job_tasks SET status = ? WHERE id IN (?, ?)")
+            .bind(STATUS_SUCCEEDED)
+            .bind("manual-releases")
+            .bind(&first[0])
+            .execute(&pool)
             .await
-        });
+            .expect("finish earlier syncs");
+        let third = enqueue_due_user_syncs(state.as_ref(), now)
+            .await
+            .expect("enqueue with sync.all queued");
+        assert!(third.is_empty(), "sync.all covers both types");
+    }
 
-        tokio::time::sleep(std::time::Duration::from_millis(70)).await;
-        hold_tx.rollback().await.expect("rollback hold writer tx");
+    #[tokio::test]
+    async fn run_maintenance_prune_now_records_last_prune_time() {
+        let pool = setup_pool().await;
+        let state = setup_state(pool.clone());
+        assert_eq!(
+            load_last_maintenance_prune_at(state.as_ref())
+                .await
+                .expect("load last prune"),
+            None
+        );
 
-        mark_task
+        let (task_id, stats) = run_maintenance_prune_now(state.as_ref(), None)
             .await
-            .expect("join brief mark task")
-            .expect("brief failure mark should succeed after writer release");
+            .expect("run prune");
+        assert_eq!(stats.deleted_tasks, 0);
 
-        let generation_source: String = sqlx::query_scalar(
-            r#"
-            SELECT generation_source
-            FROM briefs
-            WHERE id = ?
-            "#,
+        let finished_at = sqlx::query_scalar::<_, Option<String>>(
+            "SELECT finished_at FROM job_tasks WHERE id = ? AND status = ?",
         )
-        .bind("brief-writer-lock")
+        .bind(&task_id)
+        .bind(STATUS_SUCCEEDED)
         .fetch_one(&pool)
         .await
-        .expect("load brief generation source");
-        assert_eq!(generation_source, "content_refresh_failed");
-    }
-
-    async fn seed_task(
-        pool: &SqlitePool,
-        task_id: &str,
-        task_type: &str,
-        status: &str,
-        offset_seconds: i64,
-    ) {
-        let created_at = format!("2026-03-06T00:00:{offset_seconds:02}Z");
-        sqlx::query(
-            r#"
-            INSERT INTO job_tasks (
-              id,
-              task_type,
-              status,
-              source,
-              requested_by,
-              parent_task_id,
-              payload_json,
-              result_json,
-              error_message,
-              cancel_requested,
-              created_at,
-              started_at,
-              finished_at,
-              updated_at,
-              log_file_path
-            ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
-            "#,
-        )
-        .bind(task_id)
-        .bind(task_type)
-        .bind(status)
-        .bind("test")
-        .bind(Option::<i64>::None)
-        .bind(Option::<String>::None)
-        .bind("{}")
-        .bind(Option::<String>::None)
-        .bind(Option::<String>::None)
-        .bind(0_i64)
-        .bind(created_at.as_str())
-        .bind((status == STATUS_RUNNING).then_some(created_at.as_str()))
-        .bind(Option::<String>::None)
-        .bind(created_at.as_str())
-        .bind(Option::<String>::None)
-        .execute(pool)
-        .await
-        .expect("seed task");
+        .expect("load prune task");
+        assert!(finished_at.is_some());
+        assert_eq!(
+            load_last_maintenance_prune_at(state.as_ref())
+                .await
+                .expect("load last prune"),
+            finished_at
+        );
     }
 
     #[tokio::test]
@@ -5576,6 +7890,117 @@ mod tests {
         assert_eq!(status, "failed");
     }
 
+    #[test]
+    fn task_canceled_error_is_found_through_context() {
+        let err = anyhow::Error::from(super::TaskCanceled).context("translate release batch");
+        assert!(super::is_task_canceled_error(&err));
+        assert!(!super::is_task_canceled_error(&anyhow::anyhow!(
+            super::TASK_CANCELED_ERROR
+        )));
+    }
+
+    #[tokio::test]
+    async fn task_cancel_requested_polls_database_at_most_once_per_interval() {
+        let pool = setup_pool().await;
+        let state = setup_state(pool.clone());
+        let task_id = "task-cancel-poll";
+        seed_task(
+            &pool,
+            task_id,
+            super::TASK_TRANSLATE_RELEASE_BATCH,
+            STATUS_RUNNING,
+            1,
+        )
+        .await;
+        let _cancellation = super::register_task_cancellation(task_id);
+        assert!(!super::task_cancel_requested(state.as_ref(), task_id).await);
+
+        // Another instance flags the task; the throttled check does not see it yet.
+        sqlx::query(r#"UPDATE job_tasks SET cancel_requested = 1 WHERE id = ?"#)
+            .bind(task_id)
+            .execute(&pool)
+            .await
+            .expect("flag cancel");
+        assert!(!super::task_cancel_requested(state.as_ref(), task_id).await);
+
+        super::task_cancellations()
+            .lock()
+            .expect("task cancellations poisoned")
+            .get_mut(task_id)
+            .expect("registered cancellation")
+            .polled_at = Some(std::time::Instant::now() - super::TASK_CANCEL_POLL_INTERVAL);
+        assert!(super::task_cancel_requested(state.as_ref(), task_id).await);
+    }
+
+    #[tokio::test]
+    async fn cancel_task_stops_running_worker_progress() {
+        let pool = setup_pool().await;
+        let state = setup_state(pool.clone());
+        let task_id = "task-long-running";
+        seed_task(
+            &pool,
+            task_id,
+            super::TASK_TRANSLATE_RELEASE_BATCH,
+            STATUS_RUNNING,
+            1,
+        )
+        .await;
+        let _cancellation = super::register_task_cancellation(task_id);
+
+        let worker_state = state.clone();
+        let worker = tokio::spawn(crate::ai::with_llm_call_context(
+            crate::ai::LlmCallContext {
+                source: "test".to_owned(),
+                requested_by: None,
+                parent_task_id: Some(task_id.to_owned()),
+                parent_task_type: Some(super::TASK_TRANSLATE_RELEASE_BATCH.to_owned()),
+                parent_translation_batch_id: None,
+            },
+            async move {
+                let mut steps = 0_u32;
+                while !super::current_task_cancel_requested(worker_state.as_ref()).await {
+                    steps += 1;
+                    append_task_event(
+                        worker_state.as_ref(),
+                        task_id,
+                        "task.progress",
+                        json!({ "step": steps }),
+                    )
+                    .await
+                    .expect("append progress");
+                    tokio::time::sleep(std::time::Duration::from_millis(5)).await;
+                }
+                steps
+            },
+        ));
+
+        tokio::time::sleep(std::time::Duration::from_millis(30)).await;
+        let status = super::cancel_task(state.as_ref(), task_id)
+            .await
+            .expect("cancel running task");
+        assert_eq!(status, STATUS_RUNNING);
+
+        let steps = tokio::time::timeout(std::time::Duration::from_secs(2), worker)
+            .await
+            .expect("worker stops after cancel")
+            .expect("join worker");
+        assert!(steps > 0);
+
+        let count_progress = || async {
+            sqlx::query_scalar::<_, i64>(
+                "SELECT COUNT(*) FROM job_task_events WHERE task_id = ? AND event_type = 'task.progress'",
+            )
+            .bind(task_id)
+            .fetch_one(&pool)
+            .await
+            .expect("count progress events")
+        };
+        let progress_after_cancel = count_progress().await;
+        assert_eq!(progress_after_cancel, i64::from(steps));
+        tokio::time::sleep(std::time::Duration::from_millis(30)).await;
+        assert_eq!(count_progress().await, progress_after_cancel);
+    }
+
     #[tokio::test]
     async fn recent_failures_retry_skips_stale_translation_source_hash() {
         let pool = setup_pool().await;
@@ -5701,9 +8126,12 @@ mod tests {
             .expect("unique app state")
             .config
             .ai = Some(AiConfig {
+            provider: crate::config::AiProvider::OpenAi,
             base_url: Url::parse("https://example.invalid/").expect("ai base url"),
             model: "test-model".to_owned(),
             api_key: "test-key".to_owned(),
+            fallback_models: Vec::new(),
+            startup_probe: crate::config::AiStartupProbe::Off,
         });
         let now = "2026-03-07T00:00:00Z";
 
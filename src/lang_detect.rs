@@ -0,0 +1,138 @@
+//! Script-ratio guess at the language of release notes and notification
+//! titles. It only has to tell "already Chinese" apart from everything else,
+//! so it counts characters per script instead of running a real classifier.
+
+pub const LANG_EN: &str = "en";
+pub const LANG_JA: &str = "ja";
+pub const LANG_KO: &str = "ko";
+pub const LANG_ZH: &str = "zh";
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+struct ScriptCounts {
+    han: usize,
+    kana: usize,
+    hangul: usize,
+    latin: usize,
+}
+
+impl ScriptCounts {
+    fn total(&self) -> usize {
+        self.han + self.kana + self.hangul + self.latin
+    }
+}
+
+/// Guesses the language of `text` from the share of each script among its
+/// letters, ignoring fenced code, inline code and URLs. A CJK share of at
+/// least `cjk_threshold_percent` reads as Chinese, or Japanese when kana make
+/// up a fifth of it; otherwise Latin letters read as English. Returns `None`
+/// when no letters remain.
+pub fn detect_source_lang(text: &str, cjk_threshold_percent: usize) -> Option<&'static str> {
+    let counts = count_scripts(&strip_non_prose(text));
+    let total = counts.total();
+    if total == 0 {
+        return None;
+    }
+    let cjk = counts.han + counts.kana;
+    if counts.hangul * 100 >= total * cjk_threshold_percent {
+        return Some(LANG_KO);
+    }
+    if cjk * 100 >= total * cjk_threshold_percent {
+        return Some(if counts.kana * 5 >= cjk {
+            LANG_JA
+        } else {
+            LANG_ZH
+        });
+    }
+    (counts.latin > 0).then_some(LANG_EN)
+}
+
+/// Whether the text is already in the Chinese translation target.
+pub fn is_already_chinese(text: &str, cjk_threshold_percent: usize) -> bool {
+    detect_source_lang(text, cjk_threshold_percent) == Some(LANG_ZH)
+}
+
+fn strip_non_prose(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut in_fence = false;
+    for line in text.lines() {
+        if line.trim_start().starts_with("```") {
+            in_fence = !in_fence;
+            continue;
+        }
+        if in_fence {
+            continue;
+        }
+        // Odd segments between backticks are inline code.
+        for segment in line.split('`').step_by(2) {
+            for word in segment.split_whitespace() {
+                if !word.contains("://") {
+                    out.push_str(word);
+                    out.push(' ');
+                }
+            }
+        }
+        out.push('\n');
+    }
+    out
+}
+
+fn count_scripts(text: &str) -> ScriptCounts {
+    let mut counts = ScriptCounts::default();
+    for ch in text.chars() {
+        match ch {
+            '\u{3400}'..='\u{4DBF}' | '\u{4E00}'..='\u{9FFF}' | '\u{F900}'..='\u{FAFF}' => {
+                counts.han += 1
+            }
+            '\u{3040}'..='\u{30FF}' => counts.kana += 1,
+            '\u{1100}'..='\u{11FF}' | '\u{AC00}'..='\u{D7AF}' => counts.hangul += 1,
+            ch if ch.is_alphabetic() && ch < '\u{0250}' => counts.latin += 1,
+            _ => {}
+        }
+    }
+    counts
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{LANG_EN, LANG_JA, LANG_ZH, detect_source_lang, is_already_chinese};
+
+    const CHINESE_NOTES: &str = "## 新功能\n\n- 新增 `--watch` 参数，支持在 Windows 上监听配置文件变化\n- 优化了 SQLite 连接池的初始化速度\n\n## 修复\n\n- 修复 macOS 下托盘图标偶尔消失的问题（#1234）\n\n```bash\ncargo install octo-cli --locked --features full\n```\n\n完整变更见 https://github.com/acme/octo/compare/v1.2.2...v1.2.3";
+
+    const ENGLISH_NOTES_WITH_CJK: &str = "## What's Changed\n\n- Added a Simplified Chinese locale (简体中文) to the settings page\n- Fixed rendering of 日本語 filenames in the download dialog\n- Bumped tokio to 1.40 and reduced idle CPU usage\n\n**Full Changelog**: https://github.com/acme/octo/compare/v1.2.2...v1.2.3";
+
+    #[test]
+    fn detect_source_lang_flags_chinese_notes_with_english_terms() {
+        assert_eq!(detect_source_lang(CHINESE_NOTES, 30), Some(LANG_ZH));
+        assert!(is_already_chinese(CHINESE_NOTES, 30));
+    }
+
+    #[test]
+    fn detect_source_lang_keeps_mostly_english_notes_with_cjk_strings() {
+        assert_eq!(
+            detect_source_lang(ENGLISH_NOTES_WITH_CJK, 30),
+            Some(LANG_EN)
+        );
+        assert!(!is_already_chinese(ENGLISH_NOTES_WITH_CJK, 30));
+    }
+
+    #[test]
+    fn detect_source_lang_ignores_code_and_urls() {
+        let text = "修复启动崩溃\n\n```rust\nfn main() { println!(\"hello world from the example\"); }\n```\n\n`some_really_long_identifier_name` https://example.com/very/long/english/path";
+        assert_eq!(detect_source_lang(text, 30), Some(LANG_ZH));
+        assert_eq!(detect_source_lang("```\nonly code\n```", 30), None);
+    }
+
+    #[test]
+    fn detect_source_lang_treats_kana_heavy_text_as_japanese() {
+        let text = "設定画面にダークモードを追加しました。起動時のクラッシュを修正しました。";
+        assert_eq!(detect_source_lang(text, 30), Some(LANG_JA));
+        assert!(!is_already_chinese(text, 30));
+    }
+
+    #[test]
+    fn detect_source_lang_honors_threshold() {
+        let text = "支持暗色 dark mode";
+        assert_eq!(detect_source_lang(text, 30), Some(LANG_ZH));
+        assert_eq!(detect_source_lang(text, 40), Some(LANG_EN));
+    }
+}
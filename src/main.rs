@@ -1,24 +1,49 @@
+mod account;
+mod admin_audit;
 mod admin_runtime;
 mod ai;
 mod api;
+mod atom_feed;
 mod auth;
+mod backup;
+mod bootstrap;
 mod briefs;
+mod conditional;
 mod config;
 mod crypto;
+mod csrf;
+mod db_maintenance;
 mod error;
+mod export;
+mod feed_events;
+mod follows;
 mod github;
+mod github_app;
+mod github_rate_limit;
+mod health;
+mod impersonation;
 mod jobs;
+mod lang_detect;
 mod linuxdo;
 mod local_id;
+mod notification_archive;
+mod notify;
 mod observability;
+mod openapi;
 mod passkeys;
+mod rate_limit;
 mod release_links;
+mod release_render;
 mod runtime;
 mod server;
 mod session_store;
 mod sqlite_write;
+mod star_purge;
 mod state;
 mod sync;
+mod sync_issues;
+mod translation_retry;
+mod translation_stats;
 mod translations;
 mod version;
 
@@ -30,6 +55,7 @@ async fn main() -> Result<()> {
     from_filename(".env.local").ok();
     dotenv().ok();
     observability::init_tracing();
+    observability::install_metrics_recorder();
 
     let config = config::AppConfig::from_env()?;
     server::serve(config).await
@@ -0,0 +1,133 @@
+use anyhow::{Context, Result};
+use chrono::{DateTime, Duration, SecondsFormat, Utc};
+use serde::Serialize;
+
+use crate::state::AppState;
+
+/// Notifications archived or deleted per write, so the prune never holds
+/// the writer for long.
+const NOTIFICATION_PRUNE_BATCH_SIZE: i64 = 500;
+
+#[derive(Debug, Clone, Copy, Default, Serialize, PartialEq, Eq)]
+pub struct NotificationPruneStats {
+    pub archived_notifications: u64,
+    pub deleted_notifications: u64,
+    pub deleted_translations: u64,
+}
+
+fn days_before(now: DateTime<Utc>, days: usize) -> String {
+    let days = i64::try_from(days).unwrap_or(i64::MAX);
+    (now - Duration::days(days)).to_rfc3339_opts(SecondsFormat::Secs, true)
+}
+
+/// Archives threads not updated within `notification_archive_days`, then
+/// deletes archived threads whose last update is older than
+/// `notification_retention_days`, together with the owner's translations of
+/// them.
+pub async fn prune_notifications(
+    state: &AppState,
+    now: DateTime<Utc>,
+) -> Result<NotificationPruneStats> {
+    // Formatted like GitHub's `updated_at` so the cutoffs compare as text and
+    // can use the `updated_at` index.
+    let archive_cutoff = days_before(now, state.config.notification_archive_days);
+    let retention_cutoff = days_before(now, state.config.notification_retention_days);
+    let archived_at = now.to_rfc3339();
+    let mut stats = NotificationPruneStats::default();
+
+    loop {
+        let archived = state
+            .sqlite_writer
+            .write("notifications_archive_stale", |_| async {
+                let result = sqlx::query(
+                    r#"
+                    UPDATE notifications
+                    SET archived_at = ?
+                    WHERE id IN (
+                      SELECT id
+                      FROM notifications
+                      WHERE archived_at IS NULL
+                        AND updated_at < ?
+                      LIMIT ?
+                    )
+                    "#,
+                )
+                .bind(archived_at.as_str())
+                .bind(archive_cutoff.as_str())
+                .bind(NOTIFICATION_PRUNE_BATCH_SIZE)
+                .execute(&state.pool)
+                .await?;
+                Ok::<_, anyhow::Error>(result.rows_affected())
+            })
+            .await
+            .context("failed to archive stale notifications")?;
+        stats.archived_notifications += archived;
+        if (archived as i64) < NOTIFICATION_PRUNE_BATCH_SIZE {
+            break;
+        }
+    }
+
+    loop {
+        let rows = sqlx::query_as::<_, (String, String, String)>(
+            r#"
+            SELECT id, user_id, thread_id
+            FROM notifications
+            WHERE archived_at IS NOT NULL
+              AND COALESCE(updated_at, archived_at) < ?
+            ORDER BY updated_at ASC, id ASC
+            LIMIT ?
+            "#,
+        )
+        .bind(retention_cutoff.as_str())
+        .bind(NOTIFICATION_PRUNE_BATCH_SIZE)
+        .fetch_all(&state.pool)
+        .await
+        .context("failed to query expired notifications")?;
+        if rows.is_empty() {
+            break;
+        }
+
+        let (deleted_notifications, deleted_translations) = state
+            .sqlite_writer
+            .write("notifications_purge_expired", |_| {
+                let rows = rows.clone();
+                async move {
+                    let mut tx = state.pool.begin().await?;
+                    let mut deleted_notifications = 0;
+                    let mut deleted_translations = 0;
+                    for (id, user_id, thread_id) in &rows {
+                        deleted_translations += sqlx::query(
+                            r#"
+                            DELETE FROM ai_translations
+                            WHERE user_id = ?
+                              AND entity_type = 'notification'
+                              AND entity_id = ?
+                            "#,
+                        )
+                        .bind(user_id)
+                        .bind(thread_id)
+                        .execute(&mut *tx)
+                        .await?
+                        .rows_affected();
+                        deleted_notifications +=
+                            sqlx::query(r#"DELETE FROM notifications WHERE id = ?"#)
+                                .bind(id)
+                                .execute(&mut *tx)
+                                .await?
+                                .rows_affected();
+                    }
+                    tx.commit().await?;
+                    Ok::<_, anyhow::Error>((deleted_notifications, deleted_translations))
+                }
+            })
+            .await
+            .context("failed to delete expired notifications")?;
+        stats.deleted_notifications += deleted_notifications;
+        stats.deleted_translations += deleted_translations;
+        if (rows.len() as i64) < NOTIFICATION_PRUNE_BATCH_SIZE {
+            break;
+        }
+    }
+
+    Ok(stats)
+}
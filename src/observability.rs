@@ -1,10 +1,13 @@
 use std::fmt;
+use std::future::Future;
 use std::sync::{OnceLock, RwLock};
 use std::time::Duration;
 
 use axum::{
     extract::MatchedPath, extract::Request, http::StatusCode, middleware::Next, response::Response,
 };
+use metrics_exporter_prometheus::{PrometheusBuilder, PrometheusHandle};
+use tower::util::MapRequestLayer;
 use tower_http::request_id::{MakeRequestUuid, PropagateRequestIdLayer, SetRequestIdLayer};
 use tracing::Span;
 
@@ -13,6 +16,21 @@ const DEFAULT_UPSTREAM_SLOW_MS: usize = 2_000;
 const DEFAULT_SQLITE_WRITE_SLOW_MS: usize = 250;
 
 static LOGGING_THRESHOLDS: OnceLock<RwLock<LoggingThresholds>> = OnceLock::new();
+static METRICS_HANDLE: OnceLock<PrometheusHandle> = OnceLock::new();
+
+/// Metrics route label for requests no route matched, so probes of random
+/// paths share one series instead of minting one per path.
+const UNMATCHED_ROUTE_LABEL: &str = "unmatched";
+
+tokio::task_local! {
+    static REQUEST_ID: String;
+}
+
+/// Histogram buckets shared by every `_seconds` series, from fast HTTP
+/// handlers up to LLM calls parked behind the scheduler.
+const LATENCY_BUCKETS_SECONDS: &[f64] = &[
+    0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0, 30.0, 60.0, 120.0,
+];
 
 #[derive(Clone, Debug)]
 pub struct LoggingThresholds {
@@ -58,13 +76,148 @@ pub fn init_tracing() {
         .init();
 }
 
-pub fn request_id_layers() -> (SetRequestIdLayer<MakeRequestUuid>, PropagateRequestIdLayer) {
+fn metrics_builder() -> PrometheusBuilder {
+    PrometheusBuilder::new()
+        .set_buckets(LATENCY_BUCKETS_SECONDS)
+        .expect("latency buckets are non-empty")
+}
+
+/// Installs the process-wide Prometheus recorder. Until this runs every
+/// `metrics::*!` call is a no-op, which keeps tests free of global state.
+pub fn install_metrics_recorder() {
+    match metrics_builder().install_recorder() {
+        Ok(handle) => {
+            let _ = METRICS_HANDLE.set(handle);
+        }
+        Err(err) => tracing::warn!(?err, "metrics recorder install failed"),
+    }
+}
+
+/// Prometheus text exposition of everything recorded so far, or `None` when
+/// no recorder was installed.
+pub fn render_metrics() -> Option<String> {
+    METRICS_HANDLE.get().map(PrometheusHandle::render)
+}
+
+pub fn record_http_request(method: &str, route: &str, status: StatusCode, latency: Duration) {
+    let status = status.as_u16().to_string();
+    metrics::counter!(
+        "octorill_http_requests_total",
+        "method" => method.to_owned(),
+        "route" => route.to_owned(),
+        "status" => status,
+    )
+    .increment(1);
+    metrics::histogram!(
+        "octorill_http_request_duration_seconds",
+        "method" => method.to_owned(),
+        "route" => route.to_owned(),
+    )
+    .record(latency.as_secs_f64());
+}
+
+pub fn record_job_task_transition(task_type: &str, status: &str) {
+    metrics::counter!(
+        "octorill_job_task_transitions_total",
+        "task_type" => task_type.to_owned(),
+        "status" => status.to_owned(),
+    )
+    .increment(1);
+}
+
+pub fn record_llm_scheduler_wait(wait: Duration) {
+    metrics::histogram!("octorill_llm_scheduler_wait_seconds").record(wait.as_secs_f64());
+}
+
+pub fn record_llm_call(status: &str, input_tokens: Option<i64>, output_tokens: Option<i64>) {
+    metrics::counter!("octorill_llm_calls_total", "status" => status.to_owned()).increment(1);
+    for (kind, tokens) in [("input", input_tokens), ("output", output_tokens)] {
+        if let Some(tokens) = tokens.and_then(|value| u64::try_from(value).ok()) {
+            metrics::counter!("octorill_llm_tokens_total", "kind" => kind).increment(tokens);
+        }
+    }
+}
+
+pub fn record_github_request(token_kind: &str) {
+    metrics::counter!(
+        "octorill_github_requests_total",
+        "token_kind" => token_kind.to_owned(),
+    )
+    .increment(1);
+}
+
+pub fn record_github_rate_limit_remaining(user_id: &str, token_kind: &str, remaining: i64) {
+    metrics::gauge!(
+        "octorill_github_rate_limit_remaining",
+        "user_id" => user_id.to_owned(),
+        "token_kind" => token_kind.to_owned(),
+    )
+    .set(remaining as f64);
+}
+
+/// Counts cache lookups by outcome; the hit ratio is
+/// `hit / (hit + miss)` per `cache`.
+pub fn record_cache_lookups(cache: &'static str, hits: usize, misses: usize) {
+    for (result, count) in [("hit", hits), ("miss", misses)] {
+        if count > 0 {
+            metrics::counter!(
+                "octorill_cache_lookups_total",
+                "cache" => cache,
+                "result" => result,
+            )
+            .increment(count as u64);
+        }
+    }
+}
+
+type RequestIdFilterLayer = MapRequestLayer<fn(Request) -> Request>;
+
+fn keep_inbound_request_id(request: Request) -> Request {
+    request
+}
+
+fn drop_inbound_request_id(mut request: Request) -> Request {
+    request.headers_mut().remove("x-request-id");
+    request
+}
+
+/// An inbound `x-request-id` is only kept when `trust_inbound` is set, i.e.
+/// a proxy in front of us owns the header; otherwise a fresh one is minted.
+pub fn request_id_layers(
+    trust_inbound: bool,
+) -> (
+    RequestIdFilterLayer,
+    SetRequestIdLayer<MakeRequestUuid>,
+    PropagateRequestIdLayer,
+) {
+    let filter: fn(Request) -> Request = if trust_inbound {
+        keep_inbound_request_id
+    } else {
+        drop_inbound_request_id
+    };
     (
+        MapRequestLayer::new(filter),
         SetRequestIdLayer::x_request_id(MakeRequestUuid),
         PropagateRequestIdLayer::x_request_id(),
     )
 }
 
+/// Runs `fut` with `request_id` as the correlation id that tasks and LLM
+/// calls created inside it are stamped with.
+pub async fn with_request_id<F>(request_id: Option<String>, fut: F) -> F::Output
+where
+    F: Future,
+{
+    match request_id {
+        Some(request_id) => REQUEST_ID.scope(request_id, fut).await,
+        None => fut.await,
+    }
+}
+
+pub fn current_request_id() -> Option<String> {
+    REQUEST_ID.try_with(Clone::clone).ok()
+}
+
 pub fn request_route(request: &Request) -> String {
     request
         .extensions()
@@ -73,6 +226,13 @@ pub fn request_route(request: &Request) -> String {
         .unwrap_or_else(|| request.uri().path().to_owned())
 }
 
+fn request_metrics_route(request: &Request) -> String {
+    request.extensions().get::<MatchedPath>().map_or_else(
+        || UNMATCHED_ROUTE_LABEL.to_owned(),
+        |path| path.as_str().to_owned(),
+    )
+}
+
 pub fn request_id_from_headers(headers: &axum::http::HeaderMap) -> String {
     headers
         .get("x-request-id")
@@ -96,11 +256,14 @@ pub async fn access_log_middleware(request: Request, next: Next) -> Response {
     let request_id = request_id_from_headers(request.headers());
     let method = request.method().to_string();
     let route = request_route(&request);
+    let metrics_route = request_metrics_route(&request);
     let thresholds = logging_thresholds();
     let started = std::time::Instant::now();
-    let response = next.run(request).await;
+    let scoped_request_id = (request_id != "-").then(|| request_id.clone());
+    let response = with_request_id(scoped_request_id, next.run(request)).await;
     let latency = started.elapsed();
     let status = response.status();
+    record_http_request(&method, &metrics_route, status, latency);
     if is_slow_or_error(status, latency, thresholds.http_slow_ms) {
         let latency_ms = latency.as_millis();
         if status.is_client_error() || status.is_server_error() {
@@ -191,6 +354,85 @@ mod tests {
         ));
     }
 
+    #[test]
+    fn metrics_helpers_export_each_family() {
+        let recorder = metrics_builder().build_recorder();
+        let handle = recorder.handle();
+        metrics::with_local_recorder(&recorder, || {
+            record_http_request(
+                "GET",
+                "/api/feed",
+                StatusCode::OK,
+                Duration::from_millis(40),
+            );
+            record_job_task_transition("sync.releases", "succeeded");
+            record_llm_scheduler_wait(Duration::from_millis(120));
+            record_llm_call("succeeded", Some(300), Some(80));
+            record_github_request("graphql");
+            record_github_rate_limit_remaining("user-1", "graphql", 4_321);
+            record_cache_lookups("release_translation", 3, 1);
+        });
+
+        let rendered = handle.render();
+        for expected in [
+            r#"octorill_http_requests_total{method="GET",route="/api/feed",status="200"} 1"#,
+            r#"octorill_http_request_duration_seconds_bucket{method="GET",route="/api/feed",le="0.05"} 1"#,
+            r#"octorill_job_task_transitions_total{task_type="sync.releases",status="succeeded"} 1"#,
+            "octorill_llm_scheduler_wait_seconds_count 1",
+            r#"octorill_llm_calls_total{status="succeeded"} 1"#,
+            r#"octorill_llm_tokens_total{kind="input"} 300"#,
+            r#"octorill_github_requests_total{token_kind="graphql"} 1"#,
+            r#"octorill_github_rate_limit_remaining{user_id="user-1",token_kind="graphql"} 4321"#,
+            r#"octorill_cache_lookups_total{cache="release_translation",result="hit"} 3"#,
+        ] {
+            assert!(
+                rendered.contains(expected),
+                "missing {expected} in:\n{rendered}"
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn unmatched_paths_share_one_route_series() {
+        use axum::{Router, body::Body, routing::get};
+        use tower::ServiceExt;
+
+        let recorder = metrics_builder().build_recorder();
+        let handle = recorder.handle();
+        let _guard = metrics::set_default_local_recorder(&recorder);
+        let app = Router::new()
+            .route("/api/feed", get(|| async { "ok" }))
+            .layer(axum::middleware::from_fn(access_log_middleware));
+
+        for path in ["/wp-login.php", "/.env", "/api/feed"] {
+            app.clone()
+                .oneshot(
+                    axum::http::Request::builder()
+                        .uri(path)
+                        .body(Body::empty())
+                        .expect("build request"),
+                )
+                .await
+                .expect("serve request");
+        }
+
+        let rendered = handle.render();
+        assert!(
+            rendered.contains(
+                r#"octorill_http_requests_total{method="GET",route="unmatched",status="404"} 2"#
+            ),
+            "missing unmatched series in:\n{rendered}"
+        );
+        assert!(
+            rendered.contains(
+                r#"octorill_http_requests_total{method="GET",route="/api/feed",status="200"} 1"#
+            ),
+            "missing matched series in:\n{rendered}"
+        );
+        assert!(!rendered.contains("wp-login"));
+        assert!(!rendered.contains("/.env"));
+    }
+
     #[test]
     fn error_chain_summary_includes_sources() {
         let err = anyhow::anyhow!("outer").context("middle");
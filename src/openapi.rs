@@ -0,0 +1,1310 @@
+use std::sync::OnceLock;
+
+use axum::Json;
+use schemars::{
+    JsonSchema, Schema,
+    generate::{Contract, SchemaGenerator, SchemaSettings},
+};
+use serde_json::{Map, Value, json};
+
+use crate::{
+    account, admin_audit, ai, api, atom_feed, backup, bootstrap, csrf, db_maintenance,
+    error::ApiErrorBody, follows, github_app, impersonation, sync, sync_issues, translation_stats,
+    version,
+};
+
+type SchemaFn = fn(&mut SchemaGenerator) -> Schema;
+
+fn schema<T: JsonSchema>(generator: &mut SchemaGenerator) -> Schema {
+    generator.subschema_for::<T>()
+}
+
+/// Inline schema, so query structs can be expanded into one parameter per
+/// field.
+fn inline<T: JsonSchema>(generator: &mut SchemaGenerator) -> Schema {
+    generator.root_schema_for::<T>()
+}
+
+#[derive(Clone, Copy)]
+enum ResponseBody {
+    Json(Option<SchemaFn>),
+    /// `return_mode=sync` answers with the given JSON, `task_id` with a
+    /// [`api::TaskAcceptedResponse`] and `sse` with a task event stream.
+    TaskOrJson(Option<SchemaFn>),
+    EventStream,
+    Ndjson,
+    Download(&'static str),
+    Text(&'static str),
+    Redirect,
+}
+
+struct Operation {
+    method: &'static str,
+    path: &'static str,
+    tag: &'static str,
+    summary: &'static str,
+    query: Option<SchemaFn>,
+    request: Option<SchemaFn>,
+    response: ResponseBody,
+}
+
+const fn op(
+    method: &'static str,
+    path: &'static str,
+    tag: &'static str,
+    summary: &'static str,
+) -> Operation {
+    Operation {
+        method,
+        path,
+        tag,
+        summary,
+        query: None,
+        request: None,
+        response: ResponseBody::Json(None),
+    }
+}
+
+impl Operation {
+    const fn query(mut self, query: SchemaFn) -> Self {
+        self.query = Some(query);
+        self
+    }
+
+    const fn body(mut self, request: SchemaFn) -> Self {
+        self.request = Some(request);
+        self
+    }
+
+    const fn returns(mut self, response: SchemaFn) -> Self {
+        self.response = ResponseBody::Json(Some(response));
+        self
+    }
+
+    const fn responds(mut self, response: ResponseBody) -> Self {
+        self.response = response;
+        self
+    }
+
+    /// Endpoints that honour `?return_mode=sync|task_id|sse`.
+    const fn task(mut self) -> Self {
+        self.query = Some(inline::<api::ReturnModeQuery>);
+        self.response = ResponseBody::TaskOrJson(match self.response {
+            ResponseBody::Json(sync) => sync,
+            _ => None,
+        });
+        self
+    }
+}
+
+/// Every route registered in `server.rs`. The coverage test below fails when
+/// the two drift apart.
+static OPERATIONS: &[Operation] = &[
+    op(
+        "get",
+        "/api/health",
+        "system",
+        "Liveness probe with the running version",
+    ),
+    op(
+        "get",
+        "/api/version",
+        "system",
+        "Running version and where it came from",
+    ),
+    op("get", "/api/openapi.json", "system", "This document"),
+    op(
+        "get",
+        "/api/csrf",
+        "system",
+        "Session token for the X-CSRF-Token header",
+    )
+    .returns(schema::<csrf::CsrfTokenResponse>),
+    op(
+        "post",
+        "/api/bootstrap",
+        "sync",
+        "Start the first-login sync chain unless one is in flight",
+    )
+    .returns(schema::<bootstrap::BootstrapStatusResponse>),
+    op(
+        "get",
+        "/api/bootstrap/status",
+        "sync",
+        "Progress of the latest first-login sync chain",
+    )
+    .returns(schema::<bootstrap::BootstrapStatusResponse>),
+    op(
+        "get",
+        "/api/me",
+        "me",
+        "Current user and dashboard bootstrap",
+    )
+    .returns(schema::<api::MeResponse>),
+    op(
+        "delete",
+        "/api/me",
+        "me",
+        "Delete the account and all of its data",
+    )
+    .body(schema::<account::DeleteAccountRequest>)
+    .returns(schema::<account::AccountDeletionSummary>),
+    op("get", "/api/me/profile", "me", "Daily brief profile"),
+    op(
+        "patch",
+        "/api/me/profile",
+        "me",
+        "Update daily brief profile",
+    ),
+    op("get", "/api/me/linuxdo", "me", "Linked LinuxDo account"),
+    op("delete", "/api/me/linuxdo", "me", "Unlink LinuxDo account"),
+    op(
+        "get",
+        "/api/me/github-connections",
+        "accounts",
+        "Linked GitHub accounts",
+    )
+    .returns(schema::<api::MeGitHubConnectionsResponse>),
+    op(
+        "delete",
+        "/api/me/github-connections/{connection_id}",
+        "accounts",
+        "Unlink a GitHub account",
+    )
+    .returns(schema::<api::MeGitHubConnectionsResponse>),
+    op("get", "/api/accounts", "accounts", "Linked GitHub accounts")
+        .returns(schema::<api::MeGitHubConnectionsResponse>),
+    op(
+        "delete",
+        "/api/accounts/{connection_id}",
+        "accounts",
+        "Unlink a GitHub account and purge its synced rows",
+    )
+    .returns(schema::<api::MeGitHubConnectionsResponse>),
+    op("get", "/api/me/passkeys", "me", "Registered passkeys"),
+    op(
+        "delete",
+        "/api/me/passkeys/{passkey_id}",
+        "me",
+        "Remove a passkey",
+    ),
+    op(
+        "get",
+        "/api/github/rate-limit",
+        "me",
+        "Last seen GitHub rate-limit budget",
+    ),
+    op("get", "/api/export", "me", "Download a data export")
+        .responds(ResponseBody::Download("application/zip")),
+    op(
+        "post",
+        "/api/feed-token",
+        "me",
+        "Rotate the Atom feed token",
+    )
+    .returns(schema::<atom_feed::FeedTokenResponse>),
+    op(
+        "get",
+        "/api/tasks/{task_id}/events",
+        "tasks",
+        "Task progress stream",
+    )
+    .responds(ResponseBody::EventStream),
+    op("get", "/api/starred", "repos", "Starred repositories").query(inline::<api::StarredQuery>),
+    op(
+        "post",
+        "/api/repos/{repo_id}/mute",
+        "repos",
+        "Mute a repository in the feed",
+    ),
+    op(
+        "delete",
+        "/api/repos/{repo_id}/mute",
+        "repos",
+        "Unmute a repository",
+    ),
+    op(
+        "patch",
+        "/api/repos/{repo_id}/preferences",
+        "repos",
+        "Update per-repository preferences",
+    )
+    .body(schema::<api::RepoPreferencesRequest>)
+    .returns(schema::<api::RepoPreferencesResponse>),
+    op(
+        "post",
+        "/api/follows/import",
+        "repos",
+        "Follow the public repos of a user's stars or a star list",
+    )
+    .body(schema::<follows::FollowImportRequest>)
+    .returns(schema::<follows::FollowImportResponse>),
+    op(
+        "delete",
+        "/api/follows/{repo_id}",
+        "repos",
+        "Stop following a repository",
+    )
+    .returns(schema::<follows::FollowRemoveResponse>),
+    op(
+        "get",
+        "/api/repos/{repo_id}/releases",
+        "repos",
+        "Releases of one starred repository",
+    ),
+    op(
+        "get",
+        "/api/repos/{repo_id}/releases/unseen_count",
+        "repos",
+        "Unseen release count for a repository",
+    )
+    .returns(schema::<api::RepoUnseenCountResponse>),
+    op(
+        "get",
+        "/api/releases",
+        "releases",
+        "Releases across starred repositories",
+    ),
+    op(
+        "post",
+        "/api/releases/backfill-node-ids",
+        "releases",
+        "Resolve missing release node ids so reactions can load",
+    )
+    .returns(schema::<sync::BackfillReleaseNodeIdsResult>)
+    .task(),
+    op(
+        "get",
+        "/api/releases/{release_id}/detail",
+        "releases",
+        "Release detail",
+    ),
+    op(
+        "get",
+        "/api/releases/{release_id}/rendered",
+        "releases",
+        "Release notes as sanitized HTML",
+    )
+    .query(inline::<api::RenderedReleaseQuery>)
+    .returns(schema::<api::RenderedReleaseResponse>),
+    op(
+        "get",
+        "/api/releases/{release_id}/diff-prev",
+        "releases",
+        "Sections added and removed since the previous release",
+    )
+    .query(inline::<api::ReleaseDiffQuery>)
+    .returns(schema::<api::ReleaseDiffResponse>),
+    op(
+        "get",
+        "/api/repos/{owner}/{repo}/releases/tag/{tag}/detail",
+        "releases",
+        "Release detail by tag",
+    ),
+    op(
+        "get",
+        "/api/public/repos/{owner}/{repo}/releases",
+        "releases",
+        "Public release list",
+    ),
+    op(
+        "get",
+        "/api/public/repos/{owner}/{repo}/releases/tag/{tag}",
+        "releases",
+        "Public release detail",
+    ),
+    op(
+        "get",
+        "/api/notifications",
+        "notifications",
+        "Cached GitHub notifications, optionally grouped by subject",
+    )
+    .query(inline::<api::NotificationListQuery>),
+    op(
+        "get",
+        "/api/notifications/{thread_id}",
+        "notifications",
+        "Notification detail",
+    ),
+    op(
+        "post",
+        "/api/notifications/{thread_id}/archive",
+        "notifications",
+        "Archive a notification thread",
+    ),
+    op(
+        "get",
+        "/api/dashboard/updates",
+        "feed",
+        "Whether newer dashboard content exists",
+    ),
+    op("get", "/api/bookmarks", "bookmarks", "Bookmarked items")
+        .query(inline::<api::BookmarksQuery>)
+        .returns(schema::<api::BookmarksResponse>),
+    op("post", "/api/bookmarks", "bookmarks", "Bookmark an item")
+        .body(schema::<api::CreateBookmarkRequest>)
+        .returns(schema::<api::BookmarkItem>),
+    op(
+        "delete",
+        "/api/bookmarks/{bookmark_id}",
+        "bookmarks",
+        "Remove a bookmark",
+    )
+    .returns(schema::<api::BookmarkDeleteResponse>),
+    op("get", "/api/feed", "feed", "Merged feed page")
+        .query(inline::<api::FeedQuery>)
+        .returns(schema::<api::FeedResponse>),
+    op("get", "/api/feed/events", "feed", "Live feed updates").responds(ResponseBody::EventStream),
+    op("post", "/api/feed/seen", "feed", "Mark releases as seen")
+        .body(schema::<api::MarkFeedSeenRequest>)
+        .returns(schema::<api::MarkFeedSeenResponse>),
+    op(
+        "post",
+        "/api/feed/seen-at",
+        "feed",
+        "Reset the new-release counter baseline",
+    )
+    .returns(schema::<api::CountersResponse>),
+    op(
+        "get",
+        "/api/counters",
+        "feed",
+        "Unread and new-release badge counts",
+    )
+    .returns(schema::<api::CountersResponse>),
+    op(
+        "post",
+        "/api/feed/reactions/refresh",
+        "feed",
+        "Refresh reaction counts for releases",
+    ),
+    op("get", "/api/admin/users", "admin", "List users")
+        .query(inline::<api::AdminUsersQuery>)
+        .returns(schema::<api::AdminUsersListResponse>),
+    op(
+        "patch",
+        "/api/admin/users/{user_id}",
+        "admin",
+        "Change admin or disabled flags",
+    )
+    .body(schema::<api::AdminUserPatchRequest>)
+    .returns(schema::<api::AdminUserItem>),
+    op(
+        "delete",
+        "/api/admin/users/{user_id}",
+        "admin",
+        "Delete a user and all of their data",
+    )
+    .returns(schema::<account::AccountDeletionSummary>),
+    op(
+        "post",
+        "/api/admin/impersonate/{user_id}",
+        "admin",
+        "View the app as another user, read-only",
+    )
+    .returns(schema::<impersonation::ImpersonationResponse>),
+    op(
+        "delete",
+        "/api/admin/impersonate",
+        "admin",
+        "End impersonation",
+    )
+    .returns(schema::<impersonation::ImpersonationResponse>),
+    op(
+        "post",
+        "/api/admin/users/bulk",
+        "admin",
+        "Change admin or disabled flags for several users",
+    )
+    .body(schema::<api::AdminUserBulkRequest>)
+    .returns(schema::<api::AdminUserBulkResponse>),
+    op(
+        "get",
+        "/api/admin/users/{user_id}/stats",
+        "admin",
+        "User storage and sync footprint",
+    )
+    .returns(schema::<api::AdminUserStats>),
+    op(
+        "delete",
+        "/api/admin/translations",
+        "admin",
+        "Purge cached translations of one item",
+    )
+    .query(inline::<api::AdminTranslationCachePurgeQuery>)
+    .returns(schema::<api::TranslationCachePurgeResponse>),
+    op(
+        "post",
+        "/api/admin/translations/repair",
+        "admin",
+        "Queue a re-translation of damaged cached release translations",
+    )
+    .query(inline::<api::AdminTranslationRepairQuery>)
+    .returns(schema::<api::TaskAcceptedResponse>),
+    op(
+        "get",
+        "/api/admin/translations/stats",
+        "admin",
+        "Translation cache statistics across all users",
+    )
+    .returns(schema::<translation_stats::TranslationStatsResponse>),
+    op("get", "/api/admin/audit", "admin", "Admin audit log")
+        .query(inline::<admin_audit::AdminAuditQuery>)
+        .returns(schema::<admin_audit::AdminAuditListResponse>),
+    op(
+        "post",
+        "/api/admin/users/{user_id}/tasks",
+        "admin",
+        "Enqueue a task for a user",
+    ),
+    op(
+        "post",
+        "/api/admin/users/{user_id}/briefs/preview",
+        "admin",
+        "Show the prompts a brief run for a user would send",
+    )
+    .returns(schema::<ai::BriefPreview>),
+    op(
+        "get",
+        "/api/admin/users/{user_id}/profile",
+        "admin",
+        "User daily brief profile",
+    ),
+    op(
+        "patch",
+        "/api/admin/users/{user_id}/profile",
+        "admin",
+        "Update user daily brief profile",
+    ),
+    op("get", "/api/admin/dashboard", "admin", "Admin dashboard"),
+    op(
+        "get",
+        "/api/admin/repos/overview",
+        "admin",
+        "Repository governance overview",
+    ),
+    op(
+        "get",
+        "/api/admin/repos",
+        "admin",
+        "Repository governance list",
+    ),
+    op(
+        "get",
+        "/api/admin/public-release-repos",
+        "admin",
+        "Repositories served publicly",
+    ),
+    op(
+        "delete",
+        "/api/admin/public-release-repos/{usage_id}",
+        "admin",
+        "Drop a public release repository",
+    ),
+    op("get", "/api/admin/jobs/overview", "admin", "Job overview"),
+    op("get", "/api/admin/jobs/events", "admin", "Live job events")
+        .responds(ResponseBody::EventStream),
+    op("get", "/api/admin/jobs/realtime", "admin", "Realtime tasks"),
+    op(
+        "get",
+        "/api/admin/jobs/realtime/{task_id}",
+        "admin",
+        "Realtime task detail",
+    ),
+    op(
+        "get",
+        "/api/admin/jobs/realtime/{task_id}/log",
+        "admin",
+        "Download a task log",
+    )
+    .responds(ResponseBody::Download("text/plain")),
+    op(
+        "post",
+        "/api/admin/jobs/realtime/{task_id}/retry",
+        "admin",
+        "Retry a task",
+    ),
+    op(
+        "post",
+        "/api/admin/jobs/realtime/{task_id}/cancel",
+        "admin",
+        "Cancel a task",
+    ),
+    op(
+        "post",
+        "/api/admin/maintenance/prune",
+        "admin",
+        "Run maintenance pruning now",
+    ),
+    op(
+        "get",
+        "/api/admin/maintenance/db",
+        "admin",
+        "Last run of each database maintenance action",
+    )
+    .returns(schema::<db_maintenance::DbMaintenanceStatusResponse>),
+    op(
+        "post",
+        "/api/admin/maintenance/db",
+        "admin",
+        "Run a database maintenance action now",
+    )
+    .body(schema::<db_maintenance::DbMaintenanceRequest>)
+    .returns(schema::<db_maintenance::DbMaintenanceResponse>),
+    op(
+        "post",
+        "/api/admin/backup",
+        "admin",
+        "Snapshot the database into the backup directory",
+    )
+    .returns(schema::<backup::BackupResponse>),
+    op(
+        "get",
+        "/api/admin/backups",
+        "admin",
+        "Database snapshots in the backup directory",
+    )
+    .returns(schema::<backup::BackupListResponse>),
+    op(
+        "get",
+        "/api/admin/backups/{name}/download",
+        "admin",
+        "Download one database snapshot",
+    )
+    .responds(ResponseBody::Download("application/vnd.sqlite3")),
+    op(
+        "get",
+        "/api/admin/github-app/installations",
+        "admin",
+        "GitHub App installations and their last token mint outcome",
+    )
+    .returns(schema::<github_app::GitHubAppInstallationsResponse>),
+    op(
+        "get",
+        "/api/admin/jobs/scheduled",
+        "admin",
+        "Daily brief hour slots",
+    ),
+    op(
+        "patch",
+        "/api/admin/jobs/scheduled/{hour_utc}",
+        "admin",
+        "Enable or disable an hour slot",
+    ),
+    op(
+        "get",
+        "/api/admin/jobs/sync/runtime-config",
+        "admin",
+        "Sync runtime config",
+    ),
+    op(
+        "patch",
+        "/api/admin/jobs/sync/runtime-config",
+        "admin",
+        "Update sync runtime config",
+    ),
+    op(
+        "get",
+        "/api/admin/jobs/llm/status",
+        "admin",
+        "LLM scheduler status",
+    ),
+    op(
+        "patch",
+        "/api/admin/jobs/llm/runtime-config",
+        "admin",
+        "Update LLM runtime config",
+    ),
+    op("get", "/api/admin/jobs/llm/calls", "admin", "LLM call log"),
+    op(
+        "get",
+        "/api/admin/ai/health",
+        "admin",
+        "Probe AI upstream health",
+    )
+    .returns(schema::<ai::AiHealthReport>),
+    op("get", "/api/admin/llm/usage", "admin", "LLM usage"),
+    op(
+        "get",
+        "/api/admin/llm/usage/summary",
+        "admin",
+        "LLM usage per day or hour and source",
+    ),
+    op(
+        "get",
+        "/api/admin/briefs/feedback",
+        "admin",
+        "Brief rating distribution and recent notes",
+    )
+    .query(inline::<api::AdminBriefFeedbackQuery>)
+    .returns(schema::<api::AdminBriefFeedbackResponse>),
+    op(
+        "get",
+        "/api/admin/llm/calls/export",
+        "admin",
+        "Export LLM calls as CSV",
+    )
+    .responds(ResponseBody::Download("text/csv")),
+    op(
+        "patch",
+        "/api/admin/llm/scheduler",
+        "admin",
+        "Update LLM scheduler",
+    ),
+    op(
+        "get",
+        "/api/admin/llm/queue",
+        "admin",
+        "Calls waiting in the LLM scheduler queue",
+    )
+    .returns(schema::<api::AdminLlmQueueResponse>),
+    op(
+        "post",
+        "/api/admin/llm/queue/{call_id}/promote",
+        "admin",
+        "Move a queued LLM call to the front of its lane",
+    )
+    .returns(schema::<api::AdminLlmQueueResponse>),
+    op(
+        "delete",
+        "/api/admin/llm/queue/{call_id}",
+        "admin",
+        "Drop a queued LLM call and mark it failed",
+    )
+    .returns(schema::<api::AdminLlmQueueResponse>),
+    op(
+        "get",
+        "/api/admin/jobs/llm/calls/{call_id}",
+        "admin",
+        "LLM call detail",
+    ),
+    op(
+        "get",
+        "/api/admin/jobs/translations/status",
+        "admin",
+        "Translation scheduler status",
+    ),
+    op(
+        "patch",
+        "/api/admin/jobs/translations/runtime-config",
+        "admin",
+        "Update translation runtime config",
+    ),
+    op(
+        "get",
+        "/api/admin/jobs/translations/requests",
+        "admin",
+        "Translation requests",
+    ),
+    op(
+        "get",
+        "/api/admin/jobs/translations/requests/{request_id}",
+        "admin",
+        "Translation request detail",
+    ),
+    op(
+        "get",
+        "/api/admin/jobs/translations/batches",
+        "admin",
+        "Translation batches",
+    ),
+    op(
+        "get",
+        "/api/admin/jobs/translations/batches/{batch_id}",
+        "admin",
+        "Translation batch detail",
+    ),
+    op(
+        "get",
+        "/api/reaction-token/status",
+        "reactions",
+        "Reaction token status",
+    ),
+    op(
+        "post",
+        "/api/reaction-token/check",
+        "reactions",
+        "Check a reaction token",
+    ),
+    op(
+        "post",
+        "/api/reaction-token/diagnose",
+        "reactions",
+        "Diagnose reaction token repository access",
+    ),
+    op(
+        "put",
+        "/api/reaction-token",
+        "reactions",
+        "Store a reaction token",
+    ),
+    op(
+        "post",
+        "/api/release/reactions/toggle",
+        "reactions",
+        "Toggle a reaction on a release",
+    )
+    .body(schema::<api::ToggleReleaseReactionRequest>)
+    .returns(schema::<api::ToggleReleaseReactionResponse>),
+    op(
+        "get",
+        "/api/auth/bind-context",
+        "auth",
+        "Pending account binding context",
+    ),
+    op(
+        "post",
+        "/api/auth/passkeys/register/options",
+        "auth",
+        "Passkey registration options",
+    ),
+    op(
+        "post",
+        "/api/auth/passkeys/register/verify",
+        "auth",
+        "Finish passkey registration",
+    ),
+    op(
+        "post",
+        "/api/auth/passkeys/authenticate/options",
+        "auth",
+        "Passkey login options",
+    ),
+    op(
+        "post",
+        "/api/auth/passkeys/authenticate/verify",
+        "auth",
+        "Finish passkey login",
+    ),
+    op("get", "/api/briefs", "briefs", "Daily briefs"),
+    op(
+        "get",
+        "/api/briefs/deliveries",
+        "briefs",
+        "Brief delivery log",
+    ),
+    op(
+        "post",
+        "/api/briefs/generate",
+        "briefs",
+        "Generate a brief now",
+    )
+    .task(),
+    op(
+        "post",
+        "/api/briefs/preview",
+        "briefs",
+        "Show the prompts a brief run would send, without calling the model",
+    )
+    .returns(schema::<ai::BriefPreview>),
+    op(
+        "get",
+        "/api/briefs/{date}/sources",
+        "briefs",
+        "Releases a brief was built from",
+    )
+    .returns(schema::<api::BriefSourcesResponse>),
+    op(
+        "post",
+        "/api/briefs/{date}/translate",
+        "briefs",
+        "Translate the brief of a date",
+    )
+    .body(schema::<api::TranslateBriefRequest>)
+    .returns(schema::<api::TranslateResponse>),
+    op(
+        "post",
+        "/api/briefs/{date}/feedback",
+        "briefs",
+        "Rate the brief of a date (replaces an earlier rating)",
+    )
+    .body(schema::<api::BriefFeedbackRequest>)
+    .returns(schema::<api::BriefFeedbackResponse>),
+    op("get", "/api/channels", "channels", "Notification channels"),
+    op(
+        "post",
+        "/api/channels",
+        "channels",
+        "Add a notification channel",
+    ),
+    op(
+        "patch",
+        "/api/channels/{channel_id}",
+        "channels",
+        "Update a notification channel",
+    ),
+    op(
+        "delete",
+        "/api/channels/{channel_id}",
+        "channels",
+        "Remove a notification channel",
+    ),
+    op(
+        "post",
+        "/api/translate/requests",
+        "translate",
+        "Submit a translation request",
+    ),
+    op(
+        "get",
+        "/api/translate/requests/{request_id}",
+        "translate",
+        "Translation request state",
+    ),
+    op(
+        "post",
+        "/api/translate/results",
+        "translate",
+        "Resolve cached translation results",
+    ),
+    op(
+        "get",
+        "/api/translate/requests/{request_id}/stream",
+        "translate",
+        "Translation request stream",
+    )
+    .responds(ResponseBody::EventStream),
+    op(
+        "post",
+        "/api/translate/releases/batch",
+        "translate",
+        "Translate releases",
+    )
+    .body(schema::<api::TranslateReleasesBatchRequest>)
+    .returns(schema::<api::TranslateBatchResponse>),
+    op(
+        "post",
+        "/api/translate/releases/batch/stream",
+        "translate",
+        "Translate releases, streamed",
+    )
+    .body(schema::<api::TranslateReleasesBatchRequest>)
+    .responds(ResponseBody::Ndjson),
+    op(
+        "get",
+        "/api/translations/batch/{task_id}/stream",
+        "translate",
+        "Re-attach to a streamed release translation batch",
+    )
+    .responds(ResponseBody::Ndjson),
+    op(
+        "delete",
+        "/api/translations",
+        "translate",
+        "Drop your cached translations of one item",
+    )
+    .query(inline::<api::TranslationCachePurgeQuery>)
+    .returns(schema::<api::TranslationCachePurgeResponse>),
+    op(
+        "post",
+        "/api/translations/repair",
+        "translate",
+        "Re-translate your cached release translations that came back damaged",
+    )
+    .returns(schema::<api::TranslationRepairResult>)
+    .task(),
+    op(
+        "get",
+        "/api/translations/stats",
+        "translate",
+        "Statistics of your translation cache",
+    )
+    .returns(schema::<translation_stats::TranslationStatsResponse>),
+    op(
+        "post",
+        "/api/translate/release",
+        "translate",
+        "Translate one release",
+    )
+    .task(),
+    op(
+        "post",
+        "/api/translate/release/detail/batch",
+        "translate",
+        "Translate release details",
+    ),
+    op(
+        "post",
+        "/api/translate/release/detail",
+        "translate",
+        "Translate one release detail",
+    )
+    .task(),
+    op(
+        "post",
+        "/api/translate/notifications/batch",
+        "translate",
+        "Translate notifications",
+    ),
+    op(
+        "post",
+        "/api/translate/notification",
+        "translate",
+        "Translate one notification",
+    )
+    .task(),
+    op(
+        "post",
+        "/api/sync/starred",
+        "sync",
+        "Sync starred repositories",
+    )
+    .task(),
+    op("post", "/api/sync/all", "sync", "Run a full sync").task(),
+    op("post", "/api/sync/releases", "sync", "Sync releases").task(),
+    op(
+        "post",
+        "/api/sync/notifications",
+        "sync",
+        "Sync notifications",
+    )
+    .task(),
+    op(
+        "get",
+        "/api/sync/issues",
+        "sync",
+        "List repositories whose last sync failed",
+    )
+    .returns(schema::<sync_issues::SyncIssuesResponse>),
+    op(
+        "post",
+        "/api/sync/issues/{id}/dismiss",
+        "sync",
+        "Dismiss a repository sync issue",
+    )
+    .returns(schema::<sync_issues::SyncIssueDismissResponse>),
+    op(
+        "get",
+        "/api/sync/purges",
+        "sync",
+        "List unstarred repositories scheduled for data deletion",
+    )
+    .returns(schema::<sync_issues::PendingPurgesResponse>),
+    op("get", "/auth/github/login", "auth", "Start GitHub login").responds(ResponseBody::Redirect),
+    op(
+        "get",
+        "/auth/github/connect",
+        "auth",
+        "Start linking another GitHub account",
+    )
+    .responds(ResponseBody::Redirect),
+    op(
+        "get",
+        "/auth/github/callback",
+        "auth",
+        "GitHub OAuth callback",
+    )
+    .responds(ResponseBody::Redirect),
+    op("get", "/auth/linuxdo/login", "auth", "Start LinuxDo login")
+        .responds(ResponseBody::Redirect),
+    op(
+        "get",
+        "/auth/linuxdo/callback",
+        "auth",
+        "LinuxDo OAuth callback",
+    )
+    .responds(ResponseBody::Redirect),
+    op("get", "/auth/logout", "auth", "Log out").responds(ResponseBody::Redirect),
+    op(
+        "get",
+        "/feeds/{token}/releases.atom",
+        "feed",
+        "Atom feed of recent releases (secret token in the path)",
+    )
+    .query(inline::<atom_feed::AtomFeedQuery>)
+    .responds(ResponseBody::Text("application/atom+xml")),
+    op(
+        "post",
+        "/webhooks/github",
+        "system",
+        "GitHub App installation webhooks (HMAC signed with GITHUB_APP_WEBHOOK_SECRET); 204 when applied",
+    ),
+    op(
+        "get",
+        "/metrics",
+        "system",
+        "Prometheus metrics (bearer token from OCTORILL_METRICS_TOKEN)",
+    )
+    .responds(ResponseBody::Text("text/plain; version=0.0.4")),
+    op(
+        "get",
+        "/healthz",
+        "system",
+        "Liveness probe with build info",
+    ),
+    op(
+        "get",
+        "/readyz",
+        "system",
+        "Readiness probe (database, migrations, encryption key, optionally GitHub); 503 when not ready",
+    ),
+];
+
+pub async fn openapi_json() -> Json<Value> {
+    static DOCUMENT: OnceLock<Value> = OnceLock::new();
+    Json(DOCUMENT.get_or_init(build_document).clone())
+}
+
+fn build_document() -> Value {
+    // Requests are described as they are parsed, responses as they are
+    // written; the two only differ for optional and skipped fields.
+    let mut requests = SchemaSettings::openapi3().into_generator();
+    let mut responses = SchemaSettings::openapi3()
+        .with(|settings| settings.contract = Contract::Serialize)
+        .into_generator();
+    let error = responses.subschema_for::<ApiErrorBody>();
+    let task_accepted = responses.subschema_for::<api::TaskAcceptedResponse>();
+
+    let mut paths = Map::new();
+    for operation in OPERATIONS {
+        let mut parameters = path_parameters(operation.path);
+        if let Some(query) = operation.query {
+            parameters.extend(query_parameters(query(&mut requests)));
+        }
+
+        let ok = match operation.response {
+            ResponseBody::Json(body) => json!({
+                "description": "OK",
+                "content": { "application/json": { "schema": json_schema(body, &mut responses) } },
+            }),
+            ResponseBody::TaskOrJson(body) => json!({
+                "description": "Result for `return_mode=sync`, the queued task for `task_id`, or its event stream for `sse`",
+                "content": {
+                    "application/json": {
+                        "schema": { "oneOf": [json_schema(body, &mut responses), task_accepted] },
+                    },
+                    "text/event-stream": { "schema": { "type": "string" } },
+                },
+            }),
+            ResponseBody::EventStream => json!({
+                "description": "Server-sent events",
+                "content": { "text/event-stream": { "schema": { "type": "string" } } },
+            }),
+            ResponseBody::Ndjson => json!({
+                "description": "One JSON event per line",
+                "content": { "application/x-ndjson": { "schema": { "type": "string" } } },
+            }),
+            ResponseBody::Download(media_type) => json!({
+                "description": "File download",
+                "content": { media_type: { "schema": { "type": "string", "format": "binary" } } },
+            }),
+            ResponseBody::Text(media_type) => json!({
+                "description": "Plain text",
+                "content": { media_type: { "schema": { "type": "string" } } },
+            }),
+            ResponseBody::Redirect => json!({ "description": "Redirect" }),
+        };
+        let ok_status = match operation.response {
+            ResponseBody::Redirect => "303",
+            _ => "200",
+        };
+
+        let mut entry = json!({
+            "tags": [operation.tag],
+            "summary": operation.summary,
+            "responses": {
+                ok_status: ok,
+                "default": {
+                    "description": "Error",
+                    "content": { "application/json": { "schema": error } },
+                },
+            },
+        });
+        if !parameters.is_empty() {
+            entry["parameters"] = Value::Array(parameters);
+        }
+        if let Some(request) = operation.request {
+            entry["requestBody"] = json!({
+                "required": true,
+                "content": { "application/json": { "schema": request(&mut requests) } },
+            });
+        }
+
+        paths
+            .entry(operation.path)
+            .or_insert_with(|| json!({}))
+            .as_object_mut()
+            .expect("path item is an object")
+            .insert(operation.method.to_owned(), entry);
+    }
+
+    let mut schemas = requests.take_definitions(true);
+    schemas.extend(responses.take_definitions(true));
+
+    json!({
+        "openapi": "3.0.3",
+        "info": {
+            "title": "OctoRill API",
+            "version": version::resolve_effective_version().version,
+        },
+        "paths": paths,
+        "components": { "schemas": schemas },
+    })
+}
+
+fn json_schema(body: Option<SchemaFn>, generator: &mut SchemaGenerator) -> Value {
+    body.map(|body| body(generator).to_value())
+        .unwrap_or_else(|| json!({ "type": "object" }))
+}
+
+fn path_parameters(path: &str) -> Vec<Value> {
+    path.split('/')
+        .filter_map(|segment| segment.strip_prefix('{')?.strip_suffix('}'))
+        .map(|name| {
+            json!({
+                "name": name,
+                "in": "path",
+                "required": true,
+                "schema": { "type": "string" },
+            })
+        })
+        .collect()
+}
+
+fn query_parameters(query: Schema) -> Vec<Value> {
+    let required = query
+        .get("required")
+        .and_then(Value::as_array)
+        .cloned()
+        .unwrap_or_default();
+    query
+        .get("properties")
+        .and_then(Value::as_object)
+        .into_iter()
+        .flatten()
+        .map(|(name, schema)| {
+            json!({
+                "name": name,
+                "in": "query",
+                "required": required.iter().any(|field| field == name),
+                "schema": schema,
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeSet;
+
+    use super::*;
+
+    /// `(method, path)` pairs registered in `server.rs` and `api::router`, read
+    /// from their source so that a new route cannot skip this document.
+    fn registered_routes() -> BTreeSet<(String, String)> {
+        let source = include_str!("server.rs");
+        let api_source = include_str!("api/mod.rs");
+        let api_router_start = api_source
+            .find("pub(crate) fn router(")
+            .expect("api::router");
+        let api_router_end = api_router_start
+            + api_source[api_router_start..]
+                .find("\n}\n")
+                .expect("api::router end");
+        let api_start = source.find("fn api_router(").expect("api router");
+        let api_end = api_start + source[api_start..].find("\n}\n").expect("api router end");
+        let app_start = source
+            .find("let mut app = Router::new()")
+            .expect("app router");
+        let app_end = app_start + source[app_start..].find(';').expect("app router end");
+        let auth_start = source
+            .find("let auth_router = Router::new()")
+            .expect("auth router");
+        let auth_end = auth_start + source[auth_start..].find(';').expect("auth router end");
+
+        let mut routes = BTreeSet::new();
+        for (prefix, block) in [
+            ("/api", &source[api_start..api_end]),
+            ("/api", &api_source[api_router_start..api_router_end]),
+            ("", &source[app_start..app_end]),
+            ("", &source[auth_start..auth_end]),
+        ] {
+            for call in block.split(".route(").skip(1) {
+                let path = call.split('"').nth(1).expect("route path");
+                let mut depth = 1;
+                let args_end = call
+                    .char_indices()
+                    .find(|(_, ch)| {
+                        match ch {
+                            '(' => depth += 1,
+                            ')' => depth -= 1,
+                            _ => {}
+                        }
+                        depth == 0
+                    })
+                    .map(|(index, _)| index)
+                    .expect("route call end");
+                let args = &call[..args_end];
+                for method in ["get", "post", "put", "patch", "delete"] {
+                    let needle = format!("{method}(");
+                    let registered = args.match_indices(&needle).any(|(index, _)| {
+                        !args[..index].ends_with(|ch: char| ch.is_alphanumeric() || ch == '_')
+                    });
+                    if registered {
+                        routes.insert((method.to_owned(), format!("{prefix}{path}")));
+                    }
+                }
+            }
+        }
+        routes
+    }
+
+    #[test]
+    fn document_covers_exactly_the_registered_routes() {
+        let documented = OPERATIONS
+            .iter()
+            .map(|operation| (operation.method.to_owned(), operation.path.to_owned()))
+            .collect::<BTreeSet<_>>();
+
+        assert_eq!(documented.len(), OPERATIONS.len(), "duplicate operation");
+        assert_eq!(documented, registered_routes());
+    }
+
+    #[test]
+    fn document_refs_resolve_and_model_string_enums() {
+        let document = build_document();
+        let schemas = document["components"]["schemas"]
+            .as_object()
+            .expect("component schemas");
+
+        fn collect_refs(value: &Value, refs: &mut BTreeSet<String>) {
+            match value {
+                Value::Object(object) => {
+                    if let Some(Value::String(reference)) = object.get("$ref") {
+                        refs.insert(reference.clone());
+                    }
+                    object.values().for_each(|value| collect_refs(value, refs));
+                }
+                Value::Array(items) => items.iter().for_each(|value| collect_refs(value, refs)),
+                _ => {}
+            }
+        }
+        let mut refs = BTreeSet::new();
+        collect_refs(&document, &mut refs);
+        assert!(!refs.is_empty());
+        for reference in refs {
+            let name = reference
+                .strip_prefix("#/components/schemas/")
+                .unwrap_or_else(|| panic!("unexpected ref {reference}"));
+            assert!(schemas.contains_key(name), "dangling ref {reference}");
+        }
+
+        assert_eq!(
+            schemas["ReturnMode"]["enum"],
+            json!(["sync", "task_id", "sse"])
+        );
+        assert_eq!(
+            schemas["ReleaseReactionContent"]["enum"],
+            json!(["plus1", "laugh", "heart", "hooray", "rocket", "eyes"])
+        );
+        assert_eq!(schemas["ApiErrorBody"]["required"], json!(["ok", "error"]));
+        let feed = &document["paths"]["/api/feed"]["get"];
+        assert!(
+            feed["parameters"]
+                .as_array()
+                .expect("feed parameters")
+                .iter()
+                .any(|parameter| parameter["name"] == "cursor" && parameter["in"] == "query")
+        );
+    }
+}
@@ -7,7 +7,11 @@ use sqlx::{Sqlite, Transaction};
 use uuid::Uuid;
 use webauthn_rs::prelude::{AuthenticationResult, Passkey, PasskeyRegistration};
 
-use crate::{error::ApiError, local_id, state::AppState};
+use crate::{
+    error::{ApiError, codes},
+    local_id,
+    state::AppState,
+};
 
 pub const SESSION_KEY_PENDING_PASSKEY_REGISTRATION: &str = "pending_passkey_registration";
 pub const SESSION_KEY_PENDING_PASSKEY_CREDENTIAL: &str = "pending_passkey_credential";
@@ -333,7 +337,7 @@ pub async fn mark_passkey_authentication_used(
     let Some(_) = stored_passkey.passkey.update_credential(result) else {
         return Err(ApiError::new(
             StatusCode::UNAUTHORIZED,
-            "passkey_mismatch",
+            codes::PASSKEY_MISMATCH,
             "passkey does not match the authenticated credential",
         ));
     };
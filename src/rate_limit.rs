@@ -0,0 +1,410 @@
+use std::{
+    collections::HashMap,
+    net::{IpAddr, SocketAddr},
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+use axum::{
+    extract::{ConnectInfo, Request, State},
+    http::{HeaderMap, HeaderValue, StatusCode, header},
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use tower_sessions::Session;
+
+use crate::{
+    error::{ApiError, codes},
+    state::AppState,
+};
+
+pub const DEFAULT_USER_REQUESTS_PER_MINUTE: u32 = 120;
+pub const DEFAULT_AUTH_IP_REQUESTS_PER_MINUTE: u32 = 30;
+/// A bucket untouched this long has refilled completely, so dropping it is
+/// indistinguishable from keeping it.
+const BUCKET_IDLE_TTL: Duration = Duration::from_secs(60);
+const BUCKET_CLEANUP_INTERVAL: Duration = Duration::from_secs(5 * 60);
+
+/// Requests per minute allowed before the server answers 429. Each bucket
+/// holds a minute's worth of tokens, so a full budget can be spent in a burst.
+#[derive(Clone, Debug)]
+pub struct RateLimits {
+    /// API requests per signed-in user; anonymous API calls share this rate
+    /// per client IP.
+    pub user_per_minute: u32,
+    /// OAuth login and callback requests per client IP.
+    pub auth_ip_per_minute: u32,
+}
+
+impl Default for RateLimits {
+    fn default() -> Self {
+        Self {
+            user_per_minute: DEFAULT_USER_REQUESTS_PER_MINUTE,
+            auth_ip_per_minute: DEFAULT_AUTH_IP_REQUESTS_PER_MINUTE,
+        }
+    }
+}
+
+#[derive(Debug)]
+struct TokenBucket {
+    tokens: f64,
+    refilled_at: Instant,
+}
+
+/// In-process token buckets keyed by user or client IP. Limits are per
+/// server process; they are not shared between replicas.
+#[derive(Clone, Default)]
+pub struct RequestRateLimiter {
+    buckets: Arc<Mutex<HashMap<String, TokenBucket>>>,
+}
+
+impl RequestRateLimiter {
+    /// Takes one token from `key`'s bucket, or returns how long until the
+    /// next token is available.
+    fn take_at(&self, key: &str, per_minute: u32, now: Instant) -> Result<(), Duration> {
+        let capacity = f64::from(per_minute.max(1));
+        let per_sec = capacity / 60.0;
+        let mut buckets = self.buckets.lock().expect("request rate limiter poisoned");
+        let bucket = buckets
+            .entry(key.to_owned())
+            .or_insert_with(|| TokenBucket {
+                tokens: capacity,
+                refilled_at: now,
+            });
+        let elapsed = now.saturating_duration_since(bucket.refilled_at);
+        bucket.tokens = (bucket.tokens + elapsed.as_secs_f64() * per_sec).min(capacity);
+        bucket.refilled_at = now;
+        if bucket.tokens >= 1.0 {
+            bucket.tokens -= 1.0;
+            return Ok(());
+        }
+        Err(Duration::from_secs_f64((1.0 - bucket.tokens) / per_sec))
+    }
+
+    /// Drops buckets that have been idle long enough to be full again.
+    pub fn prune_idle(&self) -> usize {
+        self.prune_idle_at(Instant::now())
+    }
+
+    fn prune_idle_at(&self, now: Instant) -> usize {
+        let mut buckets = self.buckets.lock().expect("request rate limiter poisoned");
+        let before = buckets.len();
+        buckets.retain(|_, bucket| {
+            now.saturating_duration_since(bucket.refilled_at) < BUCKET_IDLE_TTL
+        });
+        before - buckets.len()
+    }
+}
+
+pub fn spawn_bucket_cleanup(state: Arc<AppState>) {
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(BUCKET_CLEANUP_INTERVAL);
+        interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+        interval.tick().await;
+        loop {
+            interval.tick().await;
+            let pruned = state.request_limiter.prune_idle();
+            if pruned > 0 {
+                tracing::debug!(pruned, "pruned idle rate limit buckets");
+            }
+        }
+    });
+}
+
+/// Limits `/api` per signed-in user, falling back to the client IP for
+/// anonymous calls. Streaming endpoints pass through once, so an SSE
+/// connection costs a single token however long it stays open.
+pub async fn limit_api(
+    State(state): State<Arc<AppState>>,
+    session: Session,
+    request: Request,
+    next: Next,
+) -> Response {
+    let key = match session.get::<String>("user_id").await {
+        Ok(Some(user_id)) => format!("user:{user_id}"),
+        _ => format!(
+            "ip:{}",
+            client_ip(&request, state.config.trust_forwarded_headers)
+        ),
+    };
+    limit(
+        &state,
+        &key,
+        state.config.rate_limits.user_per_minute,
+        request,
+        next,
+    )
+    .await
+}
+
+/// Limits the OAuth login and callback routes per client IP.
+pub async fn limit_auth(
+    State(state): State<Arc<AppState>>,
+    request: Request,
+    next: Next,
+) -> Response {
+    let key = format!(
+        "auth:{}",
+        client_ip(&request, state.config.trust_forwarded_headers)
+    );
+    limit(
+        &state,
+        &key,
+        state.config.rate_limits.auth_ip_per_minute,
+        request,
+        next,
+    )
+    .await
+}
+
+async fn limit(
+    state: &AppState,
+    key: &str,
+    per_minute: u32,
+    request: Request,
+    next: Next,
+) -> Response {
+    match state
+        .request_limiter
+        .take_at(key, per_minute, Instant::now())
+    {
+        Ok(()) => next.run(request).await,
+        Err(retry_after) => rate_limited_response(retry_after),
+    }
+}
+
+fn rate_limited_response(retry_after: Duration) -> Response {
+    let retry_after_secs = retry_after.as_secs_f64().ceil().max(1.0) as u64;
+    let mut response = ApiError::new(
+        StatusCode::TOO_MANY_REQUESTS,
+        codes::RATE_LIMITED_LOCAL,
+        format!("too many requests; retry in {retry_after_secs}s"),
+    )
+    .into_response();
+    response
+        .headers_mut()
+        .insert(header::RETRY_AFTER, HeaderValue::from(retry_after_secs));
+    response
+}
+
+/// The peer address, or the client address reported by the proxy when
+/// forwarded headers are trusted and the peer is on loopback or a private
+/// network. Headers from public peers are ignored so clients cannot pick
+/// their own bucket.
+fn client_ip(request: &Request, trust_forwarded: bool) -> String {
+    let peer = request
+        .extensions()
+        .get::<ConnectInfo<SocketAddr>>()
+        .map(|ConnectInfo(addr)| addr.ip());
+    match peer {
+        Some(peer) if trust_forwarded && is_proxy_peer(peer) => {
+            forwarded_ip(request.headers()).unwrap_or(peer).to_string()
+        }
+        Some(peer) => peer.to_string(),
+        None => "unknown".to_owned(),
+    }
+}
+
+fn is_proxy_peer(ip: IpAddr) -> bool {
+    match ip {
+        IpAddr::V4(v4) => v4.is_loopback() || v4.is_private(),
+        IpAddr::V6(v6) => v6.is_loopback() || v6.is_unique_local(),
+    }
+}
+
+/// The rightmost `X-Forwarded-For` hop is the one our proxy appended; the
+/// entries before it come from the client and can be spoofed.
+fn forwarded_ip(headers: &HeaderMap) -> Option<IpAddr> {
+    headers
+        .get("x-forwarded-for")
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.rsplit(',').next())
+        .or_else(|| {
+            headers
+                .get("x-real-ip")
+                .and_then(|value| value.to_str().ok())
+        })
+        .and_then(|value| value.trim().parse::<IpAddr>().ok())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use axum::{Router, body::Body, middleware, routing::get};
+    use sqlx::{SqlitePool, sqlite::SqlitePoolOptions};
+    use tower::ServiceExt;
+
+    use crate::config::AppConfig;
+
+    fn setup_state(
+        pool: SqlitePool,
+        rate_limits: RateLimits,
+        trust_forwarded_headers: bool,
+    ) -> Arc<AppState> {
+        let config = AppConfig {
+            rate_limits,
+            trust_forwarded_headers,
+            ..AppConfig::for_tests()
+        };
+        Arc::new(AppState::for_tests(pool, config))
+    }
+
+    async fn send(router: &Router, peer: &str) -> Response {
+        let mut request = Request::builder()
+            .uri("/auth/github/login")
+            .body(Body::empty())
+            .expect("build request");
+        request
+            .extensions_mut()
+            .insert(ConnectInfo(peer.parse::<SocketAddr>().expect("peer addr")));
+        router.clone().oneshot(request).await.expect("send request")
+    }
+
+    #[tokio::test]
+    async fn auth_burst_is_rejected_until_the_bucket_refills() {
+        let pool = SqlitePoolOptions::new()
+            .max_connections(1)
+            .connect("sqlite::memory:")
+            .await
+            .expect("create sqlite memory db");
+        let state = setup_state(
+            pool,
+            RateLimits {
+                auth_ip_per_minute: 2,
+                ..RateLimits::default()
+            },
+            false,
+        );
+        let router = Router::new().route(
+            "/auth/github/login",
+            get(|| async { StatusCode::NO_CONTENT })
+                .layer(middleware::from_fn_with_state(state.clone(), limit_auth)),
+        );
+
+        for _ in 0..2 {
+            assert_eq!(
+                send(&router, "198.51.100.7:5000").await.status(),
+                StatusCode::NO_CONTENT
+            );
+        }
+        let limited = send(&router, "198.51.100.7:5001").await;
+        assert_eq!(limited.status(), StatusCode::TOO_MANY_REQUESTS);
+        assert_eq!(
+            limited
+                .headers()
+                .get(header::RETRY_AFTER)
+                .and_then(|value| value.to_str().ok()),
+            Some("30")
+        );
+        let body = axum::body::to_bytes(limited.into_body(), usize::MAX)
+            .await
+            .expect("read body");
+        let body: serde_json::Value = serde_json::from_slice(&body).expect("parse body");
+        assert_eq!(body["error"]["code"], "rate_limited_local");
+        assert_eq!(
+            send(&router, "198.51.100.8:5000").await.status(),
+            StatusCode::NO_CONTENT,
+            "other clients keep their own bucket"
+        );
+
+        // Age the bucket by one refill interval instead of sleeping.
+        state
+            .request_limiter
+            .buckets
+            .lock()
+            .expect("lock buckets")
+            .get_mut("auth:198.51.100.7")
+            .expect("client bucket")
+            .refilled_at -= Duration::from_secs(30);
+        assert_eq!(
+            send(&router, "198.51.100.7:5000").await.status(),
+            StatusCode::NO_CONTENT
+        );
+        assert_eq!(
+            send(&router, "198.51.100.7:5000").await.status(),
+            StatusCode::TOO_MANY_REQUESTS
+        );
+    }
+
+    #[test]
+    fn prune_idle_drops_only_refilled_buckets() {
+        let limiter = RequestRateLimiter::default();
+        let start = Instant::now();
+        limiter.take_at("ip:old", 30, start).expect("take old");
+        limiter
+            .take_at("ip:new", 30, start + Duration::from_secs(30))
+            .expect("take new");
+
+        assert_eq!(limiter.prune_idle_at(start + BUCKET_IDLE_TTL), 1);
+        let buckets = limiter.buckets.lock().expect("lock buckets");
+        assert!(buckets.contains_key("ip:new"));
+    }
+
+    #[test]
+    fn client_ip_trusts_forwarded_header_only_when_enabled_and_from_private_peers() {
+        let forwarded = |peer: &str, trust_forwarded: bool| {
+            let mut request = Request::new(axum::body::Body::empty());
+            request
+                .headers_mut()
+                .insert("x-forwarded-for", HeaderValue::from_static("203.0.113.9"));
+            request
+                .extensions_mut()
+                .insert(ConnectInfo(peer.parse::<SocketAddr>().expect("peer addr")));
+            client_ip(&request, trust_forwarded)
+        };
+
+        assert_eq!(forwarded("10.0.0.2:443", true), "203.0.113.9");
+        assert_eq!(forwarded("127.0.0.1:443", true), "203.0.113.9");
+        assert_eq!(forwarded("198.51.100.7:443", true), "198.51.100.7");
+        assert_eq!(forwarded("10.0.0.2:443", false), "10.0.0.2");
+    }
+
+    #[tokio::test]
+    async fn spoofed_leftmost_forwarded_hops_share_the_proxy_reported_bucket() {
+        let pool = SqlitePoolOptions::new()
+            .max_connections(1)
+            .connect("sqlite::memory:")
+            .await
+            .expect("create sqlite memory db");
+        let state = setup_state(
+            pool,
+            RateLimits {
+                auth_ip_per_minute: 2,
+                ..RateLimits::default()
+            },
+            true,
+        );
+        let router = Router::new().route(
+            "/auth/github/login",
+            get(|| async { StatusCode::NO_CONTENT })
+                .layer(middleware::from_fn_with_state(state.clone(), limit_auth)),
+        );
+        // The client rotates its own `X-Forwarded-For`; the proxy appends
+        // the address it actually saw.
+        let send_spoofed = |spoofed: &'static str| {
+            let mut request = Request::builder()
+                .uri("/auth/github/login")
+                .header("x-forwarded-for", format!("{spoofed}, 203.0.113.9"))
+                .body(Body::empty())
+                .expect("build request");
+            request.extensions_mut().insert(ConnectInfo(
+                "10.0.0.2:443".parse::<SocketAddr>().expect("peer addr"),
+            ));
+            router.clone().oneshot(request)
+        };
+
+        for spoofed in ["192.0.2.1", "192.0.2.2"] {
+            assert_eq!(
+                send_spoofed(spoofed).await.expect("send").status(),
+                StatusCode::NO_CONTENT
+            );
+        }
+        assert_eq!(
+            send_spoofed("192.0.2.3").await.expect("send").status(),
+            StatusCode::TOO_MANY_REQUESTS
+        );
+        let buckets = state.request_limiter.buckets.lock().expect("lock buckets");
+        assert_eq!(buckets.keys().collect::<Vec<_>>(), vec!["auth:203.0.113.9"]);
+    }
+}
@@ -0,0 +1,391 @@
+use std::borrow::Cow;
+
+use anyhow::{Context, Result};
+use pulldown_cmark::{CowStr, Event, Options, Parser, Tag, TagEnd, html};
+use sha2::{Digest, Sha256};
+use sqlx::SqlitePool;
+use url::Url;
+
+/// Bumped whenever rendering rules change so cached HTML is not reused.
+const RELEASE_RENDER_VERSION: &str = "release-render-v1";
+const ISSUE_REF_MAX_DIGITS: usize = 9;
+const IMAGE_EXTENSIONS: [&str; 7] = ["png", "jpg", "jpeg", "gif", "svg", "webp", "avif"];
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RenderedMarkdown {
+    pub html: String,
+    /// Elements present before sanitizing but gone afterwards.
+    pub stripped_elements: i64,
+}
+
+/// Where relative links in a release body point: the repo at the release tag.
+#[derive(Debug, Clone)]
+struct RepoUrlBases {
+    repo: String,
+    blob: Url,
+    raw: Url,
+}
+
+impl RepoUrlBases {
+    fn new(repo_html_url: &str, git_ref: &str) -> Option<Self> {
+        let repo = repo_html_url.trim().trim_end_matches('/').to_owned();
+        let git_ref = git_ref
+            .split('/')
+            .map(|segment| urlencoding::encode(segment).into_owned())
+            .collect::<Vec<_>>()
+            .join("/");
+        Some(Self {
+            blob: Url::parse(&format!("{repo}/blob/{git_ref}/")).ok()?,
+            raw: Url::parse(&format!("{repo}/raw/{git_ref}/")).ok()?,
+            repo,
+        })
+    }
+
+    /// Absolute form of a repo-relative URL, or `None` when it should be kept
+    /// as written (absolute URLs, fragments, unparsable input).
+    fn resolve(&self, url: &str, is_image: bool) -> Option<String> {
+        let url = url.trim();
+        if url.is_empty() || url.starts_with('#') {
+            return None;
+        }
+        if !matches!(
+            Url::parse(url),
+            Err(url::ParseError::RelativeUrlWithoutBase)
+        ) {
+            return None;
+        }
+        let base = if is_image { &self.raw } else { &self.blob };
+        base.join(url).ok().map(String::from)
+    }
+}
+
+/// Raw HTML in release notes has no image/link distinction left by the time
+/// the sanitizer sees it, so images are guessed from the file extension.
+impl<'a> ammonia::UrlRelativeEvaluate<'a> for RepoUrlBases {
+    fn evaluate<'url>(&self, url: &'url str) -> Option<Cow<'url, str>> {
+        Some(
+            self.resolve(url, url_has_image_extension(url))
+                .map_or(Cow::Borrowed(url), Cow::Owned),
+        )
+    }
+}
+
+pub fn release_render_source_hash(markdown: &str, repo_html_url: &str, git_ref: &str) -> String {
+    let mut hasher = Sha256::new();
+    for part in [RELEASE_RENDER_VERSION, repo_html_url, git_ref, markdown] {
+        hasher.update(part.as_bytes());
+        hasher.update([0]);
+    }
+    format!("{:x}", hasher.finalize())
+}
+
+/// Renders release markdown to sanitized HTML. Relative links and images are
+/// resolved against the repo at `git_ref`, and bare `#123` references become
+/// issue links.
+pub fn render_release_markdown(
+    markdown: &str,
+    repo_html_url: &str,
+    git_ref: &str,
+) -> RenderedMarkdown {
+    let bases = RepoUrlBases::new(repo_html_url, git_ref);
+    let events = link_release_events(
+        Parser::new_ext(
+            markdown,
+            Options::ENABLE_TABLES | Options::ENABLE_STRIKETHROUGH | Options::ENABLE_TASKLISTS,
+        ),
+        bases.as_ref(),
+    );
+    let mut unsafe_html = String::new();
+    html::push_html(&mut unsafe_html, events.into_iter());
+
+    let mut sanitizer = ammonia::Builder::default();
+    if let Some(bases) = bases {
+        sanitizer.url_relative(ammonia::UrlRelative::Custom(Box::new(bases)));
+    }
+    let html = sanitizer.clean(&unsafe_html).to_string();
+    let stripped_elements = count_elements(&unsafe_html).saturating_sub(count_elements(&html));
+
+    RenderedMarkdown {
+        html,
+        stripped_elements: i64::try_from(stripped_elements).unwrap_or(i64::MAX),
+    }
+}
+
+/// Rendered HTML for `markdown`, reusing the cached copy for identical input.
+pub async fn render_release_markdown_cached(
+    pool: &SqlitePool,
+    markdown: &str,
+    repo_html_url: &str,
+    git_ref: &str,
+) -> Result<RenderedMarkdown> {
+    let source_hash = release_render_source_hash(markdown, repo_html_url, git_ref);
+    let cached = sqlx::query_as::<_, (String, i64)>(
+        r#"
+        SELECT html, stripped_elements
+        FROM release_render_cache
+        WHERE source_hash = ?
+        "#,
+    )
+    .bind(source_hash.as_str())
+    .fetch_optional(pool)
+    .await
+    .context("load rendered release cache")?;
+    if let Some((html, stripped_elements)) = cached {
+        return Ok(RenderedMarkdown {
+            html,
+            stripped_elements,
+        });
+    }
+
+    let rendered = render_release_markdown(markdown, repo_html_url, git_ref);
+    sqlx::query(
+        r#"
+        INSERT INTO release_render_cache (source_hash, html, stripped_elements, created_at)
+        VALUES (?, ?, ?, ?)
+        ON CONFLICT(source_hash) DO NOTHING
+        "#,
+    )
+    .bind(source_hash.as_str())
+    .bind(rendered.html.as_str())
+    .bind(rendered.stripped_elements)
+    .bind(chrono::Utc::now().to_rfc3339())
+    .execute(pool)
+    .await
+    .context("store rendered release cache")?;
+    Ok(rendered)
+}
+
+fn link_release_events<'a>(
+    parser: impl Iterator<Item = Event<'a>>,
+    bases: Option<&RepoUrlBases>,
+) -> Vec<Event<'a>> {
+    let mut out = Vec::new();
+    let mut pending_text = String::new();
+    // Issue links are skipped inside links, image alt text and code blocks.
+    let mut unlinkable_depth = 0_usize;
+
+    for event in parser {
+        if let Event::Text(text) = &event
+            && unlinkable_depth == 0
+        {
+            pending_text.push_str(text);
+            continue;
+        }
+        flush_issue_linked_text(&mut pending_text, bases, &mut out);
+
+        let event = match event {
+            Event::Start(Tag::Link {
+                link_type,
+                dest_url,
+                title,
+                id,
+            }) => {
+                unlinkable_depth += 1;
+                Event::Start(Tag::Link {
+                    link_type,
+                    dest_url: resolve_dest(dest_url, bases, false),
+                    title,
+                    id,
+                })
+            }
+            Event::Start(Tag::Image {
+                link_type,
+                dest_url,
+                title,
+                id,
+            }) => {
+                unlinkable_depth += 1;
+                Event::Start(Tag::Image {
+                    link_type,
+                    dest_url: resolve_dest(dest_url, bases, true),
+                    title,
+                    id,
+                })
+            }
+            Event::Start(tag @ Tag::CodeBlock(_)) => {
+                unlinkable_depth += 1;
+                Event::Start(tag)
+            }
+            Event::End(tag @ (TagEnd::Link | TagEnd::Image | TagEnd::CodeBlock)) => {
+                unlinkable_depth = unlinkable_depth.saturating_sub(1);
+                Event::End(tag)
+            }
+            other => other,
+        };
+        out.push(event);
+    }
+    flush_issue_linked_text(&mut pending_text, bases, &mut out);
+    out
+}
+
+fn resolve_dest<'a>(dest: CowStr<'a>, bases: Option<&RepoUrlBases>, is_image: bool) -> CowStr<'a> {
+    bases
+        .and_then(|bases| bases.resolve(&dest, is_image))
+        .map_or(dest, CowStr::from)
+}
+
+/// Emits buffered text, turning bare `#123` references into issue links.
+fn flush_issue_linked_text(
+    pending: &mut String,
+    bases: Option<&RepoUrlBases>,
+    out: &mut Vec<Event<'_>>,
+) {
+    if pending.is_empty() {
+        return;
+    }
+    let text = std::mem::take(pending);
+    let Some(bases) = bases else {
+        out.push(Event::Text(text.into()));
+        return;
+    };
+
+    let mut rest = text.as_str();
+    let mut consumed_prev: Option<char> = None;
+    while let Some(offset) = rest.find('#') {
+        let prev = rest[..offset].chars().next_back().or(consumed_prev);
+        let digits = rest[offset + 1..]
+            .bytes()
+            .take_while(u8::is_ascii_digit)
+            .count();
+        let after = rest[offset + 1 + digits..].chars().next();
+        let is_reference = (1..=ISSUE_REF_MAX_DIGITS).contains(&digits)
+            && !prev.is_some_and(is_reference_word_char)
+            && !after.is_some_and(is_reference_word_char);
+        if !is_reference {
+            let skip = offset + 1;
+            consumed_prev = Some('#');
+            out.push(Event::Text(rest[..skip].to_owned().into()));
+            rest = &rest[skip..];
+            continue;
+        }
+
+        if offset > 0 {
+            out.push(Event::Text(rest[..offset].to_owned().into()));
+        }
+        let number = &rest[offset + 1..offset + 1 + digits];
+        out.push(Event::InlineHtml(
+            format!(
+                "<a href=\"{repo}/issues/{number}\">#{number}</a>",
+                repo = bases.repo
+            )
+            .into(),
+        ));
+        consumed_prev = number.chars().next_back();
+        rest = &rest[offset + 1 + digits..];
+    }
+    if !rest.is_empty() {
+        out.push(Event::Text(rest.to_owned().into()));
+    }
+}
+
+fn is_reference_word_char(ch: char) -> bool {
+    ch.is_alphanumeric() || matches!(ch, '_' | '/' | '&' | '#')
+}
+
+fn url_has_image_extension(url: &str) -> bool {
+    let path = url.split(['?', '#']).next().unwrap_or(url);
+    path.rsplit_once('.').is_some_and(|(_, ext)| {
+        IMAGE_EXTENSIONS
+            .iter()
+            .any(|candidate| ext.eq_ignore_ascii_case(candidate))
+    })
+}
+
+/// Counts start tags. Text is entity-escaped in both rendered and sanitized
+/// output, so every `<` followed by a letter opens an element.
+fn count_elements(html: &str) -> usize {
+    html.as_bytes()
+        .windows(2)
+        .filter(|pair| pair[0] == b'<' && pair[1].is_ascii_alphabetic())
+        .count()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{release_render_source_hash, render_release_markdown};
+
+    const REPO: &str = "https://github.com/openai/codex";
+
+    #[test]
+    fn render_release_markdown_strips_scripts_and_resolves_links() {
+        let markdown = "\
+## Changes
+
+- Fixed crash (#123), see [changelog](./CHANGELOG.md)
+- Not a ref: abc#12, `#45`, [#7](https://example.com/7)
+
+![screenshot](docs/shot.png)
+
+<script>alert('xss')</script>
+<img src=\"assets/logo.svg\" onerror=\"alert(1)\">
+";
+        let rendered = render_release_markdown(markdown, REPO, "v1.2.3");
+
+        assert!(!rendered.html.contains("<script"), "{}", rendered.html);
+        assert!(!rendered.html.contains("alert"), "{}", rendered.html);
+        assert!(!rendered.html.contains("onerror"), "{}", rendered.html);
+        assert_eq!(rendered.stripped_elements, 1);
+        assert!(
+            rendered
+                .html
+                .contains(r#"href="https://github.com/openai/codex/issues/123""#),
+            "{}",
+            rendered.html
+        );
+        assert!(
+            rendered
+                .html
+                .contains(r#"href="https://github.com/openai/codex/blob/v1.2.3/CHANGELOG.md""#),
+            "{}",
+            rendered.html
+        );
+        assert!(
+            rendered
+                .html
+                .contains(r#"src="https://github.com/openai/codex/raw/v1.2.3/docs/shot.png""#),
+            "{}",
+            rendered.html
+        );
+        assert!(
+            rendered
+                .html
+                .contains(r#"src="https://github.com/openai/codex/raw/v1.2.3/assets/logo.svg""#),
+            "{}",
+            rendered.html
+        );
+        assert!(!rendered.html.contains("issues/12\""), "{}", rendered.html);
+        assert!(!rendered.html.contains("issues/45"), "{}", rendered.html);
+        assert!(!rendered.html.contains("issues/7"), "{}", rendered.html);
+        assert!(
+            rendered.html.contains("<code>#45</code>"),
+            "{}",
+            rendered.html
+        );
+    }
+
+    #[test]
+    fn render_release_markdown_keeps_absolute_and_fragment_links() {
+        let rendered = render_release_markdown(
+            "[docs](https://example.com/docs) [top](#top) #1 and #2",
+            REPO,
+            "release/2026",
+        );
+
+        assert!(rendered.html.contains(r#"href="https://example.com/docs""#));
+        assert!(rendered.html.contains(r##"href="#top""##));
+        assert!(rendered.html.contains("/issues/1\""));
+        assert!(rendered.html.contains("/issues/2\""));
+        assert_eq!(rendered.stripped_elements, 0);
+    }
+
+    #[test]
+    fn release_render_source_hash_tracks_every_input() {
+        let base = release_render_source_hash("body", REPO, "v1");
+        assert_ne!(base, release_render_source_hash("body!", REPO, "v1"));
+        assert_ne!(base, release_render_source_hash("body", REPO, "v2"));
+        assert_ne!(
+            base,
+            release_render_source_hash("body", "https://github.com/openai/other", "v1")
+        );
+    }
+}
@@ -1,4 +1,8 @@
-use std::{future::Future, sync::Arc, time::Duration};
+use std::{
+    future::Future,
+    sync::{Arc, OnceLock},
+    time::Duration,
+};
 
 use anyhow::{Context, Result};
 use chrono::{DateTime, Utc};
@@ -7,6 +11,7 @@ use tokio::{
     task::JoinHandle,
     time::{self, MissedTickBehavior},
 };
+use tokio_util::sync::CancellationToken;
 
 use crate::{admin_runtime, state::AppState};
 
@@ -14,6 +19,19 @@ pub const RUNTIME_LEASE_HEARTBEAT_INTERVAL: Duration = Duration::from_secs(10);
 pub const RUNTIME_LEASE_STALE_AFTER: Duration = Duration::from_secs(90);
 pub const RUNTIME_LEASE_EXPIRED_ERROR: &str = "runtime_lease_expired";
 pub const SQLITE_BUSY_TIMEOUT: Duration = Duration::from_secs(5);
+pub const SSE_SHUTDOWN_EVENT: &str = "server.shutdown";
+
+static SHUTDOWN: OnceLock<CancellationToken> = OnceLock::new();
+
+/// Fired once the process got SIGTERM/SIGINT. Task workers stop claiming new
+/// work and long-lived streams send a final event and close.
+pub fn shutdown_token() -> &'static CancellationToken {
+    SHUTDOWN.get_or_init(CancellationToken::new)
+}
+
+pub fn begin_shutdown() {
+    shutdown_token().cancel();
+}
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum RuntimeRecoveryMode {
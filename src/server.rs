@@ -7,11 +7,11 @@ use anyhow::{Context, Result};
 use axum::{
     Router,
     body::Body,
-    extract::Request,
-    http::{HeaderMap, HeaderValue, Method, StatusCode, Uri, header},
+    extract::{DefaultBodyLimit, Request},
+    http::{HeaderMap, HeaderName, HeaderValue, Method, StatusCode, Uri, header},
     middleware::{self, Next},
-    response::Response,
-    routing::{get, patch, post, put},
+    response::{IntoResponse, Response},
+    routing::{delete, get, patch, post},
 };
 use serde_json::json;
 use sqlx::{
@@ -23,6 +23,10 @@ use tokio::task::AbortHandle;
 use tower::ServiceBuilder;
 use tower::ServiceExt;
 use tower_http::{
+    compression::{
+        CompressionLayer, Predicate,
+        predicate::{NotForContentType, SizeAbove},
+    },
     cors::CorsLayer,
     services::{ServeDir, ServeFile},
     trace::TraceLayer,
@@ -33,15 +37,24 @@ use tower_sessions::{Expiry, SessionManagerLayer};
 use tracing::{info, warn};
 use url::Url;
 
+use crate::error::{ApiError, codes};
 use crate::runtime::SQLITE_BUSY_TIMEOUT;
 use crate::session_store::CoordinatedSqliteSessionStore;
 use crate::state::AppState;
 use crate::{
-    admin_runtime, ai, api, auth, config::AppConfig, jobs, observability, runtime, state, sync,
-    translations, version,
+    admin_audit, admin_runtime, ai, api, atom_feed, auth, backup, bootstrap, config::AppConfig,
+    csrf, db_maintenance, export, follows, github_app, health, impersonation, jobs, observability,
+    openapi, rate_limit, runtime, state, sync, sync_issues, translation_stats, translations,
+    version,
 };
 
 const SESSION_COOKIE_MAX_AGE_SECS: i64 = 30 * 24 * 60 * 60;
+/// Largest JSON body any `/api` route buffers.
+const API_BODY_LIMIT_BYTES: usize = 256 * 1024;
+/// Follow imports carry a whole exported list, so they get more room.
+const API_IMPORT_BODY_LIMIT_BYTES: usize = 1024 * 1024;
+/// Smaller responses go out uncompressed; the framing overhead eats the gain.
+const COMPRESSION_MIN_SIZE_BYTES: u16 = 1024;
 const STATIC_ASSET_EXTENSIONS: &[&str] = &[
     "avif",
     "bmp",
@@ -106,10 +119,7 @@ pub async fn serve(config: AppConfig) -> Result<()> {
     warn_if_runtime_concurrency_exceeds_sqlite_pool(&config, &runtime_settings);
 
     let sqlite_writer = crate::sqlite_write::SqliteWriteCoordinator::new();
-    let session_store = CoordinatedSqliteSessionStore::new(
-        tower_sessions_sqlx_store::SqliteStore::new(pool.clone()),
-        sqlite_writer.clone(),
-    );
+    let session_store = CoordinatedSqliteSessionStore::new(pool.clone(), sqlite_writer.clone());
     session_store
         .migrate()
         .await
@@ -135,6 +145,14 @@ pub async fn serve(config: AppConfig) -> Result<()> {
         .build()
         .context("failed to build github rest http client")?;
 
+    let github_app_tokens = config
+        .github_app
+        .as_ref()
+        .map(|app| crate::github::GitHubAppTokenProvider::new(&app.app_id, &app.private_key_pem))
+        .transpose()
+        .context("failed to load github app private key")?
+        .map(Arc::new);
+
     let app_state = Arc::new(AppState {
         llm_scheduler: Arc::new(ai::LlmScheduler::new(runtime_settings.llm_max_concurrency)),
         translation_scheduler: Arc::new(translations::TranslationSchedulerController::new(
@@ -157,11 +175,18 @@ pub async fn serve(config: AppConfig) -> Result<()> {
         webauthn,
         encryption_key: config.encryption_key.clone(),
         runtime_owner_id: crate::local_id::generate_local_id(),
+        feed_events: crate::feed_events::FeedEventHub::default(),
+        admin_mutation_limiter: crate::admin_audit::AdminMutationLimiter::default(),
+        translation_flights: crate::ai::TranslationSingleflight::default(),
+        request_limiter: crate::rate_limit::RequestRateLimiter::default(),
+        github_probe: crate::health::GitHubProbe::default(),
+        github_app_tokens,
     });
     app_state
         .llm_scheduler
         .set_model_routing(runtime_settings.llm_models.clone())
         .await;
+    ai::check_ai_config_on_startup(app_state.as_ref()).await?;
 
     let addr: SocketAddr = config.bind_addr;
     let listener = tokio::net::TcpListener::bind(addr)
@@ -176,225 +201,37 @@ pub async fn serve(config: AppConfig) -> Result<()> {
         .with_same_site(SameSite::Lax)
         .with_expiry(session_inactivity_expiry());
 
-    let api_router = Router::new()
-        .route(
-            "/health",
-            get(api_health).layer(middleware::from_fn(version_no_store_cache)),
-        )
-        .route(
-            "/version",
-            get(api_version).layer(middleware::from_fn(version_no_store_cache)),
-        )
-        .route("/me", get(api::me))
-        .route(
-            "/me/profile",
-            get(api::me_get_profile).patch(api::me_patch_profile),
-        )
-        .route("/tasks/{task_id}/events", get(api::task_events_sse))
-        .route("/starred", get(api::list_starred))
-        .route("/releases", get(api::list_releases))
-        .route(
-            "/releases/{release_id}/detail",
-            get(api::get_release_detail),
-        )
-        .route(
-            "/repos/{owner}/{repo}/releases/tag/{tag}/detail",
-            get(api::get_release_detail_by_repo_tag),
-        )
-        .route(
-            "/public/repos/{owner}/{repo}/releases",
-            get(api::public_list_repo_releases),
-        )
-        .route(
-            "/public/repos/{owner}/{repo}/releases/tag/{tag}",
-            get(api::public_get_repo_release_detail),
-        )
-        .route("/notifications", get(api::list_notifications))
-        .route("/dashboard/updates", get(api::dashboard_updates))
-        .route("/feed", get(api::list_feed))
-        .route("/feed/reactions/refresh", post(api::refresh_feed_reactions))
-        .route("/admin/users", get(api::admin_list_users))
-        .route("/admin/users/{user_id}", patch(api::admin_patch_user))
-        .route(
-            "/admin/users/{user_id}/profile",
-            get(api::admin_get_user_profile).patch(api::admin_patch_user_profile),
-        )
-        .route("/admin/dashboard", get(api::admin_dashboard))
-        .route(
-            "/admin/repos/overview",
-            get(api::admin_get_repo_governance_overview),
-        )
-        .route("/admin/repos", get(api::admin_list_repo_governance))
-        .route(
-            "/admin/public-release-repos",
-            get(api::admin_list_public_release_repos),
-        )
-        .route(
-            "/admin/public-release-repos/{usage_id}",
-            axum::routing::delete(api::admin_delete_public_release_repo),
-        )
-        .route("/admin/jobs/overview", get(api::admin_jobs_overview))
-        .route("/admin/jobs/events", get(api::admin_jobs_events_sse))
-        .route("/admin/jobs/realtime", get(api::admin_list_realtime_tasks))
-        .route(
-            "/admin/jobs/realtime/{task_id}",
-            get(api::admin_get_realtime_task_detail),
-        )
-        .route(
-            "/admin/jobs/realtime/{task_id}/log",
-            get(api::admin_download_realtime_task_log),
-        )
-        .route(
-            "/admin/jobs/realtime/{task_id}/retry",
-            post(api::admin_retry_realtime_task),
-        )
-        .route(
-            "/admin/jobs/realtime/{task_id}/cancel",
-            post(api::admin_cancel_realtime_task),
-        )
-        .route(
-            "/admin/jobs/scheduled",
-            get(api::admin_list_scheduled_slots),
-        )
-        .route(
-            "/admin/jobs/scheduled/{hour_utc}",
-            patch(api::admin_patch_scheduled_slot),
-        )
-        .route(
-            "/admin/jobs/sync/runtime-config",
-            get(api::admin_get_sync_runtime_config).patch(api::admin_patch_sync_runtime_config),
-        )
-        .route(
-            "/admin/jobs/llm/status",
-            get(api::admin_get_llm_scheduler_status),
-        )
-        .route(
-            "/admin/jobs/llm/runtime-config",
-            patch(api::admin_patch_llm_runtime_config),
-        )
-        .route("/admin/jobs/llm/calls", get(api::admin_list_llm_calls))
-        .route(
-            "/admin/jobs/llm/calls/{call_id}",
-            get(api::admin_get_llm_call_detail),
-        )
-        .route(
-            "/admin/jobs/translations/status",
-            get(translations::admin_get_translation_status),
-        )
-        .route(
-            "/admin/jobs/translations/runtime-config",
-            patch(translations::admin_patch_translation_runtime_config),
-        )
-        .route(
-            "/admin/jobs/translations/requests",
-            get(translations::admin_list_translation_requests),
-        )
-        .route(
-            "/admin/jobs/translations/requests/{request_id}",
-            get(translations::admin_get_translation_request_detail),
-        )
-        .route(
-            "/admin/jobs/translations/batches",
-            get(translations::admin_list_translation_batches),
-        )
-        .route(
-            "/admin/jobs/translations/batches/{batch_id}",
-            get(translations::admin_get_translation_batch_detail),
-        )
-        .route("/reaction-token/status", get(api::reaction_token_status))
-        .route(
-            "/me/linuxdo",
-            get(api::me_get_linuxdo).delete(api::me_delete_linuxdo),
-        )
-        .route(
-            "/me/github-connections",
-            get(api::me_get_github_connections),
-        )
-        .route(
-            "/me/github-connections/{connection_id}",
-            axum::routing::delete(api::me_delete_github_connection),
-        )
-        .route("/me/passkeys", get(api::me_get_passkeys))
-        .route(
-            "/me/passkeys/{passkey_id}",
-            axum::routing::delete(api::me_delete_passkey),
-        )
-        .route("/auth/bind-context", get(api::auth_get_bind_context))
-        .route(
-            "/auth/passkeys/register/options",
-            post(auth::passkey_register_options),
-        )
-        .route(
-            "/auth/passkeys/register/verify",
-            post(auth::passkey_register_verify),
-        )
-        .route(
-            "/auth/passkeys/authenticate/options",
-            post(auth::passkey_authenticate_options),
-        )
-        .route(
-            "/auth/passkeys/authenticate/verify",
-            post(auth::passkey_authenticate_verify),
-        )
-        .route("/reaction-token/check", post(api::check_reaction_token))
-        .route("/reaction-token", put(api::upsert_reaction_token))
-        .route(
-            "/release/reactions/toggle",
-            post(api::toggle_release_reaction),
-        )
-        .route("/briefs", get(api::list_briefs))
-        .route("/briefs/generate", post(api::generate_brief))
-        .route(
-            "/translate/requests",
-            post(translations::submit_translation_request),
-        )
-        .route(
-            "/translate/requests/{request_id}",
-            get(translations::get_translation_request),
-        )
-        .route(
-            "/translate/results",
-            post(translations::resolve_translation_results),
-        )
-        .route(
-            "/translate/requests/{request_id}/stream",
-            get(translations::stream_translation_request),
-        )
-        .route(
-            "/translate/releases/batch",
-            post(api::translate_releases_batch),
-        )
-        .route(
-            "/translate/releases/batch/stream",
-            post(api::translate_releases_batch_stream),
-        )
-        .route("/translate/release", post(api::translate_release))
-        .route(
-            "/translate/release/detail/batch",
-            post(api::translate_release_detail_batch),
-        )
-        .route(
-            "/translate/release/detail",
-            post(api::translate_release_detail),
-        )
-        .route(
-            "/translate/notifications/batch",
-            post(api::translate_notifications_batch),
-        )
-        .route("/translate/notification", post(api::translate_notification))
-        .route("/sync/starred", post(api::sync_starred))
-        .route("/sync/all", post(api::sync_all))
-        .route("/sync/releases", post(api::sync_releases))
-        .route("/sync/notifications", post(api::sync_notifications));
-
-    let mut app = Router::new()
-        .nest("/api", api_router)
+    let auth_router = Router::new()
         .route("/auth/github/login", get(auth::github_login))
         .route("/auth/github/connect", get(auth::github_connect))
         .route("/auth/github/callback", get(auth::github_callback))
         .route("/auth/linuxdo/login", get(auth::linuxdo_login))
         .route("/auth/linuxdo/callback", get(auth::linuxdo_callback))
         .route("/auth/logout", get(auth::logout))
+        .route_layer(middleware::from_fn_with_state(
+            app_state.clone(),
+            rate_limit::limit_auth,
+        ));
+
+    let mut app = Router::new()
+        .nest("/api", api_router(app_state.clone()))
+        .merge(auth_router)
+        .route("/metrics", get(metrics_endpoint))
+        .route(
+            "/healthz",
+            get(health::healthz).layer(middleware::from_fn(version_no_store_cache)),
+        )
+        .route(
+            "/readyz",
+            get(health::readyz).layer(middleware::from_fn(version_no_store_cache)),
+        )
+        .route(
+            "/feeds/{token}/releases.atom",
+            get(atom_feed::releases_atom),
+        )
+        // Not rate limited: deliveries are HMAC-verified before any work, and
+        // GitHub does not redeliver webhooks that were answered with 429.
+        .route("/webhooks/github", post(github_app::github_webhook))
         .with_state(app_state.clone())
         .layer(session_layer);
 
@@ -412,9 +249,13 @@ pub async fn serve(config: AppConfig) -> Result<()> {
         .allow_origin(cors_origin)
         .allow_credentials(true)
         .allow_methods([Method::GET, Method::POST, Method::PUT, Method::PATCH])
-        .allow_headers([axum::http::header::CONTENT_TYPE]);
+        .allow_headers([
+            axum::http::header::CONTENT_TYPE,
+            HeaderName::from_static(csrf::CSRF_HEADER),
+        ]);
 
-    let (set_request_id, propagate_request_id) = observability::request_id_layers();
+    let (filter_request_id, set_request_id, propagate_request_id) =
+        observability::request_id_layers(config.trust_request_id_header);
     let access_log = middleware::from_fn(observability::access_log_middleware);
     let trace_layer = TraceLayer::new_for_http()
         .make_span_with(|request: &Request| observability::request_trace_span(request))
@@ -423,10 +264,12 @@ pub async fn serve(config: AppConfig) -> Result<()> {
         .on_failure(());
     let app = app.layer(
         ServiceBuilder::new()
+            .layer(filter_request_id)
             .layer(set_request_id)
             .layer(trace_layer)
             .layer(access_log)
             .layer(cors)
+            .layer(compression_layer())
             .layer(propagate_request_id),
     );
 
@@ -455,7 +298,17 @@ pub async fn serve(config: AppConfig) -> Result<()> {
         jobs::spawn_hourly_scheduler(app_state.clone());
         jobs::spawn_subscription_scheduler(app_state.clone());
         jobs::spawn_recent_failures_retry_scheduler(app_state.clone());
+        jobs::spawn_maintenance_prune_scheduler(app_state.clone());
+        jobs::spawn_db_maintenance_scheduler(app_state.clone());
+        jobs::spawn_backup_scheduler(app_state.clone());
+        jobs::spawn_translation_retry_scheduler(app_state.clone());
+        jobs::spawn_star_purge_scheduler(app_state.clone());
+        jobs::spawn_user_sync_scheduler(app_state.clone());
         jobs::spawn_admin_dashboard_rollup_scheduler(app_state.clone());
+        rate_limit::spawn_bucket_cleanup(app_state.clone());
+        if config.readyz_check_github {
+            health::spawn_github_probe(app_state.clone());
+        }
         if let Err(err) = jobs::enqueue_brief_history_recompute_if_needed(app_state.as_ref()).await
         {
             tracing::warn!(?err, "failed to enqueue brief history recompute bootstrap");
@@ -487,10 +340,15 @@ pub async fn serve(config: AppConfig) -> Result<()> {
             abort_handles.push(handle);
         }
 
-        axum::serve(listener, app)
-            .with_graceful_shutdown(shutdown_signal(app_state.clone(), abort_handles))
-            .await
-            .context("http server exited")
+        let served = axum::serve(
+            listener,
+            app.into_make_service_with_connect_info::<SocketAddr>(),
+        )
+        .with_graceful_shutdown(shutdown_signal(abort_handles))
+        .await
+        .context("http server exited");
+        drain_after_shutdown(app_state.as_ref()).await;
+        served
     }
     .await;
 
@@ -508,6 +366,138 @@ pub async fn serve(config: AppConfig) -> Result<()> {
     Ok(())
 }
 
+/// Everything served under `/api`, with the CSRF, impersonation, rate limit
+/// and body limit layers applied.
+fn api_router(app_state: Arc<AppState>) -> Router<Arc<AppState>> {
+    Router::new()
+        .route(
+            "/health",
+            get(api_health).layer(middleware::from_fn(version_no_store_cache)),
+        )
+        .route(
+            "/version",
+            get(api_version).layer(middleware::from_fn(version_no_store_cache)),
+        )
+        .route("/openapi.json", get(openapi::openapi_json))
+        .route("/csrf", get(csrf::csrf_token))
+        .route("/bootstrap", post(bootstrap::start_bootstrap))
+        .route("/bootstrap/status", get(bootstrap::get_bootstrap_status))
+        .route(
+            "/follows/import",
+            post(follows::import_follows).layer(DefaultBodyLimit::max(API_IMPORT_BODY_LIMIT_BYTES)),
+        )
+        .route("/follows/{repo_id}", delete(follows::unfollow_repo))
+        .route(
+            "/admin/impersonate/{user_id}",
+            post(impersonation::admin_start_impersonation),
+        )
+        .route(
+            "/admin/impersonate",
+            delete(impersonation::admin_stop_impersonation),
+        )
+        .route("/admin/audit", get(admin_audit::admin_list_audit))
+        .route(
+            "/admin/translations/stats",
+            get(translation_stats::admin_get_translation_stats),
+        )
+        .route(
+            "/admin/maintenance/db",
+            get(db_maintenance::admin_get_db_maintenance)
+                .post(db_maintenance::admin_run_db_maintenance),
+        )
+        .route("/admin/backup", post(backup::admin_create_backup))
+        .route("/admin/backups", get(backup::admin_list_backups))
+        .route(
+            "/admin/backups/{name}/download",
+            get(backup::admin_download_backup),
+        )
+        .route(
+            "/admin/github-app/installations",
+            get(github_app::admin_list_github_app_installations),
+        )
+        .route(
+            "/admin/jobs/translations/status",
+            get(translations::admin_get_translation_status),
+        )
+        .route(
+            "/admin/jobs/translations/runtime-config",
+            patch(translations::admin_patch_translation_runtime_config),
+        )
+        .route(
+            "/admin/jobs/translations/requests",
+            get(translations::admin_list_translation_requests),
+        )
+        .route(
+            "/admin/jobs/translations/requests/{request_id}",
+            get(translations::admin_get_translation_request_detail),
+        )
+        .route(
+            "/admin/jobs/translations/batches",
+            get(translations::admin_list_translation_batches),
+        )
+        .route(
+            "/admin/jobs/translations/batches/{batch_id}",
+            get(translations::admin_get_translation_batch_detail),
+        )
+        .route(
+            "/auth/passkeys/register/options",
+            post(auth::passkey_register_options),
+        )
+        .route(
+            "/auth/passkeys/register/verify",
+            post(auth::passkey_register_verify),
+        )
+        .route(
+            "/auth/passkeys/authenticate/options",
+            post(auth::passkey_authenticate_options),
+        )
+        .route(
+            "/auth/passkeys/authenticate/verify",
+            post(auth::passkey_authenticate_verify),
+        )
+        .route("/export", get(export::export_user_data))
+        .route("/feed-token", post(atom_feed::rotate_feed_token))
+        .route(
+            "/translate/requests",
+            post(translations::submit_translation_request),
+        )
+        .route(
+            "/translate/requests/{request_id}",
+            get(translations::get_translation_request),
+        )
+        .route(
+            "/translate/results",
+            post(translations::resolve_translation_results),
+        )
+        .route(
+            "/translate/requests/{request_id}/stream",
+            get(translations::stream_translation_request),
+        )
+        .route(
+            "/translations/stats",
+            get(translation_stats::get_translation_stats),
+        )
+        .route("/sync/issues", get(sync_issues::list_sync_issues))
+        .route(
+            "/sync/issues/{id}/dismiss",
+            post(sync_issues::dismiss_sync_issue),
+        )
+        .route("/sync/purges", get(sync_issues::list_pending_purges))
+        .merge(api::router(app_state.clone()))
+        .route_layer(middleware::from_fn(csrf::require_csrf_token))
+        .route_layer(middleware::from_fn(
+            impersonation::reject_mutations_while_impersonating,
+        ))
+        .route_layer(middleware::from_fn_with_state(
+            app_state.clone(),
+            rate_limit::limit_api,
+        ))
+        .layer(DefaultBodyLimit::max(API_BODY_LIMIT_BYTES))
+        .layer(middleware::map_response(payload_too_large_as_api_error))
+        .method_not_allowed_fallback(api_method_not_allowed)
+        .fallback(api_route_not_found)
+}
+
 fn ensure_dir_exists(path: &Path) -> Result<()> {
     std::fs::create_dir_all(path)
         .with_context(|| format!("failed to create directory {}", path.display()))
@@ -624,7 +614,7 @@ async fn read_sqlite_runtime_pragmas(pool: &SqlitePool) -> Result<SqliteRuntimeP
     })
 }
 
-async fn shutdown_signal(state: Arc<AppState>, abort_handles: Vec<AbortHandle>) {
+async fn shutdown_signal(abort_handles: Vec<AbortHandle>) {
     let abort_all = || {
         for handle in &abort_handles {
             handle.abort();
@@ -649,11 +639,33 @@ async fn shutdown_signal(state: Arc<AppState>, abort_handles: Vec<AbortHandle>)
     let terminate = std::future::pending::<()>();
 
     tokio::select! {
-        _ = ctrl_c => abort_all(),
-        _ = terminate => abort_all(),
+        _ = ctrl_c => {},
+        _ = terminate => {},
     }
 
+    info!("shutdown signal received; stopping task dispatch and closing streams");
+    runtime::begin_shutdown();
+    abort_all();
+}
+
+/// Gives in-flight tasks the configured grace period, then stops the
+/// translation workers and fails whatever this process still holds.
+async fn drain_after_shutdown(state: &AppState) {
+    runtime::begin_shutdown();
+    let grace = StdDuration::from_secs(state.config.shutdown_grace_secs as u64);
+    let still_running = jobs::drain_running_tasks(grace).await;
+    if still_running > 0 {
+        warn!(
+            still_running,
+            "shutdown grace period elapsed with tasks running"
+        );
+    }
     state.translation_scheduler.abort_all().await;
+    match jobs::fail_running_tasks_on_shutdown(state).await {
+        Ok(0) => {}
+        Ok(failed) => info!(failed, "marked unfinished tasks failed on shutdown"),
+        Err(err) => warn!(?err, "failed to mark running tasks failed on shutdown"),
+    }
 }
 
 async fn api_health() -> axum::Json<serde_json::Value> {
@@ -673,6 +685,53 @@ async fn api_version() -> axum::Json<serde_json::Value> {
     }))
 }
 
+async fn metrics_endpoint(
+    axum::extract::State(state): axum::extract::State<Arc<AppState>>,
+    headers: HeaderMap,
+) -> Result<Response, ApiError> {
+    authorize_metrics_scrape(state.config.metrics_token.as_deref(), &headers)?;
+    let body = observability::render_metrics().ok_or_else(|| {
+        ApiError::new(
+            StatusCode::SERVICE_UNAVAILABLE,
+            codes::METRICS_UNAVAILABLE,
+            "metrics recorder is not installed",
+        )
+    })?;
+    Ok((
+        [(
+            header::CONTENT_TYPE,
+            HeaderValue::from_static("text/plain; version=0.0.4"),
+        )],
+        body,
+    )
+        .into_response())
+}
+
+/// `/metrics` stays hidden unless a token is configured, and then only
+/// answers scrapers presenting it as a bearer token.
+fn authorize_metrics_scrape(expected: Option<&str>, headers: &HeaderMap) -> Result<(), ApiError> {
+    let Some(expected) = expected else {
+        return Err(ApiError::new(
+            StatusCode::NOT_FOUND,
+            codes::NOT_FOUND,
+            "metrics endpoint is disabled",
+        ));
+    };
+    let presented = headers
+        .get(header::AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.strip_prefix("Bearer "))
+        .map(str::trim);
+    if !presented.is_some_and(|presented| crate::csrf::constant_time_eq(expected, presented)) {
+        return Err(ApiError::new(
+            StatusCode::UNAUTHORIZED,
+            codes::UNAUTHORIZED,
+            "missing or invalid metrics token",
+        ));
+    }
+    Ok(())
+}
+
 fn apply_no_store_headers(headers: &mut axum::http::HeaderMap) {
     headers.insert(
         axum::http::header::CACHE_CONTROL,
@@ -685,6 +744,51 @@ fn apply_no_store_headers(headers: &mut axum::http::HeaderMap) {
     headers.insert(axum::http::header::EXPIRES, HeaderValue::from_static("0"));
 }
 
+/// gzip/brotli for API and static responses. NDJSON and SSE streams stay
+/// uncompressed: the encoder buffers output, which would hold events back
+/// until enough bytes pile up.
+fn compression_layer() -> CompressionLayer<impl Predicate> {
+    CompressionLayer::new().compress_when(
+        SizeAbove::new(COMPRESSION_MIN_SIZE_BYTES)
+            .and(NotForContentType::GRPC)
+            .and(NotForContentType::IMAGES)
+            .and(NotForContentType::SSE)
+            .and(NotForContentType::const_new("application/x-ndjson")),
+    )
+}
+
+/// Body limit rejections come back from the extractors as plain text; turn
+/// them into the usual error JSON so clients can read the code.
+async fn payload_too_large_as_api_error(response: Response) -> Response {
+    if response.status() != StatusCode::PAYLOAD_TOO_LARGE {
+        return response;
+    }
+    ApiError::new(
+        StatusCode::PAYLOAD_TOO_LARGE,
+        codes::PAYLOAD_TOO_LARGE,
+        "request body is too large",
+    )
+    .into_response()
+}
+
+/// Unknown `/api` paths answer with the error JSON rather than an empty 404
+/// (or the SPA document when static files are served).
+async fn api_route_not_found() -> ApiError {
+    ApiError::new(
+        StatusCode::NOT_FOUND,
+        codes::NOT_FOUND,
+        "api route not found",
+    )
+}
+
+async fn api_method_not_allowed() -> ApiError {
+    ApiError::new(
+        StatusCode::METHOD_NOT_ALLOWED,
+        codes::METHOD_NOT_ALLOWED,
+        "method not allowed for this api route",
+    )
+}
+
 async fn version_no_store_cache(req: Request, next: Next) -> Response {
     let mut response = next.run(req).await;
     apply_no_store_headers(response.headers_mut());
@@ -891,10 +995,12 @@ fn session_inactivity_expiry() -> Expiry {
 #[cfg(test)]
 mod tests {
     use super::{
-        AppConfig, SESSION_COOKIE_MAX_AGE_SECS, SameSite, accepts_html_document, api_health,
-        api_version, apply_no_store_headers, attach_static_site_routes, build_session_cookie_name,
-        build_sqlite_connect_options, build_sqlite_pool_options, looks_like_static_asset_path,
-        read_sqlite_runtime_pragmas, session_inactivity_expiry, should_serve_spa_shell,
+        API_BODY_LIMIT_BYTES, AppConfig, AppState, SESSION_COOKIE_MAX_AGE_SECS, SameSite,
+        accepts_html_document, api_health, api_method_not_allowed, api_route_not_found, api_router,
+        api_version, apply_no_store_headers, attach_static_site_routes, authorize_metrics_scrape,
+        build_session_cookie_name, build_sqlite_connect_options, build_sqlite_pool_options,
+        compression_layer, looks_like_static_asset_path, read_sqlite_runtime_pragmas,
+        session_inactivity_expiry, should_serve_spa_shell,
     };
     use axum::{
         Router,
@@ -904,6 +1010,7 @@ mod tests {
         routing::get,
     };
     use serde_json::Value;
+    use sqlx::sqlite::{SqliteConnectOptions, SqlitePoolOptions};
     use std::{
         fs, io,
         sync::{Arc, Mutex as StdMutex, OnceLock},
@@ -1013,7 +1120,12 @@ mod tests {
     }
 
     fn build_access_log_test_app(status: StatusCode, delay_ms: u64) -> Router {
-        let (set_request_id, propagate_request_id) = crate::observability::request_id_layers();
+        build_request_id_test_app(status, delay_ms, true)
+    }
+
+    fn build_request_id_test_app(status: StatusCode, delay_ms: u64, trust_inbound: bool) -> Router {
+        let (filter_request_id, set_request_id, propagate_request_id) =
+            crate::observability::request_id_layers(trust_inbound);
         let access_log = middleware::from_fn(crate::observability::access_log_middleware);
         Router::new()
             .route(
@@ -1027,6 +1139,7 @@ mod tests {
             )
             .layer(
                 ServiceBuilder::new()
+                    .layer(filter_request_id)
                     .layer(set_request_id)
                     .layer(access_log)
                     .layer(propagate_request_id),
@@ -1068,6 +1181,334 @@ mod tests {
         })
     }
 
+    #[tokio::test]
+    async fn compression_layer_encodes_large_json_for_accepting_clients() {
+        let app = Router::new()
+            .route(
+                "/large",
+                get(|| async {
+                    axum::Json(serde_json::json!({
+                        "items": (0..200)
+                            .map(|idx| format!("release note excerpt {idx}"))
+                            .collect::<Vec<_>>(),
+                    }))
+                }),
+            )
+            .route(
+                "/small",
+                get(|| async { axum::Json(serde_json::json!({ "ok": true })) }),
+            )
+            .layer(compression_layer());
+        let send = |uri: &'static str, accept_encoding: Option<&'static str>| {
+            let mut request = Request::builder().uri(uri);
+            if let Some(accept_encoding) = accept_encoding {
+                request = request.header(header::ACCEPT_ENCODING, accept_encoding);
+            }
+            app.clone()
+                .oneshot(request.body(Body::empty()).expect("build request"))
+        };
+        let content_encoding = |response: &axum::response::Response| {
+            response
+                .headers()
+                .get(header::CONTENT_ENCODING)
+                .map(|value| value.to_str().expect("ascii header").to_owned())
+        };
+
+        let plain = send("/large", None).await.expect("plain response");
+        assert_eq!(content_encoding(&plain), None);
+        let plain_len = axum::body::to_bytes(plain.into_body(), usize::MAX)
+            .await
+            .expect("plain body")
+            .len();
+
+        let gzip = send("/large", Some("gzip")).await.expect("gzip response");
+        assert_eq!(content_encoding(&gzip).as_deref(), Some("gzip"));
+        let gzip_len = axum::body::to_bytes(gzip.into_body(), usize::MAX)
+            .await
+            .expect("gzip body")
+            .len();
+        assert!(gzip_len < plain_len);
+
+        let br = send("/large", Some("br")).await.expect("br response");
+        assert_eq!(content_encoding(&br).as_deref(), Some("br"));
+
+        let small = send("/small", Some("gzip, br"))
+            .await
+            .expect("small response");
+        assert_eq!(content_encoding(&small), None);
+    }
+
+    #[tokio::test]
+    async fn compression_layer_leaves_ndjson_streams_incremental() {
+        let (tx, rx) = tokio::sync::mpsc::unbounded_channel::<String>();
+        let rx = Arc::new(StdMutex::new(Some(rx)));
+        let app = Router::new()
+            .route(
+                "/stream",
+                get(move || {
+                    let rx = rx.lock().expect("rx lock").take().expect("single request");
+                    async move {
+                        let stream = futures::stream::unfold(rx, |mut rx| async move {
+                            rx.recv()
+                                .await
+                                .map(|line| (Ok::<_, std::io::Error>(line), rx))
+                        });
+                        (
+                            [(header::CONTENT_TYPE, "application/x-ndjson; charset=utf-8")],
+                            Body::from_stream(stream),
+                        )
+                    }
+                }),
+            )
+            .layer(compression_layer());
+        let response = app
+            .oneshot(
+                Request::builder()
+                    .uri("/stream")
+                    .header(header::ACCEPT_ENCODING, "gzip, br")
+                    .body(Body::empty())
+                    .expect("build request"),
+            )
+            .await
+            .expect("stream response");
+        assert!(response.headers().get(header::CONTENT_ENCODING).is_none());
+
+        let mut frames = response.into_body().into_data_stream();
+        for event in ["{\"event\":\"queued\"}\n", "{\"event\":\"done\"}\n"] {
+            tx.send(event.to_owned()).expect("send event");
+            let frame = tokio::time::timeout(
+                std::time::Duration::from_secs(1),
+                futures::StreamExt::next(&mut frames),
+            )
+            .await
+            .expect("event should arrive without waiting for more output")
+            .expect("stream still open")
+            .expect("frame");
+            assert_eq!(frame.as_ref(), event.as_bytes());
+        }
+    }
+
+    fn api_test_member_id() -> String {
+        crate::local_id::test_local_id("server-api-member")
+    }
+
+    async fn setup_api_state() -> Arc<AppState> {
+        let database_path = std::env::temp_dir().join(format!(
+            "octo-rill-server-api-test-{}.db",
+            crate::local_id::generate_local_id(),
+        ));
+        let pool = SqlitePoolOptions::new()
+            .max_connections(1)
+            .connect_with(
+                SqliteConnectOptions::new()
+                    .filename(&database_path)
+                    .create_if_missing(true),
+            )
+            .await
+            .expect("create sqlite db");
+        sqlx::migrate!("./migrations")
+            .run(&pool)
+            .await
+            .expect("run migrations");
+        sqlx::query(
+            r#"
+            INSERT INTO users (id, github_user_id, login, created_at, updated_at)
+            VALUES (?, 1, 'member', '2026-02-01T00:00:00Z', '2026-02-01T00:00:00Z')
+            "#,
+        )
+        .bind(api_test_member_id())
+        .execute(&pool)
+        .await
+        .expect("seed user");
+        Arc::new(AppState::for_tests(pool, AppConfig::for_tests()))
+    }
+
+    async fn log_in_api_test_member(session: Session) -> StatusCode {
+        session
+            .insert("user_id", api_test_member_id())
+            .await
+            .expect("insert user id into session");
+        StatusCode::NO_CONTENT
+    }
+
+    /// The real `/api` router behind a session layer, plus a `/login` route
+    /// that signs the test member in.
+    fn build_api_test_app(state: Arc<AppState>) -> Router {
+        Router::new()
+            .route("/login", get(log_in_api_test_member))
+            .nest("/api", api_router(state.clone()))
+            .with_state(state)
+            .layer(test_session_layer("octo_rill_sid_test"))
+    }
+
+    /// Returns the session cookie and its CSRF token, optionally signed in.
+    async fn api_test_session(app: &Router, logged_in: bool) -> (String, String) {
+        let mut cookie = None;
+        if logged_in {
+            let response = app
+                .clone()
+                .oneshot(
+                    Request::builder()
+                        .uri("/login")
+                        .body(Body::empty())
+                        .expect("build login request"),
+                )
+                .await
+                .expect("login response");
+            cookie = response
+                .headers()
+                .get(header::SET_COOKIE)
+                .and_then(|value| value.to_str().ok())
+                .and_then(|value| value.split(';').next())
+                .map(str::to_owned);
+        }
+
+        let mut request = Request::builder().uri("/api/csrf");
+        if let Some(cookie) = cookie.as_deref() {
+            request = request.header(header::COOKIE, cookie);
+        }
+        let response = app
+            .clone()
+            .oneshot(request.body(Body::empty()).expect("build csrf request"))
+            .await
+            .expect("csrf response");
+        assert_eq!(response.status(), StatusCode::OK);
+        let cookie = cookie.unwrap_or_else(|| {
+            response
+                .headers()
+                .get(header::SET_COOKIE)
+                .and_then(|value| value.to_str().ok())
+                .and_then(|value| value.split(';').next())
+                .expect("csrf set-cookie header")
+                .to_owned()
+        });
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .expect("read csrf body");
+        let body: Value = serde_json::from_slice(&body).expect("csrf json");
+        let token = body["token"].as_str().expect("csrf token").to_owned();
+        (cookie, token)
+    }
+
+    async fn post_api_json(
+        app: &Router,
+        uri: &str,
+        (cookie, csrf_token): &(String, String),
+        body: String,
+    ) -> (StatusCode, Value) {
+        let response = app
+            .clone()
+            .oneshot(
+                Request::builder()
+                    .method(Method::POST)
+                    .uri(uri)
+                    .header(header::CONTENT_TYPE, "application/json")
+                    .header(header::COOKIE, cookie)
+                    .header(crate::csrf::CSRF_HEADER, csrf_token)
+                    .body(Body::from(body))
+                    .expect("build request"),
+            )
+            .await
+            .expect("api response");
+        let status = response.status();
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .expect("read body");
+        (status, serde_json::from_slice(&body).expect("json body"))
+    }
+
+    #[tokio::test]
+    async fn oversized_api_body_gets_json_413() {
+        let app = build_api_test_app(setup_api_state().await);
+        let session = api_test_session(&app, true).await;
+
+        let oversized = serde_json::json!({ "release_ids": ["1".repeat(API_BODY_LIMIT_BYTES)] });
+        let (status, body) = post_api_json(
+            &app,
+            "/api/translate/releases/batch",
+            &session,
+            oversized.to_string(),
+        )
+        .await;
+        assert_eq!(status, StatusCode::PAYLOAD_TOO_LARGE);
+        assert_eq!(body["error"]["code"], "payload_too_large");
+    }
+
+    #[tokio::test]
+    async fn reaction_toggle_checks_login_before_the_body() {
+        let app = build_api_test_app(setup_api_state().await);
+        let invalid = serde_json::json!({ "release_id": "1", "content": "nope" }).to_string();
+
+        let anonymous = api_test_session(&app, false).await;
+        let (status, body) = post_api_json(
+            &app,
+            "/api/release/reactions/toggle",
+            &anonymous,
+            invalid.clone(),
+        )
+        .await;
+        assert_eq!(status, StatusCode::UNAUTHORIZED);
+        assert_eq!(body["error"]["code"], "unauthorized");
+
+        let member = api_test_session(&app, true).await;
+        let (status, body) =
+            post_api_json(&app, "/api/release/reactions/toggle", &member, invalid).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert_eq!(body["error"]["code"], "bad_request");
+    }
+
+    #[tokio::test]
+    async fn unknown_api_route_and_wrong_method_get_json_errors() {
+        let static_dir = std::env::temp_dir().join(format!(
+            "octo-rill-api-fallback-fixture-{}",
+            SystemTime::now()
+                .duration_since(SystemTime::UNIX_EPOCH)
+                .expect("fixture time")
+                .as_nanos()
+        ));
+        fs::create_dir_all(&static_dir).expect("create static dir");
+        fs::write(static_dir.join("index.html"), "<html></html>").expect("write index.html");
+        let api = Router::new()
+            .route("/health", get(api_health))
+            .method_not_allowed_fallback(api_method_not_allowed)
+            .fallback(api_route_not_found);
+        let app = attach_static_site_routes(Router::new().nest("/api", api), static_dir.clone());
+        let send = |method: Method, uri: &'static str| {
+            app.clone().oneshot(
+                Request::builder()
+                    .method(method)
+                    .uri(uri)
+                    .header(header::ACCEPT, "text/html")
+                    .body(Body::empty())
+                    .expect("build request"),
+            )
+        };
+
+        for (method, uri, status, code) in [
+            (
+                Method::GET,
+                "/api/does-not-exist",
+                StatusCode::NOT_FOUND,
+                "not_found",
+            ),
+            (
+                Method::DELETE,
+                "/api/health",
+                StatusCode::METHOD_NOT_ALLOWED,
+                "method_not_allowed",
+            ),
+        ] {
+            let response = send(method, uri).await.expect("api response");
+            assert_eq!(response.status(), status, "{uri}");
+            let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+                .await
+                .expect("read body");
+            let body: Value = serde_json::from_slice(&body).expect("json error body");
+            assert_eq!(body["error"]["code"], code, "{uri}");
+        }
+        fs::remove_dir_all(static_dir).expect("remove static fixture");
+    }
+
     #[tokio::test]
     async fn api_version_reports_non_empty_version_and_source() {
         let payload = api_version().await.0;
@@ -1102,6 +1543,31 @@ mod tests {
         );
     }
 
+    #[test]
+    fn metrics_scrape_requires_configured_bearer_token() {
+        let mut headers = HeaderMap::new();
+        let disabled = authorize_metrics_scrape(None, &headers).expect_err("disabled");
+        assert_eq!(disabled.code(), "not_found");
+
+        let missing =
+            authorize_metrics_scrape(Some("scrape-secret"), &headers).expect_err("missing token");
+        assert_eq!(missing.code(), "unauthorized");
+
+        headers.insert(
+            header::AUTHORIZATION,
+            HeaderValue::from_static("Bearer wrong-secret"),
+        );
+        let wrong =
+            authorize_metrics_scrape(Some("scrape-secret"), &headers).expect_err("wrong token");
+        assert_eq!(wrong.code(), "unauthorized");
+
+        headers.insert(
+            header::AUTHORIZATION,
+            HeaderValue::from_static("Bearer scrape-secret"),
+        );
+        authorize_metrics_scrape(Some("scrape-secret"), &headers).expect("matching token");
+    }
+
     #[test]
     fn version_endpoints_disable_cache_storage() {
         let mut headers = HeaderMap::new();
@@ -1198,6 +1664,16 @@ mod tests {
             static_dir: None,
             task_log_dir: std::env::temp_dir().join("octo-rill-server-tests"),
             job_worker_concurrency: 1,
+            reaction_viewer_ttl_secs: 600,
+            release_backfill_limit: 10,
+            star_purge_grace_days: 14,
+            notification_archive_days: 90,
+            notification_retention_days: 365,
+            task_retention_days: 30,
+            backup_dir: std::env::temp_dir().join("octo-rill-backups-tests"),
+            backup_retention_count: 7,
+            shutdown_grace_secs: 30,
+            task_timeouts: crate::jobs::TaskTimeouts::default(),
             encryption_key: crate::crypto::EncryptionKey::from_base64(
                 "AAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAA=",
             )
@@ -1208,12 +1684,23 @@ mod tests {
                 redirect_url: url::Url::parse("https://octo-rill.ivanli.cc/auth/github/callback")
                     .expect("parse redirect url"),
             },
+            github_app: None,
             linuxdo: None,
             ai: None,
             ai_max_concurrency: 1,
+            ai_translate_concurrency: 2,
+            ai_skip_translation_cjk_percent: 30,
+            ai_daily_token_budget_per_user: None,
+            ai_request_interval_ms: 0,
             ai_daily_at_local: None,
             app_default_time_zone: crate::briefs::DEFAULT_DAILY_BRIEF_TIME_ZONE.to_owned(),
+            smtp: None,
             logging: crate::observability::LoggingThresholds::default(),
+            metrics_token: None,
+            trust_request_id_header: false,
+            trust_forwarded_headers: false,
+            readyz_check_github: false,
+            rate_limits: crate::rate_limit::RateLimits::default(),
         }
     }
 
@@ -1326,6 +1813,32 @@ mod tests {
         );
     }
 
+    #[tokio::test(flavor = "current_thread")]
+    async fn untrusted_request_id_is_replaced() {
+        let _lock = observability_test_lock().lock().await;
+        let _thresholds =
+            LoggingThresholdTestGuard::new(crate::observability::LoggingThresholds::default());
+        let app = build_request_id_test_app(StatusCode::OK, 0, false);
+
+        let (response, _) = run_request_with_captured_logs(
+            app,
+            Request::builder()
+                .uri("/users/42")
+                .header("x-request-id", "req-spoofed-123")
+                .body(Body::empty())
+                .expect("build request"),
+        )
+        .await;
+
+        let request_id = response
+            .headers()
+            .get("x-request-id")
+            .and_then(|value| value.to_str().ok())
+            .expect("generated x-request-id header");
+        assert_ne!(request_id, "req-spoofed-123");
+        assert!(!request_id.trim().is_empty());
+    }
+
     #[tokio::test(flavor = "current_thread")]
     async fn missing_request_id_is_generated_and_echoed_back_to_client() {
         let _lock = observability_test_lock().lock().await;
@@ -1,6 +1,7 @@
 use std::{fmt, time::Duration};
 
 use async_trait::async_trait;
+use sqlx::SqlitePool;
 use tokio::time::Instant;
 use tower_sessions::{
     ExpiredDeletion, SessionStore,
@@ -16,13 +17,15 @@ use crate::sqlite_write::{SqliteWriteCoordinator, SqliteWritePriority};
 #[derive(Clone)]
 pub struct CoordinatedSqliteSessionStore {
     inner: SqliteStore,
+    pool: SqlitePool,
     sqlite_writer: SqliteWriteCoordinator,
 }
 
 impl CoordinatedSqliteSessionStore {
-    pub fn new(inner: SqliteStore, sqlite_writer: SqliteWriteCoordinator) -> Self {
+    pub fn new(pool: SqlitePool, sqlite_writer: SqliteWriteCoordinator) -> Self {
         Self {
-            inner,
+            inner: SqliteStore::new(pool.clone()),
+            pool,
             sqlite_writer,
         }
     }
@@ -247,6 +250,22 @@ impl ExpiredDeletion for CoordinatedSqliteSessionStore {
             }
             return Err(err);
         }
+        // Login records outlive the sessions they point at; drop them with
+        // the expired rows so `user_sessions` stays bounded.
+        if let Err(err) = sqlx::query(
+            "DELETE FROM user_sessions WHERE session_id NOT IN (SELECT id FROM tower_sessions)",
+        )
+        .execute(&self.pool)
+        .await
+        {
+            warn!(
+                event = "sqlite.write",
+                operation = "session_delete_expired",
+                priority = SqliteWritePriority::BestEffort.as_str(),
+                error_chain = %err,
+                "failed to prune login records of removed sessions"
+            );
+        }
         Ok(())
     }
 }
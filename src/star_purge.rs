@@ -0,0 +1,350 @@
+use std::collections::HashSet;
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Duration, Utc};
+use serde::Serialize;
+use sqlx::SqliteConnection;
+
+use crate::{jobs, state::AppState};
+
+/// Due purges one worker run handles; the rest wait for the next run.
+const STAR_PURGE_MAX_ITEMS: i64 = 100;
+/// Bound parameters per `IN (...)` list, well under SQLite's limit.
+const STAR_PURGE_CHUNK_SIZE: usize = 500;
+/// `ai_translations` entity types keyed by a release id.
+const RELEASE_TRANSLATION_ENTITY_TYPES: [&str; 5] = [
+    "release",
+    "release_detail",
+    "release_detail_summary",
+    "release_smart",
+    "release_diff",
+];
+
+#[derive(Debug, Default, Serialize)]
+pub struct StarPurgeResult {
+    pub due: usize,
+    /// Repos the user sees again through a star, follow or owned repo; their
+    /// purge was dropped without deleting anything.
+    pub canceled: usize,
+    pub purged_repos: usize,
+    /// Release rows deleted because no other user still sees the repo.
+    pub deleted_releases: u64,
+    pub deleted_translations: u64,
+    pub deleted_reaction_rows: u64,
+    /// Due purges left for the next run because the task was canceled.
+    pub skipped: usize,
+}
+
+#[derive(Debug, sqlx::FromRow)]
+struct DuePurgeRow {
+    user_id: String,
+    repo_id: i64,
+    repo_full_name: String,
+}
+
+/// Brings the pending purges of a user in line with a starred snapshot that
+/// is about to be stored, so it must run before `starred_repos` is replaced.
+/// Repos in the snapshot cancel their pending purge. On a full snapshot,
+/// stored stars missing from it are scheduled for purge after the grace
+/// period; an already scheduled purge keeps its original deadline. Returns
+/// how many purges were newly scheduled.
+pub async fn reconcile_starred_snapshot(
+    state: &AppState,
+    user_id: &str,
+    repo_ids: &HashSet<i64>,
+    is_full_snapshot: bool,
+    now: DateTime<Utc>,
+) -> Result<usize> {
+    let snapshot_ids = repo_ids.iter().copied().collect::<Vec<_>>();
+    for chunk in snapshot_ids.chunks(STAR_PURGE_CHUNK_SIZE) {
+        let mut query =
+            sqlx::QueryBuilder::<sqlx::Sqlite>::new("DELETE FROM pending_purges WHERE user_id = ");
+        query.push_bind(user_id);
+        query.push(" AND repo_id IN (");
+        {
+            let mut separated = query.separated(", ");
+            for repo_id in chunk {
+                separated.push_bind(repo_id);
+            }
+        }
+        query.push(")");
+        query
+            .build()
+            .execute(&state.pool)
+            .await
+            .context("failed to cancel pending purges")?;
+    }
+    if !is_full_snapshot {
+        return Ok(0);
+    }
+
+    let stored = sqlx::query_as::<_, (i64, String)>(
+        r#"SELECT repo_id, full_name FROM starred_repos WHERE user_id = ?"#,
+    )
+    .bind(user_id)
+    .fetch_all(&state.pool)
+    .await
+    .context("failed to load stored starred repos")?;
+    let grace_days = i64::try_from(state.config.star_purge_grace_days)
+        .context("star purge grace days out of range")?;
+    let purge_after = (now + Duration::days(grace_days)).to_rfc3339();
+    let now = now.to_rfc3339();
+    let mut scheduled = 0;
+    for (repo_id, full_name) in stored
+        .into_iter()
+        .filter(|(repo_id, _)| !repo_ids.contains(repo_id))
+    {
+        let inserted = sqlx::query(
+            r#"
+            INSERT INTO pending_purges (user_id, repo_id, repo_full_name, unstarred_at, purge_after)
+            VALUES (?, ?, ?, ?, ?)
+            ON CONFLICT(user_id, repo_id) DO NOTHING
+            "#,
+        )
+        .bind(user_id)
+        .bind(repo_id)
+        .bind(full_name.as_str())
+        .bind(now.as_str())
+        .bind(purge_after.as_str())
+        .execute(&state.pool)
+        .await
+        .context("failed to schedule pending purge")?
+        .rows_affected();
+        if inserted > 0 {
+            tracing::info!(user_id, repo_id, full_name, "star purge: scheduled");
+            scheduled += 1;
+        }
+    }
+    Ok(scheduled)
+}
+
+pub async fn has_due_star_purges(state: &AppState, now: DateTime<Utc>) -> Result<bool> {
+    let due = sqlx::query_scalar::<_, i64>(
+        r#"SELECT EXISTS(SELECT 1 FROM pending_purges WHERE purge_after <= ?)"#,
+    )
+    .bind(now.to_rfc3339())
+    .fetch_one(&state.pool)
+    .await
+    .context("failed to check due star purges")?;
+    Ok(due != 0)
+}
+
+/// Deletes the data of repos whose grace period ran out. The user's
+/// translations and reaction cache rows for the repo's releases always go;
+/// the releases themselves, and every user's rows keyed by them, only go
+/// once nobody sees the repo, no other purge of it is still waiting, and no
+/// public release page has used it.
+pub async fn run_due_star_purges(state: &AppState, now: DateTime<Utc>) -> Result<StarPurgeResult> {
+    let now_text = now.to_rfc3339();
+    let rows = sqlx::query_as::<_, DuePurgeRow>(
+        r#"
+        SELECT user_id, repo_id, repo_full_name
+        FROM pending_purges
+        WHERE purge_after <= ?
+        ORDER BY purge_after, user_id, repo_id
+        LIMIT ?
+        "#,
+    )
+    .bind(now_text.as_str())
+    .bind(STAR_PURGE_MAX_ITEMS)
+    .fetch_all(&state.pool)
+    .await
+    .context("failed to load due star purges")?;
+    let mut result = StarPurgeResult {
+        due: rows.len(),
+        ..StarPurgeResult::default()
+    };
+
+    for row in rows {
+        if jobs::current_task_cancel_requested(state).await {
+            result.skipped += 1;
+            continue;
+        }
+        let mut tx = state
+            .pool
+            .begin()
+            .await
+            .context("failed to begin star purge tx")?;
+        let still_visible = sqlx::query_scalar::<_, i64>(
+            r#"
+            SELECT EXISTS(
+              SELECT 1 FROM user_release_visible_repos WHERE user_id = ? AND repo_id = ?
+            )
+            "#,
+        )
+        .bind(row.user_id.as_str())
+        .bind(row.repo_id)
+        .fetch_one(&mut *tx)
+        .await
+        .context("failed to check repo visibility")?
+            != 0;
+        if !still_visible {
+            let release_ids = sqlx::query_scalar::<_, i64>(
+                r#"SELECT release_id FROM repo_releases WHERE repo_id = ?"#,
+            )
+            .bind(row.repo_id)
+            .fetch_all(&mut *tx)
+            .await
+            .context("failed to load repo release ids")?;
+            let shared_unused = sqlx::query_scalar::<_, i64>(
+                r#"
+                SELECT NOT EXISTS(
+                  SELECT 1 FROM user_release_visible_repos WHERE repo_id = ?
+                ) AND NOT EXISTS(
+                  SELECT 1 FROM pending_purges WHERE repo_id = ? AND purge_after > ?
+                ) AND NOT EXISTS(
+                  SELECT 1 FROM public_repo_release_usage WHERE repo_id = ?
+                )
+                "#,
+            )
+            .bind(row.repo_id)
+            .bind(row.repo_id)
+            .bind(now_text.as_str())
+            .bind(row.repo_id)
+            .fetch_one(&mut *tx)
+            .await
+            .context("failed to check shared repo usage")?
+                != 0;
+            let owner = (!shared_unused).then_some(row.user_id.as_str());
+            for chunk in release_ids.chunks(STAR_PURGE_CHUNK_SIZE) {
+                result.deleted_translations +=
+                    delete_release_translations(&mut tx, owner, chunk).await?;
+                result.deleted_reaction_rows +=
+                    delete_release_reaction_rows(&mut tx, owner, chunk).await?;
+            }
+            if shared_unused {
+                result.deleted_releases += delete_repo_releases(&mut tx, row.repo_id).await?;
+            }
+            result.purged_repos += 1;
+            tracing::info!(
+                user_id = row.user_id,
+                repo_id = row.repo_id,
+                repo_full_name = row.repo_full_name,
+                releases = release_ids.len(),
+                shared_unused,
+                "star purge: purged"
+            );
+        } else {
+            result.canceled += 1;
+        }
+        sqlx::query(r#"DELETE FROM pending_purges WHERE user_id = ? AND repo_id = ?"#)
+            .bind(row.user_id.as_str())
+            .bind(row.repo_id)
+            .execute(&mut *tx)
+            .await
+            .context("failed to clear pending purge")?;
+        tx.commit()
+            .await
+            .context("failed to commit star purge tx")?;
+    }
+
+    Ok(result)
+}
+
+/// Deletes translations of the given releases: the owner's only, or every
+/// user's plus the shared cache when `owner` is `None`.
+async fn delete_release_translations(
+    conn: &mut SqliteConnection,
+    owner: Option<&str>,
+    release_ids: &[i64],
+) -> Result<u64> {
+    let entity_ids = release_ids
+        .iter()
+        .map(|release_id| release_id.to_string())
+        .collect::<Vec<_>>();
+    let mut deleted = delete_translation_rows(conn, "ai_translations", owner, &entity_ids).await?;
+    if owner.is_none() {
+        deleted +=
+            delete_translation_rows(conn, "ai_translations_shared", None, &entity_ids).await?;
+    }
+    Ok(deleted)
+}
+
+async fn delete_translation_rows(
+    conn: &mut SqliteConnection,
+    table: &str,
+    owner: Option<&str>,
+    entity_ids: &[String],
+) -> Result<u64> {
+    let mut query = sqlx::QueryBuilder::<sqlx::Sqlite>::new(format!(
+        "DELETE FROM {table} WHERE entity_type IN ("
+    ));
+    {
+        let mut separated = query.separated(", ");
+        for entity_type in RELEASE_TRANSLATION_ENTITY_TYPES {
+            separated.push_bind(entity_type);
+        }
+    }
+    query.push(") AND entity_id IN (");
+    {
+        let mut separated = query.separated(", ");
+        for entity_id in entity_ids {
+            separated.push_bind(entity_id);
+        }
+    }
+    query.push(")");
+    if let Some(owner) = owner {
+        query.push(" AND user_id = ");
+        query.push_bind(owner);
+    }
+    Ok(query
+        .build()
+        .execute(&mut *conn)
+        .await
+        .with_context(|| format!("failed to delete release translations from {table}"))?
+        .rows_affected())
+}
+
+async fn delete_release_reaction_rows(
+    conn: &mut SqliteConnection,
+    owner: Option<&str>,
+    release_ids: &[i64],
+) -> Result<u64> {
+    let mut query = sqlx::QueryBuilder::<sqlx::Sqlite>::new(
+        "DELETE FROM release_reaction_viewer WHERE release_id IN (",
+    );
+    {
+        let mut separated = query.separated(", ");
+        for release_id in release_ids {
+            separated.push_bind(release_id);
+        }
+    }
+    query.push(")");
+    if let Some(owner) = owner {
+        query.push(" AND user_id = ");
+        query.push_bind(owner);
+    }
+    Ok(query
+        .build()
+        .execute(&mut *conn)
+        .await
+        .context("failed to delete release reaction rows")?
+        .rows_affected())
+}
+
+/// Drops the repo's releases, their assets and its sync state, so starring it
+/// again later refetches everything instead of trusting a stale ETag.
+async fn delete_repo_releases(conn: &mut SqliteConnection, repo_id: i64) -> Result<u64> {
+    sqlx::query(
+        r#"
+        DELETE FROM release_assets
+        WHERE release_id IN (SELECT release_id FROM repo_releases WHERE repo_id = ?)
+        "#,
+    )
+    .bind(repo_id)
+    .execute(&mut *conn)
+    .await
+    .context("failed to delete release assets")?;
+    let deleted = sqlx::query(r#"DELETE FROM repo_releases WHERE repo_id = ?"#)
+        .bind(repo_id)
+        .execute(&mut *conn)
+        .await
+        .context("failed to delete repo releases")?
+        .rows_affected();
+    sqlx::query(r#"DELETE FROM repo_release_sync_state WHERE repo_id = ?"#)
+        .bind(repo_id)
+        .execute(&mut *conn)
+        .await
+        .context("failed to delete repo release sync state")?;
+    Ok(deleted)
+}
@@ -26,8 +26,9 @@ use webauthn_rs_core::{
 };
 
 use crate::{
-    ai::LlmScheduler, config::AppConfig, crypto::EncryptionKey, local_id,
-    sqlite_write::SqliteWriteCoordinator, translations::TranslationSchedulerController,
+    admin_audit::AdminMutationLimiter, ai::LlmScheduler, config::AppConfig, crypto::EncryptionKey,
+    feed_events::FeedEventHub, local_id, sqlite_write::SqliteWriteCoordinator,
+    translations::TranslationSchedulerController,
 };
 
 pub type GitHubOAuthClient =
@@ -51,6 +52,49 @@ pub struct AppState {
     pub llm_scheduler: Arc<LlmScheduler>,
     pub translation_scheduler: Arc<TranslationSchedulerController>,
     pub runtime_owner_id: String,
+    pub feed_events: FeedEventHub,
+    pub admin_mutation_limiter: AdminMutationLimiter,
+    pub translation_flights: crate::ai::TranslationSingleflight,
+    pub request_limiter: crate::rate_limit::RequestRateLimiter,
+    pub github_probe: crate::health::GitHubProbe,
+    /// Set when a GitHub App is configured; see [`crate::github_app`].
+    pub github_app_tokens: Option<Arc<crate::github::GitHubAppTokenProvider>>,
+}
+
+#[cfg(test)]
+impl AppState {
+    /// State over `pool` for unit tests, talking to the public GitHub
+    /// endpoints. Tests adjust the fields they exercise before wrapping it.
+    pub fn for_tests(pool: SqlitePool, config: AppConfig) -> Self {
+        let github_oauth = build_oauth_client(&config).expect("build oauth client");
+        let webauthn = build_webauthn(&config).expect("build webauthn");
+        Self {
+            llm_scheduler: Arc::new(LlmScheduler::new(config.ai_max_concurrency)),
+            translation_scheduler: Arc::new(TranslationSchedulerController::new(
+                crate::translations::TranslationRuntimeConfig::default(),
+            )),
+            encryption_key: config.encryption_key.clone(),
+            config,
+            pool,
+            sqlite_writer: SqliteWriteCoordinator::new(),
+            http: reqwest::Client::new(),
+            github_rest_http: reqwest::Client::new(),
+            github_rest_api_base: Url::parse("https://api.github.com/")
+                .expect("parse github rest api base"),
+            github_graphql_url: Url::parse("https://api.github.com/graphql")
+                .expect("parse github graphql url"),
+            github_oauth,
+            linuxdo_oauth: None,
+            webauthn,
+            runtime_owner_id: "test-runtime-owner".to_owned(),
+            feed_events: FeedEventHub::default(),
+            admin_mutation_limiter: AdminMutationLimiter::default(),
+            translation_flights: crate::ai::TranslationSingleflight::default(),
+            request_limiter: crate::rate_limit::RequestRateLimiter::default(),
+            github_probe: crate::health::GitHubProbe::default(),
+            github_app_tokens: None,
+        }
+    }
 }
 
 #[derive(Debug, Clone, sqlx::FromRow)]
@@ -448,10 +492,11 @@ pub async fn backfill_github_connections(pool: &SqlitePool) -> Result<()> {
                   access_token_ciphertext,
                   access_token_nonce,
                   scopes,
+                  is_primary,
                   linked_at,
                   updated_at
                 )
-                VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+                VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, 1, ?, ?)
                 "#,
             )
             .bind(local_id::generate_local_id())
@@ -613,6 +658,16 @@ mod tests {
             static_dir: None,
             task_log_dir: PathBuf::from("/tmp/octo-rill-state-tests"),
             job_worker_concurrency: 1,
+            reaction_viewer_ttl_secs: 600,
+            release_backfill_limit: 10,
+            star_purge_grace_days: 14,
+            notification_archive_days: 90,
+            notification_retention_days: 365,
+            task_retention_days: 30,
+            backup_dir: std::env::temp_dir().join("octo-rill-backups-tests"),
+            backup_retention_count: 7,
+            shutdown_grace_secs: 30,
+            task_timeouts: crate::jobs::TaskTimeouts::default(),
             encryption_key: EncryptionKey::from_base64(
                 "AAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAA=",
             )
@@ -623,12 +678,23 @@ mod tests {
                 redirect_url: Url::parse("http://127.0.0.1:58090/auth/github/callback")
                     .expect("parse github redirect url"),
             },
+            github_app: None,
             linuxdo: None,
             ai: None,
             ai_max_concurrency: 1,
+            ai_translate_concurrency: 2,
+            ai_skip_translation_cjk_percent: 30,
+            ai_daily_token_budget_per_user: None,
+            ai_request_interval_ms: 0,
             ai_daily_at_local: None,
             app_default_time_zone: "Asia/Shanghai".to_owned(),
+            smtp: None,
             logging: crate::observability::LoggingThresholds::default(),
+            metrics_token: None,
+            trust_request_id_header: false,
+            trust_forwarded_headers: false,
+            readyz_check_github: false,
+            rate_limits: crate::rate_limit::RateLimits::default(),
         }
     }
 
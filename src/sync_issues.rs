@@ -0,0 +1,289 @@
+use std::sync::Arc;
+
+use axum::{
+    Json,
+    extract::{Path, State},
+    http::StatusCode,
+};
+use schemars::JsonSchema;
+use serde::Serialize;
+
+use crate::{
+    api,
+    error::{ApiError, codes},
+    state::AppState,
+};
+
+#[derive(Debug, Serialize, JsonSchema, sqlx::FromRow)]
+pub struct SyncIssueItem {
+    id: String,
+    repo_id: i64,
+    repo_full_name: String,
+    /// Which sync hit the error; only `releases` today.
+    sync_type: String,
+    /// Sync failure reason such as `repo_inaccessible` (404/451 from GitHub).
+    error_code: String,
+    message: String,
+    occurred_at: String,
+}
+
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct SyncIssuesResponse {
+    items: Vec<SyncIssueItem>,
+}
+
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct SyncIssueDismissResponse {
+    id: String,
+    dismissed: bool,
+}
+
+#[derive(Debug, Serialize, JsonSchema, sqlx::FromRow)]
+pub struct PendingPurgeItem {
+    repo_id: i64,
+    repo_full_name: String,
+    unstarred_at: String,
+    /// When the repo's releases and translations are deleted unless it is
+    /// starred again first.
+    purge_after: String,
+}
+
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct PendingPurgesResponse {
+    items: Vec<PendingPurgeItem>,
+}
+
+/// Repos whose last sync for this user failed and has not succeeded since,
+/// newest first. A dismissed issue comes back if the repo fails again.
+pub async fn list_sync_issues(
+    State(state): State<Arc<AppState>>,
+    api::AuthedUser(user_id): api::AuthedUser,
+) -> Result<Json<SyncIssuesResponse>, ApiError> {
+    let items = sqlx::query_as::<_, SyncIssueItem>(
+        r#"
+        SELECT id, repo_id, repo_full_name, sync_type, error_code, message, occurred_at
+        FROM repo_sync_errors
+        WHERE user_id = ?
+          AND resolved_at IS NULL
+        ORDER BY occurred_at DESC, id DESC
+        "#,
+    )
+    .bind(user_id.as_str())
+    .fetch_all(&state.pool)
+    .await
+    .map_err(ApiError::internal)?;
+
+    Ok(Json(SyncIssuesResponse { items }))
+}
+
+/// Unstarred repos waiting out the grace period before their data is
+/// deleted, soonest deadline first.
+pub async fn list_pending_purges(
+    State(state): State<Arc<AppState>>,
+    api::AuthedUser(user_id): api::AuthedUser,
+) -> Result<Json<PendingPurgesResponse>, ApiError> {
+    let items = sqlx::query_as::<_, PendingPurgeItem>(
+        r#"
+        SELECT repo_id, repo_full_name, unstarred_at, purge_after
+        FROM pending_purges
+        WHERE user_id = ?
+        ORDER BY purge_after ASC, repo_id ASC
+        "#,
+    )
+    .bind(user_id.as_str())
+    .fetch_all(&state.pool)
+    .await
+    .map_err(ApiError::internal)?;
+
+    Ok(Json(PendingPurgesResponse { items }))
+}
+
+pub async fn dismiss_sync_issue(
+    State(state): State<Arc<AppState>>,
+    api::AuthedUser(user_id): api::AuthedUser,
+    Path(id): Path<String>,
+) -> Result<Json<SyncIssueDismissResponse>, ApiError> {
+    let now = chrono::Utc::now().to_rfc3339();
+    let dismissed = sqlx::query(
+        r#"
+        UPDATE repo_sync_errors
+        SET resolved_at = ?
+        WHERE id = ?
+          AND user_id = ?
+          AND resolved_at IS NULL
+        "#,
+    )
+    .bind(now.as_str())
+    .bind(id.trim())
+    .bind(user_id.as_str())
+    .execute(&state.pool)
+    .await
+    .map_err(ApiError::internal)?
+    .rows_affected();
+    if dismissed == 0 {
+        return Err(ApiError::new(
+            StatusCode::NOT_FOUND,
+            codes::SYNC_ISSUE_NOT_FOUND,
+            "sync issue not found",
+        ));
+    }
+
+    Ok(Json(SyncIssueDismissResponse {
+        id: id.trim().to_owned(),
+        dismissed: true,
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use sqlx::{SqlitePool, sqlite::SqlitePoolOptions};
+
+    use crate::config::AppConfig;
+
+    async fn setup_pool() -> SqlitePool {
+        let pool = SqlitePoolOptions::new()
+            .max_connections(1)
+            .connect("sqlite::memory:")
+            .await
+            .expect("create sqlite memory db");
+        sqlx::migrate!("./migrations")
+            .run(&pool)
+            .await
+            .expect("run migrations");
+        pool
+    }
+
+    async fn seed_user(pool: &SqlitePool, user_id: &str, github_user_id: i64) {
+        sqlx::query(
+            r#"
+            INSERT INTO users (id, github_user_id, login, created_at, updated_at)
+            VALUES (?, ?, ?, '2026-02-01T00:00:00Z', '2026-02-01T00:00:00Z')
+            "#,
+        )
+        .bind(user_id)
+        .bind(github_user_id)
+        .bind(format!("user-{github_user_id}"))
+        .execute(pool)
+        .await
+        .expect("seed user");
+    }
+
+    async fn seed_issue(pool: &SqlitePool, id: &str, user_id: &str, resolved_at: Option<&str>) {
+        sqlx::query(
+            r#"
+            INSERT INTO repo_sync_errors (
+              id, user_id, repo_id, repo_full_name, sync_type, error_code, message,
+              occurred_at, resolved_at
+            )
+            VALUES (?, ?, 42, 'octo/gone', 'releases', 'repo_inaccessible',
+              'github returned 404 Not Found', '2026-02-02T00:00:00Z', ?)
+            "#,
+        )
+        .bind(id)
+        .bind(user_id)
+        .bind(resolved_at)
+        .execute(pool)
+        .await
+        .expect("seed sync issue");
+    }
+
+    fn setup_state(pool: SqlitePool) -> Arc<AppState> {
+        Arc::new(AppState::for_tests(pool, AppConfig::for_tests()))
+    }
+
+    #[tokio::test]
+    async fn sync_issues_list_open_rows_and_dismiss_only_own() {
+        let pool = setup_pool().await;
+        let member = crate::local_id::test_local_id("sync-issues-member");
+        let other = crate::local_id::test_local_id("sync-issues-other");
+        seed_user(&pool, &member, 1).await;
+        seed_user(&pool, &other, 2).await;
+        seed_issue(&pool, "issue-open", &member, None).await;
+        seed_issue(
+            &pool,
+            "issue-resolved",
+            &member,
+            Some("2026-02-03T00:00:00Z"),
+        )
+        .await;
+        seed_issue(&pool, "issue-other", &other, None).await;
+        let state = setup_state(pool.clone());
+
+        let Json(listed) = list_sync_issues(State(state.clone()), api::AuthedUser(member.clone()))
+            .await
+            .expect("list sync issues");
+        let ids = listed
+            .items
+            .iter()
+            .map(|item| item.id.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(ids, vec!["issue-open"]);
+        assert_eq!(listed.items[0].repo_full_name, "octo/gone");
+        assert_eq!(listed.items[0].error_code, "repo_inaccessible");
+
+        let err = dismiss_sync_issue(
+            State(state.clone()),
+            api::AuthedUser(member.clone()),
+            Path("issue-other".to_owned()),
+        )
+        .await
+        .expect_err("cannot dismiss another user's issue");
+        assert_eq!(err.code(), "sync_issue_not_found");
+
+        let Json(dismissed) = dismiss_sync_issue(
+            State(state.clone()),
+            api::AuthedUser(member.clone()),
+            Path("issue-open".to_owned()),
+        )
+        .await
+        .expect("dismiss own issue");
+        assert!(dismissed.dismissed);
+
+        let Json(listed) = list_sync_issues(State(state), api::AuthedUser(member.clone()))
+            .await
+            .expect("list after dismiss");
+        assert!(listed.items.is_empty());
+    }
+
+    #[tokio::test]
+    async fn pending_purges_list_only_own_rows_soonest_first() {
+        let pool = setup_pool().await;
+        let member = crate::local_id::test_local_id("pending-purges-member");
+        let other = crate::local_id::test_local_id("pending-purges-other");
+        seed_user(&pool, &member, 1).await;
+        seed_user(&pool, &other, 2).await;
+        for (user_id, repo_id, full_name, purge_after) in [
+            (&member, 11, "octo/later", "2026-03-20T00:00:00Z"),
+            (&member, 12, "octo/sooner", "2026-03-10T00:00:00Z"),
+            (&other, 13, "octo/theirs", "2026-03-01T00:00:00Z"),
+        ] {
+            sqlx::query(
+                r#"
+                INSERT INTO pending_purges (user_id, repo_id, repo_full_name, unstarred_at, purge_after)
+                VALUES (?, ?, ?, '2026-02-24T00:00:00Z', ?)
+                "#,
+            )
+            .bind(user_id)
+            .bind(repo_id)
+            .bind(full_name)
+            .bind(purge_after)
+            .execute(&pool)
+            .await
+            .expect("seed pending purge");
+        }
+        let state = setup_state(pool);
+
+        let Json(listed) = list_pending_purges(State(state), api::AuthedUser(member.clone()))
+            .await
+            .expect("list pending purges");
+        let names = listed
+            .items
+            .iter()
+            .map(|item| item.repo_full_name.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(names, vec!["octo/sooner", "octo/later"]);
+        assert_eq!(listed.items[0].purge_after, "2026-03-10T00:00:00Z");
+    }
+}
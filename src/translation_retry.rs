@@ -0,0 +1,298 @@
+use std::collections::BTreeMap;
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Duration, Utc};
+use serde::Serialize;
+use sqlx::SqliteConnection;
+
+use crate::{
+    api::{self, TranslateBatchItem},
+    jobs,
+    state::AppState,
+};
+
+/// Only batch release translations are queued today.
+const RETRY_ENTITY_RELEASE: &str = "release";
+const RETRY_LANG: &str = "zh-CN";
+/// Wait before the next retry, indexed by failures so far. An item failing
+/// again after the last step leaves the queue for good.
+const TRANSLATION_RETRY_BACKOFF_MINUTES: [i64; 5] = [1, 5, 30, 120, 360];
+/// Due entries one worker run picks up; the rest wait for the next run.
+const TRANSLATION_RETRY_MAX_ITEMS: i64 = 100;
+/// Releases handed to the batch translator per call.
+const TRANSLATION_RETRY_BATCH_SIZE: usize = 10;
+
+#[derive(Debug, Default, Serialize)]
+pub struct TranslationRetryResult {
+    pub due: usize,
+    pub succeeded: usize,
+    /// Still failing; requeued with a longer wait or dropped after the last attempt.
+    pub failed: usize,
+    /// Due entries left for the next run because the task was canceled.
+    pub skipped: usize,
+}
+
+#[derive(Debug, sqlx::FromRow)]
+struct DueRetryRow {
+    user_id: String,
+    entity_id: String,
+}
+
+fn next_attempt_at(attempts: i64, now: DateTime<Utc>) -> Option<DateTime<Utc>> {
+    let index = usize::try_from(attempts.checked_sub(1)?).ok()?;
+    let minutes = TRANSLATION_RETRY_BACKOFF_MINUTES.get(index)?;
+    Some(now + Duration::minutes(*minutes))
+}
+
+/// Counts one more failure for a queued item, or drops it once the backoff
+/// schedule is used up.
+async fn note_failure(
+    conn: &mut SqliteConnection,
+    user_id: &str,
+    entity_id: &str,
+    error: Option<&str>,
+    now: DateTime<Utc>,
+) -> Result<()> {
+    let attempts = sqlx::query_scalar::<_, i64>(
+        r#"
+        SELECT attempts
+        FROM translation_retry_queue
+        WHERE user_id = ? AND entity_type = ? AND entity_id = ? AND lang = ?
+        "#,
+    )
+    .bind(user_id)
+    .bind(RETRY_ENTITY_RELEASE)
+    .bind(entity_id)
+    .bind(RETRY_LANG)
+    .fetch_optional(&mut *conn)
+    .await
+    .context("failed to load translation retry attempts")?
+    .unwrap_or(0)
+        + 1;
+    let Some(next_at) = next_attempt_at(attempts, now) else {
+        tracing::info!(
+            user_id,
+            entity_id,
+            attempts,
+            "translation retry: giving up after max attempts"
+        );
+        return clear_entry(conn, user_id, entity_id).await;
+    };
+
+    let now = now.to_rfc3339();
+    sqlx::query(
+        r#"
+        INSERT INTO translation_retry_queue (
+          id, user_id, entity_type, entity_id, lang, attempts, next_attempt_at, last_error,
+          created_at, updated_at
+        )
+        VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+        ON CONFLICT(user_id, entity_type, entity_id, lang) DO UPDATE SET
+          attempts = excluded.attempts,
+          next_attempt_at = excluded.next_attempt_at,
+          last_error = excluded.last_error,
+          updated_at = excluded.updated_at
+        "#,
+    )
+    .bind(crate::local_id::generate_local_id())
+    .bind(user_id)
+    .bind(RETRY_ENTITY_RELEASE)
+    .bind(entity_id)
+    .bind(RETRY_LANG)
+    .bind(attempts)
+    .bind(next_at.to_rfc3339())
+    .bind(error)
+    .bind(now.as_str())
+    .bind(now.as_str())
+    .execute(&mut *conn)
+    .await
+    .context("failed to queue translation retry")?;
+    Ok(())
+}
+
+async fn clear_entry(conn: &mut SqliteConnection, user_id: &str, entity_id: &str) -> Result<()> {
+    sqlx::query(
+        r#"
+        DELETE FROM translation_retry_queue
+        WHERE user_id = ? AND entity_type = ? AND entity_id = ? AND lang = ?
+        "#,
+    )
+    .bind(user_id)
+    .bind(RETRY_ENTITY_RELEASE)
+    .bind(entity_id)
+    .bind(RETRY_LANG)
+    .execute(&mut *conn)
+    .await
+    .context("failed to clear translation retry")?;
+    Ok(())
+}
+
+/// Queues the error items of a release batch run that just talked to the
+/// model and clears the entries of items that came back otherwise. Errors
+/// the upstream marked non-retryable are cleared rather than queued.
+pub(crate) async fn record_release_batch_outcome(
+    state: &AppState,
+    user_id: &str,
+    items: &[TranslateBatchItem],
+    non_retryable_error: Option<&str>,
+) -> Result<()> {
+    let now = Utc::now();
+    let mut tx = state
+        .pool
+        .begin()
+        .await
+        .context("failed to begin translation retry tx")?;
+    for item in items {
+        let retryable = item.status == "error"
+            && (non_retryable_error.is_none() || item.error.as_deref() != non_retryable_error);
+        if retryable {
+            note_failure(&mut tx, user_id, &item.id, item.error.as_deref(), now).await?;
+        } else {
+            clear_entry(&mut tx, user_id, &item.id).await?;
+        }
+    }
+    tx.commit()
+        .await
+        .context("failed to commit translation retry tx")
+}
+
+pub async fn has_due_translation_retries(state: &AppState, now: DateTime<Utc>) -> Result<bool> {
+    let due = sqlx::query_scalar::<_, i64>(
+        r#"
+        SELECT EXISTS(
+          SELECT 1 FROM translation_retry_queue WHERE next_attempt_at <= ?
+        )
+        "#,
+    )
+    .bind(now.to_rfc3339())
+    .fetch_one(&state.pool)
+    .await
+    .context("failed to check due translation retries")?;
+    Ok(due != 0)
+}
+
+/// Drops the cached error rows of the given releases so the batch pipeline
+/// asks the model again instead of returning the stored failure.
+async fn drop_cached_errors(state: &AppState, user_id: &str, entity_ids: &[String]) -> Result<()> {
+    let mut query = sqlx::QueryBuilder::<sqlx::Sqlite>::new(
+        r#"
+        DELETE FROM ai_translations
+        WHERE entity_type IN ('release', 'release_detail')
+          AND status = 'error'
+          AND lang = "#,
+    );
+    query.push_bind(RETRY_LANG);
+    query.push(" AND user_id = ");
+    query.push_bind(user_id);
+    query.push(" AND entity_id IN (");
+    {
+        let mut separated = query.separated(", ");
+        for entity_id in entity_ids {
+            separated.push_bind(entity_id);
+        }
+    }
+    query.push(")");
+    query
+        .build()
+        .execute(&state.pool)
+        .await
+        .context("failed to drop cached translation errors")?;
+    Ok(())
+}
+
+/// Runs due queue entries through the release batch pipeline, one user at a
+/// time in small groups. The pipeline itself requeues items that fail again;
+/// items it returns any other way are cleared here.
+pub async fn run_due_translation_retries(
+    state: &AppState,
+    now: DateTime<Utc>,
+) -> Result<TranslationRetryResult> {
+    let mut result = TranslationRetryResult::default();
+    if state.config.ai.is_none() {
+        return Ok(result);
+    }
+
+    let rows = sqlx::query_as::<_, DueRetryRow>(
+        r#"
+        SELECT user_id, entity_id
+        FROM translation_retry_queue
+        WHERE entity_type = ?
+          AND lang = ?
+          AND next_attempt_at <= ?
+        ORDER BY next_attempt_at, id
+        LIMIT ?
+        "#,
+    )
+    .bind(RETRY_ENTITY_RELEASE)
+    .bind(RETRY_LANG)
+    .bind(now.to_rfc3339())
+    .bind(TRANSLATION_RETRY_MAX_ITEMS)
+    .fetch_all(&state.pool)
+    .await
+    .context("failed to load due translation retries")?;
+    result.due = rows.len();
+
+    let mut due_by_user = BTreeMap::<String, Vec<String>>::new();
+    for row in rows {
+        due_by_user
+            .entry(row.user_id)
+            .or_default()
+            .push(row.entity_id);
+    }
+
+    for (user_id, entity_ids) in due_by_user {
+        for chunk in entity_ids.chunks(TRANSLATION_RETRY_BATCH_SIZE) {
+            if jobs::current_task_cancel_requested(state).await {
+                result.skipped += chunk.len();
+                continue;
+            }
+            let release_ids = chunk
+                .iter()
+                .filter_map(|entity_id| entity_id.parse::<i64>().ok())
+                .collect::<Vec<_>>();
+            drop_cached_errors(state, &user_id, chunk).await?;
+            let items = match api::translate_releases_batch_for_user(state, &user_id, &release_ids)
+                .await
+            {
+                Ok(res) => res.items,
+                Err(err) => {
+                    tracing::warn!(
+                        user_id,
+                        code = err.code(),
+                        "translation retry: batch translate failed"
+                    );
+                    let mut conn = state
+                        .pool
+                        .acquire()
+                        .await
+                        .context("failed to acquire translation retry connection")?;
+                    for entity_id in chunk {
+                        note_failure(&mut conn, &user_id, entity_id, Some(err.code()), now).await?;
+                    }
+                    result.failed += chunk.len();
+                    continue;
+                }
+            };
+
+            let mut conn = state
+                .pool
+                .acquire()
+                .await
+                .context("failed to acquire translation retry connection")?;
+            for entity_id in chunk {
+                match items.iter().find(|item| &item.id == entity_id) {
+                    Some(item) if item.status == "error" => result.failed += 1,
+                    Some(item) => {
+                        if item.status == "ready" {
+                            result.succeeded += 1;
+                        }
+                        clear_entry(&mut conn, &user_id, entity_id).await?;
+                    }
+                    None => clear_entry(&mut conn, &user_id, entity_id).await?,
+                }
+            }
+        }
+    }
+
+    Ok(result)
+}
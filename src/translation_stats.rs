@@ -0,0 +1,334 @@
+use std::sync::Arc;
+
+use axum::{Json, extract::State};
+use chrono::{DateTime, Duration, Utc};
+use schemars::JsonSchema;
+use serde::Serialize;
+use sqlx::SqlitePool;
+
+use crate::{api, error::ApiError, state::AppState};
+
+/// Age buckets by `updated_at`, newest first; anything older lands in `older`.
+const TRANSLATION_AGE_BUCKETS: [(&str, i64); 4] = [("1d", 1), ("7d", 7), ("30d", 30), ("90d", 90)];
+
+#[derive(Debug, Serialize, JsonSchema, sqlx::FromRow)]
+pub struct TranslationStatsGroup {
+    entity_type: String,
+    lang: String,
+    entries: i64,
+    ready: i64,
+    /// Source release changed after the translation was written.
+    stale: i64,
+    /// Ready rows whose summary holds a raw model JSON payload.
+    #[sqlx(default)]
+    corrupted: i64,
+}
+
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct TranslationAgeBucket {
+    /// `1d`, `7d`, `30d`, `90d`, or `older`.
+    max_age: String,
+    entries: i64,
+}
+
+#[derive(Debug, Serialize, JsonSchema)]
+pub struct TranslationStatsResponse {
+    /// Users with at least one cached entry; only set for admins.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    users: Option<i64>,
+    entries: i64,
+    stale: i64,
+    corrupted: i64,
+    groups: Vec<TranslationStatsGroup>,
+    age: Vec<TranslationAgeBucket>,
+}
+
+/// Aggregates the translation cache of one user, or of everyone without
+/// `user_id`. Staleness comes from the flag release sync sets, so no source
+/// hashes are recomputed here.
+async fn load_translation_stats(
+    pool: &SqlitePool,
+    user_id: Option<&str>,
+    now: DateTime<Utc>,
+) -> Result<TranslationStatsResponse, sqlx::Error> {
+    let mut groups = sqlx::query_as::<_, TranslationStatsGroup>(
+        r#"
+        SELECT
+          entity_type,
+          lang,
+          COUNT(*) AS entries,
+          SUM(CASE WHEN status = 'ready' THEN 1 ELSE 0 END) AS ready,
+          SUM(CASE WHEN source_changed_at > updated_at THEN 1 ELSE 0 END) AS stale
+        FROM ai_translations
+        WHERE (? IS NULL OR user_id = ?)
+        GROUP BY entity_type, lang
+        ORDER BY entity_type, lang
+        "#,
+    )
+    .bind(user_id)
+    .bind(user_id)
+    .fetch_all(pool)
+    .await?;
+
+    // Only rows containing a JSON marker can be damaged; the precise check
+    // runs on that short list.
+    let suspects = sqlx::query_as::<_, (String, String, String)>(
+        r#"
+        SELECT entity_type, lang, summary
+        FROM ai_translations
+        WHERE (? IS NULL OR user_id = ?)
+          AND status = 'ready'
+          AND summary IS NOT NULL
+          AND (
+            instr(summary, '{') > 0
+            OR instr(summary, '"summary_md"') > 0
+            OR instr(summary, '"title_zh"') > 0
+            OR instr(summary, '"body_md"') > 0
+          )
+        "#,
+    )
+    .bind(user_id)
+    .bind(user_id)
+    .fetch_all(pool)
+    .await?;
+    for (entity_type, lang, summary) in suspects {
+        if !api::looks_like_json_blob(&summary) {
+            continue;
+        }
+        if let Some(group) = groups
+            .iter_mut()
+            .find(|group| group.entity_type == entity_type && group.lang == lang)
+        {
+            group.corrupted += 1;
+        }
+    }
+
+    let cutoffs =
+        TRANSLATION_AGE_BUCKETS.map(|(_, days)| (now - Duration::days(days)).to_rfc3339());
+    let bucket_counts = sqlx::query_as::<_, (i64, i64)>(
+        r#"
+        SELECT
+          CASE
+            WHEN updated_at >= ? THEN 0
+            WHEN updated_at >= ? THEN 1
+            WHEN updated_at >= ? THEN 2
+            WHEN updated_at >= ? THEN 3
+            ELSE 4
+          END AS bucket,
+          COUNT(*)
+        FROM ai_translations
+        WHERE (? IS NULL OR user_id = ?)
+        GROUP BY bucket
+        "#,
+    )
+    .bind(&cutoffs[0])
+    .bind(&cutoffs[1])
+    .bind(&cutoffs[2])
+    .bind(&cutoffs[3])
+    .bind(user_id)
+    .bind(user_id)
+    .fetch_all(pool)
+    .await?;
+    let age = TRANSLATION_AGE_BUCKETS
+        .iter()
+        .map(|(label, _)| *label)
+        .chain(["older"])
+        .zip(0_i64..)
+        .map(|(label, index)| TranslationAgeBucket {
+            max_age: label.to_owned(),
+            entries: bucket_counts
+                .iter()
+                .find(|(bucket, _)| *bucket == index)
+                .map_or(0, |(_, count)| *count),
+        })
+        .collect();
+
+    Ok(TranslationStatsResponse {
+        users: None,
+        entries: groups.iter().map(|group| group.entries).sum(),
+        stale: groups.iter().map(|group| group.stale).sum(),
+        corrupted: groups.iter().map(|group| group.corrupted).sum(),
+        groups,
+        age,
+    })
+}
+
+pub async fn get_translation_stats(
+    State(state): State<Arc<AppState>>,
+    api::AuthedUser(user_id): api::AuthedUser,
+) -> Result<Json<TranslationStatsResponse>, ApiError> {
+    let stats = load_translation_stats(&state.pool, Some(user_id.as_str()), Utc::now())
+        .await
+        .map_err(ApiError::internal)?;
+    Ok(Json(stats))
+}
+
+pub async fn admin_get_translation_stats(
+    State(state): State<Arc<AppState>>,
+    _: api::AdminUser,
+) -> Result<Json<TranslationStatsResponse>, ApiError> {
+    let mut stats = load_translation_stats(&state.pool, None, Utc::now())
+        .await
+        .map_err(ApiError::internal)?;
+    let users =
+        sqlx::query_scalar::<_, i64>(r#"SELECT COUNT(DISTINCT user_id) FROM ai_translations"#)
+            .fetch_one(&state.pool)
+            .await
+            .map_err(ApiError::internal)?;
+    stats.users = Some(users);
+    Ok(Json(stats))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use chrono::TimeZone;
+    use sqlx::sqlite::SqlitePoolOptions;
+
+    async fn setup_pool() -> SqlitePool {
+        let pool = SqlitePoolOptions::new()
+            .max_connections(1)
+            .connect("sqlite::memory:")
+            .await
+            .expect("create sqlite memory db");
+        sqlx::migrate!("./migrations")
+            .run(&pool)
+            .await
+            .expect("run migrations");
+        pool
+    }
+
+    async fn seed_user(pool: &SqlitePool, user_id: &str, github_user_id: i64) {
+        sqlx::query(
+            r#"
+            INSERT INTO users (id, github_user_id, login, created_at, updated_at)
+            VALUES (?, ?, ?, '2026-02-01T00:00:00Z', '2026-02-01T00:00:00Z')
+            "#,
+        )
+        .bind(user_id)
+        .bind(github_user_id)
+        .bind(format!("user-{github_user_id}"))
+        .execute(pool)
+        .await
+        .expect("seed user");
+    }
+
+    struct SeedTranslation<'a> {
+        user_id: &'a str,
+        entity_type: &'a str,
+        entity_id: &'a str,
+        summary: &'a str,
+        updated_at: &'a str,
+        source_changed_at: Option<&'a str>,
+    }
+
+    async fn seed_translation(pool: &SqlitePool, row: SeedTranslation<'_>) {
+        sqlx::query(
+            r#"
+            INSERT INTO ai_translations (
+              id, user_id, entity_type, entity_id, lang, source_hash, title, summary,
+              created_at, updated_at, source_changed_at
+            )
+            VALUES (?, ?, ?, ?, 'zh-CN', 'hash', '标题', ?, ?, ?, ?)
+            "#,
+        )
+        .bind(crate::local_id::generate_local_id())
+        .bind(row.user_id)
+        .bind(row.entity_type)
+        .bind(row.entity_id)
+        .bind(row.summary)
+        .bind(row.updated_at)
+        .bind(row.updated_at)
+        .bind(row.source_changed_at)
+        .execute(pool)
+        .await
+        .expect("seed translation");
+    }
+
+    #[tokio::test]
+    async fn translation_stats_count_stale_corrupted_and_age_per_user() {
+        let pool = setup_pool().await;
+        let member = crate::local_id::test_local_id("translation-stats-member");
+        let other = crate::local_id::test_local_id("translation-stats-other");
+        seed_user(&pool, &member, 1).await;
+        seed_user(&pool, &other, 2).await;
+        let now = Utc.with_ymd_and_hms(2026, 3, 10, 12, 0, 0).unwrap();
+        for row in [
+            SeedTranslation {
+                user_id: &member,
+                entity_type: "release",
+                entity_id: "1",
+                summary: "- 修复",
+                updated_at: "2026-03-10T00:00:00Z",
+                source_changed_at: None,
+            },
+            SeedTranslation {
+                user_id: &member,
+                entity_type: "release",
+                entity_id: "2",
+                summary: "- 旧译文",
+                updated_at: "2026-03-01T00:00:00Z",
+                source_changed_at: Some("2026-03-05T00:00:00Z"),
+            },
+            // Re-translated after the source changed: no longer stale.
+            SeedTranslation {
+                user_id: &member,
+                entity_type: "release_detail",
+                entity_id: "3",
+                summary: r#"{"title_zh":"标题","summary_md":"- 一行"}"#,
+                updated_at: "2025-10-01T00:00:00Z",
+                source_changed_at: Some("2025-09-01T00:00:00Z"),
+            },
+            SeedTranslation {
+                user_id: &other,
+                entity_type: "release",
+                entity_id: "1",
+                summary: "- 修复",
+                updated_at: "2026-03-10T00:00:00Z",
+                source_changed_at: Some("2026-03-10T06:00:00Z"),
+            },
+        ] {
+            seed_translation(&pool, row).await;
+        }
+
+        let stats = load_translation_stats(&pool, Some(member.as_str()), now)
+            .await
+            .expect("member stats");
+        assert_eq!(stats.entries, 3);
+        assert_eq!(stats.stale, 1);
+        assert_eq!(stats.corrupted, 1);
+        let groups = stats
+            .groups
+            .iter()
+            .map(|group| {
+                (
+                    group.entity_type.as_str(),
+                    group.entries,
+                    group.stale,
+                    group.corrupted,
+                )
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            groups,
+            vec![("release", 2, 1, 0), ("release_detail", 1, 0, 1)]
+        );
+        let age = stats
+            .age
+            .iter()
+            .map(|bucket| (bucket.max_age.as_str(), bucket.entries))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            age,
+            vec![("1d", 1), ("7d", 0), ("30d", 1), ("90d", 0), ("older", 1)]
+        );
+
+        let all = load_translation_stats(&pool, None, now)
+            .await
+            .expect("global stats");
+        assert_eq!(all.entries, 4);
+        assert_eq!(all.stale, 2);
+        assert_eq!(all.age[0].entries, 2);
+    }
+}
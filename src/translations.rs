@@ -17,10 +17,14 @@ use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use sqlx::{Row, Sqlite, SqlitePool, Transaction};
 use tokio::time::sleep;
-use tower_sessions::Session;
 use tracing::warn;
 
-use crate::{admin_runtime, ai, api, error::ApiError, runtime, state::AppState};
+use crate::{
+    admin_runtime, ai, api,
+    error::{ApiError, ApiJson, codes},
+    feed_events, runtime,
+    state::AppState,
+};
 
 const TRANSLATION_PROTOCOL_VERSION: &str = "translation-request.v1";
 const TRANSLATION_MODEL_PROFILE_DISABLED: &str = "ai-disabled";
@@ -127,6 +131,8 @@ pub fn classify_translation_error(error_text: Option<&str>) -> Option<Classified
         || normalized.contains("markdown structure")
     {
         ("markdown_structure_mismatch", "Markdown 结构校验失败")
+    } else if normalized.contains("changed or dropped source links") {
+        ("link_mismatch", "译文链接与原文不一致")
     } else if normalized.contains("body exceeds 3000 chars")
         || (normalized.contains("3000")
             && normalized.contains("body")
@@ -1686,10 +1692,9 @@ pub async fn recover_runtime_state_on_startup(state: &AppState) -> Result<()> {
 
 pub async fn submit_translation_request(
     State(state): State<Arc<AppState>>,
-    session: Session,
-    Json(req): Json<TranslationSubmitRequest>,
+    api::AuthedUser(user_id): api::AuthedUser,
+    ApiJson(req): ApiJson<TranslationSubmitRequest>,
 ) -> Result<Response, ApiError> {
-    let user_id = api::require_active_user_id(state.as_ref(), &session).await?;
     let mode = normalize_mode(req.mode.trim())?;
 
     match normalize_submit_payload(mode, req)? {
@@ -1732,10 +1737,9 @@ pub async fn submit_translation_request(
 
 pub async fn get_translation_request(
     State(state): State<Arc<AppState>>,
-    session: Session,
+    api::AuthedUser(user_id): api::AuthedUser,
     Path(request_id): Path<String>,
 ) -> Result<Json<TranslationRequestResponse>, ApiError> {
-    let user_id = api::require_active_user_id(state.as_ref(), &session).await?;
     let request_id = api::parse_local_id_param(request_id, "request_id")?;
     let detail = load_translation_request_detail(state.as_ref(), &user_id, &request_id).await?;
     Ok(Json(detail_to_public_response(detail)))
@@ -1743,10 +1747,9 @@ pub async fn get_translation_request(
 
 pub async fn resolve_translation_results(
     State(state): State<Arc<AppState>>,
-    session: Session,
-    Json(req): Json<TranslationResolveRequest>,
+    api::AuthedUser(user_id): api::AuthedUser,
+    ApiJson(req): ApiJson<TranslationResolveRequest>,
 ) -> Result<Json<TranslationResolveResponse>, ApiError> {
-    let user_id = api::require_active_user_id(state.as_ref(), &session).await?;
     let items = normalize_request_items(&req.items)?;
     let items =
         resolve_translation_results_for_user(state.as_ref(), &user_id, &items, req.retry_on_error)
@@ -1761,10 +1764,9 @@ pub async fn resolve_translation_results(
 
 pub async fn stream_translation_request(
     State(state): State<Arc<AppState>>,
-    session: Session,
+    api::AuthedUser(user_id): api::AuthedUser,
     Path(request_id): Path<String>,
 ) -> Result<Response, ApiError> {
-    let user_id = api::require_active_user_id(state.as_ref(), &session).await?;
     let request_id = api::parse_local_id_param(request_id, "request_id")?;
     ensure_request_owner(state.as_ref(), &user_id, &request_id).await?;
     Ok(stream_translation_request_response(
@@ -1776,9 +1778,8 @@ pub async fn stream_translation_request(
 
 pub async fn admin_get_translation_status(
     State(state): State<Arc<AppState>>,
-    session: Session,
+    _: api::AdminUser,
 ) -> Result<Json<AdminTranslationStatusResponse>, ApiError> {
-    let _acting_user_id = api::require_admin_user_id(state.as_ref(), &session).await?;
     admin_runtime::sync_persisted_runtime_settings(state.clone())
         .await
         .map_err(ApiError::internal)?;
@@ -1789,10 +1790,9 @@ pub async fn admin_get_translation_status(
 
 pub async fn admin_patch_translation_runtime_config(
     State(state): State<Arc<AppState>>,
-    session: Session,
-    Json(req): Json<AdminTranslationRuntimeConfigUpdateRequest>,
+    _: api::AdminUser,
+    ApiJson(req): ApiJson<AdminTranslationRuntimeConfigUpdateRequest>,
 ) -> Result<Json<AdminTranslationStatusResponse>, ApiError> {
-    let _acting_user_id = api::require_admin_user_id(state.as_ref(), &session).await?;
     let general_worker_concurrency = parse_positive_worker_concurrency(
         req.general_worker_concurrency,
         "general_worker_concurrency",
@@ -2079,10 +2079,9 @@ fn validate_translation_worker_concurrency_total(
 
 pub async fn admin_list_translation_requests(
     State(state): State<Arc<AppState>>,
-    session: Session,
+    _: api::AdminUser,
     Query(query): Query<AdminTranslationListQuery>,
 ) -> Result<Json<AdminTranslationRequestsResponse>, ApiError> {
-    let _acting_user_id = api::require_admin_user_id(state.as_ref(), &session).await?;
     let page = query.page.unwrap_or(1).max(1);
     let page_size = query.page_size.unwrap_or(20).clamp(1, 100);
     let offset = (page - 1) * page_size;
@@ -2140,10 +2139,9 @@ pub async fn admin_list_translation_requests(
 
 pub async fn admin_get_translation_request_detail(
     State(state): State<Arc<AppState>>,
-    session: Session,
+    _: api::AdminUser,
     Path(request_id): Path<String>,
 ) -> Result<Json<AdminTranslationRequestDetailResponse>, ApiError> {
-    let _acting_user_id = api::require_admin_user_id(state.as_ref(), &session).await?;
     let request_id = api::parse_local_id_param(request_id, "request_id")?;
     let request_row_sql = format!(
         r#"{}
@@ -2160,7 +2158,7 @@ pub async fn admin_get_translation_request_detail(
         .ok_or_else(|| {
             ApiError::new(
                 StatusCode::NOT_FOUND,
-                "not_found",
+                codes::NOT_FOUND,
                 "translation request not found",
             )
         })?;
@@ -2172,10 +2170,9 @@ pub async fn admin_get_translation_request_detail(
 
 pub async fn admin_list_translation_batches(
     State(state): State<Arc<AppState>>,
-    session: Session,
+    _: api::AdminUser,
     Query(query): Query<AdminTranslationListQuery>,
 ) -> Result<Json<AdminTranslationBatchesResponse>, ApiError> {
-    let _acting_user_id = api::require_admin_user_id(state.as_ref(), &session).await?;
     let page = query.page.unwrap_or(1).max(1);
     let page_size = query.page_size.unwrap_or(20).clamp(1, 100);
     let offset = (page - 1) * page_size;
@@ -2237,10 +2234,9 @@ pub async fn admin_list_translation_batches(
 
 pub async fn admin_get_translation_batch_detail(
     State(state): State<Arc<AppState>>,
-    session: Session,
+    _: api::AdminUser,
     Path(batch_id): Path<String>,
 ) -> Result<Json<AdminTranslationBatchDetailResponse>, ApiError> {
-    let _acting_user_id = api::require_admin_user_id(state.as_ref(), &session).await?;
     let batch_id = api::parse_local_id_param(batch_id, "batch_id")?;
     let batch = sqlx::query_as::<_, AdminTranslationBatchRow>(
         r#"
@@ -2258,7 +2254,7 @@ pub async fn admin_get_translation_batch_detail(
     .ok_or_else(|| {
         ApiError::new(
             StatusCode::NOT_FOUND,
-            "not_found",
+            codes::NOT_FOUND,
             "translation batch not found",
         )
     })?;
@@ -4271,11 +4267,12 @@ async fn resolve_batch_results(
                 state,
                 item.scope_user_id.as_str(),
                 item.entity_id.as_str(),
+                api::ReleaseDetailTranslationMode::Full,
             )
             .await
             {
                 Ok(translated) => terminal_result_from_single_response(item, &translated),
-                Err(err) if err.code() == "not_found" => TerminalWorkResult {
+                Err(err) if err.code() == codes::NOT_FOUND => TerminalWorkResult {
                     work_item_id: item.id.clone(),
                     result_status: "missing".to_owned(),
                     title_zh: None,
@@ -4306,7 +4303,7 @@ async fn resolve_batch_results(
             .await
             {
                 Ok(translated) => terminal_result_from_single_response(item, &translated),
-                Err(err) if err.code() == "not_found" => TerminalWorkResult {
+                Err(err) if err.code() == codes::NOT_FOUND => TerminalWorkResult {
                     work_item_id: item.id.clone(),
                     result_status: "missing".to_owned(),
                     title_zh: None,
@@ -4380,6 +4377,7 @@ async fn finalize_batch_success(
         .sqlite_writer
         .begin_immediate(&state.pool, "translation_batch_finalize")
         .await?;
+    let mut ready_release_translations = Vec::new();
     for result in &results {
         let Some(work_item) = batch
             .items
@@ -4568,6 +4566,11 @@ async fn finalize_batch_success(
             now.as_str(),
         )
         .await?;
+        if result.result_status == "ready"
+            && let Some(release_id) = feed_release_id_for_work_item(work_item)
+        {
+            ready_release_translations.push((work_item.scope_user_id.clone(), release_id));
+        }
     }
 
     sqlx::query(
@@ -4588,9 +4591,62 @@ async fn finalize_batch_success(
     .await?;
 
     tx.commit().await?;
+    publish_release_translation_ready_events(state, &ready_release_translations).await;
     Ok(())
 }
 
+fn feed_release_id_for_work_item(item: &WorkItemRow) -> Option<i64> {
+    match map_entity_type(item.kind.as_str(), item.variant.as_str())? {
+        "release" | "release_detail" | "release_smart" => item.entity_id.parse::<i64>().ok(),
+        _ => None,
+    }
+}
+
+async fn publish_release_translation_ready_events(
+    state: &AppState,
+    ready_release_translations: &[(String, i64)],
+) {
+    if ready_release_translations.is_empty() || !state.feed_events.has_subscribers() {
+        return;
+    }
+    for (user_id, release_id) in ready_release_translations {
+        let repo_full_name = match sqlx::query_scalar::<_, String>(
+            r#"
+            SELECT sr.full_name
+            FROM repo_releases r
+            JOIN user_release_visible_repos sr
+              ON sr.user_id = ? AND sr.repo_id = r.repo_id
+            WHERE r.release_id = ?
+            LIMIT 1
+            "#,
+        )
+        .bind(user_id.as_str())
+        .bind(release_id)
+        .fetch_optional(&state.pool)
+        .await
+        {
+            Ok(Some(full_name)) => full_name,
+            Ok(None) => continue,
+            Err(err) => {
+                warn!(
+                    release_id,
+                    ?err,
+                    "failed to resolve repo for translation feed event"
+                );
+                continue;
+            }
+        };
+        state.feed_events.publish(
+            user_id.as_str(),
+            feed_events::FeedEvent {
+                kind: feed_events::FEED_EVENT_TRANSLATION_READY,
+                release_id: *release_id,
+                repo_full_name,
+            },
+        );
+    }
+}
+
 async fn finalize_batch_failure(
     state: &AppState,
     batch: &ClaimedBatch,
@@ -5118,14 +5174,14 @@ async fn ensure_request_owner(
     .ok_or_else(|| {
         ApiError::new(
             StatusCode::NOT_FOUND,
-            "not_found",
+            codes::NOT_FOUND,
             "translation request not found",
         )
     })?;
     if owner != user_id {
         return Err(ApiError::new(
             StatusCode::FORBIDDEN,
-            "forbidden",
+            codes::FORBIDDEN,
             "translation request not found",
         ));
     }
@@ -5153,7 +5209,7 @@ async fn load_translation_request_detail(
         .ok_or_else(|| {
             ApiError::new(
                 StatusCode::NOT_FOUND,
-                "not_found",
+                codes::NOT_FOUND,
                 "translation request not found",
             )
         })?;
@@ -5897,20 +5953,15 @@ async fn load_last_batch_finished_at(state: &AppState) -> Result<Option<String>,
 
 #[cfg(test)]
 mod tests {
-    use std::{net::SocketAddr, sync::Arc, time::Duration};
+    use std::{sync::Arc, time::Duration};
 
     use sqlx::{
         SqlitePool,
         sqlite::{SqliteConnectOptions, SqliteJournalMode, SqlitePoolOptions},
     };
-    use url::Url;
 
     use super::*;
-    use crate::{
-        config::{AppConfig, GitHubOAuthConfig},
-        crypto::EncryptionKey,
-        state::build_oauth_client,
-    };
+    use crate::config::AppConfig;
 
     fn test_dedicated_worker_slot() -> i64 {
         i64::try_from(DEFAULT_TRANSLATION_GENERAL_WORKER_CONCURRENCY + 1)
@@ -5934,6 +5985,17 @@ mod tests {
         }
     }
 
+    #[test]
+    fn classify_translation_error_maps_link_mismatch() {
+        let classified = classify_translation_error(Some(
+            "release detail translation changed or dropped source links",
+        ))
+        .expect("classified link mismatch");
+
+        assert_eq!(classified.code, "link_mismatch");
+        assert_eq!(classified.summary, "译文链接与原文不一致");
+    }
+
     #[test]
     fn classify_translation_error_maps_markdown_mismatch() {
         let classified = classify_translation_error(Some(
@@ -11725,55 +11787,11 @@ mod tests {
     }
 
     fn setup_state(pool: SqlitePool) -> Arc<AppState> {
-        let encryption_key =
-            EncryptionKey::from_base64("AAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAA=")
-                .expect("build encryption key");
         let config = AppConfig {
-            bind_addr: "127.0.0.1:58090"
-                .parse::<SocketAddr>()
-                .expect("parse bind addr"),
-            public_base_url: Url::parse("http://127.0.0.1:58090").expect("parse public base url"),
-            database_url: "sqlite::memory:".to_owned(),
-            sqlite_pool_max_connections: 8,
-            static_dir: None,
-            task_log_dir: std::env::temp_dir().join("octo-rill-task-logs-translation-tests"),
             job_worker_concurrency: 2,
-            encryption_key: encryption_key.clone(),
-            github: GitHubOAuthConfig {
-                client_id: "test-client-id".to_owned(),
-                client_secret: "test-client-secret".to_owned(),
-                redirect_url: Url::parse("http://127.0.0.1:58090/auth/callback")
-                    .expect("parse github redirect"),
-            },
-            linuxdo: None,
-            ai: None,
-            ai_max_concurrency: 1,
-            ai_daily_at_local: None,
-            app_default_time_zone: crate::briefs::DEFAULT_DAILY_BRIEF_TIME_ZONE.to_owned(),
-            logging: crate::observability::LoggingThresholds::default(),
+            ..AppConfig::for_tests()
         };
-        let github_oauth = build_oauth_client(&config).expect("build oauth client");
-        let webauthn = crate::state::build_webauthn(&config).expect("build webauthn");
-        Arc::new(AppState {
-            llm_scheduler: Arc::new(crate::ai::LlmScheduler::new(config.ai_max_concurrency)),
-            translation_scheduler: Arc::new(TranslationSchedulerController::new(
-                TranslationRuntimeConfig::default(),
-            )),
-            config,
-            pool,
-            sqlite_writer: crate::sqlite_write::SqliteWriteCoordinator::new(),
-            http: reqwest::Client::new(),
-            github_rest_http: reqwest::Client::new(),
-            github_rest_api_base: Url::parse("https://api.github.com/")
-                .expect("parse github rest api base"),
-            github_graphql_url: Url::parse("https://api.github.com/graphql")
-                .expect("parse github graphql url"),
-            github_oauth,
-            linuxdo_oauth: None,
-            webauthn,
-            encryption_key,
-            runtime_owner_id: "translation-test-runtime-owner".to_owned(),
-        })
+        Arc::new(AppState::for_tests(pool, config))
     }
 
     async fn seed_user(pool: &SqlitePool, id: i64, login: &str) {
@@ -180,7 +180,11 @@ test("dashboard sync click creates the same access-refresh task visible in admin
 		}
 
 		if (req.method() === "GET" && pathname === "/api/notifications") {
-			return json(route, []);
+			return json(route, {
+				group: "none",
+				items: [],
+				counts: { total: 0, unread: 0, by_subject_type: {} },
+			});
 		}
 
 		if (req.method() === "GET" && pathname === "/api/briefs") {
@@ -141,7 +141,11 @@ async function installBaseMocks(
 		}
 
 		if (req.method() === "GET" && pathname === "/api/notifications") {
-			return json(route, []);
+			return json(route, {
+				group: "none",
+				items: [],
+				counts: { total: 0, unread: 0, by_subject_type: {} },
+			});
 		}
 
 		if (req.method() === "GET" && pathname === "/api/briefs") {
@@ -253,7 +253,11 @@ async function installAppAuthMocks(page: Page, options: AppAuthMockOptions) {
 		}
 
 		if (req.method() === "GET" && pathname === "/api/notifications") {
-			return json(route, []);
+			return json(route, {
+				group: "none",
+				items: [],
+				counts: { total: 0, unread: 0, by_subject_type: {} },
+			});
 		}
 
 		if (req.method() === "GET" && pathname === "/api/briefs") {
@@ -651,7 +655,11 @@ test("warm cache resumes the dashboard shell immediately before the network refr
 
 		if (req.method() === "GET" && pathname === "/api/notifications") {
 			await sleep(1500);
-			return json(route, []);
+			return json(route, {
+				group: "none",
+				items: [],
+				counts: { total: 0, unread: 0, by_subject_type: {} },
+			});
 		}
 
 		if (req.method() === "GET" && pathname === "/api/briefs") {
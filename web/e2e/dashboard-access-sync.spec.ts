@@ -268,7 +268,11 @@ test("dashboard keeps sync as a single header action for admins", async ({
 		}
 
 		if (req.method() === "GET" && pathname === "/api/notifications") {
-			return json(route, []);
+			return json(route, {
+				group: "none",
+				items: [],
+				counts: { total: 0, unread: 0, by_subject_type: {} },
+			});
 		}
 
 		if (req.method() === "GET" && pathname === "/api/briefs") {
@@ -405,7 +409,11 @@ test("dashboard keeps header utilities inline on tablet widths", async ({
 		}
 
 		if (req.method() === "GET" && pathname === "/api/notifications") {
-			return json(route, []);
+			return json(route, {
+				group: "none",
+				items: [],
+				counts: { total: 0, unread: 0, by_subject_type: {} },
+			});
 		}
 
 		if (req.method() === "GET" && pathname === "/api/briefs") {
@@ -563,7 +571,11 @@ test.describe("mobile dashboard shell", () => {
 			}
 
 			if (req.method() === "GET" && pathname === "/api/notifications") {
-				return json(route, []);
+				return json(route, {
+					group: "none",
+					items: [],
+					counts: { total: 0, unread: 0, by_subject_type: {} },
+				});
 			}
 
 			if (req.method() === "GET" && pathname === "/api/briefs") {
@@ -1057,18 +1069,22 @@ test.describe("mobile dashboard shell", () => {
 
 			if (req.method() === "GET" && pathname === "/api/notifications") {
 				notificationCalls += 1;
-				return json(route, [
-					{
-						thread_id: "91001",
-						repo_full_name: "owner/repo",
-						subject_title: "Build failed on main",
-						subject_type: "CheckSuite",
-						reason: "ci_activity",
-						updated_at: "2026-04-09T08:02:00Z",
-						unread: 1,
-						html_url: null,
-					},
-				]);
+				return json(route, {
+					group: "none",
+					items: [
+						{
+							thread_id: "91001",
+							repo_full_name: "owner/repo",
+							subject_title: "Build failed on main",
+							subject_type: "CheckSuite",
+							reason: "ci_activity",
+							updated_at: "2026-04-09T08:02:00Z",
+							unread: 1,
+							html_url: null,
+						},
+					],
+					counts: { total: 1, unread: 1, by_subject_type: { CheckSuite: 1 } },
+				});
 			}
 
 			if (req.method() === "GET" && pathname === "/api/briefs") {
@@ -1168,7 +1184,11 @@ test.describe("mobile dashboard shell", () => {
 			}
 
 			if (req.method() === "GET" && pathname === "/api/notifications") {
-				return json(route, []);
+				return json(route, {
+					group: "none",
+					items: [],
+					counts: { total: 0, unread: 0, by_subject_type: {} },
+				});
 			}
 
 			if (req.method() === "GET" && pathname === "/api/briefs") {
@@ -1340,7 +1360,11 @@ test.describe("mobile dashboard shell", () => {
 			}
 
 			if (req.method() === "GET" && pathname === "/api/notifications") {
-				return json(route, []);
+				return json(route, {
+					group: "none",
+					items: [],
+					counts: { total: 0, unread: 0, by_subject_type: {} },
+				});
 			}
 
 			if (req.method() === "GET" && pathname === "/api/briefs") {
@@ -1489,7 +1513,11 @@ test.describe("mobile dashboard shell", () => {
 			}
 
 			if (req.method() === "GET" && pathname === "/api/notifications") {
-				return json(route, []);
+				return json(route, {
+					group: "none",
+					items: [],
+					counts: { total: 0, unread: 0, by_subject_type: {} },
+				});
 			}
 
 			if (req.method() === "GET" && pathname === "/api/briefs") {
@@ -1636,7 +1664,11 @@ test.describe("mobile dashboard shell", () => {
 			}
 
 			if (req.method() === "GET" && pathname === "/api/notifications") {
-				return json(route, []);
+				return json(route, {
+					group: "none",
+					items: [],
+					counts: { total: 0, unread: 0, by_subject_type: {} },
+				});
 			}
 
 			if (req.method() === "GET" && pathname === "/api/briefs") {
@@ -1929,7 +1961,11 @@ test("dashboard refreshes cached and fresh feed data across access sync stages",
 
 			if (req.method() === "GET" && pathname === "/api/notifications") {
 				notificationCalls += 1;
-				return json(route, []);
+				return json(route, {
+					group: "none",
+					items: [],
+					counts: { total: 0, unread: 0, by_subject_type: {} },
+				});
 			}
 
 			if (req.method() === "GET" && pathname === "/api/briefs") {
@@ -2136,7 +2172,11 @@ test("dashboard opens access sync warmup bubble immediately after clicking sync"
 
 		if (req.method() === "GET" && pathname === "/api/notifications") {
 			notificationCalls += 1;
-			return json(route, []);
+			return json(route, {
+				group: "none",
+				items: [],
+				counts: { total: 0, unread: 0, by_subject_type: {} },
+			});
 		}
 
 		if (req.method() === "GET" && pathname === "/api/briefs") {
@@ -2348,24 +2388,28 @@ test("dashboard keeps inbox sync busy through transient task stream errors", asy
 
 			if (req.method() === "GET" && pathname === "/api/notifications") {
 				notificationCalls += 1;
-				return json(route, [
-					{
-						thread_id: inboxPhase === "fresh" ? "90002" : "90001",
-						repo_full_name: "owner/repo",
-						subject_title:
-							inboxPhase === "fresh"
-								? "Fresh inbox thread"
-								: "Cached inbox thread",
-						subject_type: "PullRequest",
-						reason: "review_requested",
-						updated_at: "2026-02-22T11:22:33Z",
-						unread: inboxPhase === "fresh" ? 1 : 0,
-						html_url:
-							inboxPhase === "fresh"
-								? "https://github.com/owner/repo/pull/77"
-								: "https://github.com/owner/repo/pull/42",
-					},
-				]);
+				return json(route, {
+					group: "none",
+					items: [
+						{
+							thread_id: inboxPhase === "fresh" ? "90002" : "90001",
+							repo_full_name: "owner/repo",
+							subject_title:
+								inboxPhase === "fresh"
+									? "Fresh inbox thread"
+									: "Cached inbox thread",
+							subject_type: "PullRequest",
+							reason: "review_requested",
+							updated_at: "2026-02-22T11:22:33Z",
+							unread: inboxPhase === "fresh" ? 1 : 0,
+							html_url:
+								inboxPhase === "fresh"
+									? "https://github.com/owner/repo/pull/77"
+									: "https://github.com/owner/repo/pull/42",
+						},
+					],
+					counts: { total: 1, unread: 0, by_subject_type: { PullRequest: 1 } },
+				});
 			}
 
 			if (req.method() === "GET" && pathname === "/api/briefs") {
@@ -2583,7 +2627,11 @@ test("dashboard keeps inbox sync reachable when inbox is empty", async ({
 		}
 
 		if (req.method() === "GET" && pathname === "/api/notifications") {
-			return json(route, []);
+			return json(route, {
+				group: "none",
+				items: [],
+				counts: { total: 0, unread: 0, by_subject_type: {} },
+			});
 		}
 
 		if (req.method() === "GET" && pathname === "/api/briefs") {
@@ -2685,18 +2733,22 @@ test("dashboard inbox cards fall back to GitHub per-thread pages when html_url i
 		}
 
 		if (req.method() === "GET" && pathname === "/api/notifications") {
-			return json(route, [
-				{
-					thread_id: "90003",
-					repo_full_name: "owner/repo",
-					subject_title: "Fallback inbox thread",
-					subject_type: "CheckSuite",
-					reason: "ci_activity",
-					updated_at: "2026-02-22T11:22:33Z",
-					unread: 1,
-					html_url: null,
-				},
-			]);
+			return json(route, {
+				group: "none",
+				items: [
+					{
+						thread_id: "90003",
+						repo_full_name: "owner/repo",
+						subject_title: "Fallback inbox thread",
+						subject_type: "CheckSuite",
+						reason: "ci_activity",
+						updated_at: "2026-02-22T11:22:33Z",
+						unread: 1,
+						html_url: null,
+					},
+				],
+				counts: { total: 1, unread: 1, by_subject_type: { CheckSuite: 1 } },
+			});
 		}
 
 		if (req.method() === "GET" && pathname === "/api/briefs") {
@@ -2777,18 +2829,22 @@ test("dashboard inbox cards ignore stale repo-homepage html_url values until rep
 		}
 
 		if (req.method() === "GET" && pathname === "/api/notifications") {
-			return json(route, [
-				{
-					thread_id: "90004",
-					repo_full_name: "owner/repo",
-					subject_title: "Stale repo homepage link",
-					subject_type: "PullRequest",
-					reason: "review_requested",
-					updated_at: "2026-02-22T11:22:33Z",
-					unread: 1,
-					html_url: "https://github.com/owner/repo/",
-				},
-			]);
+			return json(route, {
+				group: "none",
+				items: [
+					{
+						thread_id: "90004",
+						repo_full_name: "owner/repo",
+						subject_title: "Stale repo homepage link",
+						subject_type: "PullRequest",
+						reason: "review_requested",
+						updated_at: "2026-02-22T11:22:33Z",
+						unread: 1,
+						html_url: "https://github.com/owner/repo/",
+					},
+				],
+				counts: { total: 1, unread: 1, by_subject_type: { PullRequest: 1 } },
+			});
 		}
 
 		if (req.method() === "GET" && pathname === "/api/briefs") {
@@ -2860,7 +2916,11 @@ test("dashboard retries the initial sidebar bootstrap after a transient briefs f
 		}
 
 		if (req.method() === "GET" && pathname === "/api/notifications") {
-			return json(route, []);
+			return json(route, {
+				group: "none",
+				items: [],
+				counts: { total: 0, unread: 0, by_subject_type: {} },
+			});
 		}
 
 		if (req.method() === "GET" && pathname === "/api/briefs") {
@@ -2974,18 +3034,22 @@ test("dashboard retries the initial sidebar bootstrap after a transient inbox fa
 				);
 			}
 
-			return json(route, [
-				{
-					thread_id: "93001",
-					repo_full_name: "owner/repo",
-					subject_title: "Recovered inbox thread",
-					subject_type: "PullRequest",
-					reason: "review_requested",
-					updated_at: "2026-04-09T08:02:00Z",
-					unread: 1,
-					html_url: "https://github.com/owner/repo/pull/93001",
-				},
-			]);
+			return json(route, {
+				group: "none",
+				items: [
+					{
+						thread_id: "93001",
+						repo_full_name: "owner/repo",
+						subject_title: "Recovered inbox thread",
+						subject_type: "PullRequest",
+						reason: "review_requested",
+						updated_at: "2026-04-09T08:02:00Z",
+						unread: 1,
+						html_url: "https://github.com/owner/repo/pull/93001",
+					},
+				],
+				counts: { total: 1, unread: 1, by_subject_type: { PullRequest: 1 } },
+			});
 		}
 
 		if (req.method() === "GET" && pathname === "/api/briefs") {
@@ -3062,7 +3126,11 @@ test("dashboard reaction dialog keeps PAT status unknown when the PAT status fet
 		}
 
 		if (req.method() === "GET" && pathname === "/api/notifications") {
-			return json(route, []);
+			return json(route, {
+				group: "none",
+				items: [],
+				counts: { total: 0, unread: 0, by_subject_type: {} },
+			});
 		}
 
 		if (req.method() === "GET" && pathname === "/api/briefs") {
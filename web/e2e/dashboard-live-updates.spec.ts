@@ -144,7 +144,11 @@ test("dashboard live updates retain feed boundaries until they leave the viewpor
 		}
 
 		if (req.method() === "GET" && pathname === "/api/notifications") {
-			return json(route, []);
+			return json(route, {
+				group: "none",
+				items: [],
+				counts: { total: 0, unread: 0, by_subject_type: {} },
+			});
 		}
 
 		if (req.method() === "GET" && pathname === "/api/reaction-token/status") {
@@ -364,7 +368,11 @@ test("dashboard live updates do not place feed boundaries below processed existi
 		}
 
 		if (req.method() === "GET" && pathname === "/api/notifications") {
-			return json(route, []);
+			return json(route, {
+				group: "none",
+				items: [],
+				counts: { total: 0, unread: 0, by_subject_type: {} },
+			});
 		}
 
 		if (req.method() === "GET" && pathname === "/api/reaction-token/status") {
@@ -481,18 +489,22 @@ test("dashboard live updates re-baselines when inbox polling is enabled later",
 		}
 
 		if (req.method() === "GET" && pathname === "/api/notifications") {
-			return json(route, [
-				{
-					thread_id: "thread-existing",
-					repo_full_name: "openai/codex",
-					subject_title: "Existing thread",
-					subject_type: "PullRequest",
-					reason: "review_requested",
-					updated_at: "2026-04-30T08:00:00Z",
-					unread: 1,
-					html_url: null,
-				},
-			]);
+			return json(route, {
+				group: "none",
+				items: [
+					{
+						thread_id: "thread-existing",
+						repo_full_name: "openai/codex",
+						subject_title: "Existing thread",
+						subject_type: "PullRequest",
+						reason: "review_requested",
+						updated_at: "2026-04-30T08:00:00Z",
+						unread: 1,
+						html_url: null,
+					},
+				],
+				counts: { total: 1, unread: 1, by_subject_type: { PullRequest: 1 } },
+			});
 		}
 
 		if (req.method() === "GET" && pathname === "/api/reaction-token/status") {
@@ -224,7 +224,11 @@ test("dashboard renders mixed social activity in all tab and filters stars/follo
 		}
 
 		if (req.method() === "GET" && pathname === "/api/notifications") {
-			return json(route, []);
+			return json(route, {
+				group: "none",
+				items: [],
+				counts: { total: 0, unread: 0, by_subject_type: {} },
+			});
 		}
 
 		if (req.method() === "GET" && pathname === "/api/briefs") {
@@ -418,7 +422,11 @@ test("dashboard keeps social cards inline on mobile widths without horizontal ov
 		}
 
 		if (req.method() === "GET" && pathname === "/api/notifications") {
-			return json(route, []);
+			return json(route, {
+				group: "none",
+				items: [],
+				counts: { total: 0, unread: 0, by_subject_type: {} },
+			});
 		}
 
 		if (req.method() === "GET" && pathname === "/api/briefs") {
@@ -663,7 +671,11 @@ test("dashboard keeps the action centered with left-only, right-only, and bilate
 		}
 
 		if (req.method() === "GET" && pathname === "/api/notifications") {
-			return json(route, []);
+			return json(route, {
+				group: "none",
+				items: [],
+				counts: { total: 0, unread: 0, by_subject_type: {} },
+			});
 		}
 
 		if (req.method() === "GET" && pathname === "/api/briefs") {
@@ -943,7 +955,11 @@ test("dashboard keeps short follower rows right-trimmed on mobile", async ({
 		}
 
 		if (req.method() === "GET" && pathname === "/api/notifications") {
-			return json(route, []);
+			return json(route, {
+				group: "none",
+				items: [],
+				counts: { total: 0, unread: 0, by_subject_type: {} },
+			});
 		}
 
 		if (req.method() === "GET" && pathname === "/api/briefs") {
@@ -1074,7 +1090,11 @@ test("social activity cards fall back to placeholder avatar when image fails", a
 		}
 
 		if (req.method() === "GET" && pathname === "/api/notifications") {
-			return json(route, []);
+			return json(route, {
+				group: "none",
+				items: [],
+				counts: { total: 0, unread: 0, by_subject_type: {} },
+			});
 		}
 
 		if (req.method() === "GET" && pathname === "/api/briefs") {
@@ -1224,7 +1244,11 @@ test("switching social tabs clears stale feed items before the next dataset reso
 
 		if (req.method() === "GET" && pathname === "/api/notifications") {
 			_notificationsCalls += 1;
-			return json(route, []);
+			return json(route, {
+				group: "none",
+				items: [],
+				counts: { total: 0, unread: 0, by_subject_type: {} },
+			});
 		}
 
 		if (req.method() === "GET" && pathname === "/api/briefs") {
@@ -40,6 +40,7 @@ import {
 	type AdminSyncRuntimeConfigResponse,
 	type AdminSyncSubscriptionsDiagnostics,
 	type SyncAutoFetchTaskItem,
+	type TaskPriority,
 	type LocalUserId,
 	ApiError,
 	apiCancelAdminRealtimeTask,
@@ -853,6 +854,22 @@ const REALTIME_STATUS_FILTER_OPTIONS: Array<{
 	{ value: "canceled", label: "状态：取消" },
 ];
 
+const REALTIME_PRIORITY_FILTER_OPTIONS: Array<{
+	value: RealtimePriorityFilter;
+	label: string;
+}> = [
+	{ value: "all", label: "优先级：全部" },
+	{ value: "high", label: "优先级：高" },
+	{ value: "normal", label: "优先级：普通" },
+	{ value: "low", label: "优先级：低" },
+];
+
+const TASK_PRIORITY_LABELS: Record<TaskPriority, string> = {
+	high: "高优先级",
+	normal: "普通优先级",
+	low: "低优先级",
+};
+
 const LLM_STATUS_FILTER_OPTIONS: Array<{
 	value: LlmStatusFilter;
 	label: string;
@@ -1011,6 +1028,18 @@ function taskTypeLabel(taskType: string) {
 			return "订阅同步";
 		case "retry.recent_failures":
 			return "失败数据重试";
+		case "maintenance.prune":
+			return "任务历史清理";
+		case "maintenance.db":
+			return "数据库维护";
+		case "translation.repair":
+			return "损坏译文修复";
+		case "translation.retry":
+			return "失败译文重试";
+		case "maintenance.star_purge":
+			return "取消 Star 数据清理";
+		case "maintenance.backup":
+			return "数据库备份";
 		case "brief.generate":
 			return "日报生成";
 		case "brief.refresh_content":
@@ -1025,6 +1054,10 @@ function taskTypeLabel(taskType: string) {
 			return "同步 Release";
 		case "sync.notifications":
 			return "同步通知";
+		case "sync.release_node_ids":
+			return "回填 Release 节点 ID";
+		case "sync.backfill_repo_releases":
+			return "新 Star 仓库 Release 回填";
 		case "translate.release":
 			return "翻译 Release";
 		case "translate.release.batch":
@@ -1372,6 +1405,8 @@ type RealtimeStatusFilter =
 	| "succeeded"
 	| "canceled";
 
+type RealtimePriorityFilter = "all" | TaskPriority;
+
 type LlmStatusFilter = "all" | "queued" | "running" | "failed" | "succeeded";
 
 const TASK_PAGE_SIZE = 20;
@@ -1379,6 +1414,11 @@ const SCHEDULED_TASK_TYPES = new Set([
 	"brief.daily_slot",
 	"sync.subscriptions",
 	"retry.recent_failures",
+	"maintenance.prune",
+	"maintenance.db",
+	"translation.retry",
+	"maintenance.star_purge",
+	"maintenance.backup",
 ]);
 const STREAM_REFRESH_DELAY_MS = 600;
 const STREAM_RECONNECT_DELAY_MS = 1500;
@@ -3629,6 +3669,8 @@ export function JobManagement({
 	const [overviewLoading, setOverviewLoading] = useState(false);
 
 	const [statusFilter, setStatusFilter] = useState<RealtimeStatusFilter>("all");
+	const [priorityFilter, setPriorityFilter] =
+		useState<RealtimePriorityFilter>("all");
 	const [tasks, setTasks] = useState<AdminRealtimeTaskItem[]>([]);
 	const [taskTotal, setTaskTotal] = useState(0);
 	const [taskPage, setTaskPage] = useState(1);
@@ -3952,7 +3994,7 @@ export function JobManagement({
 
 	const loadRealtimeTasks = useCallback(
 		async (options?: LoadOptions) => {
-			const requestKey = `${statusFilter}:${taskPage}`;
+			const requestKey = `${statusFilter}:${priorityFilter}:${taskPage}`;
 			if (
 				shouldReuseInitialListRequest(
 					tasksLoadedOnceRef.current,
@@ -3973,6 +4015,7 @@ export function JobManagement({
 			try {
 				const params = new URLSearchParams();
 				params.set("status", statusFilter);
+				params.set("priority", priorityFilter);
 				params.set("task_group", "realtime");
 				params.set("page", String(taskPage));
 				params.set("page_size", String(TASK_PAGE_SIZE));
@@ -4003,7 +4046,7 @@ export function JobManagement({
 				}
 			}
 		},
-		[statusFilter, taskPage],
+		[statusFilter, priorityFilter, taskPage],
 	);
 
 	const loadScheduledRuns = useCallback(
@@ -5085,8 +5128,17 @@ export function JobManagement({
 	return (
 		<div className="space-y-4">
 			<Card className="border-0 bg-transparent shadow-none sm:border sm:bg-card sm:shadow-sm">
-				<CardHeader className="px-0 pb-3 sm:px-6 sm:pb-6">
+				<CardHeader className="flex flex-row flex-wrap items-baseline justify-between gap-2 px-0 pb-3 sm:px-6 sm:pb-6">
 					<CardTitle>任务总览</CardTitle>
+					<p
+						className="text-xs text-muted-foreground"
+						data-testid="job-overview-last-prune"
+					>
+						上次清理{" "}
+						{overview?.last_prune_at
+							? formatLocalDateTime(overview.last_prune_at)
+							: "暂无"}
+					</p>
 				</CardHeader>
 				<CardContent
 					className="grid grid-cols-3 gap-2 px-4 pb-4 pt-0 sm:grid-cols-2 sm:gap-3 sm:px-6 sm:pb-6 lg:grid-cols-3"
@@ -5113,7 +5165,14 @@ export function JobManagement({
 							<span className="sm:hidden">近24h成/败</span>
 							<span className="hidden sm:inline">近24h 成功 / 失败</span>
 						</p>
-						<p className="mt-0.5 truncate text-base font-semibold sm:mt-1 sm:text-xl">
+						<p
+							className="mt-0.5 truncate text-base font-semibold sm:mt-1 sm:text-xl"
+							title={
+								overview?.timed_out_24h
+									? `其中超时 ${formatCount(overview.timed_out_24h)}`
+									: undefined
+							}
+						>
 							{formatCount(overview?.succeeded_24h)} /{" "}
 							{formatCount(overview?.failed_24h)}
 						</p>
@@ -5220,6 +5279,17 @@ export function JobManagement({
 									ariaLabel="实时异步任务状态筛选"
 									className="w-full sm:w-[220px]"
 								/>
+								<FilterSelect
+									value={priorityFilter}
+									onValueChange={(nextValue) => {
+										setTaskPage(1);
+										setPriorityFilter(nextValue);
+									}}
+									options={REALTIME_PRIORITY_FILTER_OPTIONS}
+									placeholder="优先级筛选"
+									ariaLabel="实时异步任务优先级筛选"
+									className="w-full sm:w-[180px]"
+								/>
 								<span className="text-muted-foreground text-xs">
 									共 {formatCount(taskTotal)} 个任务 · 当前用户 #{currentUserId}
 								</span>
@@ -5277,6 +5347,11 @@ export function JobManagement({
 																label={taskStatusLabel(displayStatus)}
 																tone={tone}
 															/>
+															{task.priority ? (
+																<span className="rounded-md border bg-background px-1.5 py-0.5 text-[11px]">
+																	{TASK_PRIORITY_LABELS[task.priority]}
+																</span>
+															) : null}
 															{task.cancel_requested ? (
 																<FlagBadge
 																	label="已请求取消"
@@ -1,3 +1,9 @@
+import type {
+	BookmarkDeleteResponse,
+	BookmarkItem,
+	BookmarksResponse,
+	BriefSourcesResponse,
+} from "@/feed/types";
 import type { RepoVisual } from "@/lib/repoVisual";
 
 export class ApiError extends Error {
@@ -32,6 +38,37 @@ function toApiError(res: Response, body: unknown) {
 	return new ApiError(res.status, res.statusText);
 }
 
+const CSRF_HEADER = "x-csrf-token";
+let csrfToken: Promise<string | null> | null = null;
+
+function loadCsrfToken(refresh = false) {
+	if (!csrfToken || refresh) {
+		csrfToken = fetch("/api/csrf", { credentials: "include" })
+			.then(async (res) => {
+				if (!res.ok) return null;
+				const body = (await res.json()) as { token?: string };
+				return body.token ?? null;
+			})
+			.catch(() => null);
+	}
+	return csrfToken;
+}
+
+// Mutating requests carry the session CSRF token. The session may have been
+// rotated (login/logout) since the token was cached, so one refresh is allowed.
+async function fetchMutation(path: string, init: RequestInit) {
+	const send = async (token: string | null) => {
+		const headers = new Headers(init.headers);
+		if (token) headers.set(CSRF_HEADER, token);
+		return fetch(path, { ...init, credentials: "include", headers });
+	};
+	const res = await send(await loadCsrfToken());
+	if (res.status !== 403) return res;
+	const body = await parseJson(res.clone());
+	if (toApiError(res, body).code !== "csrf_invalid") return res;
+	return send(await loadCsrfToken(true));
+}
+
 export async function apiGet<T>(path: string): Promise<T> {
 	const res = await fetch(path, { credentials: "include" });
 	if (!res.ok) {
@@ -50,10 +87,9 @@ export async function apiPostJson<T>(
 ): Promise<T> {
 	const headers = new Headers(init?.headers);
 	headers.set("content-type", "application/json");
-	const res = await fetch(path, {
+	const res = await fetchMutation(path, {
 		...init,
 		method: "POST",
-		credentials: "include",
 		headers,
 		body: body === undefined ? undefined : JSON.stringify(body),
 	});
@@ -95,10 +131,9 @@ export async function apiPostTaskSse(
 ): Promise<TaskSseTerminalEvent> {
 	const headers = new Headers(init?.headers);
 	headers.set("content-type", "application/json");
-	const res = await fetch(path, {
+	const res = await fetchMutation(path, {
 		...init,
 		method: "POST",
-		credentials: "include",
 		headers,
 		body: body === undefined ? undefined : JSON.stringify(body),
 	});
@@ -150,9 +185,8 @@ export async function apiPostTaskSse(
 	);
 }
 export async function apiPutJson<T>(path: string, body?: unknown): Promise<T> {
-	const res = await fetch(path, {
+	const res = await fetchMutation(path, {
 		method: "PUT",
-		credentials: "include",
 		headers: { "content-type": "application/json" },
 		body: body === undefined ? undefined : JSON.stringify(body),
 	});
@@ -165,9 +199,8 @@ export async function apiPatchJson<T>(
 	path: string,
 	body?: unknown,
 ): Promise<T> {
-	const res = await fetch(path, {
+	const res = await fetchMutation(path, {
 		method: "PATCH",
-		credentials: "include",
 		headers: { "content-type": "application/json" },
 		body: body === undefined ? undefined : JSON.stringify(body),
 	});
@@ -180,9 +213,8 @@ export async function apiDeleteJson<T>(
 	path: string,
 	body?: unknown,
 ): Promise<T> {
-	const res = await fetch(path, {
+	const res = await fetchMutation(path, {
 		method: "DELETE",
-		credentials: "include",
 		headers:
 			body === undefined ? undefined : { "content-type": "application/json" },
 		body: body === undefined ? undefined : JSON.stringify(body),
@@ -215,6 +247,11 @@ export type MeResponse = {
 		daily_boundary_utc_offset_minutes: number;
 		include_own_releases: boolean;
 	};
+	/** Present while an admin views the app as `user`; writes are rejected. */
+	impersonating?: {
+		admin_id: LocalUserId;
+		user: MeResponse["user"];
+	};
 };
 export type DashboardUpdateList = {
 	changed: boolean;
@@ -235,6 +272,9 @@ export type AdminUserProfileResponse = {
 	daily_brief_local_time: string;
 	daily_brief_time_zone: string;
 	include_own_releases: boolean;
+	sync_frequency_minutes: number;
+	muted_notification_reasons: string[];
+	skip_muted_notifications: boolean;
 	last_active_at: string | null;
 };
 export type MeProfileResponse = {
@@ -242,6 +282,9 @@ export type MeProfileResponse = {
 	daily_brief_local_time: string;
 	daily_brief_time_zone: string;
 	include_own_releases: boolean;
+	sync_frequency_minutes: number;
+	muted_notification_reasons: string[];
+	skip_muted_notifications: boolean;
 	last_active_at: string | null;
 };
 export type SyncAutoFetchTaskItem = {
@@ -358,6 +401,9 @@ export type DailyBriefProfilePatchRequest = {
 	daily_brief_local_time: string;
 	daily_brief_time_zone: string;
 	include_own_releases?: boolean;
+	sync_frequency_minutes?: number;
+	muted_notification_reasons?: string[];
+	skip_muted_notifications?: boolean;
 };
 export type LinuxDoConnectionResponse = {
 	linuxdo_user_id: number;
@@ -382,6 +428,7 @@ export type GitHubConnectionResponse = {
 	avatar_url: string | null;
 	email: string | null;
 	scopes: string;
+	is_primary: boolean;
 	linked_at: string;
 	updated_at: string;
 };
@@ -464,6 +511,7 @@ export type ReactionTokenOwnerSummary = {
 };
 export type ReactionTokenStatusResponse = {
 	configured: boolean;
+	oauth_ready?: boolean;
 	masked_token: string | null;
 	check: {
 		state: "idle" | "valid" | "invalid" | "error";
@@ -477,13 +525,104 @@ export type ReactionTokenCheckResponse = {
 	message: string;
 	owner: ReactionTokenOwnerSummary | null;
 };
+export type ReactionTokenRepoDiagnosis = {
+	repo_full_name: string;
+	repo_visible: boolean;
+	can_react: boolean;
+	viewer_permission: string | null;
+	suggested_fix: string | null;
+};
+export type ReactionTokenDiagnoseResponse = {
+	token_valid: boolean;
+	items: ReactionTokenRepoDiagnosis[];
+};
+export type AccountDeletionSummary = {
+	deleted: Record<string, number>;
+	anonymized: Record<string, number>;
+};
+export const ACCOUNT_DELETE_CONFIRMATION = "delete my account";
+export type FeedTokenResponse = {
+	token: string;
+	feed_url: string;
+};
+export type FollowImportRequest =
+	| { source: "user"; login: string }
+	| { source: "list"; owner: string; list: string };
+export type FollowImportResponse = {
+	fetched: number;
+	added: number;
+	updated: number;
+	truncated: boolean;
+	stopped_early_rate_limit: boolean;
+};
+export type FollowRemoveResponse = {
+	repo_id: number;
+	followed: boolean;
+	deleted_release_count: number;
+};
+export type SyncIssueItem = {
+	id: string;
+	repo_id: number;
+	repo_full_name: string;
+	sync_type: "releases";
+	error_code: string;
+	message: string;
+	occurred_at: string;
+};
+export type SyncIssuesResponse = {
+	items: SyncIssueItem[];
+};
+export type SyncIssueDismissResponse = {
+	id: string;
+	dismissed: boolean;
+};
+export type PendingPurgeItem = {
+	repo_id: number;
+	repo_full_name: string;
+	unstarred_at: string;
+	purge_after: string;
+};
+export type PendingPurgesResponse = {
+	items: PendingPurgeItem[];
+};
+export type DbMaintenanceAction =
+	| "checkpoint"
+	| "vacuum"
+	| "integrity_check"
+	| "analyze";
+export type DbMaintenanceResponse = {
+	action: DbMaintenanceAction;
+	ok: boolean;
+	duration_ms: number;
+	finished_at: string;
+	wal_checkpoint?: {
+		busy: number;
+		log_frames: number;
+		checkpointed_frames: number;
+	};
+	integrity_check?: string[];
+	bytes_before?: number;
+	bytes_after?: number;
+};
+export type DbMaintenanceStatusResponse = {
+	runs: Array<{
+		action: DbMaintenanceAction;
+		source: "manual" | "schedule";
+		ok: boolean;
+		duration_ms: number;
+		finished_at: string;
+	}>;
+};
 export type AdminJobsOverviewResponse = {
 	queued: number;
 	running: number;
 	failed_24h: number;
+	timed_out_24h: number;
 	succeeded_24h: number;
 	enabled_scheduled_slots: number;
 	total_scheduled_slots: number;
+	users_due_for_sync_next_hour: number;
+	last_prune_at: string | null;
 };
 export type AdminDashboardBusinessCounts = {
 	ok: number;
@@ -586,14 +725,17 @@ export type AdminDashboardResponse = {
 		point_count: number;
 	};
 };
+export type TaskPriority = "high" | "normal" | "low";
 export type AdminRealtimeTaskItem = {
 	id: string;
 	task_type: string;
 	status: string;
 	source: string;
+	priority?: TaskPriority;
 	skipped?: boolean;
 	requested_by: LocalUserId | null;
 	parent_task_id: string | null;
+	request_id?: string | null;
 	cancel_requested: boolean;
 	error_message: string | null;
 	created_at: string;
@@ -876,6 +1018,9 @@ export type AdminLlmSchedulerStatusResponse = {
 	available_slots: number;
 	waiting_calls: number;
 	in_flight_calls: number;
+	paused: boolean;
+	request_interval_ms: number;
+	request_interval_source: "env" | "override";
 	calls_24h: number;
 	failed_24h: number;
 	avg_wait_ms_24h: number | null;
@@ -888,6 +1033,10 @@ export type AdminLlmRuntimeConfigUpdateRequest = {
 	ai_model_context_limit?: number | null;
 	llm_models?: string[];
 };
+export type AdminLlmSchedulerControlRequest = {
+	paused?: boolean;
+	request_interval_ms?: number | null;
+};
 export type AdminLlmCallItem = {
 	id: string;
 	status: string;
@@ -896,6 +1045,7 @@ export type AdminLlmCallItem = {
 	requested_by: LocalUserId | null;
 	parent_task_id: string | null;
 	parent_task_type: string | null;
+	request_id?: string | null;
 	max_tokens: number;
 	attempt_count: number;
 	scheduler_wait_ms: number;
@@ -923,6 +1073,39 @@ export type AdminLlmCallsResponse = {
 	page_size: number;
 	total: number;
 };
+export type AdminAuditItem = {
+	id: string;
+	acting_user_id: LocalUserId;
+	acting_login: string | null;
+	target_user_id: LocalUserId | null;
+	target_login: string | null;
+	action: string;
+	before_json: string;
+	after_json: string;
+	created_at: string;
+};
+export type AdminAuditListResponse = {
+	items: AdminAuditItem[];
+	page: number;
+	page_size: number;
+	total: number;
+};
+export async function apiGetAdminAudit(params: {
+	target_user_id?: LocalUserId;
+	page?: number;
+	page_size?: number;
+}): Promise<AdminAuditListResponse> {
+	const search = new URLSearchParams();
+	if (params.target_user_id) {
+		search.set("target_user_id", params.target_user_id);
+	}
+	if (params.page) search.set("page", String(params.page));
+	if (params.page_size) search.set("page_size", String(params.page_size));
+	const query = search.toString();
+	return apiGet<AdminAuditListResponse>(
+		query ? `/api/admin/audit?${query}` : "/api/admin/audit",
+	);
+}
 export async function apiGetAdminUserProfile(
 	userId: LocalUserId,
 ): Promise<AdminUserProfileResponse> {
@@ -956,6 +1139,18 @@ export async function apiGetAdminDashboard(
 		`/api/admin/dashboard${params.size > 0 ? `?${params.toString()}` : ""}`,
 	);
 }
+export async function apiDeleteMe(
+	confirm: string,
+): Promise<AccountDeletionSummary> {
+	return apiDeleteJson<AccountDeletionSummary>("/api/me", { confirm });
+}
+export async function apiDeleteAdminUser(
+	userId: LocalUserId,
+): Promise<AccountDeletionSummary> {
+	return apiDeleteJson<AccountDeletionSummary>(
+		`/api/admin/users/${encodeURIComponent(userId)}`,
+	);
+}
 export async function apiGetMeLinuxDo(): Promise<MeLinuxDoResponse> {
 	return apiGet<MeLinuxDoResponse>("/api/me/linuxdo");
 }
@@ -1023,6 +1218,14 @@ export async function apiCheckReactionToken(
 		token,
 	});
 }
+export async function apiDiagnoseReactionToken(
+	repoFullName?: string,
+): Promise<ReactionTokenDiagnoseResponse> {
+	return apiPostJson<ReactionTokenDiagnoseResponse>(
+		"/api/reaction-token/diagnose",
+		{ repo_full_name: repoFullName ?? null },
+	);
+}
 export async function apiPutReactionToken(
 	token: string,
 ): Promise<ReactionTokenStatusResponse> {
@@ -1030,6 +1233,44 @@ export async function apiPutReactionToken(
 		token,
 	});
 }
+export async function apiRotateFeedToken(): Promise<FeedTokenResponse> {
+	return apiPost<FeedTokenResponse>("/api/feed-token");
+}
+export async function apiImportFollows(
+	request: FollowImportRequest,
+): Promise<FollowImportResponse> {
+	return apiPostJson<FollowImportResponse>("/api/follows/import", request);
+}
+export async function apiUnfollowRepo(
+	repoId: number,
+): Promise<FollowRemoveResponse> {
+	return apiDeleteJson<FollowRemoveResponse>(
+		`/api/follows/${encodeURIComponent(String(repoId))}`,
+	);
+}
+export async function apiGetSyncIssues(): Promise<SyncIssuesResponse> {
+	return apiGet<SyncIssuesResponse>("/api/sync/issues");
+}
+export async function apiDismissSyncIssue(
+	id: string,
+): Promise<SyncIssueDismissResponse> {
+	return apiPost<SyncIssueDismissResponse>(
+		`/api/sync/issues/${encodeURIComponent(id)}/dismiss`,
+	);
+}
+export async function apiGetPendingPurges(): Promise<PendingPurgesResponse> {
+	return apiGet<PendingPurgesResponse>("/api/sync/purges");
+}
+export async function apiGetAdminDbMaintenance(): Promise<DbMaintenanceStatusResponse> {
+	return apiGet<DbMaintenanceStatusResponse>("/api/admin/maintenance/db");
+}
+export async function apiRunAdminDbMaintenance(
+	action: DbMaintenanceAction,
+): Promise<DbMaintenanceResponse> {
+	return apiPostJson<DbMaintenanceResponse>("/api/admin/maintenance/db", {
+		action,
+	});
+}
 export async function apiGetAdminJobsOverview(): Promise<AdminJobsOverviewResponse> {
 	return apiGet<AdminJobsOverviewResponse>("/api/admin/jobs/overview");
 }
@@ -1129,6 +1370,110 @@ export async function apiPatchAdminLlmRuntimeConfig(
 		body,
 	);
 }
+export type NotificationDetailResponse = {
+	thread_id: string;
+	repo_full_name: string | null;
+	subject_title: string | null;
+	subject_type: string | null;
+	reason: string | null;
+	updated_at: string | null;
+	unread: number;
+	html_url: string | null;
+	subject_state: "open" | "closed" | "merged" | "gone" | null;
+	subject_body: string | null;
+	translation: {
+		lang: string;
+		title: string | null;
+		summary: string | null;
+	} | null;
+};
+export async function apiGetNotificationDetail(
+	threadId: string,
+): Promise<NotificationDetailResponse> {
+	return apiGet<NotificationDetailResponse>(
+		`/api/notifications/${encodeURIComponent(threadId)}`,
+	);
+}
+export type GitHubRateLimitItem = {
+	token_kind: "oauth" | "pat" | "graphql";
+	limit: number;
+	remaining: number;
+	reset_at: string;
+	updated_at: string;
+};
+export type GitHubRateLimitResponse = {
+	items: GitHubRateLimitItem[];
+	low_remaining_threshold: number;
+};
+export async function apiGetGitHubRateLimit(): Promise<GitHubRateLimitResponse> {
+	return apiGet<GitHubRateLimitResponse>("/api/github/rate-limit");
+}
+export type MarkFeedSeenResponse = { marked: number };
+export type RepoUnseenCountResponse = { repo_id: number; unseen_count: number };
+export async function apiMarkFeedSeen(
+	releaseIds: string[],
+): Promise<MarkFeedSeenResponse> {
+	return apiPostJson<MarkFeedSeenResponse>("/api/feed/seen", {
+		release_ids: releaseIds,
+	});
+}
+export type CountersResponse = {
+	unread_notifications: number;
+	new_releases_since: number;
+	last_feed_seen_at: string | null;
+};
+export async function apiGetCounters(): Promise<CountersResponse> {
+	return apiGet<CountersResponse>("/api/counters");
+}
+export async function apiMarkFeedSeenAt(): Promise<CountersResponse> {
+	return apiPost<CountersResponse>("/api/feed/seen-at");
+}
+export async function apiGetRepoUnseenCount(
+	repoId: number,
+): Promise<RepoUnseenCountResponse> {
+	return apiGet<RepoUnseenCountResponse>(
+		`/api/repos/${repoId}/releases/unseen_count`,
+	);
+}
+export async function apiGetBookmarks(params?: {
+	cursor?: string | null;
+	limit?: number;
+}): Promise<BookmarksResponse> {
+	const query = new URLSearchParams();
+	if (params?.cursor) query.set("cursor", params.cursor);
+	if (params?.limit) query.set("limit", String(params.limit));
+	const suffix = query.size > 0 ? `?${query.toString()}` : "";
+	return apiGet<BookmarksResponse>(`/api/bookmarks${suffix}`);
+}
+export async function apiCreateBookmark(body: {
+	kind: "release";
+	entity_id: string;
+	note?: string | null;
+}): Promise<BookmarkItem> {
+	return apiPostJson<BookmarkItem>("/api/bookmarks", body);
+}
+export async function apiDeleteBookmark(
+	bookmarkId: string,
+): Promise<BookmarkDeleteResponse> {
+	return apiDeleteJson<BookmarkDeleteResponse>(
+		`/api/bookmarks/${encodeURIComponent(bookmarkId)}`,
+	);
+}
+export async function apiGetBriefSources(
+	date: string,
+): Promise<BriefSourcesResponse> {
+	return apiGet<BriefSourcesResponse>(
+		`/api/briefs/${encodeURIComponent(date)}/sources`,
+	);
+}
+export async function apiPatchAdminLlmScheduler(
+	body: AdminLlmSchedulerControlRequest,
+): Promise<AdminLlmSchedulerStatusResponse> {
+	return apiPatchJson<AdminLlmSchedulerStatusResponse>(
+		"/api/admin/llm/scheduler",
+		body,
+	);
+}
 export async function apiGetAdminLlmCalls(
 	params: URLSearchParams,
 ): Promise<AdminLlmCallsResponse> {
@@ -1143,6 +1488,37 @@ export async function apiGetAdminLlmCallDetail(
 		`/api/admin/jobs/llm/calls/${encodeURIComponent(callId)}`,
 	);
 }
+export type AdminLlmUsageStats = {
+	call_count: number;
+	failed_count: number;
+	failure_rate: number;
+	input_tokens: number;
+	output_tokens: number;
+	total_tokens: number;
+	avg_duration_ms: number | null;
+};
+export type AdminLlmUsageSummaryBucket = AdminLlmUsageStats & {
+	bucket_start: string;
+	sources: Array<AdminLlmUsageStats & { source: string }>;
+};
+export type AdminLlmUsageSummaryResponse = {
+	bucket: "day" | "hour";
+	started_from: string;
+	started_to: string | null;
+	buckets: AdminLlmUsageSummaryBucket[];
+};
+export async function apiGetAdminLlmUsageSummary(
+	params: URLSearchParams,
+): Promise<AdminLlmUsageSummaryResponse> {
+	return apiGet<AdminLlmUsageSummaryResponse>(
+		`/api/admin/llm/usage/summary?${params.toString()}`,
+	);
+}
+export function adminLlmCallsExportUrl(params: URLSearchParams): string {
+	const query = new URLSearchParams(params);
+	query.set("format", "csv");
+	return `/api/admin/llm/calls/export?${query.toString()}`;
+}
 export type ReleaseDetailTranslated = {
 	lang: string;
 	status: "ready" | "missing" | "disabled" | "error";
@@ -1152,6 +1528,8 @@ export type ReleaseDetailTranslated = {
 	error_summary?: string | null;
 	error_detail?: string | null;
 	auto_translate?: boolean;
+	mode?: "full" | "summary";
+	detected_lang?: "zh" | "ja" | "ko" | "en";
 };
 export type ReleaseDetailSmart = {
 	lang: string;
@@ -1166,6 +1544,10 @@ export type ReleaseDetailSmart = {
 export type ReleaseDetailResponse = {
 	release_id: string;
 	repo_full_name: string | null;
+	repo_owner_login?: string | null;
+	repo_owner_avatar_url?: string | null;
+	repo_description?: string | null;
+	repo_html_url?: string | null;
 	repo_visual: RepoVisual | null;
 	tag_name: string;
 	previous_tag_name: string | null;
@@ -1177,6 +1559,15 @@ export type ReleaseDetailResponse = {
 	is_draft: number;
 	translated: ReleaseDetailTranslated | null;
 	smart: ReleaseDetailSmart | null;
+	assets?: ReleaseAsset[];
+	assets_warning?: { code: string; message: string };
+};
+export type ReleaseAsset = {
+	name: string;
+	size: number;
+	download_count: number;
+	browser_download_url: string;
+	content_type: string | null;
 };
 export async function apiGetReleaseDetail(
 	releaseId: string,
@@ -1570,9 +1961,8 @@ export async function apiSubmitTranslationRequest(
 export async function apiOpenTranslationRequestStream(
 	body: TranslationStreamSubmitRequest,
 ): Promise<Response> {
-	const res = await fetch("/api/translate/requests", {
+	const res = await fetchMutation("/api/translate/requests", {
 		method: "POST",
-		credentials: "include",
 		headers: { "content-type": "application/json" },
 		body: JSON.stringify(body),
 	});
@@ -3,6 +3,7 @@ import {
 	FolderGit2,
 	GitFork,
 	Megaphone,
+	Paperclip,
 	RefreshCcw,
 	Star,
 	Tag,
@@ -252,7 +253,16 @@ function TranslatedLane(props: {
 	const { item, onTranslateNow, isAutoRetrying } = props;
 
 	if (item.translated?.status === "ready" && item.translated.summary?.trim()) {
-		return <Markdown content={item.translated.summary} />;
+		return (
+			<>
+				{item.translated.detected_lang === "zh" ? (
+					<p className="mb-2 text-xs text-muted-foreground">
+						原文已是中文，未调用翻译。
+					</p>
+				) : null}
+				<Markdown content={item.translated.summary} />
+			</>
+		);
 	}
 
 	if (item.translated?.status === "disabled") {
@@ -1316,6 +1326,16 @@ export function ReleaseFeedCard(props: {
 		item.subject_type ? item.subject_type : null,
 	].filter(Boolean);
 	const subtitle = subtitleBits.join(" · ");
+	const assetsBadge = item.assets_count ? (
+		<span
+			className="ml-2 inline-flex items-center gap-0.5 align-middle"
+			title={`${item.assets_count} 个附件`}
+			data-feed-assets-badge="true"
+		>
+			<Paperclip className="size-3" aria-hidden="true" />
+			{item.assets_count}
+		</span>
+	) : null;
 	const repoFocusTarget = buildRepoFocusTarget(item.repo_full_name, sourceTab);
 	const reactions = showReactions ? item.reactions : null;
 	const isVersionOnly = item.smart?.status === "insufficient";
@@ -1391,6 +1411,7 @@ export function ReleaseFeedCard(props: {
 								{formatIsoShortLocal(item.ts)}
 								{isFresh ? <FreshContentCue className="ml-2" /> : null}
 								{subtitle ? ` · ${subtitle}` : ""}
+								{assetsBadge}
 							</p>
 						</div>
 
@@ -1574,6 +1595,7 @@ export function ReleaseFeedCard(props: {
 												{formatIsoShortLocal(item.ts)}
 												{isFresh ? <FreshContentCue className="ml-2" /> : null}
 												{subtitle ? ` · ${subtitle}` : ""}
+												{assetsBadge}
 											</span>
 										</RepoIdentity>
 									</InternalLink>
@@ -1589,6 +1611,7 @@ export function ReleaseFeedCard(props: {
 											{formatIsoShortLocal(item.ts)}
 											{isFresh ? <FreshContentCue className="ml-2" /> : null}
 											{subtitle ? ` · ${subtitle}` : ""}
+											{assetsBadge}
 										</span>
 									</RepoIdentity>
 								)}
@@ -17,6 +17,7 @@ export type TranslatedItem = {
 	error_summary?: string | null;
 	error_detail?: string | null;
 	auto_translate?: boolean;
+	detected_lang?: "zh" | "ja" | "ko" | "en";
 };
 
 export type SmartItem = {
@@ -84,6 +85,10 @@ type FeedItemBase = {
 	ts: string;
 	id: string;
 	repo_full_name: string | null;
+	repo_owner_login?: string | null;
+	repo_owner_avatar_url?: string | null;
+	repo_description?: string | null;
+	repo_html_url?: string | null;
 	repo_visual: RepoVisual | null;
 	title: string | null;
 	body: string | null;
@@ -93,14 +98,18 @@ type FeedItemBase = {
 	subject_type: string | null;
 	html_url: string | null;
 	unread: number | null;
+	bookmarked?: boolean;
+	bookmark_id?: string;
 };
 
 export type ReleaseFeedItem = FeedItemBase & {
 	kind: "release";
 	actor?: null | undefined;
+	source?: "starred" | "followed";
 	translated: TranslatedItem | null;
 	smart: SmartItem | null;
 	reactions: ReleaseReactions | null;
+	assets_count?: number;
 };
 
 export type SocialFeedItem = FeedItemBase & {
@@ -137,6 +146,54 @@ export type FeedResponse = {
 	next_cursor: string | null;
 };
 
+export type RepoReleasesHeader = {
+	repo_id: number;
+	full_name: string;
+	description: string | null;
+	html_url: string;
+	stargazed_at: string | null;
+	latest_tag: string | null;
+};
+
+export type RepoReleasesResponse = {
+	repo: RepoReleasesHeader;
+	items: ReleaseFeedItem[];
+	next_cursor: string | null;
+};
+
+export type BookmarkItem = {
+	id: string;
+	note: string | null;
+	created_at: string;
+	release_available: boolean;
+	item: ReleaseFeedItem;
+};
+
+export type BookmarksResponse = {
+	items: BookmarkItem[];
+	next_cursor: string | null;
+};
+
+export type BookmarkDeleteResponse = {
+	id: string;
+	kind: "release";
+	entity_id: string;
+	bookmarked: false;
+};
+
+export type BriefSourceItem = {
+	section: string;
+	included: boolean;
+	item: ReleaseFeedItem;
+};
+
+export type BriefSourcesResponse = {
+	brief_id: string;
+	date: string;
+	sources_available: boolean;
+	items: BriefSourceItem[];
+};
+
 export type TranslateResponse = {
 	lang: string;
 	status: "ready" | "disabled";
@@ -158,7 +215,7 @@ export type TranslateBatchResponse = {
 };
 
 export type TranslateBatchStreamEvent = {
-	event: "item" | "done" | "error";
+	event: "item" | "partial" | "done" | "error";
 	item?: TranslateBatchItem | null;
 	error?: string | null;
 };
@@ -168,9 +225,21 @@ export type ToggleReleaseReactionResponse = {
 	reactions: ReleaseReactions;
 };
 
+export type FeedWarning = {
+	code:
+		| "rate_limited"
+		| "pat_invalid"
+		| "pat_forbidden"
+		| "reauth_required"
+		| "forbidden"
+		| "github_unavailable";
+	message: string;
+};
+
 export type FeedReactionRefreshResponse = {
 	items: Array<{
 		release_id: string;
 		reactions: ReleaseReactions;
 	}>;
+	warnings?: FeedWarning[];
 };
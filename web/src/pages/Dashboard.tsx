@@ -92,6 +92,7 @@ import { BriefListCard } from "@/sidebar/BriefListCard";
 import {
 	InboxQuickList,
 	type NotificationItem,
+	type NotificationListResponse,
 } from "@/sidebar/InboxQuickList";
 import { type BriefItem, ReleaseDailyCard } from "@/sidebar/ReleaseDailyCard";
 import { ReleaseDetailCard } from "@/sidebar/ReleaseDetailCard";
@@ -934,7 +935,8 @@ export function Dashboard(props: {
 			notificationsRequestInFlightRef.current = true;
 			setNotificationsError(null);
 			try {
-				const items = await apiGet<NotificationItem[]>("/api/notifications");
+				const { items } =
+					await apiGet<NotificationListResponse>("/api/notifications");
 				setNotifications(sortNotifications(items));
 			} catch (error) {
 				const message = describeUnknownError(
@@ -1550,6 +1552,14 @@ export function Dashboard(props: {
 						}
 						continue;
 					}
+					if (
+						result.value.warnings?.some(
+							(warning) => warning.code === "pat_invalid",
+						)
+					) {
+						setReactionTokenConfigured(false);
+						void loadReactionToken();
+					}
 					for (const item of result.value.items) {
 						const key = itemKey({ kind: "release", id: item.release_id });
 						if (
@@ -2049,6 +2059,7 @@ export function Dashboard(props: {
 				{
 					release_id: item.id,
 					content,
+					currently_reacted: server.viewer[content],
 				},
 			)
 				.then((res) => {
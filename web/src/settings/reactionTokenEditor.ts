@@ -53,6 +53,7 @@ export function normalizePatStatus(
 }
 
 export function isReactionTokenUsable(status: ReactionTokenStatusResponse) {
+	if (status.oauth_ready) return true;
 	return status.configured && status.check.state === "valid";
 }
 
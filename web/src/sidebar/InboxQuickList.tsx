@@ -23,6 +23,18 @@ export type NotificationItem = {
 	html_url: string | null;
 };
 
+export type NotificationCounts = {
+	total: number;
+	unread: number;
+	by_subject_type: Record<string, number>;
+};
+
+export type NotificationListResponse = {
+	group: "none";
+	items: NotificationItem[];
+	counts: NotificationCounts;
+};
+
 export function InboxQuickList(props: {
 	notifications: NotificationItem[];
 	freshKeys?: Set<string>;
@@ -1,7 +1,10 @@
 import { Sparkles } from "lucide-react";
 
 import { ErrorStatePanel } from "@/components/feedback/ErrorStatePanel";
-import type { DashboardReleaseTarget } from "@/dashboard/routeState";
+import {
+	buildDashboardReleaseTarget,
+	type DashboardReleaseTarget,
+} from "@/dashboard/routeState";
 import { Markdown } from "@/components/Markdown";
 import { Button } from "@/components/ui/button";
 import { formatIsoRangeInTimeZone } from "@/lib/datetime";
@@ -14,6 +17,13 @@ import {
 	CardTitle,
 } from "@/components/ui/card";
 
+export type BriefSource = {
+	section: string;
+	release_id: string;
+	title: string;
+	html_url: string;
+};
+
 export type BriefItem = {
 	id: string;
 	date: string;
@@ -24,6 +34,7 @@ export type BriefItem = {
 	release_count: number;
 	release_ids: string[];
 	content_markdown: string;
+	sources?: BriefSource[];
 	created_at: string;
 };
 
@@ -142,6 +153,29 @@ export function ReleaseDailyCard(props: {
 							content={selected.content_markdown}
 							onInternalReleaseClick={onOpenRelease}
 						/>
+						{selected.sources?.length && onOpenRelease ? (
+							<div className="mt-3 flex flex-wrap gap-1.5">
+								{selected.sources.map((source) => (
+									<Button
+										key={source.release_id}
+										variant="outline"
+										size="sm"
+										className="h-6 px-2 font-mono text-[11px]"
+										title={source.html_url}
+										onClick={() =>
+											onOpenRelease(
+												buildDashboardReleaseTarget({
+													releaseId: source.release_id,
+													fromTab: "briefs",
+												}),
+											)
+										}
+									>
+										{source.section} · {source.title}
+									</Button>
+								))}
+							</div>
+						) : null}
 					</div>
 				) : (
 					<p className="text-muted-foreground text-sm">
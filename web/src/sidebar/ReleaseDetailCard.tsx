@@ -1,7 +1,9 @@
 import {
 	ArrowUpRight,
+	Download,
 	FileText,
 	Languages,
+	Paperclip,
 	RefreshCcw,
 	Sparkles,
 	X,
@@ -34,6 +36,7 @@ import {
 	DialogTitle,
 } from "@/components/ui/dialog";
 import type { DashboardReleaseTarget } from "@/dashboard/routeState";
+import { formatBytes } from "@/lib/bytes";
 import { formatIsoShortLocal } from "@/lib/datetime";
 import {
 	describeUnknownError,
@@ -833,6 +836,46 @@ export function ReleaseDetailCard(props: {
 										该 release 无正文。
 									</p>
 								)}
+								{activeDetail.assets?.length ? (
+									<div className="space-y-1.5" data-release-assets="true">
+										<p className="text-muted-foreground flex items-center gap-1 text-xs font-medium">
+											<Paperclip className="size-3.5" />
+											附件（{activeDetail.assets.length}）
+										</p>
+										<ul className="divide-y rounded-lg border text-sm">
+											{activeDetail.assets.map((asset) => (
+												<li
+													key={asset.browser_download_url}
+													className="flex items-center gap-2 px-3 py-2"
+												>
+													<a
+														href={asset.browser_download_url}
+														target="_blank"
+														rel="noreferrer"
+														className="min-w-0 flex-1 truncate font-mono text-xs hover:underline"
+														title={asset.name}
+													>
+														{asset.name}
+													</a>
+													<span className="text-muted-foreground shrink-0 font-mono text-[11px]">
+														{formatBytes(asset.size)}
+													</span>
+													<span
+														className="text-muted-foreground inline-flex shrink-0 items-center gap-0.5 font-mono text-[11px]"
+														title="下载次数"
+													>
+														<Download className="size-3" />
+														{asset.download_count}
+													</span>
+												</li>
+											))}
+										</ul>
+									</div>
+								) : activeDetail.assets_warning ? (
+									<p className="text-muted-foreground text-xs">
+										附件暂不可用：{activeDetail.assets_warning.message}
+									</p>
+								) : null}
 							</div>
 						) : (
 							<p className="text-muted-foreground text-sm">
@@ -1704,6 +1704,9 @@ function AdminJobsPreview({
 			],
 			waiting_calls: 1,
 			in_flight_calls: 1,
+			paused: false,
+			request_interval_ms: 0,
+			request_interval_source: "env" as "env" | "override",
 			available_slots: 1,
 			calls_24h: llmCalls.length,
 			failed_24h: llmCalls.filter((item) => item.status === "failed").length,
@@ -1914,8 +1917,11 @@ function AdminJobsPreview({
 						running,
 						succeeded_24h: succeeded24h,
 						failed_24h: failed24h,
+						timed_out_24h: 0,
 						enabled_scheduled_slots: 24,
 						total_scheduled_slots: 24,
+						users_due_for_sync_next_hour: 3,
+						last_prune_at: "2026-02-26T08:00:04Z",
 					}),
 					{
 						status: 200,
@@ -90,6 +90,9 @@ function AdminPanelPreview({
 					daily_brief_local_time: "08:00",
 					daily_brief_time_zone: "Asia/Shanghai",
 					include_own_releases: user.include_own_releases,
+					sync_frequency_minutes: 0,
+					muted_notification_reasons: [],
+					skip_muted_notifications: false,
 					last_active_at: user.last_active_at,
 				},
 			]),
@@ -218,6 +221,9 @@ function AdminPanelPreview({
 							daily_brief_local_time: "08:00",
 							daily_brief_time_zone: "Asia/Shanghai",
 							include_own_releases: target.include_own_releases,
+							sync_frequency_minutes: 0,
+							muted_notification_reasons: [],
+							skip_muted_notifications: false,
 							last_active_at: target.last_active_at,
 						}),
 					}),
@@ -257,6 +263,9 @@ function AdminPanelPreview({
 					daily_brief_time_zone:
 						payload.daily_brief_time_zone ?? "Asia/Shanghai",
 					include_own_releases: target.include_own_releases,
+					sync_frequency_minutes: 0,
+					muted_notification_reasons: [],
+					skip_muted_notifications: false,
 					last_active_at: target.last_active_at,
 				};
 				profiles.set(target.id, nextProfile);
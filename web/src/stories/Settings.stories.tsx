@@ -80,6 +80,9 @@ function buildMockProfile(): MeProfileResponse {
 		daily_brief_time_zone: "Asia/Shanghai",
 		last_active_at: "2026-04-18T08:00:00+08:00",
 		include_own_releases: false,
+		sync_frequency_minutes: 0,
+		muted_notification_reasons: [],
+		skip_muted_notifications: false,
 	};
 }
 
@@ -92,6 +95,7 @@ const baseGitHubConnections: GitHubConnectionResponse[] = [
 		avatar_url: svgAvatarDataUrl("GH", "#111827"),
 		email: "storybook-user@example.com",
 		scopes: "read:user, user:email, notifications, public_repo",
+		is_primary: true,
 		linked_at: "2026-04-16T10:00:00+08:00",
 		updated_at: "2026-04-18T09:00:00+08:00",
 	},
@@ -103,6 +107,7 @@ const baseGitHubConnections: GitHubConnectionResponse[] = [
 		avatar_url: svgAvatarDataUrl("OP", "#0f766e"),
 		email: "ops@example.com",
 		scopes: "read:user, user:email, notifications, public_repo",
+		is_primary: false,
 		linked_at: "2026-04-17T10:00:00+08:00",
 		updated_at: "2026-04-18T09:05:00+08:00",
 	},